@@ -2,7 +2,7 @@ use clap::{Arg, ArgAction, ArgMatches, Command};
 use environment::Environment;
 use slashing_protection::{
     interchange::Interchange, InterchangeError, InterchangeImportOutcome, SlashingDatabase,
-    SLASHING_PROTECTION_FILENAME,
+    SLASHING_PROTECTION_FILENAME, SLASHING_PROTECTION_HISTORY_EPOCHS,
 };
 use std::fs::File;
 use std::path::PathBuf;
@@ -12,15 +12,17 @@ use types::{Epoch, EthSpec, PublicKeyBytes, Slot};
 pub const CMD: &str = "slashing-protection";
 pub const IMPORT_CMD: &str = "import";
 pub const EXPORT_CMD: &str = "export";
+pub const PRUNE_CMD: &str = "prune";
 
 pub const IMPORT_FILE_ARG: &str = "IMPORT-FILE";
 pub const EXPORT_FILE_ARG: &str = "EXPORT-FILE";
 
 pub const PUBKEYS_FLAG: &str = "pubkeys";
+pub const BEFORE_EPOCH_FLAG: &str = "before-epoch";
 
 pub fn cli_app() -> Command {
     Command::new(CMD)
-        .about("Import or export slashing protection data to or from another client")
+        .about("Import, export or prune slashing protection data")
         .display_order(0)
         .subcommand(
             Command::new(IMPORT_CMD)
@@ -55,6 +57,26 @@ pub fn cli_app() -> Command {
                         .display_order(0)
                 )
         )
+        .subcommand(
+            Command::new(PRUNE_CMD)
+                .about(
+                    "Remove historical records from the database and compact it. The most \
+                     recent block and attestation of each validator are always retained.",
+                )
+                .arg(
+                    Arg::new(BEFORE_EPOCH_FLAG)
+                        .long(BEFORE_EPOCH_FLAG)
+                        .action(ArgAction::Set)
+                        .value_name("EPOCH")
+                        .required(true)
+                        .help(
+                            "Remove attestations with a target epoch, and blocks with a slot, \
+                             prior to this epoch. Must be at least 512 epochs prior to the most \
+                             recent attestation in the database.",
+                        )
+                        .display_order(0)
+                )
+        )
 }
 
 pub fn cli_run<E: EthSpec>(
@@ -219,6 +241,59 @@ pub fn cli_run<E: EthSpec>(
 
             Ok(())
         }
+        Some((PRUNE_CMD, matches)) => {
+            let before_epoch: Epoch = clap_utils::parse_required(matches, BEFORE_EPOCH_FLAG)?;
+
+            if !slashing_protection_db_path.exists() {
+                return Err(format!(
+                    "No slashing protection database exists at: {}",
+                    slashing_protection_db_path.display()
+                ));
+            }
+
+            let slashing_protection_database = SlashingDatabase::open(&slashing_protection_db_path)
+                .map_err(|e| {
+                    format!(
+                        "Unable to open database at {}: {:?}",
+                        slashing_protection_db_path.display(),
+                        e
+                    )
+                })?;
+
+            // Records within the weak subjectivity period of the latest attestation may still be
+            // needed, so refuse to prune them.
+            let max_target = slashing_protection_database
+                .max_attestation_target()
+                .map_err(|e| format!("Unable to read database: {:?}", e))?
+                .unwrap_or_else(|| Epoch::new(0));
+            let max_before_epoch = max_target.saturating_sub(SLASHING_PROTECTION_HISTORY_EPOCHS);
+            if before_epoch > max_before_epoch {
+                return Err(format!(
+                    "--{} must be at most {} epochs prior to the latest attestation at epoch {}, \
+                     i.e., no greater than {}",
+                    BEFORE_EPOCH_FLAG,
+                    SLASHING_PROTECTION_HISTORY_EPOCHS,
+                    max_target,
+                    max_before_epoch
+                ));
+            }
+
+            let outcome = slashing_protection_database
+                .prune_all_validators(before_epoch, before_epoch.start_slot(E::slots_per_epoch()))
+                .map_err(|e| format!("Error during pruning: {:?}", e))?;
+            eprintln!(
+                "Pruned {} attestations and {} blocks",
+                outcome.attestations_pruned, outcome.blocks_pruned
+            );
+
+            eprint!("Compacting database");
+            slashing_protection_database
+                .compact()
+                .map_err(|e| format!("Error during compaction: {:?}", e))?;
+            eprintln!(" [done].");
+
+            Ok(())
+        }
         Some((command, _)) => Err(format!("No such subcommand `{}`", command)),
         _ => Err("No subcommand provided, see --help for options".to_string()),
     }
@@ -0,0 +1,86 @@
+pub use clap::{Arg, ArgAction, Args, Command, FromArgMatches, Parser};
+use clap_utils::get_color_style;
+use clap_utils::FLAG_HEADER;
+use http_api::api_tokens::ApiScope;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+#[derive(Parser, Clone, Deserialize, Serialize, Debug)]
+#[clap(
+    name = "api_token_manager",
+    about = "Manage the tokens which may access the beacon node HTTP API.",
+    styles = get_color_style(),
+    next_line_help = true,
+    term_width = 80,
+    disable_help_flag = true,
+    disable_help_subcommand = true,
+    display_order = 0,
+)]
+pub struct ApiTokenManager {
+    #[clap(
+        long,
+        global = true,
+        value_name = "PATH",
+        help = "The tokens file, as provided to the beacon node with --http-api-tokens-file.",
+        display_order = 0
+    )]
+    pub tokens_file: PathBuf,
+
+    #[clap(
+        long,
+        global = true,
+        help = "Prints help information",
+        action = clap::ArgAction::HelpLong,
+        display_order = 0,
+        help_heading = FLAG_HEADER
+    )]
+    help: Option<bool>,
+
+    #[clap(subcommand)]
+    pub subcommand: ApiTokenManagerSubcommand,
+}
+
+#[derive(Parser, Clone, Deserialize, Serialize, Debug)]
+#[clap(rename_all = "kebab-case")]
+pub enum ApiTokenManagerSubcommand {
+    Add(Add),
+    Remove(Remove),
+    List(List),
+}
+
+#[derive(Parser, Clone, Deserialize, Serialize, Debug)]
+#[clap(about = "Create a new token and print its secret.")]
+pub struct Add {
+    #[clap(
+        long,
+        value_name = "NAME",
+        help = "A unique name for the token, used to identify it in logs.",
+        display_order = 0
+    )]
+    pub name: String,
+
+    #[clap(
+        long,
+        value_name = "SCOPE",
+        help = "The routes the token may access: read-only, validator-duties or admin. \
+                Each scope includes the routes of the scopes before it.",
+        display_order = 0
+    )]
+    pub scope: ApiScope,
+}
+
+#[derive(Parser, Clone, Deserialize, Serialize, Debug)]
+#[clap(about = "Revoke an existing token.")]
+pub struct Remove {
+    #[clap(
+        long,
+        value_name = "NAME",
+        help = "The name of the token to revoke.",
+        display_order = 0
+    )]
+    pub name: String,
+}
+
+#[derive(Parser, Clone, Deserialize, Serialize, Debug)]
+#[clap(about = "List the names and scopes of all tokens.")]
+pub struct List {}
@@ -0,0 +1,44 @@
+//! Creates, revokes and lists the tokens which may access the beacon node HTTP API when it is
+//! started with `--http-api-tokens-file`.
+pub mod cli;
+
+use crate::cli::{ApiTokenManager, ApiTokenManagerSubcommand};
+use http_api::api_tokens::ApiTokens;
+
+/// Run the token manager, returning an error string if the operation did not succeed.
+pub fn run(config: &ApiTokenManager) -> Result<(), String> {
+    let path = &config.tokens_file;
+
+    match &config.subcommand {
+        ApiTokenManagerSubcommand::Add(add) => {
+            let mut tokens = ApiTokens::load_or_default(path)?;
+            let secret = tokens.add(add.name.clone(), add.scope)?;
+            tokens.save(path)?;
+            eprintln!(
+                "Added token {} with scope {}. The secret below will not be shown again.",
+                add.name, add.scope
+            );
+            println!("{}", secret);
+        }
+        ApiTokenManagerSubcommand::Remove(remove) => {
+            let mut tokens = ApiTokens::load(path)?;
+            if !tokens.remove(&remove.name) {
+                return Err(format!("No token named {}", remove.name));
+            }
+            tokens.save(path)?;
+            eprintln!("Removed token {}", remove.name);
+        }
+        ApiTokenManagerSubcommand::List(_) => {
+            let tokens = ApiTokens::load_or_default(path)?;
+            for token in &tokens.tokens {
+                println!("{}\t{}", token.name, token.scope);
+            }
+        }
+    }
+
+    if !matches!(config.subcommand, ApiTokenManagerSubcommand::List(_)) {
+        eprintln!("Restart the beacon node for the change to take effect.");
+    }
+
+    Ok(())
+}
@@ -6,6 +6,10 @@
 //! successfully indexed have their signatures verified in a batch. If that signature batch fails
 //! then all attestation signatures are verified independently.
 //!
+//! Unaggregated attestations which arrive close together frequently share the same
+//! `AttestationData`, and therefore the same signing root. The signatures of such attestations are
+//! grouped so that each distinct `AttestationData` in the batch requires only a single pairing.
+//!
 //! The outcome of each function is a `Vec<Result>` with a one-to-one mapping to the attestations
 //! supplied as input. Each result provides the exact success or failure result of the corresponding
 //! attestation, with no loss of fidelity when compared to individual verification.
@@ -14,12 +18,13 @@ use super::{
     VerifiedAggregatedAttestation, VerifiedUnaggregatedAttestation,
 };
 use crate::{metrics, BeaconChain, BeaconChainError, BeaconChainTypes};
-use bls::verify_signature_sets;
+use bls::{verify_grouped_signature_sets, verify_signature_sets};
 use state_processing::signature_sets::{
     indexed_attestation_signature_set_from_pubkeys, signed_aggregate_selection_proof_signature_set,
     signed_aggregate_signature_set,
 };
 use std::borrow::Cow;
+use std::collections::HashMap;
 use types::*;
 
 /// Verify aggregated attestations using batch BLS signature verification.
@@ -166,10 +171,12 @@ where
         let pubkey_cache = chain.validator_pubkey_cache.read();
 
         let mut signature_sets = Vec::with_capacity(num_partially_verified);
+        let mut group_sizes: HashMap<&AttestationData, usize> = HashMap::new();
 
         // Iterate, flattening to get only the `Ok` values.
         for partially_verified in partial_results.iter().flatten() {
             let indexed_attestation = &partially_verified.indexed_attestation;
+            *group_sizes.entry(indexed_attestation.data()).or_default() += 1;
             let fork = chain
                 .spec
                 .fork_at_epoch(indexed_attestation.data().target.epoch);
@@ -189,10 +196,18 @@ where
 
         metrics::stop_timer(signature_setup_timer);
 
+        for group_size in group_sizes.values() {
+            metrics::observe(
+                &metrics::ATTESTATION_PROCESSING_BATCH_UNAGG_GROUP_SIZE,
+                *group_size as f64,
+            );
+        }
+
         let _signature_verification_timer =
             metrics::start_timer(&metrics::ATTESTATION_PROCESSING_BATCH_UNAGG_SIGNATURE_TIMES);
 
-        if verify_signature_sets(signature_sets.iter()) {
+        // Signatures over the same `AttestationData` are verified with a single pairing.
+        if verify_grouped_signature_sets(signature_sets.iter()) {
             // Since all the signatures verified in a batch, there's no reason for them to be
             // checked again later.
             check_signatures = CheckAttestationSignature::No
@@ -9,11 +9,15 @@
 //! `per_epoch_processing` to transform the state from epoch `n - 1` to epoch `n` so that rewards
 //! and penalties can be computed and the `state.current_justified_checkpoint` can be updated.
 
-use crate::{BeaconChain, BeaconChainError, BeaconChainTypes};
-use parking_lot::RwLock;
+use crate::persisted_caches::PersistedAttesterCacheEntry;
+use crate::{metrics, BeaconChain, BeaconChainError, BeaconChainTypes};
+use arc_swap::ArcSwap;
+use parking_lot::{Mutex, MutexGuard};
+use ssz_derive::{Decode, Encode};
 use state_processing::state_advance::{partial_state_advance, Error as StateAdvanceError};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::ops::Range;
+use std::sync::Arc;
 use types::{
     attestation::Error as AttestationError,
     beacon_state::{
@@ -26,12 +30,13 @@ use types::{
 type JustifiedCheckpoint = Checkpoint;
 type CommitteeLength = usize;
 type CommitteeIndex = u64;
-type CacheHashMap = HashMap<AttesterCacheKey, AttesterCacheValue>;
+type EpochEntries = HashMap<Hash256, AttesterCacheValue>;
+type ShardMap = BTreeMap<Epoch, Arc<EpochShard>>;
 
 /// The maximum number of `AttesterCacheValues` to be kept in memory.
 ///
 /// Each `AttesterCacheValues` is very small (~16 bytes) and the cache will generally be kept small
-/// by pruning on finality.
+/// by pruning on finality. If the limit is exceeded, the entries of the lowest epochs are removed.
 ///
 /// The value provided here is much larger than will be used during ideal network conditions,
 /// however we make it large since the values are so small.
@@ -77,6 +82,7 @@ impl From<BeaconChainError> for Error {
 
 /// Stores the minimal amount of data required to compute the committee length for any committee at any
 /// slot in a given `epoch`.
+#[derive(Debug, PartialEq, Clone, Encode, Decode)]
 pub struct CommitteeLengths {
     /// The `epoch` to which the lengths pertain.
     epoch: Epoch,
@@ -157,6 +163,7 @@ impl CommitteeLengths {
 /// - The committee lengths for all indices and slots.
 ///
 /// These values are used during attestation production.
+#[derive(Debug, PartialEq, Clone, Encode, Decode)]
 pub struct AttesterCacheValue {
     current_justified_checkpoint: Checkpoint,
     committee_lengths: CommitteeLengths,
@@ -198,7 +205,7 @@ impl AttesterCacheValue {
 ///
 /// It is also safe, but not maximally efficient, to key the attester shuffling with the same
 /// strategy. For better shuffling keying strategies, see the `ShufflingCache`.
-#[derive(Eq, PartialEq, Hash, Clone, Copy)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy, Encode, Decode)]
 pub struct AttesterCacheKey {
     /// The epoch from which the justified checkpoint should be observed.
     ///
@@ -245,13 +252,33 @@ impl AttesterCacheKey {
     }
 }
 
+/// The entries of the `AttesterCache` for a single epoch.
+#[derive(Default)]
+struct EpochShard {
+    /// The entries for the epoch, keyed by `AttesterCacheKey::decision_root`.
+    ///
+    /// The map is replaced wholesale on each write so that readers never wait for a writer.
+    entries: ArcSwap<EpochEntries>,
+    /// Serializes writes to `self.entries` and any state reads performed to prime them.
+    ///
+    /// Holds `true` once the shard has been pruned from the cache, after which it must not be
+    /// written to since the write would be lost.
+    write_lock: Mutex<bool>,
+}
+
 /// Provides a cache for the justified checkpoint and committee length when producing an
 /// attestation.
 ///
+/// The cache is sharded by epoch. Reads are lock-free, whilst writes only contend with other
+/// writes to the same epoch.
+///
 /// See the module-level documentation for more information.
 #[derive(Default)]
 pub struct AttesterCache {
-    cache: RwLock<CacheHashMap>,
+    /// The shard for each epoch.
+    shards: ArcSwap<ShardMap>,
+    /// Serializes the addition and removal of shards.
+    shards_lock: Mutex<()>,
 }
 
 impl AttesterCache {
@@ -264,10 +291,16 @@ impl AttesterCache {
         committee_index: CommitteeIndex,
         spec: &ChainSpec,
     ) -> Result<Option<(JustifiedCheckpoint, CommitteeLength)>, Error> {
-        self.cache
-            .read()
-            .get(key)
-            .map(|cache_item| cache_item.get::<E>(slot, committee_index, spec))
+        self.shards
+            .load()
+            .get(&key.epoch)
+            .and_then(|shard| {
+                shard
+                    .entries
+                    .load()
+                    .get(&key.decision_root)
+                    .map(|cache_item| cache_item.get::<E>(slot, committee_index, spec))
+            })
             .transpose()
     }
 
@@ -279,11 +312,14 @@ impl AttesterCache {
         spec: &ChainSpec,
     ) -> Result<(), Error> {
         let key = AttesterCacheKey::new(state.current_epoch(), state, latest_block_root)?;
-        let mut cache = self.cache.write();
-        if !cache.contains_key(&key) {
-            let cache_item = AttesterCacheValue::new(state, spec)?;
-            Self::insert_respecting_max_len(&mut cache, key, cache_item);
-        }
+        self.with_shard_locked(key.epoch, |shard| -> Result<_, Error> {
+            if !shard.entries.load().contains_key(&key.decision_root) {
+                let cache_item = AttesterCacheValue::new(state, spec)?;
+                Self::insert_locked(shard, key, cache_item);
+            }
+            Ok(())
+        })?;
+        self.prune_to_max_len();
         Ok(())
     }
 
@@ -293,9 +329,8 @@ impl AttesterCache {
     ///
     /// ## Notes
     ///
-    /// This function takes a write-lock on the internal cache. Prefer attempting a `Self::get` call
-    /// before running this function as `Self::get` only takes a read-lock and is therefore less
-    /// likely to create contention.
+    /// This function takes the write-lock for the epoch of `key`. Prefer attempting a `Self::get`
+    /// call before running this function as `Self::get` is lock-free.
     pub fn load_and_cache_state<T: BeaconChainTypes>(
         &self,
         state_root: Hash256,
@@ -308,79 +343,295 @@ impl AttesterCache {
         let slots_per_epoch = T::EthSpec::slots_per_epoch();
         let epoch = slot.epoch(slots_per_epoch);
 
-        // Take a write-lock on the cache before starting the state read.
+        // Take the write-lock for the epoch before starting the state read.
         //
-        // Whilst holding the write-lock during the state read will create contention, it prevents
-        // the scenario where multiple requests from separate threads cause duplicate state reads.
-        let mut cache = self.cache.write();
-
-        // Try the cache to see if someone has already primed it between the time the function was
-        // called and when the cache write-lock was obtained. This avoids performing duplicate state
-        // reads.
-        if let Some(value) = cache
-            .get(&key)
-            .map(|cache_item| cache_item.get::<T::EthSpec>(slot, committee_index, spec))
-            .transpose()?
-        {
-            return Ok(value);
-        }
+        // Whilst holding the write-lock during the state read will delay other writers to the same
+        // epoch, it prevents the scenario where multiple requests from separate threads cause
+        // duplicate state reads. Readers are not blocked.
+        let value = self.with_shard_locked(key.epoch, |shard| -> Result<_, Error> {
+            // Try the cache to see if someone has already primed it between the time the function
+            // was called and when the write-lock was obtained. This avoids performing duplicate
+            // state reads.
+            if let Some(value) = shard
+                .entries
+                .load()
+                .get(&key.decision_root)
+                .map(|cache_item| cache_item.get::<T::EthSpec>(slot, committee_index, spec))
+                .transpose()?
+            {
+                metrics::inc_counter(&metrics::ATTESTER_CACHE_DUPLICATE_STATE_READS_AVOIDED);
+                return Ok(value);
+            }
 
-        let mut state: BeaconState<T::EthSpec> = chain
-            .get_state(&state_root, None)?
-            .ok_or(Error::MissingBeaconState(state_root))?;
+            let mut state: BeaconState<T::EthSpec> = chain
+                .get_state(&state_root, None)?
+                .ok_or(Error::MissingBeaconState(state_root))?;
+
+            if state.slot() > slot {
+                // This indicates an internal inconsistency.
+                return Err(Error::CannotAttestToFutureState {
+                    state_slot: state.slot(),
+                    request_slot: slot,
+                });
+            } else if state.current_epoch() < epoch {
+                // Only perform a "partial" state advance since we do not require the state roots
+                // to be accurate.
+                partial_state_advance(
+                    &mut state,
+                    Some(state_root),
+                    epoch.start_slot(slots_per_epoch),
+                    spec,
+                )
+                .map_err(Error::FailedToTransitionState)?;
+                state.build_committee_cache(RelativeEpoch::Current, spec)?;
+            }
 
-        if state.slot() > slot {
-            // This indicates an internal inconsistency.
-            return Err(Error::CannotAttestToFutureState {
-                state_slot: state.slot(),
-                request_slot: slot,
-            });
-        } else if state.current_epoch() < epoch {
-            // Only perform a "partial" state advance since we do not require the state roots to be
-            // accurate.
-            partial_state_advance(
-                &mut state,
-                Some(state_root),
-                epoch.start_slot(slots_per_epoch),
-                spec,
-            )
-            .map_err(Error::FailedToTransitionState)?;
-            state.build_committee_cache(RelativeEpoch::Current, spec)?;
+            let cache_item = AttesterCacheValue::new(&state, spec)?;
+            let value = cache_item.get::<T::EthSpec>(slot, committee_index, spec)?;
+            Self::insert_locked(shard, key, cache_item);
+            Ok(value)
+        })?;
+        self.prune_to_max_len();
+        Ok(value)
+    }
+
+    /// Return the shard for `epoch`, adding an empty shard if there is none.
+    fn get_or_insert_shard(&self, epoch: Epoch) -> Arc<EpochShard> {
+        if let Some(shard) = self.shards.load().get(&epoch) {
+            return shard.clone();
         }
 
-        let cache_item = AttesterCacheValue::new(&state, spec)?;
-        let value = cache_item.get::<T::EthSpec>(slot, committee_index, spec)?;
-        Self::insert_respecting_max_len(&mut cache, key, cache_item);
-        Ok(value)
+        let _shards_lock = lock_with_metrics(&self.shards_lock);
+        // Another thread may have added the shard whilst we were waiting for the lock.
+        let shards = self.shards.load();
+        if let Some(shard) = shards.get(&epoch) {
+            return shard.clone();
+        }
+
+        let shard = Arc::new(EpochShard::default());
+        let mut new_shards = ShardMap::clone(&shards);
+        new_shards.insert(epoch, shard.clone());
+        self.shards.store(Arc::new(new_shards));
+        shard
     }
 
-    /// Insert a value to `cache`, ensuring it does not exceed the maximum length.
+    /// Run `f` whilst holding the write-lock of the shard for `epoch`, adding the shard if there
+    /// is none.
     ///
-    /// If the cache is already full, the item with the lowest epoch will be removed.
-    fn insert_respecting_max_len(
-        cache: &mut CacheHashMap,
-        key: AttesterCacheKey,
-        value: AttesterCacheValue,
-    ) {
-        while cache.len() >= MAX_CACHE_LEN {
-            if let Some(oldest) = cache
-                .iter()
-                .map(|(key, _)| *key)
-                .min_by_key(|key| key.epoch)
-            {
-                cache.remove(&oldest);
-            } else {
-                break;
+    /// The write-lock must not be held whilst taking `self.shards_lock`, so `f` must not prune the
+    /// cache.
+    fn with_shard_locked<R>(&self, epoch: Epoch, f: impl FnOnce(&EpochShard) -> R) -> R {
+        loop {
+            let shard = self.get_or_insert_shard(epoch);
+            let pruned = lock_with_metrics(&shard.write_lock);
+            if !*pruned {
+                return f(&shard);
             }
+            // The shard was pruned after we fetched it. Wait for the pruning to complete, after
+            // which a new shard can be added for the epoch.
+            drop(pruned);
+            drop(lock_with_metrics(&self.shards_lock));
+        }
+    }
+
+    /// Insert a value into `shard`.
+    ///
+    /// The caller must hold the `write_lock` of `shard`, and should prune the cache to its maximum
+    /// length after releasing it.
+    fn insert_locked(shard: &EpochShard, key: AttesterCacheKey, value: AttesterCacheValue) {
+        let mut entries = EpochEntries::clone(&shard.entries.load());
+        entries.insert(key.decision_root, value);
+        shard.entries.store(Arc::new(entries));
+    }
+
+    /// Replace the shards of the cache with `retained`, marking each of the `pruned` shards so
+    /// that no further writes are made to them.
+    ///
+    /// Each pruned shard is marked whilst holding its write-lock, so that any write already in
+    /// progress completes before the shard is removed. The caller must hold `self.shards_lock`.
+    fn store_pruned(&self, retained: ShardMap, pruned: impl IntoIterator<Item = Arc<EpochShard>>) {
+        for shard in pruned {
+            *lock_with_metrics(&shard.write_lock) = true;
+        }
+        self.shards.store(Arc::new(retained));
+    }
+
+    /// If the cache exceeds the maximum length, remove the shards with the lowest epochs until it
+    /// does not. The shard with the highest epoch is always retained.
+    fn prune_to_max_len(&self) {
+        let _shards_lock = lock_with_metrics(&self.shards_lock);
+        let shards = self.shards.load();
+        let mut len = shards
+            .values()
+            .map(|shard| shard.entries.load().len())
+            .sum::<usize>();
+        if len <= MAX_CACHE_LEN {
+            return;
         }
 
-        cache.insert(key, value);
+        let mut new_shards = ShardMap::clone(&shards);
+        let mut pruned = vec![];
+        while len > MAX_CACHE_LEN && new_shards.len() > 1 {
+            if let Some((_, shard)) = new_shards.pop_first() {
+                len = len.saturating_sub(shard.entries.load().len());
+                pruned.push(shard);
+            }
+        }
+        self.store_pruned(new_shards, pruned);
     }
 
     /// Remove all entries where the `key.epoch` is lower than the given `epoch`.
     ///
     /// Generally, the provided `epoch` should be the finalized epoch.
     pub fn prune_below(&self, epoch: Epoch) {
-        self.cache.write().retain(|target, _| target.epoch >= epoch);
+        let _shards_lock = lock_with_metrics(&self.shards_lock);
+        let mut shards = ShardMap::clone(&self.shards.load());
+        let retained = shards.split_off(&epoch);
+        self.store_pruned(retained, shards.into_values());
+    }
+
+    /// Returns all entries in the cache.
+    pub fn to_persisted(&self) -> Vec<PersistedAttesterCacheEntry> {
+        self.shards
+            .load()
+            .iter()
+            .flat_map(|(&epoch, shard)| {
+                shard
+                    .entries
+                    .load()
+                    .iter()
+                    .map(|(&decision_root, value)| PersistedAttesterCacheEntry {
+                        key: AttesterCacheKey {
+                            epoch,
+                            decision_root,
+                        },
+                        value: value.clone(),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Insert the entries returned by `Self::to_persisted`.
+    pub fn restore_persisted(&self, entries: Vec<PersistedAttesterCacheEntry>) {
+        for entry in entries {
+            self.with_shard_locked(entry.key.epoch, |shard| {
+                Self::insert_locked(shard, entry.key, entry.value)
+            });
+            self.prune_to_max_len();
+        }
+    }
+}
+
+/// Acquire `lock`, recording whether it was contended and the time spent waiting for it.
+fn lock_with_metrics<T>(lock: &Mutex<T>) -> MutexGuard<T> {
+    if let Some(guard) = lock.try_lock() {
+        return guard;
+    }
+    metrics::inc_counter(&metrics::ATTESTER_CACHE_LOCK_CONTENDED);
+    let _timer = metrics::start_timer(&metrics::ATTESTER_CACHE_LOCK_WAIT_TIMES);
+    lock.lock()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+    use std::time::Duration;
+
+    fn entry(epoch: u64, decision_root: u64) -> PersistedAttesterCacheEntry {
+        let epoch = Epoch::new(epoch);
+        PersistedAttesterCacheEntry {
+            key: AttesterCacheKey {
+                epoch,
+                decision_root: Hash256::from_low_u64_be(decision_root),
+            },
+            value: AttesterCacheValue {
+                current_justified_checkpoint: Checkpoint::default(),
+                committee_lengths: CommitteeLengths {
+                    epoch,
+                    active_validator_indices_len: 1,
+                },
+            },
+        }
+    }
+
+    fn keys(cache: &AttesterCache) -> Vec<AttesterCacheKey> {
+        let mut keys = cache
+            .to_persisted()
+            .into_iter()
+            .map(|entry| entry.key)
+            .collect::<Vec<_>>();
+        keys.sort_by_key(|key| (key.epoch, key.decision_root));
+        keys
+    }
+
+    #[test]
+    fn prune_waits_for_shard_writer() {
+        let cache = AttesterCache::default();
+        cache.restore_persisted(vec![entry(1, 1), entry(2, 1)]);
+
+        // Hold the write-lock of the epoch 1 shard, as a writer reading a state would.
+        let shard = cache.get_or_insert_shard(Epoch::new(1));
+        let write_lock = shard.write_lock.lock();
+
+        thread::scope(|scope| {
+            let pruner = scope.spawn(|| cache.prune_below(Epoch::new(2)));
+            thread::sleep(Duration::from_millis(100));
+            assert!(!pruner.is_finished());
+
+            AttesterCache::insert_locked(&shard, entry(1, 2).key, entry(1, 2).value);
+            drop(write_lock);
+            pruner.join().unwrap();
+        });
+
+        // The write completed before the shard was pruned, so it was pruned along with it.
+        assert!(*shard.write_lock.lock());
+        assert_eq!(keys(&cache), vec![entry(2, 1).key]);
+    }
+
+    #[test]
+    fn write_to_pruned_shard_is_retained() {
+        let cache = AttesterCache::default();
+        cache.restore_persisted(vec![entry(1, 1), entry(2, 1)]);
+
+        // A writer fetches the shard for epoch 1, which is pruned before the writer locks it.
+        let stale_shard = cache.get_or_insert_shard(Epoch::new(1));
+        cache.prune_below(Epoch::new(2));
+        assert!(*stale_shard.write_lock.lock());
+
+        // The write is made to a new shard rather than the pruned one.
+        cache.restore_persisted(vec![entry(1, 2)]);
+        assert_eq!(stale_shard.entries.load().len(), 1);
+        assert_eq!(keys(&cache), vec![entry(1, 2).key, entry(2, 1).key]);
+    }
+
+    #[test]
+    fn concurrent_writes_and_pruning_are_not_lost() {
+        let cache = AttesterCache::default();
+        let num_writers = 4;
+        let num_entries = 200;
+
+        thread::scope(|scope| {
+            for writer in 0..num_writers {
+                let cache = &cache;
+                scope.spawn(move || {
+                    for i in 0..num_entries {
+                        cache.restore_persisted(vec![entry(10, writer * num_entries + i)]);
+                    }
+                });
+            }
+            // Repeatedly prune and re-add a lower epoch whilst the writers are running.
+            scope.spawn(|| {
+                for i in 0..num_entries {
+                    cache.restore_persisted(vec![entry(5, i)]);
+                    cache.prune_below(Epoch::new(10));
+                }
+            });
+        });
+
+        let keys = keys(&cache);
+        assert_eq!(keys.len() as u64, num_writers * num_entries);
+        assert!(keys.iter().all(|key| key.epoch == Epoch::new(10)));
     }
 }
@@ -1,6 +1,8 @@
 use crate::{BeaconChain, BeaconChainError, BeaconChainTypes, StateSkipConfig};
 use attesting_indices_base::get_attesting_indices;
-use eth2::lighthouse::StandardBlockReward;
+use eth2::lighthouse::{
+    AttestationRewardContribution, BlockRewardExplanation, StandardBlockReward,
+};
 use safe_arith::SafeArith;
 use slog::error;
 use state_processing::common::attesting_indices_base;
@@ -19,7 +21,7 @@ use store::{
     consts::altair::{PARTICIPATION_FLAG_WEIGHTS, PROPOSER_WEIGHT, WEIGHT_DENOMINATOR},
     RelativeEpoch,
 };
-use types::{AbstractExecPayload, BeaconBlockRef, BeaconState, BeaconStateError, EthSpec};
+use types::{AbstractExecPayload, BeaconBlockRef, BeaconState, BeaconStateError, EthSpec, Hash256};
 
 type BeaconBlockSubRewardValue = u64;
 
@@ -38,15 +40,63 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         initialize_epoch_cache(state, &self.spec)?;
 
         self.compute_beacon_block_reward_with_cache(block, state)
+            .map(|(reward, _)| reward)
+    }
+
+    /// Computes the same rewards as `compute_beacon_block_reward`, additionally breaking the
+    /// attestation reward down by each attestation included in `block`.
+    pub fn explain_beacon_block_reward<Payload: AbstractExecPayload<T::EthSpec>>(
+        &self,
+        block: BeaconBlockRef<'_, T::EthSpec, Payload>,
+        block_root: Hash256,
+        state: &mut BeaconState<T::EthSpec>,
+    ) -> Result<BlockRewardExplanation, BeaconChainError> {
+        if block.slot() != state.slot() {
+            return Err(BeaconChainError::BlockRewardSlotError);
+        }
+
+        state.build_committee_cache(RelativeEpoch::Previous, &self.spec)?;
+        state.build_committee_cache(RelativeEpoch::Current, &self.spec)?;
+        initialize_epoch_cache(state, &self.spec)?;
+
+        let (reward, attestation_rewards) =
+            self.compute_beacon_block_reward_with_cache(block, state)?;
+
+        let attestations = block
+            .body()
+            .attestations()
+            .zip(attestation_rewards)
+            .map(|(attestation, reward)| {
+                Ok(AttestationRewardContribution {
+                    data: attestation.data().clone(),
+                    attesters: get_attesting_indices_from_state(state, attestation)?.len() as u64,
+                    reward,
+                })
+            })
+            .collect::<Result<Vec<_>, BeaconChainError>>()?;
+
+        Ok(BlockRewardExplanation {
+            block_root,
+            slot: block.slot(),
+            proposer_index: reward.proposer_index,
+            total: reward.total,
+            attestations,
+            sync_aggregate: reward.sync_aggregate,
+            proposer_slashings: reward.proposer_slashings,
+            attester_slashings: reward.attester_slashings,
+            voluntary_exits: block.body().voluntary_exits().len() as u64,
+        })
     }
 
     // This should only be called after a committee cache has been built
-    // for both the previous and current epoch
+    // for both the previous and current epoch.
+    //
+    // Returns the reward for each attestation alongside the total rewards.
     fn compute_beacon_block_reward_with_cache<Payload: AbstractExecPayload<T::EthSpec>>(
         &self,
         block: BeaconBlockRef<'_, T::EthSpec, Payload>,
         state: &BeaconState<T::EthSpec>,
-    ) -> Result<StandardBlockReward, BeaconChainError> {
+    ) -> Result<(StandardBlockReward, Vec<BeaconBlockSubRewardValue>), BeaconChainError> {
         let proposer_index = block.proposer_index();
 
         let sync_aggregate_reward =
@@ -74,7 +124,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                 BeaconChainError::BlockRewardError
             })?;
 
-        let block_attestation_reward = if let BeaconState::Base(_) = state {
+        let attestation_rewards = if let BeaconState::Base(_) = state {
             self.compute_beacon_block_attestation_reward_base(block, state)
                 .map_err(|e| {
                     error!(
@@ -95,20 +145,25 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                     BeaconChainError::BlockRewardAttestationError
                 })?
         };
+        let block_attestation_reward = attestation_rewards
+            .iter()
+            .try_fold(0u64, |total, reward| total.safe_add(*reward))?;
 
         let total_reward = sync_aggregate_reward
             .safe_add(proposer_slashing_reward)?
             .safe_add(attester_slashing_reward)?
             .safe_add(block_attestation_reward)?;
 
-        Ok(StandardBlockReward {
+        let reward = StandardBlockReward {
             proposer_index,
             total: total_reward,
             attestations: block_attestation_reward,
             sync_aggregate: sync_aggregate_reward,
             proposer_slashings: proposer_slashing_reward,
             attester_slashings: attester_slashing_reward,
-        })
+        };
+
+        Ok((reward, attestation_rewards))
     }
 
     fn compute_beacon_block_sync_aggregate_reward<Payload: AbstractExecPayload<T::EthSpec>>(
@@ -173,7 +228,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         &self,
         block: BeaconBlockRef<'_, T::EthSpec, Payload>,
         state: &BeaconState<T::EthSpec>,
-    ) -> Result<BeaconBlockSubRewardValue, BeaconChainError> {
+    ) -> Result<Vec<BeaconBlockSubRewardValue>, BeaconChainError> {
         // In phase0, rewards for including attestations are awarded at epoch boundaries when the corresponding
         // attestations are contained in state.previous_epoch_attestations. So, if an attestation within this block has
         // target = previous_epoch, it is directly inserted into previous_epoch_attestations and we need the state at
@@ -184,11 +239,12 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         let mut next_epoch_end = None;
 
         let epoch = block.epoch();
-        let mut block_reward = 0;
+        let mut attestation_rewards = Vec::with_capacity(block.body().attestations_len());
 
         let mut rewarded_attesters = HashSet::new();
 
         for attestation in block.body().attestations() {
+            let mut attestation_reward = 0;
             let processing_epoch_end = if attestation.data().target.epoch == epoch {
                 let next_epoch_end = match &mut next_epoch_end {
                     Some(next_epoch_end) => next_epoch_end,
@@ -204,6 +260,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                 // If the next epoch end is no longer phase0, no proposer rewards are awarded, as Altair epoch boundry
                 // processing kicks in. We check this here, as we know that current_epoch_end will always be phase0.
                 if !matches!(next_epoch_end, BeaconState::Base(_)) {
+                    attestation_rewards.push(attestation_reward);
                     continue;
                 }
 
@@ -244,13 +301,14 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                     )?;
                     let proposer_reward =
                         base_reward.safe_div(self.spec.proposer_reward_quotient)?;
-                    block_reward.safe_add_assign(proposer_reward)?;
+                    attestation_reward.safe_add_assign(proposer_reward)?;
                     rewarded_attesters.insert(attester);
                 }
             }
+            attestation_rewards.push(attestation_reward);
         }
 
-        Ok(block_reward)
+        Ok(attestation_rewards)
     }
 
     fn compute_beacon_block_attestation_reward_altair_deneb<
@@ -259,8 +317,8 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         &self,
         block: BeaconBlockRef<'_, T::EthSpec, Payload>,
         state: &BeaconState<T::EthSpec>,
-    ) -> Result<BeaconBlockSubRewardValue, BeaconChainError> {
-        let mut total_proposer_reward = 0;
+    ) -> Result<Vec<BeaconBlockSubRewardValue>, BeaconChainError> {
+        let mut attestation_rewards = Vec::with_capacity(block.body().attestations_len());
 
         let proposer_reward_denominator = WEIGHT_DENOMINATOR
             .safe_sub(PROPOSER_WEIGHT)?
@@ -305,12 +363,11 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                     }
                 }
             }
-            total_proposer_reward.safe_add_assign(
-                proposer_reward_numerator.safe_div(proposer_reward_denominator)?,
-            )?;
+            attestation_rewards
+                .push(proposer_reward_numerator.safe_div(proposer_reward_denominator)?);
         }
 
-        Ok(total_proposer_reward)
+        Ok(attestation_rewards)
     }
 }
 
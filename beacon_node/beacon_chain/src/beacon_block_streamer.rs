@@ -32,8 +32,6 @@ pub enum Error {
     BlockResultNotFound,
 }
 
-const BLOCKS_PER_RANGE_REQUEST: u64 = 32;
-
 // This is the same as a DatabaseBlock but the Arc allows us to avoid an unnecessary clone.
 enum LoadedBeaconBlock<E: EthSpec> {
     Full(Arc<SignedBeaconBlock<E>>),
@@ -50,6 +48,8 @@ enum RequestState<E: EthSpec> {
 struct BodiesByRange<E: EthSpec> {
     start: u64,
     count: u64,
+    /// The maximum number of payload bodies requested at once.
+    batch_size: u64,
     state: RequestState<E>,
 }
 
@@ -178,17 +178,19 @@ fn reconstruct_blocks<E: EthSpec>(
 }
 
 impl<E: EthSpec> BodiesByRange<E> {
-    pub fn new(maybe_block_parts: Option<BlockParts<E>>) -> Self {
+    pub fn new(maybe_block_parts: Option<BlockParts<E>>, batch_size: u64) -> Self {
         if let Some(block_parts) = maybe_block_parts {
             Self {
                 start: block_parts.header.block_number(),
                 count: 1,
+                batch_size,
                 state: RequestState::UnSent(vec![block_parts]),
             }
         } else {
             Self {
                 start: 0,
                 count: 0,
+                batch_size,
                 state: RequestState::UnSent(vec![]),
             }
         }
@@ -199,7 +201,7 @@ impl<E: EthSpec> BodiesByRange<E> {
     }
 
     pub fn push_block_parts(&mut self, block_parts: BlockParts<E>) -> Result<(), BlockParts<E>> {
-        if self.count == BLOCKS_PER_RANGE_REQUEST {
+        if self.count >= self.batch_size {
             return Err(block_parts);
         }
 
@@ -214,9 +216,7 @@ impl<E: EthSpec> BodiesByRange<E> {
                     Ok(())
                 } else {
                     // need to figure out if this block fits in the request
-                    if block_number < self.start
-                        || self.start + BLOCKS_PER_RANGE_REQUEST <= block_number
-                    {
+                    if block_number < self.start || self.start + self.batch_size <= block_number {
                         return Err(block_parts);
                     }
 
@@ -298,8 +298,8 @@ enum EngineRequest<E: EthSpec> {
 }
 
 impl<E: EthSpec> EngineRequest<E> {
-    pub fn new_by_range() -> Self {
-        Self::ByRange(Arc::new(RwLock::new(BodiesByRange::new(None))))
+    pub fn new_by_range(batch_size: u64) -> Self {
+        Self::ByRange(Arc::new(RwLock::new(BodiesByRange::new(None, batch_size))))
     }
     pub fn new_no_request() -> Self {
         Self::NoRequest(Arc::new(RwLock::new(HashMap::new())))
@@ -318,8 +318,9 @@ impl<E: EthSpec> EngineRequest<E> {
                 let mut request = bodies_by_range.write().await;
 
                 if let Err(block_parts) = request.push_block_parts(block_parts) {
+                    let batch_size = request.batch_size;
                     drop(request);
-                    let new_by_range = BodiesByRange::new(Some(block_parts));
+                    let new_by_range = BodiesByRange::new(Some(block_parts), batch_size);
                     *self = Self::ByRange(Arc::new(RwLock::new(new_by_range)));
                 }
             }
@@ -408,18 +409,34 @@ impl<T: BeaconChainTypes> BeaconBlockStreamer<T> {
         }))
     }
 
+    /// Checks the in-memory caches for blocks that may not yet be in the database.
+    ///
+    /// The caches are consulted in order:
+    ///
+    /// 1. The early attester cache, for a block which is being imported.
+    /// 2. The data availability checker, for an execution-valid block which is awaiting blobs or
+    ///    data columns.
+    /// 3. The reqresp pre-import cache, for a block which is still being processed.
     fn check_caches(&self, root: Hash256) -> Option<Arc<SignedBeaconBlock<T::EthSpec>>> {
-        if self.check_caches == CheckCaches::Yes {
-            match self.beacon_chain.get_block_process_status(&root) {
-                BlockProcessStatus::Unknown => None,
-                BlockProcessStatus::NotValidated(block)
-                | BlockProcessStatus::ExecutionValidated(block) => {
-                    metrics::inc_counter(&metrics::BEACON_REQRESP_PRE_IMPORT_CACHE_HITS);
-                    Some(block)
-                }
+        if self.check_caches == CheckCaches::No {
+            return None;
+        }
+
+        if let Some(block) = self.beacon_chain.early_attester_cache.get_block(root) {
+            metrics::inc_counter(&metrics::BEACON_REQRESP_EARLY_ATTESTER_CACHE_HITS);
+            return Some(block);
+        }
+
+        match self.beacon_chain.get_block_process_status(&root) {
+            BlockProcessStatus::Unknown => None,
+            BlockProcessStatus::ExecutionValidated(block) => {
+                metrics::inc_counter(&metrics::BEACON_REQRESP_DA_CHECKER_CACHE_HITS);
+                Some(block)
+            }
+            BlockProcessStatus::NotValidated(block) => {
+                metrics::inc_counter(&metrics::BEACON_REQRESP_PRE_IMPORT_CACHE_HITS);
+                Some(block)
             }
-        } else {
-            None
         }
     }
 
@@ -524,7 +541,9 @@ impl<T: BeaconChainTypes> BeaconBlockStreamer<T> {
         }
 
         // Now deal with the by_range requests. Sort them in order of increasing slot
-        let mut by_range = EngineRequest::<T::EthSpec>::new_by_range();
+        let mut by_range = EngineRequest::<T::EthSpec>::new_by_range(
+            self.beacon_chain.config.payload_bodies_batch_size,
+        );
         by_range_blocks.sort_by_key(|block_parts| block_parts.slot());
         for block_parts in by_range_blocks {
             let root = block_parts.root();
@@ -708,14 +727,18 @@ impl From<Error> for BeaconChainError {
 
 #[cfg(test)]
 mod tests {
-    use crate::beacon_block_streamer::{BeaconBlockStreamer, CheckCaches};
+    use crate::beacon_block_streamer::{
+        BeaconBlockStreamer, BodiesByRange, CheckCaches, EngineRequest,
+    };
     use crate::test_utils::{test_spec, BeaconChainHarness, EphemeralHarnessType};
+    use crate::{AvailabilityProcessingStatus, ChainConfig, NotifyExecutionLayer};
     use execution_layer::test_utils::Block;
     use std::sync::Arc;
     use std::sync::LazyLock;
-    use tokio::sync::mpsc;
+    use tokio::sync::{mpsc, RwLock};
     use types::{
-        ChainSpec, Epoch, EthSpec, FixedBytesExtended, Hash256, Keypair, MinimalEthSpec, Slot,
+        BlockImportSource, ChainSpec, Epoch, EthSpec, FixedBytesExtended, Hash256, Keypair,
+        MinimalEthSpec, Slot,
     };
 
     const VALIDATOR_COUNT: usize = 48;
@@ -864,4 +887,161 @@ mod tests {
             }
         }
     }
+
+    #[tokio::test]
+    async fn serves_block_awaiting_blobs_from_da_checker() {
+        let mut spec = test_spec::<MinimalEthSpec>();
+        spec.altair_fork_epoch = Some(Epoch::new(0));
+        spec.bellatrix_fork_epoch = Some(Epoch::new(0));
+        spec.capella_fork_epoch = Some(Epoch::new(0));
+        spec.deneb_fork_epoch = Some(Epoch::new(0));
+        spec.electra_fork_epoch = None;
+        let spec = Arc::new(spec);
+
+        let harness = get_harness(VALIDATOR_COUNT, spec);
+
+        // The mock execution layer includes a random number of blobs, so keep extending the chain
+        // until a block with at least one blob is produced.
+        for _ in 0..MinimalEthSpec::slots_per_epoch() * 4 {
+            let slot = harness.get_current_slot();
+            let state = harness.get_current_state();
+            let ((block, blobs), _) = harness.make_block(state, slot).await;
+            let block_root = block.canonical_root();
+
+            if blobs.as_ref().map_or(true, |(_, blobs)| blobs.is_empty()) {
+                harness
+                    .process_block(slot, block_root, (block, blobs))
+                    .await
+                    .expect("should import block without blobs");
+                harness.advance_slot();
+                continue;
+            }
+
+            // Import the block without its blobs so that it is held in the DA checker.
+            let status = harness
+                .chain
+                .process_block(
+                    block_root,
+                    block.clone(),
+                    NotifyExecutionLayer::Yes,
+                    BlockImportSource::Gossip,
+                    || Ok(()),
+                )
+                .await
+                .expect("should process block");
+            assert!(
+                matches!(status, AvailabilityProcessingStatus::MissingComponents(..)),
+                "block should be awaiting blobs"
+            );
+
+            for (check_caches, expected) in
+                [(CheckCaches::Yes, Some(block)), (CheckCaches::No, None)]
+            {
+                let streamer = BeaconBlockStreamer::new(&harness.chain, check_caches)
+                    .expect("should create streamer");
+                let (block_tx, mut block_rx) = mpsc::unbounded_channel();
+                streamer.stream(vec![block_root], block_tx).await;
+
+                let (found_root, found_block_result) =
+                    block_rx.recv().await.expect("should get block");
+                assert_eq!(found_root, block_root);
+                let found_block = found_block_result
+                    .as_ref()
+                    .as_ref()
+                    .expect("should not error")
+                    .clone();
+                assert_eq!(found_block, expected);
+            }
+            return;
+        }
+
+        panic!("no block with blobs was produced");
+    }
+
+    #[tokio::test]
+    async fn payload_bodies_are_requested_in_batches() {
+        let slots_per_epoch = MinimalEthSpec::slots_per_epoch() as usize;
+        let batch_size = 4;
+
+        let mut spec = test_spec::<MinimalEthSpec>();
+        spec.altair_fork_epoch = Some(Epoch::new(0));
+        spec.bellatrix_fork_epoch = Some(Epoch::new(0));
+        spec.capella_fork_epoch = Some(Epoch::new(0));
+        spec.deneb_fork_epoch = None;
+        spec.electra_fork_epoch = None;
+
+        let harness = BeaconChainHarness::builder(MinimalEthSpec)
+            .spec(Arc::new(spec))
+            .chain_config(ChainConfig {
+                payload_bodies_batch_size: batch_size,
+                ..ChainConfig::default()
+            })
+            .keypairs(KEYPAIRS[0..VALIDATOR_COUNT].to_vec())
+            .logger(logging::test_logger())
+            .fresh_ephemeral_store()
+            .mock_execution_layer()
+            .build();
+        harness.advance_slot();
+        harness.extend_slots(4 * slots_per_epoch).await;
+
+        // Finalized blocks are stored blinded, so their payloads must be fetched from the engine.
+        // The genesis block is skipped since its payload is the default.
+        let split_slot = harness.chain.store.get_split_slot();
+        let block_roots: Vec<Hash256> = harness
+            .chain
+            .forwards_iter_block_roots(Slot::new(1))
+            .expect("should get iter")
+            .map(Result::unwrap)
+            .take_while(|(_, slot)| *slot < split_slot)
+            .map(|(root, _)| root)
+            .collect();
+
+        let streamer = BeaconBlockStreamer::new(&harness.chain, CheckCaches::No)
+            .expect("should create streamer");
+        let payloads = streamer
+            .load_payloads(block_roots.clone())
+            .await
+            .expect("should load payloads");
+        let requests = streamer.get_requests(payloads).await;
+
+        // Recent blocks may be served from the block cache without an engine request.
+        let mut by_range_requests: Vec<Arc<RwLock<BodiesByRange<MinimalEthSpec>>>> = vec![];
+        let mut blocks_by_range = 0;
+        for (_, request) in &requests {
+            if let EngineRequest::ByRange(by_range) = request {
+                blocks_by_range += 1;
+                if !by_range_requests
+                    .iter()
+                    .any(|other| Arc::ptr_eq(other, by_range))
+                {
+                    by_range_requests.push(by_range.clone());
+                }
+            }
+        }
+        assert!(blocks_by_range > batch_size as usize);
+        assert_eq!(
+            by_range_requests.len(),
+            blocks_by_range.div_ceil(batch_size as usize)
+        );
+
+        let (block_tx, mut block_rx) = mpsc::unbounded_channel();
+        streamer.stream(block_roots.clone(), block_tx).await;
+        for expected_root in block_roots {
+            let (found_root, found_block_result) = block_rx.recv().await.expect("should get block");
+            assert_eq!(found_root, expected_root);
+            let expected_block = harness
+                .chain
+                .get_block(&expected_root)
+                .await
+                .expect("should get block")
+                .expect("block should exist");
+            let found_block = found_block_result
+                .as_ref()
+                .as_ref()
+                .expect("should not error")
+                .clone()
+                .expect("should have a block");
+            assert_eq!(found_block.as_ref(), &expected_block);
+        }
+    }
 }
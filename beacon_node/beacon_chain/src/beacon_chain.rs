@@ -7,7 +7,9 @@ use crate::attester_cache::{AttesterCache, AttesterCacheKey};
 use crate::beacon_block_streamer::{BeaconBlockStreamer, CheckCaches};
 use crate::beacon_proposer_cache::compute_proposer_duties_from_head;
 use crate::beacon_proposer_cache::BeaconProposerCache;
-use crate::blob_verification::{GossipBlobError, GossipVerifiedBlob};
+use crate::blob_verification::{
+    batch_verify_kzg_for_gossip_blobs, GossipBlobError, GossipVerifiedBlob, KzgVerifiedBlob,
+};
 use crate::block_times_cache::BlockTimesCache;
 use crate::block_verification::POS_PANDA_BANNER;
 use crate::block_verification::{
@@ -31,6 +33,7 @@ use crate::eth1_chain::{Eth1Chain, Eth1ChainBackend};
 use crate::eth1_finalization_cache::{Eth1FinalizationCache, Eth1FinalizationData};
 use crate::events::ServerSentEventHandler;
 use crate::execution_payload::{get_execution_payload, NotifyExecutionLayer, PreparePayloadHandle};
+use crate::exit_broadcasts::ExitBroadcasts;
 use crate::fork_choice_signal::{ForkChoiceSignalRx, ForkChoiceSignalTx, ForkChoiceWaitResult};
 use crate::graffiti_calculator::GraffitiCalculator;
 use crate::head_tracker::{HeadTracker, HeadTrackerReader, SszHeadTracker};
@@ -57,11 +60,13 @@ use crate::observed_data_sidecars::ObservedDataSidecars;
 use crate::observed_operations::{ObservationOutcome, ObservedOperations};
 use crate::observed_slashable::ObservedSlashable;
 use crate::persisted_beacon_chain::{PersistedBeaconChain, DUMMY_CANONICAL_HEAD_BLOCK_ROOT};
+use crate::persisted_caches::{PersistedCaches, CACHES_DB_KEY};
 use crate::persisted_fork_choice::PersistedForkChoice;
 use crate::pre_finalization_cache::PreFinalizationBlockCache;
 use crate::shuffling_cache::{BlockShufflingIds, ShufflingCache};
 use crate::sync_committee_verification::{
-    Error as SyncCommitteeError, VerifiedSyncCommitteeMessage, VerifiedSyncContribution,
+    batch_verify_sync_committee_messages, Error as SyncCommitteeError,
+    VerifiedSyncCommitteeMessage, VerifiedSyncContribution,
 };
 use crate::validator_monitor::{
     get_slot_delay_ms, timestamp_now, ValidatorMonitor,
@@ -113,8 +118,9 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 use std::io::prelude::*;
 use std::marker::PhantomData;
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use store::iter::{BlockRootsIterator, ParentRootBlockIterator, StateRootsIterator};
 use store::{
     DatabaseBlock, Error as DBError, HotColdDB, KeyValueStore, KeyValueStoreOp, StoreItem, StoreOp,
@@ -123,7 +129,7 @@ use task_executor::{ShutdownReason, TaskExecutor};
 use tokio::sync::mpsc::Receiver;
 use tokio_stream::Stream;
 use tree_hash::TreeHash;
-use types::blob_sidecar::FixedBlobSidecarList;
+use types::blob_sidecar::{BlobIdentifier, FixedBlobSidecarList};
 use types::data_column_sidecar::{ColumnIndex, DataColumnIdentifier};
 use types::payload::BlockProductionVersion;
 use types::*;
@@ -234,6 +240,10 @@ pub struct PrePayloadAttributes {
     /// The parent block number is not part of the payload attributes sent to the EL, but *is*
     /// sent to builders via SSE.
     pub parent_block_number: u64,
+    /// The gas limit of the block being built upon.
+    ///
+    /// Like the parent block number, this is only sent to builders via SSE.
+    pub parent_gas_limit: u64,
     /// The block root of the block being built upon (same block as fcU `headBlockHash`).
     pub parent_beacon_block_root: Hash256,
 }
@@ -426,6 +436,8 @@ pub struct BeaconChain<T: BeaconChainTypes> {
     pub observed_slashable: RwLock<ObservedSlashable<T::EthSpec>>,
     /// Maintains a record of which validators have submitted voluntary exits.
     pub observed_voluntary_exits: Mutex<ObservedOperations<SignedVoluntaryExit, T::EthSpec>>,
+    /// Tracks the broadcasts of the voluntary exits in the op pool, for re-broadcasting.
+    pub exit_broadcasts: Mutex<ExitBroadcasts>,
     /// Maintains a record of which validators we've seen proposer slashings for.
     pub observed_proposer_slashings: Mutex<ObservedOperations<ProposerSlashing, T::EthSpec>>,
     /// Maintains a record of which validators we've seen attester slashings for.
@@ -498,6 +510,8 @@ pub struct BeaconChain<T: BeaconChainTypes> {
     pub data_availability_checker: Arc<DataAvailabilityChecker<T>>,
     /// The KZG trusted setup used by this chain.
     pub kzg: Arc<Kzg>,
+    /// Whether the chain has been persisted by `Self::persist_on_shutdown`.
+    pub persisted_on_shutdown: AtomicBool,
 }
 
 pub enum BeaconBlockResponseWrapper<E: EthSpec> {
@@ -706,6 +720,75 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         Ok(())
     }
 
+    /// Persists the slashings, voluntary exits and BLS to execution changes of `self.op_pool` to
+    /// disk, so that they are not lost if the node restarts without a clean shutdown.
+    ///
+    /// Attestations and sync contributions are omitted, which makes this cheap enough to run every
+    /// epoch. They are persisted in full by `Self::persist_op_pool` on shutdown.
+    pub fn persist_op_pool_operations(&self) -> Result<(), Error> {
+        let _timer = metrics::start_timer(&metrics::PERSIST_OP_POOL_OPERATIONS);
+
+        self.store.put_item(
+            &OP_POOL_DB_KEY,
+            &PersistedOperationPool::from_operation_pool_without_attestations(&self.op_pool),
+        )?;
+
+        Ok(())
+    }
+
+    /// Persists everything which is required to resume from the current head after a restart:
+    /// the head tracker, fork choice, op pool and eth1 cache, along with the shuffling, proposer
+    /// and attester caches so that duties can be served promptly after a restart.
+    ///
+    /// The early attester cache and the data availability checker are not persisted, since they
+    /// only hold data which is re-requested from peers or re-computed after a restart.
+    pub fn persist_to_disk(&self) -> Result<(), Error> {
+        self.persist_head_and_fork_choice()?;
+        self.persist_op_pool()?;
+        self.persist_eth1_cache()?;
+        self.persist_caches()
+    }
+
+    /// Persists the chain to disk with `Self::persist_to_disk`, unless it has already been
+    /// persisted on shutdown.
+    ///
+    /// Returns `Ok(false)` if the chain had already been persisted. This allows the chain to be
+    /// persisted as part of an orderly shutdown without being persisted again when it is dropped.
+    pub fn persist_on_shutdown(&self) -> Result<bool, Error> {
+        if self
+            .persisted_on_shutdown
+            .swap(true, AtomicOrdering::SeqCst)
+        {
+            return Ok(false);
+        }
+        self.persist_to_disk()?;
+        Ok(true)
+    }
+
+    /// Persists the shuffling, proposer and attester caches to disk.
+    ///
+    /// They are restored and removed from disk when the chain is next built.
+    pub fn persist_caches(&self) -> Result<(), Error> {
+        let _timer = metrics::start_timer(&metrics::PERSIST_CACHES);
+
+        // Only the states of the current and previous epoch are likely to be needed promptly.
+        let min_state_slot = self
+            .canonical_head
+            .cached_head()
+            .head_slot()
+            .saturating_sub(T::EthSpec::slots_per_epoch());
+        let persisted_caches = PersistedCaches::from_caches(
+            &self.shuffling_cache.read(),
+            &self.beacon_proposer_cache.lock(),
+            &self.attester_cache,
+            &self.store,
+            min_state_slot,
+        );
+        self.store.put_item(&CACHES_DB_KEY, &persisted_caches)?;
+
+        Ok(())
+    }
+
     /// Persists `self.eth1_chain` and its caches to disk.
     pub fn persist_eth1_cache(&self) -> Result<(), Error> {
         let _timer = metrics::start_timer(&metrics::PERSIST_ETH1_CACHE);
@@ -1153,6 +1236,50 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             .map_or_else(|| self.get_blobs(block_root), Ok)
     }
 
+    /// Returns the blobs of `block_root` with the given `indices`, or all of its blobs if `indices`
+    /// is `None`.
+    ///
+    /// Each blob is read from the data availability checker if present, otherwise from the early
+    /// attester cache or the store. This is the same order in which blobs are served over RPC.
+    pub fn get_blobs_checking_all_caches(
+        &self,
+        block_root: Hash256,
+        indices: Option<&[u64]>,
+    ) -> Result<Vec<Arc<BlobSidecar<T::EthSpec>>>, Error> {
+        let indices = indices.map_or_else(
+            || (0..T::EthSpec::max_blobs_per_block() as u64).collect(),
+            |indices| {
+                let mut indices = indices.to_vec();
+                indices.sort_unstable();
+                indices.dedup();
+                indices
+            },
+        );
+
+        let mut stored_blobs = None;
+        let mut blobs = vec![];
+        for index in indices {
+            if let Some(blob) = self
+                .data_availability_checker
+                .get_blob(&BlobIdentifier { block_root, index })?
+            {
+                blobs.push(blob);
+                continue;
+            }
+
+            if stored_blobs.is_none() {
+                stored_blobs = Some(self.get_blobs_checking_early_attester_cache(&block_root)?);
+            }
+            if let Some(blob) = stored_blobs
+                .as_ref()
+                .and_then(|stored_blobs| stored_blobs.iter().find(|blob| blob.index == index))
+            {
+                blobs.push(blob.clone());
+            }
+        }
+        Ok(blobs)
+    }
+
     pub fn get_data_column_checking_all_caches(
         &self,
         block_root: Hash256,
@@ -1180,6 +1307,34 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
     pub async fn get_block(
         &self,
         block_root: &Hash256,
+    ) -> Result<Option<SignedBeaconBlock<T::EthSpec>>, Error> {
+        self.get_block_with_payload_source(block_root, false).await
+    }
+
+    /// Returns the block at the given root, if any, reconstructing its payload using the
+    /// fallback execution engines rather than the primary.
+    ///
+    /// ## Errors
+    ///
+    /// May return a database error.
+    pub async fn get_block_from_fallback_execution_layer(
+        &self,
+        block_root: &Hash256,
+    ) -> Result<Option<SignedBeaconBlock<T::EthSpec>>, Error> {
+        self.get_block_with_payload_source(block_root, true).await
+    }
+
+    /// Returns `true` if fallback execution engines are available to reconstruct payloads.
+    pub fn has_fallback_execution_engines(&self) -> bool {
+        self.execution_layer
+            .as_ref()
+            .is_some_and(|el| el.has_fallback_engines())
+    }
+
+    async fn get_block_with_payload_source(
+        &self,
+        block_root: &Hash256,
+        use_fallback_engines: bool,
     ) -> Result<Option<SignedBeaconBlock<T::EthSpec>>, Error> {
         // Load block from database, returning immediately if we have the full block w payload
         // stored.
@@ -1199,16 +1354,21 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
         let exec_block_hash = execution_payload_header.block_hash();
 
-        let execution_payload = self
+        let execution_layer = self
             .execution_layer
             .as_ref()
-            .ok_or(Error::ExecutionLayerMissing)?
-            .get_payload_for_header(&execution_payload_header, fork)
-            .await
-            .map_err(|e| {
-                Error::ExecutionLayerErrorPayloadReconstruction(exec_block_hash, Box::new(e))
-            })?
-            .ok_or(Error::BlockHashMissingFromExecutionLayer(exec_block_hash))?;
+            .ok_or(Error::ExecutionLayerMissing)?;
+        let execution_payload = if use_fallback_engines {
+            execution_layer
+                .get_payload_for_header_from_fallbacks(&execution_payload_header, fork)
+                .await
+        } else {
+            execution_layer
+                .get_payload_for_header(&execution_payload_header, fork)
+                .await
+        }
+        .map_err(|e| Error::ExecutionLayerErrorPayloadReconstruction(exec_block_hash, Box::new(e)))?
+        .ok_or(Error::BlockHashMissingFromExecutionLayer(exec_block_hash))?;
 
         // Verify payload integrity.
         let header_from_payload = ExecutionPayloadHeader::from(execution_payload.to_ref());
@@ -1401,6 +1561,17 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         )
     }
 
+    /// Returns the SSZ encodings of the best light client updates for a range of periods, for
+    /// serving over RPC.
+    pub fn get_encoded_light_client_updates(
+        &self,
+        sync_committee_period: u64,
+        count: u64,
+    ) -> Result<Vec<Arc<EncodedLightClientUpdate>>, Error> {
+        self.light_client_server_cache
+            .get_encoded_light_client_updates(&self.store, sync_committee_period, count, &self.spec)
+    }
+
     /// Returns the current heads of the `BeaconChain`. For the canonical head, see `Self::head`.
     ///
     /// Returns `(block_root, block_slot)`.
@@ -2095,6 +2266,27 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         })
     }
 
+    /// Performs the same validation as `Self::verify_sync_committee_message_for_gossip`, but for
+    /// multiple messages using batch BLS verification.
+    pub fn batch_verify_sync_committee_messages_for_gossip<I>(
+        &self,
+        sync_messages: I,
+    ) -> Vec<Result<VerifiedSyncCommitteeMessage, SyncCommitteeError>>
+    where
+        I: Iterator<Item = (SyncCommitteeMessage, SyncSubnetId)> + ExactSizeIterator,
+    {
+        metrics::inc_counter_by(
+            &metrics::SYNC_MESSAGE_PROCESSING_REQUESTS,
+            sync_messages.len() as u64,
+        );
+        let results = batch_verify_sync_committee_messages(sync_messages, self);
+        metrics::inc_counter_by(
+            &metrics::SYNC_MESSAGE_PROCESSING_SUCCESSES,
+            results.iter().filter(|result| result.is_ok()).count() as u64,
+        );
+        results
+    }
+
     /// Accepts some `SignedContributionAndProof` from the network and attempts to verify it,
     /// returning `Ok(_)` if it is valid to be (re)broadcast on the gossip network.
     pub fn verify_sync_contribution_for_gossip(
@@ -2155,6 +2347,33 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         })
     }
 
+    /// Performs the same validation as `Self::verify_blob_sidecar_for_gossip` for a blob whose
+    /// KZG proof was verified by `Self::batch_verify_kzg_for_gossip_blobs`.
+    pub fn verify_kzg_verified_blob_sidecar_for_gossip(
+        self: &Arc<Self>,
+        blob: KzgVerifiedBlob<T::EthSpec>,
+        subnet_id: u64,
+    ) -> Result<GossipVerifiedBlob<T>, GossipBlobError> {
+        metrics::inc_counter(&metrics::BLOBS_SIDECAR_PROCESSING_REQUESTS);
+        let _timer = metrics::start_timer(&metrics::BLOBS_SIDECAR_GOSSIP_VERIFICATION_TIMES);
+        GossipVerifiedBlob::new_kzg_verified(blob, subnet_id, self).inspect(|_| {
+            metrics::inc_counter(&metrics::BLOBS_SIDECAR_PROCESSING_SUCCESSES);
+        })
+    }
+
+    /// Verify the KZG proofs of many gossip blob sidecars in batches, before each blob is passed
+    /// to `Self::verify_kzg_verified_blob_sidecar_for_gossip` if its proof was verified, or
+    /// `Self::verify_blob_sidecar_for_gossip` otherwise.
+    ///
+    /// The returned `Vec` has a one-to-one mapping with the supplied blobs.
+    pub fn batch_verify_kzg_for_gossip_blobs(
+        &self,
+        blob_sidecars: Vec<Arc<BlobSidecar<T::EthSpec>>>,
+    ) -> Vec<Result<KzgVerifiedBlob<T::EthSpec>, Arc<BlobSidecar<T::EthSpec>>>> {
+        let seen_timestamp = self.slot_clock.now_duration().unwrap_or_default();
+        batch_verify_kzg_for_gossip_blobs(blob_sidecars, &self.kzg, seen_timestamp)
+    }
+
     /// Accepts some 'LightClientOptimisticUpdate' from the network and attempts to verify it
     pub fn verify_optimistic_update_for_gossip(
         self: &Arc<Self>,
@@ -2486,10 +2705,32 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
     /// Accept a pre-verified exit and queue it for inclusion in an appropriate block.
     pub fn import_voluntary_exit(&self, exit: SigVerifiedOp<SignedVoluntaryExit, T::EthSpec>) {
         if self.eth1_chain.is_some() {
+            if let Ok(epoch) = self.epoch() {
+                self.exit_broadcasts
+                    .lock()
+                    .observe(exit.as_inner().message.validator_index, epoch);
+            }
             self.op_pool.insert_voluntary_exit(exit)
         }
     }
 
+    /// Returns the voluntary exits in the op pool, each paired with whether the exit is included
+    /// in the canonical chain, i.e. whether the head state has initiated the validator's exit.
+    pub fn voluntary_exits_with_inclusion(&self) -> Vec<(SignedVoluntaryExit, bool)> {
+        let head = self.head_snapshot();
+        self.op_pool
+            .get_all_voluntary_exits()
+            .into_iter()
+            .map(|exit| {
+                let included = head
+                    .beacon_state
+                    .get_validator(exit.message.validator_index as usize)
+                    .is_ok_and(|validator| validator.exit_epoch != self.spec.far_future_epoch);
+                (exit, included)
+            })
+            .collect()
+    }
+
     /// Verify a proposer slashing before allowing it to propagate on the gossip network.
     pub fn verify_proposer_slashing_for_gossip(
         &self,
@@ -3273,6 +3514,44 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         self.remove_notified(&block_root, r)
     }
 
+    /// As `process_block_with_early_caching`, but stops waiting for the import if it hasn't
+    /// completed within `timeout`, so that an unresponsive execution layer can't stall the caller
+    /// indefinitely.
+    ///
+    /// Returns `BlockError::ImportTimeout` if the deadline was reached.
+    ///
+    /// ## Limitations
+    ///
+    /// Only the import future is dropped. Work it has already handed off to other tasks, such as
+    /// the `newPayload` call to the execution layer or blocking state and signature computations,
+    /// keeps running in the background until it completes, and its result is discarded. The
+    /// timeout therefore frees the caller (e.g. a beacon processor worker) but not the resources
+    /// held by the import, and a retried import may run alongside the abandoned one.
+    pub async fn process_block_with_early_caching_and_timeout<B: IntoExecutionPendingBlock<T>>(
+        self: &Arc<Self>,
+        block_root: Hash256,
+        unverified_block: B,
+        block_source: BlockImportSource,
+        notify_execution_layer: NotifyExecutionLayer,
+        timeout: Duration,
+    ) -> Result<AvailabilityProcessingStatus, BlockError> {
+        let import = self.process_block_with_early_caching(
+            block_root,
+            unverified_block,
+            block_source,
+            notify_execution_layer,
+        );
+        match tokio::time::timeout(timeout, import).await {
+            Ok(r) => r,
+            Err(_) => {
+                metrics::inc_counter(&metrics::BLOCK_IMPORT_TIMEOUTS);
+                // The cancelled import didn't get the chance to evict the block.
+                self.reqresp_pre_import_cache.write().remove(&block_root);
+                Err(BlockError::ImportTimeout(timeout))
+            }
+        }
+    }
+
     /// Returns `Ok(block_root)` if the given `unverified_block` was successfully verified and
     /// imported into the chain.
     ///
@@ -3849,8 +4128,10 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                         );
                     }
                 }
-                // This block did not become the head, nothing to do.
-                Ok(_) => (),
+                // This block did not become the head. If an earlier block in the early attester cache
+                // is no longer the head either (e.g. due to a re-org immediately after it was added),
+                // remove it so that it can't be attested to.
+                Ok(new_head_root) => self.early_attester_cache.retain_head(new_head_root),
                 Err(e) => error!(
                     self.log,
                     "Failed to compute head during block import";
@@ -4550,6 +4831,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn produce_block_with_verification(
         self: &Arc<Self>,
         randao_reveal: Signature,
@@ -4557,6 +4839,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         validator_graffiti: Option<Graffiti>,
         verification: ProduceBlockVerification,
         builder_boost_factor: Option<u64>,
+        builder_min_bid_gwei: Option<u64>,
         block_production_version: BlockProductionVersion,
     ) -> Result<BeaconBlockResponseWrapper<T::EthSpec>, BlockProductionError> {
         metrics::inc_counter(&metrics::BLOCK_PRODUCTION_REQUESTS);
@@ -4586,6 +4869,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             validator_graffiti,
             verification,
             builder_boost_factor,
+            builder_min_bid_gwei,
             block_production_version,
         )
         .await
@@ -4625,6 +4909,10 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                     "head_to_reorg" => %head_block_root,
                 );
                 (re_org_state, Some(re_org_state_root))
+            } else if let Some((parent_state, parent_state_root)) =
+                self.get_state_for_late_payload(slot, head_slot, head_block_root)
+            {
+                (parent_state, Some(parent_state_root))
             } else {
                 // Fetch the head state advanced through to `slot`, which should be present in the
                 // state cache thanks to the state advance timer.
@@ -4770,6 +5058,155 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         Some((state, state_root))
     }
 
+    /// Fetch the beacon state to use for producing a block if the payload of the head block was
+    /// revealed too recently to be built upon.
+    ///
+    /// Building on a payload revealed just before the proposal helps a builder which withheld it
+    /// until it could no longer be re-orged, so instead the block is built on the head's parent.
+    /// This re-orgs the head, so it is only done if the `get_proposer_head` conditions from fork
+    /// choice pass, as for a proposer re-org. The reveal threshold replaces the requirement that
+    /// the head was late.
+    ///
+    /// This function will return `None` if late payload protection or proposer re-orgs are
+    /// disabled, or if the head can be built upon.
+    fn get_state_for_late_payload(
+        &self,
+        slot: Slot,
+        head_slot: Slot,
+        canonical_head: Hash256,
+    ) -> Option<(BeaconState<T::EthSpec>, Hash256)> {
+        let reveal_threshold = self.config.late_payload_reveal_threshold?;
+
+        // Blocks without an observation time weren't received from the network, so there is no
+        // reveal time to consider.
+        let head_slot_start = self.slot_clock.start_of(head_slot)?;
+        let observed_delay = self
+            .block_times_cache
+            .read()
+            .get_block_delays(canonical_head, head_slot_start)
+            .observed?;
+        let revealed_at = head_slot_start.saturating_add(observed_delay);
+        let now = self.slot_clock.now_duration()?;
+        let reveal_age = now.saturating_sub(revealed_at);
+        if reveal_age >= reveal_threshold {
+            return None;
+        }
+
+        let fee_recipient = {
+            let head = self.canonical_head.cached_head();
+            if head.head_block_root() != canonical_head {
+                return None;
+            }
+            head.snapshot
+                .beacon_block
+                .message()
+                .execution_payload()
+                .ok()?
+                .fee_recipient()
+        };
+
+        if self
+            .config
+            .late_payload_trusted_fee_recipients
+            .contains(&fee_recipient)
+        {
+            metrics::inc_counter(&metrics::BLOCK_PRODUCTION_LATE_PAYLOAD_TRUSTED);
+            debug!(
+                self.log,
+                "Building on recently revealed payload";
+                "reason" => "trusted fee recipient",
+                "head" => ?canonical_head,
+                "fee_recipient" => ?fee_recipient,
+                "reveal_age_ms" => reveal_age.as_millis(),
+            );
+            return None;
+        }
+
+        let not_refusing = |reason: &dyn std::fmt::Display| {
+            debug!(
+                self.log,
+                "Building on recently revealed payload";
+                "reason" => %reason,
+                "head" => ?canonical_head,
+                "reveal_age_ms" => reveal_age.as_millis(),
+            );
+        };
+
+        let (Some(re_org_head_threshold), Some(re_org_parent_threshold)) = (
+            self.config.re_org_head_threshold,
+            self.config.re_org_parent_threshold,
+        ) else {
+            not_refusing(&DoNotReOrg::ReOrgsDisabled);
+            return None;
+        };
+        if self.spec.proposer_score_boost.is_none() {
+            not_refusing(&"this network does not have proposer boost enabled");
+            return None;
+        }
+        let slot_delay = self.slot_clock.seconds_from_current_slot_start()?;
+        if slot_delay >= self.config.re_org_cutoff(self.spec.seconds_per_slot) {
+            not_refusing(&"not proposing on time");
+            return None;
+        }
+
+        let parent_block_root = self
+            .canonical_head
+            .fork_choice_read_lock()
+            .get_proposer_head(
+                slot,
+                canonical_head,
+                re_org_head_threshold,
+                re_org_parent_threshold,
+                &self.config.re_org_disallowed_offsets,
+                self.config.re_org_max_epochs_since_finalization,
+            )
+            .map_err(|e| match e {
+                ProposerHeadError::DoNotReOrg(reason) => not_refusing(&reason),
+                ProposerHeadError::Error(e) => {
+                    warn!(
+                        self.log,
+                        "Building on recently revealed payload";
+                        "error" => ?e,
+                    );
+                }
+            })
+            .ok()?
+            .parent_node
+            .root;
+
+        let parent_state_root = self
+            .canonical_head
+            .fork_choice_read_lock()
+            .get_block(&parent_block_root)?
+            .state_root;
+        let (state_root, state) = self
+            .store
+            .get_advanced_hot_state(parent_block_root, slot, parent_state_root)
+            .map_err(|e| {
+                warn!(
+                    self.log,
+                    "Building on recently revealed payload";
+                    "reason" => "unable to load parent state",
+                    "error" => ?e,
+                );
+            })
+            .ok()
+            .flatten()?;
+
+        metrics::inc_counter(&metrics::BLOCK_PRODUCTION_LATE_PAYLOAD_REFUSALS);
+        info!(
+            self.log,
+            "Refusing to build on recently revealed payload";
+            "head" => ?canonical_head,
+            "parent" => ?parent_block_root,
+            "fee_recipient" => ?fee_recipient,
+            "reveal_age_ms" => reveal_age.as_millis(),
+            "threshold_ms" => reveal_threshold.as_millis(),
+        );
+
+        Some((state, state_root))
+    }
+
     /// Get the proposer index and `prev_randao` value for a proposal at slot `proposal_slot`.
     ///
     /// The `proposer_head` may be the head block of `cached_head` or its parent. An error will
@@ -4863,21 +5300,37 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             proposer as u64
         };
 
-        // Get the `prev_randao` and parent block number.
+        // Get the `prev_randao`, parent block number and parent gas limit.
         let head_block_number = cached_head.head_block_number()?;
-        let (prev_randao, parent_block_number) = if proposer_head == head_parent_block_root {
-            (
-                cached_head.parent_random()?,
-                head_block_number.saturating_sub(1),
-            )
-        } else {
-            (cached_head.head_random()?, head_block_number)
-        };
+        let (prev_randao, parent_block_number, parent_gas_limit) =
+            if proposer_head == head_parent_block_root {
+                // The parent's gas limit is not retained by the head, so load it from the store.
+                // This only happens when preparing to re-org the head, which is rare.
+                let parent_gas_limit = self
+                    .store
+                    .get_blinded_block(&head_parent_block_root)?
+                    .ok_or(Error::MissingBeaconBlock(head_parent_block_root))?
+                    .message()
+                    .execution_payload()?
+                    .gas_limit();
+                (
+                    cached_head.parent_random()?,
+                    head_block_number.saturating_sub(1),
+                    parent_gas_limit,
+                )
+            } else {
+                (
+                    cached_head.head_random()?,
+                    head_block_number,
+                    cached_head.head_gas_limit()?,
+                )
+            };
 
         Ok(Some(PrePayloadAttributes {
             proposer_index,
             prev_randao,
             parent_block_number,
+            parent_gas_limit,
             parent_beacon_block_root: proposer_head,
         }))
     }
@@ -5145,6 +5598,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         validator_graffiti: Option<Graffiti>,
         verification: ProduceBlockVerification,
         builder_boost_factor: Option<u64>,
+        builder_min_bid_gwei: Option<u64>,
         block_production_version: BlockProductionVersion,
     ) -> Result<BeaconBlockResponseWrapper<T::EthSpec>, BlockProductionError> {
         // Part 1/3 (blocking)
@@ -5166,6 +5620,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                         randao_reveal,
                         graffiti,
                         builder_boost_factor,
+                        builder_min_bid_gwei,
                         block_production_version,
                     )
                 },
@@ -5264,6 +5719,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         randao_reveal: Signature,
         graffiti: Graffiti,
         builder_boost_factor: Option<u64>,
+        builder_min_bid_gwei: Option<u64>,
         block_production_version: BlockProductionVersion,
     ) -> Result<PartialBeaconBlock<T::EthSpec>, BlockProductionError> {
         let eth1_chain = self
@@ -5313,7 +5769,10 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             chain_health: self
                 .is_healthy(&parent_root)
                 .map_err(BlockProductionError::BeaconChain)?,
+            deadline: self.block_production_deadline(state.slot()),
+            min_bid_gwei: builder_min_bid_gwei,
         };
+        let deadline = builder_params.deadline;
 
         // If required, start the process of loading an execution payload from the EL early. This
         // allows it to run concurrently with things like attestation packing.
@@ -5349,9 +5808,17 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
         // Iterate through the naive aggregation pool and ensure all the attestations from there
         // are included in the operation pool.
+        //
+        // Once the block production deadline has passed, the remaining attestations are skipped
+        // in favour of those already in the operation pool.
         let unagg_import_timer =
             metrics::start_timer(&metrics::BLOCK_PRODUCTION_UNAGGREGATED_TIMES);
+        let mut num_skipped_attestations = 0;
         for attestation in self.naive_aggregation_pool.read().iter() {
+            if deadline.map_or(false, |deadline| Instant::now() >= deadline) {
+                num_skipped_attestations += 1;
+                continue;
+            }
             let import = |attestation: &Attestation<T::EthSpec>| {
                 let attesting_indices =
                     get_attesting_indices_from_state(&state, attestation.to_ref())?;
@@ -5368,6 +5835,21 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             }
         }
         drop(unagg_import_timer);
+        if num_skipped_attestations > 0 {
+            metrics::inc_counter_vec_by(
+                &metrics::BLOCK_PRODUCTION_DEADLINE_SKIPS,
+                &["unaggregated_attestations"],
+                num_skipped_attestations,
+            );
+            info!(
+                self.log,
+                "Block production deadline passed";
+                "info" => "unaggregated attestations were not added to the op pool",
+                "skipped" => "unaggregated_attestations",
+                "num_skipped" => num_skipped_attestations,
+                "slot" => state.slot(),
+            );
+        }
 
         let attestation_packing_timer =
             metrics::start_timer(&metrics::BLOCK_PRODUCTION_ATTESTATION_TIMES);
@@ -6048,13 +6530,11 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         };
 
         // If the execution layer doesn't have any proposer data for this validator then we assume
-        // it's not connected to this BN and no action is required.
+        // it's not connected to this BN and no action is required. Its fee recipient is unknown,
+        // so its payload attributes are not published either.
         let proposer = pre_payload_attributes.proposer_index;
-        if !self.config.always_prepare_payload
-            && !execution_layer
-                .has_proposer_preparation_data(proposer)
-                .await
-        {
+        let registered_fee_recipient = execution_layer.get_registered_fee_recipient(proposer).await;
+        if !self.config.always_prepare_payload && registered_fee_recipient.is_none() {
             return Ok(None);
         }
 
@@ -6123,6 +6603,15 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         // Push a server-sent event (probably to a block builder or relay).
         if let Some(event_handler) = &self.event_handler {
             if event_handler.has_payload_attributes_subscribers() {
+                // A registered proposer is expected to request a blinded block if this node is
+                // connected to a builder for it, either the builder of its builder group or the
+                // default builder.
+                let proposer_builder = match self.validator_pubkey_bytes(proposer as usize)? {
+                    Some(pubkey) => execution_layer.builder_for(&pubkey),
+                    None => execution_layer.builder(),
+                };
+                let blinded_proposal_expected =
+                    registered_fee_recipient.is_some() && proposer_builder.is_some();
                 event_handler.register(EventKind::PayloadAttributes(ForkVersionedResponse {
                     data: SseExtendedPayloadAttributes {
                         proposal_slot: prepare_slot,
@@ -6130,6 +6619,9 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                         parent_block_root: head_root,
                         parent_block_number: pre_payload_attributes.parent_block_number,
                         parent_block_hash: forkchoice_update_params.head_hash.unwrap_or_default(),
+                        parent_gas_limit: pre_payload_attributes.parent_gas_limit,
+                        proposer_fee_recipient: registered_fee_recipient,
+                        blinded_proposal_expected,
                         payload_attributes: payload_attributes.into(),
                     },
                     metadata: Default::default(),
@@ -6880,6 +7372,16 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             .map(|duration| (fork_name, duration))
     }
 
+    /// Returns the instant at which block production for `slot` stops waiting on slow components,
+    /// if a block production deadline is configured.
+    ///
+    /// The deadline may already have passed, e.g. if the block is being produced late.
+    pub fn block_production_deadline(&self, slot: Slot) -> Option<Instant> {
+        let deadline = self.slot_clock.start_of(slot)? + self.config.block_production_deadline?;
+        let now = self.slot_clock.now_duration()?;
+        Some(Instant::now() + deadline.saturating_sub(now))
+    }
+
     /// This method serves to get a sense of the current chain health. It is used in block proposal
     /// to determine whether we should outsource payload production duties.
     ///
@@ -7087,6 +7589,37 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         self.data_availability_checker.data_availability_boundary()
     }
 
+    /// The oldest epoch from which blobs are retained by the blob pruner, which may be older than
+    /// the data availability boundary if `--blob-retention-epochs` is set.
+    /// `None` if the `Deneb` fork is disabled.
+    pub fn blob_retention_boundary(&self) -> Option<Epoch> {
+        let deneb_fork_epoch = self.spec.deneb_fork_epoch?;
+        let data_availability_boundary = self.data_availability_boundary()?;
+        let boundary = match self
+            .store
+            .get_config()
+            .blob_retention
+            .epochs_before_data_availability_boundary(&self.spec)
+        {
+            Some(epochs) => data_availability_boundary.saturating_sub(epochs),
+            None => deneb_fork_epoch,
+        };
+        Some(std::cmp::max(boundary, deneb_fork_epoch))
+    }
+
+    /// The oldest epoch from which data columns are retained by the blob pruner, which is no later
+    /// than the PeerDAS fork epoch if `--data-column-archive` is set.
+    /// `None` if the `Deneb` fork is disabled.
+    pub fn data_column_retention_boundary(&self) -> Option<Epoch> {
+        let blob_retention_boundary = self.blob_retention_boundary()?;
+        match self.spec.eip7594_fork_epoch {
+            Some(eip7594_fork_epoch) if self.store.get_config().data_column_archive => {
+                Some(std::cmp::min(blob_retention_boundary, eip7594_fork_epoch))
+            }
+            _ => Some(blob_retention_boundary),
+        }
+    }
+
     /// Returns true if epoch is within the data availability boundary
     pub fn da_check_required_for_epoch(&self, epoch: Epoch) -> bool {
         self.data_availability_checker
@@ -7148,23 +7681,17 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
 impl<T: BeaconChainTypes> Drop for BeaconChain<T> {
     fn drop(&mut self) {
-        let drop = || -> Result<(), Error> {
-            self.persist_head_and_fork_choice()?;
-            self.persist_op_pool()?;
-            self.persist_eth1_cache()
-        };
-
-        if let Err(e) = drop() {
-            error!(
+        match self.persist_on_shutdown() {
+            Ok(true) => info!(
+                self.log,
+                "Saved beacon chain to disk";
+            ),
+            Ok(false) => (),
+            Err(e) => error!(
                 self.log,
                 "Failed to persist on BeaconChain drop";
                 "error" => ?e
-            )
-        } else {
-            info!(
-                self.log,
-                "Saved beacon chain to disk";
-            )
+            ),
         }
     }
 }
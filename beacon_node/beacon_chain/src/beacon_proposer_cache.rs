@@ -8,6 +8,7 @@
 //! very simple to reason about, but it might store values that are useless due to finalization. The
 //! values it stores are very small, so this should not be an issue.
 
+use crate::persisted_caches::PersistedProposers;
 use crate::{BeaconChain, BeaconChainError, BeaconChainTypes};
 use fork_choice::ExecutionStatus;
 use lru::LruCache;
@@ -132,6 +133,40 @@ impl BeaconProposerCache {
 
         Ok(())
     }
+
+    /// Returns the cached proposers, ordered from the least to the most recently used.
+    pub fn to_persisted(&self) -> Vec<PersistedProposers> {
+        self.cache
+            .iter()
+            .rev()
+            .map(
+                |(&(epoch, shuffling_decision_block), cache)| PersistedProposers {
+                    epoch,
+                    shuffling_decision_block,
+                    fork: cache.fork,
+                    proposers: cache.proposers.iter().map(|&index| index as u64).collect(),
+                },
+            )
+            .collect()
+    }
+
+    /// Insert the proposers returned by `Self::to_persisted`, preserving their LRU order.
+    pub fn restore_persisted(&mut self, proposers: Vec<PersistedProposers>) {
+        for persisted in proposers {
+            self.cache.put(
+                (persisted.epoch, persisted.shuffling_decision_block),
+                EpochBlockProposers {
+                    epoch: persisted.epoch,
+                    fork: persisted.fork,
+                    proposers: persisted
+                        .proposers
+                        .into_iter()
+                        .map(|index| index as usize)
+                        .collect(),
+                },
+            );
+        }
+    }
 }
 
 /// Compute the proposer duties using the head state without cache.
@@ -1,5 +1,6 @@
 use derivative::Derivative;
 use slot_clock::SlotClock;
+use std::collections::HashMap;
 use std::marker::PhantomData;
 use std::sync::Arc;
 
@@ -182,6 +183,23 @@ impl<T: BeaconChainTypes, O: ObservationStrategy> GossipVerifiedBlob<T, O> {
             )
         })
     }
+
+    /// Performs the same validation as `Self::new` for a blob whose KZG proof has already been
+    /// verified, e.g. by `batch_verify_kzg_for_gossip_blobs`.
+    pub fn new_kzg_verified(
+        blob: KzgVerifiedBlob<T::EthSpec>,
+        subnet_id: u64,
+        chain: &BeaconChain<T>,
+    ) -> Result<Self, GossipBlobError> {
+        let header = blob.as_blob().signed_block_header.clone();
+        validate_kzg_verified_blob_sidecar_for_gossip::<T, O>(blob, subnet_id, chain).map_err(|e| {
+            process_block_slash_info::<_, GossipBlobError>(
+                chain,
+                BlockSlashInfo::from_early_error_blob(header, e),
+            )
+        })
+    }
+
     /// Construct a `GossipVerifiedBlob` that is assumed to be valid.
     ///
     /// This should ONLY be used for testing.
@@ -381,11 +399,45 @@ where
     validate_blobs::<E>(kzg, commitments.as_slice(), blobs, proofs.as_slice())
 }
 
+/// Wraps a `BlobSidecar` that has passed all gossip checks except KZG verification and
+/// observation. Used to skip the KZG verification of blobs which were verified in a batch.
+struct PartiallyVerifiedBlob<E: EthSpec> {
+    blob_sidecar: Arc<BlobSidecar<E>>,
+    block_root: Hash256,
+    seen_timestamp: Duration,
+}
+
 pub fn validate_blob_sidecar_for_gossip<T: BeaconChainTypes, O: ObservationStrategy>(
     blob_sidecar: Arc<BlobSidecar<T::EthSpec>>,
     subnet: u64,
     chain: &BeaconChain<T>,
 ) -> Result<GossipVerifiedBlob<T, O>, GossipBlobError> {
+    let partially_verified =
+        partially_validate_blob_sidecar_for_gossip(blob_sidecar, subnet, chain)?;
+    complete_blob_sidecar_gossip_validation(partially_verified, chain, None)
+}
+
+/// Performs the same validation as `validate_blob_sidecar_for_gossip`, except for the KZG
+/// verification which has already been completed.
+pub fn validate_kzg_verified_blob_sidecar_for_gossip<
+    T: BeaconChainTypes,
+    O: ObservationStrategy,
+>(
+    kzg_verified_blob: KzgVerifiedBlob<T::EthSpec>,
+    subnet: u64,
+    chain: &BeaconChain<T>,
+) -> Result<GossipVerifiedBlob<T, O>, GossipBlobError> {
+    let partially_verified =
+        partially_validate_blob_sidecar_for_gossip(kzg_verified_blob.clone_blob(), subnet, chain)?;
+    complete_blob_sidecar_gossip_validation(partially_verified, chain, Some(kzg_verified_blob))
+}
+
+/// Run the gossip checks that happen before KZG verification.
+fn partially_validate_blob_sidecar_for_gossip<T: BeaconChainTypes>(
+    blob_sidecar: Arc<BlobSidecar<T::EthSpec>>,
+    subnet: u64,
+    chain: &BeaconChain<T>,
+) -> Result<PartiallyVerifiedBlob<T::EthSpec>, GossipBlobError> {
     let blob_slot = blob_sidecar.slot();
     let blob_index = blob_sidecar.index;
     let block_parent_root = blob_sidecar.block_parent_root();
@@ -568,11 +620,35 @@ pub fn validate_blob_sidecar_for_gossip<T: BeaconChainTypes, O: ObservationStrat
         });
     }
 
-    // Kzg verification for gossip blob sidecar
-    let kzg = chain.kzg.as_ref();
+    Ok(PartiallyVerifiedBlob {
+        blob_sidecar,
+        block_root,
+        seen_timestamp,
+    })
+}
+
+/// Complete the gossip validation of a partially verified blob, verifying its KZG proof unless
+/// `kzg_verified_blob` is provided.
+fn complete_blob_sidecar_gossip_validation<T: BeaconChainTypes, O: ObservationStrategy>(
+    partially_verified: PartiallyVerifiedBlob<T::EthSpec>,
+    chain: &BeaconChain<T>,
+    kzg_verified_blob: Option<KzgVerifiedBlob<T::EthSpec>>,
+) -> Result<GossipVerifiedBlob<T, O>, GossipBlobError> {
+    let PartiallyVerifiedBlob {
+        blob_sidecar,
+        block_root,
+        seen_timestamp,
+    } = partially_verified;
 
-    let kzg_verified_blob = KzgVerifiedBlob::new(blob_sidecar.clone(), kzg, seen_timestamp)
-        .map_err(GossipBlobError::KzgError)?;
+    // Kzg verification for gossip blob sidecar
+    let kzg_verified_blob = match kzg_verified_blob {
+        Some(kzg_verified_blob) => kzg_verified_blob,
+        None => {
+            let kzg = chain.kzg.as_ref();
+            KzgVerifiedBlob::new(blob_sidecar, kzg, seen_timestamp)
+                .map_err(GossipBlobError::KzgError)?
+        }
+    };
     let blob_sidecar = &kzg_verified_blob.blob;
 
     chain
@@ -596,6 +672,58 @@ pub fn validate_blob_sidecar_for_gossip<T: BeaconChainTypes, O: ObservationStrat
     })
 }
 
+/// Verify the KZG proofs of blob sidecars received over gossip, prior to their gossip validation.
+///
+/// The blobs are grouped by block root and the proofs of each group are verified in a single
+/// batch. The returned `Vec` has a one-to-one mapping with the supplied blobs. Blobs which could
+/// not be verified in a batch, either because they are the only blob for their block or because
+/// their group contains an invalid proof, are returned unverified so that they can be validated
+/// individually.
+pub fn batch_verify_kzg_for_gossip_blobs<E: EthSpec>(
+    blob_sidecars: Vec<Arc<BlobSidecar<E>>>,
+    kzg: &Kzg,
+    seen_timestamp: Duration,
+) -> Vec<Result<KzgVerifiedBlob<E>, Arc<BlobSidecar<E>>>> {
+    let mut groups: HashMap<Hash256, Vec<usize>> = HashMap::new();
+    for (i, blob_sidecar) in blob_sidecars.iter().enumerate() {
+        groups.entry(blob_sidecar.block_root()).or_default().push(i);
+    }
+
+    let mut verified_in_batch = vec![false; blob_sidecars.len()];
+    for indices in groups.into_values() {
+        // A lone blob gains nothing from batching.
+        if indices.len() < 2 {
+            continue;
+        }
+
+        let blobs = indices.iter().filter_map(|&i| blob_sidecars.get(i));
+        if verify_kzg_for_blob_list(blobs, kzg).is_ok() {
+            metrics::inc_counter_by(
+                &metrics::BLOBS_SIDECAR_GOSSIP_BATCH_KZG_VERIFIED,
+                indices.len() as u64,
+            );
+            for i in indices {
+                verified_in_batch[i] = true;
+            }
+        }
+    }
+
+    blob_sidecars
+        .into_iter()
+        .zip(verified_in_batch)
+        .map(|(blob, verified_in_batch)| {
+            if verified_in_batch {
+                Ok(KzgVerifiedBlob {
+                    blob,
+                    seen_timestamp,
+                })
+            } else {
+                Err(blob)
+            }
+        })
+        .collect()
+}
+
 impl<T: BeaconChainTypes> GossipVerifiedBlob<T, DoNotObserve> {
     pub fn observe(
         self,
@@ -91,6 +91,7 @@ use std::fmt::Debug;
 use std::fs;
 use std::io::Write;
 use std::sync::Arc;
+use std::time::Duration;
 use store::{Error as DBError, HotStateSummary, KeyValueStore, StoreOp};
 use task_executor::JoinHandle;
 use types::{
@@ -326,6 +327,15 @@ pub enum BlockError {
     /// We were unable to process this block due to an internal error. It's unclear if the block is
     /// valid.
     InternalError(String),
+    /// The block wasn't imported within the allowed time, e.g. because the execution layer is
+    /// unresponsive. The caller stopped waiting for the import, although work already spawned by
+    /// it may still be running.
+    ///
+    /// ## Peer scoring
+    ///
+    /// It's unclear if the block is valid, and the delay is our fault, so the peer shouldn't be
+    /// penalised.
+    ImportTimeout(Duration),
 }
 
 impl From<AvailabilityCheckError> for BlockError {
@@ -1530,8 +1540,11 @@ impl<T: BeaconChainTypes> ExecutionPendingBlock<T> {
                 // Update the summaries in a separate loop to `per_slot_processing`. This protects
                 // the `validator_monitor` lock from being bounced or held for a long time whilst
                 // performing `per_slot_processing`.
+                //
+                // Each summary is passed the epoch the state entered when it was generated, as
+                // for the state advance timer.
                 for (i, summary) in summaries.iter().enumerate() {
-                    let epoch = state_current_epoch - Epoch::from(summaries.len() - i);
+                    let epoch = state_current_epoch - Epoch::from(summaries.len() - i - 1);
                     if let Err(e) =
                         validator_monitor.process_validator_statuses(epoch, summary, &chain.spec)
                     {
@@ -13,10 +13,11 @@ use crate::light_client_server_cache::LightClientServerCache;
 use crate::migrate::{BackgroundMigrator, MigratorConfig};
 use crate::observed_data_sidecars::ObservedDataSidecars;
 use crate::persisted_beacon_chain::PersistedBeaconChain;
+use crate::persisted_caches::{restore_states, take_persisted_caches};
 use crate::shuffling_cache::{BlockShufflingIds, ShufflingCache};
 use crate::validator_monitor::{ValidatorMonitor, ValidatorMonitorConfig};
 use crate::validator_pubkey_cache::ValidatorPubkeyCache;
-use crate::ChainConfig;
+use crate::{BackfillProfile, ChainConfig};
 use crate::{
     BeaconChain, BeaconChainTypes, BeaconForkChoiceStore, BeaconSnapshot, Eth1Chain,
     Eth1ChainBackend, ServerSentEventHandler,
@@ -30,13 +31,15 @@ use operation_pool::{OperationPool, PersistedOperationPool};
 use parking_lot::{Mutex, RwLock};
 use proto_array::{DisallowedReOrgOffsets, ReOrgThreshold};
 use slasher::Slasher;
-use slog::{crit, debug, error, info, o, Logger};
+use slog::{crit, debug, error, info, o, warn, Logger};
 use slot_clock::{SlotClock, TestingSlotClock};
 use state_processing::{per_slot_processing, AllCaches};
 use std::marker::PhantomData;
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use std::time::Duration;
-use store::{Error as StoreError, HotColdDB, ItemStore, KeyValueStoreOp};
+use store::metadata::ANCHOR_UNINITIALIZED;
+use store::{AnchorInfo, Error as StoreError, HotColdDB, ItemStore, KeyValueStoreOp};
 use task_executor::{ShutdownReason, TaskExecutor};
 use types::{
     BeaconBlock, BeaconState, BlobSidecarList, ChainSpec, Checkpoint, Epoch, EthSpec,
@@ -879,7 +882,7 @@ where
         let shuffling_cache_size = self.chain_config.shuffling_cache_size;
 
         // Calculate the weak subjectivity point in which to backfill blocks to.
-        let genesis_backfill_slot = if self.chain_config.genesis_backfill {
+        let genesis_backfill_slot = if self.chain_config.backfill_profile == BackfillProfile::Full {
             Slot::new(0)
         } else {
             let backfill_epoch_range = if cfg!(feature = "test_backfill") {
@@ -890,7 +893,7 @@ where
                     / 2
             };
 
-            match slot_clock.now() {
+            let minimal_backfill_slot = match slot_clock.now() {
                 Some(current_slot) => {
                     let genesis_backfill_epoch = current_slot
                         .epoch(E::slots_per_epoch())
@@ -902,7 +905,41 @@ where
                     // at or prior to genesis and backfill should sync all the way to genesis.
                     Slot::new(0)
                 }
-            }
+            };
+
+            backfill_target_slot::<E>(
+                self.chain_config.backfill_profile,
+                minimal_backfill_slot,
+                &log,
+            )
+        };
+
+        // Record the backfill target in the anchor. Blocks which are already held are never
+        // deleted, so the target is no later than the oldest block.
+        let anchor_info = store.get_anchor_info();
+        let backfill_target_slot =
+            std::cmp::min(genesis_backfill_slot, anchor_info.oldest_block_slot);
+        if anchor_info != ANCHOR_UNINITIALIZED
+            && anchor_info.backfill_target_slot != backfill_target_slot
+        {
+            let new_anchor = AnchorInfo {
+                backfill_target_slot,
+                ..anchor_info.clone()
+            };
+            store
+                .compare_and_set_anchor_info_with_write(anchor_info, new_anchor)
+                .map_err(|e| format!("Unable to record backfill target: {:?}", e))?;
+        }
+
+        let mut op_pool = self.op_pool.ok_or("Cannot build without op pool")?;
+        if let Some(num_threads) = self.chain_config.attestation_packing_threads {
+            op_pool = op_pool.with_attestation_packing_threads(num_threads)?;
+        }
+
+        let custody_subnet_count = if self.import_all_data_columns {
+            self.spec.data_column_sidecar_subnet_count
+        } else {
+            self.spec.custody_requirement
         };
 
         let beacon_chain = BeaconChain {
@@ -914,7 +951,7 @@ where
                 .ok_or("Cannot build without task executor")?,
             store_migrator,
             slot_clock: slot_clock.clone(),
-            op_pool: self.op_pool.ok_or("Cannot build without op pool")?,
+            op_pool,
             // TODO: allow for persisting and loading the pool from disk.
             naive_aggregation_pool: <_>::default(),
             // TODO: allow for persisting and loading the pool from disk.
@@ -939,6 +976,7 @@ where
             observed_blob_sidecars: RwLock::new(ObservedDataSidecars::new(self.spec.clone())),
             observed_slashable: <_>::default(),
             observed_voluntary_exits: <_>::default(),
+            exit_broadcasts: <_>::default(),
             observed_proposer_slashings: <_>::default(),
             observed_attester_slashings: <_>::default(),
             observed_bls_to_execution_changes: <_>::default(),
@@ -993,10 +1031,42 @@ where
                 .map_err(|e| format!("Error initializing DataAvailabilityChecker: {:?}", e))?,
             ),
             kzg: self.kzg.clone(),
+            persisted_on_shutdown: AtomicBool::new(false),
         };
 
         let head = beacon_chain.head_snapshot();
 
+        // Restore the caches persisted on the last shutdown, so that duties can be served without
+        // first reading states from disk. The caches are rebuilt as usual if this fails.
+        let states_to_restore = match take_persisted_caches(&beacon_chain.store) {
+            Ok(Some(persisted_caches)) => {
+                let finalized_epoch = head.beacon_state.finalized_checkpoint().epoch;
+                info!(
+                    log,
+                    "Restoring persisted caches";
+                    "shufflings" => persisted_caches.shufflings.len(),
+                    "proposers" => persisted_caches.proposers.len(),
+                    "attester_cache" => persisted_caches.attester_cache.len(),
+                    "state_summaries" => persisted_caches.state_summaries.len(),
+                );
+                let state_summaries = persisted_caches.restore(
+                    &mut beacon_chain.shuffling_cache.write(),
+                    &mut beacon_chain.beacon_proposer_cache.lock(),
+                    &beacon_chain.attester_cache,
+                    finalized_epoch,
+                );
+                Some((
+                    state_summaries,
+                    finalized_epoch.start_slot(E::slots_per_epoch()),
+                ))
+            }
+            Ok(None) => None,
+            Err(e) => {
+                warn!(log, "Unable to restore persisted caches"; "error" => ?e);
+                None
+            }
+        };
+
         // Prime the attester cache with the head state.
         beacon_chain
             .attester_cache
@@ -1036,6 +1106,26 @@ where
             "head_slot" => format!("{}", head.beacon_block.slot()),
         );
 
+        // Record the custody subnet count, so that the columns of newly custodied subnets are
+        // backfilled for the blocks imported before it increased.
+        if beacon_chain.spec.is_peer_das_scheduled() {
+            match beacon_chain.update_data_column_custody_info(custody_subnet_count) {
+                Ok(custody_info) => {
+                    if let Some(custody_backfill_slot) = custody_info.custody_backfill_slot {
+                        info!(
+                            log,
+                            "Data column custody backfill required";
+                            "custody_subnet_count" => custody_subnet_count,
+                            "custody_backfill_slot" => custody_backfill_slot,
+                        );
+                    }
+                }
+                Err(e) => {
+                    warn!(log, "Unable to update data column custody info"; "error" => ?e);
+                }
+            }
+        }
+
         // Check for states to reconstruct (in the background).
         if beacon_chain.config.reconstruct_historic_states
             && beacon_chain.store.get_oldest_block_slot() == 0
@@ -1057,6 +1147,20 @@ where
             );
         }
 
+        // Reload the states cached on the last shutdown in the background, as reading them from disk
+        // would otherwise delay startup.
+        if let Some((state_summaries, finalized_slot)) = states_to_restore {
+            let store = beacon_chain.store.clone();
+            let log = log.clone();
+            beacon_chain.task_executor.spawn_blocking(
+                move || match restore_states(&store, &state_summaries, finalized_slot) {
+                    Ok(loaded) => debug!(log, "Restored cached states"; "count" => loaded),
+                    Err(e) => warn!(log, "Unable to restore cached states"; "error" => ?e),
+                },
+                "restore_cached_states",
+            );
+        }
+
         // Prune blobs older than the blob data availability boundary in the background.
         if let Some(data_availability_boundary) = beacon_chain.data_availability_boundary() {
             beacon_chain
@@ -1123,6 +1227,35 @@ where
     }
 }
 
+/// Returns the slot at which backfill sync stops for `profile`, where `minimal_backfill_slot` is
+/// the start of the range of blocks that the spec requires us to serve.
+fn backfill_target_slot<E: EthSpec>(
+    profile: BackfillProfile,
+    minimal_backfill_slot: Slot,
+    log: &Logger,
+) -> Slot {
+    match profile {
+        // Never backfill less than the spec requires us to serve, unless an explicit target has
+        // been set.
+        BackfillProfile::Custom(slot) => std::cmp::min(slot, minimal_backfill_slot),
+        BackfillProfile::Target(epoch) => {
+            let target_slot = epoch.start_slot(E::slots_per_epoch());
+            if target_slot > minimal_backfill_slot {
+                warn!(
+                    log,
+                    "Backfill target is later than the spec requires";
+                    "info" => "blocks requested by peers before the target will not be served",
+                    "target_slot" => target_slot,
+                    "minimal_backfill_slot" => minimal_backfill_slot,
+                );
+            }
+            target_slot
+        }
+        BackfillProfile::Minimal => minimal_backfill_slot,
+        BackfillProfile::Full => Slot::new(0),
+    }
+}
+
 fn genesis_block<E: EthSpec>(
     genesis_state: &mut BeaconState<E>,
     spec: &ChainSpec,
@@ -1180,6 +1313,35 @@ mod test {
         builder.build().expect("should build logger")
     }
 
+    #[test]
+    fn backfill_target_slot_clamps_custom_profile() {
+        let log = get_logger();
+        let minimal_backfill_slot = Slot::new(64);
+        let target_slot =
+            |profile| backfill_target_slot::<TestEthSpec>(profile, minimal_backfill_slot, &log);
+
+        assert_eq!(target_slot(BackfillProfile::Minimal), minimal_backfill_slot);
+        assert_eq!(target_slot(BackfillProfile::Full), Slot::new(0));
+        assert_eq!(
+            target_slot(BackfillProfile::Custom(Slot::new(10))),
+            Slot::new(10)
+        );
+        // A custom profile never backfills less than the spec requires.
+        assert_eq!(
+            target_slot(BackfillProfile::Custom(Slot::new(100))),
+            minimal_backfill_slot
+        );
+        // An explicit target is not clamped.
+        assert_eq!(
+            target_slot(BackfillProfile::Target(Epoch::new(4))),
+            Epoch::new(4).start_slot(TestEthSpec::slots_per_epoch())
+        );
+        assert_eq!(
+            target_slot(BackfillProfile::Target(Epoch::new(20))),
+            Epoch::new(20).start_slot(TestEthSpec::slots_per_epoch())
+        );
+    }
+
     #[test]
     fn recent_genesis() {
         let validator_count = 1;
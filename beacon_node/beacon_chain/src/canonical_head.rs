@@ -41,7 +41,10 @@ use crate::{
     validator_monitor::{get_slot_delay_ms, timestamp_now},
     BeaconChain, BeaconChainError as Error, BeaconChainTypes, BeaconSnapshot,
 };
-use eth2::types::{EventKind, SseChainReorg, SseFinalizedCheckpoint, SseHead, SseLateHead};
+use eth2::types::{
+    EventKind, SseChainReorg, SseFinalizedCheckpoint, SseHead, SseLateHead, SseReorgBlock,
+    SseReorgDetail, SseReorgEquivocation,
+};
 use fork_choice::{
     ExecutionStatus, ForkChoiceStore, ForkChoiceView, ForkchoiceUpdateParameters, ProtoBlock,
     ResetPayloadStatuses,
@@ -57,6 +60,10 @@ use store::{iter::StateRootsIterator, KeyValueStoreOp, StoreItem};
 use task_executor::{JoinHandle, ShutdownReason};
 use types::*;
 
+/// The maximum number of blocks from each side of a re-org which are included in a
+/// `reorg_detail` event.
+const MAX_REORG_DETAIL_BLOCKS: usize = 64;
+
 /// Simple wrapper around `RwLock` that uses private visibility to prevent any other modules from
 /// accessing the contained lock without it being explicitly noted in this module.
 pub struct CanonicalHeadRwLock<T>(RwLock<T>);
@@ -177,6 +184,17 @@ impl<E: EthSpec> CachedHead<E> {
             .map(|payload| payload.block_number())
     }
 
+    /// Returns the execution gas limit of the block at the head of the chain.
+    ///
+    /// Returns an error if the chain is prior to Bellatrix.
+    pub fn head_gas_limit(&self) -> Result<u64, BeaconStateError> {
+        self.snapshot
+            .beacon_block
+            .message()
+            .execution_payload()
+            .map(|payload| payload.gas_limit())
+    }
+
     /// Returns the active validator count for the current epoch of the head state.
     ///
     /// Should only return `None` if the caches have not been built on the head state (this should
@@ -688,6 +706,10 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                 // Now the new snapshot has been obtained, take a write-lock on the cached head so
                 // we can update it quickly.
                 let mut cached_head_write_lock = self.canonical_head.cached_head_write_lock();
+                // Clear the early attester cache in case it conflicts with the new head. This is
+                // done whilst holding the write-lock so that attestation production which misses
+                // the early attester cache waits for the new head, rather than using a stale one.
+                self.early_attester_cache.clear();
                 // Enshrine the new head as the canonical cached head.
                 *cached_head_write_lock = new_cached_head;
                 // Take a clone of the cached head for later use. It is cloned whilst
@@ -695,9 +717,6 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                 cached_head_write_lock.clone()
             };
 
-            // Clear the early attester cache in case it conflicts with `self.canonical_head`.
-            self.early_attester_cache.clear();
-
             new_head
         } else {
             let new_cached_head = CachedHead {
@@ -713,6 +732,12 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
             let mut cached_head_write_lock = self.canonical_head.cached_head_write_lock();
 
+            // The early attester cache may hold a block which became the head during import, but
+            // has since been re-orged out without the canonical head changing. Remove it so that
+            // attestations are produced for the canonical head.
+            self.early_attester_cache
+                .retain_head(new_view.head_block_root);
+
             // Enshrine the new head as the canonical cached head. Whilst the head block hasn't
             // changed, the FFG checkpoints must have changed.
             *cached_head_write_lock = new_cached_head;
@@ -852,6 +877,10 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             self.op_pool.prune_attestations(self.epoch()?);
         }
 
+        if is_epoch_transition {
+            self.persist_op_pool_operations()?;
+        }
+
         // Register server-sent-events for a new head.
         if let Some(event_handler) = self
             .event_handler
@@ -898,11 +927,118 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                     execution_optimistic: new_head_is_optimistic,
                 }));
             }
+
+            if let Some(event_handler) = self
+                .event_handler
+                .as_ref()
+                .filter(|handler| handler.has_reorg_detail_subscribers())
+            {
+                match self.reorg_detail(
+                    old_snapshot.beacon_block_root,
+                    new_snapshot.beacon_block_root,
+                    head_slot,
+                    depth,
+                    new_head_is_optimistic,
+                ) {
+                    Ok(Some(reorg_detail)) => {
+                        event_handler.register(EventKind::ReorgDetail(Box::new(reorg_detail)))
+                    }
+                    Ok(None) => debug!(
+                        self.log,
+                        "No common ancestor for re-org detail";
+                        "old_head" => ?old_snapshot.beacon_block_root,
+                        "new_head" => ?new_snapshot.beacon_block_root,
+                    ),
+                    Err(e) => warn!(
+                        self.log,
+                        "Unable to compute re-org detail";
+                        "error" => ?e,
+                    ),
+                }
+            }
         }
 
         Ok(())
     }
 
+    /// Describe the re-org from `old_head_root` to `new_head_root`, including the proposers of
+    /// the blocks on each side and any proposers which produced a block on both sides.
+    ///
+    /// Returns `None` if fork choice does not contain a common ancestor of both heads.
+    fn reorg_detail(
+        &self,
+        old_head_root: Hash256,
+        new_head_root: Hash256,
+        head_slot: Slot,
+        depth: Slot,
+        execution_optimistic: bool,
+    ) -> Result<Option<SseReorgDetail>, Error> {
+        let (branches, new_head_proposer_boosted) = {
+            let fork_choice = self.canonical_head.fork_choice_read_lock();
+            (
+                fork_choice
+                    .proto_array()
+                    .find_branches(&old_head_root, &new_head_root),
+                fork_choice.proposer_boost_root() == new_head_root,
+            )
+        };
+        let Some(branches) = branches else {
+            return Ok(None);
+        };
+
+        let load_blocks = |blocks: &[(Hash256, Slot)]| -> Result<Vec<SseReorgBlock>, Error> {
+            let mut loaded = Vec::with_capacity(blocks.len());
+            for &(block_root, slot) in blocks.iter().take(MAX_REORG_DETAIL_BLOCKS) {
+                let block = self
+                    .store
+                    .get_blinded_block(&block_root)?
+                    .ok_or(Error::MissingBeaconBlock(block_root))?;
+                loaded.push(SseReorgBlock {
+                    slot,
+                    block: block_root,
+                    proposer_index: block.message().proposer_index(),
+                });
+            }
+            Ok(loaded)
+        };
+        let old_blocks = load_blocks(&branches.old_blocks)?;
+        let new_blocks = load_blocks(&branches.new_blocks)?;
+
+        let equivocations = old_blocks
+            .iter()
+            .flat_map(|old_block| {
+                new_blocks
+                    .iter()
+                    .filter(move |new_block| {
+                        new_block.slot == old_block.slot
+                            && new_block.proposer_index == old_block.proposer_index
+                    })
+                    .map(move |new_block| SseReorgEquivocation {
+                        slot: old_block.slot,
+                        proposer_index: old_block.proposer_index,
+                        old_block: old_block.block,
+                        new_block: new_block.block,
+                    })
+            })
+            .collect();
+
+        let (common_ancestor_block, common_ancestor_slot) = branches.common_ancestor;
+        Ok(Some(SseReorgDetail {
+            slot: head_slot,
+            epoch: head_slot.epoch(T::EthSpec::slots_per_epoch()),
+            depth: depth.as_u64(),
+            old_head_block: old_head_root,
+            new_head_block: new_head_root,
+            common_ancestor_block,
+            common_ancestor_slot,
+            old_blocks,
+            new_blocks,
+            equivocations,
+            new_head_proposer_boosted,
+            execution_optimistic,
+        }))
+    }
+
     /// Perform updates to caches and other components after the finalized checkpoint has been
     /// changed.
     ///
@@ -1140,6 +1276,26 @@ fn spawn_execution_layer_updates<T: BeaconChainTypes>(
                     return;
                 }
 
+                // Update the mechanism for preparing for block production on the execution layer.
+                //
+                // This is done before updating the execution head, so that the payload attributes
+                // for the next slot are published to subscribers as soon as the new head and its
+                // proposer shuffling are known, rather than after a round-trip to the EL.
+                //
+                // Performing this call immediately before `update_execution_engine_forkchoice`
+                // might result in two calls to fork choice updated, both *with* payload attributes
+                // if the next slot is within the lookahead.
+                //
+                // This seems OK. It's not a significant waste of EL<>CL bandwidth or resources, as far as I
+                // know.
+                if let Err(e) = chain.prepare_beacon_proposer(current_slot).await {
+                    crit!(
+                        chain.log,
+                        "Failed to prepare proposers after fork choice";
+                        "error" => ?e
+                    );
+                }
+
                 if let Err(e) = chain
                     .update_execution_engine_forkchoice(
                         current_slot,
@@ -1154,22 +1310,6 @@ fn spawn_execution_layer_updates<T: BeaconChainTypes>(
                         "error" => ?e
                     );
                 }
-
-                // Update the mechanism for preparing for block production on the execution layer.
-                //
-                // Performing this call immediately after `update_execution_engine_forkchoice_blocking`
-                // might result in two calls to fork choice updated, one *without* payload attributes and
-                // then a second *with* payload attributes.
-                //
-                // This seems OK. It's not a significant waste of EL<>CL bandwidth or resources, as far as I
-                // know.
-                if let Err(e) = chain.prepare_beacon_proposer(current_slot).await {
-                    crit!(
-                        chain.log,
-                        "Failed to prepare proposers after fork choice";
-                        "error" => ?e
-                    );
-                }
             },
             "update_el_forkchoice",
         )
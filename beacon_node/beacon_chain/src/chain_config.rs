@@ -1,7 +1,9 @@
 pub use proto_array::{DisallowedReOrgOffsets, ReOrgThreshold};
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::str::FromStr;
 use std::time::Duration;
-use types::{Checkpoint, Epoch};
+use types::{Address, Checkpoint, Epoch, Slot};
 
 pub const DEFAULT_RE_ORG_HEAD_THRESHOLD: ReOrgThreshold = ReOrgThreshold(20);
 pub const DEFAULT_RE_ORG_PARENT_THRESHOLD: ReOrgThreshold = ReOrgThreshold(160);
@@ -16,6 +18,17 @@ pub const DEFAULT_PREPARE_PAYLOAD_LOOKAHEAD_FACTOR: u32 = 3;
 /// Fraction of a slot lookahead for fork choice in the state advance timer (500ms on mainnet).
 pub const FORK_CHOICE_LOOKAHEAD_FACTOR: u32 = 24;
 
+/// Default number of payload bodies requested from the execution engine at once when
+/// reconstructing blinded blocks.
+pub const DEFAULT_PAYLOAD_BODIES_BATCH_SIZE: u64 = 32;
+
+/// The maximum number of payload bodies which may be requested by range under the engine API.
+pub const MAX_PAYLOAD_BODIES_BATCH_SIZE: u64 = 1024;
+
+/// Default time allowed for importing an RPC block, which is generous compared to the engine API
+/// timeouts so that only a hung import is cancelled.
+pub const DEFAULT_BLOCK_IMPORT_TIMEOUT_SECS: u64 = 60;
+
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
 pub struct ChainConfig {
     /// Maximum number of slots to skip when importing an attestation.
@@ -43,6 +56,15 @@ pub struct ChainConfig {
     /// By default this list is empty, but it can be useful for reacting to network conditions, e.g.
     /// slow gossip of re-org blocks at slot 1 in the epoch.
     pub re_org_disallowed_offsets: DisallowedReOrgOffsets,
+    /// Refuse to build on a head block whose payload was revealed less than this long before
+    /// block production, building on its parent instead.
+    ///
+    /// If `None`, blocks are built on the head regardless of when its payload was revealed.
+    pub late_payload_reveal_threshold: Option<Duration>,
+    /// Fee recipients of builders which are trusted not to withhold payloads.
+    ///
+    /// Head blocks with payloads from these fee recipients are always built upon.
+    pub late_payload_trusted_fee_recipients: Vec<Address>,
     /// Number of milliseconds to wait for fork choice before proposing a block.
     ///
     /// If set to 0 then block proposal will not wait for fork choice at all.
@@ -62,6 +84,16 @@ pub struct ChainConfig {
     pub always_reset_payload_statuses: bool,
     /// Whether to apply paranoid checks to blocks proposed by this beacon node.
     pub paranoid_block_proposal: bool,
+    /// The number of threads used to aggregate and score attestations during block production.
+    ///
+    /// If `None`, the global rayon thread pool is used.
+    pub attestation_packing_threads: Option<usize>,
+    /// The time into the slot after which block production stops waiting on slow components,
+    /// such as the builder, and produces the best block available.
+    pub block_production_deadline: Option<Duration>,
+    /// The maximum time allowed for importing a block received via RPC, after which the import is
+    /// cancelled and the block may be retried.
+    pub block_import_timeout: Duration,
     /// Optionally set timeout for calls to checkpoint sync endpoint.
     pub checkpoint_sync_url_timeout: u64,
     /// The offset before the start of a proposal slot at which payload attributes should be sent.
@@ -73,9 +105,10 @@ pub struct ChainConfig {
     pub optimistic_finalized_sync: bool,
     /// The size of the shuffling cache,
     pub shuffling_cache_size: usize,
-    /// If using a weak-subjectivity sync, whether we should download blocks all the way back to
-    /// genesis.
-    pub genesis_backfill: bool,
+    /// If using a weak-subjectivity sync, how far back we should download blocks.
+    pub backfill_profile: BackfillProfile,
+    /// A directory of era files to import historic blocks from on startup, before backfill sync.
+    pub era_import_dir: Option<PathBuf>,
     /// Whether to send payload attributes every slot, regardless of connected proposers.
     ///
     /// This is useful for block builders and testing.
@@ -94,6 +127,12 @@ pub struct ChainConfig {
     /// The delay in milliseconds applied by the node between sending each blob or data column batch.
     /// This doesn't apply if the node is the block proposer.
     pub blob_publication_batch_interval: Duration,
+    /// The maximum number of blocks whose payloads are requested from the execution engine in a
+    /// single `engine_getPayloadBodiesByRange` call when reconstructing blinded blocks.
+    pub payload_bodies_batch_size: u64,
+    /// The number of epochs between re-broadcasts of voluntary exits which haven't been included
+    /// on chain. Zero disables re-broadcasting.
+    pub exit_rebroadcast_epochs: u64,
 }
 
 impl Default for ChainConfig {
@@ -108,6 +147,8 @@ impl Default for ChainConfig {
             re_org_max_epochs_since_finalization: DEFAULT_RE_ORG_MAX_EPOCHS_SINCE_FINALIZATION,
             re_org_cutoff_millis: None,
             re_org_disallowed_offsets: DisallowedReOrgOffsets::default(),
+            late_payload_reveal_threshold: None,
+            late_payload_trusted_fee_recipients: vec![],
             fork_choice_before_proposal_timeout_ms: DEFAULT_FORK_CHOICE_BEFORE_PROPOSAL_TIMEOUT,
             // Builder fallback configs that are set in `clap` will override these.
             builder_fallback_skips: 3,
@@ -116,12 +157,16 @@ impl Default for ChainConfig {
             builder_fallback_disable_checks: false,
             always_reset_payload_statuses: false,
             paranoid_block_proposal: false,
+            attestation_packing_threads: None,
+            block_production_deadline: None,
+            block_import_timeout: Duration::from_secs(DEFAULT_BLOCK_IMPORT_TIMEOUT_SECS),
             checkpoint_sync_url_timeout: 60,
             prepare_payload_lookahead: Duration::from_secs(4),
             // This value isn't actually read except in tests.
             optimistic_finalized_sync: true,
             shuffling_cache_size: crate::shuffling_cache::DEFAULT_CACHE_SIZE,
-            genesis_backfill: false,
+            backfill_profile: BackfillProfile::default(),
+            era_import_dir: None,
             always_prepare_payload: false,
             epochs_per_migration: crate::migrate::DEFAULT_EPOCHS_PER_MIGRATION,
             enable_light_client_server: false,
@@ -129,6 +174,8 @@ impl Default for ChainConfig {
             enable_sampling: false,
             blob_publication_batches: 4,
             blob_publication_batch_interval: Duration::from_millis(300),
+            payload_bodies_batch_size: DEFAULT_PAYLOAD_BODIES_BATCH_SIZE,
+            exit_rebroadcast_epochs: crate::exit_broadcasts::DEFAULT_EXIT_REBROADCAST_EPOCHS,
         }
     }
 }
@@ -143,3 +190,42 @@ impl ChainConfig {
             })
     }
 }
+
+/// The depth to which blocks are backfilled after a weak-subjectivity sync.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+pub enum BackfillProfile {
+    /// Only the blocks within `MIN_EPOCHS_FOR_BLOCK_REQUESTS` that the spec requires us to serve.
+    #[default]
+    Minimal,
+    /// All blocks back to genesis.
+    Full,
+    /// All blocks back to the given slot.
+    ///
+    /// Slots later than the start of the `Minimal` range are clamped to it.
+    Custom(Slot),
+    /// All blocks back to the start of the given epoch.
+    ///
+    /// Unlike `Custom`, the target is not clamped to the `Minimal` range, so the node may not hold
+    /// all of the blocks that the spec requires it to serve.
+    Target(Epoch),
+}
+
+impl FromStr for BackfillProfile {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "minimal" => Ok(Self::Minimal),
+            "full" => Ok(Self::Full),
+            slot => slot
+                .parse::<u64>()
+                .map(|slot| Self::Custom(Slot::new(slot)))
+                .map_err(|_| {
+                    format!(
+                        "Invalid backfill profile \"{}\", expected \"minimal\", \"full\" or a slot",
+                        s
+                    )
+                }),
+        }
+    }
+}
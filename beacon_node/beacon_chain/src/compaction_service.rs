@@ -0,0 +1,86 @@
+use crate::{BeaconChain, BeaconChainTypes};
+use slog::{debug, error, info};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use store::compaction::{compact_hot_db, CompactionScheduler};
+use task_executor::TaskExecutor;
+use tokio::time::sleep;
+
+/// The maximum time between checks of whether a scheduled compaction is due.
+const MAX_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Spawns a routine which compacts the hot database every `compaction_interval`, restricted to the
+/// `compaction_window` if one is configured.
+///
+/// The service will not be started if `compaction_interval` is not set.
+pub fn start_compaction_service<T: BeaconChainTypes>(
+    executor: TaskExecutor,
+    chain: Arc<BeaconChain<T>>,
+) {
+    let config = chain.store.get_config();
+    let Some(interval) = config.compaction_interval else {
+        return;
+    };
+    let scheduler = CompactionScheduler::new(interval, config.compaction_window, SystemTime::now());
+
+    info!(
+        chain.log,
+        "Scheduled database compaction enabled";
+        "interval" => ?interval,
+        "window" => ?config.compaction_window,
+    );
+
+    executor.clone().spawn(
+        async move { compaction_service(executor, chain, scheduler, interval).await },
+        "compaction_service",
+    );
+}
+
+/// Loop indefinitely, compacting the hot database whenever the `scheduler` says it is due.
+async fn compaction_service<T: BeaconChainTypes>(
+    executor: TaskExecutor,
+    chain: Arc<BeaconChain<T>>,
+    mut scheduler: CompactionScheduler,
+    interval: Duration,
+) {
+    let check_interval = std::cmp::min(interval, MAX_CHECK_INTERVAL);
+
+    loop {
+        sleep(check_interval).await;
+
+        if !scheduler.is_due(SystemTime::now()) {
+            continue;
+        }
+
+        debug!(chain.log, "Starting scheduled database compaction");
+
+        let store = chain.store.clone();
+        let Some(handle) =
+            executor.spawn_blocking_handle(move || compact_hot_db(&store), "compact_hot_db")
+        else {
+            // The runtime is shutting down.
+            return;
+        };
+
+        match handle.await {
+            Ok(Ok(duration)) => info!(
+                chain.log,
+                "Scheduled database compaction complete";
+                "duration_ms" => duration.as_millis(),
+            ),
+            Ok(Err(e)) => error!(
+                chain.log,
+                "Scheduled database compaction failed";
+                "error" => ?e,
+            ),
+            Err(e) => error!(
+                chain.log,
+                "Scheduled database compaction panicked";
+                "error" => ?e,
+            ),
+        }
+
+        // Wait a full interval before the next attempt, even after a failure.
+        scheduler.record_compaction(SystemTime::now());
+    }
+}
@@ -47,6 +47,21 @@ impl<E: EthSpec> EarlyAttesterCache<E> {
         *self.item.write() = None
     }
 
+    /// Removes the cached item if it is not for `head_block_root`.
+    ///
+    /// This should be called whenever fork choice determines the head, so that `Self::try_attest`
+    /// never attests to a block which has been re-orged out since it was added.
+    pub fn retain_head(&self, head_block_root: Hash256) {
+        let mut item = self.item.write();
+        if item
+            .as_ref()
+            .map_or(false, |item| item.beacon_block_root != head_block_root)
+        {
+            *item = None;
+            metrics::inc_counter(&metrics::BEACON_EARLY_ATTESTER_CACHE_INVALIDATIONS);
+        }
+    }
+
     /// Updates the cache item, so that `Self::try_attest` with return `Some` when given suitable
     /// parameters.
     pub fn add_head_block(
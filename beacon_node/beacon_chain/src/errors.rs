@@ -228,6 +228,18 @@ pub enum BeaconChainError {
     AttestationCommitteeIndexNotSet,
 }
 
+impl BeaconChainError {
+    /// Returns `true` if the error resulted from the execution layer failing to provide the
+    /// payload for a blinded block.
+    pub fn is_payload_reconstruction_failure(&self) -> bool {
+        matches!(
+            self,
+            BeaconChainError::ExecutionLayerErrorPayloadReconstruction(..)
+                | BeaconChainError::BlockStreamerError(BlockStreamerError::BlocksByRangeFailure(_))
+        )
+    }
+}
+
 easy_from_to!(SlotProcessingError, BeaconChainError);
 easy_from_to!(EpochProcessingError, BeaconChainError);
 easy_from_to!(AttestationValidationError, BeaconChainError);
@@ -1,5 +1,5 @@
 use crate::metrics;
-use eth1::{Config as Eth1Config, Eth1Block, Service as HttpService};
+use eth1::{Config as Eth1Config, Eth1Block, Service as HttpService, SszEth1Cache};
 use eth2::lighthouse::Eth1SyncStatusData;
 use ethereum_hashing::hash;
 use int_to_bytes::int_to_bytes32;
@@ -15,7 +15,8 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use store::{DBColumn, Error as StoreError, StoreItem};
 use task_executor::TaskExecutor;
 use types::{
-    BeaconState, BeaconStateError, ChainSpec, Deposit, Eth1Data, EthSpec, Hash256, Slot, Unsigned,
+    BeaconState, BeaconStateError, ChainSpec, Deposit, DepositTreeSnapshot, Eth1Data, EthSpec,
+    Hash256, Slot, Unsigned,
 };
 
 type BlockNumber = u64;
@@ -186,6 +187,30 @@ impl StoreItem for SszEth1 {
     }
 }
 
+impl SszEth1 {
+    /// Create the persisted form of a `CachingEth1Backend` whose deposit cache is initialized from
+    /// `snapshot`.
+    ///
+    /// Deposits after the snapshot are downloaded from the execution node once the backend is
+    /// started.
+    pub fn from_deposit_snapshot(
+        deposit_contract_deploy_block: u64,
+        snapshot: &DepositTreeSnapshot,
+    ) -> Result<Self, String> {
+        let cache = SszEth1Cache::from_deposit_snapshot(deposit_contract_deploy_block, snapshot)?;
+        Ok(Self {
+            use_dummy_backend: false,
+            backend_bytes: cache.as_ssz_bytes(),
+        })
+    }
+
+    /// Decode the caches of the `CachingEth1Backend`.
+    pub fn eth1_cache(&self) -> Result<SszEth1Cache, String> {
+        SszEth1Cache::from_ssz_bytes(&self.backend_bytes)
+            .map_err(|e| format!("Unable to decode eth1 cache: {:?}", e))
+    }
+}
+
 /// Holds an `Eth1ChainBackend` and serves requests from the `BeaconChain`.
 pub struct Eth1Chain<T, E>
 where
@@ -1,4 +1,7 @@
-pub use eth2::types::{EventKind, SseBlock, SseFinalizedCheckpoint, SseHead};
+pub use eth2::types::{
+    EventKind, SseBlock, SseFinalizedCheckpoint, SseHead, SseSyncChainStatus, SseSyncProgress,
+    SseSyncType,
+};
 use slog::{trace, Logger};
 use tokio::sync::broadcast;
 use tokio::sync::broadcast::{error::SendError, Receiver, Sender};
@@ -14,6 +17,7 @@ pub struct ServerSentEventHandler<E: EthSpec> {
     head_tx: Sender<EventKind<E>>,
     exit_tx: Sender<EventKind<E>>,
     chain_reorg_tx: Sender<EventKind<E>>,
+    reorg_detail_tx: Sender<EventKind<E>>,
     contribution_tx: Sender<EventKind<E>>,
     payload_attributes_tx: Sender<EventKind<E>>,
     late_head: Sender<EventKind<E>>,
@@ -24,6 +28,7 @@ pub struct ServerSentEventHandler<E: EthSpec> {
     attester_slashing_tx: Sender<EventKind<E>>,
     bls_to_execution_change_tx: Sender<EventKind<E>>,
     block_gossip_tx: Sender<EventKind<E>>,
+    sync_progress_tx: Sender<EventKind<E>>,
     log: Logger,
 }
 
@@ -43,6 +48,7 @@ impl<E: EthSpec> ServerSentEventHandler<E> {
         let (head_tx, _) = broadcast::channel(capacity);
         let (exit_tx, _) = broadcast::channel(capacity);
         let (chain_reorg_tx, _) = broadcast::channel(capacity);
+        let (reorg_detail_tx, _) = broadcast::channel(capacity);
         let (contribution_tx, _) = broadcast::channel(capacity);
         let (payload_attributes_tx, _) = broadcast::channel(capacity);
         let (late_head, _) = broadcast::channel(capacity);
@@ -53,6 +59,7 @@ impl<E: EthSpec> ServerSentEventHandler<E> {
         let (attester_slashing_tx, _) = broadcast::channel(capacity);
         let (bls_to_execution_change_tx, _) = broadcast::channel(capacity);
         let (block_gossip_tx, _) = broadcast::channel(capacity);
+        let (sync_progress_tx, _) = broadcast::channel(capacity);
 
         Self {
             attestation_tx,
@@ -62,6 +69,7 @@ impl<E: EthSpec> ServerSentEventHandler<E> {
             head_tx,
             exit_tx,
             chain_reorg_tx,
+            reorg_detail_tx,
             contribution_tx,
             payload_attributes_tx,
             late_head,
@@ -72,6 +80,7 @@ impl<E: EthSpec> ServerSentEventHandler<E> {
             attester_slashing_tx,
             bls_to_execution_change_tx,
             block_gossip_tx,
+            sync_progress_tx,
             log,
         }
     }
@@ -114,6 +123,10 @@ impl<E: EthSpec> ServerSentEventHandler<E> {
                 .chain_reorg_tx
                 .send(kind)
                 .map(|count| log_count("chain reorg", count)),
+            EventKind::ReorgDetail(_) => self
+                .reorg_detail_tx
+                .send(kind)
+                .map(|count| log_count("reorg detail", count)),
             EventKind::ContributionAndProof(_) => self
                 .contribution_tx
                 .send(kind)
@@ -154,6 +167,10 @@ impl<E: EthSpec> ServerSentEventHandler<E> {
                 .block_gossip_tx
                 .send(kind)
                 .map(|count| log_count("block gossip", count)),
+            EventKind::SyncProgress(_) => self
+                .sync_progress_tx
+                .send(kind)
+                .map(|count| log_count("sync progress", count)),
         };
         if let Err(SendError(event)) = result {
             trace!(self.log, "No receivers registered to listen for event"; "event" => ?event);
@@ -188,6 +205,10 @@ impl<E: EthSpec> ServerSentEventHandler<E> {
         self.chain_reorg_tx.subscribe()
     }
 
+    pub fn subscribe_reorg_detail(&self) -> Receiver<EventKind<E>> {
+        self.reorg_detail_tx.subscribe()
+    }
+
     pub fn subscribe_contributions(&self) -> Receiver<EventKind<E>> {
         self.contribution_tx.subscribe()
     }
@@ -228,6 +249,10 @@ impl<E: EthSpec> ServerSentEventHandler<E> {
         self.block_gossip_tx.subscribe()
     }
 
+    pub fn subscribe_sync_progress(&self) -> Receiver<EventKind<E>> {
+        self.sync_progress_tx.subscribe()
+    }
+
     pub fn has_attestation_subscribers(&self) -> bool {
         self.attestation_tx.receiver_count() > 0
     }
@@ -256,6 +281,10 @@ impl<E: EthSpec> ServerSentEventHandler<E> {
         self.chain_reorg_tx.receiver_count() > 0
     }
 
+    pub fn has_reorg_detail_subscribers(&self) -> bool {
+        self.reorg_detail_tx.receiver_count() > 0
+    }
+
     pub fn has_contribution_subscribers(&self) -> bool {
         self.contribution_tx.receiver_count() > 0
     }
@@ -287,4 +316,8 @@ impl<E: EthSpec> ServerSentEventHandler<E> {
     pub fn has_block_gossip_subscribers(&self) -> bool {
         self.block_gossip_tx.receiver_count() > 0
     }
+
+    pub fn has_sync_progress_subscribers(&self) -> bool {
+        self.sync_progress_tx.receiver_count() > 0
+    }
 }
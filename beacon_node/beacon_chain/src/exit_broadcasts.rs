@@ -0,0 +1,136 @@
+//! Tracks the broadcasts of the voluntary exits held in the op pool.
+//!
+//! An exit is published once, when it is first received. During periods of non-finality exits can
+//! wait a long time for inclusion, during which the nodes holding them may restart or drop them,
+//! so exits which are still pending are re-broadcast every `exit_rebroadcast_epochs` until they
+//! are included on chain.
+use std::collections::{HashMap, HashSet};
+use types::Epoch;
+
+/// The default number of epochs between re-broadcasts of a pending voluntary exit.
+pub const DEFAULT_EXIT_REBROADCAST_EPOCHS: u64 = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExitBroadcast {
+    /// The epoch in which the exit was accepted into the op pool.
+    pub first_seen_epoch: Epoch,
+    /// The epoch of the latest broadcast, including the initial broadcast.
+    pub last_broadcast_epoch: Epoch,
+    /// The number of times the exit has been re-broadcast.
+    pub rebroadcasts: u64,
+}
+
+impl ExitBroadcast {
+    fn new(epoch: Epoch) -> Self {
+        Self {
+            first_seen_epoch: epoch,
+            last_broadcast_epoch: epoch,
+            rebroadcasts: 0,
+        }
+    }
+
+    /// The epoch at which the exit will next be re-broadcast if it remains pending.
+    pub fn next_rebroadcast_epoch(&self, interval_epochs: u64) -> Epoch {
+        self.last_broadcast_epoch + interval_epochs
+    }
+}
+
+/// The broadcasts of voluntary exits, keyed by validator index.
+#[derive(Debug, Default)]
+pub struct ExitBroadcasts {
+    exits: HashMap<u64, ExitBroadcast>,
+}
+
+impl ExitBroadcasts {
+    /// Record that the exit of `validator_index` was accepted and broadcast in `epoch`.
+    pub fn observe(&mut self, validator_index: u64, epoch: Epoch) {
+        self.exits
+            .entry(validator_index)
+            .or_insert_with(|| ExitBroadcast::new(epoch));
+    }
+
+    pub fn get(&self, validator_index: u64) -> Option<&ExitBroadcast> {
+        self.exits.get(&validator_index)
+    }
+
+    /// Returns the validators in `pending` whose exits are due to be re-broadcast in
+    /// `current_epoch`, and records the re-broadcast.
+    ///
+    /// Exits which haven't been observed, e.g. because they were restored from disk, are treated
+    /// as broadcast in `current_epoch`.
+    pub fn take_due(
+        &mut self,
+        pending: impl IntoIterator<Item = u64>,
+        current_epoch: Epoch,
+        interval_epochs: u64,
+    ) -> HashSet<u64> {
+        pending
+            .into_iter()
+            .filter(|validator_index| {
+                let broadcast = self
+                    .exits
+                    .entry(*validator_index)
+                    .or_insert_with(|| ExitBroadcast::new(current_epoch));
+                if broadcast.next_rebroadcast_epoch(interval_epochs) <= current_epoch {
+                    broadcast.last_broadcast_epoch = current_epoch;
+                    broadcast.rebroadcasts += 1;
+                    true
+                } else {
+                    false
+                }
+            })
+            .collect()
+    }
+
+    /// Forget the exits of validators for which `f` returns `false`, e.g. because their exits have
+    /// been pruned from the op pool.
+    pub fn retain(&mut self, mut f: impl FnMut(u64) -> bool) {
+        self.exits.retain(|validator_index, _| f(*validator_index));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rebroadcast_every_interval() {
+        let mut broadcasts = ExitBroadcasts::default();
+        broadcasts.observe(1, Epoch::new(10));
+        // Observing again doesn't reset the broadcast.
+        broadcasts.observe(1, Epoch::new(11));
+
+        for epoch in 10..14 {
+            assert!(broadcasts.take_due([1], Epoch::new(epoch), 4).is_empty());
+        }
+        assert_eq!(
+            broadcasts.take_due([1], Epoch::new(14), 4),
+            HashSet::from([1])
+        );
+        assert!(broadcasts.take_due([1], Epoch::new(15), 4).is_empty());
+        assert_eq!(
+            broadcasts.take_due([1], Epoch::new(18), 4),
+            HashSet::from([1])
+        );
+
+        let broadcast = broadcasts.get(1).unwrap();
+        assert_eq!(broadcast.first_seen_epoch, Epoch::new(10));
+        assert_eq!(broadcast.last_broadcast_epoch, Epoch::new(18));
+        assert_eq!(broadcast.rebroadcasts, 2);
+        assert_eq!(broadcast.next_rebroadcast_epoch(4), Epoch::new(22));
+    }
+
+    #[test]
+    fn unobserved_exits_are_not_due() {
+        let mut broadcasts = ExitBroadcasts::default();
+        assert!(broadcasts.take_due([2], Epoch::new(5), 1).is_empty());
+        assert_eq!(broadcasts.get(2).unwrap().first_seen_epoch, Epoch::new(5));
+        assert_eq!(
+            broadcasts.take_due([2], Epoch::new(6), 1),
+            HashSet::from([2])
+        );
+
+        broadcasts.retain(|validator_index| validator_index != 2);
+        assert!(broadcasts.get(2).is_none());
+    }
+}
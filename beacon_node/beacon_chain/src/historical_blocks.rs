@@ -8,6 +8,7 @@ use state_processing::{
 };
 use std::borrow::Cow;
 use std::iter;
+use std::path::Path;
 use std::time::Duration;
 use store::metadata::DataColumnInfo;
 use store::{
@@ -59,7 +60,8 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
     /// `SignatureSetError` or `InvalidSignature` will be returned.
     ///
     /// To align with sync we allow some excess blocks with slots greater than or equal to
-    /// `oldest_block_slot` to be provided. They will be ignored without being checked.
+    /// `oldest_block_slot` to be provided. They will be ignored without being checked. Likewise,
+    /// blocks older than the first block at or before the backfill target are ignored.
     ///
     /// This function should not be called concurrently with any other function that mutates
     /// the anchor info (including this function itself). If a concurrent mutation occurs that
@@ -74,6 +76,11 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         let blob_info = self.store.get_blob_info();
         let data_column_info = self.store.get_data_column_info();
 
+        // Blocks older than the backfill target are not imported once it has been reached.
+        if anchor_info.block_backfill_complete(self.genesis_backfill_slot) {
+            return Ok(0);
+        }
+
         // Take all blocks with slots less than the oldest block slot.
         let num_relevant = blocks.partition_point(|available_block| {
             available_block.block().slot() < anchor_info.oldest_block_slot
@@ -177,9 +184,15 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                 expected_block_root = Hash256::zero();
                 break;
             }
+
+            // Stop precisely at the backfill target, ignoring any older blocks in the batch.
+            if prev_block_slot <= self.genesis_backfill_slot {
+                break;
+            }
         }
         // these were pushed in reverse order so we reverse again
         signed_blocks.reverse();
+        let num_imported = signed_blocks.len();
 
         // Verify signatures in one batch, holding the pubkey cache lock for the shortest duration
         // possible. For each block fetch the parent root from its successor. Slicing from index 1
@@ -283,6 +296,27 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             self.store_migrator.process_reconstruction();
         }
 
-        Ok(num_relevant)
+        Ok(num_imported)
+    }
+    /// Import historic blocks from the era files in `dir`, as an alternative to backfill sync.
+    ///
+    /// Eras are verified against the historical roots of the split state rather than by checking
+    /// proposer signatures. Like `import_historical_block_batch`, this must not be called
+    /// concurrently with other mutations of the anchor info, so it should run before backfill sync
+    /// starts.
+    ///
+    /// Return the number of blocks imported.
+    pub fn import_era_files(&self, dir: &Path) -> Result<usize, HistoricalBlockError> {
+        let num_imported = store::era::import_era_dir(&self.store, dir, &self.log)?;
+
+        // As for backfill, begin reconstruction once all blocks back to genesis are known.
+        if num_imported > 0
+            && self.store.get_anchor_info().oldest_block_slot == 0
+            && self.config.reconstruct_historic_states
+        {
+            self.store_migrator.process_reconstruction();
+        }
+
+        Ok(num_imported)
     }
 }
@@ -0,0 +1,168 @@
+use crate::data_column_verification::verify_kzg_for_data_column_list;
+use crate::{BeaconChain, BeaconChainError, BeaconChainTypes, WhenSlotSkipped};
+use kzg::Error as KzgError;
+use slog::debug;
+use store::metadata::DataColumnCustodyInfo;
+use store::Error as StoreError;
+use strum::IntoStaticStr;
+use types::data_column_sidecar::{ColumnIndex, DataColumnSidecarList};
+use types::{Epoch, EthSpec, Hash256, Slot};
+
+#[derive(Debug, IntoStaticStr)]
+pub enum HistoricalDataColumnError {
+    /// The block is not known to the store, caller should not retry.
+    UnknownBlock(Hash256),
+    /// A column belongs to a different block, caller should retry with different columns.
+    MismatchedBlockRoot {
+        block_root: Hash256,
+        expected_block_root: Hash256,
+    },
+    /// A column's commitments differ from those of the block, caller should retry with different
+    /// columns.
+    MismatchedKzgCommitments { index: ColumnIndex },
+    /// A column index is out of bounds, caller should retry with different columns.
+    InvalidColumnIndex(ColumnIndex),
+    /// Bad KZG proof, caller should retry with different columns.
+    InvalidKzgProof(KzgError),
+    /// Internal store error
+    StoreError(StoreError),
+}
+
+impl From<StoreError> for HistoricalDataColumnError {
+    fn from(e: StoreError) -> Self {
+        Self::StoreError(e)
+    }
+}
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Record the number of data column subnets custodied by the node on startup.
+    ///
+    /// If the custody subnet count has increased since the node was last started, the columns of
+    /// the newly custodied subnets are missing for the blocks imported so far. The returned
+    /// `custody_backfill_slot` is the slot below which custody backfill sync should fetch them.
+    pub fn update_data_column_custody_info(
+        &self,
+        custody_subnet_count: u64,
+    ) -> Result<DataColumnCustodyInfo, BeaconChainError> {
+        let current_slot = self.slot()?;
+        let eip7594_fork_slot = self
+            .spec
+            .eip7594_fork_epoch
+            .map(|fork_epoch| fork_epoch.start_slot(T::EthSpec::slots_per_epoch()));
+
+        let custody_info = match self.store.load_data_column_custody_info()? {
+            Some(custody_info) => custody_info.with_custody_subnet_count(
+                custody_subnet_count,
+                current_slot,
+                eip7594_fork_slot,
+            ),
+            // The columns stored prior to the custody info being recorded are unknown, assume that
+            // they match the current custody.
+            None => DataColumnCustodyInfo {
+                custody_subnet_count,
+                custody_backfill_slot: None,
+            },
+        };
+        self.store.store_data_column_custody_info(&custody_info)?;
+
+        Ok(custody_info)
+    }
+
+    /// Returns the canonical blocks in `epoch` with blobs which are missing any of the
+    /// `custody_columns`, along with the indices of the missing columns.
+    pub fn blocks_missing_custody_columns(
+        &self,
+        epoch: Epoch,
+        custody_columns: &[ColumnIndex],
+    ) -> Result<Vec<(Hash256, Slot, Vec<ColumnIndex>)>, BeaconChainError> {
+        let mut blocks = vec![];
+        for slot in epoch.slot_iter(T::EthSpec::slots_per_epoch()) {
+            let Some(block_root) = self.block_root_at_slot(slot, WhenSlotSkipped::None)? else {
+                continue;
+            };
+            let Some(block) = self.store.get_blinded_block(&block_root)? else {
+                continue;
+            };
+            let has_blobs = block
+                .message()
+                .body()
+                .blob_kzg_commitments()
+                .map_or(false, |commitments| !commitments.is_empty());
+            if !has_blobs {
+                continue;
+            }
+
+            let stored_columns = self.store.get_data_column_keys(block_root)?;
+            let missing_columns = custody_columns
+                .iter()
+                .filter(|index| !stored_columns.contains(index))
+                .copied()
+                .collect::<Vec<_>>();
+            if !missing_columns.is_empty() {
+                blocks.push((block_root, slot, missing_columns));
+            }
+        }
+        Ok(blocks)
+    }
+
+    /// Store data columns fetched by custody backfill for a block which has already been imported.
+    ///
+    /// Each column must belong to the block and commit to the block's blobs, and the KZG proofs of
+    /// all columns are verified. If any column is invalid then none are stored.
+    ///
+    /// Return the number of columns stored.
+    pub fn import_historical_data_columns(
+        &self,
+        block_root: Hash256,
+        data_columns: DataColumnSidecarList<T::EthSpec>,
+    ) -> Result<usize, HistoricalDataColumnError> {
+        let block = self
+            .store
+            .get_blinded_block(&block_root)?
+            .ok_or(HistoricalDataColumnError::UnknownBlock(block_root))?;
+        let commitments = block
+            .message()
+            .body()
+            .blob_kzg_commitments()
+            .map_err(|_| HistoricalDataColumnError::UnknownBlock(block_root))?;
+
+        for data_column in &data_columns {
+            if data_column.index >= self.spec.number_of_columns as u64 {
+                return Err(HistoricalDataColumnError::InvalidColumnIndex(
+                    data_column.index,
+                ));
+            }
+            let column_block_root = data_column.block_root();
+            if column_block_root != block_root {
+                return Err(HistoricalDataColumnError::MismatchedBlockRoot {
+                    block_root: column_block_root,
+                    expected_block_root: block_root,
+                });
+            }
+            if &data_column.kzg_commitments != commitments {
+                return Err(HistoricalDataColumnError::MismatchedKzgCommitments {
+                    index: data_column.index,
+                });
+            }
+        }
+
+        verify_kzg_for_data_column_list(data_columns.iter(), &self.kzg)
+            .map_err(HistoricalDataColumnError::InvalidKzgProof)?;
+
+        let num_columns = data_columns.len();
+        let mut ops = vec![];
+        self.store
+            .data_columns_as_kv_store_ops(&block_root, data_columns, &mut ops);
+        self.store.blobs_db.do_atomically(ops)?;
+
+        debug!(
+            self.log,
+            "Imported historical data columns";
+            "block_root" => ?block_root,
+            "slot" => block.slot(),
+            "count" => num_columns,
+        );
+
+        Ok(num_columns)
+    }
+}
@@ -18,6 +18,7 @@ pub mod builder;
 pub mod canonical_head;
 pub mod capella_readiness;
 pub mod chain_config;
+pub mod compaction_service;
 pub mod data_availability_checker;
 pub mod data_column_verification;
 pub mod deneb_readiness;
@@ -28,12 +29,14 @@ pub mod eth1_chain;
 mod eth1_finalization_cache;
 pub mod events;
 pub mod execution_payload;
+pub mod exit_broadcasts;
 pub mod fetch_blobs;
 pub mod fork_choice_signal;
 pub mod fork_revert;
 pub mod graffiti_calculator;
 mod head_tracker;
 pub mod historical_blocks;
+pub mod historical_data_columns;
 pub mod kzg_utils;
 pub mod light_client_finality_update_verification;
 pub mod light_client_optimistic_update_verification;
@@ -49,6 +52,7 @@ pub mod observed_operations;
 mod observed_slashable;
 pub mod otb_verification_service;
 mod persisted_beacon_chain;
+pub mod persisted_caches;
 mod persisted_fork_choice;
 mod pre_finalization_cache;
 pub mod proposer_prep_service;
@@ -65,14 +69,15 @@ pub use self::beacon_chain::{
     AttestationProcessingOutcome, AvailabilityProcessingStatus, BeaconBlockResponse,
     BeaconBlockResponseWrapper, BeaconChain, BeaconChainTypes, BeaconStore, BlockProcessStatus,
     ChainSegmentResult, ForkChoiceError, LightClientProducerEvent, OverrideForkchoiceUpdate,
-    ProduceBlockVerification, StateSkipConfig, WhenSlotSkipped,
+    ProduceBlockVerification, StateSkipConfig, WhenSlotSkipped, ETH1_CACHE_DB_KEY,
     INVALID_FINALIZED_MERGE_TRANSITION_BLOCK_SHUTDOWN_REASON,
     INVALID_JUSTIFIED_PAYLOAD_SHUTDOWN_REASON,
 };
 pub use self::beacon_snapshot::BeaconSnapshot;
-pub use self::chain_config::ChainConfig;
+pub use self::chain_config::{BackfillProfile, ChainConfig};
 pub use self::errors::{BeaconChainError, BlockProductionError};
 pub use self::historical_blocks::HistoricalBlockError;
+pub use self::historical_data_columns::HistoricalDataColumnError;
 pub use attestation_verification::Error as AttestationError;
 pub use beacon_fork_choice_store::{BeaconForkChoiceStore, Error as ForkChoiceStoreError};
 pub use block_verification::{
@@ -8,12 +8,14 @@ use std::num::NonZeroUsize;
 use std::sync::Arc;
 use store::DBColumn;
 use store::KeyValueStore;
+use tokio::sync::Semaphore;
 use tree_hash::TreeHash;
 use types::non_zero_usize::new_non_zero_usize;
 use types::{
-    BeaconBlockRef, BeaconState, ChainSpec, Checkpoint, EthSpec, ForkName, Hash256,
-    LightClientBootstrap, LightClientFinalityUpdate, LightClientOptimisticUpdate,
-    LightClientUpdate, MerkleProof, Slot, SyncAggregate, SyncCommittee,
+    BeaconBlockRef, BeaconState, ChainSpec, Checkpoint, EncodedLightClientUpdate, EthSpec,
+    ForkName, Hash256, LightClientBootstrap, LightClientFinalityUpdate,
+    LightClientOptimisticUpdate, LightClientUpdate, LightClientUpdateRank, MerkleProof,
+    SignedBlindedBeaconBlock, Slot, SyncAggregate, SyncCommittee,
 };
 
 /// A prev block cache miss requires to re-generate the state of the post-parent block. Items in the
@@ -21,6 +23,51 @@ use types::{
 /// represents unlikely re-orgs, while keeping the cache very small.
 const PREV_BLOCK_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(32);
 
+/// The encoded best update of each of the most recently requested sync committee periods is kept
+/// in memory to serve `LightClientUpdatesByRange` requests. Updates are around 25 KiB each, and
+/// 128 is the maximum number of updates in a single request.
+const ENCODED_UPDATE_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(128);
+
+/// Computing a bootstrap for a block without a persisted sync committee branch requires loading
+/// its (possibly historical) state. Bound the number of such computations running at once.
+const MAX_CONCURRENT_BOOTSTRAP_COMPUTATIONS: usize = 2;
+
+/// The SSZ encodings of persisted best updates, keyed by sync committee period.
+struct EncodedUpdateCache {
+    updates: lru::LruCache<u64, Arc<EncodedLightClientUpdate>>,
+    /// Incremented whenever a persisted update is replaced, so that an encoding read from the
+    /// database before the replacement is not cached after it.
+    version: u64,
+}
+
+impl EncodedUpdateCache {
+    fn new() -> Self {
+        Self {
+            updates: lru::LruCache::new(ENCODED_UPDATE_CACHE_SIZE),
+            version: 0,
+        }
+    }
+
+    /// Cache `update`, read from the database whilst the cache was at `version`, unless a
+    /// persisted update has been replaced since.
+    fn put_if_current(
+        &mut self,
+        sync_committee_period: u64,
+        update: Arc<EncodedLightClientUpdate>,
+        version: u64,
+    ) {
+        if self.version == version {
+            self.updates.put(sync_committee_period, update);
+        }
+    }
+
+    /// Remove the encoding for `sync_committee_period`, after its persisted update is replaced.
+    fn invalidate(&mut self, sync_committee_period: u64) {
+        self.updates.pop(&sync_committee_period);
+        self.version += 1;
+    }
+}
+
 /// This cache computes light client messages ahead of time, required to satisfy p2p and API
 /// requests. These messages include proofs on historical states, so on-demand computation is
 /// expensive.
@@ -34,12 +81,17 @@ pub struct LightClientServerCache<T: BeaconChainTypes> {
     latest_finality_update: RwLock<Option<LightClientFinalityUpdate<T::EthSpec>>>,
     /// Tracks a single global latest optimistic update out of all imported blocks.
     latest_optimistic_update: RwLock<Option<LightClientOptimisticUpdate<T::EthSpec>>>,
-    /// Caches the most recent light client update
-    latest_light_client_update: RwLock<Option<LightClientUpdate<T::EthSpec>>>,
+    /// Caches the rank of the best light client update of the most recent sync committee period,
+    /// keyed by period.
+    best_light_client_update_rank: RwLock<Option<(u64, LightClientUpdateRank)>>,
     /// Caches the current sync committee,
     latest_written_current_sync_committee: RwLock<Option<Arc<SyncCommittee<T::EthSpec>>>>,
     /// Caches state proofs by block root
     prev_block_cache: Mutex<lru::LruCache<Hash256, LightClientCachedData<T::EthSpec>>>,
+    /// Caches the SSZ encoding of the persisted best update, keyed by sync committee period.
+    encoded_update_cache: Mutex<EncodedUpdateCache>,
+    /// Limits the number of bootstraps computed from historical states at once.
+    bootstrap_computation_semaphore: Semaphore,
 }
 
 impl<T: BeaconChainTypes> LightClientServerCache<T> {
@@ -47,9 +99,11 @@ impl<T: BeaconChainTypes> LightClientServerCache<T> {
         Self {
             latest_finality_update: None.into(),
             latest_optimistic_update: None.into(),
-            latest_light_client_update: None.into(),
+            best_light_client_update_rank: None.into(),
             latest_written_current_sync_committee: None.into(),
             prev_block_cache: lru::LruCache::new(PREV_BLOCK_CACHE_SIZE).into(),
+            encoded_update_cache: EncodedUpdateCache::new().into(),
+            bootstrap_computation_semaphore: Semaphore::new(MAX_CONCURRENT_BOOTSTRAP_COMPUTATIONS),
         }
     }
 
@@ -126,10 +180,6 @@ impl<T: BeaconChainTypes> LightClientServerCache<T> {
 
         let maybe_finalized_block = store.get_blinded_block(&cached_parts.finalized_block_root)?;
 
-        let sync_period = block_slot
-            .epoch(T::EthSpec::slots_per_epoch())
-            .sync_committee_period(chain_spec)?;
-
         // Spec: Full nodes SHOULD provide the LightClientOptimisticUpdate with the highest
         // attested_header.beacon.slot (if multiple, highest signature_slot) as selected by fork choice
         let is_latest_optimistic = match &self.latest_optimistic_update.read().clone() {
@@ -189,21 +239,25 @@ impl<T: BeaconChainTypes> LightClientServerCache<T> {
         )?;
 
         // Spec: Full nodes SHOULD provide the best derivable LightClientUpdate (according to is_better_update)
-        // for each sync committee period
-        let prev_light_client_update =
-            self.get_light_client_update(&store, sync_period, chain_spec)?;
-
-        let should_persist_light_client_update =
-            if let Some(prev_light_client_update) = prev_light_client_update {
-                prev_light_client_update
-                    .is_better_light_client_update(&new_light_client_update, chain_spec)?
-            } else {
-                true
-            };
+        // for each sync committee period, where an update belongs to the period of its attested
+        // header.
+        let update_period = attested_slot
+            .epoch(T::EthSpec::slots_per_epoch())
+            .sync_committee_period(chain_spec)?;
+        let new_rank = new_light_client_update.rank(chain_spec)?;
+        let prev_rank =
+            self.get_best_light_client_update_rank(&store, update_period, chain_spec)?;
+
+        let should_persist_light_client_update = match prev_rank {
+            Some(prev_rank) => prev_rank.is_better(&new_rank)?,
+            None => true,
+        };
 
         if should_persist_light_client_update {
-            store.store_light_client_update(sync_period, &new_light_client_update)?;
-            *self.latest_light_client_update.write() = Some(new_light_client_update);
+            store.store_light_client_update(update_period, &new_light_client_update, &new_rank)?;
+            *self.best_light_client_update_rank.write() = Some((update_period, new_rank));
+            self.encoded_update_cache.lock().invalidate(update_period);
+            metrics::inc_counter(&metrics::LIGHT_CLIENT_SERVER_CACHE_BEST_UPDATE_REPLACEMENTS);
         }
 
         metrics::inc_counter(&metrics::LIGHT_CLIENT_SERVER_CACHE_PROCESSING_SUCCESSES);
@@ -237,33 +291,33 @@ impl<T: BeaconChainTypes> LightClientServerCache<T> {
         Ok(())
     }
 
-    /// Used to fetch the most recently persisted light client update for the given `sync_committee_period`.
-    /// It first checks the `latest_light_client_update` cache before querying the db.
+    /// Returns the rank of the persisted light client update for the given
+    /// `sync_committee_period`. It first checks the `best_light_client_update_rank` cache before
+    /// querying the db.
     ///
-    /// Note: Should not be used outside the light client server, as it also caches the fetched
-    /// light client update.
-    fn get_light_client_update(
+    /// Updates persisted without a rank are ranked on the fly.
+    fn get_best_light_client_update_rank(
         &self,
         store: &BeaconStore<T>,
         sync_committee_period: u64,
         chain_spec: &ChainSpec,
-    ) -> Result<Option<LightClientUpdate<T::EthSpec>>, BeaconChainError> {
-        if let Some(latest_light_client_update) = self.latest_light_client_update.read().clone() {
-            let latest_lc_update_sync_committee_period = latest_light_client_update
-                .signature_slot()
-                .epoch(T::EthSpec::slots_per_epoch())
-                .sync_committee_period(chain_spec)?;
-            if latest_lc_update_sync_committee_period == sync_committee_period {
-                return Ok(Some(latest_light_client_update));
+    ) -> Result<Option<LightClientUpdateRank>, BeaconChainError> {
+        if let Some((period, rank)) = *self.best_light_client_update_rank.read() {
+            if period == sync_committee_period {
+                return Ok(Some(rank));
             }
         }
 
-        if let Some(light_client_update) = store.get_light_client_update(sync_committee_period)? {
-            *self.latest_light_client_update.write() = Some(light_client_update.clone());
-            return Ok(Some(light_client_update));
-        }
+        let rank = match store.get_light_client_update_rank(sync_committee_period)? {
+            Some(rank) => rank,
+            None => match store.get_light_client_update(sync_committee_period)? {
+                Some(light_client_update) => light_client_update.rank(chain_spec)?,
+                None => return Ok(None),
+            },
+        };
+        *self.best_light_client_update_rank.write() = Some((sync_committee_period, rank));
 
-        Ok(None)
+        Ok(Some(rank))
     }
 
     pub fn get_light_client_updates(
@@ -301,6 +355,56 @@ impl<T: BeaconChainTypes> LightClientServerCache<T> {
         Ok(light_client_updates)
     }
 
+    /// Returns the SSZ encodings of the persisted updates for the periods in
+    /// `start_period..start_period + count`, so that they can be served without being decoded and
+    /// re-encoded. Periods without an update are skipped.
+    pub fn get_encoded_light_client_updates(
+        &self,
+        store: &BeaconStore<T>,
+        start_period: u64,
+        count: u64,
+        chain_spec: &ChainSpec,
+    ) -> Result<Vec<Arc<EncodedLightClientUpdate>>, BeaconChainError> {
+        let mut encoded_updates = vec![];
+        for sync_committee_period in start_period..start_period.saturating_add(count) {
+            let version = {
+                let mut cache = self.encoded_update_cache.lock();
+                if let Some(encoded_update) = cache.updates.get(&sync_committee_period) {
+                    encoded_updates.push(encoded_update.clone());
+                    continue;
+                }
+                cache.version
+            };
+            metrics::inc_counter(&metrics::LIGHT_CLIENT_SERVER_CACHE_ENCODED_UPDATE_CACHE_MISS);
+
+            let Some(bytes) = store.hot_db.get_bytes(
+                DBColumn::LightClientUpdate.into(),
+                &sync_committee_period.to_le_bytes(),
+            )?
+            else {
+                continue;
+            };
+
+            let epoch = sync_committee_period
+                .safe_mul(chain_spec.epochs_per_sync_committee_period.into())?;
+            let fork_name = chain_spec.fork_name_at_epoch(epoch.into());
+            let encoded_update = Arc::new(
+                EncodedLightClientUpdate::from_persisted(bytes, fork_name)
+                    .map_err(store::errors::Error::SszDecodeError)?,
+            );
+
+            // The update may have been replaced since it was read, in which case the stale encoding
+            // must not be cached.
+            self.encoded_update_cache.lock().put_if_current(
+                sync_committee_period,
+                encoded_update.clone(),
+                version,
+            );
+            encoded_updates.push(encoded_update);
+        }
+        Ok(encoded_updates)
+    }
+
     /// Retrieves prev block cached data from cache. If not present re-computes by retrieving the
     /// parent state, and inserts an entry to the cache.
     ///
@@ -344,9 +448,9 @@ impl<T: BeaconChainTypes> LightClientServerCache<T> {
     /// Fetches a light client bootstrap for a given finalized checkpoint `block_root`. We eagerly persist
     /// `sync_committee_branch and `sync_committee` to allow for a more efficient bootstrap construction.
     ///
-    /// Note: It should be the case that a `sync_committee_branch` and `sync_committee` exist in the db
-    /// for a finalized checkpoint block root. However, we currently have no backfill mechanism for these values.
-    /// Therefore, `sync_committee_branch` and `sync_committee` are only persisted while a node is synced.
+    /// Note: `sync_committee_branch` and `sync_committee` are only persisted while a node is synced,
+    /// so they are missing for older checkpoints. In that case the bootstrap is computed from the
+    /// block's state and the values are persisted for subsequent requests.
     #[allow(clippy::type_complexity)]
     pub fn get_light_client_bootstrap(
         &self,
@@ -370,24 +474,18 @@ impl<T: BeaconChainTypes> LightClientServerCache<T> {
             .epoch(T::EthSpec::slots_per_epoch())
             .sync_committee_period(chain_spec)?;
 
-        let Some(current_sync_committee_branch) = store.get_sync_committee_branch(block_root)?
-        else {
-            return Err(BeaconChainError::LightClientBootstrapError(format!(
-                "Sync committee branch for block root {:?} not found",
-                block_root
-            )));
-        };
-
         if sync_committee_period > finalized_period {
             return Err(BeaconChainError::LightClientBootstrapError(
                 format!("The blocks sync committee period {sync_committee_period} is greater than the current finalized period {finalized_period}"),
             ));
         }
 
-        let Some(current_sync_committee) = store.get_sync_committee(sync_committee_period)? else {
-            return Err(BeaconChainError::LightClientBootstrapError(format!(
-                "Sync committee for period {sync_committee_period} not found"
-            )));
+        let (current_sync_committee_branch, current_sync_committee) = match (
+            store.get_sync_committee_branch(block_root)?,
+            store.get_sync_committee(sync_committee_period)?,
+        ) {
+            (Some(branch), Some(sync_committee)) => (branch, sync_committee),
+            _ => self.compute_bootstrap_data(store, block_root, &block, sync_committee_period)?,
         };
 
         let light_client_bootstrap = LightClientBootstrap::new(
@@ -399,6 +497,42 @@ impl<T: BeaconChainTypes> LightClientServerCache<T> {
 
         Ok(Some((light_client_bootstrap, fork_name)))
     }
+
+    /// Computes the current sync committee and its branch from the state of `block`, persisting
+    /// both so that later bootstraps for the same block can be served from the database.
+    fn compute_bootstrap_data(
+        &self,
+        store: &BeaconStore<T>,
+        block_root: &Hash256,
+        block: &SignedBlindedBeaconBlock<T::EthSpec>,
+        sync_committee_period: u64,
+    ) -> Result<(MerkleProof, SyncCommittee<T::EthSpec>), BeaconChainError> {
+        let Ok(_permit) = self.bootstrap_computation_semaphore.try_acquire() else {
+            return Err(BeaconChainError::LightClientBootstrapError(
+                "Too many concurrent bootstrap computations".to_string(),
+            ));
+        };
+        metrics::inc_counter(&metrics::LIGHT_CLIENT_SERVER_CACHE_BOOTSTRAP_COMPUTATIONS);
+        let _timer =
+            metrics::start_timer(&metrics::LIGHT_CLIENT_SERVER_CACHE_BOOTSTRAP_COMPUTATION_TIMES);
+
+        let state_root = block.state_root();
+        let mut state = store
+            .get_state(&state_root, Some(block.slot()))?
+            .ok_or_else(|| {
+                BeaconChainError::LightClientBootstrapError(format!(
+                    "State {state_root:?} for block root {block_root:?} not available"
+                ))
+            })?;
+
+        let current_sync_committee_branch = state.compute_current_sync_committee_proof()?;
+        let current_sync_committee = state.current_sync_committee()?.as_ref().clone();
+
+        store.store_sync_committee_branch(*block_root, &current_sync_committee_branch)?;
+        store.store_sync_committee(sync_committee_period, &current_sync_committee)?;
+
+        Ok((current_sync_committee_branch, current_sync_committee))
+    }
 }
 
 impl<T: BeaconChainTypes> Default for LightClientServerCache<T> {
@@ -436,3 +570,47 @@ impl<E: EthSpec> LightClientCachedData<E> {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encoded_update(byte: u8) -> Arc<EncodedLightClientUpdate> {
+        Arc::new(EncodedLightClientUpdate {
+            fork_name: ForkName::Altair,
+            bytes: vec![byte],
+        })
+    }
+
+    #[test]
+    fn encoded_update_read_before_replacement_is_not_cached() {
+        let mut cache = EncodedUpdateCache::new();
+        let period = 1;
+
+        // A request reads the old update from the database, after which the update for the period
+        // is replaced.
+        let version = cache.version;
+        cache.invalidate(period);
+        cache.put_if_current(period, encoded_update(0), version);
+        assert!(cache.updates.get(&period).is_none());
+
+        // Updates read after the replacement are cached.
+        let version = cache.version;
+        cache.put_if_current(period, encoded_update(1), version);
+        assert_eq!(cache.updates.get(&period).unwrap().bytes, vec![1]);
+    }
+
+    #[test]
+    fn encoded_update_cached_before_replacement_is_removed() {
+        let mut cache = EncodedUpdateCache::new();
+        let period = 1;
+
+        let version = cache.version;
+        cache.put_if_current(period, encoded_update(0), version);
+        cache.put_if_current(period + 1, encoded_update(2), version);
+        cache.invalidate(period);
+
+        assert!(cache.updates.get(&period).is_none());
+        assert_eq!(cache.updates.get(&(period + 1)).unwrap().bytes, vec![2]);
+    }
+}
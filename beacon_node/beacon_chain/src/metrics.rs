@@ -36,6 +36,12 @@ pub static BLOCK_PROCESSING_SUCCESSES: LazyLock<Result<IntCounter>> = LazyLock::
         "Count of blocks processed without error",
     )
 });
+pub static BLOCK_IMPORT_TIMEOUTS: LazyLock<Result<IntCounter>> = LazyLock::new(|| {
+    try_create_int_counter(
+        "beacon_block_import_timeouts_total",
+        "Count of block imports cancelled for exceeding the block import timeout",
+    )
+});
 // Keeping the existing "snapshot_cache" metric name as it would break existing dashboards
 pub static BLOCK_PROCESSING_SNAPSHOT_CACHE_SIZE: LazyLock<Result<IntGauge>> = LazyLock::new(|| {
     try_create_int_gauge(
@@ -186,6 +192,22 @@ pub static BLOCK_PRODUCTION_GET_PROPOSER_HEAD_TIMES: LazyLock<Result<Histogram>>
             exponential_buckets(1e-3, 2.0, 8),
         )
     });
+pub static BLOCK_PRODUCTION_LATE_PAYLOAD_REFUSALS: LazyLock<Result<IntCounter>> = LazyLock::new(
+    || {
+        try_create_int_counter(
+            "beacon_block_production_late_payload_refusals_total",
+            "Count of block proposals built on the parent of a head with a recently revealed payload",
+        )
+    },
+);
+pub static BLOCK_PRODUCTION_LATE_PAYLOAD_TRUSTED: LazyLock<Result<IntCounter>> =
+    LazyLock::new(|| {
+        try_create_int_counter(
+            "beacon_block_production_late_payload_trusted_total",
+            "Count of block proposals built on a head with a recently revealed payload from a \
+             trusted builder",
+        )
+    });
 pub static BLOCK_PRODUCTION_STATE_LOAD_TIMES: LazyLock<Result<Histogram>> = LazyLock::new(|| {
     try_create_histogram(
         "beacon_block_production_state_load_seconds",
@@ -204,6 +226,13 @@ pub static BLOCK_PRODUCTION_UNAGGREGATED_TIMES: LazyLock<Result<Histogram>> = La
         "Time taken to import the naive aggregation pool for block production",
     )
 });
+pub static BLOCK_PRODUCTION_DEADLINE_SKIPS: LazyLock<Result<IntCounterVec>> = LazyLock::new(|| {
+    try_create_int_counter_vec(
+        "beacon_block_production_deadline_skips_total",
+        "Number of items skipped during block production because the deadline had passed",
+        &["component"],
+    )
+});
 pub static BLOCK_PRODUCTION_ATTESTATION_TIMES: LazyLock<Result<Histogram>> = LazyLock::new(|| {
     try_create_histogram(
         "beacon_block_production_attestation_seconds",
@@ -425,6 +454,14 @@ pub static ATTESTATION_PROCESSING_BATCH_UNAGG_SIGNATURE_TIMES: LazyLock<Result<H
             "Time spent on the signature verification of batch unaggregate attestation processing",
         )
     });
+pub static ATTESTATION_PROCESSING_BATCH_UNAGG_GROUP_SIZE: LazyLock<Result<Histogram>> =
+    LazyLock::new(|| {
+        try_create_histogram_with_buckets(
+            "beacon_attestation_processing_batch_unagg_group_size",
+            "Number of unaggregated attestations sharing the same AttestationData in a batch",
+            exponential_buckets(1.0, 2.0, 8),
+        )
+    });
 
 /*
  * Shuffling cache
@@ -463,6 +500,14 @@ pub static BEACON_EARLY_ATTESTER_CACHE_HITS: LazyLock<Result<IntCounter>> = Lazy
         "Count of times the early attester cache returns an attestation",
     )
 });
+pub static BEACON_EARLY_ATTESTER_CACHE_INVALIDATIONS: LazyLock<Result<IntCounter>> =
+    LazyLock::new(|| {
+        try_create_int_counter(
+            "beacon_early_attester_cache_invalidations",
+            "Count of times the early attester cache was cleared because its block is no longer \
+            the head",
+        )
+    });
 
 pub static BEACON_REQRESP_PRE_IMPORT_CACHE_SIZE: LazyLock<Result<IntGauge>> = LazyLock::new(|| {
     try_create_int_gauge(
@@ -477,6 +522,20 @@ pub static BEACON_REQRESP_PRE_IMPORT_CACHE_HITS: LazyLock<Result<IntCounter>> =
             "Count of times the reqresp pre import cache returns an item",
         )
     });
+pub static BEACON_REQRESP_DA_CHECKER_CACHE_HITS: LazyLock<Result<IntCounter>> =
+    LazyLock::new(|| {
+        try_create_int_counter(
+            "beacon_reqresp_da_checker_cache_hits",
+            "Count of times the data availability checker returns a block for a reqresp request",
+        )
+    });
+pub static BEACON_REQRESP_EARLY_ATTESTER_CACHE_HITS: LazyLock<Result<IntCounter>> =
+    LazyLock::new(|| {
+        try_create_int_counter(
+            "beacon_reqresp_early_attester_cache_hits",
+            "Count of times the early attester cache returns a block for a reqresp request",
+        )
+    });
 
 /*
  * Attestation Production
@@ -610,6 +669,29 @@ pub static BALANCES_CACHE_MISSES: LazyLock<Result<IntCounter>> = LazyLock::new(|
     )
 });
 
+/*
+ * Attester Cache
+ */
+pub static ATTESTER_CACHE_LOCK_CONTENDED: LazyLock<Result<IntCounter>> = LazyLock::new(|| {
+    try_create_int_counter(
+        "beacon_attester_cache_lock_contended_total",
+        "Count of times a write to the attester cache waited for another writer",
+    )
+});
+pub static ATTESTER_CACHE_LOCK_WAIT_TIMES: LazyLock<Result<Histogram>> = LazyLock::new(|| {
+    try_create_histogram(
+        "beacon_attester_cache_lock_wait_seconds",
+        "Time spent waiting for a contended attester cache lock",
+    )
+});
+pub static ATTESTER_CACHE_DUPLICATE_STATE_READS_AVOIDED: LazyLock<Result<IntCounter>> =
+    LazyLock::new(|| {
+        try_create_int_counter(
+            "beacon_attester_cache_duplicate_state_reads_avoided_total",
+            "Count of state reads avoided because another thread primed the attester cache",
+        )
+    });
+
 /*
  * Persisting BeaconChain components to disk
  */
@@ -625,12 +707,24 @@ pub static PERSIST_OP_POOL: LazyLock<Result<Histogram>> = LazyLock::new(|| {
         "Time taken to persist the operations pool",
     )
 });
+pub static PERSIST_OP_POOL_OPERATIONS: LazyLock<Result<Histogram>> = LazyLock::new(|| {
+    try_create_histogram(
+        "beacon_persist_op_pool_operations",
+        "Time taken to persist the operations pool without attestations",
+    )
+});
 pub static PERSIST_ETH1_CACHE: LazyLock<Result<Histogram>> = LazyLock::new(|| {
     try_create_histogram(
         "beacon_persist_eth1_cache",
         "Time taken to persist the eth1 caches",
     )
 });
+pub static PERSIST_CACHES: LazyLock<Result<Histogram>> = LazyLock::new(|| {
+    try_create_histogram(
+        "beacon_persist_caches",
+        "Time taken to persist the shuffling, proposer and attester caches",
+    )
+});
 pub static PERSIST_FORK_CHOICE: LazyLock<Result<Histogram>> = LazyLock::new(|| {
     try_create_histogram(
         "beacon_persist_fork_choice",
@@ -967,6 +1061,14 @@ pub static VALIDATOR_MONITOR_PREV_EPOCH_ON_CHAIN_ATTESTER_MISS: LazyLock<Result<
             &["validator"],
         )
     });
+pub static VALIDATOR_MONITOR_PREV_EPOCH_ATTESTATION_MISS_REASON: LazyLock<Result<IntCounterVec>> =
+    LazyLock::new(|| {
+        try_create_int_counter_vec(
+        "validator_monitor_prev_epoch_attestation_miss_reason_total",
+        "Incremented for each missed previous epoch attestation, labelled with the diagnosed reason",
+        &["reason", "validator"],
+    )
+    });
 pub static VALIDATOR_MONITOR_PREV_EPOCH_ON_CHAIN_HEAD_ATTESTER_HIT: LazyLock<
     Result<IntCounterVec>,
 > = LazyLock::new(|| {
@@ -1579,6 +1681,20 @@ pub static SYNC_MESSAGE_PROCESSING_SIGNATURE_TIMES: LazyLock<Result<Histogram>>
             "Time spent on the signature verification of sync message processing",
         )
     });
+pub static SYNC_MESSAGE_PROCESSING_BATCH_SIGNATURE_SETUP_TIMES: LazyLock<Result<Histogram>> =
+    LazyLock::new(|| {
+        try_create_histogram(
+            "beacon_sync_committee_message_processing_batch_signature_setup_seconds",
+            "Time spent on setting up for the signature verification of batch sync message processing",
+        )
+    });
+pub static SYNC_MESSAGE_PROCESSING_BATCH_SIGNATURE_TIMES: LazyLock<Result<Histogram>> =
+    LazyLock::new(|| {
+        try_create_histogram(
+            "beacon_sync_committee_message_processing_batch_signature_seconds",
+            "Time spent on the signature verification of batch sync message processing",
+        )
+    });
 
 /*
  * Checkpoint sync & backfill
@@ -1640,6 +1756,13 @@ pub static BLOBS_SIDECAR_GOSSIP_VERIFICATION_TIMES: LazyLock<Result<Histogram>>
             "Full runtime of blob sidecars gossip verification",
         )
     });
+pub static BLOBS_SIDECAR_GOSSIP_BATCH_KZG_VERIFIED: LazyLock<Result<IntCounter>> =
+    LazyLock::new(|| {
+        try_create_int_counter(
+            "beacon_blobs_sidecar_gossip_batch_kzg_verified_total",
+            "Number of gossip blob sidecars with KZG proofs verified in a batch",
+        )
+    });
 pub static BLOB_SIDECAR_INCLUSION_PROOF_VERIFICATION: LazyLock<Result<Histogram>> =
     LazyLock::new(|| {
         try_create_histogram(
@@ -1971,6 +2094,35 @@ pub static LIGHT_CLIENT_SERVER_CACHE_PREV_BLOCK_CACHE_MISS: LazyLock<Result<IntC
             "Count of prev block cache misses",
         )
     });
+pub static LIGHT_CLIENT_SERVER_CACHE_ENCODED_UPDATE_CACHE_MISS: LazyLock<Result<IntCounter>> =
+    LazyLock::new(|| {
+        try_create_int_counter(
+            "beacon_light_client_server_cache_encoded_update_cache_miss",
+            "Count of encoded light client update cache misses",
+        )
+    });
+
+pub static LIGHT_CLIENT_SERVER_CACHE_BEST_UPDATE_REPLACEMENTS: LazyLock<Result<IntCounter>> =
+    LazyLock::new(|| {
+        try_create_int_counter(
+            "beacon_light_client_server_cache_best_update_replacements_total",
+            "Count of times the persisted best light client update of a period was replaced",
+        )
+    });
+pub static LIGHT_CLIENT_SERVER_CACHE_BOOTSTRAP_COMPUTATIONS: LazyLock<Result<IntCounter>> =
+    LazyLock::new(|| {
+        try_create_int_counter(
+            "beacon_light_client_server_cache_bootstrap_computations",
+            "Count of light client bootstraps computed from a beacon state",
+        )
+    });
+pub static LIGHT_CLIENT_SERVER_CACHE_BOOTSTRAP_COMPUTATION_TIMES: LazyLock<Result<Histogram>> =
+    LazyLock::new(|| {
+        try_create_histogram(
+            "beacon_light_client_server_cache_bootstrap_computation_seconds",
+            "Time taken to compute a light client bootstrap from a beacon state",
+        )
+    });
 
 pub static LIGHT_CLIENT_SERVER_CACHE_PROCESSING_REQUESTS: LazyLock<Result<IntCounter>> =
     LazyLock::new(|| {
@@ -42,6 +42,9 @@ pub struct BackgroundMigrator<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>
     prev_migration: Arc<Mutex<PrevMigration>>,
     #[allow(clippy::type_complexity)]
     tx_thread: Option<Mutex<(mpsc::Sender<Notification>, thread::JoinHandle<()>)>>,
+    /// Separate thread for blob pruning, so that it does not hold up finalization migrations.
+    #[allow(clippy::type_complexity)]
+    blob_prune_tx_thread: Option<Mutex<(mpsc::Sender<PruneBlobsRequest>, thread::JoinHandle<()>)>>,
     /// Genesis block root, for persisting the `PersistedBeaconChain`.
     genesis_block_root: Hash256,
     log: Logger,
@@ -123,7 +126,27 @@ pub enum PruningError {
 pub enum Notification {
     Finalization(FinalizationNotification),
     Reconstruction,
-    PruneBlobs(Epoch),
+}
+
+/// Message sent to the blob pruning thread.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PruneBlobsRequest {
+    data_availability_boundary: Epoch,
+    /// Prune even if pruning is disabled or `epochs_per_blob_prune` has not elapsed.
+    force: bool,
+}
+
+impl PruneBlobsRequest {
+    /// Combine two requests into one which covers both.
+    fn merge(self, other: Self) -> Self {
+        Self {
+            data_availability_boundary: std::cmp::max(
+                self.data_availability_boundary,
+                other.data_availability_boundary,
+            ),
+            force: self.force || other.force,
+        }
+    }
 }
 
 pub struct FinalizationNotification {
@@ -147,14 +170,21 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
             epoch: db.get_split_slot().epoch(E::slots_per_epoch()),
             epochs_per_migration: config.epochs_per_migration,
         }));
-        let tx_thread = if config.blocking {
-            None
+        let (tx_thread, blob_prune_tx_thread) = if config.blocking {
+            (None, None)
         } else {
-            Some(Mutex::new(Self::spawn_thread(db.clone(), log.clone())))
+            (
+                Some(Mutex::new(Self::spawn_thread(db.clone(), log.clone()))),
+                Some(Mutex::new(Self::spawn_blob_prune_thread(
+                    db.clone(),
+                    log.clone(),
+                ))),
+            )
         };
         Self {
             db,
             tx_thread,
+            blob_prune_tx_thread,
             prev_migration,
             genesis_block_root,
             log,
@@ -201,10 +231,45 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
     }
 
     pub fn process_prune_blobs(&self, data_availability_boundary: Epoch) {
-        if let Some(Notification::PruneBlobs(data_availability_boundary)) =
-            self.send_background_notification(Notification::PruneBlobs(data_availability_boundary))
-        {
-            Self::run_prune_blobs(self.db.clone(), data_availability_boundary, &self.log);
+        self.send_prune_blobs_request(PruneBlobsRequest {
+            data_availability_boundary,
+            force: false,
+        });
+    }
+
+    /// Prune blobs regardless of the `prune_blobs` and `epochs_per_blob_prune` config.
+    pub fn process_force_prune_blobs(&self, data_availability_boundary: Epoch) {
+        self.send_prune_blobs_request(PruneBlobsRequest {
+            data_availability_boundary,
+            force: true,
+        });
+    }
+
+    /// Send `request` to the blob pruning thread if configured, otherwise run it in the foreground.
+    fn send_prune_blobs_request(&self, request: PruneBlobsRequest) {
+        let Some(tx_thread) = &self.blob_prune_tx_thread else {
+            Self::run_prune_blobs(self.db.clone(), request, &self.log);
+            return;
+        };
+        let (ref mut tx, ref mut thread) = *tx_thread.lock();
+
+        // Restart the background thread if it has crashed.
+        if let Err(tx_err) = tx.send(request) {
+            let (new_tx, new_thread) =
+                Self::spawn_blob_prune_thread(self.db.clone(), self.log.clone());
+
+            *tx = new_tx;
+            let old_thread = mem::replace(thread, new_thread);
+
+            if let Err(thread_err) = old_thread.join() {
+                warn!(
+                    self.log,
+                    "Blob pruning thread died, so it was restarted";
+                    "reason" => format!("{:?}", thread_err)
+                );
+            }
+
+            let _ = tx.send(tx_err.0);
         }
     }
 
@@ -241,10 +306,10 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
 
     pub fn run_prune_blobs(
         db: Arc<HotColdDB<E, Hot, Cold>>,
-        data_availability_boundary: Epoch,
+        request: PruneBlobsRequest,
         log: &Logger,
     ) {
-        if let Err(e) = db.try_prune_blobs(false, data_availability_boundary) {
+        if let Err(e) = db.try_prune_blobs(request.force, request.data_availability_boundary) {
             error!(
                 log,
                 "Blob pruning failed";
@@ -422,11 +487,9 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
             while let Ok(notif) = rx.recv() {
                 let mut reconstruction_notif = None;
                 let mut finalization_notif = None;
-                let mut prune_blobs_notif = None;
                 match notif {
                     Notification::Reconstruction => reconstruction_notif = Some(notif),
                     Notification::Finalization(fin) => finalization_notif = Some(fin),
-                    Notification::PruneBlobs(dab) => prune_blobs_notif = Some(dab),
                 }
                 // Read the rest of the messages in the channel, taking the best of each type.
                 for notif in rx.try_iter() {
@@ -443,20 +506,14 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
                                 finalization_notif = Some(fin);
                             }
                         }
-                        Notification::PruneBlobs(dab) => {
-                            prune_blobs_notif = std::cmp::max(prune_blobs_notif, Some(dab));
-                        }
                     }
                 }
-                // Run finalization and blob pruning migrations first, then a reconstruction batch.
-                // This prevents finalization from being starved while reconstruciton runs (a
-                // problem in previous LH versions).
+                // Run finalization migrations first, then a reconstruction batch. This prevents
+                // finalization from being starved while reconstruciton runs (a problem in previous
+                // LH versions).
                 if let Some(fin) = finalization_notif {
                     Self::run_migration(db.clone(), fin, &log);
                 }
-                if let Some(dab) = prune_blobs_notif {
-                    Self::run_prune_blobs(db.clone(), dab, &log);
-                }
                 if reconstruction_notif.is_some() {
                     Self::run_reconstruction(db.clone(), Some(inner_tx.clone()), &log);
                 }
@@ -465,6 +522,24 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
         (tx, thread)
     }
 
+    /// Spawn a new child thread to prune blobs.
+    ///
+    /// Return a channel handle for sending requests to the thread.
+    fn spawn_blob_prune_thread(
+        db: Arc<HotColdDB<E, Hot, Cold>>,
+        log: Logger,
+    ) -> (mpsc::Sender<PruneBlobsRequest>, thread::JoinHandle<()>) {
+        let (tx, rx) = mpsc::channel::<PruneBlobsRequest>();
+        let thread = thread::spawn(move || {
+            while let Ok(request) = rx.recv() {
+                // Coalesce any requests that queued up while the previous run was in progress.
+                let request = rx.try_iter().fold(request, PruneBlobsRequest::merge);
+                Self::run_prune_blobs(db.clone(), request, &log);
+            }
+        });
+        (tx, thread)
+    }
+
     /// Traverses live heads and prunes blocks and states of chains that we know can't be built
     /// upon because finalization would prohibit it. This is an optimisation intended to save disk
     /// space.
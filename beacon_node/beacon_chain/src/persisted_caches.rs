@@ -0,0 +1,231 @@
+//! Persistence of the caches used to produce and verify attestations and blocks.
+//!
+//! Without these caches a freshly started node must read and advance states from disk before it
+//! can compute attestation data, proposer duties or committees. Persisting them on shutdown allows
+//! a restarted node to resume its duties within seconds.
+//!
+//! All of the cached values are keyed by a shuffling decision block, so they remain valid
+//! regardless of how the head changes whilst the node is offline. The summaries of the states near
+//! the head are persisted so that those states can be loaded back into the state cache on startup,
+//! rather than on demand when the first block or attestation which needs them arrives.
+use crate::attester_cache::{AttesterCache, AttesterCacheKey, AttesterCacheValue};
+use crate::beacon_proposer_cache::BeaconProposerCache;
+use crate::shuffling_cache::ShufflingCache;
+use ssz::{Decode, DecodeError, Encode};
+use ssz_derive::{Decode, Encode};
+use store::{DBColumn, Error as StoreError, HotColdDB, ItemStore, StoreItem};
+use types::{
+    beacon_state::CommitteeCache, AttestationShufflingId, Epoch, EthSpec, Fork, Hash256, Slot,
+};
+
+/// 32-byte key for accessing the `PersistedCaches`. All zero because `PersistedCaches` has its own
+/// column.
+pub const CACHES_DB_KEY: Hash256 = Hash256::ZERO;
+
+/// The version of the encoding of `PersistedCaches`, which is stored as a prefix to the SSZ bytes.
+///
+/// This should be incremented whenever any of the persisted types change. Caches persisted with a
+/// different version are discarded rather than migrated, since they can always be recomputed.
+pub const PERSISTED_CACHES_VERSION: u8 = 2;
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct PersistedShuffling {
+    pub shuffling_id: AttestationShufflingId,
+    pub committee_cache: CommitteeCache,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct PersistedProposers {
+    pub epoch: Epoch,
+    pub shuffling_decision_block: Hash256,
+    pub fork: Fork,
+    pub proposers: Vec<u64>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct PersistedAttesterCacheEntry {
+    pub key: AttesterCacheKey,
+    pub value: AttesterCacheValue,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct PersistedStateSummary {
+    pub state_root: Hash256,
+    pub block_root: Hash256,
+    pub slot: Slot,
+}
+
+/// Snapshot of the shuffling, proposer and attester caches, and of the states cached near the
+/// head.
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct PersistedCaches {
+    pub shufflings: Vec<PersistedShuffling>,
+    /// Ordered from the least to the most recently used.
+    pub proposers: Vec<PersistedProposers>,
+    pub attester_cache: Vec<PersistedAttesterCacheEntry>,
+    /// Ordered by slot.
+    pub state_summaries: Vec<PersistedStateSummary>,
+}
+
+impl PersistedCaches {
+    /// Take a snapshot of the contents of the given caches.
+    ///
+    /// Only the summaries of the cached states from `min_state_slot` onwards are included.
+    pub fn from_caches<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+        shuffling_cache: &ShufflingCache,
+        beacon_proposer_cache: &BeaconProposerCache,
+        attester_cache: &AttesterCache,
+        store: &HotColdDB<E, Hot, Cold>,
+        min_state_slot: Slot,
+    ) -> Self {
+        let mut state_summaries = store
+            .state_cache_summaries()
+            .into_iter()
+            .filter(|(_, _, slot)| *slot >= min_state_slot)
+            .map(|(state_root, block_root, slot)| PersistedStateSummary {
+                state_root,
+                block_root,
+                slot,
+            })
+            .collect::<Vec<_>>();
+        state_summaries.sort_by_key(|summary| summary.slot);
+
+        Self {
+            shufflings: shuffling_cache.to_persisted(),
+            proposers: beacon_proposer_cache.to_persisted(),
+            attester_cache: attester_cache.to_persisted(),
+            state_summaries,
+        }
+    }
+
+    /// Insert the contents of the snapshot into the given caches, returning the state summaries
+    /// to be passed to `restore_states`.
+    ///
+    /// Attester cache entries prior to `finalized_epoch` are not restored.
+    pub fn restore(
+        self,
+        shuffling_cache: &mut ShufflingCache,
+        beacon_proposer_cache: &mut BeaconProposerCache,
+        attester_cache: &AttesterCache,
+        finalized_epoch: Epoch,
+    ) -> Vec<PersistedStateSummary> {
+        shuffling_cache.restore_persisted(self.shufflings);
+        beacon_proposer_cache.restore_persisted(self.proposers);
+        attester_cache.restore_persisted(self.attester_cache);
+        attester_cache.prune_below(finalized_epoch);
+        self.state_summaries
+    }
+}
+
+/// Load the states of `state_summaries` from the hot database into the state cache, returning the
+/// number of states loaded.
+///
+/// States which are already cached, no longer stored, or prior to `finalized_slot` are skipped.
+/// The summaries are loaded in order, so the most recent states are the last to be evicted.
+pub fn restore_states<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    store: &HotColdDB<E, Hot, Cold>,
+    state_summaries: &[PersistedStateSummary],
+    finalized_slot: Slot,
+) -> Result<usize, StoreError> {
+    let mut loaded = 0;
+    for summary in state_summaries {
+        if summary.slot < finalized_slot {
+            continue;
+        }
+        if store.load_hot_state_into_cache(&summary.state_root)? {
+            loaded += 1;
+        }
+    }
+    Ok(loaded)
+}
+
+impl StoreItem for PersistedCaches {
+    fn db_column() -> DBColumn {
+        DBColumn::BeaconChainCaches
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(1 + self.ssz_bytes_len());
+        bytes.push(PERSISTED_CACHES_VERSION);
+        self.ssz_append(&mut bytes);
+        bytes
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, StoreError> {
+        match bytes.split_first() {
+            Some((&PERSISTED_CACHES_VERSION, ssz_bytes)) => Ok(Self::from_ssz_bytes(ssz_bytes)?),
+            Some((version, _)) => Err(DecodeError::BytesInvalid(format!(
+                "unsupported persisted caches version: {}",
+                version
+            ))
+            .into()),
+            None => Err(DecodeError::InvalidByteLength {
+                len: 0,
+                expected: 1,
+            }
+            .into()),
+        }
+    }
+}
+
+/// Load the caches persisted on the last shutdown, removing them from the store so that a stale
+/// snapshot is never restored after an unclean shutdown.
+pub fn take_persisted_caches<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    store: &HotColdDB<E, Hot, Cold>,
+) -> Result<Option<PersistedCaches>, StoreError> {
+    // Delete the item even if it fails to decode.
+    let result = store.get_item::<PersistedCaches>(&CACHES_DB_KEY);
+    store.hot_db.delete::<PersistedCaches>(&CACHES_DB_KEY)?;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::MinimalEthSpec;
+
+    fn persisted_caches() -> PersistedCaches {
+        let mut beacon_proposer_cache = BeaconProposerCache::default();
+        beacon_proposer_cache
+            .insert(
+                Epoch::new(3),
+                Hash256::repeat_byte(3),
+                (0..MinimalEthSpec::slots_per_epoch() as usize).collect(),
+                Fork::default(),
+            )
+            .unwrap();
+
+        PersistedCaches {
+            shufflings: vec![PersistedShuffling {
+                shuffling_id: AttestationShufflingId {
+                    shuffling_epoch: Epoch::new(3),
+                    shuffling_decision_block: Hash256::repeat_byte(1),
+                },
+                committee_cache: CommitteeCache::default(),
+            }],
+            proposers: beacon_proposer_cache.to_persisted(),
+            attester_cache: vec![],
+            state_summaries: vec![PersistedStateSummary {
+                state_root: Hash256::repeat_byte(4),
+                block_root: Hash256::repeat_byte(5),
+                slot: Slot::new(24),
+            }],
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let caches = persisted_caches();
+        let bytes = caches.as_store_bytes();
+        assert_eq!(bytes[0], PERSISTED_CACHES_VERSION);
+        assert_eq!(PersistedCaches::from_store_bytes(&bytes).unwrap(), caches);
+    }
+
+    #[test]
+    fn unknown_version_is_rejected() {
+        let mut bytes = persisted_caches().as_store_bytes();
+        bytes[0] = PERSISTED_CACHES_VERSION + 1;
+        assert!(PersistedCaches::from_store_bytes(&bytes).is_err());
+        assert!(PersistedCaches::from_store_bytes(&[]).is_err());
+    }
+}
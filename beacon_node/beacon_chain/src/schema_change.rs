@@ -2,6 +2,7 @@
 mod migration_schema_v20;
 mod migration_schema_v21;
 mod migration_schema_v22;
+mod migration_schema_v23;
 
 use crate::beacon_chain::BeaconChainTypes;
 use slog::Logger;
@@ -59,6 +60,14 @@ pub fn migrate_schema<T: BeaconChainTypes>(
             // bumped inside the upgrade_to_v22 fn
             migration_schema_v22::upgrade_to_v22::<T>(db.clone(), genesis_state_root, log)
         }
+        (SchemaVersion(22), SchemaVersion(23)) => {
+            let ops = migration_schema_v23::upgrade_to_v23::<T>(db.clone(), log)?;
+            db.store_schema_version_atomically(to, ops)
+        }
+        (SchemaVersion(23), SchemaVersion(22)) => {
+            let ops = migration_schema_v23::downgrade_from_v23::<T>(db.clone(), log)?;
+            db.store_schema_version_atomically(to, ops)
+        }
         // Anything else is an error.
         (_, _) => Err(HotColdDBError::UnsupportedSchemaVersion {
             target_version: to,
@@ -0,0 +1,30 @@
+use crate::beacon_chain::BeaconChainTypes;
+use slog::{info, Logger};
+use std::sync::Arc;
+use store::metadata::{AnchorInfoV22, ANCHOR_INFO_KEY};
+use store::{Error, HotColdDB, KeyValueStoreOp, StoreItem};
+
+/// Add the `backfill_target_slot` to the anchor.
+///
+/// Anchors in the v22 format are read with a backfill target of 0, which is updated to the
+/// configured target when the beacon chain starts.
+pub fn upgrade_to_v23<T: BeaconChainTypes>(
+    db: Arc<HotColdDB<T::EthSpec, T::HotStore, T::ColdStore>>,
+    log: Logger,
+) -> Result<Vec<KeyValueStoreOp>, Error> {
+    info!(log, "Upgrading from v22 to v23");
+
+    let anchor = db.get_anchor_info();
+    Ok(vec![anchor.as_kv_store_op(ANCHOR_INFO_KEY)])
+}
+
+/// Remove the `backfill_target_slot` from the anchor.
+pub fn downgrade_from_v23<T: BeaconChainTypes>(
+    db: Arc<HotColdDB<T::EthSpec, T::HotStore, T::ColdStore>>,
+    log: Logger,
+) -> Result<Vec<KeyValueStoreOp>, Error> {
+    info!(log, "Downgrading from v23 to v22");
+
+    let anchor = AnchorInfoV22::from(db.get_anchor_info());
+    Ok(vec![anchor.as_kv_store_op(ANCHOR_INFO_KEY)])
+}
@@ -10,6 +10,7 @@ use types::{
     RelativeEpoch,
 };
 
+use crate::persisted_caches::PersistedShuffling;
 use crate::{metrics, BeaconChainError};
 
 /// The size of the cache that stores committee caches for quicker verification.
@@ -214,6 +215,30 @@ impl ShufflingCache {
     pub fn update_head_shuffling_ids(&mut self, head_shuffling_ids: BlockShufflingIds) {
         self.head_shuffling_ids = head_shuffling_ids;
     }
+
+    /// Returns the committee caches which have been computed, omitting any pending promises.
+    pub fn to_persisted(&self) -> Vec<PersistedShuffling> {
+        self.cache
+            .iter()
+            .filter_map(|(shuffling_id, item)| match item {
+                CacheItem::Committee(committee_cache) => Some(PersistedShuffling {
+                    shuffling_id: shuffling_id.clone(),
+                    committee_cache: committee_cache.as_ref().clone(),
+                }),
+                CacheItem::Promise(_) => None,
+            })
+            .collect()
+    }
+
+    /// Insert the committee caches returned by `Self::to_persisted`.
+    pub fn restore_persisted(&mut self, shufflings: Vec<PersistedShuffling>) {
+        for shuffling in shufflings {
+            self.insert_committee_cache(
+                shuffling.shuffling_id,
+                &Arc::new(shuffling.committee_cache),
+            );
+        }
+    }
 }
 
 /// A helper trait to allow lazy-cloning of the committee cache when inserting into the cache.
@@ -30,7 +30,7 @@ use crate::observed_attesters::SlotSubcommitteeIndex;
 use crate::{
     metrics, observed_aggregates::ObserveOutcome, BeaconChain, BeaconChainError, BeaconChainTypes,
 };
-use bls::{verify_signature_sets, PublicKeyBytes};
+use bls::{verify_signature_sets, PublicKeyBytes, SignatureSet};
 use derivative::Derivative;
 use safe_arith::ArithError;
 use slot_clock::SlotClock;
@@ -49,12 +49,12 @@ use tree_hash_derive::TreeHash;
 use types::consts::altair::SYNC_COMMITTEE_SUBNET_COUNT;
 use types::slot_data::SlotData;
 use types::sync_committee::Error as SyncCommitteeError;
-use types::ChainSpec;
 use types::{
     sync_committee_contribution::Error as ContributionError, AggregateSignature, BeaconStateError,
     EthSpec, Hash256, SignedContributionAndProof, Slot, SyncCommitteeContribution,
     SyncCommitteeMessage, SyncSelectionProof, SyncSubnetId,
 };
+use types::{ChainSpec, Domain, SignedRoot};
 
 /// Returned when a sync committee contribution was not successfully verified. It might not have been verified for
 /// two reasons:
@@ -443,13 +443,26 @@ impl<T: BeaconChainTypes> VerifiedSyncContribution<T> {
     }
 }
 
-impl VerifiedSyncCommitteeMessage {
-    /// Returns `Ok(Self)` if the `sync_message` is valid to be (re)published on the gossip
-    /// network.
-    ///
-    /// `subnet_id` is the subnet from which we received this sync message. This function will
-    /// verify that it was received on the correct subnet.
-    pub fn verify<T: BeaconChainTypes>(
+/// Used to avoid double-checking signatures.
+#[derive(Copy, Clone, PartialEq)]
+enum CheckSignature {
+    Yes,
+    No,
+}
+
+/// Wraps a `SyncCommitteeMessage` that has passed all checks except signature verification and
+/// observation. Used to batch the signature verification of many messages.
+struct PartiallyVerifiedSyncCommitteeMessage {
+    sync_message: SyncCommitteeMessage,
+    subnet_id: SyncSubnetId,
+    subnet_positions: HashMap<SyncSubnetId, Vec<usize>>,
+    pubkey: PublicKeyBytes,
+    head_root: Hash256,
+}
+
+impl PartiallyVerifiedSyncCommitteeMessage {
+    /// Run the checks that happen before signature verification.
+    fn verify<T: BeaconChainTypes>(
         sync_message: SyncCommitteeMessage,
         subnet_id: SyncSubnetId,
         chain: &BeaconChain<T>,
@@ -482,21 +495,6 @@ impl VerifiedSyncCommitteeMessage {
         let validator_index = sync_message.validator_index;
         let head_root = chain.canonical_head.cached_head().head_block_root();
         let new_root = sync_message.beacon_block_root;
-        let should_override_prev = |prev_root: &Hash256, new_root: &Hash256| {
-            let roots_differ = new_root != prev_root;
-            let new_elects_head = new_root == &head_root;
-
-            if roots_differ {
-                // Track sync committee messages that differ from each other.
-                metrics::inc_counter(&metrics::SYNC_MESSAGE_EQUIVOCATIONS);
-                if new_elects_head {
-                    // Track sync committee messages that swap from an old block to a new block.
-                    metrics::inc_counter(&metrics::SYNC_MESSAGE_EQUIVOCATIONS_TO_HEAD);
-                }
-            }
-
-            roots_differ && new_elects_head
-        };
         if let Some(prev_root) = chain
             .observed_sync_contributors
             .read()
@@ -506,7 +504,7 @@ impl VerifiedSyncCommitteeMessage {
             )
             .map_err(BeaconChainError::from)?
         {
-            if !should_override_prev(&prev_root, &new_root) {
+            if !should_override_prev(&prev_root, &new_root, &head_root) {
                 return Err(Error::PriorSyncCommitteeMessageKnown {
                     validator_index,
                     slot: sync_message.slot,
@@ -516,8 +514,68 @@ impl VerifiedSyncCommitteeMessage {
             }
         }
 
+        Ok(Self {
+            sync_message,
+            subnet_id,
+            subnet_positions,
+            pubkey,
+            head_root,
+        })
+    }
+}
+
+/// Returns `true` if a sync committee message for `new_root` should replace a previously observed
+/// message for `prev_root` from the same validator.
+fn should_override_prev(prev_root: &Hash256, new_root: &Hash256, head_root: &Hash256) -> bool {
+    let roots_differ = new_root != prev_root;
+    let new_elects_head = new_root == head_root;
+
+    if roots_differ {
+        // Track sync committee messages that differ from each other.
+        metrics::inc_counter(&metrics::SYNC_MESSAGE_EQUIVOCATIONS);
+        if new_elects_head {
+            // Track sync committee messages that swap from an old block to a new block.
+            metrics::inc_counter(&metrics::SYNC_MESSAGE_EQUIVOCATIONS_TO_HEAD);
+        }
+    }
+
+    roots_differ && new_elects_head
+}
+
+impl VerifiedSyncCommitteeMessage {
+    /// Returns `Ok(Self)` if the `sync_message` is valid to be (re)published on the gossip
+    /// network.
+    ///
+    /// `subnet_id` is the subnet from which we received this sync message. This function will
+    /// verify that it was received on the correct subnet.
+    pub fn verify<T: BeaconChainTypes>(
+        sync_message: SyncCommitteeMessage,
+        subnet_id: SyncSubnetId,
+        chain: &BeaconChain<T>,
+    ) -> Result<Self, Error> {
+        let partially_verified =
+            PartiallyVerifiedSyncCommitteeMessage::verify(sync_message, subnet_id, chain)?;
+        Self::from_partially_verified(partially_verified, chain, CheckSignature::Yes)
+    }
+
+    /// Complete the verification of a partially verified sync committee message.
+    fn from_partially_verified<T: BeaconChainTypes>(
+        partially_verified: PartiallyVerifiedSyncCommitteeMessage,
+        chain: &BeaconChain<T>,
+        check_signature: CheckSignature,
+    ) -> Result<Self, Error> {
+        let PartiallyVerifiedSyncCommitteeMessage {
+            sync_message,
+            subnet_id,
+            subnet_positions,
+            pubkey,
+            head_root,
+        } = partially_verified;
+
         // The aggregate signature of the sync committee message is valid.
-        verify_sync_committee_message(chain, &sync_message, &pubkey)?;
+        if check_signature == CheckSignature::Yes {
+            verify_sync_committee_message(chain, &sync_message, &pubkey)?;
+        }
 
         // Now that the sync committee message has been fully verified, store that we have received a valid
         // sync committee message from this validator.
@@ -525,6 +583,8 @@ impl VerifiedSyncCommitteeMessage {
         // It's important to double check that the sync committee message still hasn't been observed, since
         // there can be a race-condition if we receive two sync committee messages at the same time and
         // process them in different threads.
+        let validator_index = sync_message.validator_index;
+        let new_root = sync_message.beacon_block_root;
         if let Some(prev_root) = chain
             .observed_sync_contributors
             .write()
@@ -532,7 +592,7 @@ impl VerifiedSyncCommitteeMessage {
                 SlotSubcommitteeIndex::new(sync_message.slot, subnet_id.into()),
                 validator_index as usize,
                 sync_message.beacon_block_root,
-                should_override_prev,
+                |prev_root, new_root| should_override_prev(prev_root, new_root, &head_root),
             )
             .map_err(BeaconChainError::from)?
         {
@@ -712,3 +772,111 @@ pub fn verify_sync_committee_message<T: BeaconChainTypes>(
         Err(Error::InvalidSignature)
     }
 }
+
+/// Verify sync committee messages using batch BLS signature verification.
+///
+/// Each message is first partially verified. The messages which pass are grouped by
+/// `(slot, beacon_block_root)`, so that the signing root is computed once per group, and each
+/// group's signatures are verified in a single batch. If a group's batch fails then the signature
+/// of each message in that group is verified independently.
+///
+/// The returned `Vec` has a one-to-one mapping with the supplied messages, and each result is the
+/// same as would be returned by `VerifiedSyncCommitteeMessage::verify`.
+pub fn batch_verify_sync_committee_messages<T, I>(
+    sync_messages: I,
+    chain: &BeaconChain<T>,
+) -> Vec<Result<VerifiedSyncCommitteeMessage, Error>>
+where
+    T: BeaconChainTypes,
+    I: Iterator<Item = (SyncCommitteeMessage, SyncSubnetId)> + ExactSizeIterator,
+{
+    // Perform partial verification of all messages, collecting the results.
+    let partial_results = sync_messages
+        .map(|(sync_message, subnet_id)| {
+            PartiallyVerifiedSyncCommitteeMessage::verify(sync_message, subnet_id, chain)
+        })
+        .collect::<Vec<_>>();
+
+    // Group the messages which passed partial verification by the root they sign over.
+    let mut groups: HashMap<(Slot, Hash256), Vec<usize>> = HashMap::new();
+    for (i, partial) in partial_results.iter().enumerate() {
+        if let Ok(partial) = partial {
+            let key = (
+                partial.sync_message.slot,
+                partial.sync_message.beacon_block_root,
+            );
+            groups.entry(key).or_default().push(i);
+        }
+    }
+
+    // Set to `true` for messages in groups which pass batch verification, so their signatures
+    // aren't checked again.
+    let mut verified_in_batch = vec![false; partial_results.len()];
+    {
+        let pubkey_cache = chain.validator_pubkey_cache.read();
+
+        for ((slot, beacon_block_root), indices) in groups {
+            // A lone message gains nothing from batching.
+            if indices.len() < 2 {
+                continue;
+            }
+
+            let signature_setup_timer =
+                metrics::start_timer(&metrics::SYNC_MESSAGE_PROCESSING_BATCH_SIGNATURE_SETUP_TIMES);
+
+            let next_slot_epoch = (slot + 1).epoch(T::EthSpec::slots_per_epoch());
+            let fork = chain.spec.fork_at_epoch(next_slot_epoch);
+            let domain = chain.spec.get_domain(
+                slot.epoch(T::EthSpec::slots_per_epoch()),
+                Domain::SyncCommittee,
+                &fork,
+                chain.genesis_validators_root,
+            );
+            let message = beacon_block_root.signing_root(domain);
+
+            let signature_sets = indices
+                .iter()
+                .map(|&i| {
+                    let partial = partial_results.get(i)?.as_ref().ok()?;
+                    let pubkey = pubkey_cache.get_pubkey_from_pubkey_bytes(&partial.pubkey)?;
+                    Some(SignatureSet::single_pubkey(
+                        &partial.sync_message.signature,
+                        Cow::Borrowed(pubkey),
+                        message,
+                    ))
+                })
+                .collect::<Option<Vec<_>>>();
+
+            metrics::stop_timer(signature_setup_timer);
+
+            // An unknown pubkey is reported by the individual verification.
+            let Some(signature_sets) = signature_sets else {
+                continue;
+            };
+
+            let _signature_verification_timer =
+                metrics::start_timer(&metrics::SYNC_MESSAGE_PROCESSING_BATCH_SIGNATURE_TIMES);
+
+            if verify_signature_sets(signature_sets.iter()) {
+                for i in indices {
+                    verified_in_batch[i] = true;
+                }
+            }
+        }
+    }
+
+    // Complete the verification, independently verifying the signatures of any messages which
+    // were not verified in a batch.
+    partial_results
+        .into_iter()
+        .zip(verified_in_batch)
+        .map(|(result, verified_in_batch)| {
+            let check_signature = if verified_in_batch {
+                CheckSignature::No
+            } else {
+                CheckSignature::Yes
+            };
+            VerifiedSyncCommitteeMessage::from_partially_verified(result?, chain, check_signature)
+        })
+        .collect()
+}
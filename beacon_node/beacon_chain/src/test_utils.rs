@@ -408,6 +408,12 @@ where
         self
     }
 
+    /// Use `store_config` for the store created by `fresh_ephemeral_store`.
+    pub fn store_config(mut self, store_config: StoreConfig) -> Self {
+        self.store_config = Some(store_config);
+        self
+    }
+
     pub fn logger(mut self, log: Logger) -> Self {
         self.log = log.clone();
         self.runtime.set_logger(log);
@@ -898,6 +904,7 @@ where
                 Some(graffiti),
                 ProduceBlockVerification::VerifyRandao,
                 None,
+                None,
                 BlockProductionVersion::FullV2,
             )
             .await
@@ -962,6 +969,7 @@ where
                 Some(graffiti),
                 ProduceBlockVerification::VerifyRandao,
                 None,
+                None,
                 BlockProductionVersion::FullV2,
             )
             .await
@@ -4,6 +4,7 @@
 
 use crate::beacon_proposer_cache::{BeaconProposerCache, TYPICAL_SLOTS_PER_EPOCH};
 use crate::metrics;
+use eth2::lighthouse::{AttestationDiagnosis, AttestationOutcome};
 use itertools::Itertools;
 use parking_lot::{Mutex, RwLock};
 use serde::{Deserialize, Serialize};
@@ -105,6 +106,8 @@ pub struct EpochSummary {
     pub attestation_block_inclusions: usize,
     /// The minimum observed inclusion distance for an attestation for this epoch..
     pub attestation_min_block_inclusion_distance: Option<Slot>,
+    /// The outcome of the attestation, set once the epoch has been processed.
+    pub attestation_outcome: Option<AttestationOutcome>,
     /*
      * Blocks with a slot in the current epoch.
      */
@@ -232,6 +235,31 @@ impl EpochSummary {
     pub fn register_validator_total_balance(&mut self, total_balance: u64) {
         self.total_balance = Some(total_balance)
     }
+
+    /// Determine the outcome of the attestation from the observations in this summary, where
+    /// `rewarded` indicates if the attestation matched any timely flag on chain.
+    ///
+    /// Attestations observed more than `late_threshold` after they were due arrived after
+    /// aggregators would have produced their aggregates.
+    pub fn diagnose_attestation(
+        &self,
+        rewarded: bool,
+        late_threshold: Duration,
+    ) -> AttestationOutcome {
+        if rewarded {
+            AttestationOutcome::Included
+        } else if self.attestation_block_inclusions > 0 {
+            AttestationOutcome::IncludedLate
+        } else if self.attestation_aggregate_inclusions > 0 {
+            AttestationOutcome::AggregateNotIncluded
+        } else {
+            match self.attestation_min_delay {
+                None => AttestationOutcome::NotProduced,
+                Some(delay) if delay > late_threshold => AttestationOutcome::ProducedLate,
+                Some(_) => AttestationOutcome::NotAggregated,
+            }
+        }
+    }
 }
 
 type SummaryMap = HashMap<Epoch, EpochSummary>;
@@ -366,6 +394,24 @@ impl MonitoredValidator {
             summary_opt.and_then(|summary| summary.total_balance)
         })
     }
+
+    /// Returns the evidence about the validator's attestation in `epoch`, if the validator's
+    /// index is known and the epoch hasn't been pruned.
+    pub fn get_attestation_diagnosis(&self, epoch: Epoch) -> Option<AttestationDiagnosis> {
+        let validator_index = self.index?;
+        self.get_from_epoch_summary(epoch, |summary_opt| {
+            summary_opt.map(|summary| AttestationDiagnosis {
+                validator_index,
+                outcome: summary.attestation_outcome,
+                attestation_delay_ms: summary
+                    .attestation_min_delay
+                    .map(|delay| delay.as_millis() as u64),
+                aggregate_inclusions: summary.attestation_aggregate_inclusions as u64,
+                block_inclusions: summary.attestation_block_inclusions as u64,
+                min_inclusion_distance: summary.attestation_min_block_inclusion_distance,
+            })
+        })
+    }
 }
 
 #[derive(PartialEq, Hash, Eq)]
@@ -800,6 +846,10 @@ impl<E: EthSpec> ValidatorMonitor<E> {
         let mut target_miss = Vec::new();
         let mut suboptimal_inclusion = Vec::new();
 
+        // Aggregators produce aggregates two thirds of the way through the slot, a third of a slot
+        // after unaggregated attestations are due.
+        let late_threshold = Duration::from_secs(spec.seconds_per_slot) / 3;
+
         // We subtract two from the state of the epoch that generated these summaries.
         //
         // - One to account for it being the previous epoch.
@@ -835,6 +885,19 @@ impl<E: EthSpec> ValidatorMonitor<E> {
                     continue;
                 }
 
+                let outcome = monitored_validator
+                    .get_from_epoch_summary(prev_epoch, |summary_opt| {
+                        Some(
+                            summary_opt
+                                .unwrap_or(&EpochSummary::default())
+                                .diagnose_attestation(previous_epoch_matched_any, late_threshold),
+                        )
+                    })
+                    .unwrap_or(AttestationOutcome::NotProduced);
+                monitored_validator.with_epoch_summary(prev_epoch, |summary| {
+                    summary.attestation_outcome = Some(outcome)
+                });
+
                 // Store some metrics directly to be re-exposed on the HTTP API.
                 let mut validator_metrics = monitored_validator.metrics.write();
                 if previous_epoch_matched_any {
@@ -883,11 +946,18 @@ impl<E: EthSpec> ValidatorMonitor<E> {
                             &[label],
                         );
                     });
+                    self.aggregatable_metric(id, |label| {
+                        metrics::inc_counter_vec(
+                            &metrics::VALIDATOR_MONITOR_PREV_EPOCH_ATTESTATION_MISS_REASON,
+                            &[outcome.as_str(), label],
+                        );
+                    });
                     attestation_miss.push(id);
                     if self.individual_tracking() {
                         debug!(
                             self.log,
                             "Previous epoch attestation missing";
+                            "reason" => outcome.as_str(),
                             "epoch" => prev_epoch,
                             "validator" => id,
                         )
@@ -1101,6 +1171,16 @@ impl<E: EthSpec> ValidatorMonitor<E> {
         }
     }
 
+    /// Returns the attestation diagnoses of the monitored validators for `epoch`, ordered by
+    /// validator index.
+    pub fn get_attestation_diagnoses(&self, epoch: Epoch) -> Vec<AttestationDiagnosis> {
+        self.validators
+            .values()
+            .filter_map(|validator| validator.get_attestation_diagnosis(epoch))
+            .sorted_by_key(|diagnosis| diagnosis.validator_index)
+            .collect()
+    }
+
     pub fn get_monitored_validator_missed_block_count(&self, validator_index: u64) -> u64 {
         self.missed_blocks
             .iter()
@@ -1158,6 +1238,17 @@ impl<E: EthSpec> ValidatorMonitor<E> {
         self.register_beacon_block("api", seen_timestamp, block, block_root, slot_clock)
     }
 
+    /// Process a block received via RPC (e.g. a block lookup during sync).
+    pub fn register_rpc_block<S: SlotClock>(
+        &self,
+        seen_timestamp: Duration,
+        block: BeaconBlockRef<'_, E>,
+        block_root: Hash256,
+        slot_clock: &S,
+    ) {
+        self.register_beacon_block("rpc", seen_timestamp, block, block_root, slot_clock)
+    }
+
     fn register_beacon_block<S: SlotClock>(
         &self,
         src: &str,
@@ -339,3 +339,94 @@ async fn early_attester_cache_old_request() {
         .unwrap();
     assert_eq!(attested_block.slot(), attest_slot);
 }
+
+/// Ensures that the early attester cache is invalidated when its block is no longer the head, even
+/// if the canonical head does not change.
+#[tokio::test]
+async fn early_attester_cache_invalidated_on_reorg() {
+    let harness = BeaconChainHarness::builder(MainnetEthSpec)
+        .default_spec()
+        .keypairs(KEYPAIRS[..].to_vec())
+        .fresh_ephemeral_store()
+        .mock_execution_layer()
+        .build();
+
+    harness.advance_slot();
+
+    harness
+        .extend_chain(
+            2,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let head = harness.chain.head_snapshot();
+    assert_eq!(head.beacon_block.slot(), 2);
+
+    // Add the parent of the head to the early attester cache, as if it had become the head during
+    // its import and then been re-orged out.
+    let orphan_root = head.beacon_block.parent_root();
+    let orphan_block = harness
+        .chain
+        .get_block(&orphan_root)
+        .await
+        .unwrap()
+        .unwrap();
+    let orphan_state = harness
+        .chain
+        .get_state(&orphan_block.state_root(), Some(orphan_block.slot()))
+        .unwrap()
+        .unwrap();
+    let orphan_proto_block = harness
+        .chain
+        .canonical_head
+        .fork_choice_read_lock()
+        .get_block(&orphan_root)
+        .unwrap();
+    let orphan_blobs = harness
+        .chain
+        .get_blobs(&orphan_root)
+        .expect("should get blobs");
+
+    let rpc_block =
+        RpcBlock::<MainnetEthSpec>::new(None, Arc::new(orphan_block), Some(orphan_blobs)).unwrap();
+    let beacon_chain::data_availability_checker::MaybeAvailableBlock::Available(available_block) =
+        harness
+            .chain
+            .data_availability_checker
+            .verify_kzg_for_rpc_block(rpc_block)
+            .unwrap()
+    else {
+        panic!("block should be available")
+    };
+
+    harness
+        .chain
+        .early_attester_cache
+        .add_head_block(
+            orphan_root,
+            available_block,
+            orphan_proto_block,
+            &orphan_state,
+            &harness.chain.spec,
+        )
+        .unwrap();
+    assert!(harness
+        .chain
+        .early_attester_cache
+        .contains_block(orphan_root));
+
+    // Fork choice still considers the existing head to be the head, so the cache is invalidated.
+    harness.chain.recompute_head_at_current_slot().await;
+    assert!(!harness
+        .chain
+        .early_attester_cache
+        .contains_block(orphan_root));
+
+    let attestation = harness
+        .chain
+        .produce_unaggregated_attestation(head.beacon_block.slot(), 0)
+        .unwrap();
+    assert_eq!(attestation.data().beacon_block_root, head.beacon_block_root);
+}
@@ -1,5 +1,6 @@
 #![cfg(not(debug_assertions))]
 
+use beacon_chain::blob_verification::GossipBlobError;
 use beacon_chain::block_verification_types::{AsBlock, ExecutedBlock, RpcBlock};
 use beacon_chain::{
     test_utils::{
@@ -20,6 +21,7 @@ use state_processing::{
 };
 use std::marker::PhantomData;
 use std::sync::{Arc, LazyLock};
+use std::time::Duration;
 use tempfile::tempdir;
 use types::{test_utils::generate_deterministic_keypair, *};
 
@@ -1275,6 +1277,149 @@ async fn verify_block_for_gossip_slashing_detection() {
     slasher_dir.close().unwrap();
 }
 
+/// Tests that an RPC block import stalled on an unresponsive execution layer is cut short by the
+/// import timeout, and that the block can be imported once the execution layer recovers.
+#[tokio::test]
+async fn rpc_block_import_times_out_on_unresponsive_execution_layer() {
+    let spec = Arc::new(ForkName::Capella.make_genesis_spec(E::default_spec()));
+    let harness = BeaconChainHarness::builder(E::default())
+        .spec(spec)
+        .keypairs(KEYPAIRS[0..VALIDATOR_COUNT].to_vec())
+        .fresh_ephemeral_store()
+        .mock_execution_layer()
+        .build();
+    harness.advance_slot();
+
+    let state = harness.get_current_state();
+    let ((block, _), _) = harness.make_block(state, Slot::new(1)).await;
+    let block_root = block.canonical_root();
+    let mock_execution_layer = harness.mock_execution_layer.as_ref().unwrap();
+
+    // The execution layer doesn't respond to `newPayload` within the timeout.
+    mock_execution_layer
+        .server
+        .set_response_delay(Some(Duration::from_secs(2)));
+    let timeout = Duration::from_millis(100);
+    let result = harness
+        .chain
+        .process_block_with_early_caching_and_timeout(
+            block_root,
+            RpcBlock::new_without_blobs(Some(block_root), block.clone()),
+            BlockImportSource::Lookup,
+            NotifyExecutionLayer::Yes,
+            timeout,
+        )
+        .await;
+    assert!(
+        matches!(result, Err(BlockError::ImportTimeout(t)) if t == timeout),
+        "{result:?}"
+    );
+    assert!(!harness
+        .chain
+        .reqresp_pre_import_cache
+        .read()
+        .contains_key(&block_root));
+    assert!(!harness.chain.block_is_known_to_fork_choice(&block_root));
+
+    // Once the execution layer responds again the retried import succeeds.
+    mock_execution_layer.server.set_response_delay(None);
+    let result = harness
+        .chain
+        .process_block_with_early_caching_and_timeout(
+            block_root,
+            RpcBlock::new_without_blobs(Some(block_root), block),
+            BlockImportSource::Lookup,
+            NotifyExecutionLayer::Yes,
+            Duration::from_secs(60),
+        )
+        .await;
+    assert_eq!(
+        result.unwrap(),
+        AvailabilityProcessingStatus::Imported(block_root)
+    );
+    assert!(harness.chain.block_is_known_to_fork_choice(&block_root));
+}
+
+/// Tests that the KZG proofs of gossip blob sidecars are verified in batches per block, and that
+/// the blobs then complete gossip verification individually.
+#[tokio::test]
+async fn batch_verify_kzg_for_gossip_blobs() {
+    let harness = get_harness(VALIDATOR_COUNT);
+    harness.advance_slot();
+
+    if !harness
+        .spec
+        .fork_name_at_slot::<E>(Slot::new(1))
+        .deneb_enabled()
+    {
+        return;
+    }
+
+    // The number of blobs in a block is random, so find a block with at least three of them.
+    let state = harness.get_current_state();
+    let (block, sidecars) = loop {
+        let ((block, blobs), _) = harness.make_block(state.clone(), Slot::new(1)).await;
+        let Some((kzg_proofs, blobs)) = blobs else {
+            continue;
+        };
+        if blobs.len() < 3 {
+            continue;
+        }
+        let sidecars = BlobSidecar::build_sidecars(blobs, &block, kzg_proofs).unwrap();
+        break (block, sidecars);
+    };
+
+    // An invalid proof poisons the batch for the block, so every blob is returned unverified.
+    let mut batch = sidecars.iter().cloned().collect::<Vec<_>>();
+    let invalid_index = batch.len() - 1;
+    let mut invalid_sidecar = (*batch[invalid_index]).clone();
+    invalid_sidecar.kzg_proof = KzgProof::empty();
+    batch[invalid_index] = Arc::new(invalid_sidecar);
+
+    let kzg_results = harness
+        .chain
+        .batch_verify_kzg_for_gossip_blobs(batch.clone());
+    assert_eq!(kzg_results.len(), batch.len());
+    assert!(kzg_results.iter().all(Result::is_err));
+    assert!(matches!(
+        harness
+            .chain
+            .verify_blob_sidecar_for_gossip(batch[invalid_index].clone(), invalid_index as u64),
+        Err(GossipBlobError::KzgError(_))
+    ));
+
+    // Without the invalid blob the batch is verified, and each blob completes gossip
+    // verification.
+    batch.remove(invalid_index);
+    let kzg_results = harness
+        .chain
+        .batch_verify_kzg_for_gossip_blobs(batch.clone());
+    assert_eq!(kzg_results.len(), batch.len());
+    for (kzg_result, sidecar) in kzg_results.into_iter().zip(&batch) {
+        let kzg_verified_blob = kzg_result.expect("blob should be verified in a batch");
+        let verified = harness
+            .chain
+            .verify_kzg_verified_blob_sidecar_for_gossip(kzg_verified_blob, sidecar.index)
+            .expect("blob should be valid");
+        assert_eq!(verified.block_root(), block.canonical_root());
+    }
+
+    // The valid blobs have been observed, so they are rejected when received again.
+    for kzg_result in harness
+        .chain
+        .batch_verify_kzg_for_gossip_blobs(batch.clone())
+    {
+        let kzg_verified_blob = kzg_result.expect("blob should be verified in a batch");
+        let index = kzg_verified_blob.blob_index();
+        assert!(matches!(
+            harness
+                .chain
+                .verify_kzg_verified_blob_sidecar_for_gossip(kzg_verified_blob, index),
+            Err(GossipBlobError::RepeatBlob { .. })
+        ));
+    }
+}
+
 #[tokio::test]
 async fn verify_block_for_gossip_doppelganger_detection() {
     let harness = get_harness(VALIDATOR_COUNT);
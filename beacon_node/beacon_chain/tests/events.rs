@@ -1,11 +1,16 @@
 use beacon_chain::blob_verification::GossipVerifiedBlob;
-use beacon_chain::test_utils::BeaconChainHarness;
+use beacon_chain::test_utils::{AttestationStrategy, BeaconChainHarness, BlockStrategy};
 use eth2::types::{EventKind, SseBlobSidecar};
+use execution_layer::builder_groups::BuilderGroupConfig;
 use rand::rngs::StdRng;
 use rand::SeedableRng;
+use sensitive_url::SensitiveUrl;
 use std::sync::Arc;
+use std::time::Duration;
 use types::blob_sidecar::FixedBlobSidecarList;
-use types::{BlobSidecar, EthSpec, ForkName, MinimalEthSpec};
+use types::{
+    Address, BlobSidecar, Epoch, EthSpec, ForkName, MinimalEthSpec, ProposerPreparationData,
+};
 
 type E = MinimalEthSpec;
 
@@ -95,3 +100,203 @@ async fn blob_sidecar_event_on_process_rpc_blobs() {
     }
     assert_eq!(sse_blobs, expected_sse_blobs);
 }
+
+/// Verifies that payload attributes are only published for proposers whose fee recipient is known,
+/// and that they are published when the head changes.
+#[tokio::test]
+async fn payload_attributes_event_only_for_known_fee_recipients() {
+    let spec = Arc::new(ForkName::Capella.make_genesis_spec(E::default_spec()));
+    let harness = BeaconChainHarness::builder(E::default())
+        .spec(spec)
+        .deterministic_keypairs(8)
+        .fresh_ephemeral_store()
+        .mock_execution_layer()
+        .build();
+    harness.advance_slot();
+
+    // subscribe to payload attributes events
+    let event_handler = harness.chain.event_handler.as_ref().unwrap();
+    let mut payload_attributes_receiver = event_handler.subscribe_payload_attributes();
+
+    let execution_layer = harness.chain.execution_layer.as_ref().unwrap();
+    let current_slot = harness.chain.slot().unwrap();
+    let prepare_slot = current_slot + 1;
+    let head = harness.chain.head_snapshot();
+    let proposer = head
+        .beacon_state
+        .get_beacon_proposer_index(prepare_slot, &harness.chain.spec)
+        .unwrap();
+
+    // No proposers are registered, so nothing is published.
+    harness
+        .chain
+        .prepare_beacon_proposer(current_slot)
+        .await
+        .unwrap();
+    assert!(payload_attributes_receiver.try_recv().is_err());
+
+    // Another validator is registered, but the fee recipient of the proposer is still unknown.
+    execution_layer
+        .update_proposer_preparation(
+            Epoch::new(0),
+            &[ProposerPreparationData {
+                validator_index: (proposer as u64 + 1) % 8,
+                fee_recipient: Address::repeat_byte(1),
+            }],
+        )
+        .await;
+    harness
+        .chain
+        .prepare_beacon_proposer(current_slot)
+        .await
+        .unwrap();
+    assert!(payload_attributes_receiver.try_recv().is_err());
+
+    // Once the proposer is registered its payload attributes are published.
+    let fee_recipient = Address::repeat_byte(99);
+    execution_layer
+        .update_proposer_preparation(
+            Epoch::new(0),
+            &[ProposerPreparationData {
+                validator_index: proposer as u64,
+                fee_recipient,
+            }],
+        )
+        .await;
+    harness
+        .chain
+        .prepare_beacon_proposer(current_slot)
+        .await
+        .unwrap();
+    let EventKind::PayloadAttributes(event) = payload_attributes_receiver.try_recv().unwrap()
+    else {
+        panic!("`PayloadAttributes` event kind expected.");
+    };
+    assert_eq!(event.data.proposal_slot, prepare_slot);
+    assert_eq!(event.data.proposer_index, proposer as u64);
+    assert_eq!(event.data.parent_block_root, head.beacon_block_root);
+    assert_eq!(event.data.proposer_fee_recipient, Some(fee_recipient));
+    assert_eq!(
+        event.data.payload_attributes.suggested_fee_recipient(),
+        fee_recipient
+    );
+    assert_eq!(
+        event.data.parent_gas_limit,
+        harness
+            .chain
+            .canonical_head
+            .cached_head()
+            .head_gas_limit()
+            .unwrap()
+    );
+
+    // Register every validator, then check that importing a block publishes the attributes for
+    // the next slot without any explicit call to `prepare_beacon_proposer`.
+    let all_validators = (0..8)
+        .map(|validator_index| ProposerPreparationData {
+            validator_index,
+            fee_recipient,
+        })
+        .collect::<Vec<_>>();
+    execution_layer
+        .update_proposer_preparation(Epoch::new(0), &all_validators)
+        .await;
+    harness
+        .extend_chain(
+            1,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+    let new_head_root = harness.head_block_root();
+    let event = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            match payload_attributes_receiver.recv().await.unwrap() {
+                EventKind::PayloadAttributes(event)
+                    if event.data.parent_block_root == new_head_root =>
+                {
+                    break event
+                }
+                _ => continue,
+            }
+        }
+    })
+    .await
+    .expect("payload attributes should be published on a head change");
+    assert_eq!(event.data.proposer_fee_recipient, Some(fee_recipient));
+}
+
+/// Verifies that a blinded proposal is only expected from a proposer with a builder, which may be
+/// the builder of its builder group rather than the default builder.
+#[tokio::test]
+async fn payload_attributes_event_blinded_proposal_expected_for_builder_group() {
+    let spec = Arc::new(ForkName::Capella.make_genesis_spec(E::default_spec()));
+    let harness = BeaconChainHarness::builder(E::default())
+        .spec(spec)
+        .deterministic_keypairs(8)
+        .fresh_ephemeral_store()
+        .mock_execution_layer()
+        .build();
+    harness.advance_slot();
+
+    let event_handler = harness.chain.event_handler.as_ref().unwrap();
+    let mut payload_attributes_receiver = event_handler.subscribe_payload_attributes();
+
+    let execution_layer = harness.chain.execution_layer.as_ref().unwrap();
+    assert!(execution_layer.builder().is_none());
+    let current_slot = harness.chain.slot().unwrap();
+    let prepare_slot = current_slot + 1;
+    let proposer = harness
+        .chain
+        .head_snapshot()
+        .beacon_state
+        .get_beacon_proposer_index(prepare_slot, &harness.chain.spec)
+        .unwrap();
+    execution_layer
+        .update_proposer_preparation(
+            Epoch::new(0),
+            &[ProposerPreparationData {
+                validator_index: proposer as u64,
+                fee_recipient: Address::repeat_byte(99),
+            }],
+        )
+        .await;
+
+    // Without any builder the proposer builds its own payload.
+    harness
+        .chain
+        .prepare_beacon_proposer(current_slot)
+        .await
+        .unwrap();
+    let EventKind::PayloadAttributes(event) = payload_attributes_receiver.try_recv().unwrap()
+    else {
+        panic!("`PayloadAttributes` event kind expected.");
+    };
+    assert!(!event.data.blinded_proposal_expected);
+
+    // Once the proposer is in a group with its own builder a blinded proposal is expected, even
+    // though there is still no default builder.
+    execution_layer
+        .set_builder_groups(
+            vec![BuilderGroupConfig {
+                name: "group".to_string(),
+                builder_url: Some(SensitiveUrl::parse("http://127.0.0.1:18550").unwrap()),
+                min_bid_gwei: None,
+                validators: vec![harness.validator_keypairs[proposer].pk.compress()],
+            }],
+            None,
+            None,
+        )
+        .unwrap();
+    harness
+        .chain
+        .prepare_beacon_proposer(current_slot)
+        .await
+        .unwrap();
+    let EventKind::PayloadAttributes(event) = payload_attributes_receiver.try_recv().unwrap()
+    else {
+        panic!("`PayloadAttributes` event kind expected.");
+    };
+    assert_eq!(event.data.proposer_index, proposer as u64);
+    assert!(event.data.blinded_proposal_expected);
+}
@@ -0,0 +1,170 @@
+#![cfg(not(debug_assertions))]
+
+use beacon_chain::{
+    test_utils::{AttestationStrategy, BeaconChainHarness, BlockStrategy, EphemeralHarnessType},
+    BeaconBlockResponseWrapper, ChainConfig, ProduceBlockVerification,
+};
+use logging::test_logger;
+use slot_clock::SlotClock;
+use std::time::Duration;
+use types::payload::BlockProductionVersion;
+use types::*;
+
+// Enough validators that each slot has a committee of 8.
+const VALIDATOR_COUNT: usize = 256;
+
+type E = MainnetEthSpec;
+
+struct LatePayloadTest {
+    /// Whether the committee of the head's slot votes for the head rather than its parent.
+    head_votes: bool,
+    /// The late payload reveal threshold.
+    reveal_threshold: Duration,
+    /// The time into its slot at which the head block is received.
+    observed_delay: Duration,
+}
+
+/// Returns the parent of a block produced in the slot after a head which was received at
+/// `observed_delay` into its slot, along with the roots of the head's parent and the head.
+async fn late_payload_test(
+    LatePayloadTest {
+        head_votes,
+        reveal_threshold,
+        observed_delay,
+    }: LatePayloadTest,
+) -> (Hash256, Hash256, Hash256) {
+    let mut spec = ForkName::latest().make_genesis_spec(E::default_spec());
+    spec.terminal_total_difficulty = Uint256::from(1);
+
+    let harness = BeaconChainHarness::<EphemeralHarnessType<E>>::builder(E::default())
+        .spec(spec.into())
+        .chain_config(ChainConfig {
+            late_payload_reveal_threshold: Some(reveal_threshold),
+            ..ChainConfig::default()
+        })
+        .logger(test_logger())
+        .deterministic_keypairs(VALIDATOR_COUNT)
+        .mock_execution_layer()
+        .fresh_ephemeral_store()
+        .build();
+
+    let mock_el = harness.mock_execution_layer.as_ref().unwrap();
+    mock_el.server.all_payloads_valid();
+    mock_el
+        .server
+        .ctx
+        .execution_block_generator
+        .write()
+        .move_to_terminal_block()
+        .unwrap();
+
+    // Build some chain depth, ending with block A at slot 4.
+    harness.advance_slot();
+    harness
+        .extend_chain(
+            4,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+    let (state_a, state_a_root) = harness.get_current_state_and_root();
+    let block_a_root = harness.head_block_root();
+    let slot_b = state_a.slot() + 1;
+    let slot_c = slot_b + 1;
+
+    // Block B is received `observed_delay` into its slot.
+    let slot_clock = &harness.chain.slot_clock;
+    let (block_b, state_b) = harness.make_block(state_a.clone(), slot_b).await;
+    let block_b_root = block_b.0.canonical_root();
+    let observed_at = slot_clock.start_of(slot_b).unwrap() + observed_delay;
+    slot_clock.set_current_time(observed_at);
+    harness.chain.block_times_cache.write().set_time_observed(
+        block_b_root,
+        slot_b,
+        observed_at,
+        None,
+        None,
+    );
+    harness.process_block_result(block_b).await.unwrap();
+
+    // The committee of slot B votes for either block B or block A.
+    let all_validators = (0..VALIDATOR_COUNT).collect::<Vec<_>>();
+    let attestations = if head_votes {
+        let state_b_root = state_b.canonical_root().unwrap();
+        harness.make_attestations(
+            &all_validators,
+            &state_b,
+            state_b_root,
+            block_b_root.into(),
+            slot_b,
+        )
+    } else {
+        harness.make_attestations(
+            &all_validators,
+            &state_a,
+            state_a_root,
+            block_a_root.into(),
+            slot_b,
+        )
+    };
+    harness.process_attestations(attestations);
+
+    // Produce block C at the start of the next slot.
+    harness.set_current_slot(slot_c);
+    harness.chain.recompute_head_at_slot(slot_c).await;
+    assert_eq!(harness.head_block_root(), block_b_root);
+
+    let BeaconBlockResponseWrapper::Full(block_c) = harness
+        .chain
+        .produce_block_with_verification(
+            Signature::empty(),
+            slot_c,
+            None,
+            ProduceBlockVerification::NoVerification,
+            None,
+            None,
+            BlockProductionVersion::FullV2,
+        )
+        .await
+        .unwrap()
+    else {
+        panic!("should produce a full block");
+    };
+
+    (block_c.block.parent_root(), block_a_root, block_b_root)
+}
+
+#[tokio::test]
+async fn refuses_recently_revealed_payload_on_weak_head() {
+    let (parent, block_a_root, _) = late_payload_test(LatePayloadTest {
+        head_votes: false,
+        reveal_threshold: Duration::from_secs(10),
+        // Received before the attestation deadline, so a proposer re-org isn't attempted.
+        observed_delay: Duration::from_secs(3),
+    })
+    .await;
+    assert_eq!(parent, block_a_root);
+}
+
+#[tokio::test]
+async fn builds_on_recently_revealed_payload_on_strong_head() {
+    // Building on the parent would fail to re-org the head, so the head is built upon.
+    let (parent, _, block_b_root) = late_payload_test(LatePayloadTest {
+        head_votes: true,
+        reveal_threshold: Duration::from_secs(10),
+        observed_delay: Duration::from_secs(3),
+    })
+    .await;
+    assert_eq!(parent, block_b_root);
+}
+
+#[tokio::test]
+async fn builds_on_payload_revealed_before_threshold() {
+    let (parent, _, block_b_root) = late_payload_test(LatePayloadTest {
+        head_votes: false,
+        reveal_threshold: Duration::from_secs(8),
+        observed_delay: Duration::from_secs(3),
+    })
+    .await;
+    assert_eq!(parent, block_b_root);
+}
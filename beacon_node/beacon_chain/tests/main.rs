@@ -4,6 +4,7 @@ mod bellatrix;
 mod block_verification;
 mod capella;
 mod events;
+mod late_payload;
 mod op_verification;
 mod payload_invalidation;
 mod rewards;
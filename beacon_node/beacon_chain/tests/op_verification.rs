@@ -6,15 +6,17 @@ use beacon_chain::{
     observed_operations::ObservationOutcome,
     test_utils::{
         test_spec, AttestationStrategy, BeaconChainHarness, BlockStrategy, DiskHarnessType,
+        OP_POOL_DB_KEY,
     },
     BeaconChainError,
 };
+use operation_pool::PersistedOperationPool;
 use sloggers::{null::NullLoggerBuilder, Build};
 use state_processing::per_block_processing::errors::{
     AttesterSlashingInvalid, BlockOperationError, ExitInvalid, ProposerSlashingInvalid,
 };
 use std::sync::{Arc, LazyLock};
-use store::{LevelDB, StoreConfig};
+use store::{ItemStore, LevelDB, StoreConfig};
 use tempfile::{tempdir, TempDir};
 use types::*;
 
@@ -122,6 +124,67 @@ async fn voluntary_exit() {
     ));
 }
 
+#[tokio::test]
+async fn voluntary_exit_persisted_without_attestations() {
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+    let harness = get_harness(store.clone(), VALIDATOR_COUNT);
+    let spec = &harness.chain.spec.clone();
+
+    harness
+        .extend_chain(
+            (E::slots_per_epoch() * (spec.shard_committee_period + 1)) as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let exit = harness.make_voluntary_exit(0, Epoch::new(spec.shard_committee_period));
+    let ObservationOutcome::New(verified_exit) = harness
+        .chain
+        .verify_voluntary_exit_for_gossip(exit.clone())
+        .unwrap()
+    else {
+        panic!("exit should verify");
+    };
+    harness.chain.import_voluntary_exit(verified_exit);
+    assert!(harness.chain.op_pool.num_attestations() > 0);
+
+    harness
+        .chain
+        .persist_op_pool_operations()
+        .expect("should persist op pool operations");
+
+    let restored_op_pool = store
+        .get_item::<PersistedOperationPool<E>>(&OP_POOL_DB_KEY)
+        .expect("should read db")
+        .expect("should find op pool")
+        .into_operation_pool()
+        .unwrap();
+    assert_eq!(restored_op_pool.get_all_voluntary_exits(), vec![exit]);
+    assert_eq!(restored_op_pool.num_attestations(), 0);
+
+    // The operations are persisted again at the next epoch transition.
+    store
+        .hot_db
+        .delete::<PersistedOperationPool<E>>(&OP_POOL_DB_KEY)
+        .unwrap();
+    harness
+        .extend_chain(
+            E::slots_per_epoch() as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+    let restored_op_pool = store
+        .get_item::<PersistedOperationPool<E>>(&OP_POOL_DB_KEY)
+        .expect("should read db")
+        .expect("should find op pool")
+        .into_operation_pool()
+        .unwrap();
+    assert_eq!(restored_op_pool.num_attestations(), 0);
+}
+
 #[tokio::test]
 async fn voluntary_exit_duplicate_in_state() {
     let db_path = tempdir().unwrap();
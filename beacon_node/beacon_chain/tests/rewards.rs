@@ -373,6 +373,56 @@ async fn test_rewards_altair() {
     assert_eq!(expected_balances, balances);
 }
 
+#[tokio::test]
+async fn test_block_reward_explanation_altair() {
+    let spec = ForkName::Altair.make_genesis_spec(E::default_spec());
+    let harness = get_harness(spec.clone());
+
+    harness.extend_slots(E::slots_per_epoch() as usize).await;
+
+    for _ in 0..E::slots_per_epoch() {
+        let state = harness.get_current_state();
+        let slot = state.slot() + Slot::new(1);
+
+        let ((signed_block, _maybe_blob_sidecars), mut state) =
+            harness.make_block_return_pre_state(state, slot).await;
+        let beacon_block_reward = harness
+            .chain
+            .compute_beacon_block_reward(signed_block.message(), &mut state.clone())
+            .unwrap();
+        let explanation = harness
+            .chain
+            .explain_beacon_block_reward(
+                signed_block.message(),
+                signed_block.canonical_root(),
+                &mut state,
+            )
+            .unwrap();
+
+        // The breakdown must agree with the totals.
+        assert_eq!(explanation.total, beacon_block_reward.total);
+        assert_eq!(explanation.slot, slot);
+        assert_eq!(
+            explanation.attestations.len(),
+            signed_block.message().body().attestations_len()
+        );
+        assert_eq!(
+            explanation
+                .attestations
+                .iter()
+                .map(|attestation| attestation.reward)
+                .sum::<u64>(),
+            beacon_block_reward.attestations
+        );
+        assert!(explanation
+            .attestations
+            .iter()
+            .all(|attestation| attestation.attesters > 0));
+
+        harness.extend_slots(1).await;
+    }
+}
+
 #[tokio::test]
 async fn test_rewards_altair_inactivity_leak() {
     let spec = ForkName::Altair.make_genesis_spec(E::default_spec());
@@ -4,6 +4,7 @@ use beacon_chain::attestation_verification::Error as AttnError;
 use beacon_chain::block_verification_types::RpcBlock;
 use beacon_chain::builder::BeaconChainBuilder;
 use beacon_chain::data_availability_checker::AvailableBlock;
+use beacon_chain::persisted_caches::take_persisted_caches;
 use beacon_chain::schema_change::migrate_schema;
 use beacon_chain::test_utils::SyncCommitteeStrategy;
 use beacon_chain::test_utils::{
@@ -12,23 +13,29 @@ use beacon_chain::test_utils::{
 };
 use beacon_chain::{
     data_availability_checker::MaybeAvailableBlock, historical_blocks::HistoricalBlockError,
-    migrate::MigratorConfig, BeaconChain, BeaconChainError, BeaconChainTypes, BeaconSnapshot,
-    BlockError, ChainConfig, NotifyExecutionLayer, ServerSentEventHandler, WhenSlotSkipped,
+    migrate::MigratorConfig, BackfillProfile, BeaconChain, BeaconChainError, BeaconChainTypes,
+    BeaconSnapshot, BlockError, ChainConfig, NotifyExecutionLayer, ServerSentEventHandler,
+    WhenSlotSkipped,
 };
 use logging::test_logger;
 use maplit::hashset;
 use rand::Rng;
 use slot_clock::{SlotClock, TestingSlotClock};
+use ssz::Encode;
 use state_processing::{state_advance::complete_state_advance, BlockReplayer};
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::convert::TryInto;
+use std::num::NonZeroU64;
 use std::sync::{Arc, LazyLock};
 use std::time::Duration;
-use store::metadata::{SchemaVersion, CURRENT_SCHEMA_VERSION, STATE_UPPER_LIMIT_NO_RETAIN};
+use store::metadata::{
+    AnchorInfoV22, SchemaVersion, ANCHOR_INFO_KEY, CURRENT_SCHEMA_VERSION,
+    STATE_UPPER_LIMIT_NO_RETAIN,
+};
 use store::{
     iter::{BlockRootsIterator, StateRootsIterator},
-    BlobInfo, DBColumn, HotColdDB, LevelDB, StoreConfig,
+    BlobInfo, BlobRetention, DBColumn, HotColdDB, KeyValueStore, LevelDB, StoreConfig,
 };
 use tempfile::{tempdir, TempDir};
 use tokio::time::sleep;
@@ -155,6 +162,63 @@ async fn light_client_bootstrap_test() {
     );
 }
 
+#[tokio::test]
+async fn light_client_bootstrap_computed_from_state_test() {
+    let spec = test_spec::<E>();
+    let Some(_) = spec.altair_fork_epoch else {
+        // No-op prior to Altair.
+        return;
+    };
+
+    let db_path = tempdir().unwrap();
+    let store = get_store_generic(&db_path, StoreConfig::default(), spec.clone());
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+    let all_validators = (0..LOW_VALIDATOR_COUNT).collect::<Vec<_>>();
+    let num_initial_slots = E::slots_per_epoch() * 7;
+    let slots: Vec<Slot> = (1..num_initial_slots).map(Slot::new).collect();
+
+    // Import blocks without computing any light client data, so no sync committee branches are
+    // persisted.
+    let (genesis_state, genesis_state_root) = harness.get_current_state_and_root();
+    harness
+        .add_attested_blocks_at_slots(
+            genesis_state.clone(),
+            genesis_state_root,
+            &slots,
+            &all_validators,
+        )
+        .await;
+
+    let block_root = harness
+        .chain
+        .canonical_head
+        .cached_head()
+        .finalized_checkpoint()
+        .root;
+    assert!(store
+        .get_sync_committee_branch(&block_root)
+        .unwrap()
+        .is_none());
+
+    let (lc_bootstrap, _) = harness
+        .chain
+        .get_light_client_bootstrap(&block_root)
+        .unwrap()
+        .unwrap();
+
+    // The computed branch is persisted and used for subsequent requests.
+    assert!(store
+        .get_sync_committee_branch(&block_root)
+        .unwrap()
+        .is_some());
+    let (lc_bootstrap_from_db, _) = harness
+        .chain
+        .get_light_client_bootstrap(&block_root)
+        .unwrap()
+        .unwrap();
+    assert_eq!(lc_bootstrap, lc_bootstrap_from_db);
+}
+
 #[tokio::test]
 async fn light_client_updates_test() {
     let spec = test_spec::<E>();
@@ -207,6 +271,14 @@ async fn light_client_updates_test() {
 
     assert_eq!(lc_updates.len(), 1);
 
+    // Populate the encoded update cache, which should be invalidated by better updates.
+    let encoded_updates = harness
+        .chain
+        .get_encoded_light_client_updates(sync_period, 100)
+        .unwrap();
+    assert_eq!(encoded_updates.len(), 1);
+    assert_eq!(encoded_updates[0].bytes, lc_updates[0].as_ssz_bytes());
+
     // Advance to the next sync committee period
     for _i in 0..(E::slots_per_epoch() * u64::from(spec.epochs_per_sync_committee_period)) {
         harness.advance_slot();
@@ -227,6 +299,29 @@ async fn light_client_updates_test() {
         .unwrap();
 
     assert_eq!(lc_updates.len(), 2);
+
+    // The encoded updates match the persisted updates.
+    let encoded_updates = harness
+        .chain
+        .get_encoded_light_client_updates(sync_period, 100)
+        .unwrap();
+    assert_eq!(encoded_updates.len(), 2);
+    for (encoded_update, lc_update) in encoded_updates.iter().zip(&lc_updates) {
+        assert_eq!(encoded_update.bytes, lc_update.as_ssz_bytes());
+        assert_eq!(
+            encoded_update.fork_name,
+            lc_update.map_with_fork_name(|fork_name| fork_name)
+        );
+    }
+
+    // Each period's update is persisted along with its rank.
+    for (period, lc_update) in (sync_period..).zip(&lc_updates) {
+        let rank = store
+            .get_light_client_update_rank(period)
+            .unwrap()
+            .expect("rank should be persisted");
+        assert_eq!(rank, lc_update.rank(&spec).unwrap());
+    }
 }
 
 #[tokio::test]
@@ -2557,6 +2652,308 @@ async fn weak_subjectivity_sync_test(slots: Vec<Slot>, checkpoint_slot: Slot) {
     assert_eq!(store.get_anchor_info().anchor_slot, 0);
 }
 
+/// Test that backfill after a weak subjectivity sync stops at the backfill target epoch, and that
+/// the target is recorded in the anchor.
+#[tokio::test]
+async fn weak_subjectivity_sync_backfill_stops_at_target() {
+    let checkpoint_slot = Slot::new(E::slots_per_epoch() * 4);
+    let backfill_target_epoch = Epoch::new(1);
+    let backfill_target_slot = backfill_target_epoch.start_slot(E::slots_per_epoch());
+
+    // Build an initial chain on one harness, representing a synced node with full history.
+    let temp1 = tempdir().unwrap();
+    let full_store = get_store(&temp1);
+    let harness = get_harness(full_store.clone(), LOW_VALIDATOR_COUNT);
+    let all_validators = (0..LOW_VALIDATOR_COUNT).collect::<Vec<_>>();
+
+    let (genesis_state, genesis_state_root) = harness.get_current_state_and_root();
+    let slots = (1..=checkpoint_slot.as_u64())
+        .map(Slot::new)
+        .collect::<Vec<_>>();
+    harness
+        .add_attested_blocks_at_slots(
+            genesis_state.clone(),
+            genesis_state_root,
+            &slots,
+            &all_validators,
+        )
+        .await;
+
+    let wss_block_root = harness
+        .chain
+        .block_root_at_slot(checkpoint_slot, WhenSlotSkipped::Prev)
+        .unwrap()
+        .unwrap();
+    let wss_state_root = harness
+        .chain
+        .state_root_at_slot(checkpoint_slot)
+        .unwrap()
+        .unwrap();
+    let wss_block = harness
+        .chain
+        .store
+        .get_full_block(&wss_block_root)
+        .unwrap()
+        .unwrap();
+    let wss_blobs_opt = harness.chain.store.get_blobs(&wss_block_root).unwrap();
+    let wss_state = full_store
+        .get_state(&wss_state_root, Some(checkpoint_slot))
+        .unwrap()
+        .unwrap();
+
+    // Initialise a new beacon chain from the checkpoint, with a backfill target.
+    let (shutdown_tx, _shutdown_rx) = futures::channel::mpsc::channel(1);
+    let log = harness.chain.logger().clone();
+    let temp2 = tempdir().unwrap();
+    let store = get_store(&temp2);
+    let spec = test_spec::<E>();
+    let kzg = get_kzg(&spec);
+    let mock =
+        mock_execution_layer_from_parts(&harness.spec, harness.runtime.task_executor.clone());
+    let slot_clock = TestingSlotClock::new(
+        Slot::new(0),
+        Duration::from_secs(harness.chain.genesis_time),
+        Duration::from_secs(spec.seconds_per_slot),
+    );
+    slot_clock.set_slot(harness.get_current_slot().as_u64());
+
+    let beacon_chain = BeaconChainBuilder::<DiskHarnessType<E>>::new(MinimalEthSpec, kzg)
+        .store(store.clone())
+        .custom_spec(test_spec::<E>().into())
+        .task_executor(harness.chain.task_executor.clone())
+        .logger(log.clone())
+        .weak_subjectivity_state(wss_state, wss_block.clone(), wss_blobs_opt, genesis_state)
+        .unwrap()
+        .store_migrator_config(MigratorConfig::default().blocking())
+        .dummy_eth1_backend()
+        .expect("should build dummy backend")
+        .slot_clock(slot_clock)
+        .shutdown_sender(shutdown_tx)
+        .chain_config(ChainConfig {
+            backfill_profile: BackfillProfile::Target(backfill_target_epoch),
+            ..ChainConfig::default()
+        })
+        .event_handler(Some(ServerSentEventHandler::new_with_capacity(
+            log.clone(),
+            1,
+        )))
+        .execution_layer(Some(mock.el))
+        .build()
+        .expect("should build");
+
+    assert_eq!(beacon_chain.genesis_backfill_slot, backfill_target_slot);
+    let anchor = store.get_anchor_info();
+    assert_eq!(anchor.backfill_target_slot, backfill_target_slot);
+    assert!(!anchor.block_backfill_complete(beacon_chain.genesis_backfill_slot));
+
+    // Supply every historical block in a single batch.
+    let mut available_blocks = vec![];
+    for slot in 1..checkpoint_slot.as_u64() {
+        let block_root = harness
+            .chain
+            .block_root_at_slot(Slot::new(slot), WhenSlotSkipped::None)
+            .unwrap()
+            .unwrap();
+        let full_block = harness.chain.get_block(&block_root).await.unwrap().unwrap();
+        let blobs = harness.chain.get_blobs(&block_root).expect("blobs");
+        if let MaybeAvailableBlock::Available(block) = harness
+            .chain
+            .data_availability_checker
+            .verify_kzg_for_rpc_block(
+                RpcBlock::new(Some(block_root), Arc::new(full_block), Some(blobs)).unwrap(),
+            )
+            .expect("should verify kzg")
+        {
+            available_blocks.push(block);
+        }
+    }
+
+    // Backfill stops precisely at the target, ignoring the older blocks in the batch.
+    let num_imported = beacon_chain
+        .import_historical_block_batch(available_blocks.clone())
+        .unwrap();
+    assert_eq!(
+        num_imported as u64,
+        checkpoint_slot.as_u64() - backfill_target_slot.as_u64()
+    );
+    let anchor = store.get_anchor_info();
+    assert_eq!(anchor.oldest_block_slot, backfill_target_slot);
+    assert_eq!(anchor.backfill_target_slot, backfill_target_slot);
+    assert!(anchor.block_backfill_complete(beacon_chain.genesis_backfill_slot));
+    assert_eq!(
+        beacon_chain
+            .block_root_at_slot(backfill_target_slot - 1, WhenSlotSkipped::None)
+            .unwrap(),
+        None
+    );
+
+    // Resupplying the blocks imports nothing once the target has been reached.
+    assert_eq!(
+        beacon_chain
+            .import_historical_block_batch(available_blocks)
+            .unwrap(),
+        0
+    );
+    assert_eq!(
+        store.get_anchor_info().oldest_block_slot,
+        backfill_target_slot
+    );
+}
+
+/// Test that the history of a weak subjectivity synced node can be filled in from era files.
+#[tokio::test]
+async fn weak_subjectivity_sync_import_era_files() {
+    let slots_per_era = E::slots_per_historical_root() as u64;
+    let checkpoint_slot = Slot::new(slots_per_era * 2);
+
+    // Build an initial chain on one harness, representing a synced node with full history.
+    let temp1 = tempdir().unwrap();
+    let full_store = get_store(&temp1);
+    let harness = get_harness(full_store.clone(), LOW_VALIDATOR_COUNT);
+    let all_validators = (0..LOW_VALIDATOR_COUNT).collect::<Vec<_>>();
+
+    let (genesis_state, genesis_state_root) = harness.get_current_state_and_root();
+    let slots = (1..=checkpoint_slot.as_u64())
+        .map(Slot::new)
+        .collect::<Vec<_>>();
+    harness
+        .add_attested_blocks_at_slots(
+            genesis_state.clone(),
+            genesis_state_root,
+            &slots,
+            &all_validators,
+        )
+        .await;
+
+    let wss_block_root = harness
+        .chain
+        .block_root_at_slot(checkpoint_slot, WhenSlotSkipped::Prev)
+        .unwrap()
+        .unwrap();
+    let wss_state_root = harness
+        .chain
+        .state_root_at_slot(checkpoint_slot)
+        .unwrap()
+        .unwrap();
+    let wss_block = harness
+        .chain
+        .store
+        .get_full_block(&wss_block_root)
+        .unwrap()
+        .unwrap();
+    let wss_blobs_opt = harness.chain.store.get_blobs(&wss_block_root).unwrap();
+    let wss_state = full_store
+        .get_state(&wss_state_root, Some(checkpoint_slot))
+        .unwrap()
+        .unwrap();
+
+    // Initialise a new beacon chain from the checkpoint.
+    let (shutdown_tx, _shutdown_rx) = futures::channel::mpsc::channel(1);
+    let log = harness.chain.logger().clone();
+    let temp2 = tempdir().unwrap();
+    let store = get_store(&temp2);
+    let spec = test_spec::<E>();
+    let kzg = get_kzg(&spec);
+    let mock =
+        mock_execution_layer_from_parts(&harness.spec, harness.runtime.task_executor.clone());
+    let slot_clock = TestingSlotClock::new(
+        Slot::new(0),
+        Duration::from_secs(harness.chain.genesis_time),
+        Duration::from_secs(spec.seconds_per_slot),
+    );
+    slot_clock.set_slot(harness.get_current_slot().as_u64());
+
+    let beacon_chain = BeaconChainBuilder::<DiskHarnessType<E>>::new(MinimalEthSpec, kzg)
+        .store(store.clone())
+        .custom_spec(test_spec::<E>().into())
+        .task_executor(harness.chain.task_executor.clone())
+        .logger(log.clone())
+        .weak_subjectivity_state(wss_state, wss_block.clone(), wss_blobs_opt, genesis_state)
+        .unwrap()
+        .store_migrator_config(MigratorConfig::default().blocking())
+        .dummy_eth1_backend()
+        .expect("should build dummy backend")
+        .slot_clock(slot_clock)
+        .shutdown_sender(shutdown_tx)
+        .chain_config(ChainConfig::default())
+        .event_handler(Some(ServerSentEventHandler::new_with_capacity(
+            log.clone(),
+            1,
+        )))
+        .execution_layer(Some(mock.el))
+        .build()
+        .expect("should build");
+    assert_eq!(store.get_anchor_info().oldest_block_slot, checkpoint_slot);
+
+    // Write the eras preceding the checkpoint from the full node's database.
+    let era_dir = tempdir().unwrap();
+    for era in 1..=2 {
+        let era_start_slot = (era - 1) * slots_per_era;
+        let era_end_slot = Slot::new(era * slots_per_era);
+        let mut blocks = vec![];
+        for slot in era_start_slot..era_end_slot.as_u64() {
+            let block_root = harness
+                .chain
+                .block_root_at_slot(Slot::new(slot), WhenSlotSkipped::None)
+                .unwrap()
+                .unwrap();
+            let block = harness.chain.get_block(&block_root).await.unwrap().unwrap();
+            blocks.push(block);
+        }
+        let state_root = harness
+            .chain
+            .state_root_at_slot(era_end_slot)
+            .unwrap()
+            .unwrap();
+        let state = full_store
+            .get_state(&state_root, Some(era_end_slot))
+            .unwrap()
+            .unwrap();
+
+        let mut bytes = vec![];
+        store::era::write_era(&mut bytes, &blocks, &state).unwrap();
+        std::fs::write(
+            era_dir
+                .path()
+                .join(format!("minimal-{era:05}-00000000.era")),
+            bytes,
+        )
+        .unwrap();
+
+        // The first era can't be imported until the second one has been.
+        if era == 1 {
+            assert!(beacon_chain.import_era_files(era_dir.path()).is_err());
+            assert_eq!(store.get_anchor_info().oldest_block_slot, checkpoint_slot);
+        }
+    }
+
+    let num_imported = beacon_chain.import_era_files(era_dir.path()).unwrap();
+    assert_eq!(num_imported as u64, checkpoint_slot.as_u64());
+    let anchor = store.get_anchor_info();
+    assert_eq!(anchor.oldest_block_slot, 0);
+    assert!(anchor.oldest_block_parent.is_zero());
+
+    for slot in 0..checkpoint_slot.as_u64() {
+        let block_root = harness
+            .chain
+            .block_root_at_slot(Slot::new(slot), WhenSlotSkipped::None)
+            .unwrap();
+        assert_eq!(
+            beacon_chain
+                .block_root_at_slot(Slot::new(slot), WhenSlotSkipped::None)
+                .unwrap(),
+            block_root
+        );
+        assert!(store
+            .get_blinded_block(&block_root.unwrap())
+            .unwrap()
+            .is_some());
+    }
+
+    // Importing the eras again imports nothing.
+    assert_eq!(beacon_chain.import_era_files(era_dir.path()).unwrap(), 0);
+}
+
 /// Test that blocks and attestations that refer to states around an unaligned split state are
 /// processed correctly.
 #[tokio::test]
@@ -2789,6 +3186,91 @@ async fn finalizes_after_resuming_from_db() {
     );
 }
 
+#[tokio::test]
+async fn caches_are_restored_on_restart() {
+    let num_blocks_produced = E::slots_per_epoch() * 3 + 2;
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    harness
+        .extend_chain(
+            num_blocks_produced as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let shufflings = harness.chain.shuffling_cache.read().to_persisted();
+    let proposers = harness.chain.beacon_proposer_cache.lock().to_persisted();
+    assert!(!shufflings.is_empty());
+    assert!(!proposers.is_empty());
+
+    // The parent of the head is cached but is not loaded by the builder on startup.
+    let head = harness.chain.head_snapshot();
+    let parent_state_root = harness
+        .chain
+        .get_blinded_block(&head.beacon_block.parent_root())
+        .unwrap()
+        .unwrap()
+        .state_root();
+    let cached_state_roots = |store: &HotColdDB<E, LevelDB<E>, LevelDB<E>>| {
+        store
+            .state_cache_summaries()
+            .into_iter()
+            .map(|(state_root, _, _)| state_root)
+            .collect::<HashSet<_>>()
+    };
+    assert!(cached_state_roots(&store).contains(&parent_state_root));
+
+    // The chain is persisted once, and not again when it is dropped.
+    assert!(harness.chain.persist_on_shutdown().unwrap());
+    assert!(!harness.chain.persist_on_shutdown().unwrap());
+
+    let slot_clock = harness.chain.slot_clock.clone();
+    drop(store);
+    drop(harness);
+
+    let store = get_store(&db_path);
+    let resumed_harness = BeaconChainHarness::builder(MinimalEthSpec)
+        .default_spec()
+        .keypairs(KEYPAIRS[0..LOW_VALIDATOR_COUNT].to_vec())
+        .logger(store.logger().clone())
+        .testing_slot_clock(slot_clock)
+        .resumed_disk_store(store.clone())
+        .mock_execution_layer()
+        .build();
+
+    let shuffling_cache = resumed_harness.chain.shuffling_cache.read();
+    for shuffling in &shufflings {
+        assert!(shuffling_cache.contains(&shuffling.shuffling_id));
+    }
+    drop(shuffling_cache);
+
+    let mut beacon_proposer_cache = resumed_harness.chain.beacon_proposer_cache.lock();
+    for proposers in &proposers {
+        assert_eq!(
+            beacon_proposer_cache
+                .get_epoch::<E>(proposers.shuffling_decision_block, proposers.epoch)
+                .map(|indices| indices.iter().map(|i| *i as u64).collect::<Vec<_>>()),
+            Some(proposers.proposers.clone())
+        );
+    }
+    drop(beacon_proposer_cache);
+
+    // The states are reloaded in the background after the chain is built.
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while !cached_state_roots(&store).contains(&parent_state_root) {
+            sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("the cached states should be restored in the background");
+
+    // The persisted caches are removed once they have been restored.
+    assert!(take_persisted_caches(&store).unwrap().is_none());
+}
+
 #[tokio::test]
 async fn revert_minority_fork_on_resume() {
     let validator_count = 16;
@@ -3012,6 +3494,17 @@ async fn schema_downgrade_to_min_version() {
     )
     .expect("schema downgrade to minimum version should work");
 
+    // The anchor is written in the format of the minimum version.
+    let anchor_bytes = store
+        .hot_db
+        .get_bytes(DBColumn::BeaconMeta.into(), ANCHOR_INFO_KEY.as_slice())
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        anchor_bytes,
+        AnchorInfoV22::from(store.get_anchor_info()).as_ssz_bytes()
+    );
+
     // Upgrade back.
     migrate_schema::<DiskHarnessType<E>>(
         store.clone(),
@@ -3096,6 +3589,212 @@ async fn deneb_prune_blobs_happy_case() {
     check_blob_existence(&harness, oldest_blob_slot, harness.head_slot(), true);
 }
 
+/// Check that rate-limited blob pruning deletes the same blobs, committing one epoch at a time and
+/// sleeping between batches to stay below the configured rate.
+#[tokio::test]
+async fn deneb_prune_blobs_rate_limited() {
+    // Each blob list is accounted for at its maximum size, so this allows 20 lists per second.
+    let max_blob_list_bytes = (E::max_blobs_per_block() * BlobSidecar::<E>::max_size()) as u64;
+    let lists_per_second = 20;
+    let config = StoreConfig {
+        blob_prune_rate_limit: NonZeroU64::new(max_blob_list_bytes * lists_per_second),
+        ..StoreConfig::default()
+    };
+    let db_path = tempdir().unwrap();
+    let store = get_store_generic(&db_path, config, test_spec::<E>());
+
+    if store.get_chain_spec().deneb_fork_epoch.is_none() {
+        // No-op prior to Deneb.
+        return;
+    }
+
+    let num_blocks_produced = E::slots_per_epoch() * 8;
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    harness
+        .extend_chain(
+            num_blocks_produced as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    // Trigger blob pruning of blobs older than epoch 4, spanning several batches. Only the batches
+    // prior to the last one (epoch 3) are throttled.
+    let data_availability_boundary = Epoch::new(4);
+    let throttled_lists = harness
+        .chain
+        .forwards_iter_block_roots_until(
+            Slot::new(0),
+            Epoch::new(3).start_slot(E::slots_per_epoch()) - 1,
+        )
+        .unwrap()
+        .map(Result::unwrap)
+        .filter(|(block_root, _)| store.blobs_exist(block_root).unwrap())
+        .count() as u64;
+    assert_ne!(throttled_lists, 0, "expected blobs to prune");
+
+    let start = std::time::Instant::now();
+    store
+        .try_prune_blobs(true, data_availability_boundary)
+        .unwrap();
+    let min_elapsed = Duration::from_secs_f64(throttled_lists as f64 / lists_per_second as f64);
+    assert!(
+        start.elapsed() >= min_elapsed,
+        "pruning took {:?}, expected at least {:?}",
+        start.elapsed(),
+        min_elapsed
+    );
+
+    let oldest_blob_slot = store.get_blob_info().oldest_blob_slot.unwrap();
+    assert_eq!(
+        oldest_blob_slot,
+        data_availability_boundary.start_slot(E::slots_per_epoch())
+    );
+    check_blob_existence(&harness, Slot::new(0), oldest_blob_slot - 1, false);
+    check_blob_existence(&harness, oldest_blob_slot, harness.head_slot(), true);
+}
+
+/// Check that blob pruning retains blobs within the configured retention period, even when they
+/// are older than the data availability boundary.
+#[tokio::test]
+async fn deneb_prune_blobs_retention_epochs() {
+    let spec = test_spec::<E>();
+    let config = StoreConfig {
+        blob_retention: BlobRetention::Epochs(spec.min_epochs_for_blob_sidecars_requests + 2),
+        ..StoreConfig::default()
+    };
+    let db_path = tempdir().unwrap();
+    let store = get_store_generic(&db_path, config, spec);
+
+    if store.get_chain_spec().deneb_fork_epoch.is_none() {
+        // No-op prior to Deneb.
+        return;
+    }
+
+    let num_blocks_produced = E::slots_per_epoch() * 8;
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    harness
+        .extend_chain(
+            num_blocks_produced as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    // Blobs are retained for 2 epochs prior to the data availability boundary.
+    let data_availability_boundary = Epoch::new(4);
+    store
+        .try_prune_blobs(true, data_availability_boundary)
+        .unwrap();
+
+    let oldest_blob_slot = store.get_blob_info().oldest_blob_slot.unwrap();
+    assert_eq!(
+        oldest_blob_slot,
+        Epoch::new(2).start_slot(E::slots_per_epoch())
+    );
+    check_blob_existence(&harness, Slot::new(0), oldest_blob_slot - 1, false);
+    check_blob_existence(&harness, oldest_blob_slot, harness.head_slot(), true);
+}
+
+/// Check that blobs are never pruned if they are retained forever, even if pruning is forced.
+#[tokio::test]
+async fn deneb_prune_blobs_retention_forever() {
+    let spec = test_spec::<E>();
+    let config = StoreConfig {
+        blob_retention: BlobRetention::Forever,
+        ..StoreConfig::default()
+    };
+    let db_path = tempdir().unwrap();
+    let store = get_store_generic(&db_path, config, spec);
+
+    if store.get_chain_spec().deneb_fork_epoch.is_none() {
+        // No-op prior to Deneb.
+        return;
+    }
+
+    let num_blocks_produced = E::slots_per_epoch() * 8;
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    harness
+        .extend_chain(
+            num_blocks_produced as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let oldest_blob_slot = store.get_blob_info().oldest_blob_slot.unwrap();
+
+    let data_availability_boundary = Epoch::new(4);
+    store
+        .try_prune_blobs(true, data_availability_boundary)
+        .unwrap();
+
+    assert_eq!(
+        store.get_blob_info().oldest_blob_slot,
+        Some(oldest_blob_slot)
+    );
+    check_blob_existence(&harness, oldest_blob_slot, harness.head_slot(), true);
+}
+
+/// Check that blob pruning retains data columns from the PeerDAS fork onwards if
+/// `data_column_archive` is set, even when they are older than the data availability boundary.
+#[tokio::test]
+async fn prune_blobs_data_column_archive() {
+    let spec = test_spec::<E>();
+    if !spec.is_peer_das_scheduled() {
+        return;
+    }
+    let fork_epoch = spec.eip7594_fork_epoch.unwrap();
+    let fork_slot = fork_epoch.start_slot(E::slots_per_epoch());
+    let config = StoreConfig {
+        data_column_archive: true,
+        ..StoreConfig::default()
+    };
+    let db_path = tempdir().unwrap();
+    let store = get_store_generic(&db_path, config, spec);
+
+    let num_blocks_produced = fork_slot.as_u64() + E::slots_per_epoch() * 8;
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    harness
+        .extend_chain(
+            num_blocks_produced as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    // Without the archive, data columns from the 4 epochs after the fork would be pruned.
+    let data_availability_boundary = fork_epoch + 4;
+    assert!(store.get_split_slot() > data_availability_boundary.start_slot(E::slots_per_epoch()));
+    store
+        .try_prune_blobs(true, data_availability_boundary)
+        .unwrap();
+
+    assert!(store.get_blob_info().oldest_blob_slot.unwrap() <= fork_slot);
+
+    let mut blocks_with_blobs = 0;
+    for (block_root, slot) in harness
+        .chain
+        .forwards_iter_block_roots_until(fork_slot, harness.head_slot())
+        .unwrap()
+        .map(Result::unwrap)
+    {
+        let block = store.get_blinded_block(&block_root).unwrap().unwrap();
+        if block.num_expected_blobs() > 0 {
+            blocks_with_blobs += 1;
+            assert!(
+                !store.get_data_column_keys(block_root).unwrap().is_empty(),
+                "data columns at slot {slot} should be retained"
+            );
+        }
+    }
+    assert_ne!(blocks_with_blobs, 0, "expected blocks with blobs");
+}
+
 /// Check that blob pruning does not prune without finalization.
 #[tokio::test]
 async fn deneb_prune_blobs_no_finalization() {
@@ -3610,6 +4309,64 @@ fn check_iterators(harness: &TestHarness) {
     );
 }
 
+#[tokio::test]
+async fn data_column_custody_info_tracks_custody_increase() {
+    let spec = test_spec::<E>();
+    if !spec.is_peer_das_scheduled() {
+        return;
+    }
+    let fork_slot = spec
+        .eip7594_fork_epoch
+        .unwrap()
+        .start_slot(E::slots_per_epoch());
+
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+    let custody_requirement = spec.custody_requirement;
+
+    // The builder records the initial custody without requiring a backfill.
+    let custody_info = store.load_data_column_custody_info().unwrap().unwrap();
+    assert_eq!(custody_info.custody_subnet_count, custody_requirement);
+    assert_eq!(custody_info.custody_backfill_slot, None);
+
+    harness
+        .extend_chain(
+            fork_slot.as_usize() + 1,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+    let current_slot = harness.chain.slot().unwrap();
+
+    // Increasing the custody requires backfilling from the current slot.
+    let custody_info = harness
+        .chain
+        .update_data_column_custody_info(custody_requirement + 1)
+        .unwrap();
+    assert_eq!(custody_info.custody_backfill_slot, Some(current_slot));
+    assert_eq!(
+        store.load_data_column_custody_info().unwrap(),
+        Some(custody_info.clone())
+    );
+
+    // Restarting with the same custody keeps the existing backfill slot.
+    harness.advance_slot();
+    let unchanged = harness
+        .chain
+        .update_data_column_custody_info(custody_requirement + 1)
+        .unwrap();
+    assert_eq!(unchanged, custody_info);
+
+    // Decreasing the custody never requires a backfill.
+    let decreased = harness
+        .chain
+        .update_data_column_custody_info(custody_requirement)
+        .unwrap();
+    assert_eq!(decreased.custody_subnet_count, custody_requirement);
+    assert_eq!(decreased.custody_backfill_slot, Some(current_slot));
+}
+
 fn get_finalized_epoch_boundary_blocks(
     dump: &[BeaconSnapshot<MinimalEthSpec, BlindedPayload<MinimalEthSpec>>],
 ) -> HashSet<SignedBeaconBlockHash> {
@@ -811,3 +811,104 @@ async fn unaggregated_gossip_verification() {
         if received == subnet_id && !expected.contains(&subnet_id)
     );
 }
+
+/// Tests that batch verification of sync committee messages returns the same results as
+/// individual verification, including when a batch contains an invalid signature.
+#[tokio::test]
+async fn batch_unaggregated_gossip_verification() {
+    let harness = get_harness(VALIDATOR_COUNT);
+    let state = harness.get_current_state();
+
+    harness
+        .add_attested_blocks_at_slots(
+            state,
+            Hash256::zero(),
+            &[Slot::new(1), Slot::new(2)],
+            (0..VALIDATOR_COUNT).collect::<Vec<_>>().as_slice(),
+        )
+        .await;
+
+    let current_slot = harness.chain.slot().expect("should get slot");
+    let parent_root = harness.chain.head_snapshot().beacon_block.parent_root();
+
+    // Find messages from distinct validators, since a validator may appear more than once in
+    // the subcommittee.
+    let mut message_indices = vec![];
+    let mut validator_indices = vec![];
+    for message_index in 0.. {
+        let (_, validator_index, _, _) = get_valid_sync_committee_message(
+            &harness,
+            current_slot,
+            RelativeSyncCommittee::Current,
+            message_index,
+        );
+        if !validator_indices.contains(&validator_index) {
+            validator_indices.push(validator_index);
+            message_indices.push(message_index);
+        }
+        if message_indices.len() == 6 {
+            break;
+        }
+    }
+
+    // Four valid messages for the head block.
+    let mut batch = message_indices[0..4]
+        .iter()
+        .map(|&message_index| {
+            let (message, _, _, subnet_id) = get_valid_sync_committee_message(
+                &harness,
+                current_slot,
+                RelativeSyncCommittee::Current,
+                message_index,
+            );
+            (message, subnet_id)
+        })
+        .collect::<Vec<_>>();
+
+    // A message for the head block with the signature of another validator, which poisons the
+    // batch for the head block.
+    let (mut invalid_message, _, _, subnet_id) = get_valid_sync_committee_message(
+        &harness,
+        current_slot,
+        RelativeSyncCommittee::Current,
+        message_indices[4],
+    );
+    invalid_message.signature = batch[0].0.signature.clone();
+    batch.push((invalid_message, subnet_id));
+
+    // A valid message for a different block, which is verified in a separate group.
+    let (message_to_parent, _, _, subnet_id) = get_valid_sync_committee_message_for_block(
+        &harness,
+        current_slot,
+        RelativeSyncCommittee::Current,
+        message_indices[5],
+        parent_root,
+    );
+    batch.push((message_to_parent, subnet_id));
+
+    let results = harness
+        .chain
+        .batch_verify_sync_committee_messages_for_gossip(batch.clone().into_iter());
+
+    assert_eq!(results.len(), batch.len());
+    for (i, result) in results.iter().enumerate() {
+        if i == 4 {
+            assert!(
+                matches!(result, Err(SyncCommitteeError::InvalidSignature)),
+                "invalid signature should be detected"
+            );
+        } else {
+            assert!(result.is_ok(), "message {} should be valid", i);
+        }
+    }
+
+    // The valid messages have been observed, so they are rejected when received again.
+    batch.remove(4);
+    let results = harness
+        .chain
+        .batch_verify_sync_committee_messages_for_gossip(batch.into_iter());
+    assert!(results.iter().all(|result| matches!(
+        result,
+        Err(SyncCommitteeError::PriorSyncCommitteeMessageKnown { .. })
+    )));
+}
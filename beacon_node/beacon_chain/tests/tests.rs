@@ -2,6 +2,7 @@
 
 use beacon_chain::{
     attestation_verification::Error as AttnError,
+    metrics,
     test_utils::{
         AttestationStrategy, BeaconChainHarness, BlockStrategy, EphemeralHarnessType,
         OP_POOL_DB_KEY,
@@ -11,6 +12,7 @@ use beacon_chain::{
 use operation_pool::PersistedOperationPool;
 use state_processing::{per_slot_processing, per_slot_processing::Error as SlotProcessingError};
 use std::sync::LazyLock;
+use std::time::{Duration, Instant};
 use types::{
     BeaconState, BeaconStateError, BlockImportSource, EthSpec, Hash256, Keypair, MinimalEthSpec,
     RelativeEpoch, Slot,
@@ -870,3 +872,54 @@ async fn block_roots_skip_slot_behaviour() {
         "WhenSlotSkipped::Prev should return None on a future slot"
     );
 }
+
+#[tokio::test]
+async fn produces_blocks_after_block_production_deadline() {
+    let harness = BeaconChainHarness::builder(MinimalEthSpec)
+        .default_spec()
+        .chain_config(ChainConfig {
+            block_production_deadline: Some(Duration::ZERO),
+            ..ChainConfig::default()
+        })
+        .keypairs(KEYPAIRS[0..VALIDATOR_COUNT].to_vec())
+        .fresh_ephemeral_store()
+        .mock_execution_layer()
+        .build();
+    harness.advance_slot();
+
+    // The deadline is at the start of the slot, so it has always passed during block production.
+    let current_slot = harness.chain.slot().unwrap();
+    let deadline = harness
+        .chain
+        .block_production_deadline(current_slot)
+        .expect("deadline is configured");
+    assert!(deadline <= Instant::now());
+
+    let skipped_attestations = || {
+        metrics::get_int_counter(
+            &metrics::BLOCK_PRODUCTION_DEADLINE_SKIPS,
+            &["unaggregated_attestations"],
+        )
+        .map_or(0, |counter| counter.get())
+    };
+    let skipped_before = skipped_attestations();
+
+    // Blocks are still produced, without waiting on the components skipped after the deadline.
+    let num_blocks = MinimalEthSpec::slots_per_epoch() * 2;
+    harness
+        .extend_chain(
+            num_blocks as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    assert_eq!(
+        harness.chain.head_snapshot().beacon_block.slot(),
+        Slot::new(num_blocks)
+    );
+
+    // The attestations in the naive aggregation pool were skipped rather than imported into the
+    // op pool.
+    assert!(skipped_attestations() > skipped_before);
+}
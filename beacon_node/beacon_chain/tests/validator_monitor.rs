@@ -1,9 +1,14 @@
 use beacon_chain::test_utils::{
     AttestationStrategy, BeaconChainHarness, BlockStrategy, EphemeralHarnessType,
 };
-use beacon_chain::validator_monitor::{ValidatorMonitorConfig, MISSED_BLOCK_LAG_SLOTS};
+use beacon_chain::validator_monitor::{
+    EpochSummary, ValidatorMonitorConfig, MISSED_BLOCK_LAG_SLOTS,
+};
+use eth2::lighthouse::AttestationOutcome;
 use logging::test_logger;
+use slot_clock::SlotClock;
 use std::sync::LazyLock;
+use std::time::Duration;
 use types::{Epoch, EthSpec, ForkName, Keypair, MainnetEthSpec, PublicKeyBytes, Slot};
 
 // Should ideally be divisible by 3.
@@ -380,3 +385,135 @@ async fn produces_missed_blocks() {
         );
     }
 }
+
+#[tokio::test]
+async fn registers_rpc_block_delay() {
+    let validator_count = 16;
+    let harness = get_harness(validator_count, (0..validator_count).collect());
+
+    harness
+        .extend_chain(
+            1,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let head = harness.chain.head_snapshot();
+    let block = head.beacon_block.message();
+    let epoch = block.slot().epoch(E::slots_per_epoch());
+    let delay = Duration::from_millis(1500);
+    let seen_timestamp = harness.chain.slot_clock.start_of(block.slot()).unwrap() + delay;
+
+    let validator_monitor = harness.chain.validator_monitor.read();
+    validator_monitor.register_rpc_block(
+        seen_timestamp,
+        block,
+        head.beacon_block_root,
+        &harness.chain.slot_clock,
+    );
+
+    let validator = validator_monitor
+        .get_monitored_validator(block.proposer_index())
+        .unwrap();
+    let summaries = validator.summaries.read();
+    let summary = summaries.get(&epoch).unwrap();
+    assert_eq!(summary.blocks, 1);
+    assert_eq!(summary.block_min_delay, Some(delay));
+}
+
+#[tokio::test]
+async fn diagnoses_missed_attestations() {
+    let validator_count = 16;
+    let harness = get_harness(validator_count, vec![0, 1]);
+
+    // Every validator except validator 1 attests.
+    harness
+        .extend_chain(
+            4 * E::slots_per_epoch() as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::SomeValidators((0..validator_count).filter(|i| *i != 1).collect()),
+        )
+        .await;
+
+    let diagnoses = harness
+        .chain
+        .validator_monitor
+        .read()
+        .get_attestation_diagnoses(Epoch::new(1));
+    assert_eq!(diagnoses.len(), 2);
+
+    assert_eq!(diagnoses[0].validator_index, 0);
+    assert_eq!(diagnoses[0].outcome, Some(AttestationOutcome::Included));
+    assert!(diagnoses[0].block_inclusions > 0);
+
+    assert_eq!(diagnoses[1].validator_index, 1);
+    assert_eq!(diagnoses[1].outcome, Some(AttestationOutcome::NotProduced));
+    assert_eq!(diagnoses[1].block_inclusions, 0);
+    assert_eq!(diagnoses[1].aggregate_inclusions, 0);
+}
+
+// Regression test for the epoch of the validator statuses computed during block import being off
+// by one, which attributed each epoch's attestations to the epoch before it.
+#[tokio::test]
+async fn attributes_attestations_to_their_epoch() {
+    let validator_count = 16;
+    let harness = get_harness(validator_count, vec![1]);
+
+    // Validator 1 attests throughout epochs 0 and 1.
+    harness
+        .extend_chain(
+            2 * E::slots_per_epoch() as usize - 1,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    // Validator 1 stops attesting from epoch 2 onwards.
+    harness
+        .extend_chain(
+            3 * E::slots_per_epoch() as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::SomeValidators((0..validator_count).filter(|i| *i != 1).collect()),
+        )
+        .await;
+
+    let outcome = |epoch| {
+        let diagnoses = harness
+            .chain
+            .validator_monitor
+            .read()
+            .get_attestation_diagnoses(Epoch::new(epoch));
+        assert_eq!(diagnoses.len(), 1);
+        assert_eq!(diagnoses[0].validator_index, 1);
+        diagnoses[0].outcome
+    };
+
+    assert_eq!(outcome(1), Some(AttestationOutcome::Included));
+    assert_eq!(outcome(2), Some(AttestationOutcome::NotProduced));
+}
+
+#[test]
+fn diagnose_attestation_outcome() {
+    let late_threshold = Duration::from_secs(4);
+    let diagnose = |summary: &EpochSummary| summary.diagnose_attestation(false, late_threshold);
+
+    let mut summary = EpochSummary::default();
+    assert_eq!(
+        summary.diagnose_attestation(true, late_threshold),
+        AttestationOutcome::Included
+    );
+    assert_eq!(diagnose(&summary), AttestationOutcome::NotProduced);
+
+    summary.register_unaggregated_attestation(Duration::from_secs(5));
+    assert_eq!(diagnose(&summary), AttestationOutcome::ProducedLate);
+
+    summary.register_unaggregated_attestation(Duration::from_secs(1));
+    assert_eq!(diagnose(&summary), AttestationOutcome::NotAggregated);
+
+    summary.register_aggregate_attestation_inclusion();
+    assert_eq!(diagnose(&summary), AttestationOutcome::AggregateNotIncluded);
+
+    summary.register_attestation_block_inclusion(Slot::new(5));
+    assert_eq!(diagnose(&summary), AttestationOutcome::IncludedLate);
+}
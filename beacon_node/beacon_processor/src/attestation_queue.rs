@@ -0,0 +1,294 @@
+//! A queue for unaggregated gossip attestations which prefers to drop known votes.
+//!
+//! An unaggregated attestation is for a *known* vote if we have already received an identical
+//! attestation, e.g., a duplicate published by a different peer. Processing such an attestation
+//! tells us nothing new, so when the queue is saturated known votes are dropped before any novel
+//! vote.
+//!
+//! Attestations are unverified when queued, so a vote is only known if its data *and* signature
+//! match an earlier attestation. A forged attestation can therefore never cause a different vote to
+//! be dropped, and votes are only remembered for target epochs near the current epoch.
+use crate::metrics;
+use crate::LifoQueue;
+use std::collections::hash_map::RandomState;
+use std::collections::HashSet;
+use std::hash::{BuildHasher, Hash, Hasher};
+use types::{Attestation, Epoch, EthSpec, Slot};
+
+/// The maximum number of votes remembered for a single target epoch. Once reached, further votes
+/// for the epoch are treated as novel.
+const MAX_KNOWN_VOTES_PER_EPOCH: usize = 1 << 20;
+
+/// Identifies the vote of a single validator for a target.
+///
+/// The validator is identified by its position within a committee, which avoids needing the
+/// shuffling to compute its index. The `digest` commits to the attestation data and signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VoteKey {
+    slot: Slot,
+    committee_index: u64,
+    committee_position: usize,
+    target_epoch: Epoch,
+    digest: u64,
+}
+
+impl VoteKey {
+    /// Returns the vote of `attestation`, or `None` if it does not have exactly one attester.
+    ///
+    /// The digest is computed with `hash_builder`, which should be randomly seeded so that peers
+    /// can't construct an attestation with a colliding digest.
+    pub fn from_attestation<E: EthSpec>(
+        attestation: &Attestation<E>,
+        hash_builder: &impl BuildHasher,
+    ) -> Option<Self> {
+        let attestation = attestation.to_ref();
+        let [committee_position] = attestation.set_aggregation_bits()[..] else {
+            return None;
+        };
+        let data = attestation.data();
+        let mut hasher = hash_builder.build_hasher();
+        data.hash(&mut hasher);
+        attestation.signature().hash(&mut hasher);
+        Some(Self {
+            slot: data.slot,
+            committee_index: attestation.committee_index()?,
+            committee_position,
+            target_epoch: data.target.epoch,
+            digest: hasher.finish(),
+        })
+    }
+}
+
+/// The votes received for the two most recent target epochs.
+#[derive(Default)]
+struct KnownVotes {
+    current: (Epoch, HashSet<VoteKey>),
+    previous: (Epoch, HashSet<VoteKey>),
+}
+
+impl KnownVotes {
+    /// Records `vote`, returning `true` if it was already known.
+    ///
+    /// Votes are only recorded if their target is within one epoch of `current_epoch`, so that an
+    /// unverified vote for a distant epoch can't evict the votes of the current epoch.
+    fn observe(&mut self, vote: VoteKey, current_epoch: Option<Epoch>) -> bool {
+        let epoch = vote.target_epoch;
+        let Some(current_epoch) = current_epoch else {
+            return false;
+        };
+        if epoch + 1 < current_epoch || epoch > current_epoch + 1 {
+            return false;
+        }
+
+        if epoch > self.current.0 {
+            let current = std::mem::replace(&mut self.current, (epoch, HashSet::new()));
+            // Only keep the outgoing epoch if it is the one immediately prior to the new epoch.
+            self.previous = if current.0 + 1 == epoch {
+                current
+            } else {
+                (Epoch::new(0), HashSet::new())
+            };
+        }
+
+        let votes = if epoch == self.current.0 {
+            &mut self.current.1
+        } else if epoch == self.previous.0 {
+            &mut self.previous.1
+        } else {
+            return false;
+        };
+
+        if votes.contains(&vote) {
+            true
+        } else {
+            if votes.len() < MAX_KNOWN_VOTES_PER_EPOCH {
+                votes.insert(vote);
+            }
+            false
+        }
+    }
+}
+
+/// A pair of LIFO queues holding novel and known votes, which together hold at most `max_length`
+/// items.
+pub struct AttestationQueue<T> {
+    novel: LifoQueue<T>,
+    known: LifoQueue<T>,
+    known_votes: KnownVotes,
+    hash_builder: RandomState,
+    pub max_length: usize,
+}
+
+impl<T> AttestationQueue<T> {
+    pub fn new(max_length: usize) -> Self {
+        Self {
+            novel: LifoQueue::new(max_length),
+            known: LifoQueue::new(max_length),
+            known_votes: KnownVotes::default(),
+            hash_builder: RandomState::new(),
+            max_length,
+        }
+    }
+
+    /// Returns the vote of `attestation`, see `VoteKey::from_attestation`.
+    pub fn vote_key<E: EthSpec>(&self, attestation: &Attestation<E>) -> Option<VoteKey> {
+        VoteKey::from_attestation(attestation, &self.hash_builder)
+    }
+
+    /// Add a new item for `vote` to the queue.
+    ///
+    /// If the queue is full, the oldest known vote is dropped. If there are no known votes in the
+    /// queue, the new item is dropped if it is a known vote, otherwise the oldest novel vote is
+    /// dropped.
+    pub fn push(&mut self, item: T, vote: Option<VoteKey>, current_epoch: Option<Epoch>) {
+        let is_known = vote.map_or(false, |vote| self.known_votes.observe(vote, current_epoch));
+
+        if self.is_full() {
+            if self.known.pop_back().is_some() {
+                Self::record_dropped("known");
+            } else if is_known {
+                Self::record_dropped("known");
+                return;
+            } else {
+                self.novel.pop_back();
+                Self::record_dropped("novel");
+            }
+        }
+
+        if is_known {
+            self.known.push(item);
+        } else {
+            self.novel.push(item);
+        }
+    }
+
+    /// Remove the next item from the queue, preferring novel votes.
+    pub fn pop(&mut self) -> Option<T> {
+        self.novel.pop().or_else(|| self.known.pop())
+    }
+
+    /// Returns `true` if the queue is full.
+    pub fn is_full(&self) -> bool {
+        self.len() >= self.max_length
+    }
+
+    /// Returns the current length of the queue.
+    pub fn len(&self) -> usize {
+        self.novel.len() + self.known.len()
+    }
+
+    fn record_dropped(class: &str) {
+        metrics::inc_counter_vec(
+            &metrics::BEACON_PROCESSOR_ATTESTATION_QUEUE_DROPPED_COUNT,
+            &[class],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CURRENT_EPOCH: Option<Epoch> = Some(Epoch::new(1));
+
+    fn vote_with_digest(committee_position: usize, target_epoch: u64, digest: u64) -> VoteKey {
+        VoteKey {
+            slot: Slot::new(target_epoch * 32),
+            committee_index: 0,
+            committee_position,
+            target_epoch: Epoch::new(target_epoch),
+            digest,
+        }
+    }
+
+    fn vote(committee_position: usize, target_epoch: u64) -> Option<VoteKey> {
+        Some(vote_with_digest(committee_position, target_epoch, 0))
+    }
+
+    #[test]
+    fn drops_known_votes_first() {
+        let mut queue = AttestationQueue::new(3);
+        queue.push(0, vote(0, 1), CURRENT_EPOCH);
+        queue.push(1, vote(1, 1), CURRENT_EPOCH);
+        queue.push(2, vote(0, 1), CURRENT_EPOCH); // Known.
+        assert!(queue.is_full());
+
+        // The known vote is dropped to make room for a novel vote.
+        queue.push(3, vote(2, 1), CURRENT_EPOCH);
+        assert_eq!(queue.len(), 3);
+
+        // The incoming known vote is dropped rather than a novel vote.
+        queue.push(4, vote(1, 1), CURRENT_EPOCH);
+        assert_eq!(queue.len(), 3);
+
+        // Once there are only novel votes, the oldest is dropped.
+        queue.push(5, vote(3, 1), CURRENT_EPOCH);
+        assert_eq!(queue.pop(), Some(5));
+        assert_eq!(queue.pop(), Some(3));
+        assert_eq!(queue.pop(), Some(1));
+        assert_eq!(queue.pop(), None);
+    }
+
+    #[test]
+    fn novel_votes_are_popped_first() {
+        let mut queue = AttestationQueue::new(4);
+        queue.push(0, vote(0, 1), CURRENT_EPOCH);
+        queue.push(1, vote(0, 1), CURRENT_EPOCH); // Known.
+        queue.push(2, None, CURRENT_EPOCH);
+        assert_eq!(queue.pop(), Some(2));
+        assert_eq!(queue.pop(), Some(0));
+        assert_eq!(queue.pop(), Some(1));
+    }
+
+    #[test]
+    fn known_votes_are_forgotten_after_two_epochs() {
+        let mut known_votes = KnownVotes::default();
+        let observe = |known_votes: &mut KnownVotes, target_epoch, current_epoch| {
+            known_votes.observe(
+                vote(0, target_epoch).unwrap(),
+                Some(Epoch::new(current_epoch)),
+            )
+        };
+        assert!(!observe(&mut known_votes, 1, 1));
+        assert!(observe(&mut known_votes, 1, 1));
+
+        // The previous epoch is still remembered.
+        assert!(!observe(&mut known_votes, 2, 2));
+        assert!(observe(&mut known_votes, 1, 2));
+
+        // Older epochs are forgotten.
+        assert!(!observe(&mut known_votes, 3, 3));
+        assert!(!observe(&mut known_votes, 1, 3));
+        assert!(observe(&mut known_votes, 2, 3));
+    }
+
+    #[test]
+    fn forged_far_future_vote_is_ignored() {
+        let mut queue = AttestationQueue::new(4);
+        queue.push(0, vote(0, 1), CURRENT_EPOCH);
+
+        // A vote for a distant epoch is queued, but not remembered.
+        queue.push(1, vote(0, 1_000), CURRENT_EPOCH);
+        queue.push(2, vote(0, 1_000), CURRENT_EPOCH);
+        assert_eq!(queue.known.len(), 0);
+
+        // The votes of the current epoch are still remembered.
+        queue.push(3, vote(0, 1), CURRENT_EPOCH);
+        assert_eq!(queue.known.len(), 1);
+    }
+
+    #[test]
+    fn colliding_vote_is_not_known() {
+        let mut queue = AttestationQueue::new(2);
+        queue.push(0, Some(vote_with_digest(0, 1, 1)), CURRENT_EPOCH);
+
+        // A vote from the same validator for the same target with different data or signature
+        // (e.g., a forgery) is novel, so it can't cause the genuine vote to be dropped.
+        queue.push(1, Some(vote_with_digest(0, 1, 2)), CURRENT_EPOCH);
+        assert_eq!(queue.known.len(), 0);
+        queue.push(2, Some(vote_with_digest(0, 1, 2)), CURRENT_EPOCH);
+        assert_eq!(queue.pop(), Some(1));
+        assert_eq!(queue.pop(), Some(0));
+        assert_eq!(queue.pop(), None);
+    }
+}
@@ -38,31 +38,36 @@
 //! checks the queues to see if there are more parcels of work that can be spawned in a new worker
 //! task.
 
+use crate::attestation_queue::AttestationQueue;
+use crate::load_shedding::LoadShedder;
+use crate::priority::PriorityOverride;
 use crate::work_reprocessing_queue::{
     QueuedBackfillBatch, QueuedGossipBlock, ReprocessQueueMessage,
 };
+use crate::worker_caps::WorkerCaps;
 use futures::stream::{Stream, StreamExt};
 use futures::task::Poll;
-use lighthouse_network::{MessageId, NetworkGlobals, PeerId};
+use lighthouse_network::{Client, MessageId, NetworkGlobals, PeerId};
 use logging::TimeLatch;
 use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
-use slog::{crit, debug, error, trace, warn, Logger};
+use slog::{crit, debug, error, info, trace, warn, Logger};
 use slot_clock::SlotClock;
 use std::cmp;
-use std::collections::{HashSet, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt;
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::Context;
-use std::time::Duration;
-use strum::IntoStaticStr;
+use std::time::{Duration, Instant};
+use strum::{EnumString, IntoStaticStr};
 use task_executor::TaskExecutor;
 use tokio::sync::mpsc;
 use tokio::sync::mpsc::error::TrySendError;
 use types::{
-    Attestation, BeaconState, ChainSpec, Hash256, RelativeEpoch, SignedAggregateAndProof, SubnetId,
+    Attestation, BeaconState, BlobSidecar, ChainSpec, Hash256, RelativeEpoch,
+    SignedAggregateAndProof, SubnetId, SyncCommitteeMessage, SyncSubnetId,
 };
 use types::{EthSpec, Slot};
 use work_reprocessing_queue::{
@@ -71,8 +76,12 @@ use work_reprocessing_queue::{
 };
 use work_reprocessing_queue::{IgnoredRpcBlock, QueuedSamplingRequest};
 
+mod attestation_queue;
+mod load_shedding;
 mod metrics;
+pub mod priority;
 pub mod work_reprocessing_queue;
+pub mod worker_caps;
 
 /// The maximum size of the channel for work events to the `BeaconProcessor`.
 ///
@@ -120,6 +129,7 @@ pub struct BeaconProcessorQueueLengths {
     sampling_result_queue: usize,
     chain_segment_queue: usize,
     backfill_chain_segment: usize,
+    custody_backfill_columns_queue: usize,
     gossip_block_queue: usize,
     gossip_blob_queue: usize,
     gossip_data_column_queue: usize,
@@ -187,6 +197,7 @@ impl BeaconProcessorQueueLengths {
             sampling_result_queue: 1000,
             chain_segment_queue: 64,
             backfill_chain_segment: 64,
+            custody_backfill_columns_queue: 1024,
             gossip_block_queue: 1024,
             gossip_blob_queue: 1024,
             gossip_data_column_queue: 1024,
@@ -229,6 +240,14 @@ const WORKER_TASK_NAME: &str = "beacon_processor_worker";
 /// individually verifying each attestation signature.
 const DEFAULT_MAX_GOSSIP_ATTESTATION_BATCH_SIZE: usize = 64;
 const DEFAULT_MAX_GOSSIP_AGGREGATE_BATCH_SIZE: usize = 64;
+const DEFAULT_MAX_GOSSIP_SYNC_MESSAGE_BATCH_SIZE: usize = 64;
+
+/// The maximum number of queued gossip blob sidecars that will be processed by a single worker.
+///
+/// Blobs in a batch which belong to the same block have their KZG proofs verified together. A
+/// single invalid proof causes the batch for that block to fail, in which case we fall back to
+/// verifying each proof individually.
+const DEFAULT_MAX_GOSSIP_BLOB_BATCH_SIZE: usize = 16;
 
 /// Unique IDs used for metrics and testing.
 pub const WORKER_FREED: &str = "worker_freed";
@@ -241,7 +260,19 @@ pub struct BeaconProcessorConfig {
     pub max_scheduled_work_queue_len: usize,
     pub max_gossip_attestation_batch_size: usize,
     pub max_gossip_aggregate_batch_size: usize,
+    pub max_gossip_sync_message_batch_size: usize,
+    pub max_gossip_blob_batch_size: usize,
     pub enable_backfill_rate_limiting: bool,
+    /// Drop non-critical gossip once all workers have been busy for this long.
+    ///
+    /// Load shedding is disabled if this is `None`.
+    pub load_shedding_threshold: Option<Duration>,
+    /// Changes to the default order in which queued work is started, see `priority`.
+    pub priority_overrides: Vec<PriorityOverride>,
+    /// The maximum number of workers which may concurrently process each type of work.
+    ///
+    /// Types of work without a cap may use all of the workers.
+    pub worker_caps: HashMap<WorkType, usize>,
 }
 
 impl Default for BeaconProcessorConfig {
@@ -252,7 +283,12 @@ impl Default for BeaconProcessorConfig {
             max_scheduled_work_queue_len: DEFAULT_MAX_SCHEDULED_WORK_QUEUE_LEN,
             max_gossip_attestation_batch_size: DEFAULT_MAX_GOSSIP_ATTESTATION_BATCH_SIZE,
             max_gossip_aggregate_batch_size: DEFAULT_MAX_GOSSIP_AGGREGATE_BATCH_SIZE,
+            max_gossip_sync_message_batch_size: DEFAULT_MAX_GOSSIP_SYNC_MESSAGE_BATCH_SIZE,
+            max_gossip_blob_batch_size: DEFAULT_MAX_GOSSIP_BLOB_BATCH_SIZE,
             enable_backfill_rate_limiting: true,
+            load_shedding_threshold: None,
+            priority_overrides: vec![],
+            worker_caps: HashMap::new(),
         }
     }
 }
@@ -360,6 +396,11 @@ impl<T> LifoQueue<T> {
         self.queue.pop_front()
     }
 
+    /// Remove the oldest item from the queue.
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.queue.pop_back()
+    }
+
     /// Returns `true` if the queue is full.
     pub fn is_full(&self) -> bool {
         self.queue.len() >= self.max_length
@@ -523,6 +564,30 @@ pub struct GossipAggregatePackage<E: EthSpec> {
     pub seen_timestamp: Duration,
 }
 
+/// Items required to verify a batch of gossip sync committee messages.
+#[derive(Debug)]
+pub struct GossipSyncMessagePackage {
+    pub message_id: MessageId,
+    pub peer_id: PeerId,
+    pub sync_message: Box<SyncCommitteeMessage>,
+    pub subnet_id: SyncSubnetId,
+    /// `true` if a local validator is a member of the subcommittee of `subnet_id`, and so may need
+    /// this message to produce an aggregate.
+    pub required_by_local_duty: bool,
+    pub seen_timestamp: Duration,
+}
+
+/// Items required to verify a batch of gossip blob sidecars.
+#[derive(Debug)]
+pub struct GossipBlobSidecarPackage<E: EthSpec> {
+    pub message_id: MessageId,
+    pub peer_id: PeerId,
+    pub peer_client: Client,
+    pub blob_index: u64,
+    pub blob_sidecar: Arc<BlobSidecar<E>>,
+    pub seen_timestamp: Duration,
+}
+
 #[derive(Clone)]
 pub struct BeaconProcessorSend<E: EthSpec>(pub mpsc::Sender<WorkEvent<E>>);
 
@@ -585,7 +650,15 @@ pub enum Work<E: EthSpec> {
         process_batch: Box<dyn FnOnce(Vec<GossipAggregatePackage<E>>) + Send + Sync>,
     },
     GossipBlock(AsyncFn),
-    GossipBlobSidecar(AsyncFn),
+    GossipBlobSidecar {
+        blob: Box<GossipBlobSidecarPackage<E>>,
+        process_individual: Box<dyn FnOnce(GossipBlobSidecarPackage<E>) -> AsyncFn + Send + Sync>,
+        process_batch: Box<dyn FnOnce(Vec<GossipBlobSidecarPackage<E>>) -> AsyncFn + Send + Sync>,
+    },
+    GossipBlobSidecarBatch {
+        blobs: Vec<GossipBlobSidecarPackage<E>>,
+        process_batch: Box<dyn FnOnce(Vec<GossipBlobSidecarPackage<E>>) -> AsyncFn + Send + Sync>,
+    },
     GossipDataColumnSidecar(AsyncFn),
     DelayedImportBlock {
         beacon_block_slot: Slot,
@@ -595,7 +668,15 @@ pub enum Work<E: EthSpec> {
     GossipVoluntaryExit(BlockingFn),
     GossipProposerSlashing(BlockingFn),
     GossipAttesterSlashing(BlockingFn),
-    GossipSyncSignature(BlockingFn),
+    GossipSyncSignature {
+        sync_message: Box<GossipSyncMessagePackage>,
+        process_individual: Box<dyn FnOnce(GossipSyncMessagePackage) + Send + Sync>,
+        process_batch: Box<dyn FnOnce(Vec<GossipSyncMessagePackage>) + Send + Sync>,
+    },
+    GossipSyncSignatureBatch {
+        sync_messages: Vec<GossipSyncMessagePackage>,
+        process_batch: Box<dyn FnOnce(Vec<GossipSyncMessagePackage>) + Send + Sync>,
+    },
     GossipSyncContribution(BlockingFn),
     GossipLightClientFinalityUpdate(BlockingFn),
     GossipLightClientOptimisticUpdate(BlockingFn),
@@ -613,6 +694,7 @@ pub enum Work<E: EthSpec> {
     },
     ChainSegment(AsyncFn),
     ChainSegmentBackfill(AsyncFn),
+    CustodyBackfillColumns(BlockingFn),
     Status(BlockingFn),
     BlocksByRangeRequest(AsyncFn),
     BlocksByRootsRequest(AsyncFn),
@@ -635,8 +717,11 @@ impl<E: EthSpec> fmt::Debug for Work<E> {
     }
 }
 
-#[derive(IntoStaticStr, PartialEq, Eq, Debug)]
+#[derive(
+    IntoStaticStr, EnumString, PartialEq, Eq, Hash, Debug, Clone, Copy, Serialize, Deserialize,
+)]
 #[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
 pub enum WorkType {
     GossipAttestation,
     UnknownBlockAttestation,
@@ -648,12 +733,14 @@ pub enum WorkType {
     GossipAggregateBatch,
     GossipBlock,
     GossipBlobSidecar,
+    GossipBlobSidecarBatch,
     GossipDataColumnSidecar,
     DelayedImportBlock,
     GossipVoluntaryExit,
     GossipProposerSlashing,
     GossipAttesterSlashing,
     GossipSyncSignature,
+    GossipSyncSignatureBatch,
     GossipSyncContribution,
     GossipLightClientFinalityUpdate,
     GossipLightClientOptimisticUpdate,
@@ -665,6 +752,7 @@ pub enum WorkType {
     IgnoredRpcBlock,
     ChainSegment,
     ChainSegmentBackfill,
+    CustodyBackfillColumns,
     Status,
     BlocksByRangeRequest,
     BlocksByRootsRequest,
@@ -686,6 +774,25 @@ impl<E: EthSpec> Work<E> {
         self.to_type().into()
     }
 
+    /// Returns `true` if this work may be dropped when the processor is shedding load.
+    ///
+    /// This is gossip which doesn't affect the node's view of the chain and isn't required for the
+    /// duties of local validators.
+    fn is_non_critical(&self) -> bool {
+        match self {
+            // Unaggregated attestations are only imported if a local validator is aggregating on
+            // their subnet.
+            Work::GossipAttestation { attestation, .. } => !attestation.should_import,
+            // Likewise, sync committee messages are only required if a local validator is a
+            // member of their subcommittee.
+            Work::GossipSyncSignature { sync_message, .. } => !sync_message.required_by_local_duty,
+            Work::GossipSyncSignatureBatch { sync_messages, .. } => sync_messages
+                .iter()
+                .all(|sync_message| !sync_message.required_by_local_duty),
+            _ => false,
+        }
+    }
+
     /// Provides a `&str` that uniquely identifies each enum variant.
     fn to_type(&self) -> WorkType {
         match self {
@@ -694,13 +801,15 @@ impl<E: EthSpec> Work<E> {
             Work::GossipAggregate { .. } => WorkType::GossipAggregate,
             Work::GossipAggregateBatch { .. } => WorkType::GossipAggregateBatch,
             Work::GossipBlock(_) => WorkType::GossipBlock,
-            Work::GossipBlobSidecar(_) => WorkType::GossipBlobSidecar,
+            Work::GossipBlobSidecar { .. } => WorkType::GossipBlobSidecar,
+            Work::GossipBlobSidecarBatch { .. } => WorkType::GossipBlobSidecarBatch,
             Work::GossipDataColumnSidecar(_) => WorkType::GossipDataColumnSidecar,
             Work::DelayedImportBlock { .. } => WorkType::DelayedImportBlock,
             Work::GossipVoluntaryExit(_) => WorkType::GossipVoluntaryExit,
             Work::GossipProposerSlashing(_) => WorkType::GossipProposerSlashing,
             Work::GossipAttesterSlashing(_) => WorkType::GossipAttesterSlashing,
-            Work::GossipSyncSignature(_) => WorkType::GossipSyncSignature,
+            Work::GossipSyncSignature { .. } => WorkType::GossipSyncSignature,
+            Work::GossipSyncSignatureBatch { .. } => WorkType::GossipSyncSignatureBatch,
             Work::GossipSyncContribution(_) => WorkType::GossipSyncContribution,
             Work::GossipLightClientFinalityUpdate(_) => WorkType::GossipLightClientFinalityUpdate,
             Work::GossipLightClientOptimisticUpdate(_) => {
@@ -715,6 +824,7 @@ impl<E: EthSpec> Work<E> {
             Work::IgnoredRpcBlock { .. } => WorkType::IgnoredRpcBlock,
             Work::ChainSegment { .. } => WorkType::ChainSegment,
             Work::ChainSegmentBackfill(_) => WorkType::ChainSegmentBackfill,
+            Work::CustodyBackfillColumns(_) => WorkType::CustodyBackfillColumns,
             Work::Status(_) => WorkType::Status,
             Work::BlocksByRangeRequest(_) => WorkType::BlocksByRangeRequest,
             Work::BlocksByRootsRequest(_) => WorkType::BlocksByRootsRequest,
@@ -742,8 +852,8 @@ impl<E: EthSpec> Work<E> {
 
 /// Unifies all the messages processed by the `BeaconProcessor`.
 enum InboundEvent<E: EthSpec> {
-    /// A worker has completed a task and is free.
-    WorkerIdle,
+    /// A worker has completed a task of the given type and is free.
+    WorkerIdle(WorkType),
     /// There is new work to be done.
     WorkEvent(WorkEvent<E>),
     /// A work event that was queued for re-processing has become ready.
@@ -756,7 +866,7 @@ enum InboundEvent<E: EthSpec> {
 /// control (specifically in the ordering of event processing).
 struct InboundEvents<E: EthSpec> {
     /// Used by workers when they finish a task.
-    idle_rx: mpsc::Receiver<()>,
+    idle_rx: mpsc::Receiver<WorkType>,
     /// Used by upstream processes to send new work to the `BeaconProcessor`.
     event_rx: mpsc::Receiver<WorkEvent<E>>,
     /// Used internally for queuing work ready to be re-processed.
@@ -770,8 +880,8 @@ impl<E: EthSpec> Stream for InboundEvents<E> {
         // Always check for idle workers before anything else. This allows us to ensure that a big
         // stream of new events doesn't suppress the processing of existing events.
         match self.idle_rx.poll_recv(cx) {
-            Poll::Ready(Some(())) => {
-                return Poll::Ready(Some(InboundEvent::WorkerIdle));
+            Poll::Ready(Some(work_type)) => {
+                return Poll::Ready(Some(InboundEvent::WorkerIdle(work_type)));
             }
             Poll::Ready(None) => {
                 return Poll::Ready(None);
@@ -841,14 +951,15 @@ impl<E: EthSpec> BeaconProcessor<E> {
         queue_lengths: BeaconProcessorQueueLengths,
     ) -> Result<(), String> {
         // Used by workers to communicate that they are finished a task.
-        let (idle_tx, idle_rx) = mpsc::channel::<()>(MAX_IDLE_QUEUE_LEN);
+        let (idle_tx, idle_rx) = mpsc::channel::<WorkType>(MAX_IDLE_QUEUE_LEN);
+        let slot_clock = Arc::new(slot_clock);
 
         // Using LIFO queues for attestations since validator profits rely upon getting fresh
         // attestations into blocks. Additionally, later attestations contain more information than
         // earlier ones, so we consider them more valuable.
         let mut aggregate_queue = LifoQueue::new(queue_lengths.aggregate_queue);
         let mut aggregate_debounce = TimeLatch::default();
-        let mut attestation_queue = LifoQueue::new(queue_lengths.attestation_queue);
+        let mut attestation_queue = AttestationQueue::new(queue_lengths.attestation_queue);
         let mut attestation_debounce = TimeLatch::default();
         let mut unknown_block_aggregate_queue =
             LifoQueue::new(queue_lengths.unknown_block_aggregate_queue);
@@ -887,6 +998,8 @@ impl<E: EthSpec> BeaconProcessor<E> {
         let mut sampling_result_queue = FifoQueue::new(queue_lengths.sampling_result_queue);
         let mut chain_segment_queue = FifoQueue::new(queue_lengths.chain_segment_queue);
         let mut backfill_chain_segment = FifoQueue::new(queue_lengths.backfill_chain_segment);
+        let mut custody_backfill_columns_queue =
+            FifoQueue::new(queue_lengths.custody_backfill_columns_queue);
         let mut gossip_block_queue = FifoQueue::new(queue_lengths.gossip_block_queue);
         let mut gossip_blob_queue = FifoQueue::new(queue_lengths.gossip_blob_queue);
         let mut gossip_data_column_queue = FifoQueue::new(queue_lengths.gossip_data_column_queue);
@@ -920,11 +1033,13 @@ impl<E: EthSpec> BeaconProcessor<E> {
             ready_work_tx,
             work_reprocessing_rx,
             &self.executor,
-            Arc::new(slot_clock),
+            slot_clock.clone(),
             self.log.clone(),
             maximum_gossip_clock_disparity,
         )?;
 
+        let priority_order = priority::priority_order(&self.config.priority_overrides)?;
+
         let executor = self.executor.clone();
 
         // The manager future will run on the core executor and delegate tasks to worker
@@ -937,11 +1052,22 @@ impl<E: EthSpec> BeaconProcessor<E> {
             };
 
             let enable_backfill_rate_limiting = self.config.enable_backfill_rate_limiting;
+            let mut load_shedder = self.config.load_shedding_threshold.map(LoadShedder::new);
+            let mut worker_caps = WorkerCaps::new(self.config.worker_caps.clone());
+
+            if !self.config.priority_overrides.is_empty() {
+                info!(
+                    self.log,
+                    "Using custom work priorities";
+                    "order" => ?priority_order,
+                );
+            }
 
             loop {
                 let work_event = match inbound_events.next().await {
-                    Some(InboundEvent::WorkerIdle) => {
+                    Some(InboundEvent::WorkerIdle(work_type)) => {
                         self.current_workers = self.current_workers.saturating_sub(1);
+                        worker_caps.finished(work_type);
                         None
                     }
                     Some(InboundEvent::WorkEvent(event)) if enable_backfill_rate_limiting => {
@@ -1024,6 +1150,30 @@ impl<E: EthSpec> BeaconProcessor<E> {
                     .as_ref()
                     .map_or(false, |event| event.drop_during_sync);
 
+                if let Some(load_shedder) = &mut load_shedder {
+                    match load_shedder.observe(!can_spawn, Instant::now()) {
+                        Some(true) => warn!(
+                            self.log,
+                            "Beacon processor shedding load";
+                            "msg" => "all workers are busy, dropping non-critical gossip",
+                            "max_workers" => self.config.max_workers,
+                        ),
+                        Some(false) => info!(
+                            self.log,
+                            "Beacon processor stopped shedding load";
+                            "msg" => "workers are available, processing all gossip",
+                        ),
+                        None => {}
+                    }
+                    metrics::set_gauge(
+                        &metrics::BEACON_PROCESSOR_LOAD_SHEDDING_ACTIVE,
+                        load_shedder.is_active() as i64,
+                    );
+                }
+                let shedding_load = load_shedder
+                    .as_ref()
+                    .map_or(false, |load_shedder| load_shedder.is_active());
+
                 let idle_tx = idle_tx.clone();
                 let modified_queue_id = match work_event {
                     // There is no new work event, but we are able to spawn a new worker.
@@ -1031,220 +1181,292 @@ impl<E: EthSpec> BeaconProcessor<E> {
                     // We don't check the `work.drop_during_sync` here. We assume that if it made
                     // it into the queue at any point then we should process it.
                     None if can_spawn => {
-                        // Check for chain segments first, they're the most efficient way to get
-                        // blocks into the system.
-                        let work_event: Option<Work<E>> = if let Some(item) =
-                            chain_segment_queue.pop()
-                        {
-                            Some(item)
-                        // Check sync blocks before gossip blocks, since we've already explicitly
-                        // requested these blocks.
-                        } else if let Some(item) = rpc_block_queue.pop() {
-                            Some(item)
-                        } else if let Some(item) = rpc_blob_queue.pop() {
-                            Some(item)
-                        } else if let Some(item) = rpc_custody_column_queue.pop() {
-                            Some(item)
-                        // TODO(das): decide proper prioritization for sampling columns
-                        } else if let Some(item) = rpc_custody_column_queue.pop() {
-                            Some(item)
-                        } else if let Some(item) = rpc_verify_data_column_queue.pop() {
-                            Some(item)
-                        } else if let Some(item) = sampling_result_queue.pop() {
-                            Some(item)
-                        // Check delayed blocks before gossip blocks, the gossip blocks might rely
-                        // on the delayed ones.
-                        } else if let Some(item) = delayed_block_queue.pop() {
-                            Some(item)
-                        // Check gossip blocks before gossip attestations, since a block might be
-                        // required to verify some attestations.
-                        } else if let Some(item) = gossip_block_queue.pop() {
-                            Some(item)
-                        } else if let Some(item) = gossip_blob_queue.pop() {
-                            Some(item)
-                        } else if let Some(item) = gossip_data_column_queue.pop() {
-                            Some(item)
-                        // Check the priority 0 API requests after blocks and blobs, but before attestations.
-                        } else if let Some(item) = api_request_p0_queue.pop() {
-                            Some(item)
-                        // Check the aggregates, *then* the unaggregates since we assume that
-                        // aggregates are more valuable to local validators and effectively give us
-                        // more information with less signature verification time.
-                        } else if aggregate_queue.len() > 0 {
-                            let batch_size = cmp::min(
-                                aggregate_queue.len(),
-                                self.config.max_gossip_aggregate_batch_size,
-                            );
-
-                            if batch_size < 2 {
-                                // One single aggregate is in the queue, process it individually.
-                                aggregate_queue.pop()
-                            } else {
-                                // Collect two or more aggregates into a batch, so they can take
-                                // advantage of batch signature verification.
-                                //
-                                // Note: this will convert the `Work::GossipAggregate` item into a
-                                // `Work::GossipAggregateBatch` item.
-                                let mut aggregates = Vec::with_capacity(batch_size);
-                                let mut process_batch_opt = None;
-                                for _ in 0..batch_size {
-                                    if let Some(item) = aggregate_queue.pop() {
-                                        match item {
-                                            Work::GossipAggregate {
-                                                aggregate,
-                                                process_individual: _,
-                                                process_batch,
-                                            } => {
-                                                aggregates.push(*aggregate);
-                                                if process_batch_opt.is_none() {
-                                                    process_batch_opt = Some(process_batch);
+                        // Work at its cap is left in the queue until a worker of its type is
+                        // free.
+                        let work_event: Option<Work<E>> = priority_order
+                            .iter()
+                            .filter(|work_type| !worker_caps.is_at_cap(**work_type))
+                            .find_map(|work_type| match work_type {
+                                WorkType::ChainSegment => chain_segment_queue.pop(),
+                                WorkType::RpcBlock => rpc_block_queue.pop(),
+                                WorkType::RpcBlobs => rpc_blob_queue.pop(),
+                                WorkType::RpcCustodyColumn => rpc_custody_column_queue.pop(),
+                                WorkType::RpcVerifyDataColumn => rpc_verify_data_column_queue.pop(),
+                                WorkType::SamplingResult => sampling_result_queue.pop(),
+                                WorkType::DelayedImportBlock => delayed_block_queue.pop(),
+                                WorkType::GossipBlock => gossip_block_queue.pop(),
+                                // Potentially use batching.
+                                WorkType::GossipBlobSidecar => {
+                                    let batch_size = cmp::min(
+                                        gossip_blob_queue.len(),
+                                        self.config.max_gossip_blob_batch_size,
+                                    );
+
+                                    if batch_size < 2 {
+                                        // One single blob is in the queue, process it individually.
+                                        gossip_blob_queue.pop()
+                                    } else {
+                                        // Collect two or more blobs into a batch, so they can take
+                                        // advantage of batch KZG verification.
+                                        //
+                                        // Note: this will convert the `Work::GossipBlobSidecar` item into
+                                        // a `Work::GossipBlobSidecarBatch` item.
+                                        let mut blobs = Vec::with_capacity(batch_size);
+                                        let mut process_batch_opt = None;
+                                        for _ in 0..batch_size {
+                                            if let Some(item) = gossip_blob_queue.pop() {
+                                                match item {
+                                                    Work::GossipBlobSidecar {
+                                                        blob,
+                                                        process_individual: _,
+                                                        process_batch,
+                                                    } => {
+                                                        blobs.push(*blob);
+                                                        if process_batch_opt.is_none() {
+                                                            process_batch_opt = Some(process_batch);
+                                                        }
+                                                    }
+                                                    _ => error!(
+                                                        self.log,
+                                                        "Invalid item in gossip blob queue"
+                                                    ),
                                                 }
                                             }
-                                            _ => {
-                                                error!(self.log, "Invalid item in aggregate queue");
-                                            }
+                                        }
+
+                                        if let Some(process_batch) = process_batch_opt {
+                                            // Process all blobs with a single worker.
+                                            Some(Work::GossipBlobSidecarBatch {
+                                                blobs,
+                                                process_batch,
+                                            })
+                                        } else {
+                                            // There is no good reason for this to
+                                            // happen, it is a serious logic error.
+                                            // Since we only form batches when multiple
+                                            // work items exist, we should always have a
+                                            // work closure at this point.
+                                            crit!(self.log, "Missing gossip blobs work");
+                                            None
                                         }
                                     }
                                 }
+                                WorkType::GossipDataColumnSidecar => gossip_data_column_queue.pop(),
+                                WorkType::ApiRequestP0 => api_request_p0_queue.pop(),
+                                // Potentially use batching.
+                                WorkType::GossipAggregate => {
+                                    let batch_size = cmp::min(
+                                        aggregate_queue.len(),
+                                        self.config.max_gossip_aggregate_batch_size,
+                                    );
+
+                                    if batch_size < 2 {
+                                        // One single aggregate is in the queue, process it individually.
+                                        aggregate_queue.pop()
+                                    } else {
+                                        // Collect two or more aggregates into a batch, so they can take
+                                        // advantage of batch signature verification.
+                                        //
+                                        // Note: this will convert the `Work::GossipAggregate` item into a
+                                        // `Work::GossipAggregateBatch` item.
+                                        let mut aggregates = Vec::with_capacity(batch_size);
+                                        let mut process_batch_opt = None;
+                                        for _ in 0..batch_size {
+                                            if let Some(item) = aggregate_queue.pop() {
+                                                match item {
+                                                    Work::GossipAggregate {
+                                                        aggregate,
+                                                        process_individual: _,
+                                                        process_batch,
+                                                    } => {
+                                                        aggregates.push(*aggregate);
+                                                        if process_batch_opt.is_none() {
+                                                            process_batch_opt = Some(process_batch);
+                                                        }
+                                                    }
+                                                    _ => {
+                                                        error!(
+                                                            self.log,
+                                                            "Invalid item in aggregate queue"
+                                                        );
+                                                    }
+                                                }
+                                            }
+                                        }
 
-                                if let Some(process_batch) = process_batch_opt {
-                                    // Process all aggregates with a single worker.
-                                    Some(Work::GossipAggregateBatch {
-                                        aggregates,
-                                        process_batch,
-                                    })
-                                } else {
-                                    // There is no good reason for this to
-                                    // happen, it is a serious logic error.
-                                    // Since we only form batches when multiple
-                                    // work items exist, we should always have a
-                                    // work closure at this point.
-                                    crit!(self.log, "Missing aggregate work");
-                                    None
-                                }
-                            }
-                        // Check the unaggregated attestation queue.
-                        //
-                        // Potentially use batching.
-                        } else if attestation_queue.len() > 0 {
-                            let batch_size = cmp::min(
-                                attestation_queue.len(),
-                                self.config.max_gossip_attestation_batch_size,
-                            );
-
-                            if batch_size < 2 {
-                                // One single attestation is in the queue, process it individually.
-                                attestation_queue.pop()
-                            } else {
-                                // Collect two or more attestations into a batch, so they can take
-                                // advantage of batch signature verification.
-                                //
-                                // Note: this will convert the `Work::GossipAttestation` item into a
-                                // `Work::GossipAttestationBatch` item.
-                                let mut attestations = Vec::with_capacity(batch_size);
-                                let mut process_batch_opt = None;
-                                for _ in 0..batch_size {
-                                    if let Some(item) = attestation_queue.pop() {
-                                        match item {
-                                            Work::GossipAttestation {
-                                                attestation,
-                                                process_individual: _,
+                                        if let Some(process_batch) = process_batch_opt {
+                                            // Process all aggregates with a single worker.
+                                            Some(Work::GossipAggregateBatch {
+                                                aggregates,
                                                 process_batch,
-                                            } => {
-                                                attestations.push(*attestation);
-                                                if process_batch_opt.is_none() {
-                                                    process_batch_opt = Some(process_batch);
+                                            })
+                                        } else {
+                                            // There is no good reason for this to
+                                            // happen, it is a serious logic error.
+                                            // Since we only form batches when multiple
+                                            // work items exist, we should always have a
+                                            // work closure at this point.
+                                            crit!(self.log, "Missing aggregate work");
+                                            None
+                                        }
+                                    }
+                                }
+                                // Potentially use batching.
+                                WorkType::GossipAttestation => {
+                                    let batch_size = cmp::min(
+                                        attestation_queue.len(),
+                                        self.config.max_gossip_attestation_batch_size,
+                                    );
+
+                                    if batch_size < 2 {
+                                        // One single attestation is in the queue, process it individually.
+                                        attestation_queue.pop()
+                                    } else {
+                                        // Collect two or more attestations into a batch, so they can take
+                                        // advantage of batch signature verification.
+                                        //
+                                        // Note: this will convert the `Work::GossipAttestation` item into a
+                                        // `Work::GossipAttestationBatch` item.
+                                        let mut attestations = Vec::with_capacity(batch_size);
+                                        let mut process_batch_opt = None;
+                                        for _ in 0..batch_size {
+                                            if let Some(item) = attestation_queue.pop() {
+                                                match item {
+                                                    Work::GossipAttestation {
+                                                        attestation,
+                                                        process_individual: _,
+                                                        process_batch,
+                                                    } => {
+                                                        attestations.push(*attestation);
+                                                        if process_batch_opt.is_none() {
+                                                            process_batch_opt = Some(process_batch);
+                                                        }
+                                                    }
+                                                    _ => error!(
+                                                        self.log,
+                                                        "Invalid item in attestation queue"
+                                                    ),
                                                 }
                                             }
-                                            _ => error!(
-                                                self.log,
-                                                "Invalid item in attestation queue"
-                                            ),
+                                        }
+
+                                        if let Some(process_batch) = process_batch_opt {
+                                            // Process all attestations with a single worker.
+                                            Some(Work::GossipAttestationBatch {
+                                                attestations,
+                                                process_batch,
+                                            })
+                                        } else {
+                                            // There is no good reason for this to
+                                            // happen, it is a serious logic error.
+                                            // Since we only form batches when multiple
+                                            // work items exist, we should always have a
+                                            // work closure at this point.
+                                            crit!(self.log, "Missing attestations work");
+                                            None
                                         }
                                     }
                                 }
+                                WorkType::GossipSyncContribution => sync_contribution_queue.pop(),
+                                // Potentially use batching.
+                                WorkType::GossipSyncSignature => {
+                                    let batch_size = cmp::min(
+                                        sync_message_queue.len(),
+                                        self.config.max_gossip_sync_message_batch_size,
+                                    );
+
+                                    if batch_size < 2 {
+                                        // One single message is in the queue, process it individually.
+                                        sync_message_queue.pop()
+                                    } else {
+                                        // Collect two or more messages into a batch, so they can take
+                                        // advantage of batch signature verification.
+                                        //
+                                        // Note: this will convert the `Work::GossipSyncSignature` item into
+                                        // a `Work::GossipSyncSignatureBatch` item.
+                                        let mut sync_messages = Vec::with_capacity(batch_size);
+                                        let mut process_batch_opt = None;
+                                        for _ in 0..batch_size {
+                                            if let Some(item) = sync_message_queue.pop() {
+                                                match item {
+                                                    Work::GossipSyncSignature {
+                                                        sync_message,
+                                                        process_individual: _,
+                                                        process_batch,
+                                                    } => {
+                                                        sync_messages.push(*sync_message);
+                                                        if process_batch_opt.is_none() {
+                                                            process_batch_opt = Some(process_batch);
+                                                        }
+                                                    }
+                                                    _ => error!(
+                                                        self.log,
+                                                        "Invalid item in sync message queue"
+                                                    ),
+                                                }
+                                            }
+                                        }
 
-                                if let Some(process_batch) = process_batch_opt {
-                                    // Process all attestations with a single worker.
-                                    Some(Work::GossipAttestationBatch {
-                                        attestations,
-                                        process_batch,
-                                    })
-                                } else {
-                                    // There is no good reason for this to
-                                    // happen, it is a serious logic error.
-                                    // Since we only form batches when multiple
-                                    // work items exist, we should always have a
-                                    // work closure at this point.
-                                    crit!(self.log, "Missing attestations work");
-                                    None
+                                        if let Some(process_batch) = process_batch_opt {
+                                            // Process all sync messages with a single worker.
+                                            Some(Work::GossipSyncSignatureBatch {
+                                                sync_messages,
+                                                process_batch,
+                                            })
+                                        } else {
+                                            // There is no good reason for this to
+                                            // happen, it is a serious logic error.
+                                            // Since we only form batches when multiple
+                                            // work items exist, we should always have a
+                                            // work closure at this point.
+                                            crit!(self.log, "Missing sync messages work");
+                                            None
+                                        }
+                                    }
                                 }
-                            }
-                        // Check sync committee messages after attestations as their rewards are lesser
-                        // and they don't influence fork choice.
-                        } else if let Some(item) = sync_contribution_queue.pop() {
-                            Some(item)
-                        } else if let Some(item) = sync_message_queue.pop() {
-                            Some(item)
-                        // Aggregates and unaggregates queued for re-processing are older and we
-                        // care about fresher ones, so check those first.
-                        } else if let Some(item) = unknown_block_aggregate_queue.pop() {
-                            Some(item)
-                        } else if let Some(item) = unknown_block_attestation_queue.pop() {
-                            Some(item)
-                        // Check RPC methods next. Status messages are needed for sync so
-                        // prioritize them over syncing requests from other peers (BlocksByRange
-                        // and BlocksByRoot)
-                        } else if let Some(item) = status_queue.pop() {
-                            Some(item)
-                        } else if let Some(item) = bbrange_queue.pop() {
-                            Some(item)
-                        } else if let Some(item) = bbroots_queue.pop() {
-                            Some(item)
-                        } else if let Some(item) = blbrange_queue.pop() {
-                            Some(item)
-                        } else if let Some(item) = blbroots_queue.pop() {
-                            Some(item)
-                        } else if let Some(item) = dcbroots_queue.pop() {
-                            Some(item)
-                        } else if let Some(item) = dcbrange_queue.pop() {
-                            Some(item)
-                        // Prioritize sampling requests after block syncing requests
-                        } else if let Some(item) = unknown_block_sampling_request_queue.pop() {
-                            Some(item)
-                        // Check slashings after all other consensus messages so we prioritize
-                        // following head.
-                        //
-                        // Check attester slashings before proposer slashings since they have the
-                        // potential to slash multiple validators at once.
-                        } else if let Some(item) = gossip_attester_slashing_queue.pop() {
-                            Some(item)
-                        } else if let Some(item) = gossip_proposer_slashing_queue.pop() {
-                            Some(item)
-                        // Check exits and address changes late since our validators don't get
-                        // rewards from them.
-                        } else if let Some(item) = gossip_voluntary_exit_queue.pop() {
-                            Some(item)
-                        } else if let Some(item) = gossip_bls_to_execution_change_queue.pop() {
-                            Some(item)
-                        // Check the priority 1 API requests after we've
-                        // processed all the interesting things from the network
-                        // and things required for us to stay in good repute
-                        // with our P2P peers.
-                        } else if let Some(item) = api_request_p1_queue.pop() {
-                            Some(item)
-                        // Handle backfill sync chain segments.
-                        } else if let Some(item) = backfill_chain_segment.pop() {
-                            Some(item)
-                        // Handle light client requests.
-                        } else if let Some(item) = lc_bootstrap_queue.pop() {
-                            Some(item)
-                        } else if let Some(item) = lc_optimistic_update_queue.pop() {
-                            Some(item)
-                        } else if let Some(item) = lc_finality_update_queue.pop() {
-                            Some(item)
-                            // This statement should always be the final else statement.
-                        } else {
+                                WorkType::UnknownBlockAggregate => {
+                                    unknown_block_aggregate_queue.pop()
+                                }
+                                WorkType::UnknownBlockAttestation => {
+                                    unknown_block_attestation_queue.pop()
+                                }
+                                WorkType::Status => status_queue.pop(),
+                                WorkType::BlocksByRangeRequest => bbrange_queue.pop(),
+                                WorkType::BlocksByRootsRequest => bbroots_queue.pop(),
+                                WorkType::BlobsByRangeRequest => blbrange_queue.pop(),
+                                WorkType::BlobsByRootsRequest => blbroots_queue.pop(),
+                                WorkType::DataColumnsByRootsRequest => dcbroots_queue.pop(),
+                                WorkType::DataColumnsByRangeRequest => dcbrange_queue.pop(),
+                                WorkType::UnknownBlockSamplingRequest => {
+                                    unknown_block_sampling_request_queue.pop()
+                                }
+                                WorkType::GossipAttesterSlashing => {
+                                    gossip_attester_slashing_queue.pop()
+                                }
+                                WorkType::GossipProposerSlashing => {
+                                    gossip_proposer_slashing_queue.pop()
+                                }
+                                WorkType::GossipVoluntaryExit => gossip_voluntary_exit_queue.pop(),
+                                WorkType::GossipBlsToExecutionChange => {
+                                    gossip_bls_to_execution_change_queue.pop()
+                                }
+                                WorkType::ApiRequestP1 => api_request_p1_queue.pop(),
+                                WorkType::ChainSegmentBackfill => backfill_chain_segment.pop(),
+                                WorkType::CustodyBackfillColumns => {
+                                    custody_backfill_columns_queue.pop()
+                                }
+                                WorkType::LightClientBootstrapRequest => lc_bootstrap_queue.pop(),
+                                WorkType::LightClientOptimisticUpdateRequest => {
+                                    lc_optimistic_update_queue.pop()
+                                }
+                                WorkType::LightClientFinalityUpdateRequest => {
+                                    lc_finality_update_queue.pop()
+                                }
+                                // The priority order only contains work types with a queue.
+                                _ => None,
+                            });
+
+                        if work_event.is_none() {
                             // Let the journal know that a worker is freed and there's nothing else
                             // for it to do.
                             if let Some(work_journal_tx) = &work_journal_tx {
@@ -1252,11 +1474,11 @@ impl<E: EthSpec> BeaconProcessor<E> {
                                 // during testing.
                                 let _ = work_journal_tx.try_send(NOTHING_TO_DO);
                             }
-                            None
-                        };
+                        }
 
                         if let Some(work_event) = work_event {
                             let work_type = work_event.to_type();
+                            worker_caps.started(work_type);
                             self.spawn_worker(work_event, idle_tx);
                             Some(work_type)
                         } else {
@@ -1292,6 +1514,21 @@ impl<E: EthSpec> BeaconProcessor<E> {
                         );
                         None
                     }
+                    // The processor is overloaded and this event isn't critical.
+                    Some(work_event) if shedding_load && work_event.work.is_non_critical() => {
+                        let work_id = work_event.work.str_id();
+                        metrics::inc_counter_vec(
+                            &metrics::BEACON_PROCESSOR_WORK_EVENTS_SHED_COUNT,
+                            &[work_id],
+                        );
+                        trace!(
+                            self.log,
+                            "Gossip processor skipping work";
+                            "msg" => "shedding load",
+                            "work_id" => work_id
+                        );
+                        None
+                    }
                     // There is a new work event and the chain is not syncing. Process it or queue
                     // it.
                     Some(WorkEvent { work, .. }) => {
@@ -1299,8 +1536,19 @@ impl<E: EthSpec> BeaconProcessor<E> {
                         let work_type = work.to_type();
 
                         match work {
-                            _ if can_spawn => self.spawn_worker(work, idle_tx),
-                            Work::GossipAttestation { .. } => attestation_queue.push(work),
+                            _ if can_spawn && !worker_caps.is_at_cap(work_type) => {
+                                worker_caps.started(work_type);
+                                self.spawn_worker(work, idle_tx)
+                            }
+                            Work::GossipAttestation {
+                                ref attestation, ..
+                            } => {
+                                let vote = attestation_queue.vote_key(&attestation.attestation);
+                                let current_epoch = slot_clock
+                                    .now()
+                                    .map(|slot| slot.epoch(E::slots_per_epoch()));
+                                attestation_queue.push(work, vote, current_epoch)
+                            }
                             // Attestation batches are formed internally within the
                             // `BeaconProcessor`, they are not sent from external services.
                             Work::GossipAttestationBatch { .. } => crit!(
@@ -1322,6 +1570,13 @@ impl<E: EthSpec> BeaconProcessor<E> {
                             Work::GossipBlobSidecar { .. } => {
                                 gossip_blob_queue.push(work, work_id, &self.log)
                             }
+                            // Blob batches are formed internally within the `BeaconProcessor`,
+                            // they are not sent from external services.
+                            Work::GossipBlobSidecarBatch { .. } => crit!(
+                                    self.log,
+                                    "Unsupported inbound event";
+                                    "type" => "GossipBlobSidecarBatch"
+                            ),
                             Work::GossipDataColumnSidecar { .. } => {
                                 gossip_data_column_queue.push(work, work_id, &self.log)
                             }
@@ -1338,6 +1593,13 @@ impl<E: EthSpec> BeaconProcessor<E> {
                                 gossip_attester_slashing_queue.push(work, work_id, &self.log)
                             }
                             Work::GossipSyncSignature { .. } => sync_message_queue.push(work),
+                            // Sync message batches are formed internally within the
+                            // `BeaconProcessor`, they are not sent from external services.
+                            Work::GossipSyncSignatureBatch { .. } => crit!(
+                                    self.log,
+                                    "Unsupported inbound event";
+                                    "type" => "GossipSyncSignatureBatch"
+                            ),
                             Work::GossipSyncContribution { .. } => {
                                 sync_contribution_queue.push(work)
                             }
@@ -1366,6 +1628,9 @@ impl<E: EthSpec> BeaconProcessor<E> {
                             Work::ChainSegmentBackfill { .. } => {
                                 backfill_chain_segment.push(work, work_id, &self.log)
                             }
+                            Work::CustodyBackfillColumns(_) => {
+                                custody_backfill_columns_queue.push(work, work_id, &self.log)
+                            }
                             Work::Status { .. } => status_queue.push(work, work_id, &self.log),
                             Work::BlocksByRangeRequest { .. } => {
                                 bbrange_queue.push(work, work_id, &self.log)
@@ -1444,12 +1709,14 @@ impl<E: EthSpec> BeaconProcessor<E> {
                         WorkType::GossipAggregateBatch => 0, // No queue
                         WorkType::GossipBlock => gossip_block_queue.len(),
                         WorkType::GossipBlobSidecar => gossip_blob_queue.len(),
+                        WorkType::GossipBlobSidecarBatch => 0, // No queue
                         WorkType::GossipDataColumnSidecar => gossip_data_column_queue.len(),
                         WorkType::DelayedImportBlock => delayed_block_queue.len(),
                         WorkType::GossipVoluntaryExit => gossip_voluntary_exit_queue.len(),
                         WorkType::GossipProposerSlashing => gossip_proposer_slashing_queue.len(),
                         WorkType::GossipAttesterSlashing => gossip_attester_slashing_queue.len(),
                         WorkType::GossipSyncSignature => sync_message_queue.len(),
+                        WorkType::GossipSyncSignatureBatch => 0, // No queue
                         WorkType::GossipSyncContribution => sync_contribution_queue.len(),
                         WorkType::GossipLightClientFinalityUpdate => finality_update_queue.len(),
                         WorkType::GossipLightClientOptimisticUpdate => {
@@ -1462,6 +1729,7 @@ impl<E: EthSpec> BeaconProcessor<E> {
                         WorkType::SamplingResult => sampling_result_queue.len(),
                         WorkType::ChainSegment => chain_segment_queue.len(),
                         WorkType::ChainSegmentBackfill => backfill_chain_segment.len(),
+                        WorkType::CustodyBackfillColumns => custody_backfill_columns_queue.len(),
                         WorkType::Status => status_queue.len(),
                         WorkType::BlocksByRangeRequest => blbrange_queue.len(),
                         WorkType::BlocksByRootsRequest => blbroots_queue.len(),
@@ -1518,7 +1786,7 @@ impl<E: EthSpec> BeaconProcessor<E> {
     /// Spawns a blocking worker thread to process some `Work`.
     ///
     /// Sends an message on `idle_tx` when the work is complete and the task is stopping.
-    fn spawn_worker(&mut self, work: Work<E>, idle_tx: mpsc::Sender<()>) {
+    fn spawn_worker(&mut self, work: Work<E>, idle_tx: mpsc::Sender<WorkType>) {
         let work_id = work.str_id();
         let worker_timer =
             metrics::start_timer_vec(&metrics::BEACON_PROCESSOR_WORKER_TIME, &[work_id]);
@@ -1534,6 +1802,7 @@ impl<E: EthSpec> BeaconProcessor<E> {
         // As such, this instantiation should happen as early in the function as possible.
         let send_idle_on_drop = SendOnDrop {
             tx: idle_tx,
+            work_type: work.to_type(),
             _worker_timer: worker_timer,
             log: self.log.clone(),
         };
@@ -1582,6 +1851,28 @@ impl<E: EthSpec> BeaconProcessor<E> {
             } => task_spawner.spawn_blocking(move || {
                 process_batch(aggregates);
             }),
+            Work::GossipSyncSignature {
+                sync_message,
+                process_individual,
+                process_batch: _,
+            } => task_spawner.spawn_blocking(move || {
+                process_individual(*sync_message);
+            }),
+            Work::GossipSyncSignatureBatch {
+                sync_messages,
+                process_batch,
+            } => task_spawner.spawn_blocking(move || {
+                process_batch(sync_messages);
+            }),
+            Work::GossipBlobSidecar {
+                blob,
+                process_individual,
+                process_batch: _,
+            } => task_spawner.spawn_async(process_individual(*blob)),
+            Work::GossipBlobSidecarBatch {
+                blobs,
+                process_batch,
+            } => task_spawner.spawn_async(process_batch(blobs)),
             Work::ChainSegment(process_fn) => task_spawner.spawn_async(async move {
                 process_fn.await;
             }),
@@ -1602,11 +1893,10 @@ impl<E: EthSpec> BeaconProcessor<E> {
             | Work::RpcVerifyDataColumn(process_fn)
             | Work::SamplingResult(process_fn) => task_spawner.spawn_async(process_fn),
             Work::IgnoredRpcBlock { process_fn } => task_spawner.spawn_blocking(process_fn),
-            Work::GossipBlock(work)
-            | Work::GossipBlobSidecar(work)
-            | Work::GossipDataColumnSidecar(work) => task_spawner.spawn_async(async move {
-                work.await;
-            }),
+            Work::GossipBlock(work) | Work::GossipDataColumnSidecar(work) => task_spawner
+                .spawn_async(async move {
+                    work.await;
+                }),
             Work::BlobsByRangeRequest(process_fn)
             | Work::BlobsByRootsRequest(process_fn)
             | Work::DataColumnsByRootsRequest(process_fn)
@@ -1617,6 +1907,7 @@ impl<E: EthSpec> BeaconProcessor<E> {
                 task_spawner.spawn_async(work)
             }
             Work::ChainSegmentBackfill(process_fn) => task_spawner.spawn_async(process_fn),
+            Work::CustodyBackfillColumns(process_fn) => task_spawner.spawn_blocking(process_fn),
             Work::ApiRequestP0(process_fn) | Work::ApiRequestP1(process_fn) => match process_fn {
                 BlockingOrAsync::Blocking(process_fn) => task_spawner.spawn_blocking(process_fn),
                 BlockingOrAsync::Async(process_fn) => task_spawner.spawn_async(process_fn),
@@ -1624,7 +1915,6 @@ impl<E: EthSpec> BeaconProcessor<E> {
             Work::GossipVoluntaryExit(process_fn)
             | Work::GossipProposerSlashing(process_fn)
             | Work::GossipAttesterSlashing(process_fn)
-            | Work::GossipSyncSignature(process_fn)
             | Work::GossipSyncContribution(process_fn)
             | Work::GossipLightClientFinalityUpdate(process_fn)
             | Work::GossipLightClientOptimisticUpdate(process_fn)
@@ -1690,7 +1980,8 @@ impl TaskSpawner {
 ///
 /// https://doc.rust-lang.org/std/ops/trait.Drop.html#panics
 pub struct SendOnDrop {
-    tx: mpsc::Sender<()>,
+    tx: mpsc::Sender<WorkType>,
+    work_type: WorkType,
     // The field is unused, but it's here to ensure the timer is dropped once the task has finished.
     _worker_timer: Option<metrics::HistogramTimer>,
     log: Logger,
@@ -1698,7 +1989,7 @@ pub struct SendOnDrop {
 
 impl Drop for SendOnDrop {
     fn drop(&mut self) {
-        if let Err(e) = self.tx.try_send(()) {
+        if let Err(e) = self.tx.try_send(self.work_type) {
             warn!(
                 self.log,
                 "Unable to free worker";
@@ -1712,7 +2003,60 @@ impl Drop for SendOnDrop {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use types::{BeaconState, ChainSpec, Eth1Data, ForkName, MainnetEthSpec};
+    use types::{BeaconState, ChainSpec, Eth1Data, ForkName, MainnetEthSpec, Signature};
+
+    fn sync_signature(required_by_local_duty: bool) -> GossipSyncMessagePackage {
+        GossipSyncMessagePackage {
+            message_id: MessageId::new(&[]),
+            peer_id: PeerId::random(),
+            sync_message: Box::new(SyncCommitteeMessage {
+                slot: Slot::new(0),
+                beacon_block_root: Hash256::zero(),
+                validator_index: 0,
+                signature: Signature::empty(),
+            }),
+            subnet_id: SyncSubnetId::new(0),
+            required_by_local_duty,
+            seen_timestamp: Duration::ZERO,
+        }
+    }
+
+    fn sync_signature_work(required_by_local_duty: bool) -> Work<MainnetEthSpec> {
+        Work::GossipSyncSignature {
+            sync_message: Box::new(sync_signature(required_by_local_duty)),
+            process_individual: Box::new(|_| {}),
+            process_batch: Box::new(|_| {}),
+        }
+    }
+
+    fn sync_signature_batch_work(required_by_local_duty: &[bool]) -> Work<MainnetEthSpec> {
+        Work::GossipSyncSignatureBatch {
+            sync_messages: required_by_local_duty
+                .iter()
+                .copied()
+                .map(sync_signature)
+                .collect(),
+            process_batch: Box::new(|_| {}),
+        }
+    }
+
+    #[test]
+    fn load_shedding_keeps_work_for_local_duties() {
+        let noop = || Box::new(|| {}) as BlockingFn;
+
+        assert!(sync_signature_work(false).is_non_critical());
+        assert!(sync_signature_batch_work(&[false, false]).is_non_critical());
+
+        for work in [
+            sync_signature_work(true),
+            sync_signature_batch_work(&[false, true]),
+            Work::GossipSyncContribution(noop()),
+            Work::GossipLightClientFinalityUpdate(noop()),
+            Work::GossipLightClientOptimisticUpdate(noop()),
+        ] {
+            assert!(!work.is_non_critical(), "{}", work.str_id());
+        }
+    }
 
     #[test]
     fn min_queue_len() {
@@ -0,0 +1,102 @@
+//! Graceful degradation of the `BeaconProcessor` under sustained load.
+//!
+//! When every worker has been busy for longer than a configured threshold the processor enters
+//! load shedding mode, in which non-critical gossip is dropped rather than queued. Blocks, blobs,
+//! data columns and messages relevant to the duties of local validators are always processed. The
+//! mode is left once workers have been available for the same threshold, so that a brief lull
+//! doesn't cause the processor to flap between modes.
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+pub struct LoadShedder {
+    threshold: Duration,
+    /// The time at which the workers were first observed to be saturated, if they still are.
+    saturated_since: Option<Instant>,
+    /// The time at which a worker was first observed to be available, if one still is.
+    unsaturated_since: Option<Instant>,
+    active: bool,
+}
+
+impl LoadShedder {
+    pub fn new(threshold: Duration) -> Self {
+        Self {
+            threshold,
+            saturated_since: None,
+            unsaturated_since: None,
+            active: false,
+        }
+    }
+
+    /// Returns `true` if non-critical work should be dropped.
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Observe whether or not the workers are saturated at `now`.
+    ///
+    /// Returns `Some(active)` if this observation caused the processor to enter or leave load
+    /// shedding mode.
+    pub fn observe(&mut self, saturated: bool, now: Instant) -> Option<bool> {
+        let since = if saturated {
+            self.unsaturated_since = None;
+            *self.saturated_since.get_or_insert(now)
+        } else {
+            self.saturated_since = None;
+            *self.unsaturated_since.get_or_insert(now)
+        };
+
+        let sustained = now.saturating_duration_since(since) >= self.threshold;
+        if sustained && self.active != saturated {
+            self.active = saturated;
+            Some(saturated)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enters_and_leaves_after_threshold() {
+        let threshold = Duration::from_secs(2);
+        let mut shedder = LoadShedder::new(threshold);
+        let start = Instant::now();
+
+        assert_eq!(shedder.observe(true, start), None);
+        assert_eq!(shedder.observe(true, start + Duration::from_secs(1)), None);
+        assert!(!shedder.is_active());
+        assert_eq!(shedder.observe(true, start + threshold), Some(true));
+        assert!(shedder.is_active());
+        assert_eq!(shedder.observe(true, start + Duration::from_secs(3)), None);
+
+        let lull = start + Duration::from_secs(4);
+        assert_eq!(shedder.observe(false, lull), None);
+        assert!(shedder.is_active());
+        assert_eq!(shedder.observe(false, lull + threshold), Some(false));
+        assert!(!shedder.is_active());
+    }
+
+    #[test]
+    fn brief_changes_are_ignored() {
+        let threshold = Duration::from_secs(2);
+        let mut shedder = LoadShedder::new(threshold);
+        let start = Instant::now();
+
+        // A free worker resets the saturation timer.
+        shedder.observe(true, start);
+        shedder.observe(false, start + Duration::from_secs(1));
+        assert_eq!(shedder.observe(true, start + threshold), None);
+        assert!(!shedder.is_active());
+        assert_eq!(shedder.observe(true, start + threshold * 2), Some(true));
+
+        // A brief saturation resets the recovery timer.
+        let lull = start + Duration::from_secs(10);
+        shedder.observe(false, lull);
+        shedder.observe(true, lull + Duration::from_secs(1));
+        assert_eq!(shedder.observe(false, lull + threshold), None);
+        assert!(shedder.is_active());
+    }
+}
@@ -20,6 +20,29 @@ pub static BEACON_PROCESSOR_WORK_EVENTS_IGNORED_COUNT: LazyLock<Result<IntCounte
             &["type"],
         )
     });
+pub static BEACON_PROCESSOR_ATTESTATION_QUEUE_DROPPED_COUNT: LazyLock<Result<IntCounterVec>> =
+    LazyLock::new(|| {
+        try_create_int_counter_vec(
+            "beacon_processor_attestation_queue_dropped_count",
+            "Count of unaggregated attestations dropped from a full queue, by whether the vote was novel or already known",
+            &["class"],
+        )
+    });
+pub static BEACON_PROCESSOR_WORK_EVENTS_SHED_COUNT: LazyLock<Result<IntCounterVec>> =
+    LazyLock::new(|| {
+        try_create_int_counter_vec(
+            "beacon_processor_work_events_shed_count",
+            "Count of non-critical work events dropped whilst shedding load",
+            &["type"],
+        )
+    });
+pub static BEACON_PROCESSOR_LOAD_SHEDDING_ACTIVE: LazyLock<Result<IntGauge>> =
+    LazyLock::new(|| {
+        try_create_int_gauge(
+            "beacon_processor_load_shedding_active",
+            "Set to 1 whilst the beacon processor is dropping non-critical gossip due to load",
+        )
+    });
 pub static BEACON_PROCESSOR_WORK_EVENTS_STARTED_COUNT: LazyLock<Result<IntCounterVec>> =
     LazyLock::new(|| {
         try_create_int_counter_vec(
@@ -49,6 +72,14 @@ pub static BEACON_PROCESSOR_WORKERS_ACTIVE_TOTAL: LazyLock<Result<IntGauge>> =
             "Count of active workers in the gossip processing pool.",
         )
     });
+pub static BEACON_PROCESSOR_WORKERS_ACTIVE_BY_TYPE: LazyLock<Result<IntGaugeVec>> =
+    LazyLock::new(|| {
+        try_create_int_gauge_vec(
+            "beacon_processor_workers_active_by_type",
+            "Count of active workers in the gossip processing pool, by type of work.",
+            &["type"],
+        )
+    });
 pub static BEACON_PROCESSOR_IDLE_EVENTS_TOTAL: LazyLock<Result<IntCounter>> = LazyLock::new(|| {
     try_create_int_counter(
         "beacon_processor_idle_events_total",
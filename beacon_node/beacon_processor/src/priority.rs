@@ -0,0 +1,262 @@
+//! The order in which the queues of the `BeaconProcessor` are checked when a worker is free.
+//!
+//! The default order is suitable for most nodes, however it may be adjusted by providing a list of
+//! `PriorityOverride`s, each of which moves one type of work before or after another. For example,
+//! a node with few CPUs which serves many peers may wish to verify attestations before serving
+//! `BlocksByRange` requests:
+//!
+//! ```json
+//! [{"work": "blocks_by_range_request", "after": "gossip_attestation"}]
+//! ```
+use crate::WorkType;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// The default order in which queues are checked, from the highest priority to the lowest.
+///
+/// Only work types which are queued are included. Batches are formed from the queue of their
+/// individual work type.
+pub const DEFAULT_PRIORITY_ORDER: &[WorkType] = &[
+    // Check for chain segments first, they're the most efficient way to get blocks into the
+    // system.
+    WorkType::ChainSegment,
+    // Check sync blocks before gossip blocks, since we've already explicitly requested these
+    // blocks.
+    WorkType::RpcBlock,
+    WorkType::RpcBlobs,
+    WorkType::RpcCustodyColumn,
+    // TODO(das): decide proper prioritization for sampling columns
+    WorkType::RpcVerifyDataColumn,
+    WorkType::SamplingResult,
+    // Check delayed blocks before gossip blocks, the gossip blocks might rely on the delayed ones.
+    WorkType::DelayedImportBlock,
+    // Check gossip blocks before gossip attestations, since a block might be required to verify
+    // some attestations.
+    WorkType::GossipBlock,
+    WorkType::GossipBlobSidecar,
+    WorkType::GossipDataColumnSidecar,
+    // Check the priority 0 API requests after blocks and blobs, but before attestations.
+    WorkType::ApiRequestP0,
+    // Check the aggregates, *then* the unaggregates since we assume that aggregates are more
+    // valuable to local validators and effectively give us more information with less signature
+    // verification time.
+    WorkType::GossipAggregate,
+    WorkType::GossipAttestation,
+    // Check sync committee messages after attestations as their rewards are lesser and they don't
+    // influence fork choice.
+    WorkType::GossipSyncContribution,
+    WorkType::GossipSyncSignature,
+    // Aggregates and unaggregates queued for re-processing are older and we care about fresher
+    // ones, so check those first.
+    WorkType::UnknownBlockAggregate,
+    WorkType::UnknownBlockAttestation,
+    // Check RPC methods next. Status messages are needed for sync so prioritize them over syncing
+    // requests from other peers (BlocksByRange and BlocksByRoot)
+    WorkType::Status,
+    WorkType::BlocksByRangeRequest,
+    WorkType::BlocksByRootsRequest,
+    WorkType::BlobsByRangeRequest,
+    WorkType::BlobsByRootsRequest,
+    WorkType::DataColumnsByRootsRequest,
+    WorkType::DataColumnsByRangeRequest,
+    // Prioritize sampling requests after block syncing requests
+    WorkType::UnknownBlockSamplingRequest,
+    // Check slashings after all other consensus messages so we prioritize following head.
+    //
+    // Check attester slashings before proposer slashings since they have the potential to slash
+    // multiple validators at once.
+    WorkType::GossipAttesterSlashing,
+    WorkType::GossipProposerSlashing,
+    // Check exits and address changes late since our validators don't get rewards from them.
+    WorkType::GossipVoluntaryExit,
+    WorkType::GossipBlsToExecutionChange,
+    // Check the priority 1 API requests after we've processed all the interesting things from the
+    // network and things required for us to stay in good repute with our P2P peers.
+    WorkType::ApiRequestP1,
+    // Handle backfill sync chain segments.
+    WorkType::ChainSegmentBackfill,
+    // Handle the columns fetched by custody backfill.
+    WorkType::CustodyBackfillColumns,
+    // Handle light client requests.
+    WorkType::LightClientBootstrapRequest,
+    WorkType::LightClientOptimisticUpdateRequest,
+    WorkType::LightClientFinalityUpdateRequest,
+];
+
+/// Moves `work` so that it is checked immediately before or after another type of work.
+///
+/// Exactly one of `before` and `after` must be set.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PriorityOverride {
+    pub work: WorkType,
+    #[serde(default)]
+    pub before: Option<WorkType>,
+    #[serde(default)]
+    pub after: Option<WorkType>,
+}
+
+/// Read the priorities file at `path`, which is a JSON list of `PriorityOverride`.
+///
+/// The overrides are validated, so that an invalid file is rejected at startup.
+pub fn load_priority_overrides(path: &Path) -> Result<Vec<PriorityOverride>, String> {
+    let file = std::fs::File::open(path)
+        .map_err(|e| format!("Unable to open priorities file {:?}: {:?}", path, e))?;
+    let overrides: Vec<PriorityOverride> = serde_json::from_reader(file)
+        .map_err(|e| format!("Unable to parse priorities file {:?}: {:?}", path, e))?;
+    priority_order(&overrides).map_err(|e| format!("Invalid priorities file {:?}: {}", path, e))?;
+    Ok(overrides)
+}
+
+/// Apply `overrides` to the `DEFAULT_PRIORITY_ORDER`, in the order they are given.
+pub fn priority_order(overrides: &[PriorityOverride]) -> Result<Vec<WorkType>, String> {
+    let mut order = DEFAULT_PRIORITY_ORDER.to_vec();
+
+    for priority_override in overrides {
+        let work = priority_override.work;
+        let (reference, offset) = match (priority_override.before, priority_override.after) {
+            (Some(before), None) => (before, 0),
+            (None, Some(after)) => (after, 1),
+            _ => {
+                return Err(format!(
+                    "exactly one of before and after must be set for {}",
+                    <&str>::from(work)
+                ))
+            }
+        };
+        if work == reference {
+            return Err(format!(
+                "{} cannot be moved relative to itself",
+                <&str>::from(work)
+            ));
+        }
+
+        let index = position(&order, work)?;
+        order.remove(index);
+        let reference_index = position(&order, reference)?;
+        order.insert(reference_index + offset, work);
+    }
+
+    Ok(order)
+}
+
+fn position(order: &[WorkType], work: WorkType) -> Result<usize, String> {
+    order
+        .iter()
+        .position(|other| *other == work)
+        .ok_or_else(|| {
+            format!(
+                "{} does not have a queue and so has no priority",
+                <&str>::from(work)
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index_of(order: &[WorkType], work: WorkType) -> usize {
+        position(order, work).unwrap()
+    }
+
+    #[test]
+    fn default_order() {
+        assert_eq!(priority_order(&[]).unwrap(), DEFAULT_PRIORITY_ORDER);
+    }
+
+    #[test]
+    fn demote_blocks_by_range() {
+        let order = priority_order(&[PriorityOverride {
+            work: WorkType::BlocksByRangeRequest,
+            before: None,
+            after: Some(WorkType::GossipAttestation),
+        }])
+        .unwrap();
+
+        assert_eq!(order.len(), DEFAULT_PRIORITY_ORDER.len());
+        assert_eq!(
+            index_of(&order, WorkType::BlocksByRangeRequest),
+            index_of(&order, WorkType::GossipAttestation) + 1
+        );
+    }
+
+    #[test]
+    fn overrides_apply_in_order() {
+        let order = priority_order(&[
+            PriorityOverride {
+                work: WorkType::Status,
+                before: Some(WorkType::ChainSegment),
+                after: None,
+            },
+            PriorityOverride {
+                work: WorkType::GossipBlock,
+                before: Some(WorkType::Status),
+                after: None,
+            },
+        ])
+        .unwrap();
+
+        assert_eq!(order[0], WorkType::GossipBlock);
+        assert_eq!(order[1], WorkType::Status);
+        assert_eq!(order[2], WorkType::ChainSegment);
+    }
+
+    #[test]
+    fn invalid_overrides() {
+        for priority_override in [
+            // Neither before nor after.
+            PriorityOverride {
+                work: WorkType::Status,
+                before: None,
+                after: None,
+            },
+            // Both before and after.
+            PriorityOverride {
+                work: WorkType::Status,
+                before: Some(WorkType::RpcBlock),
+                after: Some(WorkType::RpcBlock),
+            },
+            // Relative to itself.
+            PriorityOverride {
+                work: WorkType::Status,
+                before: Some(WorkType::Status),
+                after: None,
+            },
+            // Batches are not queued.
+            PriorityOverride {
+                work: WorkType::GossipAttestationBatch,
+                before: Some(WorkType::Status),
+                after: None,
+            },
+            PriorityOverride {
+                work: WorkType::Status,
+                before: Some(WorkType::GossipAggregateBatch),
+                after: None,
+            },
+        ] {
+            assert!(
+                priority_order(&[priority_override.clone()]).is_err(),
+                "{:?}",
+                priority_override
+            );
+        }
+    }
+
+    #[test]
+    fn parse_overrides() {
+        let json = r#"[{"work": "blocks_by_range_request", "after": "gossip_attestation"}]"#;
+        let overrides: Vec<PriorityOverride> = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            overrides,
+            vec![PriorityOverride {
+                work: WorkType::BlocksByRangeRequest,
+                before: None,
+                after: Some(WorkType::GossipAttestation),
+            }]
+        );
+
+        let json = r#"[{"work": "not_a_work_type", "after": "gossip_attestation"}]"#;
+        assert!(serde_json::from_str::<Vec<PriorityOverride>>(json).is_err());
+    }
+}
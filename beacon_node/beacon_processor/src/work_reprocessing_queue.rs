@@ -10,6 +10,9 @@
 //!
 //! Aggregated and unaggregated attestations that failed verification due to referencing an unknown
 //! block will be re-queued until their block is imported, or until they expire.
+//!
+//! On shutdown no further work is scheduled, but the roots of the blocks which the queued work is
+//! waiting on continue to be reported so that they may be persisted by sync.
 use crate::metrics;
 use crate::{AsyncFn, BlockingFn, Work, WorkEvent};
 use fnv::FnvHashMap;
@@ -28,6 +31,7 @@ use std::time::Duration;
 use strum::AsRefStr;
 use task_executor::TaskExecutor;
 use tokio::sync::mpsc::{self, Receiver, Sender};
+use tokio::sync::oneshot;
 use tokio_util::time::delay_queue::{DelayQueue, Key as DelayKey};
 use types::{EthSpec, Hash256, Slot};
 
@@ -108,6 +112,8 @@ pub enum ReprocessQueueMessage {
     UnknownBlockSamplingRequest(QueuedSamplingRequest),
     /// A new backfill batch that needs to be scheduled for processing.
     BackfillSync(QueuedBackfillBatch),
+    /// A request for the roots of the blocks which queued work is waiting on.
+    PendingBlockRoots(oneshot::Sender<Vec<Hash256>>),
 }
 
 /// Events sent by the scheduler once they are ready for re-processing.
@@ -383,17 +389,29 @@ pub fn spawn_reprocess_scheduler<S: SlotClock + 'static>(
     }
     let mut queue = ReprocessQueue::new(ready_work_tx, work_reprocessing_rx, slot_clock);
 
-    executor.spawn(
+    let exit = executor.exit();
+    executor.spawn_without_exit(
         async move {
-            while let Some(msg) = queue.next().await {
-                queue.handle_message(msg, &log);
-            }
+            let mut exit = std::pin::pin!(exit);
+            let exited = loop {
+                tokio::select! {
+                    msg = queue.next() => match msg {
+                        Some(msg) => queue.handle_message(msg, &log),
+                        None => break false,
+                    },
+                    _ = &mut exit => break true,
+                }
+            };
 
             debug!(
                 log,
                 "Re-process queue stopped";
                 "msg" => "shutting down"
             );
+
+            if exited {
+                queue.report_pending_block_roots().await;
+            }
         },
         TASK_NAME,
     );
@@ -436,6 +454,28 @@ impl<S: SlotClock> ReprocessQueue<S> {
         }
     }
 
+    /// Returns the roots of the blocks which queued gossip blocks, attestations and sampling
+    /// requests are waiting on.
+    fn pending_block_roots(&self) -> Vec<Hash256> {
+        self.queued_gossip_block_roots
+            .iter()
+            .chain(self.awaiting_attestations_per_root.keys())
+            .chain(self.awaiting_sampling_requests_per_block_root.keys())
+            .copied()
+            .unique()
+            .collect()
+    }
+
+    /// Answer requests for the pending block roots until all senders are dropped, without
+    /// scheduling any further work.
+    async fn report_pending_block_roots(mut self) {
+        while let Some(msg) = self.work_reprocessing_rx.recv().await {
+            if let ReprocessQueueMessage::PendingBlockRoots(response_tx) = msg {
+                let _ = response_tx.send(self.pending_block_roots());
+            }
+        }
+    }
+
     fn handle_message(&mut self, msg: InboundEvent, log: &Logger) {
         use ReprocessQueueMessage::*;
         match msg {
@@ -848,6 +888,9 @@ impl<S: SlotClock> ReprocessQueue<S> {
                     self.recompute_next_backfill_batch_event();
                 }
             }
+            InboundEvent::Msg(PendingBlockRoots(response_tx)) => {
+                let _ = response_tx.send(self.pending_block_roots());
+            }
             // A block that was queued for later processing is now ready to be processed.
             InboundEvent::ReadyGossipBlock(ready_block) => {
                 let block_root = ready_block.beacon_block_root;
@@ -1164,6 +1207,67 @@ mod tests {
         ));
     }
 
+    #[tokio::test]
+    async fn reports_pending_block_roots() {
+        let runtime = TestRuntime::default();
+        let log = test_logger();
+        let (work_reprocessing_tx, work_reprocessing_rx) = mpsc::channel(4);
+        let (ready_work_tx, _ready_work_rx) = mpsc::channel(1);
+        let slot_clock = Arc::new(testing_slot_clock(12));
+
+        spawn_reprocess_scheduler(
+            ready_work_tx,
+            work_reprocessing_rx,
+            &runtime.task_executor,
+            slot_clock,
+            log,
+            Duration::from_millis(500),
+        )
+        .unwrap();
+
+        let pending_block_roots = || async {
+            let (response_tx, response_rx) = oneshot::channel();
+            work_reprocessing_tx
+                .try_send(ReprocessQueueMessage::PendingBlockRoots(response_tx))
+                .unwrap();
+            let mut roots = response_rx.await.unwrap();
+            roots.sort();
+            roots
+        };
+
+        let attestation_root = Hash256::repeat_byte(1);
+        let sampling_root = Hash256::repeat_byte(2);
+        work_reprocessing_tx
+            .try_send(ReprocessQueueMessage::UnknownBlockUnaggregate(
+                QueuedUnaggregate {
+                    beacon_block_root: attestation_root,
+                    process_fn: Box::new(|| {}),
+                },
+            ))
+            .unwrap();
+        work_reprocessing_tx
+            .try_send(ReprocessQueueMessage::UnknownBlockSamplingRequest(
+                QueuedSamplingRequest {
+                    beacon_block_root: sampling_root,
+                    process_fn: Box::new(|| {}),
+                },
+            ))
+            .unwrap();
+        assert_eq!(
+            pending_block_roots().await,
+            vec![attestation_root, sampling_root]
+        );
+
+        // The root is no longer pending once the block is imported.
+        work_reprocessing_tx
+            .try_send(ReprocessQueueMessage::BlockImported {
+                block_root: attestation_root,
+                parent_root: Hash256::ZERO,
+            })
+            .unwrap();
+        assert_eq!(pending_block_roots().await, vec![sampling_root]);
+    }
+
     /// Advances slot clock and test clock time by the same duration.
     async fn advance_time(slot_clock: &ManualSlotClock, duration: Duration) {
         slot_clock.advance_time(duration);
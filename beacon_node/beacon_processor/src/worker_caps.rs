@@ -0,0 +1,169 @@
+//! Limits on the number of workers which may concurrently process each type of work.
+//!
+//! Without limits a single type of work can occupy the entire worker pool, for example chain
+//! segments during sync. Work of a type which is at its limit is queued, and is started from the
+//! queue once one of the workers processing that type has finished.
+use crate::metrics;
+use crate::priority::DEFAULT_PRIORITY_ORDER;
+use crate::WorkType;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+#[derive(Debug, Default)]
+pub struct WorkerCaps {
+    caps: HashMap<WorkType, usize>,
+    /// The number of active workers of each type, with batches counted under their individual
+    /// work type.
+    active: HashMap<WorkType, usize>,
+}
+
+impl WorkerCaps {
+    pub fn new(caps: HashMap<WorkType, usize>) -> Self {
+        Self {
+            caps,
+            active: HashMap::new(),
+        }
+    }
+
+    /// Returns `true` if no more workers may be started for `work_type`.
+    pub fn is_at_cap(&self, work_type: WorkType) -> bool {
+        let work_type = cap_key(work_type);
+        self.caps
+            .get(&work_type)
+            .is_some_and(|cap| self.active(work_type) >= *cap)
+    }
+
+    pub fn active(&self, work_type: WorkType) -> usize {
+        self.active.get(&cap_key(work_type)).copied().unwrap_or(0)
+    }
+
+    /// Record that a worker has been started for `work_type`.
+    pub fn started(&mut self, work_type: WorkType) {
+        let work_type = cap_key(work_type);
+        let active = self.active.entry(work_type).or_default();
+        *active = active.saturating_add(1);
+        set_active_gauge(work_type, *active);
+    }
+
+    /// Record that a worker for `work_type` has finished.
+    pub fn finished(&mut self, work_type: WorkType) {
+        let work_type = cap_key(work_type);
+        let active = self.active.entry(work_type).or_default();
+        *active = active.saturating_sub(1);
+        set_active_gauge(work_type, *active);
+    }
+}
+
+fn set_active_gauge(work_type: WorkType, active: usize) {
+    metrics::set_gauge_vec(
+        &metrics::BEACON_PROCESSOR_WORKERS_ACTIVE_BY_TYPE,
+        &[work_type.into()],
+        active as i64,
+    );
+}
+
+/// Batches are formed from the queue of their individual work type, so they share its cap.
+fn cap_key(work_type: WorkType) -> WorkType {
+    match work_type {
+        WorkType::GossipAttestationBatch => WorkType::GossipAttestation,
+        WorkType::GossipAggregateBatch => WorkType::GossipAggregate,
+        WorkType::GossipBlobSidecarBatch => WorkType::GossipBlobSidecar,
+        WorkType::GossipSyncSignatureBatch => WorkType::GossipSyncSignature,
+        WorkType::IgnoredRpcBlock => WorkType::RpcBlock,
+        other => other,
+    }
+}
+
+/// Parse caps of the form `chain_segment=4,chain_segment_backfill=2`.
+pub fn parse_worker_caps(caps_str: &str) -> Result<HashMap<WorkType, usize>, String> {
+    let mut caps = HashMap::new();
+
+    for entry in caps_str.split(',') {
+        let (work_type_str, cap_str) = entry
+            .split_once('=')
+            .ok_or_else(|| format!("Worker cap {:?} is not of the form type=cap", entry))?;
+        let work_type_str = work_type_str.trim();
+        let work_type = WorkType::from_str(work_type_str)
+            .map_err(|_| format!("Unknown work type {:?}", work_type_str))?;
+        let cap = cap_str
+            .trim()
+            .parse::<usize>()
+            .map_err(|e| format!("Invalid worker cap for {}: {:?}", work_type_str, e))?;
+
+        // Work at its cap is queued, so work without a queue would never be started.
+        if !DEFAULT_PRIORITY_ORDER.contains(&work_type) {
+            return Err(format!(
+                "{} does not have a queue and so cannot be capped",
+                work_type_str
+            ));
+        }
+        if cap == 0 {
+            return Err(format!(
+                "The worker cap for {} must be non-zero",
+                work_type_str
+            ));
+        }
+        if caps.insert(work_type, cap).is_some() {
+            return Err(format!("Duplicate worker cap for {}", work_type_str));
+        }
+    }
+
+    Ok(caps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn caps_are_enforced() {
+        let mut caps = WorkerCaps::new(HashMap::from([(WorkType::ChainSegment, 2)]));
+
+        caps.started(WorkType::ChainSegment);
+        assert!(!caps.is_at_cap(WorkType::ChainSegment));
+        caps.started(WorkType::ChainSegment);
+        assert!(caps.is_at_cap(WorkType::ChainSegment));
+        caps.finished(WorkType::ChainSegment);
+        assert!(!caps.is_at_cap(WorkType::ChainSegment));
+
+        // Uncapped work is never at its cap.
+        for _ in 0..8 {
+            caps.started(WorkType::GossipBlock);
+        }
+        assert!(!caps.is_at_cap(WorkType::GossipBlock));
+        assert_eq!(caps.active(WorkType::GossipBlock), 8);
+    }
+
+    #[test]
+    fn batches_share_cap() {
+        let mut caps = WorkerCaps::new(HashMap::from([(WorkType::GossipAttestation, 1)]));
+
+        caps.started(WorkType::GossipAttestationBatch);
+        assert!(caps.is_at_cap(WorkType::GossipAttestation));
+        assert_eq!(caps.active(WorkType::GossipAttestation), 1);
+        caps.finished(WorkType::GossipAttestationBatch);
+        assert!(!caps.is_at_cap(WorkType::GossipAttestation));
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            parse_worker_caps("chain_segment=4, chain_segment_backfill=2").unwrap(),
+            HashMap::from([
+                (WorkType::ChainSegment, 4),
+                (WorkType::ChainSegmentBackfill, 2)
+            ])
+        );
+
+        for invalid in [
+            "chain_segment",
+            "chain_segment=0",
+            "chain_segment=four",
+            "not_a_work_type=1",
+            "gossip_attestation_batch=1",
+            "chain_segment=1,chain_segment=2",
+        ] {
+            assert!(parse_worker_caps(invalid).is_err(), "{}", invalid);
+        }
+    }
+}
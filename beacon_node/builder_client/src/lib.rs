@@ -71,6 +71,10 @@ impl BuilderHttpClient {
         &self.user_agent
     }
 
+    pub fn url(&self) -> &SensitiveUrl {
+        &self.server
+    }
+
     async fn get_with_timeout<T: DeserializeOwned, U: IntoUrl>(
         &self,
         url: U,
@@ -5,6 +5,7 @@ use crate::config::{ClientGenesis, Config as ClientConfig};
 use crate::notifier::spawn_notifier;
 use crate::Client;
 use beacon_chain::attestation_simulator::start_attestation_simulator_service;
+use beacon_chain::compaction_service::start_compaction_service;
 use beacon_chain::data_availability_checker::start_availability_cache_maintenance_service;
 use beacon_chain::graffiti_calculator::start_engine_version_cache_refresh_service;
 use beacon_chain::otb_verification_service::start_otb_verification_service;
@@ -31,12 +32,14 @@ use execution_layer::test_utils::generate_genesis_header;
 use execution_layer::ExecutionLayer;
 use futures::channel::mpsc::Receiver;
 use genesis::{interop_genesis_state, Eth1GenesisService, DEFAULT_ETH1_BLOCK_HASH};
+use http_api::historical_states::HistoricalStateService;
 use lighthouse_network::{prometheus_client::registry::Registry, NetworkGlobals};
 use monitoring_api::{MonitoringHttpClient, ProcessType};
+use network::exit_rebroadcast::start_exit_rebroadcast_service;
 use network::{NetworkConfig, NetworkSenders, NetworkService};
 use slasher::Slasher;
 use slasher_service::SlasherService;
-use slog::{debug, info, warn, Logger};
+use slog::{debug, error, info, warn, Logger};
 use ssz::Decode;
 use std::net::TcpListener;
 use std::path::{Path, PathBuf};
@@ -184,12 +187,16 @@ where
 
         let execution_layer = if let Some(config) = config.execution_layer.clone() {
             let context = runtime_context.service_context("exec".into());
+            let reload_jwt_secret_on_sighup = config.reload_jwt_secret_on_sighup;
             let execution_layer = ExecutionLayer::from_config(
                 config,
                 context.executor.clone(),
                 context.log().clone(),
             )
             .map_err(|e| format!("unable to start execution layer endpoints: {:?}", e))?;
+            if reload_jwt_secret_on_sighup {
+                execution_layer.spawn_jwt_secret_reload_routine();
+            }
             Some(execution_layer)
         } else {
             None
@@ -350,12 +357,11 @@ where
                 anchor_blobs_bytes,
             } => {
                 info!(context.log(), "Starting checkpoint sync");
-                if config.chain.genesis_backfill {
-                    info!(
-                        context.log(),
-                        "Blocks will downloaded all the way back to genesis"
-                    );
-                }
+                info!(
+                    context.log(),
+                    "Blocks will be backfilled";
+                    "profile" => ?config.chain.backfill_profile,
+                );
 
                 let anchor_state = BeaconState::from_ssz_bytes(&anchor_state_bytes, &spec)
                     .map_err(|e| format!("Unable to parse weak subj state SSZ: {:?}", e))?;
@@ -388,12 +394,11 @@ where
                     "Starting checkpoint sync";
                     "remote_url" => %url,
                 );
-                if config.chain.genesis_backfill {
-                    info!(
-                        context.log(),
-                        "Blocks will be downloaded all the way back to genesis"
-                    );
-                }
+                info!(
+                    context.log(),
+                    "Blocks will be backfilled";
+                    "profile" => ?config.chain.backfill_profile,
+                );
 
                 let remote = BeaconNodeHttpClient::new(
                     url,
@@ -577,6 +582,7 @@ where
                         eth1_service: Some(genesis_service.eth1_service.clone()),
                         log: context.log().clone(),
                         sse_logging_components: runtime_context.sse_logging_components.clone(),
+                        historical_states: None,
                     });
 
                     // Discard the error from the oneshot.
@@ -662,6 +668,8 @@ where
             None
         };
 
+        let log = context.log().clone();
+        let shutdown_rpc_drain_timeout = config.shutdown_rpc_drain_timeout;
         let (network_globals, network_senders) = NetworkService::start(
             beacon_chain,
             config,
@@ -673,6 +681,23 @@ where
         .await
         .map_err(|e| format!("Failed to start network: {:?}", e))?;
 
+        // When shutting down, reject new inbound RPC requests and let those in flight finish.
+        let inbound_requests = network_senders.inbound_requests();
+        context
+            .shutdown_sequencer
+            .register("drain_inbound_rpc", async move {
+                inbound_requests.stop_accepting();
+                let interrupted = inbound_requests.drain(shutdown_rpc_drain_timeout).await;
+                if interrupted > 0 {
+                    warn!(
+                        log,
+                        "Inbound RPC requests interrupted by shutdown";
+                        "count" => interrupted,
+                        "timeout" => ?shutdown_rpc_drain_timeout,
+                    );
+                }
+            });
+
         self.network_globals = Some(network_globals);
         self.network_senders = Some(network_senders);
         self.libp2p_registry = libp2p_registry;
@@ -812,6 +837,12 @@ where
                     beacon_processor_channels.work_reprocessing_tx.clone(),
                 ),
                 sse_logging_components: runtime_context.sse_logging_components.clone(),
+                historical_states: self.beacon_chain.clone().map(|chain| {
+                    Arc::new(HistoricalStateService::from_config(
+                        chain,
+                        &self.http_api_config,
+                    ))
+                }),
                 log: log.clone(),
             });
 
@@ -969,7 +1000,28 @@ where
                 );
             }
 
+            // Persist the chain once the RPC requests have drained, rather than relying on the
+            // `BeaconChain` being dropped before the runtime shuts down. It isn't persisted again
+            // when it is dropped.
+            let weak_chain = Arc::downgrade(beacon_chain);
+            let persist_log = runtime_context.log().clone();
+            runtime_context
+                .shutdown_sequencer
+                .register("persist_beacon_chain", async move {
+                    let Some(chain) = weak_chain.upgrade() else {
+                        return;
+                    };
+                    if let Err(e) = chain.persist_on_shutdown() {
+                        error!(
+                            persist_log,
+                            "Failed to persist beacon chain on shutdown";
+                            "error" => ?e,
+                        );
+                    }
+                });
+
             start_proposer_prep_service(runtime_context.executor.clone(), beacon_chain.clone());
+            start_compaction_service(runtime_context.executor.clone(), beacon_chain.clone());
             start_otb_verification_service(runtime_context.executor.clone(), beacon_chain.clone());
             start_availability_cache_maintenance_service(
                 runtime_context.executor.clone(),
@@ -983,6 +1035,13 @@ where
                 beacon_chain.task_executor.clone(),
                 beacon_chain.clone(),
             );
+            if let Some(network_senders) = &self.network_senders {
+                start_exit_rebroadcast_service(
+                    runtime_context.executor.clone(),
+                    beacon_chain.clone(),
+                    network_senders.network_send(),
+                );
+            }
         }
 
         Ok(Client {
@@ -1023,6 +1082,19 @@ where
             .build()
             .map_err(|e| format!("Failed to build beacon chain: {}", e))?;
 
+        // Import era files before the network starts, so that backfill sync resumes from the
+        // oldest imported block. Any blocks which could not be imported are left to backfill.
+        if let Some(era_dir) = &chain.config.era_import_dir {
+            if let Err(e) = chain.import_era_files(era_dir) {
+                warn!(
+                    context.log(),
+                    "Failed to import era files";
+                    "error" => ?e,
+                    "oldest_block_slot" => chain.store.get_anchor_info().oldest_block_slot,
+                );
+            }
+        }
+
         self.beacon_chain = Some(Arc::new(chain));
         self.beacon_chain_builder = None;
 
@@ -8,6 +8,22 @@ pub static SYNC_SLOTS_PER_SECOND: LazyLock<Result<IntGauge>> = LazyLock::new(||
     )
 });
 
+pub static SYNC_THROUGHPUT_PER_SECOND: LazyLock<Result<GaugeVec>> = LazyLock::new(|| {
+    try_create_float_gauge_vec(
+        "sync_throughput_per_second",
+        "The number of slots, blocks, blobs and data columns processed per second by each sync",
+        &["sync", "type"],
+    )
+});
+
+pub static SYNC_ESTIMATED_SECONDS_REMAINING: LazyLock<Result<GaugeVec>> = LazyLock::new(|| {
+    try_create_float_gauge_vec(
+        "sync_estimated_seconds_remaining",
+        "The estimated time until each sync completes, or zero if unknown",
+        &["sync"],
+    )
+});
+
 pub static IS_SYNCED: LazyLock<Result<IntGauge>> = LazyLock::new(|| {
     try_create_int_gauge(
         "sync_eth2_synced",
@@ -6,12 +6,14 @@ use beacon_chain::{
     electra_readiness::ElectraReadiness,
     BeaconChain, BeaconChainTypes, ExecutionStatus,
 };
-use lighthouse_network::{types::SyncState, NetworkGlobals};
+use lighthouse_network::{
+    types::{SyncKind, SyncState, SyncThroughput},
+    NetworkGlobals,
+};
 use slog::{crit, debug, error, info, warn, Logger};
 use slot_clock::SlotClock;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::Mutex;
 use tokio::time::sleep;
 use types::*;
 
@@ -22,9 +24,6 @@ const DAYS_PER_WEEK: i64 = 7;
 const HOURS_PER_DAY: i64 = 24;
 const MINUTES_PER_HOUR: i64 = 60;
 
-/// The number of historical observations that should be used to determine the average sync time.
-const SPEEDO_OBSERVATIONS: usize = 4;
-
 /// The number of slots between logs that give detail about backfill process.
 const BACKFILL_LOG_INTERVAL: u64 = 5;
 
@@ -37,13 +36,8 @@ pub fn spawn_notifier<T: BeaconChainTypes>(
 ) -> Result<(), String> {
     let slot_duration = Duration::from_secs(seconds_per_slot);
 
-    let speedo = Mutex::new(Speedo::default());
     let log = executor.log().clone();
 
-    // Keep track of sync state and reset the speedo on specific sync state changes.
-    // Specifically, if we switch between a sync and a backfill sync, reset the speedo.
-    let mut current_sync_state = network.sync_state();
-
     // Store info if we are required to do a backfill sync.
     let original_oldest_block_slot = beacon_chain.store.get_anchor_info().oldest_block_slot;
 
@@ -89,25 +83,7 @@ pub fn spawn_notifier<T: BeaconChainTypes>(
             sleep(wait).await;
 
             let connected_peer_count = network.connected_peers();
-            let sync_state = network.sync_state();
-
-            // Determine if we have switched syncing chains
-            if sync_state != current_sync_state {
-                match (current_sync_state, &sync_state) {
-                    (_, SyncState::BackFillSyncing { .. }) => {
-                        // We have transitioned to a backfill sync. Reset the speedo.
-                        let mut speedo = speedo.lock().await;
-                        speedo.clear();
-                    }
-                    (SyncState::BackFillSyncing { .. }, _) => {
-                        // We have transitioned from a backfill sync, reset the speedo
-                        let mut speedo = speedo.lock().await;
-                        speedo.clear();
-                    }
-                    (_, _) => {}
-                }
-                current_sync_state = sync_state;
-            }
+            let current_sync_state = network.sync_state();
 
             let cached_head = beacon_chain.canonical_head.cached_head();
             let head_slot = cached_head.head_slot();
@@ -134,36 +110,61 @@ pub fn spawn_notifier<T: BeaconChainTypes>(
             // progress.
             let mut sync_distance = current_slot - head_slot;
 
-            let mut speedo = speedo.lock().await;
-            match current_sync_state {
-                SyncState::BackFillSyncing { .. } => {
-                    // Observe backfilling sync info.
+            // Observe the progress of the active sync. The observations of a sync are cleared
+            // when it stops, so that its speed is measured afresh if it restarts.
+            let is_backfilling = matches!(current_sync_state, SyncState::BackFillSyncing { .. });
+            let (forward_throughput, backfill_throughput, custody_backfill_throughput) = {
+                let mut sync_progress = network.sync_progress.write();
+                let now = Instant::now();
+
+                if current_sync_state.is_syncing() {
+                    sync_progress.observe(SyncKind::Forward, head_slot, current_slot, now);
+                } else {
+                    sync_progress.clear(SyncKind::Forward);
+                }
+
+                if is_backfilling {
                     let current_oldest_block_slot =
                         beacon_chain.store.get_anchor_info().oldest_block_slot;
                     sync_distance = current_oldest_block_slot
                         .saturating_sub(beacon_chain.genesis_backfill_slot);
-                    speedo
-                        // For backfill sync use a fake slot which is the distance we've progressed
-                        // from the starting `original_oldest_block_slot`.
-                        .observe(
-                            original_oldest_block_slot.saturating_sub(current_oldest_block_slot),
-                            Instant::now(),
-                        );
-                }
-                SyncState::SyncingFinalized { .. }
-                | SyncState::SyncingHead { .. }
-                | SyncState::SyncTransition => {
-                    speedo.observe(head_slot, Instant::now());
+                    // For backfill sync use a fake slot which is the distance we've progressed
+                    // from the starting `original_oldest_block_slot`.
+                    sync_progress.observe(
+                        SyncKind::Backfill,
+                        original_oldest_block_slot.saturating_sub(current_oldest_block_slot),
+                        original_oldest_block_slot
+                            .saturating_sub(beacon_chain.genesis_backfill_slot),
+                        now,
+                    );
+                } else {
+                    sync_progress.clear(SyncKind::Backfill);
                 }
-                SyncState::Stalled | SyncState::Synced => {}
-            }
 
+                // Custody backfill observes its own progress as it completes each epoch.
+                (
+                    sync_progress.throughput(SyncKind::Forward),
+                    sync_progress.throughput(SyncKind::Backfill),
+                    sync_progress.throughput(SyncKind::CustodyBackfill),
+                )
+            };
+
+            set_sync_throughput_metrics("forward", forward_throughput.as_ref());
+            set_sync_throughput_metrics("backfill", backfill_throughput.as_ref());
+            set_sync_throughput_metrics("custody_backfill", custody_backfill_throughput.as_ref());
             // NOTE: This is going to change based on which sync we are currently performing. A
             // backfill sync should process slots significantly faster than the other sync
             // processes.
+            let active_throughput = if is_backfilling {
+                backfill_throughput.as_ref()
+            } else {
+                forward_throughput.as_ref()
+            };
             metrics::set_gauge(
                 &metrics::SYNC_SLOTS_PER_SECOND,
-                speedo.slots_per_second().unwrap_or(0_f64) as i64,
+                active_throughput
+                    .and_then(|throughput| throughput.slots_per_second)
+                    .unwrap_or(0_f64) as i64,
             );
 
             if connected_peer_count <= WARN_PEER_COUNT {
@@ -183,7 +184,6 @@ pub fn spawn_notifier<T: BeaconChainTypes>(
             );
 
             // Log if we are backfilling.
-            let is_backfilling = matches!(current_sync_state, SyncState::BackFillSyncing { .. });
             if is_backfilling
                 && last_backfill_log_slot
                     .map_or(true, |slot| slot + BACKFILL_LOG_INTERVAL <= current_slot)
@@ -196,7 +196,12 @@ pub fn spawn_notifier<T: BeaconChainTypes>(
                     slot_distance_pretty(sync_distance, slot_duration)
                 );
 
-                let speed = speedo.slots_per_second();
+                let speed = backfill_throughput
+                    .as_ref()
+                    .and_then(|throughput| throughput.slots_per_second);
+                let est_time = backfill_throughput
+                    .as_ref()
+                    .and_then(|throughput| throughput.estimated_seconds_remaining);
                 let display_speed = speed.map_or(false, |speed| speed != 0.0);
 
                 if display_speed {
@@ -205,14 +210,14 @@ pub fn spawn_notifier<T: BeaconChainTypes>(
                         "Downloading historical blocks";
                         "distance" => distance,
                         "speed" => sync_speed_pretty(speed),
-                        "est_time" => estimated_time_pretty(speedo.estimated_time_till_slot(original_oldest_block_slot.saturating_sub(beacon_chain.genesis_backfill_slot))),
+                        "est_time" => estimated_time_pretty(est_time),
                     );
                 } else {
                     info!(
                         log,
                         "Downloading historical blocks";
                         "distance" => distance,
-                        "est_time" => estimated_time_pretty(speedo.estimated_time_till_slot(original_oldest_block_slot.saturating_sub(beacon_chain.genesis_backfill_slot))),
+                        "est_time" => estimated_time_pretty(est_time),
                     );
                 }
             } else if !is_backfilling && last_backfill_log_slot.is_some() {
@@ -232,7 +237,12 @@ pub fn spawn_notifier<T: BeaconChainTypes>(
                     slot_distance_pretty(sync_distance, slot_duration)
                 );
 
-                let speed = speedo.slots_per_second();
+                let speed = forward_throughput
+                    .as_ref()
+                    .and_then(|throughput| throughput.slots_per_second);
+                let est_time = forward_throughput
+                    .as_ref()
+                    .and_then(|throughput| throughput.estimated_seconds_remaining);
                 let display_speed = speed.map_or(false, |speed| speed != 0.0);
 
                 if display_speed {
@@ -242,7 +252,7 @@ pub fn spawn_notifier<T: BeaconChainTypes>(
                         "peers" => peer_count_pretty(connected_peer_count),
                         "distance" => distance,
                         "speed" => sync_speed_pretty(speed),
-                        "est_time" => estimated_time_pretty(speedo.estimated_time_till_slot(current_slot)),
+                        "est_time" => estimated_time_pretty(est_time),
                     );
                 } else {
                     info!(
@@ -250,7 +260,7 @@ pub fn spawn_notifier<T: BeaconChainTypes>(
                         "Syncing";
                         "peers" => peer_count_pretty(connected_peer_count),
                         "distance" => distance,
-                        "est_time" => estimated_time_pretty(speedo.estimated_time_till_slot(current_slot)),
+                        "est_time" => estimated_time_pretty(est_time),
                     );
                 }
             } else if current_sync_state.is_synced() {
@@ -800,73 +810,29 @@ fn seconds_pretty(secs: f64) -> String {
     }
 }
 
-/// "Speedo" is Australian for speedometer. This struct observes syncing times.
-#[derive(Default)]
-pub struct Speedo(Vec<(Slot, Instant)>);
-
-impl Speedo {
-    /// Observe that we were at some `slot` at the given `instant`.
-    pub fn observe(&mut self, slot: Slot, instant: Instant) {
-        if self.0.len() > SPEEDO_OBSERVATIONS {
-            self.0.remove(0);
-        }
-
-        self.0.push((slot, instant));
-    }
-
-    /// Returns the average of the speeds between each observation.
-    ///
-    /// Does not gracefully handle slots that are above `u32::MAX`.
-    pub fn slots_per_second(&self) -> Option<f64> {
-        let speeds = self
-            .0
-            .windows(2)
-            .filter_map(|windows| {
-                let (slot_a, instant_a) = windows[0];
-                let (slot_b, instant_b) = windows[1];
-
-                // Taking advantage of saturating subtraction on `Slot`.
-                let distance = f64::from((slot_b - slot_a).as_u64() as u32);
-
-                let seconds = f64::from((instant_b - instant_a).as_millis() as u32) / 1_000.0;
-
-                if seconds > 0.0 {
-                    Some(distance / seconds)
-                } else {
-                    None
-                }
-            })
-            .collect::<Vec<f64>>();
-
-        let count = speeds.len();
-        let sum: f64 = speeds.iter().sum();
-
-        if count > 0 {
-            Some(sum / f64::from(count as u32))
-        } else {
-            None
-        }
-    }
-
-    /// Returns the time we should reach the given `slot`, judging by the latest observation and
-    /// historical average syncing time.
-    ///
-    /// Returns `None` if the slot is prior to our latest observed slot or we have not made any
-    /// observations.
-    pub fn estimated_time_till_slot(&self, target_slot: Slot) -> Option<f64> {
-        let (prev_slot, _) = self.0.last()?;
-        let slots_per_second = self.slots_per_second()?;
-
-        if target_slot > *prev_slot && slots_per_second > 0.0 {
-            let distance = (target_slot - *prev_slot).as_u64() as f64;
-            Some(distance / slots_per_second)
-        } else {
-            None
-        }
-    }
-
-    /// Clears all past observations to be used for an alternative sync (i.e backfill sync).
-    pub fn clear(&mut self) {
-        self.0.clear()
+/// Expose the throughput of a sync as metrics, or zeroes if it is not running.
+fn set_sync_throughput_metrics(sync: &str, throughput: Option<&SyncThroughput>) {
+    let rates = [
+        ("slots", throughput.and_then(|t| t.slots_per_second)),
+        ("blocks", throughput.and_then(|t| t.blocks_per_second)),
+        ("blobs", throughput.and_then(|t| t.blobs_per_second)),
+        (
+            "data_columns",
+            throughput.and_then(|t| t.data_columns_per_second),
+        ),
+    ];
+    for (kind, rate) in rates {
+        metrics::set_float_gauge_vec(
+            &metrics::SYNC_THROUGHPUT_PER_SECOND,
+            &[sync, kind],
+            rate.unwrap_or(0.0),
+        );
     }
+    metrics::set_float_gauge_vec(
+        &metrics::SYNC_ESTIMATED_SECONDS_REMAINING,
+        &[sync],
+        throughput
+            .and_then(|t| t.estimated_seconds_remaining)
+            .unwrap_or(0.0),
+    );
 }
@@ -126,6 +126,65 @@ impl SszDepositCache {
             deposit_roots: self.deposit_roots.clone(),
         })
     }
+
+    /// Check that the cache is internally consistent, by recomputing the leaves and roots of the
+    /// deposit tree from the logs and the finalized snapshot.
+    pub fn validate(&self) -> Result<(), String> {
+        let mut tree = if let Some(snapshot) = &self.deposit_tree_snapshot {
+            if !snapshot.is_valid() {
+                return Err("the finalized deposit snapshot has an incorrect root".into());
+            }
+            if snapshot.deposit_count != self.finalized_deposit_count {
+                return Err(format!(
+                    "the finalized deposit snapshot has {} deposits but {} are finalized",
+                    snapshot.deposit_count, self.finalized_deposit_count
+                ));
+            }
+            DepositDataTree::from_snapshot(snapshot, DEPOSIT_TREE_DEPTH)
+                .map_err(|e| format!("invalid finalized deposit snapshot: {:?}", e))?
+        } else {
+            if self.finalized_deposit_count != 0 {
+                return Err("the finalized deposit snapshot is missing".into());
+            }
+            DepositDataTree::create(&[], 0, DEPOSIT_TREE_DEPTH)
+        };
+
+        if self.leaves.len() != self.logs.len() {
+            return Err("logs and leaves have different lengths".into());
+        }
+        if self.leaves.len() + 1 != self.deposit_roots.len() {
+            return Err("deposit_roots length must be only one more than leaves".into());
+        }
+        if self.deposit_roots.first() != Some(&tree.root()) {
+            return Err("the deposit root of the finalized deposits is incorrect".into());
+        }
+
+        for (i, (log, leaf)) in self.logs.iter().zip(&self.leaves).enumerate() {
+            let index = self.finalized_deposit_count + i as u64;
+            if log.index != index {
+                return Err(format!(
+                    "expected deposit {} but found {}",
+                    index, log.index
+                ));
+            }
+            if *leaf != log.deposit_data.tree_hash_root() {
+                return Err(format!(
+                    "the leaf of deposit {} does not match its log",
+                    index
+                ));
+            }
+            tree.push_leaf(*leaf)
+                .map_err(|e| format!("unable to push leaf {}: {:?}", index, e))?;
+            if self.deposit_roots[i + 1] != tree.root() {
+                return Err(format!(
+                    "the deposit root after deposit {} is incorrect",
+                    index
+                ));
+            }
+        }
+
+        Ok(())
+    }
 }
 
 /// Mirrors the merkle tree of deposits in the eth1 deposit contract.
@@ -1061,6 +1120,42 @@ pub mod tests {
         verify_equality(&deposit_cache, &recovered_cache);
     }
 
+    #[test]
+    fn validate() {
+        let mut deposit_cache = get_cache_with_deposits(512);
+        assert_eq!(
+            SszDepositCache::from_deposit_cache(&deposit_cache).validate(),
+            Ok(())
+        );
+
+        let block383 = fake_eth1_block(&deposit_cache, 383).expect("should create fake eth1 block");
+        deposit_cache.finalize(block383).expect("should finalize");
+        let ssz_cache = SszDepositCache::from_deposit_cache(&deposit_cache);
+        assert_eq!(ssz_cache.validate(), Ok(()));
+
+        let mut corrupt_leaf = ssz_cache.clone();
+        corrupt_leaf.leaves[3] = Hash256::repeat_byte(1);
+        assert!(corrupt_leaf.validate().is_err());
+
+        let mut corrupt_root = ssz_cache.clone();
+        corrupt_root.deposit_roots[64] = Hash256::repeat_byte(1);
+        assert!(corrupt_root.validate().is_err());
+
+        let mut missing_log = ssz_cache.clone();
+        missing_log.logs.remove(10);
+        missing_log.leaves.remove(10);
+        missing_log.deposit_roots.remove(11);
+        assert!(missing_log.validate().is_err());
+
+        let mut corrupt_snapshot = ssz_cache;
+        corrupt_snapshot
+            .deposit_tree_snapshot
+            .as_mut()
+            .unwrap()
+            .deposit_count += 1;
+        assert!(corrupt_snapshot.validate().is_err());
+    }
+
     #[test]
     fn ssz_encode_decode_with_finalization() {
         let mut deposit_cache = get_cache_with_deposits(512);
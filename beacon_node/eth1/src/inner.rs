@@ -110,6 +110,26 @@ impl SszEth1Cache {
         }
     }
 
+    /// Create the caches of a service whose deposit tree is initialized from `snapshot`, as by
+    /// `Service::from_deposit_snapshot`.
+    pub fn from_deposit_snapshot(
+        deposit_contract_deploy_block: u64,
+        snapshot: &DepositTreeSnapshot,
+    ) -> Result<Self, String> {
+        let deposit_updater =
+            DepositUpdater::from_snapshot(deposit_contract_deploy_block, snapshot)?;
+        Ok(Self {
+            block_cache: BlockCache::default(),
+            deposit_cache: SszDepositCache::from_deposit_cache(&deposit_updater.cache),
+            last_processed_block: deposit_updater.last_processed_block,
+        })
+    }
+
+    /// Check that the deposit cache is internally consistent.
+    pub fn validate(&self) -> Result<(), String> {
+        self.deposit_cache.validate()
+    }
+
     pub fn to_inner(&self, config: Config, spec: Arc<ChainSpec>) -> Result<Inner, String> {
         Ok(Inner {
             block_cache: RwLock::new(self.block_cache.clone()),
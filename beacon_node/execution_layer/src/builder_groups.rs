@@ -0,0 +1,176 @@
+//! Per-validator builder configuration.
+//!
+//! Validators managed by the same beacon node may be assigned to groups, each with its own builder
+//! and minimum bid. This allows operators to run validators under different commercial
+//! agreements from a single node. Validators which are not in any group use the builder given by
+//! `--builder` without a minimum bid.
+use crate::Uint256;
+use builder_client::BuilderHttpClient;
+use sensitive_url::SensitiveUrl;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use types::PublicKeyBytes;
+
+/// The number of wei in a gwei.
+const WEI_PER_GWEI: u64 = 1_000_000_000;
+
+/// Convert an amount in gwei to wei.
+pub(crate) fn gwei_to_wei(gwei: u64) -> Uint256 {
+    Uint256::from(gwei).saturating_mul(Uint256::from(WEI_PER_GWEI))
+}
+
+/// The configuration of a group of validators, as read from the builder groups file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BuilderGroupConfig {
+    pub name: String,
+    /// The builder used by this group. The default builder is used if this is `None`.
+    #[serde(default)]
+    pub builder_url: Option<SensitiveUrl>,
+    /// Builder bids below this value (in gwei) are ignored in favour of the local payload.
+    #[serde(default)]
+    pub min_bid_gwei: Option<u64>,
+    pub validators: Vec<PublicKeyBytes>,
+}
+
+/// Read the builder groups file at `path`, which is a JSON list of `BuilderGroupConfig`.
+pub fn load_builder_groups(path: &Path) -> Result<Vec<BuilderGroupConfig>, String> {
+    let file = std::fs::File::open(path)
+        .map_err(|e| format!("Unable to open builder groups file {:?}: {:?}", path, e))?;
+    serde_json::from_reader(file)
+        .map_err(|e| format!("Unable to parse builder groups file {:?}: {:?}", path, e))
+}
+
+/// A group of validators sharing a builder configuration.
+pub struct BuilderGroup {
+    pub name: String,
+    pub builder: Option<Arc<BuilderHttpClient>>,
+    pub min_bid: Option<Uint256>,
+}
+
+/// The builder groups of all validators with a custom builder configuration.
+#[derive(Default)]
+pub struct BuilderGroups {
+    groups: Vec<BuilderGroup>,
+    /// Maps the public key of each grouped validator to the index of its group.
+    validators: HashMap<PublicKeyBytes, usize>,
+}
+
+impl BuilderGroups {
+    /// Build the groups from their configuration, creating a builder client for each group with
+    /// a custom builder.
+    ///
+    /// Returns an error if a validator is assigned to more than one group.
+    pub fn new(
+        configs: Vec<BuilderGroupConfig>,
+        builder_user_agent: Option<String>,
+        builder_header_timeout: Option<Duration>,
+    ) -> Result<Self, String> {
+        let mut builder_groups = Self::default();
+
+        for config in configs {
+            let index = builder_groups.groups.len();
+            for pubkey in config.validators {
+                if let Some(other) = builder_groups.validators.insert(pubkey, index) {
+                    return Err(format!(
+                        "Validator {:?} is in builder groups {} and {}",
+                        pubkey, builder_groups.groups[other].name, config.name
+                    ));
+                }
+            }
+
+            let builder = config
+                .builder_url
+                .map(|url| {
+                    BuilderHttpClient::new(url, builder_user_agent.clone(), builder_header_timeout)
+                        .map(Arc::new)
+                        .map_err(|e| {
+                            format!("Invalid builder for builder group {}: {:?}", config.name, e)
+                        })
+                })
+                .transpose()?;
+
+            builder_groups.groups.push(BuilderGroup {
+                name: config.name,
+                builder,
+                min_bid: config.min_bid_gwei.map(gwei_to_wei),
+            });
+        }
+
+        Ok(builder_groups)
+    }
+
+    /// Returns the group of the validator with `pubkey`, if any.
+    pub fn group_for(&self, pubkey: &PublicKeyBytes) -> Option<&BuilderGroup> {
+        self.validators
+            .get(pubkey)
+            .and_then(|index| self.groups.get(*index))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pubkey(byte: u8) -> PublicKeyBytes {
+        let mut bytes = [0; 48];
+        bytes[0] = byte;
+        PublicKeyBytes::deserialize(&bytes).unwrap()
+    }
+
+    fn group(name: &str, min_bid_gwei: Option<u64>, validators: &[u8]) -> BuilderGroupConfig {
+        BuilderGroupConfig {
+            name: name.to_string(),
+            builder_url: None,
+            min_bid_gwei,
+            validators: validators.iter().copied().map(pubkey).collect(),
+        }
+    }
+
+    #[test]
+    fn group_lookup() {
+        let groups = BuilderGroups::new(
+            vec![group("a", Some(2), &[1, 2]), group("b", None, &[3])],
+            None,
+            None,
+        )
+        .unwrap();
+
+        let a = groups.group_for(&pubkey(2)).unwrap();
+        assert_eq!(a.name, "a");
+        assert_eq!(a.min_bid, Some(Uint256::from(2_000_000_000u64)));
+        assert!(a.builder.is_none());
+        assert_eq!(groups.group_for(&pubkey(3)).unwrap().name, "b");
+        assert!(groups.group_for(&pubkey(4)).is_none());
+    }
+
+    #[test]
+    fn validator_in_two_groups() {
+        assert!(BuilderGroups::new(
+            vec![group("a", None, &[1, 2]), group("b", None, &[2])],
+            None,
+            None
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn parse_config() {
+        let json = format!(
+            r#"[{{"name": "a", "builder_url": "http://localhost:18550", "min_bid_gwei": 5, "validators": ["{:?}"]}}]"#,
+            pubkey(1)
+        );
+        let configs: Vec<BuilderGroupConfig> = serde_json::from_str(&json).unwrap();
+        assert_eq!(configs.len(), 1);
+        assert_eq!(configs[0].min_bid_gwei, Some(5));
+        assert_eq!(configs[0].validators, vec![pubkey(1)]);
+        assert!(configs[0].builder_url.is_some());
+    }
+}
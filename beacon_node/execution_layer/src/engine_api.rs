@@ -35,7 +35,7 @@ mod new_payload_request;
 
 pub use new_payload_request::{
     NewPayloadRequest, NewPayloadRequestBellatrix, NewPayloadRequestCapella,
-    NewPayloadRequestDeneb, NewPayloadRequestElectra,
+    NewPayloadRequestDeneb, NewPayloadRequestElectra, OwnedNewPayloadRequest,
 };
 
 pub const LATEST_TAG: &str = "latest";
@@ -84,6 +84,11 @@ impl Auth {
         }
     }
 
+    /// Replace the secret used to sign tokens, keeping the claims parameters.
+    pub fn set_secret(&mut self, secret: JwtKey) {
+        self.key = EncodingKey::from_secret(secret.as_bytes());
+    }
+
     /// Create a new `Auth` struct given the path to the file containing the hex
     /// encoded jwt key.
     pub fn new_with_path(
@@ -1,9 +1,10 @@
 //! Contains an implementation of `EngineAPI` using the JSON-RPC API via HTTP.
 
 use super::*;
-use crate::auth::Auth;
+use crate::auth::{Auth, JwtKey};
 use crate::json_structures::*;
 use lighthouse_version::{COMMIT_PREFIX, VERSION};
+use parking_lot::RwLock;
 use reqwest::header::CONTENT_TYPE;
 use sensitive_url::SensitiveUrl;
 use serde::de::DeserializeOwned;
@@ -602,7 +603,7 @@ pub struct HttpJsonRpc {
     pub execution_timeout_multiplier: u32,
     pub engine_capabilities_cache: Mutex<Option<CachedResponse<EngineCapabilities>>>,
     pub engine_version_cache: Mutex<Option<CachedResponse<Vec<ClientVersionV1>>>>,
-    auth: Option<Auth>,
+    auth: Option<RwLock<Auth>>,
 }
 
 impl HttpJsonRpc {
@@ -631,7 +632,7 @@ impl HttpJsonRpc {
             execution_timeout_multiplier: execution_timeout_multiplier.unwrap_or(1),
             engine_capabilities_cache: Mutex::new(None),
             engine_version_cache: Mutex::new(None),
-            auth: Some(auth),
+            auth: Some(RwLock::new(auth)),
         })
     }
 
@@ -657,7 +658,8 @@ impl HttpJsonRpc {
 
         // Generate and add a jwt token to the header if auth is defined.
         if let Some(auth) = &self.auth {
-            request = request.bearer_auth(auth.generate_token()?);
+            let token = auth.read().generate_token()?;
+            request = request.bearer_auth(token);
         };
 
         let body: JsonResponseBody = request.send().await?.error_for_status()?.json().await?;
@@ -678,6 +680,21 @@ impl HttpJsonRpc {
     }
 }
 
+impl HttpJsonRpc {
+    /// Sign all subsequent requests with `secret`.
+    ///
+    /// Returns an error if this client was created without authentication.
+    pub fn set_jwt_secret(&self, secret: JwtKey) -> Result<(), Error> {
+        let auth = self.auth.as_ref().ok_or_else(|| {
+            Error::Auth(auth::Error::InvalidKey(
+                "the client does not use JWT authentication".to_string(),
+            ))
+        })?;
+        auth.write().set_secret(secret);
+        Ok(())
+    }
+}
+
 impl std::fmt::Display for HttpJsonRpc {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}, auth={}", self.url, self.auth.is_some())
@@ -210,6 +210,62 @@ impl<'a, E: EthSpec> TryFrom<ExecutionPayloadRef<'a, E>> for NewPayloadRequest<'
     }
 }
 
+/// An owned copy of a `NewPayloadRequest`, which may outlive the block it was made from.
+///
+/// The fields which are absent from the request's fork are left at their defaults.
+#[derive(Clone, Debug)]
+pub struct OwnedNewPayloadRequest<E: EthSpec> {
+    execution_payload: ExecutionPayload<E>,
+    versioned_hashes: Vec<VersionedHash>,
+    parent_beacon_block_root: Hash256,
+    execution_requests: ExecutionRequests<E>,
+}
+
+impl<E: EthSpec> OwnedNewPayloadRequest<E> {
+    pub fn as_request(&self) -> NewPayloadRequest<'_, E> {
+        match &self.execution_payload {
+            ExecutionPayload::Bellatrix(execution_payload) => {
+                NewPayloadRequest::Bellatrix(NewPayloadRequestBellatrix { execution_payload })
+            }
+            ExecutionPayload::Capella(execution_payload) => {
+                NewPayloadRequest::Capella(NewPayloadRequestCapella { execution_payload })
+            }
+            ExecutionPayload::Deneb(execution_payload) => {
+                NewPayloadRequest::Deneb(NewPayloadRequestDeneb {
+                    execution_payload,
+                    versioned_hashes: self.versioned_hashes.clone(),
+                    parent_beacon_block_root: self.parent_beacon_block_root,
+                })
+            }
+            ExecutionPayload::Electra(execution_payload) => {
+                NewPayloadRequest::Electra(NewPayloadRequestElectra {
+                    execution_payload,
+                    versioned_hashes: self.versioned_hashes.clone(),
+                    parent_beacon_block_root: self.parent_beacon_block_root,
+                    execution_requests_list: &self.execution_requests,
+                })
+            }
+        }
+    }
+}
+
+impl<E: EthSpec> From<&NewPayloadRequest<'_, E>> for OwnedNewPayloadRequest<E> {
+    fn from(request: &NewPayloadRequest<'_, E>) -> Self {
+        Self {
+            execution_payload: request.clone().into_execution_payload(),
+            versioned_hashes: request.versioned_hashes().cloned().unwrap_or_default(),
+            parent_beacon_block_root: request
+                .parent_beacon_block_root()
+                .copied()
+                .unwrap_or_default(),
+            execution_requests: request
+                .execution_requests_list()
+                .map(|execution_requests| (*execution_requests).clone())
+                .unwrap_or_default(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::versioned_hashes::Error as VersionedHashError;
@@ -1,26 +1,31 @@
 //! Provides generic behaviour for multiple execution engines, specifically fallback behaviour.
 
 use crate::engine_api::{
-    EngineCapabilities, Error as EngineApiError, ForkchoiceUpdatedResponse, PayloadAttributes,
-    PayloadId,
+    EngineCapabilities, Error as EngineApiError, ForkchoiceUpdatedResponse, NewPayloadRequest,
+    PayloadAttributes, PayloadId, PayloadStatusV1, PayloadStatusV1Status,
 };
 use crate::{ClientVersionV1, HttpJsonRpc};
 use lru::LruCache;
 use slog::{debug, error, info, warn, Logger};
 use std::future::Future;
 use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use task_executor::TaskExecutor;
 use tokio::sync::{watch, Mutex, RwLock};
 use tokio_stream::wrappers::WatchStream;
 use types::non_zero_usize::new_non_zero_usize;
-use types::ExecutionBlockHash;
+use types::{EthSpec, ExecutionBlockHash, VersionedHash};
 
 /// The number of payload IDs that will be stored for each `Engine`.
 ///
 /// Since the size of each value is small (~800 bytes) a large number is used for safety.
 const PAYLOAD_ID_LRU_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(512);
+/// The number of `engine_newPayload` verdicts that will be stored for each `Engine`.
+///
+/// This only needs to cover the payloads of recent blocks and their siblings.
+const NEW_PAYLOAD_LRU_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(64);
 const CACHED_RESPONSE_AGE_LIMIT: Duration = Duration::from_secs(900); // 15 minutes
 
 /// Stores the remembered state of a engine.
@@ -113,6 +118,14 @@ struct PayloadIdCacheKey {
     pub payload_attributes: PayloadAttributes,
 }
 
+/// The versioned hashes are part of the key since they aren't committed to by the block hash, so
+/// beacon blocks with the same payload may differ in them.
+#[derive(Hash, PartialEq, std::cmp::Eq)]
+struct NewPayloadCacheKey {
+    pub block_hash: ExecutionBlockHash,
+    pub versioned_hashes: Vec<VersionedHash>,
+}
+
 #[derive(Debug)]
 pub enum EngineError {
     Offline,
@@ -125,6 +138,10 @@ pub enum EngineError {
 pub struct Engine {
     pub api: HttpJsonRpc,
     payload_id_cache: Mutex<LruCache<PayloadIdCacheKey, PayloadId>>,
+    /// The conclusive verdicts of the engine on recently verified payloads.
+    new_payload_cache: Mutex<LruCache<NewPayloadCacheKey, PayloadStatusV1>>,
+    /// The highest block number of a payload which the engine has declared `VALID`.
+    latest_valid_block_number: AtomicU64,
     state: RwLock<State>,
     latest_forkchoice_state: RwLock<Option<ForkchoiceState>>,
     executor: TaskExecutor,
@@ -137,6 +154,8 @@ impl Engine {
         Self {
             api,
             payload_id_cache: Mutex::new(LruCache::new(PAYLOAD_ID_LRU_CACHE_SIZE)),
+            new_payload_cache: Mutex::new(LruCache::new(NEW_PAYLOAD_LRU_CACHE_SIZE)),
+            latest_valid_block_number: AtomicU64::new(0),
             state: Default::default(),
             latest_forkchoice_state: Default::default(),
             executor,
@@ -191,6 +210,53 @@ impl Engine {
         Ok(response)
     }
 
+    /// Returns the verdict of the engine on a payload which it has already verified, if any.
+    pub async fn get_new_payload_status<E: EthSpec>(
+        &self,
+        new_payload_request: &NewPayloadRequest<'_, E>,
+    ) -> Option<PayloadStatusV1> {
+        self.new_payload_cache
+            .lock()
+            .await
+            .get(&NewPayloadCacheKey::new(new_payload_request))
+            .cloned()
+    }
+
+    /// Sends the payload to the engine for verification, caching the verdict if it is conclusive.
+    ///
+    /// Only `VALID` and `INVALID` verdicts are cached. The engine may be able to verify the payload
+    /// later if it returns `SYNCING` or `ACCEPTED`, and `INVALID_BLOCK_HASH` only means that the
+    /// payload we sent doesn't match the hash, which says nothing of the block with that hash.
+    pub async fn notify_new_payload<E: EthSpec>(
+        &self,
+        new_payload_request: NewPayloadRequest<'_, E>,
+    ) -> Result<PayloadStatusV1, EngineApiError> {
+        let key = NewPayloadCacheKey::new(&new_payload_request);
+        let block_number = new_payload_request.block_number();
+        let status = self.api.new_payload(new_payload_request).await?;
+
+        if status.status == PayloadStatusV1Status::Valid {
+            self.latest_valid_block_number
+                .fetch_max(block_number, Ordering::Relaxed);
+        }
+
+        match status.status {
+            PayloadStatusV1Status::Valid | PayloadStatusV1Status::Invalid => {
+                self.new_payload_cache.lock().await.put(key, status.clone());
+            }
+            PayloadStatusV1Status::InvalidBlockHash
+            | PayloadStatusV1Status::Syncing
+            | PayloadStatusV1Status::Accepted => (),
+        }
+
+        Ok(status)
+    }
+
+    /// Returns the highest block number of a payload which the engine has declared `VALID`.
+    pub fn latest_valid_block_number(&self) -> u64 {
+        self.latest_valid_block_number.load(Ordering::Relaxed)
+    }
+
     async fn get_latest_forkchoice_state(&self) -> Option<ForkchoiceState> {
         *self.latest_forkchoice_state.read().await
     }
@@ -245,6 +311,11 @@ impl Engine {
         EngineState::from(**self.state.read().await) == EngineState::Offline
     }
 
+    /// Returns `true` if the engine rejected our JWT during the last upcheck.
+    pub async fn is_auth_failed(&self) -> bool {
+        **self.state.read().await == EngineStateInternal::AuthFailed
+    }
+
     /// Run the `EngineApi::upcheck` function if the node's last known state is not synced. This
     /// might be used to recover the node if offline.
     pub async fn upcheck(&self) {
@@ -323,6 +394,9 @@ impl Engine {
             ResponseCacheAction::Clear => {
                 self.api.clear_exchange_capabilties_cache().await;
                 self.api.clear_engine_version_cache().await;
+                // The engine may have been restarted with a different version or database, so it
+                // must verify payloads afresh.
+                self.new_payload_cache.lock().await.clear();
             }
         }
 
@@ -417,6 +491,18 @@ impl Engine {
     }
 }
 
+impl NewPayloadCacheKey {
+    fn new<E: EthSpec>(new_payload_request: &NewPayloadRequest<'_, E>) -> Self {
+        Self {
+            block_hash: new_payload_request.block_hash(),
+            versioned_hashes: new_payload_request
+                .versioned_hashes()
+                .cloned()
+                .unwrap_or_default(),
+        }
+    }
+}
+
 impl PayloadIdCacheKey {
     fn new(head_block_hash: &ExecutionBlockHash, attributes: &PayloadAttributes) -> Self {
         Self {
@@ -6,24 +6,28 @@
 
 use crate::json_structures::BlobAndProofV1;
 use crate::payload_cache::PayloadCache;
-use arc_swap::ArcSwapOption;
+use arc_swap::{ArcSwap, ArcSwapOption};
 use auth::{strip_prefix, Auth, JwtKey};
 pub use block_hash::calculate_execution_block_hash;
 use builder_client::BuilderHttpClient;
+use builder_groups::{BuilderGroupConfig, BuilderGroups};
 pub use engine_api::EngineCapabilities;
 use engine_api::Error as ApiError;
 pub use engine_api::*;
 pub use engine_api::{http, http::deposit_methods, http::HttpJsonRpc};
 use engines::{Engine, EngineError};
 pub use engines::{EngineState, ForkchoiceState};
+use eth2::lighthouse::RelayStats;
 use eth2::types::FullPayloadContents;
 use eth2::types::{builder_bid::SignedBuilderBid, BlobsBundle, ForkVersionedResponse};
 use ethers_core::types::Transaction as EthersTransaction;
 use fixed_bytes::UintExtended;
 use fork_choice::ForkchoiceUpdateParameters;
+use futures::future::BoxFuture;
 use lru::LruCache;
 use payload_status::process_payload_status;
 pub use payload_status::PayloadStatus;
+use relay_scores::{RelayFailure, RelayScores};
 use sensitive_url::SensitiveUrl;
 use serde::{Deserialize, Serialize};
 use slog::{crit, debug, error, info, warn, Logger};
@@ -33,7 +37,7 @@ use std::fmt;
 use std::future::Future;
 use std::io::Write;
 use std::num::NonZeroUsize;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use strum::AsRefStr;
@@ -59,12 +63,14 @@ use types::{
 };
 
 mod block_hash;
+pub mod builder_groups;
 mod engine_api;
 pub mod engines;
 mod keccak;
 mod metrics;
 pub mod payload_cache;
 mod payload_status;
+pub mod relay_scores;
 pub mod test_utils;
 pub mod versioned_hashes;
 
@@ -78,6 +84,23 @@ pub const DEFAULT_JWT_FILE: &str = "jwt.hex";
 /// in an LRU cache to avoid redundant lookups. This is the size of that cache.
 const EXECUTION_BLOCKS_LRU_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(128);
 
+/// The number of execution payload bodies kept in memory for serving to other beacon nodes.
+///
+/// Large enough to hold the responses to two requests of `MAX_REQUEST_BLOCKS_DENEB` blocks, so
+/// that peers syncing the same range from this node are served without repeated requests to the
+/// execution engine.
+const PAYLOAD_BODIES_LRU_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(256);
+
+/// The number of payload headers from custom builders for which the builder is remembered.
+const PAYLOAD_BUILDERS_LRU_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(16);
+
+/// The maximum time to wait for the fallback engines to respond to `engine_newPayload` and
+/// `engine_forkchoiceUpdated`.
+///
+/// The primary request waits for the fallbacks, so a slow fallback must not be allowed to delay
+/// it for long.
+const FALLBACK_REQUEST_TIMEOUT: Duration = Duration::from_secs(1);
+
 /// A fee recipient address for use during block production. Only used as a very last resort if
 /// there is no address provided by the user.
 ///
@@ -151,6 +174,7 @@ pub enum Error {
     },
     PayloadBodiesByRangeNotSupported,
     InvalidJWTSecret(String),
+    InvalidBuilderGroups(String),
     InvalidForkForPayload,
     InvalidPayloadBody(String),
     InvalidPayloadConversion,
@@ -158,6 +182,7 @@ pub enum Error {
     BeaconStateError(BeaconStateError),
     PayloadTypeMismatch,
     VerifyingVersionedHashes(versioned_hashes::Error),
+    BlockProductionDeadlinePassed,
 }
 
 impl From<BeaconStateError> for Error {
@@ -342,6 +367,13 @@ pub struct BuilderParams {
     pub pubkey: PublicKeyBytes,
     pub slot: Slot,
     pub chain_health: ChainHealth,
+    /// The block production deadline, after which whichever of the builder and local payloads is
+    /// still pending is no longer waited upon, provided the other has been received.
+    pub deadline: Option<Instant>,
+    /// The minimum builder bid (in gwei) requested by the proposer. Bids below this value, or the
+    /// minimum bid of the validator's builder group if that is higher, are ignored in favour of
+    /// the local payload.
+    pub min_bid_gwei: Option<u64>,
 }
 
 #[derive(PartialEq)]
@@ -363,11 +395,28 @@ type PayloadContentsRefTuple<'a, E> = (ExecutionPayloadRef<'a, E>, Option<&'a Bl
 
 struct Inner<E: EthSpec> {
     engine: Arc<Engine>,
+    /// Additional engines which are used to reconstruct payloads when the primary engine fails
+    /// to serve them.
+    fallback_engines: Vec<Arc<Engine>>,
+    /// Send `engine_newPayload` and `engine_forkchoiceUpdated` to the fallback engines as well as
+    /// the primary.
+    fallback_new_payload: bool,
     builder: ArcSwapOption<BuilderHttpClient>,
+    /// Custom builder configurations for groups of validators.
+    builder_groups: ArcSwap<BuilderGroups>,
+    /// The builders which provided recent payload headers, keyed by block hash, so that blinded
+    /// blocks are revealed by the builder which produced them.
+    payload_builders: Mutex<LruCache<ExecutionBlockHash, Arc<BuilderHttpClient>>>,
+    /// The performance of each builder, used to prefer reliable builders.
+    relay_scores: RelayScores,
     execution_engine_forkchoice_lock: Mutex<()>,
+    /// The file from which the JWT secret of the engines was read.
+    jwt_secret_file: PathBuf,
     suggested_fee_recipient: Option<Address>,
     proposer_preparation_data: Mutex<HashMap<u64, ProposerPreparationDataEntry>>,
     execution_blocks: Mutex<LruCache<ExecutionBlockHash, ExecutionBlock>>,
+    /// Payload bodies recently fetched from the engine to be served to other beacon nodes.
+    payload_bodies: Mutex<LruCache<ExecutionBlockHash, ExecutionPayloadBodyV1<E>>>,
     proposers: RwLock<HashMap<ProposerKey, Proposer>>,
     executor: TaskExecutor,
     payload_cache: PayloadCache<E>,
@@ -383,6 +432,12 @@ struct Inner<E: EthSpec> {
 pub struct Config {
     /// Endpoint url for EL nodes that are running the engine api.
     pub execution_endpoint: Option<SensitiveUrl>,
+    /// Endpoint urls for fallback EL nodes, used to reconstruct payloads when the primary
+    /// execution endpoint fails to provide them. These share the JWT secret of the primary.
+    pub fallback_execution_endpoints: Vec<SensitiveUrl>,
+    /// Send `engine_newPayload` and `engine_forkchoiceUpdated` to the fallback EL nodes as well,
+    /// so that they remain in sync with the primary. The verdict of the primary is authoritative.
+    pub fallback_new_payload: bool,
     /// Endpoint urls for services providing the builder api.
     pub builder_url: Option<SensitiveUrl>,
     /// The timeout value used when making a request to fetch a block header
@@ -390,8 +445,12 @@ pub struct Config {
     pub builder_header_timeout: Option<Duration>,
     /// User agent to send with requests to the builder API.
     pub builder_user_agent: Option<String>,
+    /// Builder configurations for groups of validators which override the builder above.
+    pub builder_groups: Vec<BuilderGroupConfig>,
     /// JWT secret for the above endpoint running the engine api.
     pub secret_file: Option<PathBuf>,
+    /// Re-read the JWT secret file when the process receives SIGHUP, rather than shutting down.
+    pub reload_jwt_secret_on_sighup: bool,
     /// The default fee recipient to use on the beacon node if none if provided from
     /// the validator client during block preparation.
     pub suggested_fee_recipient: Option<Address>,
@@ -416,10 +475,14 @@ impl<E: EthSpec> ExecutionLayer<E> {
     pub fn from_config(config: Config, executor: TaskExecutor, log: Logger) -> Result<Self, Error> {
         let Config {
             execution_endpoint: url,
+            fallback_execution_endpoints,
+            fallback_new_payload,
             builder_url,
             builder_user_agent,
             builder_header_timeout,
+            builder_groups,
             secret_file,
+            reload_jwt_secret_on_sighup: _,
             suggested_fee_recipient,
             jwt_id,
             jwt_version,
@@ -434,16 +497,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
 
         let jwt_key = if secret_file.exists() {
             // Read secret from file if it already exists
-            std::fs::read_to_string(&secret_file)
-                .map_err(|e| format!("Failed to read JWT secret file. Error: {:?}", e))
-                .and_then(|ref s| {
-                    let secret = JwtKey::from_slice(
-                        &hex::decode(strip_prefix(s.trim_end()))
-                            .map_err(|e| format!("Invalid hex string: {:?}", e))?,
-                    )?;
-                    Ok(secret)
-                })
-                .map_err(Error::InvalidJWTSecret)
+            read_jwt_secret(&secret_file).map_err(Error::InvalidJWTSecret)
         } else {
             // Create a new file and write a randomly generated secret to it if file does not exist
             warn!(log, "No JWT found on disk. Generating"; "path" => %secret_file.display());
@@ -462,21 +516,55 @@ impl<E: EthSpec> ExecutionLayer<E> {
         }?;
 
         let engine: Engine = {
-            let auth = Auth::new(jwt_key, jwt_id, jwt_version);
+            let auth = Auth::new(jwt_key.clone(), jwt_id.clone(), jwt_version.clone());
             debug!(log, "Loaded execution endpoint"; "endpoint" => %execution_url, "jwt_path" => ?secret_file.as_path());
             let api = HttpJsonRpc::new_with_auth(execution_url, auth, execution_timeout_multiplier)
                 .map_err(Error::ApiError)?;
             Engine::new(api, executor.clone(), &log)
         };
 
+        let fallback_engines = fallback_execution_endpoints
+            .into_iter()
+            .map(|fallback_url| {
+                let auth = Auth::new(jwt_key.clone(), jwt_id.clone(), jwt_version.clone());
+                debug!(log, "Loaded fallback execution endpoint"; "endpoint" => %fallback_url);
+                let api =
+                    HttpJsonRpc::new_with_auth(fallback_url, auth, execution_timeout_multiplier)
+                        .map_err(Error::ApiError)?;
+                Ok(Arc::new(Engine::new(api, executor.clone(), &log)))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let relay_scores = RelayScores::default();
+        for builder_url in builder_groups
+            .iter()
+            .filter_map(|group| group.builder_url.as_ref())
+        {
+            relay_scores.register(builder_url);
+        }
+
+        let builder_groups = BuilderGroups::new(
+            builder_groups,
+            builder_user_agent.clone(),
+            builder_header_timeout,
+        )
+        .map_err(Error::InvalidBuilderGroups)?;
+
         let inner = Inner {
             engine: Arc::new(engine),
+            fallback_engines,
+            fallback_new_payload,
             builder: ArcSwapOption::empty(),
+            builder_groups: ArcSwap::from_pointee(builder_groups),
+            payload_builders: Mutex::new(LruCache::new(PAYLOAD_BUILDERS_LRU_CACHE_SIZE)),
+            relay_scores,
             execution_engine_forkchoice_lock: <_>::default(),
+            jwt_secret_file: secret_file,
             suggested_fee_recipient,
             proposer_preparation_data: Mutex::new(HashMap::new()),
             proposers: RwLock::new(HashMap::new()),
             execution_blocks: Mutex::new(LruCache::new(EXECUTION_BLOCKS_LRU_CACHE_SIZE)),
+            payload_bodies: Mutex::new(LruCache::new(PAYLOAD_BODIES_LRU_CACHE_SIZE)),
             executor,
             payload_cache: PayloadCache::default(),
             log,
@@ -498,10 +586,26 @@ impl<E: EthSpec> ExecutionLayer<E> {
         &self.inner.engine
     }
 
+    /// Returns `true` if one or more fallback engines are available for payload reconstruction.
+    pub fn has_fallback_engines(&self) -> bool {
+        !self.inner.fallback_engines.is_empty()
+    }
+
     pub fn builder(&self) -> Option<Arc<BuilderHttpClient>> {
         self.inner.builder.load_full()
     }
 
+    /// Returns the builder with which the validator with `pubkey` is registered, i.e. the builder
+    /// of its builder group if it has one, otherwise the default builder.
+    pub fn builder_for(&self, pubkey: &PublicKeyBytes) -> Option<Arc<BuilderHttpClient>> {
+        self.inner
+            .builder_groups
+            .load()
+            .group_for(pubkey)
+            .and_then(|group| group.builder.clone())
+            .or_else(|| self.builder())
+    }
+
     /// Set the builder URL after initialization.
     ///
     /// This is useful for breaking circular dependencies between mock ELs and mock builders in
@@ -524,10 +628,70 @@ impl<E: EthSpec> ExecutionLayer<E> {
             "builder_url" => ?builder_url,
             "local_user_agent" => builder_client.get_user_agent(),
         );
+        self.inner.relay_scores.register(&builder_url);
         self.inner.builder.swap(Some(Arc::new(builder_client)));
         Ok(())
     }
 
+    /// Replace the builder groups after initialization.
+    ///
+    /// This is useful for configuring groups with mock builders whose addresses are only known
+    /// after the execution layer has been built, in tests.
+    pub fn set_builder_groups(
+        &self,
+        builder_groups: Vec<BuilderGroupConfig>,
+        builder_user_agent: Option<String>,
+        builder_header_timeout: Option<Duration>,
+    ) -> Result<(), Error> {
+        for builder_url in builder_groups
+            .iter()
+            .filter_map(|group| group.builder_url.as_ref())
+        {
+            self.inner.relay_scores.register(builder_url);
+        }
+        let builder_groups =
+            BuilderGroups::new(builder_groups, builder_user_agent, builder_header_timeout)
+                .map_err(Error::InvalidBuilderGroups)?;
+        self.inner.builder_groups.store(Arc::new(builder_groups));
+        Ok(())
+    }
+
+    /// Returns the request statistics of each builder relay.
+    pub fn relay_stats(&self) -> Vec<RelayStats> {
+        self.inner.relay_scores.stats()
+    }
+
+    /// Returns the builder to request a header from for `slot`, if any.
+    ///
+    /// A validator's group builder is used in preference to the default builder. If that builder
+    /// has been deprioritized due to recent failures, no builder is returned so that the payload
+    /// is built locally. The default builder isn't used in its place, since the validator's group
+    /// may have been configured to avoid it.
+    fn select_builder(
+        &self,
+        group_builder: Option<Arc<BuilderHttpClient>>,
+        slot: Slot,
+    ) -> Option<Arc<BuilderHttpClient>> {
+        let builder = group_builder.or_else(|| self.builder())?;
+
+        if self
+            .inner
+            .relay_scores
+            .is_deprioritized(builder.url(), slot)
+        {
+            info!(
+                self.log(),
+                "Skipping deprioritized builder";
+                "info" => "the builder has recently failed, building the payload locally",
+                "builder" => %builder.url(),
+                "slot" => slot,
+            );
+            return None;
+        }
+
+        Some(builder)
+    }
+
     /// Cache a full payload, keyed on the `tree_hash_root` of the payload
     fn cache_payload(
         &self,
@@ -610,6 +774,75 @@ impl<E: EthSpec> ExecutionLayer<E> {
         self.executor().spawn(generate_future(self.clone()), name);
     }
 
+    /// Re-read the JWT secret file and sign all subsequent engine API requests with the new
+    /// secret.
+    ///
+    /// The primary engine is checked immediately, so that it is brought back online without
+    /// waiting for the watchdog if it rejected the previous secret.
+    pub async fn reload_jwt_secret(&self) -> Result<(), Error> {
+        let jwt_secret_file = &self.inner.jwt_secret_file;
+        let jwt_key = read_jwt_secret(jwt_secret_file).map_err(Error::InvalidJWTSecret)?;
+
+        for engine in std::iter::once(self.engine()).chain(&self.inner.fallback_engines) {
+            engine
+                .api
+                .set_jwt_secret(jwt_key.clone())
+                .map_err(Error::ApiError)?;
+        }
+
+        info!(
+            self.log(),
+            "Reloaded JWT secret";
+            "path" => %jwt_secret_file.display(),
+            "fallback_engines" => self.inner.fallback_engines.len(),
+        );
+
+        self.engine().upcheck().await;
+        Ok(())
+    }
+
+    /// Spawns a routine which reloads the JWT secret whenever the process receives SIGHUP.
+    pub fn spawn_jwt_secret_reload_routine(&self) {
+        #[cfg(target_family = "unix")]
+        self.spawn(
+            |el| async move {
+                use tokio::signal::unix::{signal, SignalKind};
+
+                let mut hangup = match signal(SignalKind::hangup()) {
+                    Ok(hangup) => hangup,
+                    Err(e) => {
+                        error!(
+                            el.log(),
+                            "Unable to listen for SIGHUP";
+                            "info" => "the JWT secret will not be reloaded",
+                            "error" => %e,
+                        );
+                        return;
+                    }
+                };
+
+                while hangup.recv().await.is_some() {
+                    if let Err(e) = el.reload_jwt_secret().await {
+                        error!(
+                            el.log(),
+                            "Failed to reload JWT secret";
+                            "info" => "the previous secret remains in use",
+                            "error" => ?e,
+                        );
+                    }
+                }
+            },
+            "el_jwt_secret_reload",
+        );
+
+        #[cfg(not(target_family = "unix"))]
+        warn!(
+            self.log(),
+            "JWT secret reloading on SIGHUP is not supported on this platform";
+            "info" => "use the HTTP API to reload the JWT secret",
+        );
+    }
+
     /// Spawns a routine which attempts to keep the execution engine online.
     pub fn spawn_watchdog_routine<S: SlotClock + 'static>(&self, slot_clock: S) {
         let watchdog = |el: ExecutionLayer<E>| async move {
@@ -710,6 +943,11 @@ impl<E: EthSpec> ExecutionLayer<E> {
         self.engine().is_offline().await || *self.inner.last_new_payload_errored.read().await
     }
 
+    /// Returns `true` if the execution engine rejected the JWT presented by this node.
+    pub async fn is_auth_failed(&self) -> bool {
+        self.engine().is_auth_failed().await
+    }
+
     /// Updates the proposer preparation data provided by validators
     pub async fn update_proposer_preparation(
         &self,
@@ -783,6 +1021,14 @@ impl<E: EthSpec> ExecutionLayer<E> {
             .contains_key(&proposer_index)
     }
 
+    /// Returns the fee-recipient address registered for `proposer_index` via the API, if any.
+    pub async fn get_registered_fee_recipient(&self, proposer_index: u64) -> Option<Address> {
+        self.proposer_preparation_data()
+            .await
+            .get(&proposer_index)
+            .map(|entry| entry.preparation_data.fee_recipient)
+    }
+
     /// Returns the fee-recipient address that should be used to build a block
     pub async fn get_suggested_fee_recipient(&self, proposer_index: u64) -> Address {
         if let Some(preparation_data_entry) =
@@ -927,6 +1173,8 @@ impl<E: EthSpec> ExecutionLayer<E> {
         payload_attributes: &PayloadAttributes,
         forkchoice_update_params: ForkchoiceUpdateParameters,
         current_fork: ForkName,
+        min_bid: Option<Uint256>,
+        spec: &ChainSpec,
     ) -> (
         Result<Option<ForkVersionedResponse<SignedBuilderBid<E>>>, builder_client::Error>,
         Result<GetPayloadResponse<E>, Error>,
@@ -942,13 +1190,15 @@ impl<E: EthSpec> ExecutionLayer<E> {
             "parent_hash" => ?parent_hash,
         );
 
-        // Wait for the builder *and* local EL to produce a payload (or return an error).
-        let ((relay_result, relay_duration), (local_result, local_duration)) = tokio::join!(
-            timed_future(metrics::GET_BLINDED_PAYLOAD_BUILDER, async {
-                builder
-                    .get_builder_header::<E>(slot, parent_hash, pubkey)
-                    .await
-            }),
+        // Wait for the builder *and* local EL to produce a payload (or return an error), unless
+        // the block production deadline passes whilst one of them is still pending. The local
+        // payload is only abandoned for a bid which we would be willing to propose without it.
+        let start = Instant::now();
+        let (relay_result, local_result) = join_until_deadline(
+            timed_future(
+                metrics::GET_BLINDED_PAYLOAD_BUILDER,
+                builder.get_builder_header::<E>(slot, parent_hash, pubkey),
+            ),
             timed_future(metrics::GET_BLINDED_PAYLOAD_LOCAL, async {
                 self.get_full_payload_caching(
                     parent_hash,
@@ -961,8 +1211,46 @@ impl<E: EthSpec> ExecutionLayer<E> {
                     GetPayloadResponseType::Full(payload) => Ok(payload),
                     GetPayloadResponseType::Blinded(_) => Err(Error::PayloadTypeMismatch),
                 })
-            })
-        );
+            }),
+            builder_params.deadline,
+            |(relay_result, _)| {
+                is_usable_bid(
+                    relay_result,
+                    parent_hash,
+                    payload_attributes,
+                    current_fork,
+                    min_bid,
+                    spec,
+                )
+            },
+            |(local_result, _)| local_result.is_ok(),
+        )
+        .await;
+
+        // `None` indicates the request was abandoned at the block production deadline.
+        let deadline_passed = relay_result.is_none();
+        for (skipped, source) in [
+            (relay_result.is_none(), metrics::BUILDER),
+            (local_result.is_none(), metrics::LOCAL),
+        ] {
+            if skipped {
+                info!(
+                    self.log(),
+                    "Block production deadline passed";
+                    "info" => "no longer waiting for the payload",
+                    "skipped" => format!("{source}_payload"),
+                    "slot" => slot,
+                );
+                metrics::inc_counter_vec(
+                    &metrics::EXECUTION_LAYER_BLOCK_PRODUCTION_DEADLINE_SKIPS,
+                    &[source],
+                );
+            }
+        }
+        let (relay_result, relay_duration) =
+            relay_result.unwrap_or_else(|| (Ok(None), start.elapsed()));
+        let (local_result, local_duration) = local_result
+            .unwrap_or_else(|| (Err(Error::BlockProductionDeadlinePassed), start.elapsed()));
 
         info!(
             self.log(),
@@ -981,9 +1269,37 @@ impl<E: EthSpec> ExecutionLayer<E> {
             "parent_hash" => ?parent_hash,
         );
 
+        let relay_scores = &self.inner.relay_scores;
+        relay_scores.record_request(builder.url(), relay_duration);
+        match &relay_result {
+            _ if deadline_passed => relay_scores.record_deadline_timeout(builder.url()),
+            // The validity of a bid is recorded once it has been verified.
+            Ok(Some(_)) => (),
+            Ok(None) => relay_scores.record_no_bid(builder.url()),
+            Err(_) => self.record_relay_failure(builder, slot, RelayFailure::Error),
+        }
+
         (relay_result, local_result)
     }
 
+    /// Record that `builder` failed to provide a usable header for `slot`.
+    fn record_relay_failure(&self, builder: &BuilderHttpClient, slot: Slot, failure: RelayFailure) {
+        if let Some(deprioritized_until) =
+            self.inner
+                .relay_scores
+                .record_failure(builder.url(), slot, failure)
+        {
+            warn!(
+                self.log(),
+                "Deprioritizing builder";
+                "info" => "the builder has repeatedly failed to provide a valid header",
+                "builder" => %builder.url(),
+                "failure" => ?failure,
+                "until_slot" => deprioritized_until,
+            );
+        }
+    }
+
     #[allow(clippy::too_many_arguments)]
     async fn determine_and_fetch_payload(
         &self,
@@ -995,7 +1311,14 @@ impl<E: EthSpec> ExecutionLayer<E> {
         builder_boost_factor: Option<u64>,
         spec: &ChainSpec,
     ) -> Result<ProvenancedPayload<BlockProposalContentsType<E>>, Error> {
-        let Some(builder) = self.builder() else {
+        let groups = self.inner.builder_groups.load_full();
+        let builder_group = groups.group_for(&builder_params.pubkey);
+        let group_builder = builder_group.and_then(|group| group.builder.clone());
+        let min_bid = builder_group
+            .and_then(|group| group.min_bid)
+            .max(builder_params.min_bid_gwei.map(builder_groups::gwei_to_wei));
+
+        let Some(builder) = self.select_builder(group_builder, builder_params.slot) else {
             // no builder.. return local payload
             return self
                 .get_full_payload_caching(
@@ -1053,9 +1376,19 @@ impl<E: EthSpec> ExecutionLayer<E> {
                 payload_attributes,
                 forkchoice_update_params,
                 current_fork,
+                min_bid,
+                spec,
             )
             .await;
 
+        if let Ok(Some(relay)) = &relay_result {
+            self.inner
+                .payload_builders
+                .lock()
+                .await
+                .put(relay.data.message.header().block_hash(), builder.clone());
+        }
+
         match (relay_result, local_result) {
             (Err(e), Ok(local)) => {
                 warn!(
@@ -1131,6 +1464,11 @@ impl<E: EthSpec> ExecutionLayer<E> {
                         &metrics::EXECUTION_LAYER_GET_PAYLOAD_BUILDER_REJECTIONS,
                         &[reason.as_ref().as_ref()],
                     );
+                    self.record_relay_failure(
+                        &builder,
+                        builder_params.slot,
+                        RelayFailure::InvalidBid,
+                    );
                     warn!(
                         self.log(),
                         "Builder returned invalid payload";
@@ -1143,9 +1481,24 @@ impl<E: EthSpec> ExecutionLayer<E> {
                         local.try_into()?,
                     )));
                 }
+                self.inner.relay_scores.record_valid_bid(builder.url());
 
                 let relay_value = *relay.data.message.value();
 
+                if let Some(min_bid) = min_bid.filter(|min_bid| relay_value < *min_bid) {
+                    info!(
+                        self.log(),
+                        "Relay block value is below the minimum bid";
+                        "info" => "using local payload",
+                        "relay_value" => %relay_value,
+                        "min_bid" => %min_bid,
+                        "builder_group" => ?builder_group.map(|group| group.name.as_str()),
+                    );
+                    return Ok(ProvenancedPayload::Local(BlockProposalContentsType::Full(
+                        local.try_into()?,
+                    )));
+                }
+
                 let boosted_relay_value = match builder_boost_factor {
                     Some(builder_boost_factor) => (relay_value / Uint256::from(100))
                         .saturating_mul(Uint256::from(builder_boost_factor)),
@@ -1210,12 +1563,33 @@ impl<E: EthSpec> ExecutionLayer<E> {
                     current_fork,
                     spec,
                 ) {
-                    Ok(()) => Ok(ProvenancedPayload::try_from(relay.data.message)?),
+                    Ok(()) => {
+                        self.inner.relay_scores.record_valid_bid(builder.url());
+                        let relay_value = *relay.data.message.value();
+                        if let Some(min_bid) = min_bid.filter(|min_bid| relay_value < *min_bid) {
+                            crit!(
+                                self.log(),
+                                "Relay block value is below the minimum bid";
+                                "info" => "no local payload either - unable to propose block",
+                                "relay_value" => %relay_value,
+                                "min_bid" => %min_bid,
+                                "local_error" => ?local_error,
+                                "builder_group" => ?builder_group.map(|group| group.name.as_str()),
+                            );
+                            return Err(Error::CannotProduceHeader);
+                        }
+                        Ok(ProvenancedPayload::try_from(relay.data.message)?)
+                    }
                     Err(reason) => {
                         metrics::inc_counter_vec(
                             &metrics::EXECUTION_LAYER_GET_PAYLOAD_BUILDER_REJECTIONS,
                             &[reason.as_ref().as_ref()],
                         );
+                        self.record_relay_failure(
+                            &builder,
+                            builder_params.slot,
+                            RelayFailure::InvalidBid,
+                        );
                         crit!(
                             self.log(),
                             "Builder returned invalid payload";
@@ -1361,20 +1735,53 @@ impl<E: EthSpec> ExecutionLayer<E> {
         &self,
         new_payload_request: NewPayloadRequest<'_, E>,
     ) -> Result<PayloadStatus, Error> {
-        let _timer = metrics::start_timer_vec(
-            &metrics::EXECUTION_LAYER_REQUEST_TIMES,
-            &[metrics::NEW_PAYLOAD],
-        );
-        let timer = std::time::Instant::now();
-
         let block_number = new_payload_request.block_number();
         let block_hash = new_payload_request.block_hash();
         let parent_hash = new_payload_request.parent_hash();
 
-        let result = self
+        // Avoid verifying the same payload twice, e.g. when it is included in sibling blocks or
+        // when the import of a block is retried.
+        if let Some(status) = self
             .engine()
-            .request(|engine| engine.api.new_payload(new_payload_request))
-            .await;
+            .get_new_payload_status(&new_payload_request)
+            .await
+        {
+            metrics::inc_counter(&metrics::EXECUTION_LAYER_NEW_PAYLOAD_CACHE_HITS);
+            debug!(
+                self.log(),
+                "Using cached engine_newPayload status";
+                "status" => <&'static str>::from(status.status),
+                "block_hash" => ?block_hash,
+                "block_number" => block_number,
+            );
+            return process_payload_status(block_hash, Ok(status), self.log())
+                .map_err(Box::new)
+                .map_err(Error::EngineError);
+        }
+
+        let primary = async {
+            let _timer = metrics::start_timer_vec(
+                &metrics::EXECUTION_LAYER_REQUEST_TIMES,
+                &[metrics::NEW_PAYLOAD],
+            );
+            let timer = std::time::Instant::now();
+
+            let result = self
+                .engine()
+                .request(|engine| engine.notify_new_payload(new_payload_request.clone()))
+                .await;
+            (result, timer.elapsed())
+        };
+        let fallback_request = Arc::new(OwnedNewPayloadRequest::from(&new_payload_request));
+        self.spawn_fallback_requests(metrics::NEW_PAYLOAD, block_hash, move |engine| {
+            let fallback_request = fallback_request.clone();
+            Box::pin(async move {
+                engine
+                    .notify_new_payload(fallback_request.as_request())
+                    .await
+            })
+        });
+        let (result, response_time) = primary.await;
 
         if let Ok(status) = &result {
             let status_str = <&'static str>::from(status.status);
@@ -1389,7 +1796,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
                 "parent_hash" => ?parent_hash,
                 "block_hash" => ?block_hash,
                 "block_number" => block_number,
-                "response_time_ms" => timer.elapsed().as_millis()
+                "response_time_ms" => response_time.as_millis()
             );
         }
         *self.inner.last_new_payload_errored.write().await = result.is_err();
@@ -1399,6 +1806,111 @@ impl<E: EthSpec> ExecutionLayer<E> {
             .map_err(Error::EngineError)
     }
 
+    /// Send a request to all of the fallback engines concurrently, if enabled, so that they remain
+    /// in sync with the primary and are able to take over from it without delay.
+    ///
+    /// The requests are sent from a background task so that they never delay the response of the
+    /// primary. The verdicts of the fallbacks are only logged, since the verdict of the primary is
+    /// authoritative.
+    fn spawn_fallback_requests<F>(
+        &self,
+        method: &'static str,
+        block_hash: ExecutionBlockHash,
+        func: F,
+    ) where
+        F: for<'a> Fn(&'a Engine) -> BoxFuture<'a, Result<PayloadStatusV1, ApiError>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        if !self.inner.fallback_new_payload || self.inner.fallback_engines.is_empty() {
+            return;
+        }
+
+        let el = self.clone();
+        self.inner.executor.spawn(
+            async move { el.request_fallbacks(method, block_hash, func).await },
+            "fallback_engine_requests",
+        );
+    }
+
+    async fn request_fallbacks<F>(
+        &self,
+        method: &'static str,
+        block_hash: ExecutionBlockHash,
+        func: F,
+    ) where
+        F: for<'a> Fn(&'a Engine) -> BoxFuture<'a, Result<PayloadStatusV1, ApiError>>,
+    {
+        let func = &func;
+        let requests = self.inner.fallback_engines.iter().map(|engine| async move {
+            let result = tokio::time::timeout(FALLBACK_REQUEST_TIMEOUT, engine.request(func)).await;
+
+            match result {
+                Ok(Ok(status)) => {
+                    let status_str = <&'static str>::from(status.status);
+                    metrics::inc_counter_vec(
+                        &metrics::EXECUTION_LAYER_FALLBACK_PAYLOAD_STATUS,
+                        &[method, status_str],
+                    );
+                    debug!(
+                        self.log(),
+                        "Processed fallback engine request";
+                        "method" => method,
+                        "endpoint" => %engine.api.url,
+                        "status" => status_str,
+                        "block_hash" => ?block_hash,
+                    );
+                }
+                Ok(Err(e)) => {
+                    metrics::inc_counter_vec(
+                        &metrics::EXECUTION_LAYER_FALLBACK_PAYLOAD_STATUS,
+                        &[method, metrics::FAILURE],
+                    );
+                    debug!(
+                        self.log(),
+                        "Fallback engine request failed";
+                        "method" => method,
+                        "endpoint" => %engine.api.url,
+                        "block_hash" => ?block_hash,
+                        "error" => ?e,
+                    );
+                }
+                Err(_) => {
+                    metrics::inc_counter_vec(
+                        &metrics::EXECUTION_LAYER_FALLBACK_PAYLOAD_STATUS,
+                        &[method, metrics::TIMEOUT],
+                    );
+                    debug!(
+                        self.log(),
+                        "Fallback engine request timed out";
+                        "method" => method,
+                        "endpoint" => %engine.api.url,
+                        "block_hash" => ?block_hash,
+                    );
+                }
+            }
+        });
+        futures::future::join_all(requests).await;
+
+        self.update_fallback_lag_metrics();
+    }
+
+    /// Record how many blocks each fallback engine is behind the primary, based on the latest
+    /// payload each engine has declared `VALID`.
+    fn update_fallback_lag_metrics(&self) {
+        let primary_block_number = self.engine().latest_valid_block_number();
+
+        for engine in &self.inner.fallback_engines {
+            let lag = primary_block_number.saturating_sub(engine.latest_valid_block_number());
+            metrics::set_gauge_vec(
+                &metrics::EXECUTION_LAYER_FALLBACK_LAG,
+                &[&engine.api.url.to_string()],
+                lag as i64,
+            );
+        }
+    }
+
     /// Update engine sync status.
     pub async fn upcheck(&self) {
         self.engine().upcheck().await;
@@ -1518,7 +2030,27 @@ impl<E: EthSpec> ExecutionLayer<E> {
         self.engine()
             .set_latest_forkchoice_state(forkchoice_state)
             .await;
+        if self.inner.fallback_new_payload {
+            for engine in &self.inner.fallback_engines {
+                engine.set_latest_forkchoice_state(forkchoice_state).await;
+            }
+        }
 
+        // The fallbacks don't build payloads, so they are only sent the forkchoice state.
+        let log = self.log().clone();
+        self.spawn_fallback_requests(
+            metrics::FORKCHOICE_UPDATED,
+            head_block_hash,
+            move |engine| {
+                let log = log.clone();
+                Box::pin(async move {
+                    engine
+                        .notify_forkchoice_updated(forkchoice_state, None, &log)
+                        .await
+                        .map(|response| response.payload_status)
+                })
+            },
+        );
         let result = self
             .engine()
             .request(|engine| async move {
@@ -1795,6 +2327,52 @@ impl<E: EthSpec> ExecutionLayer<E> {
             .map_err(Error::EngineError)
     }
 
+    /// Returns the payload bodies for `hashes` in the same order, as per
+    /// `get_payload_bodies_by_hash`.
+    ///
+    /// Bodies are cached, so that only those which have not recently been requested are fetched
+    /// from the execution engine. The bodies of requests larger than the cache are not cached, as
+    /// they would evict each other before a peer requesting the same range could be served.
+    pub async fn get_payload_bodies_by_hash_cached(
+        &self,
+        hashes: Vec<ExecutionBlockHash>,
+    ) -> Result<Vec<Option<ExecutionPayloadBodyV1<E>>>, Error> {
+        let mut bodies = {
+            let mut cache = self.inner.payload_bodies.lock().await;
+            hashes
+                .iter()
+                .map(|hash| cache.get(hash).cloned())
+                .collect::<Vec<_>>()
+        };
+
+        let missing = hashes
+            .iter()
+            .zip(bodies.iter())
+            .filter(|(_, body)| body.is_none())
+            .map(|(hash, _)| *hash)
+            .collect::<Vec<_>>();
+        if missing.is_empty() {
+            return Ok(bodies);
+        }
+
+        let fetched = self.get_payload_bodies_by_hash(missing.clone()).await?;
+        let mut cache = self.inner.payload_bodies.lock().await;
+        let cache_bodies = hashes.len() <= cache.cap().get();
+        let mut fetched = missing.into_iter().zip(fetched).collect::<HashMap<_, _>>();
+        for (hash, body) in hashes.iter().zip(bodies.iter_mut()) {
+            if body.is_some() {
+                continue;
+            }
+            if let Some(fetched_body) = fetched.remove(hash).flatten() {
+                if cache_bodies {
+                    cache.put(*hash, fetched_body.clone());
+                }
+                *body = Some(fetched_body);
+            }
+        }
+        Ok(bodies)
+    }
+
     pub async fn get_payload_bodies_by_range(
         &self,
         start: u64,
@@ -1825,16 +2403,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
 
         // Handle default payload body.
         if header.block_hash() == ExecutionBlockHash::zero() {
-            let payload = match fork {
-                ForkName::Bellatrix => ExecutionPayloadBellatrix::default().into(),
-                ForkName::Capella => ExecutionPayloadCapella::default().into(),
-                ForkName::Deneb => ExecutionPayloadDeneb::default().into(),
-                ForkName::Electra => ExecutionPayloadElectra::default().into(),
-                ForkName::Base | ForkName::Altair => {
-                    return Err(Error::InvalidForkForPayload);
-                }
-            };
-            return Ok(Some(payload));
+            return Self::default_payload_for_fork(fork).map(Some);
         }
 
         // Use efficient payload bodies by range method if supported.
@@ -1858,6 +2427,98 @@ impl<E: EthSpec> ExecutionLayer<E> {
         }
     }
 
+    /// Fetch a full payload from each of the fallback execution nodes in turn, returning the
+    /// first successful response.
+    ///
+    /// Like `get_payload_for_header`, this will fail if the payload is not from the finalized
+    /// portion of the chain.
+    pub async fn get_payload_for_header_from_fallbacks(
+        &self,
+        header: &ExecutionPayloadHeader<E>,
+        fork: ForkName,
+    ) -> Result<Option<ExecutionPayload<E>>, Error> {
+        let mut last_error = Error::NoEngine;
+
+        for engine in &self.inner.fallback_engines {
+            match Self::get_payload_for_header_from_engine(engine, header, fork).await {
+                Ok(payload) => {
+                    metrics::inc_counter_vec(
+                        &metrics::EXECUTION_LAYER_PAYLOAD_RECONSTRUCTION_FAILOVERS,
+                        &[metrics::SUCCESS],
+                    );
+                    return Ok(payload);
+                }
+                Err(e) => {
+                    debug!(
+                        self.log(),
+                        "Fallback engine failed to reconstruct payload";
+                        "block_hash" => ?header.block_hash(),
+                        "error" => ?e,
+                    );
+                    last_error = e;
+                }
+            }
+        }
+
+        metrics::inc_counter_vec(
+            &metrics::EXECUTION_LAYER_PAYLOAD_RECONSTRUCTION_FAILOVERS,
+            &[metrics::FAILURE],
+        );
+        Err(last_error)
+    }
+
+    async fn get_payload_for_header_from_engine(
+        engine: &Arc<Engine>,
+        header: &ExecutionPayloadHeader<E>,
+        fork: ForkName,
+    ) -> Result<Option<ExecutionPayload<E>>, Error> {
+        if header.block_hash() == ExecutionBlockHash::zero() {
+            return Self::default_payload_for_fork(fork).map(Some);
+        }
+
+        let block_number = header.block_number();
+        let capabilities = engine
+            .request(|engine| engine.get_engine_capabilities(None))
+            .await?;
+        if !capabilities.get_payload_bodies_by_range_v1 {
+            return Err(Error::PayloadBodiesByRangeNotSupported);
+        }
+
+        let mut payload_bodies = engine
+            .request(|engine: &Engine| async move {
+                engine
+                    .api
+                    .get_payload_bodies_by_range_v1(block_number, 1)
+                    .await
+            })
+            .await
+            .map_err(Box::new)
+            .map_err(Error::EngineError)?;
+
+        if payload_bodies.len() != 1 {
+            return Ok(None);
+        }
+
+        payload_bodies
+            .pop()
+            .flatten()
+            .map(|body| {
+                body.to_payload(header.clone())
+                    .map_err(Error::InvalidPayloadBody)
+            })
+            .transpose()
+    }
+
+    fn default_payload_for_fork(fork: ForkName) -> Result<ExecutionPayload<E>, Error> {
+        match fork {
+            ForkName::Bellatrix => Ok(ExecutionPayloadBellatrix::default().into()),
+            ForkName::Capella => Ok(ExecutionPayloadCapella::default().into()),
+            ForkName::Deneb => Ok(ExecutionPayloadDeneb::default().into()),
+            ForkName::Electra => Ok(ExecutionPayloadElectra::default().into()),
+            ForkName::Base | ForkName::Altair => Err(Error::InvalidForkForPayload),
+        }
+    }
+
     pub async fn get_blobs(
         &self,
         query: Vec<Hash256>,
@@ -1897,7 +2558,19 @@ impl<E: EthSpec> ExecutionLayer<E> {
             "root" => ?block_root,
         );
 
-        if let Some(builder) = self.builder() {
+        // Reveal the payload with the builder which produced its header.
+        let payload_builder = match block.message().execution_payload() {
+            Ok(payload) => self
+                .inner
+                .payload_builders
+                .lock()
+                .await
+                .get(&payload.block_hash())
+                .cloned(),
+            Err(_) => None,
+        };
+
+        if let Some(builder) = payload_builder.or_else(|| self.builder()) {
             let (payload_result, duration) =
                 timed_future(metrics::POST_BLINDED_PAYLOAD_BUILDER, async {
                     builder
@@ -2091,6 +2764,95 @@ fn verify_builder_bid<E: EthSpec>(
     }
 }
 
+/// Read a hex encoded JWT secret from `path`.
+fn read_jwt_secret(path: &Path) -> Result<JwtKey, String> {
+    std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read JWT secret file. Error: {:?}", e))
+        .and_then(|ref s| {
+            let secret = JwtKey::from_slice(
+                &hex::decode(strip_prefix(s.trim_end()))
+                    .map_err(|e| format!("Invalid hex string: {:?}", e))?,
+            )?;
+            Ok(secret)
+        })
+}
+
+/// Returns `true` if `relay_result` holds a bid which we would propose in place of a local payload,
+/// i.e. one which passes verification and meets the `min_bid`.
+///
+/// The bid may still lose to the local payload on value, but it's safe to stop waiting for the
+/// local payload once the block production deadline has passed.
+fn is_usable_bid<E: EthSpec>(
+    relay_result: &Result<
+        Option<ForkVersionedResponse<SignedBuilderBid<E>>>,
+        builder_client::Error,
+    >,
+    parent_hash: ExecutionBlockHash,
+    payload_attributes: &PayloadAttributes,
+    current_fork: ForkName,
+    min_bid: Option<Uint256>,
+    spec: &ChainSpec,
+) -> bool {
+    let Ok(Some(relay)) = relay_result else {
+        return false;
+    };
+    min_bid.map_or(true, |min_bid| *relay.data.message.value() >= min_bid)
+        && verify_builder_bid(
+            relay,
+            parent_hash,
+            payload_attributes,
+            None,
+            current_fork,
+            spec,
+        )
+        .is_ok()
+}
+
+/// Waits for both `relay` and `local` to complete.
+///
+/// Once `deadline` has passed, the future that is still pending is abandoned as soon as the other
+/// has produced a usable result, according to `relay_usable` and `local_usable`. An abandoned
+/// future is returned as `None`. If neither result is usable there is nothing to fall back to, so
+/// both futures are awaited regardless of the deadline.
+async fn join_until_deadline<R, L>(
+    relay: impl Future<Output = R>,
+    local: impl Future<Output = L>,
+    deadline: Option<Instant>,
+    relay_usable: impl Fn(&R) -> bool,
+    local_usable: impl Fn(&L) -> bool,
+) -> (Option<R>, Option<L>) {
+    let Some(deadline) = deadline else {
+        let (relay, local) = tokio::join!(relay, local);
+        return (Some(relay), Some(local));
+    };
+
+    let deadline = tokio::time::sleep_until(deadline.into());
+    tokio::pin!(relay, local, deadline);
+
+    let mut relay_result = None;
+    let mut local_result = None;
+    let mut any_usable = false;
+    let mut deadline_passed = false;
+    loop {
+        tokio::select! {
+            result = &mut relay, if relay_result.is_none() => {
+                any_usable |= relay_usable(&result);
+                relay_result = Some(result);
+            }
+            result = &mut local, if local_result.is_none() => {
+                any_usable |= local_usable(&result);
+                local_result = Some(result);
+            }
+            () = &mut deadline, if !deadline_passed => deadline_passed = true,
+        }
+
+        let both_complete = relay_result.is_some() && local_result.is_some();
+        if both_complete || (deadline_passed && any_usable) {
+            return (relay_result, local_result);
+        }
+    }
+}
+
 /// A helper function to record the time it takes to execute a future.
 async fn timed_future<F: Future<Output = T>, T>(metric: &str, future: F) -> (T, Duration) {
     let start = Instant::now();
@@ -2119,9 +2881,18 @@ fn noop<E: EthSpec>(
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::test_utils::MockExecutionLayer as GenericMockExecutionLayer;
+    use crate::test_utils::{MockExecutionLayer as GenericMockExecutionLayer, MockServer};
     use task_executor::test_utils::TestRuntime;
-    use types::MainnetEthSpec;
+    use types::{MainnetEthSpec, SignedRoot};
+
+    /// Wait for `condition` to hold, failing the test if it doesn't within a few timeouts.
+    async fn wait_for(condition: impl Fn() -> bool) {
+        let deadline = Instant::now() + FALLBACK_REQUEST_TIMEOUT * 2;
+        while !condition() {
+            assert!(Instant::now() < deadline, "condition was not met in time");
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
 
     type MockExecutionLayer = GenericMockExecutionLayer<MainnetEthSpec>;
 
@@ -2138,6 +2909,329 @@ mod test {
             .await;
     }
 
+    #[tokio::test]
+    async fn reload_jwt_secret() {
+        let runtime = TestRuntime::default();
+        let mock = MockExecutionLayer::default_params(runtime.task_executor.clone());
+        let el = &mock.el;
+        let jwt_secret_file = el.inner.jwt_secret_file.clone();
+
+        el.upcheck().await;
+        assert!(!el.is_auth_failed().await);
+
+        // The engine rejects requests signed with a secret it doesn't know.
+        std::fs::write(&jwt_secret_file, JwtKey::random().hex_string()).unwrap();
+        el.reload_jwt_secret().await.unwrap();
+        assert!(el.is_auth_failed().await);
+
+        // Restoring the engine's secret brings it back online immediately.
+        std::fs::write(
+            &jwt_secret_file,
+            hex::encode(test_utils::DEFAULT_JWT_SECRET),
+        )
+        .unwrap();
+        el.reload_jwt_secret().await.unwrap();
+        assert!(!el.is_auth_failed().await);
+
+        // An invalid secret is rejected and the current secret remains in use.
+        std::fs::write(&jwt_secret_file, "not a secret").unwrap();
+        assert!(el.reload_jwt_secret().await.is_err());
+        el.upcheck().await;
+        assert!(!el.is_auth_failed().await);
+    }
+
+    #[tokio::test]
+    async fn new_payload_verdicts_are_cached() {
+        let runtime = TestRuntime::default();
+        let mock = MockExecutionLayer::default_params(runtime.task_executor.clone());
+        let el = &mock.el;
+
+        let payload = ExecutionPayloadBellatrix::<MainnetEthSpec> {
+            block_hash: ExecutionBlockHash::repeat_byte(1),
+            ..Default::default()
+        };
+        let request = || {
+            NewPayloadRequest::Bellatrix(NewPayloadRequestBellatrix {
+                execution_payload: &payload,
+            })
+        };
+        let status = |status| PayloadStatusV1 {
+            status,
+            latest_valid_hash: Some(payload.block_hash),
+            validation_error: None,
+        };
+
+        mock.server
+            .set_new_payload_status(payload.block_hash, status(PayloadStatusV1Status::Valid));
+        assert_eq!(
+            el.notify_new_payload(request()).await.unwrap(),
+            PayloadStatus::Valid
+        );
+
+        // The engine isn't asked to verify the payload again.
+        mock.server
+            .set_new_payload_status(payload.block_hash, status(PayloadStatusV1Status::Invalid));
+        assert_eq!(
+            el.notify_new_payload(request()).await.unwrap(),
+            PayloadStatus::Valid
+        );
+
+        // The verdicts are forgotten when the engine goes offline, since it may be restarted.
+        mock.server.set_syncing_response(Err("offline".to_string()));
+        el.upcheck().await;
+        mock.server.set_syncing_response(Ok(false));
+        el.upcheck().await;
+        assert!(matches!(
+            el.notify_new_payload(request()).await.unwrap(),
+            PayloadStatus::Invalid { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn invalid_block_hash_verdicts_are_not_cached() {
+        let runtime = TestRuntime::default();
+        let mock = MockExecutionLayer::default_params(runtime.task_executor.clone());
+        let el = &mock.el;
+
+        let payload = ExecutionPayloadBellatrix::<MainnetEthSpec> {
+            block_hash: ExecutionBlockHash::repeat_byte(1),
+            ..Default::default()
+        };
+        let request = || {
+            NewPayloadRequest::Bellatrix(NewPayloadRequestBellatrix {
+                execution_payload: &payload,
+            })
+        };
+        let status = |status| PayloadStatusV1 {
+            status,
+            latest_valid_hash: None,
+            validation_error: None,
+        };
+
+        mock.server.set_new_payload_status(
+            payload.block_hash,
+            status(PayloadStatusV1Status::InvalidBlockHash),
+        );
+        assert!(matches!(
+            el.notify_new_payload(request()).await.unwrap(),
+            PayloadStatus::InvalidBlockHash { .. }
+        ));
+
+        // The engine is asked to verify the payload again.
+        mock.server
+            .set_new_payload_status(payload.block_hash, status(PayloadStatusV1Status::Valid));
+        assert_eq!(
+            el.notify_new_payload(request()).await.unwrap(),
+            PayloadStatus::Valid
+        );
+    }
+
+    #[tokio::test]
+    async fn fallback_engines_follow_the_primary() {
+        let runtime = TestRuntime::default();
+        let primary = MockServer::<MainnetEthSpec>::unit_testing();
+        let fallbacks = (0..2)
+            .map(|_| MockServer::<MainnetEthSpec>::unit_testing())
+            .collect::<Vec<_>>();
+
+        let secret_file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(
+            secret_file.path(),
+            hex::encode(test_utils::DEFAULT_JWT_SECRET),
+        )
+        .unwrap();
+        let config = Config {
+            execution_endpoint: Some(SensitiveUrl::parse(&primary.url()).unwrap()),
+            fallback_execution_endpoints: fallbacks
+                .iter()
+                .map(|fallback| SensitiveUrl::parse(&fallback.url()).unwrap())
+                .collect(),
+            fallback_new_payload: true,
+            secret_file: Some(secret_file.path().into()),
+            suggested_fee_recipient: Some(Address::repeat_byte(42)),
+            ..Default::default()
+        };
+        let el = ExecutionLayer::<MainnetEthSpec>::from_config(
+            config,
+            runtime.task_executor.clone(),
+            runtime.task_executor.log().clone(),
+        )
+        .unwrap();
+
+        let payload = |byte: u8| ExecutionPayloadBellatrix::<MainnetEthSpec> {
+            block_hash: ExecutionBlockHash::repeat_byte(byte),
+            block_number: byte as u64,
+            ..Default::default()
+        };
+        let request = |payload| {
+            NewPayloadRequest::Bellatrix(NewPayloadRequestBellatrix {
+                execution_payload: payload,
+            })
+        };
+        let valid = |payload: &ExecutionPayloadBellatrix<MainnetEthSpec>| PayloadStatusV1 {
+            status: PayloadStatusV1Status::Valid,
+            latest_valid_hash: Some(payload.block_hash),
+            validation_error: None,
+        };
+
+        let first = payload(1);
+        let second = payload(2);
+        let forkchoice_updates = fallbacks
+            .iter()
+            .map(|fallback| {
+                for payload in [&first, &second] {
+                    fallback.set_new_payload_status(payload.block_hash, valid(payload));
+                }
+                let received = Arc::new(parking_lot::Mutex::new(vec![]));
+                let hook_received = received.clone();
+                fallback
+                    .ctx
+                    .hook
+                    .lock()
+                    .set_forkchoice_updated_hook(Box::new(move |state, payload_attributes| {
+                        hook_received
+                            .lock()
+                            .push((state.head_block_hash, payload_attributes.is_some()));
+                        None
+                    }));
+                received
+            })
+            .collect::<Vec<_>>();
+        for payload in [&first, &second] {
+            primary.set_new_payload_status(payload.block_hash, valid(payload));
+        }
+
+        // The fallbacks are sent the requests from a background task.
+        let fallbacks_validated = |block_number| {
+            el.inner
+                .fallback_engines
+                .iter()
+                .all(|engine| engine.latest_valid_block_number() == block_number)
+        };
+
+        // The fallbacks are sent the payload and declare it valid.
+        assert_eq!(
+            el.notify_new_payload(request(&first)).await.unwrap(),
+            PayloadStatus::Valid
+        );
+        wait_for(|| fallbacks_validated(first.block_number)).await;
+
+        // The fallbacks are sent the new head, without payload attributes.
+        let _ = el
+            .notify_forkchoice_updated(
+                first.block_hash,
+                ExecutionBlockHash::zero(),
+                ExecutionBlockHash::zero(),
+                Slot::new(0),
+                Hash256::repeat_byte(42),
+            )
+            .await;
+        wait_for(|| {
+            forkchoice_updates
+                .iter()
+                .all(|received| *received.lock() == vec![(first.block_hash, false)])
+        })
+        .await;
+
+        // Unresponsive fallbacks don't delay the response of the primary at all.
+        for fallback in &fallbacks {
+            fallback.set_response_delay(Some(FALLBACK_REQUEST_TIMEOUT * 3));
+        }
+        let start = Instant::now();
+        assert_eq!(
+            el.notify_new_payload(request(&second)).await.unwrap(),
+            PayloadStatus::Valid
+        );
+        let _ = el
+            .notify_forkchoice_updated(
+                second.block_hash,
+                ExecutionBlockHash::zero(),
+                ExecutionBlockHash::zero(),
+                Slot::new(0),
+                Hash256::repeat_byte(42),
+            )
+            .await;
+        assert!(start.elapsed() < FALLBACK_REQUEST_TIMEOUT);
+
+        // The fallback requests time out in the background.
+        tokio::time::sleep(FALLBACK_REQUEST_TIMEOUT * 2).await;
+        assert!(fallbacks_validated(first.block_number));
+    }
+
+    #[tokio::test]
+    async fn payload_reconstruction_fails_over_to_fallback_engines() {
+        let runtime = TestRuntime::default();
+        let primary = MockServer::<MainnetEthSpec>::unit_testing();
+        let fallbacks = (0..2)
+            .map(|_| MockServer::<MainnetEthSpec>::unit_testing())
+            .collect::<Vec<_>>();
+
+        let secret_file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(
+            secret_file.path(),
+            hex::encode(test_utils::DEFAULT_JWT_SECRET),
+        )
+        .unwrap();
+        let config = Config {
+            execution_endpoint: Some(SensitiveUrl::parse(&primary.url()).unwrap()),
+            fallback_execution_endpoints: fallbacks
+                .iter()
+                .map(|fallback| SensitiveUrl::parse(&fallback.url()).unwrap())
+                .collect(),
+            secret_file: Some(secret_file.path().into()),
+            suggested_fee_recipient: Some(Address::repeat_byte(42)),
+            ..Default::default()
+        };
+        let el = ExecutionLayer::<MainnetEthSpec>::from_config(
+            config,
+            runtime.task_executor.clone(),
+            runtime.task_executor.log().clone(),
+        )
+        .unwrap();
+
+        let payload: ExecutionPayload<MainnetEthSpec> = ExecutionPayloadBellatrix {
+            block_hash: ExecutionBlockHash::repeat_byte(1),
+            block_number: 1,
+            transactions: vec![test_utils::static_valid_tx::<MainnetEthSpec>().unwrap()].into(),
+            ..Default::default()
+        }
+        .into();
+        let header = ExecutionPayloadHeader::from(payload.to_ref());
+        let fork = ForkName::Bellatrix;
+
+        // The primary fails to serve payload bodies, and so does the first fallback. Only the
+        // second fallback knows the payload.
+        let without_payload_bodies = EngineCapabilities {
+            get_payload_bodies_by_range_v1: false,
+            ..test_utils::DEFAULT_ENGINE_CAPABILITIES
+        };
+        primary.set_engine_capabilities(without_payload_bodies);
+        fallbacks[0].set_engine_capabilities(without_payload_bodies);
+        fallbacks[1]
+            .execution_block_generator()
+            .insert_block_without_checks(test_utils::Block::PoS(payload.clone()));
+
+        assert!(matches!(
+            el.get_payload_for_header(&header, fork).await,
+            Err(Error::PayloadBodiesByRangeNotSupported)
+        ));
+        assert_eq!(
+            el.get_payload_for_header_from_fallbacks(&header, fork)
+                .await
+                .unwrap(),
+            Some(payload)
+        );
+
+        // A fallback which doesn't know the payload responds without it, rather than failing.
+        fallbacks[1].execution_block_generator().drop_all_blocks();
+        assert_eq!(
+            el.get_payload_for_header_from_fallbacks(&header, fork)
+                .await
+                .unwrap(),
+            None
+        );
+    }
+
     #[tokio::test]
     async fn test_forked_terminal_block() {
         let runtime = TestRuntime::default();
@@ -2261,4 +3355,147 @@ mod test {
             })
             .await;
     }
+
+    /// A future which completes after `delay` with a result that is usable if `usable`.
+    async fn payload_after(delay: Duration, usable: bool) -> bool {
+        tokio::time::sleep(delay).await;
+        usable
+    }
+
+    async fn join_payloads(
+        relay: (Duration, bool),
+        local: (Duration, bool),
+        deadline: Option<Duration>,
+    ) -> (Option<bool>, Option<bool>) {
+        join_until_deadline(
+            payload_after(relay.0, relay.1),
+            payload_after(local.0, local.1),
+            deadline.map(|deadline| Instant::now() + deadline),
+            |usable| *usable,
+            |usable| *usable,
+        )
+        .await
+    }
+
+    const FAST: Duration = Duration::from_millis(10);
+    const SLOW: Duration = Duration::from_secs(2);
+    const DEADLINE: Duration = Duration::from_millis(200);
+
+    #[tokio::test]
+    async fn slow_local_payload_is_skipped_at_deadline() {
+        let result = join_payloads((FAST, true), (SLOW, true), Some(DEADLINE)).await;
+        assert_eq!(result, (Some(true), None));
+    }
+
+    #[tokio::test]
+    async fn slow_builder_payload_is_skipped_at_deadline() {
+        let result = join_payloads((SLOW, true), (FAST, true), Some(DEADLINE)).await;
+        assert_eq!(result, (None, Some(true)));
+    }
+
+    #[tokio::test]
+    async fn payloads_are_awaited_without_deadline() {
+        let result = join_payloads((FAST, true), (DEADLINE, true), None).await;
+        assert_eq!(result, (Some(true), Some(true)));
+    }
+
+    #[tokio::test]
+    async fn slow_payload_is_awaited_if_the_other_is_unusable() {
+        let result = join_payloads((FAST, false), (DEADLINE * 2, true), Some(DEADLINE)).await;
+        assert_eq!(result, (Some(false), Some(true)));
+    }
+
+    type Bid = ForkVersionedResponse<SignedBuilderBid<MainnetEthSpec>>;
+
+    fn bid_parent_hash() -> ExecutionBlockHash {
+        ExecutionBlockHash::repeat_byte(2)
+    }
+
+    fn payload_attributes() -> PayloadAttributes {
+        PayloadAttributes::new(
+            42,
+            Hash256::repeat_byte(1),
+            Address::repeat_byte(3),
+            None,
+            None,
+        )
+    }
+
+    /// Returns a valid, signed Bellatrix bid of `value` built upon `payload_attributes()`.
+    fn bellatrix_bid(value: u64, spec: &ChainSpec) -> Bid {
+        let keypair = types::test_utils::generate_deterministic_keypair(0);
+        let payload_attributes = payload_attributes();
+        let message = BuilderBid::Bellatrix(types::builder_bid::BuilderBidBellatrix {
+            header: types::ExecutionPayloadHeaderBellatrix {
+                parent_hash: bid_parent_hash(),
+                prev_randao: payload_attributes.prev_randao(),
+                timestamp: payload_attributes.timestamp(),
+                ..<_>::default()
+            },
+            value: Uint256::from(value),
+            pubkey: keypair.pk.compress(),
+        });
+        let signature = keypair
+            .sk
+            .sign(message.signing_root(spec.get_builder_domain()));
+        ForkVersionedResponse {
+            version: Some(ForkName::Bellatrix),
+            metadata: <_>::default(),
+            data: SignedBuilderBid { message, signature },
+        }
+    }
+
+    /// Returns a bid which fails verification, as it is for the wrong fork.
+    fn invalid_bid(spec: &ChainSpec) -> Bid {
+        let mut bid = bellatrix_bid(100, spec);
+        bid.version = Some(ForkName::Capella);
+        bid
+    }
+
+    fn bid_is_usable(bid: Option<Bid>, min_bid: Option<u64>, spec: &ChainSpec) -> bool {
+        is_usable_bid(
+            &Ok(bid),
+            bid_parent_hash(),
+            &payload_attributes(),
+            ForkName::Bellatrix,
+            min_bid.map(Uint256::from),
+            spec,
+        )
+    }
+
+    #[test]
+    fn only_valid_bids_meeting_the_min_bid_are_usable() {
+        let spec = MainnetEthSpec::default_spec();
+        let bid = bellatrix_bid(100, &spec);
+
+        assert!(bid_is_usable(Some(bid.clone()), None, &spec));
+        assert!(bid_is_usable(Some(bid.clone()), Some(100), &spec));
+        assert!(!bid_is_usable(Some(bid), Some(101), &spec));
+        assert!(!bid_is_usable(Some(invalid_bid(&spec)), None, &spec));
+        assert!(!bid_is_usable(None, None, &spec));
+    }
+
+    /// A bid that arrives before the deadline but can't be proposed mustn't cause the local
+    /// payload to be abandoned once the deadline passes.
+    #[tokio::test]
+    async fn local_payload_is_awaited_after_unusable_bid() {
+        let spec = MainnetEthSpec::default_spec();
+        let unusable_bids = [
+            (bellatrix_bid(100, &spec), Some(101)),
+            (invalid_bid(&spec), None),
+        ];
+
+        for (bid, min_bid) in unusable_bids {
+            let (relay_result, local_result) = join_until_deadline(
+                async { bid },
+                payload_after(DEADLINE * 2, true),
+                Some(Instant::now() + DEADLINE),
+                |bid| bid_is_usable(Some(bid.clone()), min_bid, &spec),
+                |usable| *usable,
+            )
+            .await;
+            assert!(relay_result.is_some());
+            assert_eq!(local_result, Some(true));
+        }
+    }
 }
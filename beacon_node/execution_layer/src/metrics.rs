@@ -16,6 +16,7 @@ pub const LOCAL: &str = "local";
 pub const BUILDER: &str = "builder";
 pub const SUCCESS: &str = "success";
 pub const FAILURE: &str = "failure";
+pub const TIMEOUT: &str = "timeout";
 
 pub static EXECUTION_LAYER_PROPOSER_INSERTED: LazyLock<Result<IntCounter>> = LazyLock::new(|| {
     try_create_int_counter(
@@ -61,6 +62,30 @@ pub static EXECUTION_LAYER_GET_PAYLOAD_BODIES_BY_RANGE: LazyLock<Result<Histogra
             "Time to fetch a range of payload bodies from the EE",
         )
     });
+pub static EXECUTION_LAYER_PAYLOAD_RECONSTRUCTION_FAILOVERS: LazyLock<Result<IntCounterVec>> =
+    LazyLock::new(|| {
+        try_create_int_counter_vec(
+            "execution_layer_payload_reconstruction_failovers_total",
+            "The success/failure outcomes of reconstructing payloads using the fallback EEs",
+            &["outcome"],
+        )
+    });
+pub static EXECUTION_LAYER_FALLBACK_PAYLOAD_STATUS: LazyLock<Result<IntCounterVec>> =
+    LazyLock::new(|| {
+        try_create_int_counter_vec(
+            "execution_layer_fallback_payload_status",
+            "Indicates the payload status returned by the fallback EEs for newPayload and \
+            forkchoiceUpdated",
+            &["method", "status"],
+        )
+    });
+pub static EXECUTION_LAYER_FALLBACK_LAG: LazyLock<Result<IntGaugeVec>> = LazyLock::new(|| {
+    try_create_int_gauge_vec(
+        "execution_layer_fallback_lag_blocks",
+        "The number of blocks the latest valid payload of each fallback EE is behind the primary",
+        &["endpoint"],
+    )
+});
 pub static EXECUTION_LAYER_VERIFY_BLOCK_HASH: LazyLock<Result<Histogram>> = LazyLock::new(|| {
     try_create_histogram_with_buckets(
         "execution_layer_verify_block_hash_time",
@@ -77,6 +102,14 @@ pub static EXECUTION_LAYER_PAYLOAD_STATUS: LazyLock<Result<IntCounterVec>> = Laz
         &["method", "status"],
     )
 });
+pub static EXECUTION_LAYER_NEW_PAYLOAD_CACHE_HITS: LazyLock<Result<IntCounter>> =
+    LazyLock::new(|| {
+        try_create_int_counter(
+            "execution_layer_new_payload_cache_hits_total",
+            "Count of payloads whose verification was skipped because the engine had already \
+             returned a verdict for them",
+        )
+    });
 pub static EXECUTION_LAYER_GET_PAYLOAD_OUTCOME: LazyLock<Result<IntCounterVec>> =
     LazyLock::new(|| {
         try_create_int_counter_vec(
@@ -101,6 +134,14 @@ pub static EXECUTION_LAYER_GET_PAYLOAD_SOURCE: LazyLock<Result<IntCounterVec>> =
             &["source"],
         )
     });
+pub static EXECUTION_LAYER_BLOCK_PRODUCTION_DEADLINE_SKIPS: LazyLock<Result<IntCounterVec>> =
+    LazyLock::new(|| {
+        try_create_int_counter_vec(
+            "execution_layer_block_production_deadline_skips_total",
+            "The number of payload requests abandoned at the block production deadline",
+            &["source"],
+        )
+    });
 pub static EXECUTION_LAYER_GET_PAYLOAD_BUILDER_REJECTIONS: LazyLock<Result<IntCounterVec>> =
     LazyLock::new(|| {
         try_create_int_counter_vec(
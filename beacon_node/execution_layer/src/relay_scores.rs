@@ -0,0 +1,252 @@
+//! Scoring of builder relays.
+//!
+//! The outcome and latency of every header request is recorded against the relay which served it.
+//! A relay which repeatedly fails to provide a valid bid is deprioritized for a number of slots
+//! which doubles with each further failure, during which payloads are built locally rather than
+//! waiting on the relay.
+//!
+//! A relay which responds that it has no bid, or which is abandoned at the local block production
+//! deadline, has not failed and so is not deprioritized.
+use eth2::lighthouse::RelayStats;
+use parking_lot::Mutex;
+use sensitive_url::SensitiveUrl;
+use std::collections::BTreeMap;
+use std::time::Duration;
+use types::Slot;
+
+/// The number of consecutive failures after which a relay is deprioritized.
+pub const RELAY_FAILURE_THRESHOLD: u64 = 2;
+
+/// The maximum number of slots for which a relay is deprioritized after a failure.
+pub const MAX_DEPRIORITIZED_SLOTS: u64 = 32;
+
+/// The ways in which a relay can fail to provide a usable header.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RelayFailure {
+    /// The relay returned a bid which failed verification.
+    InvalidBid,
+    /// The request failed or timed out.
+    Error,
+}
+
+#[derive(Debug, Default)]
+struct RelayRecord {
+    requests: u64,
+    valid_bids: u64,
+    invalid_bids: u64,
+    no_bids: u64,
+    deadline_timeouts: u64,
+    errors: u64,
+    total_latency: Duration,
+    consecutive_failures: u64,
+    deprioritized_until: Option<Slot>,
+}
+
+impl RelayRecord {
+    fn stats(&self, url: &str) -> RelayStats {
+        RelayStats {
+            url: url.to_string(),
+            requests: self.requests,
+            valid_bids: self.valid_bids,
+            invalid_bids: self.invalid_bids,
+            no_bids: self.no_bids,
+            deadline_timeouts: self.deadline_timeouts,
+            errors: self.errors,
+            mean_latency_ms: (self.requests > 0)
+                .then(|| self.total_latency.as_secs_f64() * 1000.0 / self.requests as f64),
+            consecutive_failures: self.consecutive_failures,
+            deprioritized_until: self.deprioritized_until,
+        }
+    }
+}
+
+/// The records of all relays, keyed by their redacted URL.
+#[derive(Debug, Default)]
+pub struct RelayScores {
+    relays: Mutex<BTreeMap<String, RelayRecord>>,
+}
+
+impl RelayScores {
+    /// Start tracking `url`, so that it is reported before any requests have been made to it.
+    pub fn register(&self, url: &SensitiveUrl) {
+        self.relays.lock().entry(url.to_string()).or_default();
+    }
+
+    /// Record a header request to `url` which took `latency` to complete.
+    pub fn record_request(&self, url: &SensitiveUrl, latency: Duration) {
+        let mut relays = self.relays.lock();
+        let record = relays.entry(url.to_string()).or_default();
+        record.requests = record.requests.saturating_add(1);
+        record.total_latency = record.total_latency.saturating_add(latency);
+    }
+
+    /// Record that `url` provided a valid bid, restoring its priority.
+    pub fn record_valid_bid(&self, url: &SensitiveUrl) {
+        let mut relays = self.relays.lock();
+        let record = relays.entry(url.to_string()).or_default();
+        record.valid_bids = record.valid_bids.saturating_add(1);
+        record.consecutive_failures = 0;
+        record.deprioritized_until = None;
+    }
+
+    /// Record that `url` responded that it has no bid.
+    ///
+    /// This is a valid response, so it restores the relay's priority.
+    pub fn record_no_bid(&self, url: &SensitiveUrl) {
+        let mut relays = self.relays.lock();
+        let record = relays.entry(url.to_string()).or_default();
+        record.no_bids = record.no_bids.saturating_add(1);
+        record.consecutive_failures = 0;
+        record.deprioritized_until = None;
+    }
+
+    /// Record that the request to `url` was abandoned at the block production deadline.
+    ///
+    /// The deadline is local, so this doesn't count as a failure of the relay.
+    pub fn record_deadline_timeout(&self, url: &SensitiveUrl) {
+        let mut relays = self.relays.lock();
+        let record = relays.entry(url.to_string()).or_default();
+        record.deadline_timeouts = record.deadline_timeouts.saturating_add(1);
+    }
+
+    /// Record that `url` failed to provide a usable header for `slot`.
+    ///
+    /// Returns the slot until which the relay is deprioritized, if this failure caused it to be.
+    pub fn record_failure(
+        &self,
+        url: &SensitiveUrl,
+        slot: Slot,
+        failure: RelayFailure,
+    ) -> Option<Slot> {
+        let mut relays = self.relays.lock();
+        let record = relays.entry(url.to_string()).or_default();
+        match failure {
+            RelayFailure::InvalidBid => record.invalid_bids = record.invalid_bids.saturating_add(1),
+            RelayFailure::Error => record.errors = record.errors.saturating_add(1),
+        }
+        record.consecutive_failures = record.consecutive_failures.saturating_add(1);
+
+        let excess_failures = record
+            .consecutive_failures
+            .checked_sub(RELAY_FAILURE_THRESHOLD)?;
+        let penalty = 1u64
+            .checked_shl(excess_failures as u32)
+            .unwrap_or(u64::MAX)
+            .min(MAX_DEPRIORITIZED_SLOTS);
+        let deprioritized_until = slot.saturating_add(penalty);
+        record.deprioritized_until = Some(deprioritized_until);
+        Some(deprioritized_until)
+    }
+
+    /// Returns `true` if `url` should not be used for `slot`.
+    pub fn is_deprioritized(&self, url: &SensitiveUrl, slot: Slot) -> bool {
+        self.relays
+            .lock()
+            .get(url.as_ref())
+            .and_then(|record| record.deprioritized_until)
+            .map_or(false, |until| slot <= until)
+    }
+
+    pub fn stats(&self) -> Vec<RelayStats> {
+        self.relays
+            .lock()
+            .iter()
+            .map(|(url, record)| record.stats(url))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deprioritized_after_repeated_failures() {
+        let scores = RelayScores::default();
+        let relay = SensitiveUrl::parse("http://relay.example:18550").unwrap();
+        scores.register(&relay);
+
+        assert_eq!(
+            scores.record_failure(&relay, Slot::new(10), RelayFailure::Error),
+            None
+        );
+        assert!(!scores.is_deprioritized(&relay, Slot::new(11)));
+
+        // The penalty doubles with each failure beyond the threshold.
+        assert_eq!(
+            scores.record_failure(&relay, Slot::new(11), RelayFailure::Error),
+            Some(Slot::new(12))
+        );
+        assert!(scores.is_deprioritized(&relay, Slot::new(12)));
+        assert!(!scores.is_deprioritized(&relay, Slot::new(13)));
+        assert_eq!(
+            scores.record_failure(&relay, Slot::new(13), RelayFailure::InvalidBid),
+            Some(Slot::new(15))
+        );
+
+        scores.record_valid_bid(&relay);
+        assert!(!scores.is_deprioritized(&relay, Slot::new(14)));
+
+        let stats = scores.stats();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].url, relay.to_string());
+        assert_eq!(stats[0].errors, 2);
+        assert_eq!(stats[0].invalid_bids, 1);
+        assert_eq!(stats[0].valid_bids, 1);
+        assert_eq!(stats[0].consecutive_failures, 0);
+        assert_eq!(stats[0].deprioritized_until, None);
+    }
+
+    #[test]
+    fn no_bids_and_deadline_timeouts_are_not_failures() {
+        let scores = RelayScores::default();
+        let relay = SensitiveUrl::parse("http://relay.example:18550").unwrap();
+
+        scores.record_failure(&relay, Slot::new(10), RelayFailure::Error);
+        for _ in 0..RELAY_FAILURE_THRESHOLD {
+            scores.record_deadline_timeout(&relay);
+        }
+        assert!(!scores.is_deprioritized(&relay, Slot::new(11)));
+
+        // A response with no bid shows the relay is responsive, so it resets the failure count.
+        scores.record_no_bid(&relay);
+        assert_eq!(
+            scores.record_failure(&relay, Slot::new(11), RelayFailure::Error),
+            None
+        );
+
+        let stats = scores.stats();
+        assert_eq!(stats[0].no_bids, 1);
+        assert_eq!(stats[0].deadline_timeouts, RELAY_FAILURE_THRESHOLD);
+        assert_eq!(stats[0].errors, 2);
+        assert_eq!(stats[0].consecutive_failures, 1);
+    }
+
+    #[test]
+    fn penalty_is_capped() {
+        let scores = RelayScores::default();
+        let relay = SensitiveUrl::parse("http://relay.example:18550").unwrap();
+
+        let mut deprioritized_until = None;
+        for _ in 0..100 {
+            deprioritized_until = scores.record_failure(&relay, Slot::new(0), RelayFailure::Error);
+        }
+        assert_eq!(
+            deprioritized_until,
+            Some(Slot::new(MAX_DEPRIORITIZED_SLOTS))
+        );
+    }
+
+    #[test]
+    fn mean_latency() {
+        let scores = RelayScores::default();
+        let relay = SensitiveUrl::parse("http://relay.example:18550").unwrap();
+        assert_eq!(scores.stats(), vec![]);
+
+        scores.record_request(&relay, Duration::from_millis(100));
+        scores.record_request(&relay, Duration::from_millis(300));
+        let stats = scores.stats();
+        assert_eq!(stats[0].requests, 2);
+        assert_eq!(stats[0].mean_latency_ms, Some(200.0));
+    }
+}
@@ -542,6 +542,27 @@ pub async fn handle_rpc<E: EthSpec>(
         ENGINE_GET_CLIENT_VERSION_V1 => {
             Ok(serde_json::to_value([DEFAULT_CLIENT_VERSION.clone()]).unwrap())
         }
+        ENGINE_GET_PAYLOAD_BODIES_BY_HASH_V1 => {
+            let block_hashes = get_param::<Vec<ExecutionBlockHash>>(params, 0)
+                .map_err(|s| (s, BAD_PARAMS_ERROR_CODE))?;
+
+            let response = block_hashes
+                .into_iter()
+                .map(|block_hash| {
+                    ctx.execution_block_generator
+                        .read()
+                        .execution_payload_by_hash(block_hash)
+                        .map(|payload| {
+                            JsonExecutionPayloadBodyV1::from(ExecutionPayloadBodyV1::<E> {
+                                transactions: payload.transactions().clone(),
+                                withdrawals: payload.withdrawals().ok().cloned(),
+                            })
+                        })
+                })
+                .collect::<Vec<_>>();
+
+            Ok(serde_json::to_value(response).unwrap())
+        }
         ENGINE_GET_PAYLOAD_BODIES_BY_RANGE_V1 => {
             #[derive(Deserialize)]
             #[serde(transparent)]
@@ -273,6 +273,11 @@ impl<E: EthSpec> MockBuilder<E> {
         self.operations.write().insert(0, op);
     }
 
+    /// Returns `true` if the validator with `pubkey` has registered with this builder.
+    pub fn is_registered(&self, pubkey: &PublicKeyBytes) -> bool {
+        self.val_registration_cache.read().contains_key(pubkey)
+    }
+
     pub fn invalid_signatures(&self) {
         *self.invalidate_signatures.write() = true;
     }
@@ -126,6 +126,8 @@ impl<E: EthSpec> MockExecutionLayer<E> {
             pubkey: PublicKeyBytes::empty(),
             slot,
             chain_health: ChainHealth::Healthy,
+            deadline: None,
+            min_bid_gwei: None,
         };
         let suggested_fee_recipient = self.el.get_suggested_fee_recipient(validator_index).await;
         let payload_attributes =
@@ -166,6 +168,8 @@ impl<E: EthSpec> MockExecutionLayer<E> {
             pubkey: PublicKeyBytes::empty(),
             slot,
             chain_health: ChainHealth::Healthy,
+            deadline: None,
+            min_bid_gwei: None,
         };
         let suggested_fee_recipient = self.el.get_suggested_fee_recipient(validator_index).await;
         let payload_attributes =
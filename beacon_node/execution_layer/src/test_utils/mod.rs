@@ -20,6 +20,7 @@ use std::future::Future;
 use std::marker::PhantomData;
 use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
 use std::sync::{Arc, LazyLock};
+use std::time::Duration;
 use tokio::{runtime, sync::oneshot};
 use types::{EthSpec, ExecutionBlockHash, Uint256};
 use warp::{http::StatusCode, Filter, Rejection};
@@ -162,6 +163,7 @@ impl<E: EthSpec> MockServer<E> {
             new_payload_statuses: <_>::default(),
             fcu_payload_statuses: <_>::default(),
             syncing_response: Arc::new(Mutex::new(Ok(false))),
+            response_delay: <_>::default(),
             engine_capabilities: Arc::new(RwLock::new(DEFAULT_ENGINE_CAPABILITIES)),
             _phantom: PhantomData,
         });
@@ -474,6 +476,11 @@ impl<E: EthSpec> MockServer<E> {
             .insert(block_hash, Err(error));
     }
 
+    /// Delay every subsequent JSON-RPC response by `delay`, or remove the delay if `None`.
+    pub fn set_response_delay(&self, delay: Option<Duration>) {
+        *self.ctx.response_delay.lock() = delay;
+    }
+
     pub fn set_syncing_response(&self, res: Result<bool, String>) {
         *self.ctx.syncing_response.lock() = res;
     }
@@ -537,6 +544,8 @@ pub struct Context<E: EthSpec> {
     pub fcu_payload_statuses:
         Arc<Mutex<HashMap<ExecutionBlockHash, Result<PayloadStatusV1, String>>>>,
     pub syncing_response: Arc<Mutex<Result<bool, String>>>,
+    /// Delay applied before responding to each JSON-RPC request, to simulate a slow EL.
+    pub response_delay: Arc<Mutex<Option<Duration>>>,
 
     pub engine_capabilities: Arc<RwLock<EngineCapabilities>>,
     pub _phantom: PhantomData<E>,
@@ -672,6 +681,11 @@ pub fn serve<E: EthSpec>(
                 .get("id")
                 .and_then(serde_json::Value::as_u64)
                 .ok_or_else(|| warp::reject::custom(MissingIdField))?;
+            let response_delay = *ctx.response_delay.lock();
+            if let Some(delay) = response_delay {
+                tokio::time::sleep(delay).await;
+            }
+
             let preloaded_response = {
                 let mut preloaded_responses = ctx.preloaded_responses.lock();
                 if !preloaded_responses.is_empty() {
@@ -0,0 +1,367 @@
+//! Optional bearer-token authentication for the beacon node HTTP API.
+//!
+//! Tokens are stored in a JSON file as SHA256 hashes alongside a name and an `ApiScope`. The
+//! plaintext token is only ever shown once, when it is created.
+//!
+//! When a tokens file is configured, every request must present a token in the `Authorization`
+//! header (`Bearer <token>`) whose scope covers the scope required by the route (see
+//! `required_scope`).
+use crate::rate_limiter::Quota;
+use filesystem::create_with_600_perms;
+use rand::distributions::Alphanumeric;
+use rand::{thread_rng, Rng};
+use serde::{Deserialize, Serialize};
+use slog::{info, Logger};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+use warp::filters::BoxedFilter;
+use warp::http::Method;
+use warp::path::FullPath;
+use warp::Filter;
+
+/// The default name of the file which stores the API tokens.
+pub const API_TOKENS_FILENAME: &str = "api-tokens.json";
+
+/// The length of newly generated tokens.
+pub const API_TOKEN_LEN: usize = 40;
+
+/// Routes which require the `Admin` scope, regardless of the method used to access them.
+///
+/// Prefixes are matched on whole path segments (see `has_path_prefix`).
+const ADMIN_PATH_PREFIXES: &[&str] = &[
+    "/lighthouse/database/reconstruct",
+    "/lighthouse/database/prune_blobs",
+    "/lighthouse/das",
+    "/lighthouse/execution_layer",
+    "/lighthouse/logs",
+    "/lighthouse/syncing/backfill_throttle",
+];
+
+/// `POST` routes which only read data and therefore only require the `ReadOnly` scope.
+const READ_ONLY_POST_PATH_PREFIXES: &[&str] = &[
+    "/eth/v1/beacon/states",
+    "/eth/v1/beacon/rewards",
+    "/lighthouse/liveness",
+    "/lighthouse/beacon/headers_by_roots",
+    "/lighthouse/beacon/roots_by_slots",
+    "/lighthouse/analysis",
+    "/lighthouse/ui",
+];
+
+/// The level of access granted by a token.
+///
+/// Scopes are ordered such that each scope also grants access to all routes of the scopes below
+/// it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ApiScope {
+    /// Access to routes which read data from the node.
+    ReadOnly,
+    /// Access to routes required by a validator client, e.g., publishing blocks and attestations.
+    ValidatorDuties,
+    /// Access to all routes, including those which administer the node.
+    Admin,
+}
+
+impl ApiScope {
+    /// Returns `true` if a token with this scope may access a route requiring `required`.
+    pub fn permits(self, required: ApiScope) -> bool {
+        self >= required
+    }
+}
+
+impl fmt::Display for ApiScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiScope::ReadOnly => write!(f, "read-only"),
+            ApiScope::ValidatorDuties => write!(f, "validator-duties"),
+            ApiScope::Admin => write!(f, "admin"),
+        }
+    }
+}
+
+impl FromStr for ApiScope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "read-only" => Ok(ApiScope::ReadOnly),
+            "validator-duties" => Ok(ApiScope::ValidatorDuties),
+            "admin" => Ok(ApiScope::Admin),
+            other => Err(format!(
+                "{} is not a valid scope, use one of read-only, validator-duties or admin",
+                other
+            )),
+        }
+    }
+}
+
+/// A named token, stored by the hash of its secret.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiToken {
+    pub name: String,
+    pub scope: ApiScope,
+    /// The `0x`-prefixed SHA256 hash of the token secret.
+    pub token_hash: String,
+    /// The rate limit quota for this token, overriding the default HTTP API quota.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quota: Option<Quota>,
+}
+
+/// The collection of tokens which may access the HTTP API.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ApiTokens {
+    pub tokens: Vec<ApiToken>,
+}
+
+impl ApiTokens {
+    /// Reads the tokens from the file at `path`.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        let bytes =
+            fs::read(path).map_err(|e| format!("Unable to read {}: {:?}", path.display(), e))?;
+        serde_json::from_slice(&bytes)
+            .map_err(|e| format!("Unable to parse {}: {:?}", path.display(), e))
+    }
+
+    /// Reads the tokens from the file at `path`, returning an empty collection if the file does
+    /// not exist.
+    pub fn load_or_default<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        if path.as_ref().exists() {
+            Self::load(path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// Writes the tokens to the file at `path`, readable only by the current user.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), String> {
+        let path = path.as_ref();
+        let bytes = serde_json::to_vec_pretty(self)
+            .map_err(|e| format!("Unable to encode tokens: {:?}", e))?;
+        create_with_600_perms(path, &bytes)
+            .map_err(|e| format!("Unable to write {}: {:?}", path.display(), e))
+    }
+
+    /// Generates a new token with the given `name` and `scope`, returning its secret.
+    pub fn add(&mut self, name: String, scope: ApiScope) -> Result<String, String> {
+        if self.tokens.iter().any(|token| token.name == name) {
+            return Err(format!("A token named {} already exists", name));
+        }
+
+        let secret: String = thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(API_TOKEN_LEN)
+            .map(char::from)
+            .collect();
+
+        self.tokens.push(ApiToken {
+            name,
+            scope,
+            token_hash: hash_secret(&secret),
+            quota: None,
+        });
+
+        Ok(secret)
+    }
+
+    /// Removes the token with the given `name`, returning `false` if no such token exists.
+    pub fn remove(&mut self, name: &str) -> bool {
+        let len_before = self.tokens.len();
+        self.tokens.retain(|token| token.name != name);
+        self.tokens.len() != len_before
+    }
+
+    /// Returns the token matching the `Authorization` header value (`Bearer <token>`), if any.
+    pub fn authenticate(&self, header: &str) -> Option<&ApiToken> {
+        let secret = header.strip_prefix("Bearer ")?;
+        let token_hash = hash_secret(secret.trim());
+        self.tokens
+            .iter()
+            .find(|token| token.token_hash == token_hash)
+    }
+}
+
+fn hash_secret(secret: &str) -> String {
+    format!(
+        "0x{}",
+        hex::encode(ethereum_hashing::hash(secret.as_bytes()))
+    )
+}
+
+/// Returns `true` if the segments of `prefix` are the leading segments of `path`.
+///
+/// Unlike `str::starts_with`, `/lighthouse/logs` is not a prefix of `/lighthouse/logs_other`.
+fn has_path_prefix(path: &str, prefix: &str) -> bool {
+    let mut path_segments = path.split('/');
+    prefix
+        .split('/')
+        .all(|segment| path_segments.next() == Some(segment))
+}
+
+/// Returns the scope required to access the route at `path` with `method`.
+pub fn required_scope(method: &Method, path: &str) -> ApiScope {
+    if ADMIN_PATH_PREFIXES
+        .iter()
+        .any(|prefix| has_path_prefix(path, prefix))
+    {
+        ApiScope::Admin
+    } else if method == Method::GET
+        || READ_ONLY_POST_PATH_PREFIXES
+            .iter()
+            .any(|prefix| has_path_prefix(path, prefix))
+    {
+        ApiScope::ReadOnly
+    } else {
+        ApiScope::ValidatorDuties
+    }
+}
+
+/// Returns a filter which rejects requests that lack a token with the scope required by the
+/// requested route.
+///
+/// If `tokens` is `None` authentication is disabled and all requests are accepted. Requests to
+/// `Admin` routes are logged for auditing purposes.
+pub fn authorization_filter(tokens: Option<Arc<ApiTokens>>, log: Logger) -> BoxedFilter<()> {
+    let Some(tokens) = tokens else {
+        return warp::any().boxed();
+    };
+
+    warp::any()
+        .map(move || (tokens.clone(), log.clone()))
+        .and(warp::method())
+        .and(warp::path::full())
+        .and(warp::header::header::<String>("Authorization"))
+        .and_then(
+            |(tokens, log): (Arc<ApiTokens>, Logger),
+             method: Method,
+             path: FullPath,
+             header: String| async move {
+                let required = required_scope(&method, path.as_str());
+                let token = tokens
+                    .authenticate(&header)
+                    .ok_or_else(|| warp_utils::reject::invalid_auth("unknown token".to_string()))?;
+
+                if !token.scope.permits(required) {
+                    return Err(warp_utils::reject::invalid_auth(format!(
+                        "token {} has scope {} but {} is required",
+                        token.name, token.scope, required
+                    )));
+                }
+
+                if required == ApiScope::Admin {
+                    info!(
+                        log,
+                        "Admin API request authorized";
+                        "token" => &token.name,
+                        "method" => %method,
+                        "path" => path.as_str(),
+                    );
+                }
+
+                Ok::<_, warp::Rejection>(())
+            },
+        )
+        .untuple_one()
+        .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scope_ordering() {
+        assert!(ApiScope::Admin.permits(ApiScope::ValidatorDuties));
+        assert!(ApiScope::ValidatorDuties.permits(ApiScope::ReadOnly));
+        assert!(!ApiScope::ReadOnly.permits(ApiScope::ValidatorDuties));
+        assert!(!ApiScope::ValidatorDuties.permits(ApiScope::Admin));
+
+        for scope in [
+            ApiScope::ReadOnly,
+            ApiScope::ValidatorDuties,
+            ApiScope::Admin,
+        ] {
+            assert_eq!(scope.to_string().parse::<ApiScope>(), Ok(scope));
+        }
+    }
+
+    #[test]
+    fn route_scopes() {
+        let get = Method::GET;
+        let post = Method::POST;
+        assert_eq!(
+            required_scope(&get, "/eth/v1/node/version"),
+            ApiScope::ReadOnly
+        );
+        assert_eq!(
+            required_scope(&post, "/eth/v1/beacon/states/head/validators"),
+            ApiScope::ReadOnly
+        );
+        assert_eq!(
+            required_scope(&post, "/eth/v2/beacon/blocks"),
+            ApiScope::ValidatorDuties
+        );
+        assert_eq!(
+            required_scope(&post, "/eth/v1/validator/duties/attester/1"),
+            ApiScope::ValidatorDuties
+        );
+        assert_eq!(
+            required_scope(&get, "/lighthouse/database/info"),
+            ApiScope::ReadOnly
+        );
+        assert_eq!(
+            required_scope(&post, "/lighthouse/database/reconstruct"),
+            ApiScope::Admin
+        );
+        assert_eq!(
+            required_scope(&post, "/lighthouse/das/reconstruct/0x00"),
+            ApiScope::Admin
+        );
+        assert_eq!(required_scope(&get, "/lighthouse/logs"), ApiScope::Admin);
+    }
+
+    #[test]
+    fn route_prefixes_match_whole_segments() {
+        assert!(has_path_prefix("/lighthouse/logs", "/lighthouse/logs"));
+        assert!(has_path_prefix("/lighthouse/das/x", "/lighthouse/das"));
+        assert!(!has_path_prefix(
+            "/lighthouse/logs_other",
+            "/lighthouse/logs"
+        ));
+        assert!(!has_path_prefix("/lighthouse", "/lighthouse/logs"));
+        assert_eq!(
+            required_scope(&Method::GET, "/lighthouse/logs_other"),
+            ApiScope::ReadOnly
+        );
+        assert_eq!(
+            required_scope(&Method::POST, "/eth/v1/beacon/states_other"),
+            ApiScope::ValidatorDuties
+        );
+    }
+
+    #[test]
+    fn add_authenticate_remove() {
+        let mut tokens = ApiTokens::default();
+        let secret = tokens
+            .add("vc".to_string(), ApiScope::ValidatorDuties)
+            .unwrap();
+        assert!(tokens.add("vc".to_string(), ApiScope::Admin).is_err());
+
+        let token = tokens.authenticate(&format!("Bearer {}", secret)).unwrap();
+        assert_eq!(token.name, "vc");
+        assert_eq!(token.scope, ApiScope::ValidatorDuties);
+        assert!(tokens
+            .authenticate(&format!("Bearer {}x", secret))
+            .is_none());
+        assert!(tokens.authenticate(&secret).is_none());
+        assert!(tokens.authenticate(&format!("Basic {}", secret)).is_none());
+
+        assert!(tokens.remove("vc"));
+        assert!(!tokens.remove("vc"));
+        assert!(tokens.authenticate(&format!("Bearer {}", secret)).is_none());
+    }
+}
@@ -0,0 +1,55 @@
+use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes};
+use eth2::types::BlobIndicesQuery;
+use types::{BlobSidecarList, EthSpec, Hash256};
+use warp_utils::reject::{
+    beacon_chain_error, custom_bad_request, custom_not_found, custom_server_error,
+};
+
+/// Returns the blob sidecars of the block at `block_root`, filtered by the indices in `query`.
+///
+/// Unlike `beacon/blob_sidecars`, the blobs of a block which is still awaiting data availability
+/// or import are also returned.
+pub fn get_blobs_by_root<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    block_root: Hash256,
+    query: BlobIndicesQuery,
+) -> Result<BlobSidecarList<T::EthSpec>, warp::Rejection> {
+    let max_blobs_per_block = T::EthSpec::max_blobs_per_block() as u64;
+    if let Some(index) = query
+        .indices
+        .iter()
+        .flatten()
+        .find(|index| **index >= max_blobs_per_block)
+    {
+        return Err(custom_bad_request(format!(
+            "blob index {} is not less than {}",
+            index, max_blobs_per_block
+        )));
+    }
+
+    let blobs = chain
+        .get_blobs_checking_all_caches(block_root, query.indices.as_deref())
+        .map_err(beacon_chain_error)?;
+
+    // A block which is awaiting its blobs in the data availability checker is known, even if none
+    // of its blobs have been received yet.
+    if blobs.is_empty()
+        && !chain.block_is_known_to_fork_choice(&block_root)
+        && chain
+            .data_availability_checker
+            .cached_blob_indexes(&block_root)
+            .is_none()
+        && !chain
+            .store
+            .block_exists(&block_root)
+            .map_err(BeaconChainError::DBError)
+            .map_err(beacon_chain_error)?
+    {
+        return Err(custom_not_found(format!(
+            "no blobs found for block root {:?}",
+            block_root
+        )));
+    }
+
+    BlobSidecarList::new(blobs).map_err(|e| custom_server_error(format!("invalid blobs: {:?}", e)))
+}
@@ -0,0 +1,308 @@
+//! Pre-serialized finalized state and block for nodes acting as checkpoint sync providers.
+//!
+//! A public checkpoint sync provider may be asked for the finalized state by hundreds of
+//! bootstrapping nodes per epoch. Rather than loading the state from the database and encoding it
+//! for each of them, the SSZ bytes of the latest finalized state and block are kept in memory and
+//! rebuilt once each time the finalized checkpoint changes, as soon as finalization is observed.
+//!
+//! Only SSZ responses are served from the cache, JSON requests fall through to the usual handlers.
+use crate::block_id::BlockId;
+use crate::metrics;
+use crate::state_id::StateId;
+use crate::version::{
+    add_consensus_version_header, add_ssz_content_type_header, inconsistent_fork_rejection,
+    ssz_snappy_response,
+};
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use bytes::Bytes;
+use eth2::types::{Accept, BlockId as CoreBlockId, StateId as CoreStateId};
+use parking_lot::RwLock;
+use slog::{debug, Logger};
+use ssz::Encode;
+use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
+use types::{Checkpoint, EthSpec, ForkName, Hash256, Slot};
+use warp::hyper::Body;
+use warp::reply::Response;
+
+/// The SSZ bytes of the state and block at a finalized checkpoint.
+pub struct CheckpointData {
+    pub checkpoint: Checkpoint,
+    pub state_root: Hash256,
+    pub state_slot: Slot,
+    pub state_fork_name: ForkName,
+    pub state_bytes: Bytes,
+    pub block_slot: Slot,
+    pub block_fork_name: ForkName,
+    pub block_bytes: Bytes,
+}
+
+impl CheckpointData {
+    fn matches_state(&self, state_id: &CoreStateId) -> bool {
+        match state_id {
+            CoreStateId::Finalized => true,
+            CoreStateId::Slot(slot) => *slot == self.state_slot,
+            CoreStateId::Root(root) => *root == self.state_root,
+            CoreStateId::Head | CoreStateId::Genesis | CoreStateId::Justified => false,
+        }
+    }
+
+    fn matches_block(&self, block_id: &CoreBlockId) -> bool {
+        match block_id {
+            CoreBlockId::Finalized => true,
+            CoreBlockId::Slot(slot) => *slot == self.block_slot,
+            CoreBlockId::Root(root) => *root == self.checkpoint.root,
+            CoreBlockId::Head | CoreBlockId::Genesis | CoreBlockId::Justified => false,
+        }
+    }
+}
+
+pub struct CheckpointProviderCache<T: BeaconChainTypes> {
+    chain: Arc<BeaconChain<T>>,
+    data: RwLock<Option<Arc<CheckpointData>>>,
+    /// Held whilst the cache is being rebuilt, so that requests arriving just after finalization
+    /// wait for a single refresh rather than each loading the state themselves.
+    refresh_lock: tokio::sync::Mutex<()>,
+    log: Logger,
+}
+
+impl<T: BeaconChainTypes> CheckpointProviderCache<T> {
+    pub fn new(chain: Arc<BeaconChain<T>>, log: Logger) -> Self {
+        Self {
+            chain,
+            data: RwLock::new(None),
+            refresh_lock: tokio::sync::Mutex::new(()),
+            log,
+        }
+    }
+
+    /// Spawn a task which rebuilds the cache each time the finalized checkpoint changes, so that
+    /// the first requests after finalization don't wait for the state to be loaded.
+    ///
+    /// Without an event handler the cache is instead rebuilt by the first request after
+    /// finalization.
+    pub fn spawn_refresh_task(self: &Arc<Self>) {
+        let Some(event_handler) = self.chain.event_handler.as_ref() else {
+            return;
+        };
+        let mut finalized_events = event_handler.subscribe_finalized();
+        let cache = self.clone();
+        self.chain.task_executor.spawn(
+            async move {
+                loop {
+                    match finalized_events.recv().await {
+                        Ok(_) | Err(RecvError::Lagged(_)) => {
+                            if let Err(e) = cache.get().await {
+                                debug!(
+                                    cache.log,
+                                    "Unable to refresh checkpoint provider cache";
+                                    "error" => ?e,
+                                );
+                            }
+                        }
+                        Err(RecvError::Closed) => break,
+                    }
+                }
+            },
+            "checkpoint_provider_refresh",
+        );
+    }
+
+    /// Return an SSZ response for `state_id` if it refers to the latest finalized state.
+    ///
+    /// Returns `None` if the request should be handled by the usual state route.
+    pub async fn state_response(
+        &self,
+        state_id: &StateId,
+        accept_header: Option<Accept>,
+    ) -> Result<Option<Response>, warp::Rejection> {
+        let accept = match accept_header {
+            Some(accept @ (Accept::Ssz | Accept::SszSnappy)) => accept,
+            _ => return Ok(None),
+        };
+        if !self.may_match_state(&state_id.0)? {
+            return Ok(None);
+        }
+
+        let data = self.get().await?;
+        if !data.matches_state(&state_id.0) {
+            return Ok(None);
+        }
+        metrics::inc_counter(&metrics::HTTP_API_CHECKPOINT_PROVIDER_HITS);
+        ssz_response(data.state_bytes.clone(), accept, data.state_fork_name)
+            .await
+            .map(Some)
+    }
+
+    /// Return an SSZ response for `block_id` if it refers to the latest finalized block.
+    ///
+    /// Returns `None` if the request should be handled by the usual block route.
+    pub async fn block_response(
+        &self,
+        block_id: &BlockId,
+        accept_header: Option<Accept>,
+    ) -> Result<Option<Response>, warp::Rejection> {
+        let accept = match accept_header {
+            Some(accept @ (Accept::Ssz | Accept::SszSnappy)) => accept,
+            _ => return Ok(None),
+        };
+        if !self.may_match_block(&block_id.0) {
+            return Ok(None);
+        }
+
+        let data = self.get().await?;
+        if !data.matches_block(&block_id.0) {
+            return Ok(None);
+        }
+        metrics::inc_counter(&metrics::HTTP_API_CHECKPOINT_PROVIDER_HITS);
+        ssz_response(data.block_bytes.clone(), accept, data.block_fork_name)
+            .await
+            .map(Some)
+    }
+
+    /// Return the data for the current finalized checkpoint, rebuilding it if finalization has
+    /// advanced since it was last loaded.
+    async fn get(&self) -> Result<Arc<CheckpointData>, warp::Rejection> {
+        if let Some(data) = self.cached() {
+            return Ok(data);
+        }
+
+        let _refresh_guard = self.refresh_lock.lock().await;
+        // Another request may have refreshed the cache whilst this one was waiting.
+        if let Some(data) = self.cached() {
+            return Ok(data);
+        }
+
+        let checkpoint = self.finalized_checkpoint();
+        let data = Arc::new(self.load(checkpoint).await?);
+        debug!(
+            self.log,
+            "Refreshed checkpoint provider cache";
+            "epoch" => %checkpoint.epoch,
+            "state_slot" => %data.state_slot,
+            "block_root" => ?checkpoint.root,
+        );
+        *self.data.write() = Some(data.clone());
+        Ok(data)
+    }
+
+    /// Returns `false` if `state_id` can be ruled out as the latest finalized state without
+    /// loading it, so that requests for other states don't trigger a refresh.
+    fn may_match_state(&self, state_id: &CoreStateId) -> Result<bool, warp::Rejection> {
+        let state_slot = self
+            .finalized_checkpoint()
+            .epoch
+            .start_slot(T::EthSpec::slots_per_epoch());
+        Ok(match state_id {
+            CoreStateId::Finalized => true,
+            CoreStateId::Slot(slot) => *slot == state_slot,
+            CoreStateId::Root(root) => match self.cached() {
+                Some(data) => *root == data.state_root,
+                None => {
+                    self.chain
+                        .state_root_at_slot(state_slot)
+                        .map_err(warp_utils::reject::beacon_chain_error)?
+                        == Some(*root)
+                }
+            },
+            CoreStateId::Head | CoreStateId::Genesis | CoreStateId::Justified => false,
+        })
+    }
+
+    /// Returns `false` if `block_id` can be ruled out as the latest finalized block without
+    /// loading it, so that requests for other blocks don't trigger a refresh.
+    fn may_match_block(&self, block_id: &CoreBlockId) -> bool {
+        let checkpoint = self.finalized_checkpoint();
+        match block_id {
+            CoreBlockId::Finalized => true,
+            CoreBlockId::Slot(slot) => self
+                .chain
+                .canonical_head
+                .fork_choice_read_lock()
+                .get_block(&checkpoint.root)
+                .map_or(true, |block| block.slot == *slot),
+            CoreBlockId::Root(root) => *root == checkpoint.root,
+            CoreBlockId::Head | CoreBlockId::Genesis | CoreBlockId::Justified => false,
+        }
+    }
+
+    fn finalized_checkpoint(&self) -> Checkpoint {
+        self.chain
+            .canonical_head
+            .cached_head()
+            .finalized_checkpoint()
+    }
+
+    fn cached(&self) -> Option<Arc<CheckpointData>> {
+        let finalized_checkpoint = self.finalized_checkpoint();
+        self.data
+            .read()
+            .as_ref()
+            .filter(|data| data.checkpoint == finalized_checkpoint)
+            .cloned()
+    }
+
+    async fn load(&self, checkpoint: Checkpoint) -> Result<CheckpointData, warp::Rejection> {
+        let _timer = metrics::start_timer(&metrics::HTTP_API_CHECKPOINT_PROVIDER_REFRESH_TIMES);
+
+        let (block, _, _) = BlockId::from_root(checkpoint.root)
+            .full_block(&self.chain)
+            .await?;
+
+        let chain = self.chain.clone();
+        warp_utils::task::blocking_task(move || {
+            // Serve the state at the start of the checkpoint epoch, as for the `finalized` state
+            // ID, which may have been advanced through skipped slots after the finalized block.
+            let state_slot = checkpoint.epoch.start_slot(T::EthSpec::slots_per_epoch());
+            let state_root = chain
+                .state_root_at_slot(state_slot)
+                .map_err(warp_utils::reject::beacon_chain_error)?
+                .ok_or_else(|| {
+                    warp_utils::reject::custom_not_found(format!(
+                        "beacon state at slot {}",
+                        state_slot
+                    ))
+                })?;
+            let state = chain
+                .get_state(&state_root, Some(state_slot))
+                .map_err(warp_utils::reject::beacon_chain_error)?
+                .ok_or_else(|| {
+                    warp_utils::reject::custom_not_found(format!(
+                        "beacon state at root {}",
+                        state_root
+                    ))
+                })?;
+
+            Ok(CheckpointData {
+                checkpoint,
+                state_root,
+                state_slot,
+                state_fork_name: state
+                    .fork_name(&chain.spec)
+                    .map_err(inconsistent_fork_rejection)?,
+                state_bytes: state.as_ssz_bytes().into(),
+                block_slot: block.slot(),
+                block_fork_name: block
+                    .fork_name(&chain.spec)
+                    .map_err(inconsistent_fork_rejection)?,
+                block_bytes: block.as_ssz_bytes().into(),
+            })
+        })
+        .await
+    }
+}
+
+async fn ssz_response(
+    bytes: Bytes,
+    accept: Accept,
+    fork_name: ForkName,
+) -> Result<Response, warp::Rejection> {
+    let response = match accept {
+        // Compressing a full state takes long enough that it shouldn't run on the async runtime.
+        Accept::SszSnappy => {
+            warp_utils::task::blocking_response_task(move || ssz_snappy_response(&bytes)).await?
+        }
+        _ => add_ssz_content_type_header(Response::new(Body::from(bytes))),
+    };
+    Ok(add_consensus_version_header(response, fork_name))
+}
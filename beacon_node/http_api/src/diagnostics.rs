@@ -0,0 +1,149 @@
+use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes, WhenSlotSkipped};
+use eth2::lighthouse::{CustodyDiagnostics, Diagnostics, ExecutionEngineDiagnostics};
+use lighthouse_network::NetworkGlobals;
+use parking_lot::RwLock;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use sysinfo::System;
+use system_health::{observe_nat, observe_system_health_bn};
+use types::{EthSpec, Slot};
+use warp_utils::reject::{beacon_chain_error, custom_server_error};
+
+/// Gathers the live diagnostics of the node for `lighthouse doctor`.
+pub async fn get_diagnostics<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    network_globals: Arc<NetworkGlobals<T::EthSpec>>,
+    sysinfo: Arc<RwLock<System>>,
+    data_dir: PathBuf,
+    app_uptime: u64,
+) -> Result<Diagnostics, warp::Rejection> {
+    let system_time_millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| custom_server_error(format!("system time before UNIX epoch: {:?}", e)))?
+        .as_millis() as u64;
+
+    let execution_engine = if let Some(el) = chain.execution_layer.as_ref() {
+        ExecutionEngineDiagnostics {
+            configured: true,
+            online: !el.is_offline_or_erroring().await,
+            synced: el.is_synced().await,
+            auth_failed: el.is_auth_failed().await,
+        }
+    } else {
+        ExecutionEngineDiagnostics {
+            configured: false,
+            online: false,
+            synced: false,
+            auth_failed: false,
+        }
+    };
+
+    let system_health =
+        observe_system_health_bn(sysinfo, data_dir, app_uptime, network_globals.clone());
+
+    let listen_addrs = network_globals.config.listen_addrs();
+    let libp2p_tcp_ports = listen_addrs
+        .v4()
+        .map(|addr| addr.tcp_port)
+        .into_iter()
+        .chain(listen_addrs.v6().map(|addr| addr.tcp_port))
+        .collect();
+
+    let mut peer_clients = BTreeMap::new();
+    for (_, peer_info) in network_globals.peers.read().connected_peers() {
+        let kind: &str = peer_info.client().kind.as_ref();
+        *peer_clients.entry(kind.to_string()).or_default() += 1;
+    }
+
+    let current_slot = chain.slot().map_err(beacon_chain_error)?;
+    let head_slot = chain.canonical_head.cached_head().head_slot();
+
+    let custody = if chain.spec.is_peer_das_scheduled() {
+        Some(custody_diagnostics(
+            &chain,
+            &network_globals.sampling_columns,
+            head_slot,
+        )?)
+    } else {
+        None
+    };
+
+    Ok(Diagnostics {
+        system_time_millis,
+        current_slot,
+        head_slot,
+        execution_engine,
+        disk_bytes_total: system_health.system_health.disk_bytes_total,
+        disk_bytes_free: system_health.system_health.disk_bytes_free,
+        libp2p_tcp_ports,
+        nat_open: observe_nat().is_anything_open(),
+        peer_clients,
+        custody,
+    })
+}
+
+/// Checks that every recent canonical block with blobs has all of our sampling columns on disk.
+///
+/// The head slot is skipped since its columns may still be arriving.
+fn custody_diagnostics<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    sampling_columns: &[u64],
+    head_slot: Slot,
+) -> Result<CustodyDiagnostics, warp::Rejection> {
+    let slots_per_epoch = T::EthSpec::slots_per_epoch();
+    let start_slot = head_slot.saturating_sub(slots_per_epoch);
+
+    let mut blocks_checked = 0;
+    let mut blocks_missing_columns = vec![];
+
+    for slot in (start_slot.as_u64()..head_slot.as_u64()).map(Slot::new) {
+        if !chain
+            .spec
+            .is_peer_das_enabled_for_epoch(slot.epoch(slots_per_epoch))
+        {
+            continue;
+        }
+
+        let Some(block_root) = chain
+            .block_root_at_slot(slot, WhenSlotSkipped::None)
+            .map_err(beacon_chain_error)?
+        else {
+            continue;
+        };
+
+        let has_blobs = chain
+            .get_blinded_block(&block_root)
+            .map_err(beacon_chain_error)?
+            .is_some_and(|block| {
+                block
+                    .message()
+                    .body()
+                    .blob_kzg_commitments()
+                    .is_ok_and(|commitments| !commitments.is_empty())
+            });
+        if !has_blobs {
+            continue;
+        }
+
+        blocks_checked += 1;
+        let stored_columns = chain
+            .store
+            .get_data_column_keys(block_root)
+            .map_err(BeaconChainError::DBError)
+            .map_err(beacon_chain_error)?;
+        if sampling_columns
+            .iter()
+            .any(|column| !stored_columns.contains(column))
+        {
+            blocks_missing_columns.push(block_root);
+        }
+    }
+
+    Ok(CustodyDiagnostics {
+        sampling_column_count: sampling_columns.len(),
+        blocks_checked,
+        blocks_missing_columns,
+    })
+}
@@ -0,0 +1,100 @@
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::{ForkChoiceInfo, ForkChoiceNodeInfo, ForkChoiceNodeValidity};
+use std::fmt::Write;
+
+/// Returns the block tree of fork choice, annotated with the weight, validity and proposer boost
+/// of each block.
+pub fn get_fork_choice_info<T: BeaconChainTypes>(chain: &BeaconChain<T>) -> ForkChoiceInfo {
+    let head_root = chain.canonical_head.cached_head().head_block_root();
+    let fork_choice = chain.canonical_head.fork_choice_read_lock();
+    let proto_array = fork_choice.proto_array().core_proto_array();
+
+    let proposer_boost = &proto_array.previous_proposer_boost;
+    let proposer_boost_root = (proposer_boost.score > 0).then_some(proposer_boost.root);
+
+    let nodes = proto_array
+        .nodes
+        .iter()
+        .map(|node| {
+            let validity = if node.execution_status.is_invalid() {
+                ForkChoiceNodeValidity::Invalid
+            } else if node.execution_status.is_strictly_optimistic() {
+                ForkChoiceNodeValidity::Optimistic
+            } else {
+                ForkChoiceNodeValidity::Valid
+            };
+
+            ForkChoiceNodeInfo {
+                slot: node.slot,
+                block_root: node.root,
+                parent_root: node
+                    .parent
+                    .and_then(|index| proto_array.nodes.get(index))
+                    .map(|parent| parent.root),
+                justified_epoch: node.justified_checkpoint.epoch,
+                finalized_epoch: node.finalized_checkpoint.epoch,
+                weight: node.weight,
+                validity,
+                execution_status: node.execution_status.to_string(),
+                execution_block_hash: node.execution_status.block_hash(),
+                proposer_boost: proposer_boost_root == Some(node.root),
+                is_head: node.root == head_root,
+            }
+        })
+        .collect();
+
+    ForkChoiceInfo {
+        head_root,
+        justified_checkpoint: proto_array.justified_checkpoint,
+        finalized_checkpoint: proto_array.finalized_checkpoint,
+        proposer_boost_root,
+        proposer_boost_score: proposer_boost_root.map_or(0, |_| proposer_boost.score),
+        nodes,
+    }
+}
+
+/// Render the block tree as a graphviz DOT graph, with edges from each block to its parent.
+///
+/// The head is drawn bold, the proposer boosted block is drawn with a double border, and blocks
+/// are filled according to their validity.
+pub fn fork_choice_to_dot(info: &ForkChoiceInfo) -> String {
+    let mut dot = String::new();
+    // Writing to a `String` is infallible.
+    let _ = writeln!(dot, "digraph fork_choice {{");
+    let _ = writeln!(dot, "  rankdir=BT;");
+    let _ = writeln!(dot, "  node [shape=box, style=filled];");
+
+    for node in &info.nodes {
+        let colour = match node.validity {
+            ForkChoiceNodeValidity::Valid => "palegreen",
+            ForkChoiceNodeValidity::Optimistic => "khaki",
+            ForkChoiceNodeValidity::Invalid => "lightcoral",
+        };
+        let mut style = "filled".to_string();
+        if node.is_head {
+            style.push_str(",bold");
+        }
+        let peripheries = if node.proposer_boost { 2 } else { 1 };
+        let _ = writeln!(
+            dot,
+            "  \"{:?}\" [label=\"slot {}\\n{:?}\\nweight {}\\njustified {} finalized {}\\n{}\", \
+             fillcolor={}, style=\"{}\", peripheries={}];",
+            node.block_root,
+            node.slot,
+            node.block_root,
+            node.weight,
+            node.justified_epoch,
+            node.finalized_epoch,
+            node.execution_status,
+            colour,
+            style,
+            peripheries,
+        );
+        if let Some(parent_root) = node.parent_root {
+            let _ = writeln!(dot, "  \"{:?}\" -> \"{:?}\";", node.block_root, parent_root);
+        }
+    }
+
+    let _ = writeln!(dot, "}}");
+    dot
+}
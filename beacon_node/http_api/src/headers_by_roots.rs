@@ -0,0 +1,120 @@
+use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes, WhenSlotSkipped};
+use eth2::lighthouse::HeaderByRoot;
+use eth2::types::BlockHeaderAndSignature;
+use futures::StreamExt;
+use std::sync::Arc;
+use types::{
+    AbstractExecPayload, EthSpec, ExecPayload, ExecutionBlockHash, Hash256, SignedBeaconBlock,
+};
+use warp_utils::reject::{beacon_chain_error, custom_bad_request};
+
+/// The maximum number of roots which may be requested at once.
+pub const MAX_HEADERS_BY_ROOTS: usize = 128;
+
+/// Fetch the headers of the blocks with the given `roots`, in the order requested.
+///
+/// Blocks are read using the same stream as blocks by root requests, so recent blocks are served
+/// from the caches rather than the store.
+pub async fn get_headers_by_roots<T: BeaconChainTypes>(
+    roots: Vec<Hash256>,
+    chain: Arc<BeaconChain<T>>,
+) -> Result<Vec<HeaderByRoot>, warp::Rejection> {
+    if roots.len() > MAX_HEADERS_BY_ROOTS {
+        return Err(custom_bad_request(format!(
+            "too many roots requested: {}, maximum is {}",
+            roots.len(),
+            MAX_HEADERS_BY_ROOTS
+        )));
+    }
+
+    let mut block_stream = chain
+        .get_blocks_checking_caches(roots.clone())
+        .map_err(beacon_chain_error)?;
+
+    let mut headers = Vec::with_capacity(roots.len());
+    while let Some((root, result)) = block_stream.next().await {
+        let header = match result.as_ref() {
+            Ok(Some(block)) => header_by_root(root, block, &chain)?,
+            Ok(None) => HeaderByRoot {
+                root,
+                header: None,
+                canonical: false,
+                execution_block_hash: None,
+                data_available: false,
+            },
+            // The payload isn't needed for the header, so fall back to the blinded block if it
+            // could not be loaded (e.g. because the execution layer is syncing).
+            Err(BeaconChainError::BlockHashMissingFromExecutionLayer(_)) => {
+                let block = chain
+                    .get_blinded_block(&root)
+                    .map_err(beacon_chain_error)?
+                    .ok_or_else(|| {
+                        warp_utils::reject::custom_server_error(format!(
+                            "block {root:?} missing from the store"
+                        ))
+                    })?;
+                header_by_root(root, &block, &chain)?
+            }
+            Err(e) => {
+                return Err(warp_utils::reject::custom_server_error(format!(
+                    "error loading block {root:?}: {e:?}"
+                )))
+            }
+        };
+        headers.push(header);
+    }
+
+    Ok(headers)
+}
+
+fn header_by_root<T: BeaconChainTypes, Payload: AbstractExecPayload<T::EthSpec>>(
+    root: Hash256,
+    block: &SignedBeaconBlock<T::EthSpec, Payload>,
+    chain: &BeaconChain<T>,
+) -> Result<HeaderByRoot, warp::Rejection> {
+    let canonical = chain
+        .block_root_at_slot(block.slot(), WhenSlotSkipped::None)
+        .map_err(beacon_chain_error)?
+        .map_or(false, |canonical| root == canonical);
+
+    let execution_block_hash = block
+        .message()
+        .execution_payload()
+        .ok()
+        .map(|payload| payload.block_hash())
+        .filter(|block_hash| *block_hash != ExecutionBlockHash::zero());
+
+    let num_blobs = block
+        .message()
+        .body()
+        .blob_kzg_commitments()
+        .map_or(0, |commitments| commitments.len());
+    let data_available = if num_blobs == 0 {
+        true
+    } else if chain
+        .spec
+        .is_peer_das_enabled_for_epoch(block.slot().epoch(T::EthSpec::slots_per_epoch()))
+    {
+        !chain
+            .store
+            .get_data_column_keys(root)
+            .map_err(|e| beacon_chain_error(e.into()))?
+            .is_empty()
+    } else {
+        chain
+            .store
+            .blobs_exist(&root)
+            .map_err(|e| beacon_chain_error(e.into()))?
+    };
+
+    Ok(HeaderByRoot {
+        root,
+        header: Some(BlockHeaderAndSignature {
+            message: block.message().block_header(),
+            signature: block.signature().clone().into(),
+        }),
+        canonical,
+        execution_block_hash,
+        data_available,
+    })
+}
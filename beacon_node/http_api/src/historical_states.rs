@@ -0,0 +1,194 @@
+//! A dedicated service for loading historical states requested via the HTTP API.
+//!
+//! Regenerating an old state may require replaying many blocks on top of a snapshot. Doing that
+//! work on the beacon processor allows heavy debug traffic to compete with block import, so this
+//! service runs regeneration on the blocking thread pool instead. Requests are admitted to a
+//! bounded queue and charged a cost against a fixed regeneration budget, and the most recently
+//! regenerated states are kept in a small LRU cache keyed by state root.
+use crate::metrics;
+use crate::state_id::StateId;
+use crate::{Config, ExecutionOptimistic};
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::types::StateId as CoreStateId;
+use lru::LruCache;
+use parking_lot::Mutex;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use types::non_zero_usize::new_non_zero_usize;
+use types::{BeaconState, Hash256};
+
+/// The cost charged against the regeneration budget for an unfinalized state.
+pub const HOT_STATE_COST: u32 = 1;
+/// The cost charged for a finalized state, which is usually replayed from the freezer database.
+pub const FINALIZED_STATE_COST: u32 = 2;
+
+pub const DEFAULT_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(4);
+pub const DEFAULT_QUEUE_SIZE: usize = 16;
+pub const DEFAULT_REGEN_BUDGET: u32 = 4;
+
+type Finalized = bool;
+
+pub struct HistoricalStateService<T: BeaconChainTypes> {
+    chain: Arc<BeaconChain<T>>,
+    cache: Mutex<LruCache<Hash256, BeaconState<T::EthSpec>>>,
+    /// Permits represent units of regeneration cost which may be in flight at once.
+    budget: Arc<Semaphore>,
+    max_cost: u32,
+    queue_size: usize,
+    /// The number of requests currently waiting for, or holding, regeneration budget.
+    queued: AtomicUsize,
+    /// The number of requests served from the cache without queueing.
+    cache_hits: AtomicU64,
+    /// The total regeneration cost charged to requests so far.
+    total_cost: AtomicU64,
+}
+
+/// Removes a request from the queue when dropped.
+struct QueueEntry<'a>(&'a AtomicUsize);
+
+impl Drop for QueueEntry<'_> {
+    fn drop(&mut self) {
+        let queued = self.0.fetch_sub(1, Ordering::Relaxed).saturating_sub(1);
+        metrics::set_gauge(
+            &metrics::HTTP_API_HISTORICAL_STATE_QUEUE_LENGTH,
+            queued as i64,
+        );
+    }
+}
+
+impl<T: BeaconChainTypes> HistoricalStateService<T> {
+    pub fn new(
+        chain: Arc<BeaconChain<T>>,
+        cache_size: NonZeroUsize,
+        queue_size: usize,
+        regen_budget: u32,
+    ) -> Self {
+        // A zero budget would prevent any state from ever being regenerated.
+        let max_cost = regen_budget.max(1);
+        Self {
+            chain,
+            cache: Mutex::new(LruCache::new(cache_size)),
+            budget: Arc::new(Semaphore::new(max_cost as usize)),
+            max_cost,
+            queue_size,
+            queued: AtomicUsize::new(0),
+            cache_hits: AtomicU64::new(0),
+            total_cost: AtomicU64::new(0),
+        }
+    }
+
+    /// Create a service using the limits from the HTTP API `config`.
+    pub fn from_config(chain: Arc<BeaconChain<T>>, config: &Config) -> Self {
+        Self::new(
+            chain,
+            config.historical_state_cache_size,
+            config.historical_state_queue_size,
+            config.historical_state_regen_budget,
+        )
+    }
+
+    /// The number of requests that have been served from the cache.
+    pub fn cache_hits(&self) -> u64 {
+        self.cache_hits.load(Ordering::Relaxed)
+    }
+
+    /// The total regeneration cost that has been charged against the budget.
+    pub fn total_cost(&self) -> u64 {
+        self.total_cost.load(Ordering::Relaxed)
+    }
+
+    /// Load the state identified by `state_id`, regenerating it if it is not cached.
+    ///
+    /// Returns a `503 Service Unavailable` rejection if the regeneration queue is full.
+    pub async fn state(
+        &self,
+        state_id: StateId,
+    ) -> Result<(BeaconState<T::EthSpec>, ExecutionOptimistic, Finalized), warp::Rejection> {
+        let slot_opt = match state_id.0 {
+            // The head state is always in memory, there is nothing to gain from queueing it.
+            CoreStateId::Head => {
+                let chain = self.chain.clone();
+                return warp_utils::task::blocking_task(move || state_id.state(&chain)).await;
+            }
+            CoreStateId::Slot(slot) => Some(slot),
+            _ => None,
+        };
+
+        let chain = self.chain.clone();
+        let (state_root, execution_optimistic, finalized) =
+            warp_utils::task::blocking_task(move || state_id.root(&chain)).await?;
+
+        if let Some(state) = self.get_cached(&state_root) {
+            self.cache_hits.fetch_add(1, Ordering::Relaxed);
+            metrics::inc_counter(&metrics::HTTP_API_HISTORICAL_STATE_CACHE_HITS);
+            return Ok((state, execution_optimistic, finalized));
+        }
+        metrics::inc_counter(&metrics::HTTP_API_HISTORICAL_STATE_CACHE_MISSES);
+
+        let _queue_entry = self.enter_queue()?;
+        let cost = if finalized {
+            FINALIZED_STATE_COST.min(self.max_cost)
+        } else {
+            HOT_STATE_COST
+        };
+        let _permit = self
+            .budget
+            .clone()
+            .acquire_many_owned(cost)
+            .await
+            .map_err(|_| {
+                warp_utils::reject::custom_server_error(
+                    "historical state service is shutting down".to_string(),
+                )
+            })?;
+        self.total_cost.fetch_add(cost as u64, Ordering::Relaxed);
+        metrics::inc_counter_by(&metrics::HTTP_API_HISTORICAL_STATE_COST_TOTAL, cost as u64);
+
+        // Another request may have regenerated the same state whilst this one was queued.
+        if let Some(state) = self.get_cached(&state_root) {
+            return Ok((state, execution_optimistic, finalized));
+        }
+
+        let chain = self.chain.clone();
+        let state = warp_utils::task::blocking_task(move || {
+            let _timer = metrics::start_timer(&metrics::HTTP_API_HISTORICAL_STATE_REGEN_TIMES);
+            chain
+                .get_state(&state_root, slot_opt)
+                .map_err(warp_utils::reject::beacon_chain_error)?
+                .ok_or_else(|| {
+                    warp_utils::reject::custom_not_found(format!(
+                        "beacon state at root {}",
+                        state_root
+                    ))
+                })
+        })
+        .await?;
+
+        self.cache.lock().put(state_root, state.clone());
+
+        Ok((state, execution_optimistic, finalized))
+    }
+
+    fn get_cached(&self, state_root: &Hash256) -> Option<BeaconState<T::EthSpec>> {
+        self.cache.lock().get(state_root).cloned()
+    }
+
+    fn enter_queue(&self) -> Result<QueueEntry<'_>, warp::Rejection> {
+        let queued = self.queued.fetch_add(1, Ordering::Relaxed);
+        let entry = QueueEntry(&self.queued);
+        if queued >= self.queue_size {
+            metrics::inc_counter(&metrics::HTTP_API_HISTORICAL_STATE_REJECTED);
+            return Err(warp_utils::reject::service_unavailable(format!(
+                "historical state queue is full ({} requests)",
+                self.queue_size
+            )));
+        }
+        metrics::set_gauge(
+            &metrics::HTTP_API_HISTORICAL_STATE_QUEUE_LENGTH,
+            queued as i64 + 1,
+        );
+        Ok(entry)
+    }
+}
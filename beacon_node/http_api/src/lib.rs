@@ -5,20 +5,32 @@
 //! There are also some additional, non-standard endpoints behind the `/lighthouse/` path which are
 //! used for development.
 
+pub mod api_tokens;
 mod attestation_performance;
 mod attester_duties;
+mod blobs;
 mod block_id;
 mod block_packing_efficiency;
 mod block_rewards;
 mod build_block_contents;
 mod builder_states;
+mod checkpoint_provider;
 mod database;
+mod diagnostics;
+mod fork_choice;
+mod headers_by_roots;
+pub mod historical_states;
 mod light_client;
+mod liveness;
 mod metrics;
+mod pool_attestations;
+mod pool_exits;
 mod produce_block;
 mod proposer_duties;
 mod publish_attestations;
 mod publish_blocks;
+pub mod rate_limiter;
+mod roots_by_slots;
 mod standard_block_rewards;
 mod state_id;
 mod sync_committee_rewards;
@@ -31,13 +43,18 @@ mod validator_inclusion;
 mod validators;
 mod version;
 
+use crate::api_tokens::ApiTokens;
+use crate::checkpoint_provider::CheckpointProviderCache;
+use crate::historical_states::HistoricalStateService;
 use crate::light_client::{get_light_client_bootstrap, get_light_client_updates};
 use crate::produce_block::{produce_blinded_block_v2, produce_block_v2, produce_block_v3};
+use crate::proposer_duties::ProposerLookaheadCache;
+use crate::rate_limiter::{RateLimitConfig, RateLimiter};
 use crate::version::fork_versioned_response;
 use beacon_chain::{
     attestation_verification::VerifiedAttestation, observed_operations::ObservationOutcome,
-    validator_monitor::timestamp_now, AttestationError as AttnError, BeaconChain, BeaconChainError,
-    BeaconChainTypes, WhenSlotSkipped,
+    validator_monitor::timestamp_now, AttestationError as AttnError, AvailabilityProcessingStatus,
+    BeaconChain, BeaconChainError, BeaconChainTypes, WhenSlotSkipped,
 };
 use beacon_processor::{work_reprocessing_queue::ReprocessQueueMessage, BeaconProcessorSend};
 pub use block_id::BlockId;
@@ -50,7 +67,10 @@ use eth2::types::{
     ValidatorStatus, ValidatorsRequestBody,
 };
 use eth2::{CONSENSUS_VERSION_HEADER, CONTENT_TYPE_HEADER, SSZ_CONTENT_TYPE_HEADER};
-use lighthouse_network::{types::SyncState, EnrExt, NetworkGlobals, PeerId, PubsubMessage};
+use lighthouse_network::{
+    types::{BackfillThrottle, SyncState},
+    EnrExt, Eth2Enr, NetworkGlobals, PeerId, PubsubMessage,
+};
 use lighthouse_version::version_with_platform;
 use logging::SSELoggingComponents;
 use network::{NetworkMessage, NetworkSenders, ValidatorSubscriptionMessage};
@@ -64,8 +84,10 @@ use slog::{crit, debug, error, info, warn, Logger};
 use slot_clock::SlotClock;
 use ssz::Encode;
 pub use state_id::StateId;
+use std::collections::HashMap;
 use std::future::Future;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::num::NonZeroUsize;
 use std::path::PathBuf;
 use std::pin::Pin;
 use std::sync::Arc;
@@ -92,7 +114,7 @@ use validator::pubkey_to_validator_index;
 use version::{
     add_consensus_version_header, add_ssz_content_type_header,
     execution_optimistic_finalized_fork_versioned_response, inconsistent_fork_rejection,
-    unsupported_version_rejection, V1, V2, V3,
+    ssz_snappy_response, unsupported_version_rejection, V1, V2, V3,
 };
 use warp::http::StatusCode;
 use warp::hyper::Body;
@@ -135,6 +157,7 @@ pub struct Context<T: BeaconChainTypes> {
     pub beacon_processor_reprocess_send: Option<Sender<ReprocessQueueMessage>>,
     pub eth1_service: Option<eth1::Service>,
     pub sse_logging_components: Option<SSELoggingComponents>,
+    pub historical_states: Option<Arc<HistoricalStateService<T>>>,
     pub log: Logger,
 }
 
@@ -153,6 +176,20 @@ pub struct Config {
     pub duplicate_block_status_code: StatusCode,
     pub enable_light_client_server: bool,
     pub target_peers: usize,
+    /// If set, requests must be authenticated with a token from this file.
+    pub api_tokens_file: Option<PathBuf>,
+    /// Number of regenerated historical states to keep in memory.
+    pub historical_state_cache_size: NonZeroUsize,
+    /// Maximum number of historical state requests that may wait for regeneration at once.
+    pub historical_state_queue_size: usize,
+    /// Total regeneration cost that may be in flight at once.
+    pub historical_state_regen_budget: u32,
+    /// If set, requests are rate limited per API token or IP address.
+    pub rate_limit: Option<RateLimitConfig>,
+    /// Serve provisional proposer duties two epochs ahead of the current epoch.
+    pub enable_proposer_lookahead: bool,
+    /// Keep the latest finalized state and block in memory as SSZ, for serving checkpoint sync.
+    pub checkpoint_provider_mode: bool,
 }
 
 impl Default for Config {
@@ -169,6 +206,13 @@ impl Default for Config {
             duplicate_block_status_code: StatusCode::ACCEPTED,
             enable_light_client_server: false,
             target_peers: 100,
+            api_tokens_file: None,
+            historical_state_cache_size: historical_states::DEFAULT_CACHE_SIZE,
+            historical_state_queue_size: historical_states::DEFAULT_QUEUE_SIZE,
+            historical_state_regen_budget: historical_states::DEFAULT_REGEN_BUDGET,
+            rate_limit: None,
+            enable_proposer_lookahead: false,
+            checkpoint_provider_mode: false,
         }
     }
 }
@@ -326,11 +370,41 @@ pub fn serve<T: BeaconChainTypes>(
     let config = ctx.config.clone();
     let log = ctx.log.clone();
 
+    // Load the API tokens, if authentication is enabled.
+    let api_tokens = config
+        .api_tokens_file
+        .as_ref()
+        .map(|path| {
+            let tokens = ApiTokens::load(path)?;
+            info!(
+                log,
+                "HTTP API authentication enabled";
+                "tokens_file" => %path.display(),
+                "token_count" => tokens.tokens.len(),
+            );
+            Ok::<_, String>(Arc::new(tokens))
+        })
+        .transpose()?;
+
+    let rate_limiter = config.rate_limit.clone().map(|rate_limit| {
+        info!(
+            log,
+            "HTTP API rate limiting enabled";
+            "quota" => %rate_limit.quota,
+        );
+        Arc::new(RateLimiter::new(rate_limit))
+    });
+
     // Configure CORS.
     let cors_builder = {
+        let allowed_headers = if api_tokens.is_some() {
+            vec!["Content-Type", "Authorization"]
+        } else {
+            vec!["Content-Type"]
+        };
         let builder = warp::cors()
             .allow_methods(vec!["GET", "POST"])
-            .allow_headers(vec!["Content-Type"]);
+            .allow_headers(allowed_headers);
 
         warp_utils::cors::set_builder_origins(
             builder,
@@ -403,6 +477,31 @@ pub fn serve<T: BeaconChainTypes>(
                 }
             });
 
+    // Create a `warp` filter that provides access to the historical state service.
+    let historical_state_service = ctx.historical_states.clone();
+    let historical_state_filter = warp::any()
+        .map(move || historical_state_service.clone())
+        .and_then(|service| async move {
+            match service {
+                Some(service) => Ok(service),
+                None => Err(warp_utils::reject::custom_not_found(
+                    "Beacon chain genesis has not yet been observed.".to_string(),
+                )),
+            }
+        });
+
+    // Create a `warp` filter that provides access to the checkpoint provider cache, if enabled.
+    let checkpoint_provider = ctx
+        .chain
+        .clone()
+        .filter(|_| config.checkpoint_provider_mode)
+        .map(|chain| {
+            let checkpoint_provider = Arc::new(CheckpointProviderCache::new(chain, log.clone()));
+            checkpoint_provider.spawn_refresh_task();
+            checkpoint_provider
+        });
+    let checkpoint_provider_filter = warp::any().map(move || checkpoint_provider.clone());
+
     // Create a `warp` filter that provides access to the network sender channel.
     let network_tx = ctx
         .network_senders
@@ -1609,13 +1708,24 @@ pub fn serve<T: BeaconChainTypes>(
         .clone()
         .and(warp::path::end())
         .and(warp::header::optional::<api_types::Accept>("accept"))
+        .and(checkpoint_provider_filter.clone())
         .then(
             |endpoint_version: EndpointVersion,
              block_id: BlockId,
              task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
-             accept_header: Option<api_types::Accept>| {
+             accept_header: Option<api_types::Accept>,
+             checkpoint_provider: Option<Arc<CheckpointProviderCache<T>>>| {
                 task_spawner.spawn_async_with_rejection(Priority::P1, async move {
+                    if let Some(checkpoint_provider) = checkpoint_provider {
+                        if let Some(response) = checkpoint_provider
+                            .block_response(&block_id, accept_header)
+                            .await?
+                        {
+                            return Ok(response);
+                        }
+                    }
+
                     let (block, execution_optimistic, finalized) =
                         block_id.full_block(&chain).await?;
                     let fork_name = block
@@ -1623,6 +1733,9 @@ pub fn serve<T: BeaconChainTypes>(
                         .map_err(inconsistent_fork_rejection)?;
 
                     match accept_header {
+                        Some(api_types::Accept::SszSnappy) => {
+                            ssz_snappy_response(&block.as_ssz_bytes())
+                        }
                         Some(api_types::Accept::Ssz) => Response::builder()
                             .status(200)
                             .body(block.as_ssz_bytes().into())
@@ -1732,6 +1845,9 @@ pub fn serve<T: BeaconChainTypes>(
                         .map_err(inconsistent_fork_rejection)?;
 
                     match accept_header {
+                        Some(api_types::Accept::SszSnappy) => {
+                            ssz_snappy_response(&block.as_ssz_bytes())
+                        }
                         Some(api_types::Accept::Ssz) => Response::builder()
                             .status(200)
                             .body(block.as_ssz_bytes().into())
@@ -1788,6 +1904,9 @@ pub fn serve<T: BeaconChainTypes>(
                         .map_err(inconsistent_fork_rejection)?;
 
                     match accept_header {
+                        Some(api_types::Accept::SszSnappy) => {
+                            ssz_snappy_response(&blob_sidecar_list_filtered.as_ssz_bytes())
+                        }
                         Some(api_types::Accept::Ssz) => Response::builder()
                             .status(200)
                             .body(blob_sidecar_list_filtered.as_ssz_bytes().into())
@@ -2680,6 +2799,10 @@ pub fn serve<T: BeaconChainTypes>(
      */
 
     // GET debug/beacon/states/{state_id}
+    //
+    // States are loaded by the historical state service rather than the beacon processor so
+    // that expensive regeneration of old states can't delay block import. In checkpoint provider
+    // mode, SSZ requests for the latest finalized state are served from pre-serialized bytes.
     let get_debug_beacon_states = any_version
         .and(warp::path("debug"))
         .and(warp::path("beacon"))
@@ -2691,56 +2814,75 @@ pub fn serve<T: BeaconChainTypes>(
         }))
         .and(warp::path::end())
         .and(warp::header::optional::<api_types::Accept>("accept"))
-        .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
+        .and(historical_state_filter.clone())
+        .and(checkpoint_provider_filter.clone())
         .and(log_filter.clone())
         .then(
             |endpoint_version: EndpointVersion,
              state_id: StateId,
              accept_header: Option<api_types::Accept>,
-             task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
-             log: Logger| {
-                task_spawner.blocking_response_task(Priority::P1, move || match accept_header {
-                    Some(api_types::Accept::Ssz) => {
-                        // We can ignore the optimistic status for the "fork" since it's a
-                        // specification constant that doesn't change across competing heads of the
-                        // beacon chain.
-                        let t = std::time::Instant::now();
-                        let (state, _execution_optimistic, _finalized) = state_id.state(&chain)?;
-                        let fork_name = state
-                            .fork_name(&chain.spec)
-                            .map_err(inconsistent_fork_rejection)?;
-                        let timer = metrics::start_timer(&metrics::HTTP_API_STATE_SSZ_ENCODE_TIMES);
-                        let response_bytes = state.as_ssz_bytes();
-                        drop(timer);
-                        debug!(
-                            log,
-                            "HTTP state load";
-                            "total_time_ms" => t.elapsed().as_millis(),
-                            "target_slot" => state.slot()
-                        );
-
-                        Response::builder()
-                            .status(200)
-                            .body(response_bytes.into())
-                            .map(|res: Response<Body>| add_ssz_content_type_header(res))
-                            .map(|resp: warp::reply::Response| {
-                                add_consensus_version_header(resp, fork_name)
-                            })
-                            .map_err(|e| {
-                                warp_utils::reject::custom_server_error(format!(
-                                    "failed to create response: {}",
-                                    e
-                                ))
-                            })
+             historical_states: Arc<HistoricalStateService<T>>,
+             checkpoint_provider: Option<Arc<CheckpointProviderCache<T>>>,
+             log: Logger| async move {
+                let t = std::time::Instant::now();
+                if let Some(checkpoint_provider) = checkpoint_provider {
+                    if let Some(result) = checkpoint_provider
+                        .state_response(&state_id, accept_header)
+                        .await
+                        .transpose()
+                    {
+                        return convert_rejection(result).await;
                     }
-                    _ => state_id.map_state_and_execution_optimistic_and_finalized(
-                        &chain,
-                        |state, execution_optimistic, finalized| {
-                            let fork_name = state
-                                .fork_name(&chain.spec)
-                                .map_err(inconsistent_fork_rejection)?;
+                }
+
+                let (state, execution_optimistic, finalized) =
+                    match historical_states.state(state_id).await {
+                        Ok(state) => state,
+                        Err(e) => return convert_rejection::<warp::reply::Response>(Err(e)).await,
+                    };
+
+                let result = warp_utils::task::blocking_response_task(move || {
+                    let fork_name = state
+                        .fork_name(&chain.spec)
+                        .map_err(inconsistent_fork_rejection)?;
+                    match accept_header {
+                        Some(accept @ (api_types::Accept::Ssz | api_types::Accept::SszSnappy)) => {
+                            // We can ignore the optimistic status for the "fork" since it's a
+                            // specification constant that doesn't change across competing heads
+                            // of the beacon chain.
+                            let timer =
+                                metrics::start_timer(&metrics::HTTP_API_STATE_SSZ_ENCODE_TIMES);
+                            let response_bytes = state.as_ssz_bytes();
+                            drop(timer);
+                            debug!(
+                                log,
+                                "HTTP state load";
+                                "total_time_ms" => t.elapsed().as_millis(),
+                                "target_slot" => state.slot()
+                            );
+
+                            if accept == api_types::Accept::SszSnappy {
+                                return ssz_snappy_response(&response_bytes)
+                                    .map(|resp| add_consensus_version_header(resp, fork_name));
+                            }
+
+                            Response::builder()
+                                .status(200)
+                                .body(response_bytes.into())
+                                .map(|res: Response<Body>| add_ssz_content_type_header(res))
+                                .map(|resp: warp::reply::Response| {
+                                    add_consensus_version_header(resp, fork_name)
+                                })
+                                .map_err(|e| {
+                                    warp_utils::reject::custom_server_error(format!(
+                                        "failed to create response: {}",
+                                        e
+                                    ))
+                                })
+                        }
+                        _ => {
                             let res = execution_optimistic_finalized_fork_versioned_response(
                                 endpoint_version,
                                 fork_name,
@@ -2752,9 +2894,11 @@ pub fn serve<T: BeaconChainTypes>(
                                 warp::reply::json(&res).into_response(),
                                 fork_name,
                             ))
-                        },
-                    ),
+                        }
+                    }
                 })
+                .await;
+                convert_rejection(result).await
             },
         );
 
@@ -3825,37 +3969,73 @@ pub fn serve<T: BeaconChainTypes>(
 
                         info!(
                             log,
-                            "Forwarding register validator request to connected builder";
+                            "Forwarding register validator request to connected builders";
                             "count" => filtered_registration_data.len(),
                         );
 
+                        // Each validator is registered with the builder of its builder group, or
+                        // with the default builder if it isn't in a group with its own builder.
+                        let mut registrations_by_builder = HashMap::new();
+                        if let Some(builder) = execution_layer.builder() {
+                            registrations_by_builder
+                                .insert(builder.url().full.to_string(), (builder, vec![]));
+                        }
+                        for registration in filtered_registration_data {
+                            let Some(builder) =
+                                execution_layer.builder_for(&registration.message.pubkey)
+                            else {
+                                continue;
+                            };
+                            registrations_by_builder
+                                .entry(builder.url().full.to_string())
+                                .or_insert_with(|| (builder, vec![]))
+                                .1
+                                .push(registration);
+                        }
+                        if registrations_by_builder.is_empty() {
+                            return Err(warp_utils::reject::beacon_chain_error(
+                                BeaconChainError::BuilderMissing,
+                            ));
+                        }
+
                         // It's a waste of a `BeaconProcessor` worker to just
                         // wait on a response from the builder (especially since
                         // they have frequent timeouts). Spawn a new task and
                         // send the response back to our original HTTP request
                         // task via a channel.
                         let builder_future = async move {
-                            let arc_builder = chain
-                                .execution_layer
-                                .as_ref()
-                                .ok_or(BeaconChainError::ExecutionLayerMissing)
-                                .map_err(warp_utils::reject::beacon_chain_error)?
-                                .builder();
-                            let builder = arc_builder
-                                .as_ref()
-                                .ok_or(BeaconChainError::BuilderMissing)
-                                .map_err(warp_utils::reject::beacon_chain_error)?;
-                            builder
-                                .post_builder_validators(&filtered_registration_data)
-                                .await
+                            let results = futures::future::join_all(
+                                registrations_by_builder.into_values().map(
+                                    |(builder, registrations)| async move {
+                                        builder
+                                            .post_builder_validators(&registrations)
+                                            .await
+                                            .map_err(|e| (builder, registrations.len(), e))
+                                    },
+                                ),
+                            )
+                            .await;
+
+                            let mut response = Ok(());
+                            for result in results {
+                                let Err((builder, num_registrations, e)) = result else {
+                                    continue;
+                                };
+                                warn!(
+                                    log,
+                                    "Relay error when registering validator(s)";
+                                    "builder" => %builder.url(),
+                                    "num_registrations" => num_registrations,
+                                    "error" => ?e
+                                );
+                                if response.is_ok() {
+                                    response = Err(e);
+                                }
+                            }
+
+                            response
                                 .map(|resp| warp::reply::json(&resp).into_response())
                                 .map_err(|e| {
-                                    warn!(
-                                        log,
-                                        "Relay error when registering validator(s)";
-                                        "num_registrations" => filtered_registration_data.len(),
-                                        "error" => ?e
-                                    );
                                     // Forward the HTTP status code if we are able to, otherwise fall back
                                     // to a server error.
                                     if let eth2::Error::ServerMessage(message) = e {
@@ -3988,6 +4168,25 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // POST lighthouse/liveness/range
+    let post_lighthouse_liveness_range = warp::path("lighthouse")
+        .and(warp::path("liveness"))
+        .and(warp::path("range"))
+        .and(warp::path::end())
+        .and(warp_utils::json::json())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |request_data: api_types::LivenessRangeRequestData,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    liveness::get_liveness_range(request_data, &chain)
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
     // POST lighthouse/liveness
     let post_lighthouse_liveness = warp::path("lighthouse")
         .and(warp::path("liveness"))
@@ -4053,9 +4252,9 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("health"))
         .and(warp::path::end())
         .and(task_spawner_filter.clone())
-        .and(system_info_filter)
+        .and(system_info_filter.clone())
         .and(app_start_filter)
-        .and(data_dir_filter)
+        .and(data_dir_filter.clone())
         .and(network_globals.clone())
         .then(
             |task_spawner: TaskSpawner<T::EthSpec>,
@@ -4075,6 +4274,39 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/diagnostics
+    let get_lighthouse_diagnostics = warp::path("lighthouse")
+        .and(warp::path("diagnostics"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .and(network_globals.clone())
+        .and(system_info_filter)
+        .and(data_dir_filter)
+        .then(
+            move |task_spawner: TaskSpawner<T::EthSpec>,
+                  chain: Arc<BeaconChain<T>>,
+                  network_globals: Arc<NetworkGlobals<T::EthSpec>>,
+                  sysinfo,
+                  data_dir| {
+                task_spawner.spawn_async_with_rejection(Priority::P1, async move {
+                    let app_uptime = app_start.elapsed().as_secs();
+                    diagnostics::get_diagnostics(
+                        chain,
+                        network_globals,
+                        sysinfo,
+                        data_dir,
+                        app_uptime,
+                    )
+                    .await
+                    .map(|diagnostics| {
+                        warp::reply::json(&api_types::GenericResponse::from(diagnostics))
+                            .into_response()
+                    })
+                })
+            },
+        );
+
     // GET lighthouse/ui/validator_count
     let get_lighthouse_ui_validator_count = warp::path("lighthouse")
         .and(warp::path("ui"))
@@ -4090,6 +4322,46 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // POST lighthouse/beacon/headers_by_roots
+    let post_lighthouse_beacon_headers_by_roots = warp::path("lighthouse")
+        .and(warp::path("beacon"))
+        .and(warp::path("headers_by_roots"))
+        .and(warp::path::end())
+        .and(warp_utils::json::json())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |roots: Vec<Hash256>,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.spawn_async_with_rejection(Priority::P1, async move {
+                    headers_by_roots::get_headers_by_roots(roots, chain)
+                        .await
+                        .map(api_types::GenericResponse::from)
+                        .map(|response| warp::reply::json(&response).into_response())
+                })
+            },
+        );
+
+    // POST lighthouse/beacon/roots_by_slots
+    let post_lighthouse_beacon_roots_by_slots = warp::path("lighthouse")
+        .and(warp::path("beacon"))
+        .and(warp::path("roots_by_slots"))
+        .and(warp::path::end())
+        .and(warp_utils::json::json())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |slots: Vec<Slot>,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    roots_by_slots::get_roots_by_slots(slots, &chain)
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
     // POST lighthouse/ui/validator_metrics
     let post_lighthouse_ui_validator_metrics = warp::path("lighthouse")
         .and(warp::path("ui"))
@@ -4145,16 +4417,103 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/syncing/progress
+    let get_lighthouse_syncing_progress = warp::path("lighthouse")
+        .and(warp::path("syncing"))
+        .and(warp::path("progress"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(network_globals.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    Ok(api_types::GenericResponse::from(
+                        network_globals.sync_progress.read().info(),
+                    ))
+                })
+            },
+        );
+
+    // GET lighthouse/syncing/chains
+    let get_lighthouse_syncing_chains = warp::path("lighthouse")
+        .and(warp::path("syncing"))
+        .and(warp::path("chains"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(network_globals.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    Ok(api_types::GenericResponse::from(
+                        network_globals.sync_chains.read().clone(),
+                    ))
+                })
+            },
+        );
+
+    // GET lighthouse/syncing/backfill_throttle
+    let get_lighthouse_syncing_backfill_throttle = warp::path("lighthouse")
+        .and(warp::path("syncing"))
+        .and(warp::path("backfill_throttle"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(network_globals.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    Ok(api_types::GenericResponse::from(
+                        *network_globals.backfill_throttle.read(),
+                    ))
+                })
+            },
+        );
+
+    // POST lighthouse/syncing/backfill_throttle
+    let post_lighthouse_syncing_backfill_throttle = warp::path("lighthouse")
+        .and(warp::path("syncing"))
+        .and(warp::path("backfill_throttle"))
+        .and(warp::path::end())
+        .and(warp_utils::json::json())
+        .and(task_spawner_filter.clone())
+        .and(network_globals.clone())
+        .and(log_filter.clone())
+        .then(
+            |throttle: BackfillThrottle,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>,
+             log: Logger| {
+                task_spawner.blocking_json_task(Priority::P0, move || {
+                    *network_globals.backfill_throttle.write() = throttle;
+                    info!(
+                        log,
+                        "Updated backfill throttle";
+                        "batches_per_minute" => throttle.batches_per_minute,
+                    );
+                    Ok(api_types::GenericResponse::from(throttle))
+                })
+            },
+        );
+
     // GET lighthouse/nat
     let get_lighthouse_nat = warp::path("lighthouse")
         .and(warp::path("nat"))
         .and(task_spawner_filter.clone())
+        .and(network_globals.clone())
         .and(warp::path::end())
-        .then(|task_spawner: TaskSpawner<T::EthSpec>| {
-            task_spawner.blocking_json_task(Priority::P1, move || {
-                Ok(api_types::GenericResponse::from(observe_nat()))
-            })
-        });
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let port_mapping = network_globals.port_mapping.read().clone();
+                    Ok(api_types::GenericResponse::from(
+                        observe_nat().with_port_mapping(port_mapping),
+                    ))
+                })
+            },
+        );
 
     // GET lighthouse/peers
     let get_lighthouse_peers = warp::path("lighthouse")
@@ -4179,40 +4538,148 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
-    // GET lighthouse/peers/connected
-    let get_lighthouse_peers_connected = warp::path("lighthouse")
+    // GET lighthouse/peers/first_deliveries
+    let get_lighthouse_peers_first_deliveries = warp::path("lighthouse")
         .and(warp::path("peers"))
-        .and(warp::path("connected"))
+        .and(warp::path("first_deliveries"))
         .and(warp::path::end())
         .and(task_spawner_filter.clone())
-        .and(network_globals)
+        .and(network_globals.clone())
         .then(
             |task_spawner: TaskSpawner<T::EthSpec>,
              network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
                 task_spawner.blocking_json_task(Priority::P1, move || {
-                    Ok(network_globals
-                        .peers
+                    let mut deliveries = network_globals
+                        .first_seen
                         .read()
-                        .connected_peers()
-                        .map(|(peer_id, peer_info)| eth2::lighthouse::Peer {
+                        .peer_stats()
+                        .map(|(peer_id, stats)| eth2::lighthouse::PeerFirstDeliveries {
                             peer_id: peer_id.to_string(),
-                            peer_info: peer_info.clone(),
+                            stats: stats.clone(),
                         })
-                        .collect::<Vec<_>>())
+                        .collect::<Vec<_>>();
+                    deliveries.sort_unstable_by_key(|peer| std::cmp::Reverse(peer.stats.total()));
+                    Ok(deliveries)
                 })
             },
         );
 
-    // GET lighthouse/proto_array
-    let get_lighthouse_proto_array = warp::path("lighthouse")
-        .and(warp::path("proto_array"))
+    // GET lighthouse/peers/clients
+    let get_lighthouse_peers_clients = warp::path("lighthouse")
+        .and(warp::path("peers"))
+        .and(warp::path("clients"))
         .and(warp::path::end())
         .and(task_spawner_filter.clone())
-        .and(chain_filter.clone())
+        .and(network_globals.clone())
         .then(
-            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
-                task_spawner.blocking_response_task(Priority::P1, move || {
-                    Ok::<_, warp::Rejection>(warp::reply::json(
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    Ok(network_globals.peers.read().client_diversity())
+                })
+            },
+        );
+
+    // GET lighthouse/peers/export
+    let get_lighthouse_peers_export = warp::path("lighthouse")
+        .and(warp::path("peers"))
+        .and(warp::path("export"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(network_globals.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    Ok(network_globals.peers.read().exported_peers())
+                })
+            },
+        );
+
+    // GET lighthouse/gossip/subscriptions
+    let get_lighthouse_gossip_subscriptions = warp::path("lighthouse")
+        .and(warp::path("gossip"))
+        .and(warp::path("subscriptions"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(network_globals.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    Ok(network_globals.gossip_topic_subscriptions())
+                })
+            },
+        );
+
+    // GET lighthouse/network/metadata
+    let get_lighthouse_network_metadata = warp::path("lighthouse")
+        .and(warp::path("network"))
+        .and(warp::path("metadata"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(network_globals.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let enr = network_globals.local_enr();
+                    let meta_data = network_globals.local_metadata.read();
+                    Ok(eth2::lighthouse::NetworkMetadata {
+                        peer_id: network_globals.local_peer_id().to_base58(),
+                        seq_number: *meta_data.seq_number(),
+                        attnets: format!(
+                            "0x{}",
+                            hex::encode(meta_data.attnets().clone().into_bytes()),
+                        ),
+                        syncnets: meta_data.syncnets().ok().map(|syncnets| {
+                            format!("0x{}", hex::encode(syncnets.clone().into_bytes()))
+                        }),
+                        custody_group_count: meta_data.custody_subnet_count().ok().copied(),
+                        listen_addresses: network_globals.listen_multiaddrs(),
+                        p2p_addresses: enr.multiaddr_p2p_tcp(),
+                        discovery_addresses: enr.multiaddr_p2p_udp(),
+                        enr_fork_id: enr.eth2().ok(),
+                        enr,
+                    })
+                })
+            },
+        );
+
+    // GET lighthouse/peers/connected
+    let get_lighthouse_peers_connected = warp::path("lighthouse")
+        .and(warp::path("peers"))
+        .and(warp::path("connected"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(network_globals)
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    Ok(network_globals
+                        .peers
+                        .read()
+                        .connected_peers()
+                        .map(|(peer_id, peer_info)| eth2::lighthouse::Peer {
+                            peer_id: peer_id.to_string(),
+                            peer_info: peer_info.clone(),
+                        })
+                        .collect::<Vec<_>>())
+                })
+            },
+        );
+
+    // GET lighthouse/proto_array
+    let get_lighthouse_proto_array = warp::path("lighthouse")
+        .and(warp::path("proto_array"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
+                    Ok::<_, warp::Rejection>(warp::reply::json(
                         &api_types::GenericResponseRef::from(
                             chain
                                 .canonical_head
@@ -4225,6 +4692,97 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/fork_choice
+    let get_lighthouse_fork_choice = warp::path("lighthouse")
+        .and(warp::path("fork_choice"))
+        .and(warp::path::end())
+        .and(warp::query::<eth2::lighthouse::ForkChoiceQuery>())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |query: eth2::lighthouse::ForkChoiceQuery,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
+                    let info = fork_choice::get_fork_choice_info(&chain);
+                    match query.format.unwrap_or_default() {
+                        eth2::lighthouse::ForkChoiceFormat::Json => {
+                            Ok(warp::reply::json(&api_types::GenericResponse::from(info))
+                                .into_response())
+                        }
+                        eth2::lighthouse::ForkChoiceFormat::Dot => Response::builder()
+                            .status(200)
+                            .header("Content-Type", "text/vnd.graphviz")
+                            .body(fork_choice::fork_choice_to_dot(&info).into())
+                            .map_err(|e| {
+                                warp_utils::reject::custom_server_error(format!(
+                                    "failed to create response: {}",
+                                    e
+                                ))
+                            }),
+                    }
+                })
+            },
+        );
+
+    // GET lighthouse/pool/attestations
+    let get_lighthouse_pool_attestations = warp::path("lighthouse")
+        .and(warp::path("pool"))
+        .and(warp::path("attestations"))
+        .and(warp::path::end())
+        .and(warp::query::<eth2::lighthouse::PoolAttestationsQuery>())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |query: eth2::lighthouse::PoolAttestationsQuery,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    pool_attestations::get_pool_attestations(&chain, query)
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
+    // GET lighthouse/pool/exits/status
+    let get_lighthouse_pool_exits_status = warp::path("lighthouse")
+        .and(warp::path("pool"))
+        .and(warp::path("exits"))
+        .and(warp::path("status"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    Ok(api_types::GenericResponse::from(
+                        pool_exits::get_pool_exits_status(&chain),
+                    ))
+                })
+            },
+        );
+
+    // GET lighthouse/validator_monitor/attestations/{epoch}
+    let get_lighthouse_validator_monitor_attestations = warp::path("lighthouse")
+        .and(warp::path("validator_monitor"))
+        .and(warp::path("attestations"))
+        .and(warp::path::param::<Epoch>())
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |epoch: Epoch, task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    Ok(api_types::GenericResponse::from(
+                        chain
+                            .validator_monitor
+                            .read()
+                            .get_attestation_diagnoses(epoch),
+                    ))
+                })
+            },
+        );
+
     // GET lighthouse/validator_inclusion/{epoch}/{validator_id}
     let get_lighthouse_validator_inclusion_global = warp::path("lighthouse")
         .and(warp::path("validator_inclusion"))
@@ -4262,6 +4820,53 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/validator/duties/proposer/{epoch}
+    let proposer_lookahead_cache = Arc::new(ProposerLookaheadCache::default());
+    let get_lighthouse_validator_duties_proposer = warp::path("lighthouse")
+        .and(warp::path("validator"))
+        .and(warp::path("duties"))
+        .and(warp::path("proposer"))
+        .and(warp::path::param::<Epoch>().or_else(|_| async {
+            Err(warp_utils::reject::custom_bad_request(
+                "Invalid epoch".to_string(),
+            ))
+        }))
+        .and(warp::path::end())
+        .and(not_while_syncing_filter.clone())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .and(warp::any().map(move || proposer_lookahead_cache.clone()))
+        .and(log_filter.clone())
+        .then(
+            |epoch: Epoch,
+             not_synced_filter: Result<(), Rejection>,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>,
+             cache: Arc<ProposerLookaheadCache>,
+             log: Logger| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    not_synced_filter?;
+                    proposer_duties::proposer_duties_with_lookahead(epoch, &chain, &cache, &log)
+                })
+            },
+        );
+
+    // GET lighthouse/fork_schedule
+    let get_lighthouse_fork_schedule = warp::path("lighthouse")
+        .and(warp::path("fork_schedule"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    Ok(api_types::GenericResponse::from(
+                        eth2::lighthouse::ForkSchedule::from_chain_spec::<T::EthSpec>(&chain.spec),
+                    ))
+                })
+            },
+        );
+
     // GET lighthouse/eth1/syncing
     let get_lighthouse_eth1_syncing = warp::path("lighthouse")
         .and(warp::path("eth1"))
@@ -4362,6 +4967,31 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/blobs/{block_root}
+    let get_lighthouse_blobs = warp::path("lighthouse")
+        .and(warp::path("blobs"))
+        .and(warp::path::param::<Hash256>().or_else(|_| async {
+            Err(warp_utils::reject::custom_bad_request(
+                "Invalid block root value".to_string(),
+            ))
+        }))
+        .and(warp::path::end())
+        .and(multi_key_query::<api_types::BlobIndicesQuery>())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |block_root: Hash256,
+             query_res: Result<api_types::BlobIndicesQuery, warp::Rejection>,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let query = query_res?;
+                    blobs::get_blobs_by_root(&chain, block_root, query)
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
     let database_path = warp::path("lighthouse").and(warp::path("database"));
 
     // GET lighthouse/database/info
@@ -4395,6 +5025,102 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // POST lighthouse/database/prune_blobs
+    let post_lighthouse_database_prune_blobs = database_path
+        .and(warp::path("prune_blobs"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let data_availability_boundary =
+                        chain.data_availability_boundary().ok_or_else(|| {
+                            warp_utils::reject::custom_bad_request(
+                                "blobs are not stored prior to Deneb".to_string(),
+                            )
+                        })?;
+                    chain
+                        .store_migrator
+                        .process_force_prune_blobs(data_availability_boundary);
+                    Ok("success")
+                })
+            },
+        );
+
+    // POST lighthouse/das/reconstruct/{block_root}
+    let post_lighthouse_das_reconstruct = warp::path("lighthouse")
+        .and(warp::path("das"))
+        .and(warp::path("reconstruct"))
+        .and(warp::path::param::<Hash256>().or_else(|_| async {
+            Err(warp_utils::reject::custom_bad_request(
+                "Invalid block root value".to_string(),
+            ))
+        }))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .and(network_tx_filter.clone())
+        .then(
+            |block_root: Hash256,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>,
+             network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>| {
+                task_spawner.spawn_async_with_rejection(Priority::P1, async move {
+                    // Only the columns of a block which is awaiting its data are reconstructed.
+                    // An imported block is available, so its custody columns are already held.
+                    if chain.block_is_known_to_fork_choice(&block_root) {
+                        return Err(warp_utils::reject::conflict(format!(
+                            "block {:?} has already been imported",
+                            block_root
+                        )));
+                    }
+                    if chain
+                        .data_availability_checker
+                        .cached_data_column_indexes(&block_root)
+                        .is_none()
+                    {
+                        return Err(warp_utils::reject::custom_not_found(format!(
+                            "block {:?} is not awaiting its data",
+                            block_root
+                        )));
+                    }
+
+                    // Reconstruction is performed by the network beacon processor, on the same
+                    // path as columns received over gossip and RPC, which also publishes the
+                    // reconstructed columns.
+                    let (result_tx, result_rx) = oneshot::channel();
+                    publish_network_message(
+                        &network_tx,
+                        NetworkMessage::ReconstructDataColumns {
+                            block_root,
+                            result_tx,
+                        },
+                    )?;
+                    let result = result_rx.await.map_err(|_| {
+                        warp_utils::reject::custom_server_error(
+                            "data column reconstruction was not performed".to_string(),
+                        )
+                    })?;
+
+                    let mut reconstruction = eth2::lighthouse::DataColumnReconstruction {
+                        block_root,
+                        reconstructed_columns: vec![],
+                        persisted: false,
+                    };
+                    if let Some((status, reconstructed_columns)) = result {
+                        reconstruction.reconstructed_columns = reconstructed_columns;
+                        reconstruction.persisted =
+                            matches!(status, AvailabilityProcessingStatus::Imported(_));
+                    }
+
+                    Ok::<_, warp::reject::Rejection>(
+                        warp::reply::json(&reconstruction).into_response(),
+                    )
+                })
+            },
+        );
+
     // GET lighthouse/analysis/block_rewards
     let get_lighthouse_block_rewards = warp::path("lighthouse")
         .and(warp::path("analysis"))
@@ -4427,6 +5153,24 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/analysis/block_reward/{block_id}
+    let get_lighthouse_block_reward_explanation = warp::path("lighthouse")
+        .and(warp::path("analysis"))
+        .and(warp::path("block_reward"))
+        .and(block_id_or_err)
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |block_id: BlockId,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    standard_block_rewards::explain_block_reward(chain, block_id)
+                })
+            },
+        );
+
     // GET lighthouse/analysis/attestation_performance/{index}
     let get_lighthouse_attestation_performance = warp::path("lighthouse")
         .and(warp::path("analysis"))
@@ -4460,6 +5204,51 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/relays
+    let get_lighthouse_relays = warp::path("lighthouse")
+        .and(warp::path("relays"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let relay_stats = chain
+                        .execution_layer
+                        .as_ref()
+                        .map(|execution_layer| execution_layer.relay_stats())
+                        .unwrap_or_default();
+                    Ok(api_types::GenericResponse::from(relay_stats))
+                })
+            },
+        );
+
+    // POST lighthouse/execution_layer/reload_jwt_secret
+    let post_lighthouse_execution_layer_reload_jwt_secret = warp::path("lighthouse")
+        .and(warp::path("execution_layer"))
+        .and(warp::path("reload_jwt_secret"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.spawn_async_with_rejection(Priority::P1, async move {
+                    let execution_layer = chain
+                        .execution_layer
+                        .as_ref()
+                        .ok_or(BeaconChainError::ExecutionLayerMissing)
+                        .map_err(warp_utils::reject::beacon_chain_error)?;
+                    execution_layer.reload_jwt_secret().await.map_err(|e| {
+                        warp_utils::reject::custom_server_error(format!(
+                            "unable to reload JWT secret: {:?}",
+                            e
+                        ))
+                    })?;
+                    Ok::<_, warp::reject::Rejection>(warp::reply::json(&"success").into_response())
+                })
+            },
+        );
+
     // GET lighthouse/merge_readiness
     let get_lighthouse_merge_readiness = warp::path("lighthouse")
         .and(warp::path("merge_readiness"))
@@ -4479,6 +5268,53 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/slasher/slashings
+    let get_lighthouse_slasher_slashings = warp::path("lighthouse")
+        .and(warp::path("slasher"))
+        .and(warp::path("slashings"))
+        .and(warp::path::end())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |accept_header: Option<api_types::Accept>,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
+                    let slasher = chain.slasher.as_ref().ok_or_else(|| {
+                        warp_utils::reject::custom_not_found("slasher is not enabled".to_string())
+                    })?;
+
+                    let read_error = |e| {
+                        warp_utils::reject::custom_server_error(format!(
+                            "unable to read slashings: {:?}",
+                            e
+                        ))
+                    };
+                    let bundle = eth2::lighthouse::SlashingsBundle::new(
+                        slasher.detected_attester_slashings().map_err(read_error)?,
+                        slasher.detected_proposer_slashings().map_err(read_error)?,
+                    );
+
+                    let response = match accept_header {
+                        Some(api_types::Accept::Ssz) => Response::builder()
+                            .status(200)
+                            .body(bundle.as_ssz_bytes().into())
+                            .map(|res: Response<Body>| add_ssz_content_type_header(res))
+                            .map_err(|e| {
+                                warp_utils::reject::custom_server_error(format!(
+                                    "failed to create response: {}",
+                                    e
+                                ))
+                            })?,
+                        _ => warp::reply::json(&api_types::GenericResponse::from(bundle))
+                            .into_response(),
+                    };
+                    Ok(response)
+                })
+            },
+        );
+
     let get_events = eth_v1
         .and(warp::path("events"))
         .and(warp::path::end())
@@ -4514,6 +5350,9 @@ pub fn serve<T: BeaconChainTypes>(
                                 api_types::EventTopic::ChainReorg => {
                                     event_handler.subscribe_reorgs()
                                 }
+                                api_types::EventTopic::ReorgDetail => {
+                                    event_handler.subscribe_reorg_detail()
+                                }
                                 api_types::EventTopic::ContributionAndProof => {
                                     event_handler.subscribe_contributions()
                                 }
@@ -4544,6 +5383,9 @@ pub fn serve<T: BeaconChainTypes>(
                                 api_types::EventTopic::BlockGossip => {
                                     event_handler.subscribe_block_gossip()
                                 }
+                                api_types::EventTopic::SyncProgress => {
+                                    event_handler.subscribe_sync_progress()
+                                }
                             };
 
                             receivers.push(
@@ -4631,7 +5473,14 @@ pub fn serve<T: BeaconChainTypes>(
 
     // Define the ultimate set of routes that will be provided to the server.
     // Use `uor` rather than `or` in order to simplify types (see `UnifyingOrFilter`).
+    // Note: the `rate_limit_filter` and `authorization_filter` must be applied to all routes.
+    // Requests are rate limited before they're authorized, so that rejected requests are charged.
+    let rate_limit_filter = rate_limiter::rate_limit_filter(rate_limiter, api_tokens.clone());
+    let authorization_filter = api_tokens::authorization_filter(api_tokens, log.clone());
+    let request_filter = rate_limit_filter.and(authorization_filter).boxed();
+
     let routes = warp::get()
+        .and(request_filter.clone())
         .and(
             get_beacon_genesis
                 .uor(get_beacon_state_root)
@@ -4679,19 +5528,39 @@ pub fn serve<T: BeaconChainTypes>(
                 .uor(get_lighthouse_health)
                 .uor(get_lighthouse_ui_health)
                 .uor(get_lighthouse_ui_validator_count)
+                .uor(get_lighthouse_diagnostics)
                 .uor(get_lighthouse_syncing)
+                .uor(get_lighthouse_syncing_progress)
+                .uor(get_lighthouse_syncing_chains)
+                .uor(get_lighthouse_syncing_backfill_throttle)
                 .uor(get_lighthouse_nat)
                 .uor(get_lighthouse_peers)
                 .uor(get_lighthouse_peers_connected)
+                .uor(get_lighthouse_peers_first_deliveries)
+                .uor(get_lighthouse_peers_clients)
+                .uor(get_lighthouse_peers_export)
+                .uor(get_lighthouse_gossip_subscriptions)
+                .uor(get_lighthouse_network_metadata)
                 .uor(get_lighthouse_proto_array)
+                .uor(get_lighthouse_fork_choice)
+                .uor(get_lighthouse_pool_attestations)
+                .uor(get_lighthouse_pool_exits_status)
+                .uor(get_lighthouse_validator_monitor_attestations)
                 .uor(get_lighthouse_validator_inclusion_global)
                 .uor(get_lighthouse_validator_inclusion)
+                .uor(
+                    enable(ctx.config.enable_proposer_lookahead)
+                        .and(get_lighthouse_validator_duties_proposer),
+                )
+                .uor(get_lighthouse_fork_schedule)
                 .uor(get_lighthouse_eth1_syncing)
                 .uor(get_lighthouse_eth1_block_cache)
                 .uor(get_lighthouse_eth1_deposit_cache)
                 .uor(get_lighthouse_staking)
+                .uor(get_lighthouse_blobs)
                 .uor(get_lighthouse_database_info)
                 .uor(get_lighthouse_block_rewards)
+                .uor(get_lighthouse_block_reward_explanation)
                 .uor(get_lighthouse_attestation_performance)
                 .uor(
                     enable(ctx.config.enable_light_client_server)
@@ -4711,6 +5580,8 @@ pub fn serve<T: BeaconChainTypes>(
                 )
                 .uor(get_lighthouse_block_packing_efficiency)
                 .uor(get_lighthouse_merge_readiness)
+                .uor(get_lighthouse_slasher_slashings)
+                .uor(get_lighthouse_relays)
                 .uor(get_events)
                 .uor(get_expected_withdrawals)
                 .uor(lighthouse_log_events.boxed())
@@ -4718,7 +5589,7 @@ pub fn serve<T: BeaconChainTypes>(
         )
         .boxed()
         .uor(
-            warp::post().and(
+            warp::post().and(request_filter).and(
                 warp::header::exact(CONTENT_TYPE_HEADER, SSZ_CONTENT_TYPE_HEADER)
                     // Routes which expect `application/octet-stream` go within this `and`.
                     .and(
@@ -4751,7 +5622,14 @@ pub fn serve<T: BeaconChainTypes>(
                     .uor(post_validator_register_validator)
                     .uor(post_validator_liveness_epoch)
                     .uor(post_lighthouse_liveness)
+                    .uor(post_lighthouse_liveness_range)
+                    .uor(post_lighthouse_beacon_headers_by_roots)
+                    .uor(post_lighthouse_beacon_roots_by_slots)
                     .uor(post_lighthouse_database_reconstruct)
+                    .uor(post_lighthouse_database_prune_blobs)
+                    .uor(post_lighthouse_syncing_backfill_throttle)
+                    .uor(post_lighthouse_das_reconstruct)
+                    .uor(post_lighthouse_execution_layer_reload_jwt_secret)
                     .uor(post_lighthouse_block_rewards)
                     .uor(post_lighthouse_ui_validator_metrics)
                     .uor(post_lighthouse_ui_validator_info)
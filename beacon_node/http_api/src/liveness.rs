@@ -0,0 +1,92 @@
+use crate::state_id::StateId;
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::types::{EpochLivenessData, LivenessRangeRequestData};
+use types::{Epoch, EthSpec};
+use warp_utils::reject::{beacon_chain_error, custom_bad_request};
+
+/// The maximum number of epochs which may be requested at once.
+pub const MAX_LIVENESS_RANGE_EPOCHS: u64 = 32;
+
+/// Returns the liveness of the requested validators for each epoch in the inclusive range
+/// `start_epoch..=end_epoch`.
+///
+/// A validator is live if it was seen on gossip or in a block by the observation caches, or if
+/// its attestation was included on chain. Inclusion is only read from the state for epochs which
+/// can no longer have attestations included, i.e., all but the current and previous epoch.
+pub fn get_liveness_range<T: BeaconChainTypes>(
+    request: LivenessRangeRequestData,
+    chain: &BeaconChain<T>,
+) -> Result<Vec<EpochLivenessData>, warp::Rejection> {
+    let current_epoch = chain.epoch().map_err(beacon_chain_error)?;
+    let next_epoch = current_epoch.saturating_add(Epoch::new(1));
+
+    if request.start_epoch > request.end_epoch {
+        return Err(custom_bad_request(format!(
+            "start epoch {} is after end epoch {}",
+            request.start_epoch, request.end_epoch
+        )));
+    }
+    if request.end_epoch > next_epoch {
+        return Err(custom_bad_request(format!(
+            "end epoch {} is more than one epoch after the current epoch {}",
+            request.end_epoch, current_epoch
+        )));
+    }
+    let num_epochs = request.end_epoch.as_u64() - request.start_epoch.as_u64() + 1;
+    if num_epochs > MAX_LIVENESS_RANGE_EPOCHS {
+        return Err(custom_bad_request(format!(
+            "too many epochs requested: {}, maximum is {}",
+            num_epochs, MAX_LIVENESS_RANGE_EPOCHS
+        )));
+    }
+
+    (request.start_epoch.as_u64()..=request.end_epoch.as_u64())
+        .map(Epoch::new)
+        .map(|epoch| {
+            let included = if epoch.saturating_add(Epoch::new(1)) < current_epoch {
+                included_attesters(epoch, &request.indices, chain)?
+            } else {
+                None
+            };
+
+            let live = request.indices.iter().enumerate().map(|(i, &index)| {
+                included.as_ref().map_or(false, |included| included[i])
+                    || chain.validator_seen_at_epoch(index as usize, epoch)
+            });
+
+            Ok(EpochLivenessData::new(epoch, live))
+        })
+        .collect()
+}
+
+/// Returns whether an attestation from each of `indices` for `epoch` was included on chain.
+///
+/// The participation flags are read from the state at the end of the following epoch, after which
+/// no further attestations from `epoch` may be included. Returns `None` for phase0 states, which
+/// do not record participation flags.
+fn included_attesters<T: BeaconChainTypes>(
+    epoch: Epoch,
+    indices: &[u64],
+    chain: &BeaconChain<T>,
+) -> Result<Option<Vec<bool>>, warp::Rejection> {
+    let target_slot = epoch
+        .saturating_add(Epoch::new(1))
+        .end_slot(T::EthSpec::slots_per_epoch());
+    let (state, _execution_optimistic, _finalized) =
+        StateId::from_slot(target_slot).state(chain)?;
+
+    let Ok(participation) = state.previous_epoch_participation() else {
+        return Ok(None);
+    };
+
+    Ok(Some(
+        indices
+            .iter()
+            .map(|&index| {
+                participation
+                    .get(index as usize)
+                    .map_or(false, |flags| flags.into_u8() != 0)
+            })
+            .collect(),
+    ))
+}
@@ -51,3 +51,71 @@ pub static HTTP_API_STATE_ROOT_TIMES: LazyLock<Result<Histogram>> = LazyLock::ne
         "Time to load a state root for a request",
     )
 });
+pub static HTTP_API_RATE_LIMITED_TOTAL: LazyLock<Result<IntCounter>> = LazyLock::new(|| {
+    try_create_int_counter(
+        "http_api_rate_limited_total",
+        "Count of HTTP requests rejected by the rate limiter",
+    )
+});
+
+/*
+ * Historical state service
+ */
+pub static HTTP_API_HISTORICAL_STATE_CACHE_HITS: LazyLock<Result<IntCounter>> =
+    LazyLock::new(|| {
+        try_create_int_counter(
+            "http_api_historical_state_cache_hits_total",
+            "Count of historical state requests served from the regenerated state cache",
+        )
+    });
+pub static HTTP_API_HISTORICAL_STATE_CACHE_MISSES: LazyLock<Result<IntCounter>> =
+    LazyLock::new(|| {
+        try_create_int_counter(
+            "http_api_historical_state_cache_misses_total",
+            "Count of historical state requests that required regeneration",
+        )
+    });
+pub static HTTP_API_HISTORICAL_STATE_REJECTED: LazyLock<Result<IntCounter>> = LazyLock::new(|| {
+    try_create_int_counter(
+        "http_api_historical_state_rejected_total",
+        "Count of historical state requests rejected because the queue was full",
+    )
+});
+pub static HTTP_API_HISTORICAL_STATE_QUEUE_LENGTH: LazyLock<Result<IntGauge>> =
+    LazyLock::new(|| {
+        try_create_int_gauge(
+            "http_api_historical_state_queue_length",
+            "Number of historical state requests queued or being regenerated",
+        )
+    });
+pub static HTTP_API_HISTORICAL_STATE_COST_TOTAL: LazyLock<Result<IntCounter>> =
+    LazyLock::new(|| {
+        try_create_int_counter(
+            "http_api_historical_state_cost_total",
+            "Total cost charged against the historical state regeneration budget",
+        )
+    });
+pub static HTTP_API_HISTORICAL_STATE_REGEN_TIMES: LazyLock<Result<Histogram>> =
+    LazyLock::new(|| {
+        try_create_histogram(
+            "http_api_historical_state_regen_times",
+            "Time to regenerate a historical state for an HTTP request",
+        )
+    });
+
+/*
+ * Checkpoint provider
+ */
+pub static HTTP_API_CHECKPOINT_PROVIDER_HITS: LazyLock<Result<IntCounter>> = LazyLock::new(|| {
+    try_create_int_counter(
+        "http_api_checkpoint_provider_hits_total",
+        "Count of requests served from the pre-serialized finalized state and block",
+    )
+});
+pub static HTTP_API_CHECKPOINT_PROVIDER_REFRESH_TIMES: LazyLock<Result<Histogram>> =
+    LazyLock::new(|| {
+        try_create_histogram(
+            "http_api_checkpoint_provider_refresh_times",
+            "Time to load and serialize the finalized state and block after finalization",
+        )
+    });
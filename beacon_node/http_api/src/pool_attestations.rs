@@ -0,0 +1,95 @@
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::{
+    AttestationInclusionScore, PoolAttestation, PoolAttestations, PoolAttestationsQuery,
+};
+use state_processing::epoch_cache::initialize_epoch_cache;
+use state_processing::state_advance::partial_state_advance;
+use std::cmp;
+use types::{Attestation, AttestationData, EthSpec};
+use warp_utils::reject::{beacon_chain_error, beacon_state_error, custom_server_error, not_synced};
+
+/// Returns the attestations in the operation pool which match `query`, each scored for inclusion
+/// in the next block built on the head.
+pub fn get_pool_attestations<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    query: PoolAttestationsQuery,
+) -> Result<PoolAttestations<T::EthSpec>, warp::Rejection> {
+    let current_slot = chain.slot().map_err(beacon_chain_error)?;
+    let (head_slot, head_block_root, head_state_root) = {
+        let head = chain.canonical_head.cached_head();
+        (
+            head.head_slot(),
+            head.head_block_root(),
+            head.head_state_root(),
+        )
+    };
+
+    // The next block is proposed in the current slot, unless the head already occupies it.
+    let proposal_slot = cmp::max(current_slot, head_slot + 1);
+    if proposal_slot > head_slot + T::EthSpec::slots_per_epoch() {
+        return Err(not_synced(format!(
+            "head slot {} is more than an epoch behind the current slot {}",
+            head_slot, current_slot
+        )));
+    }
+
+    // Use the head state advanced to the proposal slot, as for block production. It's usually
+    // in the state cache already thanks to the state advance timer.
+    let (state_root, mut state) = chain
+        .store
+        .get_advanced_hot_state(head_block_root, proposal_slot, head_state_root)
+        .map_err(|e| custom_server_error(format!("unable to load head state: {:?}", e)))?
+        .ok_or_else(|| custom_server_error("head state is not available".to_string()))?;
+    if state.slot() < proposal_slot {
+        // Advancing the state without state roots is sufficient to compute rewards.
+        partial_state_advance(&mut state, Some(state_root), proposal_slot, &chain.spec)
+            .map_err(|e| custom_server_error(format!("unable to advance head state: {:?}", e)))?;
+    }
+    state
+        .build_caches(&chain.spec)
+        .map_err(beacon_state_error)?;
+    initialize_epoch_cache(&mut state, &chain.spec)
+        .map_err(|e| custom_server_error(format!("unable to build epoch cache: {:?}", e)))?;
+
+    // The committee index is filtered after the attestations are retrieved, since `data.index` is
+    // always 0 after Electra.
+    let query_filter = |data: &AttestationData| query.slot.map_or(true, |slot| slot == data.slot);
+
+    let attestations = chain
+        .op_pool
+        .get_scored_attestations(&state, query_filter, &chain.spec)
+        .map_err(|e| custom_server_error(format!("unable to score attestations: {:?}", e)))?
+        .into_iter()
+        .filter(|(attestation, _)| {
+            query
+                .committee_index
+                .map_or(true, |index| is_for_committee(attestation, index))
+        })
+        .map(|(attestation, score)| PoolAttestation {
+            attesting_validators: attestation.num_set_aggregation_bits() as u64,
+            attestation,
+            inclusion_score: score.map(|score| AttestationInclusionScore {
+                fresh_validators: score.fresh_validators as u64,
+                reward: score.reward,
+            }),
+        })
+        .filter(|pool_attestation| {
+            query.aggregated.map_or(true, |aggregated| {
+                aggregated == (pool_attestation.attesting_validators > 1)
+            })
+        })
+        .collect();
+
+    Ok(PoolAttestations {
+        proposal_slot,
+        attestations,
+    })
+}
+
+/// Returns `true` if `attestation` includes votes from the committee at `committee_index`.
+fn is_for_committee<E: EthSpec>(attestation: &Attestation<E>, committee_index: u64) -> bool {
+    match attestation {
+        Attestation::Base(att) => att.data.index == committee_index,
+        Attestation::Electra(att) => att.get_committee_indices().contains(&committee_index),
+    }
+}
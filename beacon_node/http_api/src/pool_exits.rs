@@ -0,0 +1,30 @@
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::VoluntaryExitStatus;
+
+/// Returns the inclusion and broadcast status of every voluntary exit in the operation pool.
+pub fn get_pool_exits_status<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+) -> Vec<VoluntaryExitStatus> {
+    let interval = chain.config.exit_rebroadcast_epochs;
+    let exits = chain.voluntary_exits_with_inclusion();
+    let exit_broadcasts = chain.exit_broadcasts.lock();
+
+    exits
+        .into_iter()
+        .map(|(exit, included)| {
+            let validator_index = exit.message.validator_index;
+            let broadcast = exit_broadcasts.get(validator_index);
+            VoluntaryExitStatus {
+                validator_index,
+                epoch: exit.message.epoch,
+                included,
+                first_seen_epoch: broadcast.map(|b| b.first_seen_epoch),
+                last_broadcast_epoch: broadcast.map(|b| b.last_broadcast_epoch),
+                rebroadcasts: broadcast.map_or(0, |b| b.rebroadcasts),
+                next_rebroadcast_epoch: broadcast
+                    .filter(|_| !included && interval > 0)
+                    .map(|b| b.next_rebroadcast_epoch(interval)),
+            }
+        })
+        .collect()
+}
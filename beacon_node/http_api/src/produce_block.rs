@@ -69,6 +69,7 @@ pub async fn produce_block_v3<T: BeaconChainTypes>(
             query.graffiti,
             randao_verification,
             builder_boost_factor,
+            query.builder_min_bid_gwei,
             BlockProductionVersion::V3,
         )
         .await
@@ -150,6 +151,7 @@ pub async fn produce_blinded_block_v2<T: BeaconChainTypes>(
             query.graffiti.map(Into::into),
             randao_verification,
             None,
+            query.builder_min_bid_gwei,
             BlockProductionVersion::BlindedV2,
         )
         .await
@@ -181,6 +183,7 @@ pub async fn produce_block_v2<T: BeaconChainTypes>(
             query.graffiti.map(Into::into),
             randao_verification,
             None,
+            None,
             BlockProductionVersion::FullV2,
         )
         .await
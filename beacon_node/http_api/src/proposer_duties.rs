@@ -1,11 +1,14 @@
-//! Contains the handler for the `GET validator/duties/proposer/{epoch}` endpoint.
+//! Contains the handlers for the `GET validator/duties/proposer/{epoch}` and
+//! `GET lighthouse/validator/duties/proposer/{epoch}` endpoints.
 
 use crate::state_id::StateId;
 use beacon_chain::{
     beacon_proposer_cache::{compute_proposer_duties_from_head, ensure_state_is_in_epoch},
     BeaconChain, BeaconChainError, BeaconChainTypes,
 };
+use eth2::lighthouse::ProposerDutiesLookahead;
 use eth2::types::{self as api_types};
+use parking_lot::Mutex;
 use safe_arith::SafeArith;
 use slog::{debug, Logger};
 use slot_clock::SlotClock;
@@ -15,18 +18,26 @@ use types::{Epoch, EthSpec, Hash256, Slot};
 /// The struct that is returned to the requesting HTTP client.
 type ApiDuties = api_types::DutiesResponse<Vec<api_types::ProposerData>>;
 
+/// The most recently computed provisional proposer duties, see `proposer_duties_with_lookahead`.
+#[derive(Default)]
+pub struct ProposerLookaheadCache {
+    duties: Mutex<Option<ProvisionalDuties>>,
+}
+
+struct ProvisionalDuties {
+    epoch: Epoch,
+    dependent_root: Hash256,
+    execution_optimistic: bool,
+    indices: Vec<usize>,
+}
+
 /// Handles a request from the HTTP API for proposer duties.
 pub fn proposer_duties<T: BeaconChainTypes>(
     request_epoch: Epoch,
     chain: &BeaconChain<T>,
     log: &Logger,
 ) -> Result<ApiDuties, warp::reject::Rejection> {
-    let current_epoch = chain
-        .slot_clock
-        .now_or_genesis()
-        .map(|slot| slot.epoch(T::EthSpec::slots_per_epoch()))
-        .ok_or(BeaconChainError::UnableToReadSlot)
-        .map_err(warp_utils::reject::beacon_chain_error)?;
+    let current_epoch = current_epoch(chain)?;
 
     // Determine what the current epoch would be if we fast-forward our system clock by
     // `MAXIMUM_GOSSIP_CLOCK_DISPARITY`.
@@ -92,6 +103,102 @@ pub fn proposer_duties<T: BeaconChainTypes>(
     }
 }
 
+/// Handles a request from the HTTP API for proposer duties up to two epochs ahead of the current
+/// epoch.
+///
+/// Duties for epochs up to the next epoch are identical to those returned by `proposer_duties`.
+/// Duties for the epoch after next are computed by advancing the head state through empty slots,
+/// so they only remain correct if no further blocks are applied before their dependent root is
+/// decided. Such duties are marked as provisional and use the head block root as their dependent
+/// root, and are recomputed whenever the head changes.
+pub fn proposer_duties_with_lookahead<T: BeaconChainTypes>(
+    request_epoch: Epoch,
+    chain: &BeaconChain<T>,
+    cache: &ProposerLookaheadCache,
+    log: &Logger,
+) -> Result<ProposerDutiesLookahead, warp::reject::Rejection> {
+    let current_epoch = current_epoch(chain)?;
+
+    if request_epoch
+        != current_epoch
+            .safe_add(2)
+            .map_err(warp_utils::reject::arith_error)?
+    {
+        return proposer_duties(request_epoch, chain, log).map(|duties| ProposerDutiesLookahead {
+            dependent_root: duties.dependent_root,
+            execution_optimistic: duties.execution_optimistic,
+            provisional: false,
+            data: duties.data,
+        });
+    }
+
+    let head_block_root = chain.canonical_head.cached_head().head_block_root();
+    let cached = cache
+        .duties
+        .lock()
+        .as_ref()
+        .filter(|duties| duties.epoch == request_epoch && duties.dependent_root == head_block_root)
+        .map(|duties| {
+            (
+                duties.dependent_root,
+                duties.execution_optimistic,
+                duties.indices.clone(),
+            )
+        });
+
+    let (dependent_root, execution_optimistic, indices) = if let Some(cached) = cached {
+        cached
+    } else {
+        debug!(
+            log,
+            "Computing provisional proposer duties";
+            "request_epoch" => request_epoch,
+            "head_block_root" => ?head_block_root,
+        );
+        // Advancing the head state into `request_epoch` means that its dependent root is the head
+        // block root.
+        let (indices, dependent_root, execution_status, _fork) =
+            compute_proposer_duties_from_head(request_epoch, chain)
+                .map_err(warp_utils::reject::beacon_chain_error)?;
+        let execution_optimistic = execution_status.is_optimistic_or_invalid();
+
+        *cache.duties.lock() = Some(ProvisionalDuties {
+            epoch: request_epoch,
+            dependent_root,
+            execution_optimistic,
+            indices: indices.clone(),
+        });
+
+        (dependent_root, execution_optimistic, indices)
+    };
+
+    let duties = convert_to_api_response(
+        chain,
+        request_epoch,
+        dependent_root,
+        execution_optimistic,
+        indices,
+    )?;
+    Ok(ProposerDutiesLookahead {
+        dependent_root: duties.dependent_root,
+        execution_optimistic: duties.execution_optimistic,
+        provisional: true,
+        data: duties.data,
+    })
+}
+
+/// Returns the current epoch according to the slot clock.
+fn current_epoch<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+) -> Result<Epoch, warp::reject::Rejection> {
+    chain
+        .slot_clock
+        .now_or_genesis()
+        .map(|slot| slot.epoch(T::EthSpec::slots_per_epoch()))
+        .ok_or(BeaconChainError::UnableToReadSlot)
+        .map_err(warp_utils::reject::beacon_chain_error)
+}
+
 /// Attempt to load the proposer duties from the `chain.beacon_proposer_cache`, returning `Ok(None)`
 /// if there is a cache miss.
 ///
@@ -0,0 +1,298 @@
+//! Optional rate limiting for the beacon node HTTP API.
+//!
+//! Each client is assigned a bucket, identified by the name of its API token when one is
+//! presented, or by its IP address otherwise. Every request is charged a cost determined by the
+//! route it accesses (see `RouteCost`), so that expensive routes such as state reads exhaust a
+//! bucket faster than cheap head queries. Buckets are metered using GCRA, as in the RPC rate
+//! limiter.
+//!
+//! Rate limiting is applied before authorization, so requests which fail authorization are still
+//! charged to the bucket of their IP address. Requests which exceed their quota are rejected with
+//! `429 Too Many Requests` and a `Retry-After` header.
+use crate::api_tokens::ApiTokens;
+use crate::metrics;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use warp::filters::BoxedFilter;
+use warp::path::FullPath;
+use warp::Filter;
+
+/// The cost of a request to a route which doesn't match any `RouteCost`.
+pub const DEFAULT_ROUTE_COST: u64 = 1;
+
+/// How often buckets which have been fully replenished are removed.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Allow `max_tokens` tokens to be used every `replenish_all_every`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Quota {
+    pub max_tokens: u64,
+    pub replenish_all_every: Duration,
+}
+
+impl Quota {
+    pub const fn n_every(n: u64, seconds: u64) -> Self {
+        Quota {
+            max_tokens: n,
+            replenish_all_every: Duration::from_secs(seconds),
+        }
+    }
+}
+
+impl FromStr for Quota {
+    type Err = String;
+
+    /// Parses a quota in the form `<tokens>/<time_in_seconds>`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (tokens_str, time_str) = s.split_once('/').ok_or_else(|| {
+            format!(
+                "Quota {} should be defined as \"n/t\" (t in seconds). Missing '/' from quota.",
+                s
+            )
+        })?;
+        let max_tokens = tokens_str
+            .parse::<u64>()
+            .map_err(|_| format!("Failed to parse tokens from quota {}", s))?;
+        let seconds = time_str
+            .parse::<u64>()
+            .map_err(|_| format!("Failed to parse time in seconds from quota {}", s))?;
+        if max_tokens == 0 || seconds == 0 {
+            return Err(format!("Quota {} must have positive tokens and time", s));
+        }
+        Ok(Quota::n_every(max_tokens, seconds))
+    }
+}
+
+impl fmt::Display for Quota {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{}",
+            self.max_tokens,
+            self.replenish_all_every.as_secs()
+        )
+    }
+}
+
+impl TryFrom<String> for Quota {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<Quota> for String {
+    fn from(quota: Quota) -> Self {
+        quota.to_string()
+    }
+}
+
+/// The number of tokens charged for requests to routes starting with `path_prefix`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RouteCost {
+    pub path_prefix: String,
+    pub cost: u64,
+}
+
+impl FromStr for RouteCost {
+    type Err = String;
+
+    /// Parses a route cost in the form `<path_prefix>:<cost>`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (path_prefix, cost_str) = s
+            .rsplit_once(':')
+            .ok_or_else(|| format!("Missing ':' from route cost {}", s))?;
+        if !path_prefix.starts_with('/') {
+            return Err(format!("Route {} must start with '/'", path_prefix));
+        }
+        let cost = cost_str
+            .parse()
+            .map_err(|_| format!("Failed to parse cost from route cost {}", s))?;
+        Ok(RouteCost {
+            path_prefix: path_prefix.to_string(),
+            cost,
+        })
+    }
+}
+
+/// The route costs used when none are configured.
+pub fn default_route_costs() -> Vec<RouteCost> {
+    [
+        ("/eth/v1/debug/beacon/states/", 50),
+        ("/eth/v2/debug/beacon/states/", 50),
+        ("/eth/v1/beacon/states/", 5),
+        ("/eth/v1/beacon/rewards/", 10),
+        ("/lighthouse/analysis/", 20),
+        ("/lighthouse/beacon/states/", 5),
+    ]
+    .into_iter()
+    .map(|(path_prefix, cost)| RouteCost {
+        path_prefix: path_prefix.to_string(),
+        cost,
+    })
+    .collect()
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct RateLimitConfig {
+    /// The quota applied to each IP address, and to each token without its own quota.
+    pub quota: Quota,
+    pub route_costs: Vec<RouteCost>,
+}
+
+impl RateLimitConfig {
+    pub fn new(quota: Quota) -> Self {
+        Self {
+            quota,
+            route_costs: default_route_costs(),
+        }
+    }
+
+    /// Returns the cost of a request to `path`, using the longest matching route prefix.
+    pub fn cost(&self, path: &str) -> u64 {
+        self.route_costs
+            .iter()
+            .filter(|route| path.starts_with(&route.path_prefix))
+            .max_by_key(|route| route.path_prefix.len())
+            .map_or(DEFAULT_ROUTE_COST, |route| route.cost)
+    }
+}
+
+/// Identifies the bucket that a request is charged to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BucketKey {
+    Token(String),
+    Ip(IpAddr),
+}
+
+struct Buckets {
+    /// Time when the bucket will be full for each key, in nanoseconds since the limiter was
+    /// created. TAT (theoretical arrival time) from GCRA.
+    tat_per_key: HashMap<BucketKey, u64>,
+    last_prune: u64,
+}
+
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    start: Instant,
+    buckets: Mutex<Buckets>,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self {
+            config,
+            start: Instant::now(),
+            buckets: Mutex::new(Buckets {
+                tat_per_key: HashMap::new(),
+                last_prune: 0,
+            }),
+        }
+    }
+
+    pub fn config(&self) -> &RateLimitConfig {
+        &self.config
+    }
+
+    /// Charges `cost` tokens to the bucket for `key`.
+    ///
+    /// Returns the time to wait before the request would be allowed if the bucket doesn't hold
+    /// enough tokens.
+    pub fn allows(&self, key: BucketKey, quota: &Quota, cost: u64) -> Result<(), Duration> {
+        self.allows_at(self.start.elapsed(), key, quota, cost)
+    }
+
+    /// As `allows`, but at the given time since the limiter was created.
+    pub fn allows_at(
+        &self,
+        time_since_start: Duration,
+        key: BucketKey,
+        quota: &Quota,
+        cost: u64,
+    ) -> Result<(), Duration> {
+        let time_since_start = time_since_start.as_nanos() as u64;
+        let mut buckets = self.buckets.lock();
+
+        if time_since_start.saturating_sub(buckets.last_prune) >= PRUNE_INTERVAL.as_nanos() as u64 {
+            buckets
+                .tat_per_key
+                .retain(|_, tat| *tat >= time_since_start);
+            buckets.last_prune = time_since_start;
+        }
+
+        let tau = quota.replenish_all_every.as_nanos() as u64;
+        let t = tau / quota.max_tokens.max(1);
+        // A request which costs more than the entire quota consumes the whole bucket, rather than
+        // being impossible to serve.
+        let additional_time = t.saturating_mul(cost.min(quota.max_tokens));
+
+        // New keys are considered to have a full bucket.
+        let tat = buckets.tat_per_key.entry(key).or_insert(time_since_start);
+        let earliest_time = (*tat + additional_time).saturating_sub(tau);
+        if time_since_start < earliest_time {
+            Err(Duration::from_nanos(earliest_time - time_since_start))
+        } else {
+            *tat = time_since_start.max(*tat) + additional_time;
+            Ok(())
+        }
+    }
+}
+
+/// Returns a filter which rejects requests from clients that have exceeded their quota.
+///
+/// Requests presenting a known API token are charged to the token (using its own quota, if it has
+/// one), all other requests are charged to their IP address. If `limiter` is `None` rate limiting
+/// is disabled and all requests are accepted.
+pub fn rate_limit_filter(
+    limiter: Option<Arc<RateLimiter>>,
+    tokens: Option<Arc<ApiTokens>>,
+) -> BoxedFilter<()> {
+    let Some(limiter) = limiter else {
+        return warp::any().boxed();
+    };
+
+    warp::any()
+        .map(move || (limiter.clone(), tokens.clone()))
+        .and(warp::path::full())
+        .and(warp::addr::remote())
+        .and(warp::header::optional::<String>("Authorization"))
+        .and_then(
+            |(limiter, tokens): (Arc<RateLimiter>, Option<Arc<ApiTokens>>),
+             path: FullPath,
+             remote: Option<SocketAddr>,
+             header: Option<String>| async move {
+                let token = tokens
+                    .as_ref()
+                    .zip(header.as_ref())
+                    .and_then(|(tokens, header)| tokens.authenticate(header));
+                let (key, quota) = match token {
+                    Some(token) => (
+                        BucketKey::Token(token.name.clone()),
+                        token.quota.as_ref().unwrap_or(&limiter.config().quota),
+                    ),
+                    None => (
+                        BucketKey::Ip(
+                            remote.map_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED), |addr| addr.ip()),
+                        ),
+                        &limiter.config().quota,
+                    ),
+                };
+                let cost = limiter.config().cost(path.as_str());
+
+                limiter.allows(key, quota, cost).map_err(|retry_after| {
+                    metrics::inc_counter(&metrics::HTTP_API_RATE_LIMITED_TOTAL);
+                    warp_utils::reject::too_many_requests(retry_after)
+                })
+            },
+        )
+        .untuple_one()
+        .boxed()
+}
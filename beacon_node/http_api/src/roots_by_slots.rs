@@ -0,0 +1,116 @@
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::SlotRoots;
+use types::{Hash256, Slot};
+use warp_utils::reject::{beacon_chain_error, custom_bad_request};
+
+/// The maximum number of slots which may be requested at once.
+pub const MAX_ROOTS_BY_SLOTS: usize = 1024;
+
+/// The maximum distance between the lowest and highest slot of a request, which bounds the number
+/// of roots read from the iterators.
+pub const MAX_ROOTS_BY_SLOTS_SPAN: u64 = 8192;
+
+/// Resolve each of `slots` to the canonical block root and state root at that slot, in the order
+/// requested.
+///
+/// The roots are read with a single pass of the forwards iterators over the range spanned by the
+/// request. A slot without a block has the root of the most recent block prior to it and is
+/// reported as skipped.
+pub fn get_roots_by_slots<T: BeaconChainTypes>(
+    slots: Vec<Slot>,
+    chain: &BeaconChain<T>,
+) -> Result<Vec<SlotRoots>, warp::Rejection> {
+    if slots.len() > MAX_ROOTS_BY_SLOTS {
+        return Err(custom_bad_request(format!(
+            "too many slots requested: {}, maximum is {}",
+            slots.len(),
+            MAX_ROOTS_BY_SLOTS
+        )));
+    }
+
+    let (Some(&start_slot), Some(&end_slot)) = (slots.iter().min(), slots.iter().max()) else {
+        return Ok(vec![]);
+    };
+
+    if end_slot - start_slot >= MAX_ROOTS_BY_SLOTS_SPAN {
+        return Err(custom_bad_request(format!(
+            "slots span too many slots: {} to {}, maximum span is {}",
+            start_slot, end_slot, MAX_ROOTS_BY_SLOTS_SPAN
+        )));
+    }
+
+    let head_slot = chain.canonical_head.cached_head().head_slot();
+    if end_slot > head_slot {
+        return Err(custom_bad_request(format!(
+            "slot {} is after the head slot {}",
+            end_slot, head_slot
+        )));
+    }
+
+    let oldest_block_slot = chain.store.get_oldest_block_slot();
+    if start_slot < oldest_block_slot {
+        return Err(custom_bad_request(format!(
+            "slot {} is prior to the oldest known block at slot {}",
+            start_slot, oldest_block_slot
+        )));
+    }
+
+    // Also read the block root of the slot prior to the first, so that it can be determined
+    // whether the first slot was skipped.
+    let iter_start_slot = if start_slot > oldest_block_slot {
+        start_slot - 1
+    } else {
+        start_slot
+    };
+
+    let block_roots: Vec<Hash256> = chain
+        .forwards_iter_block_roots_until(iter_start_slot, end_slot)
+        .and_then(|iter| iter.map(|result| result.map(|(root, _)| root)).collect())
+        .map_err(beacon_chain_error)?;
+    let state_roots: Vec<Hash256> = chain
+        .forwards_iter_state_roots_until(iter_start_slot, end_slot)
+        .and_then(|iter| iter.map(|result| result.map(|(root, _)| root)).collect())
+        .map_err(beacon_chain_error)?;
+
+    resolve_slots(&slots, iter_start_slot, &block_roots, &state_roots)
+}
+
+/// Look up each of `slots` in the `block_roots` and `state_roots` of the consecutive slots
+/// starting at `first_slot`.
+fn resolve_slots(
+    slots: &[Slot],
+    first_slot: Slot,
+    block_roots: &[Hash256],
+    state_roots: &[Hash256],
+) -> Result<Vec<SlotRoots>, warp::Rejection> {
+    let root_at = |roots: &[Hash256], slot: Slot| -> Option<Hash256> {
+        let index = slot.as_u64().checked_sub(first_slot.as_u64())?;
+        roots.get(index as usize).copied()
+    };
+
+    slots
+        .iter()
+        .map(|&slot| {
+            let (Some(block_root), Some(state_root)) =
+                (root_at(block_roots, slot), root_at(state_roots, slot))
+            else {
+                return Err(warp_utils::reject::custom_server_error(format!(
+                    "roots missing for slot {}",
+                    slot
+                )));
+            };
+            let skipped = slot
+                .as_u64()
+                .checked_sub(1)
+                .and_then(|prev_slot| root_at(block_roots, Slot::new(prev_slot)))
+                .map_or(false, |prev_block_root| prev_block_root == block_root);
+
+            Ok(SlotRoots {
+                slot,
+                block_root,
+                state_root,
+                skipped,
+            })
+        })
+        .collect()
+}
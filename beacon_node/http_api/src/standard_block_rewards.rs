@@ -2,7 +2,7 @@ use crate::sync_committee_rewards::get_state_before_applying_block;
 use crate::BlockId;
 use crate::ExecutionOptimistic;
 use beacon_chain::{BeaconChain, BeaconChainTypes};
-use eth2::lighthouse::StandardBlockReward;
+use eth2::lighthouse::{BlockRewardExplanation, StandardBlockReward};
 use std::sync::Arc;
 use warp_utils::reject::beacon_chain_error;
 /// The difference between block_rewards and beacon_block_rewards is the later returns block
@@ -23,3 +23,18 @@ pub fn compute_beacon_block_rewards<T: BeaconChainTypes>(
 
     Ok((rewards, execution_optimistic, finalized))
 }
+
+/// Computes the rewards for the block identified by `block_id` by replaying it against its
+/// pre-state, breaking the attestation reward down by attestation.
+pub fn explain_block_reward<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    block_id: BlockId,
+) -> Result<BlockRewardExplanation, warp::Rejection> {
+    let (block, _execution_optimistic, _finalized) = block_id.blinded_block(&chain)?;
+
+    let mut state = get_state_before_applying_block(chain.clone(), &block)?;
+
+    chain
+        .explain_beacon_block_reward(block.message(), block.canonical_root(), &mut state)
+        .map_err(beacon_chain_error)
+}
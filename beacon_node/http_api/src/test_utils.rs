@@ -1,3 +1,4 @@
+use crate::historical_states::HistoricalStateService;
 use crate::{Config, Context};
 use beacon_chain::{
     test_utils::{BeaconChainHarness, BoxedMutator, Builder, EphemeralHarnessType},
@@ -231,16 +232,20 @@ pub async fn create_api_server_with_config<T: BeaconChainTypes>(
     )
     .unwrap();
 
+    // Override several config fields with defaults. If these need to be tweaked in future
+    // we could remove these overrides.
+    let config = Config {
+        enabled: true,
+        listen_port: port,
+        data_dir: std::path::PathBuf::from(DEFAULT_ROOT_DIR),
+        enable_light_client_server: true,
+        enable_proposer_lookahead: true,
+        ..http_config
+    };
+    let historical_states = Arc::new(HistoricalStateService::from_config(chain.clone(), &config));
+
     let ctx = Arc::new(Context {
-        // Override several config fields with defaults. If these need to be tweaked in future
-        // we could remove these overrides.
-        config: Config {
-            enabled: true,
-            listen_port: port,
-            data_dir: std::path::PathBuf::from(DEFAULT_ROOT_DIR),
-            enable_light_client_server: true,
-            ..http_config
-        },
+        config,
         chain: Some(chain),
         network_senders: Some(network_senders),
         network_globals: Some(network_globals),
@@ -248,6 +253,7 @@ pub async fn create_api_server_with_config<T: BeaconChainTypes>(
         beacon_processor_reprocess_send: Some(reprocess_send),
         eth1_service: Some(eth1_service),
         sse_logging_components: None,
+        historical_states: Some(historical_states),
         log,
     });
 
@@ -2,8 +2,11 @@ use crate::api_types::EndpointVersion;
 use eth2::{
     CONSENSUS_BLOCK_VALUE_HEADER, CONSENSUS_VERSION_HEADER, CONTENT_TYPE_HEADER,
     EXECUTION_PAYLOAD_BLINDED_HEADER, EXECUTION_PAYLOAD_VALUE_HEADER, SSZ_CONTENT_TYPE_HEADER,
+    SSZ_SNAPPY_CONTENT_TYPE_HEADER,
 };
 use serde::Serialize;
+use snap::write::FrameEncoder;
+use std::io::Write;
 use types::{
     fork_versioned_response::{
         ExecutionOptimisticFinalizedForkVersionedResponse, ExecutionOptimisticFinalizedMetadata,
@@ -64,6 +67,31 @@ pub fn add_ssz_content_type_header<T: Reply>(reply: T) -> Response {
     reply::with_header(reply, CONTENT_TYPE_HEADER, SSZ_CONTENT_TYPE_HEADER).into_response()
 }
 
+/// Compress `ssz_bytes` using the snappy framing format and return them in a response with the
+/// `Content-Type application/octet-stream+snappy` header.
+pub fn ssz_snappy_response(ssz_bytes: &[u8]) -> Result<Response, warp::Rejection> {
+    let mut encoder = FrameEncoder::new(Vec::with_capacity(ssz_bytes.len()));
+    encoder
+        .write_all(ssz_bytes)
+        .and_then(|()| encoder.flush())
+        .map_err(|e| {
+            warp_utils::reject::custom_server_error(format!("failed to compress response: {}", e))
+        })?;
+    let compressed = encoder.into_inner().map_err(|e| {
+        warp_utils::reject::custom_server_error(format!(
+            "failed to compress response: {}",
+            e.error()
+        ))
+    })?;
+
+    Ok(reply::with_header(
+        Response::new(compressed.into()),
+        CONTENT_TYPE_HEADER,
+        SSZ_SNAPPY_CONTENT_TYPE_HEADER,
+    )
+    .into_response())
+}
+
 /// Add the `Eth-Consensus-Version` header to a response.
 pub fn add_consensus_version_header<T: Reply>(reply: T, fork_name: ForkName) -> Response {
     reply::with_header(reply, CONSENSUS_VERSION_HEADER, fork_name.to_string()).into_response()
@@ -1,15 +1,23 @@
 //! Generic tests that make use of the (newer) `InteractiveApiTester`
 use beacon_chain::{
+    block_verification_types::RpcBlock,
     chain_config::{DisallowedReOrgOffsets, ReOrgThreshold},
+    kzg_utils::blobs_to_data_column_sidecars,
     test_utils::{AttestationStrategy, BlockStrategy, LightClientStrategy, SyncCommitteeStrategy},
-    ChainConfig,
+    AvailabilityProcessingStatus, ChainConfig, NotifyExecutionLayer,
 };
-use beacon_processor::work_reprocessing_queue::ReprocessQueueMessage;
+use beacon_processor::{work_reprocessing_queue::ReprocessQueueMessage, DuplicateCache};
+use eth2::reqwest::{Client, StatusCode};
 use eth2::types::ProduceBlockV3Response;
-use eth2::types::{DepositContractData, StateId};
+use eth2::types::{BlockId, DepositContractData, StateId};
 use execution_layer::{ForkchoiceState, PayloadAttributes};
+use http_api::api_tokens::{ApiScope, ApiTokens, API_TOKENS_FILENAME};
+use http_api::historical_states::{FINALIZED_STATE_COST, HOT_STATE_COST};
 use http_api::test_utils::InteractiveTester;
+use logging::test_logger;
+use network::{InboundRequestTracker, InvalidBlockStorage, NetworkBeaconProcessor, NetworkMessage};
 use parking_lot::Mutex;
+use slasher::{Config as SlasherConfig, Slasher};
 use slot_clock::SlotClock;
 use state_processing::{
     per_block_processing::get_expected_withdrawals, state_advance::complete_state_advance,
@@ -17,9 +25,12 @@ use state_processing::{
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
+use tempfile::tempdir;
+use tokio::sync::mpsc;
 use types::{
-    Address, Epoch, EthSpec, ExecPayload, ExecutionBlockHash, FixedBytesExtended, ForkName,
-    Hash256, MainnetEthSpec, MinimalEthSpec, ProposerPreparationData, Slot, Uint256,
+    Address, BlobSidecar, BlockImportSource, ChainSpec, DataColumnSubnetId, Epoch, EthSpec,
+    ExecPayload, ExecutionBlockHash, FixedBytesExtended, ForkName, Hash256, MainnetEthSpec,
+    MinimalEthSpec, ProposerPreparationData, Slot, Uint256,
 };
 
 type E = MainnetEthSpec;
@@ -122,6 +133,149 @@ async fn state_by_root_pruned_from_fork_choice() {
     }
 }
 
+// Test that historical state requests are rejected with a 503 once the regeneration queue is full,
+// and that the head state is still served because it never enters the queue.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn historical_state_queue_full() {
+    type E = MinimalEthSpec;
+
+    let validator_count = 24;
+    let tester = InteractiveTester::<E>::new_with_initializer_and_mutator(
+        None,
+        validator_count,
+        None,
+        None,
+        http_api::Config {
+            historical_state_queue_size: 0,
+            ..Default::default()
+        },
+    )
+    .await;
+    let harness = &tester.harness;
+    let client = &tester.client;
+
+    harness.advance_slot();
+    harness
+        .extend_chain(
+            4,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let err = client
+        .get_debug_beacon_states::<E>(StateId::Slot(Slot::new(1)))
+        .await
+        .unwrap_err();
+    assert_eq!(err.status(), Some(eth2::StatusCode::SERVICE_UNAVAILABLE));
+
+    let head = client
+        .get_debug_beacon_states::<E>(StateId::Head)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        head.data.slot(),
+        harness.chain.head_snapshot().beacon_state.slot()
+    );
+}
+
+// Test that a repeated historical state request is served from the cache without being charged
+// against the regeneration budget again.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn historical_state_cache_hit() {
+    type E = MinimalEthSpec;
+
+    let validator_count = 24;
+    let tester = InteractiveTester::<E>::new(None, validator_count).await;
+    let harness = &tester.harness;
+    let client = &tester.client;
+    let service = tester.ctx.historical_states.as_ref().unwrap();
+
+    harness.advance_slot();
+    harness
+        .extend_chain(
+            4,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let state_id = StateId::Slot(Slot::new(2));
+    let first = client
+        .get_debug_beacon_states::<E>(state_id)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    assert_eq!(service.cache_hits(), 0);
+    let cost = service.total_cost();
+    assert_eq!(cost, HOT_STATE_COST as u64);
+
+    let second = client
+        .get_debug_beacon_states::<E>(state_id)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    assert_eq!(service.cache_hits(), 1);
+    assert_eq!(service.total_cost(), cost);
+    assert_eq!(first, second);
+}
+
+// Test that finalized states are charged a higher regeneration cost than unfinalized states.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn historical_state_regen_cost() {
+    type E = MinimalEthSpec;
+
+    let validator_count = 24;
+    let tester = InteractiveTester::<E>::new(None, validator_count).await;
+    let harness = &tester.harness;
+    let client = &tester.client;
+    let service = tester.ctx.historical_states.as_ref().unwrap();
+
+    let num_blocks = E::slots_per_epoch() * 5;
+    harness.advance_slot();
+    harness
+        .extend_chain(
+            num_blocks as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+    assert!(harness.finalized_checkpoint().epoch > 0);
+
+    let finalized = client
+        .get_debug_beacon_states::<E>(StateId::Slot(Slot::new(1)))
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(finalized.metadata.finalized.unwrap());
+    assert_eq!(service.total_cost(), FINALIZED_STATE_COST as u64);
+
+    let unfinalized = client
+        .get_debug_beacon_states::<E>(StateId::Slot(Slot::new(num_blocks - 1)))
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(!unfinalized.metadata.finalized.unwrap());
+    assert_eq!(
+        service.total_cost(),
+        (FINALIZED_STATE_COST + HOT_STATE_COST) as u64
+    );
+
+    // Head states bypass the service entirely.
+    client
+        .get_debug_beacon_states::<E>(StateId::Head)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        service.total_cost(),
+        (FINALIZED_STATE_COST + HOT_STATE_COST) as u64
+    );
+}
+
 /// Data structure for tracking fork choice updates received by the mock execution layer.
 #[derive(Debug, Default)]
 struct ForkChoiceUpdates {
@@ -928,3 +1082,575 @@ async fn queue_attestations_from_http() {
 
     attestation_future.await.unwrap();
 }
+
+/// Reconstructs the missing columns of a block which is awaiting its data on a supernode.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn das_reconstruct_missing_columns() {
+    let validator_count = 32;
+    let mut spec = ForkName::Deneb.make_genesis_spec(E::default_spec());
+    spec.eip7594_fork_epoch = Some(Epoch::new(0));
+
+    let mut tester = InteractiveTester::<E>::new_with_initializer_and_mutator(
+        Some(spec),
+        validator_count,
+        None,
+        Some(Box::new(|builder| builder.import_all_data_columns(true))),
+        Default::default(),
+    )
+    .await;
+    let harness = &tester.harness;
+    let chain = &harness.chain;
+
+    // The network service isn't running, so the reconstruction requests of the HTTP API are
+    // handed to a network beacon processor by the test.
+    let (network_tx, _network_rx) = mpsc::unbounded_channel();
+    let (sync_tx, _sync_rx) = mpsc::unbounded_channel();
+    let network_beacon_processor = Arc::new(NetworkBeaconProcessor {
+        beacon_processor_send: tester.ctx.beacon_processor_send.clone().unwrap(),
+        duplicate_cache: DuplicateCache::default(),
+        chain: chain.clone(),
+        network_tx,
+        sync_tx,
+        reprocess_tx: tester.ctx.beacon_processor_reprocess_send.clone().unwrap(),
+        network_globals: tester.ctx.network_globals.clone().unwrap(),
+        invalid_block_storage: InvalidBlockStorage::Disabled,
+        inbound_requests: Arc::new(InboundRequestTracker::default()),
+        executor: harness.runtime.task_executor.clone(),
+        log: harness.logger().clone(),
+    });
+
+    harness.advance_slot();
+    let slot = harness.get_current_slot();
+
+    // Produce a block with at least one blob.
+    let state = harness.get_current_state();
+    let (block, blobs) = loop {
+        let ((block, blob_items), _) = harness.make_block(state.clone(), slot).await;
+        if let Some((_, blobs)) = blob_items.filter(|(_, blobs)| !blobs.is_empty()) {
+            break (block, blobs);
+        }
+    };
+    let block_root = block.canonical_root();
+    let blob_refs = blobs.iter().collect::<Vec<_>>();
+    let data_columns =
+        blobs_to_data_column_sidecars(&blob_refs, &block, &chain.kzg, &chain.spec).unwrap();
+
+    // Reconstruction requires a block which is awaiting its data.
+    let result = tester
+        .client
+        .post_lighthouse_das_reconstruct(block_root)
+        .await;
+    assert_eq!(
+        result.unwrap_err().status(),
+        Some(eth2::StatusCode::NOT_FOUND)
+    );
+
+    // Import the block along with half of its columns.
+    let status = chain
+        .process_block(
+            block_root,
+            RpcBlock::new_without_blobs(Some(block_root), block),
+            NotifyExecutionLayer::Yes,
+            BlockImportSource::Gossip,
+            || Ok(()),
+        )
+        .await
+        .unwrap();
+    assert!(matches!(
+        status,
+        AvailabilityProcessingStatus::MissingComponents(..)
+    ));
+
+    let num_received = data_columns.len() / 2;
+    let received_columns = data_columns[..num_received]
+        .iter()
+        .map(|column| {
+            let subnet =
+                DataColumnSubnetId::from_column_index::<E>(column.index as usize, &chain.spec);
+            chain
+                .verify_data_column_sidecar_for_gossip(column.clone(), *subnet)
+                .unwrap()
+        })
+        .collect();
+    let status = chain
+        .process_gossip_data_columns(received_columns, || Ok(()))
+        .await
+        .unwrap();
+    assert!(matches!(
+        status,
+        AvailabilityProcessingStatus::MissingComponents(..)
+    ));
+
+    // The remaining columns are reconstructed, completing the block.
+    let network_recv = &mut tester.network_rx.network_recv;
+    let serve_reconstruction = async {
+        loop {
+            match network_recv.recv().await.unwrap() {
+                NetworkMessage::ReconstructDataColumns {
+                    block_root,
+                    result_tx,
+                } => {
+                    network_beacon_processor
+                        .send_data_column_reconstruction(block_root, result_tx)
+                        .unwrap();
+                    break;
+                }
+                _ => continue,
+            }
+        }
+    };
+    let (reconstruction, ()) = tokio::join!(
+        tester.client.post_lighthouse_das_reconstruct(block_root),
+        serve_reconstruction
+    );
+    let reconstruction = reconstruction.unwrap();
+    assert_eq!(reconstruction.block_root, block_root);
+    assert_eq!(
+        reconstruction.reconstructed_columns,
+        data_columns[num_received..]
+            .iter()
+            .map(|column| column.index)
+            .collect::<Vec<_>>()
+    );
+    assert!(reconstruction.persisted);
+    assert!(chain.block_is_known_to_fork_choice(&block_root));
+
+    for column in &data_columns {
+        assert_eq!(
+            chain.get_data_column(&block_root, &column.index).unwrap(),
+            Some(column.clone())
+        );
+    }
+
+    // The block has now been imported, so there is nothing left to reconstruct.
+    let result = tester
+        .client
+        .post_lighthouse_das_reconstruct(block_root)
+        .await;
+    assert_eq!(
+        result.unwrap_err().status(),
+        Some(eth2::StatusCode::CONFLICT)
+    );
+}
+
+// Test that `/lighthouse/blobs` serves the blobs of a block which is awaiting data availability,
+// including before any of its blobs have been received.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn lighthouse_blobs_pending_availability() {
+    let validator_count = 24;
+    let spec = ForkName::Deneb.make_genesis_spec(E::default_spec());
+
+    let tester = InteractiveTester::<E>::new(Some(spec), validator_count).await;
+    let harness = &tester.harness;
+    let chain = &harness.chain;
+    let client = &tester.client;
+
+    harness.advance_slot();
+    let slot = harness.get_current_slot();
+
+    // Produce a block with at least one blob.
+    let state = harness.get_current_state();
+    let (block, (proofs, blobs)) = loop {
+        let ((block, blob_items), _) = harness.make_block(state.clone(), slot).await;
+        if let Some(blob_items) = blob_items.filter(|(_, blobs)| !blobs.is_empty()) {
+            break (block, blob_items);
+        }
+    };
+    let block_root = block.canonical_root();
+    let sidecars = blobs
+        .into_iter()
+        .zip(proofs.iter())
+        .enumerate()
+        .map(|(index, (blob, proof))| {
+            Arc::new(BlobSidecar::new(index, blob, &block, *proof).unwrap())
+        })
+        .collect::<Vec<_>>();
+
+    // A block which hasn't been seen is not found.
+    assert!(client
+        .get_lighthouse_blobs::<E>(block_root, None)
+        .await
+        .unwrap()
+        .is_none());
+
+    // Import the block without any of its blobs.
+    let status = chain
+        .process_block(
+            block_root,
+            RpcBlock::new_without_blobs(Some(block_root), block),
+            NotifyExecutionLayer::Yes,
+            BlockImportSource::Gossip,
+            || Ok(()),
+        )
+        .await
+        .unwrap();
+    assert!(matches!(
+        status,
+        AvailabilityProcessingStatus::MissingComponents(..)
+    ));
+
+    // The block is pending in the data availability checker, so it has no blobs yet rather than
+    // being unknown.
+    let result = client
+        .get_lighthouse_blobs::<E>(block_root, None)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    assert!(result.is_empty());
+
+    // Blobs received via gossip are served before the block is available.
+    let gossip_blob = chain
+        .verify_blob_sidecar_for_gossip(sidecars[0].clone(), 0)
+        .unwrap();
+    chain.process_gossip_blob(gossip_blob).await.unwrap();
+    let result = client
+        .get_lighthouse_blobs::<E>(block_root, None)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    assert_eq!(result[..], sidecars[..1]);
+
+    // Once the remaining blobs arrive the block is imported and all of its blobs are served.
+    for sidecar in &sidecars[1..] {
+        let gossip_blob = chain
+            .verify_blob_sidecar_for_gossip(sidecar.clone(), sidecar.index)
+            .unwrap();
+        chain.process_gossip_blob(gossip_blob).await.unwrap();
+    }
+    assert!(chain.block_is_known_to_fork_choice(&block_root));
+    let result = client
+        .get_lighthouse_blobs::<E>(block_root, None)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    assert_eq!(result[..], sidecars[..]);
+}
+
+/// Checks that the latest finalized state and block are served by finalized, slot and root.
+async fn assert_finalized_responses(tester: &InteractiveTester<E>, spec: &ChainSpec) {
+    let harness = &tester.harness;
+    let client = &tester.client;
+    let checkpoint = harness.finalized_checkpoint();
+    assert!(checkpoint.epoch > 0);
+    let state_slot = checkpoint.epoch.start_slot(E::slots_per_epoch());
+    let state_root = harness
+        .chain
+        .state_root_at_slot(state_slot)
+        .unwrap()
+        .unwrap();
+    let expected_state = harness
+        .chain
+        .get_state(&state_root, Some(state_slot))
+        .unwrap()
+        .unwrap();
+    for state_id in [
+        StateId::Finalized,
+        StateId::Slot(state_slot),
+        StateId::Root(state_root),
+    ] {
+        let state = client
+            .get_debug_beacon_states_ssz::<E>(state_id, spec)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(state, expected_state, "{state_id}");
+    }
+
+    let expected_block = harness
+        .chain
+        .get_block(&checkpoint.root)
+        .await
+        .unwrap()
+        .unwrap();
+    for block_id in [
+        BlockId::Finalized,
+        BlockId::Slot(expected_block.slot()),
+        BlockId::Root(checkpoint.root),
+    ] {
+        let block = client
+            .get_beacon_blocks_ssz::<E>(block_id, spec)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(block, expected_block, "{block_id}");
+    }
+}
+
+/// Serves the finalized state and block from the checkpoint provider cache, and falls through to
+/// the database for other states.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn checkpoint_provider_serves_finalized_state() {
+    let validator_count = 32;
+    let spec = ForkName::latest().make_genesis_spec(E::default_spec());
+    let slots_per_epoch = E::slots_per_epoch() as usize;
+
+    let tester = InteractiveTester::<E>::new_with_initializer_and_mutator(
+        Some(spec.clone()),
+        validator_count,
+        None,
+        None,
+        http_api::Config {
+            checkpoint_provider_mode: true,
+            ..Default::default()
+        },
+    )
+    .await;
+    let harness = &tester.harness;
+    let client = &tester.client;
+
+    harness.advance_slot();
+    harness
+        .extend_chain(
+            slots_per_epoch * 5,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+    assert_finalized_responses(&tester, &spec).await;
+
+    // Other states are loaded from the database.
+    let expected_state = harness.get_current_state();
+    let state = client
+        .get_debug_beacon_states_ssz::<E>(StateId::Slot(expected_state.slot()), &spec)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(state, expected_state);
+
+    // The cache follows the finalized checkpoint.
+    let finalized_epoch = harness.finalized_checkpoint().epoch;
+    harness
+        .extend_chain(
+            slots_per_epoch * 2,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+    assert!(harness.finalized_checkpoint().epoch > finalized_epoch);
+    assert_finalized_responses(&tester, &spec).await;
+}
+
+// Liveness for epochs whose attestations can no longer be included is read from the
+// participation flags of historical states, once the observation caches have been pruned.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn liveness_range_reads_historical_participation() {
+    type E = MinimalEthSpec;
+
+    let validator_count = 24;
+    let spec = ForkName::latest().make_genesis_spec(E::default_spec());
+    let tester = InteractiveTester::<E>::new(Some(spec), validator_count).await;
+    let harness = &tester.harness;
+
+    // Every validator except validator 1 attests.
+    harness.advance_slot();
+    harness
+        .extend_chain(
+            8 * E::slots_per_epoch() as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::SomeValidators((0..validator_count).filter(|i| *i != 1).collect()),
+        )
+        .await;
+
+    let (start_epoch, end_epoch) = (Epoch::new(1), Epoch::new(2));
+    for epoch in [start_epoch, end_epoch] {
+        for index in [0, 1] {
+            assert!(!harness.chain.validator_seen_at_epoch(index, epoch));
+        }
+    }
+
+    let result = tester
+        .client
+        .post_lighthouse_liveness_range(&[0, 1], start_epoch, end_epoch)
+        .await
+        .unwrap()
+        .data;
+
+    assert_eq!(
+        result.iter().map(|data| data.epoch).collect::<Vec<_>>(),
+        vec![start_epoch, end_epoch]
+    );
+    for data in result {
+        assert!(data.is_live(0));
+        assert!(!data.is_live(1));
+    }
+}
+
+// Slashings found by the slasher remain exportable after they are harvested for block production.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn slasher_slashings_are_exported() {
+    let validator_count = 24;
+    let spec = ForkName::latest().make_genesis_spec(E::default_spec());
+
+    let slasher_dir = tempdir().unwrap();
+    let slasher = Arc::new(
+        Slasher::open(
+            SlasherConfig::new(slasher_dir.path().into()),
+            Arc::new(spec.clone()),
+            test_logger(),
+        )
+        .unwrap(),
+    );
+
+    let inner_slasher = slasher.clone();
+    let tester = InteractiveTester::<E>::new_with_initializer_and_mutator(
+        Some(spec),
+        validator_count,
+        None,
+        Some(Box::new(move |builder| builder.slasher(inner_slasher))),
+        Default::default(),
+    )
+    .await;
+    let harness = &tester.harness;
+
+    // Show the slasher two conflicting blocks from the same proposer.
+    harness.advance_slot();
+    let state = harness.get_current_state();
+    let ((block1, _), _) = harness.make_block(state.clone(), Slot::new(1)).await;
+    let ((block2, _), _) = harness.make_block(state, Slot::new(1)).await;
+    slasher.accept_block_header(block1.signed_block_header());
+    slasher.accept_block_header(block2.signed_block_header());
+    slasher.process_queued(Epoch::new(0)).unwrap();
+    assert_eq!(slasher.get_proposer_slashings().len(), 1);
+
+    let bundle = tester
+        .client
+        .get_lighthouse_slasher_slashings::<E>()
+        .await
+        .unwrap()
+        .data;
+    assert_eq!(bundle.num_attester_slashings(), 0);
+    assert_eq!(bundle.proposer_slashings.len(), 1);
+    assert_eq!(
+        bundle.proposer_slashings[0]
+            .signed_header_1
+            .message
+            .proposer_index,
+        block1.message().proposer_index()
+    );
+
+    drop(tester);
+    drop(slasher);
+    slasher_dir.close().unwrap();
+}
+
+// Test that the pool attestations endpoint filters Electra attestations by their committee bits,
+// since `data.index` is always 0 after Electra.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn lighthouse_pool_attestations_committee_index_electra() {
+    type E = MinimalEthSpec;
+    let spec = ForkName::Electra.make_genesis_spec(E::default_spec());
+    // Enough validators for multiple committees per slot.
+    let validator_count = 64;
+    let tester = InteractiveTester::<E>::new(Some(spec), validator_count).await;
+    let harness = &tester.harness;
+
+    harness.advance_slot();
+    harness
+        .extend_chain(
+            4,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+    harness.advance_slot();
+
+    let head_state = harness.chain.head_beacon_state_cloned();
+    let attestation_slot = head_state.slot();
+    let committees_per_slot = head_state
+        .get_committee_count_at_slot(attestation_slot)
+        .unwrap();
+    assert!(committees_per_slot > 1);
+
+    let all_attestations = tester
+        .client
+        .get_lighthouse_pool_attestations::<E>(Some(attestation_slot), None, None)
+        .await
+        .unwrap()
+        .data
+        .attestations;
+
+    for committee_index in 0..committees_per_slot {
+        let attestations = tester
+            .client
+            .get_lighthouse_pool_attestations::<E>(
+                Some(attestation_slot),
+                Some(committee_index),
+                None,
+            )
+            .await
+            .unwrap()
+            .data
+            .attestations;
+        assert!(!attestations.is_empty());
+        assert!(attestations.iter().all(|pool_attestation| {
+            pool_attestation.attestation.committee_index() == Some(committee_index)
+                && pool_attestation.inclusion_score.is_some()
+        }));
+        assert_eq!(
+            attestations.len(),
+            all_attestations
+                .iter()
+                .filter(|pool_attestation| {
+                    pool_attestation.attestation.committee_index() == Some(committee_index)
+                })
+                .count()
+        );
+    }
+}
+
+/// Tokens may only access the routes permitted by their scope.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn api_token_scopes() {
+    let dir = tempdir().unwrap();
+    let tokens_file = dir.path().join(API_TOKENS_FILENAME);
+    let mut tokens = ApiTokens::default();
+    let read_only = tokens
+        .add("explorer".to_string(), ApiScope::ReadOnly)
+        .unwrap();
+    let admin = tokens.add("admin".to_string(), ApiScope::Admin).unwrap();
+    tokens.save(&tokens_file).unwrap();
+
+    let config = http_api::Config {
+        api_tokens_file: Some(tokens_file),
+        ..http_api::Config::default()
+    };
+    let tester =
+        InteractiveTester::<E>::new_with_initializer_and_mutator(None, 32, None, None, config)
+            .await;
+    let base_url = tester.client.as_ref().trim_end_matches('/').to_string();
+    let client = Client::new();
+    let status = |method: &str, path: &str, token: &str| {
+        let request = match method {
+            "GET" => client.get(format!("{base_url}{path}")),
+            _ => client.post(format!("{base_url}{path}")),
+        };
+        async move {
+            request
+                .header("Authorization", format!("Bearer {token}"))
+                .send()
+                .await
+                .unwrap()
+                .status()
+        }
+    };
+
+    assert_eq!(
+        status("GET", "/eth/v1/node/version", &read_only).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        status("POST", "/lighthouse/database/reconstruct", &read_only).await,
+        StatusCode::FORBIDDEN
+    );
+    assert_eq!(
+        status("GET", "/eth/v1/node/version", "unknown").await,
+        StatusCode::FORBIDDEN
+    );
+    assert_ne!(
+        status("POST", "/lighthouse/database/reconstruct", &admin).await,
+        StatusCode::FORBIDDEN
+    );
+}
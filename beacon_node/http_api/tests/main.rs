@@ -3,5 +3,6 @@
 pub mod broadcast_validation_tests;
 pub mod fork_tests;
 pub mod interactive_tests;
+pub mod rate_limiter_tests;
 pub mod status_tests;
 pub mod tests;
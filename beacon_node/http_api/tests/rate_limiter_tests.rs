@@ -0,0 +1,133 @@
+//! Tests for HTTP API rate limiting.
+use eth2::reqwest::{header::RETRY_AFTER, Client, StatusCode};
+use http_api::api_tokens::{ApiScope, ApiTokens, API_TOKENS_FILENAME};
+use http_api::rate_limiter::{BucketKey, Quota, RateLimitConfig, RateLimiter, RouteCost};
+use http_api::{test_utils::InteractiveTester, Config};
+use std::net::{IpAddr, Ipv4Addr};
+use std::time::Duration;
+use types::MainnetEthSpec;
+
+type E = MainnetEthSpec;
+
+#[test]
+fn parse_quota() {
+    assert_eq!("100/10".parse::<Quota>(), Ok(Quota::n_every(100, 10)));
+    assert_eq!(Quota::n_every(100, 10).to_string(), "100/10");
+    assert!("100".parse::<Quota>().is_err());
+    assert!("0/10".parse::<Quota>().is_err());
+    assert!("100/0".parse::<Quota>().is_err());
+}
+
+#[test]
+fn route_costs() {
+    let mut config = RateLimitConfig::new(Quota::n_every(100, 10));
+    assert_eq!(config.cost("/eth/v1/node/version"), 1);
+    assert_eq!(config.cost("/eth/v1/beacon/states/head/root"), 5);
+    assert_eq!(config.cost("/eth/v2/debug/beacon/states/head"), 50);
+
+    config.route_costs = vec![
+        "/eth/v1/beacon/:2".parse::<RouteCost>().unwrap(),
+        "/eth/v1/beacon/states/:7".parse::<RouteCost>().unwrap(),
+    ];
+    assert_eq!(config.cost("/eth/v1/beacon/genesis"), 2);
+    assert_eq!(config.cost("/eth/v1/beacon/states/head/root"), 7);
+    assert_eq!(config.cost("/eth/v2/debug/beacon/states/head"), 1);
+    assert!("eth/v1/beacon/:2".parse::<RouteCost>().is_err());
+}
+
+#[test]
+fn limiter_charges_costs_per_key() {
+    let quota = Quota::n_every(4, 2);
+    let limiter = RateLimiter::new(RateLimitConfig::new(quota.clone()));
+    let ip = BucketKey::Ip(IpAddr::V4(Ipv4Addr::LOCALHOST));
+    let token = BucketKey::Token("vc".to_string());
+    let t = Duration::from_secs(10);
+
+    // The bucket starts full.
+    assert!(limiter.allows_at(t, ip.clone(), &quota, 3).is_ok());
+    assert!(limiter.allows_at(t, ip.clone(), &quota, 1).is_ok());
+    // One token is replenished every 500ms.
+    assert_eq!(
+        limiter.allows_at(t, ip.clone(), &quota, 1),
+        Err(Duration::from_millis(500))
+    );
+    // Other keys have their own bucket.
+    assert!(limiter.allows_at(t, token, &quota, 4).is_ok());
+
+    let t = t + Duration::from_millis(500);
+    assert!(limiter.allows_at(t, ip.clone(), &quota, 1).is_ok());
+
+    // Requests costing more than the quota drain the whole bucket.
+    let t = t + Duration::from_secs(3);
+    assert!(limiter.allows_at(t, ip.clone(), &quota, 10).is_ok());
+    assert_eq!(
+        limiter.allows_at(t, ip, &quota, 1),
+        Err(Duration::from_millis(500))
+    );
+}
+
+#[test]
+fn token_quota_from_file() {
+    let tokens: ApiTokens = serde_json::from_str(
+        r#"{
+            "tokens": [
+                { "name": "vc", "scope": "validator-duties", "token_hash": "0x00" },
+                { "name": "explorer", "scope": "read-only", "token_hash": "0x01", "quota": "1000/60" }
+            ]
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(tokens.tokens[0].quota, None);
+    assert_eq!(tokens.tokens[1].quota, Some(Quota::n_every(1000, 60)));
+}
+
+/// Requests are rate limited by IP address before they're authorized, so requests with an unknown
+/// token use up the quota of their address.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn unauthorized_requests_are_rate_limited() {
+    let dir = tempfile::tempdir().unwrap();
+    let tokens_file = dir.path().join(API_TOKENS_FILENAME);
+    let mut tokens = ApiTokens::default();
+    let secret = tokens
+        .add("vc".to_string(), ApiScope::ValidatorDuties)
+        .unwrap();
+    tokens.save(&tokens_file).unwrap();
+
+    let config = Config {
+        api_tokens_file: Some(tokens_file),
+        rate_limit: Some(RateLimitConfig::new(Quota::n_every(2, 60))),
+        ..Config::default()
+    };
+    let tester =
+        InteractiveTester::<E>::new_with_initializer_and_mutator(None, 32, None, None, config)
+            .await;
+    let url = format!(
+        "{}/eth/v1/node/version",
+        tester.client.as_ref().trim_end_matches('/')
+    );
+    let client = Client::new();
+    let get = |token: &str| {
+        client
+            .get(&url)
+            .header("Authorization", format!("Bearer {token}"))
+            .send()
+    };
+
+    for _ in 0..2 {
+        assert_eq!(
+            get("unknown").await.unwrap().status(),
+            StatusCode::FORBIDDEN
+        );
+    }
+    let response = get("unknown").await.unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    let retry_after = response.headers()[RETRY_AFTER]
+        .to_str()
+        .unwrap()
+        .parse::<u64>()
+        .unwrap();
+    assert!((1..=30).contains(&retry_after), "{retry_after}");
+
+    // Requests with a known token are charged to the token instead.
+    assert_eq!(get(&secret).await.unwrap().status(), StatusCode::OK);
+}
@@ -4,6 +4,7 @@ use beacon_chain::{
     BeaconChain, ChainConfig, StateSkipConfig, WhenSlotSkipped,
 };
 use eth2::{
+    lighthouse::VoluntaryExitStatus,
     mixin::{RequestAccept, ResponseForkName, ResponseOptional},
     reqwest::RequestBuilder,
     types::{
@@ -13,6 +14,7 @@ use eth2::{
     Error::ServerMessage,
     StatusCode, Timeouts,
 };
+use execution_layer::builder_groups::BuilderGroupConfig;
 use execution_layer::test_utils::{
     MockBuilder, Operation, DEFAULT_BUILDER_PAYLOAD_VALUE_WEI, DEFAULT_MOCK_EL_PAYLOAD_VALUE_WEI,
 };
@@ -22,7 +24,10 @@ use http_api::{
     test_utils::{create_api_server, ApiServer},
     BlockId, StateId,
 };
-use lighthouse_network::{types::SyncState, Enr, EnrExt, PeerId};
+use lighthouse_network::{
+    types::{BackfillThrottle, SyncState},
+    Enr, EnrExt, Eth2Enr, PeerId,
+};
 use logging::test_logger;
 use network::NetworkReceivers;
 use proto_array::ExecutionStatus;
@@ -32,13 +37,15 @@ use state_processing::per_block_processing::get_expected_withdrawals;
 use state_processing::per_slot_processing;
 use state_processing::state_advance::partial_state_advance;
 use std::convert::TryInto;
+use std::io::Read;
 use std::sync::Arc;
 use tokio::time::Duration;
 use tree_hash::TreeHash;
 use types::application_domain::ApplicationDomain;
 use types::{
-    attestation::AttestationBase, AggregateSignature, BitList, Domain, EthSpec, ExecutionBlockHash,
-    Hash256, Keypair, MainnetEthSpec, RelativeEpoch, SelectionProof, SignedRoot, Slot,
+    attestation::AttestationBase, AggregateSignature, BeaconState, BitList, Domain, EthSpec,
+    ExecutionBlockHash, Hash256, Keypair, MainnetEthSpec, RelativeEpoch, SelectionProof,
+    SignedRoot, Slot,
 };
 
 type E = MainnetEthSpec;
@@ -1532,6 +1539,7 @@ impl ApiTester {
             let builders: Vec<fn(RequestBuilder) -> RequestBuilder> = vec![
                 |b| b,
                 |b| b.accept(Accept::Ssz),
+                |b| b.accept(Accept::SszSnappy),
                 |b| b.accept(Accept::Json),
                 |b| b.accept(Accept::Any),
             ];
@@ -1667,6 +1675,56 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_lighthouse_blobs(self) -> Self {
+        let block_id = BlockId(CoreBlockId::Finalized);
+        let (block_root, _, _) = block_id.root(&self.chain).unwrap();
+        let expected = self
+            .client
+            .get_blobs::<E>(CoreBlockId::Root(block_root), None)
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+
+        let result = self
+            .client
+            .get_lighthouse_blobs::<E>(block_root, None)
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        assert_eq!(result, expected);
+
+        // Filter by index, ignoring duplicates.
+        let result = self
+            .client
+            .get_lighthouse_blobs::<E>(block_root, Some(&[0, 0][..]))
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        assert_eq!(result.len(), expected.len().min(1));
+        assert!(result.iter().all(|blob| blob.index == 0));
+
+        // Indices beyond the maximum number of blobs per block are invalid.
+        let max_blobs_per_block = E::max_blobs_per_block() as u64;
+        assert!(self
+            .client
+            .get_lighthouse_blobs::<E>(block_root, Some(&[max_blobs_per_block][..]))
+            .await
+            .is_err());
+
+        // Unknown blocks are not found.
+        assert!(self
+            .client
+            .get_lighthouse_blobs::<E>(Hash256::repeat_byte(0xaa), None)
+            .await
+            .unwrap()
+            .is_none());
+
+        self
+    }
+
     /// Test fetching of blob sidecars that are not available in the database due to pruning.
     ///
     /// If `zero_blobs` is false, test a block with >0 blobs, which should be unavailable.
@@ -2478,14 +2536,35 @@ impl ApiTester {
                 .unwrap();
             assert_eq!(result_ssz, expected, "{:?}", state_id);
 
-            // Check that version headers are provided.
             let url = self
                 .client
                 .get_debug_beacon_states_path(state_id.0)
                 .unwrap();
 
+            // Check snappy-compressed SSZ API.
+            let result_ssz_snappy = self
+                .client
+                .get_bytes_opt_accept_header(
+                    url.clone(),
+                    Accept::SszSnappy,
+                    Duration::from_secs(SECONDS_PER_SLOT),
+                )
+                .await
+                .unwrap()
+                .map(|compressed| {
+                    let mut bytes = vec![];
+                    snap::read::FrameDecoder::new(compressed.as_slice())
+                        .read_to_end(&mut bytes)
+                        .unwrap();
+                    BeaconState::from_ssz_bytes(&bytes, &self.chain.spec).unwrap()
+                });
+            assert_eq!(result_ssz_snappy, expected, "{:?}", state_id);
+
+            // Check that version headers are provided.
             let builders: Vec<fn(RequestBuilder) -> RequestBuilder> =
-                vec![|b| b, |b| b.accept(Accept::Ssz)];
+                vec![|b| b, |b| b.accept(Accept::Ssz), |b| {
+                    b.accept(Accept::SszSnappy)
+                }];
 
             for req_builder in builders {
                 let raw_res = self
@@ -2850,6 +2929,75 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_lighthouse_validator_duties_proposer(self) -> Self {
+        let current_epoch = self.chain.epoch().unwrap();
+
+        // Epochs within the standard lookahead match the standard endpoint.
+        for epoch in [current_epoch, current_epoch + 1] {
+            let expected = self
+                .client
+                .get_validator_duties_proposer(epoch)
+                .await
+                .unwrap();
+            let result = self
+                .client
+                .get_lighthouse_validator_duties_proposer(epoch)
+                .await
+                .unwrap();
+
+            assert!(!result.provisional);
+            assert_eq!(result.dependent_root, expected.dependent_root);
+            assert_eq!(result.data, expected.data);
+        }
+
+        // The epoch after next is computed from the head state advanced through empty slots.
+        let epoch = current_epoch + 2;
+        let result = self
+            .client
+            .get_lighthouse_validator_duties_proposer(epoch)
+            .await
+            .unwrap();
+
+        let mut state = self.chain.head_beacon_state_cloned();
+        let state_root = state.update_tree_hash_cache().unwrap();
+        partial_state_advance(
+            &mut state,
+            Some(state_root),
+            epoch.start_slot(E::slots_per_epoch()),
+            &self.chain.spec,
+        )
+        .unwrap();
+        state
+            .build_committee_cache(RelativeEpoch::Current, &self.chain.spec)
+            .unwrap();
+
+        let expected_duties = epoch
+            .slot_iter(E::slots_per_epoch())
+            .map(|slot| {
+                let index = state
+                    .get_beacon_proposer_index(slot, &self.chain.spec)
+                    .unwrap();
+                ProposerData {
+                    pubkey: state.validators().get(index).unwrap().pubkey,
+                    validator_index: index as u64,
+                    slot,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        assert!(result.provisional);
+        assert_eq!(result.dependent_root, self.chain.head_beacon_block_root());
+        assert_eq!(result.data, expected_duties);
+
+        // Epochs beyond the lookahead are still rejected.
+        self.client
+            .get_lighthouse_validator_duties_proposer(current_epoch + 3)
+            .await
+            .unwrap_err();
+
+        self
+    }
+
     pub async fn test_get_validator_duties_early(self) -> Self {
         let current_epoch = self.chain.epoch().unwrap();
         let next_epoch = current_epoch + 1;
@@ -4090,6 +4238,159 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_payload_v3_builder_min_bid_above_bid(self) -> Self {
+        let slot = self.chain.slot().unwrap();
+        let epoch = self.chain.epoch().unwrap();
+
+        let (proposer_index, randao_reveal) = self.get_test_randao(slot, epoch).await;
+
+        // The maximum boost factor would select the builder payload, were it not below the
+        // minimum bid.
+        let (payload_type, metadata) = self
+            .client
+            .get_validator_blocks_v3_modular::<E>(
+                slot,
+                &randao_reveal,
+                None,
+                SkipRandaoVerification::No,
+                Some(u64::MAX),
+                Some(u64::MAX),
+            )
+            .await
+            .unwrap();
+        Self::check_block_v3_metadata(&metadata, &payload_type);
+
+        let payload: FullPayload<E> = match payload_type.data {
+            ProduceBlockV3Response::Full(payload) => {
+                payload.block().body().execution_payload().unwrap().into()
+            }
+            ProduceBlockV3Response::Blinded(_) => panic!("Expecting a full payload"),
+        };
+
+        let expected_fee_recipient = Address::from_low_u64_be(proposer_index as u64);
+        assert_eq!(payload.fee_recipient(), expected_fee_recipient);
+        assert_eq!(payload.gas_limit(), 16_384);
+
+        self
+    }
+
+    /// Start another mock builder, returning it along with its URL.
+    fn spawn_mock_builder(&self) -> (Arc<MockBuilder<E>>, SensitiveUrl) {
+        let mock_el = self.harness.mock_execution_layer.as_ref().unwrap();
+        let mock_el_url = SensitiveUrl::parse(mock_el.server.url().as_str()).unwrap();
+        let beacon_url = SensitiveUrl::parse(self.client.as_ref()).unwrap();
+
+        let (mock_builder, (addr, mock_builder_server)) = MockBuilder::new_for_testing(
+            mock_el_url,
+            beacon_url,
+            self.chain.spec.clone(),
+            self.harness.runtime.task_executor.clone(),
+        );
+        self.harness.runtime.task_executor.spawn(
+            async move { mock_builder_server.await },
+            "group_mock_builder_server",
+        );
+
+        let port = addr.port();
+        let url = SensitiveUrl::parse(format!("http://127.0.0.1:{port}").as_str()).unwrap();
+        (Arc::new(mock_builder), url)
+    }
+
+    fn set_builder_groups(&self, builder_groups: Vec<BuilderGroupConfig>) {
+        self.chain
+            .execution_layer
+            .as_ref()
+            .unwrap()
+            .set_builder_groups(builder_groups, None, None)
+            .unwrap();
+    }
+
+    pub async fn test_builder_group_registration_and_payload(self) -> Self {
+        let slot = self.chain.slot().unwrap();
+        let epoch = self.chain.epoch().unwrap();
+
+        let (proposer_index, randao_reveal) = self.get_test_randao(slot, epoch).await;
+        let proposer_pubkey = self.validator_keypairs()[proposer_index].pk.compress();
+
+        // Put the proposer in a group with its own builder, which is distinguishable from the
+        // default builder by the gas limit of its bids.
+        let (group_builder, group_builder_url) = self.spawn_mock_builder();
+        group_builder.add_operation(Operation::GasLimit(30_000_000));
+        self.set_builder_groups(vec![BuilderGroupConfig {
+            name: "group".to_string(),
+            builder_url: Some(group_builder_url),
+            min_bid_gwei: None,
+            validators: vec![proposer_pubkey],
+        }]);
+
+        let tester = self.test_post_validator_register_validator().await;
+
+        // Each validator is registered with its own builder only.
+        let default_builder = tester.mock_builder.as_ref().unwrap();
+        for keypair in tester.validator_keypairs() {
+            let pubkey = keypair.pk.compress();
+            let in_group = pubkey == proposer_pubkey;
+            assert_eq!(group_builder.is_registered(&pubkey), in_group);
+            assert_eq!(default_builder.is_registered(&pubkey), !in_group);
+        }
+
+        let (payload_type, metadata) = tester
+            .client
+            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, None)
+            .await
+            .unwrap();
+        Self::check_block_v3_metadata(&metadata, &payload_type);
+
+        let payload: BlindedPayload<E> = match payload_type.data {
+            ProduceBlockV3Response::Blinded(payload) => {
+                payload.body().execution_payload().unwrap().into()
+            }
+            ProduceBlockV3Response::Full(_) => panic!("Expecting a blinded payload"),
+        };
+
+        // The payload was built by the group's builder.
+        assert_eq!(payload.gas_limit(), 30_000_000);
+
+        tester
+    }
+
+    pub async fn test_builder_group_min_bid_above_bid(self) -> Self {
+        let slot = self.chain.slot().unwrap();
+        let epoch = self.chain.epoch().unwrap();
+
+        let (proposer_index, randao_reveal) = self.get_test_randao(slot, epoch).await;
+
+        // The proposer's group uses the default builder, but won't accept any of its bids.
+        self.set_builder_groups(vec![BuilderGroupConfig {
+            name: "group".to_string(),
+            builder_url: None,
+            min_bid_gwei: Some(u64::MAX),
+            validators: vec![self.validator_keypairs()[proposer_index].pk.compress()],
+        }]);
+
+        // The maximum boost factor would select the builder payload, were it not below the
+        // group's minimum bid.
+        let (payload_type, metadata) = self
+            .client
+            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, Some(u64::MAX))
+            .await
+            .unwrap();
+        Self::check_block_v3_metadata(&metadata, &payload_type);
+
+        let payload: FullPayload<E> = match payload_type.data {
+            ProduceBlockV3Response::Full(payload) => {
+                payload.block().body().execution_payload().unwrap().into()
+            }
+            ProduceBlockV3Response::Blinded(_) => panic!("Expecting a full payload"),
+        };
+
+        let expected_fee_recipient = Address::from_low_u64_be(proposer_index as u64);
+        assert_eq!(payload.fee_recipient(), expected_fee_recipient);
+        assert_eq!(payload.gas_limit(), 16_384);
+
+        self
+    }
+
     pub async fn test_payload_respects_registration(self) -> Self {
         let slot = self.chain.slot().unwrap();
         let epoch = self.chain.epoch().unwrap();
@@ -4361,6 +4662,49 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_lighthouse_relays_deprioritizes_invalid_bids(self) -> Self {
+        let relays = self.client.get_lighthouse_relays().await.unwrap().data;
+        assert_eq!(relays.len(), 1);
+        assert_eq!(relays[0].requests, 0);
+        assert_eq!(relays[0].mean_latency_ms, None);
+
+        // Mutate parent hash so that every bid is invalid.
+        self.mock_builder
+            .as_ref()
+            .unwrap()
+            .add_operation(Operation::ParentHash(Hash256::repeat_byte(0x42)));
+
+        let slot = self.chain.slot().unwrap();
+        let epoch = self.chain.epoch().unwrap();
+        let (_, randao_reveal) = self.get_test_randao(slot, epoch).await;
+
+        for _ in 0..execution_layer::relay_scores::RELAY_FAILURE_THRESHOLD {
+            self.client
+                .get_validator_blinded_blocks::<E>(slot, &randao_reveal, None)
+                .await
+                .unwrap();
+        }
+
+        let relays = self.client.get_lighthouse_relays().await.unwrap().data;
+        let relay = &relays[0];
+        assert_eq!(relay.requests, 2);
+        assert_eq!(relay.invalid_bids, 2);
+        assert_eq!(relay.valid_bids, 0);
+        assert_eq!(relay.consecutive_failures, 2);
+        assert!(relay.mean_latency_ms.is_some());
+        assert_eq!(relay.deprioritized_until, Some(slot + 1));
+
+        // Whilst deprioritized the relay isn't requested, and the payload is built locally.
+        self.client
+            .get_validator_blinded_blocks::<E>(slot, &randao_reveal, None)
+            .await
+            .unwrap();
+        let relays = self.client.get_lighthouse_relays().await.unwrap().data;
+        assert_eq!(relays[0].requests, 2);
+
+        self
+    }
+
     pub async fn test_payload_rejects_invalid_prev_randao(self) -> Self {
         let invalid_prev_randao =
             "0x4242424242424242424242424242424242424242424242424242424242424242"
@@ -5467,54 +5811,468 @@ impl ApiTester {
         self
     }
 
-    pub async fn test_get_lighthouse_proto_array(self) -> Self {
-        self.client.get_lighthouse_proto_array().await.unwrap();
+    pub async fn test_get_lighthouse_syncing_progress(self) -> Self {
+        // The notifier does not run in tests, so no sync has been observed.
+        let progress = self
+            .client
+            .get_lighthouse_syncing_progress()
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(progress.forward, None);
+        assert_eq!(progress.backfill, None);
 
         self
     }
 
-    pub async fn test_get_lighthouse_validator_inclusion_global(self) -> Self {
-        let epoch = self.chain.epoch().unwrap() - 1;
-        self.client
-            .get_lighthouse_validator_inclusion_global(epoch)
+    pub async fn test_lighthouse_syncing_backfill_throttle(self) -> Self {
+        let unlimited = BackfillThrottle {
+            batches_per_minute: None,
+        };
+        let throttle = BackfillThrottle {
+            batches_per_minute: Some(6),
+        };
+
+        let initial = self
+            .client
+            .get_lighthouse_syncing_backfill_throttle()
             .await
-            .unwrap();
+            .unwrap()
+            .data;
+        assert_eq!(initial, unlimited);
 
-        self
-    }
+        let updated = self
+            .client
+            .post_lighthouse_syncing_backfill_throttle(&throttle)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(updated, throttle);
+        assert_eq!(
+            self.client
+                .get_lighthouse_syncing_backfill_throttle()
+                .await
+                .unwrap()
+                .data,
+            throttle
+        );
 
-    pub async fn test_get_lighthouse_validator_inclusion(self) -> Self {
-        let epoch = self.chain.epoch().unwrap() - 1;
+        // Restore the default for subsequent tests.
         self.client
-            .get_lighthouse_validator_inclusion(epoch, ValidatorId::Index(0))
+            .post_lighthouse_syncing_backfill_throttle(&unlimited)
             .await
             .unwrap();
 
         self
     }
 
-    pub async fn test_get_lighthouse_eth1_syncing(self) -> Self {
-        self.client.get_lighthouse_eth1_syncing().await.unwrap();
+    pub async fn test_get_lighthouse_syncing_chains(self) -> Self {
+        // The tester's node is synced, so range sync has no chains.
+        let chains = self
+            .client
+            .get_lighthouse_syncing_chains()
+            .await
+            .unwrap()
+            .data;
+        assert!(chains.is_empty());
 
         self
     }
 
-    pub async fn test_get_lighthouse_eth1_block_cache(self) -> Self {
-        let blocks = self.client.get_lighthouse_eth1_block_cache().await.unwrap();
-
-        assert!(blocks.data.is_empty());
-
-        self
-    }
+    pub async fn test_post_lighthouse_beacon_headers_by_roots(self) -> Self {
+        let head = self.chain.head_snapshot();
+        let head_root = head.beacon_block_root;
+        let unknown_root = Hash256::repeat_byte(0x42);
 
-    pub async fn test_get_lighthouse_eth1_deposit_cache(self) -> Self {
-        let deposits = self
+        let headers = self
             .client
-            .get_lighthouse_eth1_deposit_cache()
+            .post_lighthouse_beacon_headers_by_roots(&[head_root, unknown_root])
             .await
-            .unwrap();
+            .unwrap()
+            .data;
+        assert_eq!(headers.len(), 2);
 
-        assert!(deposits.data.is_empty());
+        let head_header = &headers[0];
+        assert_eq!(head_header.root, head_root);
+        assert!(head_header.canonical);
+        assert!(head_header.data_available);
+        assert_eq!(
+            head_header.header.as_ref().unwrap().message,
+            head.beacon_block.message().block_header()
+        );
+
+        let unknown_header = &headers[1];
+        assert_eq!(unknown_header.root, unknown_root);
+        assert_eq!(unknown_header.header, None);
+        assert!(!unknown_header.canonical);
+
+        // Requests for too many roots are rejected.
+        let roots = vec![head_root; 129];
+        assert!(self
+            .client
+            .post_lighthouse_beacon_headers_by_roots(&roots)
+            .await
+            .is_err());
+
+        self
+    }
+
+    pub async fn test_post_lighthouse_beacon_roots_by_slots(self) -> Self {
+        let head_slot = self.chain.head_snapshot().beacon_block.slot();
+        let slots = (0..=head_slot.as_u64())
+            .rev()
+            .map(Slot::new)
+            .collect::<Vec<_>>();
+
+        let roots = self
+            .client
+            .post_lighthouse_beacon_roots_by_slots(&slots)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(roots.len(), slots.len());
+
+        for (slot, roots) in slots.iter().zip(&roots) {
+            assert_eq!(roots.slot, *slot);
+            assert_eq!(
+                Some(roots.block_root),
+                self.chain
+                    .block_root_at_slot(*slot, WhenSlotSkipped::Prev)
+                    .unwrap()
+            );
+            assert_eq!(
+                Some(roots.state_root),
+                self.chain.state_root_at_slot(*slot).unwrap()
+            );
+            assert_eq!(
+                roots.skipped,
+                self.chain
+                    .block_root_at_slot(*slot, WhenSlotSkipped::None)
+                    .unwrap()
+                    .is_none()
+            );
+        }
+
+        // Slots after the head cannot be resolved.
+        assert!(self
+            .client
+            .post_lighthouse_beacon_roots_by_slots(&[head_slot + 1])
+            .await
+            .is_err());
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_peers_first_deliveries(self) -> Self {
+        // No gossip is received by the test node.
+        let deliveries = self
+            .client
+            .get_lighthouse_peers_first_deliveries()
+            .await
+            .unwrap();
+        assert!(deliveries.is_empty());
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_peers_clients(self) -> Self {
+        let diversity = self.client.get_lighthouse_peers_clients().await.unwrap();
+        assert_eq!(
+            diversity
+                .clients
+                .values()
+                .map(|client| client.peers)
+                .sum::<usize>(),
+            diversity.connected_peers
+        );
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_peers_export(self) -> Self {
+        // The ENR of the only peer, which connected to the test node, is unknown.
+        let peers = self.client.get_lighthouse_peers_export().await.unwrap();
+        assert!(peers.is_empty());
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_gossip_subscriptions(self) -> Self {
+        let subscriptions = self
+            .client
+            .get_lighthouse_gossip_subscriptions()
+            .await
+            .unwrap();
+        let expected = self
+            .ctx
+            .network_globals
+            .as_ref()
+            .unwrap()
+            .gossip_topic_subscriptions();
+        assert_eq!(subscriptions, expected);
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_network_metadata(self) -> Self {
+        let result = self.client.get_lighthouse_network_metadata().await.unwrap();
+
+        let network_globals = self.ctx.network_globals.as_ref().unwrap();
+        assert_eq!(result.peer_id, self.local_enr.peer_id().to_string());
+        assert_eq!(result.enr, self.local_enr);
+        assert_eq!(result.seq_number, 0);
+        assert_eq!(result.attnets, "0x0000000000000000");
+        assert_eq!(result.syncnets, Some("0x00".to_string()));
+        assert_eq!(result.listen_addresses, network_globals.listen_multiaddrs());
+        assert_eq!(result.p2p_addresses, self.local_enr.multiaddr_p2p_tcp());
+        assert_eq!(
+            result.discovery_addresses,
+            self.local_enr.multiaddr_p2p_udp()
+        );
+        assert_eq!(result.enr_fork_id, self.local_enr.eth2().ok());
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_fork_schedule(self) -> Self {
+        let result = self
+            .client
+            .get_lighthouse_fork_schedule()
+            .await
+            .unwrap()
+            .data;
+
+        let expected_forks: Vec<Fork> = ForkName::list_all()
+            .into_iter()
+            .filter_map(|fork| self.chain.spec.fork_for_name(fork))
+            .collect();
+        let forks: Vec<Fork> = result.forks.iter().map(|entry| entry.fork).collect();
+        assert_eq!(forks, expected_forks);
+        assert_eq!(result.blob_schedule, self.chain.spec.blob_schedule::<E>());
+        assert_eq!(
+            result.data_columns.is_some(),
+            self.chain.spec.is_peer_das_scheduled()
+        );
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_diagnostics(self) -> Self {
+        let result = self.client.get_lighthouse_diagnostics().await.unwrap().data;
+
+        assert_eq!(result.current_slot, self.chain.slot().unwrap());
+        assert_eq!(
+            result.head_slot,
+            self.chain.canonical_head.cached_head().head_slot()
+        );
+        assert_eq!(
+            result.execution_engine.configured,
+            self.chain.execution_layer.is_some()
+        );
+        assert!(!result.execution_engine.auth_failed);
+
+        let network_globals = self.ctx.network_globals.as_ref().unwrap();
+        let listen_addrs = network_globals.config.listen_addrs();
+        let expected_ports = listen_addrs
+            .v4()
+            .map(|addr| addr.tcp_port)
+            .into_iter()
+            .chain(listen_addrs.v6().map(|addr| addr.tcp_port))
+            .collect::<Vec<_>>();
+        assert_eq!(result.libp2p_tcp_ports, expected_ports);
+        assert_eq!(
+            result.peer_clients.values().sum::<usize>(),
+            network_globals.connected_peers()
+        );
+        assert_eq!(
+            result.custody.is_some(),
+            self.chain.spec.is_peer_das_scheduled()
+        );
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_proto_array(self) -> Self {
+        self.client.get_lighthouse_proto_array().await.unwrap();
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_fork_choice(self) -> Self {
+        let info = self.client.get_lighthouse_fork_choice().await.unwrap().data;
+
+        let head_root = self.chain.head_beacon_block_root();
+        assert_eq!(info.head_root, head_root);
+        {
+            let fork_choice = self.chain.canonical_head.fork_choice_read_lock();
+            let proto_array = fork_choice.proto_array().core_proto_array();
+            assert_eq!(info.nodes.len(), proto_array.nodes.len());
+            for (node, proto_node) in info.nodes.iter().zip(&proto_array.nodes) {
+                assert_eq!(node.block_root, proto_node.root);
+                assert_eq!(node.weight, proto_node.weight);
+                assert_eq!(node.is_head, proto_node.root == head_root);
+                assert_eq!(
+                    node.proposer_boost,
+                    info.proposer_boost_root == Some(proto_node.root)
+                );
+            }
+        }
+        assert_eq!(info.nodes.iter().filter(|node| node.is_head).count(), 1);
+
+        let dot = self.client.get_lighthouse_fork_choice_dot().await.unwrap();
+        assert!(dot.starts_with("digraph fork_choice {"));
+        assert!(dot.contains(&format!("\"{:?}\"", head_root)));
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_pool_attestations(self) -> Self {
+        let result = self
+            .client
+            .get_lighthouse_pool_attestations::<E>(None, None, None)
+            .await
+            .unwrap()
+            .data;
+
+        let expected = self.chain.op_pool.get_all_attestations();
+        assert_eq!(result.attestations.len(), expected.len());
+        for pool_attestation in &result.attestations {
+            assert!(expected.contains(&pool_attestation.attestation));
+            assert_eq!(
+                pool_attestation.attesting_validators,
+                pool_attestation.attestation.num_set_aggregation_bits() as u64
+            );
+        }
+        let head_slot = self.chain.head_snapshot().beacon_block.slot();
+        assert!(result.proposal_slot > head_slot);
+
+        // The aggregated filter partitions the pool.
+        let aggregated = self
+            .client
+            .get_lighthouse_pool_attestations::<E>(None, None, Some(true))
+            .await
+            .unwrap()
+            .data
+            .attestations;
+        let unaggregated = self
+            .client
+            .get_lighthouse_pool_attestations::<E>(None, None, Some(false))
+            .await
+            .unwrap()
+            .data
+            .attestations;
+        assert!(aggregated.iter().all(|att| att.attesting_validators > 1));
+        assert!(unaggregated.iter().all(|att| att.attesting_validators == 1));
+        assert_eq!(
+            aggregated.len() + unaggregated.len(),
+            result.attestations.len()
+        );
+
+        // Filtering by a slot without attestations returns nothing.
+        let result = self
+            .client
+            .get_lighthouse_pool_attestations::<E>(Some(head_slot + 1), None, None)
+            .await
+            .unwrap()
+            .data;
+        assert!(result.attestations.is_empty());
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_pool_exits_status(mut self) -> Self {
+        self.client
+            .post_beacon_pool_voluntary_exits(&self.voluntary_exit)
+            .await
+            .unwrap();
+        assert!(self.network_rx.network_recv.recv().await.is_some());
+
+        let result = self
+            .client
+            .get_lighthouse_pool_exits_status()
+            .await
+            .unwrap()
+            .data;
+
+        let epoch = self.chain.epoch().unwrap();
+        let interval = self.chain.config.exit_rebroadcast_epochs;
+        assert_eq!(
+            result,
+            vec![VoluntaryExitStatus {
+                validator_index: self.voluntary_exit.message.validator_index,
+                epoch: self.voluntary_exit.message.epoch,
+                included: false,
+                first_seen_epoch: Some(epoch),
+                last_broadcast_epoch: Some(epoch),
+                rebroadcasts: 0,
+                next_rebroadcast_epoch: Some(epoch + interval),
+            }]
+        );
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_validator_monitor_attestations(self) -> Self {
+        let epoch = self.chain.epoch().unwrap() - 2;
+        let result = self
+            .client
+            .get_lighthouse_validator_monitor_attestations(epoch)
+            .await
+            .unwrap()
+            .data;
+
+        let expected = self
+            .chain
+            .validator_monitor
+            .read()
+            .get_attestation_diagnoses(epoch);
+        assert_eq!(result, expected);
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_validator_inclusion_global(self) -> Self {
+        let epoch = self.chain.epoch().unwrap() - 1;
+        self.client
+            .get_lighthouse_validator_inclusion_global(epoch)
+            .await
+            .unwrap();
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_validator_inclusion(self) -> Self {
+        let epoch = self.chain.epoch().unwrap() - 1;
+        self.client
+            .get_lighthouse_validator_inclusion(epoch, ValidatorId::Index(0))
+            .await
+            .unwrap();
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_eth1_syncing(self) -> Self {
+        self.client.get_lighthouse_eth1_syncing().await.unwrap();
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_eth1_block_cache(self) -> Self {
+        let blocks = self.client.get_lighthouse_eth1_block_cache().await.unwrap();
+
+        assert!(blocks.data.is_empty());
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_eth1_deposit_cache(self) -> Self {
+        let deposits = self
+            .client
+            .get_lighthouse_eth1_deposit_cache()
+            .await
+            .unwrap();
+
+        assert!(deposits.data.is_empty());
 
         self
     }
@@ -5540,6 +6298,17 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_lighthouse_slasher_slashings_without_slasher(self) -> Self {
+        let error = self
+            .client
+            .get_lighthouse_slasher_slashings::<E>()
+            .await
+            .unwrap_err();
+        assert_eq!(error.status().unwrap(), 404);
+
+        self
+    }
+
     pub async fn test_post_lighthouse_database_reconstruct(self) -> Self {
         let response = self
             .client
@@ -5550,6 +6319,35 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_post_lighthouse_database_prune_blobs(self) -> Self {
+        let result = self.client.post_lighthouse_database_prune_blobs().await;
+
+        if self.chain.data_availability_boundary().is_some() {
+            assert_eq!(result.unwrap(), "success");
+        } else {
+            assert_eq!(result.unwrap_err().status(), Some(StatusCode::BAD_REQUEST));
+        }
+        self
+    }
+
+    pub async fn test_post_lighthouse_das_reconstruct(self) -> Self {
+        // The head block is already imported, so there is nothing to reconstruct.
+        let block_root = self.chain.head_beacon_block_root();
+        let result = self
+            .client
+            .post_lighthouse_das_reconstruct(block_root)
+            .await;
+        assert_eq!(result.unwrap_err().status(), Some(StatusCode::CONFLICT));
+
+        // An unknown block is not awaiting its data.
+        let result = self
+            .client
+            .post_lighthouse_das_reconstruct(Hash256::repeat_byte(0x42))
+            .await;
+        assert_eq!(result.unwrap_err().status(), Some(StatusCode::NOT_FOUND));
+        self
+    }
+
     pub async fn test_post_lighthouse_liveness(self) -> Self {
         let epoch = self.chain.epoch().unwrap();
         let head_state = self.chain.head_beacon_state_cloned();
@@ -5614,6 +6412,69 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_post_lighthouse_liveness_range(self) -> Self {
+        let epoch = self.chain.epoch().unwrap();
+        let head_state = self.chain.head_beacon_state_cloned();
+        let indices = (0..head_state.validators().len())
+            .map(|i| i as u64)
+            .collect::<Vec<_>>();
+
+        // The attestations for the current slot were published by the liveness tests.
+        let committees = head_state
+            .get_beacon_committees_at_slot(self.chain.slot().unwrap())
+            .unwrap();
+        let attesting_validators: Vec<usize> = committees
+            .into_iter()
+            .flat_map(|committee| committee.committee.iter().cloned())
+            .collect();
+
+        let start_epoch = epoch.saturating_sub(Epoch::new(1));
+        let result = self
+            .client
+            .post_lighthouse_liveness_range(indices.as_slice(), start_epoch, epoch + 1)
+            .await
+            .unwrap()
+            .data;
+
+        assert_eq!(
+            result.iter().map(|data| data.epoch).collect::<Vec<_>>(),
+            (start_epoch.as_u64()..=epoch.as_u64() + 1)
+                .map(Epoch::new)
+                .collect::<Vec<_>>()
+        );
+        for data in result {
+            for (position, &index) in indices.iter().enumerate() {
+                assert_eq!(
+                    data.is_live(position),
+                    self.chain
+                        .validator_seen_at_epoch(index as usize, data.epoch)
+                );
+                if data.epoch == epoch && attesting_validators.contains(&(index as usize)) {
+                    assert!(data.is_live(position));
+                }
+            }
+        }
+
+        // Ranges which are inverted, too long or in the future are rejected.
+        assert!(self
+            .client
+            .post_lighthouse_liveness_range(indices.as_slice(), epoch, start_epoch)
+            .await
+            .is_err());
+        assert!(self
+            .client
+            .post_lighthouse_liveness_range(indices.as_slice(), epoch, epoch + 2)
+            .await
+            .is_err());
+        assert!(self
+            .client
+            .post_lighthouse_liveness_range(indices.as_slice(), Epoch::new(0), epoch + 64)
+            .await
+            .is_err());
+
+        self
+    }
+
     pub async fn test_get_events(self) -> Self {
         // Subscribe to all events
         let topics = vec![
@@ -5626,6 +6487,7 @@ impl ApiTester {
             EventTopic::AttesterSlashing,
             EventTopic::ProposerSlashing,
             EventTopic::BlsToExecutionChange,
+            EventTopic::SyncProgress,
         ];
         let mut events_future = self
             .client
@@ -5758,6 +6620,11 @@ impl ApiTester {
             .get_events::<E>(&[EventTopic::ChainReorg])
             .await
             .unwrap();
+        let mut reorg_detail_event_future = self
+            .client
+            .get_events::<E>(&[EventTopic::ReorgDetail])
+            .await
+            .unwrap();
 
         let expected_reorg = EventKind::ChainReorg(SseChainReorg {
             slot: self.reorg_block.signed_block().slot(),
@@ -5789,6 +6656,40 @@ impl ApiTester {
         .await;
         assert_eq!(reorg_event.as_slice(), &[expected_reorg]);
 
+        let reorg_detail_event = poll_events(
+            &mut reorg_detail_event_future,
+            1,
+            Duration::from_millis(10000),
+        )
+        .await;
+        let [EventKind::ReorgDetail(reorg_detail)] = reorg_detail_event.as_slice() else {
+            panic!("unexpected events: {reorg_detail_event:?}");
+        };
+        let old_block = self.next_block.signed_block();
+        let new_block = self.reorg_block.signed_block();
+        assert_eq!(reorg_detail.depth, 1);
+        assert_eq!(reorg_detail.old_head_block, old_block.canonical_root());
+        assert_eq!(reorg_detail.new_head_block, new_block.canonical_root());
+        assert_eq!(reorg_detail.common_ancestor_block, old_block.parent_root());
+        assert_eq!(
+            reorg_detail.old_blocks,
+            vec![SseReorgBlock {
+                slot: old_block.slot(),
+                block: old_block.canonical_root(),
+                proposer_index: old_block.message().proposer_index(),
+            }]
+        );
+        assert_eq!(
+            reorg_detail.new_blocks,
+            vec![SseReorgBlock {
+                slot: new_block.slot(),
+                block: new_block.canonical_root(),
+                proposer_index: new_block.message().proposer_index(),
+            }]
+        );
+        // The blocks are from different slots, so there is no equivocation.
+        assert!(reorg_detail.equivocations.is_empty());
+
         // Test attester slashing event
         let mut attester_slashing_event_future = self
             .client
@@ -6250,6 +7151,14 @@ async fn beacon_pools_post_voluntary_exits_invalid() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn lighthouse_pool_exits_status() {
+    ApiTester::new()
+        .await
+        .test_get_lighthouse_pool_exits_status()
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn config_get() {
     ApiTester::new()
@@ -6377,6 +7286,14 @@ async fn get_validator_duties_proposer() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn get_lighthouse_validator_duties_proposer() {
+    ApiTester::new()
+        .await
+        .test_get_lighthouse_validator_duties_proposer()
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn get_validator_duties_proposer_with_skip_slots() {
     ApiTester::new_from_config(ApiTesterConfig::default().retain_historic_states())
@@ -6671,6 +7588,30 @@ async fn post_validator_max_builder_boost_factor() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn post_validator_builder_min_bid_above_bid() {
+    ApiTester::new_mev_tester()
+        .await
+        .test_payload_v3_builder_min_bid_above_bid()
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn post_validator_builder_group_registration_and_payload() {
+    ApiTester::new_with_hard_forks(true, true)
+        .await
+        .test_builder_group_registration_and_payload()
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn post_validator_builder_group_min_bid_above_bid() {
+    ApiTester::new_mev_tester()
+        .await
+        .test_builder_group_min_bid_above_bid()
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn post_validator_register_valid_v3() {
     ApiTester::new_mev_tester()
@@ -6727,6 +7668,14 @@ async fn get_full_block_invalid_parent_hash_v3() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn get_lighthouse_relays_invalid_bids() {
+    ApiTester::new_mev_tester()
+        .await
+        .test_get_lighthouse_relays_deprioritizes_invalid_bids()
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn get_blinded_block_invalid_prev_randao() {
     ApiTester::new_mev_tester()
@@ -6938,6 +7887,8 @@ async fn get_blob_sidecars() {
         .test_get_blob_sidecars(false)
         .await
         .test_get_blob_sidecars(true)
+        .await
+        .test_get_lighthouse_blobs()
         .await;
 }
 
@@ -6987,8 +7938,38 @@ async fn lighthouse_endpoints() {
         .await
         .test_get_lighthouse_syncing()
         .await
+        .test_get_lighthouse_syncing_progress()
+        .await
+        .test_get_lighthouse_syncing_chains()
+        .await
+        .test_lighthouse_syncing_backfill_throttle()
+        .await
+        .test_post_lighthouse_beacon_headers_by_roots()
+        .await
+        .test_post_lighthouse_beacon_roots_by_slots()
+        .await
+        .test_get_lighthouse_peers_first_deliveries()
+        .await
+        .test_get_lighthouse_peers_clients()
+        .await
+        .test_get_lighthouse_peers_export()
+        .await
+        .test_get_lighthouse_fork_schedule()
+        .await
+        .test_get_lighthouse_gossip_subscriptions()
+        .await
+        .test_get_lighthouse_network_metadata()
+        .await
+        .test_get_lighthouse_diagnostics()
+        .await
         .test_get_lighthouse_proto_array()
         .await
+        .test_get_lighthouse_fork_choice()
+        .await
+        .test_get_lighthouse_pool_attestations()
+        .await
+        .test_get_lighthouse_validator_monitor_attestations()
+        .await
         .test_get_lighthouse_validator_inclusion()
         .await
         .test_get_lighthouse_validator_inclusion_global()
@@ -7003,9 +7984,17 @@ async fn lighthouse_endpoints() {
         .await
         .test_get_lighthouse_database_info()
         .await
+        .test_get_lighthouse_slasher_slashings_without_slasher()
+        .await
         .test_post_lighthouse_database_reconstruct()
         .await
+        .test_post_lighthouse_database_prune_blobs()
+        .await
+        .test_post_lighthouse_das_reconstruct()
+        .await
         .test_post_lighthouse_liveness()
+        .await
+        .test_post_lighthouse_liveness_range()
         .await;
 }
 
@@ -29,8 +29,9 @@ use std::{
     time::Duration,
 };
 
-use futures::StreamExt;
+use futures::{FutureExt, StreamExt};
 use futures_ticker::Ticker;
+use futures_timer::Delay;
 use hashlink::LinkedHashMap;
 use prometheus_client::registry::Registry;
 use rand::{seq::SliceRandom, thread_rng};
@@ -65,7 +66,7 @@ use super::topic::{Hasher, Topic, TopicHash};
 use super::transform::{DataTransform, IdentityTransform};
 use super::types::{
     ControlAction, FailedMessages, Message, MessageAcceptance, MessageId, PeerInfo, RawMessage,
-    Subscription, SubscriptionAction,
+    StaggeredPublish, Subscription, SubscriptionAction,
 };
 use super::types::{Graft, IHave, IWant, PeerConnections, PeerKind, Prune};
 use super::{backoff::BackoffStorage, types::RpcSender};
@@ -143,6 +144,16 @@ pub enum Event {
         /// The decompressed message itself.
         message: Message,
     },
+    /// A message has been received which was already received from another peer. Only generated
+    /// for topics accepted by the [`Config`]'s duplicate message filter.
+    DuplicateMessage {
+        /// The peer that forwarded us this message.
+        propagation_source: PeerId,
+        /// The [`MessageId`] of the message.
+        message_id: MessageId,
+        /// The topic the message was received on.
+        topic: TopicHash,
+    },
     /// A remote subscribed to a topic.
     Subscribed {
         /// Remote that has subscribed.
@@ -346,6 +357,10 @@ pub struct Behaviour<D = IdentityTransform, F = AllowAllSubscriptionFilter> {
 
     /// Tracks recently sent `IWANT` messages and checks if peers respond to them.
     gossip_promises: GossipPromises,
+
+    /// Published messages waiting to be sent to the recipients outside of their first burst, in
+    /// the order they are due.
+    staggered_publishes: VecDeque<StaggeredPublish>,
 }
 
 impl<D, F> Behaviour<D, F>
@@ -483,6 +498,7 @@ where
             data_transform,
             failed_messages: Default::default(),
             gossip_promises: Default::default(),
+            staggered_publishes: VecDeque::new(),
         })
     }
 }
@@ -741,6 +757,18 @@ where
             }
         }
 
+        // If publishing on this topic is staggered, only the highest priority recipients are sent
+        // the message now. The remainder are sent it after a delay.
+        let mut recipient_peers = recipient_peers.into_iter().collect::<Vec<_>>();
+        let mut deferred_peers = vec![];
+        let staggered_publish = self.config.staggered_publish(&topic_hash);
+        if let Some((first_burst_peers, _)) = staggered_publish {
+            if recipient_peers.len() > first_burst_peers {
+                self.sort_by_publish_priority(&mut recipient_peers);
+                deferred_peers = recipient_peers.split_off(first_burst_peers);
+            }
+        }
+
         // Send to peers we know are subscribed to the topic.
         let mut publish_failed = true;
         for peer_id in recipient_peers.iter() {
@@ -776,6 +804,20 @@ where
             return Err(PublishError::AllQueuesFull(recipient_peers.len()));
         }
 
+        if let Some((_, delay)) = staggered_publish.filter(|_| !deferred_peers.is_empty()) {
+            if let Some(metrics) = self.metrics.as_mut() {
+                metrics
+                    .register_staggered_publish_deferred_peers(&topic_hash, deferred_peers.len());
+            }
+            self.staggered_publishes.push_back(StaggeredPublish {
+                message_id: msg_id.clone(),
+                message: raw_message.clone(),
+                peers: deferred_peers,
+                published: Instant::now(),
+                delay: Delay::new(delay),
+            });
+        }
+
         // Broadcast IDONTWANT messages
         if raw_message.raw_protobuf_len() > self.config.idontwant_message_size_threshold() {
             self.send_idontwant(&raw_message, &msg_id, raw_message.source.as_ref());
@@ -1839,6 +1881,14 @@ where
                 peer_score.duplicated_message(propagation_source, &msg_id, &message.topic);
             }
             self.mcache.observe_duplicate(&msg_id, propagation_source);
+            if self.config.notify_duplicate_message(&message.topic) {
+                self.events
+                    .push_back(ToSwarm::GenerateEvent(Event::DuplicateMessage {
+                        propagation_source: *propagation_source,
+                        message_id: msg_id,
+                        topic: message.topic,
+                    }));
+            }
             return;
         }
 
@@ -2719,6 +2769,75 @@ where
         }
     }
 
+    /// Orders the recipients of a published message such that explicit peers come first, followed
+    /// by the remaining peers from highest to lowest score.
+    fn sort_by_publish_priority(&self, peers: &mut [PeerId]) {
+        let score = |peer: &PeerId| {
+            self.peer_score
+                .as_ref()
+                .map_or(0.0, |(peer_score, ..)| peer_score.score(peer))
+        };
+        peers.sort_by(|a, b| {
+            self.explicit_peers
+                .contains(b)
+                .cmp(&self.explicit_peers.contains(a))
+                .then_with(|| score(b).total_cmp(&score(a)))
+        });
+    }
+
+    /// Sends a staggered message to the recipients outside of its first burst, skipping those
+    /// which have since received it from another peer.
+    fn send_staggered_publish(&mut self, staggered: StaggeredPublish) {
+        let StaggeredPublish {
+            message_id,
+            message,
+            peers,
+            published,
+            ..
+        } = staggered;
+
+        let mut skipped_peers = 0;
+        for peer_id in peers {
+            let Some(peer) = self.connected_peers.get_mut(&peer_id) else {
+                continue;
+            };
+
+            // An IDONTWANT from the peer shows the message has already reached it.
+            if let Some(received) = peer.dont_send_received.get(&message_id) {
+                skipped_peers += 1;
+                if let Some(metrics) = self.metrics.as_mut() {
+                    metrics.observe_staggered_publish_propagation_time(
+                        received.saturating_duration_since(published),
+                    );
+                }
+                continue;
+            }
+
+            tracing::trace!(peer=%peer_id, message=%message_id, "Sending staggered message to peer");
+            if peer
+                .sender
+                .publish(
+                    message.clone(),
+                    self.config.publish_queue_duration(),
+                    self.metrics.as_mut(),
+                )
+                .is_err()
+            {
+                self.failed_messages.entry(peer_id).or_default().priority += 1;
+
+                tracing::warn!(peer_id=%peer_id, "Publish queue full. Could not publish to peer");
+                // Downscore the peer due to failed message.
+                if let Some((peer_score, ..)) = &mut self.peer_score {
+                    peer_score.failed_message_slow_peer(&peer_id);
+                }
+            }
+        }
+
+        if let Some(metrics) = self.metrics.as_mut() {
+            metrics.register_staggered_publish_skipped_peers(&message.topic, skipped_peers);
+        }
+    }
+
     /// Helper function which sends an IDONTWANT message to mesh\[topic\] peers.
     fn send_idontwant(
         &mut self,
@@ -3437,6 +3556,16 @@ where
             self.heartbeat();
         }
 
+        // Staggered messages all share the same delay, so they become due in order.
+        while let Some(staggered) = self.staggered_publishes.front_mut() {
+            if staggered.delay.poll_unpin(cx).is_pending() {
+                break;
+            }
+            if let Some(staggered) = self.staggered_publishes.pop_front() {
+                self.send_staggered_publish(staggered);
+            }
+        }
+
         Poll::Pending
     }
 
@@ -5483,3 +5483,59 @@ fn clear_stale_idontwant() {
     let peer = gs.connected_peers.get_mut(&peers[2]).unwrap();
     assert!(peer.dont_send_received.is_empty());
 }
+
+#[test]
+fn test_staggered_publish() {
+    let config = ConfigBuilder::default()
+        .staggered_publish(2, Duration::from_secs(1), |_| true)
+        .build()
+        .unwrap();
+
+    let topic = "test";
+    let (mut gs, peers, receivers, _) = inject_nodes1()
+        .peer_no(8)
+        .topics(vec![topic.into()])
+        .to_subscribe(true)
+        .gs_config(config)
+        .create_network();
+
+    let count_publishes = |receivers: &HashMap<PeerId, RpcReceiver>| {
+        receivers
+            .values()
+            .map(|receiver| {
+                let mut count = 0;
+                while let Ok(RpcOut::Publish { .. }) = receiver.priority.get_ref().try_recv() {
+                    count += 1;
+                }
+                count
+            })
+            .sum::<usize>()
+    };
+
+    let message_id = gs.publish(Topic::new(topic), vec![0; 42]).unwrap();
+    assert_eq!(
+        count_publishes(&receivers),
+        2,
+        "Only the first burst should be sent the message immediately"
+    );
+
+    let staggered = gs
+        .staggered_publishes
+        .pop_front()
+        .expect("The remaining recipients should be deferred");
+    assert_eq!(staggered.peers.len(), peers.len() - 2);
+
+    // One of the deferred recipients receives the message from another peer in the meantime.
+    gs.connected_peers
+        .get_mut(&staggered.peers[0])
+        .unwrap()
+        .dont_send_received
+        .insert(message_id, Instant::now());
+
+    gs.send_staggered_publish(staggered);
+    assert_eq!(
+        count_publishes(&receivers),
+        peers.len() - 3,
+        "Deferred recipients which already have the message should be skipped"
+    );
+}
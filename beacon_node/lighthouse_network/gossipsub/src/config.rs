@@ -24,6 +24,7 @@ use std::time::Duration;
 
 use super::error::ConfigBuilderError;
 use super::protocol::{ProtocolConfig, ProtocolId, FLOODSUB_PROTOCOL};
+use super::topic::TopicHash;
 use super::types::{Message, MessageId, PeerKind};
 
 use libp2p::identity::PeerId;
@@ -99,6 +100,10 @@ pub struct Config {
     connection_handler_publish_duration: Duration,
     connection_handler_forward_duration: Duration,
     idontwant_message_size_threshold: usize,
+    duplicate_message_filter: Option<Arc<dyn Fn(&TopicHash) -> bool + Send + Sync + 'static>>,
+    staggered_publish_peers: usize,
+    staggered_publish_delay: Duration,
+    staggered_publish_filter: Option<Arc<dyn Fn(&TopicHash) -> bool + Send + Sync + 'static>>,
 }
 
 impl Config {
@@ -381,6 +386,24 @@ impl Config {
     pub fn idontwant_message_size_threshold(&self) -> usize {
         self.idontwant_message_size_threshold
     }
+
+    /// Whether a [`crate::Event::DuplicateMessage`] should be generated for a duplicate message
+    /// received on `topic`. By default no duplicate messages are reported.
+    pub fn notify_duplicate_message(&self, topic: &TopicHash) -> bool {
+        self.duplicate_message_filter
+            .as_ref()
+            .is_some_and(|filter| filter(topic))
+    }
+
+    /// If messages published on `topic` are staggered, returns the number of highest-scoring
+    /// recipients the message is sent to immediately and the delay before it is sent to the
+    /// remaining recipients. By default messages are sent to all recipients at once.
+    pub fn staggered_publish(&self, topic: &TopicHash) -> Option<(usize, Duration)> {
+        self.staggered_publish_filter
+            .as_ref()
+            .filter(|filter| filter(topic))
+            .map(|_| (self.staggered_publish_peers, self.staggered_publish_delay))
+    }
 }
 
 impl Default for Config {
@@ -452,6 +475,10 @@ impl Default for ConfigBuilder {
                 connection_handler_publish_duration: Duration::from_secs(5),
                 connection_handler_forward_duration: Duration::from_millis(1000),
                 idontwant_message_size_threshold: 1000,
+                duplicate_message_filter: None,
+                staggered_publish_peers: 0,
+                staggered_publish_delay: Duration::ZERO,
+                staggered_publish_filter: None,
             },
             invalid_protocol: false,
         }
@@ -848,6 +875,36 @@ impl ConfigBuilder {
         self
     }
 
+    /// A user-defined filter selecting the topics for which duplicate messages are reported to the
+    /// application as [`crate::Event::DuplicateMessage`]. Duplicates are usually silently dropped,
+    /// so this should only select low-volume topics.
+    pub fn notify_duplicate_messages<F>(&mut self, filter: F) -> &mut Self
+    where
+        F: Fn(&TopicHash) -> bool + Send + Sync + 'static,
+    {
+        self.config.duplicate_message_filter = Some(Arc::new(filter));
+        self
+    }
+
+    /// Publish messages on the topics selected by `filter` in two bursts. The message is first
+    /// sent to the `first_burst_peers` highest-scoring recipients and, after `delay`, to the
+    /// remaining recipients which have not already sent us an IDONTWANT for it. This reduces the
+    /// upload spike of publishing large messages at the cost of slower propagation to some peers.
+    pub fn staggered_publish<F>(
+        &mut self,
+        first_burst_peers: usize,
+        delay: Duration,
+        filter: F,
+    ) -> &mut Self
+    where
+        F: Fn(&TopicHash) -> bool + Send + Sync + 'static,
+    {
+        self.config.staggered_publish_peers = first_burst_peers;
+        self.config.staggered_publish_delay = delay;
+        self.config.staggered_publish_filter = Some(Arc::new(filter));
+        self
+    }
+
     /// Constructs a [`Config`] from the given configuration and validates the settings.
     pub fn build(&self) -> Result<Config, ConfigBuilderError> {
         // check all constraints on config
@@ -879,6 +936,12 @@ impl ConfigBuilder {
             return Err(ConfigBuilderError::InvalidProtocol);
         }
 
+        if self.config.staggered_publish_filter.is_some()
+            && self.config.staggered_publish_peers == 0
+        {
+            return Err(ConfigBuilderError::StaggeredPublishPeersIsZero);
+        }
+
         Ok(self.config.clone())
     }
 }
@@ -922,6 +985,8 @@ impl std::fmt::Debug for Config {
             "idontwant_message_size_threhold",
             &self.idontwant_message_size_threshold,
         );
+        let _ = builder.field("staggered_publish_peers", &self.staggered_publish_peers);
+        let _ = builder.field("staggered_publish_delay", &self.staggered_publish_delay);
         builder.finish()
     }
 }
@@ -139,6 +139,8 @@ pub enum ConfigBuilderError {
     UnsubscribeBackoffIsZero,
     /// Invalid protocol
     InvalidProtocol,
+    /// Staggered publishing is enabled but the first burst is sent to no peers.
+    StaggeredPublishPeersIsZero,
 }
 
 impl std::error::Error for ConfigBuilderError {}
@@ -154,6 +156,9 @@ impl std::fmt::Display for ConfigBuilderError {
             Self::MeshOutboundInvalid => write!(f, "The inequality doesn't hold mesh_outbound_min <= self.config.mesh_n / 2"),
             Self::UnsubscribeBackoffIsZero => write!(f, "unsubscribe_backoff is zero"),
             Self::InvalidProtocol => write!(f, "Invalid protocol"),
+            Self::StaggeredPublishPeersIsZero => {
+                write!(f, "Staggered publishing requires at least one first burst peer")
+            }
         }
     }
 }
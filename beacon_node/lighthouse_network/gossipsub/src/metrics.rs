@@ -22,12 +22,13 @@
 //! protocol.
 
 use std::collections::HashMap;
+use std::time::Duration;
 
 use prometheus_client::encoding::{EncodeLabelSet, EncodeLabelValue};
 use prometheus_client::metrics::counter::Counter;
 use prometheus_client::metrics::family::{Family, MetricConstructor};
 use prometheus_client::metrics::gauge::Gauge;
-use prometheus_client::metrics::histogram::{linear_buckets, Histogram};
+use prometheus_client::metrics::histogram::{exponential_buckets, linear_buckets, Histogram};
 use prometheus_client::registry::Registry;
 
 use super::topic::TopicHash;
@@ -198,6 +199,15 @@ pub(crate) struct Metrics {
     priority_queue_size: Histogram,
     /// The size of the non-priority queue.
     non_priority_queue_size: Histogram,
+
+    /// The number of recipients of staggered messages which were deferred to the second burst.
+    staggered_publish_deferred_peers: Family<TopicHash, Counter>,
+    /// The number of deferred recipients of staggered messages which received the message from
+    /// another peer before the second burst, and so were not sent it.
+    staggered_publish_skipped_peers: Family<TopicHash, Counter>,
+    /// The time between publishing a staggered message and a deferred recipient reporting that it
+    /// received the message from another peer.
+    staggered_publish_propagation_time: Histogram,
 }
 
 impl Metrics {
@@ -393,6 +403,23 @@ impl Metrics {
             non_priority_queue_size.clone(),
         );
 
+        let staggered_publish_deferred_peers = register_family!(
+            "staggered_publish_deferred_peers",
+            "Number of recipients of staggered messages deferred to the second burst"
+        );
+        let staggered_publish_skipped_peers = register_family!(
+            "staggered_publish_skipped_peers",
+            "Number of deferred recipients of staggered messages which received them from another peer"
+        );
+
+        let staggered_publish_propagation_time =
+            Histogram::new(exponential_buckets(0.005, 2.0, 12));
+        registry.register(
+            "staggered_publish_propagation_time",
+            "Seconds from publishing a staggered message until a deferred recipient received it from another peer",
+            staggered_publish_propagation_time.clone(),
+        );
+
         Self {
             max_topics,
             max_never_subscribed_topics,
@@ -427,6 +454,9 @@ impl Metrics {
             idontwant_messages_ignored_per_topic,
             priority_queue_size,
             non_priority_queue_size,
+            staggered_publish_deferred_peers,
+            staggered_publish_skipped_peers,
+            staggered_publish_propagation_time,
         }
     }
 
@@ -648,6 +678,39 @@ impl Metrics {
         self.idontwant_msgs_ids.inc_by(msgs as u64);
     }
 
+    /// Register the recipients of a staggered message deferred to the second burst.
+    pub(crate) fn register_staggered_publish_deferred_peers(
+        &mut self,
+        topic: &TopicHash,
+        peers: usize,
+    ) {
+        if self.register_topic(topic).is_ok() {
+            self.staggered_publish_deferred_peers
+                .get_or_create(topic)
+                .inc_by(peers as u64);
+        }
+    }
+
+    /// Register the deferred recipients of a staggered message which were not sent it because
+    /// they received it from another peer.
+    pub(crate) fn register_staggered_publish_skipped_peers(
+        &mut self,
+        topic: &TopicHash,
+        peers: usize,
+    ) {
+        if self.register_topic(topic).is_ok() {
+            self.staggered_publish_skipped_peers
+                .get_or_create(topic)
+                .inc_by(peers as u64);
+        }
+    }
+
+    /// Observes the time a staggered message took to reach a deferred recipient via another peer.
+    pub(crate) fn observe_staggered_publish_propagation_time(&mut self, duration: Duration) {
+        self.staggered_publish_propagation_time
+            .observe(duration.as_secs_f64());
+    }
+
     /// Observes a heartbeat duration.
     pub(crate) fn observe_heartbeat_duration(&mut self, millis: u64) {
         self.heartbeat_duration.observe(millis as f64);
@@ -129,6 +129,20 @@ pub(crate) struct PeerConnections {
     pub(crate) dont_send_sent: LinkedHashMap<MessageId, Instant>,
 }
 
+/// A message published in staggered bursts which is yet to be sent to its remaining recipients.
+pub(crate) struct StaggeredPublish {
+    /// The id of the published message.
+    pub(crate) message_id: MessageId,
+    /// The published message.
+    pub(crate) message: RawMessage,
+    /// The recipients which were not part of the first burst.
+    pub(crate) peers: Vec<PeerId>,
+    /// The time the message was published.
+    pub(crate) published: Instant,
+    /// Fires once the message should be sent to `peers`.
+    pub(crate) delay: Delay,
+}
+
 /// Describes the types of peers that can exist in the gossipsub context.
 #[derive(Debug, Clone, PartialEq, Hash, EncodeLabelValue, Eq)]
 #[allow(non_camel_case_types)]
@@ -1,6 +1,6 @@
 use crate::listen_addr::{ListenAddr, ListenAddress};
 use crate::rpc::config::{InboundRateLimiterConfig, OutboundRateLimiterConfig};
-use crate::types::GossipKind;
+use crate::types::{FirstSeenKind, GossipKind};
 use crate::{Enr, PeerIdSerialized};
 use directory::{
     DEFAULT_BEACON_NODE_DIR, DEFAULT_HARDCODED_NETWORK, DEFAULT_NETWORK_DIR, DEFAULT_ROOT_DIR,
@@ -13,6 +13,7 @@ use std::num::NonZeroU16;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
+use strum::{Display, EnumString};
 use types::{ForkContext, ForkName};
 
 pub const DEFAULT_IPV4_ADDRESS: Ipv4Addr = Ipv4Addr::UNSPECIFIED;
@@ -20,6 +21,46 @@ pub const DEFAULT_TCP_PORT: u16 = 9000u16;
 pub const DEFAULT_DISC_PORT: u16 = 9000u16;
 pub const DEFAULT_QUIC_PORT: u16 = 9001u16;
 pub const DEFAULT_IDONTWANT_MESSAGE_SIZE_THRESHOLD: usize = 1000usize;
+pub const DEFAULT_STAGGERED_PUBLISH_DELAY: Duration = Duration::from_millis(200);
+pub const DEFAULT_SHUTDOWN_RPC_DRAIN_TIMEOUT: Duration = Duration::from_secs(5);
+pub const DEFAULT_AGGRESSIVE_SUBNET_EPOCHS: u64 = 2;
+pub const DEFAULT_LOOKUP_MAX_PARENT_DEPTH: usize = 32;
+
+/// The transports used to dial a peer and the order in which they are tried.
+///
+/// Dials only fall back to the next transport if all addresses of the preferred transport fail.
+/// This does not affect which transports we listen on.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, EnumString, Display,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum TransportPreference {
+    /// Dial QUIC addresses first, falling back to TCP.
+    #[default]
+    QuicFirst,
+    /// Dial TCP addresses first, falling back to QUIC.
+    TcpFirst,
+    /// Only dial QUIC addresses. Peers which don't advertise a QUIC address are not dialed.
+    QuicOnly,
+}
+
+/// How the node subscribes to attestation subnets beyond its backbone subnets.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, EnumString, Display,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum SubnetStrategy {
+    /// Subscribe to a subnet shortly before each aggregation duty, and unsubscribe after it.
+    #[default]
+    Default,
+    /// Subscribe to the subnet of every attestation duty as soon as it is known, and keep the
+    /// subscription for a number of epochs after the duty. Suited to nodes with few validators.
+    Aggressive,
+    /// Subscribe to all subnets for the duration of the runtime.
+    AllSubnets,
+}
 
 /// The maximum size of gossip messages.
 pub fn gossip_max_size(is_merge_enabled: bool, gossip_max_size: usize) -> usize {
@@ -83,6 +124,10 @@ pub struct Config {
     /// List of libp2p nodes to initially connect to.
     pub libp2p_nodes: Vec<Multiaddr>,
 
+    /// Peers exported from another node, ordered from best to worst, which are added to the
+    /// routing table and dialed at startup.
+    pub imported_peers: Vec<Enr>,
+
     /// List of trusted libp2p nodes which are not scored and marked as explicit.
     pub trusted_peers: Vec<PeerIdSerialized>,
 
@@ -98,15 +143,28 @@ pub struct Config {
     /// Disables quic support.
     pub disable_quic_support: bool,
 
+    /// The order in which transports are dialed when connecting to a peer.
+    pub transport_preference: TransportPreference,
+
     /// Attempt to construct external port mappings with UPnP.
     pub upnp_enabled: bool,
 
     /// Subscribe to all data column subnets for the duration of the runtime.
     pub subscribe_all_data_column_subnets: bool,
 
+    /// Retain all custody data columns indefinitely and advertise it in the ENR.
+    pub data_column_archive: bool,
+
     /// Subscribe to all subnets for the duration of the runtime.
     pub subscribe_all_subnets: bool,
 
+    /// How the node subscribes to attestation subnets for validator duties.
+    pub subnet_strategy: SubnetStrategy,
+
+    /// The number of epochs after a duty for which a subnet subscription is kept when using
+    /// `SubnetStrategy::Aggressive`.
+    pub aggressive_subnet_epochs: u64,
+
     /// Import/aggregate all attestations received on subscribed subnets for the duration of the
     /// runtime.
     pub import_all_attestations: bool,
@@ -122,18 +180,37 @@ pub struct Config {
     /// Shutdown beacon node after sync is completed.
     pub shutdown_after_sync: bool,
 
+    /// Persist pending block lookups on shutdown and resume them on the next start.
+    pub persist_pending_lookups: bool,
+
+    /// The maximum length of a chain of parent block lookups. Longer chains are synced with range
+    /// sync instead.
+    pub lookup_max_parent_depth: usize,
+
     /// List of extra topics to initially subscribe to as strings.
     pub topics: Vec<GossipKind>,
 
     /// Whether we are running a block proposer only node.
     pub proposer_only: bool,
 
+    /// Whether to only subscribe to the gossip topics required to follow the head of the chain,
+    /// for nodes which exist to serve RPC requests and the HTTP API.
+    pub rpc_serving_only: bool,
+
     /// Whether metrics are enabled.
     pub metrics_enabled: bool,
 
     /// Whether light client protocols should be enabled.
     pub enable_light_client_server: bool,
 
+    /// Whether to serve blinded blocks to trusted peers over the Lighthouse-specific blinded
+    /// blocks by range protocol.
+    pub enable_blinded_blocks_by_range: bool,
+
+    /// Whether to serve execution payload bodies to trusted peers over the Lighthouse-specific
+    /// execution payload bodies by range protocol.
+    pub enable_execution_payload_bodies_by_range: bool,
+
     /// Configuration for the outbound rate limiter (requests made by this node).
     pub outbound_rate_limiter_config: Option<OutboundRateLimiterConfig>,
 
@@ -146,6 +223,20 @@ pub struct Config {
     /// Configuration for the minimum message size for which IDONTWANT messages are send in the mesh.
     /// Lower the value reduces the optimization effect of the IDONTWANT messages.
     pub idontwant_message_size_threshold: usize,
+
+    /// The number of highest-scoring peers blocks and blobs are published to before the remaining
+    /// peers. If `None`, they are published to all peers at once.
+    pub staggered_publish_peers: Option<usize>,
+
+    /// The delay before a staggered block or blob is published to the remaining peers.
+    pub staggered_publish_delay: Duration,
+
+    /// The maximum time to wait for inbound RPC requests to be served when shutting down.
+    pub shutdown_rpc_drain_timeout: Duration,
+
+    /// The initial limit on the number of batches backfill sync processes per minute. The limit
+    /// can be changed at runtime via `NetworkGlobals::backfill_throttle`.
+    pub backfill_batches_per_minute: Option<u64>,
 }
 
 impl Config {
@@ -338,26 +429,40 @@ impl Default for Config {
             boot_nodes_enr: vec![],
             boot_nodes_multiaddr: vec![],
             libp2p_nodes: vec![],
+            imported_peers: vec![],
             trusted_peers: vec![],
             disable_peer_scoring: false,
             client_version: lighthouse_version::version_with_platform(),
             disable_discovery: false,
             disable_quic_support: false,
+            transport_preference: TransportPreference::default(),
             upnp_enabled: true,
             network_load: 4,
             private: false,
             subscribe_all_data_column_subnets: false,
+            data_column_archive: false,
             subscribe_all_subnets: false,
+            subnet_strategy: SubnetStrategy::default(),
+            aggressive_subnet_epochs: DEFAULT_AGGRESSIVE_SUBNET_EPOCHS,
             import_all_attestations: false,
             shutdown_after_sync: false,
+            persist_pending_lookups: false,
+            lookup_max_parent_depth: DEFAULT_LOOKUP_MAX_PARENT_DEPTH,
             topics: Vec::new(),
             proposer_only: false,
+            rpc_serving_only: false,
             metrics_enabled: false,
             enable_light_client_server: false,
+            enable_blinded_blocks_by_range: false,
+            enable_execution_payload_bodies_by_range: false,
             outbound_rate_limiter_config: None,
             invalid_block_storage: None,
             inbound_rate_limiter_config: None,
             idontwant_message_size_threshold: DEFAULT_IDONTWANT_MESSAGE_SIZE_THRESHOLD,
+            staggered_publish_peers: None,
+            staggered_publish_delay: DEFAULT_STAGGERED_PUBLISH_DELAY,
+            shutdown_rpc_drain_timeout: DEFAULT_SHUTDOWN_RPC_DRAIN_TIMEOUT,
+            backfill_batches_per_minute: None,
         }
     }
 }
@@ -440,6 +545,7 @@ pub fn gossipsub_config(
     seconds_per_slot: u64,
     slots_per_epoch: u64,
     idontwant_message_size_threshold: usize,
+    staggered_publish: Option<(usize, Duration)>,
 ) -> gossipsub::Config {
     fn prefix(
         prefix: [u8; 4],
@@ -484,7 +590,17 @@ pub fn gossipsub_config(
     // Hence we keep the same parameters for pre-deneb networks as well to avoid switching at the fork.
     let duplicate_cache_time = Duration::from_secs(slots_per_epoch * seconds_per_slot * 2);
 
-    gossipsub::ConfigBuilder::default()
+    let mut builder = gossipsub::ConfigBuilder::default();
+    if let Some((first_burst_peers, delay)) = staggered_publish {
+        builder.staggered_publish(first_burst_peers, delay, |topic| {
+            matches!(
+                FirstSeenKind::from_topic(topic.as_str()),
+                Some(FirstSeenKind::Block | FirstSeenKind::Blob)
+            )
+        });
+    }
+
+    builder
         .max_transmit_size(gossip_max_size(
             is_bellatrix_enabled,
             gossipsub_config_params.gossip_max_size,
@@ -506,6 +622,7 @@ pub fn gossipsub_config(
         .message_id_fn(gossip_message_id)
         .allow_self_origin(true)
         .idontwant_message_size_threshold(idontwant_message_size_threshold)
+        .notify_duplicate_messages(|topic| FirstSeenKind::from_topic(topic.as_str()).is_some())
         .build()
         .expect("valid gossipsub configuration")
 }
@@ -27,6 +27,9 @@ pub const ATTESTATION_BITFIELD_ENR_KEY: &str = "attnets";
 pub const SYNC_COMMITTEE_BITFIELD_ENR_KEY: &str = "syncnets";
 /// The ENR field specifying the peerdas custody subnet count.
 pub const PEERDAS_CUSTODY_SUBNET_COUNT_ENR_KEY: &str = "csc";
+/// The ENR field specifying that all custody data columns are retained since the PeerDAS fork,
+/// rather than only within the data availability period.
+pub const DATA_COLUMN_ARCHIVE_ENR_KEY: &str = "dca";
 
 /// Extension trait for ENR's within Eth2.
 pub trait Eth2Enr {
@@ -41,6 +44,9 @@ pub trait Eth2Enr {
     /// The peerdas custody subnet count associated with the ENR.
     fn custody_subnet_count<E: EthSpec>(&self, spec: &ChainSpec) -> Result<u64, &'static str>;
 
+    /// Whether the ENR advertises that all custody data columns are retained.
+    fn data_column_archive(&self) -> bool;
+
     fn eth2(&self) -> Result<EnrForkId, &'static str>;
 }
 
@@ -80,6 +86,13 @@ impl Eth2Enr for Enr {
         }
     }
 
+    fn data_column_archive(&self) -> bool {
+        matches!(
+            self.get_decodable::<u8>(DATA_COLUMN_ARCHIVE_ENR_KEY),
+            Some(Ok(1))
+        )
+    }
+
     fn eth2(&self) -> Result<EnrForkId, &'static str> {
         let eth2_bytes: Bytes = self
             .get_decodable(ETH2_ENR_KEY)
@@ -261,6 +274,10 @@ pub fn build_enr<E: EthSpec>(
             spec.custody_requirement
         };
         builder.add_value(PEERDAS_CUSTODY_SUBNET_COUNT_ENR_KEY, &custody_subnet_count);
+
+        if config.data_column_archive {
+            builder.add_value(DATA_COLUMN_ARCHIVE_ENR_KEY, &1u8);
+        }
     }
 
     builder
@@ -286,12 +303,13 @@ fn compare_enr(local_enr: &Enr, disk_enr: &Enr) -> bool {
         // take preference over disk udp port if one is not specified
         && (local_enr.udp4().is_none() || local_enr.udp4() == disk_enr.udp4())
         && (local_enr.udp6().is_none() || local_enr.udp6() == disk_enr.udp6())
-        // we need the ATTESTATION_BITFIELD_ENR_KEY and SYNC_COMMITTEE_BITFIELD_ENR_KEY and
-        // PEERDAS_CUSTODY_SUBNET_COUNT_ENR_KEY key to match, otherwise we use a new ENR. This will
-        // likely only be true for non-validating nodes.
+        // we need the ATTESTATION_BITFIELD_ENR_KEY, SYNC_COMMITTEE_BITFIELD_ENR_KEY,
+        // PEERDAS_CUSTODY_SUBNET_COUNT_ENR_KEY and DATA_COLUMN_ARCHIVE_ENR_KEY keys to match,
+        // otherwise we use a new ENR. This will likely only be true for non-validating nodes.
         && local_enr.get_decodable::<Bytes>(ATTESTATION_BITFIELD_ENR_KEY) == disk_enr.get_decodable(ATTESTATION_BITFIELD_ENR_KEY)
         && local_enr.get_decodable::<Bytes>(SYNC_COMMITTEE_BITFIELD_ENR_KEY) == disk_enr.get_decodable(SYNC_COMMITTEE_BITFIELD_ENR_KEY)
         && local_enr.get_decodable::<Bytes>(PEERDAS_CUSTODY_SUBNET_COUNT_ENR_KEY) == disk_enr.get_decodable(PEERDAS_CUSTODY_SUBNET_COUNT_ENR_KEY)
+        && local_enr.data_column_archive() == disk_enr.data_column_archive()
 }
 
 /// Loads enr from the given directory
@@ -378,6 +396,21 @@ mod test {
         );
     }
 
+    #[test]
+    fn data_column_archive() {
+        let spec = make_eip7594_spec();
+        let enr = build_enr_with_config(NetworkConfig::default(), &spec).0;
+        assert!(!enr.data_column_archive());
+
+        let config = NetworkConfig {
+            subscribe_all_data_column_subnets: true,
+            data_column_archive: true,
+            ..NetworkConfig::default()
+        };
+        let enr = build_enr_with_config(config, &spec).0;
+        assert!(enr.data_column_archive());
+    }
+
     #[test]
     fn test_encode_decode_eth2_enr() {
         let (enr, _key) = build_enr_with_config(NetworkConfig::default(), &E::default_spec());
@@ -12,7 +12,9 @@ pub mod peer_manager;
 pub mod rpc;
 pub mod types;
 
-pub use config::gossip_max_size;
+pub use config::{
+    gossip_max_size, SubnetStrategy, TransportPreference, DEFAULT_LOOKUP_MAX_PARENT_DEPTH,
+};
 use libp2p::swarm::DialError;
 pub use listen_addr::*;
 
@@ -116,7 +118,7 @@ pub use libp2p::{core::ConnectedPoint, PeerId, Swarm};
 pub use libp2p::{multiaddr, Multiaddr};
 pub use metrics::scrape_discovery_metrics;
 pub use peer_manager::{
-    peerdb::client::Client,
+    peerdb::client::{Client, ClientDiversity, ClientQuirk, ClientVersions},
     peerdb::score::{PeerAction, ReportSource},
     peerdb::PeerDB,
     ConnectionDirection, PeerConnectionStatus, PeerInfo, PeerManager, SyncInfo, SyncStatus,
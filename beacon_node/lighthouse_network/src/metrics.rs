@@ -27,6 +27,15 @@ pub static PEERS_CONNECTED_MULTI: LazyLock<Result<IntGaugeVec>> = LazyLock::new(
     )
 });
 
+pub static CONNECTIONS_ESTABLISHED_PER_TRANSPORT: LazyLock<Result<IntCounterVec>> =
+    LazyLock::new(|| {
+        try_create_int_counter_vec(
+            "libp2p_connections_established_total",
+            "Count of libp2p connections established, by direction and transport",
+            &["direction", "transport"],
+        )
+    });
+
 pub static TCP_PEERS_CONNECTED: LazyLock<Result<IntGauge>> = LazyLock::new(|| {
     try_create_int_gauge(
         "libp2p_tcp_peers",
@@ -93,6 +102,38 @@ pub static PEERS_PER_CLIENT: LazyLock<Result<IntGaugeVec>> = LazyLock::new(|| {
     )
 });
 
+pub static PEERS_PER_RPC_PROTOCOL: LazyLock<Result<IntGaugeVec>> = LazyLock::new(|| {
+    try_create_int_gauge_vec(
+        "libp2p_peers_per_rpc_protocol",
+        "The connected peers advertising each eth2 RPC protocol",
+        &["protocol"],
+    )
+});
+
+pub static PEERS_PER_GOSSIPSUB_VERSION: LazyLock<Result<IntGaugeVec>> = LazyLock::new(|| {
+    try_create_int_gauge_vec(
+        "libp2p_peers_per_gossipsub_version",
+        "The connected peers advertising each gossipsub version",
+        &["version"],
+    )
+});
+
+pub static PEERS_PER_CLIENT_QUIRK: LazyLock<Result<IntGaugeVec>> = LazyLock::new(|| {
+    try_create_int_gauge_vec(
+        "libp2p_peers_per_client_quirk",
+        "The connected peers exhibiting each deviation from the spec",
+        &["quirk"],
+    )
+});
+
+pub static PEERS_PER_SUBNET: LazyLock<Result<IntGaugeVec>> = LazyLock::new(|| {
+    try_create_int_gauge_vec(
+        "libp2p_peers_per_subnet",
+        "The current count of good peers on each subscribed subnet",
+        &["subnet_type", "subnet_id"],
+    )
+});
+
 pub static PEERS_PER_CUSTODY_SUBNET_COUNT: LazyLock<Result<IntGaugeVec>> = LazyLock::new(|| {
     try_create_int_gauge_vec(
         "peers_per_custody_subnet_count",
@@ -165,6 +206,21 @@ pub static GOSSIP_FAILED_LATE_PUBLISH_PER_TOPIC_KIND: LazyLock<Result<IntCounter
             &["topic_kind"],
         )
     });
+pub static GOSSIP_FIRST_DELIVERY_LEAD_TIME: LazyLock<Result<HistogramVec>> = LazyLock::new(|| {
+    try_create_histogram_vec_with_buckets(
+        "gossipsub_first_delivery_lead_time_seconds",
+        "Time between the first and second delivery of a gossip message by distinct peers.",
+        Ok(vec![0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.0, 4.0]),
+        &["kind"],
+    )
+});
+pub static PEER_STATUS_LATENCY: LazyLock<Result<Histogram>> = LazyLock::new(|| {
+    try_create_histogram_with_buckets(
+        "libp2p_peer_status_latency_seconds",
+        "Round-trip time of STATUS requests to peers.",
+        Ok(vec![0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.0, 5.0]),
+    )
+});
 pub static PEER_SCORE_DISTRIBUTION: LazyLock<Result<IntGaugeVec>> = LazyLock::new(|| {
     try_create_int_gauge_vec(
         "peer_score_distribution",
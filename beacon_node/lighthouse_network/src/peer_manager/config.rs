@@ -1,3 +1,5 @@
+use crate::config::TransportPreference;
+
 /// The time in seconds between re-status's peers.
 pub const DEFAULT_STATUS_INTERVAL: u64 = 300;
 
@@ -20,6 +22,8 @@ pub struct Config {
     pub metrics_enabled: bool,
     /// Whether quic is enabled.
     pub quic_enabled: bool,
+    /// The order in which transports are dialed.
+    pub transport_preference: TransportPreference,
     /// Target number of peers to connect to.
     pub target_peer_count: usize,
 
@@ -40,6 +44,7 @@ impl Default for Config {
             discovery_enabled: true,
             metrics_enabled: false,
             quic_enabled: true,
+            transport_preference: TransportPreference::default(),
             target_peer_count: DEFAULT_TARGET_PEERS,
             status_interval: DEFAULT_STATUS_INTERVAL,
             ping_interval_inbound: DEFAULT_PING_INTERVAL_INBOUND,
@@ -1,7 +1,8 @@
 //! Implementation of Lighthouse's peer management system.
 
+use crate::config::TransportPreference;
 use crate::discovery::enr_ext::EnrExt;
-use crate::discovery::peer_id_to_node_id;
+use crate::discovery::{peer_id_to_node_id, subnet_predicate};
 use crate::rpc::{GoodbyeReason, MetaData, Protocol, RPCError, RpcErrorResponse};
 use crate::service::TARGET_SUBNET_PEERS;
 use crate::{metrics, Gossipsub, NetworkGlobals, PeerId, Subnet, SubnetDiscovery};
@@ -24,7 +25,7 @@ pub use libp2p::identity::Keypair;
 
 pub mod peerdb;
 
-use crate::peer_manager::peerdb::client::ClientKind;
+use crate::peer_manager::peerdb::client::{ClientKind, ClientQuirk};
 use libp2p::multiaddr;
 pub use peerdb::peer_info::{
     ConnectionDirection, PeerConnectionStatus, PeerConnectionStatus::*, PeerInfo,
@@ -63,6 +64,10 @@ pub const MIN_OUTBOUND_ONLY_FACTOR: f32 = 0.2;
 /// limit is 55, and we are at 55 peers, the following parameter provisions a few more slots of
 /// dialing priority peers we need for validator duties.
 pub const PRIORITY_PEER_EXCESS: f32 = 0.2;
+/// The maximum number of subnets short of peers that are queried for each heartbeat. Those with
+/// the fewest peers are queried first, so that a node subscribed to many subnets doesn't
+/// continually flood the discovery queue.
+const MAX_SUBNET_DISCOVERIES_PER_HEARTBEAT: usize = 6;
 
 /// The main struct that handles peer's reputation and connection status.
 pub struct PeerManager<E: EthSpec> {
@@ -99,6 +104,9 @@ pub struct PeerManager<E: EthSpec> {
     /// discovery queries for subnet peers if we disconnect from existing sync
     /// committee subnet peers.
     sync_committee_subnets: HashMap<SyncSubnetId, Instant>,
+    /// The subscribed subnets on which we have fewer than `TARGET_SUBNET_PEERS` good peers. This
+    /// is refreshed every heartbeat and used to dial discovered peers on these subnets first.
+    subnets_needing_peers: HashSet<Subnet>,
     /// The heartbeat interval to perform routine maintenance.
     heartbeat: tokio::time::Interval,
     /// Keeps track of whether the discovery service is enabled or not.
@@ -107,6 +115,8 @@ pub struct PeerManager<E: EthSpec> {
     metrics_enabled: bool,
     /// Keeps track of whether the QUIC protocol is enabled or not.
     quic_enabled: bool,
+    /// The order in which transports are dialed.
+    transport_preference: TransportPreference,
     /// The logger associated with the `PeerManager`.
     log: slog::Logger,
 }
@@ -153,6 +163,7 @@ impl<E: EthSpec> PeerManager<E> {
             ping_interval_inbound,
             ping_interval_outbound,
             quic_enabled,
+            transport_preference,
         } = cfg;
 
         // Set up the peer manager heartbeat interval
@@ -168,10 +179,12 @@ impl<E: EthSpec> PeerManager<E> {
             target_peers: target_peer_count,
             temporary_banned_peers: LRUTimeCache::new(PEER_RECONNECTION_TIMEOUT),
             sync_committee_subnets: Default::default(),
+            subnets_needing_peers: Default::default(),
             heartbeat,
             discovery_enabled,
             metrics_enabled,
             quic_enabled,
+            transport_preference,
             log: log.clone(),
         })
     }
@@ -322,7 +335,9 @@ impl<E: EthSpec> PeerManager<E> {
         let mut to_dial_peers = 0;
         let results_count = results.len();
         let connected_or_dialing = self.network_globals.connected_or_dialing_peers();
-        for (enr, min_ttl) in results {
+        // Peers advertising subnets we are short of peers on are dialed first, so that they are
+        // not crowded out of the remaining connection slots by peers we have less use for.
+        for (enr, min_ttl) in self.order_by_needed_subnets(results) {
             // There are two conditions in deciding whether to dial this peer.
             // 1. If we are less than our max connections. Discovery queries are executed to reach
             //    our target peers, so its fine to dial up to our max peers (which will get pruned
@@ -372,6 +387,31 @@ impl<E: EthSpec> PeerManager<E> {
         self.status_peers.insert(*peer_id);
     }
 
+    /// A STATUS request has been sent to a peer. The time is recorded to measure the latency of
+    /// the peer's response.
+    pub fn status_request_sent(&mut self, peer_id: &PeerId) {
+        if let Some(peer_info) = self.network_globals.peers.write().peer_info_mut(peer_id) {
+            peer_info.on_status_request(Instant::now());
+        }
+    }
+
+    /// A STATUS response has been received from a peer. This updates the latency and score of the
+    /// peer and resets the status timer.
+    pub fn status_response(&mut self, peer_id: &PeerId) {
+        let (round_trip, score_action) = self
+            .network_globals
+            .peers
+            .write()
+            .status_response(peer_id, Instant::now());
+        if let Some(round_trip) = round_trip {
+            metrics::observe_duration(&metrics::PEER_STATUS_LATENCY, round_trip);
+            trace!(self.log, "Received STATUS response";
+                "peer_id" => %peer_id, "latency_ms" => round_trip.as_millis());
+        }
+        self.peer_statusd(peer_id);
+        self.handle_score_action(peer_id, score_action, None);
+    }
+
     /// Insert the sync subnet into list of long lived sync committee subnets that we need to
     /// maintain adequate number of peers for.
     pub fn add_sync_subnet(&mut self, subnet_id: SyncSubnetId, min_ttl: Instant) {
@@ -423,6 +463,12 @@ impl<E: EthSpec> PeerManager<E> {
     /// A peer is being dialed.
     /// Returns true, if this peer will be dialed.
     pub fn dial_peer(&mut self, peer: Enr) -> bool {
+        if self.dial_multiaddrs(&peer).is_empty() {
+            trace!(self.log, "Not dialing peer without an address for our transport preference";
+                "peer_id" => %peer.peer_id(), "transport_preference" => %self.transport_preference);
+            return false;
+        }
+
         if self
             .network_globals
             .peers
@@ -436,6 +482,22 @@ impl<E: EthSpec> PeerManager<E> {
         }
     }
 
+    /// Returns the addresses on which to dial `enr`, ordered according to our transport
+    /// preference. The swarm dials these addresses one at a time, so later addresses are only
+    /// used as a fallback.
+    fn dial_multiaddrs(&self, enr: &Enr) -> Vec<Multiaddr> {
+        let quic = if self.quic_enabled {
+            enr.multiaddr_quic()
+        } else {
+            vec![]
+        };
+        match self.transport_preference {
+            TransportPreference::QuicFirst => [quic, enr.multiaddr_tcp()].concat(),
+            TransportPreference::TcpFirst => [enr.multiaddr_tcp(), quic].concat(),
+            TransportPreference::QuicOnly => quic,
+        }
+    }
+
     /// Reports if a peer is banned or not.
     ///
     /// This is used to determine if we should accept incoming connections.
@@ -449,7 +511,8 @@ impl<E: EthSpec> PeerManager<E> {
 
     /// Updates `PeerInfo` with `identify` information.
     pub fn identify(&mut self, peer_id: &PeerId, info: &IdentifyInfo) {
-        if let Some(peer_info) = self.network_globals.peers.write().peer_info_mut(peer_id) {
+        let mut peers = self.network_globals.peers.write();
+        if let Some(peer_info) = peers.peer_info_mut(peer_id) {
             let previous_kind = peer_info.client().kind;
             let previous_listening_addresses =
                 peer_info.set_listening_addresses(info.listen_addrs.clone());
@@ -463,11 +526,18 @@ impl<E: EthSpec> PeerManager<E> {
                     "agent_version" => &info.agent_version,
                     "listening_addresses" => ?info.listen_addrs,
                     "observed_address" => ?info.observed_addr,
-                    "protocols" => ?info.protocols
+                    "protocols" => ?info.protocols,
+                    "quirks" => ?peer_info.client().quirks,
                 );
             }
         } else {
             error!(self.log, "Received an Identify response from an unknown peer"; "peer_id" => peer_id.to_string());
+            return;
+        }
+        drop(peers);
+
+        if self.metrics_enabled {
+            self.update_peer_count_metrics();
         }
     }
 
@@ -552,6 +622,8 @@ impl<E: EthSpec> PeerManager<E> {
                     Protocol::Ping => PeerAction::MidToleranceError,
                     Protocol::BlocksByRange => PeerAction::MidToleranceError,
                     Protocol::BlocksByRoot => PeerAction::MidToleranceError,
+                    Protocol::BlindedBlocksByRange => PeerAction::MidToleranceError,
+                    Protocol::ExecutionPayloadBodiesByRange => PeerAction::MidToleranceError,
                     Protocol::BlobsByRange => PeerAction::MidToleranceError,
                     // Lighthouse does not currently make light client requests; therefore, this
                     // is an unexpected scenario. We do not ban the peer for rate limiting.
@@ -578,6 +650,9 @@ impl<E: EthSpec> PeerManager<E> {
                     Protocol::Ping => PeerAction::Fatal,
                     Protocol::BlocksByRange => return,
                     Protocol::BlocksByRoot => return,
+                    // Only Lighthouse nodes that opt in serve blinded blocks and payload bodies.
+                    Protocol::BlindedBlocksByRange => return,
+                    Protocol::ExecutionPayloadBodiesByRange => return,
                     Protocol::BlobsByRange => return,
                     Protocol::BlobsByRoot => return,
                     Protocol::DataColumnsByRoot => return,
@@ -601,6 +676,8 @@ impl<E: EthSpec> PeerManager<E> {
                     Protocol::Ping => PeerAction::LowToleranceError,
                     Protocol::BlocksByRange => PeerAction::MidToleranceError,
                     Protocol::BlocksByRoot => PeerAction::MidToleranceError,
+                    Protocol::BlindedBlocksByRange => PeerAction::MidToleranceError,
+                    Protocol::ExecutionPayloadBodiesByRange => PeerAction::MidToleranceError,
                     Protocol::BlobsByRange => PeerAction::MidToleranceError,
                     Protocol::BlobsByRoot => PeerAction::MidToleranceError,
                     Protocol::DataColumnsByRoot => PeerAction::MidToleranceError,
@@ -903,6 +980,104 @@ impl<E: EthSpec> PeerManager<E> {
         }
     }
 
+    /// Determines which of our subscribed attestation, sync committee and data column subnets
+    /// have fewer than `TARGET_SUBNET_PEERS` good peers and requests discovery queries for them.
+    ///
+    /// At most `MAX_SUBNET_DISCOVERIES_PER_HEARTBEAT` subnets are queried, those with the fewest
+    /// peers first. The deficient subnets are retained so that discovered peers advertising them
+    /// are dialed ahead of others.
+    fn maintain_subnet_peers(&mut self) {
+        let subscribed_subnets: HashSet<Subnet> = self
+            .network_globals
+            .gossipsub_subscriptions
+            .read()
+            .iter()
+            .filter_map(|topic| topic.subnet_id())
+            .collect();
+
+        if self.metrics_enabled {
+            let _ = metrics::PEERS_PER_SUBNET
+                .as_ref()
+                .map(|gauge| gauge.reset());
+        }
+
+        let mut subnets_needing_peers = vec![];
+        {
+            let peers = self.network_globals.peers.read();
+            for subnet in subscribed_subnets {
+                let peer_count = peers.good_peers_on_subnet(subnet).count();
+                if self.metrics_enabled {
+                    let (subnet_type, subnet_id) = match subnet {
+                        Subnet::Attestation(id) => ("attestation", (*id).to_string()),
+                        Subnet::SyncCommittee(id) => ("sync_committee", (*id).to_string()),
+                        Subnet::DataColumn(id) => ("data_column", (*id).to_string()),
+                    };
+                    metrics::set_gauge_vec(
+                        &metrics::PEERS_PER_SUBNET,
+                        &[subnet_type, &subnet_id],
+                        peer_count as i64,
+                    );
+                }
+                if peer_count < TARGET_SUBNET_PEERS {
+                    subnets_needing_peers.push((subnet, peer_count));
+                }
+            }
+        }
+
+        // Sync committee subnets required by our validators are already queried along with their
+        // `min_ttl` by `maintain_sync_committee_peers`.
+        subnets_needing_peers.sort_by_key(|(_, peer_count)| *peer_count);
+        let subnets_to_discover: Vec<SubnetDiscovery> = subnets_needing_peers
+            .iter()
+            .filter(|(subnet, _)| match subnet {
+                Subnet::SyncCommittee(id) => !self.sync_committee_subnets.contains_key(id),
+                Subnet::Attestation(_) | Subnet::DataColumn(_) => true,
+            })
+            .take(MAX_SUBNET_DISCOVERIES_PER_HEARTBEAT)
+            .map(|(subnet, _)| SubnetDiscovery {
+                subnet: *subnet,
+                min_ttl: None,
+            })
+            .collect();
+
+        if !subnets_to_discover.is_empty() {
+            debug!(
+                self.log,
+                "Making subnet queries for subnets short of peers";
+                "subnets" => ?subnets_to_discover.iter().map(|s| s.subnet).collect::<Vec<_>>()
+            );
+            self.events
+                .push(PeerManagerEvent::DiscoverSubnetPeers(subnets_to_discover));
+        }
+
+        self.subnets_needing_peers = subnets_needing_peers
+            .into_iter()
+            .map(|(subnet, _)| subnet)
+            .collect();
+    }
+
+    /// Orders discovery results such that ENRs advertising any of the subnets we are short of
+    /// peers on come first.
+    fn order_by_needed_subnets(
+        &self,
+        results: HashMap<Enr, Option<Instant>>,
+    ) -> Vec<(Enr, Option<Instant>)> {
+        if self.subnets_needing_peers.is_empty() {
+            return results.into_iter().collect();
+        }
+
+        let on_needed_subnet = subnet_predicate::<E>(
+            self.subnets_needing_peers.iter().copied().collect(),
+            &self.log,
+            self.network_globals.spec.clone(),
+        );
+        let (mut ordered, other): (Vec<_>, Vec<_>) = results
+            .into_iter()
+            .partition(|(enr, _)| on_needed_subnet(enr));
+        ordered.extend(other);
+        ordered
+    }
+
     /// This function checks the status of our current peers and optionally requests a discovery
     /// query if we need to find more peers to maintain the current number of peers
     fn maintain_peer_count(&mut self, dialing_peers: usize) {
@@ -1214,6 +1389,9 @@ impl<E: EthSpec> PeerManager<E> {
         // Maintain minimum count for sync committee peers.
         self.maintain_sync_committee_peers();
 
+        // Search for peers on any subscribed subnets we are short of peers on.
+        self.maintain_subnet_peers();
+
         // Prune any excess peers back to our target in such a way that incentivises good scores and
         // a uniform distribution of subnets.
         self.prune_excess_peers();
@@ -1232,6 +1410,7 @@ impl<E: EthSpec> PeerManager<E> {
         self.status_peers.shrink_to(5);
         self.temporary_banned_peers.shrink_to_fit();
         self.sync_committee_subnets.shrink_to_fit();
+        self.subnets_needing_peers.shrink_to_fit();
     }
 
     // Update metrics related to peer scoring.
@@ -1369,6 +1548,33 @@ impl<E: EthSpec> PeerManager<E> {
             );
         }
 
+        // PEERS_PER_RPC_PROTOCOL, PEERS_PER_GOSSIPSUB_VERSION and PEERS_PER_CLIENT_QUIRK
+        let diversity = self.network_globals.peers.read().client_diversity();
+        for gauge in [
+            &metrics::PEERS_PER_RPC_PROTOCOL,
+            &metrics::PEERS_PER_GOSSIPSUB_VERSION,
+        ] {
+            let _ = gauge.as_ref().map(|gauge| gauge.reset());
+        }
+        for (protocol, peers) in &diversity.rpc_protocols {
+            metrics::set_gauge_vec(&metrics::PEERS_PER_RPC_PROTOCOL, &[protocol], *peers as i64);
+        }
+        for (version, peers) in &diversity.gossipsub_versions {
+            metrics::set_gauge_vec(
+                &metrics::PEERS_PER_GOSSIPSUB_VERSION,
+                &[version],
+                *peers as i64,
+            );
+        }
+        for quirk in ClientQuirk::iter() {
+            let peers = diversity.quirks.get(quirk.as_ref()).copied().unwrap_or(0);
+            metrics::set_gauge_vec(
+                &metrics::PEERS_PER_CLIENT_QUIRK,
+                &[quirk.as_ref()],
+                peers as i64,
+            );
+        }
+
         // PEERS_CONNECTED_MULTI
         for direction in ["inbound", "outbound", "none"] {
             for transport in ["quic", "tcp", "unknown"] {
@@ -1571,6 +1777,62 @@ mod tests {
         assert_eq!(peer_manager.network_globals.connected_or_dialing_peers(), 3);
     }
 
+    #[tokio::test]
+    async fn test_peer_manager_tracks_status_latency() {
+        let mut peer_manager = build_peer_manager(3).await;
+        let peer = PeerId::random();
+        peer_manager.inject_connect_ingoing(&peer, "/ip4/0.0.0.0".parse().unwrap(), None);
+
+        let status_latency = |peer_manager: &PeerManager<E>| {
+            peer_manager
+                .network_globals
+                .peers
+                .read()
+                .peer_info(&peer)
+                .unwrap()
+                .status_latency()
+        };
+
+        // A response without a pending request isn't measured.
+        peer_manager.status_response(&peer);
+        assert_eq!(status_latency(&peer_manager), None);
+
+        peer_manager.status_request_sent(&peer);
+        std::thread::sleep(Duration::from_millis(20));
+        peer_manager.status_response(&peer);
+        let latency = status_latency(&peer_manager).unwrap();
+        assert!(latency >= Duration::from_millis(20));
+
+        // A subsequent fast response only moves the latency part of the way.
+        peer_manager.status_request_sent(&peer);
+        peer_manager.status_response(&peer);
+        let smoothed = status_latency(&peer_manager).unwrap();
+        assert!(smoothed < latency);
+        assert!(smoothed >= latency * 3 / 4);
+
+        // The pending request is consumed by the response.
+        peer_manager.status_response(&peer);
+        assert_eq!(status_latency(&peer_manager), Some(smoothed));
+    }
+
+    #[tokio::test]
+    async fn test_peer_manager_prefers_low_latency_peers_by_score() {
+        let mut peer_manager = build_peer_manager(3).await;
+        let fast_peer = PeerId::random();
+        let slow_peer = PeerId::random();
+        for peer in [&fast_peer, &slow_peer] {
+            peer_manager.inject_connect_ingoing(peer, "/ip4/0.0.0.0".parse().unwrap(), None);
+            peer_manager.status_request_sent(peer);
+        }
+        peer_manager.status_response(&fast_peer);
+        std::thread::sleep(Duration::from_millis(50));
+        peer_manager.status_response(&slow_peer);
+
+        let score = |peer| peer_manager.network_globals.peers.read().score(peer);
+        assert!(score(&fast_peer) > score(&slow_peer));
+        assert!(score(&slow_peer) > 0.0);
+    }
+
     #[tokio::test]
     async fn test_peer_manager_not_enough_outbound_peers_no_panic_during_heartbeat() {
         let mut peer_manager = build_peer_manager(20).await;
@@ -2307,6 +2569,211 @@ mod tests {
         assert!(connected_peers.contains(&peers[7]));
     }
 
+    /// Test that discovery queries are requested for subscribed subnets we are short of peers on,
+    /// excluding the sync committee subnets that are already maintained for our validators.
+    #[tokio::test]
+    async fn test_peer_manager_discovers_subnets_short_of_peers() {
+        use crate::types::{GossipEncoding, GossipTopic};
+        use types::SubnetId;
+
+        let mut peer_manager = build_peer_manager(3).await;
+
+        let attestation_subnet = Subnet::Attestation(SubnetId::new(3));
+        let sync_subnet = Subnet::SyncCommittee(SyncSubnetId::new(1));
+        let duty_sync_subnet_id = SyncSubnetId::new(2);
+        peer_manager
+            .network_globals
+            .gossipsub_subscriptions
+            .write()
+            .extend(
+                [
+                    attestation_subnet,
+                    sync_subnet,
+                    Subnet::SyncCommittee(duty_sync_subnet_id),
+                ]
+                .into_iter()
+                .map(|subnet| GossipTopic::new(subnet.into(), GossipEncoding::default(), [0; 4])),
+            );
+        peer_manager.add_sync_subnet(
+            duty_sync_subnet_id,
+            Instant::now() + Duration::from_secs(60),
+        );
+        peer_manager.events.clear();
+
+        peer_manager.maintain_subnet_peers();
+
+        let discovered_subnets: HashSet<Subnet> = peer_manager
+            .events
+            .iter()
+            .filter_map(|event| match event {
+                PeerManagerEvent::DiscoverSubnetPeers(subnets) => Some(subnets),
+                _ => None,
+            })
+            .flatten()
+            .map(|discovery| discovery.subnet)
+            .collect();
+        assert_eq!(
+            discovered_subnets,
+            HashSet::from([attestation_subnet, sync_subnet])
+        );
+        assert_eq!(
+            peer_manager.subnets_needing_peers,
+            HashSet::from([
+                attestation_subnet,
+                sync_subnet,
+                Subnet::SyncCommittee(duty_sync_subnet_id),
+            ])
+        );
+    }
+
+    /// Test that the number of subnet discovery queries made each heartbeat is bounded, preferring
+    /// the subnets with the fewest peers.
+    #[tokio::test]
+    async fn test_peer_manager_caps_subnet_discoveries() {
+        use crate::types::{GossipEncoding, GossipTopic};
+        use types::SubnetId;
+
+        let mut peer_manager = build_peer_manager(3).await;
+
+        let subnets: Vec<Subnet> = (0..MAX_SUBNET_DISCOVERIES_PER_HEARTBEAT as u64 + 2)
+            .map(|id| Subnet::Attestation(SubnetId::new(id)))
+            .collect();
+        peer_manager
+            .network_globals
+            .gossipsub_subscriptions
+            .write()
+            .extend(subnets.iter().map(|subnet| {
+                GossipTopic::new((*subnet).into(), GossipEncoding::default(), [0; 4])
+            }));
+
+        // The first two subnets have a long-lived peer, the others have none.
+        for id in 0..2 {
+            let peer = PeerId::random();
+            peer_manager.inject_connect_ingoing(&peer, "/ip4/0.0.0.0".parse().unwrap(), None);
+            let mut attnets = crate::types::EnrAttestationBitfield::<E>::new();
+            attnets.set(id, true).unwrap();
+            let metadata = crate::rpc::MetaDataV2 {
+                seq_number: 0,
+                attnets,
+                syncnets: Default::default(),
+            };
+            let mut peers = peer_manager.network_globals.peers.write();
+            peers
+                .peer_info_mut(&peer)
+                .unwrap()
+                .set_meta_data(MetaData::V2(metadata));
+            peers.add_subscription(&peer, subnets[id]);
+        }
+        peer_manager.events.clear();
+
+        peer_manager.maintain_subnet_peers();
+
+        let discovered_subnets: HashSet<Subnet> = peer_manager
+            .events
+            .iter()
+            .filter_map(|event| match event {
+                PeerManagerEvent::DiscoverSubnetPeers(subnets) => Some(subnets),
+                _ => None,
+            })
+            .flatten()
+            .map(|discovery| discovery.subnet)
+            .collect();
+        assert_eq!(
+            discovered_subnets,
+            subnets[2..].iter().copied().collect::<HashSet<_>>()
+        );
+        assert_eq!(
+            peer_manager.subnets_needing_peers,
+            subnets.iter().copied().collect::<HashSet<_>>()
+        );
+    }
+
+    /// Test that discovered peers on the subnets we are short of peers on are dialed first.
+    #[tokio::test]
+    async fn test_order_by_needed_subnets() {
+        use crate::discovery::enr::ATTESTATION_BITFIELD_ENR_KEY;
+        use crate::types::EnrAttestationBitfield;
+        use alloy_rlp::bytes::Bytes;
+        use discv5::enr::CombinedKey;
+        use ssz::Encode;
+        use types::SubnetId;
+
+        let mut peer_manager = build_peer_manager(3).await;
+
+        let make_enr = |subnet_id: Option<usize>| {
+            let mut bitfield = EnrAttestationBitfield::<E>::new();
+            if let Some(id) = subnet_id {
+                bitfield.set(id, true).unwrap();
+            }
+            Enr::builder()
+                .add_value::<Bytes>(
+                    ATTESTATION_BITFIELD_ENR_KEY,
+                    &bitfield.as_ssz_bytes().into(),
+                )
+                .build(&CombinedKey::generate_secp256k1())
+                .unwrap()
+        };
+        let other_enrs = [make_enr(None), make_enr(Some(2)), make_enr(None)];
+        let needed_enr = make_enr(Some(1));
+        let results: HashMap<Enr, Option<Instant>> = other_enrs
+            .iter()
+            .chain([&needed_enr])
+            .map(|enr| (enr.clone(), None))
+            .collect();
+
+        // Without any subnets short of peers, all results are returned.
+        assert_eq!(
+            peer_manager.order_by_needed_subnets(results.clone()).len(),
+            results.len()
+        );
+
+        peer_manager.subnets_needing_peers = HashSet::from([Subnet::Attestation(SubnetId::new(1))]);
+        let ordered = peer_manager.order_by_needed_subnets(results.clone());
+        assert_eq!(ordered.len(), results.len());
+        assert_eq!(ordered[0].0, needed_enr);
+    }
+
+    #[tokio::test]
+    async fn test_dial_multiaddrs_follow_transport_preference() {
+        use crate::discovery::enr_ext::QUIC_ENR_KEY;
+        use discv5::enr::CombinedKey;
+        use std::net::Ipv4Addr;
+
+        let mut peer_manager = build_peer_manager(3).await;
+
+        let quic_and_tcp_enr = Enr::builder()
+            .ip4(Ipv4Addr::LOCALHOST)
+            .tcp4(9000)
+            .add_value(QUIC_ENR_KEY, &9001u16)
+            .build(&CombinedKey::generate_secp256k1())
+            .unwrap();
+        let tcp_only_enr = Enr::builder()
+            .ip4(Ipv4Addr::LOCALHOST)
+            .tcp4(9000)
+            .build(&CombinedKey::generate_secp256k1())
+            .unwrap();
+        let quic = quic_and_tcp_enr.multiaddr_quic();
+        let tcp = quic_and_tcp_enr.multiaddr_tcp();
+
+        peer_manager.transport_preference = TransportPreference::QuicFirst;
+        assert_eq!(
+            peer_manager.dial_multiaddrs(&quic_and_tcp_enr),
+            [quic.clone(), tcp.clone()].concat()
+        );
+
+        peer_manager.transport_preference = TransportPreference::TcpFirst;
+        assert_eq!(
+            peer_manager.dial_multiaddrs(&quic_and_tcp_enr),
+            [tcp.clone(), quic.clone()].concat()
+        );
+
+        peer_manager.transport_preference = TransportPreference::QuicOnly;
+        assert_eq!(peer_manager.dial_multiaddrs(&quic_and_tcp_enr), quic);
+        assert!(peer_manager.dial_multiaddrs(&tcp_only_enr).is_empty());
+        assert!(!peer_manager.dial_peer(tcp_only_enr));
+        assert!(peer_manager.dial_peer(quic_and_tcp_enr));
+    }
+
     // Test properties PeerManager should have using randomly generated input.
     #[cfg(test)]
     mod property_based_tests {
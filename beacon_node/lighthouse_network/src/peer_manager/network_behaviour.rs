@@ -97,14 +97,7 @@ impl<E: EthSpec> NetworkBehaviour for PeerManager<E> {
         if let Some(enr) = self.peers_to_dial.pop() {
             self.inject_peer_connection(&enr.peer_id(), ConnectingType::Dialing, Some(enr.clone()));
 
-            // Prioritize Quic connections over Tcp ones.
-            let multiaddrs = [
-                self.quic_enabled
-                    .then_some(enr.multiaddr_quic())
-                    .unwrap_or_default(),
-                enr.multiaddr_tcp(),
-            ]
-            .concat();
+            let multiaddrs = self.dial_multiaddrs(&enr);
 
             debug!(self.log, "Dialing peer"; "peer_id"=> %enr.peer_id(), "multiaddrs" => ?multiaddrs);
             return Poll::Ready(ToSwarm::Dial {
@@ -264,6 +257,24 @@ impl<E: EthSpec> PeerManager<E> {
         if self.metrics_enabled {
             metrics::inc_counter(&metrics::PEER_CONNECT_EVENT_COUNT);
 
+            let direction = match endpoint {
+                ConnectedPoint::Listener { .. } => "inbound",
+                ConnectedPoint::Dialer { .. } => "outbound",
+            };
+            let transport = endpoint
+                .get_remote_address()
+                .iter()
+                .find_map(|proto| match proto {
+                    Protocol::QuicV1 => Some("quic"),
+                    Protocol::Tcp(_) => Some("tcp"),
+                    _ => None,
+                })
+                .unwrap_or("unknown");
+            metrics::inc_counter_vec(
+                &metrics::CONNECTIONS_ESTABLISHED_PER_TRANSPORT,
+                &[direction, transport],
+            );
+
             self.update_peer_count_metrics();
         }
 
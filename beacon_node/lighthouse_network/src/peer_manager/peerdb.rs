@@ -1,12 +1,14 @@
 use crate::discovery::enr::PEERDAS_CUSTODY_SUBNET_COUNT_ENR_KEY;
 use crate::discovery::{peer_id_to_node_id, CombinedKey};
-use crate::{metrics, multiaddr::Multiaddr, types::Subnet, Enr, EnrExt, Gossipsub, PeerId};
+use crate::types::{ExportedPeer, Subnet};
+use crate::{metrics, multiaddr::Multiaddr, Enr, EnrExt, Gossipsub, PeerId};
+use client::ClientDiversity;
 use itertools::Itertools;
 use peer_info::{ConnectionDirection, PeerConnectionStatus, PeerInfo};
 use score::{PeerAction, ReportSource, Score, ScoreState};
 use slog::{crit, debug, error, trace, warn};
 use std::net::IpAddr;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use std::{cmp::Ordering, fmt::Display};
 use std::{
     collections::{HashMap, HashSet},
@@ -183,6 +185,15 @@ impl<E: EthSpec> PeerDB<E> {
         self.peers.iter().filter(|(_, info)| info.is_connected())
     }
 
+    /// Returns a breakdown of the clients, capabilities and quirks of the connected peers.
+    pub fn client_diversity(&self) -> ClientDiversity {
+        let mut diversity = ClientDiversity::default();
+        for (_, info) in self.connected_peers() {
+            diversity.add(info.client());
+        }
+        diversity
+    }
+
     /// Gives the ids of all known connected peers.
     pub fn connected_peer_ids(&self) -> impl Iterator<Item = &PeerId> {
         self.peers
@@ -310,6 +321,26 @@ impl<E: EthSpec> PeerDB<E> {
             .collect::<Vec<_>>()
     }
 
+    /// Returns the peers with a known ENR which aren't banned, sorted by score from highest to
+    /// lowest, in a form which can be imported by another node.
+    pub fn exported_peers(&self) -> Vec<ExportedPeer> {
+        self.best_peers_by_status(|info| !info.is_banned() && !info.score_is_banned())
+            .into_iter()
+            .filter_map(|(peer_id, info)| {
+                Some(ExportedPeer {
+                    peer_id: peer_id.to_string(),
+                    enr: info.enr()?.clone(),
+                    score: info.score().score(),
+                    custody_subnets: info
+                        .custody_subnets()
+                        .map(|subnet| **subnet)
+                        .sorted()
+                        .collect(),
+                })
+            })
+            .collect()
+    }
+
     /// Returns the peer with highest reputation that satisfies `is_status`
     pub fn best_by_status<F>(&self, is_status: F) -> Option<&PeerId>
     where
@@ -470,38 +501,7 @@ impl<E: EthSpec> PeerDB<E> {
         }
 
         for (peer_id, action) in actions {
-            let result = match action {
-                ScoreTransitionResult::Banned => {
-                    // The peer was banned as a result of this action.
-                    self.update_connection_state(&peer_id, NewConnectionState::Banned)
-                        .into()
-                }
-                ScoreTransitionResult::Disconnected => {
-                    // The peer needs to be disconnected
-
-                    // Update the state
-                    self.update_connection_state(
-                        &peer_id,
-                        NewConnectionState::Disconnecting { to_ban: false },
-                    );
-                    ScoreUpdateResult::Disconnect
-                }
-                ScoreTransitionResult::NoAction => ScoreUpdateResult::NoAction,
-                ScoreTransitionResult::Unbanned => {
-                    self.update_connection_state(&peer_id, NewConnectionState::Unbanned);
-                    let seen_ip_addresses = self
-                        .peers
-                        .get(&peer_id)
-                        .map(|info| {
-                            info.seen_ip_addresses()
-                                .filter(|ip| !self.is_ip_banned(ip))
-                                .collect::<Vec<_>>()
-                        })
-                        .unwrap_or_default();
-
-                    ScoreUpdateResult::Unbanned(seen_ip_addresses)
-                }
-            };
+            let result = self.apply_score_transition(&peer_id, action);
 
             // Actions to be handled by the peer manager for each peer id
             if !matches!(result, ScoreUpdateResult::NoAction) {
@@ -511,6 +511,63 @@ impl<E: EthSpec> PeerDB<E> {
         results
     }
 
+    /// Records the response of a peer to our pending STATUS request and updates the peer's score
+    /// with its latency. Returns the round-trip time of the request, if one was pending.
+    #[must_use = "Score updates need to be reported to libp2p"]
+    pub(super) fn status_response(
+        &mut self,
+        peer_id: &PeerId,
+        received: Instant,
+    ) -> (Option<Duration>, ScoreUpdateResult) {
+        let Some(info) = self.peers.get_mut(peer_id) else {
+            return (None, ScoreUpdateResult::NoAction);
+        };
+        let previous_state = info.score_state();
+        let round_trip = info.on_status_response(received);
+        let action = Self::handle_score_transition(previous_state, peer_id, info, &self.log);
+        (round_trip, self.apply_score_transition(peer_id, action))
+    }
+
+    /// Updates the connection state of a peer after its score has transitioned.
+    fn apply_score_transition(
+        &mut self,
+        peer_id: &PeerId,
+        action: ScoreTransitionResult,
+    ) -> ScoreUpdateResult {
+        match action {
+            ScoreTransitionResult::Banned => {
+                // The peer was banned as a result of this action.
+                self.update_connection_state(peer_id, NewConnectionState::Banned)
+                    .into()
+            }
+            ScoreTransitionResult::Disconnected => {
+                // The peer needs to be disconnected
+
+                // Update the state
+                self.update_connection_state(
+                    peer_id,
+                    NewConnectionState::Disconnecting { to_ban: false },
+                );
+                ScoreUpdateResult::Disconnect
+            }
+            ScoreTransitionResult::NoAction => ScoreUpdateResult::NoAction,
+            ScoreTransitionResult::Unbanned => {
+                self.update_connection_state(peer_id, NewConnectionState::Unbanned);
+                let seen_ip_addresses = self
+                    .peers
+                    .get(peer_id)
+                    .map(|info| {
+                        info.seen_ip_addresses()
+                            .filter(|ip| !self.is_ip_banned(ip))
+                            .collect::<Vec<_>>()
+                    })
+                    .unwrap_or_default();
+
+                ScoreUpdateResult::Unbanned(seen_ip_addresses)
+            }
+        }
+    }
+
     /// Reports a peer for some action.
     ///
     /// The action can only cause a negative effect. This can lead to disconnecting or banning a
@@ -1394,6 +1451,38 @@ mod tests {
         assert_eq!(pdb.connected_outbound_only_peers().count(), 1);
     }
 
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_exported_peers() {
+        let mut pdb = get_db();
+        let spec = M::default_spec();
+
+        let p0 = pdb.__add_connected_peer_testing_only(false, &spec);
+        let p1 = pdb.__add_connected_peer_testing_only(true, &spec);
+        let p2 = pdb.__add_connected_peer_testing_only(false, &spec);
+        // Peers without a known ENR can't be exported.
+        let p3 = PeerId::random();
+        pdb.connect_ingoing(&p3, "/ip4/0.0.0.0".parse().unwrap(), None);
+
+        add_score(&mut pdb, &p0, -10.0);
+        add_score(&mut pdb, &p1, 10.0);
+        add_score(&mut pdb, &p2, -100.0);
+
+        let exported = pdb.exported_peers();
+        let exported_ids = exported
+            .iter()
+            .map(|peer| peer.peer_id.clone())
+            .collect::<Vec<_>>();
+        // Peers are sorted best first and banned peers are excluded.
+        assert_eq!(exported_ids, vec![p1.to_string(), p0.to_string()]);
+        assert_eq!(exported[0].enr.peer_id(), p1);
+        assert_eq!(
+            exported[0].custody_subnets,
+            (0..spec.data_column_sidecar_subnet_count).collect::<Vec<_>>()
+        );
+        assert_eq!(exported[1].score, pdb.score(&p0));
+    }
+
     #[test]
     fn test_disconnected_removed_in_correct_order() {
         let mut pdb = get_db();
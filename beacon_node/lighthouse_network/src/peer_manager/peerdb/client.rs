@@ -2,10 +2,20 @@
 //!
 //! Currently using identify to fingerprint.
 
+use crate::rpc::Protocol;
 use libp2p::identify::Info as IdentifyInfo;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::str::FromStr;
 use strum::{AsRefStr, EnumIter, IntoStaticStr};
 
+/// The prefix of the eth2 RPC protocol ids advertised via identify.
+const RPC_PROTOCOL_PREFIX: &str = "/eth2/beacon_chain/req/";
+/// The prefix of the gossipsub protocol ids advertised via identify.
+const GOSSIPSUB_PROTOCOL_PREFIX: &str = "/meshsub/";
+/// The gossipsub versions which are recognised when fingerprinting.
+const KNOWN_GOSSIPSUB_VERSIONS: &[&str] = &["1.0.0", "1.1.0", "1.2.0"];
+
 /// Various client and protocol information related to a node.
 #[derive(Clone, Debug, Serialize)]
 pub struct Client {
@@ -19,6 +29,32 @@ pub struct Client {
     pub protocol_version: String,
     /// Identify agent string
     pub agent_string: Option<String>,
+    /// The eth2 RPC protocols advertised by the client, as `<name>/<version>`.
+    pub rpc_protocols: Vec<String>,
+    /// The gossipsub versions advertised by the client.
+    pub gossipsub_versions: Vec<String>,
+    /// Deviations from the spec observed in the client's identification.
+    pub quirks: Vec<ClientQuirk>,
+}
+
+/// A deviation from the spec, or from what is expected of a well-behaved client, observed when
+/// identifying a peer.
+#[derive(
+    Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, AsRefStr, IntoStaticStr, EnumIter,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ClientQuirk {
+    /// The peer sent an empty agent string.
+    MissingAgentVersion,
+    /// The peer's agent string does not match any known client.
+    UnrecognisedAgentVersion,
+    /// The peer does not advertise the `status` protocol, which all peers must support.
+    MissingStatusProtocol,
+    /// The peer does not advertise `metadata/2`, which all peers must support since Altair.
+    MissingMetaDataV2,
+    /// The peer does not advertise gossipsub v1.1 or later.
+    LegacyGossipsub,
 }
 
 #[derive(Clone, Copy, Debug, Serialize, PartialEq, AsRefStr, IntoStaticStr, EnumIter)]
@@ -47,6 +83,9 @@ impl Default for Client {
             os_version: "unknown".into(),
             protocol_version: "unknown".into(),
             agent_string: None,
+            rpc_protocols: vec![],
+            gossipsub_versions: vec![],
+            quirks: vec![],
         }
     }
 }
@@ -56,12 +95,107 @@ impl Client {
     pub fn from_identify_info(info: &IdentifyInfo) -> Self {
         let (kind, version, os_version) = client_from_agent_version(&info.agent_version);
 
+        let mut rpc_protocols = vec![];
+        let mut gossipsub_versions = vec![];
+        for protocol in &info.protocols {
+            let protocol = protocol.as_ref();
+            if let Some(rpc_protocol) = parse_rpc_protocol(protocol) {
+                rpc_protocols.push(rpc_protocol);
+            } else if let Some(version) = protocol
+                .strip_prefix(GOSSIPSUB_PROTOCOL_PREFIX)
+                .filter(|version| KNOWN_GOSSIPSUB_VERSIONS.contains(version))
+            {
+                gossipsub_versions.push(version.to_string());
+            }
+        }
+        rpc_protocols.sort();
+        rpc_protocols.dedup();
+        gossipsub_versions.sort();
+        gossipsub_versions.dedup();
+
+        let mut quirks = vec![];
+        if info.agent_version.is_empty() {
+            quirks.push(ClientQuirk::MissingAgentVersion);
+        } else if kind == ClientKind::Unknown {
+            quirks.push(ClientQuirk::UnrecognisedAgentVersion);
+        }
+        let supports = |name: Protocol, version: &str| {
+            rpc_protocols.contains(&format!("{}/{}", name, version))
+        };
+        if !supports(Protocol::Status, "1") {
+            quirks.push(ClientQuirk::MissingStatusProtocol);
+        }
+        if !supports(Protocol::MetaData, "2") {
+            quirks.push(ClientQuirk::MissingMetaDataV2);
+        }
+        if gossipsub_versions.iter().all(|version| version == "1.0.0") {
+            quirks.push(ClientQuirk::LegacyGossipsub);
+        }
+
         Client {
             kind,
             version,
             os_version,
             protocol_version: info.protocol_version.clone(),
             agent_string: Some(info.agent_version.clone()),
+            rpc_protocols,
+            gossipsub_versions,
+            quirks,
+        }
+    }
+}
+
+/// Parses an eth2 RPC protocol id into `<name>/<version>`, returning `None` if the protocol is
+/// not one we recognise. Only recognised protocols are kept so that peers cannot create an
+/// unbounded number of metric labels.
+fn parse_rpc_protocol(protocol: &str) -> Option<String> {
+    let mut parts = protocol.strip_prefix(RPC_PROTOCOL_PREFIX)?.split('/');
+    let name = Protocol::from_str(parts.next()?).ok()?;
+    let version = parts
+        .next()
+        .filter(|version| version.len() <= 2 && version.chars().all(|c| c.is_ascii_digit()))?;
+    Some(format!("{}/{}", name, version))
+}
+
+/// The number of connected peers running each version of a client.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ClientVersions {
+    pub peers: usize,
+    pub versions: BTreeMap<String, usize>,
+}
+
+/// A breakdown of the clients, capabilities and quirks of the connected peers.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ClientDiversity {
+    /// The number of connected peers.
+    pub connected_peers: usize,
+    /// The connected peers by client.
+    pub clients: BTreeMap<String, ClientVersions>,
+    /// The number of connected peers serving each eth2 RPC protocol.
+    pub rpc_protocols: BTreeMap<String, usize>,
+    /// The number of connected peers supporting each gossipsub version.
+    pub gossipsub_versions: BTreeMap<String, usize>,
+    /// The number of connected peers exhibiting each quirk.
+    pub quirks: BTreeMap<String, usize>,
+}
+
+impl ClientDiversity {
+    /// Adds a connected peer running `client` to the breakdown.
+    pub fn add(&mut self, client: &Client) {
+        self.connected_peers += 1;
+
+        let versions = self.clients.entry(client.kind.to_string()).or_default();
+        versions.peers += 1;
+        *versions.versions.entry(client.version.clone()).or_default() += 1;
+
+        for protocol in &client.rpc_protocols {
+            *self.rpc_protocols.entry(protocol.clone()).or_default() += 1;
+        }
+        for version in &client.gossipsub_versions {
+            *self.gossipsub_versions.entry(version.clone()).or_default() += 1;
+        }
+        for quirk in &client.quirks {
+            *self.quirks.entry(quirk.as_ref().to_string()).or_default() += 1;
         }
     }
 }
@@ -199,3 +333,84 @@ fn client_from_agent_version(agent_version: &str) -> (ClientKind, String, String
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libp2p::identity::Keypair;
+    use libp2p::{Multiaddr, StreamProtocol};
+
+    fn identify_info(agent_version: &str, protocols: &[&'static str]) -> IdentifyInfo {
+        IdentifyInfo {
+            public_key: Keypair::generate_secp256k1().public(),
+            protocol_version: "eth2/1.0.0".into(),
+            agent_version: agent_version.into(),
+            listen_addrs: vec![],
+            protocols: protocols.iter().copied().map(StreamProtocol::new).collect(),
+            observed_addr: Multiaddr::empty(),
+        }
+    }
+
+    #[test]
+    fn fingerprints_protocols_and_quirks() {
+        let client = Client::from_identify_info(&identify_info(
+            "Lighthouse/v6.0.1-0b1c9a8/x86_64-linux",
+            &[
+                "/eth2/beacon_chain/req/status/1/ssz_snappy",
+                "/eth2/beacon_chain/req/metadata/2/ssz_snappy",
+                "/eth2/beacon_chain/req/metadata/1/ssz_snappy",
+                "/eth2/beacon_chain/req/not_a_protocol/1/ssz_snappy",
+                "/meshsub/1.2.0",
+                "/meshsub/1.1.0",
+                "/ipfs/id/1.0.0",
+            ],
+        ));
+        assert_eq!(client.kind, ClientKind::Lighthouse);
+        assert_eq!(
+            client.rpc_protocols,
+            vec!["metadata/1", "metadata/2", "status/1"]
+        );
+        assert_eq!(client.gossipsub_versions, vec!["1.1.0", "1.2.0"]);
+        assert!(client.quirks.is_empty());
+
+        let client = Client::from_identify_info(&identify_info(
+            "some-client/1.0",
+            &[
+                "/eth2/beacon_chain/req/status/1/ssz_snappy",
+                "/meshsub/1.0.0",
+            ],
+        ));
+        assert_eq!(
+            client.quirks,
+            vec![
+                ClientQuirk::UnrecognisedAgentVersion,
+                ClientQuirk::MissingMetaDataV2,
+                ClientQuirk::LegacyGossipsub
+            ]
+        );
+    }
+
+    #[test]
+    fn client_diversity_counts_peers() {
+        let lighthouse = Client::from_identify_info(&identify_info(
+            "Lighthouse/v6.0.1-0b1c9a8/x86_64-linux",
+            &["/eth2/beacon_chain/req/status/1/ssz_snappy"],
+        ));
+        let unknown = Client::from_identify_info(&identify_info("", &[]));
+
+        let mut diversity = ClientDiversity::default();
+        diversity.add(&lighthouse);
+        diversity.add(&lighthouse);
+        diversity.add(&unknown);
+
+        assert_eq!(diversity.connected_peers, 3);
+        assert_eq!(diversity.clients["Lighthouse"].peers, 2);
+        assert_eq!(
+            diversity.clients["Lighthouse"].versions["v6.0.1-0b1c9a8"],
+            2
+        );
+        assert_eq!(diversity.rpc_protocols["status/1"], 2);
+        assert_eq!(diversity.quirks["missing_agent_version"], 1);
+        assert_eq!(diversity.quirks["missing_metadata_v2"], 3);
+    }
+}
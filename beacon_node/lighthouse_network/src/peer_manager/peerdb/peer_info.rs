@@ -11,11 +11,14 @@ use serde::{
 };
 use std::collections::HashSet;
 use std::net::IpAddr;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use strum::AsRefStr;
 use types::{DataColumnSubnetId, EthSpec};
 use PeerConnectionStatus::*;
 
+/// The inverse weight of each new round-trip time in a peer's STATUS latency.
+const STATUS_LATENCY_SMOOTHING: u32 = 4;
+
 /// Information about a given connected peer.
 #[derive(Clone, Debug, Serialize)]
 #[serde(bound = "E: EthSpec")]
@@ -56,6 +59,12 @@ pub struct PeerInfo<E: EthSpec> {
     connection_direction: Option<ConnectionDirection>,
     /// The enr of the peer, if known.
     enr: Option<Enr>,
+    /// The time at which our pending STATUS request was sent to the peer, if any.
+    #[serde(skip)]
+    status_request_sent: Option<Instant>,
+    /// The smoothed round-trip time of our STATUS requests to the peer, if one has been answered.
+    #[serde(skip)]
+    status_latency: Option<Duration>,
 }
 
 impl<E: EthSpec> Default for PeerInfo<E> {
@@ -74,6 +83,8 @@ impl<E: EthSpec> Default for PeerInfo<E> {
             is_trusted: false,
             connection_direction: None,
             enr: None,
+            status_request_sent: None,
+            status_latency: None,
         }
     }
 }
@@ -214,6 +225,11 @@ impl<E: EthSpec> PeerInfo<E> {
         self.custody_subnets.contains(subnet)
     }
 
+    /// Returns the data column subnets the peer is assigned to custody.
+    pub fn custody_subnets(&self) -> impl Iterator<Item = &DataColumnSubnetId> {
+        self.custody_subnets.iter()
+    }
+
     /// Returns true if the peer is connected to a long-lived subnet.
     pub fn has_long_lived_subnet(&self) -> bool {
         // Check the meta_data
@@ -239,6 +255,11 @@ impl<E: EthSpec> PeerInfo<E> {
         false
     }
 
+    /// Returns the smoothed round-trip time of STATUS requests to the peer, if known.
+    pub fn status_latency(&self) -> Option<Duration> {
+        self.status_latency
+    }
+
     /// Returns the seen addresses of the peer.
     pub fn seen_multiaddrs(&self) -> impl Iterator<Item = &Multiaddr> + '_ {
         self.seen_multiaddrs.iter()
@@ -373,6 +394,36 @@ impl<E: EthSpec> PeerInfo<E> {
         self.custody_subnets = custody_subnets
     }
 
+    /// Records that a STATUS request has been sent to the peer.
+    // VISIBILITY: The peer manager is able to track STATUS requests
+    pub(in crate::peer_manager) fn on_status_request(&mut self, sent: Instant) {
+        self.status_request_sent = Some(sent);
+    }
+
+    /// Records the response to a pending STATUS request, returning the round-trip time of the
+    /// request, if one was pending. The latency of a non-trusted peer is fed into its score.
+    ///
+    /// The latency of the peer is an exponential moving average of the round-trip times, so that a
+    /// single slow response doesn't outweigh the history of the peer.
+    // VISIBILITY: The peer manager is able to track STATUS requests
+    pub(in crate::peer_manager) fn on_status_response(
+        &mut self,
+        received: Instant,
+    ) -> Option<Duration> {
+        let round_trip = received.saturating_duration_since(self.status_request_sent.take()?);
+        let latency = match self.status_latency {
+            Some(latency) => {
+                (latency * (STATUS_LATENCY_SMOOTHING - 1) + round_trip) / STATUS_LATENCY_SMOOTHING
+            }
+            None => round_trip,
+        };
+        self.status_latency = Some(latency);
+        if !self.is_trusted {
+            self.score.update_latency_score(latency);
+        }
+        Some(round_trip)
+    }
+
     /// Sets the ENR of the peer if one is known.
     pub(super) fn set_enr(&mut self, enr: Enr) {
         self.enr = Some(enr)
@@ -39,6 +39,13 @@ const GOSSIPSUB_NEGATIVE_SCORE_WEIGHT: f64 =
     (MIN_SCORE_BEFORE_DISCONNECT + 1.0) / GOSSIPSUB_GREYLIST_THRESHOLD;
 const GOSSIPSUB_POSITIVE_SCORE_WEIGHT: f64 = GOSSIPSUB_NEGATIVE_SCORE_WEIGHT;
 
+/// The score given to a peer which responds to our STATUS requests instantly. The score decreases
+/// linearly with the peer's STATUS latency, down to zero at `LATENCY_SCORE_CUTOFF`. It is kept small
+/// so that it only ranks otherwise healthy peers and never offsets misbehaviour.
+const MAX_LATENCY_SCORE: f64 = 1.0;
+/// The STATUS latency at and above which a peer receives no latency score.
+const LATENCY_SCORE_CUTOFF: Duration = Duration::from_secs(1);
+
 /// A collection of actions a peer can perform which will adjust its score.
 /// Each variant has an associated score change.
 // To easily assess the behaviour of scores changes the number of variants should stay low, and
@@ -134,6 +141,8 @@ pub struct RealScore {
     /// We ignore the negative gossipsub scores of some peers to allow decaying without
     /// disconnecting.
     ignore_negative_gossipsub_score: bool,
+    /// The score derived from the round-trip latency of our STATUS requests to the peer.
+    latency_score: f64,
     score: f64,
     /// The time the score was last updated to perform time-based adjustments such as score-decay.
     #[serde(skip)]
@@ -148,6 +157,7 @@ impl Default for RealScore {
             score: DEFAULT_SCORE,
             last_updated: Instant::now(),
             ignore_negative_gossipsub_score: false,
+            latency_score: DEFAULT_SCORE,
         }
     }
 }
@@ -158,11 +168,14 @@ impl RealScore {
         self.score = self.lighthouse_score;
         if self.lighthouse_score <= MIN_LIGHTHOUSE_SCORE_BEFORE_BAN {
             //ignore all other scores, i.e. do nothing here
-        } else if self.gossipsub_score >= 0.0 {
+            return;
+        }
+        if self.gossipsub_score >= 0.0 {
             self.score += self.gossipsub_score * GOSSIPSUB_POSITIVE_SCORE_WEIGHT;
         } else if !self.ignore_negative_gossipsub_score {
             self.score += self.gossipsub_score * GOSSIPSUB_NEGATIVE_SCORE_WEIGHT;
         }
+        self.score += self.latency_score;
     }
 
     fn score(&self) -> f64 {
@@ -256,6 +269,16 @@ impl RealScore {
         }
     }
 
+    /// Updates the latency score with the peer's STATUS round-trip latency.
+    pub fn update_latency_score(&mut self, latency: Duration) {
+        // as with gossipsub, banned peers keep their score until the ban time is over.
+        if self.last_updated <= Instant::now() {
+            let latency_fraction = latency.as_secs_f64() / LATENCY_SCORE_CUTOFF.as_secs_f64();
+            self.latency_score = MAX_LATENCY_SCORE * (1.0 - latency_fraction.min(1.0));
+            self.update_state();
+        }
+    }
+
     pub fn is_good_gossipsub_peer(&self) -> bool {
         self.gossipsub_score >= 0.0
     }
@@ -290,6 +313,7 @@ macro_rules! apply {
 apply!(apply_peer_action, peer_action: PeerAction);
 apply!(update);
 apply!(update_gossipsub_score, new_score: f64, ignore: bool);
+apply!(update_latency_score, latency: Duration);
 #[cfg(test)]
 apply!(test_add, score: f64);
 #[cfg(test)]
@@ -422,4 +446,31 @@ mod tests {
         assert!(!score.is_good_gossipsub_peer());
         assert_eq!(score.score(), 0.0);
     }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_latency_score() {
+        let mut score = Score::default();
+        score.update_latency_score(Duration::ZERO);
+        assert_eq!(score.score(), MAX_LATENCY_SCORE);
+
+        let mut slower = Score::default();
+        slower.update_latency_score(LATENCY_SCORE_CUTOFF / 2);
+        assert!(slower.score() > DEFAULT_SCORE);
+        assert!(slower.score() < score.score());
+
+        // A slow peer gets no latency score, but isn't penalised either.
+        score.update_latency_score(LATENCY_SCORE_CUTOFF * 10);
+        assert_eq!(score.score(), DEFAULT_SCORE);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_latency_score_ignored_below_lighthouse_ban_threshold() {
+        let mut score = Score::default();
+        score.update_latency_score(Duration::ZERO);
+        score.test_add(MIN_SCORE);
+        assert_eq!(score.score(), MIN_SCORE);
+        assert_eq!(score.state(), ScoreState::Banned);
+    }
 }
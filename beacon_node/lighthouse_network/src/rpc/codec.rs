@@ -20,7 +20,7 @@ use types::{
     LightClientBootstrap, LightClientFinalityUpdate, LightClientOptimisticUpdate,
     LightClientUpdate, RuntimeVariableList, SignedBeaconBlock, SignedBeaconBlockAltair,
     SignedBeaconBlockBase, SignedBeaconBlockBellatrix, SignedBeaconBlockCapella,
-    SignedBeaconBlockDeneb, SignedBeaconBlockElectra,
+    SignedBeaconBlockDeneb, SignedBeaconBlockElectra, SignedBlindedBeaconBlock,
 };
 use unsigned_varint::codec::Uvi;
 
@@ -69,6 +69,8 @@ impl<E: EthSpec> SSZSnappyInboundCodec<E> {
                 RpcSuccessResponse::Status(res) => res.as_ssz_bytes(),
                 RpcSuccessResponse::BlocksByRange(res) => res.as_ssz_bytes(),
                 RpcSuccessResponse::BlocksByRoot(res) => res.as_ssz_bytes(),
+                RpcSuccessResponse::BlindedBlocksByRange(res) => res.as_ssz_bytes(),
+                RpcSuccessResponse::ExecutionPayloadBodiesByRange(res) => res.as_ssz_bytes(),
                 RpcSuccessResponse::BlobsByRange(res) => res.as_ssz_bytes(),
                 RpcSuccessResponse::BlobsByRoot(res) => res.as_ssz_bytes(),
                 RpcSuccessResponse::DataColumnsByRoot(res) => res.as_ssz_bytes(),
@@ -77,6 +79,7 @@ impl<E: EthSpec> SSZSnappyInboundCodec<E> {
                 RpcSuccessResponse::LightClientOptimisticUpdate(res) => res.as_ssz_bytes(),
                 RpcSuccessResponse::LightClientFinalityUpdate(res) => res.as_ssz_bytes(),
                 RpcSuccessResponse::LightClientUpdatesByRange(res) => res.as_ssz_bytes(),
+                RpcSuccessResponse::LightClientUpdatesByRangeEncoded(res) => res.bytes.clone(),
                 RpcSuccessResponse::Pong(res) => res.data.as_ssz_bytes(),
                 RpcSuccessResponse::MetaData(res) =>
                 // Encode the correct version of the MetaData response based on the negotiated version.
@@ -337,6 +340,11 @@ impl<E: EthSpec> Encoder<RequestType<E>> for SSZSnappyOutboundCodec<E> {
                 BlocksByRootRequest::V1(req) => req.block_roots.as_ssz_bytes(),
                 BlocksByRootRequest::V2(req) => req.block_roots.as_ssz_bytes(),
             },
+            RequestType::BlindedBlocksByRange(r)
+            | RequestType::ExecutionPayloadBodiesByRange(r) => match r {
+                BlocksByRangeRequest::V1(req) => req.as_ssz_bytes(),
+                BlocksByRangeRequest::V2(req) => req.as_ssz_bytes(),
+            },
             RequestType::BlobsByRange(req) => req.as_ssz_bytes(),
             RequestType::BlobsByRoot(req) => req.blob_ids.as_ssz_bytes(),
             RequestType::DataColumnsByRange(req) => req.as_ssz_bytes(),
@@ -478,6 +486,9 @@ fn context_bytes<E: EthSpec>(
                         }
                     };
                 }
+                RpcSuccessResponse::BlindedBlocksByRange(blinded_block) => {
+                    return fork_context.to_context_bytes(blinded_block.fork_name_unchecked());
+                }
                 RpcSuccessResponse::BlobsByRange(_) | RpcSuccessResponse::BlobsByRoot(_) => {
                     return fork_context.to_context_bytes(ForkName::Deneb);
                 }
@@ -509,10 +520,14 @@ fn context_bytes<E: EthSpec>(
                     return lc_update
                         .map_with_fork_name(|fork_name| fork_context.to_context_bytes(fork_name));
                 }
+                RpcSuccessResponse::LightClientUpdatesByRangeEncoded(lc_update) => {
+                    return fork_context.to_context_bytes(lc_update.fork_name);
+                }
                 // These will not pass the has_context_bytes() check
                 RpcSuccessResponse::Status(_)
                 | RpcSuccessResponse::Pong(_)
-                | RpcSuccessResponse::MetaData(_) => {
+                | RpcSuccessResponse::MetaData(_)
+                | RpcSuccessResponse::ExecutionPayloadBodiesByRange(_) => {
                     return None;
                 }
             }
@@ -583,6 +598,14 @@ fn handle_rpc_request<E: EthSpec>(
                 )?,
             }),
         ))),
+        SupportedProtocol::BlindedBlocksByRangeV1 => Ok(Some(RequestType::BlindedBlocksByRange(
+            BlocksByRangeRequest::V2(BlocksByRangeRequestV2::from_ssz_bytes(decoded_buffer)?),
+        ))),
+        SupportedProtocol::ExecutionPayloadBodiesByRangeV1 => {
+            Ok(Some(RequestType::ExecutionPayloadBodiesByRange(
+                BlocksByRangeRequest::V2(BlocksByRangeRequestV2::from_ssz_bytes(decoded_buffer)?),
+            )))
+        }
         SupportedProtocol::BlobsByRangeV1 => Ok(Some(RequestType::BlobsByRange(
             BlobsByRangeRequest::from_ssz_bytes(decoded_buffer)?,
         ))),
@@ -681,6 +704,23 @@ fn handle_rpc_response<E: EthSpec>(
         SupportedProtocol::BlocksByRootV1 => Ok(Some(RpcSuccessResponse::BlocksByRoot(Arc::new(
             SignedBeaconBlock::Base(SignedBeaconBlockBase::from_ssz_bytes(decoded_buffer)?),
         )))),
+        SupportedProtocol::BlindedBlocksByRangeV1 => match fork_name {
+            Some(fork_name) => Ok(Some(RpcSuccessResponse::BlindedBlocksByRange(Arc::new(
+                SignedBlindedBeaconBlock::from_ssz_bytes_for_fork(decoded_buffer, fork_name)?,
+            )))),
+            None => Err(RPCError::ErrorResponse(
+                RpcErrorResponse::InvalidRequest,
+                format!(
+                    "No context bytes provided for {:?} response",
+                    versioned_protocol
+                ),
+            )),
+        },
+        SupportedProtocol::ExecutionPayloadBodiesByRangeV1 => Ok(Some(
+            RpcSuccessResponse::ExecutionPayloadBodiesByRange(Arc::new(
+                ExecutionPayloadBodyWithRoot::from_ssz_bytes(decoded_buffer)?,
+            )),
+        )),
         SupportedProtocol::BlobsByRangeV1 => match fork_name {
             Some(ForkName::Deneb) | Some(ForkName::Electra) => {
                 Ok(Some(RpcSuccessResponse::BlobsByRange(Arc::new(
@@ -934,9 +974,9 @@ mod tests {
     use crate::rpc::protocol::*;
     use crate::types::{EnrAttestationBitfield, EnrSyncCommitteeBitfield};
     use types::{
-        blob_sidecar::BlobIdentifier, BeaconBlock, BeaconBlockAltair, BeaconBlockBase,
-        BeaconBlockBellatrix, DataColumnIdentifier, EmptyBlock, Epoch, FixedBytesExtended,
-        FullPayload, Signature, Slot,
+        blob_sidecar::BlobIdentifier, test_utils::test_random_instance, BeaconBlock,
+        BeaconBlockAltair, BeaconBlockBase, BeaconBlockBellatrix, DataColumnIdentifier, EmptyBlock,
+        EncodedLightClientUpdate, Epoch, FixedBytesExtended, FullPayload, Signature, Slot,
     };
 
     type Spec = types::MainnetEthSpec;
@@ -1041,6 +1081,10 @@ mod tests {
         OldBlocksByRangeRequest::new(0, 10, 1)
     }
 
+    fn blinded_bbrange_request() -> BlocksByRangeRequest {
+        BlocksByRangeRequest::new(0, 10)
+    }
+
     fn blbrange_request() -> BlobsByRangeRequest {
         BlobsByRangeRequest {
             start_slot: 0,
@@ -1225,6 +1269,12 @@ mod tests {
             RequestType::BlocksByRoot(bbroot) => {
                 assert_eq!(decoded, RequestType::BlocksByRoot(bbroot))
             }
+            RequestType::BlindedBlocksByRange(bbrange) => {
+                assert_eq!(decoded, RequestType::BlindedBlocksByRange(bbrange))
+            }
+            RequestType::ExecutionPayloadBodiesByRange(bbrange) => {
+                assert_eq!(decoded, RequestType::ExecutionPayloadBodiesByRange(bbrange))
+            }
             RequestType::BlobsByRange(blbrange) => {
                 assert_eq!(decoded, RequestType::BlobsByRange(blbrange))
             }
@@ -1831,6 +1881,110 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_encode_then_decode_blinded_blocks_by_range() {
+        let chain_spec = Spec::default_spec();
+
+        let base_block = empty_base_block().clone_as_blinded();
+        assert_eq!(
+            encode_then_decode_response(
+                SupportedProtocol::BlindedBlocksByRangeV1,
+                RpcResponse::Success(RpcSuccessResponse::BlindedBlocksByRange(Arc::new(
+                    base_block.clone()
+                ))),
+                ForkName::Altair,
+                &chain_spec,
+            ),
+            Ok(Some(RpcSuccessResponse::BlindedBlocksByRange(Arc::new(
+                base_block
+            ))))
+        );
+
+        let bellatrix_fork_context = fork_context(ForkName::Bellatrix);
+        let bellatrix_block =
+            bellatrix_block_small(&bellatrix_fork_context, &chain_spec).clone_as_blinded();
+        assert_eq!(
+            encode_then_decode_response(
+                SupportedProtocol::BlindedBlocksByRangeV1,
+                RpcResponse::Success(RpcSuccessResponse::BlindedBlocksByRange(Arc::new(
+                    bellatrix_block.clone()
+                ))),
+                ForkName::Bellatrix,
+                &chain_spec,
+            ),
+            Ok(Some(RpcSuccessResponse::BlindedBlocksByRange(Arc::new(
+                bellatrix_block
+            ))))
+        );
+    }
+
+    #[test]
+    fn test_encode_then_decode_execution_payload_bodies_by_range() {
+        let chain_spec = Spec::default_spec();
+
+        let payload_body = ExecutionPayloadBodyWithRoot::<Spec> {
+            block_root: Hash256::repeat_byte(1),
+            transactions: VariableList::from(vec![VariableList::from(vec![0u8; 128])]),
+            withdrawals: Some(VariableList::empty()),
+        };
+        assert_eq!(
+            encode_then_decode_response(
+                SupportedProtocol::ExecutionPayloadBodiesByRangeV1,
+                RpcResponse::Success(RpcSuccessResponse::ExecutionPayloadBodiesByRange(Arc::new(
+                    payload_body.clone()
+                ))),
+                ForkName::Capella,
+                &chain_spec,
+            ),
+            Ok(Some(RpcSuccessResponse::ExecutionPayloadBodiesByRange(
+                Arc::new(payload_body)
+            )))
+        );
+    }
+
+    #[test]
+    fn test_encode_then_decode_encoded_light_client_update() {
+        let chain_spec = Spec::default_spec();
+
+        let update = LightClientUpdate::Capella(test_random_instance());
+        let encoded_update =
+            EncodedLightClientUpdate::from_persisted(update.as_ssz_bytes(), ForkName::Capella)
+                .unwrap();
+
+        // The pre-encoded update is sent identically to the decoded update.
+        let encoded = encode_response(
+            SupportedProtocol::LightClientUpdatesByRangeV1,
+            RpcResponse::Success(RpcSuccessResponse::LightClientUpdatesByRangeEncoded(
+                Arc::new(encoded_update),
+            )),
+            ForkName::Capella,
+            &chain_spec,
+        )
+        .unwrap();
+        let expected = encode_response(
+            SupportedProtocol::LightClientUpdatesByRangeV1,
+            RpcResponse::Success(RpcSuccessResponse::LightClientUpdatesByRange(Arc::new(
+                update.clone(),
+            ))),
+            ForkName::Capella,
+            &chain_spec,
+        )
+        .unwrap();
+        assert_eq!(encoded, expected);
+
+        assert_eq!(
+            decode_response(
+                SupportedProtocol::LightClientUpdatesByRangeV1,
+                &mut encoded.clone(),
+                ForkName::Capella,
+                &chain_spec,
+            ),
+            Ok(Some(RpcSuccessResponse::LightClientUpdatesByRange(
+                Arc::new(update)
+            )))
+        );
+    }
+
     #[test]
     fn test_encode_then_decode_request() {
         let chain_spec = Spec::default_spec();
@@ -1843,6 +1997,8 @@ mod tests {
             RequestType::BlocksByRange(bbrange_request_v2()),
             RequestType::BlocksByRoot(bbroot_request_v1(&chain_spec)),
             RequestType::BlocksByRoot(bbroot_request_v2(&chain_spec)),
+            RequestType::BlindedBlocksByRange(blinded_bbrange_request()),
+            RequestType::ExecutionPayloadBodiesByRange(blinded_bbrange_request()),
             RequestType::MetaData(MetadataRequest::new_v1()),
             RequestType::BlobsByRange(blbrange_request()),
             RequestType::BlobsByRoot(blbroot_request(&chain_spec)),
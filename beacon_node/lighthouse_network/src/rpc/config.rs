@@ -208,7 +208,11 @@ impl FromStr for RateLimiterConfig {
             match protocol {
                 Protocol::Status => status_quota = status_quota.or(quota),
                 Protocol::Goodbye => goodbye_quota = goodbye_quota.or(quota),
-                Protocol::BlocksByRange => blocks_by_range_quota = blocks_by_range_quota.or(quota),
+                Protocol::BlocksByRange
+                | Protocol::BlindedBlocksByRange
+                | Protocol::ExecutionPayloadBodiesByRange => {
+                    blocks_by_range_quota = blocks_by_range_quota.or(quota)
+                }
                 Protocol::BlocksByRoot => blocks_by_root_quota = blocks_by_root_quota.or(quota),
                 Protocol::BlobsByRange => blobs_by_range_quota = blobs_by_range_quota.or(quota),
                 Protocol::BlobsByRoot => blobs_by_root_quota = blobs_by_root_quota.or(quota),
@@ -17,8 +17,9 @@ use types::blob_sidecar::BlobIdentifier;
 use types::light_client_update::MAX_REQUEST_LIGHT_CLIENT_UPDATES;
 use types::{
     blob_sidecar::BlobSidecar, ChainSpec, ColumnIndex, DataColumnIdentifier, DataColumnSidecar,
-    Epoch, EthSpec, Hash256, LightClientBootstrap, LightClientFinalityUpdate,
-    LightClientOptimisticUpdate, LightClientUpdate, RuntimeVariableList, SignedBeaconBlock, Slot,
+    EncodedLightClientUpdate, Epoch, EthSpec, Hash256, LightClientBootstrap,
+    LightClientFinalityUpdate, LightClientOptimisticUpdate, LightClientUpdate, RuntimeVariableList,
+    SignedBeaconBlock, SignedBlindedBeaconBlock, Slot, Transactions, Withdrawals,
 };
 
 /// Maximum length of error message.
@@ -506,6 +507,18 @@ impl LightClientUpdatesByRangeRequest {
     }
 }
 
+/// The body of the execution payload of the block with `block_root`, as served by the
+/// Lighthouse-specific execution payload bodies by range protocol.
+///
+/// Together with the blinded block, this is sufficient to reconstruct the full block.
+#[derive(Encode, Decode, Clone, Debug, PartialEq)]
+pub struct ExecutionPayloadBodyWithRoot<E: EthSpec> {
+    pub block_root: Hash256,
+    pub transactions: Transactions<E>,
+    /// `None` for payloads prior to Capella.
+    pub withdrawals: Option<Withdrawals<E>>,
+}
+
 /* RPC Handling and Grouping */
 // Collection of enums and structs used by the Codecs to encode/decode RPC messages
 
@@ -521,6 +534,12 @@ pub enum RpcSuccessResponse<E: EthSpec> {
     /// A response to a get BLOCKS_BY_ROOT request.
     BlocksByRoot(Arc<SignedBeaconBlock<E>>),
 
+    /// A response to a get BLINDED_BLOCKS_BY_RANGE request.
+    BlindedBlocksByRange(Arc<SignedBlindedBeaconBlock<E>>),
+
+    /// A response to a get EXECUTION_PAYLOAD_BODIES_BY_RANGE request.
+    ExecutionPayloadBodiesByRange(Arc<ExecutionPayloadBodyWithRoot<E>>),
+
     /// A response to a get BLOBS_BY_RANGE request
     BlobsByRange(Arc<BlobSidecar<E>>),
 
@@ -536,6 +555,10 @@ pub enum RpcSuccessResponse<E: EthSpec> {
     /// A response to a get LIGHT_CLIENT_UPDATES_BY_RANGE request.
     LightClientUpdatesByRange(Arc<LightClientUpdate<E>>),
 
+    /// A response to a get LIGHT_CLIENT_UPDATES_BY_RANGE request, sent as the persisted encoding
+    /// of the update. Only sent, responses received from peers are always decoded.
+    LightClientUpdatesByRangeEncoded(Arc<EncodedLightClientUpdate>),
+
     /// A response to a get BLOBS_BY_ROOT request.
     BlobsByRoot(Arc<BlobSidecar<E>>),
 
@@ -561,6 +584,12 @@ pub enum ResponseTermination {
     /// Blocks by root stream termination.
     BlocksByRoot,
 
+    /// Blinded blocks by range stream termination.
+    BlindedBlocksByRange,
+
+    /// Execution payload bodies by range stream termination.
+    ExecutionPayloadBodiesByRange,
+
     /// Blobs by range stream termination.
     BlobsByRange,
 
@@ -663,6 +692,10 @@ impl<E: EthSpec> RpcSuccessResponse<E> {
             RpcSuccessResponse::Status(_) => Protocol::Status,
             RpcSuccessResponse::BlocksByRange(_) => Protocol::BlocksByRange,
             RpcSuccessResponse::BlocksByRoot(_) => Protocol::BlocksByRoot,
+            RpcSuccessResponse::BlindedBlocksByRange(_) => Protocol::BlindedBlocksByRange,
+            RpcSuccessResponse::ExecutionPayloadBodiesByRange(_) => {
+                Protocol::ExecutionPayloadBodiesByRange
+            }
             RpcSuccessResponse::BlobsByRange(_) => Protocol::BlobsByRange,
             RpcSuccessResponse::BlobsByRoot(_) => Protocol::BlobsByRoot,
             RpcSuccessResponse::DataColumnsByRoot(_) => Protocol::DataColumnsByRoot,
@@ -674,7 +707,10 @@ impl<E: EthSpec> RpcSuccessResponse<E> {
                 Protocol::LightClientOptimisticUpdate
             }
             RpcSuccessResponse::LightClientFinalityUpdate(_) => Protocol::LightClientFinalityUpdate,
-            RpcSuccessResponse::LightClientUpdatesByRange(_) => Protocol::LightClientUpdatesByRange,
+            RpcSuccessResponse::LightClientUpdatesByRange(_)
+            | RpcSuccessResponse::LightClientUpdatesByRangeEncoded(_) => {
+                Protocol::LightClientUpdatesByRange
+            }
         }
     }
 }
@@ -709,6 +745,16 @@ impl<E: EthSpec> std::fmt::Display for RpcSuccessResponse<E> {
             RpcSuccessResponse::BlocksByRoot(block) => {
                 write!(f, "BlocksByRoot: Block slot: {}", block.slot())
             }
+            RpcSuccessResponse::BlindedBlocksByRange(block) => {
+                write!(f, "BlindedBlocksByRange: Block slot: {}", block.slot())
+            }
+            RpcSuccessResponse::ExecutionPayloadBodiesByRange(body) => {
+                write!(
+                    f,
+                    "ExecutionPayloadBodiesByRange: Block root: {:?}",
+                    body.block_root
+                )
+            }
             RpcSuccessResponse::BlobsByRange(blob) => {
                 write!(f, "BlobsByRange: Blob slot: {}", blob.slot())
             }
@@ -753,6 +799,14 @@ impl<E: EthSpec> std::fmt::Display for RpcSuccessResponse<E> {
                     update.signature_slot(),
                 )
             }
+            RpcSuccessResponse::LightClientUpdatesByRangeEncoded(update) => {
+                write!(
+                    f,
+                    "LightClientUpdatesByRange Fork: {}, Bytes: {}",
+                    update.fork_name,
+                    update.bytes.len(),
+                )
+            }
         }
     }
 }
@@ -159,6 +159,11 @@ pub struct RPC<Id: ReqId, E: EthSpec> {
     events: Vec<BehaviourAction<Id, E>>,
     fork_context: Arc<ForkContext>,
     enable_light_client_server: bool,
+    /// Whether to advertise and serve the Lighthouse-specific blinded blocks by range protocol.
+    enable_blinded_blocks_by_range: bool,
+    /// Whether to advertise and serve the Lighthouse-specific execution payload bodies by range
+    /// protocol.
+    enable_execution_payload_bodies_by_range: bool,
     /// Slog logger for RPC behaviour.
     log: slog::Logger,
     /// Networking constant values
@@ -171,6 +176,8 @@ impl<Id: ReqId, E: EthSpec> RPC<Id, E> {
     pub fn new(
         fork_context: Arc<ForkContext>,
         enable_light_client_server: bool,
+        enable_blinded_blocks_by_range: bool,
+        enable_execution_payload_bodies_by_range: bool,
         inbound_rate_limiter_config: Option<InboundRateLimiterConfig>,
         outbound_rate_limiter_config: Option<OutboundRateLimiterConfig>,
         log: slog::Logger,
@@ -195,6 +202,8 @@ impl<Id: ReqId, E: EthSpec> RPC<Id, E> {
             events: Vec::new(),
             fork_context,
             enable_light_client_server,
+            enable_blinded_blocks_by_range,
+            enable_execution_payload_bodies_by_range,
             log,
             network_params,
             seq_number,
@@ -285,6 +294,9 @@ where
                 fork_context: self.fork_context.clone(),
                 max_rpc_size: max_rpc_size(&self.fork_context, self.network_params.max_chunk_size),
                 enable_light_client_server: self.enable_light_client_server,
+                enable_blinded_blocks_by_range: self.enable_blinded_blocks_by_range,
+                enable_execution_payload_bodies_by_range: self
+                    .enable_execution_payload_bodies_by_range,
                 phantom: PhantomData,
                 ttfb_timeout: self.network_params.ttfb_timeout,
             },
@@ -316,6 +328,9 @@ where
                 fork_context: self.fork_context.clone(),
                 max_rpc_size: max_rpc_size(&self.fork_context, self.network_params.max_chunk_size),
                 enable_light_client_server: self.enable_light_client_server,
+                enable_blinded_blocks_by_range: self.enable_blinded_blocks_by_range,
+                enable_execution_payload_bodies_by_range: self
+                    .enable_execution_payload_bodies_by_range,
                 phantom: PhantomData,
                 ttfb_timeout: self.network_params.ttfb_timeout,
             },
@@ -414,6 +429,8 @@ where
                             if matches!(
                                 protocol,
                                 Protocol::BlocksByRange
+                                    | Protocol::BlindedBlocksByRange
+                                    | Protocol::ExecutionPayloadBodiesByRange
                                     | Protocol::BlobsByRange
                                     | Protocol::DataColumnsByRange
                                     | Protocol::BlocksByRoot
@@ -549,6 +566,12 @@ where
                         match end {
                             ResponseTermination::BlocksByRange => Protocol::BlocksByRange,
                             ResponseTermination::BlocksByRoot => Protocol::BlocksByRoot,
+                            ResponseTermination::BlindedBlocksByRange => {
+                                Protocol::BlindedBlocksByRange
+                            }
+                            ResponseTermination::ExecutionPayloadBodiesByRange => {
+                                Protocol::ExecutionPayloadBodiesByRange
+                            }
                             ResponseTermination::BlobsByRange => Protocol::BlobsByRange,
                             ResponseTermination::BlobsByRoot => Protocol::BlobsByRoot,
                             ResponseTermination::DataColumnsByRoot => Protocol::DataColumnsByRoot,
@@ -19,7 +19,7 @@ use tokio_util::{
 use types::{
     BeaconBlock, BeaconBlockAltair, BeaconBlockBase, BeaconBlockCapella, BeaconBlockElectra,
     BlobSidecar, ChainSpec, DataColumnSidecar, EmptyBlock, EthSpec, EthSpecId, ForkContext,
-    ForkName, LightClientBootstrap, LightClientBootstrapAltair, LightClientFinalityUpdate,
+    ForkName, Hash256, LightClientBootstrap, LightClientBootstrapAltair, LightClientFinalityUpdate,
     LightClientFinalityUpdateAltair, LightClientOptimisticUpdate,
     LightClientOptimisticUpdateAltair, LightClientUpdate, MainnetEthSpec, MinimalEthSpec,
     Signature, SignedBeaconBlock,
@@ -119,6 +119,16 @@ pub static DATA_COLUMNS_SIDECAR_MIN: LazyLock<usize> = LazyLock::new(|| {
 pub static DATA_COLUMNS_SIDECAR_MAX: LazyLock<usize> =
     LazyLock::new(DataColumnSidecar::<MainnetEthSpec>::max_size);
 
+pub static EXECUTION_PAYLOAD_BODY_MIN: LazyLock<usize> = LazyLock::new(|| {
+    ExecutionPayloadBodyWithRoot::<MainnetEthSpec> {
+        block_root: Hash256::ZERO,
+        transactions: VariableList::empty(),
+        withdrawals: None,
+    }
+    .as_ssz_bytes()
+    .len()
+});
+
 pub static ERROR_TYPE_MIN: LazyLock<usize> = LazyLock::new(|| {
     VariableList::<u8, MaxErrorLen>::from(Vec::<u8>::new())
         .as_ssz_bytes()
@@ -302,6 +312,12 @@ pub enum Protocol {
     /// The `BlocksByRoot` protocol name.
     #[strum(serialize = "beacon_blocks_by_root")]
     BlocksByRoot,
+    /// The Lighthouse-specific `BlindedBlocksByRange` protocol name.
+    #[strum(serialize = "lighthouse_blinded_beacon_blocks_by_range")]
+    BlindedBlocksByRange,
+    /// The Lighthouse-specific `ExecutionPayloadBodiesByRange` protocol name.
+    #[strum(serialize = "lighthouse_execution_payload_bodies_by_range")]
+    ExecutionPayloadBodiesByRange,
     /// The `BlobsByRange` protocol name.
     #[strum(serialize = "blob_sidecars_by_range")]
     BlobsByRange,
@@ -340,6 +356,10 @@ impl Protocol {
             Protocol::Goodbye => None,
             Protocol::BlocksByRange => Some(ResponseTermination::BlocksByRange),
             Protocol::BlocksByRoot => Some(ResponseTermination::BlocksByRoot),
+            Protocol::BlindedBlocksByRange => Some(ResponseTermination::BlindedBlocksByRange),
+            Protocol::ExecutionPayloadBodiesByRange => {
+                Some(ResponseTermination::ExecutionPayloadBodiesByRange)
+            }
             Protocol::BlobsByRange => Some(ResponseTermination::BlobsByRange),
             Protocol::BlobsByRoot => Some(ResponseTermination::BlobsByRoot),
             Protocol::DataColumnsByRoot => Some(ResponseTermination::DataColumnsByRoot),
@@ -369,6 +389,8 @@ pub enum SupportedProtocol {
     BlocksByRangeV2,
     BlocksByRootV1,
     BlocksByRootV2,
+    BlindedBlocksByRangeV1,
+    ExecutionPayloadBodiesByRangeV1,
     BlobsByRangeV1,
     BlobsByRootV1,
     DataColumnsByRootV1,
@@ -392,6 +414,8 @@ impl SupportedProtocol {
             SupportedProtocol::BlocksByRangeV2 => "2",
             SupportedProtocol::BlocksByRootV1 => "1",
             SupportedProtocol::BlocksByRootV2 => "2",
+            SupportedProtocol::BlindedBlocksByRangeV1 => "1",
+            SupportedProtocol::ExecutionPayloadBodiesByRangeV1 => "1",
             SupportedProtocol::BlobsByRangeV1 => "1",
             SupportedProtocol::BlobsByRootV1 => "1",
             SupportedProtocol::DataColumnsByRootV1 => "1",
@@ -415,6 +439,10 @@ impl SupportedProtocol {
             SupportedProtocol::BlocksByRangeV2 => Protocol::BlocksByRange,
             SupportedProtocol::BlocksByRootV1 => Protocol::BlocksByRoot,
             SupportedProtocol::BlocksByRootV2 => Protocol::BlocksByRoot,
+            SupportedProtocol::BlindedBlocksByRangeV1 => Protocol::BlindedBlocksByRange,
+            SupportedProtocol::ExecutionPayloadBodiesByRangeV1 => {
+                Protocol::ExecutionPayloadBodiesByRange
+            }
             SupportedProtocol::BlobsByRangeV1 => Protocol::BlobsByRange,
             SupportedProtocol::BlobsByRootV1 => Protocol::BlobsByRoot,
             SupportedProtocol::DataColumnsByRootV1 => Protocol::DataColumnsByRoot,
@@ -486,6 +514,8 @@ pub struct RPCProtocol<E: EthSpec> {
     pub fork_context: Arc<ForkContext>,
     pub max_rpc_size: usize,
     pub enable_light_client_server: bool,
+    pub enable_blinded_blocks_by_range: bool,
+    pub enable_execution_payload_bodies_by_range: bool,
     pub phantom: PhantomData<E>,
     pub ttfb_timeout: Duration,
 }
@@ -511,6 +541,18 @@ impl<E: EthSpec> UpgradeInfo for RPCProtocol<E> {
                 Encoding::SSZSnappy,
             ));
         }
+        if self.enable_blinded_blocks_by_range {
+            supported_protocols.push(ProtocolId::new(
+                SupportedProtocol::BlindedBlocksByRangeV1,
+                Encoding::SSZSnappy,
+            ));
+        }
+        if self.enable_execution_payload_bodies_by_range {
+            supported_protocols.push(ProtocolId::new(
+                SupportedProtocol::ExecutionPayloadBodiesByRangeV1,
+                Encoding::SSZSnappy,
+            ));
+        }
         supported_protocols
     }
 }
@@ -571,6 +613,12 @@ impl ProtocolId {
                 <OldBlocksByRangeRequestV2 as Encode>::ssz_fixed_len(),
             ),
             Protocol::BlocksByRoot => RpcLimits::new(0, spec.max_blocks_by_root_request),
+            Protocol::BlindedBlocksByRange | Protocol::ExecutionPayloadBodiesByRange => {
+                RpcLimits::new(
+                    <BlocksByRangeRequestV2 as Encode>::ssz_fixed_len(),
+                    <BlocksByRangeRequestV2 as Encode>::ssz_fixed_len(),
+                )
+            }
             Protocol::BlobsByRange => RpcLimits::new(
                 <BlobsByRangeRequest as Encode>::ssz_fixed_len(),
                 <BlobsByRangeRequest as Encode>::ssz_fixed_len(),
@@ -609,6 +657,13 @@ impl ProtocolId {
             Protocol::Goodbye => RpcLimits::new(0, 0), // Goodbye request has no response
             Protocol::BlocksByRange => rpc_block_limits_by_fork(fork_context.current_fork()),
             Protocol::BlocksByRoot => rpc_block_limits_by_fork(fork_context.current_fork()),
+            // Blinded blocks are never larger than their full counterparts.
+            Protocol::BlindedBlocksByRange => rpc_block_limits_by_fork(fork_context.current_fork()),
+            // Payload bodies are never larger than the blocks which contain them.
+            Protocol::ExecutionPayloadBodiesByRange => RpcLimits::new(
+                *EXECUTION_PAYLOAD_BODY_MIN,
+                rpc_block_limits_by_fork(fork_context.current_fork()).max,
+            ),
             Protocol::BlobsByRange => rpc_blob_limits::<E>(),
             Protocol::BlobsByRoot => rpc_blob_limits::<E>(),
             Protocol::DataColumnsByRoot => rpc_data_column_limits(),
@@ -642,6 +697,7 @@ impl ProtocolId {
         match self.versioned_protocol {
             SupportedProtocol::BlocksByRangeV2
             | SupportedProtocol::BlocksByRootV2
+            | SupportedProtocol::BlindedBlocksByRangeV1
             | SupportedProtocol::BlobsByRangeV1
             | SupportedProtocol::BlobsByRootV1
             | SupportedProtocol::DataColumnsByRootV1
@@ -657,6 +713,7 @@ impl ProtocolId {
             | SupportedProtocol::MetaDataV1
             | SupportedProtocol::MetaDataV2
             | SupportedProtocol::MetaDataV3
+            | SupportedProtocol::ExecutionPayloadBodiesByRangeV1
             | SupportedProtocol::GoodbyeV1 => false,
         }
     }
@@ -774,6 +831,8 @@ pub enum RequestType<E: EthSpec> {
     Goodbye(GoodbyeReason),
     BlocksByRange(OldBlocksByRangeRequest),
     BlocksByRoot(BlocksByRootRequest),
+    BlindedBlocksByRange(BlocksByRangeRequest),
+    ExecutionPayloadBodiesByRange(BlocksByRangeRequest),
     BlobsByRange(BlobsByRangeRequest),
     BlobsByRoot(BlobsByRootRequest),
     DataColumnsByRoot(DataColumnsByRootRequest),
@@ -797,6 +856,8 @@ impl<E: EthSpec> RequestType<E> {
             RequestType::Goodbye(_) => 0,
             RequestType::BlocksByRange(req) => *req.count(),
             RequestType::BlocksByRoot(req) => req.block_roots().len() as u64,
+            RequestType::BlindedBlocksByRange(req) => *req.count(),
+            RequestType::ExecutionPayloadBodiesByRange(req) => *req.count(),
             RequestType::BlobsByRange(req) => req.max_blobs_requested::<E>(),
             RequestType::BlobsByRoot(req) => req.blob_ids.len() as u64,
             RequestType::DataColumnsByRoot(req) => req.data_column_ids.len() as u64,
@@ -823,6 +884,10 @@ impl<E: EthSpec> RequestType<E> {
                 BlocksByRootRequest::V1(_) => SupportedProtocol::BlocksByRootV1,
                 BlocksByRootRequest::V2(_) => SupportedProtocol::BlocksByRootV2,
             },
+            RequestType::BlindedBlocksByRange(_) => SupportedProtocol::BlindedBlocksByRangeV1,
+            RequestType::ExecutionPayloadBodiesByRange(_) => {
+                SupportedProtocol::ExecutionPayloadBodiesByRangeV1
+            }
             RequestType::BlobsByRange(_) => SupportedProtocol::BlobsByRangeV1,
             RequestType::BlobsByRoot(_) => SupportedProtocol::BlobsByRootV1,
             RequestType::DataColumnsByRoot(_) => SupportedProtocol::DataColumnsByRootV1,
@@ -854,6 +919,10 @@ impl<E: EthSpec> RequestType<E> {
             // variants that have `multiple_responses()` can have values.
             RequestType::BlocksByRange(_) => ResponseTermination::BlocksByRange,
             RequestType::BlocksByRoot(_) => ResponseTermination::BlocksByRoot,
+            RequestType::BlindedBlocksByRange(_) => ResponseTermination::BlindedBlocksByRange,
+            RequestType::ExecutionPayloadBodiesByRange(_) => {
+                ResponseTermination::ExecutionPayloadBodiesByRange
+            }
             RequestType::BlobsByRange(_) => ResponseTermination::BlobsByRange,
             RequestType::BlobsByRoot(_) => ResponseTermination::BlobsByRoot,
             RequestType::DataColumnsByRoot(_) => ResponseTermination::DataColumnsByRoot,
@@ -888,6 +957,14 @@ impl<E: EthSpec> RequestType<E> {
                 ProtocolId::new(SupportedProtocol::BlocksByRootV2, Encoding::SSZSnappy),
                 ProtocolId::new(SupportedProtocol::BlocksByRootV1, Encoding::SSZSnappy),
             ],
+            RequestType::BlindedBlocksByRange(_) => vec![ProtocolId::new(
+                SupportedProtocol::BlindedBlocksByRangeV1,
+                Encoding::SSZSnappy,
+            )],
+            RequestType::ExecutionPayloadBodiesByRange(_) => vec![ProtocolId::new(
+                SupportedProtocol::ExecutionPayloadBodiesByRangeV1,
+                Encoding::SSZSnappy,
+            )],
             RequestType::BlobsByRange(_) => vec![ProtocolId::new(
                 SupportedProtocol::BlobsByRangeV1,
                 Encoding::SSZSnappy,
@@ -938,6 +1015,8 @@ impl<E: EthSpec> RequestType<E> {
             RequestType::Goodbye(_) => false,
             RequestType::BlocksByRange(_) => false,
             RequestType::BlocksByRoot(_) => false,
+            RequestType::BlindedBlocksByRange(_) => false,
+            RequestType::ExecutionPayloadBodiesByRange(_) => false,
             RequestType::BlobsByRange(_) => false,
             RequestType::BlobsByRoot(_) => false,
             RequestType::DataColumnsByRoot(_) => false,
@@ -1049,6 +1128,10 @@ impl<E: EthSpec> std::fmt::Display for RequestType<E> {
             RequestType::Goodbye(reason) => write!(f, "Goodbye: {}", reason),
             RequestType::BlocksByRange(req) => write!(f, "Blocks by range: {}", req),
             RequestType::BlocksByRoot(req) => write!(f, "Blocks by root: {:?}", req),
+            RequestType::BlindedBlocksByRange(req) => write!(f, "Blinded blocks by range: {}", req),
+            RequestType::ExecutionPayloadBodiesByRange(req) => {
+                write!(f, "Execution payload bodies by range: {}", req)
+            }
             RequestType::BlobsByRange(req) => write!(f, "Blobs by range: {:?}", req),
             RequestType::BlobsByRoot(req) => write!(f, "Blobs by root: {:?}", req),
             RequestType::DataColumnsByRoot(req) => write!(f, "Data columns by root: {:?}", req),
@@ -162,7 +162,10 @@ impl RPCRateLimiterBuilder {
             Protocol::Status => self.status_quota = q,
             Protocol::MetaData => self.metadata_quota = q,
             Protocol::Goodbye => self.goodbye_quota = q,
-            Protocol::BlocksByRange => self.bbrange_quota = q,
+            // Blinded blocks by range shares the blocks by range quota.
+            Protocol::BlocksByRange
+            | Protocol::BlindedBlocksByRange
+            | Protocol::ExecutionPayloadBodiesByRange => self.bbrange_quota = q,
             Protocol::BlocksByRoot => self.bbroots_quota = q,
             Protocol::BlobsByRange => self.blbrange_quota = q,
             Protocol::BlobsByRoot => self.blbroot_quota = q,
@@ -339,7 +342,9 @@ impl RPCRateLimiter {
             Protocol::Status => &mut self.status_rl,
             Protocol::MetaData => &mut self.metadata_rl,
             Protocol::Goodbye => &mut self.goodbye_rl,
-            Protocol::BlocksByRange => &mut self.bbrange_rl,
+            Protocol::BlocksByRange
+            | Protocol::BlindedBlocksByRange
+            | Protocol::ExecutionPayloadBodiesByRange => &mut self.bbrange_rl,
             Protocol::BlocksByRoot => &mut self.bbroots_rl,
             Protocol::BlobsByRange => &mut self.blbrange_rl,
             Protocol::BlobsByRoot => &mut self.blbroot_rl,
@@ -2,12 +2,16 @@ use std::sync::Arc;
 
 use libp2p::swarm::ConnectionId;
 use types::{
-    BlobSidecar, DataColumnSidecar, EthSpec, Hash256, LightClientBootstrap,
-    LightClientFinalityUpdate, LightClientOptimisticUpdate, LightClientUpdate, SignedBeaconBlock,
+    BlobSidecar, DataColumnSidecar, EncodedLightClientUpdate, Epoch, EthSpec, Hash256,
+    LightClientBootstrap, LightClientFinalityUpdate, LightClientOptimisticUpdate,
+    LightClientUpdate, SignedBeaconBlock, SignedBlindedBeaconBlock,
 };
 
 use crate::rpc::{
-    methods::{ResponseTermination, RpcResponse, RpcSuccessResponse, StatusMessage},
+    methods::{
+        ExecutionPayloadBodyWithRoot, ResponseTermination, RpcResponse, RpcSuccessResponse,
+        StatusMessage,
+    },
     SubstreamId,
 };
 
@@ -47,6 +51,15 @@ pub struct DataColumnsByRootRequestId {
 pub enum DataColumnsByRootRequester {
     Sampling(SamplingId),
     Custody(CustodyId),
+    CustodyBackfill(CustodyBackfillId),
+}
+
+/// Identifier of custody backfill requests, which fetch the missing custody columns of a block
+/// that has already been imported.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub struct CustodyBackfillId {
+    pub epoch: Epoch,
+    pub block_root: Hash256,
 }
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
@@ -107,6 +120,12 @@ pub enum Response<E: EthSpec> {
     DataColumnsByRange(Option<Arc<DataColumnSidecar<E>>>),
     /// A response to a get BLOCKS_BY_ROOT request.
     BlocksByRoot(Option<Arc<SignedBeaconBlock<E>>>),
+    /// A response to a get BLINDED_BLOCKS_BY_RANGE request. A None response signals the end of
+    /// the batch.
+    BlindedBlocksByRange(Option<Arc<SignedBlindedBeaconBlock<E>>>),
+    /// A response to a get EXECUTION_PAYLOAD_BODIES_BY_RANGE request. A None response signals
+    /// the end of the batch.
+    ExecutionPayloadBodiesByRange(Option<Arc<ExecutionPayloadBodyWithRoot<E>>>),
     /// A response to a get BLOBS_BY_ROOT request.
     BlobsByRoot(Option<Arc<BlobSidecar<E>>>),
     /// A response to a get DATA_COLUMN_SIDECARS_BY_ROOT request.
@@ -119,6 +138,9 @@ pub enum Response<E: EthSpec> {
     LightClientFinalityUpdate(Arc<LightClientFinalityUpdate<E>>),
     /// A response to a LightClientUpdatesByRange request.
     LightClientUpdatesByRange(Option<Arc<LightClientUpdate<E>>>),
+    /// A response to a LightClientUpdatesByRange request, sent without re-encoding the update.
+    /// The stream is terminated with `LightClientUpdatesByRange(None)`.
+    LightClientUpdatesByRangeEncoded(Arc<EncodedLightClientUpdate>),
 }
 
 impl<E: EthSpec> std::convert::From<Response<E>> for RpcResponse<E> {
@@ -132,6 +154,18 @@ impl<E: EthSpec> std::convert::From<Response<E>> for RpcResponse<E> {
                 Some(b) => RpcResponse::Success(RpcSuccessResponse::BlocksByRange(b)),
                 None => RpcResponse::StreamTermination(ResponseTermination::BlocksByRange),
             },
+            Response::BlindedBlocksByRange(r) => match r {
+                Some(b) => RpcResponse::Success(RpcSuccessResponse::BlindedBlocksByRange(b)),
+                None => RpcResponse::StreamTermination(ResponseTermination::BlindedBlocksByRange),
+            },
+            Response::ExecutionPayloadBodiesByRange(r) => match r {
+                Some(b) => {
+                    RpcResponse::Success(RpcSuccessResponse::ExecutionPayloadBodiesByRange(b))
+                }
+                None => RpcResponse::StreamTermination(
+                    ResponseTermination::ExecutionPayloadBodiesByRange,
+                ),
+            },
             Response::BlobsByRoot(r) => match r {
                 Some(b) => RpcResponse::Success(RpcSuccessResponse::BlobsByRoot(b)),
                 None => RpcResponse::StreamTermination(ResponseTermination::BlobsByRoot),
@@ -164,6 +198,9 @@ impl<E: EthSpec> std::convert::From<Response<E>> for RpcResponse<E> {
                     RpcResponse::StreamTermination(ResponseTermination::LightClientUpdatesByRange)
                 }
             },
+            Response::LightClientUpdatesByRangeEncoded(u) => {
+                RpcResponse::Success(RpcSuccessResponse::LightClientUpdatesByRangeEncoded(u))
+            }
         }
     }
 }
@@ -14,9 +14,10 @@ use crate::rpc::{
     RequestType, ResponseTermination, RpcErrorResponse, RpcResponse, RpcSuccessResponse, RPC,
 };
 use crate::types::{
-    attestation_sync_committee_topics, fork_core_topics, subnet_from_topic_hash, GossipEncoding,
-    GossipKind, GossipTopic, SnappyTransform, Subnet, SubnetDiscovery, ALTAIR_CORE_TOPICS,
-    BASE_CORE_TOPICS, CAPELLA_CORE_TOPICS, DENEB_CORE_TOPICS, LIGHT_CLIENT_GOSSIP_TOPICS,
+    attestation_sync_committee_topics, fork_core_topics, subnet_from_topic_hash, FirstSeenKind,
+    GossipEncoding, GossipKind, GossipTopic, SnappyTransform, Subnet, SubnetDiscovery,
+    ALTAIR_CORE_TOPICS, BASE_CORE_TOPICS, CAPELLA_CORE_TOPICS, DENEB_CORE_TOPICS,
+    LIGHT_CLIENT_GOSSIP_TOPICS,
 };
 use crate::EnrExt;
 use crate::Eth2Enr;
@@ -38,7 +39,7 @@ use std::num::{NonZeroU8, NonZeroUsize};
 use std::path::PathBuf;
 use std::pin::Pin;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use types::{
     consts::altair::SYNC_COMMITTEE_SUBNET_COUNT, EnrForkId, EthSpec, ForkContext, Slot, SubnetId,
 };
@@ -236,6 +237,9 @@ impl<E: EthSpec> Network<E> {
             ctx.chain_spec.seconds_per_slot,
             E::slots_per_epoch(),
             config.idontwant_message_size_threshold,
+            config
+                .staggered_publish_peers
+                .map(|peers| (peers, config.staggered_publish_delay)),
         );
 
         let score_settings = PeerScoreSettings::new(&ctx.chain_spec, gs_config.mesh_n());
@@ -362,6 +366,8 @@ impl<E: EthSpec> Network<E> {
         let eth2_rpc = RPC::new(
             ctx.fork_context.clone(),
             config.enable_light_client_server,
+            config.enable_blinded_blocks_by_range,
+            config.enable_execution_payload_bodies_by_range,
             config.inbound_rate_limiter_config.clone(),
             config.outbound_rate_limiter_config.clone(),
             log.clone(),
@@ -404,6 +410,7 @@ impl<E: EthSpec> Network<E> {
             let peer_manager_cfg = PeerManagerCfg {
                 discovery_enabled: !config.disable_discovery,
                 quic_enabled: !config.disable_quic_support,
+                transport_preference: config.transport_preference,
                 metrics_enabled: config.metrics_enabled,
                 target_peer_count: config.target_peers,
                 ..Default::default()
@@ -714,7 +721,11 @@ impl<E: EthSpec> Network<E> {
         }
 
         // Subscribe to core topics for the new fork
-        for kind in fork_core_topics::<E>(&new_fork, &self.fork_context.spec) {
+        let rpc_serving_only = self.network_globals.config.rpc_serving_only;
+        for kind in fork_core_topics::<E>(&new_fork, &self.fork_context.spec)
+            .into_iter()
+            .filter(|kind| !rpc_serving_only || kind.is_required_to_follow_head())
+        {
             let topic = GossipTopic::new(kind, GossipEncoding::default(), new_fork_digest);
             self.subscribe(topic);
         }
@@ -973,6 +984,10 @@ impl<E: EthSpec> Network<E> {
             return Err((request_id, RPCError::Disconnected));
         }
 
+        if let RequestType::Status(_) = request {
+            self.peer_manager_mut().status_request_sent(&peer_id);
+        }
+
         self.eth2_rpc_mut()
             .send_request(peer_id, RequestId::Application(request_id), request);
         Ok(())
@@ -1261,6 +1276,14 @@ impl<E: EthSpec> Network<E> {
                         }
                     }
                     Ok(msg) => {
+                        if let Some(kind) = FirstSeenKind::from_topic(gs_msg.topic.as_str()) {
+                            self.network_globals.first_seen.write().on_first_delivery(
+                                id.clone(),
+                                propagation_source,
+                                kind,
+                                Instant::now(),
+                            );
+                        }
                         // Notify the network
                         return Some(NetworkEvent::PubsubMessage {
                             id,
@@ -1271,6 +1294,16 @@ impl<E: EthSpec> Network<E> {
                     }
                 }
             }
+            gossipsub::Event::DuplicateMessage {
+                propagation_source,
+                message_id,
+                topic: _,
+            } => {
+                self.network_globals
+                    .first_seen
+                    .write()
+                    .on_duplicate_delivery(&message_id, &propagation_source, Instant::now());
+            }
             gossipsub::Event::Subscribed { peer_id, topic } => {
                 if let Ok(topic) = GossipTopic::decode(topic.as_str()) {
                     if let Some(subnet_id) = topic.subnet_id() {
@@ -1508,6 +1541,28 @@ impl<E: EthSpec> Network<E> {
                             request,
                         })
                     }
+                    RequestType::BlindedBlocksByRange(_) => {
+                        metrics::inc_counter_vec(
+                            &metrics::TOTAL_RPC_REQUESTS,
+                            &["blinded_blocks_by_range"],
+                        );
+                        Some(NetworkEvent::RequestReceived {
+                            peer_id,
+                            id: (connection_id, request.substream_id),
+                            request,
+                        })
+                    }
+                    RequestType::ExecutionPayloadBodiesByRange(_) => {
+                        metrics::inc_counter_vec(
+                            &metrics::TOTAL_RPC_REQUESTS,
+                            &["execution_payload_bodies_by_range"],
+                        );
+                        Some(NetworkEvent::RequestReceived {
+                            peer_id,
+                            id: (connection_id, request.substream_id),
+                            request,
+                        })
+                    }
                     RequestType::BlobsByRange(_) => {
                         metrics::inc_counter_vec(&metrics::TOTAL_RPC_REQUESTS, &["blobs_by_range"]);
                         Some(NetworkEvent::RequestReceived {
@@ -1606,8 +1661,8 @@ impl<E: EthSpec> Network<E> {
                     }
                     /* Network propagated protocols */
                     RpcSuccessResponse::Status(msg) => {
-                        // inform the peer manager that we have received a status from a peer
-                        self.peer_manager_mut().peer_statusd(&peer_id);
+                        // inform the peer manager that a peer has responded to our status
+                        self.peer_manager_mut().status_response(&peer_id);
                         // propagate the STATUS message upwards
                         self.build_response(id, peer_id, Response::Status(msg))
                     }
@@ -1620,6 +1675,14 @@ impl<E: EthSpec> Network<E> {
                     RpcSuccessResponse::BlocksByRoot(resp) => {
                         self.build_response(id, peer_id, Response::BlocksByRoot(Some(resp)))
                     }
+                    RpcSuccessResponse::BlindedBlocksByRange(resp) => {
+                        self.build_response(id, peer_id, Response::BlindedBlocksByRange(Some(resp)))
+                    }
+                    RpcSuccessResponse::ExecutionPayloadBodiesByRange(resp) => self.build_response(
+                        id,
+                        peer_id,
+                        Response::ExecutionPayloadBodiesByRange(Some(resp)),
+                    ),
                     RpcSuccessResponse::BlobsByRoot(resp) => {
                         self.build_response(id, peer_id, Response::BlobsByRoot(Some(resp)))
                     }
@@ -1648,12 +1711,24 @@ impl<E: EthSpec> Network<E> {
                         peer_id,
                         Response::LightClientUpdatesByRange(Some(update)),
                     ),
+                    RpcSuccessResponse::LightClientUpdatesByRangeEncoded(update) => self
+                        .build_response(
+                            id,
+                            peer_id,
+                            Response::LightClientUpdatesByRangeEncoded(update),
+                        ),
                 }
             }
             Ok(RPCReceived::EndOfStream(id, termination)) => {
                 let response = match termination {
                     ResponseTermination::BlocksByRange => Response::BlocksByRange(None),
                     ResponseTermination::BlocksByRoot => Response::BlocksByRoot(None),
+                    ResponseTermination::BlindedBlocksByRange => {
+                        Response::BlindedBlocksByRange(None)
+                    }
+                    ResponseTermination::ExecutionPayloadBodiesByRange => {
+                        Response::ExecutionPayloadBodiesByRange(None)
+                    }
                     ResponseTermination::BlobsByRange => Response::BlobsByRange(None),
                     ResponseTermination::BlobsByRoot => Response::BlobsByRoot(None),
                     ResponseTermination::DataColumnsByRoot => Response::DataColumnsByRoot(None),
@@ -0,0 +1,18 @@
+//! A limit on the rate at which backfill sync processes batches of historic blocks.
+//!
+//! Every batch processed by backfill sync is written to disk, which on slow disks can delay the
+//! processing of gossip and therefore the node's attestations. The limit is initialised from the
+//! `--backfill-batches-per-minute` flag and can be changed at runtime via the HTTP API.
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// The window over which the number of processed batches is limited.
+pub const BACKFILL_THROTTLE_WINDOW: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackfillThrottle {
+    /// The maximum number of non-empty batches to process per minute, or `None` if unlimited.
+    ///
+    /// A limit of zero pauses the processing of backfill batches.
+    pub batches_per_minute: Option<u64>,
+}
@@ -0,0 +1,13 @@
+use super::Enr;
+use serde::{Deserialize, Serialize};
+
+/// A peer exported from the peer database of one node, so that it can be imported by another.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedPeer {
+    pub peer_id: String,
+    pub enr: Enr,
+    /// The score of the peer at the time it was exported.
+    pub score: f64,
+    /// The data column subnets the peer is assigned to custody.
+    pub custody_subnets: Vec<u64>,
+}
@@ -0,0 +1,239 @@
+//! Attribution of gossiped blocks, blobs and data columns to the peer which delivered them first.
+//!
+//! Gossipsub only passes the first copy of each message to the application. For the topics
+//! selected by `FirstSeenKind::from_topic` it additionally reports later copies, which allows us
+//! to measure how far ahead of the next-fastest peer the first delivery was.
+use super::topics::{BEACON_BLOCK_TOPIC, BLOB_SIDECAR_PREFIX, DATA_COLUMN_SIDECAR_PREFIX};
+use crate::metrics;
+use gossipsub::MessageId;
+use libp2p::PeerId;
+use lru::LruCache;
+use serde::{Deserialize, Serialize};
+use std::num::NonZeroUsize;
+use std::time::{Duration, Instant};
+use strum::AsRefStr;
+use types::non_zero_usize::new_non_zero_usize;
+
+/// The number of recent messages for which we wait for a second delivery.
+///
+/// This covers several slots worth of blocks, blobs and sampled data columns.
+const PENDING_MESSAGES_CAPACITY: NonZeroUsize = new_non_zero_usize(2_048);
+/// The number of peers for which first delivery statistics are kept.
+const PEER_STATS_CAPACITY: NonZeroUsize = new_non_zero_usize(1_024);
+
+/// The kinds of gossip messages for which the first delivering peer is recorded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AsRefStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum FirstSeenKind {
+    Block,
+    Blob,
+    DataColumn,
+}
+
+impl FirstSeenKind {
+    /// Returns the kind of message gossiped on `topic`, or `None` if first deliveries on this
+    /// topic are not recorded.
+    ///
+    /// This is called for every duplicate message so it avoids fully decoding the topic.
+    pub fn from_topic(topic: &str) -> Option<Self> {
+        let name = topic.split('/').nth(3)?;
+        if name == BEACON_BLOCK_TOPIC {
+            Some(Self::Block)
+        } else if name.starts_with(BLOB_SIDECAR_PREFIX) {
+            Some(Self::Blob)
+        } else if name.starts_with(DATA_COLUMN_SIDECAR_PREFIX) {
+            Some(Self::DataColumn)
+        } else {
+            None
+        }
+    }
+}
+
+/// Statistics about the messages a peer delivered to us before any other peer.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FirstDeliveryStats {
+    /// The number of blocks first delivered by this peer.
+    pub blocks: u64,
+    /// The number of blob sidecars first delivered by this peer.
+    pub blobs: u64,
+    /// The number of data column sidecars first delivered by this peer.
+    pub data_columns: u64,
+    /// The number of first deliveries which were later also delivered by another peer.
+    pub contested: u64,
+    /// The sum of the times by which contested deliveries were ahead of the second peer.
+    pub total_lead_time_ms: u64,
+}
+
+impl FirstDeliveryStats {
+    /// The total number of messages first delivered by this peer.
+    pub fn total(&self) -> u64 {
+        self.blocks
+            .saturating_add(self.blobs)
+            .saturating_add(self.data_columns)
+    }
+
+    /// The mean time by which contested deliveries were ahead of the second peer.
+    pub fn mean_lead_time(&self) -> Option<Duration> {
+        self.total_lead_time_ms
+            .checked_div(self.contested)
+            .map(Duration::from_millis)
+    }
+}
+
+/// A message for which we have not yet seen a second delivery.
+struct PendingDelivery {
+    peer_id: PeerId,
+    kind: FirstSeenKind,
+    seen_at: Instant,
+}
+
+/// Records the peer which first delivered each block, blob and data column.
+pub struct FirstSeenTracker {
+    pending: LruCache<MessageId, PendingDelivery>,
+    peers: LruCache<PeerId, FirstDeliveryStats>,
+}
+
+impl Default for FirstSeenTracker {
+    fn default() -> Self {
+        Self {
+            pending: LruCache::new(PENDING_MESSAGES_CAPACITY),
+            peers: LruCache::new(PEER_STATS_CAPACITY),
+        }
+    }
+}
+
+impl FirstSeenTracker {
+    /// Register the first delivery of `message_id` by `peer_id`.
+    pub fn on_first_delivery(
+        &mut self,
+        message_id: MessageId,
+        peer_id: PeerId,
+        kind: FirstSeenKind,
+        now: Instant,
+    ) {
+        let stats = self
+            .peers
+            .get_or_insert_mut(peer_id, FirstDeliveryStats::default);
+        match kind {
+            FirstSeenKind::Block => stats.blocks += 1,
+            FirstSeenKind::Blob => stats.blobs += 1,
+            FirstSeenKind::DataColumn => stats.data_columns += 1,
+        }
+        self.pending.put(
+            message_id,
+            PendingDelivery {
+                peer_id,
+                kind,
+                seen_at: now,
+            },
+        );
+    }
+
+    /// Register a later delivery of `message_id` by `peer_id`.
+    ///
+    /// Only the first duplicate from a peer other than the first source is counted, since it
+    /// determines how much earlier we received the message thanks to the first peer.
+    pub fn on_duplicate_delivery(
+        &mut self,
+        message_id: &MessageId,
+        peer_id: &PeerId,
+        now: Instant,
+    ) {
+        if self
+            .pending
+            .peek(message_id)
+            .map_or(true, |pending| pending.peer_id == *peer_id)
+        {
+            return;
+        }
+        let Some(pending) = self.pending.pop(message_id) else {
+            return;
+        };
+
+        let lead_time = now.saturating_duration_since(pending.seen_at);
+        metrics::observe_timer_vec(
+            &metrics::GOSSIP_FIRST_DELIVERY_LEAD_TIME,
+            &[pending.kind.as_ref()],
+            lead_time,
+        );
+        if let Some(stats) = self.peers.get_mut(&pending.peer_id) {
+            stats.contested += 1;
+            stats.total_lead_time_ms = stats
+                .total_lead_time_ms
+                .saturating_add(lead_time.as_millis() as u64);
+        }
+    }
+
+    /// Returns the first delivery statistics of all tracked peers.
+    pub fn peer_stats(&self) -> impl Iterator<Item = (&PeerId, &FirstDeliveryStats)> {
+        self.peers.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kind_from_topic() {
+        let topic = |name: &str| format!("/eth2/6a95a1a9/{name}/ssz_snappy");
+        assert_eq!(
+            FirstSeenKind::from_topic(&topic("beacon_block")),
+            Some(FirstSeenKind::Block)
+        );
+        assert_eq!(
+            FirstSeenKind::from_topic(&topic("blob_sidecar_3")),
+            Some(FirstSeenKind::Blob)
+        );
+        assert_eq!(
+            FirstSeenKind::from_topic(&topic("data_column_sidecar_42")),
+            Some(FirstSeenKind::DataColumn)
+        );
+        assert_eq!(
+            FirstSeenKind::from_topic(&topic("beacon_attestation_1")),
+            None
+        );
+        assert_eq!(FirstSeenKind::from_topic("beacon_block"), None);
+    }
+
+    #[test]
+    fn lead_time_attributed_to_first_peer() {
+        let mut tracker = FirstSeenTracker::default();
+        let first = PeerId::random();
+        let second = PeerId::random();
+        let third = PeerId::random();
+        let message_id = MessageId::new(&[1]);
+        let start = Instant::now();
+
+        tracker.on_first_delivery(message_id.clone(), first, FirstSeenKind::Block, start);
+        // A repeat from the first peer is not a second source.
+        tracker.on_duplicate_delivery(&message_id, &first, start + Duration::from_millis(50));
+        tracker.on_duplicate_delivery(&message_id, &second, start + Duration::from_millis(200));
+        // Only the second source counts.
+        tracker.on_duplicate_delivery(&message_id, &third, start + Duration::from_millis(900));
+
+        let stats = tracker.peers.peek(&first).unwrap();
+        assert_eq!(stats.blocks, 1);
+        assert_eq!(stats.total(), 1);
+        assert_eq!(stats.contested, 1);
+        assert_eq!(stats.mean_lead_time(), Some(Duration::from_millis(200)));
+        assert!(tracker.peers.peek(&second).is_none());
+    }
+
+    #[test]
+    fn uncontested_delivery_has_no_lead_time() {
+        let mut tracker = FirstSeenTracker::default();
+        let peer_id = PeerId::random();
+        tracker.on_first_delivery(
+            MessageId::new(&[2]),
+            peer_id,
+            FirstSeenKind::DataColumn,
+            Instant::now(),
+        );
+
+        let stats = tracker.peers.peek(&peer_id).unwrap();
+        assert_eq!(stats.data_columns, 1);
+        assert_eq!(stats.contested, 0);
+        assert_eq!(stats.mean_lead_time(), None);
+    }
+}
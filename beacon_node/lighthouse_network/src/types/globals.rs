@@ -1,11 +1,15 @@
 //! A collection of variables that are accessible outside of the network thread itself.
 use crate::peer_manager::peerdb::PeerDB;
 use crate::rpc::{MetaData, MetaDataV3};
-use crate::types::{BackFillState, SyncState};
-use crate::{Client, Enr, EnrExt, GossipTopic, Multiaddr, NetworkConfig, PeerId};
+use crate::types::{
+    BackFillState, BackfillThrottle, FirstSeenTracker, GossipTopicSubscription, PortMappingState,
+    SubnetSubscription, SubscriptionReason, SyncChainCandidate, SyncProgress, SyncState,
+    LIGHT_CLIENT_GOSSIP_TOPICS,
+};
+use crate::{Client, Enr, EnrExt, GossipTopic, Multiaddr, NetworkConfig, PeerId, Subnet};
 use itertools::Itertools;
 use parking_lot::RwLock;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use types::{ChainSpec, ColumnIndex, DataColumnSubnetId, EthSpec};
 
@@ -22,10 +26,22 @@ pub struct NetworkGlobals<E: EthSpec> {
     pub local_metadata: RwLock<MetaData<E>>,
     /// The current gossipsub topic subscriptions.
     pub gossipsub_subscriptions: RwLock<HashSet<GossipTopic>>,
+    /// Why the node is subscribed to each subnet, and until when.
+    pub subnet_subscriptions: RwLock<HashMap<Subnet, SubnetSubscription>>,
     /// The current sync status of the node.
     pub sync_state: RwLock<SyncState>,
     /// The current state of the backfill sync.
     pub backfill_state: RwLock<BackFillState>,
+    /// The limit on the rate at which backfill sync processes batches.
+    pub backfill_throttle: RwLock<BackfillThrottle>,
+    /// The throughput of forward and backfill sync.
+    pub sync_progress: RwLock<SyncProgress>,
+    /// The candidate chains of range sync, as of its last update.
+    pub sync_chains: RwLock<Vec<SyncChainCandidate>>,
+    /// The peers which first delivered recent blocks, blobs and data columns over gossip.
+    pub first_seen: RwLock<FirstSeenTracker>,
+    /// The state of the UPnP discovery port mapping, if one is being maintained.
+    pub port_mapping: RwLock<Option<PortMappingState>>,
     /// The computed sampling subnets and columns is stored to avoid re-computing.
     pub sampling_subnets: Vec<DataColumnSubnetId>,
     pub sampling_columns: Vec<ColumnIndex>,
@@ -81,8 +97,16 @@ impl<E: EthSpec> NetworkGlobals<E> {
             local_metadata: RwLock::new(local_metadata),
             peers: RwLock::new(PeerDB::new(trusted_peers, disable_peer_scoring, log)),
             gossipsub_subscriptions: RwLock::new(HashSet::new()),
+            subnet_subscriptions: RwLock::new(HashMap::new()),
             sync_state: RwLock::new(SyncState::Stalled),
             backfill_state: RwLock::new(BackFillState::Paused),
+            backfill_throttle: RwLock::new(BackfillThrottle {
+                batches_per_minute: config.backfill_batches_per_minute,
+            }),
+            sync_progress: RwLock::new(SyncProgress::default()),
+            sync_chains: RwLock::new(Vec::new()),
+            first_seen: RwLock::new(FirstSeenTracker::default()),
+            port_mapping: RwLock::new(None),
             sampling_subnets,
             sampling_columns,
             config,
@@ -145,6 +169,43 @@ impl<E: EthSpec> NetworkGlobals<E> {
             .unwrap_or_default()
     }
 
+    /// Returns the gossip topics the node is subscribed to, along with why and until when, sorted
+    /// by topic.
+    pub fn gossip_topic_subscriptions(&self) -> Vec<GossipTopicSubscription> {
+        let subnet_subscriptions = self.subnet_subscriptions.read();
+        self.gossipsub_subscriptions
+            .read()
+            .iter()
+            .map(|topic| {
+                let subscription = match topic.subnet_id() {
+                    Some(subnet) => {
+                        subnet_subscriptions
+                            .get(&subnet)
+                            .copied()
+                            .unwrap_or_else(|| match subnet {
+                                Subnet::DataColumn(_) => {
+                                    SubnetSubscription::long_lived(SubscriptionReason::Custody)
+                                }
+                                Subnet::Attestation(_) | Subnet::SyncCommittee(_) => {
+                                    SubnetSubscription::long_lived(SubscriptionReason::Duty)
+                                }
+                            })
+                    }
+                    None if LIGHT_CLIENT_GOSSIP_TOPICS.contains(topic.kind()) => {
+                        SubnetSubscription::long_lived(SubscriptionReason::LightClient)
+                    }
+                    None => SubnetSubscription::long_lived(SubscriptionReason::Core),
+                };
+                GossipTopicSubscription {
+                    topic: topic.to_string(),
+                    reason: subscription.reason,
+                    until_slot: subscription.until_slot,
+                }
+            })
+            .sorted_by(|a, b| a.topic.cmp(&b.topic))
+            .collect()
+    }
+
     /// Updates the syncing state of the node.
     ///
     /// The old state is returned
@@ -251,6 +312,58 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_gossip_topic_subscriptions() {
+        use crate::types::{GossipEncoding, GossipKind};
+        use types::{Slot, SubnetId};
+
+        let log = logging::test_logger();
+        let config = Arc::new(NetworkConfig::default());
+        let globals = NetworkGlobals::<E>::new_test_globals(
+            vec![],
+            &log,
+            config,
+            Arc::new(E::default_spec()),
+        );
+
+        let subnet = Subnet::Attestation(SubnetId::new(3));
+        let topic = |kind| GossipTopic::new(kind, GossipEncoding::default(), [0; 4]);
+        let block_topic = topic(GossipKind::BeaconBlock);
+        let light_client_topic = topic(GossipKind::LightClientFinalityUpdate);
+        let subnet_topic = topic(subnet.into());
+        globals.gossipsub_subscriptions.write().extend([
+            block_topic.clone(),
+            light_client_topic.clone(),
+            subnet_topic.clone(),
+        ]);
+        globals.subnet_subscriptions.write().insert(
+            subnet,
+            SubnetSubscription {
+                reason: SubscriptionReason::Duty,
+                until_slot: Some(Slot::new(10)),
+            },
+        );
+
+        let subscriptions = globals.gossip_topic_subscriptions();
+        let find = |topic: &GossipTopic| {
+            subscriptions
+                .iter()
+                .find(|subscription| subscription.topic == topic.to_string())
+                .map(|subscription| (subscription.reason, subscription.until_slot))
+                .unwrap()
+        };
+        assert_eq!(subscriptions.len(), 3);
+        assert_eq!(find(&block_topic), (SubscriptionReason::Core, None));
+        assert_eq!(
+            find(&light_client_topic),
+            (SubscriptionReason::LightClient, None)
+        );
+        assert_eq!(
+            find(&subnet_topic),
+            (SubscriptionReason::Duty, Some(Slot::new(10)))
+        );
+    }
+
     fn get_metadata(custody_subnet_count: u64) -> MetaData<E> {
         MetaData::V3(MetaDataV3 {
             seq_number: 0,
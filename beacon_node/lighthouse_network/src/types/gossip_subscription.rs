@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+use types::Slot;
+
+/// The reason the node is subscribed to a gossip topic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubscriptionReason {
+    /// A core topic of the current fork.
+    Core,
+    /// A light client topic, subscribed to when the light client server is enabled.
+    LightClient,
+    /// A long-lived attestation subnet derived from the node id.
+    Backbone,
+    /// A short-lived subnet required by an attestation aggregation or sync committee duty.
+    Duty,
+    /// A data column subnet the node custodies or samples.
+    Custody,
+    /// The node is configured to subscribe to all subnets.
+    AllSubnets,
+}
+
+/// Why the node is subscribed to a subnet, and until when.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SubnetSubscription {
+    pub reason: SubscriptionReason,
+    /// The slot at which a short-lived subscription expires. `None` for long-lived subscriptions.
+    pub until_slot: Option<Slot>,
+}
+
+impl SubnetSubscription {
+    pub fn long_lived(reason: SubscriptionReason) -> Self {
+        Self {
+            reason,
+            until_slot: None,
+        }
+    }
+}
+
+/// A gossip topic the node is subscribed to, along with why and until when.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GossipTopicSubscription {
+    pub topic: String,
+    pub reason: SubscriptionReason,
+    pub until_slot: Option<Slot>,
+}
@@ -1,6 +1,13 @@
+mod backfill_throttle;
+mod exported_peer;
+mod first_seen;
 mod globals;
+mod gossip_subscription;
+mod port_mapping;
 mod pubsub;
 mod subnet;
+mod sync_chains;
+mod sync_progress;
 mod sync_state;
 mod topics;
 
@@ -11,9 +18,16 @@ pub type EnrSyncCommitteeBitfield<E> = BitVector<<E as EthSpec>::SyncCommitteeSu
 
 pub type Enr = discv5::enr::Enr<discv5::enr::CombinedKey>;
 
+pub use backfill_throttle::{BackfillThrottle, BACKFILL_THROTTLE_WINDOW};
+pub use exported_peer::ExportedPeer;
+pub use first_seen::{FirstDeliveryStats, FirstSeenKind, FirstSeenTracker};
 pub use globals::NetworkGlobals;
+pub use gossip_subscription::{GossipTopicSubscription, SubnetSubscription, SubscriptionReason};
+pub use port_mapping::{PortMappingMethod, PortMappingState};
 pub use pubsub::{PubsubMessage, SnappyTransform};
 pub use subnet::{Subnet, SubnetDiscovery};
+pub use sync_chains::{SyncChainCandidate, SyncChainKind};
+pub use sync_progress::{SyncKind, SyncProgress, SyncProgressInfo, SyncThroughput};
 pub use sync_state::{BackFillState, SyncState};
 pub use topics::{
     attestation_sync_committee_topics, core_topics_to_subscribe, fork_core_topics,
@@ -0,0 +1,59 @@
+//! The health of the port mappings that the node maintains on the local gateway.
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+
+/// The protocol used to create a port mapping on the local gateway.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PortMappingMethod {
+    Upnp,
+    NatPmp,
+}
+
+/// The state of the discovery port mapping maintained on the local gateway via UPnP, or NAT-PMP
+/// if the gateway doesn't support UPnP.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PortMappingState {
+    /// The external address which is mapped to our discovery port, if the mapping is active.
+    pub external_address: Option<SocketAddr>,
+    /// The protocol with which the current mapping was created.
+    pub method: Option<PortMappingMethod>,
+    /// The Unix timestamp in seconds at which the mapping was last created or renewed.
+    pub last_renewal: Option<u64>,
+    /// The Unix timestamp in seconds at which the current mapping expires on the gateway.
+    pub expires_at: Option<u64>,
+    /// The number of consecutive failed attempts to create or renew the mapping.
+    pub consecutive_failures: u32,
+    /// The error of the most recent failed attempt, if the last attempt failed.
+    pub last_error: Option<String>,
+}
+
+impl PortMappingState {
+    /// Records a successful mapping of `external_address`, valid for `duration_secs` from `now`.
+    pub fn on_renewed(
+        &mut self,
+        external_address: SocketAddr,
+        method: PortMappingMethod,
+        now: u64,
+        duration_secs: u64,
+    ) {
+        self.external_address = Some(external_address);
+        self.method = Some(method);
+        self.last_renewal = Some(now);
+        self.expires_at = Some(now + duration_secs);
+        self.consecutive_failures = 0;
+        self.last_error = None;
+    }
+
+    /// Records a failed attempt at `now`. An existing mapping is only considered lost once its
+    /// lease has expired.
+    pub fn on_failure(&mut self, error: String, now: u64) {
+        self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+        self.last_error = Some(error);
+        if self.expires_at.map_or(true, |expires_at| expires_at <= now) {
+            self.external_address = None;
+            self.method = None;
+            self.expires_at = None;
+        }
+    }
+}
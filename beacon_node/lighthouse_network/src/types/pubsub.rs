@@ -14,7 +14,8 @@ use types::{
     SignedAggregateAndProofElectra, SignedBeaconBlock, SignedBeaconBlockAltair,
     SignedBeaconBlockBase, SignedBeaconBlockBellatrix, SignedBeaconBlockCapella,
     SignedBeaconBlockDeneb, SignedBeaconBlockElectra, SignedBlsToExecutionChange,
-    SignedContributionAndProof, SignedVoluntaryExit, SubnetId, SyncCommitteeMessage, SyncSubnetId,
+    SignedContributionAndProof, SignedVoluntaryExit, Slot, SubnetId, SyncCommitteeMessage,
+    SyncSubnetId,
 };
 
 #[derive(Debug, Clone, PartialEq)]
@@ -141,6 +142,31 @@ impl<E: EthSpec> PubsubMessage<E> {
         }
     }
 
+    /// Returns the slot of the message, for messages which are only relevant around their slot.
+    pub fn slot(&self) -> Option<Slot> {
+        match self {
+            PubsubMessage::BeaconBlock(block) => Some(block.slot()),
+            PubsubMessage::BlobSidecar(blob_sidecar_data) => Some(blob_sidecar_data.1.slot()),
+            PubsubMessage::DataColumnSidecar(column_sidecar_data) => {
+                Some(column_sidecar_data.1.slot())
+            }
+            PubsubMessage::AggregateAndProofAttestation(aggregate) => {
+                Some(aggregate.message().aggregate().data().slot)
+            }
+            PubsubMessage::Attestation(attestation_data) => Some(attestation_data.1.data().slot),
+            PubsubMessage::SignedContributionAndProof(contribution) => {
+                Some(contribution.message.contribution.slot)
+            }
+            PubsubMessage::SyncCommitteeMessage(data) => Some(data.1.slot),
+            PubsubMessage::VoluntaryExit(_)
+            | PubsubMessage::ProposerSlashing(_)
+            | PubsubMessage::AttesterSlashing(_)
+            | PubsubMessage::BlsToExecutionChange(_)
+            | PubsubMessage::LightClientFinalityUpdate(_)
+            | PubsubMessage::LightClientOptimisticUpdate(_) => None,
+        }
+    }
+
     /// This decodes `data` into a `PubsubMessage` given a topic.
     /* Note: This is assuming we are not hashing topics. If we choose to hash topics, these will
      * need to be modified.
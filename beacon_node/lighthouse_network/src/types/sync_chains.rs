@@ -0,0 +1,37 @@
+//! The candidate chains of range sync.
+//!
+//! Range sync publishes its chains here each time it re-evaluates which of them to sync, so that
+//! the HTTP API can show why a sync is stuck on, or switching between, particular chains.
+use serde::{Deserialize, Serialize};
+use types::{Epoch, Hash256, Slot};
+
+/// The kind of target a range sync chain is syncing towards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncChainKind {
+    /// The finalized checkpoint of peers which have finalized beyond us.
+    Finalized,
+    /// The head of peers which share our finalized checkpoint.
+    Head,
+}
+
+/// A chain which range sync may sync towards.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncChainCandidate {
+    pub id: u64,
+    pub kind: SyncChainKind,
+    pub start_epoch: Epoch,
+    pub target_head_slot: Slot,
+    pub target_head_root: Hash256,
+    /// The number of peers serving the chain.
+    pub peers: u64,
+    /// The number of peers whose finalized checkpoint is known to agree with ours.
+    pub agreeing_peers: u64,
+    /// The weight of the chain, derived from the scores of its peers and their agreement with our
+    /// finalized checkpoint. Head chains are synced in order of weight.
+    pub weight: f64,
+    /// Whether the chain is currently being synced.
+    pub syncing: bool,
+    /// The number of epochs processed by the chain.
+    pub processed_epochs: u64,
+}
@@ -0,0 +1,250 @@
+//! Throughput and estimated completion time of forward sync, backfill sync and custody backfill
+//! sync.
+//!
+//! Sync records the blocks, blobs and data columns it imports here, and the notifier records the
+//! slot progress of the active sync once per slot. The notifier's logs, its metrics and the HTTP
+//! API all read the resulting `SyncThroughput`.
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+use types::Slot;
+
+/// The number of observations used to determine the average sync speed.
+const SPEEDO_OBSERVATIONS: usize = 5;
+
+/// The kinds of sync for which progress is tracked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncKind {
+    /// Range sync towards the head of the chain.
+    Forward,
+    /// Backfill sync of historic blocks.
+    Backfill,
+    /// Backfill of the data columns of newly custodied subnets.
+    CustodyBackfill,
+}
+
+/// The throughput of a sync and an estimate of when it will complete.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncThroughput {
+    pub slots_per_second: Option<f64>,
+    pub blocks_per_second: Option<f64>,
+    pub blobs_per_second: Option<f64>,
+    pub data_columns_per_second: Option<f64>,
+    /// The number of slots remaining until the sync completes.
+    pub distance: u64,
+    /// The estimated number of seconds until the sync completes at the current speed.
+    pub estimated_seconds_remaining: Option<f64>,
+}
+
+/// The progress of all syncs. A sync which is not currently running has no progress.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncProgressInfo {
+    pub forward: Option<SyncThroughput>,
+    pub backfill: Option<SyncThroughput>,
+    #[serde(default)]
+    pub custody_backfill: Option<SyncThroughput>,
+}
+
+/// Cumulative counts of the data imported by a sync.
+#[derive(Debug, Default, Clone, Copy)]
+struct ImportTotals {
+    blocks: u64,
+    blobs: u64,
+    data_columns: u64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Observation {
+    slot: Slot,
+    instant: Instant,
+    totals: ImportTotals,
+}
+
+/// "Speedo" is Australian for speedometer. This struct observes the progress of a single sync.
+#[derive(Debug, Default)]
+struct Speedo {
+    observations: Vec<Observation>,
+    totals: ImportTotals,
+    target_slot: Slot,
+}
+
+impl Speedo {
+    fn observe(&mut self, slot: Slot, target_slot: Slot, instant: Instant) {
+        if self.observations.len() >= SPEEDO_OBSERVATIONS {
+            self.observations.remove(0);
+        }
+        self.observations.push(Observation {
+            slot,
+            instant,
+            totals: self.totals,
+        });
+        self.target_slot = target_slot;
+    }
+
+    /// Returns the average of the rates of change of `value` between each pair of consecutive
+    /// observations.
+    fn rate(&self, value: impl Fn(&Observation) -> u64) -> Option<f64> {
+        let rates = self
+            .observations
+            .windows(2)
+            .filter_map(|window| {
+                let distance = value(&window[1]).saturating_sub(value(&window[0])) as f64;
+                let seconds = window[1]
+                    .instant
+                    .saturating_duration_since(window[0].instant)
+                    .as_secs_f64();
+                (seconds > 0.0).then(|| distance / seconds)
+            })
+            .collect::<Vec<_>>();
+
+        (!rates.is_empty()).then(|| rates.iter().sum::<f64>() / rates.len() as f64)
+    }
+
+    fn throughput(&self) -> Option<SyncThroughput> {
+        let latest = self.observations.last()?;
+        let slots_per_second = self.rate(|observation| observation.slot.as_u64());
+        let distance = self.target_slot.saturating_sub(latest.slot).as_u64();
+        let estimated_seconds_remaining = slots_per_second
+            .filter(|speed| *speed > 0.0 && distance > 0)
+            .map(|speed| distance as f64 / speed);
+
+        Some(SyncThroughput {
+            slots_per_second,
+            blocks_per_second: self.rate(|observation| observation.totals.blocks),
+            blobs_per_second: self.rate(|observation| observation.totals.blobs),
+            data_columns_per_second: self.rate(|observation| observation.totals.data_columns),
+            distance,
+            estimated_seconds_remaining,
+        })
+    }
+}
+
+/// Tracks the progress of forward, backfill and custody backfill sync.
+#[derive(Debug, Default)]
+pub struct SyncProgress {
+    forward: Speedo,
+    backfill: Speedo,
+    custody_backfill: Speedo,
+}
+
+impl SyncProgress {
+    fn speedo_mut(&mut self, kind: SyncKind) -> &mut Speedo {
+        match kind {
+            SyncKind::Forward => &mut self.forward,
+            SyncKind::Backfill => &mut self.backfill,
+            SyncKind::CustodyBackfill => &mut self.custody_backfill,
+        }
+    }
+
+    /// Record that a sync imported the given number of blocks, blobs and data columns.
+    pub fn record_import(
+        &mut self,
+        kind: SyncKind,
+        blocks: usize,
+        blobs: usize,
+        data_columns: usize,
+    ) {
+        let totals = &mut self.speedo_mut(kind).totals;
+        totals.blocks = totals.blocks.saturating_add(blocks as u64);
+        totals.blobs = totals.blobs.saturating_add(blobs as u64);
+        totals.data_columns = totals.data_columns.saturating_add(data_columns as u64);
+    }
+
+    /// Observe that a sync had progressed to `slot` at `instant`, on its way to `target_slot`.
+    ///
+    /// Backfill and custody backfill sync progress towards lower slots, so they should be observed
+    /// with the number of slots backfilled so far and the total number of slots to backfill.
+    pub fn observe(&mut self, kind: SyncKind, slot: Slot, target_slot: Slot, instant: Instant) {
+        self.speedo_mut(kind).observe(slot, target_slot, instant);
+    }
+
+    /// Clear the observations of a sync which is no longer running.
+    pub fn clear(&mut self, kind: SyncKind) {
+        self.speedo_mut(kind).observations.clear();
+    }
+
+    /// Returns the throughput of a sync, or `None` if it has not been observed since it was last
+    /// cleared.
+    pub fn throughput(&self, kind: SyncKind) -> Option<SyncThroughput> {
+        match kind {
+            SyncKind::Forward => self.forward.throughput(),
+            SyncKind::Backfill => self.backfill.throughput(),
+            SyncKind::CustodyBackfill => self.custody_backfill.throughput(),
+        }
+    }
+
+    pub fn info(&self) -> SyncProgressInfo {
+        SyncProgressInfo {
+            forward: self.throughput(SyncKind::Forward),
+            backfill: self.throughput(SyncKind::Backfill),
+            custody_backfill: self.throughput(SyncKind::CustodyBackfill),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn throughput_and_estimate() {
+        let mut progress = SyncProgress::default();
+        let start = Instant::now();
+
+        progress.observe(SyncKind::Forward, Slot::new(0), Slot::new(100), start);
+        assert_eq!(
+            progress.throughput(SyncKind::Forward),
+            Some(SyncThroughput {
+                distance: 100,
+                ..SyncThroughput::default()
+            })
+        );
+
+        progress.record_import(SyncKind::Forward, 16, 32, 0);
+        progress.observe(
+            SyncKind::Forward,
+            Slot::new(20),
+            Slot::new(100),
+            start + Duration::from_secs(2),
+        );
+        let throughput = progress.throughput(SyncKind::Forward).unwrap();
+        assert_eq!(throughput.slots_per_second, Some(10.0));
+        assert_eq!(throughput.blocks_per_second, Some(8.0));
+        assert_eq!(throughput.blobs_per_second, Some(16.0));
+        assert_eq!(throughput.data_columns_per_second, Some(0.0));
+        assert_eq!(throughput.distance, 80);
+        assert_eq!(throughput.estimated_seconds_remaining, Some(8.0));
+
+        assert_eq!(progress.throughput(SyncKind::Backfill), None);
+    }
+
+    #[test]
+    fn clear_keeps_totals() {
+        let mut progress = SyncProgress::default();
+        let start = Instant::now();
+
+        progress.record_import(SyncKind::Backfill, 64, 0, 0);
+        progress.observe(SyncKind::Backfill, Slot::new(64), Slot::new(640), start);
+        progress.clear(SyncKind::Backfill);
+        assert_eq!(progress.info(), SyncProgressInfo::default());
+
+        // Imports before the clear do not count towards the rate after it.
+        progress.observe(
+            SyncKind::Backfill,
+            Slot::new(64),
+            Slot::new(640),
+            start + Duration::from_secs(1),
+        );
+        progress.record_import(SyncKind::Backfill, 32, 0, 0);
+        progress.observe(
+            SyncKind::Backfill,
+            Slot::new(96),
+            Slot::new(640),
+            start + Duration::from_secs(3),
+        );
+        let throughput = progress.throughput(SyncKind::Backfill).unwrap();
+        assert_eq!(throughput.blocks_per_second, Some(16.0));
+        assert_eq!(throughput.slots_per_second, Some(16.0));
+        assert_eq!(throughput.estimated_seconds_remaining, Some(34.0));
+    }
+}
@@ -137,6 +137,23 @@ pub enum GossipKind {
     LightClientOptimisticUpdate,
 }
 
+impl GossipKind {
+    /// Returns `true` if messages on this topic are required to follow the head of the chain.
+    ///
+    /// Fork choice learns of attestations via the blocks which include them, so attestations are
+    /// only required to follow the head when they're slashable. Nodes which only serve RPC
+    /// requests don't subscribe to any other topics.
+    pub fn is_required_to_follow_head(&self) -> bool {
+        matches!(
+            self,
+            GossipKind::BeaconBlock
+                | GossipKind::BlobSidecar(_)
+                | GossipKind::DataColumnSidecar(_)
+                | GossipKind::AttesterSlashing
+        )
+    }
+}
+
 impl std::fmt::Display for GossipKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -478,4 +495,24 @@ mod tests {
             all_topics
         );
     }
+
+    #[test]
+    fn test_required_to_follow_head() {
+        type E = MainnetEthSpec;
+        let spec = E::default_spec();
+
+        let latest_fork = *ForkName::list_all().last().unwrap();
+        let required = core_topics_to_subscribe::<E>(latest_fork, &spec)
+            .into_iter()
+            .filter(GossipKind::is_required_to_follow_head)
+            .collect::<Vec<_>>();
+        let mut expected = fork_core_topics::<E>(&ForkName::Deneb, &spec);
+        expected.extend([BeaconBlock, AttesterSlashing]);
+        assert_eq!(required, expected);
+
+        for kind in attestation_sync_committee_topics::<E>().chain(LIGHT_CLIENT_GOSSIP_TOPICS) {
+            assert!(!kind.is_required_to_follow_head(), "{kind}");
+        }
+        assert!(DataColumnSidecar(DataColumnSubnetId::new(0)).is_required_to_follow_head());
+    }
 }
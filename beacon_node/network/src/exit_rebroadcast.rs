@@ -0,0 +1,262 @@
+//! Periodic re-broadcasting of the voluntary exits in the op pool which haven't been included on
+//! chain. See `beacon_chain::exit_broadcasts` for the tracking of broadcasts.
+use crate::metrics;
+use crate::NetworkMessage;
+use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes};
+use lighthouse_network::PubsubMessage;
+use slog::{debug, error, info, warn};
+use slot_clock::SlotClock;
+use std::sync::Arc;
+use task_executor::TaskExecutor;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::time::sleep;
+use types::EthSpec;
+
+/// Spawns a routine which re-broadcasts pending voluntary exits once per epoch, at a third of the
+/// way through the first slot of the epoch.
+///
+/// The service will not be started if re-broadcasting is disabled.
+pub fn start_exit_rebroadcast_service<T: BeaconChainTypes>(
+    executor: TaskExecutor,
+    chain: Arc<BeaconChain<T>>,
+    network_send: UnboundedSender<NetworkMessage<T::EthSpec>>,
+) {
+    if chain.config.exit_rebroadcast_epochs == 0 {
+        return;
+    }
+
+    executor.spawn(
+        async move { exit_rebroadcast_service(chain, network_send).await },
+        "exit_rebroadcast_service",
+    );
+}
+
+/// Loop indefinitely, re-broadcasting pending exits at the start of each epoch.
+async fn exit_rebroadcast_service<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    network_send: UnboundedSender<NetworkMessage<T::EthSpec>>,
+) {
+    let slot_duration = chain.slot_clock.slot_duration();
+
+    loop {
+        match chain
+            .slot_clock
+            .duration_to_next_epoch(T::EthSpec::slots_per_epoch())
+        {
+            Some(duration) => {
+                sleep(duration + slot_duration / 3).await;
+
+                if let Err(e) = rebroadcast_exits(&chain, &network_send) {
+                    warn!(
+                        chain.log,
+                        "Unable to re-broadcast voluntary exits";
+                        "error" => ?e
+                    );
+                }
+            }
+            None => {
+                error!(chain.log, "Failed to read slot clock");
+                // If we can't read the slot clock, just wait another slot.
+                sleep(slot_duration).await;
+            }
+        }
+    }
+}
+
+/// Publish the pending voluntary exits which are due for re-broadcast, returning the number of
+/// exits published.
+pub fn rebroadcast_exits<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    network_send: &UnboundedSender<NetworkMessage<T::EthSpec>>,
+) -> Result<usize, BeaconChainError> {
+    let current_slot = chain.slot()?;
+    let current_epoch = current_slot.epoch(T::EthSpec::slots_per_epoch());
+
+    // The head state can't tell whether an exit was included whilst the node is syncing.
+    if chain.best_slot() + T::EthSpec::slots_per_epoch() < current_slot {
+        debug!(
+            chain.log,
+            "Skipping exit re-broadcast whilst syncing";
+            "current_slot" => current_slot,
+        );
+        return Ok(0);
+    }
+
+    let exits = chain.voluntary_exits_with_inclusion();
+    let due = {
+        let mut exit_broadcasts = chain.exit_broadcasts.lock();
+        exit_broadcasts.retain(|validator_index| {
+            exits
+                .iter()
+                .any(|(exit, _)| exit.message.validator_index == validator_index)
+        });
+        exit_broadcasts.take_due(
+            exits
+                .iter()
+                .filter(|(_, included)| !included)
+                .map(|(exit, _)| exit.message.validator_index),
+            current_epoch,
+            chain.config.exit_rebroadcast_epochs,
+        )
+    };
+
+    let messages = exits
+        .into_iter()
+        .filter(|(exit, _)| due.contains(&exit.message.validator_index))
+        .map(|(exit, _)| PubsubMessage::VoluntaryExit(Box::new(exit)))
+        .collect::<Vec<_>>();
+    let count = messages.len();
+    if count == 0 {
+        return Ok(0);
+    }
+
+    if network_send
+        .send(NetworkMessage::Publish { messages })
+        .is_err()
+    {
+        warn!(
+            chain.log,
+            "Unable to publish voluntary exits";
+            "reason" => "network channel closed",
+        );
+        return Ok(0);
+    }
+
+    metrics::inc_counter_by(&metrics::VOLUNTARY_EXIT_REBROADCASTS, count as u64);
+    info!(
+        chain.log,
+        "Re-broadcast pending voluntary exits";
+        "count" => count,
+        "epoch" => current_epoch,
+    );
+
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use beacon_chain::observed_operations::ObservationOutcome;
+    use beacon_chain::test_utils::{BeaconChainHarness, EphemeralHarnessType};
+    use beacon_chain::ChainConfig;
+    use tokio::sync::mpsc::{self, UnboundedReceiver};
+    use types::{Epoch, MinimalEthSpec as E, Slot};
+
+    const VALIDATOR_COUNT: usize = 16;
+    const EXIT_REBROADCAST_EPOCHS: u64 = 1;
+
+    fn get_harness() -> BeaconChainHarness<EphemeralHarnessType<E>> {
+        let mut spec = E::default_spec();
+        // Allow the genesis validators to exit straight away.
+        spec.shard_committee_period = 0;
+        BeaconChainHarness::builder(E)
+            .spec(Arc::new(spec))
+            .chain_config(ChainConfig {
+                exit_rebroadcast_epochs: EXIT_REBROADCAST_EPOCHS,
+                ..ChainConfig::default()
+            })
+            .deterministic_keypairs(VALIDATOR_COUNT)
+            .fresh_ephemeral_store()
+            .build()
+    }
+
+    fn import_exit(harness: &BeaconChainHarness<EphemeralHarnessType<E>>, validator_index: u64) {
+        let exit = harness.make_voluntary_exit(validator_index, Epoch::new(0));
+        match harness
+            .chain
+            .verify_voluntary_exit_for_gossip(exit)
+            .unwrap()
+        {
+            ObservationOutcome::New(exit) => harness.chain.import_voluntary_exit(exit),
+            ObservationOutcome::AlreadyKnown => panic!("exit should be new"),
+        }
+    }
+
+    /// Returns the validator indices of the exits published to the network, in order.
+    fn published_exits(network_recv: &mut UnboundedReceiver<NetworkMessage<E>>) -> Vec<u64> {
+        let mut published = vec![];
+        while let Ok(message) = network_recv.try_recv() {
+            let NetworkMessage::Publish { messages } = message else {
+                panic!("unexpected network message");
+            };
+            for message in messages {
+                let PubsubMessage::VoluntaryExit(exit) = message else {
+                    panic!("unexpected pubsub message");
+                };
+                published.push(exit.message.validator_index);
+            }
+        }
+        published
+    }
+
+    #[tokio::test]
+    async fn rebroadcasts_pending_exits_once_per_interval() {
+        let harness = get_harness();
+        let (network_send, mut network_recv) = mpsc::unbounded_channel();
+
+        // The exit of validator 1 is included on chain, whilst the exit of validator 0 remains
+        // pending in the op pool.
+        harness.extend_to_slot(Slot::new(6)).await;
+        import_exit(&harness, 1);
+        harness.extend_to_slot(Slot::new(7)).await;
+        import_exit(&harness, 0);
+        assert_eq!(
+            harness.chain.voluntary_exits_with_inclusion().len(),
+            2,
+            "both exits should remain in the op pool"
+        );
+
+        // Neither exit is due in the epoch it was first broadcast.
+        assert_eq!(rebroadcast_exits(&harness.chain, &network_send).unwrap(), 0);
+        assert!(published_exits(&mut network_recv).is_empty());
+
+        // Only the pending exit is re-broadcast in the next epoch.
+        harness.advance_slot();
+        assert_eq!(rebroadcast_exits(&harness.chain, &network_send).unwrap(), 1);
+        assert_eq!(published_exits(&mut network_recv), vec![0]);
+        let broadcast = *harness.chain.exit_broadcasts.lock().get(0).unwrap();
+        assert_eq!(broadcast.first_seen_epoch, Epoch::new(0));
+        assert_eq!(broadcast.last_broadcast_epoch, Epoch::new(1));
+        assert_eq!(broadcast.rebroadcasts, 1);
+
+        // It isn't re-broadcast again until the interval has passed.
+        assert_eq!(rebroadcast_exits(&harness.chain, &network_send).unwrap(), 0);
+        assert!(published_exits(&mut network_recv).is_empty());
+    }
+
+    #[tokio::test]
+    async fn skips_rebroadcast_whilst_syncing() {
+        let harness = get_harness();
+        let (network_send, mut network_recv) = mpsc::unbounded_channel();
+
+        harness.extend_to_slot(Slot::new(1)).await;
+        import_exit(&harness, 0);
+
+        // The head is more than an epoch behind the wall clock.
+        harness.set_current_slot(Slot::new(2 + E::slots_per_epoch()));
+        assert_eq!(rebroadcast_exits(&harness.chain, &network_send).unwrap(), 0);
+        assert!(published_exits(&mut network_recv).is_empty());
+        assert_eq!(
+            harness
+                .chain
+                .exit_broadcasts
+                .lock()
+                .get(0)
+                .unwrap()
+                .rebroadcasts,
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn nothing_is_rebroadcast_if_network_is_unavailable() {
+        let harness = get_harness();
+        let (network_send, network_recv) = mpsc::unbounded_channel();
+        drop(network_recv);
+
+        harness.extend_to_slot(Slot::new(7)).await;
+        import_exit(&harness, 0);
+        harness.advance_slot();
+        assert_eq!(rebroadcast_exits(&harness.chain, &network_send).unwrap(), 0);
+    }
+}
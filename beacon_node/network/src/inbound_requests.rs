@@ -0,0 +1,112 @@
+//! Tracks the inbound RPC requests which are being served, so that they may be allowed to finish
+//! before the node shuts down.
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::sync::Notify;
+use tokio::time::{timeout, Duration};
+
+/// Counts the inbound RPC requests which have been accepted but not yet fully responded to.
+#[derive(Debug)]
+pub struct InboundRequestTracker {
+    accepting: AtomicBool,
+    in_flight: AtomicUsize,
+    idle: Notify,
+}
+
+impl Default for InboundRequestTracker {
+    fn default() -> Self {
+        Self {
+            accepting: AtomicBool::new(true),
+            in_flight: AtomicUsize::new(0),
+            idle: Notify::new(),
+        }
+    }
+}
+
+impl InboundRequestTracker {
+    /// Start tracking a new request, returning `None` if new requests are no longer accepted.
+    ///
+    /// The request is tracked until the returned guard is dropped.
+    pub fn try_start(self: &Arc<Self>) -> Option<InboundRequestGuard> {
+        if !self.accepting.load(Ordering::SeqCst) {
+            return None;
+        }
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        Some(InboundRequestGuard {
+            tracker: self.clone(),
+        })
+    }
+
+    /// Reject all requests which arrive after this call.
+    pub fn stop_accepting(&self) {
+        self.accepting.store(false, Ordering::SeqCst);
+    }
+
+    /// Returns the number of requests which are being served.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Wait for all requests to be served, for at most `max_wait`.
+    ///
+    /// Returns the number of requests which were still being served when `max_wait` elapsed.
+    pub async fn drain(&self, max_wait: Duration) -> usize {
+        let wait_for_idle = async {
+            loop {
+                // Register for notification before checking the count, so that a request
+                // finishing in between is not missed.
+                let idle = self.idle.notified();
+                if self.in_flight() == 0 {
+                    return;
+                }
+                idle.await;
+            }
+        };
+        let _ = timeout(max_wait, wait_for_idle).await;
+        self.in_flight()
+    }
+}
+
+/// Marks a request as being served until dropped.
+#[derive(Debug)]
+pub struct InboundRequestGuard {
+    tracker: Arc<InboundRequestTracker>,
+}
+
+impl Drop for InboundRequestGuard {
+    fn drop(&mut self) {
+        if self.tracker.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.tracker.idle.notify_waiters();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn drain_waits_for_in_flight_requests() {
+        let tracker = Arc::new(InboundRequestTracker::default());
+        let guard = tracker.try_start().unwrap();
+
+        tracker.stop_accepting();
+        assert!(tracker.try_start().is_none());
+        assert_eq!(tracker.in_flight(), 1);
+
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            drop(guard);
+        });
+        assert_eq!(tracker.drain(Duration::from_secs(5)).await, 0);
+    }
+
+    #[tokio::test]
+    async fn drain_is_bounded_by_timeout() {
+        let tracker = Arc::new(InboundRequestTracker::default());
+        let _guard = tracker.try_start().unwrap();
+
+        tracker.stop_accepting();
+        assert_eq!(tracker.drain(Duration::from_millis(10)).await, 1);
+    }
+}
@@ -1,16 +1,24 @@
 /// This crate provides the network server for Lighthouse.
+pub mod exit_rebroadcast;
 pub mod service;
 
+mod inbound_requests;
 mod metrics;
 mod nat;
 mod network_beacon_processor;
 mod persisted_dht;
+mod persisted_lookups;
 mod router;
 mod status;
 mod subnet_service;
 mod sync;
 
+pub use inbound_requests::InboundRequestTracker;
 pub use lighthouse_network::NetworkConfig;
+pub use network_beacon_processor::{
+    ChainSegmentProcessId, InvalidBlockStorage, NetworkBeaconProcessor,
+};
 pub use service::{
     NetworkMessage, NetworkReceivers, NetworkSenders, NetworkService, ValidatorSubscriptionMessage,
 };
+pub use sync::{BatchProcessResult, SyncMessage};
@@ -17,6 +17,14 @@ use types::EthSpec;
 pub const SUCCESS: &str = "SUCCESS";
 pub const FAILURE: &str = "FAILURE";
 
+pub static NAT_PORT_MAPPING_ATTEMPTS: LazyLock<Result<IntCounterVec>> = LazyLock::new(|| {
+    try_create_int_counter_vec(
+        "nat_port_mapping_attempts_total",
+        "Count of attempts to create or renew the discovery port mapping, by result",
+        &["result"],
+    )
+});
+
 pub static BEACON_BLOCK_MESH_PEERS_PER_CLIENT: LazyLock<Result<IntGaugeVec>> =
     LazyLock::new(|| {
         try_create_int_gauge_vec(
@@ -59,6 +67,16 @@ pub static SYNC_COMMITTEE_SUBSCRIPTION_REQUESTS: LazyLock<Result<IntCounter>> =
         )
     });
 
+/*
+ * Voluntary exit re-broadcasts
+ */
+pub static VOLUNTARY_EXIT_REBROADCASTS: LazyLock<Result<IntCounter>> = LazyLock::new(|| {
+    try_create_int_counter(
+        "beacon_voluntary_exit_rebroadcasts_total",
+        "Count of pending voluntary exits re-broadcast to the network.",
+    )
+});
+
 /*
  * Gossip processor
  */
@@ -325,6 +343,13 @@ pub static NETWORK_RECEIVE_EVENTS: LazyLock<Result<IntCounterVec>> = LazyLock::n
         &["type"],
     )
 });
+pub static GOSSIP_PRE_CHECK_DROPPED: LazyLock<Result<IntCounterVec>> = LazyLock::new(|| {
+    try_create_int_counter_vec(
+        "gossip_pre_check_dropped_total",
+        "Count of gossip messages dropped by the network service before being queued for processing",
+        &["type", "reason"],
+    )
+});
 pub static NETWORK_RECEIVE_TIMES: LazyLock<Result<HistogramVec>> = LazyLock::new(|| {
     try_create_histogram_vec(
         "network_receive_times",
@@ -448,6 +473,26 @@ pub static SYNC_UNKNOWN_NETWORK_REQUESTS: LazyLock<Result<IntCounterVec>> = Lazy
         &["type"],
     )
 });
+pub static SYNC_CUSTODY_BACKFILL_SLOT: LazyLock<Result<IntGauge>> = LazyLock::new(|| {
+    try_create_int_gauge(
+        "sync_custody_backfill_slot",
+        "The slot from which the columns of all custodied subnets are stored",
+    )
+});
+pub static SYNC_CUSTODY_BACKFILL_COLUMNS_IMPORTED: LazyLock<Result<IntCounter>> =
+    LazyLock::new(|| {
+        try_create_int_counter(
+            "sync_custody_backfill_data_columns_imported_total",
+            "Total count of data columns imported by custody backfill",
+        )
+    });
+pub static SYNC_CUSTODY_BACKFILL_SKIPPED_BLOCKS: LazyLock<Result<IntCounter>> =
+    LazyLock::new(|| {
+        try_create_int_counter(
+            "sync_custody_backfill_skipped_blocks_total",
+            "Total count of blocks whose columns custody backfill failed to fetch",
+        )
+    });
 
 /*
  * Block Delay Metrics
@@ -590,6 +635,42 @@ pub static SAMPLING_REQUEST_RESULT: LazyLock<Result<IntCounterVec>> = LazyLock::
     )
 });
 
+/*
+ * Data availability sampling
+ */
+pub static DAS_SAMPLES_REQUESTED: LazyLock<Result<IntCounter>> = LazyLock::new(|| {
+    try_create_int_counter(
+        "das_samples_requested_total",
+        "Total count of data column samples requested from peers",
+    )
+});
+pub static DAS_SAMPLE_SUCCESS_SECONDS: LazyLock<Result<Histogram>> = LazyLock::new(|| {
+    try_create_histogram_with_buckets(
+        "das_sample_success_seconds",
+        "Time from requesting a data column sample to its successful verification",
+        Ok(vec![0.05, 0.1, 0.2, 0.5, 1.0, 2.0, 4.0, 8.0, 12.0]),
+    )
+});
+pub static DAS_SAMPLING_PEER_FAILURES: LazyLock<Result<IntCounterVec>> = LazyLock::new(|| {
+    try_create_int_counter_vec(
+        "das_sampling_peer_failures_total",
+        "Total count of failed data column samples by peer client and reason",
+        &["client", "reason"],
+    )
+});
+pub static DAS_RECONSTRUCTION_ATTEMPTS: LazyLock<Result<IntCounter>> = LazyLock::new(|| {
+    try_create_int_counter(
+        "das_reconstruction_attempts_total",
+        "Total count of data column reconstruction attempts",
+    )
+});
+pub static DAS_RECONSTRUCTION_SUCCESSES: LazyLock<Result<IntCounter>> = LazyLock::new(|| {
+    try_create_int_counter(
+        "das_reconstruction_successes_total",
+        "Total count of successful data column reconstructions",
+    )
+});
+
 pub fn register_finality_update_error(error: &LightClientFinalityUpdateError) {
     inc_counter_vec(&GOSSIP_FINALITY_UPDATE_ERRORS_PER_TYPE, &[error.as_ref()]);
 }
@@ -2,13 +2,22 @@
 //!
 //! Currently supported strategies:
 //! - UPnP
+//! - NAT-PMP, if the gateway doesn't support UPnP
 
-use anyhow::{bail, Context, Error};
+use crate::metrics;
+use anyhow::{anyhow, bail, Context, Error};
 use igd_next::{aio::tokio as igd, PortMappingProtocol};
-use slog::debug;
+use lighthouse_network::{
+    types::{PortMappingMethod, PortMappingState},
+    NetworkGlobals,
+};
+use slog::{debug, info};
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
-use std::time::Duration;
-use tokio::time::sleep;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::net::UdpSocket;
+use tokio::time::{sleep, timeout};
+use types::EthSpec;
 
 /// The duration in seconds of a port mapping on the gateway.
 const MAPPING_DURATION: u32 = 3600;
@@ -16,12 +25,122 @@ const MAPPING_DURATION: u32 = 3600;
 /// Renew the Mapping every half of `MAPPING_DURATION` to avoid the port being unmapped.
 const MAPPING_TIMEOUT: u64 = MAPPING_DURATION as u64 / 2;
 
-/// Attempts to map Discovery external port mappings with UPnP.
-pub async fn construct_upnp_mappings(
+/// The delay before retrying a failed mapping. The delay doubles with each consecutive failure,
+/// up to `MAPPING_TIMEOUT`.
+const INITIAL_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// The port on which the gateway listens for NAT-PMP requests.
+const NAT_PMP_PORT: u16 = 5351;
+
+/// The delay before resending an unanswered NAT-PMP request. The delay doubles with each attempt.
+const NAT_PMP_INITIAL_TIMEOUT: Duration = Duration::from_millis(250);
+
+/// The number of times a NAT-PMP request is sent before the gateway is assumed not to support it.
+const NAT_PMP_ATTEMPTS: u32 = 4;
+
+/// The NAT-PMP opcode requesting the gateway's external address.
+const NAT_PMP_OP_EXTERNAL_ADDRESS: u8 = 0;
+
+/// The NAT-PMP opcode requesting a UDP port mapping.
+const NAT_PMP_OP_MAP_UDP: u8 = 1;
+
+/// A port mapping created on the gateway.
+#[derive(Debug, PartialEq)]
+struct PortMapping {
+    external_address: SocketAddr,
+    /// The lifetime of the mapping in seconds, as granted by the gateway.
+    lifetime: u32,
+    method: PortMappingMethod,
+}
+
+/// Maintains a mapping of the Discovery port for the lifetime of the node, using UPnP or NAT-PMP
+/// if the gateway doesn't support UPnP.
+///
+/// The mapping is renewed before its lease on the gateway expires, and failed attempts are retried
+/// with exponential backoff. This allows the mapping to recover if the gateway restarts or drops
+/// it. The state of the mapping is recorded in `network_globals.port_mapping`.
+pub async fn maintain_port_mappings<E: EthSpec>(
     addr: Ipv4Addr,
     port: u16,
+    network_globals: Arc<NetworkGlobals<E>>,
     log: slog::Logger,
-) -> Result<(), Error> {
+) {
+    *network_globals.port_mapping.write() = Some(PortMappingState::default());
+
+    loop {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        let delay = match construct_mapping(addr, port).await {
+            Ok(PortMapping {
+                external_address,
+                lifetime,
+                method,
+            }) => {
+                let mut port_mapping = network_globals.port_mapping.write();
+                let state = port_mapping.get_or_insert_with(PortMappingState::default);
+                if state.external_address == Some(external_address) {
+                    debug!(log, "Discovery port mapping renewed"; "external_address" => %external_address, "method" => ?method);
+                } else {
+                    info!(log, "Discovery port mapped"; "external_address" => %external_address, "method" => ?method);
+                }
+                state.on_renewed(external_address, method, now, lifetime as u64);
+                metrics::inc_counter_vec(&metrics::NAT_PORT_MAPPING_ATTEMPTS, &[metrics::SUCCESS]);
+                Duration::from_secs(lifetime as u64 / 2)
+            }
+            Err(e) => {
+                let consecutive_failures = {
+                    let mut port_mapping = network_globals.port_mapping.write();
+                    let state = port_mapping.get_or_insert_with(PortMappingState::default);
+                    state.on_failure(format!("{:#}", e), now);
+                    state.consecutive_failures
+                };
+                metrics::inc_counter_vec(&metrics::NAT_PORT_MAPPING_ATTEMPTS, &[metrics::FAILURE]);
+
+                let delay = retry_delay(consecutive_failures);
+                // Only log the first of a run of failures at info, the gateway is unlikely to
+                // change its mind on each retry.
+                if consecutive_failures == 1 {
+                    info!(log, "Could not map Discovery port"; "error" => %e, "retry_in_secs" => delay.as_secs());
+                } else {
+                    debug!(log, "Could not map Discovery port"; "error" => %e,
+                        "consecutive_failures" => consecutive_failures, "retry_in_secs" => delay.as_secs());
+                }
+                delay
+            }
+        };
+
+        sleep(delay).await;
+    }
+}
+
+/// Attempts to map the Discovery port with UPnP, falling back to NAT-PMP.
+async fn construct_mapping(addr: Ipv4Addr, port: u16) -> Result<PortMapping, Error> {
+    let upnp_error = match construct_upnp_mapping(addr, port).await {
+        Ok(external_ip) => {
+            return Ok(PortMapping {
+                external_address: SocketAddr::new(external_ip, port),
+                lifetime: MAPPING_DURATION,
+                method: PortMappingMethod::Upnp,
+            })
+        }
+        Err(e) => e,
+    };
+
+    let nat_pmp_result = match default_gateway() {
+        Ok(gateway) => {
+            construct_nat_pmp_mapping(SocketAddr::new(IpAddr::V4(gateway), NAT_PMP_PORT), port)
+                .await
+        }
+        Err(e) => Err(e),
+    };
+    nat_pmp_result.map_err(|e| anyhow!("{:#}, NAT-PMP: {:#}", upnp_error, e))
+}
+
+/// Attempts to map the Discovery port with UPnP, returning the gateway's external IP address.
+async fn construct_upnp_mapping(addr: Ipv4Addr, port: u16) -> Result<IpAddr, Error> {
     let gateway = igd::search_gateway(Default::default())
         .await
         .context("Gateway does not support UPnP")?;
@@ -43,18 +162,269 @@ pub async fn construct_upnp_mappings(
         );
     }
 
-    loop {
-        gateway
-            .add_port(
-                PortMappingProtocol::UDP,
-                port,
-                SocketAddr::new(IpAddr::V4(addr), port),
-                MAPPING_DURATION,
-                "Lighthouse Discovery port",
-            )
+    gateway
+        .add_port(
+            PortMappingProtocol::UDP,
+            port,
+            SocketAddr::new(IpAddr::V4(addr), port),
+            MAPPING_DURATION,
+            "Lighthouse Discovery port",
+        )
+        .await
+        .with_context(|| format!("Could not UPnP map port: {} on the gateway", port))?;
+
+    Ok(external_address)
+}
+
+/// Attempts to map the Discovery port with NAT-PMP on `gateway`.
+///
+/// The gateway maps the port to the address from which the request is sent, so unlike UPnP the
+/// local address isn't required. The gateway may map a different external port.
+async fn construct_nat_pmp_mapping(gateway: SocketAddr, port: u16) -> Result<PortMapping, Error> {
+    let socket = UdpSocket::bind(SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0))
+        .await
+        .context("Could not bind NAT-PMP socket")?;
+    socket
+        .connect(gateway)
+        .await
+        .with_context(|| format!("Could not connect to gateway {}", gateway))?;
+
+    let response = nat_pmp_request(&socket, &[0, NAT_PMP_OP_EXTERNAL_ADDRESS]).await?;
+    let external_ip = decode_nat_pmp_external_address(&response)?;
+    if external_ip.is_private() {
+        bail!(
+            "Gateway's external address is a private address: {}",
+            external_ip
+        );
+    }
+
+    let response = nat_pmp_request(
+        &socket,
+        &encode_nat_pmp_mapping_request(port, MAPPING_DURATION),
+    )
+    .await?;
+    let (external_port, lifetime) = decode_nat_pmp_mapping(&response, port)?;
+
+    Ok(PortMapping {
+        external_address: SocketAddr::new(IpAddr::V4(external_ip), external_port),
+        lifetime,
+        method: PortMappingMethod::NatPmp,
+    })
+}
+
+/// Sends `request` to the gateway, resending it with a doubling timeout until it is answered.
+async fn nat_pmp_request(socket: &UdpSocket, request: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut response_timeout = NAT_PMP_INITIAL_TIMEOUT;
+    let mut buf = [0; 16];
+    for _ in 0..NAT_PMP_ATTEMPTS {
+        socket
+            .send(request)
             .await
-            .with_context(|| format!("Could not UPnP map port: {} on the gateway", port))?;
-        debug!(log, "Discovery UPnP port mapped"; "port" => %port);
-        sleep(Duration::from_secs(MAPPING_TIMEOUT)).await;
+            .context("Could not send NAT-PMP request")?;
+        match timeout(response_timeout, socket.recv(&mut buf)).await {
+            Ok(Ok(len)) => return Ok(buf[..len].to_vec()),
+            Ok(Err(e)) => return Err(e).context("Could not receive NAT-PMP response"),
+            Err(_) => response_timeout *= 2,
+        }
+    }
+    bail!("Gateway does not support NAT-PMP")
+}
+
+/// Checks the header of a NAT-PMP response to a request with `op`, returning its payload.
+fn check_nat_pmp_response(response: &[u8], op: u8, len: usize) -> Result<&[u8], Error> {
+    if response.len() < len {
+        bail!("NAT-PMP response is too short: {} bytes", response.len());
+    }
+    if response[0] != 0 || response[1] != (op | 128) {
+        bail!(
+            "Unexpected NAT-PMP response version {} opcode {}",
+            response[0],
+            response[1]
+        );
+    }
+    let result_code = u16::from_be_bytes([response[2], response[3]]);
+    if result_code != 0 {
+        bail!(
+            "Gateway refused NAT-PMP request: result code {}",
+            result_code
+        );
+    }
+    // Skip the seconds since the gateway's mapping table was initialized.
+    Ok(&response[8..len])
+}
+
+fn decode_nat_pmp_external_address(response: &[u8]) -> Result<Ipv4Addr, Error> {
+    let payload = check_nat_pmp_response(response, NAT_PMP_OP_EXTERNAL_ADDRESS, 12)?;
+    Ok(Ipv4Addr::new(
+        payload[0], payload[1], payload[2], payload[3],
+    ))
+}
+
+fn encode_nat_pmp_mapping_request(port: u16, lifetime: u32) -> Vec<u8> {
+    let mut request = vec![0, NAT_PMP_OP_MAP_UDP, 0, 0];
+    request.extend_from_slice(&port.to_be_bytes());
+    // Suggest the same external port.
+    request.extend_from_slice(&port.to_be_bytes());
+    request.extend_from_slice(&lifetime.to_be_bytes());
+    request
+}
+
+/// Returns the external port and lifetime of the mapping of `port`.
+fn decode_nat_pmp_mapping(response: &[u8], port: u16) -> Result<(u16, u32), Error> {
+    let payload = check_nat_pmp_response(response, NAT_PMP_OP_MAP_UDP, 16)?;
+    let internal_port = u16::from_be_bytes([payload[0], payload[1]]);
+    let external_port = u16::from_be_bytes([payload[2], payload[3]]);
+    let lifetime = u32::from_be_bytes([payload[4], payload[5], payload[6], payload[7]]);
+    if internal_port != port {
+        bail!("Gateway mapped port {} instead of {}", internal_port, port);
+    }
+    if lifetime == 0 {
+        bail!("Gateway granted a mapping without a lifetime");
+    }
+    Ok((external_port, lifetime))
+}
+
+/// Returns the gateway of the default IPv4 route.
+fn default_gateway() -> Result<Ipv4Addr, Error> {
+    let routes =
+        std::fs::read_to_string("/proc/net/route").context("Could not read the routing table")?;
+    parse_default_gateway(&routes).context("Could not find the default gateway")
+}
+
+/// Parses the gateway of the default route from the contents of `/proc/net/route`, in which
+/// addresses are hex encoded in host byte order.
+fn parse_default_gateway(routes: &str) -> Option<Ipv4Addr> {
+    routes.lines().skip(1).find_map(|line| {
+        let mut fields = line.split_whitespace().skip(1);
+        let destination = fields.next()?;
+        let gateway = u32::from_str_radix(fields.next()?, 16).ok()?;
+        (destination == "00000000" && gateway != 0).then(|| Ipv4Addr::from(gateway.to_ne_bytes()))
+    })
+}
+
+/// Returns the delay before retrying a mapping after `consecutive_failures` failed attempts.
+fn retry_delay(consecutive_failures: u32) -> Duration {
+    let exponent = consecutive_failures.saturating_sub(1).min(16);
+    INITIAL_RETRY_DELAY
+        .saturating_mul(1 << exponent)
+        .min(Duration::from_secs(MAPPING_TIMEOUT))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_delay_backs_off_to_renewal_interval() {
+        assert_eq!(retry_delay(1), INITIAL_RETRY_DELAY);
+        assert_eq!(retry_delay(2), INITIAL_RETRY_DELAY * 2);
+        assert_eq!(retry_delay(3), INITIAL_RETRY_DELAY * 4);
+        assert_eq!(retry_delay(100), Duration::from_secs(MAPPING_TIMEOUT));
+    }
+
+    #[test]
+    fn mapping_outlives_failures_until_expiry() {
+        let external_address: SocketAddr = "1.2.3.4:9000".parse().unwrap();
+        let mut state = PortMappingState::default();
+        state.on_renewed(
+            external_address,
+            PortMappingMethod::Upnp,
+            100,
+            MAPPING_DURATION as u64,
+        );
+
+        state.on_failure("gateway unreachable".to_string(), 200);
+        assert_eq!(state.external_address, Some(external_address));
+        assert_eq!(state.consecutive_failures, 1);
+
+        state.on_failure(
+            "gateway unreachable".to_string(),
+            100 + MAPPING_DURATION as u64,
+        );
+        assert_eq!(state.external_address, None);
+        assert_eq!(state.method, None);
+        assert_eq!(state.consecutive_failures, 2);
+        assert_eq!(state.last_renewal, Some(100));
+
+        state.on_renewed(
+            external_address,
+            PortMappingMethod::NatPmp,
+            5000,
+            MAPPING_DURATION as u64,
+        );
+        assert_eq!(state.method, Some(PortMappingMethod::NatPmp));
+        assert_eq!(state.consecutive_failures, 0);
+        assert_eq!(state.last_error, None);
+    }
+
+    #[cfg(target_endian = "little")]
+    #[test]
+    fn parses_default_gateway() {
+        let routes = "\
+Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\t\tMTU\tWindow\tIRTT
+eth0\t0001A8C0\t00000000\t0001\t0\t0\t0\t00FFFFFF\t0\t0\t0
+eth0\t00000000\t0101A8C0\t0003\t0\t0\t0\t00000000\t0\t0\t0
+";
+        assert_eq!(
+            parse_default_gateway(routes),
+            Some(Ipv4Addr::new(192, 168, 1, 1))
+        );
+        assert_eq!(parse_default_gateway(routes.lines().next().unwrap()), None);
+    }
+
+    /// Answers NAT-PMP requests as a gateway with an external address of 203.0.113.7 which maps
+    /// requested ports to `external_port`, dropping the first request to test resending.
+    async fn mock_nat_pmp_gateway(result_code: u16, external_port: u16) -> SocketAddr {
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let gateway = socket.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut buf = [0; 12];
+            let mut dropped_first = false;
+            loop {
+                let (len, from) = socket.recv_from(&mut buf).await.unwrap();
+                if !dropped_first {
+                    dropped_first = true;
+                    continue;
+                }
+                let mut response = vec![0, buf[1] | 128];
+                response.extend_from_slice(&result_code.to_be_bytes());
+                response.extend_from_slice(&42u32.to_be_bytes());
+                match (buf[1], len) {
+                    (NAT_PMP_OP_EXTERNAL_ADDRESS, 2) => {
+                        response.extend_from_slice(&[203, 0, 113, 7])
+                    }
+                    (NAT_PMP_OP_MAP_UDP, 12) => {
+                        response.extend_from_slice(&buf[4..6]);
+                        response.extend_from_slice(&external_port.to_be_bytes());
+                        response.extend_from_slice(&buf[8..12]);
+                    }
+                    _ => panic!("unexpected NAT-PMP request {:?}", &buf[..len]),
+                }
+                socket.send_to(&response, from).await.unwrap();
+            }
+        });
+        gateway
+    }
+
+    #[tokio::test]
+    async fn nat_pmp_maps_discovery_port() {
+        let gateway = mock_nat_pmp_gateway(0, 9100).await;
+        let mapping = construct_nat_pmp_mapping(gateway, 9000).await.unwrap();
+        assert_eq!(
+            mapping,
+            PortMapping {
+                external_address: "203.0.113.7:9100".parse().unwrap(),
+                lifetime: MAPPING_DURATION,
+                method: PortMappingMethod::NatPmp,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn nat_pmp_refusal_is_an_error() {
+        // Result code 2: not authorized.
+        let gateway = mock_nat_pmp_gateway(2, 9000).await;
+        let error = construct_nat_pmp_mapping(gateway, 9000).await.unwrap_err();
+        assert!(error.to_string().contains("result code 2"), "{:#}", error);
     }
 }
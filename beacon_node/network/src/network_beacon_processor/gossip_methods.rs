@@ -14,7 +14,9 @@ use beacon_chain::{
     light_client_finality_update_verification::Error as LightClientFinalityUpdateError,
     light_client_optimistic_update_verification::Error as LightClientOptimisticUpdateError,
     observed_operations::ObservationOutcome,
-    sync_committee_verification::{self, Error as SyncCommitteeError},
+    sync_committee_verification::{
+        self, Error as SyncCommitteeError, VerifiedSyncCommitteeMessage,
+    },
     validator_monitor::{get_block_delay_ms, get_slot_delay_ms},
     AvailabilityProcessingStatus, BeaconChainError, BeaconChainTypes, BlockError, ForkChoiceError,
     GossipVerifiedBlock, NotifyExecutionLayer,
@@ -45,7 +47,8 @@ use beacon_processor::{
         QueuedAggregate, QueuedGossipBlock, QueuedLightClientUpdate, QueuedUnaggregate,
         ReprocessQueueMessage,
     },
-    DuplicateCache, GossipAggregatePackage, GossipAttestationPackage,
+    DuplicateCache, GossipAggregatePackage, GossipAttestationPackage, GossipBlobSidecarPackage,
+    GossipSyncMessagePackage,
 };
 
 /// Set to `true` to introduce stricter penalties for peers who send some types of late consensus
@@ -747,6 +750,56 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         blob_index: u64,
         blob_sidecar: Arc<BlobSidecar<T::EthSpec>>,
         seen_duration: Duration,
+    ) {
+        let result = self
+            .chain
+            .verify_blob_sidecar_for_gossip(blob_sidecar.clone(), blob_index);
+
+        self.process_gossip_blob_result(result, message_id, peer_id, blob_sidecar, seen_duration)
+            .await
+    }
+
+    /// Process a batch of gossip blobs, verifying the KZG proofs of blobs for the same block
+    /// together. Each blob is then validated and imported exactly as by `process_gossip_blob`.
+    pub async fn process_gossip_blob_batch(
+        self: &Arc<Self>,
+        packages: Vec<GossipBlobSidecarPackage<T::EthSpec>>,
+    ) {
+        let kzg_results = self.chain.batch_verify_kzg_for_gossip_blobs(
+            packages
+                .iter()
+                .map(|package| package.blob_sidecar.clone())
+                .collect(),
+        );
+
+        for (package, kzg_result) in packages.into_iter().zip(kzg_results) {
+            let result = match kzg_result {
+                Ok(kzg_verified_blob) => self.chain.verify_kzg_verified_blob_sidecar_for_gossip(
+                    kzg_verified_blob,
+                    package.blob_index,
+                ),
+                Err(blob_sidecar) => self
+                    .chain
+                    .verify_blob_sidecar_for_gossip(blob_sidecar, package.blob_index),
+            };
+            self.process_gossip_blob_result(
+                result,
+                package.message_id,
+                package.peer_id,
+                package.blob_sidecar,
+                package.seen_timestamp,
+            )
+            .await;
+        }
+    }
+
+    async fn process_gossip_blob_result(
+        self: &Arc<Self>,
+        result: Result<GossipVerifiedBlob<T>, GossipBlobError>,
+        message_id: MessageId,
+        peer_id: PeerId,
+        blob_sidecar: Arc<BlobSidecar<T::EthSpec>>,
+        seen_duration: Duration,
     ) {
         let slot = blob_sidecar.slot();
         let root = blob_sidecar.block_root();
@@ -755,10 +808,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         let delay = get_slot_delay_ms(seen_duration, slot, &self.chain.slot_clock);
         // Log metrics to track delay from other nodes on the network.
         metrics::set_gauge(&metrics::BEACON_BLOB_DELAY_GOSSIP, delay.as_millis() as i64);
-        match self
-            .chain
-            .verify_blob_sidecar_for_gossip(blob_sidecar.clone(), blob_index)
-        {
+        match result {
             Ok(gossip_verified_blob) => {
                 metrics::inc_counter(&metrics::BEACON_PROCESSOR_GOSSIP_BLOB_VERIFIED_TOTAL);
 
@@ -1304,7 +1354,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                 );
                 return None;
             }
-            Err(e @ BlockError::InternalError(_)) => {
+            Err(e @ BlockError::InternalError(_)) | Err(e @ BlockError::ImportTimeout(_)) => {
                 error!(self.log, "Internal block gossip validation error";
                     "error" => %e
                 );
@@ -1857,10 +1907,76 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         seen_timestamp: Duration,
     ) {
         let message_slot = sync_signature.slot;
-        let sync_signature = match self
+        let result = self
             .chain
-            .verify_sync_committee_message_for_gossip(sync_signature, subnet_id)
+            .verify_sync_committee_message_for_gossip(sync_signature, subnet_id);
+
+        self.process_gossip_sync_committee_signature_result(
+            result,
+            message_id,
+            peer_id,
+            message_slot,
+            seen_timestamp,
+        );
+    }
+
+    pub fn process_gossip_sync_committee_signature_batch(
+        self: Arc<Self>,
+        packages: Vec<GossipSyncMessagePackage>,
+    ) {
+        let (metadata, sync_messages): (Vec<_>, Vec<_>) = packages
+            .into_iter()
+            .map(|package| {
+                (
+                    (
+                        package.message_id,
+                        package.peer_id,
+                        package.sync_message.slot,
+                        package.seen_timestamp,
+                    ),
+                    (*package.sync_message, package.subnet_id),
+                )
+            })
+            .unzip();
+
+        let results = self
+            .chain
+            .batch_verify_sync_committee_messages_for_gossip(sync_messages.into_iter());
+
+        // Sanity check.
+        if results.len() != metadata.len() {
+            // The log is `crit` since in this scenario we might be penalizing/rewarding the wrong
+            // peer.
+            crit!(
+                self.log,
+                "Batch sync message result mismatch";
+                "results" => results.len(),
+                "packages" => metadata.len(),
+            )
+        }
+
+        for (result, (message_id, peer_id, message_slot, seen_timestamp)) in
+            results.into_iter().zip(metadata)
         {
+            self.process_gossip_sync_committee_signature_result(
+                result,
+                message_id,
+                peer_id,
+                message_slot,
+                seen_timestamp,
+            );
+        }
+    }
+
+    fn process_gossip_sync_committee_signature_result(
+        self: &Arc<Self>,
+        result: Result<VerifiedSyncCommitteeMessage, SyncCommitteeError>,
+        message_id: MessageId,
+        peer_id: PeerId,
+        message_slot: Slot,
+        seen_timestamp: Duration,
+    ) {
+        let sync_signature = match result {
             Ok(sync_signature) => sync_signature,
             Err(e) => {
                 self.handle_sync_committee_message_failure(
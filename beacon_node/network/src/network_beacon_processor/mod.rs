@@ -1,6 +1,7 @@
+use crate::inbound_requests::{InboundRequestGuard, InboundRequestTracker};
 use crate::sync::manager::BlockProcessType;
 use crate::sync::SamplingId;
-use crate::{service::NetworkMessage, sync::manager::SyncMessage};
+use crate::{metrics, service::NetworkMessage, sync::manager::SyncMessage};
 use beacon_chain::blob_verification::{GossipBlobError, GossipVerifiedBlob};
 use beacon_chain::block_verification_types::RpcBlock;
 use beacon_chain::data_column_verification::{observe_gossip_data_column, GossipDataColumnError};
@@ -13,8 +14,9 @@ use beacon_chain::{
     BeaconChainTypes, BlockError, NotifyExecutionLayer,
 };
 use beacon_processor::{
-    work_reprocessing_queue::ReprocessQueueMessage, BeaconProcessorChannels, BeaconProcessorSend,
-    DuplicateCache, GossipAggregatePackage, GossipAttestationPackage, Work,
+    work_reprocessing_queue::ReprocessQueueMessage, AsyncFn, BeaconProcessorChannels,
+    BeaconProcessorSend, BlockingOrAsync, DuplicateCache, GossipAggregatePackage,
+    GossipAttestationPackage, GossipBlobSidecarPackage, GossipSyncMessagePackage, Work,
     WorkEvent as BeaconWorkEvent,
 };
 use lighthouse_network::discovery::ConnectionId;
@@ -22,7 +24,8 @@ use lighthouse_network::rpc::methods::{
     BlobsByRangeRequest, BlobsByRootRequest, DataColumnsByRangeRequest, DataColumnsByRootRequest,
     LightClientUpdatesByRangeRequest,
 };
-use lighthouse_network::rpc::{RequestId, SubstreamId};
+use lighthouse_network::rpc::{RequestId, RpcErrorResponse, SubstreamId};
+use lighthouse_network::service::api_types::CustodyBackfillId;
 use lighthouse_network::{
     rpc::{BlocksByRangeRequest, BlocksByRootRequest, LightClientBootstrapRequest, StatusMessage},
     Client, MessageId, NetworkGlobals, PeerId, PubsubMessage,
@@ -37,6 +40,7 @@ use store::MemoryStore;
 use task_executor::TaskExecutor;
 use tokio::sync::mpsc::UnboundedSender;
 use tokio::sync::mpsc::{self, error::TrySendError};
+use tokio::sync::oneshot;
 use types::*;
 
 pub use sync_methods::ChainSegmentProcessId;
@@ -70,6 +74,7 @@ pub struct NetworkBeaconProcessor<T: BeaconChainTypes> {
     pub reprocess_tx: mpsc::Sender<ReprocessQueueMessage>,
     pub network_globals: Arc<NetworkGlobals<T::EthSpec>>,
     pub invalid_block_storage: InvalidBlockStorage,
+    pub inbound_requests: Arc<InboundRequestTracker>,
     pub executor: TaskExecutor,
     pub log: Logger,
 }
@@ -222,23 +227,43 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         blob_sidecar: Arc<BlobSidecar<T::EthSpec>>,
         seen_timestamp: Duration,
     ) -> Result<(), Error<T::EthSpec>> {
+        // Define a closure for processing individual blob sidecars.
         let processor = self.clone();
-        let process_fn = async move {
-            processor
-                .process_gossip_blob(
+        let process_individual = move |package: GossipBlobSidecarPackage<T::EthSpec>| -> AsyncFn {
+            Box::pin(async move {
+                processor
+                    .process_gossip_blob(
+                        package.message_id,
+                        package.peer_id,
+                        package.peer_client,
+                        package.blob_index,
+                        package.blob_sidecar,
+                        package.seen_timestamp,
+                    )
+                    .await
+            })
+        };
+
+        // Define a closure for processing batches of blob sidecars.
+        let processor = self.clone();
+        let process_batch = move |blobs: Vec<GossipBlobSidecarPackage<T::EthSpec>>| -> AsyncFn {
+            Box::pin(async move { processor.process_gossip_blob_batch(blobs).await })
+        };
+
+        self.try_send(BeaconWorkEvent {
+            drop_during_sync: false,
+            work: Work::GossipBlobSidecar {
+                blob: Box::new(GossipBlobSidecarPackage {
                     message_id,
                     peer_id,
                     peer_client,
                     blob_index,
                     blob_sidecar,
                     seen_timestamp,
-                )
-                .await
-        };
-
-        self.try_send(BeaconWorkEvent {
-            drop_during_sync: false,
-            work: Work::GossipBlobSidecar(Box::pin(process_fn)),
+                }),
+                process_individual: Box::new(process_individual),
+                process_batch: Box::new(process_batch),
+            },
         })
     }
 
@@ -279,22 +304,41 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         peer_id: PeerId,
         sync_signature: SyncCommitteeMessage,
         subnet_id: SyncSubnetId,
+        required_by_local_duty: bool,
         seen_timestamp: Duration,
     ) -> Result<(), Error<T::EthSpec>> {
+        // Define a closure for processing individual sync committee messages.
         let processor = self.clone();
-        let process_fn = move || {
+        let process_individual = move |package: GossipSyncMessagePackage| {
             processor.process_gossip_sync_committee_signature(
-                message_id,
-                peer_id,
-                sync_signature,
-                subnet_id,
-                seen_timestamp,
+                package.message_id,
+                package.peer_id,
+                *package.sync_message,
+                package.subnet_id,
+                package.seen_timestamp,
             )
         };
 
+        // Define a closure for processing batches of sync committee messages.
+        let processor = self.clone();
+        let process_batch = move |sync_messages| {
+            processor.process_gossip_sync_committee_signature_batch(sync_messages)
+        };
+
         self.try_send(BeaconWorkEvent {
             drop_during_sync: true,
-            work: Work::GossipSyncSignature(Box::new(process_fn)),
+            work: Work::GossipSyncSignature {
+                sync_message: Box::new(GossipSyncMessagePackage {
+                    message_id,
+                    peer_id,
+                    sync_message: Box::new(sync_signature),
+                    subnet_id,
+                    required_by_local_duty,
+                    seen_timestamp,
+                }),
+                process_individual: Box::new(process_individual),
+                process_batch: Box::new(process_batch),
+            },
         })
     }
 
@@ -554,6 +598,28 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         })
     }
 
+    /// Create a new work event to store the custody columns of an imported block, which were
+    /// fetched by custody backfill sync.
+    pub fn send_custody_backfill_columns(
+        self: &Arc<Self>,
+        id: CustodyBackfillId,
+        data_columns: DataColumnSidecarList<T::EthSpec>,
+    ) -> Result<(), Error<T::EthSpec>> {
+        let processor = self.clone();
+        let process_fn = move || {
+            let result = processor
+                .chain
+                .import_historical_data_columns(id.block_root, data_columns);
+            // Sync handles these results
+            processor.send_sync_message(SyncMessage::CustodyBackfillProcessed { id, result });
+        };
+
+        self.try_send(BeaconWorkEvent {
+            drop_during_sync: false,
+            work: Work::CustodyBackfillColumns(Box::new(process_fn)),
+        })
+    }
+
     /// Create a new work event to import `blocks` as a beacon chain segment.
     pub fn send_chain_segment(
         self: &Arc<Self>,
@@ -593,6 +659,30 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         })
     }
 
+    /// Track an inbound RPC request until the returned guard is dropped.
+    ///
+    /// Returns `None` and responds with an error if the node is shutting down and no longer
+    /// accepts requests.
+    fn start_inbound_request(
+        &self,
+        peer_id: PeerId,
+        connection_id: ConnectionId,
+        substream_id: SubstreamId,
+        request_id: RequestId,
+    ) -> Option<InboundRequestGuard> {
+        let guard = self.inbound_requests.try_start();
+        if guard.is_none() {
+            self.send_error_response(
+                peer_id,
+                RpcErrorResponse::ServerError,
+                "Shutting down".into(),
+                (connection_id, substream_id),
+                request_id,
+            );
+        }
+        guard
+    }
+
     /// Create a new work event to process `StatusMessage`s from the RPC network.
     pub fn send_status_message(
         self: &Arc<Self>,
@@ -617,8 +707,14 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         request_id: RequestId,
         request: BlocksByRangeRequest,
     ) -> Result<(), Error<T::EthSpec>> {
+        let Some(guard) =
+            self.start_inbound_request(peer_id, connection_id, substream_id, request_id)
+        else {
+            return Ok(());
+        };
         let processor = self.clone();
         let process_fn = async move {
+            let _guard = guard;
             processor
                 .handle_blocks_by_range_request(
                     peer_id,
@@ -636,6 +732,74 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         })
     }
 
+    /// Create a new work event to process `BlindedBlocksByRange`s requests from the RPC network.
+    pub fn send_blinded_blocks_by_range_request(
+        self: &Arc<Self>,
+        peer_id: PeerId,
+        connection_id: ConnectionId,
+        substream_id: SubstreamId,
+        request_id: RequestId,
+        request: BlocksByRangeRequest,
+    ) -> Result<(), Error<T::EthSpec>> {
+        let Some(guard) =
+            self.start_inbound_request(peer_id, connection_id, substream_id, request_id)
+        else {
+            return Ok(());
+        };
+        let processor = self.clone();
+        let process_fn = async move {
+            let _guard = guard;
+            processor.handle_blinded_blocks_by_range_request(
+                peer_id,
+                connection_id,
+                substream_id,
+                request_id,
+                request,
+            );
+        };
+
+        // Blinded requests share the blocks by range queue, they are just cheaper to serve.
+        self.try_send(BeaconWorkEvent {
+            drop_during_sync: false,
+            work: Work::BlocksByRangeRequest(Box::pin(process_fn)),
+        })
+    }
+
+    /// Create a new work event to process `ExecutionPayloadBodiesByRange` requests from the RPC
+    /// network.
+    pub fn send_execution_payload_bodies_by_range_request(
+        self: &Arc<Self>,
+        peer_id: PeerId,
+        connection_id: ConnectionId,
+        substream_id: SubstreamId,
+        request_id: RequestId,
+        request: BlocksByRangeRequest,
+    ) -> Result<(), Error<T::EthSpec>> {
+        let Some(guard) =
+            self.start_inbound_request(peer_id, connection_id, substream_id, request_id)
+        else {
+            return Ok(());
+        };
+        let processor = self.clone();
+        let process_fn = async move {
+            let _guard = guard;
+            processor
+                .handle_execution_payload_bodies_by_range_request(
+                    peer_id,
+                    connection_id,
+                    substream_id,
+                    request_id,
+                    request,
+                )
+                .await;
+        };
+
+        self.try_send(BeaconWorkEvent {
+            drop_during_sync: false,
+            work: Work::BlocksByRangeRequest(Box::pin(process_fn)),
+        })
+    }
+
     /// Create a new work event to process `BlocksByRootRequest`s from the RPC network.
     pub fn send_blocks_by_roots_request(
         self: &Arc<Self>,
@@ -645,8 +809,14 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         request_id: RequestId,
         request: BlocksByRootRequest,
     ) -> Result<(), Error<T::EthSpec>> {
+        let Some(guard) =
+            self.start_inbound_request(peer_id, connection_id, substream_id, request_id)
+        else {
+            return Ok(());
+        };
         let processor = self.clone();
         let process_fn = async move {
+            let _guard = guard;
             processor
                 .handle_blocks_by_root_request(
                     peer_id,
@@ -673,8 +843,14 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         request_id: RequestId,
         request: BlobsByRangeRequest,
     ) -> Result<(), Error<T::EthSpec>> {
+        let Some(guard) =
+            self.start_inbound_request(peer_id, connection_id, substream_id, request_id)
+        else {
+            return Ok(());
+        };
         let processor = self.clone();
         let process_fn = move || {
+            let _guard = guard;
             processor.handle_blobs_by_range_request(
                 peer_id,
                 connection_id,
@@ -699,8 +875,14 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         request_id: RequestId,
         request: BlobsByRootRequest,
     ) -> Result<(), Error<T::EthSpec>> {
+        let Some(guard) =
+            self.start_inbound_request(peer_id, connection_id, substream_id, request_id)
+        else {
+            return Ok(());
+        };
         let processor = self.clone();
         let process_fn = move || {
+            let _guard = guard;
             processor.handle_blobs_by_root_request(
                 peer_id,
                 connection_id,
@@ -725,8 +907,14 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         request_id: RequestId,
         request: DataColumnsByRootRequest,
     ) -> Result<(), Error<T::EthSpec>> {
+        let Some(guard) =
+            self.start_inbound_request(peer_id, connection_id, substream_id, request_id)
+        else {
+            return Ok(());
+        };
         let processor = self.clone();
         let process_fn = move || {
+            let _guard = guard;
             processor.handle_data_columns_by_root_request(
                 peer_id,
                 connection_id,
@@ -751,8 +939,14 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         request_id: RequestId,
         request: DataColumnsByRangeRequest,
     ) -> Result<(), Error<T::EthSpec>> {
+        let Some(guard) =
+            self.start_inbound_request(peer_id, connection_id, substream_id, request_id)
+        else {
+            return Ok(());
+        };
         let processor = self.clone();
         let process_fn = move || {
+            let _guard = guard;
             processor.handle_data_columns_by_range_request(
                 peer_id,
                 connection_id,
@@ -777,8 +971,14 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         request_id: RequestId,
         request: LightClientBootstrapRequest,
     ) -> Result<(), Error<T::EthSpec>> {
+        let Some(guard) =
+            self.start_inbound_request(peer_id, connection_id, substream_id, request_id)
+        else {
+            return Ok(());
+        };
         let processor = self.clone();
         let process_fn = move || {
+            let _guard = guard;
             processor.handle_light_client_bootstrap(
                 peer_id,
                 connection_id,
@@ -802,8 +1002,14 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         substream_id: SubstreamId,
         request_id: RequestId,
     ) -> Result<(), Error<T::EthSpec>> {
+        let Some(guard) =
+            self.start_inbound_request(peer_id, connection_id, substream_id, request_id)
+        else {
+            return Ok(());
+        };
         let processor = self.clone();
         let process_fn = move || {
+            let _guard = guard;
             processor.handle_light_client_optimistic_update(
                 peer_id,
                 connection_id,
@@ -826,8 +1032,14 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         substream_id: SubstreamId,
         request_id: RequestId,
     ) -> Result<(), Error<T::EthSpec>> {
+        let Some(guard) =
+            self.start_inbound_request(peer_id, connection_id, substream_id, request_id)
+        else {
+            return Ok(());
+        };
         let processor = self.clone();
         let process_fn = move || {
+            let _guard = guard;
             processor.handle_light_client_finality_update(
                 peer_id,
                 connection_id,
@@ -851,8 +1063,14 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         request_id: RequestId,
         request: LightClientUpdatesByRangeRequest,
     ) -> Result<(), Error<T::EthSpec>> {
+        let Some(guard) =
+            self.start_inbound_request(peer_id, connection_id, substream_id, request_id)
+        else {
+            return Ok(());
+        };
         let processor = self.clone();
         let process_fn = move || {
+            let _guard = guard;
             processor.handle_light_client_updates_by_range(
                 peer_id,
                 connection_id,
@@ -961,19 +1179,45 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         }
     }
 
+    /// Create a new `Work` event to reconstruct the data columns of a block on request of the HTTP
+    /// API. The outcome of `attempt_data_column_reconstruction` is sent on `result_tx`.
+    pub fn send_data_column_reconstruction(
+        self: &Arc<Self>,
+        block_root: Hash256,
+        result_tx: oneshot::Sender<Option<(AvailabilityProcessingStatus, Vec<ColumnIndex>)>>,
+    ) -> Result<(), Error<T::EthSpec>> {
+        let processor = self.clone();
+        self.try_send(BeaconWorkEvent {
+            drop_during_sync: false,
+            work: Work::ApiRequestP1(BlockingOrAsync::Async(Box::pin(async move {
+                let result = processor
+                    .attempt_data_column_reconstruction(block_root)
+                    .await;
+                // The HTTP request may have been cancelled.
+                let _ = result_tx.send(result);
+            }))),
+        })
+    }
+
     /// Attempt to reconstruct all data columns if the following conditions satisfies:
     /// - Our custody requirement is all columns
     /// - We have >= 50% of columns, but not all columns
     ///
-    /// Returns `Some(AvailabilityProcessingStatus)` if reconstruction is successfully performed,
-    /// otherwise returns `None`.
+    /// Returns the `AvailabilityProcessingStatus` and the indices of the reconstructed columns if
+    /// reconstruction is successfully performed, otherwise returns `None`.
     async fn attempt_data_column_reconstruction(
         self: &Arc<Self>,
         block_root: Hash256,
-    ) -> Option<AvailabilityProcessingStatus> {
+    ) -> Option<(AvailabilityProcessingStatus, Vec<ColumnIndex>)> {
         let result = self.chain.reconstruct_data_columns(block_root).await;
         match result {
             Ok(Some((availability_processing_status, data_columns_to_publish))) => {
+                metrics::inc_counter(&metrics::DAS_RECONSTRUCTION_ATTEMPTS);
+                metrics::inc_counter(&metrics::DAS_RECONSTRUCTION_SUCCESSES);
+                let reconstructed_columns = data_columns_to_publish
+                    .iter()
+                    .map(|data_column| data_column.index)
+                    .collect();
                 self.publish_data_columns_gradually(data_columns_to_publish, block_root);
                 match &availability_processing_status {
                     AvailabilityProcessingStatus::Imported(hash) => {
@@ -995,7 +1239,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                     }
                 }
 
-                Some(availability_processing_status)
+                Some((availability_processing_status, reconstructed_columns))
             }
             Ok(None) => {
                 // reason is tracked via the `KZG_DATA_COLUMN_RECONSTRUCTION_INCOMPLETE_TOTAL` metric
@@ -1007,6 +1251,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                 None
             }
             Err(e) => {
+                metrics::inc_counter(&metrics::DAS_RECONSTRUCTION_ATTEMPTS);
                 error!(
                     self.log,
                     "Error during data column reconstruction";
@@ -1221,6 +1466,7 @@ impl<E: EthSpec> NetworkBeaconProcessor<TestBeaconChainType<E>> {
             reprocess_tx: work_reprocessing_tx,
             network_globals,
             invalid_block_storage: InvalidBlockStorage::Disabled,
+            inbound_requests: Arc::new(InboundRequestTracker::default()),
             executor,
             log,
         };
@@ -7,6 +7,7 @@ use itertools::process_results;
 use lighthouse_network::discovery::ConnectionId;
 use lighthouse_network::rpc::methods::{
     BlobsByRangeRequest, BlobsByRootRequest, DataColumnsByRangeRequest, DataColumnsByRootRequest,
+    ExecutionPayloadBodyWithRoot,
 };
 use lighthouse_network::rpc::*;
 use lighthouse_network::{PeerId, PeerRequestId, ReportSource, Response, SyncInfo};
@@ -17,7 +18,7 @@ use std::collections::{hash_map::Entry, HashMap};
 use std::sync::Arc;
 use tokio_stream::StreamExt;
 use types::blob_sidecar::BlobIdentifier;
-use types::{Epoch, EthSpec, FixedBytesExtended, Hash256, Slot};
+use types::{Epoch, EthSpec, ExecPayload, ExecutionBlockHash, FixedBytesExtended, Hash256, Slot};
 
 impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
     /* Auxiliary functions */
@@ -478,9 +479,10 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
             ));
         }
 
+        // The persisted encodings are sent as-is, rather than decoding and re-encoding them.
         let lc_updates = match self
             .chain
-            .get_light_client_updates(req.start_period, req.count)
+            .get_encoded_light_client_updates(req.start_period, req.count)
         {
             Ok(lc_updates) => lc_updates,
             Err(e) => {
@@ -496,7 +498,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         for lc_update in lc_updates.iter() {
             self.send_network_message(NetworkMessage::SendResponse {
                 peer_id,
-                response: Response::LightClientUpdatesByRange(Some(Arc::new(lc_update.clone()))),
+                response: Response::LightClientUpdatesByRangeEncoded(lc_update.clone()),
                 request_id,
                 id: (connection_id, substream_id),
             });
@@ -652,89 +654,14 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         substream_id: SubstreamId,
         request_id: RequestId,
         req: BlocksByRangeRequest,
-    ) -> Result<(), (RpcErrorResponse, &'static str)> {
+    ) -> Result<(), (RpcErrorResponse, String)> {
         debug!(self.log, "Received BlocksByRange Request";
             "peer_id" => %peer_id,
             "count" => req.count(),
             "start_slot" => req.start_slot(),
         );
 
-        // Should not send more than max request blocks
-        let max_request_size =
-            self.chain
-                .epoch()
-                .map_or(self.chain.spec.max_request_blocks, |epoch| {
-                    if self.chain.spec.fork_name_at_epoch(epoch).deneb_enabled() {
-                        self.chain.spec.max_request_blocks_deneb
-                    } else {
-                        self.chain.spec.max_request_blocks
-                    }
-                });
-        if *req.count() > max_request_size {
-            return Err((
-                RpcErrorResponse::InvalidRequest,
-                "Request exceeded max size",
-            ));
-        }
-
-        let forwards_block_root_iter = match self
-            .chain
-            .forwards_iter_block_roots(Slot::from(*req.start_slot()))
-        {
-            Ok(iter) => iter,
-            Err(BeaconChainError::HistoricalBlockOutOfRange {
-                slot,
-                oldest_block_slot,
-            }) => {
-                debug!(self.log, "Range request failed during backfill";
-                    "requested_slot" => slot,
-                    "oldest_known_slot" => oldest_block_slot
-                );
-                return Err((RpcErrorResponse::ResourceUnavailable, "Backfilling"));
-            }
-            Err(e) => {
-                error!(self.log, "Unable to obtain root iter";
-                    "request" => ?req,
-                    "peer" => %peer_id,
-                    "error" => ?e
-                );
-                return Err((RpcErrorResponse::ServerError, "Database error"));
-            }
-        };
-
-        // Pick out the required blocks, ignoring skip-slots.
-        let mut last_block_root = None;
-        let maybe_block_roots = process_results(forwards_block_root_iter, |iter| {
-            iter.take_while(|(_, slot)| {
-                slot.as_u64() < req.start_slot().saturating_add(*req.count())
-            })
-            // map skip slots to None
-            .map(|(root, _)| {
-                let result = if Some(root) == last_block_root {
-                    None
-                } else {
-                    Some(root)
-                };
-                last_block_root = Some(root);
-                result
-            })
-            .collect::<Vec<Option<Hash256>>>()
-        });
-
-        let block_roots = match maybe_block_roots {
-            Ok(block_roots) => block_roots,
-            Err(e) => {
-                error!(self.log, "Error during iteration over blocks";
-                    "request" => ?req,
-                    "peer" => %peer_id,
-                    "error" => ?e
-                );
-                return Err((RpcErrorResponse::ServerError, "Iteration error"));
-            }
-        };
-
-        // remove all skip slots
-        let block_roots = block_roots.into_iter().flatten().collect::<Vec<_>>();
+        let block_roots = self.get_block_roots_by_range(peer_id, &req)?;
 
         let current_slot = self
             .chain
@@ -770,13 +697,35 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
             Ok(block_stream) => block_stream,
             Err(e) => {
                 error!(self.log, "Error getting block stream"; "error" => ?e);
-                return Err((RpcErrorResponse::ServerError, "Iterator error"));
+                return Err((RpcErrorResponse::ServerError, "Iterator error".into()));
             }
         };
 
         // Fetching blocks is async because it may have to hit the execution layer for payloads.
         let mut blocks_sent = 0;
-        while let Some((root, result)) = block_stream.next().await {
+        while let Some((root, mut result)) = block_stream.next().await {
+            // If the primary execution engine failed to reconstruct the payload, retry against the
+            // fallback engines before failing the whole stream.
+            if result
+                .as_ref()
+                .as_ref()
+                .is_err_and(BeaconChainError::is_payload_reconstruction_failure)
+                && self.chain.has_fallback_execution_engines()
+            {
+                debug!(
+                    self.log,
+                    "Retrying payload reconstruction with fallback engines";
+                    "block_root" => ?root,
+                    "error" => ?result.as_ref().as_ref().err(),
+                );
+                result = Arc::new(
+                    self.chain
+                        .get_block_from_fallback_execution_layer(&root)
+                        .await
+                        .map(|opt_block| opt_block.map(Arc::new)),
+                );
+            }
+
             match result.as_ref() {
                 Ok(Some(block)) => {
                     // Due to skip slots, blocks could be out of the range, we ensure they
@@ -802,7 +751,10 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                         "request_root" => ?root
                     );
                     log_results(req, peer_id, blocks_sent);
-                    return Err((RpcErrorResponse::ServerError, "Database inconsistency"));
+                    return Err((
+                        RpcErrorResponse::ServerError,
+                        "Database inconsistency".into(),
+                    ));
                 }
                 Err(BeaconChainError::BlockHashMissingFromExecutionLayer(_)) => {
                     debug!(
@@ -815,7 +767,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                     // send the stream terminator
                     return Err((
                         RpcErrorResponse::ResourceUnavailable,
-                        "Execution layer not synced",
+                        "Execution layer not synced".into(),
                     ));
                 }
                 Err(e) => {
@@ -841,7 +793,10 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                     }
                     log_results(req, peer_id, blocks_sent);
                     // send the stream terminator
-                    return Err((RpcErrorResponse::ServerError, "Failed fetching blocks"));
+                    return Err((
+                        RpcErrorResponse::ServerError,
+                        "Failed fetching blocks".into(),
+                    ));
                 }
             }
         }
@@ -850,6 +805,378 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         Ok(())
     }
 
+    /// Returns the roots of the canonical blocks in the range of a `BlocksByRange` request,
+    /// skipping empty slots.
+    fn get_block_roots_by_range(
+        &self,
+        peer_id: PeerId,
+        req: &BlocksByRangeRequest,
+    ) -> Result<Vec<Hash256>, (RpcErrorResponse, String)> {
+        // Should not send more than max request blocks
+        let max_request_size =
+            self.chain
+                .epoch()
+                .map_or(self.chain.spec.max_request_blocks, |epoch| {
+                    if self.chain.spec.fork_name_at_epoch(epoch).deneb_enabled() {
+                        self.chain.spec.max_request_blocks_deneb
+                    } else {
+                        self.chain.spec.max_request_blocks
+                    }
+                });
+        if *req.count() > max_request_size {
+            return Err((
+                RpcErrorResponse::InvalidRequest,
+                "Request exceeded max size".into(),
+            ));
+        }
+
+        let forwards_block_root_iter = match self
+            .chain
+            .forwards_iter_block_roots(Slot::from(*req.start_slot()))
+        {
+            Ok(iter) => iter,
+            Err(BeaconChainError::HistoricalBlockOutOfRange {
+                slot,
+                oldest_block_slot,
+            }) => {
+                return Err(self.historical_range_unavailable(slot, oldest_block_slot));
+            }
+            Err(e) => {
+                error!(self.log, "Unable to obtain root iter";
+                    "request" => ?req,
+                    "peer" => %peer_id,
+                    "error" => ?e
+                );
+                return Err((RpcErrorResponse::ServerError, "Database error".into()));
+            }
+        };
+
+        // Pick out the required blocks, ignoring skip-slots.
+        let mut last_block_root = None;
+        let maybe_block_roots = process_results(forwards_block_root_iter, |iter| {
+            iter.take_while(|(_, slot)| {
+                slot.as_u64() < req.start_slot().saturating_add(*req.count())
+            })
+            // map skip slots to None
+            .map(|(root, _)| {
+                let result = if Some(root) == last_block_root {
+                    None
+                } else {
+                    Some(root)
+                };
+                last_block_root = Some(root);
+                result
+            })
+            .collect::<Vec<Option<Hash256>>>()
+        });
+
+        let block_roots = match maybe_block_roots {
+            Ok(block_roots) => block_roots,
+            Err(e) => {
+                error!(self.log, "Error during iteration over blocks";
+                    "request" => ?req,
+                    "peer" => %peer_id,
+                    "error" => ?e
+                );
+                return Err((RpcErrorResponse::ServerError, "Iteration error".into()));
+            }
+        };
+
+        // remove all skip slots
+        Ok(block_roots.into_iter().flatten().collect())
+    }
+
+    /// Handle a `BlindedBlocksByRange` request from the peer.
+    pub fn handle_blinded_blocks_by_range_request(
+        self: Arc<Self>,
+        peer_id: PeerId,
+        connection_id: ConnectionId,
+        substream_id: SubstreamId,
+        request_id: RequestId,
+        req: BlocksByRangeRequest,
+    ) {
+        self.terminate_response_stream(
+            peer_id,
+            connection_id,
+            substream_id,
+            request_id,
+            self.handle_blinded_blocks_by_range_request_inner(
+                peer_id,
+                connection_id,
+                substream_id,
+                request_id,
+                req,
+            ),
+            Response::BlindedBlocksByRange,
+        );
+    }
+
+    /// Handle a `BlindedBlocksByRange` request from the peer.
+    ///
+    /// Blinded blocks are read straight from the store, so unlike `BlocksByRange` the execution
+    /// layer is never asked to reconstruct payloads. Only trusted peers are served.
+    pub fn handle_blinded_blocks_by_range_request_inner(
+        &self,
+        peer_id: PeerId,
+        connection_id: ConnectionId,
+        substream_id: SubstreamId,
+        request_id: RequestId,
+        req: BlocksByRangeRequest,
+    ) -> Result<(), (RpcErrorResponse, String)> {
+        debug!(self.log, "Received BlindedBlocksByRange Request";
+            "peer_id" => %peer_id,
+            "count" => req.count(),
+            "start_slot" => req.start_slot(),
+        );
+
+        let is_trusted = self
+            .network_globals
+            .peers
+            .read()
+            .peer_info(&peer_id)
+            .is_some_and(|info| info.is_trusted());
+        if !is_trusted {
+            debug!(self.log, "Refusing BlindedBlocksByRange request from untrusted peer";
+                "peer_id" => %peer_id,
+            );
+            return Err((
+                RpcErrorResponse::ResourceUnavailable,
+                "Blinded blocks are only served to trusted peers".into(),
+            ));
+        }
+
+        let block_roots = self.get_block_roots_by_range(peer_id, &req)?;
+
+        let mut blocks_sent = 0;
+        for root in block_roots {
+            match self.chain.get_blinded_block(&root) {
+                Ok(Some(block)) => {
+                    // Due to skip slots, blocks could be out of the range, we ensure they
+                    // are in the range before sending
+                    if block.slot() >= *req.start_slot()
+                        && block.slot() < req.start_slot() + req.count()
+                    {
+                        blocks_sent += 1;
+                        self.send_network_message(NetworkMessage::SendResponse {
+                            peer_id,
+                            request_id,
+                            response: Response::BlindedBlocksByRange(Some(Arc::new(block))),
+                            id: (connection_id, substream_id),
+                        });
+                    }
+                }
+                Ok(None) => {
+                    error!(
+                        self.log,
+                        "Block in the chain is not in the store";
+                        "request" => ?req,
+                        "peer" => %peer_id,
+                        "request_root" => ?root
+                    );
+                    return Err((
+                        RpcErrorResponse::ServerError,
+                        "Database inconsistency".into(),
+                    ));
+                }
+                Err(e) => {
+                    error!(
+                        self.log,
+                        "Error fetching blinded block for peer";
+                        "block_root" => ?root,
+                        "error" => ?e
+                    );
+                    return Err((
+                        RpcErrorResponse::ServerError,
+                        "Failed fetching blocks".into(),
+                    ));
+                }
+            }
+        }
+
+        debug!(
+            self.log,
+            "BlindedBlocksByRange outgoing response processed";
+            "peer" => %peer_id,
+            "start_slot" => req.start_slot(),
+            "requested" => req.count(),
+            "returned" => blocks_sent
+        );
+        Ok(())
+    }
+
+    /// Handle an `ExecutionPayloadBodiesByRange` request from the peer.
+    pub async fn handle_execution_payload_bodies_by_range_request(
+        self: Arc<Self>,
+        peer_id: PeerId,
+        connection_id: ConnectionId,
+        substream_id: SubstreamId,
+        request_id: RequestId,
+        req: BlocksByRangeRequest,
+    ) {
+        self.terminate_response_stream(
+            peer_id,
+            connection_id,
+            substream_id,
+            request_id,
+            self.handle_execution_payload_bodies_by_range_request_inner(
+                peer_id,
+                connection_id,
+                substream_id,
+                request_id,
+                req,
+            )
+            .await,
+            Response::ExecutionPayloadBodiesByRange,
+        );
+    }
+
+    /// Handle an `ExecutionPayloadBodiesByRange` request from the peer.
+    ///
+    /// Together with `BlindedBlocksByRange` this allows trusted peers to reconstruct full blocks
+    /// without a request to their own execution layer. Bodies are served from the cache of the
+    /// execution layer, so peers requesting the same range only cost a single engine request.
+    /// Blocks prior to the merge have no payload and are omitted from the response.
+    pub async fn handle_execution_payload_bodies_by_range_request_inner(
+        &self,
+        peer_id: PeerId,
+        connection_id: ConnectionId,
+        substream_id: SubstreamId,
+        request_id: RequestId,
+        req: BlocksByRangeRequest,
+    ) -> Result<(), (RpcErrorResponse, String)> {
+        debug!(self.log, "Received ExecutionPayloadBodiesByRange Request";
+            "peer_id" => %peer_id,
+            "count" => req.count(),
+            "start_slot" => req.start_slot(),
+        );
+
+        let is_trusted = self
+            .network_globals
+            .peers
+            .read()
+            .peer_info(&peer_id)
+            .is_some_and(|info| info.is_trusted());
+        if !is_trusted {
+            debug!(self.log, "Refusing ExecutionPayloadBodiesByRange request from untrusted peer";
+                "peer_id" => %peer_id,
+            );
+            return Err((
+                RpcErrorResponse::ResourceUnavailable,
+                "Payload bodies are only served to trusted peers".into(),
+            ));
+        }
+
+        let Some(execution_layer) = self.chain.execution_layer.as_ref() else {
+            return Err((
+                RpcErrorResponse::ResourceUnavailable,
+                "No execution layer".into(),
+            ));
+        };
+
+        let block_roots = self.get_block_roots_by_range(peer_id, &req)?;
+
+        let mut block_hashes = Vec::with_capacity(block_roots.len());
+        for root in block_roots {
+            let block = match self.chain.get_blinded_block(&root) {
+                Ok(Some(block)) => block,
+                Ok(None) => {
+                    error!(
+                        self.log,
+                        "Block in the chain is not in the store";
+                        "request" => ?req,
+                        "peer" => %peer_id,
+                        "request_root" => ?root
+                    );
+                    return Err((
+                        RpcErrorResponse::ServerError,
+                        "Database inconsistency".into(),
+                    ));
+                }
+                Err(e) => {
+                    error!(
+                        self.log,
+                        "Error fetching blinded block for peer";
+                        "block_root" => ?root,
+                        "error" => ?e
+                    );
+                    return Err((
+                        RpcErrorResponse::ServerError,
+                        "Failed fetching blocks".into(),
+                    ));
+                }
+            };
+            // Due to skip slots, blocks could be out of the range, we ensure they are in the
+            // range before sending
+            if block.slot() < *req.start_slot() || block.slot() >= req.start_slot() + req.count() {
+                continue;
+            }
+            if let Ok(payload) = block.message().execution_payload() {
+                let block_hash = payload.block_hash();
+                if block_hash != ExecutionBlockHash::zero() {
+                    block_hashes.push((root, block_hash));
+                }
+            }
+        }
+
+        let bodies = execution_layer
+            .get_payload_bodies_by_hash_cached(
+                block_hashes
+                    .iter()
+                    .map(|(_, block_hash)| *block_hash)
+                    .collect(),
+            )
+            .await
+            .map_err(|e| {
+                error!(
+                    self.log,
+                    "Error fetching payload bodies for peer";
+                    "peer" => %peer_id,
+                    "error" => ?e
+                );
+                (
+                    RpcErrorResponse::ServerError,
+                    "Failed fetching payload bodies".to_string(),
+                )
+            })?;
+
+        let mut bodies_sent = 0;
+        for ((block_root, block_hash), body) in block_hashes.into_iter().zip(bodies) {
+            let Some(body) = body else {
+                // The peer is expected to fall back to its own execution layer.
+                debug!(
+                    self.log,
+                    "Payload body unavailable for peer";
+                    "block_root" => ?block_root,
+                    "block_hash" => ?block_hash
+                );
+                continue;
+            };
+            bodies_sent += 1;
+            self.send_network_message(NetworkMessage::SendResponse {
+                peer_id,
+                request_id,
+                response: Response::ExecutionPayloadBodiesByRange(Some(Arc::new(
+                    ExecutionPayloadBodyWithRoot {
+                        block_root,
+                        transactions: body.transactions,
+                        withdrawals: body.withdrawals,
+                    },
+                ))),
+                id: (connection_id, substream_id),
+            });
+        }
+
+        debug!(
+            self.log,
+            "ExecutionPayloadBodiesByRange outgoing response processed";
+            "peer" => %peer_id,
+            "start_slot" => req.start_slot(),
+            "requested" => req.count(),
+            "returned" => bodies_sent
+        );
+        Ok(())
+    }
+
     /// Handle a `BlobsByRange` request from the peer.
     pub fn handle_blobs_by_range_request(
         self: Arc<Self>,
@@ -883,7 +1210,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         substream_id: SubstreamId,
         request_id: RequestId,
         req: BlobsByRangeRequest,
-    ) -> Result<(), (RpcErrorResponse, &'static str)> {
+    ) -> Result<(), (RpcErrorResponse, String)> {
         debug!(self.log, "Received BlobsByRange Request";
             "peer_id" => %peer_id,
             "count" => req.count,
@@ -894,7 +1221,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         if req.max_blobs_requested::<T::EthSpec>() > self.chain.spec.max_request_blob_sidecars {
             return Err((
                 RpcErrorResponse::InvalidRequest,
-                "Request exceeded `MAX_REQUEST_BLOBS_SIDECARS`",
+                "Request exceeded `MAX_REQUEST_BLOBS_SIDECARS`".into(),
             ));
         }
 
@@ -904,10 +1231,21 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
             Some(boundary) => boundary.start_slot(T::EthSpec::slots_per_epoch()),
             None => {
                 debug!(self.log, "Deneb fork is disabled");
-                return Err((RpcErrorResponse::InvalidRequest, "Deneb fork is disabled"));
+                return Err((
+                    RpcErrorResponse::InvalidRequest,
+                    "Deneb fork is disabled".into(),
+                ));
             }
         };
 
+        // Blobs older than the data availability boundary are served if they are retained.
+        let blob_retention_boundary_slot = self
+            .chain
+            .blob_retention_boundary()
+            .map_or(data_availability_boundary_slot, |boundary| {
+                boundary.start_slot(T::EthSpec::slots_per_epoch())
+            });
+
         let oldest_blob_slot = self
             .chain
             .store
@@ -920,18 +1258,19 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                 "Range request start slot is older than data availability boundary.";
                 "requested_slot" => request_start_slot,
                 "oldest_blob_slot" => oldest_blob_slot,
-                "data_availability_boundary" => data_availability_boundary_slot
+                "data_availability_boundary" => data_availability_boundary_slot,
+                "blob_retention_boundary" => blob_retention_boundary_slot,
             );
 
-            return if data_availability_boundary_slot < oldest_blob_slot {
+            return if blob_retention_boundary_slot < oldest_blob_slot {
                 Err((
                     RpcErrorResponse::ResourceUnavailable,
-                    "blobs pruned within boundary",
+                    "blobs pruned within boundary".into(),
                 ))
             } else {
                 Err((
                     RpcErrorResponse::InvalidRequest,
-                    "Req outside availability period",
+                    "Req outside availability period".into(),
                 ))
             };
         }
@@ -943,11 +1282,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                     slot,
                     oldest_block_slot,
                 }) => {
-                    debug!(self.log, "Range request failed during backfill";
-                        "requested_slot" => slot,
-                        "oldest_known_slot" => oldest_block_slot
-                    );
-                    return Err((RpcErrorResponse::ResourceUnavailable, "Backfilling"));
+                    return Err(self.historical_range_unavailable(slot, oldest_block_slot));
                 }
                 Err(e) => {
                     error!(self.log, "Unable to obtain root iter";
@@ -955,7 +1290,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                         "peer" => %peer_id,
                         "error" => ?e
                     );
-                    return Err((RpcErrorResponse::ServerError, "Database error"));
+                    return Err((RpcErrorResponse::ServerError, "Database error".into()));
                 }
             };
 
@@ -992,7 +1327,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                     "peer" => %peer_id,
                     "error" => ?e
                 );
-                return Err((RpcErrorResponse::ServerError, "Database error"));
+                return Err((RpcErrorResponse::ServerError, "Database error".into()));
             }
         };
 
@@ -1043,7 +1378,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
 
                     return Err((
                         RpcErrorResponse::ServerError,
-                        "No blobs and failed fetching corresponding block",
+                        "No blobs and failed fetching corresponding block".into(),
                     ));
                 }
             }
@@ -1086,7 +1421,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         substream_id: SubstreamId,
         request_id: RequestId,
         req: DataColumnsByRangeRequest,
-    ) -> Result<(), (RpcErrorResponse, &'static str)> {
+    ) -> Result<(), (RpcErrorResponse, String)> {
         debug!(self.log, "Received DataColumnsByRange Request";
             "peer_id" => %peer_id,
             "count" => req.count,
@@ -1097,7 +1432,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         if req.max_requested::<T::EthSpec>() > self.chain.spec.max_request_data_column_sidecars {
             return Err((
                 RpcErrorResponse::InvalidRequest,
-                "Request exceeded `MAX_REQUEST_BLOBS_SIDECARS`",
+                "Request exceeded `MAX_REQUEST_BLOBS_SIDECARS`".into(),
             ));
         }
 
@@ -1107,10 +1442,22 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
             Some(boundary) => boundary.start_slot(T::EthSpec::slots_per_epoch()),
             None => {
                 debug!(self.log, "Deneb fork is disabled");
-                return Err((RpcErrorResponse::InvalidRequest, "Deneb fork is disabled"));
+                return Err((
+                    RpcErrorResponse::InvalidRequest,
+                    "Deneb fork is disabled".into(),
+                ));
             }
         };
 
+        // Data columns older than the data availability boundary are served if they are retained,
+        // e.g. by a data column archive.
+        let data_column_retention_boundary_slot = self
+            .chain
+            .data_column_retention_boundary()
+            .map_or(data_availability_boundary_slot, |boundary| {
+                boundary.start_slot(T::EthSpec::slots_per_epoch())
+            });
+
         let oldest_data_column_slot = self
             .chain
             .store
@@ -1124,18 +1471,19 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                 "Range request start slot is older than data availability boundary.";
                 "requested_slot" => request_start_slot,
                 "oldest_data_column_slot" => oldest_data_column_slot,
-                "data_availability_boundary" => data_availability_boundary_slot
+                "data_availability_boundary" => data_availability_boundary_slot,
+                "data_column_retention_boundary" => data_column_retention_boundary_slot,
             );
 
-            return if data_availability_boundary_slot < oldest_data_column_slot {
+            return if data_column_retention_boundary_slot < oldest_data_column_slot {
                 Err((
                     RpcErrorResponse::ResourceUnavailable,
-                    "blobs pruned within boundary",
+                    "blobs pruned within boundary".into(),
                 ))
             } else {
                 Err((
                     RpcErrorResponse::InvalidRequest,
-                    "Req outside availability period",
+                    "Req outside availability period".into(),
                 ))
             };
         }
@@ -1147,11 +1495,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                     slot,
                     oldest_block_slot,
                 }) => {
-                    debug!(self.log, "Range request failed during backfill";
-                        "requested_slot" => slot,
-                        "oldest_known_slot" => oldest_block_slot
-                    );
-                    return Err((RpcErrorResponse::ResourceUnavailable, "Backfilling"));
+                    return Err(self.historical_range_unavailable(slot, oldest_block_slot));
                 }
                 Err(e) => {
                     error!(self.log, "Unable to obtain root iter";
@@ -1159,7 +1503,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                         "peer" => %peer_id,
                         "error" => ?e
                     );
-                    return Err((RpcErrorResponse::ServerError, "Database error"));
+                    return Err((RpcErrorResponse::ServerError, "Database error".into()));
                 }
             };
 
@@ -1196,7 +1540,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                     "peer" => %peer_id,
                     "error" => ?e
                 );
-                return Err((RpcErrorResponse::ServerError, "Database error"));
+                return Err((RpcErrorResponse::ServerError, "Database error".into()));
             }
         };
 
@@ -1230,7 +1574,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                         );
                         return Err((
                             RpcErrorResponse::ServerError,
-                            "No data columns and failed fetching corresponding block",
+                            "No data columns and failed fetching corresponding block".into(),
                         ));
                     }
                 }
@@ -1255,6 +1599,34 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         Ok(())
     }
 
+    /// The error for a ByRange request which starts before the oldest block in the database.
+    ///
+    /// If backfill has reached its target the blocks are not stored at all, so the reason tells the
+    /// peer which range we serve rather than asking it to retry later.
+    fn historical_range_unavailable(
+        &self,
+        requested_slot: Slot,
+        oldest_block_slot: Slot,
+    ) -> (RpcErrorResponse, String) {
+        let backfill_complete = self
+            .chain
+            .store
+            .get_anchor_info()
+            .block_backfill_complete(self.chain.genesis_backfill_slot);
+        debug!(self.log, "Range request before oldest block";
+            "requested_slot" => requested_slot,
+            "oldest_known_slot" => oldest_block_slot,
+            "backfill_complete" => backfill_complete,
+        );
+
+        let reason = if backfill_complete {
+            format!("Blocks prior to slot {} are not stored", oldest_block_slot)
+        } else {
+            format!("Backfilling, oldest block slot is {}", oldest_block_slot)
+        };
+        (RpcErrorResponse::ResourceUnavailable, reason)
+    }
+
     /// Helper function to ensure single item protocol always end with either a single chunk or an
     /// error
     fn terminate_response_single_item<R, F: Fn(R) -> Response<T::EthSpec>>(
@@ -1298,7 +1670,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         connection_id: ConnectionId,
         substream_id: SubstreamId,
         request_id: RequestId,
-        result: Result<(), (RpcErrorResponse, &'static str)>,
+        result: Result<(), (RpcErrorResponse, impl Into<String>)>,
         into_response: F,
     ) {
         match result {
@@ -17,6 +17,7 @@ use beacon_processor::{
     work_reprocessing_queue::{QueuedRpcBlock, ReprocessQueueMessage},
     AsyncFn, BlockingFn, DuplicateCache,
 };
+use lighthouse_network::types::SyncKind;
 use lighthouse_network::PeerAction;
 use slog::{debug, error, info, warn};
 use std::sync::Arc;
@@ -153,14 +154,26 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
             "process_type" => ?process_type,
         );
 
+        // Register the block with any monitored validators.
+        //
+        // Run this event *prior* to importing the block, so that late blocks received via lookup
+        // are attributed to the proposer alongside gossip blocks.
+        self.chain.validator_monitor.read().register_rpc_block(
+            seen_timestamp,
+            block.message(),
+            block_root,
+            &self.chain.slot_clock,
+        );
+
         let signed_beacon_block = block.block_cloned();
         let result = self
             .chain
-            .process_block_with_early_caching(
+            .process_block_with_early_caching_and_timeout(
                 block_root,
                 block,
                 BlockImportSource::Lookup,
                 NotifyExecutionLayer::Yes,
+                self.chain.config.block_import_timeout,
             )
             .await;
 
@@ -363,7 +376,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                     );
                     // Attempt reconstruction here before notifying sync, to avoid sending out more requests
                     // that we may no longer need.
-                    if let Some(availability) =
+                    if let Some((availability, _)) =
                         self.attempt_data_column_reconstruction(block_root).await
                     {
                         result = Ok(availability)
@@ -426,12 +439,26 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                 let start_slot = downloaded_blocks.first().map(|b| b.slot().as_u64());
                 let end_slot = downloaded_blocks.last().map(|b| b.slot().as_u64());
                 let sent_blocks = downloaded_blocks.len();
+                let n_blobs = downloaded_blocks
+                    .iter()
+                    .map(|wrapped| wrapped.n_blobs())
+                    .sum::<usize>();
+                let n_data_columns = downloaded_blocks
+                    .iter()
+                    .map(|wrapped| wrapped.n_data_columns())
+                    .sum::<usize>();
 
                 match self
                     .process_blocks(downloaded_blocks.iter(), notify_execution_layer)
                     .await
                 {
                     (imported_blocks, Ok(_)) => {
+                        self.network_globals.sync_progress.write().record_import(
+                            SyncKind::Forward,
+                            imported_blocks,
+                            n_blobs,
+                            n_data_columns,
+                        );
                         debug!(self.log, "Batch processed";
                             "batch_epoch" => epoch,
                             "first_block_slot" => start_slot,
@@ -445,6 +472,14 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                         }
                     }
                     (imported_blocks, Err(e)) => {
+                        // The blobs and data columns of the imported blocks are unknown, as they
+                        // are only counted per batch.
+                        self.network_globals.sync_progress.write().record_import(
+                            SyncKind::Forward,
+                            imported_blocks,
+                            0,
+                            0,
+                        );
                         debug!(self.log, "Batch processing failed";
                             "batch_epoch" => epoch,
                             "first_block_slot" => start_slot,
@@ -479,6 +514,12 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
 
                 match self.process_backfill_blocks(downloaded_blocks) {
                     (imported_blocks, Ok(_)) => {
+                        self.network_globals.sync_progress.write().record_import(
+                            SyncKind::Backfill,
+                            imported_blocks,
+                            n_blobs,
+                            n_data_columns,
+                        );
                         debug!(self.log, "Backfill batch processed";
                             "batch_epoch" => epoch,
                             "first_block_slot" => start_slot,
@@ -2,6 +2,7 @@
 #![cfg(test)]
 
 use crate::{
+    inbound_requests::InboundRequestTracker,
     network_beacon_processor::{
         ChainSegmentProcessId, DuplicateCache, InvalidBlockStorage, NetworkBeaconProcessor,
     },
@@ -14,25 +15,30 @@ use beacon_chain::test_utils::{
 };
 use beacon_chain::{BeaconChain, WhenSlotSkipped};
 use beacon_processor::{work_reprocessing_queue::*, *};
+use execution_layer::ExecutionPayloadBodyV1;
 use lighthouse_network::discovery::ConnectionId;
-use lighthouse_network::rpc::methods::BlobsByRangeRequest;
+use lighthouse_network::rpc::methods::{
+    BlobsByRangeRequest, BlocksByRangeRequest, DataColumnsByRangeRequest,
+};
 use lighthouse_network::rpc::{RequestId, SubstreamId};
 use lighthouse_network::{
     discv5::enr::{self, CombinedKey},
     rpc::methods::{MetaData, MetaDataV2},
+    rpc::RpcErrorResponse,
     types::{EnrAttestationBitfield, EnrSyncCommitteeBitfield},
-    Client, MessageId, NetworkConfig, NetworkGlobals, PeerId, Response,
+    Client, MessageAcceptance, MessageId, NetworkConfig, NetworkGlobals, PeerId, Response,
 };
 use slot_clock::SlotClock;
 use std::iter::Iterator;
 use std::sync::Arc;
 use std::time::Duration;
+use store::StoreConfig;
 use tokio::sync::mpsc;
 use types::blob_sidecar::FixedBlobSidecarList;
 use types::{
-    Attestation, AttesterSlashing, BlobSidecar, BlobSidecarList, Epoch, Hash256, MainnetEthSpec,
-    ProposerSlashing, SignedAggregateAndProof, SignedBeaconBlock, SignedVoluntaryExit, Slot,
-    SubnetId,
+    Attestation, AttesterSlashing, BlobSidecar, BlobSidecarList, ChainSpec, Epoch, ExecPayload,
+    Hash256, MainnetEthSpec, ProposerSlashing, SignedAggregateAndProof, SignedBeaconBlock,
+    SignedVoluntaryExit, Slot, SubnetId,
 };
 
 type E = MainnetEthSpec;
@@ -65,6 +71,7 @@ struct TestRig {
     _sync_rx: mpsc::UnboundedReceiver<SyncMessage<E>>,
     duplicate_cache: DuplicateCache,
     network_beacon_processor: Arc<NetworkBeaconProcessor<T>>,
+    trusted_peer: PeerId,
     _harness: BeaconChainHarness<T>,
 }
 
@@ -88,14 +95,29 @@ impl TestRig {
     }
 
     pub async fn new_parametric(chain_length: u64, enable_backfill_rate_limiting: bool) -> Self {
+        Self::new_with_configs(
+            chain_length,
+            enable_backfill_rate_limiting,
+            test_spec::<E>(),
+            StoreConfig::default(),
+        )
+        .await
+    }
+
+    pub async fn new_with_configs(
+        chain_length: u64,
+        enable_backfill_rate_limiting: bool,
+        mut spec: ChainSpec,
+        store_config: StoreConfig,
+    ) -> Self {
         // This allows for testing voluntary exits without building out a massive chain.
-        let mut spec = test_spec::<E>();
         spec.shard_committee_period = 2;
         let spec = Arc::new(spec);
 
         let harness = BeaconChainHarness::builder(MainnetEthSpec)
             .spec(spec.clone())
             .deterministic_keypairs(VALIDATOR_COUNT)
+            .store_config(store_config)
             .fresh_ephemeral_store()
             .mock_execution_layer()
             .chain_config(<_>::default())
@@ -206,10 +228,11 @@ impl TestRig {
         let enr_key = CombinedKey::generate_secp256k1();
         let enr = enr::Enr::builder().build(&enr_key).unwrap();
         let network_config = Arc::new(NetworkConfig::default());
+        let trusted_peer = PeerId::random();
         let network_globals = Arc::new(NetworkGlobals::new(
             enr,
             meta_data,
-            vec![],
+            vec![trusted_peer],
             false,
             &log,
             network_config,
@@ -230,6 +253,7 @@ impl TestRig {
             reprocess_tx: work_reprocessing_tx.clone(),
             network_globals: network_globals.clone(),
             invalid_block_storage: InvalidBlockStorage::Disabled,
+            inbound_requests: Arc::new(InboundRequestTracker::default()),
             executor: executor.clone(),
             log: log.clone(),
         };
@@ -279,6 +303,7 @@ impl TestRig {
             _sync_rx,
             duplicate_cache,
             network_beacon_processor,
+            trusted_peer,
             _harness: harness,
         }
     }
@@ -371,6 +396,46 @@ impl TestRig {
             .unwrap();
     }
 
+    pub fn enqueue_data_columns_by_range_request(&self, start_slot: u64, count: u64) {
+        self.network_beacon_processor
+            .send_data_columns_by_range_request(
+                PeerId::random(),
+                ConnectionId::new_unchecked(42),
+                SubstreamId::new(24),
+                RequestId::new_unchecked(0),
+                DataColumnsByRangeRequest {
+                    start_slot,
+                    count,
+                    columns: (0..self.chain.spec.number_of_columns as u64).collect(),
+                },
+            )
+            .unwrap();
+    }
+
+    pub fn enqueue_blinded_blocks_by_range_request(&self, peer_id: PeerId, count: u64) {
+        self.network_beacon_processor
+            .send_blinded_blocks_by_range_request(
+                peer_id,
+                ConnectionId::new_unchecked(42),
+                SubstreamId::new(24),
+                RequestId::new_unchecked(0),
+                BlocksByRangeRequest::new(0, count),
+            )
+            .unwrap();
+    }
+
+    pub fn enqueue_execution_payload_bodies_by_range_request(&self, peer_id: PeerId, count: u64) {
+        self.network_beacon_processor
+            .send_execution_payload_bodies_by_range_request(
+                peer_id,
+                ConnectionId::new_unchecked(42),
+                SubstreamId::new(24),
+                RequestId::new_unchecked(0),
+                BlocksByRangeRequest::new(0, count),
+            )
+            .unwrap();
+    }
+
     pub fn enqueue_backfill_batch(&self) {
         self.network_beacon_processor
             .send_chain_segment(
@@ -711,6 +776,54 @@ async fn import_gossip_block_at_current_slot() {
     );
 }
 
+/// Blobs processed as a batch are KZG verified together, then validated and imported
+/// individually.
+#[tokio::test]
+async fn import_gossip_blob_batch() {
+    let mut rig = TestRig::new(SMALL_CHAIN).await;
+    let Some(blobs) = rig.next_blobs.clone().filter(|blobs| blobs.len() > 1) else {
+        return;
+    };
+
+    rig.enqueue_gossip_block();
+    rig.assert_event_journal_completes(&[WorkType::GossipBlock])
+        .await;
+
+    let packages = blobs
+        .iter()
+        .map(|blob| GossipBlobSidecarPackage {
+            message_id: junk_message_id(),
+            peer_id: junk_peer_id(),
+            peer_client: Client::default(),
+            blob_index: blob.index,
+            blob_sidecar: blob.clone(),
+            seen_timestamp: Duration::from_secs(0),
+        })
+        .collect();
+    rig.network_beacon_processor
+        .process_gossip_blob_batch(packages)
+        .await;
+
+    assert_eq!(
+        rig.head_root(),
+        rig.next_block.canonical_root(),
+        "block should be imported and become head"
+    );
+
+    // The block and each blob are accepted for propagation.
+    let mut accepted = 0;
+    while let Ok(message) = rig._network_rx.try_recv() {
+        if let NetworkMessage::ValidationResult {
+            validation_result, ..
+        } = message
+        {
+            assert_eq!(validation_result, MessageAcceptance::Accept);
+            accepted += 1;
+        }
+    }
+    assert_eq!(accepted, blobs.len() + 1);
+}
+
 /// Ensure a valid attestation can be imported.
 #[tokio::test]
 async fn import_gossip_attestation() {
@@ -1153,3 +1266,199 @@ async fn test_blobs_by_range() {
     }
     assert_eq!(blob_count, actual_count);
 }
+
+/// Ensure that a data column archive serves data columns from beyond the data availability
+/// boundary, as they are retained by blob pruning.
+#[tokio::test]
+async fn test_data_columns_by_range_data_column_archive() {
+    let mut spec = test_spec::<E>();
+    if !spec.is_peer_das_enabled_for_epoch(Epoch::new(0)) {
+        return;
+    };
+    // Shorten the data availability period so that the boundary is within the chain.
+    spec.min_epochs_for_blob_sidecars_requests = 1;
+    let store_config = StoreConfig {
+        data_column_archive: true,
+        ..StoreConfig::default()
+    };
+    let mut rig = TestRig::new_with_configs(
+        SLOTS_PER_EPOCH * 5,
+        BeaconProcessorConfig::default().enable_backfill_rate_limiting,
+        spec,
+        store_config,
+    )
+    .await;
+
+    let slot_count = SLOTS_PER_EPOCH;
+    let data_availability_boundary = rig.chain.data_availability_boundary().unwrap();
+    assert!(data_availability_boundary.start_slot(SLOTS_PER_EPOCH) >= slot_count);
+
+    // Without the archive, this would prune the data columns of the requested slots.
+    rig.chain.store.try_prune_most_blobs(true).unwrap();
+
+    rig.enqueue_data_columns_by_range_request(0, slot_count);
+
+    let mut column_count = 0;
+    for slot in 0..slot_count {
+        let root = rig
+            .chain
+            .block_root_at_slot(Slot::new(slot), WhenSlotSkipped::None)
+            .unwrap();
+        column_count += root
+            .map(|root| rig.chain.store.get_data_column_keys(root).unwrap().len())
+            .unwrap_or(0);
+    }
+    assert_ne!(column_count, 0, "expected data columns to be retained");
+
+    let mut actual_count = 0;
+    while let Some(next) = rig._network_rx.recv().await {
+        if let NetworkMessage::SendResponse {
+            peer_id: _,
+            response: Response::DataColumnsByRange(data_column),
+            id: _,
+            request_id: _,
+        } = next
+        {
+            if data_column.is_some() {
+                actual_count += 1;
+            } else {
+                break;
+            }
+        } else {
+            panic!("unexpected message {:?}", next);
+        }
+    }
+    assert_eq!(column_count, actual_count);
+}
+
+#[tokio::test]
+async fn test_blinded_blocks_by_range() {
+    let mut rig = TestRig::new(SMALL_CHAIN).await;
+    let slot_count = SMALL_CHAIN + 1;
+    rig.enqueue_blinded_blocks_by_range_request(rig.trusted_peer, slot_count);
+
+    let mut expected_roots = vec![];
+    for slot in 0..slot_count {
+        if let Some(root) = rig
+            .chain
+            .block_root_at_slot(Slot::new(slot), WhenSlotSkipped::None)
+            .unwrap()
+        {
+            expected_roots.push(root);
+        }
+    }
+    let mut actual_roots = vec![];
+    while let Some(next) = rig._network_rx.recv().await {
+        if let NetworkMessage::SendResponse {
+            response: Response::BlindedBlocksByRange(block),
+            ..
+        } = next
+        {
+            match block {
+                Some(block) => actual_roots.push(block.canonical_root()),
+                None => break,
+            }
+        } else {
+            panic!("unexpected message {:?}", next);
+        }
+    }
+    assert!(!expected_roots.is_empty());
+    assert_eq!(expected_roots, actual_roots);
+}
+
+#[tokio::test]
+async fn test_blinded_blocks_by_range_refused_for_untrusted_peer() {
+    let mut rig = TestRig::new(SMALL_CHAIN).await;
+    rig.enqueue_blinded_blocks_by_range_request(PeerId::random(), SMALL_CHAIN);
+
+    match rig._network_rx.recv().await {
+        Some(NetworkMessage::SendErrorResponse {
+            error: RpcErrorResponse::ResourceUnavailable,
+            ..
+        }) => {}
+        other => panic!("unexpected message {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_execution_payload_bodies_by_range() {
+    if test_spec::<E>().bellatrix_fork_epoch != Some(Epoch::new(0)) {
+        return;
+    };
+    let mut rig = TestRig::new(SMALL_CHAIN).await;
+    let slot_count = SMALL_CHAIN + 1;
+    rig.enqueue_execution_payload_bodies_by_range_request(rig.trusted_peer, slot_count);
+
+    let mut bodies = vec![];
+    while let Some(next) = rig._network_rx.recv().await {
+        if let NetworkMessage::SendResponse {
+            response: Response::ExecutionPayloadBodiesByRange(body),
+            ..
+        } = next
+        {
+            match body {
+                Some(body) => bodies.push(body),
+                None => break,
+            }
+        } else {
+            panic!("unexpected message {:?}", next);
+        }
+    }
+
+    // Each body together with the blinded block is sufficient to reconstruct the full block.
+    assert_eq!(bodies.len() as u64, SMALL_CHAIN);
+    for body in bodies {
+        let blinded_block = rig
+            .chain
+            .get_blinded_block(&body.block_root)
+            .unwrap()
+            .unwrap();
+        let header = blinded_block
+            .message()
+            .execution_payload()
+            .unwrap()
+            .to_execution_payload_header();
+        let payload = ExecutionPayloadBodyV1 {
+            transactions: body.transactions.clone(),
+            withdrawals: body.withdrawals.clone(),
+        }
+        .to_payload(header)
+        .unwrap();
+        let full_block = blinded_block.try_into_full_block(Some(payload)).unwrap();
+        assert_eq!(
+            Some(full_block),
+            rig.chain.get_block(&body.block_root).await.unwrap()
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_execution_payload_bodies_by_range_refused_for_untrusted_peer() {
+    let mut rig = TestRig::new(SMALL_CHAIN).await;
+    rig.enqueue_execution_payload_bodies_by_range_request(PeerId::random(), SMALL_CHAIN);
+
+    match rig._network_rx.recv().await {
+        Some(NetworkMessage::SendErrorResponse {
+            error: RpcErrorResponse::ResourceUnavailable,
+            ..
+        }) => {}
+        other => panic!("unexpected message {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_rpc_requests_rejected_when_shutting_down() {
+    let mut rig = TestRig::new(SMALL_CHAIN).await;
+    let inbound_requests = rig.network_beacon_processor.inbound_requests.clone();
+    inbound_requests.stop_accepting();
+    rig.enqueue_blobs_by_range_request(1);
+
+    match rig._network_rx.recv().await {
+        Some(NetworkMessage::SendErrorResponse {
+            error: RpcErrorResponse::ServerError,
+            ..
+        }) => {}
+        other => panic!("unexpected message {:?}", other),
+    }
+    assert_eq!(inbound_requests.in_flight(), 0);
+}
@@ -0,0 +1,110 @@
+use lighthouse_network::PeerId;
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+use std::sync::Arc;
+use store::{DBColumn, Error as StoreError, HotColdDB, ItemStore, StoreItem};
+use types::{EthSpec, Hash256};
+
+/// 32-byte key for accessing the `PersistedLookups`. All zero because `PersistedLookups` has its
+/// own column.
+pub const LOOKUPS_DB_KEY: Hash256 = Hash256::ZERO;
+
+/// Load the block lookups persisted on the last shutdown, removing them from the store so that
+/// they are only restored once.
+pub fn take_lookups<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    store: Arc<HotColdDB<E, Hot, Cold>>,
+) -> Result<Vec<(Hash256, Vec<PeerId>)>, StoreError> {
+    let Some(persisted) = store.get_item::<PersistedLookups>(&LOOKUPS_DB_KEY)? else {
+        return Ok(vec![]);
+    };
+    store.hot_db.delete::<PersistedLookups>(&LOOKUPS_DB_KEY)?;
+
+    Ok(persisted
+        .lookups
+        .into_iter()
+        .map(|lookup| {
+            let peers = lookup
+                .peers
+                .iter()
+                .filter_map(|bytes| PeerId::from_bytes(bytes).ok())
+                .collect();
+            (lookup.block_root, peers)
+        })
+        .collect())
+}
+
+/// Attempt to persist the block roots of pending lookups and the peers which claim to have them.
+pub fn persist_lookups<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    store: Arc<HotColdDB<E, Hot, Cold>>,
+    lookups: Vec<(Hash256, Vec<PeerId>)>,
+) -> Result<(), StoreError> {
+    let lookups = lookups
+        .into_iter()
+        .map(|(block_root, peers)| PersistedLookup {
+            block_root,
+            peers: peers.iter().map(|peer_id| peer_id.to_bytes()).collect(),
+        })
+        .collect();
+    store.put_item(&LOOKUPS_DB_KEY, &PersistedLookups { lookups })
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct PersistedLookup {
+    block_root: Hash256,
+    /// Encoded `PeerId`s.
+    peers: Vec<Vec<u8>>,
+}
+
+/// Wrapper around pending block lookups for persistence to disk.
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct PersistedLookups {
+    lookups: Vec<PersistedLookup>,
+}
+
+impl StoreItem for PersistedLookups {
+    fn db_column() -> DBColumn {
+        DBColumn::SyncLookups
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, StoreError> {
+        Ok(Self::from_ssz_bytes(bytes)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sloggers::{null::NullLoggerBuilder, Build};
+    use store::config::StoreConfig;
+    use store::MemoryStore;
+    use types::{ChainSpec, MinimalEthSpec};
+
+    #[test]
+    fn test_persisted_lookups() {
+        let log = NullLoggerBuilder.build().unwrap();
+        let store: Arc<
+            HotColdDB<MinimalEthSpec, MemoryStore<MinimalEthSpec>, MemoryStore<MinimalEthSpec>>,
+        > = Arc::new(
+            HotColdDB::open_ephemeral(StoreConfig::default(), ChainSpec::minimal().into(), log)
+                .unwrap(),
+        );
+        assert!(take_lookups(store.clone()).unwrap().is_empty());
+
+        let lookups = vec![
+            (
+                Hash256::repeat_byte(1),
+                vec![PeerId::random(), PeerId::random()],
+            ),
+            (Hash256::repeat_byte(2), vec![]),
+        ];
+        persist_lookups(store.clone(), lookups.clone()).unwrap();
+        assert_eq!(take_lookups(store.clone()).unwrap(), lookups);
+
+        // Lookups are only restored once.
+        assert!(take_lookups(store).unwrap().is_empty());
+    }
+}
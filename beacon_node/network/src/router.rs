@@ -5,11 +5,12 @@
 //! syncing-related responses to the Sync manager.
 #![allow(clippy::unit_arg)]
 
+use crate::inbound_requests::InboundRequestTracker;
 use crate::network_beacon_processor::{InvalidBlockStorage, NetworkBeaconProcessor};
 use crate::service::NetworkMessage;
 use crate::status::status_message;
 use crate::sync::SyncMessage;
-use beacon_chain::{BeaconChain, BeaconChainTypes};
+use beacon_chain::{AvailabilityProcessingStatus, BeaconChain, BeaconChainTypes};
 use beacon_processor::{
     work_reprocessing_queue::ReprocessQueueMessage, BeaconProcessorSend, DuplicateCache,
 };
@@ -26,9 +27,9 @@ use slog::{crit, debug, o, trace};
 use slog::{error, warn};
 use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 use tokio_stream::wrappers::UnboundedReceiverStream;
-use types::{BlobSidecar, DataColumnSidecar, EthSpec, SignedBeaconBlock};
+use types::{BlobSidecar, ColumnIndex, DataColumnSidecar, EthSpec, Hash256, SignedBeaconBlock};
 
 /// Handles messages from the network and routes them to the appropriate service to be handled.
 pub struct Router<T: BeaconChainTypes> {
@@ -77,6 +78,11 @@ pub enum RouterMessage<E: EthSpec> {
     PubsubMessage(MessageId, PeerId, PubsubMessage<E>, bool),
     /// The peer manager has requested we re-status a peer.
     StatusPeer(PeerId),
+    /// The HTTP API has requested the reconstruction of the data columns of a block.
+    ReconstructDataColumns {
+        block_root: Hash256,
+        result_tx: oneshot::Sender<Option<(AvailabilityProcessingStatus, Vec<ColumnIndex>)>>,
+    },
 }
 
 impl<T: BeaconChainTypes> Router<T> {
@@ -86,6 +92,7 @@ impl<T: BeaconChainTypes> Router<T> {
         beacon_chain: Arc<BeaconChain<T>>,
         network_globals: Arc<NetworkGlobals<T::EthSpec>>,
         network_send: mpsc::UnboundedSender<NetworkMessage<T::EthSpec>>,
+        inbound_requests: Arc<InboundRequestTracker>,
         executor: task_executor::TaskExecutor,
         invalid_block_storage: InvalidBlockStorage,
         beacon_processor_send: BeaconProcessorSend<T::EthSpec>,
@@ -110,6 +117,7 @@ impl<T: BeaconChainTypes> Router<T> {
             reprocess_tx: beacon_processor_reprocess_tx,
             network_globals: network_globals.clone(),
             invalid_block_storage,
+            inbound_requests,
             executor: executor.clone(),
             log: log.clone(),
         };
@@ -186,6 +194,15 @@ impl<T: BeaconChainTypes> Router<T> {
             RouterMessage::PubsubMessage(id, peer_id, gossip, should_process) => {
                 self.handle_gossip(id, peer_id, gossip, should_process);
             }
+            RouterMessage::ReconstructDataColumns {
+                block_root,
+                result_tx,
+            } => {
+                self.handle_beacon_processor_send_result(
+                    self.network_beacon_processor
+                        .send_data_column_reconstruction(block_root, result_tx),
+                );
+            }
         }
     }
 
@@ -235,6 +252,27 @@ impl<T: BeaconChainTypes> Router<T> {
                     ),
                 )
             }
+            RequestType::BlindedBlocksByRange(request) => self.handle_beacon_processor_send_result(
+                self.network_beacon_processor
+                    .send_blinded_blocks_by_range_request(
+                        peer_id,
+                        request_id.0,
+                        request_id.1,
+                        rpc_request.id,
+                        request,
+                    ),
+            ),
+            RequestType::ExecutionPayloadBodiesByRange(request) => self
+                .handle_beacon_processor_send_result(
+                    self.network_beacon_processor
+                        .send_execution_payload_bodies_by_range_request(
+                            peer_id,
+                            request_id.0,
+                            request_id.1,
+                            rpc_request.id,
+                            request,
+                        ),
+                ),
             RequestType::BlocksByRoot(request) => self.handle_beacon_processor_send_result(
                 self.network_beacon_processor.send_blocks_by_roots_request(
                     peer_id,
@@ -358,18 +396,31 @@ impl<T: BeaconChainTypes> Router<T> {
             Response::DataColumnsByRange(data_column) => {
                 self.on_data_columns_by_range_response(peer_id, request_id, data_column);
             }
+            // Lighthouse only serves blinded blocks and payload bodies to its trusted peers and
+            // never requests them, so there is no request for a response to belong to.
+            Response::BlindedBlocksByRange(_) | Response::ExecutionPayloadBodiesByRange(_) => {
+                debug!(
+                    self.log,
+                    "Dropping unrequested blinded block or payload body response";
+                    "peer" => %peer_id,
+                );
+            }
             // Light client responses should not be received
             Response::LightClientBootstrap(_)
             | Response::LightClientOptimisticUpdate(_)
             | Response::LightClientFinalityUpdate(_)
-            | Response::LightClientUpdatesByRange(_) => unreachable!(),
+            | Response::LightClientUpdatesByRange(_)
+            | Response::LightClientUpdatesByRangeEncoded(_) => unreachable!(),
         }
     }
 
     /// Handle RPC messages.
-    /// Note: `should_process` is currently only useful for the `Attestation` variant.
-    /// if `should_process` is `false`, we only propagate the message on successful verification,
-    /// else, we propagate **and** import into the beacon chain.
+    /// Note: `should_process` is currently only useful for the `Attestation` and
+    /// `SyncCommitteeMessage` variants.
+    /// For attestations, if `should_process` is `false`, we only propagate the message on
+    /// successful verification, else, we propagate **and** import into the beacon chain.
+    /// For sync committee messages, if `should_process` is `false` the message may be shed when the
+    /// beacon processor is overloaded.
     fn handle_gossip(
         &mut self,
         message_id: MessageId,
@@ -496,6 +547,7 @@ impl<T: BeaconChainTypes> Router<T> {
                         peer_id,
                         sync_committtee_msg.1,
                         sync_committtee_msg.0,
+                        should_process,
                         timestamp_now(),
                     ),
                 )
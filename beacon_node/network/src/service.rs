@@ -1,3 +1,4 @@
+use crate::inbound_requests::InboundRequestTracker;
 use crate::metrics;
 use crate::nat;
 use crate::network_beacon_processor::InvalidBlockStorage;
@@ -5,7 +6,7 @@ use crate::persisted_dht::{clear_dht, load_dht, persist_dht};
 use crate::router::{Router, RouterMessage};
 use crate::subnet_service::{SubnetService, SubnetServiceMessage, Subscription};
 use crate::NetworkConfig;
-use beacon_chain::{BeaconChain, BeaconChainTypes};
+use beacon_chain::{AvailabilityProcessingStatus, BeaconChain, BeaconChainTypes};
 use beacon_processor::{work_reprocessing_queue::ReprocessQueueMessage, BeaconProcessorSend};
 use futures::channel::mpsc::Sender;
 use futures::future::OptionFuture;
@@ -13,7 +14,7 @@ use futures::prelude::*;
 use futures::StreamExt;
 use lighthouse_network::rpc::{RequestId, RequestType};
 use lighthouse_network::service::Network;
-use lighthouse_network::types::GossipKind;
+use lighthouse_network::types::{GossipKind, SubnetSubscription, SubscriptionReason};
 use lighthouse_network::{prometheus_client::registry::Registry, MessageAcceptance};
 use lighthouse_network::{
     rpc::{GoodbyeReason, RpcErrorResponse},
@@ -22,21 +23,23 @@ use lighthouse_network::{
 use lighthouse_network::{
     service::api_types::AppRequestId,
     types::{core_topics_to_subscribe, GossipEncoding, GossipTopic},
-    MessageId, NetworkEvent, NetworkGlobals, PeerId,
+    MessageId, NetworkEvent, NetworkGlobals, PeerId, TopicHash,
 };
 use slog::{crit, debug, error, info, o, trace, warn};
+use slot_clock::SlotClock;
 use std::collections::BTreeSet;
 use std::{collections::HashSet, pin::Pin, sync::Arc, time::Duration};
 use store::HotColdDB;
 use strum::IntoStaticStr;
 use task_executor::ShutdownReason;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 use tokio::time::Sleep;
 use types::{
-    ChainSpec, DataColumnSubnetId, EthSpec, ForkContext, Slot, SubnetId, SyncCommitteeSubscription,
-    SyncSubnetId, Unsigned, ValidatorSubscription,
+    ChainSpec, ColumnIndex, DataColumnSubnetId, EthSpec, ForkContext, Hash256, Slot, SubnetId,
+    SyncCommitteeSubscription, SyncSubnetId, Unsigned, ValidatorSubscription,
 };
 
+mod gossip_pre_check;
 mod tests;
 
 /// The interval (in seconds) that various network metrics will update.
@@ -101,6 +104,13 @@ pub enum NetworkMessage<E: EthSpec> {
         reason: GoodbyeReason,
         source: ReportSource,
     },
+    /// Reconstructs the missing data columns of a block which is awaiting its data, on request of
+    /// the HTTP API. The availability of the block and the indices of the reconstructed columns
+    /// are sent on `result_tx`, or `None` if no columns were reconstructed.
+    ReconstructDataColumns {
+        block_root: Hash256,
+        result_tx: oneshot::Sender<Option<(AvailabilityProcessingStatus, Vec<ColumnIndex>)>>,
+    },
 }
 
 /// Messages triggered by validators that may trigger a subscription to a subnet.
@@ -124,6 +134,7 @@ pub enum ValidatorSubscriptionMessage {
 pub struct NetworkSenders<E: EthSpec> {
     network_send: mpsc::UnboundedSender<NetworkMessage<E>>,
     validator_subscription_send: mpsc::Sender<ValidatorSubscriptionMessage>,
+    inbound_requests: Arc<InboundRequestTracker>,
 }
 
 pub struct NetworkReceivers<E: EthSpec> {
@@ -139,6 +150,7 @@ impl<E: EthSpec> NetworkSenders<E> {
         let senders = Self {
             network_send,
             validator_subscription_send,
+            inbound_requests: Arc::new(InboundRequestTracker::default()),
         };
         let receivers = NetworkReceivers {
             network_recv,
@@ -154,6 +166,12 @@ impl<E: EthSpec> NetworkSenders<E> {
     pub fn validator_subscription_send(&self) -> mpsc::Sender<ValidatorSubscriptionMessage> {
         self.validator_subscription_send.clone()
     }
+
+    /// Returns the tracker of the inbound RPC requests being served, which is used to let them
+    /// finish before shutting down.
+    pub fn inbound_requests(&self) -> Arc<InboundRequestTracker> {
+        self.inbound_requests.clone()
+    }
 }
 
 /// Service that handles communication between internal services and the `lighthouse_network` network service.
@@ -185,6 +203,8 @@ pub struct NetworkService<T: BeaconChainTypes> {
     subscribe_all_data_column_subnets: bool,
     /// Subscribe to all the subnets once synced.
     subscribe_all_subnets: bool,
+    /// Only subscribe to the gossip topics required to follow the head of the chain.
+    rpc_serving_only: bool,
     /// Shutdown beacon node after sync is complete.
     shutdown_after_sync: bool,
     /// Whether metrics are enabled or not.
@@ -226,26 +246,6 @@ impl<T: BeaconChainTypes> NetworkService<T> {
             "Backfill is disabled. DO NOT RUN IN PRODUCTION"
         );
 
-        if let (true, false, Some(v4)) = (
-            config.upnp_enabled,
-            config.disable_discovery,
-            config.listen_addrs().v4(),
-        ) {
-            let nw = network_log.clone();
-            let v4 = v4.clone();
-            executor.spawn(
-                async move {
-                    info!(nw, "UPnP Attempting to initialise routes");
-                    if let Err(e) =
-                        nat::construct_upnp_mappings(v4.addr, v4.disc_port, nw.clone()).await
-                    {
-                        info!(nw, "Could not UPnP map Discovery port"; "error" => %e);
-                    }
-                },
-                "UPnP",
-            );
-        }
-
         // get a reference to the beacon chain store
         let store = beacon_chain.store.clone();
 
@@ -283,6 +283,23 @@ impl<T: BeaconChainTypes> NetworkService<T> {
         let (mut libp2p, network_globals) =
             Network::new(executor.clone(), service_context, &network_log).await?;
 
+        if let (true, false, Some(v4)) = (
+            config.upnp_enabled,
+            config.disable_discovery,
+            config.listen_addrs().v4(),
+        ) {
+            let nw = network_log.clone();
+            let v4 = v4.clone();
+            let network_globals = network_globals.clone();
+            executor.spawn(
+                async move {
+                    info!(nw, "UPnP Attempting to initialise routes");
+                    nat::maintain_port_mappings(v4.addr, v4.disc_port, network_globals, nw).await
+                },
+                "UPnP",
+            );
+        }
+
         // Repopulate the DHT with stored ENR's if discovery is not disabled.
         if !config.disable_discovery {
             let enrs_to_load = load_dht::<T::EthSpec, T::HotStore, T::ColdStore>(store.clone());
@@ -295,6 +312,28 @@ impl<T: BeaconChainTypes> NetworkService<T> {
             }
         }
 
+        // Seed the peer manager with the peers imported from another node, so that the best of
+        // them are dialed without waiting for discovery.
+        if !config.imported_peers.is_empty() {
+            info!(
+                network_log,
+                "Importing peers"; "peers" => config.imported_peers.len()
+            );
+            if !config.disable_discovery {
+                for enr in &config.imported_peers {
+                    libp2p.add_enr(enr.clone());
+                }
+            }
+            libp2p.peer_manager_mut().peers_discovered(
+                config
+                    .imported_peers
+                    .iter()
+                    .take(config.target_peers)
+                    .map(|enr| (enr.clone(), None))
+                    .collect(),
+            );
+        }
+
         let invalid_block_storage = config
             .invalid_block_storage
             .clone()
@@ -308,6 +347,7 @@ impl<T: BeaconChainTypes> NetworkService<T> {
             beacon_chain.clone(),
             network_globals.clone(),
             network_senders.network_send(),
+            network_senders.inbound_requests(),
             executor.clone(),
             invalid_block_storage,
             beacon_processor_send,
@@ -318,7 +358,7 @@ impl<T: BeaconChainTypes> NetworkService<T> {
         // attestation and sync committee subnet service
         let subnet_service = SubnetService::new(
             beacon_chain.clone(),
-            network_globals.local_enr().node_id(),
+            network_globals.clone(),
             &config,
             &network_log,
         );
@@ -350,6 +390,7 @@ impl<T: BeaconChainTypes> NetworkService<T> {
             next_unsubscribe,
             subscribe_all_data_column_subnets: config.subscribe_all_data_column_subnets,
             subscribe_all_subnets: config.subscribe_all_subnets,
+            rpc_serving_only: config.rpc_serving_only,
             shutdown_after_sync: config.shutdown_after_sync,
             metrics_enabled: config.metrics_enabled,
             metrics_update,
@@ -429,6 +470,42 @@ impl<T: BeaconChainTypes> NetworkService<T> {
         }
     }
 
+    /// Run the cheap checks of `gossip_pre_check` against a gossip message received on `topic`.
+    fn gossip_pre_check(
+        &self,
+        topic: &TopicHash,
+        message: &PubsubMessage<T::EthSpec>,
+    ) -> Result<(), gossip_pre_check::DropReason> {
+        let spec = &self.beacon_chain.spec;
+        // Without a valid topic or clock there is nothing to check against.
+        let Ok(gossip_topic) = GossipTopic::decode(topic.as_str()) else {
+            return Ok(());
+        };
+        let Some(current_slot) = self
+            .beacon_chain
+            .slot_clock
+            .now_with_future_tolerance(spec.maximum_gossip_clock_disparity())
+        else {
+            return Ok(());
+        };
+        let finalized_slot = self
+            .beacon_chain
+            .canonical_head
+            .cached_head()
+            .finalized_checkpoint()
+            .epoch
+            .start_slot(T::EthSpec::slots_per_epoch());
+
+        gossip_pre_check::pre_check(
+            message,
+            gossip_topic.fork_digest,
+            current_slot,
+            finalized_slot,
+            &self.fork_context,
+            spec,
+        )
+    }
+
     fn spawn_service(mut self, executor: task_executor::TaskExecutor) {
         let mut shutdown_sender = executor.shutdown_sender();
 
@@ -537,9 +614,28 @@ impl<T: BeaconChainTypes> NetworkService<T> {
             NetworkEvent::PubsubMessage {
                 id,
                 source,
+                topic,
                 message,
-                ..
             } => {
+                if let Err(reason) = self.gossip_pre_check(&topic, &message) {
+                    trace!(
+                        self.log,
+                        "Dropping irrelevant gossip message";
+                        "reason" => ?reason,
+                        "topic" => %topic,
+                        "peer_id" => %source,
+                    );
+                    metrics::inc_counter_vec(
+                        &metrics::GOSSIP_PRE_CHECK_DROPPED,
+                        &[message.kind().as_ref(), reason.into()],
+                    );
+                    self.libp2p.report_message_validation_result(
+                        &source,
+                        id,
+                        MessageAcceptance::Ignore,
+                    );
+                    return;
+                }
                 match message {
                     // attestation information gets processed in the attestation service
                     PubsubMessage::Attestation(ref subnet_and_attestation) => {
@@ -558,6 +654,19 @@ impl<T: BeaconChainTypes> NetworkService<T> {
                             should_process,
                         ));
                     }
+                    // sync committee messages are only required if a local validator may
+                    // aggregate them, otherwise they may be shed under load.
+                    PubsubMessage::SyncCommitteeMessage(ref subnet_and_message) => {
+                        let should_process = self
+                            .subnet_service
+                            .should_process_sync_committee_message(subnet_and_message.0);
+                        self.send_to_router(RouterMessage::PubsubMessage(
+                            id,
+                            source,
+                            message,
+                            should_process,
+                        ));
+                    }
                     _ => {
                         // all else is sent to the router
                         self.send_to_router(RouterMessage::PubsubMessage(
@@ -674,6 +783,13 @@ impl<T: BeaconChainTypes> NetworkService<T> {
                 reason,
                 source,
             } => self.libp2p.goodbye_peer(&peer_id, reason, source),
+            NetworkMessage::ReconstructDataColumns {
+                block_root,
+                result_tx,
+            } => self.send_to_router(RouterMessage::ReconstructDataColumns {
+                block_root,
+                result_tx,
+            }),
             NetworkMessage::SubscribeCoreTopics => {
                 if self.subscribed_core_topics() {
                     return;
@@ -697,10 +813,7 @@ impl<T: BeaconChainTypes> NetworkService<T> {
                 }
 
                 let mut subscribed_topics: Vec<GossipTopic> = vec![];
-                for topic_kind in core_topics_to_subscribe::<T::EthSpec>(
-                    self.fork_context.current_fork(),
-                    &self.fork_context.spec,
-                ) {
+                for topic_kind in self.core_topics_to_subscribe() {
                     for fork_digest in self.required_gossip_fork_digests() {
                         let topic = GossipTopic::new(
                             topic_kind.clone(),
@@ -715,7 +828,7 @@ impl<T: BeaconChainTypes> NetworkService<T> {
                     }
                 }
 
-                if self.enable_light_client_server {
+                if self.enable_light_client_server && !self.rpc_serving_only {
                     for light_client_topic_kind in
                         lighthouse_network::types::LIGHT_CLIENT_GOSSIP_TOPICS.iter()
                     {
@@ -744,11 +857,12 @@ impl<T: BeaconChainTypes> NetworkService<T> {
                 }
 
                 // If we are to subscribe to all subnets we do it here
-                if self.subscribe_all_subnets {
+                if self.subscribe_all_subnets && !self.rpc_serving_only {
                     for subnet_id in 0..<<T as BeaconChainTypes>::EthSpec as EthSpec>::SubnetBitfieldLength::to_u64() {
                         let subnet = Subnet::Attestation(SubnetId::new(subnet_id));
                         // Update the ENR bitfield
                         self.libp2p.update_enr_subnet(subnet, true);
+                        self.record_subnet_subscription(subnet, SubscriptionReason::AllSubnets);
                         for fork_digest in self.required_gossip_fork_digests() {
                             let topic = GossipTopic::new(subnet.into(), GossipEncoding::default(), fork_digest);
                             if self.libp2p.subscribe(topic.clone()) {
@@ -763,6 +877,7 @@ impl<T: BeaconChainTypes> NetworkService<T> {
                         let subnet = Subnet::SyncCommittee(SyncSubnetId::new(subnet_id));
                         // Update the ENR bitfield
                         self.libp2p.update_enr_subnet(subnet, true);
+                        self.record_subnet_subscription(subnet, SubscriptionReason::AllSubnets);
                         for fork_digest in self.required_gossip_fork_digests() {
                             let topic = GossipTopic::new(
                                 subnet.into(),
@@ -792,9 +907,10 @@ impl<T: BeaconChainTypes> NetworkService<T> {
     fn subscribe_to_peer_das_topics(&mut self, subscribed_topics: &mut Vec<GossipTopic>) {
         if self.subscribe_all_data_column_subnets {
             for column_subnet in 0..self.fork_context.spec.data_column_sidecar_subnet_count {
+                let subnet = Subnet::DataColumn(DataColumnSubnetId::new(column_subnet));
+                self.record_subnet_subscription(subnet, SubscriptionReason::AllSubnets);
                 for fork_digest in self.required_gossip_fork_digests() {
-                    let gossip_kind =
-                        Subnet::DataColumn(DataColumnSubnetId::new(column_subnet)).into();
+                    let gossip_kind = subnet.into();
                     let topic =
                         GossipTopic::new(gossip_kind, GossipEncoding::default(), fork_digest);
                     if self.libp2p.subscribe(topic.clone()) {
@@ -806,6 +922,10 @@ impl<T: BeaconChainTypes> NetworkService<T> {
             }
         } else {
             for column_subnet in &self.network_globals.sampling_subnets {
+                self.record_subnet_subscription(
+                    Subnet::DataColumn(*column_subnet),
+                    SubscriptionReason::Custody,
+                );
                 for fork_digest in self.required_gossip_fork_digests() {
                     let gossip_kind = Subnet::DataColumn(*column_subnet).into();
                     let topic =
@@ -820,6 +940,14 @@ impl<T: BeaconChainTypes> NetworkService<T> {
         }
     }
 
+    /// Records a long-lived subnet subscription made by the network service.
+    fn record_subnet_subscription(&self, subnet: Subnet, reason: SubscriptionReason) {
+        self.network_globals
+            .subnet_subscriptions
+            .write()
+            .insert(subnet, SubnetSubscription::long_lived(reason));
+    }
+
     /// Handle a message sent to the network service.
     async fn on_validator_subscription_msg(&mut self, msg: ValidatorSubscriptionMessage) {
         match msg {
@@ -931,11 +1059,20 @@ impl<T: BeaconChainTypes> NetworkService<T> {
         }
     }
 
-    fn subscribed_core_topics(&self) -> bool {
-        let core_topics = core_topics_to_subscribe::<T::EthSpec>(
+    /// Returns the core topics of the current fork which this node subscribes to.
+    fn core_topics_to_subscribe(&self) -> Vec<GossipKind> {
+        let mut core_topics = core_topics_to_subscribe::<T::EthSpec>(
             self.fork_context.current_fork(),
             &self.fork_context.spec,
         );
+        if self.rpc_serving_only {
+            core_topics.retain(GossipKind::is_required_to_follow_head);
+        }
+        core_topics
+    }
+
+    fn subscribed_core_topics(&self) -> bool {
+        let core_topics = self.core_topics_to_subscribe();
         let core_topics: HashSet<&GossipKind> = HashSet::from_iter(&core_topics);
         let subscriptions = self.network_globals.gossipsub_subscriptions.read();
         let subscribed_topics: HashSet<&GossipKind> =
@@ -0,0 +1,178 @@
+//! Cheap checks on gossip messages, run on the network thread before they are queued for the
+//! beacon processor.
+//!
+//! These only drop messages which gossip verification would certainly ignore, such as messages
+//! for a slot in a different fork than their topic or for slots far from the current slot. They
+//! reduce the load that misconfigured or malicious peers can put on the processor queues.
+use lighthouse_network::PubsubMessage;
+use strum::IntoStaticStr;
+use types::{ChainSpec, EthSpec, ForkContext, Slot};
+
+/// Messages for slots more than this many slots after the current slot are dropped.
+///
+/// The current slot already accounts for `MAXIMUM_GOSSIP_CLOCK_DISPARITY`, so this is a generous
+/// margin which leaves the precise decision to gossip verification.
+const FUTURE_SLOT_TOLERANCE: u64 = 1;
+
+/// Attestations and sync committee messages older than this many epochs are dropped.
+const PAST_EPOCH_TOLERANCE: u64 = 2;
+
+/// The reason a gossip message was dropped before being queued.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum DropReason {
+    /// The fork digest of the topic matches neither the fork of the message's slot nor the
+    /// current fork.
+    ForkDigestMismatch,
+    /// The message is for a slot too far in the future.
+    FutureSlot,
+    /// The block, blob or data column is for a slot that is already finalized.
+    FinalizedSlot,
+    /// The attestation or sync committee message is for a slot too far in the past.
+    ExpiredSlot,
+}
+
+/// Check whether `message`, received on a topic with `topic_fork_digest`, is worth queueing.
+///
+/// Messages without a slot, such as exits and slashings, are always accepted.
+pub fn pre_check<E: EthSpec>(
+    message: &PubsubMessage<E>,
+    topic_fork_digest: [u8; 4],
+    current_slot: Slot,
+    finalized_slot: Slot,
+    fork_context: &ForkContext,
+    spec: &ChainSpec,
+) -> Result<(), DropReason> {
+    let Some(slot) = message.slot() else {
+        return Ok(());
+    };
+
+    // Messages published around a fork boundary may be sent on the topics of the current fork for
+    // a slot of the previous one, so both digests are acceptable.
+    let digest_at = |slot: Slot| fork_context.to_context_bytes(spec.fork_name_at_slot::<E>(slot));
+    if digest_at(slot) != Some(topic_fork_digest)
+        && digest_at(current_slot) != Some(topic_fork_digest)
+    {
+        return Err(DropReason::ForkDigestMismatch);
+    }
+
+    if slot > current_slot + FUTURE_SLOT_TOLERANCE {
+        return Err(DropReason::FutureSlot);
+    }
+
+    match message {
+        PubsubMessage::BeaconBlock(_)
+        | PubsubMessage::BlobSidecar(_)
+        | PubsubMessage::DataColumnSidecar(_) => {
+            if slot <= finalized_slot {
+                return Err(DropReason::FinalizedSlot);
+            }
+        }
+        _ => {
+            if slot + PAST_EPOCH_TOLERANCE * E::slots_per_epoch() < current_slot {
+                return Err(DropReason::ExpiredSlot);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{
+        BeaconBlock, Epoch, ForkName, Hash256, MinimalEthSpec, Signature, SignedBeaconBlock,
+        SyncCommitteeMessage, SyncSubnetId,
+    };
+
+    type E = MinimalEthSpec;
+
+    /// A spec which forks to Altair at slot 16.
+    fn spec() -> ChainSpec {
+        let mut spec = ForkName::Base.make_genesis_spec(E::default_spec());
+        spec.altair_fork_epoch = Some(Epoch::new(2));
+        spec
+    }
+
+    fn block(slot: u64, spec: &ChainSpec) -> PubsubMessage<E> {
+        let mut block = BeaconBlock::empty(spec);
+        *block.slot_mut() = Slot::new(slot);
+        PubsubMessage::BeaconBlock(SignedBeaconBlock::from_block(block, Signature::empty()).into())
+    }
+
+    fn sync_message(slot: u64) -> PubsubMessage<E> {
+        PubsubMessage::SyncCommitteeMessage(Box::new((
+            SyncSubnetId::new(0),
+            SyncCommitteeMessage {
+                slot: Slot::new(slot),
+                beacon_block_root: Hash256::zero(),
+                validator_index: 0,
+                signature: Signature::empty(),
+            },
+        )))
+    }
+
+    fn check(
+        message: &PubsubMessage<E>,
+        fork: ForkName,
+        current_slot: u64,
+        finalized_slot: u64,
+    ) -> Result<(), DropReason> {
+        let spec = spec();
+        let fork_context = ForkContext::new::<E>(Slot::new(current_slot), Hash256::zero(), &spec);
+        let digest = fork_context.to_context_bytes(fork).unwrap();
+        pre_check(
+            message,
+            digest,
+            Slot::new(current_slot),
+            Slot::new(finalized_slot),
+            &fork_context,
+            &spec,
+        )
+    }
+
+    #[test]
+    fn accepts_current_messages() {
+        let spec = spec();
+        assert_eq!(check(&block(20, &spec), ForkName::Altair, 20, 8), Ok(()));
+        assert_eq!(check(&sync_message(20), ForkName::Altair, 20, 8), Ok(()));
+    }
+
+    #[test]
+    fn fork_digest_mismatch() {
+        let spec = spec();
+        // A pre-fork block on the post-fork topic, sent around the fork boundary.
+        assert_eq!(check(&block(15, &spec), ForkName::Altair, 16, 0), Ok(()));
+        // A post-fork message on the pre-fork topic long after the fork.
+        assert_eq!(
+            check(&sync_message(30), ForkName::Base, 30, 8),
+            Err(DropReason::ForkDigestMismatch)
+        );
+    }
+
+    #[test]
+    fn future_slot() {
+        let spec = spec();
+        assert_eq!(check(&block(21, &spec), ForkName::Altair, 20, 8), Ok(()));
+        assert_eq!(
+            check(&block(22, &spec), ForkName::Altair, 20, 8),
+            Err(DropReason::FutureSlot)
+        );
+    }
+
+    #[test]
+    fn past_slot() {
+        let spec = spec();
+        assert_eq!(
+            check(&block(24, &spec), ForkName::Altair, 40, 24),
+            Err(DropReason::FinalizedSlot)
+        );
+        assert_eq!(check(&block(25, &spec), ForkName::Altair, 40, 24), Ok(()));
+        assert_eq!(check(&sync_message(24), ForkName::Altair, 40, 24), Ok(()));
+        assert_eq!(
+            check(&sync_message(23), ForkName::Altair, 40, 24),
+            Err(DropReason::ExpiredSlot)
+        );
+    }
+}
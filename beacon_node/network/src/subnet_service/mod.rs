@@ -13,7 +13,8 @@ use tokio::time::Instant;
 use beacon_chain::{BeaconChain, BeaconChainTypes};
 use delay_map::HashSetDelay;
 use futures::prelude::*;
-use lighthouse_network::{discv5::enr::NodeId, NetworkConfig, Subnet, SubnetDiscovery};
+use lighthouse_network::types::{SubnetSubscription, SubscriptionReason};
+use lighthouse_network::{NetworkConfig, NetworkGlobals, Subnet, SubnetDiscovery, SubnetStrategy};
 use slog::{debug, error, o, warn};
 use slot_clock::SlotClock;
 use types::{
@@ -78,6 +79,9 @@ pub struct SubnetService<T: BeaconChainTypes> {
     /// A reference to the beacon chain to process received attestations.
     pub(crate) beacon_chain: Arc<BeaconChain<T>>,
 
+    /// Network globals, used to record why we are subscribed to each subnet.
+    network_globals: Arc<NetworkGlobals<T::EthSpec>>,
+
     /// Subnets we are currently subscribed to as short lived subscriptions.
     ///
     /// Once they expire, we unsubscribe from these.
@@ -105,9 +109,19 @@ pub struct SubnetService<T: BeaconChainTypes> {
     /// We are always subscribed to all subnets.
     subscribe_all_subnets: bool,
 
+    /// How to subscribe to subnets for validator duties.
+    subnet_strategy: SubnetStrategy,
+
+    /// The number of slots after a duty for which a subscription is kept with
+    /// `SubnetStrategy::Aggressive`.
+    aggressive_subscription_slots: u64,
+
     /// Whether this node is a block proposer-only node.
     proposer_only: bool,
 
+    /// Whether this node only subscribes to the gossip required to follow the head of the chain.
+    rpc_serving_only: bool,
+
     /// The logger for the attestation service.
     log: slog::Logger,
 }
@@ -118,7 +132,7 @@ impl<T: BeaconChainTypes> SubnetService<T> {
     /// Establish the service based on the passed configuration.
     pub fn new(
         beacon_chain: Arc<BeaconChain<T>>,
-        node_id: NodeId,
+        network_globals: Arc<NetworkGlobals<T::EthSpec>>,
         config: &NetworkConfig,
         log: &slog::Logger,
     ) -> Self {
@@ -126,14 +140,27 @@ impl<T: BeaconChainTypes> SubnetService<T> {
 
         let slot_duration = beacon_chain.slot_clock.slot_duration();
 
-        if config.subscribe_all_subnets {
+        if config.rpc_serving_only {
+            slog::info!(
+                log,
+                "Not subscribing to attestation or sync committee subnets"
+            );
+        } else if config.subscribe_all_subnets {
             slog::info!(log, "Subscribing to all subnets");
+        } else if config.subnet_strategy == SubnetStrategy::Aggressive {
+            slog::info!(
+                log,
+                "Subscribing to the subnets of all validator duties";
+                "epochs_after_duty" => config.aggressive_subnet_epochs,
+            );
         }
 
         // Build the list of known permanent subscriptions, so that we know not to subscribe or
         // discover them.
         let mut permanent_attestation_subscriptions = HashSet::default();
-        if config.subscribe_all_subnets {
+        if config.rpc_serving_only {
+            // Attestations aren't required to follow the head, so no subnets are subscribed to.
+        } else if config.subscribe_all_subnets {
             // We are subscribed to all subnets, set all the bits to true.
             for index in 0..beacon_chain.spec.attestation_subnet_count {
                 permanent_attestation_subscriptions
@@ -142,12 +169,23 @@ impl<T: BeaconChainTypes> SubnetService<T> {
         } else {
             // Not subscribed to all subnets, so just calculate the required subnets from the node
             // id.
+            let node_id = network_globals.local_enr().node_id();
             for subnet_id in
                 SubnetId::compute_attestation_subnets(node_id.raw(), &beacon_chain.spec)
             {
                 permanent_attestation_subscriptions.insert(Subnet::Attestation(subnet_id));
             }
         }
+        let permanent_reason = if config.subscribe_all_subnets {
+            SubscriptionReason::AllSubnets
+        } else {
+            SubscriptionReason::Backbone
+        };
+        network_globals.subnet_subscriptions.write().extend(
+            permanent_attestation_subscriptions
+                .iter()
+                .map(|subnet| (*subnet, SubnetSubscription::long_lived(permanent_reason))),
+        );
 
         // Set up the sync committee subscriptions
         let spec = &beacon_chain.spec;
@@ -164,7 +202,7 @@ impl<T: BeaconChainTypes> SubnetService<T> {
         let mut events = VecDeque::with_capacity(10);
 
         // Queue discovery queries for the permanent attestation subnets
-        if !config.disable_discovery {
+        if !config.disable_discovery && !permanent_attestation_subscriptions.is_empty() {
             events.push_back(SubnetServiceMessage::DiscoverPeers(
                 permanent_attestation_subscriptions
                     .iter()
@@ -186,6 +224,7 @@ impl<T: BeaconChainTypes> SubnetService<T> {
         SubnetService {
             events,
             beacon_chain,
+            network_globals,
             subscriptions: HashSetDelay::new(default_sync_committee_duration),
             permanent_attestation_subscriptions,
             scheduled_subscriptions: HashSetDelay::default(),
@@ -193,7 +232,12 @@ impl<T: BeaconChainTypes> SubnetService<T> {
             waker: None,
             discovery_disabled: config.disable_discovery,
             subscribe_all_subnets: config.subscribe_all_subnets,
+            subnet_strategy: config.subnet_strategy,
+            aggressive_subscription_slots: config
+                .aggressive_subnet_epochs
+                .saturating_mul(T::EthSpec::slots_per_epoch()),
             proposer_only: config.proposer_only,
+            rpc_serving_only: config.rpc_serving_only,
             log,
         }
     }
@@ -228,8 +272,9 @@ impl<T: BeaconChainTypes> SubnetService<T> {
     /// This returns a result simply for the ergonomics of using ?. The result can be
     /// safely dropped.
     pub fn validator_subscriptions(&mut self, subscriptions: impl Iterator<Item = Subscription>) {
-        // If the node is in a proposer-only state, we ignore all subnet subscriptions.
-        if self.proposer_only {
+        // If the node is in a proposer-only or RPC serving only state, we ignore all subnet
+        // subscriptions.
+        if self.proposer_only || self.rpc_serving_only {
             return;
         }
 
@@ -286,6 +331,13 @@ impl<T: BeaconChainTypes> SubnetService<T> {
                                 "error" => e,
                             );
                         }
+                    } else if self.subnet_strategy == SubnetStrategy::Aggressive {
+                        if let Err(e) = self.subscribe_to_subnet_in_advance(exact_subnet) {
+                            warn!(self.log,
+                                "Subscription to subnet error";
+                                "error" => e,
+                            );
+                        }
                     }
                 }
                 Subscription::SyncCommittee(subscription) => {
@@ -380,6 +432,23 @@ impl<T: BeaconChainTypes> SubnetService<T> {
             .unwrap_or(true)
     }
 
+    /// Checks if a sync committee message on `subnet_id` is required by a local validator.
+    ///
+    /// Only members of a sync subcommittee may aggregate its messages, so this is `true` whilst a
+    /// local validator's subscription to the subnet is active.
+    pub fn should_process_sync_committee_message(&self, subnet_id: SyncSubnetId) -> bool {
+        if self.proposer_only {
+            return false;
+        }
+        // Subscriptions aren't tracked when importing all attestations or subscribing to all
+        // subnets, so assume every message is required.
+        if self.aggregate_validators_on_subnet.is_none() || self.subscribe_all_subnets {
+            return true;
+        }
+        self.subscriptions
+            .contains_key(&Subnet::SyncCommittee(subnet_id))
+    }
+
     /* Internal private functions */
 
     /// Adds an event to the event queue and notifies that this service is ready to be polled
@@ -479,7 +548,9 @@ impl<T: BeaconChainTypes> SubnetService<T> {
 
         // If the subscription should be done in the future, schedule it. Otherwise subscribe
         // immediately.
-        if time_to_subscription_start.is_zero() {
+        if self.subnet_strategy == SubnetStrategy::Aggressive {
+            self.subscribe_to_subnet_in_advance(ExactSubnet { subnet, slot })?;
+        } else if time_to_subscription_start.is_zero() {
             // This is a current or past slot, we subscribe immediately.
             self.subscribe_to_subnet_immediately(subnet, slot + 1)?;
         } else {
@@ -493,6 +564,26 @@ impl<T: BeaconChainTypes> SubnetService<T> {
         Ok(())
     }
 
+    /// Subscribes to the subnet immediately and keeps the subscription until
+    /// `aggressive_subscription_slots` after the duty at `slot`.
+    ///
+    /// Duties on the same subnet within that window extend the existing subscription rather than
+    /// causing the node to unsubscribe and resubscribe.
+    fn subscribe_to_subnet_in_advance(
+        &mut self,
+        ExactSubnet { subnet, slot }: ExactSubnet,
+    ) -> Result<(), &'static str> {
+        if self.subscribe_all_subnets || self.permanent_attestation_subscriptions.contains(&subnet)
+        {
+            return Ok(());
+        }
+
+        let end_slot = slot
+            .saturating_add(1u64)
+            .saturating_add(self.aggressive_subscription_slots);
+        self.subscribe_to_subnet_immediately(subnet, end_slot)
+    }
+
     /// Adds a subscription event to the sync subnet.
     fn subscribe_to_sync_subnet(
         &mut self,
@@ -517,11 +608,13 @@ impl<T: BeaconChainTypes> SubnetService<T> {
             if duration_to_unsubscribe > current_duration_to_unsubscribe {
                 self.subscriptions
                     .update_timeout(&subnet, duration_to_unsubscribe);
+                self.record_duty_subscription(subnet, slot_required_until);
             }
         } else {
             // We have not subscribed before, so subscribe
             self.subscriptions
                 .insert_at(subnet, duration_to_unsubscribe);
+            self.record_duty_subscription(subnet, slot_required_until);
             // We are not currently subscribed and have no waiting subscription, create one
             debug!(self.log, "Subscribing to subnet"; "subnet" => ?subnet, "until" => ?slot_required_until);
             self.events
@@ -573,6 +666,7 @@ impl<T: BeaconChainTypes> SubnetService<T> {
                 {
                     self.subscriptions
                         .update_timeout(&subnet, time_to_subscription_end);
+                    self.record_duty_subscription(subnet, end_slot);
                 }
             }
             None => {
@@ -580,6 +674,7 @@ impl<T: BeaconChainTypes> SubnetService<T> {
                 // notification.
                 self.subscriptions
                     .insert_at(subnet, time_to_subscription_end);
+                self.record_duty_subscription(subnet, end_slot);
 
                 // Inform of the subscription.
                 debug!(self.log, "Subscribing to subnet";
@@ -592,12 +687,27 @@ impl<T: BeaconChainTypes> SubnetService<T> {
         Ok(())
     }
 
+    /// Records that we are subscribed to `subnet` for a validator duty until `until_slot`.
+    fn record_duty_subscription(&self, subnet: Subnet, until_slot: Slot) {
+        self.network_globals.subnet_subscriptions.write().insert(
+            subnet,
+            SubnetSubscription {
+                reason: SubscriptionReason::Duty,
+                until_slot: Some(until_slot),
+            },
+        );
+    }
+
     // Unsubscribes from a subnet that was removed.
     fn handle_removed_subnet(&mut self, subnet: Subnet) {
         if !self.subscriptions.contains_key(&subnet) {
             // Subscription no longer exists as short lived subnet
             debug!(self.log, "Unsubscribing from subnet"; "subnet" => ?subnet);
             self.queue_event(SubnetServiceMessage::Unsubscribe(subnet));
+            self.network_globals
+                .subnet_subscriptions
+                .write()
+                .remove(&subnet);
 
             // If this is a sync subnet, we need to remove it from our ENR.
             if let Subnet::SyncCommittee(sync_subnet_id) = subnet {
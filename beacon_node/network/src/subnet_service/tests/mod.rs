@@ -6,7 +6,7 @@ use beacon_chain::{
     BeaconChain,
 };
 use genesis::{generate_deterministic_keypairs, interop_genesis_state, DEFAULT_ETH1_BLOCK_HASH};
-use lighthouse_network::NetworkConfig;
+use lighthouse_network::{NetworkConfig, NetworkGlobals};
 use logging::test_logger;
 use slog::{o, Drain, Logger};
 use sloggers::{null::NullLoggerBuilder, Build};
@@ -119,17 +119,22 @@ fn get_logger(log_level: Option<slog::Level>) -> Logger {
 static CHAIN: LazyLock<TestBeaconChain> = LazyLock::new(TestBeaconChain::new_with_system_clock);
 
 fn get_subnet_service() -> SubnetService<TestBeaconChainType> {
+    get_subnet_service_with_config(NetworkConfig::default())
+}
+
+fn get_subnet_service_with_config(config: NetworkConfig) -> SubnetService<TestBeaconChainType> {
     let log = test_logger();
-    let config = NetworkConfig::default();
 
     let beacon_chain = CHAIN.chain.clone();
 
-    SubnetService::new(
-        beacon_chain,
-        lighthouse_network::discv5::enr::NodeId::random(),
-        &config,
+    let network_globals = Arc::new(NetworkGlobals::new_test_globals(
+        vec![],
         &log,
-    )
+        Arc::new(config.clone()),
+        beacon_chain.spec.clone(),
+    ));
+
+    SubnetService::new(beacon_chain, network_globals, &config, &log)
 }
 
 // gets a number of events from the subscription service, or returns none if it times out after a number
@@ -258,6 +263,20 @@ mod test {
         // submit the subscriptions
         subnet_service.validator_subscriptions(subscriptions.into_iter());
 
+        // the subscription is recorded as a duty lasting until the end of the subscription slot
+        assert_eq!(
+            subnet_service
+                .network_globals
+                .subnet_subscriptions
+                .read()
+                .get(&subnet)
+                .copied(),
+            Some(SubnetSubscription {
+                reason: SubscriptionReason::Duty,
+                until_slot: Some(subscription_slot + 1),
+            })
+        );
+
         // not enough time for peer discovery, just subscribe, unsubscribe
         let expected = [
             SubnetServiceMessage::Subscribe(subnet),
@@ -279,6 +298,11 @@ mod test {
             subnets_per_node
         );
         assert_eq!(subnet_service.subscriptions().count(), 0);
+        assert!(!subnet_service
+            .network_globals
+            .subnet_subscriptions
+            .read()
+            .contains_key(&subnet));
     }
 
     /// Test to verify that we are not unsubscribing to a subnet before a required subscription.
@@ -366,6 +390,77 @@ mod test {
         assert_eq!(subnet_service.subscriptions().count(), 0);
     }
 
+    #[tokio::test]
+    async fn aggressive_strategy_subscribes_in_advance() {
+        let committee_index = 1;
+        let config = NetworkConfig {
+            subnet_strategy: SubnetStrategy::Aggressive,
+            ..NetworkConfig::default()
+        };
+        let aggressive_subnet_epochs = config.aggressive_subnet_epochs;
+
+        let mut subnet_service = get_subnet_service_with_config(config);
+        let _events = get_events(&mut subnet_service, None, 1).await;
+
+        let current_slot = subnet_service
+            .beacon_chain
+            .slot_clock
+            .now()
+            .expect("Could not get current slot");
+
+        // Generate a subnet for a duty well in the future that isn't a permanent subnet.
+        let subscription_slot = current_slot + 10;
+        let mut committee_count = 1;
+        let subnet = loop {
+            let subnet = Subnet::Attestation(
+                SubnetId::compute_subnet::<MainnetEthSpec>(
+                    subscription_slot,
+                    committee_index,
+                    committee_count,
+                    &subnet_service.beacon_chain.spec,
+                )
+                .unwrap(),
+            );
+            if !subnet_service
+                .permanent_subscriptions()
+                .any(|x| *x == subnet)
+            {
+                break subnet;
+            }
+            committee_count += 1;
+        };
+
+        // Even though the validator is not an aggregator, the subnet is subscribed to immediately.
+        let subscriptions = vec![get_subscription(
+            committee_index,
+            subscription_slot,
+            committee_count,
+            false,
+        )];
+        subnet_service.validator_subscriptions(subscriptions.into_iter());
+        assert!(subnet_service.is_subscribed(&subnet));
+
+        // The subscription is kept for the configured number of epochs after the duty.
+        let until_slot =
+            subscription_slot + 1 + aggressive_subnet_epochs * MainnetEthSpec::slots_per_epoch();
+        assert_eq!(
+            subnet_service
+                .network_globals
+                .subnet_subscriptions
+                .read()
+                .get(&subnet)
+                .copied(),
+            Some(SubnetSubscription {
+                reason: SubscriptionReason::Duty,
+                until_slot: Some(until_slot),
+            })
+        );
+
+        let events = get_events(&mut subnet_service, None, 1).await;
+        assert!(events.contains(&SubnetServiceMessage::Subscribe(subnet)));
+        assert!(!events.contains(&SubnetServiceMessage::Unsubscribe(subnet)));
+    }
+
     #[tokio::test]
     async fn subscribe_all_subnets() {
         let attestation_subnet_count = MainnetEthSpec::default_spec().attestation_subnet_count;
@@ -672,6 +767,9 @@ mod test {
         .unwrap();
         let subnet_id = subnet_ids.iter().next().unwrap();
 
+        // Messages on the subnet are required whilst the validator is subscribed.
+        assert!(subnet_service.should_process_sync_committee_message(*subnet_id));
+
         // Note: the unsubscription event takes 2 epochs (8 * 2 * 0.4 secs = 3.2 secs)
         let events = get_events(
             &mut subnet_service,
@@ -697,6 +795,43 @@ mod test {
 
         // Should be unsubscribed at the end.
         assert_eq!(subnet_service.subscriptions().count(), 0);
+        assert!(!subnet_service.should_process_sync_committee_message(*subnet_id));
+    }
+
+    #[tokio::test]
+    async fn rpc_serving_only_does_not_subscribe() {
+        let config = NetworkConfig {
+            rpc_serving_only: true,
+            ..NetworkConfig::default()
+        };
+        let mut subnet_service = get_subnet_service_with_config(config);
+        assert_eq!(subnet_service.permanent_subscriptions().count(), 0);
+
+        let current_slot = subnet_service
+            .beacon_chain
+            .slot_clock
+            .now()
+            .expect("Could not get current slot");
+        let subscriptions = get_subscriptions(4, current_slot + 1, 1, true)
+            .into_iter()
+            .chain(std::iter::once(Subscription::SyncCommittee(
+                SyncCommitteeSubscription {
+                    validator_index: 1,
+                    sync_committee_indices: vec![1],
+                    until_epoch: Epoch::new(1),
+                },
+            )));
+        subnet_service.validator_subscriptions(subscriptions);
+
+        // No subnets are subscribed to, advertised or discovered.
+        let events = get_events(&mut subnet_service, None, 3).await;
+        assert!(events.is_empty(), "{events:?}");
+        assert_eq!(subnet_service.subscriptions().count(), 0);
+        assert!(subnet_service
+            .network_globals
+            .subnet_subscriptions
+            .read()
+            .is_empty());
     }
 
     #[tokio::test]
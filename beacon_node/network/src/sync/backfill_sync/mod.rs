@@ -18,15 +18,17 @@ use crate::sync::range_sync::{
 use beacon_chain::block_verification_types::RpcBlock;
 use beacon_chain::{BeaconChain, BeaconChainTypes};
 use lighthouse_network::service::api_types::Id;
-use lighthouse_network::types::{BackFillState, NetworkGlobals};
+use lighthouse_network::types::{BackFillState, NetworkGlobals, BACKFILL_THROTTLE_WINDOW};
 use lighthouse_network::{PeerAction, PeerId};
 use rand::seq::SliceRandom;
 use slog::{crit, debug, error, info, warn};
+use std::cmp::Reverse;
 use std::collections::{
     btree_map::{BTreeMap, Entry},
-    HashMap, HashSet,
+    HashMap, HashSet, VecDeque,
 };
 use std::sync::Arc;
+use std::time::Instant;
 use types::{Epoch, EthSpec};
 
 /// Blocks are downloaded in batches from peers. This constant specifies how many epochs worth of
@@ -127,6 +129,9 @@ pub struct BackFillSync<T: BeaconChainTypes> {
     /// The current processing batch, if any.
     current_processing_batch: Option<BatchId>,
 
+    /// Limits the rate at which batches are sent for processing.
+    throttle: BatchThrottle,
+
     /// Batches validated by this chain.
     validated_batches: u64,
 
@@ -182,6 +187,7 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
             to_be_downloaded: current_start,
             network_globals,
             current_processing_batch: None,
+            throttle: BatchThrottle::default(),
             validated_batches: 0,
             participating_peers: HashSet::new(),
             restart_failed_sync: false,
@@ -475,6 +481,7 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
         self.to_be_downloaded = self.current_start;
         self.last_batch_downloaded = false;
         self.current_processing_batch = None;
+        self.throttle.release();
 
         // NOTE: Lets keep validated_batches for posterity
 
@@ -522,6 +529,9 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
 
         let process_id = ChainSegmentProcessId::BackSyncBatchId(batch_id);
         self.current_processing_batch = Some(batch_id);
+        if !blocks.is_empty() {
+            self.throttle.on_batch_processed(Instant::now());
+        }
 
         if let Err(e) = network
             .beacon_processor()
@@ -700,6 +710,9 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
             let state = batch.state();
             match state {
                 BatchState::AwaitingProcessing(..) => {
+                    if self.is_throttled() {
+                        return Ok(ProcessResult::Successful);
+                    }
                     return self.process_batch(network, self.processing_target);
                 }
                 BatchState::Downloading(..) => {
@@ -741,6 +754,38 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
         Ok(ProcessResult::Successful)
     }
 
+    /// Processes the next batch if it was held back by the backfill throttle, which may have since
+    /// allowed it.
+    #[must_use = "A failure here indicates the backfill sync has failed and the global sync state should be updated"]
+    pub fn resume_throttled(
+        &mut self,
+        network: &mut SyncNetworkContext<T>,
+    ) -> Result<ProcessResult, BackFillError> {
+        if !self.throttle.is_holding_back() {
+            return Ok(ProcessResult::Successful);
+        }
+        self.process_completed_batches(network)
+    }
+
+    /// Returns `true` if processing another batch now would exceed the backfill throttle.
+    ///
+    /// Whilst throttled, downloaded batches wait in the batch buffer, which in turn stops further
+    /// batches from being downloaded.
+    fn is_throttled(&mut self) -> bool {
+        let limit = self
+            .network_globals
+            .backfill_throttle
+            .read()
+            .batches_per_minute;
+        let was_throttled = self.throttle.is_holding_back();
+        let throttled = self.throttle.check(Instant::now(), limit);
+        if throttled && !was_throttled {
+            debug!(self.log, "Backfill sync throttled";
+                "batches_per_minute" => limit, "processing_target" => self.processing_target);
+        }
+        throttled
+    }
+
     /// Removes any batches previous to the given `validating_epoch` and updates the current
     /// boundaries of the chain.
     ///
@@ -1044,6 +1089,9 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
             .collect::<Vec<_>>();
 
         idle_peers.shuffle(&mut rng);
+        // peers are popped from the back, so place the lowest latency peers last
+        let latency_keys = network.peer_latency_keys(&idle_peers);
+        idle_peers.sort_by_key(|peer| Reverse(latency_keys[peer]));
 
         while let Some(peer) = idle_peers.pop() {
             if let Some(batch_id) = self.include_next_batch(network) {
@@ -1171,3 +1219,106 @@ enum ResetEpochError {
     /// The chain has already completed.
     SyncCompleted,
 }
+
+/// Tracks the batches recently sent for processing in order to limit their rate.
+#[derive(Default)]
+struct BatchThrottle {
+    /// The times at which non-empty batches were sent for processing within the last
+    /// `BACKFILL_THROTTLE_WINDOW`.
+    recently_processed: VecDeque<Instant>,
+    /// Whether a batch is being held back from processing by the throttle.
+    holding_back: bool,
+}
+
+impl BatchThrottle {
+    /// Records that a non-empty batch was sent for processing at `now`.
+    fn on_batch_processed(&mut self, now: Instant) {
+        self.recently_processed.push_back(now);
+    }
+
+    /// Returns `true` if processing another batch at `now` would exceed `limit` batches per
+    /// `BACKFILL_THROTTLE_WINDOW`, in which case the batch is held back until the next check.
+    fn check(&mut self, now: Instant, limit: Option<u64>) -> bool {
+        while self
+            .recently_processed
+            .front()
+            .is_some_and(|sent| now.saturating_duration_since(*sent) >= BACKFILL_THROTTLE_WINDOW)
+        {
+            self.recently_processed.pop_front();
+        }
+        self.holding_back =
+            limit.is_some_and(|limit| self.recently_processed.len() as u64 >= limit);
+        self.holding_back
+    }
+
+    /// Returns `true` if the last check held back a batch.
+    fn is_holding_back(&self) -> bool {
+        self.holding_back
+    }
+
+    /// Forgets any batch being held back, e.g. when the sync is restarted.
+    fn release(&mut self) {
+        self.holding_back = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn throttle_holds_back_batches_over_the_limit() {
+        let mut throttle = BatchThrottle::default();
+        let start = Instant::now();
+
+        for i in 0..3 {
+            assert!(!throttle.check(start, Some(3)));
+            throttle.on_batch_processed(start + Duration::from_secs(i));
+        }
+        assert!(throttle.check(start + Duration::from_secs(3), Some(3)));
+        assert!(throttle.is_holding_back());
+
+        // Raising the limit at runtime releases the held back batch on the next check.
+        assert!(!throttle.check(start + Duration::from_secs(3), Some(4)));
+        assert!(!throttle.is_holding_back());
+    }
+
+    #[test]
+    fn throttle_resumes_once_batches_leave_the_window() {
+        let mut throttle = BatchThrottle::default();
+        let start = Instant::now();
+        throttle.on_batch_processed(start);
+        throttle.on_batch_processed(start + Duration::from_secs(10));
+
+        assert!(throttle.check(start + Duration::from_secs(30), Some(2)));
+        // Only the first batch has left the window.
+        assert!(!throttle.check(start + BACKFILL_THROTTLE_WINDOW, Some(2)));
+        assert!(throttle.check(start + BACKFILL_THROTTLE_WINDOW, Some(1)));
+        assert!(!throttle.check(
+            start + BACKFILL_THROTTLE_WINDOW + Duration::from_secs(10),
+            Some(1)
+        ));
+    }
+
+    #[test]
+    fn throttle_without_limit_never_holds_back() {
+        let mut throttle = BatchThrottle::default();
+        let start = Instant::now();
+        for _ in 0..100 {
+            throttle.on_batch_processed(start);
+        }
+        assert!(!throttle.check(start, None));
+        assert!(!throttle.is_holding_back());
+    }
+
+    #[test]
+    fn throttle_with_zero_limit_pauses_processing() {
+        let mut throttle = BatchThrottle::default();
+        assert!(throttle.check(Instant::now(), Some(0)));
+
+        // Restarting the sync forgets the held back batch until it is checked again.
+        throttle.release();
+        assert!(!throttle.is_holding_back());
+    }
+}
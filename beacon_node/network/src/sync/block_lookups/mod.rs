@@ -23,7 +23,7 @@
 use self::parent_chain::{compute_parent_chains, NodeChain};
 pub use self::single_block_lookup::DownloadResult;
 use self::single_block_lookup::{LookupRequestError, LookupResult, SingleBlockLookup};
-use super::manager::{BlockProcessType, BlockProcessingResult, SLOT_IMPORT_TOLERANCE};
+use super::manager::{BlockProcessType, BlockProcessingResult};
 use super::network_context::{PeerGroup, RpcResponseError, SyncNetworkContext};
 use crate::metrics;
 use crate::sync::block_lookups::common::ResponseType;
@@ -51,21 +51,13 @@ pub mod common;
 pub mod parent_chain;
 mod single_block_lookup;
 
-/// The maximum depth we will search for a parent block. In principle we should have sync'd any
-/// canonical chain to its head once the peer connects. A chain should not appear where it's depth
-/// is further back than the most recent head slot.
-///
-/// Have the same value as range's sync tolerance to consider a peer synced. Once sync lookup
-/// reaches the maximum depth it will force trigger range sync.
-pub(crate) const PARENT_DEPTH_TOLERANCE: usize = SLOT_IMPORT_TOLERANCE;
-
 const FAILED_CHAINS_CACHE_EXPIRY_SECONDS: u64 = 60;
 pub const SINGLE_BLOCK_LOOKUP_MAX_ATTEMPTS: u8 = 4;
 
 /// Maximum time we allow a lookup to exist before assuming it is stuck and will never make
-/// progress. Assume the worse case processing time per block component set * times max depth.
-/// 15 * 2 * 32 = 16 minutes.
-const LOOKUP_MAX_DURATION_STUCK_SECS: u64 = 15 * PARENT_DEPTH_TOLERANCE as u64;
+/// progress, per block of the maximum parent depth. Assume the worse case processing time per
+/// block component set * times max depth. 15 * 32 = 8 minutes with the default depth.
+const LOOKUP_MAX_DURATION_STUCK_SECS_PER_PARENT: u64 = 15;
 /// The most common case of child-lookup without peers is receiving block components before the
 /// attestation deadline when the node is lagging behind. Once peers start attesting for the child
 /// lookup at most after 4 seconds, the lookup should gain peers.
@@ -116,6 +108,14 @@ pub struct BlockLookups<T: BeaconChainTypes> {
     // TODO: Why not index lookups by block_root?
     single_block_lookups: FnvHashMap<SingleLookupId, SingleBlockLookup<T>>,
 
+    /// The maximum depth we will search for a parent block. In principle we should have sync'd any
+    /// canonical chain to its head once the peer connects. A chain should not appear where it's
+    /// depth is further back than the most recent head slot.
+    ///
+    /// Defaults to range's sync tolerance to consider a peer synced. Once sync lookup reaches the
+    /// maximum depth it will force trigger range sync.
+    max_parent_depth: usize,
+
     /// The logger for the import manager.
     log: Logger,
 }
@@ -129,12 +129,13 @@ use lighthouse_network::service::api_types::Id;
 pub(crate) type BlockLookupSummary = (Id, Hash256, Option<Hash256>, Vec<PeerId>);
 
 impl<T: BeaconChainTypes> BlockLookups<T> {
-    pub fn new(log: Logger) -> Self {
+    pub fn new(max_parent_depth: usize, log: Logger) -> Self {
         Self {
             failed_chains: LRUTimeCache::new(Duration::from_secs(
                 FAILED_CHAINS_CACHE_EXPIRY_SECONDS,
             )),
             single_block_lookups: Default::default(),
+            max_parent_depth,
             log,
         }
     }
@@ -242,9 +243,12 @@ impl<T: BeaconChainTypes> BlockLookups<T> {
             let trigger_is_chain_tip = parent_chain.tip == child_block_root_trigger;
 
             if (block_would_extend_chain || trigger_is_chain_tip)
-                && parent_chain.len() >= PARENT_DEPTH_TOLERANCE
+                && parent_chain.len() >= self.max_parent_depth
             {
-                debug!(self.log, "Parent lookup chain too long"; "block_root" => ?block_root_to_search);
+                debug!(self.log, "Parent lookup chain too long";
+                    "block_root" => ?block_root_to_search,
+                    "max_parent_depth" => self.max_parent_depth,
+                );
 
                 // Searching for this parent would extend a parent chain over the max
                 // Insert the tip only to failed chains
@@ -252,7 +256,7 @@ impl<T: BeaconChainTypes> BlockLookups<T> {
 
                 // Note: Drop only the chain that's too long until it merges with another chain
                 // that's not too long. Consider this attack: there's a chain of valid unknown
-                // blocks A -> B. A malicious peer builds `PARENT_DEPTH_TOLERANCE` garbage
+                // blocks A -> B. A malicious peer builds `max_parent_depth` garbage
                 // blocks on top of A forming A -> C. The malicious peer forces us to fetch C
                 // from it, which will result in parent A hitting the chain_too_long error. Then
                 // the valid chain A -> B is dropped too.
@@ -282,9 +286,22 @@ impl<T: BeaconChainTypes> BlockLookups<T> {
                         .iter()
                         .find(|(_, l)| l.block_root() == parent_chain_tip)
                     {
+                        // The tip's block may not have been downloaded, e.g. if its lookup was
+                        // created from a gossip blob and the block has not been served yet, even
+                        // if the tip has since been attested to. Rather than letting range sync
+                        // assume the local head slot, which may not cover any of the unknown
+                        // ancestors, fall back to the most recent block of the chain which has
+                        // been downloaded.
+                        let head_slot = tip_lookup.peek_downloaded_block_slot().or_else(|| {
+                            self.single_block_lookups
+                                .values()
+                                .filter(|l| parent_chain.chain.contains(&l.block_root()))
+                                .filter_map(|l| l.peek_downloaded_block_slot())
+                                .max()
+                        });
                         cx.send_sync_message(SyncMessage::AddPeersForceRangeSync {
                             peers: lookup.all_peers().copied().collect(),
-                            head_slot: tip_lookup.peek_downloaded_block_slot(),
+                            head_slot,
                             head_root: parent_chain_tip,
                         });
                     } else {
@@ -631,6 +648,20 @@ impl<T: BeaconChainTypes> BlockLookups<T> {
                         );
                         Action::Drop
                     }
+                    BlockError::ImportTimeout(timeout) => {
+                        // The import was cancelled before the block could be verified, most likely
+                        // because the execution layer is unresponsive. Do not downscore the peer,
+                        // but count the failure so that a persistently hung import is eventually
+                        // dropped.
+                        warn!(
+                            self.log,
+                            "Lookup block import timed out";
+                            "block_root" => ?block_root,
+                            "timeout" => ?timeout
+                        );
+                        request_state.on_processing_failure()?;
+                        Action::Retry
+                    }
                     BlockError::AvailabilityCheck(e)
                         if e.category() == AvailabilityCheckErrorCategory::Internal =>
                     {
@@ -803,6 +834,16 @@ impl<T: BeaconChainTypes> BlockLookups<T> {
 
     /* Helper functions */
 
+    /// Returns the block root of every lookup with peers, along with the peers which claim to
+    /// have imported it.
+    pub fn lookups_to_persist(&self) -> Vec<(Hash256, Vec<PeerId>)> {
+        self.single_block_lookups
+            .values()
+            .filter(|lookup| !lookup.has_no_peers())
+            .map(|lookup| (lookup.block_root(), lookup.all_peers().copied().collect()))
+            .collect()
+    }
+
     /// Drops all the single block requests and returns how many requests were dropped.
     pub fn drop_single_block_requests(&mut self) -> usize {
         let requests_to_drop = self.single_block_lookups.len();
@@ -879,9 +920,14 @@ impl<T: BeaconChainTypes> BlockLookups<T> {
     /// - If the original bug is sporadic, it reduces the time a node is stuck from forever to 15 min
     fn drop_stuck_lookups(&mut self) {
         // While loop to find and drop all disjoint trees of potentially stuck lookups.
-        while let Some(stuck_lookup) = self.single_block_lookups.values().find(|lookup| {
-            lookup.elapsed_since_created() > Duration::from_secs(LOOKUP_MAX_DURATION_STUCK_SECS)
-        }) {
+        let max_duration = Duration::from_secs(
+            LOOKUP_MAX_DURATION_STUCK_SECS_PER_PARENT * self.max_parent_depth as u64,
+        );
+        while let Some(stuck_lookup) = self
+            .single_block_lookups
+            .values()
+            .find(|lookup| lookup.elapsed_since_created() > max_duration)
+        {
             let ancestor_stuck_lookup = match self.find_oldest_ancestor_lookup(stuck_lookup) {
                 Ok(lookup) => lookup,
                 Err(e) => {
@@ -7,7 +7,6 @@ use crate::sync::network_context::{
 use beacon_chain::{BeaconChainTypes, BlockProcessStatus};
 use derivative::Derivative;
 use lighthouse_network::service::api_types::Id;
-use rand::seq::IteratorRandom;
 use std::collections::HashSet;
 use std::fmt::Debug;
 use std::sync::Arc;
@@ -283,7 +282,7 @@ impl<T: BeaconChainTypes> SingleBlockLookup<T> {
                 return Err(LookupRequestError::TooManyAttempts { cannot_process });
             }
 
-            let Some(peer_id) = self.use_rand_available_peer() else {
+            let Some(peer_id) = self.use_low_latency_available_peer(cx) else {
                 // Allow lookup to not have any peers and do nothing. This is an optimization to not
                 // lose progress of lookups created from a block with unknown parent before we receive
                 // attestations for said block.
@@ -368,9 +367,9 @@ impl<T: BeaconChainTypes> SingleBlockLookup<T> {
         self.peers.is_empty()
     }
 
-    /// Selects a random peer from available peers if any
-    fn use_rand_available_peer(&mut self) -> Option<PeerId> {
-        self.peers.iter().choose(&mut rand::thread_rng()).copied()
+    /// Selects a random peer amongst the available peers with the lowest latency, if any
+    fn use_low_latency_available_peer(&self, cx: &SyncNetworkContext<T>) -> Option<PeerId> {
+        cx.choose_low_latency_peer(self.peers.iter().copied().collect())
     }
 }
 
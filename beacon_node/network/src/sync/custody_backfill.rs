@@ -0,0 +1,617 @@
+//! Custody backfill sync fetches the data columns of newly custodied subnets.
+//!
+//! When the node is restarted with a higher custody subnet count (e.g. to become a supernode), the
+//! blocks it has already imported are missing the columns of the newly custodied subnets. The
+//! beacon chain records the slot below which columns may be missing in the store's
+//! `DataColumnCustodyInfo`.
+//!
+//! Custody backfill works backwards from that slot one epoch at a time. It requests the missing
+//! columns of each block in the epoch by root from custodial peers, and has them verified and
+//! stored by the beacon processor. Once every block in the epoch has been handled the custody info
+//! is updated, so that custody backfill resumes from the next epoch after a restart. It completes
+//! once it reaches the data availability boundary.
+//!
+//! The columns of a block which repeatedly fail to download or process are skipped so that custody
+//! backfill can make progress, but the stored custody backfill slot is never moved below a skipped
+//! block, so that its columns are fetched again after a restart.
+
+use super::network_context::{
+    DataColumnsByRootSingleBlockRequest, LookupRequestResult, RpcResponseResult, SyncNetworkContext,
+};
+use crate::metrics;
+use beacon_chain::{BeaconChainTypes, HistoricalDataColumnError};
+use fnv::FnvHashMap;
+use lighthouse_network::service::api_types::{
+    CustodyBackfillId, DataColumnsByRootRequestId, DataColumnsByRootRequester,
+};
+use lighthouse_network::types::SyncKind;
+use lighthouse_network::{PeerAction, PeerId};
+use rand::{seq::SliceRandom, thread_rng};
+use slog::{debug, error, info, warn};
+use std::collections::HashSet;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::Instant;
+use store::metadata::DataColumnCustodyInfo;
+use types::data_column_sidecar::ColumnIndex;
+use types::{DataColumnSidecar, Epoch, EthSpec, Hash256, Slot};
+
+/// The number of times the columns of a block may fail to download or process before the block is
+/// skipped.
+const MAX_BLOCK_ATTEMPTS: usize = 5;
+
+/// The state of custody backfill.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CustodyBackfillState {
+    /// The custody info has not been loaded from the store yet.
+    Pending,
+    /// Fetching the missing columns of the blocks in `epoch`.
+    Syncing {
+        epoch: Epoch,
+        custody_info: DataColumnCustodyInfo,
+        /// The custody backfill slot when custody backfill started, to report progress.
+        start_slot: Slot,
+    },
+    /// The columns of all custodied subnets are stored for the retention period.
+    Completed,
+}
+
+pub struct CustodyBackfillSync<T: BeaconChainTypes> {
+    state: CustodyBackfillState,
+    /// The blocks of the current epoch which are missing columns, by block root.
+    blocks: FnvHashMap<Hash256, BlockColumnsRequest<T::EthSpec>>,
+    /// The slot of the latest block whose columns were skipped since the node started.
+    latest_skipped_slot: Option<Slot>,
+    log: slog::Logger,
+    _phantom: PhantomData<T>,
+}
+
+impl<T: BeaconChainTypes> CustodyBackfillSync<T> {
+    pub fn new(log: slog::Logger) -> Self {
+        Self {
+            state: CustodyBackfillState::Pending,
+            blocks: <_>::default(),
+            latest_skipped_slot: None,
+            log,
+            _phantom: PhantomData,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn state(&self) -> &CustodyBackfillState {
+        &self.state
+    }
+
+    /// Start or make progress on custody backfill. Requests which stalled for lack of custodial
+    /// peers are retried.
+    pub fn continue_sync(&mut self, cx: &mut SyncNetworkContext<T>) {
+        if self.state == CustodyBackfillState::Pending {
+            let custody_info = match cx.chain.store.load_data_column_custody_info() {
+                Ok(custody_info) => custody_info,
+                Err(e) => {
+                    error!(self.log, "Unable to load data column custody info"; "error" => ?e);
+                    return;
+                }
+            };
+            let Some((custody_info, start_slot)) = custody_info.and_then(|custody_info| {
+                let start_slot = custody_info.custody_backfill_slot?;
+                Some((custody_info, start_slot))
+            }) else {
+                self.state = CustodyBackfillState::Completed;
+                return;
+            };
+            info!(
+                self.log,
+                "Starting custody backfill";
+                "custody_subnet_count" => custody_info.custody_subnet_count,
+                "custody_backfill_slot" => start_slot,
+            );
+            self.start_epoch(custody_info, start_slot, cx);
+        }
+
+        let block_roots = self.blocks.keys().copied().collect::<Vec<_>>();
+        for block_root in block_roots {
+            self.continue_block(block_root, cx);
+        }
+    }
+
+    /// Start fetching the columns of the epoch prior to the custody backfill slot, skipping epochs
+    /// which are not missing any columns. Completes custody backfill if the data availability
+    /// boundary has been reached.
+    fn start_epoch(
+        &mut self,
+        mut custody_info: DataColumnCustodyInfo,
+        start_slot: Slot,
+        cx: &mut SyncNetworkContext<T>,
+    ) {
+        let slots_per_epoch = T::EthSpec::slots_per_epoch();
+        loop {
+            let Some(backfill_slot) = custody_info.custody_backfill_slot else {
+                self.complete(cx);
+                return;
+            };
+            let epoch = backfill_slot.saturating_sub(1_u64).epoch(slots_per_epoch);
+            if backfill_slot == 0
+                || !cx
+                    .chain
+                    .data_availability_checker
+                    .data_columns_required_for_epoch(epoch)
+            {
+                custody_info.custody_backfill_slot = None;
+                self.store_custody_info(&custody_info, cx);
+                self.complete(cx);
+                return;
+            }
+
+            let blocks = match cx
+                .chain
+                .blocks_missing_custody_columns(epoch, &cx.network_globals().sampling_columns)
+            {
+                Ok(blocks) => blocks,
+                Err(e) => {
+                    // Retried on the next call to `continue_sync`.
+                    error!(self.log, "Unable to determine missing custody columns"; "epoch" => epoch, "error" => ?e);
+                    self.state = CustodyBackfillState::Pending;
+                    return;
+                }
+            };
+
+            if blocks.is_empty() {
+                custody_info.custody_backfill_slot = Some(epoch.start_slot(slots_per_epoch));
+                self.store_custody_info(&custody_info, cx);
+                continue;
+            }
+
+            debug!(
+                self.log,
+                "Starting custody backfill epoch";
+                "epoch" => epoch,
+                "blocks" => blocks.len(),
+            );
+            self.blocks = blocks
+                .into_iter()
+                .map(|(block_root, slot, columns)| {
+                    (block_root, BlockColumnsRequest::new(slot, columns))
+                })
+                .collect();
+            self.state = CustodyBackfillState::Syncing {
+                epoch,
+                custody_info,
+                start_slot,
+            };
+            self.observe_progress(cx);
+            return;
+        }
+    }
+
+    /// Insert the response to a `data_columns_by_root` request, then make progress on the block.
+    pub fn on_data_columns_downloaded(
+        &mut self,
+        id: CustodyBackfillId,
+        req_id: DataColumnsByRootRequestId,
+        peer_id: PeerId,
+        resp: RpcResponseResult<Vec<Arc<DataColumnSidecar<T::EthSpec>>>>,
+        cx: &mut SyncNetworkContext<T>,
+    ) {
+        let request = if self.is_active_epoch(id.epoch) {
+            self.blocks.get_mut(&id.block_root)
+        } else {
+            None
+        };
+        let Some(request) = request else {
+            debug!(self.log, "Custody backfill columns downloaded for unknown request"; "id" => ?id);
+            return;
+        };
+        let Some(indices) = request.downloading.remove(&req_id) else {
+            debug!(self.log, "Custody backfill response for unknown request"; "id" => ?id, "req_id" => %req_id);
+            return;
+        };
+
+        match resp {
+            Ok((data_columns, _)) => {
+                for index in indices {
+                    match data_columns.iter().find(|column| column.index == index) {
+                        Some(data_column) => {
+                            request
+                                .downloaded
+                                .insert(index, (data_column.clone(), peer_id));
+                        }
+                        None => {
+                            // The peer does not have the column, request it from another peer.
+                            request.failed_peers.insert((index, peer_id));
+                        }
+                    }
+                }
+            }
+            Err(e) => {
+                debug!(
+                    self.log,
+                    "Custody backfill download error";
+                    "block_root" => ?id.block_root,
+                    "peer" => %peer_id,
+                    "error" => ?e,
+                );
+                for index in indices {
+                    request.failed_peers.insert((index, peer_id));
+                }
+            }
+        }
+
+        self.continue_block(id.block_root, cx);
+    }
+
+    /// Handle the result of storing the columns of a block, then make progress on the epoch.
+    pub fn on_columns_processed(
+        &mut self,
+        id: CustodyBackfillId,
+        result: Result<usize, HistoricalDataColumnError>,
+        cx: &mut SyncNetworkContext<T>,
+    ) {
+        let request = if self.is_active_epoch(id.epoch) {
+            self.blocks.get_mut(&id.block_root)
+        } else {
+            None
+        };
+        let Some(request) = request else {
+            debug!(self.log, "Custody backfill columns processed for unknown request"; "id" => ?id);
+            return;
+        };
+        request.processing = false;
+        let slot = request.slot;
+
+        match result {
+            Ok(imported_columns) => {
+                metrics::inc_counter_by(
+                    &metrics::SYNC_CUSTODY_BACKFILL_COLUMNS_IMPORTED,
+                    imported_columns as u64,
+                );
+                cx.network_globals().sync_progress.write().record_import(
+                    SyncKind::CustodyBackfill,
+                    0,
+                    0,
+                    imported_columns,
+                );
+                self.blocks.remove(&id.block_root);
+                if self.blocks.is_empty() {
+                    self.on_epoch_completed(cx);
+                }
+            }
+            Err(
+                e @ (HistoricalDataColumnError::UnknownBlock(_)
+                | HistoricalDataColumnError::StoreError(_)),
+            ) => {
+                // Not the fault of the peers, nor likely to succeed if retried.
+                warn!(
+                    self.log,
+                    "Skipping custody backfill of block";
+                    "block_root" => ?id.block_root,
+                    "slot" => slot,
+                    "error" => ?e,
+                );
+                self.skip_block(id.block_root, slot, cx);
+            }
+            Err(e) => {
+                debug!(
+                    self.log,
+                    "Invalid custody backfill columns";
+                    "block_root" => ?id.block_root,
+                    "error" => ?e,
+                );
+                // The error doesn't identify the column at fault, so penalize every peer which
+                // served the block and fetch all of its columns again.
+                request.attempts += 1;
+                let mut peers = HashSet::new();
+                for (index, (_, peer_id)) in request.downloaded.drain() {
+                    request.failed_peers.insert((index, peer_id));
+                    peers.insert(peer_id);
+                }
+                for peer_id in peers {
+                    cx.report_peer(
+                        peer_id,
+                        PeerAction::LowToleranceError,
+                        "invalid_custody_backfill_columns",
+                    );
+                }
+                self.continue_block(id.block_root, cx);
+            }
+        }
+    }
+
+    /// Returns `true` if custody backfill is fetching the columns of `epoch`.
+    fn is_active_epoch(&self, epoch: Epoch) -> bool {
+        matches!(self.state, CustodyBackfillState::Syncing { epoch: active_epoch, .. } if active_epoch == epoch)
+    }
+
+    /// Request the columns of a block which are not yet downloaded, or send the block's columns
+    /// for processing once all of them are downloaded.
+    fn continue_block(&mut self, block_root: Hash256, cx: &mut SyncNetworkContext<T>) {
+        let CustodyBackfillState::Syncing { epoch, .. } = self.state else {
+            return;
+        };
+        let Some(request) = self.blocks.get_mut(&block_root) else {
+            return;
+        };
+        if request.processing {
+            return;
+        }
+
+        let id = CustodyBackfillId { epoch, block_root };
+
+        // Choose a peer for each column to request, grouping the columns by peer.
+        let mut columns_by_peer: FnvHashMap<PeerId, Vec<ColumnIndex>> = <_>::default();
+        let mut peers_exhausted = false;
+        for index in request.columns_to_request() {
+            let custodial_peers = cx.get_custodial_peers(index);
+            let mut peers = custodial_peers
+                .iter()
+                .filter(|peer_id| !request.failed_peers.contains(&(index, **peer_id)))
+                .collect::<Vec<_>>();
+            if peers.is_empty() && !custodial_peers.is_empty() {
+                // Every custodial peer has failed to serve the column, try them all again.
+                peers_exhausted = true;
+                request
+                    .failed_peers
+                    .retain(|(failed_index, _)| *failed_index != index);
+                peers = custodial_peers.iter().collect();
+            }
+            if let Some(peer_id) = peers.choose(&mut thread_rng()) {
+                columns_by_peer.entry(**peer_id).or_default().push(index);
+            }
+        }
+        if peers_exhausted {
+            request.attempts += 1;
+        }
+
+        if request.attempts >= MAX_BLOCK_ATTEMPTS {
+            let slot = request.slot;
+            warn!(
+                self.log,
+                "Skipping custody backfill of block";
+                "block_root" => ?block_root,
+                "slot" => slot,
+                "error" => "too many failed attempts",
+            );
+            self.skip_block(block_root, slot, cx);
+            return;
+        }
+
+        if request.is_downloaded() {
+            let data_columns = request
+                .downloaded
+                .values()
+                .map(|(data_column, _)| data_column.clone())
+                .collect::<Vec<_>>();
+            match cx
+                .beacon_processor()
+                .send_custody_backfill_columns(id, data_columns)
+            {
+                Ok(()) => request.processing = true,
+                Err(e) => {
+                    // Retried on the next call to `continue_sync`.
+                    debug!(self.log, "Unable to send custody backfill columns for processing"; "error" => %e);
+                }
+            }
+            return;
+        }
+
+        for (peer_id, indices) in columns_by_peer {
+            match cx.data_column_lookup_request(
+                DataColumnsByRootRequester::CustodyBackfill(id),
+                peer_id,
+                DataColumnsByRootSingleBlockRequest {
+                    block_root,
+                    indices: indices.clone(),
+                },
+                // Peers may have pruned, or not yet backfilled, the columns of older blocks.
+                false,
+            ) {
+                Ok(LookupRequestResult::RequestSent(req_id)) => {
+                    request.downloading.insert(req_id, indices);
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    debug!(self.log, "Unable to send custody backfill request"; "error" => e);
+                }
+            }
+        }
+    }
+
+    /// Give up on the columns of a block until the next restart, then make progress on the epoch.
+    fn skip_block(&mut self, block_root: Hash256, slot: Slot, cx: &mut SyncNetworkContext<T>) {
+        metrics::inc_counter(&metrics::SYNC_CUSTODY_BACKFILL_SKIPPED_BLOCKS);
+        self.latest_skipped_slot = std::cmp::max(self.latest_skipped_slot, Some(slot));
+        self.blocks.remove(&block_root);
+        if self.blocks.is_empty() {
+            self.on_epoch_completed(cx);
+        }
+    }
+
+    /// Record that the columns of every block in the current epoch have been handled, then start
+    /// the next epoch.
+    fn on_epoch_completed(&mut self, cx: &mut SyncNetworkContext<T>) {
+        let CustodyBackfillState::Syncing {
+            epoch,
+            mut custody_info,
+            start_slot,
+        } = self.state.clone()
+        else {
+            return;
+        };
+
+        let backfill_slot = epoch.start_slot(T::EthSpec::slots_per_epoch());
+        custody_info.custody_backfill_slot = Some(backfill_slot);
+        self.store_custody_info(&custody_info, cx);
+        debug!(self.log, "Custody backfill epoch completed"; "epoch" => epoch);
+
+        self.start_epoch(custody_info, start_slot, cx);
+    }
+
+    /// Persist the custody info, so that custody backfill resumes from the same epoch after a
+    /// restart, or from the latest skipped block if any block was skipped.
+    fn store_custody_info(&self, custody_info: &DataColumnCustodyInfo, cx: &SyncNetworkContext<T>) {
+        let custody_info = DataColumnCustodyInfo {
+            custody_backfill_slot: stored_custody_backfill_slot(
+                custody_info.custody_backfill_slot,
+                self.latest_skipped_slot,
+            ),
+            ..custody_info.clone()
+        };
+        if let Err(e) = cx.chain.store.store_data_column_custody_info(&custody_info) {
+            error!(self.log, "Unable to store data column custody info"; "error" => ?e);
+        }
+        if let Some(backfill_slot) = custody_info.custody_backfill_slot {
+            metrics::set_gauge(
+                &metrics::SYNC_CUSTODY_BACKFILL_SLOT,
+                backfill_slot.as_u64() as i64,
+            );
+        }
+    }
+
+    /// Observe the number of slots backfilled so far, out of the total number of slots to
+    /// backfill.
+    fn observe_progress(&self, cx: &SyncNetworkContext<T>) {
+        let CustodyBackfillState::Syncing {
+            custody_info,
+            start_slot,
+            ..
+        } = &self.state
+        else {
+            return;
+        };
+        let Some(backfill_slot) = custody_info.custody_backfill_slot else {
+            return;
+        };
+        let slots_per_epoch = T::EthSpec::slots_per_epoch();
+        let target_slot = std::cmp::max(
+            cx.chain.data_availability_boundary(),
+            cx.chain.spec.eip7594_fork_epoch,
+        )
+        .map_or(Slot::new(0), |epoch| epoch.start_slot(slots_per_epoch));
+
+        cx.network_globals().sync_progress.write().observe(
+            SyncKind::CustodyBackfill,
+            start_slot.saturating_sub(backfill_slot),
+            start_slot.saturating_sub(target_slot),
+            Instant::now(),
+        );
+    }
+
+    fn complete(&mut self, cx: &mut SyncNetworkContext<T>) {
+        if self.state != CustodyBackfillState::Completed {
+            match self.latest_skipped_slot {
+                Some(latest_skipped_slot) => warn!(
+                    self.log,
+                    "Custody backfill completed with skipped blocks";
+                    "info" => "the skipped blocks will be retried after a restart",
+                    "latest_skipped_slot" => latest_skipped_slot,
+                ),
+                None => info!(self.log, "Custody backfill completed"),
+            }
+        }
+        self.state = CustodyBackfillState::Completed;
+        self.blocks.clear();
+        metrics::set_gauge(&metrics::SYNC_CUSTODY_BACKFILL_SLOT, 0);
+        cx.network_globals()
+            .sync_progress
+            .write()
+            .clear(SyncKind::CustodyBackfill);
+    }
+}
+
+/// Returns the custody backfill slot to persist, which is never below the latest skipped block so
+/// that the skipped columns are fetched again after a restart.
+fn stored_custody_backfill_slot(
+    backfill_slot: Option<Slot>,
+    latest_skipped_slot: Option<Slot>,
+) -> Option<Slot> {
+    match (backfill_slot, latest_skipped_slot) {
+        (backfill_slot, None) => backfill_slot,
+        (None, Some(skipped_slot)) => Some(skipped_slot + 1),
+        (Some(backfill_slot), Some(skipped_slot)) => {
+            Some(std::cmp::max(backfill_slot, skipped_slot + 1))
+        }
+    }
+}
+
+/// The missing custody columns of a single block.
+struct BlockColumnsRequest<E: EthSpec> {
+    slot: Slot,
+    /// The indices of the custody columns missing from the store.
+    columns: Vec<ColumnIndex>,
+    /// The columns downloaded so far, and the peer which served each of them.
+    downloaded: FnvHashMap<ColumnIndex, (Arc<DataColumnSidecar<E>>, PeerId)>,
+    /// The column indices of each active `data_columns_by_root` request.
+    downloading: FnvHashMap<DataColumnsByRootRequestId, Vec<ColumnIndex>>,
+    /// Peers which failed to serve a column, which are not asked for it again until every
+    /// custodial peer has failed.
+    failed_peers: HashSet<(ColumnIndex, PeerId)>,
+    /// The number of times the block's columns failed processing, or every custodial peer of a
+    /// column failed to serve it.
+    attempts: usize,
+    processing: bool,
+}
+
+impl<E: EthSpec> BlockColumnsRequest<E> {
+    fn new(slot: Slot, columns: Vec<ColumnIndex>) -> Self {
+        Self {
+            slot,
+            columns,
+            downloaded: <_>::default(),
+            downloading: <_>::default(),
+            failed_peers: <_>::default(),
+            attempts: 0,
+            processing: false,
+        }
+    }
+
+    fn is_downloaded(&self) -> bool {
+        self.columns
+            .iter()
+            .all(|index| self.downloaded.contains_key(index))
+    }
+
+    /// Returns the columns which are neither downloaded nor being downloaded.
+    fn columns_to_request(&self) -> Vec<ColumnIndex> {
+        self.columns
+            .iter()
+            .filter(|index| {
+                !self.downloaded.contains_key(index)
+                    && !self
+                        .downloading
+                        .values()
+                        .any(|indices| indices.contains(index))
+            })
+            .copied()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stored_slot_is_never_below_a_skipped_block() {
+        let slot = Slot::new;
+        assert_eq!(
+            stored_custody_backfill_slot(Some(slot(64)), None),
+            Some(slot(64))
+        );
+        assert_eq!(stored_custody_backfill_slot(None, None), None);
+
+        // A block at slot 70 was skipped, so backfill must resume above it after a restart.
+        assert_eq!(
+            stored_custody_backfill_slot(Some(slot(64)), Some(slot(70))),
+            Some(slot(71))
+        );
+        assert_eq!(
+            stored_custody_backfill_slot(Some(slot(96)), Some(slot(70))),
+            Some(slot(96))
+        );
+        // Reaching the data availability boundary doesn't complete custody backfill.
+        assert_eq!(
+            stored_custody_backfill_slot(None, Some(slot(70))),
+            Some(slot(71))
+        );
+    }
+}
@@ -35,6 +35,7 @@
 
 use super::backfill_sync::{BackFillSync, ProcessResult, SyncStart};
 use super::block_lookups::BlockLookups;
+use super::custody_backfill::CustodyBackfillSync;
 use super::network_context::{
     BlockOrBlob, CustodyByRootResult, RangeRequestId, RpcEvent, SyncNetworkContext,
 };
@@ -42,6 +43,7 @@ use super::peer_sampling::{Sampling, SamplingConfig, SamplingResult};
 use super::peer_sync_info::{remote_sync_type, PeerSyncType};
 use super::range_sync::{RangeSync, RangeSyncType, EPOCHS_PER_BATCH};
 use crate::network_beacon_processor::{ChainSegmentProcessId, NetworkBeaconProcessor};
+use crate::persisted_lookups::{persist_lookups, take_lookups};
 use crate::service::NetworkMessage;
 use crate::status::ToStatusMessage;
 use crate::sync::block_lookups::{
@@ -53,22 +55,26 @@ use beacon_chain::block_verification_types::AsBlock;
 use beacon_chain::validator_monitor::timestamp_now;
 use beacon_chain::{
     AvailabilityProcessingStatus, BeaconChain, BeaconChainTypes, BlockError, EngineState,
+    HistoricalDataColumnError,
 };
+use beacon_processor::work_reprocessing_queue::ReprocessQueueMessage;
+use fnv::FnvHashMap;
 use futures::StreamExt;
 use lighthouse_network::rpc::RPCError;
 use lighthouse_network::service::api_types::{
-    CustodyRequester, DataColumnsByRootRequestId, DataColumnsByRootRequester, Id, SamplingId,
-    SamplingRequester, SingleLookupReqId, SyncRequestId,
+    CustodyBackfillId, CustodyRequester, DataColumnsByRootRequestId, DataColumnsByRootRequester,
+    Id, SamplingId, SamplingRequester, SingleLookupReqId, SyncRequestId,
 };
 use lighthouse_network::types::{NetworkGlobals, SyncState};
 use lighthouse_network::SyncInfo;
 use lighthouse_network::{PeerAction, PeerId};
 use lru_cache::LRUTimeCache;
 use slog::{crit, debug, error, info, o, trace, warn, Logger};
+use std::future::Future;
 use std::ops::Sub;
 use std::sync::Arc;
-use std::time::Duration;
-use tokio::sync::mpsc;
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, oneshot};
 use types::{BlobSidecar, DataColumnSidecar, EthSpec, Hash256, SignedBeaconBlock, Slot};
 
 #[cfg(test)]
@@ -88,6 +94,14 @@ pub const SLOT_IMPORT_TOLERANCE: usize = 32;
 /// arbitrary number that covers a full slot, but allows recovery if sync get stuck for a few slots.
 const NOTIFIED_UNKNOWN_ROOT_EXPIRY_SECONDS: u64 = 30;
 
+/// Lookups restored from the store are only resumed once one of the peers which claimed to have
+/// the block reconnects. Lookups whose peers have not reconnected after this duration are dropped.
+const RESTORED_LOOKUPS_EXPIRY_SECONDS: u64 = 120;
+
+/// The maximum time to wait on shutdown for the reprocess queue to report the roots of the blocks
+/// which its queued work is waiting on.
+const REPROCESS_QUEUE_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(1);
+
 #[derive(Debug)]
 /// A message that can be sent to the sync manager thread.
 pub enum SyncMessage<E: EthSpec> {
@@ -172,6 +186,12 @@ pub enum SyncMessage<E: EthSpec> {
         result: Result<(), String>,
     },
 
+    /// The custody columns of a block fetched by custody backfill have been processed.
+    CustodyBackfillProcessed {
+        id: CustodyBackfillId,
+        result: Result<usize, HistoricalDataColumnError>,
+    },
+
     /// A block from gossip has completed processing,
     GossipBlockProcessResult { block_root: Hash256, imported: bool },
 }
@@ -237,12 +257,22 @@ pub struct SyncManager<T: BeaconChainTypes> {
     /// Backfill syncing.
     backfill_sync: BackFillSync<T>,
 
+    /// Backfill of the columns of newly custodied subnets.
+    custody_backfill: CustodyBackfillSync<T>,
+
     block_lookups: BlockLookups<T>,
     /// debounce duplicated `UnknownBlockHashFromAttestation` for the same root peer tuple. A peer
     /// may forward us thousands of a attestations, each one triggering an individual event. Only
     /// one event is useful, the rest generating log noise and wasted cycles
     notified_unknown_roots: LRUTimeCache<(PeerId, Hash256)>,
 
+    /// Lookups which were pending on the last shutdown, by block root, along with the peers
+    /// which claimed to have the block. Only populated with `--persist-pending-lookups`.
+    restored_lookups: FnvHashMap<Hash256, Vec<PeerId>>,
+
+    /// The time after which `restored_lookups` are dropped.
+    restored_lookups_expiry: Instant,
+
     sampling: Sampling<T>,
 
     /// The logger for the import manager.
@@ -275,9 +305,14 @@ pub fn spawn<T: BeaconChainTypes>(
         log.clone(),
     );
 
-    // spawn the sync manager thread
+    // spawn the sync manager thread, which handles the exit signal itself so that it can persist
+    // its state on shutdown
     debug!(log, "Sync Manager started");
-    executor.spawn(async move { Box::pin(sync_manager.main()).await }, "sync");
+    let exit = executor.exit();
+    executor.spawn_without_exit(
+        async move { Box::pin(sync_manager.main(exit)).await },
+        "sync",
+    );
 }
 
 impl<T: BeaconChainTypes> SyncManager<T> {
@@ -290,6 +325,28 @@ impl<T: BeaconChainTypes> SyncManager<T> {
         log: slog::Logger,
     ) -> Self {
         let network_globals = beacon_processor.network_globals.clone();
+
+        let restored_lookups = if network_globals.config.persist_pending_lookups {
+            match take_lookups(beacon_chain.store.clone()) {
+                Ok(lookups) => {
+                    if !lookups.is_empty() {
+                        info!(
+                            log,
+                            "Restored pending block lookups";
+                            "count" => lookups.len(),
+                        );
+                    }
+                    lookups.into_iter().collect()
+                }
+                Err(e) => {
+                    warn!(log, "Failed to restore pending block lookups"; "error" => ?e);
+                    FnvHashMap::default()
+                }
+            }
+        } else {
+            FnvHashMap::default()
+        };
+
         Self {
             chain: beacon_chain.clone(),
             input_channel: sync_recv,
@@ -305,13 +362,22 @@ impl<T: BeaconChainTypes> SyncManager<T> {
             ),
             backfill_sync: BackFillSync::new(
                 beacon_chain.clone(),
-                network_globals,
+                network_globals.clone(),
                 log.new(o!("service" => "backfill_sync")),
             ),
-            block_lookups: BlockLookups::new(log.new(o!("service"=> "lookup_sync"))),
+            custody_backfill: CustodyBackfillSync::new(
+                log.new(o!("service" => "custody_backfill")),
+            ),
+            block_lookups: BlockLookups::new(
+                network_globals.config.lookup_max_parent_depth,
+                log.new(o!("service"=> "lookup_sync")),
+            ),
             notified_unknown_roots: LRUTimeCache::new(Duration::from_secs(
                 NOTIFIED_UNKNOWN_ROOT_EXPIRY_SECONDS,
             )),
+            restored_lookups,
+            restored_lookups_expiry: Instant::now()
+                + Duration::from_secs(RESTORED_LOOKUPS_EXPIRY_SECONDS),
             sampling: Sampling::new(sampling_config, log.new(o!("service" => "sampling"))),
             log: log.clone(),
         }
@@ -348,6 +414,11 @@ impl<T: BeaconChainTypes> SyncManager<T> {
         self.block_lookups.insert_failed_chain(block_root);
     }
 
+    #[cfg(test)]
+    pub(crate) fn custody_backfill_state(&self) -> &super::custody_backfill::CustodyBackfillState {
+        self.custody_backfill.state()
+    }
+
     #[cfg(test)]
     pub(crate) fn active_sampling_requests(&self) -> Vec<Hash256> {
         self.sampling.active_sampling_requests()
@@ -418,12 +489,68 @@ impl<T: BeaconChainTypes> SyncManager<T> {
 
         self.update_sync_state();
 
+        self.resume_restored_lookups(peer_id);
+
         // Try to make progress on custody requests that are waiting for peers
         for (id, result) in self.network.continue_custody_by_root_requests() {
             self.on_custody_by_root_result(id, result);
         }
     }
 
+    /// Resume the lookups restored from the store which `peer_id` claimed to have, now that it
+    /// has reconnected.
+    fn resume_restored_lookups(&mut self, peer_id: PeerId) {
+        if self.restored_lookups.is_empty() {
+            return;
+        }
+        if Instant::now() > self.restored_lookups_expiry {
+            debug!(
+                self.log,
+                "Dropping restored block lookups";
+                "count" => self.restored_lookups.len(),
+            );
+            self.restored_lookups.clear();
+            return;
+        }
+
+        // Lookups without known peers, e.g. for blocks which queued attestations referenced, are
+        // resumed with the first peer to connect.
+        let block_roots = self
+            .restored_lookups
+            .iter()
+            .filter(|(_, peers)| peers.is_empty() || peers.contains(&peer_id))
+            .map(|(block_root, _)| *block_root)
+            .collect::<Vec<_>>();
+        for block_root in block_roots {
+            let Some(peers) = self.restored_lookups.remove(&block_root) else {
+                continue;
+            };
+            if self.chain.block_is_known_to_fork_choice(&block_root) {
+                continue;
+            }
+            let connected_peers = if peers.is_empty() {
+                vec![peer_id]
+            } else {
+                let peer_db = self.network_globals().peers.read();
+                peers
+                    .into_iter()
+                    .filter(|peer| peer_db.is_connected(peer))
+                    .collect::<Vec<_>>()
+            };
+            debug!(
+                self.log,
+                "Resuming restored block lookup";
+                "block_root" => ?block_root,
+                "peers" => connected_peers.len(),
+            );
+            self.block_lookups.search_unknown_block(
+                block_root,
+                &connected_peers,
+                &mut self.network,
+            );
+        }
+    }
+
     /// Trigger range sync for a set of peers that claim to have imported a head unknown to us.
     fn add_peers_force_range_sync(
         &mut self,
@@ -703,7 +830,7 @@ impl<T: BeaconChainTypes> SyncManager<T> {
     }
 
     /// The main driving future for the sync manager.
-    async fn main(&mut self) {
+    async fn main(&mut self, exit: impl Future<Output = ()>) {
         let check_ee = self.chain.execution_layer.is_some();
         let mut check_ee_stream = {
             // some magic to have an instance implementing stream even if there is no execution layer
@@ -725,6 +852,15 @@ impl<T: BeaconChainTypes> SyncManager<T> {
 
         let mut register_metrics_interval = tokio::time::interval(Duration::from_secs(5));
 
+        // Custody backfill is driven by responses, this only retries requests which stalled for
+        // lack of custodial peers.
+        let mut custody_backfill_interval = tokio::time::interval(Duration::from_secs(12));
+
+        // Backfill batches held back by the backfill throttle are not driven by any response.
+        let mut backfill_throttle_interval = tokio::time::interval(Duration::from_secs(5));
+
+        let mut exit = std::pin::pin!(exit);
+
         // process any inbound messages
         loop {
             tokio::select! {
@@ -743,10 +879,72 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                 _ = register_metrics_interval.tick() => {
                     self.network.register_metrics();
                 }
+                _ = custody_backfill_interval.tick() => {
+                    self.continue_custody_backfill();
+                }
+                _ = backfill_throttle_interval.tick() => {
+                    self.resume_throttled_backfill();
+                }
+                _ = &mut exit => {
+                    self.shutdown().await;
+                    return;
+                }
             }
         }
     }
 
+    /// Persist the pending block lookups, if enabled, along with the blocks which work in the
+    /// reprocess queue is waiting on.
+    async fn shutdown(&mut self) {
+        if !self.network_globals().config.persist_pending_lookups {
+            return;
+        }
+
+        let (response_tx, response_rx) = oneshot::channel();
+        let queued_block_roots = if self
+            .network
+            .beacon_processor()
+            .reprocess_tx
+            .try_send(ReprocessQueueMessage::PendingBlockRoots(response_tx))
+            .is_ok()
+        {
+            tokio::time::timeout(REPROCESS_QUEUE_SHUTDOWN_TIMEOUT, response_rx)
+                .await
+                .ok()
+                .and_then(Result::ok)
+                .unwrap_or_default()
+        } else {
+            vec![]
+        };
+
+        self.persist_pending_lookups(queued_block_roots);
+    }
+
+    /// Persist the pending block lookups, and lookups without peers for `queued_block_roots`.
+    pub(crate) fn persist_pending_lookups(&mut self, queued_block_roots: Vec<Hash256>) {
+        let mut lookups = self.block_lookups.lookups_to_persist();
+        if Instant::now() <= self.restored_lookups_expiry {
+            lookups.extend(self.restored_lookups.drain());
+        }
+        for block_root in queued_block_roots {
+            if !lookups.iter().any(|(root, _)| *root == block_root)
+                && !self.chain.block_is_known_to_fork_choice(&block_root)
+            {
+                lookups.push((block_root, vec![]));
+            }
+        }
+
+        let count = lookups.len();
+        match persist_lookups(self.chain.store.clone(), lookups) {
+            Ok(()) => debug!(self.log, "Persisted pending block lookups"; "count" => count),
+            Err(e) => error!(
+                self.log,
+                "Failed to persist pending block lookups";
+                "error" => ?e
+            ),
+        }
+    }
+
     pub(crate) fn handle_message(&mut self, sync_message: SyncMessage<T::EthSpec>) {
         match sync_message {
             SyncMessage::AddPeer(peer_id, info) => {
@@ -841,9 +1039,9 @@ impl<T: BeaconChainTypes> SyncManager<T> {
             }
             SyncMessage::SampleBlock(block_root, block_slot) => {
                 debug!(self.log, "Received SampleBlock message"; "block_root" => %block_root, "slot" => block_slot);
-                if let Some((requester, result)) = self
-                    .sampling
-                    .on_new_sample_request(block_root, &mut self.network)
+                if let Some((requester, result)) =
+                    self.sampling
+                        .on_new_sample_request(block_root, block_slot, &mut self.network)
                 {
                     self.on_sampling_result(requester, result)
                 }
@@ -897,6 +1095,9 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                     }
                 }
             },
+            SyncMessage::CustodyBackfillProcessed { id, result } => self
+                .custody_backfill
+                .on_columns_processed(id, result, &mut self.network),
             SyncMessage::SampleVerified { id, result } => {
                 if let Some((requester, result)) =
                     self.sampling
@@ -1168,6 +1369,34 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                         self.on_custody_by_root_result(custody_id.requester, result);
                     }
                 }
+                DataColumnsByRootRequester::CustodyBackfill(id) => {
+                    self.custody_backfill.on_data_columns_downloaded(
+                        id,
+                        req_id,
+                        peer_id,
+                        resp,
+                        &mut self.network,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Make progress on custody backfill. Custody backfill only runs once the node is synced.
+    pub(crate) fn continue_custody_backfill(&mut self) {
+        if self.network_globals().sync_state.read().is_synced() {
+            self.custody_backfill.continue_sync(&mut self.network);
+        }
+    }
+
+    /// Process any backfill batch which was held back by the backfill throttle.
+    fn resume_throttled_backfill(&mut self) {
+        match self.backfill_sync.resume_throttled(&mut self.network) {
+            Ok(ProcessResult::Successful) => {}
+            Ok(ProcessResult::SyncCompleted) => self.update_sync_state(),
+            Err(error) => {
+                error!(self.log, "Backfill sync failed"; "error" => ?error);
+                self.update_sync_state();
             }
         }
     }
@@ -1210,6 +1439,11 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                 }
             }
         }
+
+        // A sampling request has completed, so there is capacity to start a queued one.
+        for (requester, result) in self.sampling.start_queued_requests(&mut self.network) {
+            self.on_sampling_result(requester, result);
+        }
     }
 
     /// Handles receiving a response for a range sync request that should have both blocks and
@@ -49,6 +49,10 @@ use types::{
 pub mod custody;
 mod requests;
 
+/// The granularity with which peers are preferred by their STATUS round-trip latency. Peers within
+/// the same bucket are treated as equally preferable, so that requests remain spread across them.
+const PEER_LATENCY_BUCKET: Duration = Duration::from_millis(100);
+
 pub struct BlocksAndBlobsByRangeResponse<E: EthSpec> {
     pub sender_id: RangeRequestId,
     pub responses: Result<Vec<RpcBlock<E>>, String>,
@@ -297,10 +301,38 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
             .custody_peers_for_column(column_index)
     }
 
-    pub fn get_random_custodial_peer(&self, column_index: ColumnIndex) -> Option<PeerId> {
-        self.get_custodial_peers(column_index)
-            .choose(&mut thread_rng())
-            .cloned()
+    pub fn get_low_latency_custodial_peer(&self, column_index: ColumnIndex) -> Option<PeerId> {
+        self.choose_low_latency_peer(self.get_custodial_peers(column_index))
+    }
+
+    /// Returns keys by which to prefer the `peers` with a low STATUS round-trip latency, lowest
+    /// first. Peers of unknown latency are ordered last.
+    pub fn peer_latency_keys<'a>(
+        &self,
+        peers: impl IntoIterator<Item = &'a PeerId>,
+    ) -> HashMap<PeerId, u128> {
+        let peer_db = self.network_globals().peers.read();
+        peers
+            .into_iter()
+            .map(|peer_id| {
+                let key = peer_db
+                    .peer_info(peer_id)
+                    .and_then(|info| info.status_latency())
+                    .map_or(u128::MAX, |latency| {
+                        latency.as_millis() / PEER_LATENCY_BUCKET.as_millis()
+                    });
+                (*peer_id, key)
+            })
+            .collect()
+    }
+
+    /// Chooses a random peer amongst the `peers` with the lowest latency key.
+    pub fn choose_low_latency_peer(&self, mut peers: Vec<PeerId>) -> Option<PeerId> {
+        peers.shuffle(&mut thread_rng());
+        let latency_keys = self.peer_latency_keys(&peers);
+        peers
+            .into_iter()
+            .min_by_key(|peer_id| latency_keys[peer_id])
     }
 
     pub fn network_globals(&self) -> &NetworkGlobals<T::EthSpec> {
@@ -467,7 +499,7 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
             // TODO(das): The peer selection logic here needs to be improved - we should probably
             // avoid retrying from failed peers, however `BatchState` currently only tracks the peer
             // serving the blocks.
-            let Some(custody_peer) = self.get_random_custodial_peer(*column_index) else {
+            let Some(custody_peer) = self.get_low_latency_custodial_peer(*column_index) else {
                 // TODO(das): this will be pretty bad UX. To improve we should:
                 // - Attempt to fetch custody requests first, before requesting blocks
                 // - Handle the no peers case gracefully, maybe add some timeout and give a few
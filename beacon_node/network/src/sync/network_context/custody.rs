@@ -230,6 +230,7 @@ impl<T: BeaconChainTypes> ActiveCustodyRequest<T> {
                 // TODO(das): When is a fork and only a subset of your peers know about a block, we should
                 // only query the peers on that fork. Should this case be handled? How to handle it?
                 let custodial_peers = cx.get_custodial_peers(*column_index);
+                let latency_keys = cx.peer_latency_keys(&custodial_peers);
 
                 // TODO(das): cache this computation in a OneCell or similar to prevent having to
                 // run it every loop
@@ -249,6 +250,8 @@ impl<T: BeaconChainTypes> ActiveCustodyRequest<T> {
                             self.failed_peers.contains(peer),
                             // Prefer peers with less requests to load balance across peers
                             active_requests_by_peer.get(peer).copied().unwrap_or(0),
+                            // Prefer peers which respond quickly
+                            latency_keys[peer],
                             // Final random factor to give all peers a shot in each retry
                             rand::thread_rng().gen::<u32>(),
                             *peer,
@@ -257,7 +260,7 @@ impl<T: BeaconChainTypes> ActiveCustodyRequest<T> {
                     .collect::<Vec<_>>();
                 priorized_peers.sort_unstable();
 
-                if let Some((_, _, _, peer_id)) = priorized_peers.first() {
+                if let Some((_, _, _, _, peer_id)) = priorized_peers.first() {
                     columns_to_request_by_peer
                         .entry(*peer_id)
                         .or_default()
@@ -14,17 +14,29 @@ use lighthouse_network::{PeerAction, PeerId};
 use rand::{seq::SliceRandom, thread_rng};
 use slog::{debug, error, warn};
 use std::{
-    collections::hash_map::Entry, collections::HashMap, marker::PhantomData, sync::Arc,
+    collections::hash_map::Entry,
+    collections::{BinaryHeap, HashMap, HashSet},
+    marker::PhantomData,
+    sync::Arc,
     time::Duration,
 };
-use types::{data_column_sidecar::ColumnIndex, ChainSpec, DataColumnSidecar, Hash256};
+use types::{data_column_sidecar::ColumnIndex, ChainSpec, DataColumnSidecar, Hash256, Slot};
 
 pub type SamplingResult = Result<(), SamplingError>;
 
+/// The maximum number of sampling requests that may be active at once. Further requests are queued
+/// until an active request completes.
+pub(crate) const MAX_ACTIVE_SAMPLING_REQUESTS: usize = 16;
+
 type DataColumnSidecarList<E> = Vec<Arc<DataColumnSidecar<E>>>;
 
 pub struct Sampling<T: BeaconChainTypes> {
     requests: HashMap<SamplingRequester, ActiveSamplingRequest<T>>,
+    /// Requests waiting for an active request to complete, popped highest slot first so that
+    /// blocks closest to the head are sampled first.
+    queued_requests: BinaryHeap<(Slot, Hash256)>,
+    /// The block roots in `queued_requests`, to ignore duplicate requests.
+    queued_block_roots: HashSet<Hash256>,
     sampling_config: SamplingConfig,
     log: slog::Logger,
 }
@@ -33,6 +45,8 @@ impl<T: BeaconChainTypes> Sampling<T> {
     pub fn new(sampling_config: SamplingConfig, log: slog::Logger) -> Self {
         Self {
             requests: <_>::default(),
+            queued_requests: <_>::default(),
+            queued_block_roots: <_>::default(),
             sampling_config,
             log,
         }
@@ -62,6 +76,62 @@ impl<T: BeaconChainTypes> Sampling<T> {
     /// - `Some`: Request completed, won't make more progress. Expect requester to act on the result.
     /// - `None`: Request still active, requester should do no action
     pub fn on_new_sample_request(
+        &mut self,
+        block_root: Hash256,
+        block_slot: Slot,
+        cx: &mut SyncNetworkContext<T>,
+    ) -> Option<(SamplingRequester, SamplingResult)> {
+        let id = SamplingRequester::ImportedBlock(block_root);
+
+        if self.requests.contains_key(&id) || self.queued_block_roots.contains(&block_root) {
+            // Sampling is triggered from multiple sources, duplicate sampling requests are
+            // likely (gossip block + gossip data column)
+            // TODO(das): Should track failed sampling request for some time? Otherwise there's
+            // a risk of a loop with multiple triggers creating the request, then failing,
+            // and repeat.
+            debug!(self.log, "Ignoring duplicate sampling request"; "id" => ?id);
+            return None;
+        }
+
+        if self.requests.len() >= MAX_ACTIVE_SAMPLING_REQUESTS {
+            // A burst of imported blocks during sync would otherwise start a sampling request
+            // for every block at once.
+            debug!(self.log,
+                "Queueing sampling request";
+                "id" => ?id,
+                "slot" => block_slot,
+                "queued" => self.queued_requests.len()
+            );
+            self.queued_block_roots.insert(block_root);
+            self.queued_requests.push((block_slot, block_root));
+            return None;
+        }
+
+        self.start_sample_request(block_root, cx)
+    }
+
+    /// Start queued sampling requests, highest slot first, while there is capacity for them.
+    ///
+    /// ### Returns
+    ///
+    /// The results of any started requests which completed immediately. Expect requester to act
+    /// on them.
+    pub fn start_queued_requests(
+        &mut self,
+        cx: &mut SyncNetworkContext<T>,
+    ) -> Vec<(SamplingRequester, SamplingResult)> {
+        let mut results = vec![];
+        while self.requests.len() < MAX_ACTIVE_SAMPLING_REQUESTS {
+            let Some((_, block_root)) = self.queued_requests.pop() else {
+                break;
+            };
+            self.queued_block_roots.remove(&block_root);
+            results.extend(self.start_sample_request(block_root, cx));
+        }
+        results
+    }
+
+    fn start_sample_request(
         &mut self,
         block_root: Hash256,
         cx: &mut SyncNetworkContext<T>,
@@ -77,11 +147,6 @@ impl<T: BeaconChainTypes> Sampling<T> {
                 &cx.chain.spec,
             )),
             Entry::Occupied(_) => {
-                // Sampling is triggered from multiple sources, duplicate sampling requests are
-                // likely (gossip block + gossip data column)
-                // TODO(das): Should track failed sampling request for some time? Otherwise there's
-                // a risk of a loop with multiple triggers creating the request, then failing,
-                // and repeat.
                 debug!(self.log, "Ignoring duplicate sampling request"; "id" => ?id);
                 return None;
             }
@@ -261,7 +326,7 @@ impl<T: BeaconChainTypes> ActiveSamplingRequest<T> {
     /// - `Ok(None)`: Sampling request still active
     pub(crate) fn on_sample_downloaded(
         &mut self,
-        _peer_id: PeerId,
+        peer_id: PeerId,
         sampling_request_id: SamplingRequestId,
         resp: Result<(DataColumnSidecarList<T::EthSpec>, Duration), RpcResponseError>,
         cx: &mut SyncNetworkContext<T>,
@@ -320,6 +385,7 @@ impl<T: BeaconChainTypes> ActiveSamplingRequest<T> {
                             "column_index" => column_index
                         );
                         request.on_sampling_error()?;
+                        register_sampling_failure(cx, &peer_id, "missing_data");
                         continue;
                     };
 
@@ -404,6 +470,7 @@ impl<T: BeaconChainTypes> ActiveSamplingRequest<T> {
                         continue;
                     };
                     request.on_sampling_error()?;
+                    register_sampling_failure(cx, &peer_id, "download_error");
                 }
             }
         };
@@ -465,6 +532,7 @@ impl<T: BeaconChainTypes> ActiveSamplingRequest<T> {
                         continue;
                     };
                     let peer_id = request.on_sampling_error()?;
+                    register_sampling_failure(cx, &peer_id, "invalid_data");
                     cx.report_peer(
                         peer_id,
                         PeerAction::LowToleranceError,
@@ -551,6 +619,7 @@ impl<T: BeaconChainTypes> ActiveSamplingRequest<T> {
                 false,
             )
             .map_err(SamplingError::SendFailed)?;
+            metrics::inc_counter_by(&metrics::DAS_SAMPLES_REQUESTED, column_indexes.len() as u64);
             self.column_indexes_by_sampling_request
                 .insert(self.current_sampling_request_id, column_indexes.clone());
             self.current_sampling_request_id.0 += 1;
@@ -577,14 +646,26 @@ impl<T: BeaconChainTypes> ActiveSamplingRequest<T> {
     }
 }
 
+/// Record a failed column sample against the client of the peer that served it.
+fn register_sampling_failure<T: BeaconChainTypes>(
+    cx: &SyncNetworkContext<T>,
+    peer_id: &PeerId,
+    reason: &'static str,
+) {
+    metrics::inc_counter_vec(
+        &metrics::DAS_SAMPLING_PEER_FAILURES,
+        &[cx.client_type(peer_id).kind.as_ref(), reason],
+    );
+}
+
 mod request {
     use super::SamplingError;
+    use crate::metrics;
     use crate::sync::network_context::SyncNetworkContext;
     use beacon_chain::BeaconChainTypes;
     use lighthouse_network::PeerId;
-    use rand::seq::SliceRandom;
-    use rand::thread_rng;
     use std::collections::HashSet;
+    use std::time::Instant;
     use types::data_column_sidecar::ColumnIndex;
 
     pub(crate) struct ActiveColumnSampleRequest {
@@ -592,6 +673,8 @@ mod request {
         status: Status,
         // TODO(das): Should downscore peers that claim to not have the sample?
         peers_dont_have: HashSet<PeerId>,
+        /// The time at which the column was last requested from a peer.
+        sampling_started: Option<Instant>,
     }
 
     // Exposed only for testing assertions in lookup tests
@@ -609,6 +692,7 @@ mod request {
                 column_index,
                 status: Status::NotStarted,
                 peers_dont_have: <_>::default(),
+                sampling_started: None,
             }
         }
 
@@ -655,8 +739,8 @@ mod request {
 
             peer_ids.retain(|peer_id| !self.peers_dont_have.contains(peer_id));
 
-            if let Some(peer_id) = peer_ids.choose(&mut thread_rng()) {
-                Some(*peer_id)
+            if let Some(peer_id) = cx.choose_low_latency_peer(peer_ids) {
+                Some(peer_id)
             } else {
                 self.status = Status::NoPeers;
                 None
@@ -667,6 +751,7 @@ mod request {
             match self.status.clone() {
                 Status::NoPeers | Status::NotStarted => {
                     self.status = Status::Sampling(peer_id);
+                    self.sampling_started = Some(Instant::now());
                     Ok(())
                 }
                 other => Err(SamplingError::BadState(format!(
@@ -694,6 +779,12 @@ mod request {
             match &self.status {
                 Status::Sampling(_) => {
                     self.status = Status::Verified;
+                    if let Some(sampling_started) = self.sampling_started.take() {
+                        metrics::observe_duration(
+                            &metrics::DAS_SAMPLE_SUCCESS_SECONDS,
+                            sampling_started.elapsed(),
+                        );
+                    }
                     Ok(())
                 }
                 other => Err(SamplingError::BadState(format!(
@@ -6,6 +6,7 @@ use crate::network_beacon_processor::ChainSegmentProcessId;
 use crate::sync::network_context::RangeRequestId;
 use crate::sync::{network_context::SyncNetworkContext, BatchOperationOutcome, BatchProcessResult};
 use beacon_chain::block_verification_types::RpcBlock;
+use beacon_chain::events::{EventKind, SseSyncChainStatus, SseSyncProgress, SseSyncType};
 use beacon_chain::BeaconChainTypes;
 use fnv::FnvHashMap;
 use lighthouse_network::service::api_types::Id;
@@ -14,6 +15,7 @@ use metrics::set_int_gauge;
 use rand::seq::SliceRandom;
 use rand::Rng;
 use slog::{crit, debug, o, warn};
+use std::cmp::Reverse;
 use std::collections::{btree_map::Entry, BTreeMap, HashSet};
 use std::hash::{Hash, Hasher};
 use strum::IntoStaticStr;
@@ -195,6 +197,30 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
             .sum()
     }
 
+    /// Publishes a `sync_progress` event describing the chain, if there are any subscribers.
+    pub fn emit_progress_event(&self, network: &SyncNetworkContext<T>, status: SseSyncChainStatus) {
+        let Some(event_handler) = network.chain.event_handler.as_ref() else {
+            return;
+        };
+        if !event_handler.has_sync_progress_subscribers() {
+            return;
+        }
+        event_handler.register(EventKind::SyncProgress(SseSyncProgress {
+            chain_id: self.id,
+            sync_type: match self.chain_type {
+                SyncingChainType::Head => SseSyncType::Head,
+                SyncingChainType::Finalized => SseSyncType::Finalized,
+                SyncingChainType::Backfill => SseSyncType::Backfill,
+            },
+            status,
+            start_epoch: self.start_epoch,
+            processing_target: self.processing_target,
+            target_head_slot: self.target_head_slot,
+            target_head_root: self.target_head_root,
+            peers: self.peers.len() as u64,
+        }));
+    }
+
     /// Removes a peer from the chain.
     /// If the peer has active batches, those are considered failed and re-requested.
     pub fn remove_peer(
@@ -552,6 +578,7 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
                     Err(RemoveChain::ChainCompleted)
                 } else {
                     // chain is not completed
+                    self.emit_progress_event(network, SseSyncChainStatus::Advanced);
                     // attempt to request more batches
                     self.request_batches(network)?;
                     // attempt to process more batches
@@ -831,7 +858,10 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
         }
 
         // update the state
-        self.state = ChainSyncingState::Syncing;
+        if self.state != ChainSyncingState::Syncing {
+            self.state = ChainSyncingState::Syncing;
+            self.emit_progress_event(network, SseSyncChainStatus::Started);
+        }
 
         // begin requesting blocks from the peer pool, until all peers are exhausted.
         self.request_batches(network)?;
@@ -1071,6 +1101,9 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
             })
             .collect::<Vec<_>>();
         idle_peers.shuffle(&mut rng);
+        // peers are popped from the back, so place the lowest latency peers last
+        let latency_keys = network.peer_latency_keys(&idle_peers);
+        idle_peers.sort_by_key(|peer| Reverse(latency_keys[peer]));
 
         // check if we have the batch for our optimistic start. If not, request it first.
         // We wait for this batch before requesting any other batches.
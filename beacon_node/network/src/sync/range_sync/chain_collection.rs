@@ -8,10 +8,11 @@ use super::chain::{ChainId, ProcessingResult, RemoveChain, SyncingChain};
 use super::sync_type::RangeSyncType;
 use crate::metrics;
 use crate::sync::network_context::SyncNetworkContext;
+use beacon_chain::events::SseSyncChainStatus;
 use beacon_chain::BeaconChainTypes;
 use fnv::FnvHashMap;
-use lighthouse_network::PeerId;
-use lighthouse_network::SyncInfo;
+use lighthouse_network::types::{SyncChainCandidate, SyncChainKind};
+use lighthouse_network::{PeerId, PeerInfo, SyncInfo, SyncStatus};
 use slog::{crit, debug, error};
 use smallvec::SmallVec;
 use std::collections::hash_map::Entry;
@@ -26,6 +27,64 @@ const PARALLEL_HEAD_CHAINS: usize = 2;
 /// Minimum work we require a finalized chain to do before picking a chain with more peers.
 const MIN_FINALIZED_CHAIN_PROCESSED_EPOCHS: u64 = 10;
 
+/// The peer score at which a peer carries twice the weight of a new peer when weighting chains. A
+/// peer with the negation of this score is about to be disconnected and carries no weight.
+const PEER_SCORE_WEIGHT_SCALE: f64 = 20.0;
+
+/// The weight, relative to a peer that agrees with our finalized checkpoint, of a peer whose
+/// finalized checkpoint can't be checked against ours.
+const UNVERIFIED_PEER_WEIGHT: f64 = 0.5;
+
+/// The weight by which a head chain must exceed a syncing head chain to replace it, equivalent to
+/// a single agreeing peer. This prevents switching between chains as peer scores fluctuate.
+const HEAD_CHAIN_SWITCH_MARGIN: f64 = 1.0;
+
+/// How a peer's finalized checkpoint relates to ours.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FinalizedAgreement {
+    /// The peer's finalized checkpoint is ours, or a block we know of.
+    Agrees,
+    /// The peer has finalized a different block at our finalized epoch.
+    Conflicts,
+    /// The peer's finalized checkpoint is unknown to us.
+    Unverified,
+}
+
+impl FinalizedAgreement {
+    fn new(local: &SyncInfo, remote: &SyncInfo, is_block_known: impl Fn(&Hash256) -> bool) -> Self {
+        if remote.finalized_root == local.finalized_root || is_block_known(&remote.finalized_root) {
+            FinalizedAgreement::Agrees
+        } else if remote.finalized_epoch == local.finalized_epoch {
+            FinalizedAgreement::Conflicts
+        } else {
+            FinalizedAgreement::Unverified
+        }
+    }
+
+    fn weight(&self) -> f64 {
+        match self {
+            FinalizedAgreement::Agrees => 1.0,
+            FinalizedAgreement::Conflicts => 0.0,
+            FinalizedAgreement::Unverified => UNVERIFIED_PEER_WEIGHT,
+        }
+    }
+}
+
+/// Scales the weight of a peer by its score, from nothing for a peer about to be disconnected to
+/// double for a peer with a high or trusted score.
+fn peer_score_weight(score: f64) -> f64 {
+    (1.0 + score / PEER_SCORE_WEIGHT_SCALE).clamp(0.0, 2.0)
+}
+
+/// The weight of a chain amongst the other candidate chains.
+#[derive(Debug, Default, Clone, Copy)]
+struct ChainWeight {
+    /// The number of peers of the chain which agree with our finalized checkpoint.
+    agreeing_peers: usize,
+    /// The sum of the weights of the peers of the chain.
+    weight: f64,
+}
+
 /// The state of the long range/batch sync.
 #[derive(Clone)]
 pub enum RangeSyncState {
@@ -196,7 +255,7 @@ impl<T: BeaconChainTypes, C: BlockStorage> ChainCollection<T, C> {
         awaiting_head_peers: &mut HashMap<PeerId, SyncInfo>,
     ) {
         // Remove any outdated finalized/head chains
-        self.purge_outdated_chains(local, awaiting_head_peers);
+        self.purge_outdated_chains(network, local, awaiting_head_peers);
 
         let local_head_epoch = local.head_slot.epoch(T::EthSpec::slots_per_epoch());
         // Choose the best finalized chain if one needs to be selected.
@@ -204,13 +263,10 @@ impl<T: BeaconChainTypes, C: BlockStorage> ChainCollection<T, C> {
 
         if !matches!(self.state, RangeSyncState::Finalized(_)) {
             // Handle head syncing chains if there are no finalized chains left.
-            self.update_head_chains(
-                network,
-                local.finalized_epoch,
-                local_head_epoch,
-                awaiting_head_peers,
-            );
+            self.update_head_chains(network, local, local_head_epoch, awaiting_head_peers);
         }
+
+        self.publish_candidates(network, local);
     }
 
     pub fn state(
@@ -313,6 +369,9 @@ impl<T: BeaconChainTypes, C: BlockStorage> ChainCollection<T, C> {
                     // this happens only if sending a batch over the `network` fails a lot
                     error!(self.log, "Chain removed while switching chains"; "chain" => new_id, "reason" => ?remove_reason);
                 }
+                if chain.is_syncing() {
+                    chain.emit_progress_event(network, SseSyncChainStatus::Removed);
+                }
                 self.finalized_chains.remove(&new_id);
                 self.on_chain_removed(&new_id, true, RangeSyncType::Finalized);
             }
@@ -320,13 +379,17 @@ impl<T: BeaconChainTypes, C: BlockStorage> ChainCollection<T, C> {
     }
 
     /// Start syncing any head chains if required.
+    ///
+    /// Head chains are synced in order of their weight, which accounts for the number of peers of
+    /// each chain, their scores and whether they agree with our finalized checkpoint.
     fn update_head_chains(
         &mut self,
         network: &mut SyncNetworkContext<T>,
-        local_epoch: Epoch,
+        local: &SyncInfo,
         local_head_epoch: Epoch,
         awaiting_head_peers: &mut HashMap<PeerId, SyncInfo>,
     ) {
+        let local_epoch = local.finalized_epoch;
         // Include the awaiting head peers
         for (peer_id, peer_sync_info) in awaiting_head_peers.drain() {
             debug!(self.log, "including head peer");
@@ -346,14 +409,24 @@ impl<T: BeaconChainTypes, C: BlockStorage> ChainCollection<T, C> {
             return;
         }
 
-        // Order chains by available peers, if two chains have the same number of peers, prefer one
-        // that is already syncing
+        // Order chains by weight, favouring those already syncing, then by number of peers
         let mut preferred_ids = self
             .head_chains
             .iter()
-            .map(|(id, chain)| (chain.available_peers(), !chain.is_syncing(), *id))
+            .map(|(id, chain)| {
+                let mut weight = self.chain_weight(chain, network, local).weight;
+                if chain.is_syncing() {
+                    weight += HEAD_CHAIN_SWITCH_MARGIN;
+                }
+                (weight, chain.available_peers(), *id)
+            })
             .collect::<Vec<_>>();
-        preferred_ids.sort_unstable();
+        preferred_ids.sort_unstable_by(|(a_weight, a_peers, a_id), (b_weight, b_peers, b_id)| {
+            b_weight
+                .total_cmp(a_weight)
+                .then(b_peers.cmp(a_peers))
+                .then(a_id.cmp(b_id))
+        });
 
         let mut syncing_chains = SmallVec::<[u64; PARALLEL_HEAD_CHAINS]>::new();
         for (_, _, id) in preferred_ids {
@@ -366,6 +439,9 @@ impl<T: BeaconChainTypes, C: BlockStorage> ChainCollection<T, C> {
                 if let Err(remove_reason) =
                     chain.start_syncing(network, local_epoch, local_head_epoch)
                 {
+                    if chain.is_syncing() {
+                        chain.emit_progress_event(network, SseSyncChainStatus::Removed);
+                    }
                     self.head_chains.remove(&id);
                     if remove_reason.is_critical() {
                         crit!(self.log, "Chain removed while switching head chains"; "chain" => id, "reason" => ?remove_reason);
@@ -388,6 +464,75 @@ impl<T: BeaconChainTypes, C: BlockStorage> ChainCollection<T, C> {
         };
     }
 
+    /// Computes the weight of `chain` from its peers' scores and their agreement with our finalized
+    /// checkpoint.
+    fn chain_weight(
+        &self,
+        chain: &SyncingChain<T>,
+        network: &SyncNetworkContext<T>,
+        local: &SyncInfo,
+    ) -> ChainWeight {
+        let peers = network.network_globals().peers.read();
+        chain
+            .peers()
+            .filter_map(|peer_id| peers.peer_info(&peer_id))
+            .fold(ChainWeight::default(), |mut chain_weight, peer_info| {
+                let agreement = self.finalized_agreement(peer_info, local);
+                if agreement == FinalizedAgreement::Agrees {
+                    chain_weight.agreeing_peers += 1;
+                }
+                chain_weight.weight +=
+                    agreement.weight() * peer_score_weight(peer_info.score().score());
+                chain_weight
+            })
+    }
+
+    fn finalized_agreement(
+        &self,
+        peer_info: &PeerInfo<T::EthSpec>,
+        local: &SyncInfo,
+    ) -> FinalizedAgreement {
+        match peer_info.sync_status() {
+            SyncStatus::Synced { info }
+            | SyncStatus::Advanced { info }
+            | SyncStatus::Behind { info } => {
+                FinalizedAgreement::new(local, info, |root| self.beacon_chain.is_block_known(root))
+            }
+            SyncStatus::IrrelevantPeer => FinalizedAgreement::Conflicts,
+            SyncStatus::Unknown => FinalizedAgreement::Unverified,
+        }
+    }
+
+    /// Publishes the current chains to the network globals, for the HTTP API.
+    fn publish_candidates(&self, network: &SyncNetworkContext<T>, local: &SyncInfo) {
+        let candidates = self
+            .finalized_chains
+            .values()
+            .map(|chain| (SyncChainKind::Finalized, chain))
+            .chain(
+                self.head_chains
+                    .values()
+                    .map(|chain| (SyncChainKind::Head, chain)),
+            )
+            .map(|(kind, chain)| {
+                let chain_weight = self.chain_weight(chain, network, local);
+                SyncChainCandidate {
+                    id: chain.get_id(),
+                    kind,
+                    start_epoch: chain.start_epoch,
+                    target_head_slot: chain.target_head_slot,
+                    target_head_root: chain.target_head_root,
+                    peers: chain.available_peers() as u64,
+                    agreeing_peers: chain_weight.agreeing_peers as u64,
+                    weight: chain_weight.weight,
+                    syncing: chain.is_syncing(),
+                    processed_epochs: chain.processed_epochs(),
+                }
+            })
+            .collect();
+        *network.network_globals().sync_chains.write() = candidates;
+    }
+
     /// Returns if `true` if any finalized chains exist, `false` otherwise.
     pub fn is_finalizing_sync(&self) -> bool {
         !self.finalized_chains.is_empty()
@@ -398,6 +543,7 @@ impl<T: BeaconChainTypes, C: BlockStorage> ChainCollection<T, C> {
     /// finalized block slot. Peers that would create outdated chains are removed too.
     pub fn purge_outdated_chains(
         &mut self,
+        network: &SyncNetworkContext<T>,
         local_info: &SyncInfo,
         awaiting_head_peers: &mut HashMap<PeerId, SyncInfo>,
     ) {
@@ -420,34 +566,42 @@ impl<T: BeaconChainTypes, C: BlockStorage> ChainCollection<T, C> {
         // Remove chains that are out-dated
         let mut removed_chains = Vec::new();
         removed_chains.extend(self.finalized_chains.iter().filter_map(|(id, chain)| {
-            if is_outdated(&chain.target_head_slot, &chain.target_head_root)
-                || chain.available_peers() == 0
-            {
+            let outdated = is_outdated(&chain.target_head_slot, &chain.target_head_root);
+            if outdated || chain.available_peers() == 0 {
                 debug!(log_ref, "Purging out of finalized chain"; &chain);
-                Some((*id, chain.is_syncing(), RangeSyncType::Finalized))
+                Some((*id, chain.is_syncing(), RangeSyncType::Finalized, outdated))
             } else {
                 None
             }
         }));
 
         removed_chains.extend(self.head_chains.iter().filter_map(|(id, chain)| {
-            if is_outdated(&chain.target_head_slot, &chain.target_head_root)
-                || chain.available_peers() == 0
-            {
+            let outdated = is_outdated(&chain.target_head_slot, &chain.target_head_root);
+            if outdated || chain.available_peers() == 0 {
                 debug!(log_ref, "Purging out of date head chain"; &chain);
-                Some((*id, chain.is_syncing(), RangeSyncType::Head))
+                Some((*id, chain.is_syncing(), RangeSyncType::Head, outdated))
             } else {
                 None
             }
         }));
 
         // update the state of the collection
-        for (id, was_syncing, sync_type) in removed_chains {
+        for (id, was_syncing, sync_type, outdated) in removed_chains {
             // remove each chain, updating the state for each removal.
-            match sync_type {
+            let removed = match sync_type {
                 RangeSyncType::Finalized => self.finalized_chains.remove(&id),
                 RangeSyncType::Head => self.head_chains.remove(&id),
             };
+            // Only chains which were reported as started are reported as removed. A chain whose
+            // target is now known or finalized has been synced, otherwise it ran out of peers.
+            if let Some(chain) = removed.filter(|_| was_syncing) {
+                let status = if outdated {
+                    SseSyncChainStatus::Completed
+                } else {
+                    SseSyncChainStatus::Removed
+                };
+                chain.emit_progress_event(network, status);
+            }
             self.on_chain_removed(&id, was_syncing, sync_type);
         }
     }
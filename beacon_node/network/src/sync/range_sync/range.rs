@@ -48,6 +48,7 @@ use crate::status::ToStatusMessage;
 use crate::sync::network_context::SyncNetworkContext;
 use crate::sync::BatchProcessResult;
 use beacon_chain::block_verification_types::RpcBlock;
+use beacon_chain::events::SseSyncChainStatus;
 use beacon_chain::{BeaconChain, BeaconChainTypes};
 use lighthouse_network::rpc::GoodbyeReason;
 use lighthouse_network::service::api_types::Id;
@@ -346,6 +347,16 @@ where
             }
         }
 
+        // Only chains which were reported as started are reported as removed.
+        if chain.is_syncing() {
+            let status = if matches!(remove_reason, RemoveChain::ChainCompleted) {
+                SseSyncChainStatus::Completed
+            } else {
+                SseSyncChainStatus::Removed
+            };
+            chain.emit_progress_event(network, status);
+        }
+
         metrics::inc_counter_vec_by(
             &metrics::SYNCING_CHAINS_DROPPED_BLOCKS,
             &[sync_type.as_str()],
@@ -393,13 +404,16 @@ mod tests {
     use crate::sync::network_context::{BlockOrBlob, RangeRequestId};
     use beacon_chain::builder::Witness;
     use beacon_chain::eth1_chain::CachingEth1Backend;
+    use beacon_chain::events::{EventKind, SseSyncType};
     use beacon_chain::parking_lot::RwLock;
     use beacon_chain::test_utils::{BeaconChainHarness, EphemeralHarnessType};
     use beacon_chain::EngineState;
     use beacon_processor::WorkEvent as BeaconWorkEvent;
     use lighthouse_network::service::api_types::SyncRequestId;
+    use lighthouse_network::types::SyncChainKind;
     use lighthouse_network::{
         rpc::StatusMessage, service::api_types::AppRequestId, NetworkConfig, NetworkGlobals,
+        SyncStatus,
     };
     use slog::{o, Drain};
     use slot_clock::TestingSlotClock;
@@ -756,6 +770,129 @@ mod tests {
         range.assert_state(RangeSyncType::Finalized);
     }
 
+    #[test]
+    fn sync_progress_events() {
+        let (mut rig, mut range) = range(false);
+        let mut events = rig
+            .cx
+            .chain
+            .event_handler
+            .as_ref()
+            .expect("harness has an event handler")
+            .subscribe_sync_progress();
+
+        // A new head chain starts syncing.
+        let (head_peer, local_info, remote_info) = rig.head_peer();
+        range.add_peer(&mut rig.cx, local_info, head_peer, remote_info.clone());
+        range.assert_state(RangeSyncType::Head);
+
+        let Ok(EventKind::SyncProgress(started)) = events.try_recv() else {
+            panic!("expected a sync progress event");
+        };
+        assert_eq!(started.status, SseSyncChainStatus::Started);
+        assert_eq!(started.sync_type, SseSyncType::Head);
+        assert_eq!(started.target_head_root, remote_info.head_root);
+        assert_eq!(started.target_head_slot, remote_info.head_slot);
+        assert_eq!(started.peers, 1);
+
+        // The chain is removed when its only peer disconnects.
+        range.remove_peer(&mut rig.cx, &head_peer);
+        let Ok(EventKind::SyncProgress(removed)) = events.try_recv() else {
+            panic!("expected a sync progress event");
+        };
+        assert_eq!(removed.status, SseSyncChainStatus::Removed);
+        assert_eq!(removed.chain_id, started.chain_id);
+        assert!(events.try_recv().is_err());
+    }
+
+    #[test]
+    fn sync_progress_events_for_purged_chains() {
+        let (mut rig, mut range) = range(false);
+        let mut events = rig
+            .cx
+            .chain
+            .event_handler
+            .as_ref()
+            .expect("harness has an event handler")
+            .subscribe_sync_progress();
+
+        let (head_peer, local_info, remote_info) = rig.head_peer();
+        range.add_peer(&mut rig.cx, local_info, head_peer, remote_info.clone());
+        let Ok(EventKind::SyncProgress(started)) = events.try_recv() else {
+            panic!("expected a sync progress event");
+        };
+        assert_eq!(started.status, SseSyncChainStatus::Started);
+
+        // The chain's target becomes known, so the next update purges it as completed.
+        rig.chain.remember_block(remote_info.head_root);
+        let (other_peer, local_info, other_info) = rig.head_peer();
+        range.add_peer(&mut rig.cx, local_info, other_peer, other_info);
+
+        let Ok(EventKind::SyncProgress(completed)) = events.try_recv() else {
+            panic!("expected a sync progress event");
+        };
+        assert_eq!(completed.status, SseSyncChainStatus::Completed);
+        assert_eq!(completed.chain_id, started.chain_id);
+
+        // Only the replacement chain starting is reported afterwards.
+        let Ok(EventKind::SyncProgress(next)) = events.try_recv() else {
+            panic!("expected a sync progress event");
+        };
+        assert_eq!(next.status, SseSyncChainStatus::Started);
+        assert_ne!(next.chain_id, started.chain_id);
+        assert!(events.try_recv().is_err());
+    }
+
+    #[test]
+    fn head_chains_synced_by_weight() {
+        let (mut rig, mut range) = range(false);
+
+        // Create three head chains, served by one, two and three peers respectively.
+        let mut chain_roots = vec![];
+        for chain_peers in 1..=3 {
+            let (_, local_info, remote_info) = rig.head_peer();
+            chain_roots.push(remote_info.head_root);
+            for _ in 0..chain_peers {
+                let peer_id = rig
+                    .globals
+                    .peers
+                    .write()
+                    .__add_connected_peer_testing_only(false, &rig.cx.chain.spec);
+                rig.globals
+                    .peers
+                    .write()
+                    .peer_info_mut(&peer_id)
+                    .unwrap()
+                    .update_sync_status(SyncStatus::Advanced {
+                        info: remote_info.clone(),
+                    });
+                range.add_peer(
+                    &mut rig.cx,
+                    local_info.clone(),
+                    peer_id,
+                    remote_info.clone(),
+                );
+            }
+        }
+        range.assert_state(RangeSyncType::Head);
+
+        // The two chains with the most weight are synced.
+        let candidates = rig.globals.sync_chains.read().clone();
+        assert_eq!(candidates.len(), 3);
+        for (chain_peers, root) in (1..=3).zip(chain_roots) {
+            let candidate = candidates
+                .iter()
+                .find(|candidate| candidate.target_head_root == root)
+                .expect("chain is a candidate");
+            assert_eq!(candidate.kind, SyncChainKind::Head);
+            assert_eq!(candidate.peers, chain_peers);
+            // All peers agree with our finalized checkpoint and have a neutral score.
+            assert_eq!(candidate.agreeing_peers, chain_peers);
+            assert_eq!(candidate.weight, chain_peers as f64);
+            assert_eq!(candidate.syncing, chain_peers > 1);
+        }
+    }
+
     #[test]
     fn state_update_while_purging() {
         // NOTE: this is a regression test.
@@ -1,10 +1,9 @@
 use crate::network_beacon_processor::NetworkBeaconProcessor;
-use crate::sync::block_lookups::{
-    BlockLookupSummary, PARENT_DEPTH_TOLERANCE, SINGLE_BLOCK_LOOKUP_MAX_ATTEMPTS,
-};
+use crate::sync::block_lookups::{BlockLookupSummary, SINGLE_BLOCK_LOOKUP_MAX_ATTEMPTS};
+use crate::sync::custody_backfill::CustodyBackfillState;
 use crate::sync::{
     manager::{BlockProcessType, BlockProcessingResult, SyncManager},
-    peer_sampling::SamplingConfig,
+    peer_sampling::{SamplingConfig, MAX_ACTIVE_SAMPLING_REQUESTS},
     SamplingId, SyncMessage,
 };
 use crate::NetworkMessage;
@@ -34,10 +33,11 @@ use lighthouse_network::{
         SamplingRequester, SingleLookupReqId, SyncRequestId,
     },
     types::SyncState,
-    NetworkConfig, NetworkGlobals, PeerId,
+    NetworkConfig, NetworkGlobals, PeerId, SyncInfo, DEFAULT_LOOKUP_MAX_PARENT_DEPTH,
 };
 use slog::info;
 use slot_clock::{SlotClock, TestingSlotClock};
+use store::metadata::DataColumnCustodyInfo;
 use tokio::sync::mpsc;
 use types::{
     data_column_sidecar::ColumnIndex,
@@ -48,13 +48,17 @@ use types::{
 
 const D: Duration = Duration::new(0, 0);
 const PARENT_FAIL_TOLERANCE: u8 = SINGLE_BLOCK_LOOKUP_MAX_ATTEMPTS;
+const PARENT_DEPTH_TOLERANCE: usize = DEFAULT_LOOKUP_MAX_PARENT_DEPTH;
 const SAMPLING_REQUIRED_SUCCESSES: usize = 2;
 
 type DCByRootIds = Vec<DCByRootId>;
 type DCByRootId = (SyncRequestId, Vec<ColumnIndex>);
 
+#[derive(Default)]
 struct TestRigConfig {
     peer_das_enabled: bool,
+    lookup_max_parent_depth: Option<usize>,
+    persist_pending_lookups: bool,
 }
 
 impl TestRig {
@@ -70,11 +74,16 @@ impl TestRig {
 
         // Use `fork_from_env` logic to set correct fork epochs
         let mut spec = test_spec::<E>();
+        let mut network_config = NetworkConfig::default();
 
         if let Some(config) = config {
             if config.peer_das_enabled {
                 spec.eip7594_fork_epoch = Some(Epoch::new(0));
             }
+            if let Some(depth) = config.lookup_max_parent_depth {
+                network_config.lookup_max_parent_depth = depth;
+            }
+            network_config.persist_pending_lookups = config.persist_pending_lookups;
         }
 
         // Initialise a new beacon chain
@@ -96,11 +105,10 @@ impl TestRig {
         let (sync_tx, sync_rx) = mpsc::unbounded_channel::<SyncMessage<E>>();
         // TODO(das): make the generation of the ENR use the deterministic rng to have consistent
         // column assignments
-        let network_config = Arc::new(NetworkConfig::default());
         let globals = Arc::new(NetworkGlobals::new_test_globals(
             Vec::new(),
             &log,
-            network_config,
+            Arc::new(network_config),
             chain.spec.clone(),
         ));
         let (beacon_processor, beacon_processor_rx) = NetworkBeaconProcessor::null_for_testing(
@@ -160,6 +168,7 @@ impl TestRig {
     fn test_setup_after_peerdas() -> Option<Self> {
         let r = Self::test_setup_with_config(Some(TestRigConfig {
             peer_das_enabled: true,
+            ..Default::default()
         }));
         if r.after_deneb() {
             Some(r)
@@ -168,6 +177,49 @@ impl TestRig {
         }
     }
 
+    /// Replace the sync manager with a new one on the same chain, as on a restart.
+    fn restart_sync_manager(&mut self) {
+        let (network_tx, network_rx) = mpsc::unbounded_channel();
+        let (sync_tx, sync_rx) = mpsc::unbounded_channel::<SyncMessage<E>>();
+        let (beacon_processor, beacon_processor_rx) = NetworkBeaconProcessor::null_for_testing(
+            self.network_globals.clone(),
+            sync_tx,
+            self.harness.chain.clone(),
+            self.harness.runtime.task_executor.clone(),
+            self.log.clone(),
+        );
+
+        self.beacon_processor_rx = beacon_processor_rx;
+        self.beacon_processor_rx_queue.clear();
+        self.network_rx = network_rx;
+        self.network_rx_queue.clear();
+        self.sync_rx = sync_rx;
+        self.sync_manager = SyncManager::new(
+            self.harness.chain.clone(),
+            network_tx,
+            beacon_processor.into(),
+            mpsc::unbounded_channel().1,
+            SamplingConfig::Custom {
+                required_successes: vec![SAMPLING_REQUIRED_SUCCESSES],
+            },
+            self.log.clone(),
+        );
+    }
+
+    /// Reconnect `peer_id`, at the same head as the local chain.
+    fn reconnect_peer(&mut self, peer_id: PeerId) {
+        let status = self.harness.chain.status_message();
+        self.send_sync_message(SyncMessage::AddPeer(
+            peer_id,
+            SyncInfo {
+                head_slot: status.head_slot,
+                head_root: status.head_root,
+                finalized_epoch: status.finalized_epoch,
+                finalized_root: status.finalized_root,
+            },
+        ));
+    }
+
     fn log(&self, msg: &str) {
         info!(self.log, "TEST_RIG"; "msg" => msg);
     }
@@ -1712,6 +1764,47 @@ fn test_parent_lookup_too_deep_grow_ancestor() {
     rig.assert_failed_chain(chain_hash);
 }
 
+#[test]
+fn test_parent_lookup_too_deep_configured_depth() {
+    let max_parent_depth = 8;
+    let mut rig = TestRig::test_setup_with_config(Some(TestRigConfig {
+        lookup_max_parent_depth: Some(max_parent_depth),
+        ..Default::default()
+    }));
+    let mut blocks = rig.rand_blockchain(max_parent_depth);
+
+    let peer_id = rig.new_connected_peer();
+    let trigger_block = blocks.pop().unwrap();
+    let chain_hash = trigger_block.canonical_root();
+    rig.trigger_unknown_parent_block(peer_id, trigger_block);
+
+    for block in blocks.into_iter().rev() {
+        let id = rig.expect_block_parent_request(block.canonical_root());
+        rig.parent_lookup_block_response(id, peer_id, Some(block.clone()));
+        rig.parent_lookup_block_response(id, peer_id, None);
+        rig.expect_block_process(ResponseType::Block);
+        rig.parent_block_processed(
+            chain_hash,
+            BlockProcessingResult::Err(BlockError::ParentUnknown {
+                parent_root: block.parent_root(),
+            }),
+        )
+    }
+
+    // The chain reaches the configured depth long before the default, and is handed to range sync
+    rig.drain_sync_rx();
+    assert_eq!(
+        rig.active_range_sync_chain(),
+        (
+            RangeSyncType::Head,
+            Slot::new(0),
+            Slot::new(max_parent_depth as u64 - 1)
+        )
+    );
+    rig.expect_no_penalty_for(peer_id);
+    rig.assert_failed_chain(chain_hash);
+}
+
 #[test]
 fn test_parent_lookup_too_deep_grow_tip() {
     let mut rig = TestRig::test_setup();
@@ -1750,6 +1843,46 @@ fn test_parent_lookup_too_deep_grow_tip() {
     rig.assert_failed_chain(tip.canonical_root());
 }
 
+#[test]
+fn test_parent_lookup_too_deep_tip_not_downloaded() {
+    let Some(mut rig) = TestRig::test_setup_after_deneb() else {
+        return;
+    };
+    let blocks = rig.rand_blockchain(PARENT_DEPTH_TOLERANCE - 1);
+    let deepest_downloaded_slot = blocks.last().unwrap().slot();
+    let (tip, blobs) = rig
+        .block_with_parent_and_blobs(blocks.last().unwrap().canonical_root(), NumBlobs::Number(1));
+    let tip_root = tip.canonical_root();
+    let peer_id = rig.new_connected_peer();
+
+    // The tip lookup is created from a gossip blob, and the tip is then attested to. Its block is
+    // never served, so only its ancestors are downloaded.
+    rig.trigger_unknown_parent_blob(peer_id, blobs[0].clone());
+    rig.trigger_unknown_block_from_attestation(tip_root, peer_id);
+
+    for block in blocks.into_iter().rev() {
+        let id = rig.expect_block_parent_request(block.canonical_root());
+        rig.parent_lookup_block_response(id, peer_id, Some(block.clone()));
+        rig.parent_lookup_block_response(id, peer_id, None);
+        rig.expect_block_process(ResponseType::Block);
+        rig.parent_block_processed(
+            tip_root,
+            BlockProcessingResult::Err(BlockError::ParentUnknown {
+                parent_root: block.parent_root(),
+            }),
+        )
+    }
+
+    // Range sync targets the most recent downloaded block of the chain, rather than the local head
+    rig.drain_sync_rx();
+    assert_eq!(
+        rig.active_range_sync_chain(),
+        (RangeSyncType::Head, Slot::new(0), deepest_downloaded_slot)
+    );
+    rig.expect_no_penalty_for(peer_id);
+    rig.assert_failed_chain(tip_root);
+}
+
 #[test]
 fn test_lookup_peer_disconnected_no_peers_left_while_request() {
     let mut rig = TestRig::test_setup();
@@ -1840,6 +1973,30 @@ fn test_single_block_lookup_ignored_response() {
     rig.expect_no_active_lookups_empty_network();
 }
 
+#[test]
+fn test_single_block_lookup_import_timeout() {
+    let mut rig = TestRig::test_setup();
+
+    let block = rig.rand_block();
+    let block_root = block.canonical_root();
+    let peer_id = rig.new_connected_peer();
+
+    // Trigger the request
+    rig.trigger_unknown_block_from_attestation(block_root, peer_id);
+    let id = rig.expect_block_lookup_request(block_root);
+    rig.single_lookup_block_response(id, peer_id, Some(block.into()));
+    rig.expect_block_process(ResponseType::Block);
+
+    // The import times out. The peer should not be penalized and the block retried.
+    rig.single_block_component_processed(
+        id.lookup_id,
+        BlockError::ImportTimeout(Duration::from_secs(60)).into(),
+    );
+    rig.expect_no_penalty_for(peer_id);
+    rig.expect_block_lookup_request(block_root);
+    assert_eq!(rig.active_single_lookups_count(), 1);
+}
+
 #[test]
 fn test_parent_lookup_ignored_response() {
     let mut rig = TestRig::test_setup();
@@ -2068,6 +2225,42 @@ fn sampling_avoid_retrying_same_peer() {
     r.expect_empty_network();
 }
 
+#[test]
+fn sampling_requests_queued_by_slot() {
+    let Some(mut r) = TestRig::test_setup_after_peerdas() else {
+        return;
+    };
+    let _supernode = r.new_connected_supernode_peer();
+    let (block, data_columns) = r.rand_block_and_data_columns();
+    let block_root = block.canonical_root();
+    r.trigger_sample_block(block_root, block.slot());
+    let sampling_ids = r.expect_data_columns_by_root_requests(block_root, 1);
+
+    // Fill the remaining capacity for active sampling requests.
+    for _ in 1..MAX_ACTIVE_SAMPLING_REQUESTS {
+        r.trigger_sample_block(Hash256::random(), Slot::new(0));
+    }
+
+    // Further requests are queued, ignoring duplicates.
+    let old_block_root = Hash256::random();
+    let recent_block_root = Hash256::random();
+    r.trigger_sample_block(old_block_root, Slot::new(1));
+    r.trigger_sample_block(recent_block_root, Slot::new(2));
+    r.trigger_sample_block(recent_block_root, Slot::new(2));
+    let active = r.sync_manager.active_sampling_requests();
+    assert_eq!(active.len(), MAX_ACTIVE_SAMPLING_REQUESTS);
+    assert!(!active.contains(&old_block_root));
+    assert!(!active.contains(&recent_block_root));
+
+    // Completing a request starts the queued request with the highest slot.
+    r.complete_valid_sampling_column_requests(sampling_ids, data_columns);
+    let active = r.sync_manager.active_sampling_requests();
+    assert_eq!(active.len(), MAX_ACTIVE_SAMPLING_REQUESTS);
+    assert!(!active.contains(&block_root));
+    assert!(!active.contains(&old_block_root));
+    assert!(active.contains(&recent_block_root));
+}
+
 #[test]
 fn sampling_batch_requests() {
     let Some(mut r) = TestRig::test_setup_after_peerdas() else {
@@ -2161,6 +2354,70 @@ fn custody_lookup_happy_path() {
     r.expect_no_active_lookups();
 }
 
+#[test]
+fn pending_lookups_are_restored_after_restart() {
+    let mut r = TestRig::test_setup_with_config(Some(TestRigConfig {
+        persist_pending_lookups: true,
+        ..Default::default()
+    }));
+    let peer_id = r.new_connected_peer();
+    let block_root = Hash256::repeat_byte(1);
+    let queued_block_root = Hash256::repeat_byte(2);
+    r.trigger_unknown_block_from_attestation(block_root, peer_id);
+    r.expect_block_lookup_request(block_root);
+
+    // Shut down while the reprocess queue holds work for another unknown block.
+    r.sync_manager
+        .persist_pending_lookups(vec![queued_block_root]);
+    r.restart_sync_manager();
+    r.assert_single_lookups_count(0);
+
+    // Both lookups resume once the peer reconnects.
+    r.reconnect_peer(peer_id);
+    r.assert_single_lookups_count(2);
+    r.expect_block_lookup_request(block_root);
+    r.expect_block_lookup_request(queued_block_root);
+
+    // The lookups are only restored once.
+    r.restart_sync_manager();
+    r.reconnect_peer(peer_id);
+    r.assert_single_lookups_count(0);
+}
+
+#[test]
+fn custody_backfill_completes_without_missing_columns() {
+    let Some(mut r) = TestRig::test_setup_after_peerdas() else {
+        return;
+    };
+    let store = r.harness.chain.store.clone();
+    store
+        .store_data_column_custody_info(&DataColumnCustodyInfo {
+            custody_subnet_count: r.harness.spec.data_column_sidecar_subnet_count,
+            custody_backfill_slot: Some(Slot::new(2 * E::slots_per_epoch())),
+        })
+        .unwrap();
+    assert_eq!(
+        r.sync_manager.custody_backfill_state(),
+        &CustodyBackfillState::Pending
+    );
+
+    // No block is missing any columns, so custody backfill completes without requesting any.
+    r.sync_manager.continue_custody_backfill();
+    assert_eq!(
+        r.sync_manager.custody_backfill_state(),
+        &CustodyBackfillState::Completed
+    );
+    assert_eq!(
+        store
+            .load_data_column_custody_info()
+            .unwrap()
+            .unwrap()
+            .custody_backfill_slot,
+        None
+    );
+    r.expect_empty_network();
+}
+
 // TODO(das): Test retries of DataColumnByRoot:
 // - Expect request for column_index
 // - Respond with bad data
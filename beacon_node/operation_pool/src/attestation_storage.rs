@@ -1,5 +1,6 @@
 use crate::AttestationStats;
 use itertools::Itertools;
+use rayon::prelude::*;
 use std::collections::{BTreeMap, HashMap};
 use types::{
     attestation::{AttestationBase, AttestationElectra},
@@ -336,68 +337,72 @@ impl<E: EthSpec> AttestationMap<E> {
         let Some(attestation_map) = self.checkpoint_map.get_mut(&checkpoint_key) else {
             return;
         };
-        for compact_indexed_attestations in attestation_map.attestations.values_mut() {
-            let unaggregated_attestations = std::mem::take(compact_indexed_attestations);
-            let mut aggregated_attestations: Vec<CompactIndexedAttestation<E>> = vec![];
-
-            // Aggregate the best attestations for each committee and leave the rest.
-            let mut best_attestations_by_committee: BTreeMap<
-                u64,
-                CompactIndexedAttestationElectra<E>,
-            > = BTreeMap::new();
-
-            for committee_attestation in unaggregated_attestations {
-                let mut electra_attestation = match committee_attestation {
-                    CompactIndexedAttestation::Electra(att)
-                        if att.committee_bits.num_set_bits() == 1 =>
-                    {
-                        att
-                    }
-                    CompactIndexedAttestation::Electra(att) => {
-                        // Aggregate already covers multiple committees, leave it as-is.
-                        aggregated_attestations.push(CompactIndexedAttestation::Electra(att));
-                        continue;
-                    }
-                    CompactIndexedAttestation::Base(att) => {
-                        // Leave as-is.
-                        aggregated_attestations.push(CompactIndexedAttestation::Base(att));
-                        continue;
-                    }
-                };
-                if let Some(committee_index) = electra_attestation.committee_index() {
-                    if let Some(existing_attestation) =
-                        best_attestations_by_committee.get_mut(&committee_index)
-                    {
-                        // Search for the best (most aggregation bits) attestation for this committee
-                        // index.
-                        if electra_attestation.aggregation_bits.num_set_bits()
-                            > existing_attestation.aggregation_bits.num_set_bits()
+        // Each attestation data is aggregated independently, so spread them across threads.
+        attestation_map.attestations.par_iter_mut().for_each(
+            |(_, compact_indexed_attestations)| {
+                let unaggregated_attestations = std::mem::take(compact_indexed_attestations);
+                let mut aggregated_attestations: Vec<CompactIndexedAttestation<E>> = vec![];
+
+                // Aggregate the best attestations for each committee and leave the rest.
+                let mut best_attestations_by_committee: BTreeMap<
+                    u64,
+                    CompactIndexedAttestationElectra<E>,
+                > = BTreeMap::new();
+
+                for committee_attestation in unaggregated_attestations {
+                    let mut electra_attestation = match committee_attestation {
+                        CompactIndexedAttestation::Electra(att)
+                            if att.committee_bits.num_set_bits() == 1 =>
+                        {
+                            att
+                        }
+                        CompactIndexedAttestation::Electra(att) => {
+                            // Aggregate already covers multiple committees, leave it as-is.
+                            aggregated_attestations.push(CompactIndexedAttestation::Electra(att));
+                            continue;
+                        }
+                        CompactIndexedAttestation::Base(att) => {
+                            // Leave as-is.
+                            aggregated_attestations.push(CompactIndexedAttestation::Base(att));
+                            continue;
+                        }
+                    };
+                    if let Some(committee_index) = electra_attestation.committee_index() {
+                        if let Some(existing_attestation) =
+                            best_attestations_by_committee.get_mut(&committee_index)
                         {
-                            // New attestation is better than the previously known one for this
-                            // committee. Replace it.
-                            std::mem::swap(existing_attestation, &mut electra_attestation);
+                            // Search for the best (most aggregation bits) attestation for this committee
+                            // index.
+                            if electra_attestation.aggregation_bits.num_set_bits()
+                                > existing_attestation.aggregation_bits.num_set_bits()
+                            {
+                                // New attestation is better than the previously known one for this
+                                // committee. Replace it.
+                                std::mem::swap(existing_attestation, &mut electra_attestation);
+                            }
+                            // Put the inferior attestation into the list of aggregated attestations
+                            // without performing any cross-committee aggregation.
+                            aggregated_attestations
+                                .push(CompactIndexedAttestation::Electra(electra_attestation));
+                        } else {
+                            // First attestation seen for this committee. Place it in the map
+                            // provisionally.
+                            best_attestations_by_committee
+                                .insert(committee_index, electra_attestation);
                         }
-                        // Put the inferior attestation into the list of aggregated attestations
-                        // without performing any cross-committee aggregation.
-                        aggregated_attestations
-                            .push(CompactIndexedAttestation::Electra(electra_attestation));
-                    } else {
-                        // First attestation seen for this committee. Place it in the map
-                        // provisionally.
-                        best_attestations_by_committee.insert(committee_index, electra_attestation);
                     }
                 }
-            }
 
-            if let Some(on_chain_aggregate) =
-                Self::compute_on_chain_aggregate(best_attestations_by_committee)
-            {
-                aggregated_attestations
-                    .push(CompactIndexedAttestation::Electra(on_chain_aggregate));
-            }
+                if let Some(on_chain_aggregate) =
+                    Self::compute_on_chain_aggregate(best_attestations_by_committee)
+                {
+                    aggregated_attestations
+                        .push(CompactIndexedAttestation::Electra(on_chain_aggregate));
+                }
 
-            *compact_indexed_attestations = aggregated_attestations;
-        }
+                *compact_indexed_attestations = aggregated_attestations;
+            },
+        );
     }
 
     pub fn compute_on_chain_aggregate(
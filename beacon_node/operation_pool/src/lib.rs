@@ -27,6 +27,8 @@ use max_cover::maximum_cover;
 use parking_lot::{RwLock, RwLockWriteGuard};
 use rand::seq::SliceRandom;
 use rand::thread_rng;
+use rayon::prelude::*;
+use rayon::ThreadPool;
 use state_processing::per_block_processing::errors::AttestationValidationError;
 use state_processing::per_block_processing::{
     get_slashable_indices_modular, verify_exit, VerifySignatures,
@@ -35,6 +37,7 @@ use state_processing::{SigVerifiedOp, VerifyOperation};
 use std::collections::{hash_map::Entry, HashMap, HashSet};
 use std::marker::PhantomData;
 use std::ptr;
+use std::sync::Arc;
 use types::{
     sync_aggregate::Error as SyncAggregateError, typenum::Unsigned, AbstractExecPayload,
     Attestation, AttestationData, AttesterSlashing, BeaconState, BeaconStateError, ChainSpec,
@@ -60,6 +63,9 @@ pub struct OperationPool<E: EthSpec + Default> {
     bls_to_execution_changes: RwLock<BlsToExecutionChanges<E>>,
     /// Reward cache for accelerating attestation packing.
     reward_cache: RwLock<RewardCache>,
+    /// Thread pool used to aggregate and score attestations during packing. The global rayon pool
+    /// is used if this is `None`.
+    attestation_packing_pool: Option<Arc<ThreadPool>>,
     _phantom: PhantomData<E>,
 }
 
@@ -89,6 +95,15 @@ pub struct AttestationStats {
     pub max_aggregates_per_data: usize,
 }
 
+/// The value of including an attestation in a block built on a particular state.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AttestationInclusionScore {
+    /// Number of attesting validators whose attestations have not yet been included on chain.
+    pub fresh_validators: usize,
+    /// The proposer reward for including the attestation, in gwei.
+    pub reward: u64,
+}
+
 impl From<SyncAggregateError> for OpPoolError {
     fn from(e: SyncAggregateError) -> Self {
         OpPoolError::SyncAggregateError(e)
@@ -101,6 +116,26 @@ impl<E: EthSpec> OperationPool<E> {
         Self::default()
     }
 
+    /// Aggregate and score attestations during packing on a dedicated pool of `num_threads`
+    /// threads, rather than on the global rayon pool.
+    pub fn with_attestation_packing_threads(mut self, num_threads: usize) -> Result<Self, String> {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .thread_name(|index| format!("attestation_packing_{index}"))
+            .build()
+            .map_err(|e| format!("Unable to build attestation packing thread pool: {e:?}"))?;
+        self.attestation_packing_pool = Some(Arc::new(pool));
+        Ok(self)
+    }
+
+    /// Run `f` on the attestation packing thread pool, if one is configured.
+    fn install_packing<R: Send>(&self, f: impl FnOnce() -> R + Send) -> R {
+        match &self.attestation_packing_pool {
+            Some(pool) => pool.install(f),
+            None => f(),
+        }
+    }
+
     /// Insert a sync contribution into the pool. We don't aggregate these contributions until they
     /// are retrieved from the pool.
     ///
@@ -218,6 +253,9 @@ impl<E: EthSpec> OperationPool<E> {
     }
 
     /// Return all valid attestations for the given epoch, for use in max cover.
+    ///
+    /// The validity filter is applied sequentially, after which the remaining attestations are
+    /// scored in parallel.
     #[allow(clippy::too_many_arguments)]
     fn get_valid_attestations_for_epoch<'a>(
         &'a self,
@@ -228,17 +266,25 @@ impl<E: EthSpec> OperationPool<E> {
         total_active_balance: u64,
         validity_filter: impl FnMut(&CompactAttestationRef<'a, E>) -> bool + Send,
         spec: &'a ChainSpec,
-    ) -> impl Iterator<Item = AttMaxCover<'a, E>> + Send {
-        all_attestations
+    ) -> Vec<AttMaxCover<'a, E>> {
+        let candidates = all_attestations
             .get_attestations(checkpoint_key)
             .filter(|att| {
                 att.data.slot + spec.min_attestation_inclusion_delay <= state.slot()
                     && state.slot() <= att.data.slot + E::slots_per_epoch()
             })
             .filter(validity_filter)
-            .filter_map(move |att| {
-                AttMaxCover::new(att, state, reward_cache, total_active_balance, spec)
-            })
+            .collect::<Vec<_>>();
+
+        let _timer = metrics::start_timer(&metrics::ATTESTATION_SCORING_TIME);
+        self.install_packing(|| {
+            candidates
+                .into_par_iter()
+                .filter_map(|att| {
+                    AttMaxCover::new(att, state, reward_cache, total_active_balance, spec)
+                })
+                .collect()
+        })
     }
 
     /// Get a list of attestations for inclusion in a block.
@@ -277,43 +323,39 @@ impl<E: EthSpec> OperationPool<E> {
         let reward_cache = RwLockWriteGuard::downgrade(reward_cache);
         drop(reward_timer);
 
-        // Split attestations for the previous & current epochs, so that we
-        // can optimise them individually in parallel.
-        let mut num_prev_valid = 0_i64;
-        let mut num_curr_valid = 0_i64;
-
         // TODO(electra): Work out how to do this more elegantly. This is a bit of a hack.
         let mut all_attestations = self.attestations.write();
 
         if fork_name.electra_enabled() {
-            all_attestations.aggregate_across_committees(prev_epoch_key);
-            all_attestations.aggregate_across_committees(curr_epoch_key);
+            let attestation_map = &mut *all_attestations;
+            self.install_packing(|| {
+                attestation_map.aggregate_across_committees(prev_epoch_key);
+                attestation_map.aggregate_across_committees(curr_epoch_key);
+            });
         }
 
         let all_attestations = parking_lot::RwLockWriteGuard::downgrade(all_attestations);
 
-        let prev_epoch_att = self
-            .get_valid_attestations_for_epoch(
-                &prev_epoch_key,
-                &*all_attestations,
-                state,
-                &reward_cache,
-                total_active_balance,
-                prev_epoch_validity_filter,
-                spec,
-            )
-            .inspect(|_| num_prev_valid += 1);
-        let curr_epoch_att = self
-            .get_valid_attestations_for_epoch(
-                &curr_epoch_key,
-                &*all_attestations,
-                state,
-                &reward_cache,
-                total_active_balance,
-                curr_epoch_validity_filter,
-                spec,
-            )
-            .inspect(|_| num_curr_valid += 1);
+        let prev_epoch_att = self.get_valid_attestations_for_epoch(
+            &prev_epoch_key,
+            &*all_attestations,
+            state,
+            &reward_cache,
+            total_active_balance,
+            prev_epoch_validity_filter,
+            spec,
+        );
+        let curr_epoch_att = self.get_valid_attestations_for_epoch(
+            &curr_epoch_key,
+            &*all_attestations,
+            state,
+            &reward_cache,
+            total_active_balance,
+            curr_epoch_validity_filter,
+            spec,
+        );
+        let num_prev_valid = prev_epoch_att.len() as i64;
+        let num_curr_valid = curr_epoch_att.len() as i64;
 
         let curr_epoch_limit = if fork_name.electra_enabled() {
             E::MaxAttestationsElectra::to_usize()
@@ -330,21 +372,27 @@ impl<E: EthSpec> OperationPool<E> {
             curr_epoch_limit
         };
 
-        let (prev_cover, curr_cover) = rayon::join(
-            move || {
-                let _timer = metrics::start_timer(&metrics::ATTESTATION_PREV_EPOCH_PACKING_TIME);
-                // If we're in the genesis epoch, just use the current epoch attestations.
-                if prev_epoch_key == curr_epoch_key {
-                    vec![]
-                } else {
-                    maximum_cover(prev_epoch_att, prev_epoch_limit, "prev_epoch_attestations")
-                }
-            },
-            move || {
-                let _timer = metrics::start_timer(&metrics::ATTESTATION_CURR_EPOCH_PACKING_TIME);
-                maximum_cover(curr_epoch_att, curr_epoch_limit, "curr_epoch_attestations")
-            },
-        );
+        // Split attestations for the previous & current epochs, so that we
+        // can optimise them individually in parallel.
+        let (prev_cover, curr_cover) = self.install_packing(|| {
+            rayon::join(
+                move || {
+                    let _timer =
+                        metrics::start_timer(&metrics::ATTESTATION_PREV_EPOCH_PACKING_TIME);
+                    // If we're in the genesis epoch, just use the current epoch attestations.
+                    if prev_epoch_key == curr_epoch_key {
+                        vec![]
+                    } else {
+                        maximum_cover(prev_epoch_att, prev_epoch_limit, "prev_epoch_attestations")
+                    }
+                },
+                move || {
+                    let _timer =
+                        metrics::start_timer(&metrics::ATTESTATION_CURR_EPOCH_PACKING_TIME);
+                    maximum_cover(curr_epoch_att, curr_epoch_limit, "curr_epoch_attestations")
+                },
+            )
+        });
 
         metrics::set_gauge(&metrics::NUM_PREV_EPOCH_ATTESTATIONS, num_prev_valid);
         metrics::set_gauge(&metrics::NUM_CURR_EPOCH_ATTESTATIONS, num_curr_valid);
@@ -687,6 +735,67 @@ impl<E: EthSpec> OperationPool<E> {
             .collect()
     }
 
+    /// Returns all known `Attestation` objects that pass the provided filter, each with its
+    /// `AttestationInclusionScore` for a block built on `state`.
+    ///
+    /// Attestations which can't be included in such a block, for example because their source
+    /// checkpoint doesn't match the state or they are outside of the inclusion window, have no
+    /// score.
+    pub fn get_scored_attestations<F>(
+        &self,
+        state: &BeaconState<E>,
+        filter: F,
+        spec: &ChainSpec,
+    ) -> Result<Vec<(Attestation<E>, Option<AttestationInclusionScore>)>, OpPoolError>
+    where
+        F: Fn(&AttestationData) -> bool,
+    {
+        if !matches!(state, BeaconState::Base(_))
+            && !is_epoch_cache_initialized(state).map_err(OpPoolError::EpochCacheError)?
+        {
+            return Err(OpPoolError::EpochCacheNotInitialized);
+        }
+
+        let (prev_epoch_key, curr_epoch_key) = CheckpointKey::keys_for_state(state);
+        let total_active_balance = state
+            .get_total_active_balance()
+            .map_err(OpPoolError::GetAttestationsTotalBalanceError)?;
+
+        let mut reward_cache = self.reward_cache.write();
+        reward_cache.update(state)?;
+        let reward_cache = RwLockWriteGuard::downgrade(reward_cache);
+
+        let all_attestations = self.attestations.read();
+        let scored_attestations = all_attestations
+            .iter()
+            .filter(|att| filter(&att.attestation_data()))
+            .map(|att| {
+                let includable = (*att.checkpoint == prev_epoch_key
+                    || *att.checkpoint == curr_epoch_key)
+                    && att.data.slot + spec.min_attestation_inclusion_delay <= state.slot()
+                    && state.slot() <= att.data.slot + E::slots_per_epoch();
+                let score = includable
+                    .then(|| {
+                        AttMaxCover::new(
+                            att.clone(),
+                            state,
+                            &reward_cache,
+                            total_active_balance,
+                            spec,
+                        )
+                    })
+                    .flatten()
+                    .map(|cover| AttestationInclusionScore {
+                        fresh_validators: cover.fresh_validators_rewards.len(),
+                        reward: cover.fresh_validators_rewards.values().sum(),
+                    });
+                (att.clone_as_attestation(), score)
+            })
+            .collect();
+
+        Ok(scored_attestations)
+    }
+
     /// Returns all known `AttesterSlashing` objects.
     ///
     /// This method may return objects that are invalid for block inclusion.
@@ -1009,9 +1118,28 @@ mod release_tests {
             0
         );
 
+        // Nor should they be scored.
+        let scored = op_pool
+            .get_scored_attestations(&state, |_| true, spec)
+            .expect("should score attestations");
+        assert_eq!(scored.len(), committees.len());
+        assert!(scored.iter().all(|(_, score)| score.is_none()));
+
         // Then once the delay has elapsed, we should get a single aggregated attestation.
         *state.slot_mut() += spec.min_attestation_inclusion_delay;
 
+        let scored = op_pool
+            .get_scored_attestations(&state, |_| true, spec)
+            .expect("should score attestations");
+        assert_eq!(scored.len(), committees.len());
+        let score = scored[0].1.expect("attestation should be includable");
+        assert_eq!(score.fresh_validators, spec.target_committee_size);
+        assert!(score.reward > 0);
+        assert!(op_pool
+            .get_scored_attestations(&state, |data| data.slot != slot, spec)
+            .expect("should score attestations")
+            .is_empty());
+
         let block_attestations = op_pool
             .get_attestations(&state, |_| true, |_| true, spec)
             .expect("Should have block attestations");
@@ -1432,6 +1560,103 @@ mod release_tests {
         }
     }
 
+    /// Packing on a dedicated thread pool should select the same attestations, with the same total
+    /// reward, regardless of the number of threads.
+    #[test]
+    fn attestation_packing_thread_count() {
+        let num_committees = 4;
+        let (harness, ref spec) = attestation_test_state::<MainnetEthSpec>(num_committees);
+        let mut state = get_current_state_initialize_epoch_cache(&harness, spec);
+        let slot = state.slot();
+
+        let num_validators = num_committees
+            * MainnetEthSpec::slots_per_epoch() as usize
+            * spec.target_committee_size;
+        let attestations = harness.make_attestations(
+            (0..num_validators).collect::<Vec<_>>().as_slice(),
+            &state,
+            Hash256::zero(),
+            SignedBeaconBlockHash::from(Hash256::zero()),
+            slot,
+        );
+
+        let op_pools = [
+            OperationPool::<MainnetEthSpec>::new(),
+            OperationPool::new()
+                .with_attestation_packing_threads(1)
+                .unwrap(),
+            OperationPool::new()
+                .with_attestation_packing_threads(4)
+                .unwrap(),
+        ];
+
+        // Insert overlapping aggregates of different sizes so that packing has choices to make.
+        for (committee_attestations, _) in attestations {
+            for step_size in [2, 3, 5] {
+                for chunk in committee_attestations.chunks(step_size) {
+                    let mut aggregate = chunk[0].0.clone();
+                    for (attestation, _) in &chunk[1..] {
+                        aggregate.aggregate(attestation.to_ref());
+                    }
+                    let attesting_indices =
+                        get_attesting_indices_from_state(&state, aggregate.to_ref()).unwrap();
+                    for op_pool in &op_pools {
+                        op_pool
+                            .insert_attestation(aggregate.clone(), attesting_indices.clone())
+                            .unwrap();
+                    }
+                }
+            }
+        }
+
+        *state.slot_mut() += spec.min_attestation_inclusion_delay;
+        let total_active_balance = state.get_total_active_balance().unwrap();
+        let mut reward_cache = RewardCache::default();
+        reward_cache.update(&state).unwrap();
+
+        // The total reward of the fresh validators covered by the packed attestations.
+        let packed_reward = |attestations: &[Attestation<MainnetEthSpec>]| -> u64 {
+            let mut seen_indices = BTreeSet::<u64>::new();
+            let mut total_reward = 0;
+            for att in attestations {
+                let attesting_indices =
+                    get_attesting_indices_from_state(&state, att.to_ref()).unwrap();
+                let split_attestation = SplitAttestation::new(att.clone(), attesting_indices);
+                let rewards = AttMaxCover::new(
+                    split_attestation.as_ref(),
+                    &state,
+                    &reward_cache,
+                    total_active_balance,
+                    spec,
+                )
+                .unwrap()
+                .fresh_validators_rewards;
+                for (validator_index, reward) in rewards {
+                    if seen_indices.insert(validator_index) {
+                        total_reward += reward;
+                    }
+                }
+            }
+            total_reward
+        };
+
+        let packings = op_pools
+            .iter()
+            .map(|op_pool| {
+                op_pool
+                    .get_attestations(&state, |_| true, |_| true, spec)
+                    .expect("should pack attestations")
+            })
+            .collect::<Vec<_>>();
+
+        let expected_reward = packed_reward(&packings[0]);
+        assert!(expected_reward > 0);
+        for packing in &packings[1..] {
+            assert_eq!(packing, &packings[0]);
+            assert_eq!(packed_reward(packing), expected_reward);
+        }
+    }
+
     /// Insert two slashings for the same proposer and ensure only one is returned.
     #[test]
     fn duplicate_proposer_slashing() {
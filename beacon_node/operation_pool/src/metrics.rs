@@ -19,6 +19,12 @@ pub static ATTESTATION_CURR_EPOCH_PACKING_TIME: LazyLock<Result<Histogram>> = La
         "Time to pack current epoch attestations",
     )
 });
+pub static ATTESTATION_SCORING_TIME: LazyLock<Result<Histogram>> = LazyLock::new(|| {
+    try_create_histogram(
+        "op_pool_attestation_scoring_time",
+        "Time to score the valid attestations of an epoch before packing",
+    )
+});
 pub static NUM_PREV_EPOCH_ATTESTATIONS: LazyLock<Result<IntGauge>> = LazyLock::new(|| {
     try_create_int_gauge(
         "op_pool_prev_epoch_attestations",
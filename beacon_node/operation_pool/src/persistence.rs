@@ -57,24 +57,48 @@ pub struct PersistedOperationPool<E: EthSpec> {
 impl<E: EthSpec> PersistedOperationPool<E> {
     /// Convert an `OperationPool` into serializable form.
     pub fn from_operation_pool(operation_pool: &OperationPool<E>) -> Self {
-        let attestations = operation_pool
-            .attestations
-            .read()
-            .iter()
-            .map(|att| {
-                (
-                    AttestationOnDisk::from(att.clone_as_attestation()),
-                    att.indexed.attesting_indices().clone(),
-                )
-            })
-            .collect();
+        Self::from_operation_pool_inner(operation_pool, true)
+    }
 
-        let sync_contributions = operation_pool
-            .sync_contributions
-            .read()
-            .iter()
-            .map(|(id, contribution)| (id.clone(), contribution.clone()))
-            .collect();
+    /// Convert an `OperationPool` into serializable form, omitting attestations and sync
+    /// contributions.
+    ///
+    /// The remaining operations are few in number and remain valid for much longer than an
+    /// attestation, so this is cheap enough to persist periodically.
+    pub fn from_operation_pool_without_attestations(operation_pool: &OperationPool<E>) -> Self {
+        Self::from_operation_pool_inner(operation_pool, false)
+    }
+
+    fn from_operation_pool_inner(
+        operation_pool: &OperationPool<E>,
+        include_attestations: bool,
+    ) -> Self {
+        let attestations = if include_attestations {
+            operation_pool
+                .attestations
+                .read()
+                .iter()
+                .map(|att| {
+                    (
+                        AttestationOnDisk::from(att.clone_as_attestation()),
+                        att.indexed.attesting_indices().clone(),
+                    )
+                })
+                .collect()
+        } else {
+            vec![]
+        };
+
+        let sync_contributions = if include_attestations {
+            operation_pool
+                .sync_contributions
+                .read()
+                .iter()
+                .map(|(id, contribution)| (id.clone(), contribution.clone()))
+                .collect()
+        } else {
+            vec![]
+        };
 
         let attester_slashings = operation_pool
             .attester_slashings
@@ -212,6 +236,7 @@ impl<E: EthSpec> PersistedOperationPool<E> {
             voluntary_exits,
             bls_to_execution_changes: RwLock::new(bls_to_execution_changes),
             reward_cache: Default::default(),
+            attestation_packing_pool: None,
             _phantom: Default::default(),
         };
         Ok(op_pool)
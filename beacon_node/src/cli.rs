@@ -67,6 +67,19 @@ pub fn cli_app() -> Command {
                 .display_order(0)
                 .hide(true)
         )
+        .arg(
+            Arg::new("data-column-archive")
+                .long("data-column-archive")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .requires("subscribe-all-data-column-subnets")
+                .help("Retain all custody data columns since the PeerDAS fork rather than only \
+                        those within the data availability period, serve them to peers and \
+                        advertise the extended retention in the ENR. \
+                        NOTE: this is an experimental flag and may change any time without notice!")
+                .display_order(0)
+                .hide(true)
+        )
         .arg(
             // TODO(das): remove this before PeerDAS release
             Arg::new("malicious-withhold-count")
@@ -113,6 +126,32 @@ pub fn cli_app() -> Command {
                        This will also advertise the beacon node as being long-lived subscribed to all subnets.")
                 .display_order(0)
         )
+        .arg(
+            Arg::new("subnet-strategy")
+                .long("subnet-strategy")
+                .value_name("STRATEGY")
+                .help("How to subscribe to attestation subnets for validator duties. With \
+                       default, the node subscribes shortly before each aggregation duty. With \
+                       aggressive, the node subscribes to the subnet of every duty as soon as it \
+                       is known and keeps the subscription for --aggressive-subnet-epochs after \
+                       it, which reduces subnet churn for nodes with few validators. With \
+                       all-subnets, the node subscribes to all subnets, as for \
+                       --subscribe-all-subnets.")
+                .value_parser(["default", "aggressive", "all-subnets"])
+                .default_value("default")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("aggressive-subnet-epochs")
+                .long("aggressive-subnet-epochs")
+                .value_name("EPOCHS")
+                .help("The number of epochs after a duty for which a subnet subscription is kept \
+                       with --subnet-strategy aggressive.")
+                .default_value("2")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("import-all-attestations")
                 .long("import-all-attestations")
@@ -141,6 +180,52 @@ pub fn cli_app() -> Command {
                 .help_heading(FLAG_HEADER)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("blinded-blocks-by-range")
+                .long("blinded-blocks-by-range")
+                .help("Advertise a Lighthouse-specific variant of the blocks by range protocol \
+                       which serves blinded blocks to trusted peers without reconstructing \
+                       their execution payloads. Intended for replicas sharing an execution \
+                       node. Requests from peers not listed in --trusted-peers are refused.")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("execution-payload-bodies-by-range")
+                .long("execution-payload-bodies-by-range")
+                .help("Advertise a Lighthouse-specific protocol which serves execution payload \
+                       bodies to trusted peers, so that they can reconstruct blocks without \
+                       requesting payloads from their own execution node. Recently served \
+                       bodies are cached. Requests from peers not listed in --trusted-peers \
+                       are refused.")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("persist-pending-lookups")
+                .long("persist-pending-lookups")
+                .help("Persist the block roots of pending block lookups and of blocks awaited \
+                       by queued work on shutdown, along with the peers which claimed to have \
+                       them. On the next start each lookup is resumed once one of its peers \
+                       reconnects, or with the first peer to connect if none are known, rather \
+                       than waiting for the block to be referenced again.")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("lookup-max-parent-depth")
+                .long("lookup-max-parent-depth")
+                .value_name("DEPTH")
+                .help("The maximum number of unknown ancestors of a block which are looked up one \
+                       at a time. Once a chain of unknown ancestors grows longer, the blocks are \
+                       downloaded in batches by range sync instead.")
+                .default_value("32")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("zero-ports")
                 .long("zero-ports")
@@ -253,7 +338,7 @@ pub fn cli_app() -> Command {
         .arg(
             Arg::new("disable-upnp")
                 .long("disable-upnp")
-                .help("Disables UPnP support. Setting this will prevent Lighthouse from attempting to automatically establish external port mappings.")
+                .help("Disables UPnP and NAT-PMP support. Setting this will prevent Lighthouse from attempting to automatically establish external port mappings.")
                 .action(ArgAction::SetTrue)
                 .help_heading(FLAG_HEADER)
                 .display_order(0)
@@ -365,6 +450,17 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("import-peers")
+                .long("import-peers")
+                .value_name("FILE")
+                .help("Path to a JSON file of peers exported from another node via the \
+                       /lighthouse/peers/export HTTP API endpoint. The peers are added to the \
+                       routing table and the best of them are dialed at startup, skipping the \
+                       wait for discovery. Peers with a negative score are ignored.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         // NOTE: This is hide because it is primarily a developer feature for testnets and
         // debugging. We remove it from the list to avoid clutter.
         .arg(
@@ -384,6 +480,20 @@ pub fn cli_app() -> Command {
                 .help("Disables the quic transport. The node will rely solely on the TCP transport for libp2p connections.")
                 .display_order(0)
         )
+        .arg(
+            Arg::new("transport-preference")
+                .long("transport-preference")
+                .value_name("PREFERENCE")
+                .help("The order in which transports are used when dialing a peer. With \
+                       quic-first or tcp-first, the other transport is only dialed if all \
+                       addresses of the preferred transport fail. With quic-only, peers which \
+                       don't advertise a QUIC address are not dialed. This does not affect the \
+                       transports that inbound connections are accepted on.")
+                .value_parser(["quic-first", "tcp-first", "quic-only"])
+                .default_value("quic-first")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("disable-peer-scoring")
                 .long("disable-peer-scoring")
@@ -411,6 +521,45 @@ pub fn cli_app() -> Command {
                 .help_heading(FLAG_HEADER)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("backfill-profile")
+                .long("backfill-profile")
+                .value_name("PROFILE")
+                .help("How far back to download blocks when checkpoint syncing. \"minimal\" \
+                       downloads only the blocks the spec requires nodes to serve, \"full\" \
+                       downloads all blocks back to genesis, and a slot number downloads all \
+                       blocks back to that slot (and never less than \"minimal\").")
+                .action(ArgAction::Set)
+                .conflicts_with_all(["genesis-backfill", "reconstruct-historic-states"])
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("backfill-target-epoch")
+                .long("backfill-target-epoch")
+                .value_name("EPOCH")
+                .help("Download blocks back to the start of this epoch when checkpoint syncing, \
+                       and no further. Unlike --backfill-profile the target may be later than \
+                       the blocks the spec requires nodes to serve, in which case requests from \
+                       peers for older blocks will not be served.")
+                .action(ArgAction::Set)
+                .conflicts_with_all([
+                    "backfill-profile",
+                    "genesis-backfill",
+                    "reconstruct-historic-states",
+                ])
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("import-era-dir")
+                .long("import-era-dir")
+                .value_name("DIR")
+                .help("Import historic blocks from the era files in this directory on startup, \
+                       before backfill sync. Each era is verified against the historical roots \
+                       of the finalized state. Backfill sync continues from the oldest imported \
+                       block.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("enable-private-discovery")
                 .long("enable-private-discovery")
@@ -493,6 +642,18 @@ pub fn cli_app() -> Command {
                 .help_heading(FLAG_HEADER)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("backfill-batches-per-minute")
+                .long("backfill-batches-per-minute")
+                .value_name("COUNT")
+                .help("The maximum number of batches of historic blocks that backfill sync \
+                       writes to disk per minute. Limiting backfill sync reduces disk I/O \
+                       contention on nodes which are performing validator duties. The limit can \
+                       be changed at runtime via the /lighthouse/syncing/backfill_throttle HTTP \
+                       API. Unlimited by default.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         /* REST API related arguments */
         .arg(
             Arg::new("http")
@@ -534,6 +695,99 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("http-api-tokens-file")
+                .long("http-api-tokens-file")
+                .requires("enable_http")
+                .value_name("PATH")
+                .help("Require requests to the HTTP API to present a bearer token from this \
+                    file in the Authorization header. Each token is scoped to read-only, \
+                    validator-duties or admin routes. Tokens are managed with the \
+                    `lighthouse api_token_manager` command.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("http-historical-state-cache-size")
+                .long("http-historical-state-cache-size")
+                .requires("enable_http")
+                .value_name("N")
+                .help("Number of regenerated historical states requested via the HTTP API to \
+                    keep in memory. [default: 4]")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("http-historical-state-queue-size")
+                .long("http-historical-state-queue-size")
+                .requires("enable_http")
+                .value_name("N")
+                .help("Maximum number of HTTP API requests that may wait for a historical state \
+                    to be regenerated. Further requests are rejected with a 503 response. \
+                    [default: 16]")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("http-historical-state-regen-budget")
+                .long("http-historical-state-regen-budget")
+                .requires("enable_http")
+                .value_name("N")
+                .help("Total cost of historical state regenerations that may run concurrently \
+                    for the HTTP API. Unfinalized states cost 1 and finalized states cost 2. \
+                    [default: 4]")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("http-rate-limit")
+                .long("http-rate-limit")
+                .requires("enable_http")
+                .value_name("TOKENS/SECONDS")
+                .help("Rate limit requests to the HTTP API. Each IP address, or API token if \
+                    one is presented, may spend TOKENS every SECONDS. Requests cost 1 token, \
+                    unless configured otherwise with --http-rate-limit-route-costs. Tokens may \
+                    override this quota with a `quota` field in the API tokens file. Requests \
+                    exceeding the limit receive a 429 response with a Retry-After header.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("http-rate-limit-route-costs")
+                .long("http-rate-limit-route-costs")
+                .requires("http-rate-limit")
+                .value_name("ROUTE_COSTS")
+                .help("The cost of requests to HTTP API routes, in the form \
+                    <path_prefix>:<cost>. To set costs for multiple routes, separate them by \
+                    ';'. The longest matching prefix is used. By default, state and reward \
+                    queries cost more than other requests.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("http-proposer-lookahead")
+                .long("http-proposer-lookahead")
+                .requires("enable_http")
+                .help("Serve proposer duties for the epoch after next at \
+                    /lighthouse/validator/duties/proposer/{epoch}. These duties are computed \
+                    from the head state and marked as provisional, since they may change until \
+                    their dependent block is known.")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("checkpoint-provider-mode")
+                .long("checkpoint-provider-mode")
+                .requires("enable_http")
+                .help("Keep the latest finalized state and block in memory as SSZ bytes, \
+                    refreshed at each finalization, and use them to serve SSZ requests for the \
+                    finalized state and block. Intended for nodes serving checkpoint sync to many \
+                    clients. Increases memory usage by roughly the size of one state.")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
         .arg(
             Arg::new("http-enable-tls")
                 .long("http-enable-tls")
@@ -657,6 +911,39 @@ pub fn cli_app() -> Command {
                 .hide(true)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("staggered-publish-peers")
+                .long("staggered-publish-peers")
+                .value_name("COUNT")
+                .help("Publish blocks and blobs to this many of the highest-scoring peers first, \
+                       and to the remaining peers after --staggered-publish-delay. This reduces \
+                       the upload spike when publishing on low-bandwidth connections. By default \
+                       blocks and blobs are published to all peers at once.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("staggered-publish-delay")
+                .long("staggered-publish-delay")
+                .value_name("MILLISECONDS")
+                .help("The delay before a block or blob is published to the peers outside of the \
+                       first burst. Only used with --staggered-publish-peers.")
+                .requires("staggered-publish-peers")
+                .default_value("200")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("shutdown-rpc-drain-timeout")
+                .long("shutdown-rpc-drain-timeout")
+                .value_name("SECONDS")
+                .help("When shutting down, stop accepting inbound RPC requests and wait up to this \
+                       many seconds for the requests already accepted to be served, so that peers \
+                       do not see their streams reset.")
+                .default_value("5")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         /*
          * Monitoring metrics
          */
@@ -775,6 +1062,19 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("hierarchical-state-diffs")
+                .long("hierarchical-state-diffs")
+                .value_name("BOOLEAN")
+                .help("Whether to store states in the freezer DB as hierarchical diffs against \
+                       periodic full snapshots. If set to false, only full snapshots are stored, \
+                       every 2048 slots. Cannot be changed after initialization. \
+                       [default: true]")
+                .value_parser(["true", "false"])
+                .conflicts_with("hierarchy-exponents")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("epochs-per-migration")
                 .long("epochs-per-migration")
@@ -786,6 +1086,17 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("exit-rebroadcast-epochs")
+                .long("exit-rebroadcast-epochs")
+                .value_name("EPOCHS")
+                .help("The number of epochs between re-broadcasts of the voluntary exits in the \
+                       op pool which have not been included on chain. Set to 0 to disable \
+                       re-broadcasting.")
+                .default_value("4")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("block-cache-size")
                 .long("block-cache-size")
@@ -821,11 +1132,23 @@ pub fn cli_app() -> Command {
             Arg::new("state-cache-size")
                 .long("state-cache-size")
                 .value_name("STATE_CACHE_SIZE")
-                .help("Specifies the size of the state cache")
+                .help("Specifies the maximum number of states in the state cache")
                 .default_value("128")
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("state-cache-size-mb")
+                .long("state-cache-size-mb")
+                .value_name("MEGABYTES")
+                .help("Specifies the maximum memory used by the state cache, including the \
+                       finalized state. Memory shared between states is only counted once. \
+                       States are culled once either this limit or --state-cache-size is \
+                       reached.")
+                .default_value("2048")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         /*
          * Execution Layer Integration
          */
@@ -841,6 +1164,31 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("execution-fallback-endpoints")
+                .long("execution-fallback-endpoints")
+                .value_name("EXECUTION-ENDPOINTS")
+                .help("One or more comma-delimited server endpoints for fallback execution \
+                       nodes. These are used to reconstruct execution payloads when serving \
+                       blocks to peers if the primary execution endpoint fails, and are sent \
+                       new payloads if --execution-fallback-new-payload is set. They must \
+                       accept the same JWT secret as the --execution-endpoint.")
+                .requires("execution-endpoint")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("execution-fallback-new-payload")
+                .long("execution-fallback-new-payload")
+                .help("Send each new execution payload and forkchoice update to the fallback \
+                       execution nodes as well as the primary, so that they remain in sync and \
+                       can take over without delay. The verdict of the primary execution \
+                       endpoint is authoritative.")
+                .requires("execution-fallback-endpoints")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
         .arg(
             Arg::new("execution-jwt")
                 .long("execution-jwt")
@@ -888,6 +1236,17 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("execution-jwt-reload-on-sighup")
+                .long("execution-jwt-reload-on-sighup")
+                .help("Reload the secret from the --execution-jwt file when the beacon node \
+                       receives SIGHUP, rather than shutting down. This allows the JWT secret \
+                       to be rotated without restarting the beacon node.")
+                .requires("execution-jwt")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
         .arg(
             Arg::new("suggested-fee-recipient")
                 .long("suggested-fee-recipient")
@@ -918,6 +1277,18 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("payload-bodies-batch-size")
+                .long("payload-bodies-batch-size")
+                .value_name("COUNT")
+                .help("The maximum number of execution payloads requested from the execution \
+                       engine at once when serving finalized blocks to peers. Larger batches \
+                       reduce the number of engine API calls for long block ranges. \
+                       Must be between 1 and 1024.")
+                .default_value("32")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("builder-header-timeout")
                 .long("builder-header-timeout")
@@ -944,6 +1315,18 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("builder-groups")
+                .long("builder-groups")
+                .value_name("FILE")
+                .help("Path to a JSON file assigning groups of validators their own builder URL \
+                       and minimum bid (in gwei). Bids below a group's minimum are ignored in \
+                       favour of the local payload. Validators outside all groups use the \
+                       --builder URL.")
+                .requires("builder")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         /* Deneb settings */
         .arg(
             Arg::new("trusted-setup-file-override")
@@ -974,6 +1357,17 @@ pub fn cli_app() -> Command {
                 .help("If present, the chain database will be deleted without confirmation. Use with caution.")
                 .display_order(0)
         )
+        .arg(
+            Arg::new("self-test")
+                .long("self-test")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .help("Instead of starting the beacon node, check that the database, KZG \
+                       verification, BLS performance, execution endpoint and system clock are \
+                       working, print a JSON report to stdout and exit. Exits with a non-zero \
+                       code if any check fails.")
+                .display_order(0)
+        )
         .arg(
             Arg::new("compact-db")
                 .long("compact-db")
@@ -983,6 +1377,25 @@ pub fn cli_app() -> Command {
                        It is generally not recommended unless auto-compaction is disabled.")
                 .display_order(0)
         )
+        .arg(
+            Arg::new("db-compaction-interval")
+                .long("db-compaction-interval")
+                .value_name("SECONDS")
+                .help("Periodically compact the hot database, at most once per this many \
+                       seconds. Disabled by default.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("db-compaction-window")
+                .long("db-compaction-window")
+                .value_name("START-END")
+                .help("Restrict periodic compaction of the hot database to the given window of \
+                       UTC hours, e.g. 2-6. The window may wrap past midnight, e.g. 22-4.")
+                .requires("db-compaction-interval")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("auto-compact-db")
                 .long("auto-compact-db")
@@ -1032,6 +1445,26 @@ pub fn cli_app() -> Command {
                 .default_value("0")
                 .display_order(0)
         )
+        .arg(
+            Arg::new("blob-retention-epochs")
+                .long("blob-retention-epochs")
+                .value_name("EPOCHS")
+                .help("Retain blobs for this many epochs, or \"forever\", so that a longer blob \
+                       history can be served to peers. Retention can't be shorter than the data \
+                       availability period, which is the default.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("blob-prune-rate-limit")
+                .long("blob-prune-rate-limit")
+                .value_name("BYTES_PER_SECOND")
+                .help("Limit the rate at which blobs are deleted from Lighthouse's database \
+                       during blob pruning, in bytes per second. Pruning runs in the background \
+                       and is unlimited by default.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         /*
          * Misc.
          */
@@ -1370,6 +1803,29 @@ pub fn cli_app() -> Command {
                 .conflicts_with("disable-proposer-reorgs")
                 .display_order(0)
         )
+        .arg(
+            Arg::new("late-payload-reveal-threshold")
+                .long("late-payload-reveal-threshold")
+                .value_name("MILLISECONDS")
+                .action(ArgAction::Set)
+                .help("Refuse to build on a head block whose payload was revealed less than this \
+                       many milliseconds before block production, and build on its parent \
+                       instead. This protects against builders which withhold their payload \
+                       until it is too late for the next proposer to re-org it. Disabled by \
+                       default.")
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("late-payload-trusted-fee-recipients")
+                .long("late-payload-trusted-fee-recipients")
+                .value_name("ADDRESSES")
+                .action(ArgAction::Set)
+                .help("Comma-separated list of fee recipients of builders which are trusted not \
+                       to withhold payloads. Head blocks with payloads from these fee recipients \
+                       are always built upon.")
+                .requires("late-payload-reveal-threshold")
+                .display_order(0)
+        )
         .arg(
             Arg::new("prepare-payload-lookahead")
                 .long("prepare-payload-lookahead")
@@ -1403,6 +1859,40 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("attestation-packing-threads")
+                .long("attestation-packing-threads")
+                .value_name("INTEGER")
+                .help("The number of threads used to aggregate and score attestations when \
+                       producing a block. By default the attestations are packed on the threads \
+                       shared with other parallel tasks.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("block-production-deadline")
+                .long("block-production-deadline")
+                .value_name("MILLISECONDS")
+                .help("The time into the slot after which block production stops waiting on slow \
+                       components and returns the best block available. Once the deadline has \
+                       passed, unaggregated attestations are no longer added to the block, and \
+                       whichever of the builder and local execution payloads is still pending \
+                       is no longer waited upon if the other is available. By default there is \
+                       no deadline.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("block-import-timeout")
+                .long("block-import-timeout")
+                .value_name("SECONDS")
+                .help("The maximum time allowed for importing a block received from a peer by \
+                       lookup sync. Imports which exceed it, e.g. due to an unresponsive \
+                       execution node, are abandoned and retried without penalising the peer.")
+                .default_value("60")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("paranoid-block-proposal")
                 .long("paranoid-block-proposal")
@@ -1591,5 +2081,79 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("beacon-processor-sync-message-batch-size")
+                .long("beacon-processor-sync-message-batch-size")
+                .value_name("INTEGER")
+                .help("Specifies the number of gossip sync committee messages in a signature \
+                       verification batch. \
+                       Higher values may reduce CPU usage in a healthy network while lower values may \
+                       increase CPU usage in an unhealthy or hostile network.")
+                .hide(true)
+                .default_value("64")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("beacon-processor-blob-batch-size")
+                .long("beacon-processor-blob-batch-size")
+                .value_name("INTEGER")
+                .help("Specifies the maximum number of queued gossip blob sidecars processed by a \
+                       single worker. Blobs for the same block in a batch have their KZG proofs \
+                       verified together.")
+                .hide(true)
+                .default_value("16")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("beacon-processor-load-shedding-threshold")
+                .long("beacon-processor-load-shedding-threshold")
+                .value_name("MILLISECONDS")
+                .help("Enables load shedding once all beacon processor workers have been busy for \
+                       this many milliseconds. Whilst shedding load, the node drops gossip which is \
+                       not required by its own validators, such as sync committee messages and \
+                       attestations on subnets without local aggregation duties. Blocks, blobs and \
+                       data columns are always processed. Load shedding stops once workers have \
+                       been available for the same period. Disabled by default.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("beacon-processor-priorities")
+                .long("beacon-processor-priorities")
+                .value_name("FILE")
+                .help("Path to a JSON file which changes the order in which queued work is \
+                       started by the beacon processor. The file is a list of entries such as \
+                       {\"work\": \"blocks_by_range_request\", \"after\": \"gossip_attestation\"}, \
+                       each moving one type of work immediately before or after another. \
+                       Entries are applied in order to the default priorities.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("beacon-processor-worker-caps")
+                .long("beacon-processor-worker-caps")
+                .value_name("CAPS")
+                .help("Comma-separated limits on the number of beacon processor workers which \
+                       may concurrently process each type of work, e.g. \
+                       \"chain_segment=4,chain_segment_backfill=2\". Work beyond its limit is \
+                       queued. Types of work without a limit may use all workers.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("rpc-serving-only")
+                .long("rpc-serving-only")
+                .help("Only subscribe to the gossip topics required to follow the head of the \
+                       chain, for nodes which exist to serve blocks, blobs and light client data \
+                       over RPC and the HTTP API, such as bootstrap and archive servers. The node \
+                       doesn't subscribe to attestation, aggregate, sync committee or light \
+                       client topics, nor to most operations. This node should not be used by \
+                       validators.")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
         .group(ArgGroup::new("enable_http").args(["http", "gui", "staking"]).multiple(true))
 }
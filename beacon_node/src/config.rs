@@ -1,8 +1,9 @@
 use account_utils::{read_input_from_user, STDIN_INPUTS_FLAG};
 use beacon_chain::chain_config::{
-    DisallowedReOrgOffsets, ReOrgThreshold, DEFAULT_PREPARE_PAYLOAD_LOOKAHEAD_FACTOR,
-    DEFAULT_RE_ORG_HEAD_THRESHOLD, DEFAULT_RE_ORG_MAX_EPOCHS_SINCE_FINALIZATION,
-    DEFAULT_RE_ORG_PARENT_THRESHOLD,
+    BackfillProfile, DisallowedReOrgOffsets, ReOrgThreshold,
+    DEFAULT_PREPARE_PAYLOAD_LOOKAHEAD_FACTOR, DEFAULT_RE_ORG_HEAD_THRESHOLD,
+    DEFAULT_RE_ORG_MAX_EPOCHS_SINCE_FINALIZATION, DEFAULT_RE_ORG_PARENT_THRESHOLD,
+    MAX_PAYLOAD_BODIES_BATCH_SIZE,
 };
 use beacon_chain::graffiti_calculator::GraffitiOrigin;
 use beacon_chain::TrustedSetup;
@@ -14,9 +15,11 @@ use directory::{DEFAULT_BEACON_NODE_DIR, DEFAULT_NETWORK_DIR, DEFAULT_ROOT_DIR};
 use environment::RuntimeContext;
 use execution_layer::DEFAULT_JWT_FILE;
 use genesis::Eth1Endpoint;
+use http_api::rate_limiter::RateLimitConfig;
 use http_api::TlsConfig;
-use lighthouse_network::ListenAddress;
+use lighthouse_network::types::ExportedPeer;
 use lighthouse_network::{multiaddr::Protocol, Enr, Multiaddr, NetworkConfig, PeerIdSerialized};
+use lighthouse_network::{ListenAddress, SubnetStrategy, TransportPreference};
 use sensitive_url::SensitiveUrl;
 use slog::{info, warn, Logger};
 use std::cmp::max;
@@ -29,8 +32,9 @@ use std::num::NonZeroU16;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::time::Duration;
+use store::hdiff::HierarchyConfig;
 use types::graffiti::GraffitiString;
-use types::{Checkpoint, Epoch, EthSpec, Hash256, PublicKeyBytes};
+use types::{Address, Checkpoint, Epoch, EthSpec, Hash256, PublicKeyBytes};
 
 const PURGE_DB_CONFIRMATION: &str = "confirm";
 
@@ -151,6 +155,39 @@ pub fn get_config<E: EthSpec>(
             client_config.http_api.allow_origin = Some(allow_origin.to_string());
         }
 
+        client_config.http_api.api_tokens_file =
+            clap_utils::parse_optional(cli_args, "http-api-tokens-file")?;
+
+        if let Some(cache_size) =
+            clap_utils::parse_optional(cli_args, "http-historical-state-cache-size")?
+        {
+            client_config.http_api.historical_state_cache_size = cache_size;
+        }
+
+        if let Some(queue_size) =
+            clap_utils::parse_optional(cli_args, "http-historical-state-queue-size")?
+        {
+            client_config.http_api.historical_state_queue_size = queue_size;
+        }
+
+        if let Some(budget) =
+            clap_utils::parse_optional(cli_args, "http-historical-state-regen-budget")?
+        {
+            client_config.http_api.historical_state_regen_budget = budget;
+        }
+
+        if let Some(quota) = clap_utils::parse_optional(cli_args, "http-rate-limit")? {
+            let mut rate_limit = RateLimitConfig::new(quota);
+            if let Some(route_costs) = cli_args.get_one::<String>("http-rate-limit-route-costs") {
+                rate_limit.route_costs = route_costs
+                    .split(';')
+                    .filter(|route_cost| !route_cost.is_empty())
+                    .map(str::parse)
+                    .collect::<Result<_, _>>()?;
+            }
+            client_config.http_api.rate_limit = Some(rate_limit);
+        }
+
         if cli_args.get_flag("http-enable-tls") {
             client_config.http_api.tls_config = Some(TlsConfig {
                 cert: cli_args
@@ -177,6 +214,12 @@ pub fn get_config<E: EthSpec>(
 
         client_config.http_api.enable_light_client_server =
             cli_args.get_flag("light-client-server");
+
+        client_config.http_api.enable_proposer_lookahead =
+            cli_args.get_flag("http-proposer-lookahead");
+
+        client_config.http_api.checkpoint_provider_mode =
+            cli_args.get_flag("checkpoint-provider-mode");
     }
 
     if cli_args.get_flag("light-client-server") {
@@ -200,6 +243,16 @@ pub fn get_config<E: EthSpec>(
         client_config.chain.blob_publication_batch_interval = Duration::from_millis(interval);
     }
 
+    let payload_bodies_batch_size: u64 =
+        clap_utils::parse_required(cli_args, "payload-bodies-batch-size")?;
+    if payload_bodies_batch_size == 0 || payload_bodies_batch_size > MAX_PAYLOAD_BODIES_BATCH_SIZE {
+        return Err(format!(
+            "--payload-bodies-batch-size must be between 1 and {}",
+            MAX_PAYLOAD_BODIES_BATCH_SIZE
+        ));
+    }
+    client_config.chain.payload_bodies_batch_size = payload_bodies_batch_size;
+
     /*
      * Prometheus metrics HTTP server
      */
@@ -338,7 +391,21 @@ pub fn get_config<E: EthSpec>(
         el_config.builder_header_timeout =
             clap_utils::parse_optional(cli_args, "builder-header-timeout")?
                 .map(Duration::from_millis);
+
+        if let Some(path) = clap_utils::parse_optional::<PathBuf>(cli_args, "builder-groups")? {
+            el_config.builder_groups = execution_layer::builder_groups::load_builder_groups(&path)?;
+        }
+    }
+
+    // Parse the fallback execution endpoints used for payload reconstruction, if any.
+    if let Some(endpoints) = cli_args.get_one::<String>("execution-fallback-endpoints") {
+        el_config.fallback_execution_endpoints = endpoints
+            .split(',')
+            .map(SensitiveUrl::parse)
+            .collect::<Result<_, _>>()
+            .map_err(|e| format!("Invalid --execution-fallback-endpoints: {:?}", e))?;
     }
+    el_config.fallback_new_payload = cli_args.get_flag("execution-fallback-new-payload");
 
     // Set config values from parse values.
     el_config.secret_file = Some(secret_file.clone());
@@ -347,6 +414,7 @@ pub fn get_config<E: EthSpec>(
         clap_utils::parse_optional(cli_args, "suggested-fee-recipient")?;
     el_config.jwt_id = clap_utils::parse_optional(cli_args, "execution-jwt-id")?;
     el_config.jwt_version = clap_utils::parse_optional(cli_args, "execution-jwt-version")?;
+    el_config.reload_jwt_secret_on_sighup = cli_args.get_flag("execution-jwt-reload-on-sighup");
     el_config
         .default_datadir
         .clone_from(client_config.data_dir());
@@ -405,6 +473,11 @@ pub fn get_config<E: EthSpec>(
             .map_err(|_| "state-cache-size is not a valid integer".to_string())?;
     }
 
+    if let Some(state_cache_size_mb) = clap_utils::parse_optional(cli_args, "state-cache-size-mb")?
+    {
+        client_config.store.state_cache_size_mb = state_cache_size_mb;
+    }
+
     if let Some(historic_state_cache_size) =
         clap_utils::parse_optional(cli_args, "historic-state-cache-size")?
     {
@@ -418,6 +491,16 @@ pub fn get_config<E: EthSpec>(
     }
 
     client_config.store.compact_on_init = cli_args.get_flag("compact-db");
+    if let Some(compaction_interval) =
+        clap_utils::parse_optional::<u64>(cli_args, "db-compaction-interval")?
+    {
+        if compaction_interval == 0 {
+            return Err("db-compaction-interval must be greater than zero".to_string());
+        }
+        client_config.store.compaction_interval = Some(Duration::from_secs(compaction_interval));
+    }
+    client_config.store.compaction_window =
+        clap_utils::parse_optional(cli_args, "db-compaction-window")?;
     if let Some(compact_on_prune) = cli_args.get_one::<String>("auto-compact-db") {
         client_config.store.compact_on_prune = compact_on_prune
             .parse()
@@ -436,12 +519,19 @@ pub fn get_config<E: EthSpec>(
         client_config.store.hierarchy_config = hierarchy_config;
     }
 
+    if clap_utils::parse_optional(cli_args, "hierarchical-state-diffs")? == Some(false) {
+        client_config.store.hierarchy_config = HierarchyConfig::snapshots_only();
+    }
+
     if let Some(epochs_per_migration) =
         clap_utils::parse_optional(cli_args, "epochs-per-migration")?
     {
         client_config.chain.epochs_per_migration = epochs_per_migration;
     }
 
+    client_config.chain.exit_rebroadcast_epochs =
+        clap_utils::parse_required(cli_args, "exit-rebroadcast-epochs")?;
+
     if let Some(prune_blobs) = clap_utils::parse_optional(cli_args, "prune-blobs")? {
         client_config.store.prune_blobs = prune_blobs;
     }
@@ -458,6 +548,15 @@ pub fn get_config<E: EthSpec>(
         client_config.store.blob_prune_margin_epochs = blob_prune_margin_epochs;
     }
 
+    if let Some(blob_retention) = clap_utils::parse_optional(cli_args, "blob-retention-epochs")? {
+        client_config.store.blob_retention = blob_retention;
+    }
+
+    client_config.store.data_column_archive = parse_flag(cli_args, "data-column-archive");
+
+    client_config.store.blob_prune_rate_limit =
+        clap_utils::parse_optional(cli_args, "blob-prune-rate-limit")?;
+
     if let Some(malicious_withhold_count) =
         clap_utils::parse_optional(cli_args, "malicious-withhold-count")?
     {
@@ -589,7 +688,7 @@ pub fn get_config<E: EthSpec>(
 
     if cli_args.get_flag("reconstruct-historic-states") {
         client_config.chain.reconstruct_historic_states = true;
-        client_config.chain.genesis_backfill = true;
+        client_config.chain.backfill_profile = BackfillProfile::Full;
     }
 
     let beacon_graffiti = if let Some(graffiti) = cli_args.get_one::<String>("graffiti") {
@@ -804,6 +903,22 @@ pub fn get_config<E: EthSpec>(
         client_config.sync_eth1_chain = false;
     }
 
+    client_config.chain.late_payload_reveal_threshold =
+        clap_utils::parse_optional(cli_args, "late-payload-reveal-threshold")?
+            .map(Duration::from_millis);
+
+    if let Some(fee_recipients_str) =
+        clap_utils::parse_optional::<String>(cli_args, "late-payload-trusted-fee-recipients")?
+    {
+        client_config.chain.late_payload_trusted_fee_recipients = fee_recipients_str
+            .split(',')
+            .map(|s| {
+                Address::from_str(s.trim())
+                    .map_err(|e| format!("invalid late-payload-trusted-fee-recipients: {e:?}"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+    }
+
     client_config.chain.prepare_payload_lookahead =
         clap_utils::parse_optional(cli_args, "prepare-payload-lookahead")?
             .map(Duration::from_millis)
@@ -824,6 +939,18 @@ pub fn get_config<E: EthSpec>(
 
     client_config.chain.paranoid_block_proposal = cli_args.get_flag("paranoid-block-proposal");
 
+    client_config.chain.attestation_packing_threads =
+        clap_utils::parse_optional(cli_args, "attestation-packing-threads")?;
+
+    client_config.chain.block_production_deadline =
+        clap_utils::parse_optional(cli_args, "block-production-deadline")?
+            .map(Duration::from_millis);
+
+    client_config.chain.block_import_timeout = Duration::from_secs(clap_utils::parse_required(
+        cli_args,
+        "block-import-timeout",
+    )?);
+
     /*
      * Builder fallback configs.
      */
@@ -848,10 +975,20 @@ pub fn get_config<E: EthSpec>(
     client_config.chain.optimistic_finalized_sync =
         !cli_args.get_flag("disable-optimistic-finalized-sync");
 
+    if let Some(backfill_profile) = clap_utils::parse_optional(cli_args, "backfill-profile")? {
+        client_config.chain.backfill_profile = backfill_profile;
+    }
+
+    if let Some(epoch) = clap_utils::parse_optional(cli_args, "backfill-target-epoch")? {
+        client_config.chain.backfill_profile = BackfillProfile::Target(Epoch::new(epoch));
+    }
+
     if cli_args.get_flag("genesis-backfill") {
-        client_config.chain.genesis_backfill = true;
+        client_config.chain.backfill_profile = BackfillProfile::Full;
     }
 
+    client_config.chain.era_import_dir = clap_utils::parse_optional(cli_args, "import-era-dir")?;
+
     // Backfill sync rate-limiting
     client_config.beacon_processor.enable_backfill_rate_limiting =
         !cli_args.get_flag("disable-backfill-rate-limiting");
@@ -882,6 +1019,26 @@ pub fn get_config<E: EthSpec>(
         .beacon_processor
         .max_gossip_aggregate_batch_size =
         clap_utils::parse_required(cli_args, "beacon-processor-aggregate-batch-size")?;
+    client_config
+        .beacon_processor
+        .max_gossip_sync_message_batch_size =
+        clap_utils::parse_required(cli_args, "beacon-processor-sync-message-batch-size")?;
+    client_config.beacon_processor.max_gossip_blob_batch_size =
+        clap_utils::parse_required(cli_args, "beacon-processor-blob-batch-size")?;
+    client_config.beacon_processor.load_shedding_threshold =
+        clap_utils::parse_optional(cli_args, "beacon-processor-load-shedding-threshold")?
+            .map(Duration::from_millis);
+    if let Some(path) =
+        clap_utils::parse_optional::<PathBuf>(cli_args, "beacon-processor-priorities")?
+    {
+        client_config.beacon_processor.priority_overrides =
+            beacon_processor::priority::load_priority_overrides(&path)?;
+    }
+    if let Some(caps) = cli_args.get_one::<String>("beacon-processor-worker-caps") {
+        client_config.beacon_processor.worker_caps =
+            beacon_processor::worker_caps::parse_worker_caps(caps)
+                .map_err(|e| format!("Invalid --beacon-processor-worker-caps: {}", e))?;
+    }
 
     Ok(client_config)
 }
@@ -1128,8 +1285,28 @@ pub fn set_network_config(
         config.subscribe_all_data_column_subnets = true;
     }
 
+    if parse_flag(cli_args, "data-column-archive") {
+        config.data_column_archive = true;
+    }
+
+    if let Some(strategy) = cli_args.get_one::<String>("subnet-strategy") {
+        config.subnet_strategy = strategy
+            .parse()
+            .map_err(|_| format!("Invalid subnet strategy: {}", strategy))?;
+    }
+
     if parse_flag(cli_args, "subscribe-all-subnets") {
-        config.subscribe_all_subnets = true;
+        if config.subnet_strategy == SubnetStrategy::Aggressive {
+            return Err(
+                "--subscribe-all-subnets cannot be used with --subnet-strategy aggressive".into(),
+            );
+        }
+        config.subnet_strategy = SubnetStrategy::AllSubnets;
+    }
+    config.subscribe_all_subnets = config.subnet_strategy == SubnetStrategy::AllSubnets;
+
+    if let Some(epochs) = clap_utils::parse_optional(cli_args, "aggressive-subnet-epochs")? {
+        config.aggressive_subnet_epochs = epochs;
     }
 
     if parse_flag(cli_args, "import-all-attestations") {
@@ -1140,6 +1317,23 @@ pub fn set_network_config(
         config.shutdown_after_sync = true;
     }
 
+    if parse_flag(cli_args, "blinded-blocks-by-range") {
+        config.enable_blinded_blocks_by_range = true;
+    }
+
+    if parse_flag(cli_args, "execution-payload-bodies-by-range") {
+        config.enable_execution_payload_bodies_by_range = true;
+    }
+
+    if parse_flag(cli_args, "persist-pending-lookups") {
+        config.persist_pending_lookups = true;
+    }
+
+    config.lookup_max_parent_depth = parse_required(cli_args, "lookup-max-parent-depth")?;
+    if config.lookup_max_parent_depth == 0 {
+        return Err("lookup-max-parent-depth must be greater than zero".to_string());
+    }
+
     config.set_listening_addr(parse_listening_addresses(cli_args, log)?);
 
     // A custom target-peers command will overwrite the --proposer-only default.
@@ -1192,6 +1386,17 @@ pub fn set_network_config(
             .collect::<Result<Vec<Multiaddr>, _>>()?;
     }
 
+    if let Some(import_peers_path) = cli_args.get_one::<String>("import-peers") {
+        let file = fs::File::open(import_peers_path)
+            .map_err(|e| format!("Unable to open peers file {}: {:?}", import_peers_path, e))?;
+        let mut peers: Vec<ExportedPeer> = serde_json::from_reader(file)
+            .map_err(|e| format!("Unable to parse peers file {}: {:?}", import_peers_path, e))?;
+        // Peers which were being penalized by the exporting node are unlikely to be good peers.
+        peers.retain(|peer| peer.score >= 0.0);
+        peers.sort_by(|a, b| b.score.total_cmp(&a.score));
+        config.imported_peers = peers.into_iter().map(|peer| peer.enr).collect();
+    }
+
     if parse_flag(cli_args, "disable-peer-scoring") {
         config.disable_peer_scoring = true;
     }
@@ -1392,6 +1597,15 @@ pub fn set_network_config(
         config.disable_quic_support = true;
     }
 
+    if let Some(preference) = cli_args.get_one::<String>("transport-preference") {
+        config.transport_preference = preference
+            .parse()
+            .map_err(|_| format!("Invalid transport preference: {}", preference))?;
+    }
+    if config.disable_quic_support && config.transport_preference == TransportPreference::QuicOnly {
+        return Err("--transport-preference quic-only cannot be used with --disable-quic".into());
+    }
+
     if parse_flag(cli_args, "disable-upnp") {
         config.upnp_enabled = false;
     }
@@ -1426,6 +1640,7 @@ pub fn set_network_config(
     // of peers.
     if parse_flag(cli_args, "proposer-only") {
         config.subscribe_all_subnets = false;
+        config.subnet_strategy = SubnetStrategy::Default;
 
         if cli_args.get_one::<String>("target-peers").is_none() {
             // If a custom value is not set, change the default to 15
@@ -1434,6 +1649,10 @@ pub fn set_network_config(
         config.proposer_only = true;
         warn!(log, "Proposer-only mode enabled"; "info"=> "Do not connect a validator client to this node unless via the --proposer-nodes flag");
     }
+
+    // RPC serving only mode avoids subscribing to the gossip topics which aren't required to
+    // follow the head, including all attestation and sync committee subnets.
+    config.rpc_serving_only = parse_flag(cli_args, "rpc-serving-only");
     // The inbound rate limiter is enabled by default unless `disabled` via the
     // `disable-inbound-rate-limiter` flag.
     config.inbound_rate_limiter_config = if parse_flag(cli_args, "disable-inbound-rate-limiter") {
@@ -1460,6 +1679,39 @@ pub fn set_network_config(
             })?;
     }
 
+    if let Some(staggered_publish_peers) = cli_args.get_one::<String>("staggered-publish-peers") {
+        let peers = staggered_publish_peers
+            .parse::<usize>()
+            .ok()
+            .filter(|peers| *peers > 0)
+            .ok_or_else(|| {
+                format!(
+                    "Invalid staggered publish peers value passed: {}",
+                    staggered_publish_peers
+                )
+            })?;
+        config.staggered_publish_peers = Some(peers);
+    }
+
+    if let Some(staggered_publish_delay) = cli_args.get_one::<String>("staggered-publish-delay") {
+        config.staggered_publish_delay = staggered_publish_delay
+            .parse::<u64>()
+            .map(Duration::from_millis)
+            .map_err(|_| {
+                format!(
+                    "Invalid staggered publish delay value passed: {}",
+                    staggered_publish_delay
+                )
+            })?;
+    }
+
+    config.shutdown_rpc_drain_timeout =
+        clap_utils::parse_required(cli_args, "shutdown-rpc-drain-timeout")
+            .map(Duration::from_secs)?;
+
+    config.backfill_batches_per_minute =
+        clap_utils::parse_optional(cli_args, "backfill-batches-per-minute")?;
+
     Ok(())
 }
 
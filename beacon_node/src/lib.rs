@@ -1,5 +1,6 @@
 mod cli;
 mod config;
+pub mod self_test;
 
 pub use beacon_chain;
 use beacon_chain::store::LevelDB;
@@ -0,0 +1,441 @@
+//! A suite of checks which exercise the components the beacon node depends upon, run via
+//! `--self-test` instead of starting the node.
+//!
+//! Each check produces a `SelfTestCheck` and the results are collected into a `SelfTestReport`
+//! which is printed as JSON so that it may be consumed by deployment tooling.
+use beacon_chain::store::{DBColumn, KeyValueStore, LevelDB};
+use client::ClientConfig;
+use environment::RuntimeContext;
+use execution_layer::{BlockByNumberQuery, ExecutionLayer, LATEST_TAG};
+use kzg::{Blob, Kzg, BYTES_PER_BLOB, BYTES_PER_FIELD_ELEMENT};
+use serde::Serialize;
+use slog::{info, warn};
+use std::fs;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use types::{EthSpec, Hash256, Keypair};
+
+/// The name of the temporary database created within the datadir by the store check.
+const SELF_TEST_DB_DIR: &str = "self_test_db";
+/// The number of keys written to, and iterated from, the temporary database.
+const STORE_KEY_COUNT: usize = 64;
+/// The number of signatures created and verified by the BLS check.
+const BLS_ITERATIONS: u32 = 64;
+/// The maximum acceptable average time to verify a single BLS signature.
+const BLS_MAX_AVERAGE_VERIFY_TIME: Duration = Duration::from_millis(10);
+/// How far in the future the execution layer's head may be before the local clock is deemed to
+/// be behind.
+const MAX_CLOCK_DISPARITY: Duration = Duration::from_secs(2);
+/// The number of slots the head of a synced execution layer may be in the past, e.g., due to
+/// missed proposals, before the local clock is deemed to be ahead.
+const MAX_SYNCED_HEAD_AGE_SLOTS: u32 = 4;
+
+/// The outcome of a single check.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SelfTestStatus {
+    Passed,
+    Failed,
+    /// The check could not be run with the provided configuration, e.g., no execution endpoint.
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SelfTestCheck {
+    pub name: &'static str,
+    pub status: SelfTestStatus,
+    pub duration_ms: u128,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SelfTestReport {
+    pub passed: bool,
+    pub checks: Vec<SelfTestCheck>,
+}
+
+impl SelfTestReport {
+    fn new(checks: Vec<SelfTestCheck>) -> Self {
+        let passed = checks
+            .iter()
+            .all(|check| check.status != SelfTestStatus::Failed);
+        Self { passed, checks }
+    }
+}
+
+/// The result of a check which ran, either a description of success or of failure.
+type CheckResult = Result<String, String>;
+
+/// The outcome of a check, with a description of success, failure or why it was skipped.
+#[derive(Debug, PartialEq)]
+enum CheckOutcome {
+    Passed(String),
+    Failed(String),
+    Skipped(String),
+}
+
+impl From<CheckResult> for CheckOutcome {
+    fn from(result: CheckResult) -> Self {
+        match result {
+            Ok(message) => CheckOutcome::Passed(message),
+            Err(message) => CheckOutcome::Failed(message),
+        }
+    }
+}
+
+/// The latest block of the execution layer.
+#[derive(Debug, Clone, Copy)]
+struct ExecutionHead {
+    timestamp: u64,
+    synced: bool,
+}
+
+/// Runs every check against the components described by `client_config`.
+pub async fn run<E: EthSpec>(
+    client_config: &ClientConfig,
+    context: &RuntimeContext<E>,
+) -> SelfTestReport {
+    let log = context.log();
+    let mut checks = vec![];
+
+    checks.push(timed("store", || check_store::<E>(client_config).into()));
+    checks.push(timed("kzg", || check_kzg(client_config).into()));
+    checks.push(timed("bls", || check_bls().into()));
+
+    let start = Instant::now();
+    let el_head = latest_execution_block(client_config, context).await;
+    checks.push(to_check(
+        "execution_layer",
+        start,
+        match &el_head {
+            Some(Ok(head)) => CheckOutcome::Passed(format!(
+                "latest block timestamp {}, synced: {}",
+                head.timestamp, head.synced
+            )),
+            Some(Err(e)) => CheckOutcome::Failed(e.clone()),
+            None => CheckOutcome::Skipped("no execution endpoint configured".to_string()),
+        },
+    ));
+
+    let seconds_per_slot = context.eth2_config.spec.seconds_per_slot;
+    checks.push(timed("clock", || match el_head {
+        Some(Ok(head)) => SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|e| format!("System time is before the unix epoch: {:?}", e))
+            .map_or_else(CheckOutcome::Failed, |now| {
+                check_clock(head, seconds_per_slot, now)
+            }),
+        Some(Err(_)) => CheckOutcome::Skipped("execution layer is unreachable".to_string()),
+        None => CheckOutcome::Skipped("no execution endpoint configured".to_string()),
+    }));
+
+    for check in &checks {
+        match check.status {
+            SelfTestStatus::Failed => warn!(
+                log,
+                "Self-test check failed";
+                "check" => check.name,
+                "reason" => &check.message,
+            ),
+            _ => info!(
+                log,
+                "Self-test check complete";
+                "check" => check.name,
+                "status" => ?check.status,
+                "duration_ms" => check.duration_ms,
+            ),
+        }
+    }
+
+    SelfTestReport::new(checks)
+}
+
+fn timed(name: &'static str, func: impl FnOnce() -> CheckOutcome) -> SelfTestCheck {
+    let start = Instant::now();
+    let outcome = func();
+    to_check(name, start, outcome)
+}
+
+fn to_check(name: &'static str, start: Instant, outcome: CheckOutcome) -> SelfTestCheck {
+    let (status, message) = match outcome {
+        CheckOutcome::Passed(message) => (SelfTestStatus::Passed, message),
+        CheckOutcome::Failed(message) => (SelfTestStatus::Failed, message),
+        CheckOutcome::Skipped(message) => (SelfTestStatus::Skipped, message),
+    };
+    SelfTestCheck {
+        name,
+        status,
+        duration_ms: start.elapsed().as_millis(),
+        message,
+    }
+}
+
+/// Writes, reads, iterates and deletes keys in a temporary database within the datadir.
+fn check_store<E: EthSpec>(client_config: &ClientConfig) -> CheckResult {
+    let path = client_config.create_data_dir()?.join(SELF_TEST_DB_DIR);
+    if path.exists() {
+        fs::remove_dir_all(&path)
+            .map_err(|e| format!("Unable to remove stale {}: {:?}", path.display(), e))?;
+    }
+
+    let result = (|| {
+        let db = LevelDB::<E>::open(&path).map_err(|e| format!("Unable to open: {:?}", e))?;
+        let column = DBColumn::BeaconBlock;
+        let keys = (0..STORE_KEY_COUNT)
+            .map(|_| Hash256::random())
+            .collect::<Vec<_>>();
+
+        for key in &keys {
+            db.put_bytes(column.as_str(), key.as_slice(), key.as_slice())
+                .map_err(|e| format!("Unable to write: {:?}", e))?;
+        }
+
+        for key in &keys {
+            let value = db
+                .get_bytes(column.as_str(), key.as_slice())
+                .map_err(|e| format!("Unable to read: {:?}", e))?;
+            if value.as_deref() != Some(key.as_slice()) {
+                return Err(format!("Read back unexpected value for {:?}", key));
+            }
+        }
+
+        let iterated = db
+            .iter_column_keys::<Hash256>(column)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Unable to iterate: {:?}", e))?;
+        if iterated.len() != STORE_KEY_COUNT {
+            return Err(format!(
+                "Iterated {} keys, expected {}",
+                iterated.len(),
+                STORE_KEY_COUNT
+            ));
+        }
+
+        for key in &keys {
+            db.key_delete(column.as_str(), key.as_slice())
+                .map_err(|e| format!("Unable to delete: {:?}", e))?;
+        }
+
+        Ok(format!("wrote, read and iterated {} keys", STORE_KEY_COUNT))
+    })();
+
+    fs::remove_dir_all(&path)
+        .map_err(|e| format!("Unable to remove {}: {:?}", path.display(), e))?;
+
+    result
+}
+
+/// Loads the trusted setup, then commits to, proves and verifies a blob.
+fn check_kzg(client_config: &ClientConfig) -> CheckResult {
+    let kzg = Kzg::new_from_trusted_setup_no_precomp(client_config.trusted_setup.clone())
+        .map_err(|e| format!("Unable to load trusted setup: {:?}", e))?;
+
+    // Each field element must be less than the BLS modulus, so only the low bytes are set.
+    let mut bytes = vec![0; BYTES_PER_BLOB];
+    for (i, element) in bytes.chunks_mut(BYTES_PER_FIELD_ELEMENT).enumerate() {
+        element[BYTES_PER_FIELD_ELEMENT - 8..].copy_from_slice(&(i as u64).to_be_bytes());
+    }
+    let blob = Blob::from_bytes(&bytes).map_err(|e| format!("Invalid blob: {:?}", e))?;
+
+    let commitment = kzg
+        .blob_to_kzg_commitment(&blob)
+        .map_err(|e| format!("Unable to compute commitment: {:?}", e))?;
+    let proof = kzg
+        .compute_blob_kzg_proof(&blob, commitment)
+        .map_err(|e| format!("Unable to compute proof: {:?}", e))?;
+    kzg.verify_blob_kzg_proof(&blob, commitment, proof)
+        .map_err(|e| format!("Unable to verify proof: {:?}", e))?;
+
+    Ok("verified blob proof".to_string())
+}
+
+/// Signs and verifies messages, failing if verification is unreasonably slow.
+fn check_bls() -> CheckResult {
+    let keypair = Keypair::random();
+    let mut verify_time = Duration::ZERO;
+
+    for _ in 0..BLS_ITERATIONS {
+        let message = Hash256::random();
+        let signature = keypair.sk.sign(message);
+
+        let start = Instant::now();
+        let valid = signature.verify(&keypair.pk, message);
+        verify_time += start.elapsed();
+
+        if !valid {
+            return Err("Signature failed to verify".to_string());
+        }
+    }
+
+    let average = verify_time / BLS_ITERATIONS;
+    if average > BLS_MAX_AVERAGE_VERIFY_TIME {
+        Err(format!(
+            "Average verification time of {:?} exceeds {:?}",
+            average, BLS_MAX_AVERAGE_VERIFY_TIME
+        ))
+    } else {
+        Ok(format!("average verification time {:?}", average))
+    }
+}
+
+/// Fetches the latest block and sync status of the execution layer, returning `None` if no
+/// execution endpoint is configured.
+async fn latest_execution_block<E: EthSpec>(
+    client_config: &ClientConfig,
+    context: &RuntimeContext<E>,
+) -> Option<Result<ExecutionHead, String>> {
+    let config = client_config.execution_layer.clone()?;
+    config.execution_endpoint.as_ref()?;
+
+    let result = async {
+        let execution_layer = ExecutionLayer::<E>::from_config(
+            config,
+            context.executor.clone(),
+            context.log().clone(),
+        )
+        .map_err(|e| format!("Unable to initialize: {:?}", e))?;
+
+        let timestamp = execution_layer
+            .get_block_by_number(BlockByNumberQuery::Tag(LATEST_TAG))
+            .await
+            .map_err(|e| format!("Unable to reach execution endpoint: {:?}", e))?
+            .map(|block| block.timestamp)
+            .ok_or_else(|| "Execution endpoint returned no latest block".to_string())?;
+
+        execution_layer.upcheck().await;
+        let synced = execution_layer.is_synced().await;
+
+        Ok(ExecutionHead { timestamp, synced })
+    }
+    .await;
+
+    Some(result)
+}
+
+/// Compares the local clock, `now`, against the timestamp of the execution layer's head.
+///
+/// A head in the future means the local clock is behind. A synced execution layer's head is at
+/// most a few slots in the past, so an older head means the local clock is ahead. A syncing
+/// execution layer's head may be arbitrarily old, so the check is skipped.
+fn check_clock(head: ExecutionHead, seconds_per_slot: u64, now: Duration) -> CheckOutcome {
+    let head_time = Duration::from_secs(head.timestamp);
+
+    if head_time > now + MAX_CLOCK_DISPARITY {
+        return CheckOutcome::Failed(format!(
+            "Local clock is {:?} behind the execution layer head",
+            head_time - now
+        ));
+    }
+
+    if !head.synced {
+        return CheckOutcome::Skipped(
+            "execution layer is syncing, its head is not a reference for the clock".to_string(),
+        );
+    }
+
+    let head_age = now.saturating_sub(head_time);
+    let max_head_age =
+        Duration::from_secs(seconds_per_slot) * MAX_SYNCED_HEAD_AGE_SLOTS + MAX_CLOCK_DISPARITY;
+    if head_age > max_head_age {
+        CheckOutcome::Failed(format!(
+            "Local clock is {:?} ahead of the synced execution layer head",
+            head_age
+        ))
+    } else {
+        CheckOutcome::Passed(format!(
+            "execution layer head is {:?} behind the local clock",
+            head_age
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use types::MinimalEthSpec;
+
+    const SECONDS_PER_SLOT: u64 = 12;
+
+    #[test]
+    fn store_check_cleans_up() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut client_config = ClientConfig::default();
+        client_config.set_data_dir(dir.path().to_path_buf());
+
+        check_store::<MinimalEthSpec>(&client_config).unwrap();
+        assert!(!dir.path().join(SELF_TEST_DB_DIR).exists());
+    }
+
+    #[test]
+    fn kzg_and_bls_checks_pass() {
+        check_kzg(&ClientConfig::default()).unwrap();
+        check_bls().unwrap();
+    }
+
+    fn clock_check(timestamp: u64, synced: bool) -> CheckOutcome {
+        let head = ExecutionHead { timestamp, synced };
+        check_clock(head, SECONDS_PER_SLOT, Duration::from_secs(100_000))
+    }
+
+    #[test]
+    fn clock_check_detects_clock_behind() {
+        assert!(matches!(
+            clock_check(100_000, true),
+            CheckOutcome::Passed(_)
+        ));
+        assert!(matches!(
+            clock_check(100_001, true),
+            CheckOutcome::Passed(_)
+        ));
+        assert!(matches!(
+            clock_check(100_060, true),
+            CheckOutcome::Failed(_)
+        ));
+        assert!(matches!(
+            clock_check(100_060, false),
+            CheckOutcome::Failed(_)
+        ));
+    }
+
+    #[test]
+    fn clock_check_detects_clock_ahead_of_synced_execution_layer() {
+        // A few missed slots are tolerated.
+        assert!(matches!(clock_check(99_964, true), CheckOutcome::Passed(_)));
+        assert!(matches!(clock_check(96_400, true), CheckOutcome::Failed(_)));
+    }
+
+    #[test]
+    fn clock_check_skipped_for_syncing_execution_layer() {
+        assert!(matches!(
+            clock_check(96_400, false),
+            CheckOutcome::Skipped(_)
+        ));
+        assert!(matches!(
+            clock_check(100_000, false),
+            CheckOutcome::Skipped(_)
+        ));
+    }
+
+    #[test]
+    fn report_fails_only_on_failed_checks() {
+        let check = |status| SelfTestCheck {
+            name: "check",
+            status,
+            duration_ms: 0,
+            message: String::new(),
+        };
+        assert!(
+            SelfTestReport::new(vec![
+                check(SelfTestStatus::Passed),
+                check(SelfTestStatus::Skipped)
+            ])
+            .passed
+        );
+        assert!(
+            !SelfTestReport::new(vec![
+                check(SelfTestStatus::Passed),
+                check(SelfTestStatus::Failed)
+            ])
+            .passed
+        );
+    }
+}
@@ -0,0 +1,235 @@
+//! Scheduling of periodic compactions of the hot database.
+//!
+//! Finalization-triggered compaction (`compact_on_prune`) only runs when pruning deletes data. On
+//! nodes with heavy churn in the hot database it can be useful to compact more regularly, ideally
+//! outside of busy hours. The `CompactionScheduler` decides when such a compaction is due, and
+//! `compact_hot_db` runs it while recording stats for `scrape_for_metrics`.
+use crate::hot_cold_store::HotColdDB;
+use crate::{metrics, DBColumn, Error, ItemStore, KeyValueStore};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::sync::LazyLock;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use types::EthSpec;
+
+/// Columns of the hot database which are compacted by scheduled compactions.
+const HOT_DB_COLUMNS: [DBColumn; 5] = [
+    DBColumn::BeaconBlock,
+    DBColumn::ExecPayload,
+    DBColumn::BeaconState,
+    DBColumn::BeaconStateSummary,
+    DBColumn::BeaconStateTemporary,
+];
+
+/// Columns of the blobs database which are compacted by scheduled compactions.
+const BLOBS_DB_COLUMNS: [DBColumn; 2] = [DBColumn::BeaconBlob, DBColumn::BeaconDataColumn];
+
+/// Stats about scheduled compactions, read by `scrape_for_metrics`.
+static COMPACTION_STATS: LazyLock<RwLock<CompactionStats>> =
+    LazyLock::new(|| RwLock::new(CompactionStats::default()));
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CompactionStats {
+    /// Whether a scheduled compaction is currently running.
+    pub in_progress: bool,
+    /// The time at which the most recent scheduled compaction finished.
+    pub last_completed: Option<SystemTime>,
+    /// How long the most recent scheduled compaction took.
+    pub last_duration: Option<Duration>,
+}
+
+/// Return the stats about scheduled compactions of this process.
+pub fn compaction_stats() -> CompactionStats {
+    *COMPACTION_STATS.read()
+}
+
+/// A daily window of UTC hours during which scheduled compactions may run.
+///
+/// The window includes `start_hour` and excludes `end_hour`. It wraps past midnight if
+/// `end_hour < start_hour`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompactionWindow {
+    pub start_hour: u8,
+    pub end_hour: u8,
+}
+
+impl CompactionWindow {
+    pub fn contains(&self, time: SystemTime) -> bool {
+        let hour = time
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since_epoch| (since_epoch.as_secs() / 3600) % 24) as u8;
+
+        if self.start_hour <= self.end_hour {
+            self.start_hour <= hour && hour < self.end_hour
+        } else {
+            self.start_hour <= hour || hour < self.end_hour
+        }
+    }
+}
+
+impl FromStr for CompactionWindow {
+    type Err = String;
+
+    /// Parse a window of the form `START-END`, e.g. `2-6`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s
+            .split_once('-')
+            .ok_or_else(|| format!("Invalid compaction window {}, expected START-END", s))?;
+        let parse_hour = |hour: &str| {
+            hour.trim()
+                .parse::<u8>()
+                .ok()
+                .filter(|hour| *hour < 24)
+                .ok_or_else(|| format!("Invalid hour {} in compaction window, expected 0-23", hour))
+        };
+        let window = Self {
+            start_hour: parse_hour(start)?,
+            end_hour: parse_hour(end)?,
+        };
+        if window.start_hour == window.end_hour {
+            return Err(format!("Empty compaction window {}", s));
+        }
+        Ok(window)
+    }
+}
+
+/// Decides when the next scheduled compaction of the hot database is due.
+#[derive(Debug, Clone)]
+pub struct CompactionScheduler {
+    interval: Duration,
+    window: Option<CompactionWindow>,
+    last_compaction: SystemTime,
+}
+
+impl CompactionScheduler {
+    /// Create a scheduler which first compacts at least `interval` after `now`.
+    pub fn new(interval: Duration, window: Option<CompactionWindow>, now: SystemTime) -> Self {
+        Self {
+            interval,
+            window,
+            last_compaction: now,
+        }
+    }
+
+    /// Return `true` if `interval` has elapsed since the last compaction and `now` is within the
+    /// compaction window.
+    pub fn is_due(&self, now: SystemTime) -> bool {
+        let interval_elapsed = now
+            .duration_since(self.last_compaction)
+            .is_ok_and(|elapsed| elapsed >= self.interval);
+        interval_elapsed && self.window.map_or(true, |window| window.contains(now))
+    }
+
+    pub fn record_compaction(&mut self, now: SystemTime) {
+        self.last_compaction = now;
+    }
+}
+
+/// Compact the columns of the hot and blobs databases with the most churn.
+///
+/// This may take a long time and should be run on a blocking thread.
+pub fn compact_hot_db<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    db: &HotColdDB<E, Hot, Cold>,
+) -> Result<Duration, Error> {
+    COMPACTION_STATS.write().in_progress = true;
+    let _timer = metrics::start_timer(&metrics::STORE_HOT_DB_COMPACTION_TIMES);
+    let start = Instant::now();
+
+    let result = HOT_DB_COLUMNS
+        .into_iter()
+        .try_for_each(|column| db.hot_db.compact_column(column))
+        .and_then(|()| {
+            BLOBS_DB_COLUMNS
+                .into_iter()
+                .try_for_each(|column| db.blobs_db.compact_column(column))
+        });
+
+    let duration = start.elapsed();
+    let mut stats = COMPACTION_STATS.write();
+    stats.in_progress = false;
+    if result.is_ok() {
+        stats.last_completed = Some(SystemTime::now());
+        stats.last_duration = Some(duration);
+        metrics::inc_counter(&metrics::STORE_HOT_DB_COMPACTIONS);
+    }
+    result.map(|()| duration)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn at_hour(hour: u64) -> SystemTime {
+        // Midnight UTC on 2024-01-01.
+        UNIX_EPOCH + Duration::from_secs(1704067200 + hour * 3600)
+    }
+
+    #[test]
+    fn parse_window() {
+        assert_eq!(
+            "2-6".parse::<CompactionWindow>(),
+            Ok(CompactionWindow {
+                start_hour: 2,
+                end_hour: 6
+            })
+        );
+        assert!("6".parse::<CompactionWindow>().is_err());
+        assert!("2-24".parse::<CompactionWindow>().is_err());
+        assert!("3-3".parse::<CompactionWindow>().is_err());
+    }
+
+    #[test]
+    fn window_contains() {
+        let window = CompactionWindow {
+            start_hour: 2,
+            end_hour: 6,
+        };
+        assert!(!window.contains(at_hour(1)));
+        assert!(window.contains(at_hour(2)));
+        assert!(window.contains(at_hour(5)));
+        assert!(!window.contains(at_hour(6)));
+    }
+
+    #[test]
+    fn window_wraps_past_midnight() {
+        let window = CompactionWindow {
+            start_hour: 22,
+            end_hour: 3,
+        };
+        assert!(window.contains(at_hour(23)));
+        assert!(window.contains(at_hour(24)));
+        assert!(window.contains(at_hour(26)));
+        assert!(!window.contains(at_hour(27)));
+        assert!(!window.contains(at_hour(12)));
+    }
+
+    #[test]
+    fn scheduler_waits_for_interval_and_window() {
+        let interval = Duration::from_secs(6 * 3600);
+        let window = CompactionWindow {
+            start_hour: 2,
+            end_hour: 6,
+        };
+        let mut scheduler = CompactionScheduler::new(interval, Some(window), at_hour(0));
+
+        // Within the window, but the interval has not elapsed.
+        assert!(!scheduler.is_due(at_hour(3)));
+        // The interval has elapsed, but outside the window.
+        assert!(!scheduler.is_due(at_hour(12)));
+        // Both satisfied.
+        assert!(scheduler.is_due(at_hour(26)));
+
+        scheduler.record_compaction(at_hour(26));
+        assert!(!scheduler.is_due(at_hour(27)));
+        assert!(scheduler.is_due(at_hour(50)));
+    }
+
+    #[test]
+    fn scheduler_without_window() {
+        let interval = Duration::from_secs(3600);
+        let scheduler = CompactionScheduler::new(interval, None, at_hour(0));
+        assert!(!scheduler.is_due(at_hour(0)));
+        assert!(scheduler.is_due(at_hour(1)));
+    }
+}
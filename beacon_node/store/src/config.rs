@@ -1,19 +1,23 @@
+use crate::compaction::CompactionWindow;
 use crate::hdiff::HierarchyConfig;
 use crate::{AnchorInfo, DBColumn, Error, Split, StoreItem};
 use serde::{Deserialize, Serialize};
 use ssz::{Decode, Encode};
 use ssz_derive::{Decode, Encode};
 use std::io::Write;
-use std::num::NonZeroUsize;
+use std::num::{NonZeroU64, NonZeroUsize};
+use std::str::FromStr;
+use std::time::Duration;
 use superstruct::superstruct;
 use types::non_zero_usize::new_non_zero_usize;
-use types::EthSpec;
+use types::{ChainSpec, EthSpec};
 use zstd::Encoder;
 
 // Only used in tests. Mainnet sets a higher default on the CLI.
 pub const DEFAULT_EPOCHS_PER_STATE_DIFF: u64 = 8;
 pub const DEFAULT_BLOCK_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(64);
 pub const DEFAULT_STATE_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(128);
+pub const DEFAULT_STATE_CACHE_SIZE_MB: NonZeroUsize = new_non_zero_usize(2048);
 pub const DEFAULT_COMPRESSION_LEVEL: i32 = 1;
 pub const DEFAULT_HISTORIC_STATE_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(1);
 pub const DEFAULT_HDIFF_BUFFER_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(16);
@@ -28,6 +32,8 @@ pub struct StoreConfig {
     pub block_cache_size: NonZeroUsize,
     /// Maximum number of states to store in the in-memory state cache.
     pub state_cache_size: NonZeroUsize,
+    /// Maximum memory used by the in-memory state cache, in megabytes.
+    pub state_cache_size_mb: NonZeroUsize,
     /// Compression level for blocks, state diffs and other compressed values.
     pub compression_level: i32,
     /// Maximum number of historic states to store in the in-memory historic state cache.
@@ -49,6 +55,62 @@ pub struct StoreConfig {
     /// The margin for blob pruning in epochs. The oldest blobs are pruned up until
     /// data_availability_boundary - blob_prune_margin_epochs. Default: 0.
     pub blob_prune_margin_epochs: u64,
+    /// How long blobs are retained for, independent of the data availability boundary.
+    pub blob_retention: BlobRetention,
+    /// Whether to retain all data columns indefinitely, i.e. never prune data columns.
+    pub data_column_archive: bool,
+    /// Maximum rate at which blobs are deleted during blob pruning, in bytes per second.
+    ///
+    /// Unlimited if `None`.
+    pub blob_prune_rate_limit: Option<NonZeroU64>,
+    /// Interval between scheduled compactions of the hot database. Disabled if `None`.
+    pub compaction_interval: Option<Duration>,
+    /// Daily window of UTC hours to which scheduled compactions are restricted.
+    pub compaction_window: Option<CompactionWindow>,
+}
+
+/// The period for which blobs are retained by the blob pruner.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BlobRetention {
+    /// Retain blobs for the data availability period required by the spec.
+    #[default]
+    DataAvailability,
+    /// Retain blobs for this many epochs prior to the current epoch, or the data availability
+    /// period if it is longer.
+    Epochs(u64),
+    /// Never prune blobs.
+    Forever,
+}
+
+impl BlobRetention {
+    /// The number of epochs prior to the data availability boundary from which blobs are
+    /// retained, or `None` if blobs are never pruned.
+    pub fn epochs_before_data_availability_boundary(&self, spec: &ChainSpec) -> Option<u64> {
+        match self {
+            BlobRetention::DataAvailability => Some(0),
+            BlobRetention::Epochs(epochs) => {
+                Some(epochs.saturating_sub(spec.min_epochs_for_blob_sidecars_requests))
+            }
+            BlobRetention::Forever => None,
+        }
+    }
+}
+
+impl FromStr for BlobRetention {
+    type Err = String;
+
+    /// Parse either a number of epochs or `forever`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "forever" {
+            return Ok(BlobRetention::Forever);
+        }
+        s.parse::<u64>().map(BlobRetention::Epochs).map_err(|_| {
+            format!(
+                "Invalid blob retention {}, expected a number of epochs or \"forever\"",
+                s
+            )
+        })
+    }
 }
 
 /// Variant of `StoreConfig` that gets written to disk. Contains immutable configuration params.
@@ -98,6 +160,7 @@ impl Default for StoreConfig {
         Self {
             block_cache_size: DEFAULT_BLOCK_CACHE_SIZE,
             state_cache_size: DEFAULT_STATE_CACHE_SIZE,
+            state_cache_size_mb: DEFAULT_STATE_CACHE_SIZE_MB,
             historic_state_cache_size: DEFAULT_HISTORIC_STATE_CACHE_SIZE,
             hdiff_buffer_cache_size: DEFAULT_HDIFF_BUFFER_CACHE_SIZE,
             compression_level: DEFAULT_COMPRESSION_LEVEL,
@@ -108,11 +171,22 @@ impl Default for StoreConfig {
             prune_blobs: true,
             epochs_per_blob_prune: DEFAULT_EPOCHS_PER_BLOB_PRUNE,
             blob_prune_margin_epochs: DEFAULT_BLOB_PUNE_MARGIN_EPOCHS,
+            blob_retention: BlobRetention::default(),
+            data_column_archive: false,
+            blob_prune_rate_limit: None,
+            compaction_interval: None,
+            compaction_window: None,
         }
     }
 }
 
 impl StoreConfig {
+    /// Maximum memory used by the in-memory state cache, in bytes.
+    pub fn state_cache_max_bytes(&self) -> NonZeroUsize {
+        self.state_cache_size_mb
+            .saturating_mul(new_non_zero_usize(1024 * 1024))
+    }
+
     pub fn as_disk_config(&self) -> OnDiskStoreConfig {
         OnDiskStoreConfig::V22(OnDiskStoreConfigV22::new(self.hierarchy_config.clone()))
     }
@@ -235,6 +309,37 @@ mod test {
     use ssz::DecodeError;
     use types::{Hash256, Slot};
 
+    #[test]
+    fn blob_retention_parse() {
+        assert_eq!("forever".parse(), Ok(BlobRetention::Forever));
+        assert_eq!("8192".parse(), Ok(BlobRetention::Epochs(8192)));
+        assert!("-1".parse::<BlobRetention>().is_err());
+        assert!("always".parse::<BlobRetention>().is_err());
+    }
+
+    #[test]
+    fn blob_retention_beyond_data_availability_boundary() {
+        let spec = ChainSpec::mainnet();
+        let min_epochs = spec.min_epochs_for_blob_sidecars_requests;
+        assert_eq!(
+            BlobRetention::DataAvailability.epochs_before_data_availability_boundary(&spec),
+            Some(0)
+        );
+        assert_eq!(
+            BlobRetention::Epochs(min_epochs + 100).epochs_before_data_availability_boundary(&spec),
+            Some(100)
+        );
+        // Retention can't be shorter than the data availability period.
+        assert_eq!(
+            BlobRetention::Epochs(1).epochs_before_data_availability_boundary(&spec),
+            Some(0)
+        );
+        assert_eq!(
+            BlobRetention::Forever.epochs_before_data_availability_boundary(&spec),
+            None
+        );
+    }
+
     #[test]
     fn check_compatibility_ok() {
         let store_config = StoreConfig {
@@ -293,6 +398,7 @@ mod test {
             oldest_block_parent: Hash256::ZERO,
             state_upper_limit: STATE_UPPER_LIMIT_NO_RETAIN,
             state_lower_limit: Slot::new(0),
+            backfill_target_slot: Slot::new(0),
         };
         assert!(store_config
             .check_compatibility(&on_disk_config, &split, &anchor)
@@ -0,0 +1,94 @@
+//! Reading and writing of records in the e2store format, as used by era files.
+//!
+//! An e2store file is a sequence of records of the form
+//! `type (2 bytes) | length (4 bytes, little-endian) | reserved (2 bytes) | data`, starting with a
+//! version record.
+use std::io::{self, Read, Write};
+
+pub const RECORD_HEADER_LEN: usize = 8;
+/// The type of the version record which starts every e2store file.
+pub const VERSION: [u8; 2] = [0x65, 0x32];
+
+/// Write a record of `record_type` holding `data`.
+pub fn write_record<W: Write>(writer: &mut W, record_type: [u8; 2], data: &[u8]) -> io::Result<()> {
+    let len = u32::try_from(data.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "record too large"))?;
+    writer.write_all(&record_type)?;
+    writer.write_all(&len.to_le_bytes())?;
+    writer.write_all(&[0, 0])?;
+    writer.write_all(data)
+}
+
+/// Read the next record, returning `None` at the end of the input.
+///
+/// Records longer than `max_len` are rejected before their data is allocated.
+pub fn read_record<R: Read>(
+    reader: &mut R,
+    max_len: usize,
+) -> io::Result<Option<([u8; 2], Vec<u8>)>> {
+    let mut header = [0; RECORD_HEADER_LEN];
+    match reader.read_exact(&mut header) {
+        Ok(()) => (),
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    }
+    let record_type = [header[0], header[1]];
+    let len = u32::from_le_bytes([header[2], header[3], header[4], header[5]]) as usize;
+    if len > max_len {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("record length {len} exceeds the maximum of {max_len}"),
+        ));
+    }
+    let mut data = vec![0; len];
+    reader.read_exact(&mut data)?;
+    Ok(Some((record_type, data)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RECORD: [u8; 2] = [0x01, 0x00];
+    const MAX_LEN: usize = 1024;
+
+    #[test]
+    fn record_round_trip() {
+        let mut bytes = vec![];
+        write_record(&mut bytes, VERSION, &[]).unwrap();
+        write_record(&mut bytes, RECORD, &[1, 2, 3]).unwrap();
+
+        let mut reader = bytes.as_slice();
+        assert_eq!(
+            read_record(&mut reader, MAX_LEN).unwrap(),
+            Some((VERSION, vec![]))
+        );
+        assert_eq!(
+            read_record(&mut reader, MAX_LEN).unwrap(),
+            Some((RECORD, vec![1, 2, 3]))
+        );
+        assert_eq!(read_record(&mut reader, MAX_LEN).unwrap(), None);
+    }
+
+    #[test]
+    fn truncated_record_is_an_error() {
+        let mut bytes = vec![];
+        write_record(&mut bytes, RECORD, &[1, 2, 3]).unwrap();
+        bytes.pop();
+        assert!(read_record(&mut bytes.as_slice(), MAX_LEN).is_err());
+    }
+
+    #[test]
+    fn oversized_record_is_an_error() {
+        let mut bytes = vec![];
+        write_record(&mut bytes, RECORD, &[1, 2, 3]).unwrap();
+        assert!(read_record(&mut bytes.as_slice(), 2).is_err());
+
+        // The length is checked before the data is read.
+        let mut header = RECORD.to_vec();
+        header.extend_from_slice(&u32::MAX.to_le_bytes());
+        header.extend_from_slice(&[0, 0]);
+        let err = read_record(&mut header.as_slice(), MAX_LEN).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
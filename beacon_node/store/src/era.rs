@@ -0,0 +1,411 @@
+//! Import of historic blocks from era files, as an alternative to backfilling them from the network.
+//!
+//! An era file holds the blocks of one era (`SLOTS_PER_HISTORICAL_ROOT` slots) followed by the
+//! state at the first slot of the next era. It uses the e2store format (see `e2store`), where
+//! blocks and states are SSZ encoded and compressed with the snappy framing format.
+//!
+//! The state of each era is verified against the historical roots and summaries of the split
+//! state, and each block against the block roots of the era's state. Blocks are imported in the
+//! same way as backfilled blocks, extending the anchor backwards, so eras must be imported from
+//! newest to oldest. The era states are only used for verification: historic states can be
+//! regenerated from the imported blocks by state reconstruction.
+use crate::e2store::{read_record, write_record, VERSION};
+use crate::hot_cold_store::{HotColdDB, HotColdDBError};
+use crate::{
+    get_key_for_col, AnchorInfo, DBColumn, Error, ItemStore, KeyValueStore, KeyValueStoreOp,
+};
+use slog::{debug, info, Logger};
+use snap::write::FrameEncoder;
+use ssz::{DecodeError, Encode};
+use std::fs::{self, File};
+use std::io::{self, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use tree_hash::TreeHash;
+use types::{
+    BeaconState, BeaconStateError, ChainSpec, EthSpec, FixedBytesExtended, Hash256,
+    HistoricalSummary, SignedBeaconBlock, Slot,
+};
+
+/// The file extension of era files.
+pub const ERA_FILE_EXTENSION: &str = "era";
+
+/// Record types defined for era files, in addition to the version record.
+const COMPRESSED_SIGNED_BEACON_BLOCK: [u8; 2] = [0x01, 0x00];
+const COMPRESSED_BEACON_STATE: [u8; 2] = [0x02, 0x00];
+/// The maximum length of a record in an era file. This is far larger than any compressed block or
+/// state, and prevents a corrupt length from causing an allocation of up to 4 GiB.
+const MAX_RECORD_LEN: usize = 1 << 30;
+
+#[derive(Debug)]
+pub enum EraError {
+    Io(io::Error),
+    SszDecode(DecodeError),
+    BeaconState(BeaconStateError),
+    /// The file does not start with a version record.
+    MissingVersion,
+    /// The file contains no state, or more than one.
+    InvalidStateCount(usize),
+    /// The state of the file is not at the start of an era.
+    UnalignedState(Slot),
+    /// The era is not yet covered by the historical roots of the split state.
+    EraNotFinalized(u64),
+    /// The state of the era does not match the historical roots of the split state.
+    HistoricalRootMismatch(u64),
+    /// A block in the file is not part of the canonical chain.
+    BlockRootMismatch {
+        slot: Slot,
+        block_root: Hash256,
+        expected_block_root: Hash256,
+    },
+    /// A block in the file is outside of the era.
+    BlockOutsideEra(Slot),
+    /// The newest relevant block of the era is not the parent of the oldest block in the database.
+    Disconnected {
+        era: u64,
+        oldest_block_slot: Slot,
+    },
+}
+
+impl From<EraError> for Error {
+    fn from(e: EraError) -> Self {
+        Error::Era(e)
+    }
+}
+
+impl From<io::Error> for EraError {
+    fn from(e: io::Error) -> Self {
+        EraError::Io(e)
+    }
+}
+
+impl From<DecodeError> for EraError {
+    fn from(e: DecodeError) -> Self {
+        EraError::SszDecode(e)
+    }
+}
+
+impl From<BeaconStateError> for EraError {
+    fn from(e: BeaconStateError) -> Self {
+        EraError::BeaconState(e)
+    }
+}
+
+/// The contents of an era file.
+pub struct Era<E: EthSpec> {
+    /// The era number, i.e. the state's slot divided by `SLOTS_PER_HISTORICAL_ROOT`.
+    pub era: u64,
+    /// The blocks of the era in slot-ascending order.
+    pub blocks: Vec<SignedBeaconBlock<E>>,
+    /// The state at the end of the era.
+    pub state: BeaconState<E>,
+}
+
+fn compress(data: &[u8]) -> io::Result<Vec<u8>> {
+    let mut encoder = FrameEncoder::new(Vec::with_capacity(data.len()));
+    encoder.write_all(data)?;
+    encoder.flush()?;
+    Ok(encoder.get_ref().clone())
+}
+
+fn decompress(data: &[u8]) -> io::Result<Vec<u8>> {
+    let mut decompressed = vec![];
+    snap::read::FrameDecoder::new(data).read_to_end(&mut decompressed)?;
+    Ok(decompressed)
+}
+
+/// Write an era file holding `blocks` and the `state` at the end of their era, the inverse of
+/// `read_era`. Slot indices are not written.
+pub fn write_era<E: EthSpec, W: Write>(
+    writer: &mut W,
+    blocks: &[SignedBeaconBlock<E>],
+    state: &BeaconState<E>,
+) -> io::Result<()> {
+    write_record(writer, VERSION, &[])?;
+    for block in blocks {
+        let data = compress(&block.as_ssz_bytes())?;
+        write_record(writer, COMPRESSED_SIGNED_BEACON_BLOCK, &data)?;
+    }
+    let data = compress(&state.as_ssz_bytes())?;
+    write_record(writer, COMPRESSED_BEACON_STATE, &data)
+}
+
+/// Read an era file, ignoring records other than blocks and states (such as slot indices).
+pub fn read_era<E: EthSpec, R: Read>(reader: &mut R, spec: &ChainSpec) -> Result<Era<E>, EraError> {
+    match read_record(reader, MAX_RECORD_LEN)? {
+        Some((record_type, _)) if record_type == VERSION => (),
+        _ => return Err(EraError::MissingVersion),
+    }
+
+    let mut blocks = vec![];
+    let mut states = vec![];
+    while let Some((record_type, data)) = read_record(reader, MAX_RECORD_LEN)? {
+        if record_type == COMPRESSED_SIGNED_BEACON_BLOCK {
+            let bytes = decompress(&data)?;
+            blocks.push(SignedBeaconBlock::from_ssz_bytes(&bytes, spec)?);
+        } else if record_type == COMPRESSED_BEACON_STATE {
+            let bytes = decompress(&data)?;
+            states.push(BeaconState::from_ssz_bytes(&bytes, spec)?);
+        }
+    }
+
+    if states.len() != 1 {
+        return Err(EraError::InvalidStateCount(states.len()));
+    }
+    let state = states.remove(0);
+    let slots_per_era = E::slots_per_historical_root() as u64;
+    if state.slot() == 0 || state.slot() % slots_per_era != 0 {
+        return Err(EraError::UnalignedState(state.slot()));
+    }
+    blocks.sort_by_key(|block| block.slot());
+
+    Ok(Era {
+        era: state.slot().as_u64() / slots_per_era,
+        blocks,
+        state,
+    })
+}
+
+/// Verify the state of an era against the historical roots of a newer, trusted state.
+///
+/// Eras before Capella are committed to by `historical_roots`, and later ones by
+/// `historical_summaries`. The root of a `HistoricalBatch` equals the root of the
+/// `HistoricalSummary` of the same block and state roots, so both are checked the same way.
+pub fn verify_era_state<E: EthSpec>(
+    era: u64,
+    era_state: &BeaconState<E>,
+    trusted_state: &BeaconState<E>,
+) -> Result<(), EraError> {
+    let index = era.saturating_sub(1) as usize;
+    let historical_roots = trusted_state.historical_roots();
+    let expected_root = match historical_roots.get(index) {
+        Some(root) => *root,
+        None => trusted_state
+            .historical_summaries()
+            .ok()
+            .and_then(|summaries| summaries.get(index - historical_roots.len()))
+            .ok_or(EraError::EraNotFinalized(era))?
+            .tree_hash_root(),
+    };
+
+    if HistoricalSummary::new(era_state).tree_hash_root() != expected_root {
+        return Err(EraError::HistoricalRootMismatch(era));
+    }
+    Ok(())
+}
+
+/// Import the blocks of an era which precede the oldest block in the database.
+///
+/// The era's state is verified against `trusted_state`, and all blocks against the era's state.
+/// Return the number of blocks imported.
+pub fn import_era<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    db: &HotColdDB<E, Hot, Cold>,
+    era: &Era<E>,
+    trusted_state: &BeaconState<E>,
+) -> Result<usize, Error> {
+    verify_era_state(era.era, &era.state, trusted_state)?;
+
+    let anchor_info = db.get_anchor_info();
+    let era_end_slot = era.state.slot();
+    let era_start_slot = era_end_slot - E::slots_per_historical_root() as u64;
+
+    let mut expected_block_root = anchor_info.oldest_block_parent;
+    let mut prev_block_slot = anchor_info.oldest_block_slot;
+    let mut hot_batch = vec![];
+    let mut cold_batch = vec![];
+    let mut num_imported = 0;
+
+    let relevant_blocks = era
+        .blocks
+        .iter()
+        .rev()
+        .skip_while(|block| block.slot() >= anchor_info.oldest_block_slot);
+    for block in relevant_blocks {
+        if expected_block_root.is_zero() {
+            break;
+        }
+
+        let slot = block.slot();
+        if slot < era_start_slot || slot >= era_end_slot {
+            return Err(EraError::BlockOutsideEra(slot).into());
+        }
+        let block_root = block.canonical_root();
+        let canonical_block_root = *era.state.get_block_root(slot).map_err(EraError::from)?;
+        if block_root != canonical_block_root {
+            return Err(EraError::BlockRootMismatch {
+                slot,
+                block_root,
+                expected_block_root: canonical_block_root,
+            }
+            .into());
+        }
+        if block_root != expected_block_root {
+            return Err(EraError::Disconnected {
+                era: era.era,
+                oldest_block_slot: prev_block_slot,
+            }
+            .into());
+        }
+
+        // Store the block in the hot database without its payload, as backfill does.
+        db.blinded_block_as_kv_store_ops(&block_root, &block.clone_as_blinded(), &mut hot_batch);
+
+        // Store block roots, including at all skip slots in the freezer DB.
+        for slot in (slot.as_u64()..prev_block_slot.as_u64()).rev() {
+            cold_batch.push(block_root_op(slot, block_root));
+        }
+
+        prev_block_slot = slot;
+        expected_block_root = block.parent_root();
+        num_imported += 1;
+    }
+
+    // The genesis block is not necessarily included in the first era. If the next block expected
+    // is the genesis block then fill in its root for all slots before the oldest block.
+    if era.era == 1 && !expected_block_root.is_zero() && prev_block_slot > era_start_slot {
+        let genesis_block_root = *era
+            .state
+            .get_block_root(era_start_slot)
+            .map_err(EraError::from)?;
+        if expected_block_root == genesis_block_root {
+            for slot in 0..prev_block_slot.as_u64() {
+                cold_batch.push(block_root_op(slot, genesis_block_root));
+            }
+            prev_block_slot = Slot::new(0);
+            expected_block_root = Hash256::zero();
+        }
+    }
+
+    if prev_block_slot == anchor_info.oldest_block_slot {
+        return Ok(0);
+    }
+
+    // As for backfill, write the blocks first as it's better for the hot DB to contain extra
+    // blocks than for the cold DB to point to blocks that do not exist.
+    db.hot_db.do_atomically(hot_batch)?;
+    db.cold_db.do_atomically(cold_batch)?;
+
+    let new_anchor = AnchorInfo {
+        oldest_block_slot: prev_block_slot,
+        oldest_block_parent: expected_block_root,
+        ..anchor_info.clone()
+    };
+    db.compare_and_set_anchor_info_with_write(anchor_info, new_anchor)?;
+
+    Ok(num_imported)
+}
+
+fn block_root_op(slot: u64, block_root: Hash256) -> KeyValueStoreOp {
+    KeyValueStoreOp::PutKeyValue(
+        get_key_for_col(DBColumn::BeaconBlockRoots.into(), &slot.to_be_bytes()),
+        block_root.as_slice().to_vec(),
+    )
+}
+
+/// Parse the era number from a file name of the form `<config-name>-<era-number>-<short-root>.era`.
+fn era_number_from_path(path: &Path) -> Option<u64> {
+    if path.extension()? != ERA_FILE_EXTENSION {
+        return None;
+    }
+    let stem = path.file_stem()?.to_str()?;
+    let mut parts = stem.rsplitn(3, '-');
+    let _short_root = parts.next()?;
+    parts.next()?.parse().ok()
+}
+
+/// Import the blocks of all era files in `dir` which precede the oldest block in the database.
+///
+/// Eras are imported from newest to oldest, and eras whose blocks are all already known are
+/// skipped without being read. Return the total number of blocks imported.
+pub fn import_era_dir<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    db: &HotColdDB<E, Hot, Cold>,
+    dir: &Path,
+    log: &Logger,
+) -> Result<usize, Error> {
+    let split = db.get_split_info();
+    let trusted_state = db.get_state(&split.state_root, Some(split.slot))?.ok_or(
+        HotColdDBError::MissingSplitState(split.state_root, split.slot),
+    )?;
+
+    let mut era_files: Vec<(u64, PathBuf)> = fs::read_dir(dir)
+        .map_err(EraError::from)?
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            Some((era_number_from_path(&path)?, path))
+        })
+        .collect();
+    era_files.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+    info!(
+        log,
+        "Importing era files";
+        "dir" => %dir.display(),
+        "files" => era_files.len(),
+        "oldest_block_slot" => db.get_anchor_info().oldest_block_slot,
+    );
+
+    let slots_per_era = E::slots_per_historical_root() as u64;
+    let mut total_imported = 0;
+    for (era_number, path) in era_files {
+        let anchor_info = db.get_anchor_info();
+        if anchor_info.oldest_block_parent.is_zero() {
+            break;
+        }
+        if era_number.saturating_sub(1) * slots_per_era >= anchor_info.oldest_block_slot.as_u64() {
+            debug!(log, "Skipping known era"; "era" => era_number);
+            continue;
+        }
+
+        let file = File::open(&path).map_err(EraError::from)?;
+        let era = read_era::<E, _>(&mut BufReader::new(file), &db.spec)?;
+        if era.era != era_number {
+            return Err(EraError::UnalignedState(era.state.slot()).into());
+        }
+        let num_imported = import_era(db, &era, &trusted_state)?;
+        total_imported += num_imported;
+
+        info!(
+            log,
+            "Imported era";
+            "era" => era_number,
+            "blocks" => num_imported,
+            "oldest_block_slot" => db.get_anchor_info().oldest_block_slot,
+        );
+    }
+
+    Ok(total_imported)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn era_number_from_file_name() {
+        let era = |name: &str| era_number_from_path(Path::new(name));
+        assert_eq!(era("mainnet-00042-a1b2c3d4.era"), Some(42));
+        assert_eq!(era("/data/era/holesky-01234-5e6f7a8b.era"), Some(1234));
+        assert_eq!(era("mainnet-00042-a1b2c3d4.e2s"), None);
+        assert_eq!(era("mainnet-blobs-00042.e2s"), None);
+        assert_eq!(era("notes.era"), None);
+    }
+
+    #[test]
+    fn read_era_requires_version() {
+        let spec = ChainSpec::mainnet();
+        let mut input: &[u8] = &[];
+        assert!(matches!(
+            read_era::<types::MainnetEthSpec, _>(&mut input, &spec),
+            Err(EraError::MissingVersion)
+        ));
+    }
+
+    #[test]
+    fn read_era_requires_state() {
+        let spec = ChainSpec::mainnet();
+        let mut input: &[u8] = &[0x65, 0x32, 0, 0, 0, 0, 0, 0];
+        assert!(matches!(
+            read_era::<types::MainnetEthSpec, _>(&mut input, &spec),
+            Err(EraError::InvalidStateCount(0))
+        ));
+    }
+}
@@ -1,5 +1,6 @@
 use crate::chunked_vector::ChunkError;
 use crate::config::StoreConfigError;
+use crate::era::EraError;
 use crate::hot_cold_store::HotColdDBError;
 use crate::{hdiff, DBColumn};
 use ssz::DecodeError;
@@ -69,6 +70,7 @@ pub enum Error {
     RandaoMixOutOfBounds,
     GenesisStateUnknown,
     ArithError(safe_arith::ArithError),
+    Era(EraError),
 }
 
 pub trait HandleUnavailable<T> {
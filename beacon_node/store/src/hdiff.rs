@@ -594,6 +594,17 @@ impl Default for HierarchyConfig {
 }
 
 impl HierarchyConfig {
+    /// A single-layer hierarchy which stores only full snapshots, without any diffs.
+    ///
+    /// Snapshots are stored every 2^11 = 2048 slots, matching the default of the former
+    /// `--slots-per-restore-point` flag. This uses much more space than the default hierarchy,
+    /// but avoids the cost of applying diffs when loading historic states.
+    pub fn snapshots_only() -> Self {
+        HierarchyConfig {
+            exponents: vec![11],
+        }
+    }
+
     pub fn to_moduli(&self) -> Result<HierarchyModuli, Error> {
         self.validate()?;
         let moduli = self.exponents.iter().map(|n| 1 << n).collect();
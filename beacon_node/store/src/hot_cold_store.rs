@@ -7,9 +7,10 @@ use crate::iter::{BlockRootsIterator, ParentRootBlockIterator, RootsIterator};
 use crate::leveldb_store::{BytesKey, LevelDB};
 use crate::memory_store::MemoryStore;
 use crate::metadata::{
-    AnchorInfo, BlobInfo, CompactionTimestamp, DataColumnInfo, PruningCheckpoint, SchemaVersion,
-    ANCHOR_FOR_ARCHIVE_NODE, ANCHOR_INFO_KEY, ANCHOR_UNINITIALIZED, BLOB_INFO_KEY,
-    COMPACTION_TIMESTAMP_KEY, CONFIG_KEY, CURRENT_SCHEMA_VERSION, DATA_COLUMN_INFO_KEY,
+    AnchorInfo, BlobInfo, CompactionTimestamp, DataColumnCustodyInfo, DataColumnInfo,
+    PruningCheckpoint, SchemaVersion, ANCHOR_FOR_ARCHIVE_NODE, ANCHOR_INFO_KEY,
+    ANCHOR_UNINITIALIZED, BLOB_INFO_KEY, COMPACTION_TIMESTAMP_KEY, CONFIG_KEY,
+    CURRENT_SCHEMA_VERSION, DATA_COLUMN_CUSTODY_INFO_KEY, DATA_COLUMN_INFO_KEY,
     PRUNING_CHECKPOINT_KEY, SCHEMA_VERSION_KEY, SPLIT_KEY, STATE_UPPER_LIMIT_NO_RETAIN,
 };
 use crate::state_cache::{PutStateOutcome, StateCache};
@@ -35,7 +36,8 @@ use std::cmp::min;
 use std::collections::{HashMap, HashSet};
 use std::io::{Read, Write};
 use std::marker::PhantomData;
-use std::num::NonZeroUsize;
+use std::mem;
+use std::num::{NonZeroU64, NonZeroUsize};
 use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
@@ -215,7 +217,10 @@ impl<E: EthSpec> HotColdDB<E, MemoryStore<E>, MemoryStore<E>> {
             blobs_db: MemoryStore::open(),
             hot_db: MemoryStore::open(),
             block_cache: Mutex::new(BlockCache::new(config.block_cache_size)),
-            state_cache: Mutex::new(StateCache::new(config.state_cache_size)),
+            state_cache: Mutex::new(StateCache::new(
+                config.state_cache_size,
+                config.state_cache_max_bytes(),
+            )),
             historic_state_cache: Mutex::new(HistoricStateCache::new(
                 config.hdiff_buffer_cache_size,
                 config.historic_state_cache_size,
@@ -261,7 +266,10 @@ impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
             blobs_db: LevelDB::open(blobs_db_path)?,
             hot_db,
             block_cache: Mutex::new(BlockCache::new(config.block_cache_size)),
-            state_cache: Mutex::new(StateCache::new(config.state_cache_size)),
+            state_cache: Mutex::new(StateCache::new(
+                config.state_cache_size,
+                config.state_cache_max_bytes(),
+            )),
             historic_state_cache: Mutex::new(HistoricStateCache::new(
                 config.hdiff_buffer_cache_size,
                 config.historic_state_cache_size,
@@ -444,6 +452,12 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         self.state_cache.lock().len()
     }
 
+    /// Return the `(state_root, block_root, slot)` of every state in the state cache, excluding
+    /// the finalized state.
+    pub fn state_cache_summaries(&self) -> Vec<(Hash256, Hash256, Slot)> {
+        self.state_cache.lock().summaries()
+    }
+
     pub fn register_metrics(&self) {
         let hsc_metrics = self.historic_state_cache.lock().metrics();
 
@@ -455,10 +469,16 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
             &metrics::STORE_BEACON_BLOB_CACHE_SIZE,
             self.block_cache.lock().blob_cache.len() as i64,
         );
+        let state_cache = self.state_cache.lock();
         metrics::set_gauge(
             &metrics::STORE_BEACON_STATE_CACHE_SIZE,
-            self.state_cache.lock().len() as i64,
+            state_cache.len() as i64,
+        );
+        metrics::set_gauge(
+            &metrics::STORE_BEACON_STATE_CACHE_MEMORY_SIZE,
+            state_cache.resident_bytes() as i64,
         );
+        drop(state_cache);
         metrics::set_gauge(
             &metrics::STORE_BEACON_HISTORIC_STATE_CACHE_SIZE,
             hsc_metrics.num_state as i64,
@@ -485,6 +505,13 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
             &metrics::STORE_BEACON_STATE_LOWER_LIMIT,
             anchor_info.state_lower_limit.as_u64() as i64,
         );
+
+        if let Some(oldest_blob_slot) = self.get_blob_info().oldest_blob_slot {
+            metrics::set_gauge(
+                &metrics::STORE_BEACON_OLDEST_BLOB_SLOT,
+                oldest_blob_slot.as_u64() as i64,
+            );
+        }
     }
 
     /// Store a block and update the LRU cache.
@@ -817,20 +844,42 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         Ok(light_client_updates)
     }
 
+    /// Store the best light client update for `sync_committee_period`, along with its rank.
     pub fn store_light_client_update(
         &self,
         sync_committee_period: u64,
         light_client_update: &LightClientUpdate<E>,
+        rank: &LightClientUpdateRank,
     ) -> Result<(), Error> {
-        let column = DBColumn::LightClientUpdate;
-
-        self.hot_db.put_bytes(
-            column.into(),
-            &sync_committee_period.to_le_bytes(),
-            &light_client_update.as_ssz_bytes(),
-        )?;
+        let key = sync_committee_period.to_le_bytes();
+        let ops = vec![
+            KeyValueStoreOp::PutKeyValue(
+                get_key_for_col(DBColumn::LightClientUpdate.into(), &key),
+                light_client_update.as_ssz_bytes(),
+            ),
+            KeyValueStoreOp::PutKeyValue(
+                get_key_for_col(DBColumn::LightClientUpdateRank.into(), &key),
+                rank.as_ssz_bytes(),
+            ),
+        ];
+        self.hot_db.do_atomically(ops)
+    }
 
-        Ok(())
+    /// Fetch the rank of the light client update stored for `sync_committee_period`.
+    ///
+    /// Returns `None` if there is no update, or if the update was stored without a rank.
+    pub fn get_light_client_update_rank(
+        &self,
+        sync_committee_period: u64,
+    ) -> Result<Option<LightClientUpdateRank>, Error> {
+        self.hot_db
+            .get_bytes(
+                DBColumn::LightClientUpdateRank.into(),
+                &sync_committee_period.to_le_bytes(),
+            )?
+            .map(|bytes| LightClientUpdateRank::from_ssz_bytes(&bytes))
+            .transpose()
+            .map_err(Into::into)
     }
 
     /// Check if the blobs for a block exists on disk.
@@ -856,6 +905,13 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
             .key_delete(DBColumn::BeaconBlob.into(), block_root.as_slice())
     }
 
+    /// Delete the blobs for a block from the store and the block cache.
+    pub fn delete_blobs(&self, block_root: &Hash256) -> Result<(), Error> {
+        self.block_cache.lock().delete_blobs(block_root);
+        self.blobs_db
+            .key_delete(DBColumn::BeaconBlob.into(), block_root.as_slice())
+    }
+
     pub fn put_blobs(&self, block_root: &Hash256, blobs: BlobSidecarList<E>) -> Result<(), Error> {
         self.blobs_db.put_bytes(
             DBColumn::BeaconBlob.into(),
@@ -1517,6 +1573,31 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         }
     }
 
+    /// Load a post-finalization state from the hot database into the state cache.
+    ///
+    /// Return `true` if the state was loaded, or `false` if it was already cached or is not stored
+    /// in the hot database.
+    pub fn load_hot_state_into_cache(&self, state_root: &Hash256) -> Result<bool, Error> {
+        if self
+            .state_cache
+            .lock()
+            .get_by_state_root(*state_root)
+            .is_some()
+        {
+            return Ok(false);
+        }
+
+        let Some((mut state, block_root)) = self.load_hot_state(state_root)? else {
+            return Ok(false);
+        };
+        state.update_tree_hash_cache()?;
+        state.build_all_caches(&self.spec)?;
+        self.state_cache
+            .lock()
+            .put_state(*state_root, block_root, &state)?;
+        Ok(true)
+    }
+
     /// Load a post-finalization state from the hot database.
     ///
     /// Will replay blocks from the nearest epoch boundary.
@@ -2184,6 +2265,8 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
                 oldest_block_parent: block.parent_root(),
                 state_upper_limit,
                 state_lower_limit: self.spec.genesis_slot,
+                // Nothing is backfilled until the beacon chain sets the backfill target.
+                backfill_target_slot: anchor_slot,
             }
         };
         self.compare_and_set_anchor_info(ANCHOR_UNINITIALIZED, anchor_info)
@@ -2563,6 +2646,19 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         )
     }
 
+    /// Load the custody info of data columns from disk.
+    pub fn load_data_column_custody_info(&self) -> Result<Option<DataColumnCustodyInfo>, Error> {
+        self.hot_db.get(&DATA_COLUMN_CUSTODY_INFO_KEY)
+    }
+
+    /// Store the custody info of data columns to disk.
+    pub fn store_data_column_custody_info(
+        &self,
+        custody_info: &DataColumnCustodyInfo,
+    ) -> Result<(), Error> {
+        self.hot_db.put(&DATA_COLUMN_CUSTODY_INFO_KEY, custody_info)
+    }
+
     /// Update the linear array of frozen block roots with the block root for several skipped slots.
     ///
     /// Write the block root at all slots from `start_slot` (inclusive) to `end_slot` (exclusive).
@@ -2709,14 +2805,21 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
 
     /// Try to prune blobs older than the data availability boundary.
     ///
-    /// Blobs from the epoch `data_availability_boundary - blob_prune_margin_epochs` are retained.
-    /// This epoch is an _exclusive_ endpoint for the pruning process.
+    /// Blobs from the epoch `data_availability_boundary - blob_prune_margin_epochs` are retained,
+    /// as are any older blobs within the configured `blob_retention` period. This epoch is an
+    /// _exclusive_ endpoint for the pruning process. If `data_column_archive` is set, data columns
+    /// are never pruned, so only blobs prior to the PeerDAS fork are pruned.
     ///
     /// This function only supports pruning blobs older than the split point, which is older than
     /// (or equal to) finalization. Pruning blobs newer than finalization is not supported.
     ///
-    /// This function also assumes that the split is stationary while it runs. It should only be
-    /// run from the migrator thread (where `migrate_database` runs) or the database manager.
+    /// Deletions are committed one epoch at a time, sleeping between batches if
+    /// `blob_prune_rate_limit` is set. To avoid reading every blob just to measure it, the size of
+    /// each batch is estimated from the number of blob lists and data columns it deletes, assuming
+    /// each block has the maximum number of blobs. The split may advance while this function runs, as only
+    /// blobs older than the split at the start of the run are pruned. It assumes that it is the
+    /// only writer of the blob info, so it should only be run from the migrator's blob pruning
+    /// thread or the database manager.
     pub fn try_prune_blobs(
         &self,
         force: bool,
@@ -2730,12 +2833,24 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         let pruning_enabled = self.get_config().prune_blobs;
         let margin_epochs = self.get_config().blob_prune_margin_epochs;
         let epochs_per_blob_prune = self.get_config().epochs_per_blob_prune;
+        let blob_retention = self.get_config().blob_retention;
+        let retention_epochs = blob_retention.epochs_before_data_availability_boundary(&self.spec);
+
+        // Blobs which are retained forever are never pruned, even when pruning is forced.
+        let Some(retention_epochs) = retention_epochs else {
+            debug!(
+                self.log,
+                "Blob pruning is disabled";
+                "blob_retention" => ?blob_retention,
+            );
+            return Ok(());
+        };
 
         if !force && !pruning_enabled {
             debug!(
                 self.log,
                 "Blob pruning is disabled";
-                "prune_blobs" => pruning_enabled
+                "prune_blobs" => pruning_enabled,
             );
             return Ok(());
         }
@@ -2750,14 +2865,20 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         // The start epoch is inclusive (blobs in this epoch will be pruned).
         let start_epoch = oldest_blob_slot.epoch(E::slots_per_epoch());
 
-        // Prune blobs up until the `data_availability_boundary - margin` or the split
-        // slot's epoch, whichever is older. We can't prune blobs newer than the split.
-        // The end epoch is also inclusive (blobs in this epoch will be pruned).
+        // Prune blobs up until the `data_availability_boundary - margin`, the start of the
+        // retention period or the split slot's epoch, whichever is older. We can't prune blobs
+        // newer than the split. The end epoch is also inclusive (blobs in this epoch will be
+        // pruned).
         let split = self.get_split_info();
-        let end_epoch = std::cmp::min(
-            data_availability_boundary - margin_epochs - 1,
+        let mut end_epoch = std::cmp::min(
+            data_availability_boundary - std::cmp::max(margin_epochs, retention_epochs) - 1,
             split.slot.epoch(E::slots_per_epoch()) - 1,
         );
+        if self.get_config().data_column_archive {
+            if let Some(eip7594_fork_epoch) = self.spec.eip7594_fork_epoch {
+                end_epoch = std::cmp::min(end_epoch, eip7594_fork_epoch.saturating_sub(1u64));
+            }
+        }
         let end_slot = end_epoch.end_slot(E::slots_per_epoch());
 
         let can_prune = end_epoch != 0 && start_epoch <= end_epoch;
@@ -2797,7 +2918,14 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
             "data_availability_boundary" => data_availability_boundary,
         );
 
+        let rate_limit = self.get_config().blob_prune_rate_limit;
+        let max_blob_list_bytes = (E::max_blobs_per_block() * BlobSidecar::<E>::max_size()) as u64;
+        let max_data_column_bytes = DataColumnSidecar::<E>::max_size() as u64;
         let mut ops = vec![];
+        let mut batch_epoch = start_epoch;
+        let mut batch_bytes = 0;
+        let mut blob_lists_pruned = 0;
+        let mut bytes_reclaimed = 0;
         let mut last_pruned_block_root = None;
 
         for res in self.forwards_block_roots_iterator_until(oldest_blob_slot, end_slot, || {
@@ -2822,6 +2950,21 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
                 }
             };
 
+            // Commit the deletions one epoch at a time so that progress is persisted and other
+            // database operations are not blocked behind a single large write.
+            let epoch = slot.epoch(E::slots_per_epoch());
+            if epoch > batch_epoch {
+                self.commit_blob_prune_batch(
+                    mem::take(&mut ops),
+                    epoch.start_slot(E::slots_per_epoch()),
+                    batch_bytes,
+                    rate_limit,
+                )?;
+                bytes_reclaimed += batch_bytes;
+                batch_bytes = 0;
+                batch_epoch = epoch;
+            }
+
             if Some(block_root) != last_pruned_block_root {
                 if self
                     .spec
@@ -2836,7 +2979,9 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
                             "slot" => slot,
                             "block_root" => ?block_root,
                         );
+                        batch_bytes += indices.len() as u64 * max_data_column_bytes;
                         last_pruned_block_root = Some(block_root);
+                        blob_lists_pruned += 1;
                         ops.push(StoreOp::DeleteDataColumns(block_root, indices));
                     }
                 } else if self.blobs_exist(&block_root)? {
@@ -2846,7 +2991,9 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
                         "slot" => slot,
                         "block_root" => ?block_root,
                     );
+                    batch_bytes += max_blob_list_bytes;
                     last_pruned_block_root = Some(block_root);
+                    blob_lists_pruned += 1;
                     ops.push(StoreOp::DeleteBlobs(block_root));
                 }
             }
@@ -2855,21 +3002,53 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
                 break;
             }
         }
-        let blob_lists_pruned = ops.len();
+        self.commit_blob_prune_batch(ops, end_slot + 1, batch_bytes, None)?;
+        bytes_reclaimed += batch_bytes;
+
+        debug!(
+            self.log,
+            "Blob pruning complete";
+            "blob_lists_pruned" => blob_lists_pruned,
+            "bytes_reclaimed" => bytes_reclaimed,
+        );
+
+        Ok(())
+    }
+
+    /// Delete one batch of blobs and advance the `oldest_blob_slot` to `new_oldest_blob_slot`.
+    ///
+    /// If a `rate_limit` in bytes per second is provided, sleep long enough after the write to
+    /// keep the deletion rate below it.
+    fn commit_blob_prune_batch(
+        &self,
+        mut ops: Vec<StoreOp<E>>,
+        new_oldest_blob_slot: Slot,
+        bytes: u64,
+        rate_limit: Option<NonZeroU64>,
+    ) -> Result<(), Error> {
+        let blob_info = self.get_blob_info();
         let new_blob_info = BlobInfo {
-            oldest_blob_slot: Some(end_slot + 1),
+            oldest_blob_slot: Some(new_oldest_blob_slot),
             blobs_db: blob_info.blobs_db,
         };
         let update_blob_info = self.compare_and_set_blob_info(blob_info, new_blob_info)?;
         ops.push(StoreOp::KeyValueOp(update_blob_info));
 
         self.do_atomically_with_block_and_blobs_cache(ops)?;
-        debug!(
-            self.log,
-            "Blob pruning complete";
-            "blob_lists_pruned" => blob_lists_pruned,
+
+        metrics::set_gauge(
+            &metrics::STORE_BEACON_OLDEST_BLOB_SLOT,
+            new_oldest_blob_slot.as_u64() as i64,
         );
+        metrics::inc_counter_by(&metrics::STORE_BEACON_BLOB_PRUNE_BYTES_RECLAIMED, bytes);
 
+        if let Some(bytes_per_second) = rate_limit {
+            if bytes > 0 {
+                std::thread::sleep(Duration::from_secs_f64(
+                    bytes as f64 / bytes_per_second.get() as f64,
+                ));
+            }
+        }
         Ok(())
     }
 
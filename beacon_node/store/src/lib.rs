@@ -9,8 +9,11 @@
 //! tests for implementation examples.
 pub mod chunked_iter;
 pub mod chunked_vector;
+pub mod compaction;
 pub mod config;
 pub mod consensus_context;
+pub mod e2store;
+pub mod era;
 pub mod errors;
 mod forwards_iter;
 mod garbage_collection;
@@ -28,7 +31,7 @@ pub mod state_cache;
 
 pub mod iter;
 
-pub use self::config::StoreConfig;
+pub use self::config::{BlobRetention, StoreConfig};
 pub use self::consensus_context::OnDiskConsensusContext;
 pub use self::hot_cold_store::{HotColdDB, HotStateSummary, Split};
 pub use self::leveldb_store::LevelDB;
@@ -293,6 +296,9 @@ pub enum DBColumn {
     OpPool,
     #[strum(serialize = "etc")]
     Eth1Cache,
+    /// For the shuffling, proposer and attester caches persisted on shutdown.
+    #[strum(serialize = "bcc")]
+    BeaconChainCaches,
     #[strum(serialize = "frk")]
     ForkChoice,
     #[strum(serialize = "pkc")]
@@ -332,6 +338,9 @@ pub enum DBColumn {
     BeaconRandaoMixes,
     #[strum(serialize = "dht")]
     DhtEnrs,
+    /// For block lookups which were pending when the node was last shut down.
+    #[strum(serialize = "slk")]
+    SyncLookups,
     /// For Optimistically Imported Merge Transition Blocks
     #[strum(serialize = "otb")]
     OptimisticTransitionBlock,
@@ -343,6 +352,9 @@ pub enum DBColumn {
     /// For persisting eagerly computed light client data
     #[strum(serialize = "lcu")]
     LightClientUpdate,
+    /// For ranking newly computed light client updates against the persisted ones
+    #[strum(serialize = "lcr")]
+    LightClientUpdateRank,
     /// For helping persist eagerly computed light client bootstrap data
     #[strum(serialize = "scb")]
     SyncCommitteeBranch,
@@ -383,10 +395,12 @@ impl DBColumn {
             | Self::BeaconChain
             | Self::OpPool
             | Self::Eth1Cache
+            | Self::BeaconChainCaches
             | Self::ForkChoice
             | Self::PubkeyCache
             | Self::BeaconRestorePoint
             | Self::DhtEnrs
+            | Self::SyncLookups
             | Self::OptimisticTransitionBlock => 32,
             Self::BeaconBlockRoots
             | Self::BeaconBlockRootsChunked
@@ -399,7 +413,8 @@ impl DBColumn {
             | Self::BeaconStateDiff
             | Self::SyncCommittee
             | Self::SyncCommitteeBranch
-            | Self::LightClientUpdate => 8,
+            | Self::LightClientUpdate
+            | Self::LightClientUpdateRank => 8,
             Self::BeaconDataColumn => DATA_COLUMN_DB_KEY_SIZE,
         }
     }
@@ -4,7 +4,7 @@ use ssz::{Decode, Encode};
 use ssz_derive::{Decode, Encode};
 use types::{Checkpoint, Hash256, Slot};
 
-pub const CURRENT_SCHEMA_VERSION: SchemaVersion = SchemaVersion(22);
+pub const CURRENT_SCHEMA_VERSION: SchemaVersion = SchemaVersion(23);
 
 // All the keys that get stored under the `BeaconMeta` column.
 //
@@ -17,6 +17,7 @@ pub const COMPACTION_TIMESTAMP_KEY: Hash256 = Hash256::repeat_byte(4);
 pub const ANCHOR_INFO_KEY: Hash256 = Hash256::repeat_byte(5);
 pub const BLOB_INFO_KEY: Hash256 = Hash256::repeat_byte(6);
 pub const DATA_COLUMN_INFO_KEY: Hash256 = Hash256::repeat_byte(7);
+pub const DATA_COLUMN_CUSTODY_INFO_KEY: Hash256 = Hash256::repeat_byte(8);
 
 /// State upper limit value used to indicate that a node is not storing historic states.
 pub const STATE_UPPER_LIMIT_NO_RETAIN: Slot = Slot::new(u64::MAX);
@@ -28,6 +29,7 @@ pub const ANCHOR_FOR_ARCHIVE_NODE: AnchorInfo = AnchorInfo {
     oldest_block_parent: Hash256::ZERO,
     state_upper_limit: Slot::new(0),
     state_lower_limit: Slot::new(0),
+    backfill_target_slot: Slot::new(0),
 };
 
 /// The `AnchorInfo` encoding an uninitialized anchor.
@@ -40,6 +42,7 @@ pub const ANCHOR_UNINITIALIZED: AnchorInfo = AnchorInfo {
     oldest_block_parent: Hash256::ZERO,
     state_upper_limit: Slot::new(u64::MAX),
     state_lower_limit: Slot::new(0),
+    backfill_target_slot: Slot::new(u64::MAX),
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -151,6 +154,16 @@ pub struct AnchorInfo {
     /// When full block backfill completes (`oldest_block_slot == 0`) state reconstruction starts and
     /// this value will progressively increase until reaching `state_upper_limit`.
     pub state_lower_limit: Slot,
+    /// The slot back to which block backfill downloads blocks, as set by the backfill profile.
+    /// Once backfill completes, `oldest_block_slot` is at or before this slot.
+    ///
+    /// Values on start:
+    /// - Genesis start: 0
+    /// - Checkpoint sync: the backfill target, or `oldest_block_slot` if it is older
+    ///
+    /// Updated on start-up if the backfill profile changes. Anchors written prior to schema v23
+    /// lack this field, and are read with a value of 0.
+    pub backfill_target_slot: Slot,
 }
 
 impl AnchorInfo {
@@ -186,6 +199,58 @@ impl StoreItem for AnchorInfo {
         self.as_ssz_bytes()
     }
 
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() == <AnchorInfoV22 as Decode>::ssz_fixed_len() {
+            return Ok(AnchorInfoV22::from_ssz_bytes(bytes)?.into());
+        }
+        Ok(Self::from_ssz_bytes(bytes)?)
+    }
+}
+
+/// The `AnchorInfo` of schema v22 and earlier, which lacks the `backfill_target_slot`.
+#[derive(Debug, PartialEq, Eq, Clone, Encode, Decode)]
+pub struct AnchorInfoV22 {
+    pub anchor_slot: Slot,
+    pub oldest_block_slot: Slot,
+    pub oldest_block_parent: Hash256,
+    pub state_upper_limit: Slot,
+    pub state_lower_limit: Slot,
+}
+
+impl From<AnchorInfoV22> for AnchorInfo {
+    fn from(anchor: AnchorInfoV22) -> Self {
+        Self {
+            anchor_slot: anchor.anchor_slot,
+            oldest_block_slot: anchor.oldest_block_slot,
+            oldest_block_parent: anchor.oldest_block_parent,
+            state_upper_limit: anchor.state_upper_limit,
+            state_lower_limit: anchor.state_lower_limit,
+            backfill_target_slot: Slot::new(0),
+        }
+    }
+}
+
+impl From<AnchorInfo> for AnchorInfoV22 {
+    fn from(anchor: AnchorInfo) -> Self {
+        Self {
+            anchor_slot: anchor.anchor_slot,
+            oldest_block_slot: anchor.oldest_block_slot,
+            oldest_block_parent: anchor.oldest_block_parent,
+            state_upper_limit: anchor.state_upper_limit,
+            state_lower_limit: anchor.state_lower_limit,
+        }
+    }
+}
+
+impl StoreItem for AnchorInfoV22 {
+    fn db_column() -> DBColumn {
+        DBColumn::BeaconMeta
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
     fn from_store_bytes(bytes: &[u8]) -> Result<Self, Error> {
         Ok(Self::from_ssz_bytes(bytes)?)
     }
@@ -246,3 +311,54 @@ impl StoreItem for DataColumnInfo {
         Ok(Self::from_ssz_bytes(bytes)?)
     }
 }
+
+/// Database parameters relevant to the custody of data columns.
+#[derive(Debug, PartialEq, Eq, Clone, Encode, Decode, Serialize, Deserialize)]
+pub struct DataColumnCustodyInfo {
+    /// The number of data column subnets custodied by the node when it was last started.
+    pub custody_subnet_count: u64,
+    /// The slot from which the columns of all custodied subnets are stored (>=).
+    ///
+    /// Blocks prior to this slot were imported with a lower custody subnet count and may be missing
+    /// some of the columns which are now custodied. If the `custody_backfill_slot` is `None` then
+    /// no columns are missing.
+    pub custody_backfill_slot: Option<Slot>,
+}
+
+impl DataColumnCustodyInfo {
+    /// Returns the custody info after a restart with `custody_subnet_count`.
+    ///
+    /// If the custody subnet count has increased then the columns of the new subnets are missing
+    /// for all blocks prior to `current_slot`. A decrease never requires any columns to be fetched.
+    pub fn with_custody_subnet_count(
+        &self,
+        custody_subnet_count: u64,
+        current_slot: Slot,
+        eip7594_fork_slot: Option<Slot>,
+    ) -> Self {
+        let custody_increased = custody_subnet_count > self.custody_subnet_count
+            && eip7594_fork_slot.map_or(false, |fork_slot| fork_slot < current_slot);
+        Self {
+            custody_subnet_count,
+            custody_backfill_slot: if custody_increased {
+                Some(current_slot)
+            } else {
+                self.custody_backfill_slot
+            },
+        }
+    }
+}
+
+impl StoreItem for DataColumnCustodyInfo {
+    fn db_column() -> DBColumn {
+        DBColumn::BeaconMeta
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Self::from_ssz_bytes(bytes)?)
+    }
+}
@@ -1,8 +1,10 @@
 pub use metrics::{set_gauge, try_create_int_gauge, *};
 
+use crate::compaction::compaction_stats;
 use directory::size_of_dir;
 use std::path::Path;
 use std::sync::LazyLock;
+use std::time::UNIX_EPOCH;
 
 /*
  * General
@@ -94,6 +96,22 @@ pub static STORE_BEACON_STATE_LOWER_LIMIT: LazyLock<Result<IntGauge>> = LazyLock
         "Current anchor info state_lower_limit value",
     )
 });
+/*
+ * Blob Info
+ */
+pub static STORE_BEACON_OLDEST_BLOB_SLOT: LazyLock<Result<IntGauge>> = LazyLock::new(|| {
+    try_create_int_gauge(
+        "store_beacon_oldest_blob_slot",
+        "Current blob info oldest_blob_slot value",
+    )
+});
+pub static STORE_BEACON_BLOB_PRUNE_BYTES_RECLAIMED: LazyLock<Result<IntCounter>> =
+    LazyLock::new(|| {
+        try_create_int_counter(
+            "store_beacon_blob_prune_bytes_reclaimed_total",
+            "Estimated total bytes of blobs and data columns deleted by blob pruning",
+        )
+    });
 /*
  * Beacon State
  */
@@ -212,6 +230,18 @@ pub static STORE_BEACON_STATE_CACHE_SIZE: LazyLock<Result<IntGauge>> = LazyLock:
         "Current count of items in beacon store state cache",
     )
 });
+pub static STORE_BEACON_STATE_CACHE_MEMORY_SIZE: LazyLock<Result<IntGauge>> = LazyLock::new(|| {
+    try_create_int_gauge(
+        "store_beacon_state_cache_memory_size",
+        "Bytes of memory used by the finalized state and states in the state cache",
+    )
+});
+pub static STORE_BEACON_STATE_CACHE_EVICTIONS: LazyLock<Result<IntCounter>> = LazyLock::new(|| {
+    try_create_int_counter(
+        "store_beacon_state_cache_evictions_total",
+        "Number of states culled from the state cache to stay within its size limits",
+    )
+});
 pub static STORE_BEACON_HISTORIC_STATE_CACHE_SIZE: LazyLock<Result<IntGauge>> =
     LazyLock::new(|| {
         try_create_int_gauge(
@@ -367,10 +397,67 @@ pub static BEACON_DATA_COLUMNS_CACHE_HIT_COUNT: LazyLock<Result<IntCounter>> =
         )
     });
 
+/*
+ * Scheduled compaction
+ */
+pub static STORE_HOT_DB_COMPACTIONS: LazyLock<Result<IntCounter>> = LazyLock::new(|| {
+    try_create_int_counter(
+        "store_hot_db_compactions_total",
+        "Number of scheduled compactions of the hot database",
+    )
+});
+pub static STORE_HOT_DB_COMPACTION_TIMES: LazyLock<Result<Histogram>> = LazyLock::new(|| {
+    try_create_histogram_with_buckets(
+        "store_hot_db_compaction_seconds",
+        "Time taken to run a scheduled compaction of the hot database",
+        Ok(vec![
+            1.0, 5.0, 15.0, 30.0, 60.0, 120.0, 300.0, 600.0, 1800.0,
+        ]),
+    )
+});
+pub static STORE_HOT_DB_COMPACTION_IN_PROGRESS: LazyLock<Result<IntGauge>> = LazyLock::new(|| {
+    try_create_int_gauge(
+        "store_hot_db_compaction_in_progress",
+        "Set to 1 while a scheduled compaction of the hot database is running",
+    )
+});
+pub static STORE_HOT_DB_LAST_COMPACTION_TIMESTAMP: LazyLock<Result<IntGauge>> =
+    LazyLock::new(|| {
+        try_create_int_gauge(
+            "store_hot_db_last_compaction_timestamp_seconds",
+            "Unix timestamp at which the last scheduled compaction of the hot database finished",
+        )
+    });
+pub static STORE_HOT_DB_LAST_COMPACTION_DURATION: LazyLock<Result<IntGauge>> =
+    LazyLock::new(|| {
+        try_create_int_gauge(
+            "store_hot_db_last_compaction_duration_milliseconds",
+            "Duration of the last scheduled compaction of the hot database",
+        )
+    });
+
 /// Updates the global metrics registry with store-related information.
 pub fn scrape_for_metrics(db_path: &Path, freezer_db_path: &Path) {
     let db_size = size_of_dir(db_path);
     set_gauge(&DISK_DB_SIZE, db_size as i64);
     let freezer_db_size = size_of_dir(freezer_db_path);
     set_gauge(&FREEZER_DB_SIZE, freezer_db_size as i64);
+
+    let compaction_stats = compaction_stats();
+    set_gauge(
+        &STORE_HOT_DB_COMPACTION_IN_PROGRESS,
+        compaction_stats.in_progress as i64,
+    );
+    if let Some(last_completed) = compaction_stats.last_completed {
+        let timestamp = last_completed
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since_epoch| since_epoch.as_secs());
+        set_gauge(&STORE_HOT_DB_LAST_COMPACTION_TIMESTAMP, timestamp as i64);
+    }
+    if let Some(last_duration) = compaction_stats.last_duration {
+        set_gauge(
+            &STORE_HOT_DB_LAST_COMPACTION_DURATION,
+            last_duration.as_millis() as i64,
+        );
+    }
 }
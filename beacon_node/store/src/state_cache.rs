@@ -1,13 +1,18 @@
-use crate::Error;
+use crate::{metrics, Error};
 use lru::LruCache;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::num::NonZeroUsize;
+use types::milhouse::mem::MemoryTracker;
 use types::{BeaconState, ChainSpec, Epoch, EthSpec, Hash256, Slot};
 
 /// Fraction of the LRU cache to leave intact during culling.
 const CULL_EXEMPT_NUMERATOR: usize = 1;
 const CULL_EXEMPT_DENOMINATOR: usize = 10;
 
+/// Fraction of the memory budget to cull down to once it has been exceeded.
+const MEMORY_CULL_TARGET_NUMERATOR: usize = 9;
+const MEMORY_CULL_TARGET_DENOMINATOR: usize = 10;
+
 /// States that are less than or equal to this many epochs old *could* become finalized and will not
 /// be culled from the cache.
 const EPOCH_FINALIZATION_LIMIT: u64 = 4;
@@ -30,12 +35,37 @@ pub struct SlotMap {
     slots: BTreeMap<Slot, Hash256>,
 }
 
-#[derive(Debug)]
 pub struct StateCache<E: EthSpec> {
     finalized_state: Option<FinalizedState<E>>,
     states: LruCache<Hash256, BeaconState<E>>,
     block_map: BlockMap,
     max_epoch: Epoch,
+    /// Maximum number of bytes which may be used by the finalized state and cached states.
+    max_bytes: usize,
+    /// Tracks the tree nodes of all cached states, so that the additional memory used by each new
+    /// state can be measured incrementally.
+    ///
+    /// This is replaced by a new tracker on each call to `measure_memory`, so that it doesn't
+    /// retain the nodes of states which have been removed from the cache.
+    memory_tracker: MemoryTracker,
+    /// Number of bytes used by the cached states.
+    ///
+    /// This is an over-estimate between measurements, as states which are removed from the cache
+    /// are only accounted for by the next call to `measure_memory`.
+    resident_bytes: usize,
+}
+
+impl<E: EthSpec> std::fmt::Debug for StateCache<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StateCache")
+            .field("finalized_state", &self.finalized_state)
+            .field("states", &self.states)
+            .field("block_map", &self.block_map)
+            .field("max_epoch", &self.max_epoch)
+            .field("max_bytes", &self.max_bytes)
+            .field("resident_bytes", &self.resident_bytes)
+            .finish_non_exhaustive()
+    }
 }
 
 #[derive(Debug)]
@@ -47,12 +77,15 @@ pub enum PutStateOutcome {
 
 #[allow(clippy::len_without_is_empty)]
 impl<E: EthSpec> StateCache<E> {
-    pub fn new(capacity: NonZeroUsize) -> Self {
+    pub fn new(capacity: NonZeroUsize, max_bytes: NonZeroUsize) -> Self {
         StateCache {
             finalized_state: None,
             states: LruCache::new(capacity),
             block_map: BlockMap::default(),
             max_epoch: Epoch::new(0),
+            max_bytes: max_bytes.get(),
+            memory_tracker: MemoryTracker::default(),
+            resident_bytes: 0,
         }
     }
 
@@ -64,6 +97,14 @@ impl<E: EthSpec> StateCache<E> {
         self.states.cap().get()
     }
 
+    pub fn max_bytes(&self) -> usize {
+        self.max_bytes
+    }
+
+    pub fn resident_bytes(&self) -> usize {
+        self.resident_bytes
+    }
+
     pub fn update_finalized_state(
         &mut self,
         state_root: Hash256,
@@ -97,6 +138,9 @@ impl<E: EthSpec> StateCache<E> {
 
         // Update finalized state.
         self.finalized_state = Some(FinalizedState { state_root, state });
+
+        // Re-measure to account for the pruned states and the new finalized state.
+        self.measure_memory(self.max_bytes);
         Ok(())
     }
 
@@ -162,6 +206,16 @@ impl<E: EthSpec> StateCache<E> {
         let slot = state.slot();
         self.block_map.insert(block_root, slot, state_root);
 
+        // Account for the tree nodes which are not shared with any other cached state, culling
+        // states if the memory budget is exceeded. Culling reduces the memory used to less than
+        // the budget so that the cache isn't re-measured on every insertion once it is full.
+        self.resident_bytes += self.memory_tracker.track_item(state).differential_size;
+        if self.resident_bytes > self.max_bytes {
+            self.measure_memory(
+                self.max_bytes / MEMORY_CULL_TARGET_DENOMINATOR * MEMORY_CULL_TARGET_NUMERATOR,
+            );
+        }
+
         Ok(PutStateOutcome::New)
     }
 
@@ -194,6 +248,21 @@ impl<E: EthSpec> StateCache<E> {
         Some((state_root, state))
     }
 
+    /// Return the `(state_root, block_root, slot)` of every cached state, excluding the finalized
+    /// state.
+    pub fn summaries(&self) -> Vec<(Hash256, Hash256, Slot)> {
+        self.block_map
+            .blocks
+            .iter()
+            .flat_map(|(block_root, slot_map)| {
+                slot_map
+                    .slots
+                    .iter()
+                    .map(|(slot, state_root)| (*state_root, *block_root, *slot))
+            })
+            .collect()
+    }
+
     pub fn delete_state(&mut self, state_root: &Hash256) {
         self.states.pop(state_root);
         self.block_map.delete(state_root);
@@ -216,6 +285,79 @@ impl<E: EthSpec> StateCache<E> {
     /// - Epoch-boundary states that are too old to be finalized.
     /// - Epoch-boundary states that could be finalized.
     pub fn cull(&mut self, count: usize) {
+        let state_roots = self.cull_candidates(count);
+        metrics::inc_counter_by(
+            &metrics::STORE_BEACON_STATE_CACHE_EVICTIONS,
+            state_roots.len() as u64,
+        );
+        for state_root in &state_roots {
+            self.delete_state(state_root);
+        }
+    }
+
+    /// Measure the memory used by the finalized state and cached states from scratch, culling
+    /// states once the memory used exceeds `target_bytes`.
+    ///
+    /// States are measured in the reverse of the order in which they would be culled by `cull`,
+    /// so that the size of each state only counts the tree nodes which it doesn't share with the
+    /// finalized state or a state which will be kept. The first state which would take the memory
+    /// used above `target_bytes` is culled, along with every state after it. States exempt from
+    /// culling are always kept.
+    fn measure_memory(&mut self, target_bytes: usize) {
+        let mut memory_tracker = MemoryTracker::default();
+        let mut resident_bytes = 0;
+
+        if let Some(finalized_state) = &self.finalized_state {
+            resident_bytes += memory_tracker
+                .track_item(&finalized_state.state)
+                .differential_size;
+        }
+
+        let cull_order = self.cull_candidates(self.len());
+        let cull_candidates = cull_order.iter().copied().collect::<HashSet<_>>();
+        let keep_order = self
+            .states
+            .iter()
+            .map(|(&state_root, _)| state_root)
+            .filter(|state_root| !cull_candidates.contains(state_root))
+            .chain(cull_order.iter().rev().copied());
+
+        let mut state_roots_to_cull = vec![];
+        let mut over_target = false;
+        for state_root in keep_order {
+            let is_cull_candidate = cull_candidates.contains(&state_root);
+            if over_target && is_cull_candidate {
+                state_roots_to_cull.push(state_root);
+                continue;
+            }
+            let Some(state) = self.states.peek(&state_root) else {
+                continue;
+            };
+            let size = memory_tracker.track_item(state).differential_size;
+            if is_cull_candidate && resident_bytes + size > target_bytes {
+                // The nodes of this state remain in the tracker, so the size of states added
+                // before the next measurement may be slightly under-estimated.
+                over_target = true;
+                state_roots_to_cull.push(state_root);
+            } else {
+                resident_bytes += size;
+            }
+        }
+
+        metrics::inc_counter_by(
+            &metrics::STORE_BEACON_STATE_CACHE_EVICTIONS,
+            state_roots_to_cull.len() as u64,
+        );
+        for state_root in &state_roots_to_cull {
+            self.delete_state(state_root);
+        }
+
+        self.memory_tracker = memory_tracker;
+        self.resident_bytes = resident_bytes;
+    }
+
+    /// Return approximately `count` states to cull in the order described by `cull`.
+    fn cull_candidates(&self, count: usize) -> Vec<Hash256> {
         let cull_exempt = std::cmp::max(
             1,
             self.len() * CULL_EXEMPT_NUMERATOR / CULL_EXEMPT_DENOMINATOR,
@@ -250,17 +392,14 @@ impl<E: EthSpec> StateCache<E> {
             }
         }
 
-        // Stage 2: delete.
-        // This could probably be more efficient in how it interacts with the block map.
-        for state_root in advanced_state_roots
-            .iter()
-            .chain(mid_epoch_state_roots.iter())
-            .chain(old_boundary_state_roots.iter())
-            .chain(good_boundary_state_roots.iter())
+        // Stage 2: order.
+        advanced_state_roots
+            .into_iter()
+            .chain(mid_epoch_state_roots)
+            .chain(old_boundary_state_roots)
+            .chain(good_boundary_state_roots)
             .take(count)
-        {
-            self.delete_state(state_root);
-        }
+            .collect()
     }
 }
 
@@ -301,3 +440,77 @@ impl BlockMap {
         self.blocks.remove(block_root)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{Eth1Data, FixedBytesExtended, MinimalEthSpec, Vector};
+
+    type E = MinimalEthSpec;
+
+    /// Returns a state at `slot` whose randao mixes are not shared with any other state.
+    fn distinct_state(slot: u64, spec: &ChainSpec) -> BeaconState<E> {
+        let mut state = BeaconState::<E>::new(0, Eth1Data::default(), spec);
+        *state.slot_mut() = Slot::new(slot);
+        let mixes = (0..E::epochs_per_historical_vector() as u64)
+            .map(|i| Hash256::from_low_u64_be(slot * 1_000_000 + i + 1));
+        *state.randao_mixes_mut() = Vector::try_from_iter(mixes).unwrap();
+        state.apply_pending_mutations().unwrap();
+        state
+    }
+
+    #[test]
+    fn culls_to_memory_budget() {
+        let spec = E::default_spec();
+        let states = (1..=4)
+            .map(|slot| distinct_state(slot, &spec))
+            .collect::<Vec<_>>();
+        let mut memory_tracker = MemoryTracker::default();
+        let state_sizes = states
+            .iter()
+            .map(|state| memory_tracker.track_item(state).differential_size)
+            .collect::<Vec<_>>();
+
+        // Room for two and a half states.
+        let max_bytes = state_sizes[0] + state_sizes[1] + state_sizes[2] / 2;
+        let mut cache = StateCache::new(
+            NonZeroUsize::new(16).unwrap(),
+            NonZeroUsize::new(max_bytes).unwrap(),
+        );
+
+        for (i, state) in states.iter().enumerate() {
+            let state_root = Hash256::from_low_u64_be(i as u64 + 1);
+            cache.put_state(state_root, state_root, state).unwrap();
+            assert!(cache.resident_bytes() <= max_bytes);
+        }
+
+        // The third and fourth states each exceeded the budget, causing a state to be culled.
+        // The most recent state is exempt from culling.
+        assert_eq!(cache.len(), 2);
+        assert!(cache.resident_bytes() >= state_sizes[0] + state_sizes[1]);
+        assert!(cache
+            .get_by_state_root(Hash256::from_low_u64_be(4))
+            .is_some());
+    }
+
+    #[test]
+    fn shared_memory_is_counted_once() {
+        let spec = E::default_spec();
+        let state = distinct_state(1, &spec);
+        let state_size = MemoryTracker::default().track_item(&state).total_size;
+        let mut cache = StateCache::new(
+            NonZeroUsize::new(16).unwrap(),
+            NonZeroUsize::new(state_size * 2).unwrap(),
+        );
+
+        // Clones of a state share all of their tree nodes, so don't exceed the budget.
+        for i in 1..=4 {
+            let mut clone = state.clone();
+            *clone.slot_mut() = Slot::new(i);
+            let state_root = Hash256::from_low_u64_be(i);
+            cache.put_state(state_root, state_root, &clone).unwrap();
+        }
+        assert_eq!(cache.len(), 4);
+        assert!(cache.resident_bytes() < state_size * 2);
+    }
+}
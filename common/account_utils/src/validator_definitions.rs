@@ -62,6 +62,12 @@ pub enum PasswordStorage {
 #[derive(Clone, PartialEq, Serialize, Deserialize, Hash, Eq)]
 pub struct Web3SignerDefinition {
     pub url: String,
+    /// Additional Web3Signer instances holding the same key, used if `url` is unavailable.
+    ///
+    /// All instances must share a slashing protection database, if they have one.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fallback_urls: Vec<String>,
     /// Path to a .pem file.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub root_certificate_path: Option<PathBuf>,
@@ -162,6 +168,16 @@ pub struct ValidatorDefinition {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub prefer_builder_proposals: Option<bool>,
+    /// Builder bids below this value (in gwei) are ignored by the beacon node in favour of a
+    /// locally built payload.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub builder_min_bid_gwei: Option<u64>,
+    /// The time into the slot at which to produce attestations, in milliseconds. Defaults to one
+    /// third of the slot. Must be less than two thirds of the slot, when aggregates are produced.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attestation_offset_ms: Option<u64>,
     #[serde(default)]
     pub description: String,
     #[serde(flatten)]
@@ -207,6 +223,8 @@ impl ValidatorDefinition {
             builder_proposals,
             builder_boost_factor,
             prefer_builder_proposals,
+            attestation_offset_ms: None,
+            builder_min_bid_gwei: None,
             signing_definition: SigningDefinition::LocalKeystore {
                 voting_keystore_path,
                 voting_keystore_password_path,
@@ -357,6 +375,8 @@ impl ValidatorDefinitions {
                     builder_proposals: None,
                     builder_boost_factor: None,
                     prefer_builder_proposals: None,
+                    attestation_offset_ms: None,
+                    builder_min_bid_gwei: None,
                     signing_definition: SigningDefinition::LocalKeystore {
                         voting_keystore_path,
                         voting_keystore_password_path,
@@ -648,6 +668,60 @@ mod tests {
         assert_eq!(def.gas_limit, Some(35000000));
     }
 
+    #[test]
+    fn attestation_offset_checks() {
+        let no_attestation_offset = r#"---
+        description: ""
+        enabled: true
+        type: local_keystore
+        voting_keystore_path: ""
+        voting_public_key: "0xaf3c7ddab7e293834710fca2d39d068f884455ede270e0d0293dc818e4f2f0f975355067e8437955cb29aec674e5c9e7"
+        "#;
+        let def: ValidatorDefinition = serde_yaml::from_str(no_attestation_offset).unwrap();
+        assert!(def.attestation_offset_ms.is_none());
+        assert!(!serde_yaml::to_string(&def)
+            .unwrap()
+            .contains("attestation_offset_ms"));
+
+        let valid_attestation_offset = r#"---
+        description: ""
+        enabled: true
+        type: local_keystore
+        attestation_offset_ms: 4500
+        voting_keystore_path: ""
+        voting_public_key: "0xaf3c7ddab7e293834710fca2d39d068f884455ede270e0d0293dc818e4f2f0f975355067e8437955cb29aec674e5c9e7"
+        "#;
+        let def: ValidatorDefinition = serde_yaml::from_str(valid_attestation_offset).unwrap();
+        assert_eq!(def.attestation_offset_ms, Some(4500));
+    }
+
+    #[test]
+    fn builder_min_bid_checks() {
+        let no_builder_min_bid = r#"---
+        description: ""
+        enabled: true
+        type: local_keystore
+        voting_keystore_path: ""
+        voting_public_key: "0xaf3c7ddab7e293834710fca2d39d068f884455ede270e0d0293dc818e4f2f0f975355067e8437955cb29aec674e5c9e7"
+        "#;
+        let def: ValidatorDefinition = serde_yaml::from_str(no_builder_min_bid).unwrap();
+        assert!(def.builder_min_bid_gwei.is_none());
+        assert!(!serde_yaml::to_string(&def)
+            .unwrap()
+            .contains("builder_min_bid_gwei"));
+
+        let valid_builder_min_bid = r#"---
+        description: ""
+        enabled: true
+        type: local_keystore
+        builder_min_bid_gwei: 50000000
+        voting_keystore_path: ""
+        voting_public_key: "0xaf3c7ddab7e293834710fca2d39d068f884455ede270e0d0293dc818e4f2f0f975355067e8437955cb29aec674e5c9e7"
+        "#;
+        let def: ValidatorDefinition = serde_yaml::from_str(valid_builder_min_bid).unwrap();
+        assert_eq!(def.builder_min_bid_gwei, Some(50000000));
+    }
+
     #[test]
     fn builder_proposals_checks() {
         let no_builder_proposals = r#"---
@@ -687,4 +761,44 @@ mod tests {
         let def: ValidatorDefinition = serde_yaml::from_str(valid_builder_proposals).unwrap();
         assert_eq!(def.builder_proposals, Some(true));
     }
+
+    #[test]
+    fn web3signer_fallback_urls_checks() {
+        let no_fallback_urls = r#"---
+        description: ""
+        enabled: true
+        type: web3signer
+        url: "http://signer-1:9000"
+        voting_public_key: "0xaf3c7ddab7e293834710fca2d39d068f884455ede270e0d0293dc818e4f2f0f975355067e8437955cb29aec674e5c9e7"
+        "#;
+        let def: ValidatorDefinition = serde_yaml::from_str(no_fallback_urls).unwrap();
+        match &def.signing_definition {
+            SigningDefinition::Web3Signer(web3_signer) => {
+                assert!(web3_signer.fallback_urls.is_empty())
+            }
+            SigningDefinition::LocalKeystore { .. } => panic!("expected a web3signer definition"),
+        }
+        assert!(!serde_yaml::to_string(&def)
+            .unwrap()
+            .contains("fallback_urls"));
+
+        let fallback_urls = r#"---
+        description: ""
+        enabled: true
+        type: web3signer
+        url: "http://signer-1:9000"
+        fallback_urls:
+          - "http://signer-2:9000"
+          - "http://signer-3:9000"
+        voting_public_key: "0xaf3c7ddab7e293834710fca2d39d068f884455ede270e0d0293dc818e4f2f0f975355067e8437955cb29aec674e5c9e7"
+        "#;
+        let def: ValidatorDefinition = serde_yaml::from_str(fallback_urls).unwrap();
+        match &def.signing_definition {
+            SigningDefinition::Web3Signer(web3_signer) => assert_eq!(
+                web3_signer.fallback_urls,
+                vec!["http://signer-2:9000", "http://signer-3:9000"]
+            ),
+            SigningDefinition::LocalKeystore { .. } => panic!("expected a web3signer definition"),
+        }
+    }
 }
@@ -47,6 +47,7 @@ pub const CONSENSUS_BLOCK_VALUE_HEADER: &str = "Eth-Consensus-Block-Value";
 
 pub const CONTENT_TYPE_HEADER: &str = "Content-Type";
 pub const SSZ_CONTENT_TYPE_HEADER: &str = "application/octet-stream";
+pub const SSZ_SNAPPY_CONTENT_TYPE_HEADER: &str = "application/octet-stream+snappy";
 
 #[derive(Debug)]
 pub enum Error {
@@ -2018,6 +2019,7 @@ impl BeaconNodeHttpClient {
         graffiti: Option<&Graffiti>,
         skip_randao_verification: SkipRandaoVerification,
         builder_booster_factor: Option<u64>,
+        builder_min_bid_gwei: Option<u64>,
     ) -> Result<Url, Error> {
         let mut path = self.eth_path(V3)?;
 
@@ -2045,6 +2047,11 @@ impl BeaconNodeHttpClient {
                 .append_pair("builder_boost_factor", &builder_booster_factor.to_string());
         }
 
+        if let Some(builder_min_bid_gwei) = builder_min_bid_gwei {
+            path.query_pairs_mut()
+                .append_pair("builder_min_bid_gwei", &builder_min_bid_gwei.to_string());
+        }
+
         Ok(path)
     }
 
@@ -2062,6 +2069,7 @@ impl BeaconNodeHttpClient {
             graffiti,
             SkipRandaoVerification::No,
             builder_booster_factor,
+            None,
         )
         .await
     }
@@ -2074,6 +2082,7 @@ impl BeaconNodeHttpClient {
         graffiti: Option<&Graffiti>,
         skip_randao_verification: SkipRandaoVerification,
         builder_booster_factor: Option<u64>,
+        builder_min_bid_gwei: Option<u64>,
     ) -> Result<(JsonProduceBlockV3Response<E>, ProduceBlockV3Metadata), Error> {
         let path = self
             .get_validator_blocks_v3_path(
@@ -2082,6 +2091,7 @@ impl BeaconNodeHttpClient {
                 graffiti,
                 skip_randao_verification,
                 builder_booster_factor,
+                builder_min_bid_gwei,
             )
             .await?;
 
@@ -2131,6 +2141,7 @@ impl BeaconNodeHttpClient {
             graffiti,
             SkipRandaoVerification::No,
             builder_booster_factor,
+            None,
         )
         .await
     }
@@ -2143,6 +2154,7 @@ impl BeaconNodeHttpClient {
         graffiti: Option<&Graffiti>,
         skip_randao_verification: SkipRandaoVerification,
         builder_booster_factor: Option<u64>,
+        builder_min_bid_gwei: Option<u64>,
     ) -> Result<(ProduceBlockV3Response<E>, ProduceBlockV3Metadata), Error> {
         let path = self
             .get_validator_blocks_v3_path(
@@ -2151,6 +2163,7 @@ impl BeaconNodeHttpClient {
                 graffiti,
                 skip_randao_verification,
                 builder_booster_factor,
+                builder_min_bid_gwei,
             )
             .await?;
 
@@ -2449,6 +2462,33 @@ impl BeaconNodeHttpClient {
         .await
     }
 
+    /// `POST lighthouse/liveness/range`
+    pub async fn post_lighthouse_liveness_range(
+        &self,
+        ids: &[u64],
+        start_epoch: Epoch,
+        end_epoch: Epoch,
+    ) -> Result<GenericResponse<Vec<EpochLivenessData>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("liveness")
+            .push("range");
+
+        self.post_with_timeout_and_response(
+            path,
+            &LivenessRangeRequestData {
+                start_epoch,
+                end_epoch,
+                indices: ids.to_vec(),
+            },
+            self.timeouts.liveness,
+        )
+        .await
+    }
+
     /// `POST validator/liveness/{epoch}`
     pub async fn post_validator_liveness_epoch(
         &self,
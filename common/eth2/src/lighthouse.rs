@@ -9,14 +9,20 @@ mod sync_committee_rewards;
 
 use crate::{
     types::{
-        DepositTreeSnapshot, Epoch, EthSpec, FinalizedExecutionBlock, GenericResponse, ValidatorId,
+        Attestation, AttesterSlashing, AttesterSlashingBase, AttesterSlashingElectra,
+        BlobParameters, BlobSidecarList, BlockHeaderAndSignature, BlockId, ChainSpec, Checkpoint,
+        DepositTreeSnapshot, EnrForkId, Epoch, EthSpec, ExecutionBlockHash,
+        FinalizedExecutionBlock, Fork, ForkName, GenericResponse, ProposerData, ProposerSlashing,
+        ValidatorId,
     },
     BeaconNodeHttpClient, DepositData, Error, Eth1Data, Hash256, Slot,
 };
+use lighthouse_network::{Enr, Multiaddr};
 use proto_array::core::ProtoArray;
 use serde::{Deserialize, Serialize};
 use ssz::four_byte_option_impl;
 use ssz_derive::{Decode, Encode};
+use std::collections::BTreeMap;
 use store::{AnchorInfo, BlobInfo, Split, StoreConfig};
 
 pub use attestation_performance::{
@@ -27,8 +33,17 @@ pub use block_packing_efficiency::{
     BlockPackingEfficiency, BlockPackingEfficiencyQuery, ProposerInfo, UniqueAttestation,
 };
 pub use block_rewards::{AttestationRewards, BlockReward, BlockRewardMeta, BlockRewardsQuery};
-pub use lighthouse_network::{types::SyncState, PeerInfo};
-pub use standard_block_rewards::StandardBlockReward;
+pub use lighthouse_network::{
+    types::{
+        BackfillThrottle, ExportedPeer, FirstDeliveryStats, GossipTopicSubscription,
+        SubscriptionReason, SyncChainCandidate, SyncChainKind, SyncProgressInfo, SyncState,
+        SyncThroughput,
+    },
+    ClientDiversity, ClientQuirk, ClientVersions, PeerInfo,
+};
+pub use standard_block_rewards::{
+    AttestationRewardContribution, BlockRewardExplanation, StandardBlockReward,
+};
 pub use sync_committee_rewards::SyncCommitteeReward;
 
 // Define "legacy" implementations of `Option<T>` which use four bytes for encoding the union
@@ -47,6 +62,109 @@ pub struct Peer<E: EthSpec> {
     pub peer_info: PeerInfo<E>,
 }
 
+/// The gossip messages a peer delivered to us before any other peer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeerFirstDeliveries {
+    /// The Peer's ID
+    pub peer_id: String,
+    pub stats: FirstDeliveryStats,
+}
+
+/// The networking metadata which the node currently advertises to its peers.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NetworkMetadata {
+    pub peer_id: String,
+    pub enr: Enr,
+    /// The sequence number of the node's `MetaData`.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub seq_number: u64,
+    pub attnets: String,
+    /// The sync committee subnets, or `None` prior to Altair.
+    pub syncnets: Option<String>,
+    /// The number of custody groups, or `None` prior to PeerDAS.
+    pub custody_group_count: Option<u64>,
+    /// The addresses the node is listening on.
+    pub listen_addresses: Vec<Multiaddr>,
+    /// The TCP addresses advertised in the ENR.
+    pub p2p_addresses: Vec<Multiaddr>,
+    /// The UDP addresses advertised in the ENR.
+    pub discovery_addresses: Vec<Multiaddr>,
+    /// The fork digest and next fork advertised in the ENR, or `None` if the ENR has none.
+    pub enr_fork_id: Option<EnrForkId>,
+}
+
+/// The result of reconstructing the data columns of a block on demand.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DataColumnReconstruction {
+    pub block_root: Hash256,
+    /// The indices of the columns which were reconstructed, empty if reconstruction was not
+    /// required or not possible.
+    #[serde(with = "serde_utils::quoted_u64_vec")]
+    pub reconstructed_columns: Vec<u64>,
+    /// Whether the reconstructed columns completed the block and were written to the database.
+    pub persisted: bool,
+}
+
+/// The header of a block requested by root, along with its execution block hash and whether its
+/// data is available.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HeaderByRoot {
+    pub root: Hash256,
+    /// The header of the block, or `None` if the block is unknown.
+    pub header: Option<BlockHeaderAndSignature>,
+    pub canonical: bool,
+    /// The hash of the block's execution payload, or `None` prior to the merge.
+    pub execution_block_hash: Option<ExecutionBlockHash>,
+    /// Whether the blobs or data columns of the block are stored, or the block has none.
+    pub data_available: bool,
+}
+
+/// Proposer duties for an epoch, which may be computed beyond the standard lookahead.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProposerDutiesLookahead {
+    pub dependent_root: Hash256,
+    pub execution_optimistic: Option<bool>,
+    /// Whether the duties were computed before their dependent root was decided. Provisional duties
+    /// may change and should be fetched again whenever `dependent_root` changes.
+    pub provisional: bool,
+    pub data: Vec<ProposerData>,
+}
+
+/// The canonical block root and state root at a slot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SlotRoots {
+    pub slot: Slot,
+    pub block_root: Hash256,
+    pub state_root: Hash256,
+    /// Whether the slot is empty, in which case `block_root` is the root of the latest prior
+    /// block.
+    pub skipped: bool,
+}
+
+/// The header requests made to a builder relay and their outcomes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RelayStats {
+    /// The URL of the relay, with any credentials redacted.
+    pub url: String,
+    pub requests: u64,
+    /// Requests which returned a valid bid.
+    pub valid_bids: u64,
+    /// Requests which returned a bid that failed verification.
+    pub invalid_bids: u64,
+    /// Requests which returned no bid.
+    pub no_bids: u64,
+    /// Requests abandoned at the local block production deadline.
+    pub deadline_timeouts: u64,
+    /// Requests which failed or timed out.
+    pub errors: u64,
+    /// The mean duration of header requests, in milliseconds.
+    pub mean_latency_ms: Option<f64>,
+    /// The number of requests since the relay last returned a valid bid.
+    pub consecutive_failures: u64,
+    /// The slot until which the relay is deprioritized in favour of other relays.
+    pub deprioritized_until: Option<Slot>,
+}
+
 /// The results of validators voting during an epoch.
 ///
 /// Provides information about the current and previous epochs.
@@ -356,6 +474,114 @@ impl From<Eth1Block> for FinalizedExecutionBlock {
     }
 }
 
+/// Live diagnostics of a beacon node, as consumed by `lighthouse doctor`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Diagnostics {
+    /// The node's wall-clock time when the report was generated, in milliseconds since the UNIX
+    /// epoch.
+    pub system_time_millis: u64,
+    /// The current slot according to the node's slot clock.
+    pub current_slot: Slot,
+    /// The slot of the node's head block.
+    pub head_slot: Slot,
+    /// The status of the connection to the execution engine.
+    pub execution_engine: ExecutionEngineDiagnostics,
+    /// Total capacity of the disk holding the data directory.
+    pub disk_bytes_total: u64,
+    /// Free space on the disk holding the data directory.
+    pub disk_bytes_free: u64,
+    /// The TCP ports that libp2p is listening on.
+    pub libp2p_tcp_ports: Vec<u16>,
+    /// `true` if any of the node's ports have been observed to be reachable from the internet.
+    pub nat_open: bool,
+    /// The number of connected peers, grouped by client.
+    pub peer_clients: BTreeMap<String, usize>,
+    /// Data availability custody diagnostics, present once PeerDAS is scheduled.
+    pub custody: Option<CustodyDiagnostics>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionEngineDiagnostics {
+    /// `true` if an execution engine is configured.
+    pub configured: bool,
+    /// `true` if the engine is responding and its last `newPayload` call succeeded.
+    pub online: bool,
+    /// `true` if the engine reports that it is synced.
+    pub synced: bool,
+    /// `true` if the engine rejected the JWT presented by the node.
+    pub auth_failed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustodyDiagnostics {
+    /// The number of data columns the node samples and stores.
+    pub sampling_column_count: usize,
+    /// The number of recent blocks with blobs that were checked for custody completeness.
+    pub blocks_checked: usize,
+    /// The roots of recent blocks with blobs that are missing one or more sampling columns.
+    pub blocks_missing_columns: Vec<Hash256>,
+}
+
+/// The full fork schedule of the chain, as returned by `lighthouse/fork_schedule`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForkSchedule {
+    /// All forks which have an activation epoch, in activation order.
+    pub forks: Vec<ScheduledFork>,
+    /// The maximum number of blobs per block, by epoch.
+    pub blob_schedule: Vec<BlobParameters>,
+    /// Data column parameters, present once PeerDAS is scheduled.
+    pub data_columns: Option<DataColumnParameters>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScheduledFork {
+    pub fork_name: ForkName,
+    #[serde(flatten)]
+    pub fork: Fork,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DataColumnParameters {
+    pub fork_epoch: Epoch,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub number_of_columns: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub data_column_sidecar_subnet_count: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub custody_requirement: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub samples_per_slot: u64,
+}
+
+impl ForkSchedule {
+    pub fn from_chain_spec<E: EthSpec>(spec: &ChainSpec) -> Self {
+        let forks = ForkName::list_all()
+            .into_iter()
+            .filter_map(|fork_name| {
+                spec.fork_for_name(fork_name)
+                    .map(|fork| ScheduledFork { fork_name, fork })
+            })
+            .collect();
+
+        let data_columns = spec
+            .eip7594_fork_epoch
+            .filter(|_| spec.is_peer_das_scheduled())
+            .map(|fork_epoch| DataColumnParameters {
+                fork_epoch,
+                number_of_columns: spec.number_of_columns as u64,
+                data_column_sidecar_subnet_count: spec.data_column_sidecar_subnet_count,
+                custody_requirement: spec.custody_requirement,
+                samples_per_slot: spec.samples_per_slot,
+            });
+
+        Self {
+            forks,
+            blob_schedule: spec.blob_schedule::<E>(),
+            data_columns,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DatabaseInfo {
     pub schema_version: u64,
@@ -365,6 +591,211 @@ pub struct DatabaseInfo {
     pub blob_info: BlobInfo,
 }
 
+/// The slashings found by the slasher which are not yet known to be included on chain.
+///
+/// Attester slashings are split by fork so that the SSZ encoding of the bundle is unambiguous.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode)]
+#[serde(bound = "E: EthSpec")]
+pub struct SlashingsBundle<E: EthSpec> {
+    /// Attester slashings in the format used prior to Electra.
+    pub attester_slashings_base: Vec<AttesterSlashingBase<E>>,
+    pub attester_slashings_electra: Vec<AttesterSlashingElectra<E>>,
+    pub proposer_slashings: Vec<ProposerSlashing>,
+}
+
+impl<E: EthSpec> SlashingsBundle<E> {
+    pub fn new(
+        attester_slashings: Vec<AttesterSlashing<E>>,
+        proposer_slashings: Vec<ProposerSlashing>,
+    ) -> Self {
+        let mut attester_slashings_base = vec![];
+        let mut attester_slashings_electra = vec![];
+        for attester_slashing in attester_slashings {
+            match attester_slashing {
+                AttesterSlashing::Base(slashing) => attester_slashings_base.push(slashing),
+                AttesterSlashing::Electra(slashing) => attester_slashings_electra.push(slashing),
+            }
+        }
+        Self {
+            attester_slashings_base,
+            attester_slashings_electra,
+            proposer_slashings,
+        }
+    }
+
+    /// The number of attester slashings of either fork.
+    pub fn num_attester_slashings(&self) -> usize {
+        self.attester_slashings_base.len() + self.attester_slashings_electra.len()
+    }
+}
+
+/// The format of the response to `GET lighthouse/fork_choice`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ForkChoiceFormat {
+    #[default]
+    Json,
+    /// A graphviz DOT graph of the block tree.
+    Dot,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PoolAttestationsQuery {
+    pub slot: Option<Slot>,
+    pub committee_index: Option<u64>,
+    /// Only return attestations signed by more (`true`) or exactly one (`false`) validator.
+    pub aggregated: Option<bool>,
+}
+
+/// The attestations in the operation pool, scored for inclusion in a block at `proposal_slot`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "E: EthSpec")]
+pub struct PoolAttestations<E: EthSpec> {
+    /// The slot of the next block built on the head.
+    pub proposal_slot: Slot,
+    pub attestations: Vec<PoolAttestation<E>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "E: EthSpec")]
+pub struct PoolAttestation<E: EthSpec> {
+    pub attestation: Attestation<E>,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub attesting_validators: u64,
+    /// `None` if the attestation can't be included in the block at `proposal_slot`.
+    pub inclusion_score: Option<AttestationInclusionScore>,
+}
+
+/// The value of including an attestation in a block.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AttestationInclusionScore {
+    /// Number of attesting validators whose attestations are not yet included on chain.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub fresh_validators: u64,
+    /// The proposer reward for including the attestation, in gwei.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub reward: u64,
+}
+
+/// The inclusion and broadcast status of a voluntary exit in the operation pool.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VoluntaryExitStatus {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub validator_index: u64,
+    /// The epoch at which the validator may exit.
+    pub epoch: Epoch,
+    /// `true` if the exit has been included in the head chain.
+    pub included: bool,
+    /// The epoch in which the exit was first accepted by this node, if known.
+    pub first_seen_epoch: Option<Epoch>,
+    /// The epoch in which the exit was last broadcast by this node, if known.
+    pub last_broadcast_epoch: Option<Epoch>,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub rebroadcasts: u64,
+    /// The epoch in which the exit will next be re-broadcast, or `None` if it won't be.
+    pub next_rebroadcast_epoch: Option<Epoch>,
+}
+
+/// The outcome of a monitored validator's attestation for an epoch, as diagnosed by the validator
+/// monitor once the epoch has been processed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttestationOutcome {
+    /// The attestation was included on chain in time to earn a reward.
+    Included,
+    /// The attestation was included on chain, but too late to earn a reward.
+    IncludedLate,
+    /// No attestation was observed on gossip, the HTTP API, in an aggregate or in a block.
+    NotProduced,
+    /// The attestation was observed after aggregators would have produced their aggregates.
+    ProducedLate,
+    /// The attestation was observed in time, but was not included in any observed aggregate.
+    NotAggregated,
+    /// The attestation was included in an aggregate, but not in any block.
+    AggregateNotIncluded,
+}
+
+impl AttestationOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Included => "included",
+            Self::IncludedLate => "included_late",
+            Self::NotProduced => "not_produced",
+            Self::ProducedLate => "produced_late",
+            Self::NotAggregated => "not_aggregated",
+            Self::AggregateNotIncluded => "aggregate_not_included",
+        }
+    }
+}
+
+/// The evidence gathered by the validator monitor about a validator's attestation for an epoch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AttestationDiagnosis {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub validator_index: u64,
+    /// `None` until the epoch has been processed.
+    pub outcome: Option<AttestationOutcome>,
+    /// The earliest the unaggregated attestation was observed, relative to when it was due.
+    pub attestation_delay_ms: Option<u64>,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub aggregate_inclusions: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub block_inclusions: u64,
+    /// The smallest distance between the attestation slot and the slot of an including block.
+    pub min_inclusion_distance: Option<Slot>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ForkChoiceQuery {
+    pub format: Option<ForkChoiceFormat>,
+}
+
+/// The validity of a block's execution payload, as known to fork choice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ForkChoiceNodeValidity {
+    /// The payload was verified by the execution layer, or the block has no payload.
+    Valid,
+    /// The payload has not yet been verified by the execution layer.
+    Optimistic,
+    /// The payload was found to be invalid by the execution layer.
+    Invalid,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForkChoiceNodeInfo {
+    pub slot: Slot,
+    pub block_root: Hash256,
+    pub parent_root: Option<Hash256>,
+    pub justified_epoch: Epoch,
+    pub finalized_epoch: Epoch,
+    /// The total balance of the votes for this block and its descendants, including any proposer
+    /// boost.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub weight: u64,
+    pub validity: ForkChoiceNodeValidity,
+    /// The execution status as stored by fork choice, e.g. `irrelevant` for pre-merge blocks.
+    pub execution_status: String,
+    pub execution_block_hash: Option<ExecutionBlockHash>,
+    /// Whether proposer boost was applied to this block in the last fork choice run.
+    pub proposer_boost: bool,
+    pub is_head: bool,
+}
+
+/// The block tree of fork choice, with the information required to debug re-orgs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForkChoiceInfo {
+    pub head_root: Hash256,
+    pub justified_checkpoint: Checkpoint,
+    pub finalized_checkpoint: Checkpoint,
+    /// The block to which proposer boost was applied in the last fork choice run, if any.
+    pub proposer_boost_root: Option<Hash256>,
+    /// The weight added to `proposer_boost_root` and its ancestors by proposer boost.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub proposer_boost_score: u64,
+    pub nodes: Vec<ForkChoiceNodeInfo>,
+}
+
 impl BeaconNodeHttpClient {
     /// `GET lighthouse/health`
     pub async fn get_lighthouse_health(&self) -> Result<GenericResponse<Health>, Error> {
@@ -390,6 +821,168 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET lighthouse/syncing/progress`
+    pub async fn get_lighthouse_syncing_progress(
+        &self,
+    ) -> Result<GenericResponse<SyncProgressInfo>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("syncing")
+            .push("progress");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/syncing/chains`
+    pub async fn get_lighthouse_syncing_chains(
+        &self,
+    ) -> Result<GenericResponse<Vec<SyncChainCandidate>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("syncing")
+            .push("chains");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/syncing/backfill_throttle`
+    pub async fn get_lighthouse_syncing_backfill_throttle(
+        &self,
+    ) -> Result<GenericResponse<BackfillThrottle>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("syncing")
+            .push("backfill_throttle");
+
+        self.get(path).await
+    }
+
+    /// `POST lighthouse/syncing/backfill_throttle`
+    pub async fn post_lighthouse_syncing_backfill_throttle(
+        &self,
+        throttle: &BackfillThrottle,
+    ) -> Result<GenericResponse<BackfillThrottle>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("syncing")
+            .push("backfill_throttle");
+
+        self.post_with_response(path, throttle).await
+    }
+
+    /// `POST lighthouse/beacon/headers_by_roots`
+    pub async fn post_lighthouse_beacon_headers_by_roots(
+        &self,
+        roots: &[Hash256],
+    ) -> Result<GenericResponse<Vec<HeaderByRoot>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("beacon")
+            .push("headers_by_roots");
+
+        self.post_with_response(path, &roots).await
+    }
+
+    /// `POST lighthouse/beacon/roots_by_slots`
+    pub async fn post_lighthouse_beacon_roots_by_slots(
+        &self,
+        slots: &[Slot],
+    ) -> Result<GenericResponse<Vec<SlotRoots>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("beacon")
+            .push("roots_by_slots");
+
+        self.post_with_response(path, &slots).await
+    }
+
+    /// `GET lighthouse/peers/first_deliveries`
+    pub async fn get_lighthouse_peers_first_deliveries(
+        &self,
+    ) -> Result<Vec<PeerFirstDeliveries>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("peers")
+            .push("first_deliveries");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/peers/clients`
+    pub async fn get_lighthouse_peers_clients(&self) -> Result<ClientDiversity, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("peers")
+            .push("clients");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/peers/export`
+    pub async fn get_lighthouse_peers_export(&self) -> Result<Vec<ExportedPeer>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("peers")
+            .push("export");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/network/metadata`
+    pub async fn get_lighthouse_network_metadata(&self) -> Result<NetworkMetadata, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("network")
+            .push("metadata");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/gossip/subscriptions`
+    pub async fn get_lighthouse_gossip_subscriptions(
+        &self,
+    ) -> Result<Vec<GossipTopicSubscription>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("gossip")
+            .push("subscriptions");
+
+        self.get(path).await
+    }
+
     /*
      * Note:
      *
@@ -427,6 +1020,24 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET lighthouse/validator/duties/proposer/{epoch}`
+    pub async fn get_lighthouse_validator_duties_proposer(
+        &self,
+        epoch: Epoch,
+    ) -> Result<ProposerDutiesLookahead, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validator")
+            .push("duties")
+            .push("proposer")
+            .push(&epoch.to_string());
+
+        self.get(path).await
+    }
+
     /// `GET lighthouse/validator_inclusion/{epoch}/{validator_id}`
     pub async fn get_lighthouse_validator_inclusion(
         &self,
@@ -445,6 +1056,20 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET lighthouse/fork_schedule`
+    pub async fn get_lighthouse_fork_schedule(
+        &self,
+    ) -> Result<GenericResponse<ForkSchedule>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("fork_schedule");
+
+        self.get(path).await
+    }
+
     /// `GET lighthouse/eth1/syncing`
     pub async fn get_lighthouse_eth1_syncing(
         &self,
@@ -502,6 +1127,59 @@ impl BeaconNodeHttpClient {
         self.get_opt::<(), _>(path).await.map(|opt| opt.is_some())
     }
 
+    /// `GET lighthouse/diagnostics`
+    pub async fn get_lighthouse_diagnostics(&self) -> Result<GenericResponse<Diagnostics>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("diagnostics");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/relays`
+    pub async fn get_lighthouse_relays(&self) -> Result<GenericResponse<Vec<RelayStats>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("relays");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/blobs/{block_root}`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_lighthouse_blobs<E: EthSpec>(
+        &self,
+        block_root: Hash256,
+        indices: Option<&[u64]>,
+    ) -> Result<Option<GenericResponse<BlobSidecarList<E>>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("blobs")
+            .push(&format!("{:?}", block_root));
+
+        if let Some(indices) = indices {
+            let indices_string = indices
+                .iter()
+                .map(|i| i.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            path.query_pairs_mut()
+                .append_pair("indices", &indices_string);
+        }
+
+        self.get_opt(path).await
+    }
+
     /// `GET lighthouse/database/info`
     pub async fn get_lighthouse_database_info(&self) -> Result<DatabaseInfo, Error> {
         let mut path = self.server.full.clone();
@@ -528,6 +1206,160 @@ impl BeaconNodeHttpClient {
         self.post_with_response(path, &()).await
     }
 
+    /// `POST lighthouse/database/prune_blobs`
+    pub async fn post_lighthouse_database_prune_blobs(&self) -> Result<String, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("database")
+            .push("prune_blobs");
+
+        self.post_with_response(path, &()).await
+    }
+
+    /// `POST lighthouse/das/reconstruct/{block_root}`
+    pub async fn post_lighthouse_das_reconstruct(
+        &self,
+        block_root: Hash256,
+    ) -> Result<DataColumnReconstruction, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("das")
+            .push("reconstruct")
+            .push(&format!("{:?}", block_root));
+
+        self.post_with_response(path, &()).await
+    }
+
+    /// `GET lighthouse/pool/attestations?slot,committee_index,aggregated`
+    pub async fn get_lighthouse_pool_attestations<E: EthSpec>(
+        &self,
+        slot: Option<Slot>,
+        committee_index: Option<u64>,
+        aggregated: Option<bool>,
+    ) -> Result<GenericResponse<PoolAttestations<E>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("pool")
+            .push("attestations");
+
+        if let Some(slot) = slot {
+            path.query_pairs_mut()
+                .append_pair("slot", &slot.to_string());
+        }
+
+        if let Some(index) = committee_index {
+            path.query_pairs_mut()
+                .append_pair("committee_index", &index.to_string());
+        }
+
+        if let Some(aggregated) = aggregated {
+            path.query_pairs_mut()
+                .append_pair("aggregated", &aggregated.to_string());
+        }
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/pool/exits/status`
+    pub async fn get_lighthouse_pool_exits_status(
+        &self,
+    ) -> Result<GenericResponse<Vec<VoluntaryExitStatus>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("pool")
+            .push("exits")
+            .push("status");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/validator_monitor/attestations/{epoch}`
+    pub async fn get_lighthouse_validator_monitor_attestations(
+        &self,
+        epoch: Epoch,
+    ) -> Result<GenericResponse<Vec<AttestationDiagnosis>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validator_monitor")
+            .push("attestations")
+            .push(&epoch.to_string());
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/fork_choice`
+    pub async fn get_lighthouse_fork_choice(
+        &self,
+    ) -> Result<GenericResponse<ForkChoiceInfo>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("fork_choice");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/fork_choice?format=dot`
+    ///
+    /// Returns the block tree of fork choice as a graphviz DOT graph.
+    pub async fn get_lighthouse_fork_choice_dot(&self) -> Result<String, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("fork_choice");
+
+        path.query_pairs_mut().append_pair("format", "dot");
+
+        Ok(self.get_response(path, |b| b).await?.text().await?)
+    }
+
+    /// `GET lighthouse/slasher/slashings`
+    pub async fn get_lighthouse_slasher_slashings<E: EthSpec>(
+        &self,
+    ) -> Result<GenericResponse<SlashingsBundle<E>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("slasher")
+            .push("slashings");
+
+        self.get(path).await
+    }
+
+    /// `POST lighthouse/execution_layer/reload_jwt_secret`
+    pub async fn post_lighthouse_execution_layer_reload_jwt_secret(&self) -> Result<String, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("execution_layer")
+            .push("reload_jwt_secret");
+
+        self.post_with_response(path, &()).await
+    }
+
     /*
      Analysis endpoints.
     */
@@ -553,6 +1385,23 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET` lighthouse/analysis/block_reward/{block_id}
+    pub async fn get_lighthouse_analysis_block_reward(
+        &self,
+        block_id: BlockId,
+    ) -> Result<BlockRewardExplanation, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("analysis")
+            .push("block_reward")
+            .push(&block_id.to_string());
+
+        self.get(path).await
+    }
+
     /// `GET` lighthouse/analysis/block_packing?start_epoch,end_epoch
     pub async fn get_lighthouse_analysis_block_packing(
         &self,
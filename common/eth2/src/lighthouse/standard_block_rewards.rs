@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use types::{AttestationData, Hash256, Slot};
 
 // Details about the rewards for a single block
 // All rewards in GWei
@@ -24,3 +25,41 @@ pub struct StandardBlockReward {
     #[serde(with = "serde_utils::quoted_u64")]
     pub attester_slashings: u64,
 }
+
+/// The rewards paid to a block proposer for proposing a block, with the attestation reward broken
+/// down by attestation.
+///
+/// All rewards in GWei.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct BlockRewardExplanation {
+    pub block_root: Hash256,
+    pub slot: Slot,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub proposer_index: u64,
+    /// Sum of all reward components, as for `StandardBlockReward`.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub total: u64,
+    /// The contribution of each attestation, in the order they appear in the block.
+    pub attestations: Vec<AttestationRewardContribution>,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub sync_aggregate: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub proposer_slashings: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub attester_slashings: u64,
+    /// The number of voluntary exits included. The proposer is not rewarded for these.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub voluntary_exits: u64,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct AttestationRewardContribution {
+    pub data: AttestationData,
+    /// The number of validators attesting, including those already rewarded by an earlier
+    /// attestation.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub attesters: u64,
+    /// The proposer reward for including this attestation, which is zero if it adds no new votes.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub reward: u64,
+}
@@ -412,6 +412,35 @@ impl ValidatorClientHttpClient {
         self.post(path, &request).await
     }
 
+    /// `GET lighthouse/bls_to_execution_changes`
+    pub async fn get_lighthouse_bls_to_execution_changes(
+        &self,
+    ) -> Result<GenericResponse<Vec<QueuedBlsToExecutionChange>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("bls_to_execution_changes");
+
+        self.get(path).await
+    }
+
+    /// `POST lighthouse/bls_to_execution_changes`
+    pub async fn post_lighthouse_bls_to_execution_changes(
+        &self,
+        request: &QueueBlsToExecutionChangesRequest,
+    ) -> Result<(), Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("bls_to_execution_changes");
+
+        self.post(path, &request).await
+    }
+
     /// `PATCH lighthouse/validators/{validator_pubkey}`
     #[allow(clippy::too_many_arguments)]
     pub async fn patch_lighthouse_validators(
@@ -422,6 +451,7 @@ impl ValidatorClientHttpClient {
         builder_proposals: Option<bool>,
         builder_boost_factor: Option<u64>,
         prefer_builder_proposals: Option<bool>,
+        builder_min_bid_gwei: Option<u64>,
         graffiti: Option<GraffitiString>,
     ) -> Result<(), Error> {
         let mut path = self.server.full.clone();
@@ -440,6 +470,7 @@ impl ValidatorClientHttpClient {
                 builder_proposals,
                 builder_boost_factor,
                 prefer_builder_proposals,
+                builder_min_bid_gwei,
                 graffiti,
             },
         )
@@ -461,6 +492,39 @@ impl ValidatorClientHttpClient {
         self.delete_with_unsigned_response(path, req).await
     }
 
+    /// `POST lighthouse/keystores/import`
+    pub async fn post_lighthouse_keystores_import(
+        &self,
+        req: &ImportKeystoresRequest,
+    ) -> Result<GenericResponse<KeystoreImportJob>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("keystores")
+            .push("import");
+
+        self.post(path, req).await
+    }
+
+    /// `GET lighthouse/keystores/import/{import_id}`
+    pub async fn get_lighthouse_keystores_import(
+        &self,
+        import_id: u64,
+    ) -> Result<Option<GenericResponse<KeystoreImportProgress>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("keystores")
+            .push("import")
+            .push(&import_id.to_string());
+
+        self.get_opt(path).await
+    }
+
     fn make_keystores_url(&self) -> Result<Url, Error> {
         let mut url = self.server.full.clone();
         url.path_segments_mut()
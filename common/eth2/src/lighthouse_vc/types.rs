@@ -14,6 +14,9 @@ pub struct ValidatorData {
     pub enabled: bool,
     pub description: String,
     pub voting_pubkey: PublicKeyBytes,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub builder_min_bid_gwei: Option<u64>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -98,6 +101,9 @@ pub struct ValidatorPatchRequest {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub prefer_builder_proposals: Option<bool>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub builder_min_bid_gwei: Option<u64>,
 }
 
 #[derive(Clone, PartialEq, Serialize, Deserialize)]
@@ -144,6 +150,9 @@ pub struct Web3SignerValidatorRequest {
     pub voting_public_key: PublicKey,
     pub url: String,
     #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fallback_urls: Vec<String>,
+    #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub root_certificate_path: Option<PathBuf>,
     #[serde(default)]
@@ -198,3 +207,85 @@ pub struct SingleExportKeystoresResponse {
 pub struct SetGraffitiRequest {
     pub graffiti: GraffitiString,
 }
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueueBlsToExecutionChangesRequest {
+    /// The epoch from which the changes will be broadcast to the beacon node.
+    pub broadcast_epoch: Epoch,
+    pub changes: Vec<SignedBlsToExecutionChange>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlsToExecutionChangeStatus {
+    /// Waiting for the broadcast epoch.
+    Pending,
+    /// Accepted by the beacon node.
+    Broadcast,
+    /// Rejected by the beacon node. The change will not be retried.
+    Failed,
+}
+
+/// A signed BLS to execution change held by the validator client until its broadcast epoch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueuedBlsToExecutionChange {
+    pub change: SignedBlsToExecutionChange,
+    pub broadcast_epoch: Epoch,
+    pub status: BlsToExecutionChangeStatus,
+    /// The epoch during which the change was broadcast or failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub processed_epoch: Option<Epoch>,
+    /// The reason for a failed broadcast, or for the most recent failed attempt of a change which
+    /// is still pending.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// The response to `POST lighthouse/keystores/import`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeystoreImportJob {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub import_id: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeystoreImportStage {
+    /// Waiting for earlier keystores in the batch to be imported.
+    Pending,
+    /// Importing the slashing protection data provided with the batch.
+    SlashingProtection,
+    /// Checking the password and writing the keystore to disk.
+    Decrypting,
+    /// Adding the validator to the running validator client.
+    Activating,
+    /// Finished, successfully or not. See the `status`.
+    Complete,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SingleKeystoreImportProgress {
+    /// `None` if the keystore contains an invalid public key.
+    pub validating_pubkey: Option<PublicKeyBytes>,
+    pub stage: KeystoreImportStage,
+    /// The result of the import, once `stage` is `complete`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<Status<ImportKeystoreStatus>>,
+}
+
+/// The progress of a keystore import started via `POST lighthouse/keystores/import`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeystoreImportProgress {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub completed: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub total: u64,
+    /// The progress of each keystore, in the order they were provided.
+    pub keystores: Vec<SingleKeystoreImportProgress>,
+}
+
+impl KeystoreImportProgress {
+    pub fn is_complete(&self) -> bool {
+        self.completed == self.total
+    }
+}
@@ -746,6 +746,9 @@ pub struct ValidatorBlocksQuery {
     pub graffiti: Option<Graffiti>,
     pub skip_randao_verification: SkipRandaoVerification,
     pub builder_boost_factor: Option<u64>,
+    /// Lighthouse extension: builder bids below this value (in gwei) are ignored in favour of the
+    /// local payload.
+    pub builder_min_bid_gwei: Option<u64>,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
@@ -1008,6 +1011,89 @@ pub struct SseChainReorg {
     pub execution_optimistic: bool,
 }
 
+/// A block in one of the chains involved in a re-org.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+pub struct SseReorgBlock {
+    pub slot: Slot,
+    pub block: Hash256,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub proposer_index: u64,
+}
+
+/// Two blocks from the same proposer for the same slot, one on each side of a re-org.
+///
+/// This is a hint that the proposer may be slashable: it has not been verified that both blocks
+/// are validly signed.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+pub struct SseReorgEquivocation {
+    pub slot: Slot,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub proposer_index: u64,
+    pub old_block: Hash256,
+    pub new_block: Hash256,
+}
+
+/// A re-org, with the blocks and proposers involved.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+pub struct SseReorgDetail {
+    pub slot: Slot,
+    pub epoch: Epoch,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub depth: u64,
+    pub old_head_block: Hash256,
+    pub new_head_block: Hash256,
+    pub common_ancestor_block: Hash256,
+    pub common_ancestor_slot: Slot,
+    /// The blocks which are no longer canonical, most recent first.
+    pub old_blocks: Vec<SseReorgBlock>,
+    /// The blocks which became canonical, most recent first.
+    pub new_blocks: Vec<SseReorgBlock>,
+    pub equivocations: Vec<SseReorgEquivocation>,
+    /// Whether the new head is the block which currently receives proposer boost.
+    pub new_head_proposer_boosted: bool,
+    pub execution_optimistic: bool,
+}
+
+/// The type of a range sync chain.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum SseSyncType {
+    Finalized,
+    Head,
+    Backfill,
+}
+
+/// The change in the progress of a range sync chain reported by a `sync_progress` event.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum SseSyncChainStatus {
+    /// The chain started or resumed syncing.
+    Started,
+    /// A batch of the chain was processed successfully.
+    Advanced,
+    /// The chain synced to its target head.
+    Completed,
+    /// The chain was removed before reaching its target head, e.g. because it failed or lost all
+    /// of its peers.
+    Removed,
+}
+
+/// The progress of a range sync chain.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+pub struct SseSyncProgress {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub chain_id: u64,
+    pub sync_type: SseSyncType,
+    pub status: SseSyncChainStatus,
+    pub start_epoch: Epoch,
+    /// The first epoch which has not yet been processed.
+    pub processing_target: Epoch,
+    pub target_head_slot: Slot,
+    pub target_head_root: Hash256,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub peers: u64,
+}
+
 #[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
 pub struct SseLateHead {
     pub slot: Slot,
@@ -1054,6 +1140,15 @@ pub struct SseExtendedPayloadAttributesGeneric<T> {
     pub parent_block_number: u64,
 
     pub parent_block_hash: ExecutionBlockHash,
+    /// The gas limit of the execution block being built upon.
+    #[serde(default, with = "serde_utils::quoted_u64")]
+    pub parent_gas_limit: u64,
+    /// The fee recipient registered by the proposer, if it has registered with this node.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proposer_fee_recipient: Option<Address>,
+    /// Whether the proposer is expected to request a blinded block from a builder.
+    #[serde(default)]
+    pub blinded_proposal_expected: bool,
     pub payload_attributes: T,
 }
 
@@ -1098,6 +1193,9 @@ impl ForkVersionDeserialize for SseExtendedPayloadAttributes {
             parent_block_root: helper.parent_block_root,
             parent_block_number: helper.parent_block_number,
             parent_block_hash: helper.parent_block_hash,
+            parent_gas_limit: helper.parent_gas_limit,
+            proposer_fee_recipient: helper.proposer_fee_recipient,
+            blinded_proposal_expected: helper.blinded_proposal_expected,
             payload_attributes: SsePayloadAttributes::deserialize_by_fork::<D>(
                 helper.payload_attributes,
                 fork_name,
@@ -1116,6 +1214,7 @@ pub enum EventKind<E: EthSpec> {
     Head(SseHead),
     VoluntaryExit(SignedVoluntaryExit),
     ChainReorg(SseChainReorg),
+    ReorgDetail(Box<SseReorgDetail>),
     ContributionAndProof(Box<SignedContributionAndProof<E>>),
     LateHead(SseLateHead),
     LightClientFinalityUpdate(Box<LightClientFinalityUpdate<E>>),
@@ -1127,6 +1226,7 @@ pub enum EventKind<E: EthSpec> {
     AttesterSlashing(Box<AttesterSlashing<E>>),
     BlsToExecutionChange(Box<SignedBlsToExecutionChange>),
     BlockGossip(Box<BlockGossip>),
+    SyncProgress(SseSyncProgress),
 }
 
 impl<E: EthSpec> EventKind<E> {
@@ -1139,6 +1239,7 @@ impl<E: EthSpec> EventKind<E> {
             EventKind::VoluntaryExit(_) => "voluntary_exit",
             EventKind::FinalizedCheckpoint(_) => "finalized_checkpoint",
             EventKind::ChainReorg(_) => "chain_reorg",
+            EventKind::ReorgDetail(_) => "reorg_detail",
             EventKind::ContributionAndProof(_) => "contribution_and_proof",
             EventKind::PayloadAttributes(_) => "payload_attributes",
             EventKind::LateHead(_) => "late_head",
@@ -1150,6 +1251,7 @@ impl<E: EthSpec> EventKind<E> {
             EventKind::AttesterSlashing(_) => "attester_slashing",
             EventKind::BlsToExecutionChange(_) => "bls_to_execution_change",
             EventKind::BlockGossip(_) => "block_gossip",
+            EventKind::SyncProgress(_) => "sync_progress",
         }
     }
 
@@ -1184,6 +1286,9 @@ impl<E: EthSpec> EventKind<E> {
             "chain_reorg" => Ok(EventKind::ChainReorg(serde_json::from_str(data).map_err(
                 |e| ServerError::InvalidServerSentEvent(format!("Chain Reorg: {:?}", e)),
             )?)),
+            "reorg_detail" => Ok(EventKind::ReorgDetail(serde_json::from_str(data).map_err(
+                |e| ServerError::InvalidServerSentEvent(format!("Reorg Detail: {:?}", e)),
+            )?)),
             "finalized_checkpoint" => Ok(EventKind::FinalizedCheckpoint(
                 serde_json::from_str(data).map_err(|e| {
                     ServerError::InvalidServerSentEvent(format!("Finalized Checkpoint: {:?}", e))
@@ -1248,6 +1353,11 @@ impl<E: EthSpec> EventKind<E> {
             "block_gossip" => Ok(EventKind::BlockGossip(serde_json::from_str(data).map_err(
                 |e| ServerError::InvalidServerSentEvent(format!("Block Gossip: {:?}", e)),
             )?)),
+            "sync_progress" => Ok(EventKind::SyncProgress(
+                serde_json::from_str(data).map_err(|e| {
+                    ServerError::InvalidServerSentEvent(format!("Sync Progress: {:?}", e))
+                })?,
+            )),
             _ => Err(ServerError::InvalidServerSentEvent(
                 "Could not parse event tag".to_string(),
             )),
@@ -1272,6 +1382,7 @@ pub enum EventTopic {
     VoluntaryExit,
     FinalizedCheckpoint,
     ChainReorg,
+    ReorgDetail,
     ContributionAndProof,
     LateHead,
     PayloadAttributes,
@@ -1283,6 +1394,7 @@ pub enum EventTopic {
     ProposerSlashing,
     BlsToExecutionChange,
     BlockGossip,
+    SyncProgress,
 }
 
 impl FromStr for EventTopic {
@@ -1297,6 +1409,7 @@ impl FromStr for EventTopic {
             "voluntary_exit" => Ok(EventTopic::VoluntaryExit),
             "finalized_checkpoint" => Ok(EventTopic::FinalizedCheckpoint),
             "chain_reorg" => Ok(EventTopic::ChainReorg),
+            "reorg_detail" => Ok(EventTopic::ReorgDetail),
             "contribution_and_proof" => Ok(EventTopic::ContributionAndProof),
             "payload_attributes" => Ok(EventTopic::PayloadAttributes),
             "late_head" => Ok(EventTopic::LateHead),
@@ -1308,6 +1421,7 @@ impl FromStr for EventTopic {
             "proposer_slashing" => Ok(EventTopic::ProposerSlashing),
             "bls_to_execution_change" => Ok(EventTopic::BlsToExecutionChange),
             "block_gossip" => Ok(EventTopic::BlockGossip),
+            "sync_progress" => Ok(EventTopic::SyncProgress),
             _ => Err("event topic cannot be parsed.".to_string()),
         }
     }
@@ -1323,6 +1437,7 @@ impl fmt::Display for EventTopic {
             EventTopic::VoluntaryExit => write!(f, "voluntary_exit"),
             EventTopic::FinalizedCheckpoint => write!(f, "finalized_checkpoint"),
             EventTopic::ChainReorg => write!(f, "chain_reorg"),
+            EventTopic::ReorgDetail => write!(f, "reorg_detail"),
             EventTopic::ContributionAndProof => write!(f, "contribution_and_proof"),
             EventTopic::PayloadAttributes => write!(f, "payload_attributes"),
             EventTopic::LateHead => write!(f, "late_head"),
@@ -1334,6 +1449,7 @@ impl fmt::Display for EventTopic {
             EventTopic::ProposerSlashing => write!(f, "proposer_slashing"),
             EventTopic::BlsToExecutionChange => write!(f, "bls_to_execution_change"),
             EventTopic::BlockGossip => write!(f, "block_gossip"),
+            EventTopic::SyncProgress => write!(f, "sync_progress"),
         }
     }
 }
@@ -1342,6 +1458,8 @@ impl fmt::Display for EventTopic {
 pub enum Accept {
     Json,
     Ssz,
+    /// SSZ compressed using the snappy framing format.
+    SszSnappy,
     Any,
 }
 
@@ -1349,6 +1467,7 @@ impl fmt::Display for Accept {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Accept::Ssz => write!(f, "application/octet-stream"),
+            Accept::SszSnappy => write!(f, "application/octet-stream+snappy"),
             Accept::Json => write!(f, "application/json"),
             Accept::Any => write!(f, "*/*"),
         }
@@ -1371,19 +1490,25 @@ impl FromStr for Accept {
         const JSON: &str = names::JSON.as_str();
         const STAR: &str = names::_STAR.as_str();
         const Q: &str = names::Q.as_str();
+        const SNAPPY: &str = "snappy";
 
         media_type_list.into_iter().for_each(|item| {
             if let Ok(MediaType {
                 ty,
                 subty,
-                suffix: _,
+                suffix,
                 params,
             }) = item
             {
-                let q_accept = match (ty.as_str(), subty.as_str()) {
-                    (APPLICATION, OCTET_STREAM) => Some(Accept::Ssz),
-                    (APPLICATION, JSON) => Some(Accept::Json),
-                    (STAR, STAR) => Some(Accept::Any),
+                let q_accept = match (
+                    ty.as_str(),
+                    subty.as_str(),
+                    suffix.as_ref().map(|suffix| suffix.as_str()),
+                ) {
+                    (APPLICATION, OCTET_STREAM, Some(SNAPPY)) => Some(Accept::SszSnappy),
+                    (APPLICATION, OCTET_STREAM, _) => Some(Accept::Ssz),
+                    (APPLICATION, JSON, _) => Some(Accept::Json),
+                    (STAR, STAR, _) => Some(Accept::Any),
                     _ => None,
                 }
                 .map(|item_accept_type| {
@@ -1435,6 +1560,44 @@ pub struct LivenessResponseData {
     pub is_live: bool,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LivenessRangeRequestData {
+    pub start_epoch: Epoch,
+    pub end_epoch: Epoch,
+    #[serde(with = "serde_utils::quoted_u64_vec")]
+    pub indices: Vec<u64>,
+}
+
+/// The liveness of the requested validators during `epoch`.
+///
+/// Bit `i` of `live` is set if the validator at position `i` of the request's `indices` was live,
+/// using the same little-endian bit ordering as SSZ bitfields.
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+pub struct EpochLivenessData {
+    pub epoch: Epoch,
+    #[serde(with = "serde_utils::hex_vec")]
+    pub live: Vec<u8>,
+}
+
+impl EpochLivenessData {
+    pub fn new(epoch: Epoch, live: impl ExactSizeIterator<Item = bool>) -> Self {
+        let mut bytes = vec![0; live.len().div_ceil(8)];
+        for (i, is_live) in live.enumerate() {
+            if is_live {
+                bytes[i / 8] |= 1 << (i % 8);
+            }
+        }
+        Self { epoch, live: bytes }
+    }
+
+    /// Returns `true` if the validator at `position` in the request's `indices` was live.
+    pub fn is_live(&self, position: usize) -> bool {
+        self.live
+            .get(position / 8)
+            .map_or(false, |byte| byte & (1 << (position % 8)) != 0)
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ForkChoice {
     pub justified_checkpoint: Checkpoint,
@@ -1556,6 +1719,19 @@ mod tests {
             Accept::from_str("application/json;message=\"Hello, world!\";q=0.3,*/*;q=0.6").unwrap(),
             Accept::Any
         );
+
+        assert_eq!(
+            Accept::from_str("application/octet-stream+snappy").unwrap(),
+            Accept::SszSnappy
+        );
+
+        assert_eq!(
+            Accept::from_str(
+                "application/octet-stream+snappy;q=0.9,application/octet-stream;q=0.5"
+            )
+            .unwrap(),
+            Accept::SszSnappy
+        );
     }
 
     #[test]
@@ -2034,4 +2210,48 @@ mod test {
         let y: ValidatorId = serde_json::from_str(pubkey_str).unwrap();
         assert_eq!(serde_json::to_string(&y).unwrap(), pubkey_str);
     }
+
+    #[test]
+    fn payload_attributes_builder_fields_serde() {
+        let payload_attributes = SseExtendedPayloadAttributes {
+            proposal_slot: Slot::new(2),
+            proposer_index: 1,
+            parent_block_root: Hash256::repeat_byte(1),
+            parent_block_number: 5,
+            parent_block_hash: ExecutionBlockHash::repeat_byte(2),
+            parent_gas_limit: 30_000_000,
+            proposer_fee_recipient: Some(Address::repeat_byte(3)),
+            blinded_proposal_expected: true,
+            payload_attributes: SsePayloadAttributes::V1(SsePayloadAttributesV1 {
+                timestamp: 24,
+                prev_randao: Hash256::repeat_byte(4),
+                suggested_fee_recipient: Address::repeat_byte(3),
+            }),
+        };
+        let json = serde_json::to_value(&payload_attributes).unwrap();
+        assert_eq!(json["parent_gas_limit"], "30000000");
+        assert_eq!(json["blinded_proposal_expected"], true);
+
+        let decoded = SseExtendedPayloadAttributes::deserialize_by_fork::<serde_json::Value>(
+            json.clone(),
+            ForkName::Bellatrix,
+        )
+        .unwrap();
+        assert_eq!(decoded, payload_attributes);
+
+        // Events without the builder fields are still accepted.
+        let mut json = json;
+        let object = json.as_object_mut().unwrap();
+        object.remove("parent_gas_limit");
+        object.remove("proposer_fee_recipient");
+        object.remove("blinded_proposal_expected");
+        let decoded = SseExtendedPayloadAttributes::deserialize_by_fork::<serde_json::Value>(
+            json,
+            ForkName::Bellatrix,
+        )
+        .unwrap();
+        assert_eq!(decoded.parent_gas_limit, 0);
+        assert_eq!(decoded.proposer_fee_recipient, None);
+        assert!(!decoded.blinded_proposal_expected);
+    }
 }
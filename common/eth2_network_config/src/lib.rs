@@ -14,7 +14,7 @@
 use bytes::Bytes;
 use discv5::enr::{CombinedKey, Enr};
 use eth2_config::{instantiate_hardcoded_nets, HardcodedNet};
-use kzg::trusted_setup::get_trusted_setup;
+use kzg::{trusted_setup::get_trusted_setup, TrustedSetup};
 use pretty_reqwest_error::PrettyReqwestError;
 use reqwest::{Client, Error};
 use sensitive_url::SensitiveUrl;
@@ -34,6 +34,7 @@ pub const DEPLOY_BLOCK_FILE: &str = "deposit_contract_block.txt";
 pub const BOOT_ENR_FILE: &str = "boot_enr.yaml";
 pub const GENESIS_STATE_FILE: &str = "genesis.ssz";
 pub const BASE_CONFIG_FILE: &str = "config.yaml";
+pub const TRUSTED_SETUP_FILE: &str = "trusted_setup.json";
 
 // Creates definitions for:
 //
@@ -85,6 +86,8 @@ pub struct Eth2NetworkConfig {
     pub genesis_state_source: GenesisStateSource,
     pub genesis_state_bytes: Option<GenesisStateBytes>,
     pub config: Config,
+    /// The JSON encoded KZG trusted setup. Networks use the mainnet setup unless their directory
+    /// contains a `TRUSTED_SETUP_FILE`.
     pub kzg_trusted_setup: Vec<u8>,
 }
 
@@ -316,6 +319,18 @@ impl Eth2NetworkConfig {
                 })?;
         }
 
+        // The trusted setup is JSON and is only written if it isn't the mainnet setup.
+        if self.kzg_trusted_setup != get_trusted_setup() {
+            let file = base_dir.join(TRUSTED_SETUP_FILE);
+
+            File::create(&file)
+                .map_err(|e| format!("Unable to create {:?}: {:?}", file, e))
+                .and_then(|mut file| {
+                    file.write_all(&self.kzg_trusted_setup)
+                        .map_err(|e| format!("Unable to write {:?}: {:?}", file, e))
+                })?;
+        }
+
         Ok(())
     }
 
@@ -367,7 +382,18 @@ impl Eth2NetworkConfig {
             (None, GenesisStateSource::Unknown)
         };
 
-        let kzg_trusted_setup = get_trusted_setup();
+        let trusted_setup_file_path = base_dir.join(TRUSTED_SETUP_FILE);
+        let kzg_trusted_setup = if trusted_setup_file_path.exists() {
+            let bytes = std::fs::read(&trusted_setup_file_path)
+                .map_err(|e| format!("Unable to read {:?}: {:?}", trusted_setup_file_path, e))?;
+            serde_json::from_slice::<TrustedSetup>(&bytes)
+                .map_err(|e| format!("Unable to parse {}: {:?}", TRUSTED_SETUP_FILE, e))?
+                .validate()
+                .map_err(|e| format!("Invalid {}: {}", TRUSTED_SETUP_FILE, e))?;
+            bytes
+        } else {
+            get_trusted_setup()
+        };
 
         Ok(Self {
             deposit_contract_deploy_block,
@@ -568,6 +594,54 @@ mod tests {
         do_test::<E>(None, None, config);
     }
 
+    #[test]
+    fn custom_trusted_setup_round_trip() {
+        // Re-encoding the mainnet setup changes its bytes, so it is treated as a custom setup.
+        let trusted_setup: TrustedSetup = serde_json::from_slice(&get_trusted_setup()).unwrap();
+        let kzg_trusted_setup = serde_json::to_vec(&trusted_setup).unwrap();
+        assert_ne!(kzg_trusted_setup, get_trusted_setup());
+
+        let temp_dir = TempBuilder::new()
+            .prefix("eth2_testnet_test")
+            .tempdir()
+            .expect("should create temp dir");
+        let base_dir = temp_dir.path().join("my_testnet");
+
+        let testnet = Eth2NetworkConfig {
+            kzg_trusted_setup,
+            ..Eth2NetworkConfig::constant("mainnet").unwrap().unwrap()
+        };
+        testnet
+            .write_to_file(base_dir.clone(), false)
+            .expect("should write to file");
+        assert!(base_dir.join(TRUSTED_SETUP_FILE).exists());
+
+        let decoded = Eth2NetworkConfig::load(base_dir).expect("should load struct");
+        assert_eq!(testnet.kzg_trusted_setup, decoded.kzg_trusted_setup);
+    }
+
+    #[test]
+    fn invalid_trusted_setup_rejected() {
+        let temp_dir = TempBuilder::new()
+            .prefix("eth2_testnet_test")
+            .tempdir()
+            .expect("should create temp dir");
+        let base_dir = temp_dir.path().join("my_testnet");
+
+        Eth2NetworkConfig::constant("mainnet")
+            .unwrap()
+            .unwrap()
+            .write_to_file(base_dir.clone(), false)
+            .expect("should write to file");
+        std::fs::write(
+            base_dir.join(TRUSTED_SETUP_FILE),
+            r#"{"g1_monomial": [], "g1_lagrange": [], "g2_monomial": []}"#,
+        )
+        .unwrap();
+
+        assert!(Eth2NetworkConfig::load(base_dir).is_err());
+    }
+
     fn do_test<E: EthSpec>(
         boot_enr: Option<Vec<Enr<CombinedKey>>>,
         genesis_state: Option<BeaconState<E>>,
@@ -1,4 +1,7 @@
-use lighthouse_network::{types::SyncState, NetworkGlobals};
+use lighthouse_network::{
+    types::{PortMappingState, SyncState},
+    NetworkGlobals,
+};
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
@@ -209,12 +212,20 @@ pub struct NatState {
     libp2p_ipv4: bool,
     /// Contactable on libp2p ipv6.
     libp2p_ipv6: bool,
+    /// The state of the discovery port mapping, if one is being maintained.
+    port_mapping: Option<PortMappingState>,
 }
 
 impl NatState {
     pub fn is_anything_open(&self) -> bool {
         self.discv5_ipv4 || self.discv5_ipv6 || self.libp2p_ipv4 || self.libp2p_ipv6
     }
+
+    /// Adds the state of the port mapping maintained by the node.
+    pub fn with_port_mapping(mut self, port_mapping: Option<PortMappingState>) -> Self {
+        self.port_mapping = port_mapping;
+        self
+    }
 }
 
 /// Observes if NAT traversal is possible.
@@ -252,6 +263,7 @@ pub fn observe_nat() -> NatState {
         discv5_ipv6,
         libp2p_ipv4,
         libp2p_ipv6,
+        port_mapping: None,
     }
 }
 
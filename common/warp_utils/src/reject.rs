@@ -2,6 +2,8 @@ use eth2::types::{ErrorMessage, Failure, IndexedErrorMessage};
 use std::convert::Infallible;
 use std::error::Error;
 use std::fmt;
+use std::time::Duration;
+use warp::http::header::RETRY_AFTER;
 use warp::{http::StatusCode, reject::Reject, reply::Response, Reply};
 
 #[derive(Debug)]
@@ -127,6 +129,35 @@ pub fn not_synced(msg: String) -> warp::reject::Rejection {
     warp::reject::custom(NotSynced(msg))
 }
 
+#[derive(Debug)]
+pub struct ServiceUnavailable(pub String);
+
+impl Reject for ServiceUnavailable {}
+
+pub fn service_unavailable(msg: String) -> warp::reject::Rejection {
+    warp::reject::custom(ServiceUnavailable(msg))
+}
+
+#[derive(Debug)]
+pub struct Conflict(pub String);
+
+impl Reject for Conflict {}
+
+pub fn conflict(msg: String) -> warp::reject::Rejection {
+    warp::reject::custom(Conflict(msg))
+}
+
+#[derive(Debug)]
+pub struct TooManyRequests {
+    pub retry_after: Duration,
+}
+
+impl Reject for TooManyRequests {}
+
+pub fn too_many_requests(retry_after: Duration) -> warp::reject::Rejection {
+    warp::reject::custom(TooManyRequests { retry_after })
+}
+
 #[derive(Debug)]
 pub struct InvalidAuthorization(pub String);
 
@@ -173,7 +204,31 @@ pub async fn handle_rejection(err: warp::Rejection) -> Result<impl warp::Reply,
             failures: e.failures.clone(),
         });
 
-        return Ok(warp::reply::with_status(json, code));
+        return Ok(warp::reply::with_status(json, code).into_response());
+    }
+
+    if let Some(e) = err.find::<crate::reject::TooManyRequests>() {
+        // Round up so that clients which honour `Retry-After` are not rejected again.
+        let retry_after_secs =
+            e.retry_after.as_secs() + u64::from(e.retry_after.subsec_nanos() > 0);
+        code = StatusCode::TOO_MANY_REQUESTS;
+        message = format!(
+            "TOO_MANY_REQUESTS: rate limit exceeded, retry after {} seconds",
+            retry_after_secs
+        );
+
+        let json = warp::reply::json(&ErrorMessage {
+            code: code.as_u16(),
+            message,
+            stacktraces: vec![],
+        });
+
+        return Ok(warp::reply::with_header(
+            warp::reply::with_status(json, code),
+            RETRY_AFTER,
+            retry_after_secs.to_string(),
+        )
+        .into_response());
     }
 
     if err.is_not_found() {
@@ -225,6 +280,12 @@ pub async fn handle_rejection(err: warp::Rejection) -> Result<impl warp::Reply,
     } else if let Some(e) = err.find::<crate::reject::NotSynced>() {
         code = StatusCode::SERVICE_UNAVAILABLE;
         message = format!("SERVICE_UNAVAILABLE: beacon node is syncing: {}", e.0);
+    } else if let Some(e) = err.find::<crate::reject::ServiceUnavailable>() {
+        code = StatusCode::SERVICE_UNAVAILABLE;
+        message = format!("SERVICE_UNAVAILABLE: {}", e.0);
+    } else if let Some(e) = err.find::<crate::reject::Conflict>() {
+        code = StatusCode::CONFLICT;
+        message = format!("CONFLICT: {}", e.0);
     } else if let Some(e) = err.find::<crate::reject::InvalidAuthorization>() {
         code = StatusCode::FORBIDDEN;
         message = format!("FORBIDDEN: Invalid auth token: {}", e.0);
@@ -253,7 +314,7 @@ pub async fn handle_rejection(err: warp::Rejection) -> Result<impl warp::Reply,
         stacktraces: vec![],
     });
 
-    Ok(warp::reply::with_status(json, code))
+    Ok(warp::reply::with_status(json, code).into_response())
 }
 
 /// Convert a warp `Rejection` into a `Response`.
@@ -6,7 +6,7 @@ mod proto_array_fork_choice;
 mod ssz_container;
 
 pub use crate::justified_balances::JustifiedBalances;
-pub use crate::proto_array::{calculate_committee_fraction, InvalidationOperation};
+pub use crate::proto_array::{calculate_committee_fraction, InvalidationOperation, ReorgBranches};
 pub use crate::proto_array_fork_choice::{
     Block, DisallowedReOrgOffsets, DoNotReOrg, ExecutionStatus, ProposerHeadError,
     ProposerHeadInfo, ProtoArrayForkChoice, ReOrgThreshold,
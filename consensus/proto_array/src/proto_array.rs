@@ -110,6 +110,17 @@ pub struct ProtoNode {
     pub unrealized_finalized_checkpoint: Option<Checkpoint>,
 }
 
+/// The blocks which differ between two chains in the block tree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReorgBranches {
+    /// The root and slot of the most recent block which is in both chains.
+    pub common_ancestor: (Hash256, Slot),
+    /// The roots and slots of the blocks only in the old chain, most recent first.
+    pub old_blocks: Vec<(Hash256, Slot)>,
+    /// The roots and slots of the blocks only in the new chain, most recent first.
+    pub new_blocks: Vec<(Hash256, Slot)>,
+}
+
 #[derive(PartialEq, Debug, Encode, Decode, Serialize, Deserialize, Copy, Clone)]
 pub struct ProposerBoost {
     pub root: Hash256,
@@ -934,6 +945,43 @@ impl ProtoArray {
             .map(|node| (node.root, node.slot))
     }
 
+    /// Returns the blocks which are only in the chain of `old_head_root` or `new_head_root`,
+    /// along with their most recent common ancestor.
+    ///
+    /// Returns `None` if either root is unknown or the chains have no common ancestor in the
+    /// tree, e.g. because it has been pruned.
+    pub fn find_branches(
+        &self,
+        old_head_root: &Hash256,
+        new_head_root: &Hash256,
+    ) -> Option<ReorgBranches> {
+        let mut old_index = *self.indices.get(old_head_root)?;
+        let mut new_index = *self.indices.get(new_head_root)?;
+        let mut old_blocks = vec![];
+        let mut new_blocks = vec![];
+
+        // A parent always has a lower index than its children, so stepping back from the higher
+        // index of the two must eventually reach the common ancestor.
+        while old_index != new_index {
+            if old_index > new_index {
+                let node = self.nodes.get(old_index)?;
+                old_blocks.push((node.root, node.slot));
+                old_index = node.parent?;
+            } else {
+                let node = self.nodes.get(new_index)?;
+                new_blocks.push((node.root, node.slot));
+                new_index = node.parent?;
+            }
+        }
+
+        let common_ancestor = self.nodes.get(old_index)?;
+        Some(ReorgBranches {
+            common_ancestor: (common_ancestor.root, common_ancestor.slot),
+            old_blocks,
+            new_blocks,
+        })
+    }
+
     /// Returns `true` if the `descendant_root` has an ancestor with `ancestor_root`. Always
     /// returns `false` if either input root is unknown.
     ///
@@ -2,7 +2,7 @@ use crate::{
     error::Error,
     proto_array::{
         calculate_committee_fraction, InvalidationOperation, Iter, ProposerBoost, ProtoArray,
-        ProtoNode,
+        ProtoNode, ReorgBranches,
     },
     ssz_container::SszContainer,
     JustifiedBalances,
@@ -855,6 +855,15 @@ impl ProtoArrayForkChoice {
         }
     }
 
+    /// See `ProtoArray::find_branches`
+    pub fn find_branches(
+        &self,
+        old_head_root: &Hash256,
+        new_head_root: &Hash256,
+    ) -> Option<ReorgBranches> {
+        self.proto_array.find_branches(old_head_root, new_head_root)
+    }
+
     /// See `ProtoArray::iter_nodes`
     pub fn iter_nodes<'a>(&'a self, block_root: &Hash256) -> Iter<'a> {
         self.proto_array.iter_nodes(block_root)
@@ -1105,6 +1114,84 @@ mod test_compute_deltas {
         assert!(!fc.is_descendant(not_finalized_desc, unknown));
     }
 
+    #[test]
+    fn find_branches() {
+        let genesis_slot = Slot::new(0);
+        let state_root = Hash256::from_low_u64_be(0);
+        let genesis_root = Hash256::from_low_u64_be(1);
+        let junk_shuffling_id =
+            AttestationShufflingId::from_components(Epoch::new(0), Hash256::zero());
+        let execution_status = ExecutionStatus::irrelevant();
+        let genesis_checkpoint = Checkpoint {
+            epoch: Epoch::new(0),
+            root: genesis_root,
+        };
+
+        let mut fc = ProtoArrayForkChoice::new::<MainnetEthSpec>(
+            genesis_slot,
+            genesis_slot,
+            state_root,
+            genesis_checkpoint,
+            genesis_checkpoint,
+            junk_shuffling_id.clone(),
+            junk_shuffling_id.clone(),
+            execution_status,
+        )
+        .unwrap();
+
+        // Build the tree:
+        //
+        // genesis <- 2 <- 3 <- 4
+        //             \
+        //              <- 5
+        let mut add_block = |root: u64, parent: u64, slot: u64| {
+            fc.proto_array
+                .on_block::<MainnetEthSpec>(
+                    Block {
+                        slot: Slot::new(slot),
+                        root: Hash256::from_low_u64_be(root),
+                        parent_root: Some(Hash256::from_low_u64_be(parent)),
+                        state_root,
+                        target_root: genesis_root,
+                        current_epoch_shuffling_id: junk_shuffling_id.clone(),
+                        next_epoch_shuffling_id: junk_shuffling_id.clone(),
+                        justified_checkpoint: genesis_checkpoint,
+                        finalized_checkpoint: genesis_checkpoint,
+                        execution_status,
+                        unrealized_justified_checkpoint: Some(genesis_checkpoint),
+                        unrealized_finalized_checkpoint: Some(genesis_checkpoint),
+                    },
+                    Slot::new(slot),
+                )
+                .unwrap();
+        };
+        add_block(2, 1, 1);
+        add_block(3, 2, 2);
+        add_block(4, 3, 3);
+        add_block(5, 2, 3);
+
+        let block = |root: u64, slot: u64| (Hash256::from_low_u64_be(root), Slot::new(slot));
+
+        let branches = fc
+            .find_branches(&Hash256::from_low_u64_be(4), &Hash256::from_low_u64_be(5))
+            .unwrap();
+        assert_eq!(branches.common_ancestor, block(2, 1));
+        assert_eq!(branches.old_blocks, vec![block(4, 3), block(3, 2)]);
+        assert_eq!(branches.new_blocks, vec![block(5, 3)]);
+
+        // A chain which extends the old head has no old blocks.
+        let branches = fc
+            .find_branches(&Hash256::from_low_u64_be(2), &Hash256::from_low_u64_be(4))
+            .unwrap();
+        assert_eq!(branches.common_ancestor, block(2, 1));
+        assert!(branches.old_blocks.is_empty());
+        assert_eq!(branches.new_blocks, vec![block(4, 3), block(3, 2)]);
+
+        assert!(fc
+            .find_branches(&Hash256::from_low_u64_be(4), &Hash256::from_low_u64_be(42))
+            .is_none());
+    }
+
     /// This test covers an interesting case where a block can be a descendant
     /// of the finalized *block*, but not a descenant of the finalized
     /// *checkpoint*.
@@ -9,6 +9,7 @@ use derivative::Derivative;
 use ethereum_hashing::hash;
 use int_to_bytes::{int_to_bytes4, int_to_bytes8};
 use metastruct::{metastruct, NumFields};
+use milhouse::mem::MemorySize;
 pub use pubkey_cache::PubkeyCache;
 use safe_arith::{ArithError, SafeArith};
 use serde::{Deserialize, Serialize};
@@ -2673,3 +2674,63 @@ impl<E: EthSpec> ForkVersionDeserialize for BeaconState<E> {
         ))
     }
 }
+
+/// Measure the memory used by the tree-backed fields of the state.
+///
+/// Caches and fixed-size fields are only counted by their inline size, so the measurement is a
+/// lower bound on the memory used by the state.
+impl<E: EthSpec> MemorySize for BeaconState<E> {
+    fn self_pointer(&self) -> usize {
+        self as *const Self as usize
+    }
+
+    fn subtrees(&self) -> Vec<&dyn MemorySize> {
+        let mut subtrees: Vec<&dyn MemorySize> = vec![];
+        match self {
+            Self::Base(self_inner) => {
+                map_beacon_state_base_tree_list_fields_immutable!(self_inner, |_, self_field| {
+                    subtrees.push(self_field);
+                });
+            }
+            Self::Altair(self_inner) => {
+                map_beacon_state_altair_tree_list_fields_immutable!(self_inner, |_, self_field| {
+                    subtrees.push(self_field);
+                });
+            }
+            Self::Bellatrix(self_inner) => {
+                map_beacon_state_bellatrix_tree_list_fields_immutable!(
+                    self_inner,
+                    |_, self_field| {
+                        subtrees.push(self_field);
+                    }
+                );
+            }
+            Self::Capella(self_inner) => {
+                map_beacon_state_capella_tree_list_fields_immutable!(
+                    self_inner,
+                    |_, self_field| {
+                        subtrees.push(self_field);
+                    }
+                );
+            }
+            Self::Deneb(self_inner) => {
+                map_beacon_state_deneb_tree_list_fields_immutable!(self_inner, |_, self_field| {
+                    subtrees.push(self_field);
+                });
+            }
+            Self::Electra(self_inner) => {
+                map_beacon_state_electra_tree_list_fields_immutable!(
+                    self_inner,
+                    |_, self_field| {
+                        subtrees.push(self_field);
+                    }
+                );
+            }
+        }
+        subtrees
+    }
+
+    fn intrinsic_size(&self) -> usize {
+        mem::size_of::<Self>()
+    }
+}
@@ -12,6 +12,14 @@ use std::path::Path;
 use std::time::Duration;
 use tree_hash::TreeHash;
 
+/// The blob parameters which apply from `epoch` until the next entry in the blob schedule.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub struct BlobParameters {
+    pub epoch: Epoch,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub max_blobs_per_block: u64,
+}
+
 /// Each of the BLS signature domains.
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum Domain {
@@ -427,6 +435,39 @@ impl ChainSpec {
         })
     }
 
+    /// Returns the maximum number of blobs permitted in a block of the given fork.
+    ///
+    /// Forks prior to Deneb do not support blobs and return `0`.
+    pub fn max_blobs_per_block_for_fork<E: EthSpec>(&self, fork_name: ForkName) -> u64 {
+        if fork_name.deneb_enabled() {
+            E::max_blobs_per_block() as u64
+        } else {
+            0
+        }
+    }
+
+    /// Returns the schedule of blob parameters for all forks that have an activation epoch.
+    ///
+    /// An entry is only added when the maximum number of blobs per block changes, so the first
+    /// entry corresponds to the activation of Deneb.
+    pub fn blob_schedule<E: EthSpec>(&self) -> Vec<BlobParameters> {
+        let mut schedule: Vec<BlobParameters> = vec![];
+        for (fork_name, fork_epoch) in ForkName::list_all_fork_epochs(self) {
+            let Some(epoch) = fork_epoch.filter(|epoch| *epoch != self.far_future_epoch) else {
+                continue;
+            };
+            let max_blobs_per_block = self.max_blobs_per_block_for_fork::<E>(fork_name);
+            let previous = schedule.last().map_or(0, |entry| entry.max_blobs_per_block);
+            if max_blobs_per_block != previous {
+                schedule.push(BlobParameters {
+                    epoch,
+                    max_blobs_per_block,
+                });
+            }
+        }
+        schedule
+    }
+
     /// Returns a full `Fork` struct for a given epoch.
     pub fn fork_at_epoch(&self, epoch: Epoch) -> Fork {
         let current_fork_name = self.fork_name_at_epoch(epoch);
@@ -1963,6 +2004,26 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn blob_schedule_starts_at_deneb() {
+        type E = MainnetEthSpec;
+        let mut spec = ChainSpec::mainnet();
+
+        spec.deneb_fork_epoch = None;
+        spec.electra_fork_epoch = None;
+        assert!(spec.blob_schedule::<E>().is_empty());
+
+        spec.deneb_fork_epoch = Some(Epoch::new(10));
+        spec.electra_fork_epoch = Some(Epoch::new(20));
+        assert_eq!(
+            spec.blob_schedule::<E>(),
+            vec![BlobParameters {
+                epoch: Epoch::new(10),
+                max_blobs_per_block: E::max_blobs_per_block() as u64,
+            }]
+        );
+    }
 }
 
 #[cfg(test)]
@@ -139,7 +139,7 @@ pub use crate::beacon_committee::{BeaconCommittee, OwnedBeaconCommittee};
 pub use crate::beacon_state::{Error as BeaconStateError, *};
 pub use crate::blob_sidecar::{BlobIdentifier, BlobSidecar, BlobSidecarList, BlobsList};
 pub use crate::bls_to_execution_change::BlsToExecutionChange;
-pub use crate::chain_spec::{ChainSpec, Config, Domain};
+pub use crate::chain_spec::{BlobParameters, ChainSpec, Config, Domain};
 pub use crate::checkpoint::Checkpoint;
 pub use crate::config_and_preset::{
     ConfigAndPreset, ConfigAndPresetCapella, ConfigAndPresetDeneb, ConfigAndPresetElectra,
@@ -199,8 +199,9 @@ pub use crate::light_client_optimistic_update::{
     LightClientOptimisticUpdateElectra,
 };
 pub use crate::light_client_update::{
-    Error as LightClientUpdateError, LightClientUpdate, LightClientUpdateAltair,
-    LightClientUpdateCapella, LightClientUpdateDeneb, LightClientUpdateElectra, MerkleProof,
+    EncodedLightClientUpdate, Error as LightClientUpdateError, LightClientUpdate,
+    LightClientUpdateAltair, LightClientUpdateCapella, LightClientUpdateDeneb,
+    LightClientUpdateElectra, LightClientUpdateRank, MerkleProof,
 };
 pub use crate::participation_flags::ParticipationFlags;
 pub use crate::payload::{
@@ -363,62 +363,20 @@ impl<E: EthSpec> LightClientUpdate<E> {
         new: &Self,
         chain_spec: &ChainSpec,
     ) -> Result<bool, Error> {
-        // Compare super majority (> 2/3) sync committee participation
-        let max_active_participants = new.sync_aggregate().sync_committee_bits.len();
-
-        let new_active_participants = new.sync_aggregate().sync_committee_bits.num_set_bits();
-        let prev_active_participants = self.sync_aggregate().sync_committee_bits.num_set_bits();
-
-        let new_has_super_majority =
-            new_active_participants.safe_mul(3)? >= max_active_participants.safe_mul(2)?;
-        let prev_has_super_majority =
-            prev_active_participants.safe_mul(3)? >= max_active_participants.safe_mul(2)?;
-
-        if new_has_super_majority != prev_has_super_majority {
-            return Ok(new_has_super_majority);
-        }
-
-        if !new_has_super_majority && new_active_participants != prev_active_participants {
-            return Ok(new_active_participants > prev_active_participants);
-        }
-
-        // Compare presence of relevant sync committee
-        let new_has_relevant_sync_committee = new.is_sync_committee_update(chain_spec)?;
-        let prev_has_relevant_sync_committee = self.is_sync_committee_update(chain_spec)?;
-        if new_has_relevant_sync_committee != prev_has_relevant_sync_committee {
-            return Ok(new_has_relevant_sync_committee);
-        }
-
-        // Compare indication of any finality
-        let new_has_finality = !new.is_finality_branch_empty();
-        let prev_has_finality = !self.is_finality_branch_empty();
-        if new_has_finality != prev_has_finality {
-            return Ok(new_has_finality);
-        }
-
-        // Compare sync committee finality
-        if new_has_finality {
-            let new_has_sync_committee_finality = new.has_sync_committee_finality(chain_spec)?;
-            let prev_has_sync_committee_finality = self.has_sync_committee_finality(chain_spec)?;
-            if new_has_sync_committee_finality != prev_has_sync_committee_finality {
-                return Ok(new_has_sync_committee_finality);
-            }
-        }
-
-        // Tiebreaker 1: Sync committee participation beyond super majority
-        if new_active_participants != prev_active_participants {
-            return Ok(new_active_participants > prev_active_participants);
-        }
-
-        let new_attested_header_slot = new.attested_header_slot();
-        let prev_attested_header_slot = self.attested_header_slot();
-
-        // Tiebreaker 2: Prefer older data (fewer changes to best)
-        if new_attested_header_slot != prev_attested_header_slot {
-            return Ok(new_attested_header_slot < prev_attested_header_slot);
-        }
+        self.rank(chain_spec)?.is_better(&new.rank(chain_spec)?)
+    }
 
-        Ok(new.signature_slot() < self.signature_slot())
+    /// Returns the properties of this update used to compare it against other updates.
+    pub fn rank(&self, chain_spec: &ChainSpec) -> Result<LightClientUpdateRank, Error> {
+        Ok(LightClientUpdateRank {
+            active_participants: self.sync_aggregate().sync_committee_bits.num_set_bits() as u64,
+            max_active_participants: self.sync_aggregate().sync_committee_bits.len() as u64,
+            has_relevant_sync_committee: self.is_sync_committee_update(chain_spec)?,
+            has_finality: !self.is_finality_branch_empty(),
+            has_sync_committee_finality: self.has_sync_committee_finality(chain_spec)?,
+            attested_header_slot: self.attested_header_slot(),
+            signature_slot: *self.signature_slot(),
+        })
     }
 
     fn is_next_sync_committee_branch_empty<'a>(&'a self) -> bool {
@@ -462,6 +420,102 @@ impl<E: EthSpec> LightClientUpdate<E> {
     }
 }
 
+/// The SSZ encoding of a `LightClientUpdate`, which can be served to peers without decoding and
+/// re-encoding it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EncodedLightClientUpdate {
+    /// The fork of the encoded update's variant, which determines its context bytes.
+    pub fork_name: ForkName,
+    pub bytes: Vec<u8>,
+}
+
+impl EncodedLightClientUpdate {
+    /// Wrap the bytes of an update persisted for a period starting in `fork_name`, which are
+    /// decoded as the variant of that fork by `LightClientUpdate::from_ssz_bytes`.
+    pub fn from_persisted(bytes: Vec<u8>, fork_name: ForkName) -> Result<Self, ssz::DecodeError> {
+        let fork_name = match fork_name {
+            ForkName::Altair | ForkName::Bellatrix => ForkName::Altair,
+            ForkName::Capella => ForkName::Capella,
+            ForkName::Deneb => ForkName::Deneb,
+            ForkName::Electra => ForkName::Electra,
+            ForkName::Base => {
+                return Err(ssz::DecodeError::BytesInvalid(format!(
+                    "LightClientUpdate decoding for {fork_name} not implemented"
+                )))
+            }
+        };
+        Ok(Self { fork_name, bytes })
+    }
+}
+
+/// The properties of a `LightClientUpdate` which determine whether it is better than another
+/// update for the same sync committee period.
+///
+/// Persisted alongside the best update for each period, so that newly derived updates can be
+/// ranked against it without decoding the stored update.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+pub struct LightClientUpdateRank {
+    pub active_participants: u64,
+    pub max_active_participants: u64,
+    pub has_relevant_sync_committee: bool,
+    pub has_finality: bool,
+    pub has_sync_committee_finality: bool,
+    pub attested_header_slot: Slot,
+    pub signature_slot: Slot,
+}
+
+impl LightClientUpdateRank {
+    /// Returns `true` if an update with rank `new` is better than an update with this rank,
+    /// following the spec's `is_better_update`.
+    pub fn is_better(&self, new: &Self) -> Result<bool, Error> {
+        // Compare super majority (> 2/3) sync committee participation
+        let max_active_participants = new.max_active_participants;
+
+        let new_active_participants = new.active_participants;
+        let prev_active_participants = self.active_participants;
+
+        let new_has_super_majority =
+            new_active_participants.safe_mul(3)? >= max_active_participants.safe_mul(2)?;
+        let prev_has_super_majority =
+            prev_active_participants.safe_mul(3)? >= max_active_participants.safe_mul(2)?;
+
+        if new_has_super_majority != prev_has_super_majority {
+            return Ok(new_has_super_majority);
+        }
+
+        if !new_has_super_majority && new_active_participants != prev_active_participants {
+            return Ok(new_active_participants > prev_active_participants);
+        }
+
+        // Compare presence of relevant sync committee
+        if new.has_relevant_sync_committee != self.has_relevant_sync_committee {
+            return Ok(new.has_relevant_sync_committee);
+        }
+
+        // Compare indication of any finality
+        if new.has_finality != self.has_finality {
+            return Ok(new.has_finality);
+        }
+
+        // Compare sync committee finality
+        if new.has_finality && new.has_sync_committee_finality != self.has_sync_committee_finality {
+            return Ok(new.has_sync_committee_finality);
+        }
+
+        // Tiebreaker 1: Sync committee participation beyond super majority
+        if new_active_participants != prev_active_participants {
+            return Ok(new_active_participants > prev_active_participants);
+        }
+
+        // Tiebreaker 2: Prefer older data (fewer changes to best)
+        if new.attested_header_slot != self.attested_header_slot {
+            return Ok(new.attested_header_slot < self.attested_header_slot);
+        }
+
+        Ok(new.signature_slot < self.signature_slot)
+    }
+}
+
 fn is_empty_branch(branch: &[Hash256]) -> bool {
     for index in branch.iter() {
         if *index != Hash256::default() {
@@ -580,4 +634,52 @@ mod tests {
             NEXT_SYNC_COMMITTEE_PROOF_LEN_ELECTRA
         );
     }
+
+    #[test]
+    fn update_rank_ordering() {
+        let rank = LightClientUpdateRank {
+            active_participants: 400,
+            max_active_participants: 512,
+            has_relevant_sync_committee: true,
+            has_finality: true,
+            has_sync_committee_finality: true,
+            attested_header_slot: Slot::new(10),
+            signature_slot: Slot::new(11),
+        };
+
+        // Losing the super majority is worse, even with other improvements.
+        let no_super_majority = LightClientUpdateRank {
+            active_participants: 300,
+            attested_header_slot: Slot::new(5),
+            ..rank
+        };
+        assert!(!rank.is_better(&no_super_majority).unwrap());
+        assert!(no_super_majority.is_better(&rank).unwrap());
+
+        // A relevant sync committee is preferred over finality.
+        let no_sync_committee = LightClientUpdateRank {
+            has_relevant_sync_committee: false,
+            ..rank
+        };
+        assert!(!rank.is_better(&no_sync_committee).unwrap());
+
+        // Between otherwise equal updates, more participation and then older data is preferred.
+        let more_participants = LightClientUpdateRank {
+            active_participants: 450,
+            ..rank
+        };
+        assert!(rank.is_better(&more_participants).unwrap());
+        let older = LightClientUpdateRank {
+            attested_header_slot: Slot::new(9),
+            ..rank
+        };
+        assert!(rank.is_better(&older).unwrap());
+
+        // An identical update is not better.
+        assert!(!rank.is_better(&rank).unwrap());
+        assert_eq!(
+            LightClientUpdateRank::from_ssz_bytes(&rank.as_ssz_bytes()).unwrap(),
+            rank
+        );
+    }
 }
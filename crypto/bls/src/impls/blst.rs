@@ -11,6 +11,7 @@ use crate::{
 pub use blst::min_pk as blst_core;
 use blst::{blst_scalar, BLST_ERROR};
 use rand::Rng;
+use std::collections::HashMap;
 
 pub const DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
 pub const RAND_BITS: usize = 64;
@@ -20,6 +21,7 @@ pub mod types {
     pub use super::blst_core::PublicKey;
     pub use super::blst_core::SecretKey;
     pub use super::blst_core::Signature;
+    pub use super::verify_grouped_signature_sets;
     pub use super::verify_signature_sets;
     pub use super::BlstAggregatePublicKey as AggregatePublicKey;
     pub use super::BlstAggregateSignature as AggregateSignature;
@@ -51,66 +53,101 @@ pub fn verify_signature_sets<'a>(
     let mut pks = Vec::with_capacity(sets.len());
 
     for set in &sets {
-        // Generate random scalars.
-        let mut vals = [0u64; 4];
-        while vals[0] == 0 {
-            // Do not use zero
-            vals[0] = rng.gen();
-        }
-        let mut rand_i = std::mem::MaybeUninit::<blst_scalar>::uninit();
-
-        // TODO: remove this `unsafe` code-block once we get a safe option from `blst`.
-        //
-        // https://github.com/sigp/lighthouse/issues/1720
-        unsafe {
-            blst::blst_scalar_from_uint64(rand_i.as_mut_ptr(), vals.as_ptr());
-            rands.push(rand_i.assume_init());
-        }
+        rands.push(random_scalar(rng));
 
         // Grab a slice of the message, to satisfy the blst API.
         msgs_refs.push(set.message.as_slice());
 
-        if let Some(point) = set.signature.point() {
-            // Subgroup check the signature
-            if !point.0.subgroup_check() {
-                return false;
-            }
-            // Convert the aggregate signature into a signature.
-            sigs.push(point.0.to_signature())
-        } else {
-            // Any "empty" signature should cause a signature failure.
+        let Some((sig, pk)) = signature_and_public_key(set) else {
             return false;
-        }
+        };
+        sigs.push(sig);
+        pks.push(pk);
+    }
+
+    let (sig_refs, pks_refs): (Vec<_>, Vec<_>) = sigs.iter().zip(pks.iter()).unzip();
 
-        // Sanity check.
-        if set.signing_keys.is_empty() {
-            // A signature that has no signing keys is invalid.
+    // Public keys have already been checked for subgroup and infinity
+    // Signatures have already been checked for subgroup
+    // Signature checks above could be done here for convienence as well
+    let err = blst_core::Signature::verify_multiple_aggregate_signatures(
+        &msgs_refs, DST, &pks_refs, false, &sig_refs, false, &rands, RAND_BITS,
+    );
+
+    err == blst::BLST_ERROR::BLST_SUCCESS
+}
+
+/// Verify `signature_sets` in the same manner as `verify_signature_sets`, except that sets with
+/// identical messages are combined so that each distinct message requires a single pairing.
+///
+/// The signatures and public keys of sets with the same message are combined using a random
+/// linear combination, so that invalid signatures cannot cancel each other out.
+pub fn verify_grouped_signature_sets<'a>(
+    signature_sets: impl ExactSizeIterator<Item = &'a SignatureSet<'a>>,
+) -> bool {
+    // The signatures and public keys of the sets for each distinct message.
+    let mut groups: HashMap<Hash256, (Vec<blst_core::Signature>, Vec<blst_core::PublicKey>)> =
+        HashMap::new();
+
+    for set in signature_sets {
+        let Some((sig, pk)) = signature_and_public_key(set) else {
             return false;
-        }
+        };
+        let (sigs, pks) = groups.entry(set.message).or_default();
+        sigs.push(sig);
+        pks.push(pk);
+    }
 
-        // Collect all the public keys into a point, to satisfy the blst API.
-        //
-        // Note: we could potentially have the `SignatureSet` take a pubkey point instead of a
-        // `GenericPublicKey` and avoid this allocation.
-        let signing_keys = set
-            .signing_keys
-            .iter()
-            .map(|pk| pk.point())
-            .collect::<Vec<_>>();
-
-        // Aggregate all the public keys.
+    if groups.is_empty() {
+        return false;
+    }
+
+    let rng = &mut rand::thread_rng();
+
+    let mut rands: Vec<blst_scalar> = Vec::with_capacity(groups.len());
+    let mut msgs_refs = Vec::with_capacity(groups.len());
+    let mut sigs = Vec::with_capacity(groups.len());
+    let mut pks = Vec::with_capacity(groups.len());
+
+    for (message, (group_sigs, group_pks)) in &groups {
+        rands.push(random_scalar(rng));
+        msgs_refs.push(message.as_slice());
+
+        // Each set in the group is weighted by a non-zero random scalar of `RAND_BITS` bits.
+        let randomness = (0..group_sigs.len())
+            .flat_map(|_| {
+                let mut val = 0u64;
+                while val == 0 {
+                    val = rng.gen();
+                }
+                val.to_le_bytes()
+            })
+            .collect::<Vec<u8>>();
+
+        // Signatures have already been checked for subgroup
+        let Ok(agg_sig) = blst_core::AggregateSignature::aggregate_with_randomness(
+            group_sigs,
+            &randomness,
+            RAND_BITS,
+            false,
+        ) else {
+            return false;
+        };
         // Public keys have already been checked for subgroup and infinity
-        let Ok(agg_pk) = blst_core::AggregatePublicKey::aggregate(&signing_keys, false) else {
+        let Ok(agg_pk) = blst_core::AggregatePublicKey::aggregate_with_randomness(
+            group_pks,
+            &randomness,
+            RAND_BITS,
+            false,
+        ) else {
             return false;
         };
+        sigs.push(agg_sig.to_signature());
         pks.push(agg_pk.to_public_key());
     }
 
     let (sig_refs, pks_refs): (Vec<_>, Vec<_>) = sigs.iter().zip(pks.iter()).unzip();
 
-    // Public keys have already been checked for subgroup and infinity
-    // Signatures have already been checked for subgroup
-    // Signature checks above could be done here for convienence as well
     let err = blst_core::Signature::verify_multiple_aggregate_signatures(
         &msgs_refs, DST, &pks_refs, false, &sig_refs, false, &rands, RAND_BITS,
     );
@@ -118,6 +155,60 @@ pub fn verify_signature_sets<'a>(
     err == blst::BLST_ERROR::BLST_SUCCESS
 }
 
+/// Generate a non-zero random scalar of `RAND_BITS` bits.
+fn random_scalar(rng: &mut impl Rng) -> blst_scalar {
+    let mut vals = [0u64; 4];
+    while vals[0] == 0 {
+        // Do not use zero
+        vals[0] = rng.gen();
+    }
+    let mut rand_i = std::mem::MaybeUninit::<blst_scalar>::uninit();
+
+    // TODO: remove this `unsafe` code-block once we get a safe option from `blst`.
+    //
+    // https://github.com/sigp/lighthouse/issues/1720
+    unsafe {
+        blst::blst_scalar_from_uint64(rand_i.as_mut_ptr(), vals.as_ptr());
+        rand_i.assume_init()
+    }
+}
+
+/// Returns the signature of `set` and the aggregate of its signing keys, or `None` if the set is
+/// trivially invalid.
+fn signature_and_public_key(
+    set: &SignatureSet,
+) -> Option<(blst_core::Signature, blst_core::PublicKey)> {
+    // Any "empty" signature should cause a signature failure.
+    let point = set.signature.point()?;
+    // Subgroup check the signature
+    if !point.0.subgroup_check() {
+        return None;
+    }
+
+    // Sanity check.
+    if set.signing_keys.is_empty() {
+        // A signature that has no signing keys is invalid.
+        return None;
+    }
+
+    // Collect all the public keys into a point, to satisfy the blst API.
+    //
+    // Note: we could potentially have the `SignatureSet` take a pubkey point instead of a
+    // `GenericPublicKey` and avoid this allocation.
+    let signing_keys = set
+        .signing_keys
+        .iter()
+        .map(|pk| pk.point())
+        .collect::<Vec<_>>();
+
+    // Aggregate all the public keys.
+    // Public keys have already been checked for subgroup and infinity
+    let agg_pk = blst_core::AggregatePublicKey::aggregate(&signing_keys, false).ok()?;
+
+    // Convert the aggregate signature into a signature.
+    Some((point.0.to_signature(), agg_pk.to_public_key()))
+}
+
 impl TPublicKey for blst_core::PublicKey {
     fn serialize(&self) -> [u8; PUBLIC_KEY_BYTES_LEN] {
         self.compress()
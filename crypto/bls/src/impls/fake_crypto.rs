@@ -11,6 +11,7 @@ use crate::{
 
 /// Provides the externally-facing, core BLS types.
 pub mod types {
+    pub use super::verify_grouped_signature_sets;
     pub use super::verify_signature_sets;
     pub use super::AggregatePublicKey;
     pub use super::AggregateSignature;
@@ -34,6 +35,12 @@ pub fn verify_signature_sets<'a>(
     true
 }
 
+pub fn verify_grouped_signature_sets<'a>(
+    _signature_sets: impl ExactSizeIterator<Item = &'a SignatureSet<'a>>,
+) -> bool {
+    true
+}
+
 #[derive(Clone)]
 pub struct PublicKey([u8; PUBLIC_KEY_BYTES_LEN]);
 
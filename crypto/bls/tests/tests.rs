@@ -417,6 +417,26 @@ macro_rules! test_suite {
                 self
             }
 
+            /// Push two sets across the same message with their signatures swapped. The sum of
+            /// the signatures is valid for the sum of the public keys, but neither set is valid.
+            pub fn push_swapped_sets(mut self) -> Self {
+                let message = Hash256::from_low_u64_be(42);
+
+                for (signer, key) in [(0, 1), (1, 0)] {
+                    let mut signature = AggregateSignature::infinity();
+                    signature.add_assign(&secret_from_u64(signer).sign(message));
+
+                    self.owned_sets.push(OwnedSignatureSet {
+                        signature,
+                        signing_keys: vec![secret_from_u64(key).public_key()],
+                        message,
+                        should_be_valid: false,
+                    });
+                }
+
+                self
+            }
+
             pub fn push_invalid_pubkey_infinity_set(mut self) -> Self {
                 self.owned_sets.push(OwnedSignatureSet {
                     signature: AggregateSignature::deserialize(&INFINITY_SIGNATURE).unwrap(),
@@ -449,6 +469,10 @@ macro_rules! test_suite {
                     verify_signature_sets(signature_sets.iter()),
                     should_be_valid
                 );
+                assert_eq!(
+                    verify_grouped_signature_sets(signature_sets.iter()),
+                    should_be_valid
+                );
             }
         }
 
@@ -500,6 +524,22 @@ macro_rules! test_suite {
                 .run_checks()
         }
 
+        #[test]
+        fn signature_set_2_swapped_sets() {
+            SignatureSetTester::default()
+                .push_swapped_sets()
+                .run_checks()
+        }
+
+        #[test]
+        fn signature_set_2_valid_sets_with_2_swapped_sets() {
+            SignatureSetTester::default()
+                .push_valid_set(1)
+                .push_swapped_sets()
+                .push_valid_set(2)
+                .run_checks()
+        }
+
         #[test]
         fn signature_set_3_sets_with_one_invalid_pubkey_infinity_set() {
             SignatureSetTester::default()
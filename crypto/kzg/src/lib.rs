@@ -39,6 +39,8 @@ pub enum Error {
     ReconstructFailed(String),
     /// Kzg was not initialized with PeerDAS enabled.
     DASContextUninitialized,
+    /// The trusted setup is not usable with the compiled blob parameters.
+    InvalidTrustedSetup(String),
 }
 
 impl From<c_kzg::Error> for Error {
@@ -56,6 +58,9 @@ pub struct Kzg {
 
 impl Kzg {
     pub fn new_from_trusted_setup_no_precomp(trusted_setup: TrustedSetup) -> Result<Self, Error> {
+        trusted_setup
+            .validate()
+            .map_err(Error::InvalidTrustedSetup)?;
         let peerdas_trusted_setup = PeerDASTrustedSetup::from(&trusted_setup);
 
         let context = DASContext::new(&peerdas_trusted_setup, rust_eth_kzg::UsePrecomp::No);
@@ -71,6 +76,9 @@ impl Kzg {
 
     /// Load the kzg trusted setup parameters from a vec of G1 and G2 points.
     pub fn new_from_trusted_setup(trusted_setup: TrustedSetup) -> Result<Self, Error> {
+        trusted_setup
+            .validate()
+            .map_err(Error::InvalidTrustedSetup)?;
         let peerdas_trusted_setup = PeerDASTrustedSetup::from(&trusted_setup);
 
         let context = DASContext::new(
@@ -90,6 +98,10 @@ impl Kzg {
     }
 
     pub fn new_from_trusted_setup_das_enabled(trusted_setup: TrustedSetup) -> Result<Self, Error> {
+        trusted_setup
+            .validate()
+            .map_err(Error::InvalidTrustedSetup)?;
+
         // Initialize the trusted setup using default parameters
         //
         // Note: One can also use `from_json` to initialize it from the consensus-specs
@@ -1,4 +1,4 @@
-use crate::PeerDASTrustedSetup;
+use crate::{PeerDASTrustedSetup, FIELD_ELEMENTS_PER_BLOB};
 use c_kzg::{BYTES_PER_G1_POINT, BYTES_PER_G2_POINT};
 use serde::{
     de::{self, Deserializer, Visitor},
@@ -48,6 +48,31 @@ impl TrustedSetup {
     pub fn g1_len(&self) -> usize {
         self.g1_points.len()
     }
+
+    /// Check that the setup is usable with blobs of `FIELD_ELEMENTS_PER_BLOB` field elements.
+    ///
+    /// Setups with different points may be used by test networks, but the blob size is fixed at
+    /// compile time.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.g1_points.len() != FIELD_ELEMENTS_PER_BLOB {
+            return Err(format!(
+                "trusted setup has {} G1 Lagrange points, but blobs have {} field elements",
+                self.g1_points.len(),
+                FIELD_ELEMENTS_PER_BLOB
+            ));
+        }
+        if self.g1_monomial_points.len() != self.g1_points.len() {
+            return Err(format!(
+                "trusted setup has {} G1 monomial points and {} G1 Lagrange points",
+                self.g1_monomial_points.len(),
+                self.g1_points.len()
+            ));
+        }
+        if self.g2_points.is_empty() {
+            return Err("trusted setup has no G2 points".to_string());
+        }
+        Ok(())
+    }
 }
 
 impl From<&TrustedSetup> for PeerDASTrustedSetup {
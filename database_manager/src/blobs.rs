@@ -0,0 +1,527 @@
+//! Export and import of blob sidecars using era-style files.
+//!
+//! Each file covers the finalized slots of at most one era (`SLOTS_PER_HISTORICAL_ROOT` slots)
+//! and uses the e2store format of era files: a sequence of records of the form
+//! `type (2 bytes) | length (4 bytes, little-endian) | reserved (2 bytes) | data`. The records are:
+//!
+//! - A version record, as in era files.
+//! - A slot range record holding the first and last slot covered by the file. Blocks in this
+//!   range without a blob sidecars record have no blobs.
+//! - One blob sidecars record for every block with blobs, holding its SSZ `BlobSidecarList`.
+use crate::cli::{ExportBlobs, ImportBlobs};
+use beacon_chain::{blob_verification::verify_kzg_for_blob_list, Kzg};
+use beacon_node::ClientConfig;
+use environment::RuntimeContext;
+use slog::{info, warn, Logger};
+use ssz::{Decode, Encode};
+use std::cmp::{max, min};
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use store::e2store::{read_record, write_record, VERSION};
+use store::{metadata::BlobInfo, Error, HotColdDB, KeyValueStore, LevelDB};
+use types::{BlobSidecar, BlobSidecarList, Epoch, EthSpec, Slot, Unsigned};
+
+/// The file extension of blob files, as used by e2store files which aren't full era files.
+const FILE_EXTENSION: &str = "e2s";
+
+/// Record types, in addition to the version record.
+const SLOT_RANGE: [u8; 2] = [0x10, 0x00];
+const BLOB_SIDECARS: [u8; 2] = [0x11, 0x00];
+
+pub struct ExportBlobsConfig {
+    start_epoch: Option<Epoch>,
+    end_epoch: Option<Epoch>,
+    output_dir: PathBuf,
+}
+
+pub fn parse_export_blobs_config(export_config: &ExportBlobs) -> Result<ExportBlobsConfig, String> {
+    Ok(ExportBlobsConfig {
+        start_epoch: export_config.start_epoch.map(Epoch::new),
+        end_epoch: export_config.end_epoch.map(Epoch::new),
+        output_dir: export_config.output.clone(),
+    })
+}
+
+pub struct ImportBlobsConfig {
+    input_dir: PathBuf,
+}
+
+pub fn parse_import_blobs_config(import_config: &ImportBlobs) -> Result<ImportBlobsConfig, String> {
+    Ok(ImportBlobsConfig {
+        input_dir: import_config.input.clone(),
+    })
+}
+
+/// The maximum length of any record in a blob file, that of a full blob sidecars record.
+fn max_record_len<E: EthSpec>() -> usize {
+    BlobSidecar::<E>::max_size() * E::max_blobs_per_block()
+}
+
+fn encode_slot_range(start_slot: Slot, end_slot: Slot) -> Vec<u8> {
+    let mut data = start_slot.as_u64().to_le_bytes().to_vec();
+    data.extend_from_slice(&end_slot.as_u64().to_le_bytes());
+    data
+}
+
+fn decode_slot_range(data: &[u8]) -> Option<(Slot, Slot)> {
+    let start = data.get(0..8)?.try_into().ok()?;
+    let end = data.get(8..16)?.try_into().ok()?;
+    (data.len() == 16).then(|| {
+        (
+            Slot::new(u64::from_le_bytes(start)),
+            Slot::new(u64::from_le_bytes(end)),
+        )
+    })
+}
+
+fn blob_file_name(config_name: &str, era: u64) -> String {
+    format!("{config_name}-blobs-{era:05}.{FILE_EXTENSION}")
+}
+
+fn open_db<E: EthSpec>(
+    client_config: ClientConfig,
+    runtime_context: &RuntimeContext<E>,
+    log: Logger,
+) -> Result<Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>>, String> {
+    let hot_path = client_config.get_db_path();
+    let cold_path = client_config.get_freezer_db_path();
+    let blobs_path = client_config.get_blobs_db_path();
+
+    HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+        &hot_path,
+        &cold_path,
+        &blobs_path,
+        |_, _, _| Ok(()),
+        client_config.store,
+        runtime_context.eth2_config.spec.clone(),
+        log,
+    )
+    .map_err(|e| format!("Unable to open database: {e:?}"))
+}
+
+/// Write the blobs of all finalized blocks between the configured epochs to era-style files.
+pub fn export_blobs<E: EthSpec>(
+    export_config: ExportBlobsConfig,
+    client_config: ClientConfig,
+    runtime_context: &RuntimeContext<E>,
+    log: Logger,
+) -> Result<(), String> {
+    let db = open_db(client_config, runtime_context, log.clone())?;
+    let config_name = runtime_context
+        .eth2_config
+        .spec
+        .config_name
+        .clone()
+        .unwrap_or_else(|| "unknown".to_string());
+
+    let oldest_blob_slot = db
+        .get_blob_info()
+        .oldest_blob_slot
+        .ok_or("Deneb is not scheduled for this network")?;
+    let oldest_block_slot = db.get_anchor_info().oldest_block_slot;
+    // Only finalized blocks in the freezer database are exported, which avoids loading a state
+    // to iterate the hot database and guarantees that all exported blobs are canonical.
+    let split_slot = db.get_split_slot();
+
+    let mut start_slot = max(oldest_blob_slot, oldest_block_slot);
+    if let Some(start_epoch) = export_config.start_epoch {
+        start_slot = max(start_slot, start_epoch.start_slot(E::slots_per_epoch()));
+    }
+    let Some(mut end_slot) = split_slot.as_u64().checked_sub(1).map(Slot::new) else {
+        return Err("No finalized blocks stored".into());
+    };
+    if let Some(end_epoch) = export_config.end_epoch {
+        end_slot = min(end_slot, end_epoch.end_slot(E::slots_per_epoch()));
+    }
+    if start_slot > end_slot {
+        return Err(format!(
+            "No finalized blobs stored in the requested range, blobs are available from slot \
+             {start_slot} to slot {}",
+            split_slot.saturating_sub(1u64)
+        ));
+    }
+
+    fs::create_dir_all(&export_config.output_dir)
+        .map_err(|e| format!("Unable to create output directory: {e:?}"))?;
+
+    info!(
+        log,
+        "Exporting blobs";
+        "start_slot" => start_slot,
+        "end_slot" => end_slot,
+        "output_dir" => ?export_config.output_dir,
+    );
+
+    let slots_per_era = E::SlotsPerHistoricalRoot::to_u64();
+    let mut era_start_slot = start_slot;
+    while era_start_slot <= end_slot {
+        let era = era_start_slot.as_u64() / slots_per_era;
+        let era_end_slot = min(end_slot, Slot::new((era + 1) * slots_per_era - 1));
+        let path = export_config
+            .output_dir
+            .join(blob_file_name(&config_name, era));
+
+        let num_blocks = export_era(&db, &path, era_start_slot, era_end_slot)
+            .map_err(|e| format!("Unable to export blobs to {path:?}: {e}"))?;
+
+        info!(
+            log,
+            "Exported blobs";
+            "file" => ?path,
+            "start_slot" => era_start_slot,
+            "end_slot" => era_end_slot,
+            "blocks_with_blobs" => num_blocks,
+        );
+        era_start_slot = era_end_slot + 1;
+    }
+
+    Ok(())
+}
+
+/// Write the blobs from `start_slot..=end_slot` to the file at `path`.
+///
+/// Returns the number of blocks with blobs.
+fn export_era<E: EthSpec>(
+    db: &HotColdDB<E, LevelDB<E>, LevelDB<E>>,
+    path: &Path,
+    start_slot: Slot,
+    end_slot: Slot,
+) -> Result<usize, String> {
+    let file = File::create(path).map_err(|e| format!("{e:?}"))?;
+    let mut writer = BufWriter::new(file);
+    write_record(&mut writer, VERSION, &[]).map_err(|e| format!("{e:?}"))?;
+    write_record(
+        &mut writer,
+        SLOT_RANGE,
+        &encode_slot_range(start_slot, end_slot),
+    )
+    .map_err(|e| format!("{e:?}"))?;
+
+    let mut num_blocks = 0;
+    let mut prev_block_root = None;
+    let block_roots = db
+        .forwards_block_roots_iterator_until(start_slot, end_slot, || {
+            Err(Error::NoContinuationData)
+        })
+        .map_err(|e| format!("{e:?}"))?;
+    for res in block_roots {
+        let (block_root, _) = res.map_err(|e| format!("{e:?}"))?;
+        // Skipped slots repeat the root of the previous block.
+        if prev_block_root == Some(block_root) {
+            continue;
+        }
+        prev_block_root = Some(block_root);
+
+        let Some(blobs) = db.get_blobs(&block_root).map_err(|e| format!("{e:?}"))? else {
+            continue;
+        };
+        // The first root may belong to a block prior to `start_slot` if that slot was skipped.
+        if blobs.first().map_or(true, |blob| blob.slot() < start_slot) {
+            continue;
+        }
+        write_record(&mut writer, BLOB_SIDECARS, &blobs.as_ssz_bytes())
+            .map_err(|e| format!("{e:?}"))?;
+        num_blocks += 1;
+    }
+
+    writer.flush().map_err(|e| format!("{e:?}"))?;
+    Ok(num_blocks)
+}
+
+/// Import the blobs from all files in the input directory.
+///
+/// Blobs are only imported for blocks which are already stored, after checking that they match
+/// the block's KZG commitments and verifying their KZG proofs. The oldest blob slot of the database is lowered if the imported
+/// files fill the gap before it.
+pub fn import_blobs<E: EthSpec>(
+    import_config: ImportBlobsConfig,
+    client_config: ClientConfig,
+    runtime_context: &RuntimeContext<E>,
+    log: Logger,
+) -> Result<(), String> {
+    let kzg = Kzg::new_from_trusted_setup(client_config.trusted_setup.clone())
+        .map_err(|e| format!("Unable to load the KZG trusted setup: {e:?}"))?;
+    let db = open_db(client_config, runtime_context, log.clone())?;
+
+    let mut paths = fs::read_dir(&import_config.input_dir)
+        .map_err(|e| format!("Unable to read input directory: {e:?}"))?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Unable to read input directory: {e:?}"))?;
+    paths.retain(|path| path.extension().is_some_and(|ext| ext == FILE_EXTENSION));
+    paths.sort();
+
+    if paths.is_empty() {
+        return Err(format!(
+            "No .{FILE_EXTENSION} files found in {:?}",
+            import_config.input_dir
+        ));
+    }
+
+    let mut complete_ranges = vec![];
+    for path in paths {
+        let stats = import_file(&db, &kzg, &path)
+            .map_err(|e| format!("Unable to import blobs from {path:?}: {e}"))?;
+
+        if stats.unknown_blocks > 0 {
+            warn!(
+                log,
+                "Skipped blobs for unknown blocks";
+                "file" => ?path,
+                "count" => stats.unknown_blocks,
+            );
+        } else if let Some(range) = stats.slot_range {
+            complete_ranges.push(range);
+        }
+        info!(
+            log,
+            "Imported blobs";
+            "file" => ?path,
+            "blocks_with_blobs" => stats.imported_blocks,
+        );
+    }
+
+    // Extend the range of available blobs backwards through contiguous imported ranges.
+    let blob_info = db.get_blob_info();
+    let mut oldest_blob_slot = blob_info.oldest_blob_slot;
+    complete_ranges.sort_by_key(|(start_slot, _)| std::cmp::Reverse(*start_slot));
+    for (start_slot, end_slot) in complete_ranges {
+        if oldest_blob_slot.is_some_and(|oldest| start_slot < oldest && end_slot + 1 >= oldest) {
+            oldest_blob_slot = Some(start_slot);
+        }
+    }
+    if oldest_blob_slot != blob_info.oldest_blob_slot {
+        info!(
+            log,
+            "Updating oldest blob slot";
+            "previous" => ?blob_info.oldest_blob_slot,
+            "new" => ?oldest_blob_slot,
+        );
+        let new_blob_info = BlobInfo {
+            oldest_blob_slot,
+            ..blob_info.clone()
+        };
+        db.compare_and_set_blob_info_with_write(blob_info, new_blob_info)
+            .map_err(|e| format!("Unable to update blob info: {e:?}"))?;
+    }
+
+    Ok(())
+}
+
+struct ImportStats {
+    slot_range: Option<(Slot, Slot)>,
+    imported_blocks: usize,
+    unknown_blocks: usize,
+}
+
+fn import_file<E: EthSpec>(
+    db: &HotColdDB<E, LevelDB<E>, LevelDB<E>>,
+    kzg: &Kzg,
+    path: &Path,
+) -> Result<ImportStats, String> {
+    let file = File::open(path).map_err(|e| format!("{e:?}"))?;
+    let mut reader = BufReader::new(file);
+
+    let mut stats = ImportStats {
+        slot_range: None,
+        imported_blocks: 0,
+        unknown_blocks: 0,
+    };
+    let mut ops = vec![];
+
+    while let Some((record_type, data)) =
+        read_record(&mut reader, max_record_len::<E>()).map_err(|e| format!("{e:?}"))?
+    {
+        match record_type {
+            VERSION => (),
+            SLOT_RANGE => {
+                stats.slot_range =
+                    Some(decode_slot_range(&data).ok_or("Invalid slot range record")?);
+            }
+            BLOB_SIDECARS => {
+                let blobs = BlobSidecarList::<E>::from_ssz_bytes(&data)
+                    .map_err(|e| format!("Invalid blob sidecars: {e:?}"))?;
+                let block_root = blobs
+                    .first()
+                    .ok_or("Empty blob sidecars record")?
+                    .block_root();
+
+                let Some(block) = db
+                    .get_blinded_block(&block_root)
+                    .map_err(|e| format!("{e:?}"))?
+                else {
+                    stats.unknown_blocks += 1;
+                    continue;
+                };
+                let commitments = block
+                    .message()
+                    .body()
+                    .blob_kzg_commitments()
+                    .map_err(|_| format!("Blobs for pre-Deneb block {block_root:?}"))?;
+                let matches_block = blobs.len() == commitments.len()
+                    && blobs.iter().all(|blob| {
+                        blob.block_root() == block_root
+                            && commitments.get(blob.index as usize) == Some(&blob.kzg_commitment)
+                            && blob.verify_blob_sidecar_inclusion_proof()
+                    });
+                if !matches_block {
+                    return Err(format!("Blobs do not match block {block_root:?}"));
+                }
+                verify_kzg_for_blob_list(blobs.iter(), kzg)
+                    .map_err(|e| format!("Invalid KZG proofs for block {block_root:?}: {e:?}"))?;
+
+                db.blobs_as_kv_store_ops(&block_root, blobs, &mut ops);
+                stats.imported_blocks += 1;
+            }
+            other => return Err(format!("Unknown record type {other:?}")),
+        }
+    }
+
+    db.blobs_db
+        .do_atomically(ops)
+        .map_err(|e| format!("{e:?}"))?;
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use beacon_chain::test_utils::{
+        get_kzg, test_spec, AttestationStrategy, BeaconChainHarness, BlockStrategy, DiskHarnessType,
+    };
+    use logging::test_logger;
+    use std::collections::HashMap;
+    use store::{StoreConfig, StoreOp};
+    use tempfile::tempdir;
+    use types::{Hash256, MinimalEthSpec};
+
+    type E = MinimalEthSpec;
+
+    const MAX_LEN: usize = 1024;
+
+    #[test]
+    fn record_round_trip() {
+        let mut bytes = vec![];
+        write_record(&mut bytes, VERSION, &[]).unwrap();
+        write_record(
+            &mut bytes,
+            SLOT_RANGE,
+            &encode_slot_range(Slot::new(8192), Slot::new(16383)),
+        )
+        .unwrap();
+        write_record(&mut bytes, BLOB_SIDECARS, &[1, 2, 3]).unwrap();
+
+        let mut reader = bytes.as_slice();
+        assert_eq!(
+            read_record(&mut reader, MAX_LEN).unwrap(),
+            Some((VERSION, vec![]))
+        );
+        let (record_type, data) = read_record(&mut reader, MAX_LEN).unwrap().unwrap();
+        assert_eq!(record_type, SLOT_RANGE);
+        assert_eq!(
+            decode_slot_range(&data),
+            Some((Slot::new(8192), Slot::new(16383)))
+        );
+        assert_eq!(
+            read_record(&mut reader, MAX_LEN).unwrap(),
+            Some((BLOB_SIDECARS, vec![1, 2, 3]))
+        );
+        assert_eq!(read_record(&mut reader, MAX_LEN).unwrap(), None);
+    }
+
+    #[test]
+    fn invalid_slot_range() {
+        assert_eq!(decode_slot_range(&[0; 15]), None);
+        assert_eq!(decode_slot_range(&[0; 17]), None);
+    }
+
+    /// Blobs exported from a database and deleted can be imported again, but only if they're
+    /// valid for their blocks.
+    #[tokio::test]
+    async fn export_import_round_trip() {
+        let spec = test_spec::<E>();
+        if spec.deneb_fork_epoch.is_none() {
+            return;
+        }
+        let kzg = get_kzg(&spec);
+        let db_path = tempdir().unwrap();
+        let db = HotColdDB::open(
+            &db_path.path().join("chain_db"),
+            &db_path.path().join("freezer_db"),
+            &db_path.path().join("blobs_db"),
+            |_, _, _| Ok(()),
+            StoreConfig::default(),
+            spec.into(),
+            test_logger(),
+        )
+        .unwrap();
+
+        let harness = BeaconChainHarness::<DiskHarnessType<E>>::builder(E::default())
+            .spec(db.get_chain_spec().clone())
+            .deterministic_keypairs(24)
+            .logger(db.logger().clone())
+            .fresh_disk_store(db.clone())
+            .mock_execution_layer()
+            .build();
+        harness.advance_slot();
+        harness
+            .extend_chain(
+                E::slots_per_epoch() as usize * 6,
+                BlockStrategy::OnCanonicalHead,
+                AttestationStrategy::AllValidators,
+            )
+            .await;
+
+        let start_slot = db.get_blob_info().oldest_blob_slot.unwrap();
+        let end_slot = db.get_split_slot() - 1;
+        if start_slot > end_slot {
+            // Deneb is scheduled after the finalized part of the chain.
+            return;
+        }
+        let path = db_path.path().join("blobs.e2s");
+        let num_blocks = export_era(&db, &path, start_slot, end_slot).unwrap();
+        assert!(num_blocks > 0);
+
+        // Delete the exported blobs.
+        let mut exported = HashMap::<Hash256, _>::new();
+        let block_roots = db
+            .forwards_block_roots_iterator_until(start_slot, end_slot, || {
+                Err(Error::NoContinuationData)
+            })
+            .unwrap();
+        for res in block_roots {
+            let (block_root, _) = res.unwrap();
+            if let Some(blobs) = db.get_blobs(&block_root).unwrap() {
+                exported.insert(block_root, blobs);
+            }
+        }
+        assert_eq!(exported.len(), num_blocks);
+        db.do_atomically_with_block_and_blobs_cache(
+            exported.keys().copied().map(StoreOp::DeleteBlobs).collect(),
+        )
+        .unwrap();
+        for block_root in exported.keys() {
+            assert_eq!(db.get_blobs(block_root).unwrap(), None);
+        }
+
+        // Blobs which don't match their KZG proofs are rejected.
+        let mut corrupted = fs::read(&path).unwrap();
+        let blob_offset = corrupted.len() - BlobSidecar::<E>::max_size() + 64;
+        corrupted[blob_offset] ^= 1;
+        let corrupted_path = db_path.path().join("corrupted.e2s");
+        fs::write(&corrupted_path, corrupted).unwrap();
+        assert!(import_file(&db, &kzg, &corrupted_path)
+            .unwrap_err()
+            .contains("Invalid KZG proofs"));
+
+        let stats = import_file(&db, &kzg, &path).unwrap();
+        assert_eq!(stats.slot_range, Some((start_slot, end_slot)));
+        assert_eq!(stats.imported_blocks, num_blocks);
+        assert_eq!(stats.unknown_blocks, 0);
+        for (block_root, blobs) in exported {
+            assert_eq!(db.get_blobs(&block_root).unwrap(), Some(blobs));
+        }
+    }
+}
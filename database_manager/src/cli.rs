@@ -81,6 +81,10 @@ pub enum DatabaseManagerSubcommand {
     PruneBlobs(PruneBlobs),
     PruneStates(PruneStates),
     Compact(Compact),
+    ExportBlobs(ExportBlobs),
+    ImportBlobs(ImportBlobs),
+    ImportEra(ImportEra),
+    RepairDepositCache(RepairDepositCache),
 }
 
 #[derive(Parser, Clone, Deserialize, Serialize, Debug)]
@@ -229,3 +233,114 @@ pub struct Compact {
     )]
     pub output_dir: Option<PathBuf>,
 }
+
+#[derive(Parser, Clone, Deserialize, Serialize, Debug)]
+#[clap(
+    about = "Export finalized blob sidecars to era-style files.",
+    alias = "export_blobs"
+)]
+pub struct ExportBlobs {
+    #[clap(
+        long,
+        value_name = "EPOCH",
+        help = "The first epoch to export blobs from. Defaults to the oldest epoch for which \
+                blobs are stored.",
+        display_order = 0
+    )]
+    pub start_epoch: Option<u64>,
+
+    #[clap(
+        long,
+        value_name = "EPOCH",
+        help = "The last epoch to export blobs from (inclusive). Defaults to the last \
+                finalized epoch stored in the freezer database.",
+        display_order = 0
+    )]
+    pub end_epoch: Option<u64>,
+
+    #[clap(
+        long,
+        value_name = "DIR",
+        help = "Directory to write the blob files to. It is created if it does not exist.",
+        display_order = 0
+    )]
+    pub output: PathBuf,
+}
+
+#[derive(Parser, Clone, Deserialize, Serialize, Debug)]
+#[clap(
+    about = "Import blob sidecars from files written by export-blobs.",
+    alias = "import_blobs"
+)]
+pub struct ImportBlobs {
+    #[clap(
+        long,
+        value_name = "DIR",
+        help = "Directory containing the blob files to import.",
+        display_order = 0
+    )]
+    pub input: PathBuf,
+}
+
+#[derive(Parser, Clone, Deserialize, Serialize, Debug)]
+#[clap(
+    about = "Import historic blocks from era files, as an alternative to backfill sync.",
+    alias = "import_era"
+)]
+pub struct ImportEra {
+    #[clap(
+        value_name = "DIR",
+        help = "Directory containing the era files to import. Only eras older than the oldest \
+                block in the database are read.",
+        display_order = 0
+    )]
+    pub dir: PathBuf,
+}
+
+#[derive(Parser, Clone, Deserialize, Serialize, Debug)]
+#[clap(
+    about = "Validate the eth1 deposit cache and repair it if it is corrupt.",
+    alias = "repair_deposit_cache"
+)]
+pub struct RepairDepositCache {
+    #[clap(
+        long,
+        value_name = "FILE",
+        help = "Rebuild the deposit cache from the SSZ-encoded deposit tree snapshot in this \
+                file, as served by the /eth/v1/beacon/deposit_snapshot API. Without this flag a \
+                corrupt cache is rebuilt from its own finalized snapshot if it is valid, and \
+                from the deposit logs of the execution node otherwise.",
+        display_order = 0
+    )]
+    pub snapshot: Option<PathBuf>,
+
+    #[clap(
+        long,
+        value_name = "EXECUTION-ENDPOINT",
+        requires = "execution_jwt",
+        help = "Server endpoint for an execution layer JWT-authenticated HTTP JSON-RPC \
+                connection. Used to download every deposit log when a corrupt cache cannot be \
+                rebuilt from a snapshot.",
+        display_order = 0
+    )]
+    pub execution_endpoint: Option<String>,
+
+    #[clap(
+        long,
+        value_name = "EXECUTION-JWT",
+        requires = "execution_endpoint",
+        help = "File path which contains the hex-encoded JWT secret for the execution endpoint \
+                provided in the --execution-endpoint flag.",
+        display_order = 0
+    )]
+    pub execution_jwt: Option<PathBuf>,
+
+    #[clap(
+        long,
+        help = "Commit to repairing the deposit cache. Without this flag the command will \
+                only report the changes it would make.",
+        help_heading = FLAG_HEADER,
+        display_order = 0
+    )]
+    pub confirm: bool,
+}
@@ -0,0 +1,484 @@
+//! Validation and repair of the eth1 deposit cache persisted by the beacon node.
+//!
+//! A corrupt deposit cache prevents the beacon node from starting, and previously could only be
+//! resolved with `--eth1-purge-cache`, which downloads every deposit log again when the beacon node
+//! starts. Instead the cache is rebuilt by this command, either from a deposit tree snapshot, after
+//! which only the deposits following the snapshot are downloaded from the execution node, or from
+//! the deposit logs of the execution node. The snapshot is either the finalized snapshot held
+//! within the cache itself, or one provided in a file.
+use crate::cli::RepairDepositCache;
+use beacon_chain::{eth1_chain::SszEth1, ETH1_CACHE_DB_KEY};
+use beacon_node::ClientConfig;
+use environment::RuntimeContext;
+use eth1::{Config as Eth1Config, Eth1Endpoint, Service as Eth1Service};
+use sensitive_url::SensitiveUrl;
+use slog::{info, warn, Logger};
+use ssz::Decode;
+use std::fs;
+use std::path::{Path, PathBuf};
+use store::{HotColdDB, ItemStore, LevelDB};
+use types::{DepositTreeSnapshot, EthSpec};
+
+pub struct RepairDepositCacheConfig {
+    snapshot: Option<PathBuf>,
+    execution_endpoint: Option<Eth1Endpoint>,
+    confirm: bool,
+}
+
+pub fn parse_repair_deposit_cache_config(
+    repair_config: &RepairDepositCache,
+) -> Result<RepairDepositCacheConfig, String> {
+    let execution_endpoint = match (
+        &repair_config.execution_endpoint,
+        &repair_config.execution_jwt,
+    ) {
+        (Some(endpoint), Some(jwt_path)) => Some(Eth1Endpoint::Auth {
+            endpoint: SensitiveUrl::parse(endpoint)
+                .map_err(|e| format!("Invalid --execution-endpoint: {e:?}"))?,
+            jwt_path: jwt_path.clone(),
+            jwt_id: None,
+            jwt_version: None,
+        }),
+        (None, None) => None,
+        _ => {
+            return Err(
+                "--execution-endpoint and --execution-jwt must be provided together".to_string(),
+            )
+        }
+    };
+
+    Ok(RepairDepositCacheConfig {
+        snapshot: repair_config.snapshot.clone(),
+        execution_endpoint,
+        confirm: repair_config.confirm,
+    })
+}
+
+/// The change to make to the persisted eth1 cache.
+enum Repair {
+    /// Replace the cache with one initialized from a deposit tree snapshot.
+    Rebuild(SszEth1),
+    /// Replace the cache with one built from every deposit log held by the execution node.
+    Download,
+}
+/// Read a deposit tree snapshot in the SSZ encoding served by the
+/// `/eth/v1/beacon/deposit_snapshot` API.
+fn load_snapshot(path: &Path) -> Result<DepositTreeSnapshot, String> {
+    let bytes = fs::read(path)
+        .map_err(|e| format!("Unable to read deposit snapshot {}: {e:?}", path.display()))?;
+    let snapshot = DepositTreeSnapshot::from_ssz_bytes(&bytes).map_err(|e| {
+        format!(
+            "Unable to decode deposit snapshot {}: {e:?}",
+            path.display()
+        )
+    })?;
+    if !snapshot.is_valid() {
+        return Err(format!(
+            "Deposit snapshot {} has an incorrect deposit root",
+            path.display()
+        ));
+    }
+    Ok(snapshot)
+}
+
+pub fn repair_deposit_cache<E: EthSpec>(
+    repair_config: RepairDepositCacheConfig,
+    client_config: ClientConfig,
+    runtime_context: &RuntimeContext<E>,
+    log: Logger,
+) -> Result<(), String> {
+    let deposit_contract_deploy_block = runtime_context
+        .eth2_network_config
+        .as_ref()
+        .ok_or("Missing network config")?
+        .deposit_contract_deploy_block;
+
+    // Read the snapshot before opening the database, so that an invalid file is reported early.
+    let provided_snapshot = repair_config
+        .snapshot
+        .as_ref()
+        .map(|path| load_snapshot(path))
+        .transpose()?;
+
+    let hot_path = client_config.get_db_path();
+    let cold_path = client_config.get_freezer_db_path();
+    let blobs_path = client_config.get_blobs_db_path();
+
+    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+        &hot_path,
+        &cold_path,
+        &blobs_path,
+        |_, _, _| Ok(()),
+        client_config.store,
+        runtime_context.eth2_config.spec.clone(),
+        log.clone(),
+    )
+    .map_err(|e| format!("Unable to open database: {e:?}"))?;
+
+    let execution_endpoint = repair_config.execution_endpoint;
+    repair_deposit_cache_in_db(
+        &db,
+        provided_snapshot,
+        deposit_contract_deploy_block,
+        repair_config.confirm,
+        || {
+            let execution_endpoint = execution_endpoint.ok_or(
+                "Rebuilding the deposit cache without a snapshot requires --execution-endpoint \
+                 and --execution-jwt",
+            )?;
+            download_deposit_cache(
+                execution_endpoint,
+                deposit_contract_deploy_block,
+                runtime_context,
+                &log,
+            )
+        },
+        &log,
+    )
+}
+
+/// Build a deposit cache from every deposit log held by the execution node at `endpoint`.
+fn download_deposit_cache<E: EthSpec>(
+    endpoint: Eth1Endpoint,
+    deposit_contract_deploy_block: u64,
+    runtime_context: &RuntimeContext<E>,
+    log: &Logger,
+) -> Result<SszEth1, String> {
+    let spec = runtime_context.eth2_config.spec.clone();
+    let eth1_config = Eth1Config {
+        endpoint,
+        deposit_contract_address: format!("{:?}", spec.deposit_contract_address),
+        chain_id: spec.deposit_chain_id.into(),
+        deposit_contract_deploy_block,
+        lowest_cached_block_number: deposit_contract_deploy_block,
+        follow_distance: spec.eth1_follow_distance,
+        // Download every deposit log up to the follow distance in a single update.
+        max_log_requests_per_update: None,
+        ..Eth1Config::default()
+    };
+    let service = Eth1Service::new(eth1_config, log.clone(), spec)?;
+
+    info!(
+        log,
+        "Downloading deposit logs from the execution node";
+        "from_block" => deposit_contract_deploy_block,
+    );
+    let outcome = runtime_context
+        .executor
+        .block_on_dangerous(service.update_deposit_cache(None), "repair_deposit_cache")
+        .ok_or("Shutting down")?
+        .map_err(|e| format!("Unable to download deposit logs: {e:?}"))?;
+    info!(
+        log,
+        "Downloaded deposit logs";
+        "deposit_count" => outcome.logs_imported,
+        "last_processed_execution_block" => service.deposits().read().last_processed_block,
+    );
+
+    Ok(SszEth1 {
+        use_dummy_backend: false,
+        backend_bytes: service.as_bytes(),
+    })
+}
+
+/// Validate the deposit cache persisted in `db`, and repair it if it is corrupt or a snapshot is
+/// provided.
+///
+/// A corrupt cache without a valid finalized snapshot is replaced by the one returned by
+/// `download`, which is only called once `confirm` is set.
+fn repair_deposit_cache_in_db<E, Hot, Cold>(
+    db: &HotColdDB<E, Hot, Cold>,
+    provided_snapshot: Option<DepositTreeSnapshot>,
+    deposit_contract_deploy_block: u64,
+    confirm: bool,
+    download: impl FnOnce() -> Result<SszEth1, String>,
+    log: &Logger,
+) -> Result<(), String>
+where
+    E: EthSpec,
+    Hot: ItemStore<E>,
+    Cold: ItemStore<E>,
+{
+    let persisted = db.get_item::<SszEth1>(&ETH1_CACHE_DB_KEY);
+    let eth1_cache = match &persisted {
+        Ok(Some(ssz_eth1)) => Some(ssz_eth1.eth1_cache()),
+        Ok(None) => None,
+        Err(e) => Some(Err(format!("Unable to decode eth1 cache: {e:?}"))),
+    };
+
+    let repair = match (provided_snapshot, eth1_cache) {
+        (Some(snapshot), _) => {
+            info!(
+                log,
+                "Rebuilding deposit cache from the provided snapshot";
+                "deposit_count" => snapshot.deposit_count,
+                "execution_block_height" => snapshot.execution_block_height,
+            );
+            Repair::Rebuild(SszEth1::from_deposit_snapshot(
+                deposit_contract_deploy_block,
+                &snapshot,
+            )?)
+        }
+        (None, None) => {
+            info!(log, "No deposit cache in the database");
+            return Ok(());
+        }
+        (None, Some(Ok(cache))) => match cache.validate() {
+            Ok(()) => {
+                info!(
+                    log,
+                    "Deposit cache is valid";
+                    "finalized_deposit_count" => cache.deposit_cache.finalized_deposit_count,
+                    "deposit_count" => cache.deposit_cache.finalized_deposit_count
+                        + cache.deposit_cache.logs.len() as u64,
+                );
+                return Ok(());
+            }
+            Err(e) => {
+                warn!(log, "Deposit cache is corrupt"; "error" => e);
+                match cache
+                    .deposit_cache
+                    .deposit_tree_snapshot
+                    .filter(DepositTreeSnapshot::is_valid)
+                {
+                    Some(snapshot) => {
+                        info!(
+                            log,
+                            "Rebuilding deposit cache from its finalized snapshot";
+                            "deposit_count" => snapshot.deposit_count,
+                            "execution_block_height" => snapshot.execution_block_height,
+                        );
+                        Repair::Rebuild(SszEth1::from_deposit_snapshot(
+                            deposit_contract_deploy_block,
+                            &snapshot,
+                        )?)
+                    }
+                    None => {
+                        warn!(
+                            log,
+                            "Deposit cache has no valid finalized snapshot";
+                            "info" => "provide one with --snapshot to avoid downloading all \
+                                       deposit logs again"
+                        );
+                        Repair::Download
+                    }
+                }
+            }
+        },
+        (None, Some(Err(e))) => {
+            warn!(
+                log,
+                "Deposit cache cannot be decoded";
+                "error" => e,
+                "info" => "provide a snapshot with --snapshot to avoid downloading all deposit \
+                           logs again"
+            );
+            Repair::Download
+        }
+    };
+
+    if !confirm {
+        match repair {
+            Repair::Rebuild(_) => info!(
+                log,
+                "Ready to rebuild deposit cache";
+                "info" => "deposits after the snapshot will be downloaded from the execution node"
+            ),
+            Repair::Download => info!(
+                log,
+                "Ready to rebuild deposit cache";
+                "info" => "all deposit logs will be downloaded from the execution node"
+            ),
+        }
+        warn!(
+            log,
+            "Re-run this command with --confirm to commit to repairing the deposit cache"
+        );
+        return Err("Error: confirmation flag required".into());
+    }
+
+    let ssz_eth1 = match repair {
+        Repair::Rebuild(ssz_eth1) => ssz_eth1,
+        Repair::Download => download()?,
+    };
+    db.put_item(&ETH1_CACHE_DB_KEY, &ssz_eth1)
+        .map_err(|e| format!("Unable to write deposit cache: {e:?}"))?;
+    info!(log, "Deposit cache rebuilt");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use slog::{o, Discard};
+    use ssz::Encode;
+    use std::cell::Cell;
+    use std::sync::Arc;
+    use store::{MemoryStore, StoreConfig};
+    use types::{ChainSpec, Hash256, MinimalEthSpec};
+
+    type E = MinimalEthSpec;
+    type TestDB = HotColdDB<E, MemoryStore<E>, MemoryStore<E>>;
+
+    const DEPLOY_BLOCK: u64 = 16;
+
+    fn logger() -> Logger {
+        Logger::root(Discard, o!())
+    }
+
+    fn get_db() -> TestDB {
+        HotColdDB::open_ephemeral(
+            StoreConfig::default(),
+            Arc::new(ChainSpec::minimal()),
+            logger(),
+        )
+        .unwrap()
+    }
+
+    /// A valid snapshot of 8 finalized deposits.
+    fn snapshot(execution_block_height: u64) -> DepositTreeSnapshot {
+        let mut snapshot = DepositTreeSnapshot {
+            finalized: vec![Hash256::repeat_byte(1)],
+            deposit_count: 8,
+            execution_block_height,
+            ..DepositTreeSnapshot::default()
+        };
+        snapshot.deposit_root = snapshot.calculate_root().unwrap();
+        assert!(snapshot.is_valid());
+        snapshot
+    }
+
+    fn cache_from_snapshot(execution_block_height: u64) -> SszEth1 {
+        SszEth1::from_deposit_snapshot(DEPLOY_BLOCK, &snapshot(execution_block_height)).unwrap()
+    }
+
+    /// Corrupt `ssz_eth1` by adding a leaf without a log, optionally dropping its snapshot too.
+    fn corrupt(ssz_eth1: &SszEth1, drop_snapshot: bool) -> SszEth1 {
+        let mut cache = ssz_eth1.eth1_cache().unwrap();
+        cache.deposit_cache.leaves.push(Hash256::repeat_byte(2));
+        if drop_snapshot {
+            cache.deposit_cache.deposit_tree_snapshot = None;
+        }
+        SszEth1 {
+            use_dummy_backend: false,
+            backend_bytes: cache.as_ssz_bytes(),
+        }
+    }
+
+    fn persist(db: &TestDB, ssz_eth1: &SszEth1) {
+        db.put_item(&ETH1_CACHE_DB_KEY, ssz_eth1).unwrap();
+    }
+
+    fn persisted(db: &TestDB) -> Option<SszEth1> {
+        db.get_item::<SszEth1>(&ETH1_CACHE_DB_KEY).unwrap()
+    }
+
+    fn repair(
+        db: &TestDB,
+        provided_snapshot: Option<DepositTreeSnapshot>,
+        confirm: bool,
+        download: impl FnOnce() -> Result<SszEth1, String>,
+    ) -> Result<(), String> {
+        repair_deposit_cache_in_db(
+            db,
+            provided_snapshot,
+            DEPLOY_BLOCK,
+            confirm,
+            download,
+            &logger(),
+        )
+    }
+
+    fn no_download() -> Result<SszEth1, String> {
+        panic!("the deposit cache should not be downloaded")
+    }
+
+    #[test]
+    fn missing_cache_is_not_created() {
+        let db = get_db();
+        repair(&db, None, true, no_download).unwrap();
+        assert!(persisted(&db).is_none());
+    }
+
+    #[test]
+    fn valid_cache_is_unchanged() {
+        let db = get_db();
+        let cache = cache_from_snapshot(100);
+        persist(&db, &cache);
+
+        repair(&db, None, true, no_download).unwrap();
+        assert_eq!(persisted(&db).unwrap().backend_bytes, cache.backend_bytes);
+    }
+
+    #[test]
+    fn corrupt_cache_is_rebuilt_from_finalized_snapshot() {
+        let db = get_db();
+        let corrupt_cache = corrupt(&cache_from_snapshot(100), false);
+        assert!(corrupt_cache.eth1_cache().unwrap().validate().is_err());
+        persist(&db, &corrupt_cache);
+
+        // Nothing is written without confirmation.
+        assert!(repair(&db, None, false, no_download).is_err());
+        assert_eq!(
+            persisted(&db).unwrap().backend_bytes,
+            corrupt_cache.backend_bytes
+        );
+
+        repair(&db, None, true, no_download).unwrap();
+        let repaired = persisted(&db).unwrap().eth1_cache().unwrap();
+        assert_eq!(repaired.validate(), Ok(()));
+        assert_eq!(
+            repaired.deposit_cache.deposit_tree_snapshot,
+            Some(snapshot(100))
+        );
+    }
+
+    #[test]
+    fn corrupt_cache_without_snapshot_is_downloaded() {
+        let db = get_db();
+        let corrupt_cache = corrupt(&cache_from_snapshot(100), true);
+        persist(&db, &corrupt_cache);
+
+        // Nothing is downloaded or written without confirmation.
+        assert!(repair(&db, None, false, no_download).is_err());
+        assert_eq!(
+            persisted(&db).unwrap().backend_bytes,
+            corrupt_cache.backend_bytes
+        );
+
+        // A failed download leaves the cache in place.
+        assert!(repair(&db, None, true, || Err("offline".into())).is_err());
+        assert_eq!(
+            persisted(&db).unwrap().backend_bytes,
+            corrupt_cache.backend_bytes
+        );
+
+        let downloaded = cache_from_snapshot(200);
+        let downloads = Cell::new(0);
+        repair(&db, None, true, || {
+            downloads.set(downloads.get() + 1);
+            Ok(downloaded.clone())
+        })
+        .unwrap();
+        assert_eq!(downloads.get(), 1);
+        assert_eq!(
+            persisted(&db).unwrap().backend_bytes,
+            downloaded.backend_bytes
+        );
+    }
+
+    #[test]
+    fn provided_snapshot_replaces_cache() {
+        let db = get_db();
+        persist(&db, &corrupt(&cache_from_snapshot(100), true));
+
+        repair(&db, Some(snapshot(300)), true, no_download).unwrap();
+        let repaired = persisted(&db).unwrap().eth1_cache().unwrap();
+        assert_eq!(repaired.validate(), Ok(()));
+        assert_eq!(
+            repaired.deposit_cache.deposit_tree_snapshot,
+            Some(snapshot(300))
+        );
+    }
+}
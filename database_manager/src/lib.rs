@@ -1,4 +1,6 @@
+mod blobs;
 pub mod cli;
+mod deposit_cache;
 use crate::cli::DatabaseManager;
 use crate::cli::Migrate;
 use crate::cli::PruneStates;
@@ -9,7 +11,7 @@ use beacon_chain::{
 use beacon_node::{get_data_dir, ClientConfig};
 use clap::ArgMatches;
 use clap::ValueEnum;
-use cli::{Compact, Inspect};
+use cli::{Compact, ImportEra, Inspect};
 use environment::{Environment, RuntimeContext};
 use serde::{Deserialize, Serialize};
 use slog::{info, warn, Logger};
@@ -384,6 +386,38 @@ pub fn prune_blobs<E: EthSpec>(
     db.try_prune_most_blobs(true)
 }
 
+pub fn import_era<E: EthSpec>(
+    import_config: &ImportEra,
+    client_config: ClientConfig,
+    runtime_context: &RuntimeContext<E>,
+    log: Logger,
+) -> Result<(), Error> {
+    let spec = &runtime_context.eth2_config.spec;
+    let hot_path = client_config.get_db_path();
+    let cold_path = client_config.get_freezer_db_path();
+    let blobs_path = client_config.get_blobs_db_path();
+
+    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+        &hot_path,
+        &cold_path,
+        &blobs_path,
+        |_, _, _| Ok(()),
+        client_config.store,
+        spec.clone(),
+        log.clone(),
+    )?;
+
+    let num_imported = store::era::import_era_dir(&db, &import_config.dir, &log)?;
+    let anchor_info = db.get_anchor_info();
+    info!(
+        log,
+        "Era import complete";
+        "blocks_imported" => num_imported,
+        "oldest_block_slot" => anchor_info.oldest_block_slot,
+    );
+    Ok(())
+}
+
 pub struct PruneStatesConfig {
     confirm: bool,
 }
@@ -522,5 +556,20 @@ pub fn run<E: EthSpec>(
             let compact_config = parse_compact_config(compact_config)?;
             compact_db::<E>(compact_config, client_config, log).map_err(format_err)
         }
+        cli::DatabaseManagerSubcommand::ExportBlobs(export_config) => {
+            let export_config = blobs::parse_export_blobs_config(export_config)?;
+            blobs::export_blobs(export_config, client_config, &context, log)
+        }
+        cli::DatabaseManagerSubcommand::ImportBlobs(import_config) => {
+            let import_config = blobs::parse_import_blobs_config(import_config)?;
+            blobs::import_blobs(import_config, client_config, &context, log)
+        }
+        cli::DatabaseManagerSubcommand::ImportEra(import_config) => {
+            import_era(import_config, client_config, &context, log).map_err(format_err)
+        }
+        cli::DatabaseManagerSubcommand::RepairDepositCache(repair_config) => {
+            let repair_config = deposit_cache::parse_repair_deposit_cache_config(repair_config)?;
+            deposit_cache::repair_deposit_cache(repair_config, client_config, &context, log)
+        }
     }
 }
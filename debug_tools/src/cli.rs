@@ -0,0 +1,94 @@
+pub use clap::{Arg, ArgAction, Args, Command, FromArgMatches, Parser};
+use clap_utils::get_color_style;
+use clap_utils::FLAG_HEADER;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use store::hdiff::HierarchyConfig;
+
+#[derive(Parser, Clone, Deserialize, Serialize, Debug)]
+#[clap(
+    name = "debug",
+    about = "Tools for investigating the performance of a beacon node using its database.",
+    styles = get_color_style(),
+    next_line_help = true,
+    term_width = 80,
+    disable_help_flag = true,
+    disable_help_subcommand = true,
+    display_order = 0,
+)]
+pub struct DebugTools {
+    #[clap(
+        long,
+        global = true,
+        value_name = "N0,N1,N2,...",
+        help = "The frequency of full state snapshots and hierarchical diffs in the freezer DB, \
+                as provided to the beacon node.",
+        default_value_t = HierarchyConfig::default(),
+        display_order = 0
+    )]
+    pub hierarchy_exponents: HierarchyConfig,
+
+    #[clap(
+        long,
+        global = true,
+        value_name = "DIR",
+        help = "Data directory for the freezer database.",
+        display_order = 0
+    )]
+    pub freezer_dir: Option<PathBuf>,
+
+    #[clap(
+        long,
+        global = true,
+        value_name = "DIR",
+        help = "Data directory for the blobs database.",
+        display_order = 0
+    )]
+    pub blobs_dir: Option<PathBuf>,
+
+    #[clap(
+        long,
+        global = true,
+        help = "Prints help information",
+        action = clap::ArgAction::HelpLong,
+        display_order = 0,
+        help_heading = FLAG_HEADER
+    )]
+    help: Option<bool>,
+
+    #[clap(subcommand)]
+    pub subcommand: DebugToolsSubcommand,
+}
+
+#[derive(Parser, Clone, Deserialize, Serialize, Debug)]
+#[clap(rename_all = "kebab-case")]
+pub enum DebugToolsSubcommand {
+    ReplayStateTransitions(ReplayStateTransitions),
+}
+
+#[derive(Parser, Clone, Deserialize, Serialize, Debug)]
+#[clap(
+    about = "Re-execute the state transitions of the finalized blocks between two slots from the \
+             database, reporting the time spent verifying signatures, running the state \
+             transition and verifying KZG proofs for each block. Blocks are not imported, so \
+             execution payloads, fork choice and database writes are not timed. The beacon node \
+             must be stopped first."
+)]
+pub struct ReplayStateTransitions {
+    #[clap(
+        long,
+        value_name = "SLOT",
+        help = "The slot of the first block to replay. The state prior to this block must be \
+                available in the database.",
+        display_order = 0
+    )]
+    pub from_slot: u64,
+
+    #[clap(
+        long,
+        value_name = "SLOT",
+        help = "The slot of the last block to replay, which must be finalized.",
+        display_order = 0
+    )]
+    pub to_slot: u64,
+}
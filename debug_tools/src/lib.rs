@@ -0,0 +1,109 @@
+//! Tools for investigating the performance of a beacon node using the data in its database.
+pub mod cli;
+pub mod replay;
+
+use crate::cli::{DebugTools, DebugToolsSubcommand, ReplayStateTransitions};
+use crate::replay::{replay_state_transitions, BlockTimes};
+use beacon_chain::Kzg;
+use beacon_node::{get_data_dir, ClientConfig};
+use clap::ArgMatches;
+use environment::Environment;
+use std::time::Duration;
+use store::{HotColdDB, LevelDB};
+use types::{EthSpec, Slot};
+
+/// Run the debug tools, returning an error string if the operation did not succeed.
+pub fn run<E: EthSpec>(
+    cli_args: &ArgMatches,
+    config: &DebugTools,
+    env: Environment<E>,
+) -> Result<(), String> {
+    let mut client_config = ClientConfig::default();
+    client_config.set_data_dir(get_data_dir(cli_args));
+    client_config
+        .freezer_db_path
+        .clone_from(&config.freezer_dir);
+    client_config.blobs_db_path.clone_from(&config.blobs_dir);
+    client_config.store.hierarchy_config = config.hierarchy_exponents.clone();
+
+    match &config.subcommand {
+        DebugToolsSubcommand::ReplayStateTransitions(replay_config) => {
+            run_replay_state_transitions(replay_config, client_config, env)
+        }
+    }
+}
+
+fn run_replay_state_transitions<E: EthSpec>(
+    replay_config: &ReplayStateTransitions,
+    client_config: ClientConfig,
+    env: Environment<E>,
+) -> Result<(), String> {
+    let context = env.core_context();
+    let log = context.log().clone();
+    let spec = context.eth2_config.spec.clone();
+
+    let kzg_err_msg = |e| format!("Failed to load trusted setup: {:?}", e);
+    let trusted_setup = client_config.trusted_setup.clone();
+    let kzg = if spec.is_peer_das_scheduled() {
+        Kzg::new_from_trusted_setup_das_enabled(trusted_setup).map_err(kzg_err_msg)?
+    } else {
+        Kzg::new_from_trusted_setup(trusted_setup).map_err(kzg_err_msg)?
+    };
+
+    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+        &client_config.get_db_path(),
+        &client_config.get_freezer_db_path(),
+        &client_config.get_blobs_db_path(),
+        |_, _, _| Ok(()),
+        client_config.store,
+        spec,
+        log.clone(),
+    )
+    .map_err(|e| format!("Unable to open database: {:?}", e))?;
+
+    let times = replay_state_transitions(
+        &db,
+        &kzg,
+        Slot::new(replay_config.from_slot),
+        Slot::new(replay_config.to_slot),
+        &log,
+    )?;
+    print_report(&times);
+    Ok(())
+}
+
+fn print_report(times: &[BlockTimes]) {
+    println!(
+        "{:>10}  {:<66}  {:>8}  {:>12}  {:>19}  {:>12}",
+        "slot", "block_root", "sidecars", "signature_ms", "state_transition_ms", "kzg_ms"
+    );
+    for block in times {
+        println!(
+            "{:>10}  {:<66?}  {:>8}  {:>12.3}  {:>19.3}  {:>12.3}",
+            block.slot,
+            block.block_root,
+            block.num_sidecars,
+            millis(block.signature),
+            millis(block.state_transition),
+            millis(block.kzg),
+        );
+    }
+
+    let total = |f: fn(&BlockTimes) -> Duration| times.iter().map(f).sum::<Duration>();
+    let signature = total(|block| block.signature);
+    let state_transition = total(|block| block.state_transition);
+    let kzg = total(|block| block.kzg);
+    println!(
+        "Replayed {} blocks: signature {:.3}ms, state transition {:.3}ms, kzg {:.3}ms, \
+         total {:.3}ms",
+        times.len(),
+        millis(signature),
+        millis(state_transition),
+        millis(kzg),
+        millis(signature + state_transition + kzg),
+    );
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
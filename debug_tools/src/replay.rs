@@ -0,0 +1,436 @@
+//! Re-executes the state transitions of a range of finalized blocks from the database, timing each
+//! stage of processing.
+//!
+//! This is a state-transition-only replay, it does not use the chain segment import path. Stored
+//! blocks are blinded, so they can't be imported by the beacon chain again. Instead they are loaded
+//! and applied with the same `BlockReplayer` that the database uses to regenerate states, with hooks
+//! to verify signatures, check state roots and time each stage. The stages timed are signature
+//! verification, the state transition (including slot processing and the state root computation)
+//! and verification of the KZG proofs of the block's blobs or data columns. Execution payload
+//! verification, fork choice and database writes are not performed.
+//!
+//! Signatures are verified per block rather than in a single batch across the range so that the
+//! cost of each block can be attributed to it.
+use beacon_chain::kzg_utils::{validate_blobs, validate_data_columns};
+use beacon_chain::Kzg;
+use bls::{PublicKey, PublicKeyBytes};
+use slog::{info, Logger};
+use state_processing::{BlockReplayError, BlockReplayer, BlockSignatureVerifier, ConsensusContext};
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use store::{Error as StoreError, HotColdDB, ItemStore};
+use types::{BeaconState, EthSpec, Hash256, SignedBlindedBeaconBlock, Slot};
+
+/// The time spent on each stage of processing a single block.
+#[derive(Debug, Clone)]
+pub struct BlockTimes {
+    pub slot: Slot,
+    pub block_root: Hash256,
+    /// The number of blobs or data columns verified for the block.
+    pub num_sidecars: usize,
+    pub signature: Duration,
+    pub state_transition: Duration,
+    pub kzg: Duration,
+}
+
+/// Decompressed validator public keys, so that signature verification timings do not include
+/// the cost of decompression.
+#[derive(Default)]
+struct PubkeyCache {
+    pubkeys: Vec<PublicKey>,
+    indices: HashMap<PublicKeyBytes, usize>,
+}
+
+impl PubkeyCache {
+    /// Add the keys of any validators in `state` which are not yet in the cache.
+    fn import<E: EthSpec>(&mut self, state: &BeaconState<E>) -> Result<(), String> {
+        for validator in state.validators().iter().skip(self.pubkeys.len()) {
+            let pubkey = validator
+                .pubkey
+                .decompress()
+                .map_err(|e| format!("Invalid validator pubkey: {:?}", e))?;
+            self.indices.insert(validator.pubkey, self.pubkeys.len());
+            self.pubkeys.push(pubkey);
+        }
+        Ok(())
+    }
+
+    fn get(&self, validator_index: usize) -> Option<Cow<PublicKey>> {
+        self.pubkeys.get(validator_index).map(Cow::Borrowed)
+    }
+
+    fn get_by_bytes(&self, pubkey: &PublicKeyBytes) -> Option<Cow<PublicKey>> {
+        self.indices
+            .get(pubkey)
+            .and_then(|validator_index| self.get(*validator_index))
+    }
+}
+
+/// An error raised while replaying blocks, either by the `BlockReplayer` or by one of its hooks.
+struct ReplayError(String);
+
+impl From<BlockReplayError> for ReplayError {
+    fn from(e: BlockReplayError) -> Self {
+        Self(format!("Unable to replay block: {:?}", e))
+    }
+}
+
+/// Accumulates the times for the block currently being replayed.
+struct ReplayTimer {
+    times: Vec<BlockTimes>,
+    started: Instant,
+    signature: Duration,
+    state_transition: Duration,
+}
+
+impl ReplayTimer {
+    fn new(num_blocks: usize) -> Self {
+        Self {
+            times: Vec::with_capacity(num_blocks),
+            started: Instant::now(),
+            signature: Duration::ZERO,
+            state_transition: Duration::ZERO,
+        }
+    }
+
+    /// Attribute the time since the last call to `resume_state_transition` to the state
+    /// transition.
+    fn pause_state_transition(&mut self) {
+        self.state_transition += self.started.elapsed();
+    }
+
+    fn resume_state_transition(&mut self) {
+        self.started = Instant::now();
+    }
+
+    /// Record the times of a block and start timing the next one.
+    fn finish_block(
+        &mut self,
+        slot: Slot,
+        block_root: Hash256,
+        num_sidecars: usize,
+        kzg: Duration,
+    ) {
+        self.times.push(BlockTimes {
+            slot,
+            block_root,
+            num_sidecars,
+            signature: std::mem::take(&mut self.signature),
+            state_transition: std::mem::take(&mut self.state_transition),
+            kzg,
+        });
+        self.resume_state_transition();
+    }
+}
+
+/// Replay the state transitions of the blocks from `from_slot` to `to_slot` (inclusive), starting
+/// from the state prior to the first block.
+pub fn replay_state_transitions<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    store: &HotColdDB<E, Hot, Cold>,
+    kzg: &Kzg,
+    from_slot: Slot,
+    to_slot: Slot,
+    log: &Logger,
+) -> Result<Vec<BlockTimes>, String> {
+    let spec = store.get_chain_spec();
+    if from_slot == spec.genesis_slot {
+        return Err("Cannot replay the genesis block".into());
+    }
+    if to_slot < from_slot {
+        return Err("--to-slot must not be prior to --from-slot".into());
+    }
+    // Only the canonical block roots of finalized slots are known without a head state.
+    let split_slot = store.get_split_slot();
+    if to_slot >= split_slot {
+        return Err(format!(
+            "Only finalized blocks may be replayed, --to-slot must be prior to slot {}",
+            split_slot
+        ));
+    }
+
+    let (end_block_root, _) = store
+        .forwards_block_roots_iterator_until(to_slot, to_slot, || {
+            Err(StoreError::NoContinuationData)
+        })
+        .map_err(|e| format!("Unable to iterate block roots: {:?}", e))?
+        .next()
+        .ok_or("No block root at --to-slot")?
+        .map_err(|e| format!("Unable to load block root: {:?}", e))?;
+    let mut blocks = store
+        .load_blocks_to_replay(from_slot, to_slot, end_block_root)
+        .map_err(|e| format!("Unable to load blocks: {:?}", e))?;
+    let first_block = blocks.first().ok_or("No blocks in the range")?;
+
+    let parent_block = load_block(store, &first_block.parent_root())?;
+    let state_root = parent_block.state_root();
+    info!(
+        log,
+        "Loading pre-state";
+        "slot" => parent_block.slot(),
+        "state_root" => ?state_root,
+    );
+    let state = store
+        .get_state(&state_root, Some(parent_block.slot()))
+        .map_err(|e| format!("Unable to load pre-state: {:?}", e))?
+        .ok_or_else(|| {
+            format!(
+                "The state at slot {} is not available, historic states may need to be \
+                 reconstructed",
+                parent_block.slot()
+            )
+        })?;
+
+    info!(log, "Decompressing validator pubkeys"; "count" => state.validators().len());
+    let mut pubkey_cache = PubkeyCache::default();
+    pubkey_cache.import(&state)?;
+    let pubkey_cache = RefCell::new(pubkey_cache);
+
+    info!(log, "Replaying blocks"; "count" => blocks.len());
+    let timer = RefCell::new(ReplayTimer::new(blocks.len()));
+    // The replayer skips the parent block, using it only for the state root of the pre-state.
+    blocks.insert(0, parent_block);
+
+    BlockReplayer::<E, ReplayError>::new(state, spec)
+        // Signatures are verified by the pre-block hook, so they can be timed separately.
+        .no_signature_verification()
+        .pre_block_hook(Box::new(|state, block| {
+            let mut timer = timer.borrow_mut();
+            state
+                .build_all_caches(spec)
+                .map_err(|e| ReplayError(format!("Unable to build caches: {:?}", e)))?;
+            timer.pause_state_transition();
+
+            // Epoch processing may have added validators.
+            let mut pubkey_cache = pubkey_cache.borrow_mut();
+            pubkey_cache.import(state).map_err(ReplayError)?;
+
+            let pubkeys = &*pubkey_cache;
+            let mut ctxt = ConsensusContext::new(block.slot())
+                .set_proposer_index(block.message().proposer_index());
+            let t = Instant::now();
+            BlockSignatureVerifier::verify_entire_block(
+                state,
+                |validator_index| pubkeys.get(validator_index),
+                |pubkey| pubkeys.get_by_bytes(pubkey),
+                block,
+                &mut ctxt,
+                spec,
+            )
+            .map_err(|e| {
+                ReplayError(format!(
+                    "Invalid signature in block at slot {}: {:?}",
+                    block.slot(),
+                    e
+                ))
+            })?;
+            timer.signature = t.elapsed();
+
+            timer.resume_state_transition();
+            Ok(())
+        }))
+        .post_block_hook(Box::new(|state, block| {
+            let mut timer = timer.borrow_mut();
+            let post_state_root = state
+                .update_tree_hash_cache()
+                .map_err(|e| ReplayError(format!("Unable to compute state root: {:?}", e)))?;
+            timer.pause_state_transition();
+
+            if post_state_root != block.state_root() {
+                return Err(ReplayError(format!(
+                    "State root mismatch at slot {}, block has {:?} but computed {:?}",
+                    block.slot(),
+                    block.state_root(),
+                    post_state_root
+                )));
+            }
+
+            let block_root = block.canonical_root();
+            let (num_sidecars, kzg_time) =
+                verify_sidecars(store, kzg, block_root).map_err(ReplayError)?;
+            timer.finish_block(block.slot(), block_root, num_sidecars, kzg_time);
+            Ok(())
+        }))
+        .apply_blocks(blocks, None)
+        .map_err(|ReplayError(e)| e)?;
+
+    Ok(timer.into_inner().times)
+}
+
+fn load_block<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    store: &HotColdDB<E, Hot, Cold>,
+    block_root: &Hash256,
+) -> Result<SignedBlindedBeaconBlock<E>, String> {
+    store
+        .get_blinded_block(block_root)
+        .map_err(|e| format!("Unable to load block {:?}: {:?}", block_root, e))?
+        .ok_or_else(|| format!("Block {:?} is not in the database", block_root))
+}
+
+/// Verify the KZG proofs of the blobs or data columns stored for `block_root`, returning the
+/// number of sidecars and the time taken to verify them.
+fn verify_sidecars<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    store: &HotColdDB<E, Hot, Cold>,
+    kzg: &Kzg,
+    block_root: Hash256,
+) -> Result<(usize, Duration), String> {
+    let blobs = store
+        .get_blobs(&block_root)
+        .map_err(|e| format!("Unable to load blobs: {:?}", e))?
+        .unwrap_or_default();
+    if !blobs.is_empty() {
+        let commitments = blobs
+            .iter()
+            .map(|blob| blob.kzg_commitment)
+            .collect::<Vec<_>>();
+        let proofs = blobs.iter().map(|blob| blob.kzg_proof).collect::<Vec<_>>();
+
+        let t = Instant::now();
+        validate_blobs::<E>(
+            kzg,
+            &commitments,
+            blobs.iter().map(|blob| &blob.blob).collect(),
+            &proofs,
+        )
+        .map_err(|e| format!("Invalid blobs for block {:?}: {:?}", block_root, e))?;
+        return Ok((blobs.len(), t.elapsed()));
+    }
+
+    let data_columns = store
+        .get_data_column_keys(block_root)
+        .map_err(|e| format!("Unable to load data column indices: {:?}", e))?
+        .iter()
+        .filter_map(|index| store.get_data_column(&block_root, index).transpose())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Unable to load data columns: {:?}", e))?;
+    if data_columns.is_empty() {
+        return Ok((0, Duration::ZERO));
+    }
+
+    let t = Instant::now();
+    validate_data_columns::<E, _>(kzg, data_columns.iter())
+        .map_err(|e| format!("Invalid data columns for block {:?}: {:?}", block_root, e))?;
+    Ok((data_columns.len(), t.elapsed()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use beacon_chain::test_utils::{
+        get_kzg, AttestationStrategy, BeaconChainHarness, BlockStrategy, EphemeralHarnessType,
+    };
+    use beacon_chain::WhenSlotSkipped;
+    use std::sync::Arc;
+    use types::{ForkName, MinimalEthSpec};
+
+    type E = MinimalEthSpec;
+
+    const VALIDATOR_COUNT: usize = 32;
+    const SKIPPED_SLOT: u64 = 10;
+
+    /// Build a chain with blobs and a skipped slot, and finalize most of it.
+    async fn finalized_harness() -> BeaconChainHarness<EphemeralHarnessType<E>> {
+        let spec = ForkName::Deneb.make_genesis_spec(E::default_spec());
+        let harness = BeaconChainHarness::builder(MinimalEthSpec)
+            .spec(Arc::new(spec))
+            .deterministic_keypairs(VALIDATOR_COUNT)
+            .fresh_ephemeral_store()
+            .mock_execution_layer()
+            .build();
+
+        harness.advance_slot();
+        harness
+            .extend_chain(
+                SKIPPED_SLOT as usize - 1,
+                BlockStrategy::OnCanonicalHead,
+                AttestationStrategy::AllValidators,
+            )
+            .await;
+        // The clock is at the slot of the last block, so advance past the skipped slot.
+        harness.advance_slot();
+        harness.advance_slot();
+        harness
+            .extend_chain(
+                E::slots_per_epoch() as usize * 5,
+                BlockStrategy::OnCanonicalHead,
+                AttestationStrategy::AllValidators,
+            )
+            .await;
+        assert!(harness.chain.store.get_split_slot() > SKIPPED_SLOT);
+        harness
+    }
+
+    fn replay(
+        harness: &BeaconChainHarness<EphemeralHarnessType<E>>,
+        from_slot: u64,
+        to_slot: u64,
+    ) -> Result<Vec<BlockTimes>, String> {
+        replay_state_transitions(
+            &harness.chain.store,
+            &get_kzg(&harness.spec),
+            Slot::new(from_slot),
+            Slot::new(to_slot),
+            harness.logger(),
+        )
+    }
+
+    #[tokio::test]
+    async fn replays_each_canonical_block() {
+        let harness = finalized_harness().await;
+        let to_slot = harness.chain.store.get_split_slot().as_u64() - 1;
+
+        let times = replay(&harness, 2, to_slot).unwrap();
+
+        let expected = (2..=to_slot)
+            .filter_map(|slot| {
+                harness
+                    .chain
+                    .block_root_at_slot(Slot::new(slot), WhenSlotSkipped::None)
+                    .unwrap()
+                    .map(|block_root| (Slot::new(slot), block_root))
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(expected.len() as u64, to_slot - 2);
+        assert_eq!(
+            times
+                .iter()
+                .map(|block| (block.slot, block.block_root))
+                .collect::<Vec<_>>(),
+            expected
+        );
+
+        for block in &times {
+            let num_blobs = harness
+                .chain
+                .store
+                .get_blobs(&block.block_root)
+                .unwrap()
+                .map_or(0, |blobs| blobs.len());
+            assert_eq!(block.num_sidecars, num_blobs);
+            assert_eq!(block.kzg.is_zero(), num_blobs == 0);
+            assert!(!block.signature.is_zero());
+            assert!(!block.state_transition.is_zero());
+        }
+    }
+
+    #[tokio::test]
+    async fn replays_from_skipped_slot() {
+        let harness = finalized_harness().await;
+
+        let times = replay(&harness, SKIPPED_SLOT, SKIPPED_SLOT + 1).unwrap();
+
+        assert_eq!(times.len(), 1);
+        assert_eq!(times[0].slot, SKIPPED_SLOT + 1);
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_ranges() {
+        let harness = finalized_harness().await;
+        let split_slot = harness.chain.store.get_split_slot().as_u64();
+
+        assert!(replay(&harness, 0, 1).is_err());
+        assert!(replay(&harness, 3, 2).is_err());
+        assert!(replay(&harness, 2, split_slot).is_err());
+    }
+}
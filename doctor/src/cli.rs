@@ -0,0 +1,56 @@
+pub use clap::{Arg, ArgAction, Args, Command, FromArgMatches, Parser};
+use clap_utils::get_color_style;
+use clap_utils::FLAG_HEADER;
+use sensitive_url::SensitiveUrl;
+use serde::{Deserialize, Serialize};
+
+#[derive(Parser, Clone, Deserialize, Serialize, Debug)]
+#[clap(
+    name = "doctor",
+    about = "Diagnose the connectivity and configuration of a running beacon node.",
+    styles = get_color_style(),
+    next_line_help = true,
+    term_width = 80,
+    disable_help_flag = true,
+    disable_help_subcommand = true,
+    display_order = 0,
+)]
+pub struct Doctor {
+    #[clap(
+        long,
+        value_name = "NETWORK_ADDRESS",
+        default_value = "http://localhost:5052",
+        help = "The HTTP API address of the beacon node to diagnose.",
+        display_order = 0
+    )]
+    pub beacon_node: SensitiveUrl,
+
+    #[clap(
+        long,
+        value_name = "PERCENT",
+        default_value_t = 10,
+        help = "Report an error if less than this percentage of the disk holding the data \
+                directory is free.",
+        display_order = 0
+    )]
+    pub min_disk_free_percent: u64,
+
+    #[clap(
+        long,
+        value_name = "MILLISECONDS",
+        default_value_t = 500,
+        help = "Report an error if the clock of the beacon node differs from the local \
+                clock by more than this amount.",
+        display_order = 0
+    )]
+    pub max_clock_skew_millis: u64,
+
+    #[clap(
+        long,
+        help = "Prints help information",
+        action = clap::ArgAction::HelpLong,
+        display_order = 0,
+        help_heading = FLAG_HEADER
+    )]
+    help: Option<bool>,
+}
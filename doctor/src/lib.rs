@@ -0,0 +1,464 @@
+//! Performs live connectivity and configuration checks against a running beacon node, printing
+//! actionable findings.
+pub mod cli;
+
+use crate::cli::Doctor;
+use environment::Environment;
+use eth2::lighthouse::Diagnostics;
+use eth2::{BeaconNodeHttpClient, Timeouts};
+use std::fmt;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::net::TcpStream;
+use types::EthSpec;
+
+/// The timeout for requests to the beacon node HTTP API.
+const HTTP_TIMEOUT: Duration = Duration::from_secs(12);
+/// The timeout for connecting to the libp2p TCP ports.
+const PORT_TIMEOUT: Duration = Duration::from_secs(3);
+/// The number of slots the head may lag the wall-clock slot before it is reported.
+const MAX_HEAD_DISTANCE_SLOTS: u64 = 2;
+/// Report a warning if a single client makes up more than this percentage of connected peers.
+const MAX_CLIENT_SHARE_PERCENT: usize = 80;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Ok,
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Ok => write!(f, "OK"),
+            Severity::Warning => write!(f, "WARN"),
+            Severity::Error => write!(f, "FAIL"),
+        }
+    }
+}
+
+/// The outcome of a single check.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    pub check: &'static str,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl Finding {
+    fn new(check: &'static str, severity: Severity, message: impl Into<String>) -> Self {
+        Self {
+            check,
+            severity,
+            message: message.into(),
+        }
+    }
+}
+
+/// Thresholds used when evaluating the diagnostics of a node.
+#[derive(Debug, Clone, Copy)]
+pub struct Thresholds {
+    pub min_disk_free_percent: u64,
+    pub max_clock_skew: Duration,
+}
+
+/// Run the doctor, returning an error if any check failed.
+pub fn run<E: EthSpec>(config: &Doctor, env: Environment<E>) -> Result<(), String> {
+    let context = env.core_context();
+    let thresholds = Thresholds {
+        min_disk_free_percent: config.min_disk_free_percent,
+        max_clock_skew: Duration::from_millis(config.max_clock_skew_millis),
+    };
+
+    let findings = context
+        .executor
+        .block_on_dangerous(
+            run_checks(config.beacon_node.clone(), thresholds),
+            "lighthouse_doctor",
+        )
+        .ok_or("Shutting down")?;
+
+    for finding in &findings {
+        println!(
+            "[{}] {}: {}",
+            finding.severity, finding.check, finding.message
+        );
+    }
+
+    let failures = findings
+        .iter()
+        .filter(|finding| finding.severity == Severity::Error)
+        .count();
+    if failures > 0 {
+        Err(format!("{} check(s) failed", failures))
+    } else {
+        Ok(())
+    }
+}
+
+async fn run_checks(
+    beacon_node: sensitive_url::SensitiveUrl,
+    thresholds: Thresholds,
+) -> Vec<Finding> {
+    let client = BeaconNodeHttpClient::new(beacon_node.clone(), Timeouts::set_all(HTTP_TIMEOUT));
+
+    let request_start = Instant::now();
+    let diagnostics = match client.get_lighthouse_diagnostics().await {
+        Ok(response) => response.data,
+        Err(e) => {
+            return vec![Finding::new(
+                "http_api",
+                Severity::Error,
+                format!(
+                    "unable to query {}: {:?}. Check that the beacon node is running with --http",
+                    beacon_node, e
+                ),
+            )]
+        }
+    };
+    let round_trip = request_start.elapsed();
+    let local_time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+
+    let mut findings = vec![Finding::new(
+        "http_api",
+        Severity::Ok,
+        format!("connected to {}", beacon_node),
+    )];
+    findings.extend(check_ports(&beacon_node, &diagnostics).await);
+    findings.extend(diagnose(&diagnostics, local_time, round_trip, thresholds));
+    findings
+}
+
+/// Attempts to connect to each of the node's libp2p TCP ports via the host of the HTTP API.
+async fn check_ports(
+    beacon_node: &sensitive_url::SensitiveUrl,
+    diagnostics: &Diagnostics,
+) -> Vec<Finding> {
+    let Some(host) = beacon_node.full.host_str() else {
+        return vec![];
+    };
+
+    let mut findings = vec![];
+    for port in &diagnostics.libp2p_tcp_ports {
+        let connect = TcpStream::connect((host, *port));
+        let finding = match tokio::time::timeout(PORT_TIMEOUT, connect).await {
+            Ok(Ok(_)) => Finding::new(
+                "ports",
+                Severity::Ok,
+                format!("libp2p TCP port {} is accepting connections", port),
+            ),
+            Ok(Err(e)) => Finding::new(
+                "ports",
+                Severity::Error,
+                format!(
+                    "libp2p TCP port {} refused the connection ({}). Check --port and any \
+                     local firewall",
+                    port, e
+                ),
+            ),
+            Err(_) => Finding::new(
+                "ports",
+                Severity::Error,
+                format!(
+                    "timed out connecting to libp2p TCP port {}. Check any local firewall",
+                    port
+                ),
+            ),
+        };
+        findings.push(finding);
+    }
+    findings
+}
+
+/// Evaluates the diagnostics reported by the node.
+///
+/// `local_time` is the wall-clock time at which the diagnostics were received and `round_trip`
+/// is the duration of the request, which bounds the accuracy of the clock comparison.
+pub fn diagnose(
+    diagnostics: &Diagnostics,
+    local_time: Duration,
+    round_trip: Duration,
+    thresholds: Thresholds,
+) -> Vec<Finding> {
+    let mut findings = vec![];
+
+    if diagnostics.nat_open {
+        findings.push(Finding::new(
+            "ports",
+            Severity::Ok,
+            "the node is reachable from the internet",
+        ));
+    } else {
+        findings.push(Finding::new(
+            "ports",
+            Severity::Warning,
+            "no inbound connections have been observed. Check that the libp2p and discovery \
+             ports are forwarded to this node",
+        ));
+    }
+
+    let engine = &diagnostics.execution_engine;
+    let engine_finding = if !engine.configured {
+        Finding::new(
+            "execution_engine",
+            Severity::Error,
+            "no execution engine is configured. Set --execution-endpoint",
+        )
+    } else if engine.auth_failed {
+        Finding::new(
+            "execution_engine",
+            Severity::Error,
+            "the execution engine rejected the JWT. Ensure --execution-jwt points to the same \
+             secret used by the execution client",
+        )
+    } else if !engine.online {
+        Finding::new(
+            "execution_engine",
+            Severity::Error,
+            "the execution engine is offline or erroring. Check the execution client logs",
+        )
+    } else if !engine.synced {
+        Finding::new(
+            "execution_engine",
+            Severity::Warning,
+            "the execution engine is online but still syncing",
+        )
+    } else {
+        Finding::new(
+            "execution_engine",
+            Severity::Ok,
+            "the execution engine is online and synced",
+        )
+    };
+    findings.push(engine_finding);
+
+    if diagnostics.disk_bytes_total > 0 {
+        let free_percent =
+            diagnostics.disk_bytes_free.saturating_mul(100) / diagnostics.disk_bytes_total;
+        let severity = if free_percent < thresholds.min_disk_free_percent {
+            Severity::Error
+        } else {
+            Severity::Ok
+        };
+        findings.push(Finding::new(
+            "disk",
+            severity,
+            format!(
+                "{}% ({} GiB) of the disk holding the data directory is free",
+                free_percent,
+                diagnostics.disk_bytes_free / (1 << 30)
+            ),
+        ));
+    }
+
+    let node_time = Duration::from_millis(diagnostics.system_time_millis);
+    let skew = if node_time > local_time {
+        node_time - local_time
+    } else {
+        local_time - node_time
+    };
+    if skew.saturating_sub(round_trip) > thresholds.max_clock_skew {
+        findings.push(Finding::new(
+            "time_sync",
+            Severity::Error,
+            format!(
+                "the node's clock differs from the local clock by {}ms. Ensure NTP is running \
+                 on both hosts",
+                skew.as_millis()
+            ),
+        ));
+    } else {
+        findings.push(Finding::new(
+            "time_sync",
+            Severity::Ok,
+            format!("clock skew is {}ms", skew.as_millis()),
+        ));
+    }
+
+    let head_distance = diagnostics
+        .current_slot
+        .saturating_sub(diagnostics.head_slot)
+        .as_u64();
+    if head_distance > MAX_HEAD_DISTANCE_SLOTS {
+        findings.push(Finding::new(
+            "sync",
+            Severity::Warning,
+            format!(
+                "the head is {} slots behind the current slot. This is expected while syncing",
+                head_distance
+            ),
+        ));
+    }
+
+    let total_peers: usize = diagnostics.peer_clients.values().sum();
+    if total_peers == 0 {
+        findings.push(Finding::new(
+            "peers",
+            Severity::Error,
+            "the node has no connected peers. Check network connectivity and the ports above",
+        ));
+    } else if let Some((client, count)) = diagnostics
+        .peer_clients
+        .iter()
+        .max_by_key(|(_, count)| **count)
+        .filter(|(_, count)| **count * 100 > total_peers * MAX_CLIENT_SHARE_PERCENT)
+    {
+        findings.push(Finding::new(
+            "peers",
+            Severity::Warning,
+            format!(
+                "{} of {} connected peers are {} nodes. A diverse peer set reduces the impact \
+                 of client bugs",
+                count, total_peers, client
+            ),
+        ));
+    } else {
+        findings.push(Finding::new(
+            "peers",
+            Severity::Ok,
+            format!(
+                "{} connected peers across {} clients",
+                total_peers,
+                diagnostics.peer_clients.len()
+            ),
+        ));
+    }
+
+    if let Some(custody) = &diagnostics.custody {
+        if custody.blocks_missing_columns.is_empty() {
+            findings.push(Finding::new(
+                "custody",
+                Severity::Ok,
+                format!(
+                    "all {} sampling columns are stored for the {} recent blocks with blobs",
+                    custody.sampling_column_count, custody.blocks_checked
+                ),
+            ));
+        } else {
+            findings.push(Finding::new(
+                "custody",
+                Severity::Warning,
+                format!(
+                    "{} of {} recent blocks with blobs are missing sampling columns: {:?}",
+                    custody.blocks_missing_columns.len(),
+                    custody.blocks_checked,
+                    custody.blocks_missing_columns
+                ),
+            ));
+        }
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eth2::lighthouse::ExecutionEngineDiagnostics;
+    use std::collections::BTreeMap;
+    use types::Slot;
+
+    fn healthy_diagnostics() -> Diagnostics {
+        Diagnostics {
+            system_time_millis: 1_000_000,
+            current_slot: Slot::new(100),
+            head_slot: Slot::new(100),
+            execution_engine: ExecutionEngineDiagnostics {
+                configured: true,
+                online: true,
+                synced: true,
+                auth_failed: false,
+            },
+            disk_bytes_total: 1 << 40,
+            disk_bytes_free: 1 << 39,
+            libp2p_tcp_ports: vec![9000],
+            nat_open: true,
+            peer_clients: BTreeMap::from([
+                ("Lighthouse".to_string(), 20),
+                ("Teku".to_string(), 20),
+            ]),
+            custody: None,
+        }
+    }
+
+    fn thresholds() -> Thresholds {
+        Thresholds {
+            min_disk_free_percent: 10,
+            max_clock_skew: Duration::from_millis(500),
+        }
+    }
+
+    fn severity_of(findings: &[Finding], check: &str) -> Severity {
+        findings
+            .iter()
+            .filter(|finding| finding.check == check)
+            .map(|finding| finding.severity)
+            .max()
+            .unwrap()
+    }
+
+    #[test]
+    fn healthy_node_has_no_warnings() {
+        let findings = diagnose(
+            &healthy_diagnostics(),
+            Duration::from_millis(1_000_000),
+            Duration::ZERO,
+            thresholds(),
+        );
+        assert!(findings
+            .iter()
+            .all(|finding| finding.severity == Severity::Ok));
+    }
+
+    #[test]
+    fn jwt_failure_is_reported() {
+        let mut diagnostics = healthy_diagnostics();
+        diagnostics.execution_engine.online = false;
+        diagnostics.execution_engine.auth_failed = true;
+        let findings = diagnose(
+            &diagnostics,
+            Duration::from_millis(1_000_000),
+            Duration::ZERO,
+            thresholds(),
+        );
+        assert_eq!(severity_of(&findings, "execution_engine"), Severity::Error);
+        assert!(findings
+            .iter()
+            .any(|finding| finding.message.contains("JWT")));
+    }
+
+    #[test]
+    fn clock_skew_accounts_for_round_trip() {
+        let diagnostics = healthy_diagnostics();
+        let local_time = Duration::from_millis(1_000_800);
+
+        let findings = diagnose(&diagnostics, local_time, Duration::ZERO, thresholds());
+        assert_eq!(severity_of(&findings, "time_sync"), Severity::Error);
+
+        let findings = diagnose(
+            &diagnostics,
+            local_time,
+            Duration::from_millis(400),
+            thresholds(),
+        );
+        assert_eq!(severity_of(&findings, "time_sync"), Severity::Ok);
+    }
+
+    #[test]
+    fn low_disk_and_peer_monoculture_are_reported() {
+        let mut diagnostics = healthy_diagnostics();
+        diagnostics.disk_bytes_free = diagnostics.disk_bytes_total / 20;
+        diagnostics.peer_clients =
+            BTreeMap::from([("Lighthouse".to_string(), 2), ("Prysm".to_string(), 30)]);
+        let findings = diagnose(
+            &diagnostics,
+            Duration::from_millis(1_000_000),
+            Duration::ZERO,
+            thresholds(),
+        );
+        assert_eq!(severity_of(&findings, "disk"), Severity::Error);
+        assert_eq!(severity_of(&findings, "peers"), Severity::Warning);
+    }
+}
@@ -7,6 +7,8 @@
 //! `Context` which can be handed to any service that wishes to start async tasks or perform
 //! logging.
 
+mod shutdown_sequencer;
+
 use eth2_config::Eth2Config;
 use eth2_network_config::Eth2NetworkConfig;
 use futures::channel::mpsc::{channel, Receiver, Sender};
@@ -24,6 +26,8 @@ use task_executor::{ShutdownReason, TaskExecutor};
 use tokio::runtime::{Builder as RuntimeBuilder, Runtime};
 use types::{EthSpec, GnosisEthSpec, MainnetEthSpec, MinimalEthSpec};
 
+pub use shutdown_sequencer::ShutdownSequencer;
+
 #[cfg(target_family = "unix")]
 use {
     futures::Future,
@@ -89,6 +93,8 @@ pub struct RuntimeContext<E: EthSpec> {
     pub eth2_config: Eth2Config,
     pub eth2_network_config: Option<Arc<Eth2NetworkConfig>>,
     pub sse_logging_components: Option<SSELoggingComponents>,
+    /// Steps to run once a shutdown is requested, before all tasks are stopped.
+    pub shutdown_sequencer: Arc<ShutdownSequencer>,
 }
 
 impl<E: EthSpec> RuntimeContext<E> {
@@ -102,6 +108,7 @@ impl<E: EthSpec> RuntimeContext<E> {
             eth2_config: self.eth2_config.clone(),
             eth2_network_config: self.eth2_network_config.clone(),
             sse_logging_components: self.sse_logging_components.clone(),
+            shutdown_sequencer: self.shutdown_sequencer.clone(),
         }
     }
 
@@ -356,6 +363,8 @@ impl<E: EthSpec> EnvironmentBuilder<E> {
             eth_spec_instance: self.eth_spec_instance,
             eth2_config: self.eth2_config,
             eth2_network_config: self.eth2_network_config.map(Arc::new),
+            shutdown_on_sighup: true,
+            shutdown_sequencer: Arc::new(ShutdownSequencer::default()),
         })
     }
 }
@@ -375,6 +384,10 @@ pub struct Environment<E: EthSpec> {
     eth_spec_instance: E,
     pub eth2_config: Eth2Config,
     pub eth2_network_config: Option<Arc<Eth2NetworkConfig>>,
+    /// Whether SIGHUP requests a shutdown. It is ignored if a service handles it instead.
+    #[cfg_attr(not(target_family = "unix"), allow(dead_code))]
+    shutdown_on_sighup: bool,
+    shutdown_sequencer: Arc<ShutdownSequencer>,
 }
 
 impl<E: EthSpec> Environment<E> {
@@ -399,6 +412,7 @@ impl<E: EthSpec> Environment<E> {
             eth2_config: self.eth2_config.clone(),
             eth2_network_config: self.eth2_network_config.clone(),
             sse_logging_components: self.sse_logging_components.clone(),
+            shutdown_sequencer: self.shutdown_sequencer.clone(),
         }
     }
 
@@ -415,6 +429,7 @@ impl<E: EthSpec> Environment<E> {
             eth2_config: self.eth2_config.clone(),
             eth2_network_config: self.eth2_network_config.clone(),
             sse_logging_components: self.sse_logging_components.clone(),
+            shutdown_sequencer: self.shutdown_sequencer.clone(),
         }
     }
 
@@ -454,12 +469,14 @@ impl<E: EthSpec> Environment<E> {
             }
 
             // setup for handling a SIGHUP
-            match signal(SignalKind::hangup()) {
-                Ok(hup_stream) => {
-                    let hup = SignalFuture::new(hup_stream, "Received SIGHUP");
-                    handles.push(hup);
+            if self.shutdown_on_sighup {
+                match signal(SignalKind::hangup()) {
+                    Ok(hup_stream) => {
+                        let hup = SignalFuture::new(hup_stream, "Received SIGHUP");
+                        handles.push(hup);
+                    }
+                    Err(e) => error!(self.log, "Could not register SIGHUP handler"; "error" => e),
                 }
-                Err(e) => error!(self.log, "Could not register SIGHUP handler"; "error" => e),
             }
 
             future::select(inner_shutdown, future::select_all(handles.into_iter())).await
@@ -538,6 +555,19 @@ impl<E: EthSpec> Environment<E> {
         }
     }
 
+    /// Stop SIGHUP from requesting a shutdown, so that it can be handled by a service.
+    pub fn disable_shutdown_on_sighup(&mut self) {
+        self.shutdown_on_sighup = false;
+    }
+
+    /// Run the steps registered with the `ShutdownSequencer` whilst all services are still running.
+    ///
+    /// This should be called after a shutdown is requested and before `Self::fire_signal`.
+    pub fn run_shutdown_sequence(&self) {
+        self.runtime
+            .block_on(self.shutdown_sequencer.run(&self.log));
+    }
+
     /// Fire exit signal which shuts down all spawned services
     pub fn fire_signal(&mut self) {
         if let Some(signal) = self.signal.take() {
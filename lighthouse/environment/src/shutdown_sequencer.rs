@@ -0,0 +1,39 @@
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use parking_lot::Mutex;
+use slog::{info, Logger};
+use std::future::Future;
+use std::time::Instant;
+
+/// Steps which are run in order once a shutdown has been requested, but before the exit signal
+/// stops all tasks.
+///
+/// All services are still running whilst the steps run, so that services may use them to finish
+/// work which would otherwise be interrupted. Each step is responsible for bounding its own
+/// duration.
+#[derive(Default)]
+pub struct ShutdownSequencer {
+    steps: Mutex<Vec<(&'static str, BoxFuture<'static, ()>)>>,
+}
+
+impl ShutdownSequencer {
+    /// Add `step` to be run after all steps registered before it.
+    pub fn register(&self, name: &'static str, step: impl Future<Output = ()> + Send + 'static) {
+        self.steps.lock().push((name, step.boxed()));
+    }
+
+    /// Run all registered steps in order, removing them from the sequencer.
+    pub async fn run(&self, log: &Logger) {
+        let steps = std::mem::take(&mut *self.steps.lock());
+        for (name, step) in steps {
+            let start = Instant::now();
+            step.await;
+            info!(
+                log,
+                "Completed shutdown step";
+                "step" => name,
+                "duration_ms" => start.elapsed().as_millis(),
+            );
+        }
+    }
+}
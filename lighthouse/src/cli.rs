@@ -1,9 +1,21 @@
+use api_token_manager::cli::ApiTokenManager;
 use clap::Parser;
 use database_manager::cli::DatabaseManager;
+use debug_tools::cli::DebugTools;
+use doctor::cli::Doctor;
 use serde::{Deserialize, Serialize};
+use slasher_manager::cli::SlasherManager;
 
 #[derive(Parser, Clone, Deserialize, Serialize, Debug)]
 pub enum LighthouseSubcommands {
     #[clap(name = "database_manager")]
     DatabaseManager(DatabaseManager),
+    #[clap(name = "debug")]
+    DebugTools(DebugTools),
+    #[clap(name = "doctor")]
+    Doctor(Doctor),
+    #[clap(name = "api_token_manager")]
+    ApiTokenManager(ApiTokenManager),
+    #[clap(name = "slasher")]
+    SlasherManager(SlasherManager),
 }
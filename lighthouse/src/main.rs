@@ -677,6 +677,37 @@ fn run<E: EthSpec>(
         return Ok(());
     };
 
+    if let Ok(LighthouseSubcommands::DebugTools(debug_tools_config)) =
+        LighthouseSubcommands::from_arg_matches(matches)
+    {
+        info!(log, "Running debug tools for {} network", network_name);
+        debug_tools::run(matches, &debug_tools_config, environment)?;
+        return Ok(());
+    };
+
+    if let Ok(LighthouseSubcommands::Doctor(doctor_config)) =
+        LighthouseSubcommands::from_arg_matches(matches)
+    {
+        eprintln!("Running doctor for {} network", network_name);
+        doctor::run(&doctor_config, environment)?;
+        return Ok(());
+    };
+
+    if let Ok(LighthouseSubcommands::ApiTokenManager(api_token_manager_config)) =
+        LighthouseSubcommands::from_arg_matches(matches)
+    {
+        api_token_manager::run(&api_token_manager_config)?;
+        return Ok(());
+    };
+
+    if let Ok(LighthouseSubcommands::SlasherManager(slasher_manager_config)) =
+        LighthouseSubcommands::from_arg_matches(matches)
+    {
+        info!(log, "Running slasher manager for {} network", network_name);
+        slasher_manager::run(matches, &slasher_manager_config, environment)?;
+        return Ok(());
+    };
+
     info!(log, "Lighthouse started"; "version" => VERSION);
     info!(
         log,
@@ -700,34 +731,63 @@ fn run<E: EthSpec>(
                 return Ok(());
             }
 
-            let mut tracing_log_path: Option<PathBuf> =
-                clap_utils::parse_optional(matches, "logfile")?;
-
-            if tracing_log_path.is_none() {
-                tracing_log_path = Some(
-                    parse_path_or_default(matches, "datadir")?
-                        .join(DEFAULT_BEACON_NODE_DIR)
-                        .join("logs"),
-                )
+            if matches.get_flag("self-test") {
+                executor.clone().spawn(
+                    async move {
+                        let report = beacon_node::self_test::run(&config, &context).await;
+                        match serde_json::to_string_pretty(&report) {
+                            Ok(json) => println!("{}", json),
+                            Err(e) => {
+                                crit!(log, "Unable to encode self-test report"; "error" => ?e)
+                            }
+                        }
+                        let reason = if report.passed {
+                            ShutdownReason::Success("Self-test passed")
+                        } else {
+                            ShutdownReason::Failure("Self-test failed")
+                        };
+                        let _ = executor.shutdown_sender().try_send(reason);
+                    },
+                    "self_test",
+                );
+            } else {
+                if config
+                    .execution_layer
+                    .as_ref()
+                    .map_or(false, |el_config| el_config.reload_jwt_secret_on_sighup)
+                {
+                    environment.disable_shutdown_on_sighup();
+                }
+
+                let mut tracing_log_path: Option<PathBuf> =
+                    clap_utils::parse_optional(matches, "logfile")?;
+
+                if tracing_log_path.is_none() {
+                    tracing_log_path = Some(
+                        parse_path_or_default(matches, "datadir")?
+                            .join(DEFAULT_BEACON_NODE_DIR)
+                            .join("logs"),
+                    )
+                }
+
+                let path = tracing_log_path.clone().unwrap();
+
+                logging::create_tracing_layer(path);
+
+                executor.clone().spawn(
+                    async move {
+                        if let Err(e) = ProductionBeaconNode::new(context.clone(), config).await {
+                            crit!(log, "Failed to start beacon node"; "reason" => e);
+                            // Ignore the error since it always occurs during normal operation when
+                            // shutting down.
+                            let _ = executor
+                                .shutdown_sender()
+                                .try_send(ShutdownReason::Failure("Failed to start beacon node"));
+                        }
+                    },
+                    "beacon_node",
+                );
             }
-
-            let path = tracing_log_path.clone().unwrap();
-
-            logging::create_tracing_layer(path);
-
-            executor.clone().spawn(
-                async move {
-                    if let Err(e) = ProductionBeaconNode::new(context.clone(), config).await {
-                        crit!(log, "Failed to start beacon node"; "reason" => e);
-                        // Ignore the error since it always occurs during normal operation when
-                        // shutting down.
-                        let _ = executor
-                            .shutdown_sender()
-                            .try_send(ShutdownReason::Failure("Failed to start beacon node"));
-                    }
-                },
-                "beacon_node",
-            );
         }
         Some(("validator_client", matches)) => {
             let context = environment.core_context();
@@ -744,6 +804,10 @@ fn run<E: EthSpec>(
                 return Ok(());
             }
 
+            if config.hot_reload_definitions {
+                environment.disable_shutdown_on_sighup();
+            }
+
             executor.clone().spawn(
                 async move {
                     if let Err(e) = ProductionValidatorClient::new(context, config)
@@ -771,6 +835,9 @@ fn run<E: EthSpec>(
     let shutdown_reason = environment.block_until_shutdown_requested()?;
     info!(log, "Shutting down.."; "reason" => ?shutdown_reason);
 
+    // Allow services to finish in-flight work before they are stopped.
+    environment.run_shutdown_sequence();
+
     environment.fire_signal();
 
     // Shutdown the environment once all tasks have completed.
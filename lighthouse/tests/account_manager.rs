@@ -3,6 +3,7 @@ use account_manager::{
         create::*,
         import::{self, CMD as IMPORT_CMD},
         modify::{ALL, CMD as MODIFY_CMD, DISABLE, ENABLE, PUBKEY_FLAG},
+        slashing_protection::{BEFORE_EPOCH_FLAG, CMD as SLASHING_PROTECTION_CMD, PRUNE_CMD},
         CMD as VALIDATOR_CMD,
     },
     wallet::{
@@ -17,7 +18,9 @@ use account_utils::{
     validator_definitions::{SigningDefinition, ValidatorDefinition, ValidatorDefinitions},
     ZeroizeString, STDIN_INPUTS_FLAG,
 };
-use slashing_protection::{SlashingDatabase, SLASHING_PROTECTION_FILENAME};
+use slashing_protection::{
+    SlashingDatabase, SLASHING_PROTECTION_FILENAME, SLASHING_PROTECTION_HISTORY_EPOCHS,
+};
 use std::env;
 use std::fs::{self, File};
 use std::io::{BufRead, BufReader, Write};
@@ -25,7 +28,7 @@ use std::path::{Path, PathBuf};
 use std::process::{Child, Command, Output, Stdio};
 use std::str::from_utf8;
 use tempfile::{tempdir, TempDir};
-use types::{Keypair, PublicKey};
+use types::{Epoch, Hash256, Keypair, PublicKey, Slot};
 use validator_dir::ValidatorDir;
 
 /// Returns the `lighthouse account` command.
@@ -494,6 +497,8 @@ fn validator_import_launchpad() {
         builder_proposals: None,
         builder_boost_factor: None,
         prefer_builder_proposals: None,
+        attestation_offset_ms: None,
+        builder_min_bid_gwei: None,
         voting_public_key: keystore.public_key().unwrap(),
         signing_definition: SigningDefinition::LocalKeystore {
             voting_keystore_path,
@@ -618,6 +623,8 @@ fn validator_import_launchpad_no_password_then_add_password() {
         builder_proposals: None,
         builder_boost_factor: None,
         prefer_builder_proposals: None,
+        attestation_offset_ms: None,
+        builder_min_bid_gwei: None,
         voting_public_key: keystore.public_key().unwrap(),
         signing_definition: SigningDefinition::LocalKeystore {
             voting_keystore_path,
@@ -646,6 +653,8 @@ fn validator_import_launchpad_no_password_then_add_password() {
         builder_proposals: None,
         builder_boost_factor: None,
         prefer_builder_proposals: None,
+        attestation_offset_ms: None,
+        builder_min_bid_gwei: None,
         voting_public_key: keystore.public_key().unwrap(),
         signing_definition: SigningDefinition::LocalKeystore {
             voting_keystore_path: dst_keystore_dir.join(KEYSTORE_NAME),
@@ -750,6 +759,8 @@ fn validator_import_launchpad_password_file() {
         builder_proposals: None,
         builder_boost_factor: None,
         prefer_builder_proposals: None,
+        attestation_offset_ms: None,
+        builder_min_bid_gwei: None,
         signing_definition: SigningDefinition::LocalKeystore {
             voting_keystore_path,
             voting_keystore_password_path: None,
@@ -773,3 +784,66 @@ fn check_slashing_protection(validator_dir: &TempDir, pubkeys: impl Iterator<Ite
             .unwrap();
     }
 }
+
+#[test]
+fn slashing_protection_prune() {
+    let validator_dir = tempdir().unwrap();
+    let slashing_db_path = validator_dir.path().join(SLASHING_PROTECTION_FILENAME);
+    let slashing_db = SlashingDatabase::create(&slashing_db_path).unwrap();
+
+    let pubkey = Keypair::random().pk.compress();
+    slashing_db.register_validator(pubkey).unwrap();
+    let latest_target = 2 * SLASHING_PROTECTION_HISTORY_EPOCHS;
+    for target in [1, 2, 3, latest_target] {
+        slashing_db
+            .check_and_insert_attestation_signing_root(
+                &pubkey,
+                Epoch::new(target - 1),
+                Epoch::new(target),
+                Hash256::random().into(),
+            )
+            .unwrap();
+    }
+    for slot in 1..=3 {
+        slashing_db
+            .check_and_insert_block_signing_root(&pubkey, Slot::new(slot), Hash256::random().into())
+            .unwrap();
+    }
+
+    let prune = |before_epoch: u64| {
+        output_result(
+            validator_cmd()
+                .arg(format!("--{}", VALIDATOR_DIR_FLAG))
+                .arg(validator_dir.path().as_os_str())
+                .arg(SLASHING_PROTECTION_CMD)
+                .arg(PRUNE_CMD)
+                .arg(format!("--{}", BEFORE_EPOCH_FLAG))
+                .arg(before_epoch.to_string()),
+        )
+    };
+
+    // Records within the retained history of the latest attestation can't be pruned.
+    prune(latest_target - SLASHING_PROTECTION_HISTORY_EPOCHS + 1).unwrap_err();
+
+    prune(3).unwrap();
+
+    let interchange = slashing_db
+        .export_all_interchange_info(Hash256::zero())
+        .unwrap();
+    assert_eq!(interchange.data.len(), 1);
+    let data = &interchange.data[0];
+    let mut targets = data
+        .signed_attestations
+        .iter()
+        .map(|attestation| attestation.target_epoch.as_u64())
+        .collect::<Vec<_>>();
+    targets.sort_unstable();
+    assert_eq!(targets, vec![3, latest_target]);
+    // The most recent block is retained even though it precedes `--before-epoch`.
+    let slots = data
+        .signed_blocks
+        .iter()
+        .map(|block| block.slot.as_u64())
+        .collect::<Vec<_>>();
+    assert_eq!(slots, vec![3]);
+}
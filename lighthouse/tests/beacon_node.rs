@@ -2,17 +2,21 @@ use beacon_node::ClientConfig as Config;
 
 use crate::exec::{CommandLineTestExec, CompletedTest};
 use beacon_node::beacon_chain::chain_config::{
-    DisallowedReOrgOffsets, DEFAULT_RE_ORG_CUTOFF_DENOMINATOR, DEFAULT_RE_ORG_HEAD_THRESHOLD,
+    BackfillProfile, DisallowedReOrgOffsets, DEFAULT_BLOCK_IMPORT_TIMEOUT_SECS,
+    DEFAULT_RE_ORG_CUTOFF_DENOMINATOR, DEFAULT_RE_ORG_HEAD_THRESHOLD,
     DEFAULT_RE_ORG_MAX_EPOCHS_SINCE_FINALIZATION,
 };
 use beacon_node::beacon_chain::graffiti_calculator::GraffitiOrigin;
-use beacon_processor::BeaconProcessorConfig;
+use beacon_processor::priority::PriorityOverride;
+use beacon_processor::{BeaconProcessorConfig, WorkType};
 use eth1::Eth1Endpoint;
-use lighthouse_network::PeerId;
+use lighthouse_network::{PeerId, SubnetStrategy, TransportPreference};
 use lighthouse_version;
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::{Read, Write};
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::num::NonZeroU64;
 use std::path::Path;
 use std::path::PathBuf;
 use std::process::Command;
@@ -21,7 +25,7 @@ use std::string::ToString;
 use std::time::Duration;
 use tempfile::TempDir;
 use types::non_zero_usize::new_non_zero_usize;
-use types::{Address, Checkpoint, Epoch, Hash256, MainnetEthSpec};
+use types::{Address, Checkpoint, Epoch, Hash256, MainnetEthSpec, Slot};
 use unused_port::{unused_tcp4_port, unused_tcp6_port, unused_udp4_port, unused_udp6_port};
 
 const DEFAULT_EXECUTION_ENDPOINT: &str = "http://localhost:8551/";
@@ -297,6 +301,63 @@ fn paranoid_block_proposal_on() {
         .with_config(|config| assert!(config.chain.paranoid_block_proposal));
 }
 
+#[test]
+fn attestation_packing_threads_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.attestation_packing_threads, None));
+}
+
+#[test]
+fn attestation_packing_threads_flag() {
+    CommandLineTest::new()
+        .flag("attestation-packing-threads", Some("4"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.attestation_packing_threads, Some(4)));
+}
+
+#[test]
+fn block_production_deadline_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.block_production_deadline, None));
+}
+
+#[test]
+fn block_production_deadline_flag() {
+    CommandLineTest::new()
+        .flag("block-production-deadline", Some("2000"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.block_production_deadline,
+                Some(Duration::from_millis(2000))
+            )
+        });
+}
+
+#[test]
+fn block_import_timeout_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.block_import_timeout,
+                Duration::from_secs(DEFAULT_BLOCK_IMPORT_TIMEOUT_SECS)
+            )
+        });
+}
+
+#[test]
+fn block_import_timeout_flag() {
+    CommandLineTest::new()
+        .flag("block-import-timeout", Some("30"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.chain.block_import_timeout, Duration::from_secs(30))
+        });
+}
+
 #[test]
 fn reset_payload_statuses_default() {
     CommandLineTest::new()
@@ -383,7 +444,7 @@ fn genesis_backfill_flag() {
     CommandLineTest::new()
         .flag("genesis-backfill", None)
         .run_with_zero_port()
-        .with_config(|config| assert_eq!(config.chain.genesis_backfill, true));
+        .with_config(|config| assert_eq!(config.chain.backfill_profile, BackfillProfile::Full));
 }
 
 /// The genesis backfill flag should be enabled if historic states flag is set.
@@ -392,7 +453,97 @@ fn genesis_backfill_with_historic_flag() {
     CommandLineTest::new()
         .flag("reconstruct-historic-states", None)
         .run_with_zero_port()
-        .with_config(|config| assert_eq!(config.chain.genesis_backfill, true));
+        .with_config(|config| assert_eq!(config.chain.backfill_profile, BackfillProfile::Full));
+}
+
+#[test]
+fn backfill_profile_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.backfill_profile, BackfillProfile::Minimal));
+}
+
+#[test]
+fn backfill_profile_full() {
+    CommandLineTest::new()
+        .flag("backfill-profile", Some("full"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.backfill_profile, BackfillProfile::Full));
+}
+
+#[test]
+fn backfill_profile_custom_slot() {
+    CommandLineTest::new()
+        .flag("backfill-profile", Some("4000000"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.backfill_profile,
+                BackfillProfile::Custom(Slot::new(4000000))
+            )
+        });
+}
+
+#[test]
+#[should_panic]
+fn backfill_profile_invalid() {
+    CommandLineTest::new()
+        .flag("backfill-profile", Some("everything"))
+        .run_with_zero_port();
+}
+
+#[test]
+fn backfill_target_epoch_flag() {
+    CommandLineTest::new()
+        .flag("backfill-target-epoch", Some("250000"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.backfill_profile,
+                BackfillProfile::Target(Epoch::new(250000))
+            )
+        });
+}
+
+#[test]
+#[should_panic]
+fn backfill_target_epoch_conflicts_with_genesis_backfill() {
+    CommandLineTest::new()
+        .flag("backfill-target-epoch", Some("250000"))
+        .flag("genesis-backfill", None)
+        .run_with_zero_port();
+}
+
+#[test]
+fn backfill_batches_per_minute_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.backfill_batches_per_minute, None));
+}
+
+#[test]
+fn backfill_batches_per_minute_flag() {
+    CommandLineTest::new()
+        .flag("backfill-batches-per-minute", Some("30"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.backfill_batches_per_minute, Some(30)));
+}
+
+#[test]
+fn import_era_dir_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.era_import_dir, None));
+}
+
+#[test]
+fn import_era_dir_flag() {
+    CommandLineTest::new()
+        .flag("import-era-dir", Some("/tmp/era"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.chain.era_import_dir, Some(PathBuf::from("/tmp/era")))
+        });
 }
 
 // Tests for Eth1 flags.
@@ -563,6 +714,47 @@ fn bellatrix_jwt_secrets_flag() {
         });
 }
 #[test]
+fn execution_fallback_endpoints_flag() {
+    CommandLineTest::new()
+        .flag(
+            "execution-fallback-endpoints",
+            Some("http://localhost:8561/,http://localhost:8571/"),
+        )
+        .run_with_zero_port()
+        .with_config(|config| {
+            let config = config.execution_layer.as_ref().unwrap();
+            let fallbacks = config
+                .fallback_execution_endpoints
+                .iter()
+                .map(|url| url.full.to_string())
+                .collect::<Vec<_>>();
+            assert_eq!(
+                fallbacks,
+                vec!["http://localhost:8561/", "http://localhost:8571/"]
+            );
+            assert!(!config.fallback_new_payload);
+        });
+}
+#[test]
+fn execution_fallback_new_payload_flag() {
+    CommandLineTest::new()
+        .flag(
+            "execution-fallback-endpoints",
+            Some("http://localhost:8561/"),
+        )
+        .flag("execution-fallback-new-payload", None)
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert!(
+                config
+                    .execution_layer
+                    .as_ref()
+                    .unwrap()
+                    .fallback_new_payload
+            );
+        });
+}
+#[test]
 fn bellatrix_fee_recipient_flag() {
     let dir = TempDir::new().expect("Unable to create temporary directory");
     CommandLineTest::new_with_no_execution_endpoint()
@@ -720,6 +912,34 @@ fn builder_user_agent() {
     );
 }
 
+#[test]
+fn builder_groups() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = dir.path().join("builder-groups.json");
+    let pubkey = "0xa1d1ad0714035353258038e964ae9675dc0252ee22cea896825c01458e1807bfad2f9969338798548d9858a571f7425c";
+    std::fs::write(
+        &path,
+        format!(
+            r#"[{{"name": "pool", "builder_url": "http://woof.dogs", "min_bid_gwei": 1000, "validators": ["{pubkey}"]}}]"#
+        ),
+    )
+    .expect("Unable to write builder groups file");
+
+    run_payload_builder_flag_test_with_config(
+        "builder",
+        "http://meow.cats",
+        Some("builder-groups"),
+        path.to_str(),
+        |config| {
+            let groups = &config.execution_layer.as_ref().unwrap().builder_groups;
+            assert_eq!(groups.len(), 1);
+            assert_eq!(groups[0].name, "pool");
+            assert_eq!(groups[0].min_bid_gwei, Some(1000));
+            assert_eq!(groups[0].validators.len(), 1);
+        },
+    );
+}
+
 fn run_jwt_optional_flags_test(jwt_flag: &str, jwt_id_flag: &str, jwt_version_flag: &str) {
     use sensitive_url::SensitiveUrl;
 
@@ -757,6 +977,41 @@ fn jwt_optional_flags() {
 fn jwt_optional_alias_flags() {
     run_jwt_optional_flags_test("jwt-secrets", "jwt-id", "jwt-version");
 }
+#[test]
+fn execution_jwt_reload_on_sighup_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    CommandLineTest::new_with_no_execution_endpoint()
+        .flag("execution-endpoint", Some("http://meow.cats"))
+        .flag(
+            "execution-jwt",
+            dir.path().join("jwt-file").as_os_str().to_str(),
+        )
+        .flag("execution-jwt-reload-on-sighup", None)
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert!(
+                config
+                    .execution_layer
+                    .as_ref()
+                    .unwrap()
+                    .reload_jwt_secret_on_sighup
+            );
+        });
+}
+#[test]
+fn execution_jwt_reload_on_sighup_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert!(
+                !config
+                    .execution_layer
+                    .as_ref()
+                    .unwrap()
+                    .reload_jwt_secret_on_sighup
+            )
+        });
+}
 // DEPRECATED. This flag is deprecated but should not cause a crash.
 #[test]
 fn terminal_total_difficulty_override_flag() {
@@ -809,6 +1064,33 @@ fn network_subscribe_all_data_column_subnets_flag() {
         .with_config(|config| assert!(config.network.subscribe_all_data_column_subnets));
 }
 #[test]
+fn data_column_archive_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert!(!config.network.data_column_archive);
+            assert!(!config.store.data_column_archive);
+        });
+}
+#[test]
+fn data_column_archive_flag() {
+    CommandLineTest::new()
+        .flag("subscribe-all-data-column-subnets", None)
+        .flag("data-column-archive", None)
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert!(config.network.data_column_archive);
+            assert!(config.store.data_column_archive);
+        });
+}
+#[test]
+#[should_panic]
+fn data_column_archive_requires_all_data_column_subnets() {
+    CommandLineTest::new()
+        .flag("data-column-archive", None)
+        .run_with_zero_port();
+}
+#[test]
 fn network_enable_sampling_flag() {
     CommandLineTest::new()
         .flag("enable-sampling", None)
@@ -836,6 +1118,29 @@ fn blob_publication_batch_interval() {
         });
 }
 
+#[test]
+fn payload_bodies_batch_size_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.payload_bodies_batch_size, 32));
+}
+
+#[test]
+fn payload_bodies_batch_size_flag() {
+    CommandLineTest::new()
+        .flag("payload-bodies-batch-size", Some("128"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.payload_bodies_batch_size, 128));
+}
+
+#[test]
+#[should_panic]
+fn payload_bodies_batch_size_flag_too_large() {
+    CommandLineTest::new()
+        .flag("payload-bodies-batch-size", Some("1025"))
+        .run_with_zero_port();
+}
+
 #[test]
 fn network_enable_sampling_flag_default() {
     CommandLineTest::new()
@@ -850,6 +1155,46 @@ fn network_subscribe_all_subnets_flag() {
         .with_config(|config| assert!(config.network.subscribe_all_subnets));
 }
 #[test]
+fn network_subnet_strategy_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.network.subnet_strategy, SubnetStrategy::Default);
+            assert!(!config.network.subscribe_all_subnets);
+            assert_eq!(config.network.aggressive_subnet_epochs, 2);
+        });
+}
+#[test]
+fn network_subnet_strategy_aggressive() {
+    CommandLineTest::new()
+        .flag("subnet-strategy", Some("aggressive"))
+        .flag("aggressive-subnet-epochs", Some("4"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.network.subnet_strategy, SubnetStrategy::Aggressive);
+            assert!(!config.network.subscribe_all_subnets);
+            assert_eq!(config.network.aggressive_subnet_epochs, 4);
+        });
+}
+#[test]
+fn network_subnet_strategy_all_subnets() {
+    CommandLineTest::new()
+        .flag("subnet-strategy", Some("all-subnets"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.network.subnet_strategy, SubnetStrategy::AllSubnets);
+            assert!(config.network.subscribe_all_subnets);
+        });
+}
+#[test]
+#[should_panic]
+fn network_subnet_strategy_aggressive_with_subscribe_all_subnets() {
+    CommandLineTest::new()
+        .flag("subnet-strategy", Some("aggressive"))
+        .flag("subscribe-all-subnets", None)
+        .run_with_zero_port();
+}
+#[test]
 fn network_import_all_attestations_flag() {
     CommandLineTest::new()
         .flag("import-all-attestations", None)
@@ -864,6 +1209,45 @@ fn network_shutdown_after_sync_flag() {
         .with_config(|config| assert!(config.network.shutdown_after_sync));
 }
 #[test]
+fn network_blinded_blocks_by_range_flag() {
+    CommandLineTest::new()
+        .flag("blinded-blocks-by-range", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.network.enable_blinded_blocks_by_range));
+}
+#[test]
+fn network_blinded_blocks_by_range_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert!(!config.network.enable_blinded_blocks_by_range));
+}
+#[test]
+fn network_execution_payload_bodies_by_range_flag() {
+    CommandLineTest::new()
+        .flag("execution-payload-bodies-by-range", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.network.enable_execution_payload_bodies_by_range));
+}
+#[test]
+fn network_execution_payload_bodies_by_range_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert!(!config.network.enable_execution_payload_bodies_by_range));
+}
+#[test]
+fn network_persist_pending_lookups_flag() {
+    CommandLineTest::new()
+        .flag("persist-pending-lookups", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.network.persist_pending_lookups));
+}
+#[test]
+fn network_persist_pending_lookups_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert!(!config.network.persist_pending_lookups));
+}
+#[test]
 fn network_shutdown_after_sync_disabled_flag() {
     CommandLineTest::new()
         .run_with_zero_port()
@@ -1212,6 +1596,113 @@ fn disable_quic_flag() {
         .with_config(|config| assert!(config.network.disable_quic_support));
 }
 #[test]
+fn transport_preference_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.network.transport_preference,
+                TransportPreference::QuicFirst
+            )
+        });
+}
+#[test]
+fn transport_preference_flag() {
+    CommandLineTest::new()
+        .flag("transport-preference", Some("tcp-first"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.network.transport_preference,
+                TransportPreference::TcpFirst
+            )
+        });
+}
+#[test]
+fn staggered_publish_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.network.staggered_publish_peers, None);
+            assert_eq!(
+                config.network.staggered_publish_delay,
+                Duration::from_millis(200)
+            );
+        });
+}
+#[test]
+fn staggered_publish_flags() {
+    CommandLineTest::new()
+        .flag("staggered-publish-peers", Some("4"))
+        .flag("staggered-publish-delay", Some("500"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.network.staggered_publish_peers, Some(4));
+            assert_eq!(
+                config.network.staggered_publish_delay,
+                Duration::from_millis(500)
+            );
+        });
+}
+#[test]
+fn lookup_max_parent_depth_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.lookup_max_parent_depth, 32));
+}
+#[test]
+fn lookup_max_parent_depth_flag() {
+    CommandLineTest::new()
+        .flag("lookup-max-parent-depth", Some("64"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.lookup_max_parent_depth, 64));
+}
+#[test]
+#[should_panic]
+fn lookup_max_parent_depth_zero() {
+    CommandLineTest::new()
+        .flag("lookup-max-parent-depth", Some("0"))
+        .run_with_zero_port();
+}
+#[test]
+fn shutdown_rpc_drain_timeout_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.network.shutdown_rpc_drain_timeout,
+                Duration::from_secs(5)
+            )
+        });
+}
+#[test]
+fn shutdown_rpc_drain_timeout_flag() {
+    CommandLineTest::new()
+        .flag("shutdown-rpc-drain-timeout", Some("30"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.network.shutdown_rpc_drain_timeout,
+                Duration::from_secs(30)
+            )
+        });
+}
+#[test]
+#[should_panic]
+fn staggered_publish_zero_peers() {
+    CommandLineTest::new()
+        .flag("staggered-publish-peers", Some("0"))
+        .run_with_zero_port();
+}
+#[test]
+#[should_panic]
+fn transport_preference_quic_only_with_disable_quic() {
+    CommandLineTest::new()
+        .flag("transport-preference", Some("quic-only"))
+        .flag("disable-quic", None)
+        .run_with_zero_port();
+}
+#[test]
 fn disable_peer_scoring_flag() {
     CommandLineTest::new()
         .flag("disable-peer-scoring", None)
@@ -1263,6 +1754,46 @@ fn boot_nodes_flag() {
         });
 }
 #[test]
+fn import_peers_flag() {
+    let good = "enr:-IS4QHCYrYZbAKWCBRlAy5zzaDZXJBGkcnh4MHcBFZntXNFrdvJjX04jRzjzCBOonrkTfj499SZuOh8R33Ls8RRcy5wBgmlkgnY0gmlwhH8AAAGJc2VjcDI1NmsxoQPKY0yuDUmstAHYpMa2_oxVtw0RW_QAdpzBQA8yWM0xOIN1ZHCCdl8";
+    let best = "enr:-LK4QFOFWca5ABQzxiCRcy37G7wy1K6zD4qMYBSN5ozzanwze_XVvXVhCk9JvF0cHXOBZrHK1E4vU7Gn-a0bHVczoDU6h2F0dG5ldHOIAAAAAAAAAACEZXRoMpA7CIeVAAAgCf__________gmlkgnY0gmlwhNIy-4iJc2VjcDI1NmsxoQJA3AXQJ6M3NpBWtJS3HPtbXG14t7qHjXuIaL6IOz89T4N0Y3CCIyiDdWRwgiMo";
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = dir.path().join("peers.json");
+    std::fs::write(
+        &path,
+        format!(
+            r#"[
+                {{"peer_id": "a", "enr": "{good}", "score": 1.5, "custody_subnets": []}},
+                {{"peer_id": "b", "enr": "{good}", "score": -20.0, "custody_subnets": []}},
+                {{"peer_id": "c", "enr": "{best}", "score": 10.0, "custody_subnets": [0, 1]}}
+            ]"#
+        ),
+    )
+    .expect("Unable to write peers file");
+    CommandLineTest::new()
+        .flag("import-peers", path.as_os_str().to_str())
+        .run_with_zero_port()
+        .with_config(|config| {
+            let imported = config
+                .network
+                .imported_peers
+                .iter()
+                .map(|enr| enr.to_base64())
+                .collect::<Vec<_>>();
+            assert_eq!(imported, vec![best, good]);
+        });
+}
+#[test]
+#[should_panic]
+fn import_peers_flag_invalid_file() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = dir.path().join("peers.json");
+    std::fs::write(&path, "not json").expect("Unable to write peers file");
+    CommandLineTest::new()
+        .flag("import-peers", path.as_os_str().to_str())
+        .run_with_zero_port();
+}
+#[test]
 fn boot_nodes_multiaddr_flag() {
     let nodes = "/ip4/0.0.0.0/tcp/9000/p2p/16Uiu2HAkynrfLjeoAP7R3WFySad2NfduShkTpx8f8ygpSSfP1yen,\
                 /ip4/192.167.55.55/tcp/9000/p2p/16Uiu2HAkynrfLjeoBP7R3WFyDad2NfduVhkWpx8f8ygpSSfP1yen";
@@ -1545,91 +2076,200 @@ fn enr_address_flag_with_ipv6() {
         });
 }
 #[test]
-fn enr_address_dns_flag() {
-    let addr = Ipv4Addr::LOCALHOST;
-    let ipv6addr = Ipv6Addr::LOCALHOST;
-    let port = DUMMY_ENR_UDP_PORT;
+fn enr_address_dns_flag() {
+    let addr = Ipv4Addr::LOCALHOST;
+    let ipv6addr = Ipv6Addr::LOCALHOST;
+    let port = DUMMY_ENR_UDP_PORT;
+    CommandLineTest::new()
+        .flag("enr-address", Some("localhost"))
+        .flag("enr-udp-port", Some(port.to_string().as_str()))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert!(
+                config.network.enr_address.0 == Some(addr)
+                    || config.network.enr_address.1 == Some(ipv6addr)
+            );
+            assert_eq!(
+                config.network.enr_udp4_port.map(|port| port.get()),
+                Some(port)
+            );
+        });
+}
+#[test]
+fn disable_enr_auto_update_flag() {
+    CommandLineTest::new()
+        .flag("disable-enr-auto-update", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.network.discv5_config.enr_update));
+}
+
+// Tests for HTTP flags.
+#[test]
+fn http_flag() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.http_api.enabled));
+}
+#[test]
+fn http_address_flag() {
+    let addr = "127.0.0.99".parse::<IpAddr>().unwrap();
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-address", Some("127.0.0.99"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.http_api.listen_addr, addr));
+}
+#[test]
+fn http_address_ipv6_flag() {
+    let addr = "::1".parse::<IpAddr>().unwrap();
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-address", Some("::1"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.http_api.listen_addr, addr));
+}
+#[test]
+fn http_port_flag() {
+    let port1 = 0;
+    let port2 = 0;
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-port", Some(port1.to_string().as_str()))
+        .flag("port", Some(port2.to_string().as_str()))
+        .flag("allow-insecure-genesis-sync", None)
+        .run()
+        .with_config(|config| assert_eq!(config.http_api.listen_port, port1));
+}
+
+#[test]
+fn empty_inbound_rate_limiter_flag() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.network.inbound_rate_limiter_config,
+                Some(lighthouse_network::rpc::config::InboundRateLimiterConfig::default())
+            )
+        });
+}
+#[test]
+fn disable_inbound_rate_limiter_flag() {
+    CommandLineTest::new()
+        .flag("disable-inbound-rate-limiter", None)
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.inbound_rate_limiter_config, None));
+}
+
+#[test]
+fn http_api_tokens_file_flag() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-api-tokens-file", Some("/tmp/api-tokens.json"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.http_api.api_tokens_file,
+                Some(PathBuf::from("/tmp/api-tokens.json"))
+            );
+        });
+}
+#[test]
+fn http_historical_state_flags() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-historical-state-cache-size", Some("8"))
+        .flag("http-historical-state-queue-size", Some("32"))
+        .flag("http-historical-state-regen-budget", Some("6"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.http_api.historical_state_cache_size.get(), 8);
+            assert_eq!(config.http_api.historical_state_queue_size, 32);
+            assert_eq!(config.http_api.historical_state_regen_budget, 6);
+        });
+}
+#[test]
+fn http_historical_state_defaults() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.http_api.historical_state_cache_size.get(), 4);
+            assert_eq!(config.http_api.historical_state_queue_size, 16);
+            assert_eq!(config.http_api.historical_state_regen_budget, 4);
+        });
+}
+#[test]
+fn http_rate_limit_flag() {
     CommandLineTest::new()
-        .flag("enr-address", Some("localhost"))
-        .flag("enr-udp-port", Some(port.to_string().as_str()))
+        .flag("http", None)
+        .flag("http-rate-limit", Some("100/10"))
         .run_with_zero_port()
         .with_config(|config| {
-            assert!(
-                config.network.enr_address.0 == Some(addr)
-                    || config.network.enr_address.1 == Some(ipv6addr)
-            );
+            let rate_limit = config.http_api.rate_limit.as_ref().unwrap();
+            assert_eq!(rate_limit.quota.max_tokens, 100);
             assert_eq!(
-                config.network.enr_udp4_port.map(|port| port.get()),
-                Some(port)
+                rate_limit.quota.replenish_all_every,
+                Duration::from_secs(10)
             );
+            assert_eq!(rate_limit.cost("/eth/v2/debug/beacon/states/head"), 50);
+            assert_eq!(rate_limit.cost("/eth/v1/node/version"), 1);
         });
 }
 #[test]
-fn disable_enr_auto_update_flag() {
+fn http_rate_limit_route_costs_flag() {
     CommandLineTest::new()
-        .flag("disable-enr-auto-update", None)
+        .flag("http", None)
+        .flag("http-rate-limit", Some("100/10"))
+        .flag(
+            "http-rate-limit-route-costs",
+            Some("/eth/v2/debug/beacon/states/:80;/eth/v1/node/:2"),
+        )
         .run_with_zero_port()
-        .with_config(|config| assert!(config.network.discv5_config.enr_update));
+        .with_config(|config| {
+            let rate_limit = config.http_api.rate_limit.as_ref().unwrap();
+            assert_eq!(rate_limit.cost("/eth/v2/debug/beacon/states/head"), 80);
+            assert_eq!(rate_limit.cost("/eth/v1/node/version"), 2);
+            assert_eq!(rate_limit.cost("/eth/v1/beacon/states/head/root"), 1);
+        });
 }
-
-// Tests for HTTP flags.
 #[test]
-fn http_flag() {
+fn http_rate_limit_default() {
     CommandLineTest::new()
         .flag("http", None)
         .run_with_zero_port()
-        .with_config(|config| assert!(config.http_api.enabled));
+        .with_config(|config| assert_eq!(config.http_api.rate_limit, None));
 }
 #[test]
-fn http_address_flag() {
-    let addr = "127.0.0.99".parse::<IpAddr>().unwrap();
+fn http_proposer_lookahead_flag() {
     CommandLineTest::new()
         .flag("http", None)
-        .flag("http-address", Some("127.0.0.99"))
+        .flag("http-proposer-lookahead", None)
         .run_with_zero_port()
-        .with_config(|config| assert_eq!(config.http_api.listen_addr, addr));
+        .with_config(|config| assert!(config.http_api.enable_proposer_lookahead));
 }
 #[test]
-fn http_address_ipv6_flag() {
-    let addr = "::1".parse::<IpAddr>().unwrap();
+fn http_proposer_lookahead_default() {
     CommandLineTest::new()
         .flag("http", None)
-        .flag("http-address", Some("::1"))
         .run_with_zero_port()
-        .with_config(|config| assert_eq!(config.http_api.listen_addr, addr));
+        .with_config(|config| assert!(!config.http_api.enable_proposer_lookahead));
 }
 #[test]
-fn http_port_flag() {
-    let port1 = 0;
-    let port2 = 0;
+fn checkpoint_provider_mode_flag() {
     CommandLineTest::new()
         .flag("http", None)
-        .flag("http-port", Some(port1.to_string().as_str()))
-        .flag("port", Some(port2.to_string().as_str()))
-        .flag("allow-insecure-genesis-sync", None)
-        .run()
-        .with_config(|config| assert_eq!(config.http_api.listen_port, port1));
-}
-
-#[test]
-fn empty_inbound_rate_limiter_flag() {
-    CommandLineTest::new()
+        .flag("checkpoint-provider-mode", None)
         .run_with_zero_port()
-        .with_config(|config| {
-            assert_eq!(
-                config.network.inbound_rate_limiter_config,
-                Some(lighthouse_network::rpc::config::InboundRateLimiterConfig::default())
-            )
-        });
+        .with_config(|config| assert!(config.http_api.checkpoint_provider_mode));
 }
 #[test]
-fn disable_inbound_rate_limiter_flag() {
+fn checkpoint_provider_mode_default() {
     CommandLineTest::new()
-        .flag("disable-inbound-rate-limiter", None)
+        .flag("http", None)
         .run_with_zero_port()
-        .with_config(|config| assert_eq!(config.network.inbound_rate_limiter_config, None));
+        .with_config(|config| assert!(!config.http_api.checkpoint_provider_mode));
 }
-
 #[test]
 fn http_allow_origin_flag() {
     CommandLineTest::new()
@@ -1849,6 +2489,23 @@ fn state_cache_size_flag() {
         .with_config(|config| assert_eq!(config.store.state_cache_size, new_non_zero_usize(64)));
 }
 #[test]
+fn state_cache_size_mb_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.store.state_cache_size_mb, new_non_zero_usize(2048))
+        });
+}
+#[test]
+fn state_cache_size_mb_flag() {
+    CommandLineTest::new()
+        .flag("state-cache-size-mb", Some("512"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.store.state_cache_size_mb, new_non_zero_usize(512))
+        });
+}
+#[test]
 fn historic_state_cache_size_flag() {
     CommandLineTest::new()
         .flag("historic-state-cache-size", Some("4"))
@@ -1894,6 +2551,43 @@ fn hdiff_buffer_cache_size_default() {
         });
 }
 #[test]
+fn hierarchical_state_diffs_default() {
+    use beacon_node::beacon_chain::store::hdiff::HierarchyConfig;
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.store.hierarchy_config, HierarchyConfig::default())
+        });
+}
+#[test]
+fn hierarchical_state_diffs_disabled() {
+    use beacon_node::beacon_chain::store::hdiff::HierarchyConfig;
+    CommandLineTest::new()
+        .flag("hierarchical-state-diffs", Some("false"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.store.hierarchy_config,
+                HierarchyConfig::snapshots_only()
+            )
+        });
+}
+#[test]
+fn hierarchy_exponents_flag() {
+    CommandLineTest::new()
+        .flag("hierarchy-exponents", Some("5,8,12"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.store.hierarchy_config.exponents, vec![5, 8, 12]));
+}
+#[test]
+#[should_panic]
+fn hierarchical_state_diffs_conflicts_with_exponents() {
+    CommandLineTest::new()
+        .flag("hierarchical-state-diffs", Some("false"))
+        .flag("hierarchy-exponents", Some("5,8,12"))
+        .run_with_zero_port();
+}
+#[test]
 fn auto_compact_db_flag() {
     CommandLineTest::new()
         .flag("auto-compact-db", Some("false"))
@@ -1909,6 +2603,60 @@ fn compact_db_flag() {
         .with_config(|config| assert!(config.store.compact_on_init));
 }
 #[test]
+fn db_compaction_interval_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.store.compaction_interval, None);
+            assert_eq!(config.store.compaction_window, None);
+        });
+}
+#[test]
+fn db_compaction_interval_flag() {
+    CommandLineTest::new()
+        .flag("db-compaction-interval", Some("21600"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.store.compaction_interval,
+                Some(Duration::from_secs(21600))
+            );
+            assert_eq!(config.store.compaction_window, None);
+        });
+}
+#[test]
+#[should_panic]
+fn db_compaction_interval_zero() {
+    CommandLineTest::new()
+        .flag("db-compaction-interval", Some("0"))
+        .run_with_zero_port();
+}
+#[test]
+fn db_compaction_window_flag() {
+    use beacon_node::beacon_chain::store::compaction::CompactionWindow;
+    CommandLineTest::new()
+        .flag("db-compaction-interval", Some("21600"))
+        .flag("db-compaction-window", Some("22-4"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.store.compaction_window,
+                Some(CompactionWindow {
+                    start_hour: 22,
+                    end_hour: 4
+                })
+            )
+        });
+}
+#[test]
+#[should_panic]
+fn db_compaction_window_invalid() {
+    CommandLineTest::new()
+        .flag("db-compaction-interval", Some("21600"))
+        .flag("db-compaction-window", Some("2-25"))
+        .run_with_zero_port();
+}
+#[test]
 fn prune_payloads_default() {
     CommandLineTest::new()
         .run_with_zero_port()
@@ -1961,6 +2709,51 @@ fn blob_prune_margin_epochs_on_startup_ten() {
         .with_config(|config| assert!(config.store.blob_prune_margin_epochs == 10));
 }
 #[test]
+fn blob_retention_epochs_default() {
+    use beacon_node::beacon_chain::store::BlobRetention;
+
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.store.blob_retention, BlobRetention::DataAvailability)
+        });
+}
+#[test]
+fn blob_retention_epochs_flag() {
+    use beacon_node::beacon_chain::store::BlobRetention;
+
+    CommandLineTest::new()
+        .flag("blob-retention-epochs", Some("16384"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.store.blob_retention, BlobRetention::Epochs(16384))
+        });
+}
+#[test]
+fn blob_retention_epochs_forever() {
+    use beacon_node::beacon_chain::store::BlobRetention;
+
+    CommandLineTest::new()
+        .flag("blob-retention-epochs", Some("forever"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.store.blob_retention, BlobRetention::Forever));
+}
+#[test]
+fn blob_prune_rate_limit_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.store.blob_prune_rate_limit, None));
+}
+#[test]
+fn blob_prune_rate_limit_flag() {
+    CommandLineTest::new()
+        .flag("blob-prune-rate-limit", Some("1048576"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.store.blob_prune_rate_limit, NonZeroU64::new(1048576))
+        });
+}
+#[test]
 fn reconstruct_historic_states_flag() {
     CommandLineTest::new()
         .flag("reconstruct-historic-states", None)
@@ -1992,6 +2785,24 @@ fn epochs_per_migration_override() {
         .with_config(|config| assert_eq!(config.chain.epochs_per_migration, 128));
 }
 #[test]
+fn exit_rebroadcast_epochs_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.exit_rebroadcast_epochs,
+                beacon_node::beacon_chain::exit_broadcasts::DEFAULT_EXIT_REBROADCAST_EPOCHS
+            )
+        });
+}
+#[test]
+fn exit_rebroadcast_epochs_disabled() {
+    CommandLineTest::new()
+        .flag("exit-rebroadcast-epochs", Some("0"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.exit_rebroadcast_epochs, 0));
+}
+#[test]
 fn malicious_withhold_count_flag() {
     CommandLineTest::new()
         .flag("malicious-withhold-count", Some("128"))
@@ -2367,6 +3178,58 @@ fn proposer_re_org_disallowed_offsets_invalid() {
         .run_with_zero_port();
 }
 
+#[test]
+fn late_payload_reveal_threshold_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.chain.late_payload_reveal_threshold, None);
+            assert!(config.chain.late_payload_trusted_fee_recipients.is_empty());
+        });
+}
+
+#[test]
+fn late_payload_reveal_threshold_flag() {
+    CommandLineTest::new()
+        .flag("late-payload-reveal-threshold", Some("1500"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.late_payload_reveal_threshold,
+                Some(Duration::from_millis(1500))
+            )
+        });
+}
+
+#[test]
+fn late_payload_trusted_fee_recipients_flag() {
+    CommandLineTest::new()
+        .flag("late-payload-reveal-threshold", Some("1500"))
+        .flag(
+            "late-payload-trusted-fee-recipients",
+            Some("0x00000000219ab540356cbb839cbe05303d7705fa,0x0000000000000000000000000000000000000001"),
+        )
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.late_payload_trusted_fee_recipients,
+                vec![
+                    Address::from_str("0x00000000219ab540356cbb839cbe05303d7705fa").unwrap(),
+                    Address::from_low_u64_be(1),
+                ]
+            )
+        });
+}
+
+#[test]
+#[should_panic]
+fn late_payload_trusted_fee_recipients_invalid() {
+    CommandLineTest::new()
+        .flag("late-payload-reveal-threshold", Some("1500"))
+        .flag("late-payload-trusted-fee-recipients", Some("0x1234,0xzz"))
+        .run_with_zero_port();
+}
+
 #[test]
 fn monitoring_endpoint() {
     CommandLineTest::new()
@@ -2607,6 +3470,8 @@ fn beacon_processor() {
         .flag("beacon-processor-reprocess-queue-len", Some("3"))
         .flag("beacon-processor-attestation-batch-size", Some("4"))
         .flag("beacon-processor-aggregate-batch-size", Some("5"))
+        .flag("beacon-processor-sync-message-batch-size", Some("6"))
+        .flag("beacon-processor-blob-batch-size", Some("7"))
         .flag("disable-backfill-rate-limiting", None)
         .run_with_zero_port()
         .with_config(|config| {
@@ -2618,12 +3483,112 @@ fn beacon_processor() {
                     max_scheduled_work_queue_len: 3,
                     max_gossip_attestation_batch_size: 4,
                     max_gossip_aggregate_batch_size: 5,
-                    enable_backfill_rate_limiting: false
+                    max_gossip_sync_message_batch_size: 6,
+                    max_gossip_blob_batch_size: 7,
+                    enable_backfill_rate_limiting: false,
+                    load_shedding_threshold: None,
+                    priority_overrides: vec![],
+                    worker_caps: HashMap::new(),
                 }
             )
         });
 }
 
+#[test]
+fn beacon_processor_load_shedding_threshold() {
+    CommandLineTest::new()
+        .flag("beacon-processor-load-shedding-threshold", Some("2500"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.beacon_processor.load_shedding_threshold,
+                Some(Duration::from_millis(2500))
+            )
+        });
+}
+
+#[test]
+fn beacon_processor_priorities() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = dir.path().join("priorities.json");
+    std::fs::write(
+        &path,
+        r#"[{"work": "blocks_by_range_request", "after": "gossip_attestation"}]"#,
+    )
+    .expect("Unable to write priorities file");
+
+    CommandLineTest::new()
+        .flag("beacon-processor-priorities", path.to_str())
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.beacon_processor.priority_overrides,
+                vec![PriorityOverride {
+                    work: WorkType::BlocksByRangeRequest,
+                    before: None,
+                    after: Some(WorkType::GossipAttestation),
+                }]
+            )
+        });
+}
+
+#[test]
+#[should_panic]
+fn beacon_processor_priorities_invalid() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = dir.path().join("priorities.json");
+    std::fs::write(
+        &path,
+        r#"[{"work": "gossip_attestation_batch", "before": "status"}]"#,
+    )
+    .expect("Unable to write priorities file");
+
+    CommandLineTest::new()
+        .flag("beacon-processor-priorities", path.to_str())
+        .run_with_zero_port();
+}
+
+#[test]
+fn beacon_processor_worker_caps() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.beacon_processor.worker_caps.is_empty()));
+    CommandLineTest::new()
+        .flag(
+            "beacon-processor-worker-caps",
+            Some("chain_segment=4,chain_segment_backfill=2"),
+        )
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.beacon_processor.worker_caps,
+                HashMap::from([
+                    (WorkType::ChainSegment, 4),
+                    (WorkType::ChainSegmentBackfill, 2)
+                ])
+            )
+        });
+}
+
+#[test]
+#[should_panic]
+fn beacon_processor_worker_caps_zero() {
+    CommandLineTest::new()
+        .flag("beacon-processor-worker-caps", Some("chain_segment=0"))
+        .run_with_zero_port();
+}
+
+#[test]
+fn rpc_serving_only_flag() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert!(!config.network.rpc_serving_only));
+    CommandLineTest::new()
+        .flag("rpc-serving-only", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.network.rpc_serving_only));
+}
+
 #[test]
 #[should_panic]
 fn beacon_processor_zero_workers() {
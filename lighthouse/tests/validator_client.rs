@@ -113,6 +113,37 @@ fn disable_auto_discover_flag() {
         .with_config(|config| assert!(config.disable_auto_discover));
 }
 
+#[test]
+fn hot_reload_definitions_flag() {
+    CommandLineTest::new()
+        .run()
+        .with_config(|config| assert!(!config.hot_reload_definitions));
+    CommandLineTest::new()
+        .flag("hot-reload-definitions", None)
+        .run()
+        .with_config(|config| assert!(config.hot_reload_definitions));
+}
+
+#[test]
+fn precompute_selection_proofs_flag() {
+    CommandLineTest::new()
+        .run()
+        .with_config(|config| assert!(!config.precompute_selection_proofs));
+    CommandLineTest::new()
+        .flag("precompute-selection-proofs", None)
+        .run()
+        .with_config(|config| assert!(config.precompute_selection_proofs));
+}
+
+#[test]
+#[should_panic]
+fn precompute_selection_proofs_distributed_flag() {
+    CommandLineTest::new()
+        .flag("precompute-selection-proofs", None)
+        .flag("distributed", None)
+        .run();
+}
+
 #[test]
 fn init_slashing_protections_flag() {
     CommandLineTest::new()
@@ -467,6 +498,24 @@ fn no_builder_boost_factor_flag() {
         .with_config(|config| assert_eq!(config.validator_store.builder_boost_factor, None));
 }
 #[test]
+fn builder_min_bid_gwei_flag() {
+    CommandLineTest::new()
+        .flag("builder-min-bid-gwei", Some("50000000"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.validator_store.builder_min_bid_gwei,
+                Some(50_000_000)
+            )
+        });
+}
+#[test]
+fn no_builder_min_bid_gwei_flag() {
+    CommandLineTest::new()
+        .run()
+        .with_config(|config| assert_eq!(config.validator_store.builder_min_bid_gwei, None));
+}
+#[test]
 fn prefer_builder_proposals_flag() {
     CommandLineTest::new()
         .flag("prefer-builder-proposals", None)
@@ -661,6 +710,42 @@ fn validator_web3_signer_keep_alive_override() {
         });
 }
 
+#[test]
+fn validator_web3_signer_max_concurrent_requests_default() {
+    CommandLineTest::new().run().with_config(|config| {
+        assert_eq!(
+            config
+                .initialized_validators
+                .web3_signer_max_concurrent_requests,
+            None
+        );
+    });
+}
+
+#[test]
+fn validator_web3_signer_max_concurrent_requests_override() {
+    CommandLineTest::new()
+        .flag("web3-signer-max-concurrent-requests", Some("16"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config
+                    .initialized_validators
+                    .web3_signer_max_concurrent_requests,
+                Some(16)
+            );
+        });
+}
+
+#[test]
+#[should_panic]
+fn validator_web3_signer_max_concurrent_requests_zero() {
+    CommandLineTest::new()
+        .flag("web3-signer-max-concurrent-requests", Some("0"))
+        .run()
+        .with_config(|_| {});
+}
+
 #[test]
 fn validator_proposer_nodes_default_empty() {
     CommandLineTest::new().run().with_config(|config| {
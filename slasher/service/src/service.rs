@@ -11,10 +11,11 @@ use slasher::{
 use slog::{debug, error, info, trace, warn, Logger};
 use slot_clock::SlotClock;
 use state_processing::{
-    per_block_processing::errors::{
-        AttesterSlashingInvalid, BlockOperationError, ProposerSlashingInvalid,
+    per_block_processing::{
+        errors::{AttesterSlashingInvalid, BlockOperationError, ProposerSlashingInvalid},
+        verify_attester_slashing, verify_proposer_slashing,
     },
-    VerifyOperation,
+    VerifyOperation, VerifySignatures,
 };
 use std::sync::mpsc::{sync_channel, Receiver, SyncSender, TrySendError};
 use std::sync::Arc;
@@ -149,6 +150,7 @@ impl<T: BeaconChainTypes> SlasherService<T> {
 
             // Provide slashings to the beacon chain, and optionally publish them.
             Self::process_slashings(&beacon_chain, &slasher, &network_sender);
+            Self::prune_detected_slashings(&beacon_chain, &slasher);
 
             let database_size = size_of_dir(&slasher.config().database_path);
             metrics::set_gauge(&SLASHER_DATABASE_SIZE, database_size as i64);
@@ -176,6 +178,44 @@ impl<T: BeaconChainTypes> SlasherService<T> {
         Self::process_proposer_slashings(beacon_chain, slasher, network_sender);
     }
 
+    /// Forget detected slashings which are no longer valid at the head, e.g., because they have
+    /// been included on chain.
+    fn prune_detected_slashings(beacon_chain: &BeaconChain<T>, slasher: &Slasher<T::EthSpec>) {
+        let spec = &beacon_chain.spec;
+        let result = beacon_chain.with_head(|head| {
+            let state = &head.beacon_state;
+            let result = slasher.retain_detected_slashings(
+                |slashing| {
+                    verify_attester_slashing(
+                        state,
+                        slashing.to_ref(),
+                        VerifySignatures::False,
+                        spec,
+                    )
+                    .is_ok()
+                },
+                |slashing| {
+                    verify_proposer_slashing(slashing, state, VerifySignatures::False, spec).is_ok()
+                },
+            );
+            Ok::<_, BeaconChainError>(result)
+        });
+
+        match result {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => error!(
+                slasher.log(),
+                "Failed to prune detected slashings";
+                "error" => ?e,
+            ),
+            Err(e) => debug!(
+                slasher.log(),
+                "Unable to prune detected slashings";
+                "error" => ?e,
+            ),
+        }
+    }
+
     fn process_attester_slashings(
         beacon_chain: &BeaconChain<T>,
         slasher: &Slasher<T::EthSpec>,
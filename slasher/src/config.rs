@@ -1,4 +1,5 @@
 use crate::Error;
+use lockfile::Lockfile;
 use serde::{Deserialize, Serialize};
 use std::num::NonZeroUsize;
 use std::path::PathBuf;
@@ -27,7 +28,12 @@ pub const DEFAULT_BACKEND: DatabaseBackend = DatabaseBackend::Disabled;
 pub const MAX_HISTORY_LENGTH: usize = 1 << 16;
 pub const MEGABYTE: usize = 1 << 20;
 pub const MDBX_DATA_FILENAME: &str = "mdbx.dat";
+pub const LMDB_DATA_FILENAME: &str = "data.mdb";
 pub const REDB_DATA_FILENAME: &str = "slasher.redb";
+/// Held while the database is open, so that it can't be migrated from under a running slasher.
+pub const LOCK_FILENAME: &str = "slasher.lock";
+/// Records the destination backend of a migration until it completes.
+pub const MIGRATION_FILENAME: &str = "migration_in_progress";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -176,6 +182,43 @@ impl Config {
             .copied()
     }
 
+    /// Return the names of the backends with a data file in the database directory.
+    ///
+    /// Backends which are not compiled in are still detected, so that their data is never
+    /// mistaken for an empty database.
+    pub fn backends_on_disk(&self) -> Vec<&'static str> {
+        [
+            ("mdbx", MDBX_DATA_FILENAME),
+            ("lmdb", LMDB_DATA_FILENAME),
+            ("redb", REDB_DATA_FILENAME),
+        ]
+        .into_iter()
+        .filter(|(_, filename)| self.database_path.join(filename).exists())
+        .map(|(backend, _)| backend)
+        .collect()
+    }
+
+    /// Take an exclusive lock on the database directory, creating it if necessary.
+    ///
+    /// Fails if the database is already open in another process, e.g. a running beacon node.
+    pub fn lock(&self) -> Result<Lockfile, Error> {
+        std::fs::create_dir_all(&self.database_path)?;
+        Lockfile::new(self.database_path.join(LOCK_FILENAME)).map_err(Error::DatabaseLockError)
+    }
+
+    /// Check that any existing database on disk was written by the configured backend.
+    pub fn validate_backend(&self) -> Result<(), Error> {
+        let on_disk = self.backends_on_disk();
+        if on_disk.is_empty() || on_disk.contains(&self.backend.to_string().as_str()) {
+            Ok(())
+        } else {
+            Err(Error::DatabaseBackendMismatch {
+                configured: self.backend,
+                on_disk,
+            })
+        }
+    }
+
     pub fn override_backend(&mut self) -> DatabaseBackendOverride {
         let mdbx_path = self.database_path.join(MDBX_DATA_FILENAME);
 
@@ -9,6 +9,7 @@ use crate::{
 };
 use byteorder::{BigEndian, ByteOrder};
 use interface::{Environment, OpenDatabases, RwTransaction};
+use lockfile::Lockfile;
 use lru::LruCache;
 use parking_lot::Mutex;
 use serde::de::DeserializeOwned;
@@ -20,7 +21,8 @@ use std::marker::PhantomData;
 use std::sync::Arc;
 use tree_hash::TreeHash;
 use types::{
-    AggregateSignature, AttestationData, ChainSpec, Epoch, EthSpec, Hash256, IndexedAttestation,
+    AggregateSignature, AttestationData, AttesterSlashing, AttesterSlashingOnDisk,
+    AttesterSlashingRefOnDisk, ChainSpec, Epoch, EthSpec, Hash256, IndexedAttestation,
     IndexedAttestationBase, IndexedAttestationElectra, ProposerSlashing, SignedBeaconBlockHeader,
     Slot, VariableList,
 };
@@ -51,9 +53,15 @@ const MAX_TARGETS_DB: &str = "max_targets";
 const CURRENT_EPOCHS_DB: &str = "current_epochs";
 /// Map from `(slot, validator_index)` to `SignedBeaconBlockHeader`.
 const PROPOSERS_DB: &str = "proposers";
+/// Map from `(target_epoch, slashing_root)` to `AttesterSlashingOnDisk`, for attester slashings
+/// which have been found but are not yet known to be included on chain.
+const ATTESTER_SLASHINGS_DB: &str = "attester_slashings";
+/// Map from `(slot, validator_index)` to `ProposerSlashing`, for proposer slashings which have been
+/// found but are not yet known to be included on chain.
+const PROPOSER_SLASHINGS_DB: &str = "proposer_slashings";
 
 /// The number of DBs for MDBX to use (equal to the number of DBs defined above).
-const MAX_NUM_DBS: usize = 9;
+const MAX_NUM_DBS: usize = 11;
 
 /// Constant key under which the schema version is stored in the `metadata_db`.
 const METADATA_VERSION_KEY: &[u8] = &[0];
@@ -66,6 +74,9 @@ const CURRENT_EPOCH_KEY_SIZE: usize = 8;
 const INDEXED_ATTESTATION_ID_SIZE: usize = 6;
 const INDEXED_ATTESTATION_ID_KEY_SIZE: usize = 40;
 
+/// The number of entries written per transaction when copying between databases.
+const COPY_BATCH_SIZE: usize = 100_000;
+
 #[derive(Debug)]
 pub struct SlasherDB<E: EthSpec> {
     pub(crate) env: &'static Environment,
@@ -74,6 +85,8 @@ pub struct SlasherDB<E: EthSpec> {
     attestation_root_cache: Mutex<LruCache<IndexedAttestationId, Hash256>>,
     pub(crate) config: Arc<Config>,
     pub(crate) spec: Arc<ChainSpec>,
+    /// Exclusive lock on the database directory, held for as long as the database is open.
+    _lockfile: Option<Lockfile>,
     _phantom: PhantomData<E>,
 }
 
@@ -288,6 +301,37 @@ fn ssz_decode<T: Decode>(bytes: Cow<[u8]>) -> Result<T, Error> {
 
 impl<E: EthSpec> SlasherDB<E> {
     pub fn open(config: Arc<Config>, spec: Arc<ChainSpec>, log: Logger) -> Result<Self, Error> {
+        config.validate_backend()?;
+
+        let lockfile = config.lock()?;
+        let mut db = Self::open_unchecked(config, spec, log)?;
+        db._lockfile = Some(lockfile);
+
+        db = db.migrate()?;
+
+        let mut txn = db.begin_rw_txn()?;
+        if let Some(on_disk_config) = db.load_config(&mut txn)? {
+            let current_disk_config = db.config.disk_config();
+            if current_disk_config != on_disk_config {
+                return Err(Error::ConfigIncompatible {
+                    on_disk_config,
+                    config: current_disk_config,
+                });
+            }
+        }
+        txn.commit()?;
+
+        Ok(db)
+    }
+
+    /// Open the database without checking the backend, schema or config of the data on disk.
+    ///
+    /// Only used when copying a database between backends, which transfers the data verbatim.
+    pub(crate) fn open_unchecked(
+        config: Arc<Config>,
+        spec: Arc<ChainSpec>,
+        log: Logger,
+    ) -> Result<Self, Error> {
         info!(log, "Opening slasher database"; "backend" => %config.backend);
 
         std::fs::create_dir_all(&config.database_path)?;
@@ -305,30 +349,15 @@ impl<E: EthSpec> SlasherDB<E> {
 
         let attestation_root_cache = Mutex::new(LruCache::new(config.attestation_root_cache_size));
 
-        let mut db = Self {
+        Ok(Self {
             env,
             databases,
             attestation_root_cache,
             config,
             spec,
+            _lockfile: None,
             _phantom: PhantomData,
-        };
-
-        db = db.migrate()?;
-
-        let mut txn = db.begin_rw_txn()?;
-        if let Some(on_disk_config) = db.load_config(&mut txn)? {
-            let current_disk_config = db.config.disk_config();
-            if current_disk_config != on_disk_config {
-                return Err(Error::ConfigIncompatible {
-                    on_disk_config,
-                    config: current_disk_config,
-                });
-            }
-        }
-        txn.commit()?;
-
-        Ok(db)
+        })
     }
 
     pub fn begin_rw_txn(&self) -> Result<RwTransaction, Error> {
@@ -718,6 +747,109 @@ impl<E: EthSpec> SlasherDB<E> {
         }
     }
 
+    /// Store an attester slashing which has been found, so that it may be exported until it is
+    /// known to be included on chain.
+    pub fn store_attester_slashing(
+        &self,
+        txn: &mut RwTransaction<'_>,
+        attester_slashing: &AttesterSlashing<E>,
+    ) -> Result<(), Error> {
+        let slashing = attester_slashing.to_ref();
+        let target_epoch = std::cmp::max(
+            slashing.attestation_1().data().target.epoch,
+            slashing.attestation_2().data().target.epoch,
+        );
+        txn.put(
+            &self.databases.attester_slashings_db,
+            IndexedAttestationIdKey::new(target_epoch, attester_slashing.tree_hash_root()),
+            AttesterSlashingRefOnDisk::from(slashing).as_ssz_bytes(),
+        )?;
+        Ok(())
+    }
+
+    /// Store a proposer slashing which has been found, so that it may be exported until it is
+    /// known to be included on chain.
+    pub fn store_proposer_slashing(
+        &self,
+        txn: &mut RwTransaction<'_>,
+        proposer_slashing: &ProposerSlashing,
+    ) -> Result<(), Error> {
+        let header = &proposer_slashing.signed_header_1.message;
+        txn.put(
+            &self.databases.proposer_slashings_db,
+            ProposerKey::new(header.proposer_index, header.slot),
+            proposer_slashing.as_ssz_bytes(),
+        )?;
+        Ok(())
+    }
+
+    /// Load every attester slashing which has been found and not yet pruned.
+    pub fn get_attester_slashings(
+        &self,
+        txn: &mut RwTransaction<'_>,
+    ) -> Result<Vec<AttesterSlashing<E>>, Error> {
+        Ok(self
+            .load_all::<AttesterSlashingOnDisk<E>>(txn, &self.databases.attester_slashings_db)?
+            .into_iter()
+            .map(|(_, slashing)| slashing.into())
+            .collect())
+    }
+
+    /// Load every proposer slashing which has been found and not yet pruned.
+    pub fn get_proposer_slashings(
+        &self,
+        txn: &mut RwTransaction<'_>,
+    ) -> Result<Vec<ProposerSlashing>, Error> {
+        Ok(self
+            .load_all::<ProposerSlashing>(txn, &self.databases.proposer_slashings_db)?
+            .into_iter()
+            .map(|(_, slashing)| slashing)
+            .collect())
+    }
+
+    /// Delete the stored slashings for which `keep_attester` or `keep_proposer` return `false`,
+    /// e.g., because they have been included on chain.
+    pub fn retain_slashings(
+        &self,
+        txn: &mut RwTransaction<'_>,
+        mut keep_attester: impl FnMut(&AttesterSlashing<E>) -> bool,
+        mut keep_proposer: impl FnMut(&ProposerSlashing) -> bool,
+    ) -> Result<(), Error> {
+        let attester_slashings_db = &self.databases.attester_slashings_db;
+        for (key, slashing) in
+            self.load_all::<AttesterSlashingOnDisk<E>>(txn, attester_slashings_db)?
+        {
+            if !keep_attester(&slashing.into()) {
+                txn.del(attester_slashings_db, key)?;
+            }
+        }
+
+        let proposer_slashings_db = &self.databases.proposer_slashings_db;
+        for (key, slashing) in self.load_all::<ProposerSlashing>(txn, proposer_slashings_db)? {
+            if !keep_proposer(&slashing) {
+                txn.del(proposer_slashings_db, key)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Decode every entry of `db`, returning each along with its key.
+    fn load_all<T: Decode>(
+        &self,
+        txn: &mut RwTransaction<'_>,
+        db: &Database<'static>,
+    ) -> Result<Vec<(Vec<u8>, T)>, Error> {
+        let mut cursor = txn.cursor(db)?;
+        let mut entries = vec![];
+        let mut has_entry = cursor.first_key()?.is_some();
+        while has_entry {
+            let (key, value) = cursor.get_current()?.ok_or(Error::MissingKey)?;
+            entries.push((key.into_owned(), ssz_decode(value)?));
+            has_entry = cursor.next_key()?.is_some();
+        }
+        Ok(entries)
+    }
+
     /// Attempt to prune the database, deleting old blocks and attestations.
     pub fn prune(&self, current_epoch: Epoch) -> Result<(), Error> {
         let mut txn = self.begin_rw_txn()?;
@@ -734,6 +866,38 @@ impl<E: EthSpec> SlasherDB<E> {
     ) -> Result<(), Error> {
         self.prune_proposers(current_epoch, txn)?;
         self.prune_indexed_attestations(current_epoch, txn)?;
+        self.prune_slashings(current_epoch, txn)?;
+        Ok(())
+    }
+
+    /// Delete slashings for blocks and attestations older than the history length.
+    fn prune_slashings(
+        &self,
+        current_epoch: Epoch,
+        txn: &mut RwTransaction<'_>,
+    ) -> Result<(), Error> {
+        let min_epoch = current_epoch
+            .saturating_add(1u64)
+            .saturating_sub(self.config.history_length as u64);
+        let min_slot = min_epoch.start_slot(E::slots_per_epoch());
+
+        let mut cursor = txn.cursor(&self.databases.attester_slashings_db)?;
+        if cursor.first_key()?.is_some() {
+            cursor.delete_while(|key| {
+                let (target_epoch, _) = IndexedAttestationIdKey::parse(Cow::from(key))?;
+                Ok(target_epoch < min_epoch)
+            })?;
+        }
+        drop(cursor);
+
+        let mut cursor = txn.cursor(&self.databases.proposer_slashings_db)?;
+        if cursor.first_key()?.is_some() {
+            cursor.delete_while(|key| {
+                let (slot, _) = ProposerKey::parse(Cow::from(key))?;
+                Ok(slot < min_slot)
+            })?;
+        }
+
         Ok(())
     }
 
@@ -815,9 +979,10 @@ impl<E: EthSpec> SlasherDB<E> {
     /// Delete all data from the database, essentially re-initialising it.
     ///
     /// We use this reset pattern in tests instead of leaking tonnes of file descriptors and
-    /// exhausting our allocation by creating (and leaking) databases.
+    /// exhausting our allocation by creating (and leaking) databases. It is also used to discard
+    /// the partial copy left behind by an interrupted backend migration.
     ///
-    /// THIS FUNCTION SHOULD ONLY BE USED IN TESTS.
+    /// THIS FUNCTION SHOULD NOT BE USED ON A LIVE DATABASE.
     pub fn reset(&self) -> Result<(), Error> {
         // Clear the cache(s) first.
         self.attestation_root_cache.lock().clear();
@@ -832,6 +997,8 @@ impl<E: EthSpec> SlasherDB<E> {
             max_targets_db,
             current_epochs_db,
             proposers_db,
+            attester_slashings_db,
+            proposer_slashings_db,
             metadata_db,
         } = &self.databases;
         let mut txn = self.begin_rw_txn()?;
@@ -843,10 +1010,78 @@ impl<E: EthSpec> SlasherDB<E> {
         self.reset_db(&mut txn, max_targets_db)?;
         self.reset_db(&mut txn, current_epochs_db)?;
         self.reset_db(&mut txn, proposers_db)?;
+        self.reset_db(&mut txn, attester_slashings_db)?;
+        self.reset_db(&mut txn, proposer_slashings_db)?;
         self.reset_db(&mut txn, metadata_db)?;
         txn.commit()
     }
 
+    /// Copy every entry of every database into `dest`, which may use a different backend.
+    ///
+    /// Returns the number of entries copied.
+    pub fn copy_into(&self, dest: &Self) -> Result<usize, Error> {
+        // Pattern match to avoid missing any database.
+        let OpenDatabases {
+            indexed_attestation_db,
+            indexed_attestation_id_db,
+            attesters_db,
+            attesters_max_targets_db,
+            min_targets_db,
+            max_targets_db,
+            current_epochs_db,
+            proposers_db,
+            attester_slashings_db,
+            proposer_slashings_db,
+            metadata_db,
+        } = &self.databases;
+        let databases = [
+            (
+                indexed_attestation_db,
+                &dest.databases.indexed_attestation_db,
+            ),
+            (
+                indexed_attestation_id_db,
+                &dest.databases.indexed_attestation_id_db,
+            ),
+            (attesters_db, &dest.databases.attesters_db),
+            (
+                attesters_max_targets_db,
+                &dest.databases.attesters_max_targets_db,
+            ),
+            (min_targets_db, &dest.databases.min_targets_db),
+            (max_targets_db, &dest.databases.max_targets_db),
+            (current_epochs_db, &dest.databases.current_epochs_db),
+            (proposers_db, &dest.databases.proposers_db),
+            (attester_slashings_db, &dest.databases.attester_slashings_db),
+            (proposer_slashings_db, &dest.databases.proposer_slashings_db),
+            (metadata_db, &dest.databases.metadata_db),
+        ];
+
+        let mut txn = self.begin_rw_txn()?;
+        let mut dest_txn = dest.begin_rw_txn()?;
+        let mut num_copied = 0;
+
+        for (db, dest_db) in databases {
+            let mut cursor = txn.cursor(db)?;
+            let mut has_entry = cursor.first_key()?.is_some();
+            while has_entry {
+                let (key, value) = cursor.get_current()?.ok_or(Error::MissingKey)?;
+                dest_txn.put(dest_db, key, value)?;
+                num_copied += 1;
+
+                // Commit periodically to bound the size of the destination transaction.
+                if num_copied % COPY_BATCH_SIZE == 0 {
+                    dest_txn.commit()?;
+                    dest_txn = dest.begin_rw_txn()?;
+                }
+                has_entry = cursor.next_key()?.is_some();
+            }
+        }
+        dest_txn.commit()?;
+
+        Ok(num_copied)
+    }
+
     fn reset_db(&self, txn: &mut RwTransaction<'_>, db: &Database<'static>) -> Result<(), Error> {
         let mut cursor = txn.cursor(db)?;
         if cursor.first_key()?.is_none() {
@@ -53,6 +53,8 @@ pub struct OpenDatabases<'env> {
     pub max_targets_db: Database<'env>,
     pub current_epochs_db: Database<'env>,
     pub proposers_db: Database<'env>,
+    pub attester_slashings_db: Database<'env>,
+    pub proposer_slashings_db: Database<'env>,
     pub metadata_db: Database<'env>,
 }
 
@@ -231,6 +233,19 @@ impl Cursor<'_> {
         }
     }
 
+    /// Return the key and value at the cursor's current position.
+    pub fn get_current(&mut self) -> Result<Option<(Key, Value)>, Error> {
+        match self {
+            #[cfg(feature = "mdbx")]
+            Cursor::Mdbx(cursor) => cursor.get_current(),
+            #[cfg(feature = "lmdb")]
+            Cursor::Lmdb(cursor) => cursor.get_current(),
+            #[cfg(feature = "redb")]
+            Cursor::Redb(cursor) => cursor.get_current(),
+            _ => Err(Error::MismatchedDatabaseVariant),
+        }
+    }
+
     pub fn delete_current(&mut self) -> Result<(), Error> {
         match self {
             #[cfg(feature = "mdbx")]
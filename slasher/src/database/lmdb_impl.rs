@@ -1,7 +1,7 @@
 #![cfg(feature = "lmdb")]
 
 use crate::{
-    config::MEGABYTE,
+    config::{LMDB_DATA_FILENAME, MEGABYTE},
     database::{
         interface::{Key, Value},
         *,
@@ -58,6 +58,12 @@ impl Environment {
             .env
             .create_db(Some(CURRENT_EPOCHS_DB), Self::db_flags())?;
         let proposers_db = self.env.create_db(Some(PROPOSERS_DB), Self::db_flags())?;
+        let attester_slashings_db = self
+            .env
+            .create_db(Some(ATTESTER_SLASHINGS_DB), Self::db_flags())?;
+        let proposer_slashings_db = self
+            .env
+            .create_db(Some(PROPOSER_SLASHINGS_DB), Self::db_flags())?;
         let metadata_db = self.env.create_db(Some(METADATA_DB), Self::db_flags())?;
 
         let wrap = |db| {
@@ -76,6 +82,8 @@ impl Environment {
             max_targets_db: wrap(max_targets_db),
             current_epochs_db: wrap(current_epochs_db),
             proposers_db: wrap(proposers_db),
+            attester_slashings_db: wrap(attester_slashings_db),
+            proposer_slashings_db: wrap(proposer_slashings_db),
             metadata_db: wrap(metadata_db),
         })
     }
@@ -87,7 +95,7 @@ impl Environment {
 
     pub fn filenames(&self, config: &Config) -> Vec<PathBuf> {
         vec![
-            config.database_path.join("data.mdb"),
+            config.database_path.join(LMDB_DATA_FILENAME),
             config.database_path.join("lock.mdb"),
         ]
     }
@@ -54,6 +54,8 @@ impl Environment {
         txn.create_db(MAX_TARGETS_DB)?;
         txn.create_db(CURRENT_EPOCHS_DB)?;
         txn.create_db(PROPOSERS_DB)?;
+        txn.create_db(ATTESTER_SLASHINGS_DB)?;
+        txn.create_db(PROPOSER_SLASHINGS_DB)?;
         txn.create_db(METADATA_DB)?;
 
         // This is all rather nasty
@@ -73,6 +75,8 @@ impl Environment {
             max_targets_db: next_db(),
             current_epochs_db: next_db(),
             proposers_db: next_db(),
+            attester_slashings_db: next_db(),
+            proposer_slashings_db: next_db(),
             metadata_db: next_db(),
         })
     }
@@ -60,6 +60,8 @@ impl Environment {
         let max_targets_db = self.create_table(MAX_TARGETS_DB)?;
         let current_epochs_db = self.create_table(CURRENT_EPOCHS_DB)?;
         let proposers_db = self.create_table(PROPOSERS_DB)?;
+        let attester_slashings_db = self.create_table(ATTESTER_SLASHINGS_DB)?;
+        let proposer_slashings_db = self.create_table(PROPOSER_SLASHINGS_DB)?;
         let metadata_db = self.create_table(METADATA_DB)?;
 
         Ok(OpenDatabases {
@@ -71,6 +73,8 @@ impl Environment {
             max_targets_db,
             current_epochs_db,
             proposers_db,
+            attester_slashings_db,
+            proposer_slashings_db,
             metadata_db,
         })
     }
@@ -1,4 +1,5 @@
-use crate::config::{Config, DiskConfig};
+use crate::config::{Config, DatabaseBackend, DiskConfig};
+use lockfile::LockfileError;
 use std::io;
 use types::Epoch;
 
@@ -11,6 +12,21 @@ pub enum Error {
     #[cfg(feature = "redb")]
     DatabaseRedbError(redb::Error),
     SlasherDatabaseBackendDisabled,
+    /// The database on disk was written by a different backend to the one configured.
+    DatabaseBackendMismatch {
+        configured: DatabaseBackend,
+        on_disk: Vec<&'static str>,
+    },
+    /// A backend migration was requested from a backend with no database on disk.
+    MigrationSourceMissing(DatabaseBackend),
+    /// A backend migration was requested to a backend which already has a database on disk.
+    MigrationDestinationExists(DatabaseBackend),
+    /// An interrupted migration to a different backend must be completed first.
+    MigrationInterrupted {
+        to: String,
+    },
+    /// The database is open in another process.
+    DatabaseLockError(LockfileError),
     MismatchedDatabaseVariant,
     DatabaseIOError(io::Error),
     DatabasePermissionsError(filesystem::Error),
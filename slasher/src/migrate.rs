@@ -1,5 +1,11 @@
-use crate::{database::CURRENT_SCHEMA_VERSION, Error, SlasherDB};
-use types::EthSpec;
+use crate::{
+    config::MIGRATION_FILENAME, database::CURRENT_SCHEMA_VERSION, Config, DatabaseBackend, Error,
+    SlasherDB,
+};
+use slog::{info, Logger};
+use std::fs;
+use std::sync::Arc;
+use types::{ChainSpec, EthSpec};
 
 impl<E: EthSpec> SlasherDB<E> {
     /// If the database exists, and has a schema, attempt to migrate it to the current version.
@@ -27,3 +33,87 @@ impl<E: EthSpec> SlasherDB<E> {
         }
     }
 }
+
+impl<E: EthSpec> SlasherDB<E> {
+    /// Convert the database at `config.database_path` from `config.backend` to the `to` backend.
+    ///
+    /// The data is copied verbatim, so the schema and disk config of the database are preserved
+    /// regardless of the parameters in `config`. The files of the original backend are deleted
+    /// once every entry has been copied, so that the slasher can't subsequently be started
+    /// against the stale database. Returns the number of entries copied.
+    ///
+    /// The database directory is locked for the duration, so the migration is refused while the
+    /// database is open elsewhere, e.g. by a running beacon node. An interrupted migration is
+    /// recorded on disk, and running it again discards the partial copy and starts afresh.
+    pub fn migrate_backend(
+        config: Config,
+        to: DatabaseBackend,
+        spec: Arc<ChainSpec>,
+        log: Logger,
+    ) -> Result<usize, Error> {
+        let on_disk = config.backends_on_disk();
+        let source_exists = on_disk.contains(&config.backend.to_string().as_str());
+        let marker_path = config.database_path.join(MIGRATION_FILENAME);
+        let interrupted = marker_path.exists();
+
+        if !source_exists && !interrupted {
+            return Err(Error::MigrationSourceMissing(config.backend));
+        }
+        let _lockfile = config.lock()?;
+
+        if interrupted {
+            let marker_to = fs::read_to_string(&marker_path)?;
+            if marker_to != to.to_string() {
+                return Err(Error::MigrationInterrupted { to: marker_to });
+            }
+            info!(
+                log,
+                "Resuming interrupted slasher database migration";
+                "from" => %config.backend,
+                "to" => %to,
+            );
+        } else if on_disk.contains(&to.to_string().as_str()) {
+            return Err(Error::MigrationDestinationExists(to));
+        } else {
+            fs::write(&marker_path, to.to_string())?;
+        }
+
+        // The source is only deleted once the copy is complete, so if it's gone the interrupted
+        // migration only failed to clean up.
+        let mut num_copied = 0;
+        if source_exists {
+            let source = Self::open_unchecked(Arc::new(config.clone()), spec.clone(), log.clone())?;
+            let dest_config = Config {
+                backend: to,
+                ..config
+            };
+            let dest = Self::open_unchecked(Arc::new(dest_config), spec, log.clone())?;
+            if interrupted {
+                dest.reset()?;
+            }
+
+            info!(
+                log,
+                "Copying slasher database";
+                "from" => %source.config.backend,
+                "to" => %to,
+            );
+            num_copied = source.copy_into(&dest)?;
+
+            for path in source.env.filenames(&source.config) {
+                if path.exists() {
+                    fs::remove_file(&path)?;
+                }
+            }
+        }
+        fs::remove_file(&marker_path)?;
+
+        info!(
+            log,
+            "Slasher database migrated";
+            "entries" => num_copied,
+            "backend" => %to,
+        );
+        Ok(num_copied)
+    }
+}
@@ -76,6 +76,41 @@ impl<E: EthSpec> Slasher<E> {
         std::mem::take(&mut self.proposer_slashings.lock())
     }
 
+    /// Return all attester slashings found which are not yet known to be included on chain.
+    ///
+    /// Unlike `get_attester_slashings` these are read from the database, so they are not drained
+    /// when harvested and persist across restarts.
+    pub fn detected_attester_slashings(&self) -> Result<Vec<AttesterSlashing<E>>, Error> {
+        let mut txn = self.db.begin_rw_txn()?;
+        let slashings = self.db.get_attester_slashings(&mut txn)?;
+        txn.commit()?;
+        Ok(slashings)
+    }
+
+    /// Return all proposer slashings found which are not yet known to be included on chain.
+    ///
+    /// Unlike `get_proposer_slashings` these are read from the database, so they are not drained
+    /// when harvested and persist across restarts.
+    pub fn detected_proposer_slashings(&self) -> Result<Vec<ProposerSlashing>, Error> {
+        let mut txn = self.db.begin_rw_txn()?;
+        let slashings = self.db.get_proposer_slashings(&mut txn)?;
+        txn.commit()?;
+        Ok(slashings)
+    }
+
+    /// Forget the detected slashings for which `keep_attester` or `keep_proposer` return `false`,
+    /// e.g., because they have been included on chain.
+    pub fn retain_detected_slashings(
+        &self,
+        keep_attester: impl FnMut(&AttesterSlashing<E>) -> bool,
+        keep_proposer: impl FnMut(&ProposerSlashing) -> bool,
+    ) -> Result<(), Error> {
+        let mut txn = self.db.begin_rw_txn()?;
+        self.db
+            .retain_slashings(&mut txn, keep_attester, keep_proposer)?;
+        txn.commit()
+    }
+
     pub fn config(&self) -> &Config {
         &self.config
     }
@@ -131,6 +166,9 @@ impl<E: EthSpec> Slasher<E> {
                 "Found {} new proposer slashings!",
                 slashings.len(),
             );
+            for slashing in &slashings {
+                self.db.store_proposer_slashing(txn, slashing)?;
+            }
             self.proposer_slashings.lock().extend(slashings);
         }
 
@@ -245,6 +283,9 @@ impl<E: EthSpec> Slasher<E> {
                             slashings.len()
                         );
                     }
+                    for slashing in &slashings {
+                        self.db.store_attester_slashing(txn, slashing)?;
+                    }
                     self.attester_slashings.lock().extend(slashings);
                 }
                 Err(e) => {
@@ -275,6 +316,9 @@ impl<E: EthSpec> Slasher<E> {
                         slashings.len()
                     );
                 }
+                for slashing in &slashings {
+                    self.db.store_attester_slashing(txn, slashing)?;
+                }
                 self.attester_slashings.lock().extend(slashings);
             }
             Err(e) => {
@@ -288,6 +288,14 @@ fn slasher_test(
 
     assert_eq!(&slashings, expected);
 
+    // The slashings are also stored in the database, retaining the format of their fork.
+    let detected = slasher
+        .detected_attester_slashings()
+        .unwrap()
+        .into_iter()
+        .collect::<HashSet<_>>();
+    assert_eq!(&detected, expected);
+
     // Pruning should not error.
     slasher.prune_database(current_epoch).unwrap();
     // windows won't delete the temporary directory if you don't do this..
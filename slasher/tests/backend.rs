@@ -1,7 +1,13 @@
 #![cfg(feature = "lmdb")]
 
-use slasher::{config::MDBX_DATA_FILENAME, Config, DatabaseBackend, DatabaseBackendOverride};
+use logging::test_logger;
+use slasher::{
+    config::{LMDB_DATA_FILENAME, MDBX_DATA_FILENAME},
+    test_utils::{chain_spec, E},
+    Config, DatabaseBackend, DatabaseBackendOverride, Error, SlasherDB,
+};
 use std::fs::File;
+use std::sync::Arc;
 use tempfile::tempdir;
 
 #[test]
@@ -55,3 +61,176 @@ fn failed_override_with_existing_mdbx_db() {
     );
     assert_eq!(config.backend, DatabaseBackend::Lmdb);
 }
+
+#[test]
+fn mismatched_backend_on_disk() {
+    let tempdir = tempdir().unwrap();
+    let config = Config::new(tempdir.path().into());
+    assert_eq!(config.backend, DatabaseBackend::Lmdb);
+
+    File::create(config.database_path.join(MDBX_DATA_FILENAME)).unwrap();
+
+    assert!(matches!(
+        config.validate_backend(),
+        Err(Error::DatabaseBackendMismatch { on_disk, .. }) if on_disk == vec!["mdbx"]
+    ));
+    assert!(matches!(
+        SlasherDB::<E>::open(Arc::new(config), chain_spec(), test_logger()),
+        Err(Error::DatabaseBackendMismatch { .. })
+    ));
+}
+
+#[test]
+fn matching_backend_on_disk() {
+    let tempdir = tempdir().unwrap();
+    let config = Config::new(tempdir.path().into());
+    config.validate_backend().unwrap();
+
+    File::create(config.database_path.join(LMDB_DATA_FILENAME)).unwrap();
+    config.validate_backend().unwrap();
+}
+
+#[test]
+#[cfg(all(feature = "lmdb", feature = "redb"))]
+fn copy_between_backends() {
+    use slasher::test_utils::block;
+
+    let source_dir = tempdir().unwrap();
+    let dest_dir = tempdir().unwrap();
+    let spec = chain_spec();
+
+    let source_config = Arc::new(Config::new(source_dir.path().into()));
+    let source = SlasherDB::<E>::open(source_config.clone(), spec.clone(), test_logger()).unwrap();
+
+    let mut dest_config = Config::new(dest_dir.path().into());
+    dest_config.backend = DatabaseBackend::Redb;
+    let dest = SlasherDB::<E>::open(Arc::new(dest_config), spec, test_logger()).unwrap();
+
+    let blocks = (0..10).map(|slot| block(slot, slot, 0)).collect::<Vec<_>>();
+    let mut txn = source.begin_rw_txn().unwrap();
+    source.store_config(&source_config, &mut txn).unwrap();
+    for block in &blocks {
+        source
+            .check_or_insert_block_proposal(&mut txn, block.clone())
+            .unwrap();
+    }
+    txn.commit().unwrap();
+
+    assert_eq!(source.copy_into(&dest).unwrap(), blocks.len() + 1);
+
+    let mut txn = dest.begin_rw_txn().unwrap();
+    for block in &blocks {
+        let proposal = dest
+            .get_block_proposal(&mut txn, block.message.proposer_index, block.message.slot)
+            .unwrap();
+        assert_eq!(proposal.as_ref(), Some(block));
+    }
+    assert_eq!(
+        dest.load_config::<Config>(&mut txn)
+            .unwrap()
+            .map(|config| config.disk_config()),
+        Some(source_config.disk_config())
+    );
+}
+
+#[test]
+#[cfg(feature = "redb")]
+fn migrate_between_backends() {
+    use slasher::config::{LOCK_FILENAME, MIGRATION_FILENAME, REDB_DATA_FILENAME};
+    use slasher::test_utils::block;
+    use std::fs;
+
+    let db_dir = tempdir().unwrap();
+    let spec = chain_spec();
+    let config = Config::new(db_dir.path().into());
+    let marker_path = config.database_path.join(MIGRATION_FILENAME);
+
+    let db = SlasherDB::<E>::open(Arc::new(config.clone()), spec.clone(), test_logger()).unwrap();
+    let blocks = (0..10).map(|slot| block(slot, slot, 0)).collect::<Vec<_>>();
+    let mut txn = db.begin_rw_txn().unwrap();
+    db.store_config(&config, &mut txn).unwrap();
+    for block in &blocks {
+        db.check_or_insert_block_proposal(&mut txn, block.clone())
+            .unwrap();
+    }
+    txn.commit().unwrap();
+
+    // The database can't be migrated while it is open.
+    assert!(matches!(
+        SlasherDB::<E>::migrate_backend(
+            config.clone(),
+            DatabaseBackend::Redb,
+            spec.clone(),
+            test_logger()
+        ),
+        Err(Error::DatabaseLockError(_))
+    ));
+    assert!(!marker_path.exists());
+    drop(db);
+
+    // Simulate a migration which was interrupted after creating the destination.
+    fs::write(&marker_path, "redb").unwrap();
+    File::create(config.database_path.join(REDB_DATA_FILENAME)).unwrap();
+
+    let num_copied = SlasherDB::<E>::migrate_backend(
+        config.clone(),
+        DatabaseBackend::Redb,
+        spec.clone(),
+        test_logger(),
+    )
+    .unwrap();
+    assert_eq!(num_copied, blocks.len() + 1);
+    assert_eq!(config.backends_on_disk(), vec!["redb"]);
+    assert!(!marker_path.exists());
+    assert!(!config.database_path.join(LOCK_FILENAME).exists());
+
+    // Open a copy of the migrated database, as the original remains open in this process.
+    let copy_dir = tempdir().unwrap();
+    fs::copy(
+        config.database_path.join(REDB_DATA_FILENAME),
+        copy_dir.path().join(REDB_DATA_FILENAME),
+    )
+    .unwrap();
+    let mut copy_config = Config::new(copy_dir.path().into());
+    copy_config.backend = DatabaseBackend::Redb;
+    let copy = SlasherDB::<E>::open(Arc::new(copy_config), spec, test_logger()).unwrap();
+
+    let mut txn = copy.begin_rw_txn().unwrap();
+    for block in &blocks {
+        let proposal = copy
+            .get_block_proposal(&mut txn, block.message.proposer_index, block.message.slot)
+            .unwrap();
+        assert_eq!(proposal.as_ref(), Some(block));
+    }
+}
+
+#[test]
+fn migrate_refuses_to_overwrite() {
+    use slasher::config::{MIGRATION_FILENAME, REDB_DATA_FILENAME};
+    use std::fs;
+
+    let tempdir = tempdir().unwrap();
+    let config = Config::new(tempdir.path().into());
+    let migrate =
+        |to| SlasherDB::<E>::migrate_backend(config.clone(), to, chain_spec(), test_logger());
+
+    assert!(matches!(
+        migrate(DatabaseBackend::Redb),
+        Err(Error::MigrationSourceMissing(DatabaseBackend::Lmdb))
+    ));
+
+    // A destination which wasn't created by an interrupted migration is never overwritten.
+    File::create(config.database_path.join(LMDB_DATA_FILENAME)).unwrap();
+    File::create(config.database_path.join(REDB_DATA_FILENAME)).unwrap();
+    assert!(matches!(
+        migrate(DatabaseBackend::Redb),
+        Err(Error::MigrationDestinationExists(DatabaseBackend::Redb))
+    ));
+
+    // An interrupted migration must be completed to the same backend.
+    fs::write(config.database_path.join(MIGRATION_FILENAME), "mdbx").unwrap();
+    assert!(matches!(
+        migrate(DatabaseBackend::Redb),
+        Err(Error::MigrationInterrupted { to }) if to == "mdbx"
+    ));
+}
@@ -64,3 +64,82 @@ fn block_pruning() {
         .epoch(slots_per_epoch)
         > current_epoch - config.history_length as u64));
 }
+
+#[test]
+fn detected_slashings_outlive_harvest() {
+    let tempdir = tempdir().unwrap();
+    let config = Config::new(tempdir.path().into());
+    let spec = chain_spec();
+    let slasher = Slasher::<E>::open(config, spec, test_logger()).unwrap();
+    let current_epoch = Epoch::new(0);
+
+    slasher.accept_block_header(test_block(1, 0, 0));
+    slasher.accept_block_header(test_block(1, 0, 1));
+    slasher.accept_block_header(test_block(2, 1, 0));
+    slasher.accept_block_header(test_block(2, 1, 1));
+    slasher.process_queued(current_epoch).unwrap();
+
+    // Harvesting the slashings does not remove them from the detected slashings.
+    assert_eq!(slasher.get_proposer_slashings().len(), 2);
+    assert!(slasher.get_proposer_slashings().is_empty());
+    assert_eq!(slasher.detected_proposer_slashings().unwrap().len(), 2);
+
+    // Only the retained slashings remain detected.
+    slasher
+        .retain_detected_slashings(
+            |_| true,
+            |slashing| slashing.signed_header_1.message.proposer_index == 1,
+        )
+        .unwrap();
+    let detected = slasher.detected_proposer_slashings().unwrap();
+    assert_eq!(detected.len(), 1);
+    assert_eq!(detected[0].signed_header_1.message.proposer_index, 1);
+    assert!(slasher.detected_attester_slashings().unwrap().is_empty());
+}
+
+#[test]
+fn detected_slashings_persist_across_restarts() {
+    let tempdir = tempdir().unwrap();
+    let config = Config::new(tempdir.path().into());
+    let spec = chain_spec();
+    let current_epoch = Epoch::new(0);
+
+    let slasher = Slasher::<E>::open(config.clone(), spec.clone(), test_logger()).unwrap();
+    slasher.accept_block_header(test_block(1, 0, 0));
+    slasher.accept_block_header(test_block(1, 0, 1));
+    slasher.process_queued(current_epoch).unwrap();
+    drop(slasher);
+
+    let slasher = Slasher::<E>::open(config, spec, test_logger()).unwrap();
+    let detected = slasher.detected_proposer_slashings().unwrap();
+    assert_eq!(detected.len(), 1);
+    assert_eq!(detected[0].signed_header_1, test_block(1, 0, 0));
+    assert_eq!(detected[0].signed_header_2, test_block(1, 0, 1));
+}
+
+#[test]
+fn detected_slashings_pruning() {
+    let slots_per_epoch = E::slots_per_epoch();
+
+    let tempdir = tempdir().unwrap();
+    let mut config = Config::new(tempdir.path().into());
+    config.chunk_size = 2;
+    config.history_length = 2;
+    let spec = chain_spec();
+    let slasher = Slasher::<E>::open(config.clone(), spec, test_logger()).unwrap();
+
+    let old_slot = 1;
+    let new_slot = 4 * slots_per_epoch;
+    for slot in [old_slot, new_slot] {
+        slasher.accept_block_header(test_block(slot, 0, 0));
+        slasher.accept_block_header(test_block(slot, 0, 1));
+    }
+    slasher.process_queued(Epoch::new(4)).unwrap();
+    assert_eq!(slasher.detected_proposer_slashings().unwrap().len(), 2);
+
+    // Only the slashing within the history length survives pruning.
+    slasher.prune_database(Epoch::new(4)).unwrap();
+    let detected = slasher.detected_proposer_slashings().unwrap();
+    assert_eq!(detected.len(), 1);
+    assert_eq!(detected[0].signed_header_1.message.slot, new_slot);
+}
@@ -0,0 +1,118 @@
+use clap::ValueEnum;
+pub use clap::{Arg, ArgAction, Args, Command, FromArgMatches, Parser};
+use clap_utils::get_color_style;
+use clap_utils::FLAG_HEADER;
+use sensitive_url::SensitiveUrl;
+use serde::{Deserialize, Serialize};
+use slasher::DatabaseBackend;
+use std::path::PathBuf;
+
+#[derive(Parser, Clone, Deserialize, Serialize, Debug)]
+#[clap(
+    name = "slasher",
+    about = "Manage the slasher database of a beacon node.",
+    styles = get_color_style(),
+    next_line_help = true,
+    term_width = 80,
+    disable_help_flag = true,
+    disable_help_subcommand = true,
+    display_order = 0,
+)]
+pub struct SlasherManager {
+    #[clap(
+        long,
+        global = true,
+        value_name = "DIR",
+        help = "The slasher database directory, as provided to the beacon node with \
+                --slasher-dir. Defaults to the slasher_db directory within the beacon node \
+                data directory.",
+        display_order = 0
+    )]
+    pub slasher_dir: Option<PathBuf>,
+
+    #[clap(
+        long,
+        global = true,
+        help = "Prints help information",
+        action = clap::ArgAction::HelpLong,
+        display_order = 0,
+        help_heading = FLAG_HEADER
+    )]
+    help: Option<bool>,
+
+    #[clap(subcommand)]
+    pub subcommand: SlasherManagerSubcommand,
+}
+
+#[derive(Parser, Clone, Deserialize, Serialize, Debug)]
+#[clap(rename_all = "kebab-case")]
+pub enum SlasherManagerSubcommand {
+    Migrate(Migrate),
+    Export(Export),
+}
+
+#[derive(Parser, Clone, Deserialize, Serialize, Debug)]
+#[clap(
+    about = "Convert the slasher database to a different backend. The beacon node must be \
+             stopped first. The database of the original backend is deleted once the \
+             conversion completes."
+)]
+pub struct Migrate {
+    #[clap(
+        long,
+        value_name = "BACKEND",
+        help = "The backend of the existing database: mdbx, lmdb or redb.",
+        display_order = 0
+    )]
+    pub from: DatabaseBackend,
+
+    #[clap(
+        long,
+        value_name = "BACKEND",
+        help = "The backend to convert the database to: mdbx, lmdb or redb. The beacon node \
+                must subsequently be run with --slasher-backend set to this value.",
+        display_order = 0
+    )]
+    pub to: DatabaseBackend,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Json,
+    Ssz,
+}
+
+#[derive(Parser, Clone, Deserialize, Serialize, Debug)]
+#[clap(
+    about = "Export the slashings detected by the slasher of a running beacon node which have \
+             not yet been included on chain, so that they may be submitted to other nodes."
+)]
+pub struct Export {
+    #[clap(
+        long,
+        value_name = "NETWORK_ADDRESS",
+        default_value = "http://localhost:5052",
+        help = "The HTTP API address of a beacon node with the slasher enabled.",
+        display_order = 0
+    )]
+    pub beacon_node: SensitiveUrl,
+
+    #[clap(
+        long,
+        value_name = "FILE",
+        help = "The file to write the slashings to.",
+        display_order = 0
+    )]
+    pub output: PathBuf,
+
+    #[clap(
+        long,
+        value_name = "FORMAT",
+        value_enum,
+        default_value_t = ExportFormat::Json,
+        help = "The encoding of the exported slashings.",
+        display_order = 0
+    )]
+    pub format: ExportFormat,
+}
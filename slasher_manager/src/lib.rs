@@ -0,0 +1,91 @@
+//! Maintenance of the slasher database, e.g., converting it between backends, and export of the
+//! slashings it has detected.
+pub mod cli;
+
+use crate::cli::{Export, ExportFormat, SlasherManager, SlasherManagerSubcommand};
+use beacon_node::get_data_dir;
+use clap::ArgMatches;
+use environment::Environment;
+use eth2::{BeaconNodeHttpClient, Timeouts};
+use slasher::{Config, SlasherDB};
+use ssz::Encode;
+use std::fs;
+use std::time::Duration;
+use types::EthSpec;
+
+/// The timeout for requests to the beacon node when exporting slashings.
+const HTTP_TIMEOUT: Duration = Duration::from_secs(12);
+
+/// Run the slasher manager, returning an error string if the operation did not succeed.
+pub fn run<E: EthSpec>(
+    cli_args: &ArgMatches,
+    config: &SlasherManager,
+    env: Environment<E>,
+) -> Result<(), String> {
+    let context = env.core_context();
+    let slasher_dir = config
+        .slasher_dir
+        .clone()
+        .unwrap_or_else(|| get_data_dir(cli_args).join("slasher_db"));
+
+    match &config.subcommand {
+        SlasherManagerSubcommand::Migrate(migrate) => {
+            let mut slasher_config = Config::new(slasher_dir);
+            slasher_config.backend = migrate.from;
+
+            let num_copied = SlasherDB::<E>::migrate_backend(
+                slasher_config,
+                migrate.to,
+                context.eth2_config.spec.clone(),
+                context.log().clone(),
+            )
+            .map_err(|e| format!("Unable to migrate slasher database: {:?}", e))?;
+
+            eprintln!(
+                "Migrated {} entries from {} to {}. Run the beacon node with \
+                 --slasher-backend {} to use the migrated database.",
+                num_copied, migrate.from, migrate.to, migrate.to
+            );
+        }
+        SlasherManagerSubcommand::Export(export) => {
+            let (num_attester_slashings, num_proposer_slashings) = context
+                .executor
+                .block_on_dangerous(export_slashings::<E>(export), "slasher_export")
+                .ok_or("Shutting down")??;
+
+            eprintln!(
+                "Exported {} attester slashings and {} proposer slashings to {}",
+                num_attester_slashings,
+                num_proposer_slashings,
+                export.output.display()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Fetch the detected slashings from the beacon node and write them to the output file, returning
+/// the number of attester and proposer slashings written.
+async fn export_slashings<E: EthSpec>(export: &Export) -> Result<(usize, usize), String> {
+    let client =
+        BeaconNodeHttpClient::new(export.beacon_node.clone(), Timeouts::set_all(HTTP_TIMEOUT));
+    let bundle = client
+        .get_lighthouse_slasher_slashings::<E>()
+        .await
+        .map_err(|e| format!("Unable to fetch slashings from the beacon node: {:?}", e))?
+        .data;
+
+    let bytes = match export.format {
+        ExportFormat::Json => serde_json::to_vec_pretty(&bundle)
+            .map_err(|e| format!("Unable to encode slashings: {:?}", e))?,
+        ExportFormat::Ssz => bundle.as_ssz_bytes(),
+    };
+    fs::write(&export.output, bytes)
+        .map_err(|e| format!("Unable to write {}: {:?}", export.output.display(), e))?;
+
+    Ok((
+        bundle.num_attester_slashings(),
+        bundle.proposer_slashings.len(),
+    ))
+}
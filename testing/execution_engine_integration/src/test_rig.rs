@@ -311,6 +311,8 @@ impl<Engine: GenericExecutionEngine> TestRig<Engine> {
             pubkey: PublicKeyBytes::empty(),
             slot: Slot::new(0),
             chain_health: ChainHealth::Healthy,
+            deadline: None,
+            min_bid_gwei: None,
         };
         let suggested_fee_recipient = self
             .ee_a
@@ -463,6 +465,8 @@ impl<Engine: GenericExecutionEngine> TestRig<Engine> {
             pubkey: PublicKeyBytes::empty(),
             slot: Slot::new(0),
             chain_health: ChainHealth::Healthy,
+            deadline: None,
+            min_bid_gwei: None,
         };
         let suggested_fee_recipient = self
             .ee_a
@@ -0,0 +1,522 @@
+//! Provides a harness for testing the RPC handlers of the `NetworkBeaconProcessor` end to end.
+//!
+//! Each `TestNode` runs a `NetworkBeaconProcessor` and `BeaconProcessor` against its own in-memory
+//! chain. The `MockSwarm` stands in for libp2p, delivering requests from one node to another and
+//! collecting the responses which the serving node sends to its network service. The responses to
+//! a range of blocks can be delivered back to the requesting node, which imports them in the same
+//! way as a range sync batch.
+//!
+//! Faults such as a slow execution layer or blobs missing from the database can be injected into
+//! individual nodes to check how the handlers behave when serving from, or importing into, a
+//! degraded node.
+use beacon_chain::block_verification_types::RpcBlock;
+use beacon_chain::test_utils::{
+    AttestationStrategy, BeaconChainHarness, BlockStrategy, EphemeralHarnessType,
+};
+use beacon_chain::BeaconChain;
+use beacon_processor::{
+    BeaconProcessor, BeaconProcessorChannels, BeaconProcessorConfig, BeaconProcessorQueueLengths,
+    BeaconProcessorSend, DuplicateCache,
+};
+use lighthouse_network::discovery::ConnectionId;
+use lighthouse_network::rpc::methods::{
+    BlobsByRangeRequest, BlobsByRootRequest, DataColumnsByRangeRequest, DataColumnsByRootRequest,
+    MetaData, MetaDataV2,
+};
+use lighthouse_network::rpc::{
+    BlocksByRangeRequest, BlocksByRootRequest, RequestId, RpcErrorResponse, SubstreamId,
+};
+use lighthouse_network::{
+    discv5::enr::{self, CombinedKey},
+    types::{EnrAttestationBitfield, EnrSyncCommitteeBitfield},
+    NetworkConfig, NetworkGlobals, PeerId, Response,
+};
+use network::{
+    BatchProcessResult, ChainSegmentProcessId, InboundRequestTracker, InvalidBlockStorage,
+    NetworkBeaconProcessor, NetworkMessage, SyncMessage,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use types::{
+    BlobSidecar, BlobSidecarList, ChainSpec, DataColumnSidecar, EthSpec, Hash256, MainnetEthSpec,
+    SignedBeaconBlock, Slot,
+};
+
+pub type E = MainnetEthSpec;
+pub type T = EphemeralHarnessType<E>;
+
+pub const VALIDATOR_COUNT: usize = 32;
+
+/// The time to wait for a node to finish responding to a request.
+pub const RESPONSE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A request which may be sent between nodes in the `MockSwarm`.
+#[derive(Debug, Clone)]
+pub enum RpcRequest {
+    BlocksByRange(BlocksByRangeRequest),
+    BlocksByRoot(BlocksByRootRequest),
+    BlobsByRange(BlobsByRangeRequest),
+    BlobsByRoot(BlobsByRootRequest),
+    DataColumnsByRange(DataColumnsByRangeRequest),
+    DataColumnsByRoot(DataColumnsByRootRequest),
+}
+
+/// The outcome of a single request, as observed by the requesting peer.
+#[derive(Debug)]
+pub struct RpcExchange {
+    /// The responses sent before the stream was terminated, excluding the terminator itself.
+    pub responses: Vec<Response<E>>,
+    /// The error which terminated the stream, if any.
+    pub error: Option<RpcErrorResponse>,
+    /// The time taken for the stream to terminate.
+    pub duration: Duration,
+}
+
+impl RpcExchange {
+    pub fn blocks(&self) -> Vec<Arc<SignedBeaconBlock<E>>> {
+        self.responses
+            .iter()
+            .filter_map(|response| match response {
+                Response::BlocksByRange(Some(block)) | Response::BlocksByRoot(Some(block)) => {
+                    Some(block.clone())
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn blobs(&self) -> Vec<Arc<BlobSidecar<E>>> {
+        self.responses
+            .iter()
+            .filter_map(|response| match response {
+                Response::BlobsByRange(Some(blob)) | Response::BlobsByRoot(Some(blob)) => {
+                    Some(blob.clone())
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn data_columns(&self) -> Vec<Arc<DataColumnSidecar<E>>> {
+        self.responses
+            .iter()
+            .filter_map(|response| match response {
+                Response::DataColumnsByRange(Some(column))
+                | Response::DataColumnsByRoot(Some(column)) => Some(column.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// The outcome of a batch of blocks being imported by the requesting peer.
+#[derive(Debug)]
+pub struct BatchImport {
+    pub result: BatchProcessResult,
+    /// The time taken for the batch to be processed.
+    pub duration: Duration,
+}
+
+/// A node serving RPC requests from an in-memory chain.
+pub struct TestNode {
+    pub peer_id: PeerId,
+    pub harness: BeaconChainHarness<T>,
+    pub network_beacon_processor: Arc<NetworkBeaconProcessor<T>>,
+    beacon_processor_tx: BeaconProcessorSend<E>,
+    network_rx: mpsc::UnboundedReceiver<NetworkMessage<E>>,
+    sync_rx: mpsc::UnboundedReceiver<SyncMessage<E>>,
+}
+
+/// This custom drop implementation ensures that we shut down the tokio runtime gracefully. Without
+/// it, tests will hang indefinitely.
+impl Drop for TestNode {
+    fn drop(&mut self) {
+        // Causes the beacon processor to shutdown.
+        let len = BeaconProcessorConfig::default().max_work_event_queue_len;
+        self.beacon_processor_tx = BeaconProcessorSend(mpsc::channel(len).0);
+    }
+}
+
+impl TestNode {
+    /// Build a node whose chain has a block in each of the first `chain_length` slots.
+    pub async fn new(spec: ChainSpec, chain_length: u64) -> Self {
+        let spec = Arc::new(spec);
+        let harness = BeaconChainHarness::builder(MainnetEthSpec)
+            .spec(spec.clone())
+            .deterministic_keypairs(VALIDATOR_COUNT)
+            .fresh_ephemeral_store()
+            .mock_execution_layer()
+            .chain_config(<_>::default())
+            .build();
+
+        harness.advance_slot();
+        for _ in 0..chain_length {
+            harness
+                .extend_chain(
+                    1,
+                    BlockStrategy::OnCanonicalHead,
+                    AttestationStrategy::AllValidators,
+                )
+                .await;
+            harness.advance_slot();
+        }
+
+        let chain = harness.chain.clone();
+        let log = harness.logger().clone();
+        let executor = harness.runtime.task_executor.clone();
+
+        let beacon_processor_config = BeaconProcessorConfig::default();
+        let BeaconProcessorChannels {
+            beacon_processor_tx,
+            beacon_processor_rx,
+            work_reprocessing_tx,
+            work_reprocessing_rx,
+        } = BeaconProcessorChannels::new(&beacon_processor_config);
+
+        let (network_tx, network_rx) = mpsc::unbounded_channel();
+        let (sync_tx, sync_rx) = mpsc::unbounded_channel();
+
+        let meta_data = MetaData::V2(MetaDataV2 {
+            seq_number: 0,
+            attnets: EnrAttestationBitfield::<E>::default(),
+            syncnets: EnrSyncCommitteeBitfield::<E>::default(),
+        });
+        let enr_key = CombinedKey::generate_secp256k1();
+        let enr = enr::Enr::builder().build(&enr_key).unwrap();
+        let network_globals = Arc::new(NetworkGlobals::new(
+            enr,
+            meta_data,
+            vec![],
+            false,
+            &log,
+            Arc::new(NetworkConfig::default()),
+            spec,
+        ));
+
+        let network_beacon_processor = Arc::new(NetworkBeaconProcessor {
+            beacon_processor_send: beacon_processor_tx.clone(),
+            duplicate_cache: DuplicateCache::default(),
+            chain: chain.clone(),
+            network_tx,
+            sync_tx,
+            reprocess_tx: work_reprocessing_tx.clone(),
+            network_globals: network_globals.clone(),
+            invalid_block_storage: InvalidBlockStorage::Disabled,
+            inbound_requests: Arc::new(InboundRequestTracker::default()),
+            executor: executor.clone(),
+            log: log.clone(),
+        });
+
+        BeaconProcessor {
+            network_globals,
+            executor,
+            current_workers: 0,
+            config: beacon_processor_config,
+            log,
+        }
+        .spawn_manager(
+            beacon_processor_rx,
+            work_reprocessing_tx,
+            work_reprocessing_rx,
+            None,
+            chain.slot_clock.clone(),
+            chain.spec.maximum_gossip_clock_disparity(),
+            BeaconProcessorQueueLengths::from_state(
+                &chain.canonical_head.cached_head().snapshot.beacon_state,
+                &chain.spec,
+            )
+            .unwrap(),
+        )
+        .expect("should spawn beacon processor");
+
+        Self {
+            peer_id: PeerId::random(),
+            harness,
+            network_beacon_processor,
+            beacon_processor_tx,
+            network_rx,
+            sync_rx,
+        }
+    }
+
+    pub fn chain(&self) -> &Arc<BeaconChain<T>> {
+        &self.harness.chain
+    }
+
+    /// Delay every response from this node's execution layer by `delay`, or remove the delay if
+    /// `None`.
+    pub fn set_execution_delay(&self, delay: Option<Duration>) {
+        self.harness
+            .mock_execution_layer
+            .as_ref()
+            .expect("node should have a mock execution layer")
+            .server
+            .set_response_delay(delay);
+    }
+
+    /// Remove the blobs for `block_root` from this node's database, leaving the block in place.
+    pub fn delete_blobs(&self, block_root: Hash256) {
+        self.chain()
+            .store
+            .delete_blobs(&block_root)
+            .expect("should delete blobs");
+    }
+
+    /// Import `blocks` as a range sync batch, waiting for the result to be sent to sync.
+    async fn import_batch(&mut self, blocks: Vec<RpcBlock<E>>) -> Result<BatchImport, String> {
+        let start = Instant::now();
+        let epoch = blocks
+            .first()
+            .map_or(Slot::new(0), |block| block.as_block().slot())
+            .epoch(E::slots_per_epoch());
+        self.network_beacon_processor
+            .send_chain_segment(ChainSegmentProcessId::RangeBatchId(0, epoch), blocks)
+            .map_err(|e| format!("Unable to queue batch: {}", e))?;
+
+        loop {
+            let message = tokio::time::timeout(RESPONSE_TIMEOUT, self.sync_rx.recv())
+                .await
+                .map_err(|_| "Timed out waiting for batch to be processed".to_string())?
+                .ok_or("Sync channel closed")?;
+
+            if let SyncMessage::BatchProcessed { result, .. } = message {
+                return Ok(BatchImport {
+                    result,
+                    duration: start.elapsed(),
+                });
+            }
+        }
+    }
+
+    /// Queue `request` from `peer_id` on this node's beacon processor.
+    fn enqueue_request(
+        &self,
+        peer_id: PeerId,
+        request_id: RequestId,
+        request: RpcRequest,
+    ) -> Result<(), String> {
+        let processor = &self.network_beacon_processor;
+        let connection_id = ConnectionId::new_unchecked(0);
+        let substream_id = SubstreamId::new(0);
+        match request {
+            RpcRequest::BlocksByRange(request) => processor.send_blocks_by_range_request(
+                peer_id,
+                connection_id,
+                substream_id,
+                request_id,
+                request,
+            ),
+            RpcRequest::BlocksByRoot(request) => processor.send_blocks_by_roots_request(
+                peer_id,
+                connection_id,
+                substream_id,
+                request_id,
+                request,
+            ),
+            RpcRequest::BlobsByRange(request) => processor.send_blobs_by_range_request(
+                peer_id,
+                connection_id,
+                substream_id,
+                request_id,
+                request,
+            ),
+            RpcRequest::BlobsByRoot(request) => processor.send_blobs_by_roots_request(
+                peer_id,
+                connection_id,
+                substream_id,
+                request_id,
+                request,
+            ),
+            RpcRequest::DataColumnsByRange(request) => processor
+                .send_data_columns_by_range_request(
+                    peer_id,
+                    connection_id,
+                    substream_id,
+                    request_id,
+                    request,
+                ),
+            RpcRequest::DataColumnsByRoot(request) => processor.send_data_columns_by_roots_request(
+                peer_id,
+                connection_id,
+                substream_id,
+                request_id,
+                request,
+            ),
+        }
+        .map_err(|e| format!("Unable to queue request: {}", e))
+    }
+
+    /// Collect the responses to `request_id` until the stream is terminated.
+    async fn collect_responses(&mut self, request_id: RequestId) -> Result<RpcExchange, String> {
+        let start = Instant::now();
+        let mut responses = vec![];
+        loop {
+            let message = tokio::time::timeout(RESPONSE_TIMEOUT, self.network_rx.recv())
+                .await
+                .map_err(|_| "Timed out waiting for response".to_string())?
+                .ok_or("Network channel closed")?;
+
+            match message {
+                NetworkMessage::SendResponse {
+                    request_id: id,
+                    response,
+                    ..
+                } if id == request_id => {
+                    if is_stream_termination(&response) {
+                        break;
+                    }
+                    responses.push(response);
+                }
+                NetworkMessage::SendErrorResponse {
+                    request_id: id,
+                    error,
+                    ..
+                } if id == request_id => {
+                    return Ok(RpcExchange {
+                        responses,
+                        error: Some(error),
+                        duration: start.elapsed(),
+                    });
+                }
+                // Ignore messages unrelated to the request, e.g. peer reports.
+                _ => {}
+            }
+        }
+
+        Ok(RpcExchange {
+            responses,
+            error: None,
+            duration: start.elapsed(),
+        })
+    }
+}
+
+fn is_stream_termination(response: &Response<E>) -> bool {
+    matches!(
+        response,
+        Response::BlocksByRange(None)
+            | Response::BlocksByRoot(None)
+            | Response::BlobsByRange(None)
+            | Response::BlobsByRoot(None)
+            | Response::DataColumnsByRange(None)
+            | Response::DataColumnsByRoot(None)
+    )
+}
+
+/// Connects a set of `TestNode`s, delivering requests between them in place of libp2p.
+#[derive(Default)]
+pub struct MockSwarm {
+    nodes: HashMap<PeerId, TestNode>,
+}
+
+impl MockSwarm {
+    /// Add `node` to the swarm, returning its peer ID.
+    pub fn add_node(&mut self, node: TestNode) -> PeerId {
+        let peer_id = node.peer_id;
+        self.nodes.insert(peer_id, node);
+        peer_id
+    }
+
+    pub fn node(&self, peer_id: &PeerId) -> &TestNode {
+        self.nodes.get(peer_id).expect("peer should be in swarm")
+    }
+
+    /// Send `request` from `from` to `to` and wait for `to` to finish responding.
+    pub async fn request(
+        &mut self,
+        from: PeerId,
+        to: PeerId,
+        request: RpcRequest,
+    ) -> Result<RpcExchange, String> {
+        if !self.nodes.contains_key(&from) {
+            return Err(format!("Unknown requesting peer {}", from));
+        }
+        let request_id = RequestId::next();
+        let node = self
+            .nodes
+            .get_mut(&to)
+            .ok_or_else(|| format!("Unknown serving peer {}", to))?;
+        node.enqueue_request(from, request_id, request)?;
+        node.collect_responses(request_id).await
+    }
+
+    /// Request the blocks and blobs in `count` slots from `start_slot` from `to`, and deliver them
+    /// to `from` to be imported as a range sync batch.
+    ///
+    /// Data columns are not requested, so this fails if PeerDAS is scheduled.
+    pub async fn sync_range(
+        &mut self,
+        from: PeerId,
+        to: PeerId,
+        start_slot: u64,
+        count: u64,
+    ) -> Result<BatchImport, String> {
+        let spec = self.node(&to).chain().spec.clone();
+        if spec.is_peer_das_scheduled() {
+            return Err("Data columns are not supported by range sync in the swarm".to_string());
+        }
+
+        let blocks = self
+            .request(
+                from,
+                to,
+                RpcRequest::BlocksByRange(BlocksByRangeRequest::new(start_slot, count)),
+            )
+            .await?;
+        if let Some(error) = blocks.error {
+            return Err(format!("Blocks request failed: {:?}", error));
+        }
+
+        let mut blobs_by_root = HashMap::<Hash256, Vec<Arc<BlobSidecar<E>>>>::new();
+        if spec.deneb_fork_epoch.is_some() {
+            let blobs = self
+                .request(
+                    from,
+                    to,
+                    RpcRequest::BlobsByRange(BlobsByRangeRequest { start_slot, count }),
+                )
+                .await?;
+            if let Some(error) = blobs.error {
+                return Err(format!("Blobs request failed: {:?}", error));
+            }
+            for blob in blobs.blobs() {
+                blobs_by_root
+                    .entry(blob.block_root())
+                    .or_default()
+                    .push(blob);
+            }
+        }
+
+        let rpc_blocks = blocks
+            .blocks()
+            .into_iter()
+            .map(|block| {
+                let block_root = block.canonical_root();
+                let blobs = blobs_by_root
+                    .remove(&block_root)
+                    .map(BlobSidecarList::new)
+                    .transpose()
+                    .map_err(|e| format!("Too many blobs: {:?}", e))?;
+                RpcBlock::new(Some(block_root), block, blobs)
+                    .map_err(|e| format!("Unable to couple blobs: {:?}", e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // The requesting node must have reached the slots of the blocks in order to import them.
+        let current_slot = self
+            .node(&to)
+            .chain()
+            .slot()
+            .map_err(|e| format!("{:?}", e))?;
+        let node = self
+            .nodes
+            .get_mut(&from)
+            .ok_or_else(|| format!("Unknown requesting peer {}", from))?;
+        if node.chain().slot().map_err(|e| format!("{:?}", e))? < current_slot {
+            node.harness.set_current_slot(current_slot);
+        }
+        node.import_batch(rpc_blocks).await
+    }
+}
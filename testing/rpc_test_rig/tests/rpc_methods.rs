@@ -0,0 +1,276 @@
+#![cfg(not(debug_assertions))] // Tests are too slow in debug.
+
+use beacon_chain::test_utils::test_spec;
+use beacon_chain::WhenSlotSkipped;
+use lighthouse_network::rpc::methods::{BlobsByRangeRequest, DataColumnsByRangeRequest};
+use lighthouse_network::rpc::{BlocksByRangeRequest, BlocksByRootRequest};
+use lighthouse_network::PeerId;
+use network::BatchProcessResult;
+use rpc_test_rig::{MockSwarm, RpcRequest, TestNode, E};
+use std::time::Duration;
+use types::{Epoch, Hash256, Slot};
+
+const CHAIN_LENGTH: u64 = 32;
+
+/// Build a swarm of a requesting node with only the genesis block and a serving node, returning
+/// their peer IDs.
+async fn two_node_swarm() -> (MockSwarm, PeerId, PeerId) {
+    let mut swarm = MockSwarm::default();
+    let client = swarm.add_node(TestNode::new(test_spec::<E>(), 0).await);
+    let server = swarm.add_node(TestNode::new(test_spec::<E>(), CHAIN_LENGTH).await);
+    (swarm, client, server)
+}
+
+/// The roots of the blocks in the first `count` slots of the node's chain.
+fn block_roots(node: &TestNode, count: u64) -> Vec<Hash256> {
+    (0..count)
+        .filter_map(|slot| {
+            node.chain()
+                .block_root_at_slot(Slot::new(slot), WhenSlotSkipped::None)
+                .unwrap()
+        })
+        .collect()
+}
+
+fn blob_count(node: &TestNode, block_roots: &[Hash256]) -> usize {
+    block_roots
+        .iter()
+        .map(|root| node.chain().get_blobs(root).unwrap_or_default().len())
+        .sum()
+}
+
+#[tokio::test]
+async fn blocks_by_range_matches_chain() {
+    let (mut swarm, client, server) = two_node_swarm().await;
+
+    let exchange = swarm
+        .request(
+            client,
+            server,
+            RpcRequest::BlocksByRange(BlocksByRangeRequest::new(0, CHAIN_LENGTH)),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(exchange.error, None);
+    let roots = exchange
+        .blocks()
+        .iter()
+        .map(|block| block.canonical_root())
+        .collect::<Vec<_>>();
+    assert_eq!(roots, block_roots(swarm.node(&server), CHAIN_LENGTH));
+}
+
+#[tokio::test]
+async fn blocks_by_root_matches_chain() {
+    let (mut swarm, client, server) = two_node_swarm().await;
+    let spec = swarm.node(&server).chain().spec.clone();
+    let mut roots = block_roots(swarm.node(&server), CHAIN_LENGTH);
+    roots.truncate(8);
+    // Unknown roots are skipped rather than terminating the stream.
+    let unknown_root = Hash256::repeat_byte(0xff);
+
+    let exchange = swarm
+        .request(
+            client,
+            server,
+            RpcRequest::BlocksByRoot(BlocksByRootRequest::new(
+                [roots.clone(), vec![unknown_root]].concat(),
+                &spec,
+            )),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(exchange.error, None);
+    let returned = exchange
+        .blocks()
+        .iter()
+        .map(|block| block.canonical_root())
+        .collect::<Vec<_>>();
+    assert_eq!(returned, roots);
+}
+
+#[tokio::test]
+async fn range_sync_imports_served_chain() {
+    let (mut swarm, client, server) = two_node_swarm().await;
+    // The genesis block is already known to the requesting node.
+    let expected_blocks = block_roots(swarm.node(&server), CHAIN_LENGTH).len() - 1;
+
+    let import = swarm
+        .sync_range(client, server, 1, CHAIN_LENGTH - 1)
+        .await
+        .unwrap();
+
+    assert!(
+        matches!(
+            import.result,
+            BatchProcessResult::Success { sent_blocks, imported_blocks }
+                if sent_blocks == expected_blocks && imported_blocks == expected_blocks
+        ),
+        "{:?}",
+        import.result
+    );
+    assert_eq!(
+        swarm
+            .node(&client)
+            .chain()
+            .head_snapshot()
+            .beacon_block_root,
+        swarm
+            .node(&server)
+            .chain()
+            .head_snapshot()
+            .beacon_block_root
+    );
+}
+
+#[tokio::test]
+async fn blocks_by_range_is_served_without_execution_layer() {
+    let (mut swarm, client, server) = two_node_swarm().await;
+    let delay = Duration::from_secs(5);
+    swarm.node(&server).set_execution_delay(Some(delay));
+
+    let exchange = swarm
+        .request(
+            client,
+            server,
+            RpcRequest::BlocksByRange(BlocksByRangeRequest::new(0, CHAIN_LENGTH)),
+        )
+        .await
+        .unwrap();
+
+    // Full payloads are stored, so the blocks are served without waiting on the slow EL.
+    assert_eq!(exchange.error, None);
+    assert_eq!(
+        exchange.blocks().len(),
+        block_roots(swarm.node(&server), CHAIN_LENGTH).len()
+    );
+    assert!(exchange.duration < delay, "{:?}", exchange.duration);
+}
+
+#[tokio::test]
+async fn range_sync_with_slow_execution_layer() {
+    if test_spec::<E>().bellatrix_fork_epoch != Some(Epoch::new(0)) {
+        return;
+    }
+    let (mut swarm, client, server) = two_node_swarm().await;
+    let delay = Duration::from_millis(100);
+    let count = 8;
+    swarm.node(&client).set_execution_delay(Some(delay));
+
+    let import = swarm.sync_range(client, server, 1, count).await.unwrap();
+
+    // Each payload is verified by the EL in turn, so the batch takes at least one delay per block.
+    let BatchProcessResult::Success {
+        imported_blocks, ..
+    } = import.result
+    else {
+        panic!("batch should be imported: {:?}", import.result);
+    };
+    assert_eq!(
+        imported_blocks,
+        block_roots(swarm.node(&server), count + 1).len() - 1
+    );
+    assert!(
+        import.duration >= delay * imported_blocks as u32,
+        "{:?}",
+        import.duration
+    );
+}
+
+#[tokio::test]
+async fn blobs_by_range_with_missing_blobs() {
+    if test_spec::<E>().deneb_fork_epoch.is_none() {
+        return;
+    }
+    let (mut swarm, client, server) = two_node_swarm().await;
+    let request = RpcRequest::BlobsByRange(BlobsByRangeRequest {
+        start_slot: 0,
+        count: CHAIN_LENGTH,
+    });
+
+    let roots = block_roots(swarm.node(&server), CHAIN_LENGTH);
+    let expected = blob_count(swarm.node(&server), &roots);
+    let exchange = swarm
+        .request(client, server, request.clone())
+        .await
+        .unwrap();
+    assert_eq!(exchange.error, None);
+    assert_eq!(exchange.blobs().len(), expected);
+
+    // Remove the blobs for one block and check that the rest are still served.
+    let Some(missing_root) = roots
+        .iter()
+        .find(|root| blob_count(swarm.node(&server), &[**root]) > 0)
+        .copied()
+    else {
+        return;
+    };
+    let missing = blob_count(swarm.node(&server), &[missing_root]);
+    swarm.node(&server).delete_blobs(missing_root);
+
+    let exchange = swarm.request(client, server, request).await.unwrap();
+    assert_eq!(exchange.error, None);
+    assert_eq!(exchange.blobs().len(), expected - missing);
+    assert!(exchange
+        .blobs()
+        .iter()
+        .all(|blob| blob.block_root() != missing_root));
+
+    // The requesting node refuses to import the block without its blobs.
+    let import = swarm
+        .sync_range(client, server, 1, CHAIN_LENGTH - 1)
+        .await
+        .unwrap();
+    assert!(
+        !matches!(import.result, BatchProcessResult::Success { .. }),
+        "{:?}",
+        import.result
+    );
+    assert!(!swarm
+        .node(&client)
+        .chain()
+        .block_is_known_to_fork_choice(&missing_root));
+}
+
+#[tokio::test]
+async fn data_columns_by_range_matches_store() {
+    if !test_spec::<E>().is_peer_das_scheduled() {
+        return;
+    }
+    let (mut swarm, client, server) = two_node_swarm().await;
+    let column_index = 0;
+
+    let expected = block_roots(swarm.node(&server), CHAIN_LENGTH)
+        .iter()
+        .filter(|root| {
+            swarm
+                .node(&server)
+                .chain()
+                .get_data_column(root, &column_index)
+                .unwrap()
+                .is_some()
+        })
+        .count();
+
+    let exchange = swarm
+        .request(
+            client,
+            server,
+            RpcRequest::DataColumnsByRange(DataColumnsByRangeRequest {
+                start_slot: 0,
+                count: CHAIN_LENGTH,
+                columns: vec![column_index],
+            }),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(exchange.error, None);
+    assert_eq!(exchange.data_columns().len(), expected);
+    assert!(exchange
+        .data_columns()
+        .iter()
+        .all(|column| column.index == column_index));
+}
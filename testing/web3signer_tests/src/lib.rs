@@ -423,6 +423,8 @@ mod tests {
                     builder_proposals: None,
                     builder_boost_factor: None,
                     prefer_builder_proposals: None,
+                    attestation_offset_ms: None,
+                    builder_min_bid_gwei: None,
                     description: String::default(),
                     signing_definition: SigningDefinition::LocalKeystore {
                         voting_keystore_path: signer_rig.keystore_path.clone(),
@@ -449,9 +451,12 @@ mod tests {
                     builder_proposals: None,
                     builder_boost_factor: None,
                     prefer_builder_proposals: None,
+                    attestation_offset_ms: None,
+                    builder_min_bid_gwei: None,
                     description: String::default(),
                     signing_definition: SigningDefinition::Web3Signer(Web3SignerDefinition {
                         url: signer_rig.url.to_string(),
+                        fallback_urls: vec![],
                         root_certificate_path: Some(root_certificate_path()),
                         request_timeout_ms: None,
                         client_identity_path: Some(client_identity_path()),
@@ -6,6 +6,7 @@ use slog::{warn, Logger};
 use std::cmp::Ordering;
 use std::fmt::{Debug, Display, Formatter};
 use std::str::FromStr;
+use std::time::Duration;
 use types::Slot;
 
 /// Sync distances between 0 and DEFAULT_SYNC_TOLERANCE are considered `synced`.
@@ -20,6 +21,23 @@ const DEFAULT_SYNC_TOLERANCE: Slot = Slot::new(8);
 const DEFAULT_SMALL_SYNC_DISTANCE_MODIFIER: Slot = Slot::new(8);
 const DEFAULT_MEDIUM_SYNC_DISTANCE_MODIFIER: Slot = Slot::new(48);
 
+/// The score of each health tier. Penalties for sync distance, request latency and request errors
+/// are added on top, so a node which fails most requests ranks below healthy nodes in the next
+/// tier. Lower scores are better.
+const TIER_SCORE: u64 = 100;
+/// The maximum penalty for the sync distance of a node outside the `synced` range.
+const MAX_SYNC_DISTANCE_PENALTY: u64 = 50;
+/// One point is added to the score for each `LATENCY_PENALTY_INTERVAL_MS` of average latency.
+///
+/// The interval is coarse so that small fluctuations in latency don't reorder nodes.
+const LATENCY_PENALTY_INTERVAL_MS: u64 = 100;
+/// The maximum penalty for request latency.
+const MAX_LATENCY_PENALTY: u64 = 50;
+/// Points added to the score for each percent of recent requests which failed.
+const ERROR_RATE_PENALTY: u64 = 2;
+/// The weight given to each new request when updating the moving averages in `RequestStats`.
+const REQUEST_STATS_WEIGHT: f64 = 0.1;
+
 type HealthTier = u8;
 type SyncDistance = Slot;
 
@@ -157,6 +175,42 @@ impl BeaconNodeHealthTier {
     }
 }
 
+/// Exponential moving averages of the latency and outcome of requests made to a beacon node.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct RequestStats {
+    /// Average latency of successful requests, or `None` if no request has succeeded yet.
+    latency_ms: Option<f64>,
+    /// Fraction of recent requests which failed, between 0 and 1.
+    error_rate: f64,
+}
+
+impl RequestStats {
+    pub fn record_success(&mut self, latency: Duration) {
+        let latency_ms = latency.as_secs_f64() * 1000.0;
+        self.latency_ms = Some(match self.latency_ms {
+            Some(average) => moving_average(average, latency_ms),
+            None => latency_ms,
+        });
+        self.error_rate = moving_average(self.error_rate, 0.0);
+    }
+
+    pub fn record_error(&mut self) {
+        self.error_rate = moving_average(self.error_rate, 1.0);
+    }
+
+    pub fn latency_ms(&self) -> u64 {
+        self.latency_ms.unwrap_or(0.0).round() as u64
+    }
+
+    pub fn error_rate_percent(&self) -> u8 {
+        (self.error_rate * 100.0).round() as u8
+    }
+}
+
+fn moving_average(average: f64, value: f64) -> f64 {
+    average + REQUEST_STATS_WEIGHT * (value - average)
+}
+
 /// Beacon Node Health metrics.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct BeaconNodeHealth {
@@ -170,14 +224,20 @@ pub struct BeaconNodeHealth {
     pub optimistic_status: IsOptimistic,
     // The status of the nodes connected Execution Engine.
     pub execution_status: ExecutionEngineHealth,
-    // The overall health tier of the Beacon Node. Used to rank the nodes for the purposes of
-    // fallbacks.
+    // The overall health tier of the Beacon Node.
     pub health_tier: BeaconNodeHealthTier,
+    // The average latency of recent requests to the node, in milliseconds.
+    pub latency_ms: u64,
+    // The percentage of recent requests to the node which failed.
+    pub error_rate: u8,
+    // The score combining the health tier, sync distance, latency and error rate. Used to rank
+    // the nodes for the purposes of fallbacks, lower is better.
+    pub score: u64,
 }
 
 impl Ord for BeaconNodeHealth {
     fn cmp(&self, other: &Self) -> Ordering {
-        let ordering = self.health_tier.cmp(&other.health_tier);
+        let ordering = self.score.cmp(&other.score);
         if ordering == Ordering::Equal {
             // Tie-break node health by `user_index`.
             self.user_index.cmp(&other.user_index)
@@ -200,6 +260,7 @@ impl BeaconNodeHealth {
         head: Slot,
         optimistic_status: IsOptimistic,
         execution_status: ExecutionEngineHealth,
+        request_stats: RequestStats,
         distance_tiers: &BeaconNodeSyncDistanceTiers,
     ) -> Self {
         let health_tier = BeaconNodeHealth::compute_health_tier(
@@ -208,6 +269,8 @@ impl BeaconNodeHealth {
             execution_status,
             distance_tiers,
         );
+        let latency_ms = request_stats.latency_ms();
+        let error_rate = request_stats.error_rate_percent();
 
         Self {
             user_index,
@@ -215,6 +278,9 @@ impl BeaconNodeHealth {
             optimistic_status,
             execution_status,
             health_tier,
+            latency_ms,
+            error_rate,
+            score: BeaconNodeHealth::compute_score(health_tier, latency_ms, error_rate),
         }
     }
 
@@ -226,6 +292,35 @@ impl BeaconNodeHealth {
         self.health_tier
     }
 
+    /// Returns true if the node is within the `synced` sync distance tier.
+    pub fn is_synced(&self) -> bool {
+        self.health_tier.distance_tier == SyncDistanceTier::Synced
+    }
+
+    pub fn compute_score(
+        health_tier: BeaconNodeHealthTier,
+        latency_ms: u64,
+        error_rate: u8,
+    ) -> u64 {
+        // Nodes within the `synced` range aren't penalised for their sync distance, so that
+        // validator clients don't artificially prefer one node.
+        let sync_distance_penalty = if health_tier.distance_tier == SyncDistanceTier::Synced {
+            0
+        } else {
+            health_tier
+                .sync_distance
+                .as_u64()
+                .min(MAX_SYNC_DISTANCE_PENALTY)
+        };
+        let latency_penalty = (latency_ms / LATENCY_PENALTY_INTERVAL_MS).min(MAX_LATENCY_PENALTY);
+        let error_penalty = error_rate as u64 * ERROR_RATE_PENALTY;
+
+        health_tier.tier as u64 * TIER_SCORE
+            + sync_distance_penalty
+            + latency_penalty
+            + error_penalty
+    }
+
     fn compute_health_tier(
         sync_distance: SyncDistance,
         optimistic_status: IsOptimistic,
@@ -317,10 +412,11 @@ mod tests {
     use super::ExecutionEngineHealth::{Healthy, Unhealthy};
     use super::{
         BeaconNodeHealth, BeaconNodeHealthTier, BeaconNodeSyncDistanceTiers, IsOptimistic,
-        SyncDistanceTier,
+        RequestStats, SyncDistanceTier,
     };
     use crate::Config;
     use std::str::FromStr;
+    use std::time::Duration;
     use types::Slot;
 
     #[test]
@@ -339,6 +435,7 @@ mod tests {
                         Slot::new(head_slot),
                         *optimistic_status,
                         *ee_health,
+                        RequestStats::default(),
                         &beacon_node_sync_distance_tiers,
                     );
                     health_vec.push(health);
@@ -444,4 +541,47 @@ mod tests {
         assert_eq!(medium_high.tier, 4);
         assert_eq!(large.tier, 10);
     }
+
+    #[test]
+    fn request_stats_moving_average() {
+        let mut stats = RequestStats::default();
+        assert_eq!(stats.latency_ms(), 0);
+        assert_eq!(stats.error_rate_percent(), 0);
+
+        // The first successful request sets the latency.
+        stats.record_success(Duration::from_millis(200));
+        assert_eq!(stats.latency_ms(), 200);
+
+        stats.record_success(Duration::from_millis(1200));
+        assert_eq!(stats.latency_ms(), 300);
+
+        stats.record_error();
+        assert_eq!(stats.error_rate_percent(), 10);
+        assert_eq!(stats.latency_ms(), 300);
+
+        stats.record_success(Duration::from_millis(300));
+        assert_eq!(stats.error_rate_percent(), 9);
+    }
+
+    #[test]
+    fn score_penalties() {
+        let distance_tiers = BeaconNodeSyncDistanceTiers::default();
+        let synced = new_distance_tier(2, &distance_tiers);
+        let small = new_distance_tier(10, &distance_tiers);
+
+        // Sync distance within the `synced` range isn't penalised.
+        assert_eq!(BeaconNodeHealth::compute_score(synced, 0, 0), 100);
+        assert_eq!(BeaconNodeHealth::compute_score(small, 0, 0), 210);
+
+        // Latency is penalised in coarse steps, up to a limit.
+        assert_eq!(BeaconNodeHealth::compute_score(synced, 99, 0), 100);
+        assert_eq!(BeaconNodeHealth::compute_score(synced, 250, 0), 102);
+        assert_eq!(BeaconNodeHealth::compute_score(synced, 60_000, 0), 150);
+
+        // A synced node failing most requests ranks below a healthy node in the next tier.
+        assert!(
+            BeaconNodeHealth::compute_score(synced, 0, 60)
+                > BeaconNodeHealth::compute_score(small, 0, 0)
+        );
+    }
 }
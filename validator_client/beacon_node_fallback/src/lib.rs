@@ -5,11 +5,12 @@
 pub mod beacon_node_health;
 use beacon_node_health::{
     check_node_health, BeaconNodeHealth, BeaconNodeSyncDistanceTiers, ExecutionEngineHealth,
-    IsOptimistic, SyncDistanceTier,
+    IsOptimistic, RequestStats, SyncDistanceTier,
 };
 use environment::RuntimeContext;
 use eth2::BeaconNodeHttpClient;
 use futures::future;
+use parking_lot::Mutex;
 use serde::{ser::SerializeStruct, Deserialize, Serialize, Serializer};
 use slog::{debug, error, warn, Logger};
 use slot_clock::SlotClock;
@@ -20,10 +21,13 @@ use std::future::Future;
 use std::marker::PhantomData;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use strum::{EnumString, EnumVariantNames};
+use strum::{EnumString, EnumVariantNames, IntoStaticStr};
 use tokio::{sync::RwLock, time::sleep};
 use types::{ChainSpec, Config as ConfigSpec, EthSpec, Slot};
-use validator_metrics::{inc_counter_vec, ENDPOINT_ERRORS, ENDPOINT_REQUESTS};
+use validator_metrics::{
+    inc_counter_vec, set_gauge_vec, ENDPOINT_BROADCASTS, ENDPOINT_DUTIES_SERVED, ENDPOINT_ERRORS,
+    ENDPOINT_HEALTH_SCORE, ENDPOINT_REQUESTS,
+};
 
 /// Message emitted when the VC detects the BN is using a different spec.
 const UPDATE_REQUIRED_LOG_HINT: &str = "this VC or the remote BN may need updating";
@@ -41,6 +45,10 @@ const SLOT_LOOKAHEAD: Duration = Duration::from_secs(2);
 /// will be marked as CandidateError::TimeDiscrepancy.
 const FUTURE_SLOT_TOLERANCE: Slot = Slot::new(1);
 
+/// The preferred node is only replaced when another node's health score is better by more than
+/// this margin, to avoid switching nodes on small fluctuations in latency.
+const STICKY_SCORE_MARGIN: u64 = 10;
+
 // Configuration for the Beacon Node fallback.
 #[derive(Copy, Clone, Debug, Default, Serialize, Deserialize)]
 pub struct Config {
@@ -95,12 +103,15 @@ pub fn start_fallback_updater_service<T: SlotClock + 'static, E: EthSpec>(
 pub enum Error<T> {
     /// We attempted to contact the node but it failed.
     RequestFailed(T),
+    /// The node was skipped because the duty requires a synced node.
+    Unsynced,
 }
 
 impl<T> Error<T> {
     pub fn request_failure(&self) -> Option<&T> {
         match self {
             Error::RequestFailed(e) => Some(e),
+            Error::Unsynced => None,
         }
     }
 }
@@ -188,6 +199,7 @@ pub struct CandidateBeaconNode<E> {
     pub index: usize,
     pub beacon_node: BeaconNodeHttpClient,
     pub health: Arc<RwLock<Result<BeaconNodeHealth, CandidateError>>>,
+    pub request_stats: Arc<Mutex<RequestStats>>,
     _phantom: PhantomData<E>,
 }
 
@@ -206,6 +218,7 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
             index,
             beacon_node,
             health: Arc::new(RwLock::new(Err(CandidateError::Uninitialized))),
+            request_stats: Arc::new(Mutex::new(RequestStats::default())),
             _phantom: PhantomData,
         }
     }
@@ -259,14 +272,21 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
                         IsOptimistic::No
                     };
 
+                    let request_stats = *self.request_stats.lock();
                     let new_health = BeaconNodeHealth::from_status(
                         self.index,
                         sync_distance,
                         head,
                         optimistic_status,
                         execution_status,
+                        request_stats,
                         distance_tiers,
                     );
+                    set_gauge_vec(
+                        &ENDPOINT_HEALTH_SCORE,
+                        &[self.beacon_node.as_ref()],
+                        new_health.score as i64,
+                    );
 
                     *self.health.write().await = Ok(new_health);
                     Ok(())
@@ -552,6 +572,22 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
     /// Run `func` against each candidate in `self`, returning immediately if a result is found.
     /// Otherwise, return all the errors encountered along the way.
     pub async fn first_success<F, O, Err, R>(&self, func: F) -> Result<O, Errors<Err>>
+    where
+        F: Fn(BeaconNodeHttpClient) -> R,
+        R: Future<Output = Result<O, Err>>,
+        Err: Debug,
+    {
+        self.first_success_for(DutyType::Other, func).await
+    }
+
+    /// As `first_success`, but only using candidates which are suitable for `duty`.
+    ///
+    /// The candidate which served the request is recorded in metrics against `duty`.
+    pub async fn first_success_for<F, O, Err, R>(
+        &self,
+        duty: DutyType,
+        func: F,
+    ) -> Result<O, Errors<Err>>
     where
         F: Fn(BeaconNodeHttpClient) -> R,
         R: Future<Output = Result<O, Err>>,
@@ -559,47 +595,38 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
     {
         let mut errors = vec![];
 
-        // First pass: try `func` on all candidates. Candidate order has already been set in
+        // Two passes are made over the candidates, so that a transient error on every node doesn't
+        // cause the request to fail. Candidate order has already been set in
         // `update_all_candidates`. This ensures the most suitable node is always tried first.
-        let candidates = self.candidates.read().await;
-        let mut futures = vec![];
-
-        // Run `func` using a `candidate`, returning the value or capturing errors.
-        for candidate in candidates.iter() {
-            futures.push(Self::run_on_candidate(
-                candidate.beacon_node.clone(),
-                &func,
-                &self.log,
-            ));
-        }
-        drop(candidates);
-
-        for future in futures {
-            match future.await {
-                Ok(val) => return Ok(val),
-                Err(e) => errors.push(e),
+        // The second pass will duplicate errors.
+        for _ in 0..2 {
+            let candidates = self.candidates.read().await.clone();
+
+            let mut is_synced = Vec::with_capacity(candidates.len());
+            for candidate in candidates.iter() {
+                is_synced
+                    .push(matches!(candidate.health().await, Ok(health) if health.is_synced()));
             }
-        }
-
-        // Second pass. No candidates returned successfully. Try again with the same order.
-        // This will duplicate errors.
-        let candidates = self.candidates.read().await;
-        let mut futures = vec![];
+            // If no candidate is synced, fall back to all candidates rather than failing the duty.
+            let skip_unsynced = duty.requires_synced() && is_synced.contains(&true);
 
-        // Run `func` using a `candidate`, returning the value or capturing errors.
-        for candidate in candidates.iter() {
-            futures.push(Self::run_on_candidate(
-                candidate.beacon_node.clone(),
-                &func,
-                &self.log,
-            ));
-        }
-        drop(candidates);
+            for (candidate, is_synced) in candidates.iter().zip(is_synced) {
+                if skip_unsynced && !is_synced {
+                    errors.push((candidate.beacon_node.to_string(), Error::Unsynced));
+                    continue;
+                }
 
-        for future in futures {
-            match future.await {
-                Ok(val) => return Ok(val),
-                Err(e) => errors.push(e),
+                match Self::run_on_candidate(candidate, &func, &self.log).await {
+                    Ok(val) => {
+                        let duty_label: &'static str = duty.into();
+                        inc_counter_vec(
+                            &ENDPOINT_DUTIES_SERVED,
+                            &[candidate.beacon_node.as_ref(), duty_label],
+                        );
+                        return Ok(val);
+                    }
+                    Err(e) => errors.push(e),
+                }
             }
         }
 
@@ -608,8 +635,10 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
     }
 
     /// Run the future `func` on `candidate` while reporting metrics.
+    ///
+    /// The latency and outcome of the request are recorded in the candidate's `request_stats`.
     async fn run_on_candidate<F, R, Err, O>(
-        candidate: BeaconNodeHttpClient,
+        candidate: &CandidateBeaconNode<E>,
         func: F,
         log: &Logger,
     ) -> Result<O, (String, Error<Err>)>
@@ -618,21 +647,30 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         R: Future<Output = Result<O, Err>>,
         Err: Debug,
     {
-        inc_counter_vec(&ENDPOINT_REQUESTS, &[candidate.as_ref()]);
+        let beacon_node = &candidate.beacon_node;
+        inc_counter_vec(&ENDPOINT_REQUESTS, &[beacon_node.as_ref()]);
 
         // There exists a race condition where `func` may be called when the candidate is
         // actually not ready. We deem this an acceptable inefficiency.
-        match func(candidate.clone()).await {
-            Ok(val) => Ok(val),
+        let request_instant = Instant::now();
+        match func(beacon_node.clone()).await {
+            Ok(val) => {
+                candidate
+                    .request_stats
+                    .lock()
+                    .record_success(request_instant.elapsed());
+                Ok(val)
+            }
             Err(e) => {
                 debug!(
                     log,
                     "Request to beacon node failed";
-                    "node" => %candidate,
+                    "node" => %beacon_node,
                     "error" => ?e,
                 );
-                inc_counter_vec(&ENDPOINT_ERRORS, &[candidate.as_ref()]);
-                Err((candidate.to_string(), Error::RequestFailed(e)))
+                inc_counter_vec(&ENDPOINT_ERRORS, &[beacon_node.as_ref()]);
+                candidate.request_stats.lock().record_error();
+                Err((beacon_node.to_string(), Error::RequestFailed(e)))
             }
         }
     }
@@ -645,27 +683,52 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
     /// Since this ignores the actual result of `func`, this function should only be used for beacon
     /// node calls whose results we do not care about, only that they completed successfully.
     pub async fn broadcast<F, O, Err, R>(&self, func: F) -> Result<(), Errors<Err>>
+    where
+        F: Fn(BeaconNodeHttpClient) -> R,
+        R: Future<Output = Result<O, Err>>,
+        Err: Debug,
+    {
+        self.broadcast_inner(None, func).await
+    }
+
+    /// As `broadcast`, recording the outcome for each candidate in metrics against `topic` if it
+    /// is provided.
+    async fn broadcast_inner<F, O, Err, R>(
+        &self,
+        topic: Option<ApiTopic>,
+        func: F,
+    ) -> Result<(), Errors<Err>>
     where
         F: Fn(BeaconNodeHttpClient) -> R,
         R: Future<Output = Result<O, Err>>,
         Err: Debug,
     {
         // Run `func` on all candidates.
-        let candidates = self.candidates.read().await;
+        let candidates = self.candidates.read().await.clone();
         let mut futures = vec![];
 
         // Run `func` using a `candidate`, returning the value or capturing errors.
         for candidate in candidates.iter() {
-            futures.push(Self::run_on_candidate(
-                candidate.beacon_node.clone(),
-                &func,
-                &self.log,
-            ));
+            futures.push(Self::run_on_candidate(candidate, &func, &self.log));
         }
-        drop(candidates);
 
         let results = future::join_all(futures).await;
 
+        if let Some(topic) = topic {
+            let topic_label: &'static str = topic.into();
+            for (candidate, result) in candidates.iter().zip(results.iter()) {
+                let outcome = if result.is_ok() {
+                    validator_metrics::SUCCESS
+                } else {
+                    validator_metrics::FAILURE
+                };
+                inc_counter_vec(
+                    &ENDPOINT_BROADCASTS,
+                    &[candidate.beacon_node.as_ref(), topic_label, outcome],
+                );
+            }
+        }
+
         let errors: Vec<_> = results.into_iter().filter_map(|res| res.err()).collect();
 
         if !errors.is_empty() {
@@ -678,21 +741,39 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
     /// Call `func` on first beacon node that returns success or on all beacon nodes
     /// depending on the `topic` and configuration.
     pub async fn request<F, Err, R>(&self, topic: ApiTopic, func: F) -> Result<(), Errors<Err>>
+    where
+        F: Fn(BeaconNodeHttpClient) -> R,
+        R: Future<Output = Result<(), Err>>,
+        Err: Debug,
+    {
+        self.request_for(topic, DutyType::Other, func).await
+    }
+
+    /// As `request`, but using `first_success_for` with `duty` if `topic` isn't broadcast.
+    pub async fn request_for<F, Err, R>(
+        &self,
+        topic: ApiTopic,
+        duty: DutyType,
+        func: F,
+    ) -> Result<(), Errors<Err>>
     where
         F: Fn(BeaconNodeHttpClient) -> R,
         R: Future<Output = Result<(), Err>>,
         Err: Debug,
     {
         if self.broadcast_topics.contains(&topic) {
-            self.broadcast(func).await
+            self.broadcast_inner(Some(topic), func).await
         } else {
-            self.first_success(func).await?;
+            self.first_success_for(duty, func).await?;
             Ok(())
         }
     }
 }
 
 /// Helper functions to allow sorting candidate nodes by health.
+///
+/// The node at the front of `nodes` is kept there whilst it remains within `STICKY_SCORE_MARGIN` of
+/// the best node, so that the preferred node doesn't change on every update.
 async fn sort_nodes_by_health<E: EthSpec>(nodes: &mut Vec<CandidateBeaconNode<E>>) {
     // Fetch all health values.
     let health_results: Vec<Result<BeaconNodeHealth, CandidateError>> =
@@ -710,6 +791,19 @@ async fn sort_nodes_by_health<E: EthSpec>(nodes: &mut Vec<CandidateBeaconNode<E>
         (Err(_), Err(_)) => Ordering::Equal,
     });
 
+    // Move the previously preferred node back to the front if it is still close to the best.
+    if let Some((_, Ok(best))) = indices_with_health.first() {
+        let best_score = best.score;
+        let previous_position = indices_with_health.iter().position(|(index, health)| {
+            *index == 0
+                && matches!(health, Ok(health) if health.score <= best_score + STICKY_SCORE_MARGIN)
+        });
+        if let Some(position) = previous_position {
+            let previous = indices_with_health.remove(position);
+            indices_with_health.insert(0, previous);
+        }
+    }
+
     // Reorder candidates based on the sorted indices.
     let sorted_nodes: Vec<CandidateBeaconNode<E>> = indices_with_health
         .into_iter()
@@ -719,7 +813,17 @@ async fn sort_nodes_by_health<E: EthSpec>(nodes: &mut Vec<CandidateBeaconNode<E>
 }
 
 /// Serves as a cue for `BeaconNodeFallback` to tell which requests need to be broadcasted.
-#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize, EnumString, EnumVariantNames)]
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Deserialize,
+    Serialize,
+    EnumString,
+    EnumVariantNames,
+    IntoStaticStr,
+)]
 #[strum(serialize_all = "kebab-case")]
 pub enum ApiTopic {
     Attestations,
@@ -735,6 +839,30 @@ impl ApiTopic {
     }
 }
 
+/// The duty a request is made for. Used to route requests to suitable candidates, and to record
+/// which candidate served each type of duty.
+#[derive(Clone, Copy, Debug, PartialEq, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum DutyType {
+    AttestationData,
+    Aggregation,
+    BlockProposal,
+    SyncCommittee,
+    Duties,
+    Other,
+}
+
+impl DutyType {
+    /// Returns true if the duty should only be served by candidates within the `synced` sync
+    /// distance tier, whenever such a candidate exists.
+    ///
+    /// Attestation data from a node which is behind would result in votes for a stale head, but
+    /// this is still preferable to not attesting at all.
+    pub fn requires_synced(&self) -> bool {
+        matches!(self, DutyType::AttestationData)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -754,7 +882,13 @@ mod tests {
         assert!(ApiTopic::VARIANTS
             .iter()
             .map(|topic| ApiTopic::from_str(topic).unwrap())
-            .eq(all.into_iter()));
+            .eq(all.iter().copied()));
+
+        // Metrics labels use the same names as the `--broadcast` flag.
+        for topic in all {
+            let label: &'static str = topic.into();
+            assert_eq!(ApiTopic::from_str(label), Ok(topic));
+        }
     }
 
     #[tokio::test]
@@ -788,56 +922,31 @@ mod tests {
         let synced = SyncDistanceTier::Synced;
         let small = SyncDistanceTier::Small;
 
-        // Despite `health_1` having a larger sync distance, it is inside the `synced` range which
-        // does not tie-break on sync distance and so will tie-break on `user_index` instead.
-        let health_1 = BeaconNodeHealth {
-            user_index: 1,
+        let new_health = |user_index, health_tier| BeaconNodeHealth {
+            user_index,
             head,
             optimistic_status,
             execution_status,
-            health_tier: BeaconNodeHealthTier::new(1, Slot::new(2), synced),
-        };
-        let health_2 = BeaconNodeHealth {
-            user_index: 2,
-            head,
-            optimistic_status,
-            execution_status,
-            health_tier: BeaconNodeHealthTier::new(2, Slot::new(1), synced),
+            health_tier,
+            latency_ms: 0,
+            error_rate: 0,
+            score: BeaconNodeHealth::compute_score(health_tier, 0, 0),
         };
 
+        // Despite `health_1` having a larger sync distance, it is inside the `synced` range which
+        // does not tie-break on sync distance and so will tie-break on `user_index` instead.
+        let health_1 = new_health(1, BeaconNodeHealthTier::new(1, Slot::new(2), synced));
+        let health_2 = new_health(2, BeaconNodeHealthTier::new(2, Slot::new(1), synced));
+
         // `health_3` and `health_4` have the same health tier and sync distance so should
         // tie-break on `user_index`.
-        let health_3 = BeaconNodeHealth {
-            user_index: 3,
-            head,
-            optimistic_status,
-            execution_status,
-            health_tier: BeaconNodeHealthTier::new(3, Slot::new(9), small),
-        };
-        let health_4 = BeaconNodeHealth {
-            user_index: 4,
-            head,
-            optimistic_status,
-            execution_status,
-            health_tier: BeaconNodeHealthTier::new(3, Slot::new(9), small),
-        };
+        let health_3 = new_health(3, BeaconNodeHealthTier::new(3, Slot::new(9), small));
+        let health_4 = new_health(4, BeaconNodeHealthTier::new(3, Slot::new(9), small));
 
         // `health_5` has a smaller sync distance and is outside the `synced` range so should be
         // sorted first. Note the values of `user_index`.
-        let health_5 = BeaconNodeHealth {
-            user_index: 6,
-            head,
-            optimistic_status,
-            execution_status,
-            health_tier: BeaconNodeHealthTier::new(4, Slot::new(9), small),
-        };
-        let health_6 = BeaconNodeHealth {
-            user_index: 5,
-            head,
-            optimistic_status,
-            execution_status,
-            health_tier: BeaconNodeHealthTier::new(4, Slot::new(10), small),
-        };
+        let health_5 = new_health(6, BeaconNodeHealthTier::new(4, Slot::new(9), small));
+        let health_6 = new_health(5, BeaconNodeHealthTier::new(4, Slot::new(10), small));
 
         *candidate_1.health.write().await = Ok(health_1);
         *candidate_2.health.write().await = Ok(health_2);
@@ -867,4 +976,175 @@ mod tests {
 
         assert_eq!(candidates, expected_candidates);
     }
+
+    #[tokio::test]
+    async fn preferred_candidate_is_sticky() {
+        let synced = SyncDistanceTier::Synced;
+        let health_tier = BeaconNodeHealthTier::new(1, Slot::new(0), synced);
+        let new_candidate = |index: usize, latency_ms| {
+            let beacon_node = BeaconNodeHttpClient::new(
+                SensitiveUrl::parse(&format!("http://example_{index}.com")).unwrap(),
+                Timeouts::set_all(Duration::from_secs(1)),
+            );
+            let candidate = CandidateBeaconNode::<E>::new(beacon_node, index);
+            let health = BeaconNodeHealth {
+                user_index: index,
+                head: Slot::new(99),
+                optimistic_status: IsOptimistic::No,
+                execution_status: ExecutionEngineHealth::Healthy,
+                health_tier,
+                latency_ms,
+                error_rate: 0,
+                score: BeaconNodeHealth::compute_score(health_tier, latency_ms, 0),
+            };
+            (candidate, health)
+        };
+
+        // The preferred node is slightly slower than the other, so it remains preferred.
+        let (candidate_1, health_1) = new_candidate(1, 0);
+        let (candidate_2, health_2) = new_candidate(2, 500);
+        *candidate_1.health.write().await = Ok(health_1);
+        *candidate_2.health.write().await = Ok(health_2);
+        let mut candidates = vec![candidate_2.clone(), candidate_1.clone()];
+        sort_nodes_by_health(&mut candidates).await;
+        assert_eq!(candidates, vec![candidate_2.clone(), candidate_1.clone()]);
+
+        // Once the difference exceeds the margin, the faster node is preferred.
+        let (_, health_2) = new_candidate(2, 2_000);
+        *candidate_2.health.write().await = Ok(health_2);
+        sort_nodes_by_health(&mut candidates).await;
+        assert_eq!(candidates, vec![candidate_1, candidate_2]);
+    }
+
+    #[tokio::test]
+    async fn attestation_data_prefers_synced_candidate() {
+        let new_candidate = |index: usize| {
+            let beacon_node = BeaconNodeHttpClient::new(
+                SensitiveUrl::parse(&format!("http://example_{index}.com")).unwrap(),
+                Timeouts::set_all(Duration::from_secs(1)),
+            );
+            CandidateBeaconNode::<E>::new(beacon_node, index)
+        };
+        let new_fallback = |candidates| {
+            BeaconNodeFallback::<slot_clock::TestingSlotClock, E>::new(
+                candidates,
+                Config::default(),
+                vec![],
+                Arc::new(E::default_spec()),
+                logging::test_logger(),
+            )
+        };
+        let request_attestation_data =
+            |fallback: BeaconNodeFallback<slot_clock::TestingSlotClock, E>| async move {
+                let served_by = std::sync::Mutex::new(vec![]);
+                let result = fallback
+                    .first_success_for(DutyType::AttestationData, |beacon_node| {
+                        served_by.lock().unwrap().push(beacon_node.to_string());
+                        async { Ok::<(), ()>(()) }
+                    })
+                    .await;
+                assert!(result.is_ok());
+                served_by.into_inner().unwrap()
+            };
+
+        // The health of both candidates is uninitialized, so neither is known to be synced. The
+        // request falls back to the first candidate rather than failing.
+        let unsynced = new_candidate(1);
+        let synced = new_candidate(2);
+        let served_by =
+            request_attestation_data(new_fallback(vec![unsynced.clone(), synced.clone()])).await;
+        assert_eq!(served_by, vec![unsynced.beacon_node.to_string()]);
+
+        // Once a candidate is synced, it is used in preference to the unsynced candidate.
+        let health_tier = BeaconNodeHealthTier::new(0, Slot::new(0), SyncDistanceTier::Synced);
+        *synced.health.write().await = Ok(BeaconNodeHealth {
+            user_index: 2,
+            head: Slot::new(99),
+            optimistic_status: IsOptimistic::No,
+            execution_status: ExecutionEngineHealth::Healthy,
+            health_tier,
+            latency_ms: 0,
+            error_rate: 0,
+            score: BeaconNodeHealth::compute_score(health_tier, 0, 0),
+        });
+        let served_by =
+            request_attestation_data(new_fallback(vec![unsynced, synced.clone()])).await;
+        assert_eq!(served_by, vec![synced.beacon_node.to_string()]);
+    }
+
+    #[tokio::test]
+    async fn request_for_records_duty_or_broadcast_metrics() {
+        let new_candidate = |index: usize| {
+            let beacon_node = BeaconNodeHttpClient::new(
+                SensitiveUrl::parse(&format!("http://request_for_{index}.com")).unwrap(),
+                Timeouts::set_all(Duration::from_secs(1)),
+            );
+            CandidateBeaconNode::<E>::new(beacon_node, index)
+        };
+        let new_fallback = |candidates, broadcast_topics| {
+            BeaconNodeFallback::<slot_clock::TestingSlotClock, E>::new(
+                candidates,
+                Config::default(),
+                broadcast_topics,
+                Arc::new(E::default_spec()),
+                logging::test_logger(),
+            )
+        };
+        let candidate_1 = new_candidate(1);
+        let candidate_2 = new_candidate(2);
+        let endpoint_1 = candidate_1.beacon_node.as_ref().to_string();
+        let endpoint_2 = candidate_2.beacon_node.as_ref().to_string();
+        let duty_label: &'static str = DutyType::Aggregation.into();
+        let topic_label: &'static str = ApiTopic::Attestations.into();
+        let duties_served = |endpoint: &str| {
+            validator_metrics::get_int_counter(&ENDPOINT_DUTIES_SERVED, &[endpoint, duty_label])
+                .unwrap()
+                .get()
+        };
+        let broadcasts = |endpoint: &str, outcome: &str| {
+            validator_metrics::get_int_counter(
+                &ENDPOINT_BROADCASTS,
+                &[endpoint, topic_label, outcome],
+            )
+            .unwrap()
+            .get()
+        };
+
+        // Without broadcasting, the first candidate serves the duty and is recorded against it.
+        let fallback = new_fallback(vec![candidate_1.clone(), candidate_2.clone()], vec![]);
+        fallback
+            .request_for(ApiTopic::Attestations, DutyType::Aggregation, |_| async {
+                Ok::<(), ()>(())
+            })
+            .await
+            .unwrap();
+        assert_eq!(duties_served(&endpoint_1), 1);
+        assert_eq!(duties_served(&endpoint_2), 0);
+        assert_eq!(broadcasts(&endpoint_1, validator_metrics::SUCCESS), 0);
+
+        // When the topic is broadcast, the outcome for every candidate is recorded instead.
+        let fallback = new_fallback(vec![candidate_1, candidate_2], vec![ApiTopic::Attestations]);
+        let result = fallback
+            .request_for(
+                ApiTopic::Attestations,
+                DutyType::Aggregation,
+                |beacon_node| {
+                    let endpoint = beacon_node.as_ref().to_string();
+                    let endpoint_1 = endpoint_1.clone();
+                    async move {
+                        if endpoint == endpoint_1 {
+                            Ok(())
+                        } else {
+                            Err(())
+                        }
+                    }
+                },
+            )
+            .await;
+        assert!(result.is_err());
+        assert_eq!(duties_served(&endpoint_1), 1);
+        assert_eq!(broadcasts(&endpoint_1, validator_metrics::SUCCESS), 1);
+        assert_eq!(broadcasts(&endpoint_2, validator_metrics::FAILURE), 1);
+        assert_eq!(broadcasts(&endpoint_2, validator_metrics::SUCCESS), 0);
+    }
 }
@@ -0,0 +1,139 @@
+//! Background import of keystore batches via `POST lighthouse/keystores/import`.
+//!
+//! Importing thousands of keystores through the standard `POST eth/v1/keystores` endpoint can take
+//! longer than a client is willing to wait for a response, since each keystore must be decrypted.
+//! This endpoint instead starts the import in the background and returns an ID, which is used to
+//! poll the progress of each keystore via `GET lighthouse/keystores/import/{import_id}`.
+use crate::keystores::{self, ImportProgress};
+use eth2::lighthouse_vc::{
+    std_types::{ImportKeystoreStatus, ImportKeystoresRequest, KeystoreJsonStr, Status},
+    types::{
+        KeystoreImportJob, KeystoreImportProgress, KeystoreImportStage,
+        SingleKeystoreImportProgress,
+    },
+};
+use parking_lot::Mutex;
+use slog::{info, Logger};
+use slot_clock::SlotClock;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use task_executor::TaskExecutor;
+use types::EthSpec;
+use validator_store::ValidatorStore;
+use warp::Rejection;
+
+/// The number of imports whose progress is retained, including imports which have finished.
+const MAX_RETAINED_IMPORTS: usize = 16;
+
+#[derive(Default)]
+pub struct KeystoreImports {
+    next_id: AtomicU64,
+    imports: Mutex<BTreeMap<u64, Arc<Mutex<KeystoreImportProgress>>>>,
+}
+
+impl KeystoreImports {
+    /// Start importing the keystores in `request` in the background, returning the ID used to
+    /// query its progress.
+    pub fn start<T: SlotClock + 'static, E: EthSpec>(
+        &self,
+        request: ImportKeystoresRequest,
+        validator_dir: PathBuf,
+        secrets_dir: Option<PathBuf>,
+        validator_store: Arc<ValidatorStore<T, E>>,
+        task_executor: TaskExecutor,
+        log: Logger,
+    ) -> Result<KeystoreImportJob, Rejection> {
+        keystores::check_import_request(&request)?;
+
+        let progress = Arc::new(Mutex::new(KeystoreImportProgress {
+            completed: 0,
+            total: request.keystores.len() as u64,
+            keystores: request
+                .keystores
+                .iter()
+                .map(|KeystoreJsonStr(keystore)| SingleKeystoreImportProgress {
+                    validating_pubkey: keystore.public_key().map(|pubkey| pubkey.compress()),
+                    stage: KeystoreImportStage::Pending,
+                    status: None,
+                })
+                .collect(),
+        }));
+        let import_id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.insert(import_id, progress.clone());
+
+        info!(
+            log,
+            "Started background keystore import";
+            "import_id" => import_id,
+            "count" => request.keystores.len(),
+        );
+
+        let executor = task_executor.clone();
+        task_executor.spawn_blocking(
+            move || {
+                keystores::import_keystores(
+                    request,
+                    validator_dir,
+                    secrets_dir,
+                    &validator_store,
+                    &executor,
+                    &log,
+                    &*progress,
+                );
+                info!(
+                    log,
+                    "Finished background keystore import";
+                    "import_id" => import_id,
+                );
+            },
+            "keystore_import",
+        );
+
+        Ok(KeystoreImportJob { import_id })
+    }
+
+    /// Returns the progress of the import with `import_id`, if it is known.
+    pub fn progress(&self, import_id: u64) -> Option<KeystoreImportProgress> {
+        self.imports
+            .lock()
+            .get(&import_id)
+            .map(|progress| progress.lock().clone())
+    }
+
+    fn insert(&self, import_id: u64, progress: Arc<Mutex<KeystoreImportProgress>>) {
+        let mut imports = self.imports.lock();
+        imports.insert(import_id, progress);
+
+        // Forget the oldest finished imports. Imports which are still running are always kept.
+        while imports.len() > MAX_RETAINED_IMPORTS {
+            let Some(oldest_complete) = imports
+                .iter()
+                .find(|(_, progress)| progress.lock().is_complete())
+                .map(|(import_id, _)| *import_id)
+            else {
+                break;
+            };
+            imports.remove(&oldest_complete);
+        }
+    }
+}
+
+impl ImportProgress for Mutex<KeystoreImportProgress> {
+    fn stage(&self, index: usize, stage: KeystoreImportStage) {
+        if let Some(keystore) = self.lock().keystores.get_mut(index) {
+            keystore.stage = stage;
+        }
+    }
+
+    fn complete(&self, index: usize, status: &Status<ImportKeystoreStatus>) {
+        let mut guard = self.lock();
+        let progress = &mut *guard;
+        if let Some(keystore) = progress.keystores.get_mut(index) {
+            keystore.stage = KeystoreImportStage::Complete;
+            keystore.status = Some(status.clone());
+            progress.completed += 1;
+        }
+    }
+}
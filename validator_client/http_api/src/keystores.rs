@@ -6,7 +6,7 @@ use eth2::lighthouse_vc::{
         ImportKeystoreStatus, ImportKeystoresRequest, ImportKeystoresResponse, InterchangeJsonStr,
         KeystoreJsonStr, ListKeystoresResponse, SingleKeystoreResponse, Status,
     },
-    types::{ExportKeystoresResponse, SingleExportKeystoresResponse},
+    types::{ExportKeystoresResponse, KeystoreImportStage, SingleExportKeystoresResponse},
 };
 use eth2_keystore::Keystore;
 use initialized_validators::{Error, InitializedValidators};
@@ -57,6 +57,33 @@ pub fn list<T: SlotClock + 'static, E: EthSpec>(
     ListKeystoresResponse { data: keystores }
 }
 
+/// Receives updates as each keystore in a batch is imported.
+pub trait ImportProgress {
+    /// The keystore at `index` in the request has reached `stage`.
+    fn stage(&self, index: usize, stage: KeystoreImportStage);
+
+    /// The keystore at `index` in the request has finished importing with `status`.
+    fn complete(&self, index: usize, status: &Status<ImportKeystoreStatus>);
+}
+
+impl ImportProgress for () {
+    fn stage(&self, _: usize, _: KeystoreImportStage) {}
+
+    fn complete(&self, _: usize, _: &Status<ImportKeystoreStatus>) {}
+}
+
+/// Check request validity. This is the only case in which we should return a 4xx code.
+pub fn check_import_request(request: &ImportKeystoresRequest) -> Result<(), Rejection> {
+    if request.keystores.len() != request.passwords.len() {
+        return Err(custom_bad_request(format!(
+            "mismatched numbers of keystores ({}) and passwords ({})",
+            request.keystores.len(),
+            request.passwords.len(),
+        )));
+    }
+    Ok(())
+}
+
 pub fn import<T: SlotClock + 'static, E: EthSpec>(
     request: ImportKeystoresRequest,
     validator_dir: PathBuf,
@@ -65,19 +92,38 @@ pub fn import<T: SlotClock + 'static, E: EthSpec>(
     task_executor: TaskExecutor,
     log: Logger,
 ) -> Result<ImportKeystoresResponse, Rejection> {
-    // Check request validity. This is the only cases in which we should return a 4xx code.
-    if request.keystores.len() != request.passwords.len() {
-        return Err(custom_bad_request(format!(
-            "mismatched numbers of keystores ({}) and passwords ({})",
-            request.keystores.len(),
-            request.passwords.len(),
-        )));
-    }
+    check_import_request(&request)?;
+
+    Ok(import_keystores(
+        request,
+        validator_dir,
+        secrets_dir,
+        &validator_store,
+        &task_executor,
+        &log,
+        &(),
+    ))
+}
 
+/// Import the keystores in a request which has passed `check_import_request`, reporting the
+/// progress of each keystore to `progress`.
+pub fn import_keystores<T: SlotClock + 'static, E: EthSpec>(
+    request: ImportKeystoresRequest,
+    validator_dir: PathBuf,
+    secrets_dir: Option<PathBuf>,
+    validator_store: &ValidatorStore<T, E>,
+    task_executor: &TaskExecutor,
+    log: &Logger,
+    progress: &impl ImportProgress,
+) -> ImportKeystoresResponse {
     // Import slashing protection data before keystores, so that new keystores don't start signing
     // without it. Do not return early on failure, propagate the failure to each key.
     let slashing_protection_status =
         if let Some(InterchangeJsonStr(slashing_protection)) = request.slashing_protection {
+            for index in 0..request.keystores.len() {
+                progress.stage(index, KeystoreImportStage::SlashingProtection);
+            }
+
             // Warn for missing slashing protection.
             for KeystoreJsonStr(ref keystore) in &request.keystores {
                 if let Some(public_key) = keystore.public_key() {
@@ -105,10 +151,11 @@ pub fn import<T: SlotClock + 'static, E: EthSpec>(
     // Import each keystore. Some keystores may fail to be imported, so we record a status for each.
     let mut statuses = Vec::with_capacity(request.keystores.len());
 
-    for (KeystoreJsonStr(keystore), password) in request
+    for (index, (KeystoreJsonStr(keystore), password)) in request
         .keystores
         .into_iter()
         .zip(request.passwords.into_iter())
+        .enumerate()
     {
         let pubkey_str = keystore.pubkey().to_string();
 
@@ -121,13 +168,15 @@ pub fn import<T: SlotClock + 'static, E: EthSpec>(
             )
         } else if let Some(handle) = task_executor.handle() {
             // Import the keystore.
+            progress.stage(index, KeystoreImportStage::Decrypting);
             match import_single_keystore(
                 keystore,
                 password,
                 validator_dir.clone(),
                 secrets_dir.clone(),
-                &validator_store,
+                validator_store,
                 handle,
+                || progress.stage(index, KeystoreImportStage::Activating),
             ) {
                 Ok(status) => Status::ok(status),
                 Err(e) => {
@@ -146,6 +195,7 @@ pub fn import<T: SlotClock + 'static, E: EthSpec>(
                 "validator client shutdown".into(),
             )
         };
+        progress.complete(index, &status);
         statuses.push(status);
     }
 
@@ -162,7 +212,7 @@ pub fn import<T: SlotClock + 'static, E: EthSpec>(
         );
     }
 
-    Ok(ImportKeystoresResponse { data: statuses })
+    ImportKeystoresResponse { data: statuses }
 }
 
 fn import_single_keystore<T: SlotClock + 'static, E: EthSpec>(
@@ -172,6 +222,7 @@ fn import_single_keystore<T: SlotClock + 'static, E: EthSpec>(
     secrets_dir: Option<PathBuf>,
     validator_store: &ValidatorStore<T, E>,
     handle: Handle,
+    on_activating: impl FnOnce(),
 ) -> Result<ImportKeystoreStatus, String> {
     // Check if the validator key already exists, erroring if it is a remote signer validator.
     let pubkey = keystore
@@ -221,6 +272,7 @@ fn import_single_keystore<T: SlotClock + 'static, E: EthSpec>(
     let voting_keystore_path = validator_dir.voting_keystore_path();
     drop(validator_dir);
 
+    on_activating();
     handle
         .block_on(validator_store.add_validator_keystore(
             voting_keystore_path,
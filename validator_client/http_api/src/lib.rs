@@ -2,6 +2,7 @@ mod api_secret;
 mod create_signed_voluntary_exit;
 mod create_validator;
 mod graffiti;
+mod keystore_import;
 mod keystores;
 mod remotekeys;
 mod tests;
@@ -12,6 +13,7 @@ use graffiti::{delete_graffiti, get_graffiti, set_graffiti};
 
 use create_signed_voluntary_exit::create_signed_voluntary_exit;
 use graffiti_file::{determine_graffiti, GraffitiFile};
+use keystore_import::KeystoreImports;
 use validator_store::ValidatorStore;
 
 use account_utils::{
@@ -49,6 +51,7 @@ use tokio_stream::{wrappers::BroadcastStream, StreamExt};
 use types::{ChainSpec, ConfigAndPreset, EthSpec};
 use validator_dir::Builder as ValidatorDirBuilder;
 use validator_services::block_service::BlockService;
+use validator_services::bls_to_execution_change_service::BlsToExecutionChangeService;
 use warp::{sse::Event, Filter};
 use warp_utils::task::blocking_json_task;
 
@@ -77,6 +80,7 @@ pub struct Context<T: SlotClock, E: EthSpec> {
     pub task_executor: TaskExecutor,
     pub api_secret: ApiSecret,
     pub block_service: Option<BlockService<T, E>>,
+    pub bls_to_execution_change_service: Option<Arc<BlsToExecutionChangeService<T, E>>>,
     pub validator_store: Option<Arc<ValidatorStore<T, E>>>,
     pub validator_dir: Option<PathBuf>,
     pub secrets_dir: Option<PathBuf>,
@@ -185,6 +189,17 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             })
         });
 
+    let inner_bls_to_execution_change_service = ctx.bls_to_execution_change_service.clone();
+    let bls_to_execution_change_service_filter = warp::any()
+        .map(move || inner_bls_to_execution_change_service.clone())
+        .and_then(|service: Option<_>| async move {
+            service.ok_or_else(|| {
+                warp_utils::reject::custom_not_found(
+                    "BLS to execution change service is not initialized.".to_string(),
+                )
+            })
+        });
+
     let inner_validator_store = ctx.validator_store.clone();
     let validator_store_filter = warp::any()
         .map(move || inner_validator_store.clone())
@@ -272,6 +287,9 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
     let app_start = std::time::Instant::now();
     let app_start_filter = warp::any().map(move || app_start);
 
+    let keystore_imports = Arc::new(KeystoreImports::default());
+    let keystore_imports_filter = warp::any().map(move || keystore_imports.clone());
+
     // GET lighthouse/version
     let get_node_version = warp::path("lighthouse")
         .and(warp::path("version"))
@@ -324,6 +342,7 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                         enabled: def.enabled,
                         description: def.description.clone(),
                         voting_pubkey: PublicKeyBytes::from(&def.voting_public_key),
+                        builder_min_bid_gwei: def.builder_min_bid_gwei,
                     })
                     .collect::<Vec<_>>();
 
@@ -350,6 +369,7 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                             enabled: def.enabled,
                             description: def.description.clone(),
                             voting_pubkey: PublicKeyBytes::from(&def.voting_public_key),
+                            builder_min_bid_gwei: def.builder_min_bid_gwei,
                         })
                         .ok_or_else(|| {
                             warp_utils::reject::custom_not_found(format!(
@@ -448,6 +468,37 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             blocking_json_task(move || Ok(api_types::GenericResponse::from(result))).await
         });
 
+    // GET lighthouse/bls_to_execution_changes
+    let get_lighthouse_bls_to_execution_changes = warp::path("lighthouse")
+        .and(warp::path("bls_to_execution_changes"))
+        .and(warp::path::end())
+        .and(bls_to_execution_change_service_filter.clone())
+        .then(|service: Arc<BlsToExecutionChangeService<T, E>>| {
+            blocking_json_task(move || Ok(api_types::GenericResponse::from(service.changes())))
+        });
+
+    // POST lighthouse/bls_to_execution_changes
+    let post_lighthouse_bls_to_execution_changes = warp::path("lighthouse")
+        .and(warp::path("bls_to_execution_changes"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(bls_to_execution_change_service_filter.clone())
+        .then(
+            |body: api_types::QueueBlsToExecutionChangesRequest,
+             service: Arc<BlsToExecutionChangeService<T, E>>| {
+                blocking_json_task(move || {
+                    service
+                        .queue(body.changes, body.broadcast_epoch)
+                        .map_err(|e| {
+                            warp_utils::reject::custom_bad_request(format!(
+                                "unable to queue changes: {}",
+                                e
+                            ))
+                        })
+                })
+            },
+        );
+
     // POST lighthouse/validators/
     let post_validators = warp::path("lighthouse")
         .and(warp::path("validators"))
@@ -625,6 +676,7 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                         enabled: body.enable,
                         description: validator_def.description,
                         voting_pubkey: keypair.pk.into(),
+                        builder_min_bid_gwei: validator_def.builder_min_bid_gwei,
                     }))
                 })
             },
@@ -655,10 +707,13 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                                 builder_proposals: web3signer.builder_proposals,
                                 builder_boost_factor: web3signer.builder_boost_factor,
                                 prefer_builder_proposals: web3signer.prefer_builder_proposals,
+                                attestation_offset_ms: None,
+                                builder_min_bid_gwei: None,
                                 description: web3signer.description,
                                 signing_definition: SigningDefinition::Web3Signer(
                                     Web3SignerDefinition {
                                         url: web3signer.url,
+                                        fallback_urls: web3signer.fallback_urls,
                                         root_certificate_path: web3signer.root_certificate_path,
                                         request_timeout_ms: web3signer.request_timeout_ms,
                                         client_identity_path: web3signer.client_identity_path,
@@ -745,6 +800,10 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                                     initialized_validator.get_prefer_builder_proposals(),
                                     body.prefer_builder_proposals,
                                 )
+                                && equal_or_none(
+                                    initialized_validator.get_builder_min_bid_gwei(),
+                                    body.builder_min_bid_gwei,
+                                )
                                 && equal_or_none(
                                     initialized_validator.get_graffiti(),
                                     maybe_graffiti,
@@ -760,6 +819,7 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                                 && body.builder_boost_factor.is_none()
                                 && body.builder_proposals.is_none()
                                 && body.prefer_builder_proposals.is_none()
+                                && body.builder_min_bid_gwei.is_none()
                                 && maybe_graffiti.is_none() =>
                         {
                             Ok(())
@@ -782,6 +842,7 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                                                 body.builder_proposals,
                                                 body.builder_boost_factor,
                                                 body.prefer_builder_proposals,
+                                                body.builder_min_bid_gwei,
                                                 body.graffiti,
                                             ),
                                     )
@@ -835,6 +896,63 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             })
         });
 
+    // POST /lighthouse/keystores/import
+    let post_lighthouse_keystores_import = warp::path("lighthouse")
+        .and(warp::path("keystores"))
+        .and(warp::path("import"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(validator_dir_filter.clone())
+        .and(secrets_dir_filter.clone())
+        .and(validator_store_filter.clone())
+        .and(keystore_imports_filter.clone())
+        .and(task_executor_filter.clone())
+        .and(log_filter.clone())
+        .then(
+            move |request,
+                  validator_dir,
+                  secrets_dir,
+                  validator_store,
+                  keystore_imports: Arc<KeystoreImports>,
+                  task_executor,
+                  log| {
+                let secrets_dir = store_passwords_in_secrets_dir.then_some(secrets_dir);
+                blocking_json_task(move || {
+                    keystore_imports
+                        .start(
+                            request,
+                            validator_dir,
+                            secrets_dir,
+                            validator_store,
+                            task_executor,
+                            log,
+                        )
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
+    // GET /lighthouse/keystores/import/{import_id}
+    let get_lighthouse_keystores_import = warp::path("lighthouse")
+        .and(warp::path("keystores"))
+        .and(warp::path("import"))
+        .and(warp::path::param::<u64>())
+        .and(warp::path::end())
+        .and(keystore_imports_filter)
+        .then(|import_id: u64, keystore_imports: Arc<KeystoreImports>| {
+            blocking_json_task(move || {
+                keystore_imports
+                    .progress(import_id)
+                    .map(api_types::GenericResponse::from)
+                    .ok_or_else(|| {
+                        warp_utils::reject::custom_not_found(format!(
+                            "no keystore import with id {}",
+                            import_id
+                        ))
+                    })
+            })
+        });
+
     // Standard key-manager endpoints.
     let eth_v1 = warp::path("eth").and(warp::path("v1"));
     let std_keystores = eth_v1.and(warp::path("keystores")).and(warp::path::end());
@@ -1304,11 +1422,13 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                         .or(get_lighthouse_ui_health)
                         .or(get_lighthouse_ui_graffiti)
                         .or(get_lighthouse_ui_fallback_health)
+                        .or(get_lighthouse_bls_to_execution_changes)
                         .or(get_fee_recipient)
                         .or(get_gas_limit)
                         .or(get_graffiti)
                         .or(get_std_keystores)
                         .or(get_std_remotekeys)
+                        .or(get_lighthouse_keystores_import)
                         .recover(warp_utils::reject::handle_rejection),
                 )
                 .or(warp::post().and(
@@ -1317,11 +1437,13 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                         .or(post_validators_mnemonic)
                         .or(post_validators_web3signer)
                         .or(post_validators_voluntary_exits)
+                        .or(post_lighthouse_bls_to_execution_changes)
                         .or(post_fee_recipient)
                         .or(post_gas_limit)
                         .or(post_std_keystores)
                         .or(post_std_remotekeys)
                         .or(post_graffiti)
+                        .or(post_lighthouse_keystores_import)
                         .recover(warp_utils::reject::handle_rejection),
                 ))
                 .or(warp::patch()
@@ -128,9 +128,12 @@ fn import_single_remotekey<T: SlotClock + 'static, E: EthSpec>(
         builder_proposals: None,
         builder_boost_factor: None,
         prefer_builder_proposals: None,
+        attestation_offset_ms: None,
+        builder_min_bid_gwei: None,
         description: String::from("Added by remotekey API"),
         signing_definition: SigningDefinition::Web3Signer(Web3SignerDefinition {
             url,
+            fallback_urls: vec![],
             root_certificate_path: None,
             request_timeout_ms: None,
             client_identity_path: None,
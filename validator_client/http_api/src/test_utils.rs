@@ -125,6 +125,7 @@ impl ApiTester {
             task_executor: test_runtime.task_executor.clone(),
             api_secret,
             block_service: None,
+            bls_to_execution_change_service: None,
             validator_dir: Some(validator_dir.path().into()),
             secrets_dir: Some(secrets_dir.path().into()),
             validator_store: Some(validator_store.clone()),
@@ -512,6 +513,7 @@ impl ApiTester {
                     builder_proposals: None,
                     voting_public_key: kp.pk,
                     url: format!("http://signer_{}.com/", i),
+                    fallback_urls: vec![],
                     root_certificate_path: None,
                     request_timeout_ms: None,
                     client_identity_path: None,
@@ -549,6 +551,7 @@ impl ApiTester {
                 None,
                 None,
                 None,
+                None,
             )
             .await
             .unwrap();
@@ -599,6 +602,7 @@ impl ApiTester {
                 None,
                 None,
                 None,
+                None,
             )
             .await
             .unwrap();
@@ -629,6 +633,7 @@ impl ApiTester {
                 None,
                 None,
                 None,
+                None,
             )
             .await
             .unwrap();
@@ -11,6 +11,7 @@ use account_utils::{
     eth2_wallet::WalletBuilder, mnemonic_from_phrase, random_mnemonic, random_password,
     random_password_string, validator_definitions::ValidatorDefinitions, ZeroizeString,
 };
+use beacon_node_fallback::{BeaconNodeFallback, Config as BeaconNodeFallbackConfig};
 use deposit_contract::decode_eth1_tx_data;
 use eth2::{
     lighthouse_vc::{http_client::ValidatorClientHttpClient, types::*},
@@ -32,6 +33,7 @@ use std::time::Duration;
 use task_executor::test_utils::TestRuntime;
 use tempfile::{tempdir, TempDir};
 use types::graffiti::GraffitiString;
+use validator_services::bls_to_execution_change_service::BlsToExecutionChangeService;
 use validator_store::{Config as ValidatorStoreConfig, ValidatorStore};
 
 const PASSWORD_BYTES: &[u8] = &[42, 50, 37];
@@ -109,10 +111,30 @@ impl ApiTester {
 
         let initialized_validators = validator_store.initialized_validators();
 
+        // No beacon nodes are configured, so queued changes are never broadcast.
+        let beacon_nodes = Arc::new(BeaconNodeFallback::new(
+            vec![],
+            BeaconNodeFallbackConfig::default(),
+            vec![],
+            spec.clone(),
+            log.clone(),
+        ));
+        let bls_to_execution_change_service = Arc::new(
+            BlsToExecutionChangeService::new(
+                validator_dir.path(),
+                beacon_nodes,
+                slot_clock.clone(),
+                test_runtime.task_executor.clone(),
+                log.clone(),
+            )
+            .unwrap(),
+        );
+
         let context = Arc::new(Context {
             task_executor: test_runtime.task_executor.clone(),
             api_secret,
             block_service: None,
+            bls_to_execution_change_service: Some(bls_to_execution_change_service),
             validator_dir: Some(validator_dir.path().into()),
             secrets_dir: Some(secrets_dir.path().into()),
             validator_store: Some(validator_store.clone()),
@@ -476,6 +498,7 @@ impl ApiTester {
                     prefer_builder_proposals: None,
                     voting_public_key: kp.pk,
                     url: format!("http://signer_{}.com/", i),
+                    fallback_urls: vec![],
                     root_certificate_path: None,
                     request_timeout_ms: None,
                     client_identity_path: None,
@@ -538,6 +561,7 @@ impl ApiTester {
                 None,
                 None,
                 None,
+                None,
             )
             .await
             .unwrap();
@@ -588,6 +612,7 @@ impl ApiTester {
                 None,
                 None,
                 None,
+                None,
             )
             .await
             .unwrap();
@@ -606,6 +631,30 @@ impl ApiTester {
         self
     }
 
+    /// Writes `gas_limit` for the validator at `index` directly to the definitions file, then
+    /// reloads the file as the validator client does upon SIGHUP.
+    pub async fn reload_gas_limit(self, index: usize, gas_limit: u64, expect_ok: bool) -> Self {
+        let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
+        let voting_public_key = validator.voting_pubkey.decompress().unwrap();
+
+        let mut definitions = ValidatorDefinitions::open(self._validator_dir.path()).unwrap();
+        definitions
+            .as_mut_slice()
+            .iter_mut()
+            .find(|def| def.voting_public_key == voting_public_key)
+            .unwrap()
+            .gas_limit = Some(gas_limit);
+        definitions.save(self._validator_dir.path()).unwrap();
+
+        let result = self
+            .initialized_validators
+            .write()
+            .reload_fee_recipients_and_gas_limits();
+        assert_eq!(result.is_ok(), expect_ok, "{:?}", result);
+
+        self
+    }
+
     pub async fn set_builder_proposals(self, index: usize, builder_proposals: bool) -> Self {
         let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
 
@@ -618,6 +667,7 @@ impl ApiTester {
                 None,
                 None,
                 None,
+                None,
             )
             .await
             .unwrap();
@@ -637,6 +687,27 @@ impl ApiTester {
                 Some(builder_boost_factor),
                 None,
                 None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        self
+    }
+
+    pub async fn set_builder_min_bid_gwei(self, index: usize, builder_min_bid_gwei: u64) -> Self {
+        let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
+
+        self.client
+            .patch_lighthouse_validators(
+                &validator.voting_pubkey,
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(builder_min_bid_gwei),
+                None,
             )
             .await
             .unwrap();
@@ -660,6 +731,7 @@ impl ApiTester {
                 None,
                 Some(prefer_builder_proposals),
                 None,
+                None,
             )
             .await
             .unwrap();
@@ -721,6 +793,22 @@ impl ApiTester {
         self
     }
 
+    pub async fn assert_builder_min_bid_gwei(
+        self,
+        index: usize,
+        builder_min_bid_gwei: Option<u64>,
+    ) -> Self {
+        let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
+
+        assert_eq!(
+            self.validator_store
+                .get_builder_min_bid_gwei(&validator.voting_pubkey),
+            builder_min_bid_gwei
+        );
+
+        self
+    }
+
     pub async fn assert_prefer_builder_proposals(
         self,
         index: usize,
@@ -748,6 +836,7 @@ impl ApiTester {
                 None,
                 None,
                 None,
+                None,
                 Some(graffiti_str),
             )
             .await
@@ -809,6 +898,65 @@ impl ApiTester {
 
         self
     }
+
+    pub async fn queue_bls_to_execution_changes(
+        self,
+        validator_indices: &[u64],
+        broadcast_epoch: Epoch,
+        should_succeed: bool,
+    ) -> Self {
+        let changes = validator_indices
+            .iter()
+            .map(|&validator_index| SignedBlsToExecutionChange {
+                message: BlsToExecutionChange {
+                    validator_index,
+                    from_bls_pubkey: PublicKeyBytes::empty(),
+                    to_execution_address: Address::repeat_byte(42),
+                },
+                signature: Signature::empty(),
+            })
+            .collect();
+        let request = QueueBlsToExecutionChangesRequest {
+            broadcast_epoch,
+            changes,
+        };
+
+        let result = self
+            .client
+            .post_lighthouse_bls_to_execution_changes(&request)
+            .await;
+        assert_eq!(result.is_ok(), should_succeed, "{:?}", result);
+
+        self
+    }
+
+    pub async fn assert_pending_bls_to_execution_changes(
+        self,
+        validator_indices: &[u64],
+        broadcast_epoch: Epoch,
+    ) -> Self {
+        let queued = self
+            .client
+            .get_lighthouse_bls_to_execution_changes()
+            .await
+            .unwrap()
+            .data;
+
+        assert_eq!(
+            queued
+                .iter()
+                .map(|queued| queued.change.message.validator_index)
+                .collect::<Vec<_>>(),
+            validator_indices
+        );
+        for queued in queued {
+            assert_eq!(queued.status, BlsToExecutionChangeStatus::Pending);
+            assert_eq!(queued.broadcast_epoch, broadcast_epoch);
+            assert_eq!(queued.processed_epoch, None);
+        }
+
+        self
+    }
 }
 
 struct HdValidatorScenario {
@@ -851,6 +999,19 @@ async fn routes_with_invalid_auth() {
         .await
         .test_with_invalid_auth(|client| async move { client.get_lighthouse_validators().await })
         .await
+        .test_with_invalid_auth(|client| async move {
+            client.get_lighthouse_bls_to_execution_changes().await
+        })
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client
+                .post_lighthouse_bls_to_execution_changes(&QueueBlsToExecutionChangesRequest {
+                    broadcast_epoch: Epoch::new(0),
+                    changes: vec![],
+                })
+                .await
+        })
+        .await
         .test_with_invalid_auth(|client| async move {
             client
                 .get_lighthouse_validators_pubkey(&PublicKeyBytes::empty())
@@ -915,6 +1076,7 @@ async fn routes_with_invalid_auth() {
                     None,
                     None,
                     None,
+                    None,
                 )
                 .await
         })
@@ -938,6 +1100,20 @@ async fn routes_with_invalid_auth() {
                 .await
         })
         .await
+        .test_with_invalid_auth(|client| async move {
+            client
+                .post_lighthouse_keystores_import(&ImportKeystoresRequest {
+                    keystores: vec![],
+                    passwords: vec![],
+                    slashing_protection: None,
+                })
+                .await
+        })
+        .await
+        .test_with_invalid_auth(
+            |client| async move { client.get_lighthouse_keystores_import(0).await },
+        )
+        .await
         .test_with_invalid_auth(|client| async move {
             let keypair = Keypair::random();
             client
@@ -1083,6 +1259,52 @@ async fn validator_gas_limit() {
         .await;
 }
 
+#[tokio::test]
+async fn validator_gas_limit_reload() {
+    ApiTester::new()
+        .await
+        .create_hd_validators(HdValidatorScenario {
+            count: 2,
+            specify_mnemonic: false,
+            key_derivation_path_offset: 0,
+            disabled: vec![],
+        })
+        .await
+        .set_gas_limit(0, 500)
+        .await
+        .reload_gas_limit(0, 1000, true)
+        .await
+        .assert_gas_limit(0, 1000)
+        .await
+        // An invalid gas limit is rejected, leaving the previous value in place.
+        .reload_gas_limit(0, 0, false)
+        .await
+        .assert_gas_limit(0, 1000)
+        .await;
+}
+
+#[tokio::test]
+async fn bls_to_execution_changes() {
+    let broadcast_epoch = Epoch::new(1_000);
+    ApiTester::new()
+        .await
+        .assert_pending_bls_to_execution_changes(&[], broadcast_epoch)
+        .await
+        .queue_bls_to_execution_changes(&[1, 2], broadcast_epoch, true)
+        .await
+        .assert_pending_bls_to_execution_changes(&[1, 2], broadcast_epoch)
+        .await
+        // A second change for a validator which already has one queued is rejected, along with
+        // the rest of its batch.
+        .queue_bls_to_execution_changes(&[3, 2], broadcast_epoch, false)
+        .await
+        // Duplicates within a single request are also rejected.
+        .queue_bls_to_execution_changes(&[4, 4], broadcast_epoch, false)
+        .await
+        .assert_pending_bls_to_execution_changes(&[1, 2], broadcast_epoch)
+        .await;
+}
+
 #[tokio::test]
 async fn validator_builder_proposals() {
     ApiTester::new()
@@ -1229,6 +1451,31 @@ async fn validator_builder_boost_factor_global_prefer_builder_proposals_true_ove
         .assert_default_builder_boost_factor(Some(u64::MAX));
 }
 
+#[tokio::test]
+async fn builder_min_bid_gwei_validator() {
+    let config = ValidatorStoreConfig {
+        builder_min_bid_gwei: Some(10_000_000),
+        ..ValidatorStoreConfig::default()
+    };
+    ApiTester::new_with_config(config)
+        .await
+        .create_hd_validators(HdValidatorScenario {
+            count: 2,
+            specify_mnemonic: false,
+            key_derivation_path_offset: 0,
+            disabled: vec![],
+        })
+        .await
+        .assert_builder_min_bid_gwei(0, Some(10_000_000))
+        .await
+        .set_builder_min_bid_gwei(0, 50_000_000)
+        .await
+        .assert_builder_min_bid_gwei(0, Some(50_000_000))
+        .await
+        .assert_builder_min_bid_gwei(1, Some(10_000_000))
+        .await;
+}
+
 #[tokio::test]
 async fn prefer_builder_proposals_validator() {
     ApiTester::new()
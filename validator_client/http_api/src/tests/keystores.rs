@@ -5,7 +5,7 @@ use eth2::lighthouse_vc::types::UpdateFeeRecipientRequest;
 use eth2::lighthouse_vc::{
     http_client::ValidatorClientHttpClient as HttpClient,
     std_types::{KeystoreJsonStr as Keystore, *},
-    types::Web3SignerValidatorRequest,
+    types::{KeystoreImportStage, Web3SignerValidatorRequest},
 };
 use itertools::Itertools;
 use rand::{rngs::SmallRng, Rng, SeedableRng};
@@ -47,6 +47,7 @@ fn web3signer_validator_with_pubkey(pubkey: PublicKey) -> Web3SignerValidatorReq
         prefer_builder_proposals: None,
         voting_public_key: pubkey,
         url: web3_signer_url(),
+        fallback_urls: vec![],
         root_certificate_path: None,
         request_timeout_ms: None,
         client_identity_path: None,
@@ -280,6 +281,96 @@ async fn import_only_duplicate_keystores() {
     .await;
 }
 
+#[tokio::test]
+async fn import_keystores_in_background() {
+    run_test(|tester| async move {
+        let password = random_password_string();
+        let keystores = (0..4)
+            .map(|_| new_keystore(password.clone()))
+            .collect::<Vec<_>>();
+
+        // Use the wrong password for the last keystore.
+        let mut passwords = vec![password.clone(); keystores.len()];
+        passwords[3] = random_password_string();
+
+        let import_id = tester
+            .client
+            .post_lighthouse_keystores_import(&ImportKeystoresRequest {
+                keystores: keystores.clone(),
+                passwords,
+                slashing_protection: None,
+            })
+            .await
+            .unwrap()
+            .data
+            .import_id;
+
+        // Poll until every keystore has been processed.
+        let mut progress = None;
+        for _ in 0..200 {
+            let current = tester
+                .client
+                .get_lighthouse_keystores_import(import_id)
+                .await
+                .unwrap()
+                .unwrap()
+                .data;
+            if current.is_complete() {
+                progress = Some(current);
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        }
+        let progress = progress.expect("import should complete");
+
+        assert_eq!(progress.total, keystores.len() as u64);
+        for (i, (keystore, single)) in keystores.iter().zip(&progress.keystores).enumerate() {
+            assert_eq!(single.validating_pubkey, Some(keystore_pubkey(keystore)));
+            assert_eq!(single.stage, KeystoreImportStage::Complete);
+            let expected = if i < 3 {
+                ImportKeystoreStatus::Imported
+            } else {
+                ImportKeystoreStatus::Error
+            };
+            assert_eq!(single.status.as_ref().unwrap().status, expected);
+        }
+
+        // Only the keystores with the correct password are listed.
+        let get_res = tester.client.get_keystores().await.unwrap();
+        check_keystore_get_response(&get_res, &keystores[..3]);
+
+        // Unknown imports are not found.
+        assert!(tester
+            .client
+            .get_lighthouse_keystores_import(import_id + 1)
+            .await
+            .unwrap()
+            .is_none());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn import_keystores_in_background_mismatched_passwords() {
+    run_test(|tester| async move {
+        let password = random_password_string();
+        let keystores = (0..2)
+            .map(|_| new_keystore(password.clone()))
+            .collect::<Vec<_>>();
+
+        tester
+            .client
+            .post_lighthouse_keystores_import(&ImportKeystoresRequest {
+                keystores,
+                passwords: vec![password],
+                slashing_protection: None,
+            })
+            .await
+            .unwrap_err();
+    })
+    .await;
+}
+
 #[tokio::test]
 async fn import_some_duplicate_keystores() {
     run_test(|tester| async move {
@@ -470,7 +561,16 @@ async fn import_and_delete_conflicting_web3_signer_keystores() {
         for pubkey in &pubkeys {
             tester
                 .client
-                .patch_lighthouse_validators(pubkey, Some(false), None, None, None, None, None)
+                .patch_lighthouse_validators(
+                    pubkey,
+                    Some(false),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
                 .await
                 .unwrap();
         }
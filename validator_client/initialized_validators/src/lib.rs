@@ -22,7 +22,9 @@ use metrics::set_gauge;
 use parking_lot::{MappedMutexGuard, Mutex, MutexGuard};
 use reqwest::{Certificate, Client, Error as ReqwestError, Identity};
 use serde::{Deserialize, Serialize};
-use signing_method::SigningMethod;
+use signing_method::{
+    web3signer_url, SigningMethod, Web3SignerEndpoint, Web3SignerHealth, CONNECT_TIMEOUT,
+};
 use slog::{debug, error, info, warn, Logger};
 use std::collections::{HashMap, HashSet};
 use std::fs::{self, File};
@@ -30,9 +32,10 @@ use std::io::{self, Read};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::Semaphore;
 use types::graffiti::GraffitiString;
 use types::{Address, Graffiti, Keypair, PublicKey, PublicKeyBytes};
-use url::{ParseError, Url};
+use url::Url;
 use validator_dir::Builder as ValidatorDirBuilder;
 
 use key_cache::KeyCache;
@@ -53,6 +56,8 @@ pub const DEFAULT_WEB3SIGNER_KEEP_ALIVE: Option<Duration> = Some(Duration::from_
 pub struct Config {
     pub web3_signer_keep_alive_timeout: Option<Duration>,
     pub web3_signer_max_idle_connections: Option<usize>,
+    /// Maximum number of requests in flight to each web3signer at once. Unlimited if `None`.
+    pub web3_signer_max_concurrent_requests: Option<usize>,
 }
 
 impl Default for Config {
@@ -60,10 +65,29 @@ impl Default for Config {
         Config {
             web3_signer_keep_alive_timeout: DEFAULT_WEB3SIGNER_KEEP_ALIVE,
             web3_signer_max_idle_connections: None,
+            web3_signer_max_concurrent_requests: None,
         }
     }
 }
 
+/// Returns the `host:port` of a remote signer, used to share request limits between signers on
+/// the same host.
+fn web3_signer_host(url: &Url) -> String {
+    format!(
+        "{}:{}",
+        url.host_str().unwrap_or_default(),
+        url.port_or_known_default().unwrap_or_default()
+    )
+}
+
+/// A client for a remote signer, shared by all validators with the same `Web3SignerDefinition`.
+#[derive(Clone)]
+struct Web3SignerClient {
+    http_client: Client,
+    /// The primary signer followed by any fallbacks, in order of preference.
+    signers: Vec<Arc<Web3SignerHealth>>,
+}
+
 pub enum OnDecryptFailure {
     /// If the key cache fails to decrypt, create a new cache.
     CreateNew,
@@ -102,6 +126,13 @@ pub enum Error {
     UnableToReadVotingKeystorePassword(io::Error),
     /// There was an error updating the on-disk validator definitions file.
     UnableToSaveDefinitions(validator_definitions::Error),
+    /// There was an error reading the on-disk validator definitions file whilst reloading it.
+    UnableToReloadDefinitions(validator_definitions::Error),
+    /// A reloaded validator definition contained a field which cannot be applied.
+    InvalidReloadedDefinition {
+        voting_public_key: Box<PublicKey>,
+        reason: String,
+    },
     /// It is not legal to try and initialize a disabled validator definition.
     UnableToInitializeDisabledValidator,
     /// There was an error while deleting a keystore file.
@@ -153,6 +184,8 @@ pub struct InitializedValidator {
     builder_proposals: Option<bool>,
     builder_boost_factor: Option<u64>,
     prefer_builder_proposals: Option<bool>,
+    builder_min_bid_gwei: Option<u64>,
+    attestation_offset: Option<Duration>,
     /// The validators index in `state.validators`, to be updated by an external service.
     index: Option<u64>,
 }
@@ -193,6 +226,14 @@ impl InitializedValidator {
         self.builder_proposals
     }
 
+    pub fn get_builder_min_bid_gwei(&self) -> Option<u64> {
+        self.builder_min_bid_gwei
+    }
+
+    pub fn get_attestation_offset(&self) -> Option<Duration> {
+        self.attestation_offset
+    }
+
     pub fn get_index(&self) -> Option<u64> {
         self.index
     }
@@ -227,7 +268,8 @@ impl InitializedValidator {
         def: ValidatorDefinition,
         key_cache: &mut KeyCache,
         key_stores: &mut HashMap<PathBuf, Keystore>,
-        web3_signer_client_map: &mut Option<HashMap<Web3SignerDefinition, Client>>,
+        web3_signer_client_map: &mut Option<HashMap<Web3SignerDefinition, Web3SignerClient>>,
+        web3_signer_request_limits: &mut HashMap<String, Arc<Semaphore>>,
         config: &Config,
     ) -> Result<Self, Error> {
         if !def.enabled {
@@ -314,51 +356,70 @@ impl InitializedValidator {
                 }
             }
             SigningDefinition::Web3Signer(web3_signer) => {
-                let signing_url = build_web3_signer_url(&web3_signer.url, &def.voting_public_key)
-                    .map_err(|e| Error::InvalidWeb3SignerUrl(e.to_string()))?;
-
                 let request_timeout = web3_signer
                     .request_timeout_ms
                     .map(Duration::from_millis)
                     .unwrap_or(DEFAULT_REMOTE_SIGNER_REQUEST_TIMEOUT);
 
                 // Check if a client has already been initialized for this remote signer url.
-                let http_client = if let Some(client_map) = web3_signer_client_map {
-                    match client_map.get(&web3_signer) {
-                        Some(client) => client.clone(),
-                        None => {
-                            let client = build_web3_signer_client(
-                                web3_signer.root_certificate_path.clone(),
-                                web3_signer.client_identity_path.clone(),
-                                web3_signer.client_identity_password.clone(),
-                                request_timeout,
-                                config.web3_signer_keep_alive_timeout,
-                                config.web3_signer_max_idle_connections,
-                            )?;
-                            client_map.insert(web3_signer, client.clone());
-                            client
-                        }
+                let client_map = web3_signer_client_map.get_or_insert_with(HashMap::new);
+                let client = match client_map.get(&web3_signer) {
+                    Some(client) => client.clone(),
+                    None => {
+                        let http_client = build_web3_signer_client(
+                            web3_signer.root_certificate_path.clone(),
+                            web3_signer.client_identity_path.clone(),
+                            web3_signer.client_identity_password.clone(),
+                            request_timeout,
+                            config.web3_signer_keep_alive_timeout,
+                            config.web3_signer_max_idle_connections,
+                        )?;
+                        let signers = std::iter::once(&web3_signer.url)
+                            .chain(&web3_signer.fallback_urls)
+                            .map(|url| {
+                                Url::parse(url)
+                                    .map(|url| Arc::new(Web3SignerHealth::new(url)))
+                                    .map_err(|e| Error::InvalidWeb3SignerUrl(e.to_string()))
+                            })
+                            .collect::<Result<_, _>>()?;
+                        let client = Web3SignerClient {
+                            http_client,
+                            signers,
+                        };
+                        client_map.insert(web3_signer, client.clone());
+                        client
                     }
-                } else {
-                    // There are no clients in the map.
-                    let mut new_web3_signer_client_map: HashMap<Web3SignerDefinition, Client> =
-                        HashMap::new();
-                    let client = build_web3_signer_client(
-                        web3_signer.root_certificate_path.clone(),
-                        web3_signer.client_identity_path.clone(),
-                        web3_signer.client_identity_password.clone(),
-                        request_timeout,
-                        config.web3_signer_keep_alive_timeout,
-                        config.web3_signer_max_idle_connections,
-                    )?;
-                    new_web3_signer_client_map.insert(web3_signer, client.clone());
-                    *web3_signer_client_map = Some(new_web3_signer_client_map);
-                    client
                 };
 
+                let endpoints = client
+                    .signers
+                    .iter()
+                    .map(|health| {
+                        Ok(Web3SignerEndpoint {
+                            signing_url: web3signer_url(
+                                health.url(),
+                                &format!("api/v1/eth2/sign/{}", def.voting_public_key),
+                            )
+                            .map_err(|e| Error::InvalidWeb3SignerUrl(e.to_string()))?,
+                            health: health.clone(),
+                            // The limit is shared by all validators and signers on the same host.
+                            request_limit: config.web3_signer_max_concurrent_requests.map(
+                                |limit| {
+                                    web3_signer_request_limits
+                                        .entry(web3_signer_host(health.url()))
+                                        .or_insert_with(|| Arc::new(Semaphore::new(limit)))
+                                        .clone()
+                                },
+                            ),
+                        })
+                    })
+                    .collect::<Result<_, Error>>()?;
+
                 SigningMethod::Web3Signer {
-                    signing_url,
-                    http_client,
+                    endpoints,
+                    http_client: client.http_client,
+                    // A request shouldn't wait in the queue for longer than it may take to sign.
+                    queue_timeout: request_timeout,
                     voting_public_key: def.voting_public_key,
                 }
             }
@@ -372,6 +433,8 @@ impl InitializedValidator {
             builder_proposals: def.builder_proposals,
             builder_boost_factor: def.builder_boost_factor,
             prefer_builder_proposals: def.prefer_builder_proposals,
+            builder_min_bid_gwei: def.builder_min_bid_gwei,
+            attestation_offset: def.attestation_offset_ms.map(Duration::from_millis),
             index: None,
         })
     }
@@ -409,10 +472,6 @@ pub fn load_pkcs12_identity<P: AsRef<Path>>(
         .map_err(Error::InvalidWeb3SignerClientIdentityCertificate)
 }
 
-fn build_web3_signer_url(base_url: &str, voting_public_key: &PublicKey) -> Result<Url, ParseError> {
-    Url::parse(base_url)?.join(&format!("api/v1/eth2/sign/{}", voting_public_key))
-}
-
 fn build_web3_signer_client(
     root_certificate_path: Option<PathBuf>,
     client_identity_path: Option<PathBuf>,
@@ -423,6 +482,7 @@ fn build_web3_signer_client(
 ) -> Result<Client, Error> {
     let builder = Client::builder()
         .timeout(request_timeout)
+        .connect_timeout(std::cmp::min(request_timeout, CONNECT_TIMEOUT))
         .pool_idle_timeout(keep_alive_timeout)
         .pool_max_idle_per_host(max_idle_connections.unwrap_or(usize::MAX));
 
@@ -502,7 +562,9 @@ pub struct InitializedValidators {
     /// The canonical set of validators.
     validators: HashMap<PublicKeyBytes, InitializedValidator>,
     /// The clients used for communications with a remote signer.
-    web3_signer_client_map: Option<HashMap<Web3SignerDefinition, Client>>,
+    web3_signer_client_map: Option<HashMap<Web3SignerDefinition, Web3SignerClient>>,
+    /// Limits on the requests in flight to each remote signer host, keyed by `host:port`.
+    web3_signer_request_limits: HashMap<String, Arc<Semaphore>>,
     /// For logging via `slog`.
     log: Logger,
     config: Config,
@@ -521,6 +583,7 @@ impl InitializedValidators {
             definitions,
             validators: HashMap::default(),
             web3_signer_client_map: None,
+            web3_signer_request_limits: HashMap::new(),
             config,
             log,
         };
@@ -553,6 +616,20 @@ impl InitializedValidators {
             .map(|v| v.signing_method.clone())
     }
 
+    /// Returns each remote signer in use, with the client used to communicate with it.
+    pub fn web3_signers(&self) -> Vec<(Client, Arc<Web3SignerHealth>)> {
+        self.web3_signer_client_map
+            .iter()
+            .flat_map(HashMap::values)
+            .flat_map(|client| {
+                client
+                    .signers
+                    .iter()
+                    .map(|health| (client.http_client.clone(), health.clone()))
+            })
+            .collect()
+    }
+
     /// Add a validator definition to `self`, replacing any disabled definition with the same
     /// voting public key.
     ///
@@ -852,6 +929,25 @@ impl InitializedValidators {
         self.validators.get(public_key).and_then(|v| v.gas_limit)
     }
 
+    /// Returns the attestation production offset for a given public key specified in the
+    /// `ValidatorDefinitions`.
+    pub fn attestation_offset(&self, public_key: &PublicKeyBytes) -> Option<Duration> {
+        self.validators
+            .get(public_key)
+            .and_then(|v| v.attestation_offset)
+    }
+
+    /// Returns the earliest attestation production offset of any validator.
+    ///
+    /// Offsets are not validated against the slot duration here, the attestation service ignores
+    /// those which are not before aggregate production.
+    pub fn min_attestation_offset(&self) -> Option<Duration> {
+        self.validators
+            .values()
+            .filter_map(|v| v.attestation_offset)
+            .min()
+    }
+
     /// Returns the `builder_proposals` for a given public key specified in the
     /// `ValidatorDefinitions`.
     pub fn builder_proposals(&self, public_key: &PublicKeyBytes) -> Option<bool> {
@@ -876,6 +972,14 @@ impl InitializedValidators {
             .and_then(|v| v.prefer_builder_proposals)
     }
 
+    /// Returns the `builder_min_bid_gwei` for a given public key specified in the
+    /// `ValidatorDefinitions`.
+    pub fn builder_min_bid_gwei(&self, public_key: &PublicKeyBytes) -> Option<u64> {
+        self.validators
+            .get(public_key)
+            .and_then(|v| v.builder_min_bid_gwei)
+    }
+
     /// Returns an `Option` of a reference to an `InitializedValidator` for a given public key specified in the
     /// `ValidatorDefinitions`.
     pub fn validator(&self, public_key: &PublicKeyBytes) -> Option<&InitializedValidator> {
@@ -883,7 +987,7 @@ impl InitializedValidators {
     }
 
     /// Sets the `InitializedValidator` and `ValidatorDefinition` `enabled`, `gas_limit`,
-    /// `builder_proposals`, and `graffiti` values.
+    /// `builder_proposals`, `builder_min_bid_gwei` and `graffiti` values.
     ///
     /// ## Notes
     ///
@@ -905,6 +1009,7 @@ impl InitializedValidators {
         builder_proposals: Option<bool>,
         builder_boost_factor: Option<u64>,
         prefer_builder_proposals: Option<bool>,
+        builder_min_bid_gwei: Option<u64>,
         graffiti: Option<GraffitiString>,
     ) -> Result<(), Error> {
         if let Some(def) = self
@@ -932,6 +1037,9 @@ impl InitializedValidators {
             if let Some(prefer_builder_proposals) = prefer_builder_proposals {
                 def.prefer_builder_proposals = Some(prefer_builder_proposals);
             }
+            if let Some(builder_min_bid_gwei) = builder_min_bid_gwei {
+                def.builder_min_bid_gwei = Some(builder_min_bid_gwei);
+            }
         }
 
         self.update_validators().await?;
@@ -956,6 +1064,9 @@ impl InitializedValidators {
             if let Some(prefer_builder_proposals) = prefer_builder_proposals {
                 val.prefer_builder_proposals = Some(prefer_builder_proposals);
             }
+            if let Some(builder_min_bid_gwei) = builder_min_bid_gwei {
+                val.builder_min_bid_gwei = Some(builder_min_bid_gwei);
+            }
         }
 
         self.definitions
@@ -1109,6 +1220,97 @@ impl InitializedValidators {
         Ok(())
     }
 
+    /// Re-reads the on-disk validator definitions file and applies any changes to the
+    /// `suggested_fee_recipient` and `gas_limit` fields of known validators.
+    ///
+    /// ## Notes
+    ///
+    /// All other changes to the file, including added or removed definitions, are ignored. If any
+    /// reloaded field is invalid then no changes are applied.
+    ///
+    /// Returns the number of fields which were changed.
+    pub fn reload_fee_recipients_and_gas_limits(&mut self) -> Result<usize, Error> {
+        let reloaded = ValidatorDefinitions::open(&self.validators_dir)
+            .map_err(Error::UnableToReloadDefinitions)?;
+
+        let mut updates = vec![];
+        for def in self.definitions.as_slice() {
+            let Some(new_def) = reloaded
+                .as_slice()
+                .iter()
+                .find(|new_def| new_def.voting_public_key == def.voting_public_key)
+            else {
+                continue;
+            };
+
+            let invalid = |reason: &str| Error::InvalidReloadedDefinition {
+                voting_public_key: Box::new(def.voting_public_key.clone()),
+                reason: reason.to_string(),
+            };
+            if new_def.suggested_fee_recipient == Some(Address::zero()) {
+                return Err(invalid("the fee recipient is the zero address"));
+            }
+            if new_def.gas_limit == Some(0) {
+                return Err(invalid("the gas limit is zero"));
+            }
+
+            if new_def.suggested_fee_recipient != def.suggested_fee_recipient
+                || new_def.gas_limit != def.gas_limit
+            {
+                updates.push((
+                    def.voting_public_key.clone(),
+                    new_def.suggested_fee_recipient,
+                    new_def.gas_limit,
+                ));
+            }
+        }
+
+        let mut num_changed = 0;
+        for (voting_public_key, fee_recipient, gas_limit) in updates {
+            let Some(def) = self
+                .definitions
+                .as_mut_slice()
+                .iter_mut()
+                .find(|def| def.voting_public_key == voting_public_key)
+            else {
+                continue;
+            };
+
+            if def.suggested_fee_recipient != fee_recipient {
+                info!(
+                    self.log,
+                    "Reloaded validator fee recipient";
+                    "voting_pubkey" => %voting_public_key,
+                    "old" => ?def.suggested_fee_recipient,
+                    "new" => ?fee_recipient,
+                );
+                num_changed += 1;
+            }
+            if def.gas_limit != gas_limit {
+                info!(
+                    self.log,
+                    "Reloaded validator gas limit";
+                    "voting_pubkey" => %voting_public_key,
+                    "old" => ?def.gas_limit,
+                    "new" => ?gas_limit,
+                );
+                num_changed += 1;
+            }
+
+            def.suggested_fee_recipient = fee_recipient;
+            def.gas_limit = gas_limit;
+            if let Some(val) = self
+                .validators
+                .get_mut(&PublicKeyBytes::from(&voting_public_key))
+            {
+                val.suggested_fee_recipient = fee_recipient;
+                val.gas_limit = gas_limit;
+            }
+        }
+
+        Ok(num_changed)
+    }
+
     /// Tries to decrypt the key cache.
     ///
     /// Returns the decrypted cache if decryption was successful, or an error if a required password
@@ -1267,6 +1469,7 @@ impl InitializedValidators {
                             &mut key_cache,
                             &mut key_stores,
                             &mut None,
+                            &mut HashMap::new(),
                             &self.config,
                         )
                         .await
@@ -1318,6 +1521,7 @@ impl InitializedValidators {
                             &mut key_cache,
                             &mut key_stores,
                             &mut self.web3_signer_client_map,
+                            &mut self.web3_signer_request_limits,
                             &self.config,
                         )
                         .await
@@ -1456,3 +1660,116 @@ impl InitializedValidators {
         self.definitions.as_mut_slice()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn web3_signer_definition(
+        url: &str,
+        fallback_urls: &[&str],
+        request_timeout_ms: Option<u64>,
+    ) -> ValidatorDefinition {
+        ValidatorDefinition {
+            enabled: true,
+            voting_public_key: Keypair::random().pk,
+            graffiti: None,
+            suggested_fee_recipient: None,
+            gas_limit: None,
+            builder_proposals: None,
+            builder_boost_factor: None,
+            prefer_builder_proposals: None,
+            builder_min_bid_gwei: None,
+            attestation_offset_ms: None,
+            description: String::new(),
+            signing_definition: SigningDefinition::Web3Signer(Web3SignerDefinition {
+                url: url.to_string(),
+                fallback_urls: fallback_urls.iter().map(ToString::to_string).collect(),
+                root_certificate_path: None,
+                request_timeout_ms,
+                client_identity_path: None,
+                client_identity_password: None,
+            }),
+        }
+    }
+
+    /// Returns the request limit of each signer used by the validator, in order of preference.
+    fn request_limits(
+        validators: &InitializedValidators,
+        def: &ValidatorDefinition,
+    ) -> Vec<Option<Arc<Semaphore>>> {
+        let signing_method = validators
+            .signing_method(&def.voting_public_key.compress())
+            .unwrap();
+        match signing_method.as_ref() {
+            SigningMethod::Web3Signer { endpoints, .. } => endpoints
+                .iter()
+                .map(|endpoint| endpoint.request_limit.clone())
+                .collect(),
+            SigningMethod::LocalKeystore { .. } => panic!("expected a web3signer validator"),
+        }
+    }
+
+    #[tokio::test]
+    async fn web3_signer_request_limit_is_shared_per_host() {
+        let validators_dir = tempdir().unwrap();
+        let definitions = vec![
+            web3_signer_definition("http://signer-a:9000", &["http://signer-b:9000"], None),
+            // A different definition, and so a different client, for a signer on the same host.
+            web3_signer_definition("http://signer-a:9000/prefix", &[], Some(1_000)),
+            web3_signer_definition("http://signer-b:9000", &["http://signer-a:9000"], None),
+            web3_signer_definition("http://signer-a:9001", &[], None),
+        ];
+        let config = Config {
+            web3_signer_max_concurrent_requests: Some(2),
+            ..Config::default()
+        };
+        let validators = InitializedValidators::from_definitions(
+            definitions.clone().into(),
+            validators_dir.path().to_path_buf(),
+            config,
+            Logger::root(slog::Discard, slog::o!()),
+        )
+        .await
+        .unwrap();
+
+        let limits = definitions
+            .iter()
+            .map(|def| {
+                request_limits(&validators, def)
+                    .into_iter()
+                    .map(Option::unwrap)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let host_a = &limits[0][0];
+        let host_b = &limits[0][1];
+
+        assert!(!Arc::ptr_eq(host_a, host_b));
+        assert!(Arc::ptr_eq(&limits[1][0], host_a));
+        assert!(Arc::ptr_eq(&limits[2][0], host_b));
+        assert!(Arc::ptr_eq(&limits[2][1], host_a));
+        // A different port is a different signer.
+        assert!(!Arc::ptr_eq(&limits[3][0], host_a));
+        assert_eq!(host_a.available_permits(), 2);
+    }
+
+    #[tokio::test]
+    async fn web3_signer_requests_are_unlimited_by_default() {
+        let validators_dir = tempdir().unwrap();
+        let def = web3_signer_definition("http://signer-a:9000", &[], None);
+        let validators = InitializedValidators::from_definitions(
+            vec![def.clone()].into(),
+            validators_dir.path().to_path_buf(),
+            Config::default(),
+            Logger::root(slog::Discard, slog::o!()),
+        )
+        .await
+        .unwrap();
+
+        assert!(request_limits(&validators, &def)
+            .iter()
+            .all(Option::is_none));
+    }
+}
@@ -1,7 +1,7 @@
 //! Provides methods for obtaining validator signatures, including:
 //!
 //! - Via a local `Keypair`.
-//! - Via a remote signer (Web3Signer)
+//! - Via a remote signer (Web3Signer), failing over between multiple instances if configured.
 
 use eth2_keystore::Keystore;
 use lockfile::Lockfile;
@@ -9,14 +9,18 @@ use parking_lot::Mutex;
 use reqwest::{header::ACCEPT, Client};
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 use task_executor::TaskExecutor;
+use tokio::sync::{Semaphore, SemaphorePermit};
 use types::*;
-use url::Url;
+use url::{ParseError, Url};
 use web3signer::{ForkInfo, SigningRequest, SigningResponse};
 
 pub use web3signer::Web3SignerObject;
+pub use web3signer_health::{Web3SignerHealth, CONNECT_TIMEOUT};
 
 mod web3signer;
+mod web3signer_health;
 
 #[derive(Debug, PartialEq)]
 pub enum Error {
@@ -26,12 +30,59 @@ pub enum Error {
     },
     Web3SignerRequestFailed(String),
     Web3SignerJsonParsingFailed(String),
+    /// No request slot for the signer became free before the queue timeout.
+    Web3SignerQueueTimeout,
     ShuttingDown,
     TokioJoin(String),
     MergeForkNotSupported,
     GenesisForkVersionRequired,
 }
 
+/// Returns the URL of the Web3Signer endpoint at `path`, relative to the `base_url` of the signer.
+///
+/// Any path in `base_url` is retained, regardless of whether it has a trailing slash.
+pub fn web3signer_url(base_url: &Url, path: &str) -> Result<Url, ParseError> {
+    let mut url = base_url.clone();
+    url.path_segments_mut()
+        .map_err(|()| ParseError::RelativeUrlWithCannotBeABaseBase)?
+        .pop_if_empty()
+        .extend(path.split('/'));
+    Ok(url)
+}
+
+/// A Web3Signer instance which can sign for a validator.
+#[derive(Clone)]
+pub struct Web3SignerEndpoint {
+    /// The URL to which signing requests for the validator are sent.
+    pub signing_url: Url,
+    /// The status of the signer, shared by all validators which use it.
+    pub health: Arc<Web3SignerHealth>,
+    /// Limits the number of requests in flight to the signer's host. Shared by all validators and
+    /// signers on the same host.
+    pub request_limit: Option<Arc<Semaphore>>,
+}
+
+impl Web3SignerEndpoint {
+    /// Wait for a slot to send a request to the signer, if the number of concurrent requests to
+    /// its host is limited.
+    ///
+    /// Returns `Error::Web3SignerQueueTimeout` if no slot becomes free within `queue_timeout`.
+    async fn acquire_request_permit(
+        &self,
+        queue_timeout: Duration,
+    ) -> Result<Option<SemaphorePermit<'_>>, Error> {
+        let Some(request_limit) = &self.request_limit else {
+            return Ok(None);
+        };
+        let _timer = validator_metrics::start_timer(&validator_metrics::WEB3SIGNER_QUEUE_TIMES);
+        match tokio::time::timeout(queue_timeout, request_limit.acquire()).await {
+            Ok(Ok(permit)) => Ok(Some(permit)),
+            Ok(Err(_)) => Err(Error::ShuttingDown),
+            Err(_) => Err(Error::Web3SignerQueueTimeout),
+        }
+    }
+}
+
 /// Enumerates all messages that can be signed by a validator.
 pub enum SignableMessage<'a, E: EthSpec, Payload: AbstractExecPayload<E> = FullPayload<E>> {
     RandaoReveal(Epoch),
@@ -88,8 +139,11 @@ pub enum SigningMethod {
     ///
     /// See: https://docs.web3signer.consensys.net/en/latest/
     Web3Signer {
-        signing_url: Url,
+        /// The signers which hold the key, in order of preference.
+        endpoints: Vec<Web3SignerEndpoint>,
         http_client: Client,
+        /// The longest time to wait for a request slot on a signer before moving on to the next.
+        queue_timeout: Duration,
         voting_public_key: PublicKey,
     },
 }
@@ -184,8 +238,9 @@ impl SigningMethod {
                 Ok(signature)
             }
             SigningMethod::Web3Signer {
-                signing_url,
+                endpoints,
                 http_client,
+                queue_timeout,
                 ..
             } => {
                 let _timer = validator_metrics::start_timer_vec(
@@ -243,22 +298,252 @@ impl SigningMethod {
                     object,
                 };
 
-                // Request a signature from the Web3Signer instance via HTTP(S).
-                let response: SigningResponse = http_client
-                    .post(signing_url.clone())
-                    .header(ACCEPT, "application/json")
-                    .json(&request)
-                    .send()
-                    .await
-                    .map_err(|e| Error::Web3SignerRequestFailed(e.to_string()))?
-                    .error_for_status()
-                    .map_err(|e| Error::Web3SignerRequestFailed(e.to_string()))?
-                    .json()
-                    .await
-                    .map_err(|e| Error::Web3SignerJsonParsingFailed(e.to_string()))?;
+                // Try signers which are believed to be online first, but fall back to the others in
+                // case their status is stale.
+                let (online, offline): (Vec<_>, Vec<_>) = endpoints
+                    .iter()
+                    .partition(|endpoint| endpoint.health.is_online());
+                let num_endpoints = endpoints.len();
+                let mut last_error = Error::Web3SignerRequestFailed(
+                    "no web3signer endpoints are configured".to_string(),
+                );
+
+                for (i, endpoint) in online.into_iter().chain(offline).enumerate() {
+                    // Web3Signer signs a single message per request, so requests can't be batched.
+                    // Instead, wait for a slot if the number of concurrent requests to the host is
+                    // limited. A signer which stays saturated is skipped, but not marked offline.
+                    let _permit = match endpoint.acquire_request_permit(*queue_timeout).await {
+                        Ok(permit) => permit,
+                        Err(Error::Web3SignerQueueTimeout) => {
+                            last_error = Error::Web3SignerQueueTimeout;
+                            continue;
+                        }
+                        Err(e) => return Err(e),
+                    };
+                    let _timer = validator_metrics::start_timer_vec(
+                        &validator_metrics::WEB3SIGNER_REQUEST_TIMES,
+                        &[message_type.as_str()],
+                    );
 
-                Ok(response.signature)
+                    // Request a signature from the Web3Signer instance via HTTP(S).
+                    let result = http_client
+                        .post(endpoint.signing_url.clone())
+                        .header(ACCEPT, "application/json")
+                        .json(&request)
+                        .send()
+                        .await
+                        .and_then(|response| response.error_for_status());
+
+                    match result {
+                        Ok(response) => {
+                            endpoint.health.set_online(true);
+                            let response: SigningResponse = response
+                                .json()
+                                .await
+                                .map_err(|e| Error::Web3SignerJsonParsingFailed(e.to_string()))?;
+                            return Ok(response.signature);
+                        }
+                        // The signer responded, but refused to sign (e.g. due to its own slashing
+                        // protection). Asking another signer could bypass that decision, so the
+                        // refusal is final.
+                        Err(e) if e.status().is_some_and(|status| status.is_client_error()) => {
+                            return Err(Error::Web3SignerRequestFailed(e.to_string()));
+                        }
+                        // The signer is unreachable, timed out or failed internally. The same
+                        // signing root may safely be sent to another signer, since it has already
+                        // been checked against the slashing protection database.
+                        Err(e) => {
+                            endpoint.health.set_online(false);
+                            if i + 1 < num_endpoints {
+                                validator_metrics::inc_counter_vec(
+                                    &validator_metrics::WEB3SIGNER_FAILOVERS,
+                                    &[endpoint.health.label()],
+                                );
+                            }
+                            last_error = Error::Web3SignerRequestFailed(e.to_string());
+                        }
+                    }
+                }
+
+                Err(last_error)
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{SocketAddr, TcpListener};
+    use task_executor::test_utils::TestRuntime;
+    use warp::Filter;
+
+    /// Serve `signature` in response to every signing request.
+    fn spawn_signer(signature: Signature) -> Url {
+        let route = warp::post()
+            .map(move || warp::reply::json(&serde_json::json!({ "signature": signature.clone() })));
+        let (addr, server) =
+            warp::serve(route).bind_ephemeral(SocketAddr::from(([127, 0, 0, 1], 0)));
+        tokio::spawn(server);
+        Url::parse(&format!("http://{}", addr)).unwrap()
+    }
+
+    /// Returns the URL of a port on which nothing is listening.
+    fn dead_signer() -> Url {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = Url::parse(&format!("http://{}", listener.local_addr().unwrap())).unwrap();
+        drop(listener);
+        url
+    }
+
+    fn endpoint(base_url: Url, voting_public_key: &PublicKey) -> Web3SignerEndpoint {
+        Web3SignerEndpoint {
+            signing_url: web3signer_url(
+                &base_url,
+                &format!("api/v1/eth2/sign/{}", voting_public_key),
+            )
+            .unwrap(),
+            health: Arc::new(Web3SignerHealth::new(base_url)),
+            request_limit: None,
+        }
+    }
+
+    async fn sign_randao(
+        signing_method: &SigningMethod,
+        signing_root: Hash256,
+        runtime: &TestRuntime,
+    ) -> Result<Signature, Error> {
+        signing_method
+            .get_signature_from_root::<MainnetEthSpec, FullPayload<MainnetEthSpec>>(
+                SignableMessage::RandaoReveal(Epoch::new(0)),
+                signing_root,
+                &runtime.task_executor,
+                None,
+            )
+            .await
+    }
+
+    #[test]
+    fn web3signer_url_retains_base_path() {
+        for base_url in ["http://signer:9000/prefix", "http://signer:9000/prefix/"] {
+            assert_eq!(
+                web3signer_url(&Url::parse(base_url).unwrap(), "upcheck")
+                    .unwrap()
+                    .as_str(),
+                "http://signer:9000/prefix/upcheck"
+            );
+        }
+        assert_eq!(
+            web3signer_url(
+                &Url::parse("http://signer:9000").unwrap(),
+                "api/v1/eth2/sign/0x00"
+            )
+            .unwrap()
+            .as_str(),
+            "http://signer:9000/api/v1/eth2/sign/0x00"
+        );
+    }
+
+    #[tokio::test]
+    async fn fails_over_from_dead_primary() {
+        let runtime = TestRuntime::default();
+        let keypair = Keypair::random();
+        let signing_root = Hash256::repeat_byte(1);
+        let signature = keypair.sk.sign(signing_root);
+
+        let primary = endpoint(dead_signer(), &keypair.pk);
+        let fallback = endpoint(spawn_signer(signature.clone()), &keypair.pk);
+        let signing_method = SigningMethod::Web3Signer {
+            endpoints: vec![primary.clone(), fallback.clone()],
+            http_client: Client::new(),
+            queue_timeout: Duration::from_secs(1),
+            voting_public_key: keypair.pk.clone(),
+        };
+
+        for _ in 0..2 {
+            let result = sign_randao(&signing_method, signing_root, &runtime).await;
+            assert_eq!(result, Ok(signature.clone()));
+
+            // The primary is marked offline, so later requests are sent to the fallback first.
+            assert!(!primary.health.is_online());
+            assert!(fallback.health.is_online());
+        }
+
+        // Requests fail if no signer is reachable.
+        let signing_method = SigningMethod::Web3Signer {
+            endpoints: vec![primary],
+            http_client: Client::new(),
+            queue_timeout: Duration::from_secs(1),
+            voting_public_key: keypair.pk,
+        };
+        assert!(matches!(
+            sign_randao(&signing_method, signing_root, &runtime).await,
+            Err(Error::Web3SignerRequestFailed(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn queue_times_out_while_host_is_saturated() {
+        let runtime = TestRuntime::default();
+        let keypair = Keypair::random();
+        let signing_root = Hash256::repeat_byte(1);
+        let signature = keypair.sk.sign(signing_root);
+
+        let request_limit = Arc::new(Semaphore::new(1));
+        let mut signer = endpoint(spawn_signer(signature.clone()), &keypair.pk);
+        signer.request_limit = Some(request_limit.clone());
+        let signing_method = SigningMethod::Web3Signer {
+            endpoints: vec![signer.clone()],
+            http_client: Client::new(),
+            queue_timeout: Duration::from_millis(100),
+            voting_public_key: keypair.pk.clone(),
+        };
+
+        // Another validator on the same host holds the only slot for longer than the timeout.
+        let permit = request_limit.acquire().await.unwrap();
+        assert_eq!(
+            sign_randao(&signing_method, signing_root, &runtime).await,
+            Err(Error::Web3SignerQueueTimeout)
+        );
+        // Waiting in the queue says nothing about the signer's health.
+        assert!(signer.health.is_online());
+
+        // A request which is queued is sent once the slot is released.
+        let release = async {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            drop(permit);
+        };
+        let (result, ()) = tokio::join!(
+            sign_randao(&signing_method, signing_root, &runtime),
+            release
+        );
+        assert_eq!(result, Ok(signature));
+        assert_eq!(request_limit.available_permits(), 1);
+    }
+
+    #[tokio::test]
+    async fn fails_over_from_saturated_host() {
+        let runtime = TestRuntime::default();
+        let keypair = Keypair::random();
+        let signing_root = Hash256::repeat_byte(1);
+        let signature = keypair.sk.sign(signing_root);
+
+        let request_limit = Arc::new(Semaphore::new(1));
+        let mut primary = endpoint(spawn_signer(signature.clone()), &keypair.pk);
+        primary.request_limit = Some(request_limit.clone());
+        let fallback = endpoint(spawn_signer(signature.clone()), &keypair.pk);
+        let signing_method = SigningMethod::Web3Signer {
+            endpoints: vec![primary.clone(), fallback],
+            http_client: Client::new(),
+            queue_timeout: Duration::from_millis(100),
+            voting_public_key: keypair.pk,
+        };
+
+        let _permit = request_limit.acquire().await.unwrap();
+        assert_eq!(
+            sign_randao(&signing_method, signing_root, &runtime).await,
+            Ok(signature)
+        );
+        assert!(primary.health.is_online());
+    }
+}
@@ -20,6 +20,27 @@ pub enum MessageType {
     ValidatorRegistration,
 }
 
+impl MessageType {
+    /// Returns a name for the message type, for use in metrics.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MessageType::AggregationSlot => "aggregation_slot",
+            MessageType::AggregateAndProof => "aggregate_and_proof",
+            MessageType::Attestation => "attestation",
+            MessageType::BlockV2 => "block_v2",
+            MessageType::Deposit => "deposit",
+            MessageType::RandaoReveal => "randao_reveal",
+            MessageType::VoluntaryExit => "voluntary_exit",
+            MessageType::SyncCommitteeMessage => "sync_committee_message",
+            MessageType::SyncCommitteeSelectionProof => "sync_committee_selection_proof",
+            MessageType::SyncCommitteeContributionAndProof => {
+                "sync_committee_contribution_and_proof"
+            }
+            MessageType::ValidatorRegistration => "validator_registration",
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Copy, Clone, Serialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum ForkName {
@@ -0,0 +1,78 @@
+//! Tracks whether each Web3Signer instance is reachable, so that signing requests can fail over to
+//! another instance listed in a validator's definition.
+use crate::web3signer_url;
+use reqwest::Client;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use url::Url;
+
+/// The timeout for requests to the `upcheck` endpoint.
+pub const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// The timeout for establishing a connection to a signer.
+///
+/// This is shorter than the request timeout so that a signer which is unreachable is failed over
+/// from promptly, whilst a signer which is slow to sign is still given the full request timeout.
+pub const CONNECT_TIMEOUT: Duration = HEALTH_CHECK_TIMEOUT;
+
+/// The health of a single Web3Signer instance, shared by all validators which use it.
+#[derive(Debug)]
+pub struct Web3SignerHealth {
+    url: Url,
+    /// The scheme, host and port of `url`, used to identify the signer in logs and metrics
+    /// without exposing any credentials in the URL.
+    label: String,
+    online: AtomicBool,
+}
+
+impl Web3SignerHealth {
+    /// Signers are assumed to be online until a request or health check fails.
+    pub fn new(url: Url) -> Self {
+        let label = url.origin().ascii_serialization();
+        Self {
+            url,
+            label,
+            online: AtomicBool::new(true),
+        }
+    }
+
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn is_online(&self) -> bool {
+        self.online.load(Ordering::Relaxed)
+    }
+
+    /// Records whether the signer is online, returning `true` if this changes its status.
+    pub fn set_online(&self, online: bool) -> bool {
+        validator_metrics::set_gauge_vec(
+            &validator_metrics::WEB3SIGNER_ONLINE,
+            &[&self.label],
+            online as i64,
+        );
+        self.online.swap(online, Ordering::Relaxed) != online
+    }
+
+    /// Query the `upcheck` endpoint of the signer and update its status.
+    pub async fn check(&self, client: &Client) -> Result<(), String> {
+        let result = async {
+            let url = web3signer_url(&self.url, "upcheck").map_err(|e| e.to_string())?;
+            client
+                .get(url)
+                .timeout(HEALTH_CHECK_TIMEOUT)
+                .send()
+                .await
+                .and_then(|response| response.error_for_status())
+                .map_err(|e| e.to_string())
+        }
+        .await;
+
+        self.set_online(result.is_ok());
+        result.map(|_| ())
+    }
+}
@@ -13,7 +13,7 @@ pub mod test_utils;
 pub use crate::signed_attestation::{InvalidAttestation, SignedAttestation};
 pub use crate::signed_block::{InvalidBlock, SignedBlock};
 pub use crate::slashing_database::{
-    InterchangeError, InterchangeImportOutcome, SlashingDatabase,
+    InterchangeError, InterchangeImportOutcome, PruneOutcome, SlashingDatabase,
     SUPPORTED_INTERCHANGE_FORMAT_VERSION,
 };
 use rusqlite::Error as SQLError;
@@ -24,6 +24,12 @@ use types::{Hash256, PublicKeyBytes};
 /// The filename within the `validators` directory that contains the slashing protection DB.
 pub const SLASHING_PROTECTION_FILENAME: &str = "slashing_protection.sqlite";
 
+/// The number of epochs of history retained in the slashing protection DB when pruning.
+///
+/// Records older than this are outside the weak subjectivity period of a typical network, so they
+/// are no longer required to protect against slashing.
+pub const SLASHING_PROTECTION_HISTORY_EPOCHS: u64 = 512;
+
 /// The attestation or block is not safe to sign.
 ///
 /// This could be because it's slashable, or because an error occurred.
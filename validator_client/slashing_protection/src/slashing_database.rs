@@ -895,73 +895,6 @@ impl SlashingDatabase {
         .collect()
     }
 
-    /// Remove all blocks for `public_key` with slots less than `new_min_slot`.
-    fn prune_signed_blocks(
-        &self,
-        public_key: &PublicKeyBytes,
-        new_min_slot: Slot,
-        txn: &Transaction,
-    ) -> Result<(), NotSafe> {
-        let validator_id = self.get_validator_id_in_txn(txn, public_key)?;
-
-        txn.execute(
-            "DELETE FROM signed_blocks
-             WHERE
-                validator_id = ?1 AND
-                slot < ?2 AND
-                slot < (SELECT MAX(slot)
-                        FROM signed_blocks
-                        WHERE validator_id = ?1)",
-            params![validator_id, new_min_slot],
-        )?;
-
-        Ok(())
-    }
-
-    /// Prune the signed blocks table for the given public keys.
-    pub fn prune_all_signed_blocks<'a>(
-        &self,
-        mut public_keys: impl Iterator<Item = &'a PublicKeyBytes>,
-        new_min_slot: Slot,
-    ) -> Result<(), NotSafe> {
-        let mut conn = self.conn_pool.get()?;
-        let txn = conn.transaction()?;
-        public_keys.try_for_each(|pubkey| self.prune_signed_blocks(pubkey, new_min_slot, &txn))?;
-        txn.commit()?;
-        Ok(())
-    }
-
-    /// Remove all attestations for `public_key` with `target < new_min_target`.
-    ///
-    /// If the `new_min_target` was plucked out of thin air and doesn't necessarily correspond to
-    /// an extant attestation then this function is still safe. It will never delete *all* the
-    /// attestations in the database.
-    fn prune_signed_attestations(
-        &self,
-        public_key: &PublicKeyBytes,
-        new_min_target: Epoch,
-        txn: &Transaction,
-    ) -> Result<(), NotSafe> {
-        let validator_id = self.get_validator_id_in_txn(txn, public_key)?;
-
-        // The following holds, because we never store mutually slashable attestations:
-        //   a.target < new_min_target --> a.source <= new_min_source
-        //
-        // The `MAX(target_epoch)` acts as a guard to prevent accidentally clearing the DB.
-        txn.execute(
-            "DELETE FROM signed_attestations
-             WHERE
-                validator_id = ?1 AND
-                target_epoch < ?2 AND
-                target_epoch < (SELECT MAX(target_epoch)
-                                FROM signed_attestations
-                                WHERE validator_id = ?1)",
-            params![validator_id, new_min_target],
-        )?;
-
-        Ok(())
-    }
-
     /// Remove all attestations signed by a given `public_key`.
     ///
     /// This function is incredibly dangerous and should be used with extreme caution. Presently
@@ -998,20 +931,67 @@ impl SlashingDatabase {
         Ok(())
     }
 
-    /// Prune the signed attestations table for the given validator keys.
-    pub fn prune_all_signed_attestations<'a>(
+    /// Remove attestations with `target < new_min_target` and blocks with `slot < new_min_slot`
+    /// for every registered validator, including disabled validators.
+    ///
+    /// The most recent block and attestation of each validator are always retained so that the
+    /// database continues to protect against slashing, even if `new_min_target` or `new_min_slot`
+    /// lie beyond them.
+    pub fn prune_all_validators(
         &self,
-        mut public_keys: impl Iterator<Item = &'a PublicKeyBytes>,
         new_min_target: Epoch,
-    ) -> Result<(), NotSafe> {
+        new_min_slot: Slot,
+    ) -> Result<PruneOutcome, NotSafe> {
         let mut conn = self.conn_pool.get()?;
         let txn = conn.transaction()?;
-        public_keys
-            .try_for_each(|pubkey| self.prune_signed_attestations(pubkey, new_min_target, &txn))?;
+
+        // The following holds, because we never store mutually slashable attestations:
+        //   a.target < new_min_target --> a.source <= new_min_source
+        //
+        // The `MAX(target_epoch)` acts as a guard to prevent accidentally clearing the DB.
+        let attestations_pruned = txn.execute(
+            "DELETE FROM signed_attestations
+             WHERE
+                target_epoch < ?1 AND
+                target_epoch < (SELECT MAX(target_epoch)
+                                FROM signed_attestations AS latest
+                                WHERE latest.validator_id = signed_attestations.validator_id)",
+            params![new_min_target],
+        )?;
+        let blocks_pruned = txn.execute(
+            "DELETE FROM signed_blocks
+             WHERE
+                slot < ?1 AND
+                slot < (SELECT MAX(slot)
+                        FROM signed_blocks AS latest
+                        WHERE latest.validator_id = signed_blocks.validator_id)",
+            params![new_min_slot],
+        )?;
+
         txn.commit()?;
+        Ok(PruneOutcome {
+            attestations_pruned,
+            blocks_pruned,
+        })
+    }
+
+    /// Rebuild the database file so that the space freed by pruning is returned to the OS.
+    pub fn compact(&self) -> Result<(), NotSafe> {
+        let conn = self.conn_pool.get()?;
+        conn.execute_batch("VACUUM")?;
         Ok(())
     }
 
+    /// The greatest attestation target epoch signed by any validator.
+    pub fn max_attestation_target(&self) -> Result<Option<Epoch>, NotSafe> {
+        let mut conn = self.conn_pool.get()?;
+        let txn = conn.transaction()?;
+        let max_target = txn
+            .prepare("SELECT MAX(target_epoch) FROM signed_attestations")?
+            .query_row(params![], |row| row.get(0))?;
+        Ok(max_target)
+    }
+
     pub fn num_validator_rows(&self) -> Result<u32, NotSafe> {
         let mut conn = self.conn_pool.get()?;
         let txn = conn.transaction()?;
@@ -1062,6 +1042,13 @@ impl SlashingDatabase {
     }
 }
 
+/// The number of records removed by `SlashingDatabase::prune_all_validators`.
+#[derive(Debug, PartialEq)]
+pub struct PruneOutcome {
+    pub attestations_pruned: usize,
+    pub blocks_pruned: usize,
+}
+
 /// Minimum and maximum slots and epochs signed by a validator.
 #[derive(Debug)]
 pub struct ValidatorSummary {
@@ -1181,6 +1168,7 @@ impl From<serde_json::Error> for InterchangeError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::pubkey;
     use tempfile::tempdir;
 
     #[test]
@@ -1238,6 +1226,65 @@ mod tests {
         check(&db2);
     }
 
+    #[test]
+    fn prune_all_validators_keeps_latest() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("db.sqlite");
+        let db = SlashingDatabase::create(&file).unwrap();
+
+        let old = pubkey(0);
+        let recent = pubkey(1);
+        db.register_validators([&old, &recent].into_iter()).unwrap();
+
+        for epoch in 1..4 {
+            db.check_and_insert_attestation_signing_root(
+                &old,
+                Epoch::new(epoch - 1),
+                Epoch::new(epoch),
+                Hash256::random().into(),
+            )
+            .unwrap();
+            db.check_and_insert_block_signing_root(
+                &old,
+                Slot::new(epoch),
+                Hash256::random().into(),
+            )
+            .unwrap();
+        }
+        for epoch in 10..14 {
+            db.check_and_insert_attestation_signing_root(
+                &recent,
+                Epoch::new(epoch - 1),
+                Epoch::new(epoch),
+                Hash256::random().into(),
+            )
+            .unwrap();
+        }
+        assert_eq!(db.max_attestation_target().unwrap(), Some(Epoch::new(13)));
+
+        let outcome = db
+            .prune_all_validators(Epoch::new(12), Slot::new(12))
+            .unwrap();
+        assert_eq!(
+            outcome,
+            PruneOutcome {
+                // Only the latest attestation of `old` is retained.
+                attestations_pruned: 2 + 2,
+                blocks_pruned: 2,
+            }
+        );
+        db.compact().unwrap();
+
+        let txn = &db.conn_pool.get().unwrap().transaction().unwrap();
+        let old_summary = db.validator_summary(&old, txn).unwrap();
+        assert_eq!(old_summary.min_attestation_target, Some(Epoch::new(3)));
+        assert_eq!(old_summary.max_block_slot, Some(Slot::new(3)));
+        assert_eq!(old_summary.min_block_slot, Some(Slot::new(3)));
+        let recent_summary = db.validator_summary(&recent, txn).unwrap();
+        assert_eq!(recent_summary.min_attestation_target, Some(Epoch::new(12)));
+        assert_eq!(recent_summary.max_attestation_target, Some(Epoch::new(13)));
+    }
+
     #[test]
     fn test_transaction_failure() {
         let dir = tempdir().unwrap();
@@ -161,6 +161,29 @@ pub fn cli_app() -> Command {
                 .help_heading(FLAG_HEADER)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("precompute-selection-proofs")
+                .long("precompute-selection-proofs")
+                .help("Compute the attestation selection proofs of all validators for the \
+                       current and next epoch as soon as their duties are known, rather than \
+                       a few slots before each duty. This reduces the work done when producing \
+                       aggregates, at the cost of a burst of signing after each duties update.")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("distributed")
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("hot-reload-definitions")
+                .long("hot-reload-definitions")
+                .help("Reload the fee recipient and gas limit of each validator from the \
+                       validator_definitions.yml file whenever it is modified or the process \
+                       receives SIGHUP, without restarting. Other changes to the file are not \
+                       applied. SIGHUP will no longer shut down the validator client.")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
         /* REST API related arguments */
         .arg(
             Arg::new("http")
@@ -404,6 +427,17 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("builder-min-bid-gwei")
+                .long("builder-min-bid-gwei")
+                .value_name("GWEI")
+                .help("Builder bids below this value (in gwei) are ignored by the beacon node \
+                    in favour of a payload from the local execution node. This can be \
+                    overridden for each validator in the validator definitions file or \
+                    via the validator client API.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("prefer-builder-proposals")
                 .long("prefer-builder-proposals")
@@ -475,4 +509,15 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("web3-signer-max-concurrent-requests")
+                .long("web3-signer-max-concurrent-requests")
+                .value_name("COUNT")
+                .help("Maximum number of signing requests in flight to each web3signer host. \
+                       Requests beyond this limit wait for an earlier request to complete, \
+                       failing over to the next web3signer if none completes within the \
+                       request timeout. Default is unlimited.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
 }
@@ -81,6 +81,11 @@ pub struct Config {
     pub validator_registration_batch_size: usize,
     /// Whether we are running with distributed network support.
     pub distributed: bool,
+    /// Whether to compute all attestation selection proofs as soon as duties are known.
+    pub precompute_selection_proofs: bool,
+    /// If true, reload the fee recipient and gas limit of each validator from the validator
+    /// definitions file when it is modified or upon SIGHUP.
+    pub hot_reload_definitions: bool,
     /// Configuration for the initialized validators
     #[serde(flatten)]
     pub initialized_validators: InitializedValidatorsConfig,
@@ -124,6 +129,8 @@ impl Default for Config {
             enable_latency_measurement_service: true,
             validator_registration_batch_size: 500,
             distributed: false,
+            precompute_selection_proofs: false,
+            hot_reload_definitions: false,
             initialized_validators: <_>::default(),
         }
     }
@@ -230,6 +237,10 @@ impl Config {
             config.distributed = true;
         }
 
+        config.precompute_selection_proofs = cli_args.get_flag("precompute-selection-proofs");
+
+        config.hot_reload_definitions = cli_args.get_flag("hot-reload-definitions");
+
         if let Some(broadcast_topics) = cli_args.get_one::<String>("broadcast") {
             config.broadcast_topics = broadcast_topics
                 .split(',')
@@ -269,6 +280,14 @@ impl Config {
                 .initialized_validators
                 .web3_signer_max_idle_connections = Some(n);
         }
+        if let Some(n) = parse_optional::<usize>(cli_args, "web3-signer-max-concurrent-requests")? {
+            if n == 0 {
+                return Err("web3-signer-max-concurrent-requests must be greater than 0".into());
+            }
+            config
+                .initialized_validators
+                .web3_signer_max_concurrent_requests = Some(n);
+        }
 
         /*
          * Http API server
@@ -399,6 +418,9 @@ impl Config {
         config.validator_store.builder_boost_factor =
             parse_optional(cli_args, "builder-boost-factor")?;
 
+        config.validator_store.builder_min_bid_gwei =
+            parse_optional(cli_args, "builder-min-bid-gwei")?;
+
         config.enable_latency_measurement_service =
             !cli_args.get_flag("disable-latency-measurement-service");
 
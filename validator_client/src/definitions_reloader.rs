@@ -0,0 +1,96 @@
+use crate::ProductionValidatorClient;
+use account_utils::validator_definitions::CONFIG_FILENAME;
+use initialized_validators::InitializedValidators;
+use slog::{debug, error, info, Logger};
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+use tokio::time::{interval, Duration};
+use types::EthSpec;
+
+/// How often the validator definitions file is checked for modifications.
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Spawns a service which reloads the fee recipient and gas limit of each validator from the
+/// validator definitions file whenever the file is modified or the process receives SIGHUP.
+pub fn spawn_definitions_reloader<E: EthSpec>(
+    client: &ProductionValidatorClient<E>,
+) -> Result<(), String> {
+    let context = client
+        .context
+        .service_context("definitions_reloader".into());
+    let executor = context.executor.clone();
+    let initialized_validators = client.validator_store.initialized_validators();
+    let definitions_path = client.config.validator_dir.join(CONFIG_FILENAME);
+
+    let reload_fut = async move {
+        let log = context.log();
+        let mut last_modified = modified_time(&definitions_path);
+        let mut poll = interval(POLL_INTERVAL);
+
+        #[cfg(target_family = "unix")]
+        let mut hangup = match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup())
+        {
+            Ok(hangup) => Some(hangup),
+            Err(e) => {
+                error!(
+                    log,
+                    "Unable to listen for SIGHUP";
+                    "info" => "the validator definitions will only be reloaded when modified",
+                    "error" => %e,
+                );
+                None
+            }
+        };
+
+        loop {
+            #[cfg(target_family = "unix")]
+            let sighup = tokio::select! {
+                _ = poll.tick() => false,
+                Some(()) = async { hangup.as_mut()?.recv().await } => true,
+            };
+            #[cfg(not(target_family = "unix"))]
+            let sighup = {
+                poll.tick().await;
+                false
+            };
+
+            let modified = modified_time(&definitions_path);
+            if !sighup && modified == last_modified {
+                continue;
+            }
+            last_modified = modified;
+
+            let trigger = if sighup { "sighup" } else { "file_modified" };
+            debug!(log, "Reloading validator definitions"; "trigger" => trigger);
+            reload(&mut initialized_validators.write(), log, trigger);
+        }
+    };
+
+    executor.spawn(reload_fut, "definitions_reloader");
+    Ok(())
+}
+
+fn reload(initialized_validators: &mut InitializedValidators, log: &Logger, trigger: &str) {
+    match initialized_validators.reload_fee_recipients_and_gas_limits() {
+        Ok(0) => debug!(log, "No validator definition changes to apply"),
+        Ok(num_changed) => info!(
+            log,
+            "Applied validator definition changes";
+            "changed_fields" => num_changed,
+            "trigger" => trigger,
+        ),
+        Err(e) => error!(
+            log,
+            "Failed to reload validator definitions";
+            "info" => "the previous fee recipients and gas limits remain in use",
+            "error" => ?e,
+        ),
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
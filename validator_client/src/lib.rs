@@ -1,7 +1,9 @@
 mod cli;
 pub mod config;
+mod definitions_reloader;
 mod latency;
 mod notifier;
+mod web3signer_health;
 
 pub use cli::cli_app;
 pub use config::Config;
@@ -17,6 +19,7 @@ use beacon_node_fallback::{
 
 use account_utils::validator_definitions::ValidatorDefinitions;
 use clap::ArgMatches;
+use definitions_reloader::spawn_definitions_reloader;
 use doppelganger_service::DoppelgangerService;
 use environment::RuntimeContext;
 use eth2::{reqwest::ClientBuilder, BeaconNodeHttpClient, StatusCode, Timeouts};
@@ -43,12 +46,14 @@ use validator_http_api::ApiSecret;
 use validator_services::{
     attestation_service::{AttestationService, AttestationServiceBuilder},
     block_service::{BlockService, BlockServiceBuilder},
+    bls_to_execution_change_service::BlsToExecutionChangeService,
     duties_service::{self, DutiesService},
     preparation_service::{PreparationService, PreparationServiceBuilder},
     sync::SyncDutiesMap,
     sync_committee_service::SyncCommitteeService,
 };
 use validator_store::ValidatorStore;
+use web3signer_health::spawn_web3signer_health_checker;
 
 /// The interval between attempts to contact the beacon node during startup.
 const RETRY_DELAY: Duration = Duration::from_secs(2);
@@ -82,6 +87,7 @@ pub struct ProductionValidatorClient<E: EthSpec> {
     sync_committee_service: SyncCommitteeService<SystemTimeSlotClock, E>,
     doppelganger_service: Option<Arc<DoppelgangerService>>,
     preparation_service: PreparationService<SystemTimeSlotClock, E>,
+    bls_to_execution_change_service: Arc<BlsToExecutionChangeService<SystemTimeSlotClock, E>>,
     validator_store: Arc<ValidatorStore<SystemTimeSlotClock, E>>,
     slot_clock: SystemTimeSlotClock,
     http_api_listen_addr: Option<SocketAddr>,
@@ -478,6 +484,7 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             context: duties_context,
             enable_high_validator_count_metrics: config.enable_high_validator_count_metrics,
             distributed: config.distributed,
+            precompute_selection_proofs: config.precompute_selection_proofs,
         });
 
         // Update the metrics server.
@@ -526,6 +533,16 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             context.service_context("sync_committee".into()),
         );
 
+        let bls_to_execution_change_context =
+            context.service_context("bls_to_execution_change".into());
+        let bls_to_execution_change_service = Arc::new(BlsToExecutionChangeService::new(
+            &config.validator_dir,
+            beacon_nodes.clone(),
+            slot_clock.clone(),
+            bls_to_execution_change_context.executor.clone(),
+            bls_to_execution_change_context.log().clone(),
+        )?);
+
         Ok(Self {
             context,
             duties_service,
@@ -534,6 +551,7 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             sync_committee_service,
             doppelganger_service,
             preparation_service,
+            bls_to_execution_change_service,
             validator_store,
             config,
             slot_clock,
@@ -558,6 +576,7 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
                 task_executor: self.context.executor.clone(),
                 api_secret,
                 block_service: Some(self.block_service.clone()),
+                bls_to_execution_change_service: Some(self.bls_to_execution_change_service.clone()),
                 validator_store: Some(self.validator_store.clone()),
                 validator_dir: Some(self.config.validator_dir.clone()),
                 secrets_dir: Some(self.config.secrets_dir.clone()),
@@ -612,6 +631,11 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             .start_update_service(&self.context.eth2_config.spec)
             .map_err(|e| format!("Unable to start preparation service: {}", e))?;
 
+        self.bls_to_execution_change_service
+            .clone()
+            .start_update_service()
+            .map_err(|e| format!("Unable to start BLS to execution change service: {}", e))?;
+
         if let Some(doppelganger_service) = self.doppelganger_service.clone() {
             DoppelgangerService::start_update_service(
                 doppelganger_service,
@@ -628,6 +652,14 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
 
         spawn_notifier(self).map_err(|e| format!("Failed to start notifier: {}", e))?;
 
+        if self.config.hot_reload_definitions {
+            spawn_definitions_reloader(self)
+                .map_err(|e| format!("Failed to start definitions reloader: {}", e))?;
+        }
+
+        spawn_web3signer_health_checker(self)
+            .map_err(|e| format!("Failed to start web3signer health checker: {}", e))?;
+
         if self.config.enable_latency_measurement_service {
             latency::start_latency_service(
                 self.context.clone(),
@@ -95,6 +95,9 @@ async fn notify<T: SlotClock + 'static, E: EthSpec>(
                 "is_optimistic" => ?health.optimistic_status,
                 "execution_engine_status" => ?health.execution_status,
                 "health_tier" => %health.health_tier,
+                "latency_ms" => health.latency_ms,
+                "error_rate" => health.error_rate,
+                "health_score" => health.score,
             );
         } else {
             debug!(
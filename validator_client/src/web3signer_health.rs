@@ -0,0 +1,56 @@
+use crate::ProductionValidatorClient;
+use slog::{debug, info, warn};
+use tokio::time::{interval, Duration};
+use types::EthSpec;
+
+/// How often each Web3Signer instance is checked.
+const CHECK_INTERVAL: Duration = Duration::from_secs(6);
+
+/// Spawns a service which periodically queries the `upcheck` endpoint of each Web3Signer instance,
+/// so that signing requests are sent to signers which are online before those which are not.
+pub fn spawn_web3signer_health_checker<E: EthSpec>(
+    client: &ProductionValidatorClient<E>,
+) -> Result<(), String> {
+    let context = client.context.service_context("web3signer_health".into());
+    let executor = context.executor.clone();
+    let initialized_validators = client.validator_store.initialized_validators();
+
+    let check_fut = async move {
+        let log = context.log();
+        let mut poll = interval(CHECK_INTERVAL);
+
+        loop {
+            poll.tick().await;
+
+            // Collect the signers before checking them, so the lock isn't held across requests.
+            let signers = initialized_validators.read().web3_signers();
+            for (http_client, health) in signers {
+                let was_online = health.is_online();
+                match health.check(&http_client).await {
+                    Ok(()) if !was_online => info!(
+                        log,
+                        "Web3Signer is back online";
+                        "endpoint" => health.label(),
+                    ),
+                    Ok(()) => debug!(log, "Web3Signer is online"; "endpoint" => health.label()),
+                    Err(e) if was_online => warn!(
+                        log,
+                        "Web3Signer is offline";
+                        "info" => "signing requests will be sent to fallback signers, if any",
+                        "endpoint" => health.label(),
+                        "error" => e,
+                    ),
+                    Err(e) => debug!(
+                        log,
+                        "Web3Signer is still offline";
+                        "endpoint" => health.label(),
+                        "error" => e,
+                    ),
+                }
+            }
+        }
+    };
+
+    executor.spawn(check_fut, "web3signer_health");
+    Ok(())
+}
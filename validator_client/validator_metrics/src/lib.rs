@@ -1,6 +1,7 @@
 use std::sync::LazyLock;
 
 pub const SUCCESS: &str = "success";
+pub const FAILURE: &str = "failure";
 pub const SLASHABLE: &str = "slashable";
 pub const SAME_DATA: &str = "same_data";
 pub const UNREGISTERED: &str = "unregistered";
@@ -135,6 +136,13 @@ pub static ATTESTATION_SERVICE_TIMES: LazyLock<Result<HistogramVec>> = LazyLock:
         &["task"],
     )
 });
+pub static ATTESTATION_PRODUCTION_DELAY: LazyLock<Result<HistogramVec>> = LazyLock::new(|| {
+    try_create_histogram_vec(
+        "vc_attestation_production_delay_seconds",
+        "Time into the slot at which attestation production was targeted and actually began",
+        &["type"],
+    )
+});
 pub static SLASHING_PROTECTION_PRUNE_TIMES: LazyLock<Result<Histogram>> = LazyLock::new(|| {
     try_create_histogram(
         "vc_slashing_protection_prune_times_seconds",
@@ -185,6 +193,27 @@ pub static ENDPOINT_REQUESTS: LazyLock<Result<IntCounterVec>> = LazyLock::new(||
         &["endpoint"],
     )
 });
+pub static ENDPOINT_DUTIES_SERVED: LazyLock<Result<IntCounterVec>> = LazyLock::new(|| {
+    try_create_int_counter_vec(
+        "bn_endpoint_duties_served",
+        "The number of requests for each type of duty served by each endpoint",
+        &["endpoint", "duty"],
+    )
+});
+pub static ENDPOINT_BROADCASTS: LazyLock<Result<IntCounterVec>> = LazyLock::new(|| {
+    try_create_int_counter_vec(
+        "bn_endpoint_broadcasts",
+        "The outcome of each broadcast request for each endpoint and API topic",
+        &["endpoint", "topic", "outcome"],
+    )
+});
+pub static ENDPOINT_HEALTH_SCORE: LazyLock<Result<IntGaugeVec>> = LazyLock::new(|| {
+    try_create_int_gauge_vec(
+        "bn_endpoint_health_score",
+        "The health score of each endpoint, lower is better",
+        &["endpoint"],
+    )
+});
 
 /*
  * Beacon node availability metrics
@@ -237,6 +266,33 @@ pub static BLOCK_SIGNING_TIMES: LazyLock<Result<Histogram>> = LazyLock::new(|| {
         "Duration to obtain a signature for a block",
     )
 });
+pub static WEB3SIGNER_QUEUE_TIMES: LazyLock<Result<Histogram>> = LazyLock::new(|| {
+    try_create_histogram(
+        "vc_web3signer_queue_times_seconds",
+        "Time spent waiting for a free slot when web3signer requests are limited",
+    )
+});
+pub static WEB3SIGNER_REQUEST_TIMES: LazyLock<Result<HistogramVec>> = LazyLock::new(|| {
+    try_create_histogram_vec(
+        "vc_web3signer_request_times_seconds",
+        "Duration of signing requests to web3signer, by message type",
+        &["type"],
+    )
+});
+pub static WEB3SIGNER_ONLINE: LazyLock<Result<IntGaugeVec>> = LazyLock::new(|| {
+    try_create_int_gauge_vec(
+        "vc_web3signer_online",
+        "Set to 1 if the web3signer endpoint is online, 0 if it is unreachable",
+        &["endpoint"],
+    )
+});
+pub static WEB3SIGNER_FAILOVERS: LazyLock<Result<IntCounterVec>> = LazyLock::new(|| {
+    try_create_int_counter_vec(
+        "vc_web3signer_failovers_total",
+        "Count of signing requests retried on another web3signer, by the endpoint which failed",
+        &["endpoint"],
+    )
+});
 
 pub static ATTESTATION_DUTY: LazyLock<Result<IntGaugeVec>> = LazyLock::new(|| {
     try_create_int_gauge_vec(
@@ -1,5 +1,5 @@
 use crate::duties_service::{DutiesService, DutyAndProof};
-use beacon_node_fallback::{ApiTopic, BeaconNodeFallback};
+use beacon_node_fallback::{ApiTopic, BeaconNodeFallback, DutyType};
 use environment::RuntimeContext;
 use futures::future::join_all;
 use slog::{crit, debug, error, info, trace, warn};
@@ -90,7 +90,8 @@ pub struct Inner<T, E: EthSpec> {
     context: RuntimeContext<E>,
 }
 
-/// Attempts to produce attestations for all known validators 1/3rd of the way through each slot.
+/// Attempts to produce attestations for all known validators 1/3rd of the way through each slot,
+/// or at the offset into the slot specified in the validator's definition.
 ///
 /// If any validators are on the same committee, a single attestation will be downloaded and
 /// returned to the beacon node. This attestation will have a signature from each of the
@@ -137,7 +138,13 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
         let interval_fut = async move {
             loop {
                 if let Some(duration_to_next_slot) = self.slot_clock.duration_to_next_slot() {
-                    sleep(duration_to_next_slot + slot_duration / 3).await;
+                    // Wake in time for the validator with the earliest offset, if any are earlier
+                    // than the default.
+                    let offset = first_attestation_offset(
+                        self.validator_store.min_attestation_offset(),
+                        slot_duration,
+                    );
+                    sleep(duration_to_next_slot + offset).await;
                     let log = self.context.log();
 
                     if let Err(e) = self.spawn_attestation_tasks(slot_duration) {
@@ -180,36 +187,46 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
             + duration_to_next_slot
                 .checked_sub(slot_duration / 3)
                 .unwrap_or_else(|| Duration::from_secs(0));
-
-        let duties_by_committee_index: HashMap<CommitteeIndex, Vec<DutyAndProof>> = self
-            .duties_service
-            .attesters(slot)
-            .into_iter()
-            .fold(HashMap::new(), |mut map, duty_and_proof| {
-                map.entry(duty_and_proof.duty.committee_index)
+        let slot_start_instant = Instant::now()
+            .checked_sub(slot_duration.saturating_sub(duration_to_next_slot))
+            .unwrap_or_else(Instant::now);
+
+        // Validators with a custom offset produce their attestations separately from the rest of
+        // their committee.
+        let duties_by_offset_and_committee_index: HashMap<
+            (Duration, CommitteeIndex),
+            Vec<DutyAndProof>,
+        > = self.duties_service.attesters(slot).into_iter().fold(
+            HashMap::new(),
+            |mut map, duty_and_proof| {
+                let offset = self.attestation_offset(&duty_and_proof, slot_duration);
+                map.entry((offset, duty_and_proof.duty.committee_index))
                     .or_default()
                     .push(duty_and_proof);
                 map
-            });
+            },
+        );
 
-        // For each committee index for this slot:
+        // For each offset and committee index for this slot:
         //
         // - Create and publish an `Attestation` for all required validators.
         // - Create and publish `SignedAggregateAndProof` for all aggregating validators.
-        duties_by_committee_index
-            .into_iter()
-            .for_each(|(committee_index, validator_duties)| {
+        duties_by_offset_and_committee_index.into_iter().for_each(
+            |((offset, committee_index), validator_duties)| {
                 // Spawn a separate task for each attestation.
                 self.inner.context.executor.spawn_ignoring_error(
                     self.clone().publish_attestations_and_aggregates(
                         slot,
                         committee_index,
                         validator_duties,
+                        slot_start_instant + offset,
+                        offset,
                         aggregate_production_instant,
                     ),
                     "attestation publish",
                 );
-            });
+            },
+        );
 
         // Schedule pruning of the slashing protection database once all unaggregated
         // attestations have (hopefully) been signed, i.e. at the same time as aggregate
@@ -219,6 +236,31 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
         Ok(())
     }
 
+    /// Returns the time into the slot at which the validator of `duty_and_proof` should attest.
+    ///
+    /// Offsets which are not before aggregate production are ignored in favour of the default of
+    /// 1/3rd of the way through the slot.
+    fn attestation_offset(
+        &self,
+        duty_and_proof: &DutyAndProof,
+        slot_duration: Duration,
+    ) -> Duration {
+        let configured_offset = self
+            .validator_store
+            .get_attestation_offset(&duty_and_proof.duty.pubkey);
+        let offset = attestation_offset_or_default(configured_offset, slot_duration);
+        if let Some(configured_offset) = configured_offset.filter(|o| *o != offset) {
+            warn!(
+                self.context.log(),
+                "Ignoring invalid attestation offset";
+                "info" => "the offset must be less than two thirds of the slot duration",
+                "validator" => ?duty_and_proof.duty.pubkey,
+                "offset_ms" => configured_offset.as_millis(),
+            );
+        }
+        offset
+    }
+
     /// Performs the first step of the attesting process: downloading `Attestation` objects,
     /// signing them and returning them to the validator.
     ///
@@ -228,14 +270,38 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
     ///
     /// The given `validator_duties` should already be filtered to only contain those that match
     /// `slot` and `committee_index`. Critical errors will be logged if this is not the case.
+    ///
+    /// Production begins at `production_instant`, which is `target_offset` into the slot.
     async fn publish_attestations_and_aggregates(
         self,
         slot: Slot,
         committee_index: CommitteeIndex,
         validator_duties: Vec<DutyAndProof>,
+        production_instant: Instant,
+        target_offset: Duration,
         aggregate_production_instant: Instant,
     ) -> Result<(), ()> {
         let log = self.context.log();
+
+        sleep_until(production_instant).await;
+        validator_metrics::observe_timer_vec(
+            &validator_metrics::ATTESTATION_PRODUCTION_DELAY,
+            &["target"],
+            target_offset,
+        );
+        if let Some(actual_offset) = self
+            .slot_clock
+            .now_duration()
+            .zip(self.slot_clock.start_of(slot))
+            .and_then(|(now, slot_start)| now.checked_sub(slot_start))
+        {
+            validator_metrics::observe_timer_vec(
+                &validator_metrics::ATTESTATION_PRODUCTION_DELAY,
+                &["actual"],
+                actual_offset,
+            );
+        }
+
         let attestations_timer = validator_metrics::start_timer_vec(
             &validator_metrics::ATTESTATION_SERVICE_TIMES,
             &[validator_metrics::ATTESTATIONS],
@@ -336,7 +402,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
 
         let attestation_data = self
             .beacon_nodes
-            .first_success(|beacon_node| async move {
+            .first_success_for(DutyType::AttestationData, |beacon_node| async move {
                 let _timer = validator_metrics::start_timer_vec(
                     &validator_metrics::ATTESTATION_SERVICE_TIMES,
                     &[validator_metrics::ATTESTATIONS_HTTP_GET],
@@ -528,7 +594,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
 
         let aggregated_attestation = &self
             .beacon_nodes
-            .first_success(|beacon_node| async move {
+            .first_success_for(DutyType::Aggregation, |beacon_node| async move {
                 let _timer = validator_metrics::start_timer_vec(
                     &validator_metrics::ATTESTATION_SERVICE_TIMES,
                     &[validator_metrics::AGGREGATES_HTTP_GET],
@@ -617,26 +683,30 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
             let signed_aggregate_and_proofs_slice = signed_aggregate_and_proofs.as_slice();
             match self
                 .beacon_nodes
-                .first_success(|beacon_node| async move {
-                    let _timer = validator_metrics::start_timer_vec(
-                        &validator_metrics::ATTESTATION_SERVICE_TIMES,
-                        &[validator_metrics::AGGREGATES_HTTP_POST],
-                    );
-                    if fork_name.electra_enabled() {
-                        beacon_node
-                            .post_validator_aggregate_and_proof_v2(
-                                signed_aggregate_and_proofs_slice,
-                                fork_name,
-                            )
-                            .await
-                    } else {
-                        beacon_node
-                            .post_validator_aggregate_and_proof_v1(
-                                signed_aggregate_and_proofs_slice,
-                            )
-                            .await
-                    }
-                })
+                .request_for(
+                    ApiTopic::Attestations,
+                    DutyType::Aggregation,
+                    |beacon_node| async move {
+                        let _timer = validator_metrics::start_timer_vec(
+                            &validator_metrics::ATTESTATION_SERVICE_TIMES,
+                            &[validator_metrics::AGGREGATES_HTTP_POST],
+                        );
+                        if fork_name.electra_enabled() {
+                            beacon_node
+                                .post_validator_aggregate_and_proof_v2(
+                                    signed_aggregate_and_proofs_slice,
+                                    fork_name,
+                                )
+                                .await
+                        } else {
+                            beacon_node
+                                .post_validator_aggregate_and_proof_v1(
+                                    signed_aggregate_and_proofs_slice,
+                                )
+                                .await
+                        }
+                    },
+                )
                 .await
             {
                 Ok(()) => {
@@ -701,6 +771,23 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
     }
 }
 
+/// Returns the time into the slot at which a validator with the configured `offset` attests.
+///
+/// Attestations must be produced before aggregates, which are produced 2/3rds of the way through
+/// the slot. Offsets at or beyond that point are ignored in favour of the default of 1/3rd of the
+/// way through the slot.
+fn attestation_offset_or_default(offset: Option<Duration>, slot_duration: Duration) -> Duration {
+    offset
+        .filter(|offset| *offset < slot_duration * 2 / 3)
+        .unwrap_or(slot_duration / 3)
+}
+
+/// Returns the time into each slot at which the service wakes to spawn attestation tasks, given
+/// the earliest offset configured for any validator.
+fn first_attestation_offset(min_offset: Option<Duration>, slot_duration: Duration) -> Duration {
+    attestation_offset_or_default(min_offset, slot_duration).min(slot_duration / 3)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -724,4 +811,54 @@ mod tests {
             "state should have been updated"
         );
     }
+
+    #[test]
+    fn attestations_are_scheduled_at_valid_offsets() {
+        let slot_duration = Duration::from_secs(12);
+        let ms = Duration::from_millis;
+
+        assert_eq!(attestation_offset_or_default(None, slot_duration), ms(4000));
+        assert_eq!(
+            attestation_offset_or_default(Some(ms(0)), slot_duration),
+            ms(0)
+        );
+        assert_eq!(
+            attestation_offset_or_default(Some(ms(5500)), slot_duration),
+            ms(5500)
+        );
+        assert_eq!(
+            attestation_offset_or_default(Some(ms(7999)), slot_duration),
+            ms(7999)
+        );
+
+        // Offsets at or after aggregate production fall back to the default.
+        for invalid in [ms(8000), ms(11_000), ms(12_000), ms(60_000)] {
+            assert_eq!(
+                attestation_offset_or_default(Some(invalid), slot_duration),
+                ms(4000)
+            );
+        }
+    }
+
+    #[test]
+    fn service_wakes_for_the_earliest_offset() {
+        let slot_duration = Duration::from_secs(12);
+        let ms = Duration::from_millis;
+
+        assert_eq!(first_attestation_offset(None, slot_duration), ms(4000));
+        assert_eq!(
+            first_attestation_offset(Some(ms(1500)), slot_duration),
+            ms(1500)
+        );
+        // Validators without an offset still attest at the default, so the service wakes no
+        // later than that.
+        assert_eq!(
+            first_attestation_offset(Some(ms(6000)), slot_duration),
+            ms(4000)
+        );
+        assert_eq!(
+            first_attestation_offset(Some(ms(9000)), slot_duration),
+            ms(4000)
+        );
+    }
 }
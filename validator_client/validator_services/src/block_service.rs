@@ -1,7 +1,9 @@
-use beacon_node_fallback::{ApiTopic, BeaconNodeFallback, Error as FallbackError, Errors};
+use beacon_node_fallback::{
+    ApiTopic, BeaconNodeFallback, DutyType, Error as FallbackError, Errors,
+};
 use bls::SignatureBytes;
 use environment::RuntimeContext;
-use eth2::types::{FullBlockContents, PublishBlockRequest};
+use eth2::types::{FullBlockContents, PublishBlockRequest, SkipRandaoVerification};
 use eth2::{BeaconNodeHttpClient, StatusCode};
 use graffiti_file::{determine_graffiti, GraffitiFile};
 use slog::{crit, debug, error, info, trace, warn, Logger};
@@ -163,7 +165,10 @@ impl<T: SlotClock, E: EthSpec> ProposerFallback<T, E> {
         Err: Debug,
     {
         // Try running `func` on the non-proposer beacon nodes.
-        let beacon_nodes_result = self.beacon_nodes.first_success(func.clone()).await;
+        let beacon_nodes_result = self
+            .beacon_nodes
+            .first_success_for(DutyType::BlockProposal, func.clone())
+            .await;
 
         match (beacon_nodes_result, &self.proposer_nodes) {
             // The non-proposer node call succeed, return the result.
@@ -171,7 +176,11 @@ impl<T: SlotClock, E: EthSpec> ProposerFallback<T, E> {
             // The non-proposer node call failed, but we don't have any proposer nodes. Return an error.
             (Err(e), None) => Err(e),
             // The non-proposer node call failed, try the same call on the proposer nodes.
-            (Err(_), Some(proposer_nodes)) => proposer_nodes.first_success(func).await,
+            (Err(_), Some(proposer_nodes)) => {
+                proposer_nodes
+                    .first_success_for(DutyType::BlockProposal, func)
+                    .await
+            }
         }
     }
 }
@@ -456,6 +465,9 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
         let randao_reveal_ref = &randao_reveal;
         let self_ref = &self;
         let proposer_index = self.validator_store.validator_index(&validator_pubkey);
+        let builder_min_bid_gwei = self
+            .validator_store
+            .get_builder_min_bid_gwei(&validator_pubkey);
         let proposer_fallback = ProposerFallback {
             beacon_nodes: self.beacon_nodes.clone(),
             proposer_nodes: self.proposer_nodes.clone(),
@@ -484,6 +496,7 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
                     graffiti,
                     proposer_index,
                     builder_boost_factor,
+                    builder_min_bid_gwei,
                     log,
                 )
                 .await
@@ -541,6 +554,7 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
         Ok::<_, BlockError>(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn get_validator_block(
         beacon_node: &BeaconNodeHttpClient,
         slot: Slot,
@@ -548,14 +562,17 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
         graffiti: Option<Graffiti>,
         proposer_index: Option<u64>,
         builder_boost_factor: Option<u64>,
+        builder_min_bid_gwei: Option<u64>,
         log: &Logger,
     ) -> Result<UnsignedBlock<E>, BlockError> {
         let (block_response, _) = beacon_node
-            .get_validator_blocks_v3::<E>(
+            .get_validator_blocks_v3_modular::<E>(
                 slot,
                 randao_reveal_ref,
                 graffiti.as_ref(),
+                SkipRandaoVerification::No,
                 builder_boost_factor,
+                builder_min_bid_gwei,
             )
             .await
             .map_err(|e| {
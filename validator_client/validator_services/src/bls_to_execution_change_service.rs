@@ -0,0 +1,356 @@
+//! Holds signed BLS to execution changes until a configured epoch, then publishes them to the
+//! beacon node.
+//!
+//! Queued changes are persisted to the validator directory so that they survive restarts.
+use beacon_node_fallback::{BeaconNodeFallback, Errors};
+use eth2::lighthouse_vc::types::{BlsToExecutionChangeStatus, QueuedBlsToExecutionChange};
+use eth2::StatusCode;
+use parking_lot::Mutex;
+use slog::{debug, error, info, warn, Logger};
+use slot_clock::SlotClock;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use task_executor::TaskExecutor;
+use tokio::time::{sleep, Duration};
+use types::{Epoch, EthSpec, SignedBlsToExecutionChange};
+
+/// The file in the validator directory which stores queued changes.
+pub const BLS_TO_EXECUTION_CHANGES_FILENAME: &str = "bls_to_execution_changes.json";
+
+/// The result of publishing the change of each validator index.
+type PublishResults = Vec<(u64, Result<(), Errors<eth2::Error>>)>;
+
+pub struct BlsToExecutionChangeService<T, E: EthSpec> {
+    changes: Mutex<Vec<QueuedBlsToExecutionChange>>,
+    /// The epoch from which each change which could not be published is retried, by validator
+    /// index.
+    retry_epochs: Mutex<HashMap<u64, Epoch>>,
+    path: PathBuf,
+    beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
+    slot_clock: T,
+    executor: TaskExecutor,
+    log: Logger,
+}
+
+impl<T: SlotClock + 'static, E: EthSpec> BlsToExecutionChangeService<T, E> {
+    /// Creates the service, loading any changes previously queued in `validator_dir`.
+    pub fn new(
+        validator_dir: &Path,
+        beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
+        slot_clock: T,
+        executor: TaskExecutor,
+        log: Logger,
+    ) -> Result<Self, String> {
+        let path = validator_dir.join(BLS_TO_EXECUTION_CHANGES_FILENAME);
+        let changes = if path.exists() {
+            let bytes = fs::read(&path)
+                .map_err(|e| format!("Unable to read {}: {:?}", path.display(), e))?;
+            serde_json::from_slice(&bytes)
+                .map_err(|e| format!("Unable to parse {}: {:?}", path.display(), e))?
+        } else {
+            vec![]
+        };
+
+        Ok(Self {
+            changes: Mutex::new(changes),
+            retry_epochs: Mutex::new(HashMap::new()),
+            path,
+            beacon_nodes,
+            slot_clock,
+            executor,
+            log,
+        })
+    }
+
+    /// Returns all queued changes, including those which have been processed.
+    pub fn changes(&self) -> Vec<QueuedBlsToExecutionChange> {
+        self.changes.lock().clone()
+    }
+
+    /// Queues `changes` for broadcast at `broadcast_epoch`.
+    ///
+    /// Fails without queuing any change if a change is already pending or broadcast for the same
+    /// validator.
+    pub fn queue(
+        &self,
+        changes: Vec<SignedBlsToExecutionChange>,
+        broadcast_epoch: Epoch,
+    ) -> Result<(), String> {
+        let mut queued = self.changes.lock();
+
+        for (i, change) in changes.iter().enumerate() {
+            let validator_index = change.message.validator_index;
+            let is_duplicate = queued
+                .iter()
+                .filter(|queued| queued.status != BlsToExecutionChangeStatus::Failed)
+                .map(|queued| &queued.change)
+                .chain(&changes[..i])
+                .any(|existing| existing.message.validator_index == validator_index);
+            if is_duplicate {
+                return Err(format!(
+                    "a change is already queued for validator {}",
+                    validator_index
+                ));
+            }
+        }
+
+        let mut updated = queued.clone();
+        updated.extend(
+            changes
+                .into_iter()
+                .map(|change| QueuedBlsToExecutionChange {
+                    change,
+                    broadcast_epoch,
+                    status: BlsToExecutionChangeStatus::Pending,
+                    processed_epoch: None,
+                    error: None,
+                }),
+        );
+        self.persist(&updated)?;
+        *queued = updated;
+
+        Ok(())
+    }
+
+    fn persist(&self, changes: &[QueuedBlsToExecutionChange]) -> Result<(), String> {
+        let bytes = serde_json::to_vec_pretty(changes)
+            .map_err(|e| format!("Unable to serialize changes: {:?}", e))?;
+        fs::write(&self.path, bytes)
+            .map_err(|e| format!("Unable to write {}: {:?}", self.path.display(), e))
+    }
+
+    /// Starts the service which publishes pending changes once their broadcast epoch is reached.
+    pub fn start_update_service(self: Arc<Self>) -> Result<(), String> {
+        let log = self.log.clone();
+        let slot_duration = self.slot_clock.slot_duration();
+        let executor = self.executor.clone();
+
+        info!(
+            log,
+            "BLS to execution change service started";
+            "queued" => self.changes.lock().len(),
+        );
+
+        let interval_fut = async move {
+            loop {
+                if let Some(epoch) = self
+                    .slot_clock
+                    .now()
+                    .map(|slot| slot.epoch(E::slots_per_epoch()))
+                {
+                    self.publish_due_changes(epoch).await;
+                }
+
+                if let Some(duration_to_next_slot) = self.slot_clock.duration_to_next_slot() {
+                    sleep(duration_to_next_slot).await;
+                } else {
+                    error!(log, "Failed to read slot clock");
+                    // If we can't read the slot clock, just wait another slot.
+                    sleep(slot_duration).await;
+                }
+            }
+        };
+
+        executor.spawn(interval_fut, "bls_to_execution_change_service");
+        Ok(())
+    }
+
+    /// Publishes each pending change with a broadcast epoch at or before `current_epoch`.
+    async fn publish_due_changes(&self, current_epoch: Epoch) {
+        let log = &self.log;
+        let due = {
+            let retry_epochs = self.retry_epochs.lock();
+            self.changes
+                .lock()
+                .iter()
+                .filter(|queued| is_due(queued, &retry_epochs, current_epoch))
+                .map(|queued| queued.change.clone())
+                .collect::<Vec<_>>()
+        };
+
+        if due.is_empty() {
+            return;
+        }
+
+        // Each change is published on its own so that an invalid change does not prevent the
+        // others from being accepted.
+        let mut results = Vec::with_capacity(due.len());
+        for change in due {
+            let validator_index = change.message.validator_index;
+            let result = self
+                .beacon_nodes
+                .first_success(|beacon_node| {
+                    let change = change.clone();
+                    async move {
+                        beacon_node
+                            .post_beacon_pool_bls_to_execution_changes(&[change])
+                            .await
+                    }
+                })
+                .await;
+
+            match &result {
+                Ok(()) => info!(
+                    log,
+                    "Published BLS to execution change";
+                    "validator_index" => validator_index,
+                    "epoch" => current_epoch,
+                ),
+                Err(e) => warn!(
+                    log,
+                    "Failed to publish BLS to execution change";
+                    "validator_index" => validator_index,
+                    "error" => %e,
+                ),
+            }
+            results.push((validator_index, result));
+        }
+
+        let mut queued = self.changes.lock();
+        record_publish_results(
+            &mut queued,
+            &mut self.retry_epochs.lock(),
+            results,
+            current_epoch,
+        );
+
+        match self.persist(&queued) {
+            Ok(()) => debug!(log, "Persisted BLS to execution changes"),
+            Err(e) => error!(
+                log,
+                "Unable to persist BLS to execution changes";
+                "error" => e,
+            ),
+        }
+    }
+}
+
+/// Returns `true` if `queued` should be published during `current_epoch`.
+fn is_due(
+    queued: &QueuedBlsToExecutionChange,
+    retry_epochs: &HashMap<u64, Epoch>,
+    current_epoch: Epoch,
+) -> bool {
+    queued.status == BlsToExecutionChangeStatus::Pending
+        && queued.broadcast_epoch <= current_epoch
+        && retry_epochs
+            .get(&queued.change.message.validator_index)
+            .map_or(true, |retry_epoch| *retry_epoch <= current_epoch)
+}
+
+/// Records the outcome of publishing changes during `current_epoch`.
+///
+/// A change is only marked as failed if a beacon node rejected it as invalid with a 400. Other
+/// errors, e.g. an unreachable or overloaded beacon node, leave the change pending so that it is
+/// retried in the next epoch.
+fn record_publish_results(
+    queued: &mut [QueuedBlsToExecutionChange],
+    retry_epochs: &mut HashMap<u64, Epoch>,
+    results: PublishResults,
+    current_epoch: Epoch,
+) {
+    for (validator_index, result) in results {
+        let Some(change) = queued.iter_mut().find(|queued| {
+            queued.status == BlsToExecutionChangeStatus::Pending
+                && queued.change.message.validator_index == validator_index
+        }) else {
+            continue;
+        };
+        match result {
+            Ok(()) => {
+                retry_epochs.remove(&validator_index);
+                change.status = BlsToExecutionChangeStatus::Broadcast;
+                change.processed_epoch = Some(current_epoch);
+                change.error = None;
+            }
+            Err(errors) => {
+                let rejected = errors
+                    .0
+                    .iter()
+                    .filter_map(|(_, e)| e.request_failure())
+                    .any(|e| e.status() == Some(StatusCode::BAD_REQUEST));
+                if rejected {
+                    retry_epochs.remove(&validator_index);
+                    change.status = BlsToExecutionChangeStatus::Failed;
+                    change.processed_epoch = Some(current_epoch);
+                } else {
+                    retry_epochs.insert(validator_index, current_epoch + 1);
+                }
+                change.error = Some(errors.to_string());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use beacon_node_fallback::Error as FallbackError;
+    use types::{Address, BlsToExecutionChange, PublicKeyBytes, Signature};
+
+    fn queued_change(validator_index: u64) -> QueuedBlsToExecutionChange {
+        QueuedBlsToExecutionChange {
+            change: SignedBlsToExecutionChange {
+                message: BlsToExecutionChange {
+                    validator_index,
+                    from_bls_pubkey: PublicKeyBytes::empty(),
+                    to_execution_address: Address::repeat_byte(42),
+                },
+                signature: Signature::empty(),
+            },
+            broadcast_epoch: Epoch::new(1),
+            status: BlsToExecutionChangeStatus::Pending,
+            processed_epoch: None,
+            error: None,
+        }
+    }
+
+    fn http_error(status: StatusCode) -> Result<(), Errors<eth2::Error>> {
+        Err(Errors(vec![(
+            "http://localhost:5052/".to_string(),
+            FallbackError::RequestFailed(eth2::Error::StatusCode(status)),
+        )]))
+    }
+
+    #[test]
+    fn only_rejected_changes_fail() {
+        let mut queued = vec![queued_change(1), queued_change(2), queued_change(3)];
+        let mut retry_epochs = HashMap::new();
+        let epoch = Epoch::new(1);
+        assert!(queued
+            .iter()
+            .all(|queued| is_due(queued, &retry_epochs, epoch)));
+
+        record_publish_results(
+            &mut queued,
+            &mut retry_epochs,
+            vec![
+                (1, Ok(())),
+                (2, http_error(StatusCode::BAD_REQUEST)),
+                (3, http_error(StatusCode::SERVICE_UNAVAILABLE)),
+            ],
+            epoch,
+        );
+
+        assert_eq!(queued[0].status, BlsToExecutionChangeStatus::Broadcast);
+        assert_eq!(queued[0].processed_epoch, Some(epoch));
+        assert_eq!(queued[1].status, BlsToExecutionChangeStatus::Failed);
+        assert_eq!(queued[1].processed_epoch, Some(epoch));
+        assert!(queued[1].error.is_some());
+
+        // The transient error is recorded, but the change is retried in the next epoch.
+        assert_eq!(queued[2].status, BlsToExecutionChangeStatus::Pending);
+        assert_eq!(queued[2].processed_epoch, None);
+        assert!(queued[2].error.is_some());
+        assert!(!is_due(&queued[2], &retry_epochs, epoch));
+        assert!(is_due(&queued[2], &retry_epochs, epoch + 1));
+
+        record_publish_results(&mut queued, &mut retry_epochs, vec![(3, Ok(()))], epoch + 1);
+        assert_eq!(queued[2].status, BlsToExecutionChangeStatus::Broadcast);
+        assert_eq!(queued[2].processed_epoch, Some(epoch + 1));
+        assert_eq!(queued[2].error, None);
+        assert!(retry_epochs.is_empty());
+    }
+}
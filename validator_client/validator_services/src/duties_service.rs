@@ -9,7 +9,7 @@
 use crate::block_service::BlockServiceNotification;
 use crate::sync::poll_sync_committee_duties;
 use crate::sync::SyncDutiesMap;
-use beacon_node_fallback::{ApiTopic, BeaconNodeFallback};
+use beacon_node_fallback::{ApiTopic, BeaconNodeFallback, DutyType};
 use doppelganger_service::DoppelgangerStatus;
 use environment::RuntimeContext;
 use eth2::types::{
@@ -41,6 +41,10 @@ const SELECTION_PROOF_SLOT_LOOKAHEAD: u64 = 8;
 /// The attestation selection proof lookahead for those running with the --distributed flag.
 const SELECTION_PROOF_SLOT_LOOKAHEAD_DVT: u64 = 1;
 
+/// The attestation selection proof lookahead, in epochs, for those running with the
+/// --precompute-selection-proofs flag. This covers all duties for the current and next epoch.
+const SELECTION_PROOF_EPOCH_LOOKAHEAD_PRECOMPUTE: u64 = 2;
+
 /// Fraction of a slot at which selection proof signing should happen (2 means half way).
 const SELECTION_PROOF_SCHEDULE_DENOM: u32 = 2;
 
@@ -230,6 +234,9 @@ pub struct DutiesService<T, E: EthSpec> {
     pub enable_high_validator_count_metrics: bool,
     /// If this validator is running in distributed mode.
     pub distributed: bool,
+    /// If true, compute the selection proofs for all attestation duties in a single batch as soon
+    /// as the duties are known, rather than shortly before each duty.
+    pub precompute_selection_proofs: bool,
 }
 
 impl<T: SlotClock + 'static, E: EthSpec> DutiesService<T, E> {
@@ -515,7 +522,7 @@ async fn poll_validator_indices<T: SlotClock + 'static, E: EthSpec>(
             // Query the remote BN to resolve a pubkey to a validator index.
             let download_result = duties_service
                 .beacon_nodes
-                .first_success(|beacon_node| async move {
+                .first_success_for(DutyType::Duties, |beacon_node| async move {
                     let _timer = validator_metrics::start_timer_vec(
                         &validator_metrics::DUTIES_SERVICE_TIMES,
                         &[validator_metrics::VALIDATOR_ID_HTTP_GET],
@@ -1028,7 +1035,7 @@ async fn post_validator_duties_attester<T: SlotClock + 'static, E: EthSpec>(
 ) -> Result<DutiesResponse<Vec<AttesterData>>, Error> {
     duties_service
         .beacon_nodes
-        .first_success(|beacon_node| async move {
+        .first_success_for(DutyType::Duties, |beacon_node| async move {
             let _timer = validator_metrics::start_timer_vec(
                 &validator_metrics::DUTIES_SERVICE_TIMES,
                 &[validator_metrics::ATTESTER_DUTIES_HTTP_POST],
@@ -1041,9 +1048,33 @@ async fn post_validator_duties_attester<T: SlotClock + 'static, E: EthSpec>(
         .map_err(|e| Error::FailedToDownloadAttesters(e.to_string()))
 }
 
+/// Returns the number of slots after the current slot for which selection proofs are computed.
+fn selection_proof_lookahead<E: EthSpec>(
+    distributed: bool,
+    precompute_selection_proofs: bool,
+) -> u64 {
+    if distributed {
+        SELECTION_PROOF_SLOT_LOOKAHEAD_DVT
+    } else if precompute_selection_proofs {
+        E::slots_per_epoch() * SELECTION_PROOF_EPOCH_LOOKAHEAD_PRECOMPUTE
+    } else {
+        SELECTION_PROOF_SLOT_LOOKAHEAD
+    }
+}
+
+/// Remove and return the duties in `duties_by_slot` with slots prior to `lookahead_slot`.
+fn take_duties_before(
+    duties_by_slot: &mut BTreeMap<Slot, Vec<AttesterData>>,
+    lookahead_slot: Slot,
+) -> BTreeMap<Slot, Vec<AttesterData>> {
+    let later_duties = duties_by_slot.split_off(&lookahead_slot);
+    std::mem::replace(duties_by_slot, later_duties)
+}
+
 /// Compute the attestation selection proofs for the `duties` and add them to the `attesters` map.
 ///
-/// Duties are computed in batches each slot. If a re-org is detected then the process will
+/// Duties are computed in batches each slot, or in a single batch when
+/// `precompute_selection_proofs` is enabled. If a re-org is detected then the process will
 /// terminate early as it is assumed the selection proofs from `duties` are no longer relevant.
 async fn fill_in_selection_proofs<T: SlotClock + 'static, E: EthSpec>(
     duties_service: Arc<DutiesService<T, E>>,
@@ -1072,16 +1103,12 @@ async fn fill_in_selection_proofs<T: SlotClock + 'static, E: EthSpec>(
                 continue;
             };
 
-            let selection_lookahead = if duties_service.distributed {
-                SELECTION_PROOF_SLOT_LOOKAHEAD_DVT
-            } else {
-                SELECTION_PROOF_SLOT_LOOKAHEAD
-            };
-
+            let selection_lookahead = selection_proof_lookahead::<E>(
+                duties_service.distributed,
+                duties_service.precompute_selection_proofs,
+            );
             let lookahead_slot = current_slot + selection_lookahead;
-
-            let mut relevant_duties = duties_by_slot.split_off(&lookahead_slot);
-            std::mem::swap(&mut relevant_duties, &mut duties_by_slot);
+            let relevant_duties = take_duties_before(&mut duties_by_slot, lookahead_slot);
 
             let batch_size = relevant_duties.values().map(Vec::len).sum::<usize>();
 
@@ -1262,7 +1289,7 @@ async fn poll_beacon_proposers<T: SlotClock + 'static, E: EthSpec>(
     if !local_pubkeys.is_empty() {
         let download_result = duties_service
             .beacon_nodes
-            .first_success(|beacon_node| async move {
+            .first_success_for(DutyType::Duties, |beacon_node| async move {
                 let _timer = validator_metrics::start_timer_vec(
                     &validator_metrics::DUTIES_SERVICE_TIMES,
                     &[validator_metrics::PROPOSER_DUTIES_HTTP_GET],
@@ -1391,6 +1418,7 @@ async fn notify_block_production_service<T: SlotClock + 'static, E: EthSpec>(
 #[cfg(test)]
 mod test {
     use super::*;
+    use types::MainnetEthSpec;
 
     #[test]
     fn subscription_slots_exact() {
@@ -1471,4 +1499,67 @@ mod test {
         assert_eq!(subscription_slots.slots.len(), 1);
         assert!(subscription_slots.should_send_subscription_at(current_slot + 1),);
     }
+
+    /// Returns the number of selection proofs signed in each batch, starting at `current_slot`.
+    fn selection_proof_batch_sizes(
+        duty_slots: impl Iterator<Item = u64>,
+        mut current_slot: Slot,
+        selection_lookahead: u64,
+    ) -> Vec<usize> {
+        let mut duties_by_slot: BTreeMap<Slot, Vec<_>> = BTreeMap::new();
+        for slot in duty_slots {
+            let duty = AttesterData {
+                pubkey: PublicKeyBytes::empty(),
+                validator_index: 0,
+                committees_at_slot: 1,
+                committee_index: 0,
+                committee_length: 1,
+                validator_committee_index: 0,
+                slot: Slot::new(slot),
+            };
+            duties_by_slot.entry(duty.slot).or_default().push(duty);
+        }
+
+        let mut batch_sizes = vec![];
+        while !duties_by_slot.is_empty() {
+            let batch = take_duties_before(&mut duties_by_slot, current_slot + selection_lookahead);
+            assert!(batch
+                .keys()
+                .all(|slot| *slot < current_slot + selection_lookahead));
+            let batch_size = batch.values().map(Vec::len).sum::<usize>();
+            if batch_size > 0 {
+                batch_sizes.push(batch_size);
+            }
+            current_slot += 1;
+        }
+        batch_sizes
+    }
+
+    #[test]
+    fn precomputed_selection_proofs_are_signed_in_one_batch() {
+        type E = MainnetEthSpec;
+        let slots_per_epoch = E::slots_per_epoch();
+        // Duties at every slot of the current and next epoch, known at the start of the current
+        // epoch.
+        let current_slot = Slot::new(slots_per_epoch);
+        let duty_slots = || slots_per_epoch..3 * slots_per_epoch;
+        let num_duties = 2 * slots_per_epoch as usize;
+
+        // By default, proofs are signed a few slots ahead of each duty.
+        let lookahead = selection_proof_lookahead::<E>(false, false);
+        let batch_sizes = selection_proof_batch_sizes(duty_slots(), current_slot, lookahead);
+        assert_eq!(batch_sizes[0], SELECTION_PROOF_SLOT_LOOKAHEAD as usize);
+        assert!(batch_sizes[1..].iter().all(|size| *size == 1));
+        assert_eq!(batch_sizes.iter().sum::<usize>(), num_duties);
+
+        // When precomputing, all proofs are signed in the first batch.
+        let lookahead = selection_proof_lookahead::<E>(false, true);
+        let batch_sizes = selection_proof_batch_sizes(duty_slots(), current_slot, lookahead);
+        assert_eq!(batch_sizes, vec![num_duties]);
+
+        // Distributed validators sign each proof just before its duty.
+        let lookahead = selection_proof_lookahead::<E>(true, false);
+        let batch_sizes = selection_proof_batch_sizes(duty_slots(), current_slot, lookahead);
+        assert_eq!(batch_sizes, vec![1; num_duties]);
+    }
 }
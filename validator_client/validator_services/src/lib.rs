@@ -1,5 +1,6 @@
 pub mod attestation_service;
 pub mod block_service;
+pub mod bls_to_execution_change_service;
 pub mod duties_service;
 pub mod preparation_service;
 pub mod sync;
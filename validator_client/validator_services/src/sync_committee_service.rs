@@ -1,5 +1,5 @@
 use crate::duties_service::DutiesService;
-use beacon_node_fallback::{ApiTopic, BeaconNodeFallback};
+use beacon_node_fallback::{ApiTopic, BeaconNodeFallback, DutyType};
 use environment::RuntimeContext;
 use eth2::types::BlockId;
 use futures::future::join_all;
@@ -176,7 +176,8 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
         // Fetch `block_root` with non optimistic execution for `SyncCommitteeContribution`.
         let response = self
             .beacon_nodes
-            .first_success(
+            .first_success_for(
+                DutyType::SyncCommittee,
                 |beacon_node| async move {
                     match beacon_node.get_beacon_blocks_root(BlockId::Head).await {
                         Ok(Some(block)) if block.execution_optimistic == Some(false) => {
@@ -361,7 +362,7 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
 
         let contribution = &self
             .beacon_nodes
-            .first_success(|beacon_node| async move {
+            .first_success_for(DutyType::SyncCommittee, |beacon_node| async move {
                 let sync_contribution_data = SyncContributionData {
                     slot,
                     beacon_block_root,
@@ -439,11 +440,15 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
 
         // Publish to the beacon node.
         self.beacon_nodes
-            .first_success(|beacon_node| async move {
-                beacon_node
-                    .post_validator_contribution_and_proofs(signed_contributions)
-                    .await
-            })
+            .request_for(
+                ApiTopic::SyncCommittee,
+                DutyType::SyncCommittee,
+                |beacon_node| async move {
+                    beacon_node
+                        .post_validator_contribution_and_proofs(signed_contributions)
+                        .await
+                },
+            )
             .await
             .map_err(|e| {
                 error!(
@@ -6,12 +6,14 @@ use serde::{Deserialize, Serialize};
 use signing_method::{Error as SigningError, SignableMessage, SigningContext, SigningMethod};
 use slashing_protection::{
     interchange::Interchange, InterchangeError, NotSafe, Safe, SlashingDatabase,
+    SLASHING_PROTECTION_HISTORY_EPOCHS,
 };
 use slog::{crit, error, info, warn, Logger};
 use slot_clock::SlotClock;
 use std::marker::PhantomData;
 use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 use task_executor::TaskExecutor;
 use types::{
     attestation::Error as AttestationError, graffiti::GraffitiString, AbstractExecPayload, Address,
@@ -56,6 +58,8 @@ pub struct Config {
     pub prefer_builder_proposals: bool,
     /// Specifies the boost factor, a percentage multiplier to apply to the builder's payload value.
     pub builder_boost_factor: Option<u64>,
+    /// Builder bids below this value (in gwei) are ignored in favour of a locally built payload.
+    pub builder_min_bid_gwei: Option<u64>,
 }
 
 /// A helper struct, used for passing data from the validator store to services.
@@ -66,11 +70,6 @@ pub struct ProposalData {
     pub builder_proposals: bool,
 }
 
-/// Number of epochs of slashing protection history to keep.
-///
-/// This acts as a maximum safe-guard against clock drift.
-const SLASHING_PROTECTION_HISTORY_EPOCHS: u64 = 512;
-
 /// Currently used as the default gas limit in execution clients.
 ///
 /// https://github.com/ethereum/builder-specs/issues/17
@@ -91,6 +90,7 @@ pub struct ValidatorStore<T, E: EthSpec> {
     enable_web3signer_slashing_protection: bool,
     prefer_builder_proposals: bool,
     builder_boost_factor: Option<u64>,
+    builder_min_bid_gwei: Option<u64>,
     task_executor: TaskExecutor,
     _phantom: PhantomData<E>,
 }
@@ -131,6 +131,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             enable_web3signer_slashing_protection: config.enable_web3signer_slashing_protection,
             prefer_builder_proposals: config.prefer_builder_proposals,
             builder_boost_factor: config.builder_boost_factor,
+            builder_min_bid_gwei: config.builder_min_bid_gwei,
             task_executor,
             _phantom: PhantomData,
         }
@@ -454,6 +455,17 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             .suggested_fee_recipient(validator_pubkey)
     }
 
+    /// Returns the time into the slot at which the given public key should produce attestations,
+    /// if it is specified in `validator_definitions.yml`.
+    pub fn get_attestation_offset(&self, validator_pubkey: &PublicKeyBytes) -> Option<Duration> {
+        self.validators.read().attestation_offset(validator_pubkey)
+    }
+
+    /// Returns the earliest attestation production offset specified for any validator.
+    pub fn min_attestation_offset(&self) -> Option<Duration> {
+        self.validators.read().min_attestation_offset()
+    }
+
     /// Returns the gas limit for the given public key. The priority order for fetching
     /// the gas limit is:
     ///
@@ -510,6 +522,18 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             .unwrap_or(self.prefer_builder_proposals)
     }
 
+    /// Returns the minimum builder bid in gwei for the given public key. The priority order for
+    /// fetching this value is:
+    ///
+    /// 1. validator_definitions.yml
+    /// 2. process level flag
+    pub fn get_builder_min_bid_gwei(&self, validator_pubkey: &PublicKeyBytes) -> Option<u64> {
+        self.validators
+            .read()
+            .builder_min_bid_gwei(validator_pubkey)
+            .or(self.builder_min_bid_gwei)
+    }
+
     fn get_builder_proposals_defaulting(&self, builder_proposals: Option<bool>) -> bool {
         builder_proposals
             // If there's nothing in the file, try the process-level default value.
@@ -1083,34 +1107,30 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         let new_min_target_epoch = current_epoch.saturating_sub(SLASHING_PROTECTION_HISTORY_EPOCHS);
         let new_min_slot = new_min_target_epoch.start_slot(E::slots_per_epoch());
 
-        let all_pubkeys: Vec<_> = self.voting_pubkeys(DoppelgangerStatus::ignored);
-
-        if let Err(e) = self
+        // Prune all registered validators, including those which have since been disabled, so
+        // that their history doesn't accumulate indefinitely.
+        let outcome = match self
             .slashing_protection
-            .prune_all_signed_attestations(all_pubkeys.iter(), new_min_target_epoch)
+            .prune_all_validators(new_min_target_epoch, new_min_slot)
         {
-            error!(
-                self.log,
-                "Error during pruning of signed attestations";
-                "error" => ?e,
-            );
-            return;
-        }
-
-        if let Err(e) = self
-            .slashing_protection
-            .prune_all_signed_blocks(all_pubkeys.iter(), new_min_slot)
-        {
-            error!(
-                self.log,
-                "Error during pruning of signed blocks";
-                "error" => ?e,
-            );
-            return;
-        }
+            Ok(outcome) => outcome,
+            Err(e) => {
+                error!(
+                    self.log,
+                    "Error during pruning of slashing protection DB";
+                    "error" => ?e,
+                );
+                return;
+            }
+        };
 
         *last_prune = current_epoch;
 
-        info!(self.log, "Completed pruning of slashing protection DB");
+        info!(
+            self.log,
+            "Completed pruning of slashing protection DB";
+            "attestations_pruned" => outcome.attestations_pruned,
+            "blocks_pruned" => outcome.blocks_pruned,
+        );
     }
 }
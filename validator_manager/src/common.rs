@@ -48,6 +48,7 @@ pub struct ValidatorSpecification {
     pub builder_proposals: Option<bool>,
     pub builder_boost_factor: Option<u64>,
     pub prefer_builder_proposals: Option<bool>,
+    pub builder_min_bid_gwei: Option<u64>,
     pub enabled: Option<bool>,
 }
 
@@ -68,6 +69,7 @@ impl ValidatorSpecification {
             enabled,
             builder_boost_factor,
             prefer_builder_proposals,
+            builder_min_bid_gwei,
         } = self;
 
         let voting_public_key = voting_keystore
@@ -133,7 +135,11 @@ impl ValidatorSpecification {
                 .map_err(UploadError::FeeRecipientUpdateFailed)?;
         }
 
-        if gas_limit.is_some() || builder_proposals.is_some() || enabled.is_some() {
+        if gas_limit.is_some()
+            || builder_proposals.is_some()
+            || builder_min_bid_gwei.is_some()
+            || enabled.is_some()
+        {
             http_client
                 .patch_lighthouse_validators(
                     &voting_public_key,
@@ -142,6 +148,7 @@ impl ValidatorSpecification {
                     builder_proposals,
                     builder_boost_factor,
                     prefer_builder_proposals,
+                    builder_min_bid_gwei,
                     None, // Grafitti field is not maintained between validator moves.
                 )
                 .await
@@ -509,6 +509,7 @@ impl ValidatorsAndDeposits {
                 builder_proposals,
                 builder_boost_factor,
                 prefer_builder_proposals,
+                builder_min_bid_gwei: None,
                 // Allow the VC to choose a default "enabled" state. Since "enabled" is not part of
                 // the standard API, leaving this as `None` means we are not forced to use the
                 // non-standard API.
@@ -259,6 +259,7 @@ async fn run<'a>(config: ImportConfig) -> Result<(), String> {
                 builder_proposals,
                 builder_boost_factor,
                 prefer_builder_proposals,
+                builder_min_bid_gwei: None,
                 enabled,
             }]
         } else {
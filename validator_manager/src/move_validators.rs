@@ -369,6 +369,19 @@ async fn run<'a>(config: MoveConfig) -> Result<(), String> {
             eprintln!("Skipping read-only validator {:?}", pubkey_to_move);
         }
 
+        // The minimum builder bid is not part of the exported keystore, so it's read from the
+        // source VC before the validator is removed.
+        let builder_min_bid_gwei = src_http_client
+            .get_lighthouse_validators_pubkey(&pubkey_to_move)
+            .await
+            .map_err(|e| {
+                format!(
+                    "Failed to read {:?} from the source VC: {:?}",
+                    pubkey_to_move, e
+                )
+            })?
+            .and_then(|validator| validator.data.builder_min_bid_gwei);
+
         let request = DeleteKeystoresRequest {
             pubkeys: vec![pubkey_to_move],
         };
@@ -533,6 +546,7 @@ async fn run<'a>(config: MoveConfig) -> Result<(), String> {
             builder_proposals,
             builder_boost_factor,
             prefer_builder_proposals,
+            builder_min_bid_gwei,
             // Allow the VC to choose a default "enabled" state. Since "enabled" is not part of
             // the standard API, leaving this as `None` means we are not forced to use the
             // non-standard API.
@@ -687,6 +701,7 @@ mod test {
         passwords: HashMap<PublicKeyBytes, Vec<String>>,
         use_password_files: bool,
         reuse_password_files: Option<usize>,
+        builder_min_bid_gwei: Option<u64>,
     }
 
     impl TestBuilder {
@@ -704,6 +719,7 @@ mod test {
                 passwords: <_>::default(),
                 use_password_files: false,
                 reuse_password_files: None,
+                builder_min_bid_gwei: None,
             }
         }
 
@@ -723,6 +739,11 @@ mod test {
             self
         }
 
+        fn with_builder_min_bid_gwei(mut self, builder_min_bid_gwei: u64) -> Self {
+            self.builder_min_bid_gwei = Some(builder_min_bid_gwei);
+            self
+        }
+
         async fn with_src_validators(mut self, count: u32, first_index: u32) -> Self {
             let builder = ImportTestBuilder::new_with_http_config(self.http_config.clone())
                 .await
@@ -921,6 +942,23 @@ mod test {
                     }
                 }
 
+                // If set, check that the minimum builder bid was carried over with each moved
+                // validator.
+                if let Some(builder_min_bid_gwei) = self.builder_min_bid_gwei {
+                    let dest_validators = dest_vc.initialized_validators.read();
+                    src_vc_initial_keystores
+                        .iter()
+                        .filter(|keystore| dest_vc_final_keystores.contains(keystore))
+                        .filter(|keystore| !dest_vc_initial_keystores.contains(keystore))
+                        .for_each(|keystore| {
+                            assert_eq!(
+                                dest_validators.builder_min_bid_gwei(&keystore.validating_pubkey),
+                                Some(builder_min_bid_gwei),
+                                "the builder min bid should be present at the dest"
+                            )
+                        });
+                }
+
                 // If enabled, check that all VCs still have the password files for their validators.
                 if self.use_password_files {
                     src_vc_final_keystores
@@ -988,6 +1026,13 @@ mod test {
                 })
             }
 
+            if let Some(builder_min_bid_gwei) = self.builder_min_bid_gwei {
+                let mut initialized_validators = src_vc.initialized_validators.write();
+                for def in initialized_validators.as_mut_slice_testing_only() {
+                    def.builder_min_bid_gwei = Some(builder_min_bid_gwei);
+                }
+            }
+
             let dest_vc = if let Some(import_builder) = self.dest_import_builder.take() {
                 let import_test_result = import_builder.run_test().await;
                 assert!(import_test_result.result.is_ok());
@@ -1071,6 +1116,18 @@ mod test {
             .assert_ok();
     }
 
+    #[tokio::test]
+    async fn one_validator_move_all_with_builder_min_bid() {
+        TestBuilder::new()
+            .await
+            .with_src_validators(1, 0)
+            .await
+            .with_builder_min_bid_gwei(50_000_000)
+            .run_test(|_| Validators::All)
+            .await
+            .assert_ok();
+    }
+
     #[tokio::test]
     async fn one_validator_move_one() {
         TestBuilder::new()